@@ -16,6 +16,7 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
             Args {
                 nest_group: parse_quote!(CONTEXT),
                 capture: false,
+                animatable: false,
                 default: None,
                 impl_for: None,
             }
@@ -24,6 +25,7 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
 
     let nest_group = args.nest_group;
     let capture = args.capture;
+    let animatable = args.animatable;
     let impl_for = args.impl_for;
 
     let mut item = match parse::<ItemFn>(input.clone()) {
@@ -217,6 +219,22 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
                 None
             };
         }
+        let animatable_fn = if animatable {
+            quote! {
+                fn __easing__ #impl_gens(
+                    duration: std::time::Duration,
+                    easing: std::sync::Arc<dyn Fn(#core::var::animation::easing::EasingTime) -> #core::var::animation::easing::EasingStep + Send + Sync>,
+                ) -> std::vec::Vec<std::boxed::Box<dyn #core::widget::builder::AnyPropertyBuildAction>> #where_gens {
+                    #core::widget::easing_property::easing_property(#ident_meta {}.input_types #path_gens(), duration, easing)
+                }
+                Some(__easing__ #path_gens)
+            }
+        } else {
+            quote! {
+                None
+            }
+        };
+
         let mut input_info = quote!();
         let mut get_var = quote!();
         let mut get_value = quote!();
@@ -526,6 +544,7 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
                             #nest_group
                         },
                         capture: #capture,
+                        animatable: self.animatable_fn #path_gens(),
                         id: self.id(),
                         name: std::stringify!(#ident),
                         location: #source_location,
@@ -541,6 +560,10 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
                     #core::widget::builder::PropertyInputTypes::unit()
                 }
 
+                pub fn animatable_fn #impl_gens(&self) -> std::option::Option<#core::widget::builder::PropertyEasingFn> #where_gens {
+                    #animatable_fn
+                }
+
                 pub fn default_fn #impl_gens(&self) -> std::option::Option<fn () -> std::boxed::Box<dyn #core::widget::builder::PropertyArgs>> #where_gens {
                     #default
                     #default_fn
@@ -755,6 +778,7 @@ pub fn expand(args: proc_macro::TokenStream, input: proc_macro::TokenStream) ->
 struct Args {
     nest_group: Expr,
     capture: bool,
+    animatable: bool,
     default: Option<Default>,
     impl_for: Option<ImplFor>,
 }
@@ -769,6 +793,13 @@ impl Parse for Args {
             } else {
                 false
             },
+            animatable: if input.peek(Token![,]) && input.peek2(keyword::animatable) {
+                let _: Token![,] = input.parse()?;
+                let _: keyword::animatable = input.parse()?;
+                true
+            } else {
+                false
+            },
             default: if input.peek(Token![,]) && input.peek2(Token![default]) {
                 Some(input.parse()?)
             } else {
@@ -955,6 +986,7 @@ impl Input {
 
 pub mod keyword {
     syn::custom_keyword!(capture);
+    syn::custom_keyword!(animatable);
     syn::custom_keyword!(widget_impl);
 }
 
@@ -383,9 +383,19 @@ impl Parse for Properties {
         while !input.is_empty() {
             let attrs = parse_outer_attrs(input, &mut errors);
 
-            if input.peek(widget_util::keyword::when) {
+            if input.peek(widget_util::keyword::when) || input.peek(Token![else]) && input.peek2(widget_util::keyword::when) {
+                let else_token = if input.peek(Token![else]) {
+                    let else_token = input.parse::<Token![else]>().unwrap();
+                    if !matches!(items.last(), Some(WgtItem::When(_))) {
+                        errors.push("expected `when` block before `else when`", else_token.span());
+                    }
+                    Some(else_token)
+                } else {
+                    None
+                };
                 if let Some(mut when) = WgtWhen::parse(input, &mut errors) {
                     when.attrs = util::Attributes::new(attrs);
+                    when.else_token = else_token;
                     items.push(WgtItem::When(when));
                 }
             } else if input.peek(Token![pub])
@@ -457,7 +467,7 @@ pub fn expand_new(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let mut items = quote!();
 
-    for item in &p.items {
+    for (i, item) in p.items.iter().enumerate() {
         match item {
             WgtItem::Property(prop) => {
                 items.extend(prop_assign(prop, &mut p.errors, false));
@@ -546,6 +556,52 @@ pub fn expand_new(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 };
 
                 let source_location = widget_util::source_location(&core, Span::call_site());
+
+                let is_else = when.else_token.is_some();
+                let has_else = matches!(p.items.get(i + 1), Some(WgtItem::When(w)) if w.else_token.is_some());
+                if is_else || has_else {
+                    if !is_else {
+                        // start of `else when` chain, previous conditions and inputs
+                        items.extend(quote! {
+                            let mut when_chain_states__ = std::vec::Vec::<#core::var::BoxedVar<bool>>::new();
+                            let mut when_chain_inputs__ = std::vec::Vec::<#core::widget::builder::WhenInput>::new();
+                        });
+                    }
+                    let expr_str = if is_else {
+                        format!("else when {expr_str}")
+                    } else {
+                        expr_str.clone()
+                    };
+                    items.extend(quote! {
+                        #attrs {
+                            #when_expr_vars
+                            let own_inputs__ = [
+                                #inputs
+                            ];
+                            let own_state__ = #box_expr;
+                            let inputs__: std::boxed::Box<[#core::widget::builder::WhenInput]> = own_inputs__
+                                .iter()
+                                .cloned()
+                                .chain(when_chain_inputs__.iter().cloned())
+                                .collect();
+                            let state__ = #core::widget::builder::when_condition_else_var(own_state__.clone(), &when_chain_states__);
+                            when_chain_states__.push(own_state__);
+                            when_chain_inputs__.extend(own_inputs__);
+                            #core::widget::base::WidgetImpl::base(&mut *wgt__).start_when_block(
+                                inputs__,
+                                state__,
+                                #expr_str,
+                                #source_location,
+                            );
+
+                            #assigns
+
+                            #core::widget::base::WidgetImpl::base(&mut *wgt__).end_when_block();
+                        }
+                    });
+                    continue;
+                }
+
                 items.extend(quote! {
                     #attrs {
                         #when_expr_vars
@@ -231,6 +231,9 @@ fn peek_next_wgt_item(lookahead: parse::ParseStream) -> bool {
     if lookahead.peek(keyword::when) {
         return true; // when ..
     }
+    if lookahead.peek(Token![else]) && lookahead.peek2(keyword::when) {
+        return true; // else when ..
+    }
 
     if lookahead.peek(Token![pub]) {
         let _ = lookahead.parse::<Visibility>();
@@ -254,6 +257,8 @@ pub mod keyword {
 
 pub struct WgtWhen {
     pub attrs: Attributes,
+    /// If is an `else when` block, only applies if the previous blocks in the chain are not active.
+    pub else_token: Option<Token![else]>,
     #[allow(dead_code)]
     pub when: keyword::when,
     pub condition_expr: TokenStream,
@@ -345,6 +350,7 @@ impl WgtWhen {
 
         Some(WgtWhen {
             attrs: Attributes::new(vec![]), // must be parsed before.
+            else_token: None,               // must be parsed before.
             when,
             condition_expr_str: expr_str,
             condition_expr,
@@ -3,7 +3,7 @@
 use zng_txt::Txt;
 use zng_view_api::access::AccessCmd;
 
-pub use zng_view_api::access::ScrollCmd;
+pub use zng_view_api::access::{AccessLivePriority, ScrollCmd};
 
 use crate::{
     event::{event, event_args},
@@ -287,6 +287,30 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`ACCESS_ANNOUNCE_EVENT`].
+    pub struct AccessAnnounceArgs {
+        /// Target window.
+        ///
+        /// If `None` the announcement is made in the focused window.
+        pub window_id: Option<WindowId>,
+
+        /// Text that is announced.
+        pub txt: Txt,
+
+        /// Announcement priority.
+        pub priority: AccessLivePriority,
+
+        ..
+
+        /// Target the window or broadcast.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            match self.window_id {
+                Some(id) => list.insert_window(id),
+                None => list.search_all(),
+            }
+        }
+    }
+
     /// Arguments for the [`ACCESS_SELECTION_EVENT`].
     pub struct AccessSelectionArgs {
         /// Target window.
@@ -353,6 +377,13 @@ event! {
 
     /// Select text.
     pub static ACCESS_SELECTION_EVENT: AccessSelectionArgs;
+
+    /// Announce a text using the screen reader.
+    ///
+    /// The window implementation handles this event, see [`ACCESS.announce`] for more details.
+    ///
+    /// [`ACCESS.announce`]: ACCESS::announce
+    pub static ACCESS_ANNOUNCE_EVENT: AccessAnnounceArgs;
 }
 
 /// Accessibility service.
@@ -383,4 +414,21 @@ impl ACCESS {
         let wgt = widget_id.into();
         ACCESS_TOOLTIP_EVENT.notify(AccessToolTipArgs::now(win, wgt, false));
     }
+
+    /// Announce the text using the screen reader in the focused window.
+    ///
+    /// Use this for notifications that are not associated with the focused widget, like toasts and validation errors.
+    /// Nothing is announced if no window is focused or if accessibility is not enabled in the view-process.
+    pub fn announce(&self, txt: impl Into<Txt>, priority: AccessLivePriority) {
+        ACCESS_ANNOUNCE_EVENT.notify(AccessAnnounceArgs::now(None, txt.into(), priority));
+    }
+
+    /// Announce the text using the screen reader in the window.
+    ///
+    /// See [`announce`] for more details.
+    ///
+    /// [`announce`]: Self::announce
+    pub fn announce_in(&self, window_id: impl Into<WindowId>, txt: impl Into<Txt>, priority: AccessLivePriority) {
+        ACCESS_ANNOUNCE_EVENT.notify(AccessAnnounceArgs::now(Some(window_id.into()), txt.into(), priority));
+    }
 }
@@ -6,9 +6,11 @@
 
 use parking_lot::Mutex;
 use std::{
+    collections::VecDeque,
     fmt,
     io::{BufRead, Write},
     path::{Path, PathBuf},
+    sync::Arc,
     time::SystemTime,
 };
 use zng_layout::unit::TimeUnits as _;
@@ -34,13 +36,21 @@ zng_env::on_process_start!(|process_start_args| {
         }
     }
 
+    if zng_view_api::ViewConfig::from_env().is_some() {
+        // view-process spawned by a crash handled app-process or dialog-process
+        if std::env::var(APP_PROCESS).is_ok() || std::env::var(DIALOG_PROCESS).is_ok() {
+            crash_handler_view_process(config.dump_dir.as_deref());
+        }
+        return;
+    }
+
     if process_start_args.next_handlers_count > 0 && process_start_args.yield_count < zng_env::ProcessStartArgs::MAX_YIELD_COUNT - 10 {
         // extra sure that this is the app-process
         return process_start_args.yield_once();
     }
 
     if std::env::var(APP_PROCESS) != Err(std::env::VarError::NotPresent) {
-        return crash_handler_app_process(config.dump_dir.as_deref());
+        return crash_handler_app_process(config.dump_dir.as_deref(), config.breadcrumbs_capacity);
     }
 
     match std::env::var(DIALOG_PROCESS) {
@@ -50,6 +60,7 @@ zng_env::on_process_start!(|process_start_args| {
                 .dialog
                 .or(config.default_dialog)
                 .expect("dialog-process spawned without dialog handler"),
+            config.report,
             args_file,
         ),
         Err(e) => match e {
@@ -62,9 +73,37 @@ zng_env::on_process_start!(|process_start_args| {
         config.app_process,
         config.dialog_process,
         config.default_dialog.is_some() || config.dialog.is_some(),
+        config.metadata,
     );
 });
 
+/// Sets a metadata entry that is included in the crash report if the app-process crashes.
+///
+/// The entry replaces any previous value of the same `key`, see [`CrashError::metadata`] for more details.
+///
+/// The entry is only kept in memory, it is sent to the monitor-process only if the app-process panics or crashes.
+///
+/// Does nothing if not called in the app-process.
+pub fn set_metadata(key: impl Into<Txt>, value: impl Into<Txt>) {
+    if let Some(log) = &mut *APP_CRASH_LOG.lock() {
+        log.log.set_metadata(key.into(), value.into());
+    }
+}
+
+/// Record a breadcrumb message that is included in the crash report if the app-process crashes.
+///
+/// Use this to record the last actions of the user before a crash. Only the latest breadcrumbs are retained,
+/// see [`CrashConfig::breadcrumbs_capacity`] for more details. The messages are only kept in memory, they are
+/// sent to the monitor-process only if the app-process panics or crashes.
+///
+/// Does nothing if not called in the app-process.
+pub fn breadcrumb(msg: impl Into<Txt>) {
+    if let Some(log) = &mut *APP_CRASH_LOG.lock() {
+        let capacity = log.capacity;
+        log.log.push_breadcrumb(msg.into(), capacity);
+    }
+}
+
 /// Gets the number of crash restarts in the app-process.
 ///
 /// Always returns zero if called in other processes.
@@ -77,6 +116,7 @@ pub fn restart_count() -> usize {
 
 const APP_PROCESS: &str = "ZNG_CRASH_HANDLER_APP";
 const DIALOG_PROCESS: &str = "ZNG_CRASH_HANDLER_DIALOG";
+const APP_LOG_FILE: &str = "ZNG_CRASH_HANDLER_LOG";
 const RESPONSE_PREFIX: &str = "zng_crash_response: ";
 
 #[linkme::distributed_slice]
@@ -122,6 +162,59 @@ pub use crate::crash_handler_config;
 
 type ConfigProcess = Vec<Box<dyn for<'a, 'b> FnMut(&'a mut std::process::Command, &'b CrashArgs) -> &'a mut std::process::Command>>;
 type CrashDialogHandler = Box<dyn FnOnce(CrashArgs)>;
+type CrashReportHandler = Arc<dyn Fn(&CrashArgs) -> Result<(), Txt> + Send + Sync>;
+
+/// Metadata and breadcrumbs recorded by the app-process.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct CrashLog {
+    metadata: Vec<(Txt, Txt)>,
+    breadcrumbs: VecDeque<Txt>,
+}
+impl CrashLog {
+    fn set_metadata(&mut self, key: Txt, value: Txt) {
+        if let Some(e) = self.metadata.iter_mut().find(|(k, _)| *k == key) {
+            e.1 = value;
+        } else {
+            self.metadata.push((key, value));
+        }
+    }
+
+    fn push_breadcrumb(&mut self, msg: Txt, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        while self.breadcrumbs.len() >= capacity {
+            self.breadcrumbs.pop_front();
+        }
+        self.breadcrumbs.push_back(msg);
+    }
+}
+
+struct AppCrashLog {
+    log: CrashLog,
+    capacity: usize,
+    file: PathBuf,
+}
+static APP_CRASH_LOG: Mutex<Option<AppCrashLog>> = Mutex::new(None);
+
+/// Write the app-process log to the file read by the monitor-process.
+///
+/// Called on panic and native crash only.
+fn write_app_crash_log() {
+    // try_lock because the crash may have happened while the log was locked.
+    if let Some(guard) = APP_CRASH_LOG.try_lock() {
+        if let Some(log) = &*guard {
+            match serde_json::to_vec(&log.log) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(&log.file, json) {
+                        eprintln!("error writing crash log, {e}");
+                    }
+                }
+                Err(e) => eprintln!("error serializing crash log, {e}"),
+            }
+        }
+    }
+}
 
 /// Crash handler config.
 ///
@@ -135,6 +228,9 @@ pub struct CrashConfig {
     dialog_process: ConfigProcess,
     dump_dir: Option<PathBuf>,
     no_crash_handler: bool,
+    metadata: Vec<(Txt, Txt)>,
+    breadcrumbs_capacity: usize,
+    report: Option<CrashReportHandler>,
 }
 impl CrashConfig {
     fn new() -> Self {
@@ -145,6 +241,9 @@ impl CrashConfig {
             dialog_process: vec![],
             dump_dir: Some(zng_env::cache("zng_minidump")),
             no_crash_handler: false,
+            metadata: vec![],
+            breadcrumbs_capacity: 50,
+            report: None,
         }
     }
 
@@ -202,6 +301,40 @@ impl CrashConfig {
     pub fn no_crash_handler(&mut self) {
         self.no_crash_handler = true;
     }
+
+    /// Add a metadata entry that is included in all crash reports.
+    ///
+    /// Use this for static info like the app version, use [`set_metadata`] in the app-process for dynamic info.
+    ///
+    /// [`set_metadata`]: crate::crash_handler::set_metadata
+    pub fn metadata(&mut self, key: impl Into<Txt>, value: impl Into<Txt>) {
+        let key = key.into();
+        let value = value.into();
+        if let Some(e) = self.metadata.iter_mut().find(|(k, _)| *k == key) {
+            e.1 = value;
+        } else {
+            self.metadata.push((key, value));
+        }
+    }
+
+    /// Maximum number of [`breadcrumb`] messages retained for each crash report.
+    ///
+    /// Is `50` by default.
+    ///
+    /// [`breadcrumb`]: crate::crash_handler::breadcrumb
+    pub fn breadcrumbs_capacity(&mut self, capacity: usize) {
+        self.breadcrumbs_capacity = capacity;
+    }
+
+    /// Set the crash report upload handler.
+    ///
+    /// The `handler` is called in the dialog-process by [`CrashArgs::send_report`], after the user consents to
+    /// sending the report. Crash dialogs only offer this option if a report handler is set, see [`CrashArgs::can_send_report`].
+    ///
+    /// The handler is called in a blocking context, it must return an error message if the upload fails.
+    pub fn report(&mut self, handler: impl Fn(&CrashArgs) -> Result<(), Txt> + Send + Sync + 'static) {
+        self.report = Some(Arc::new(handler));
+    }
 }
 
 /// Arguments for the crash handler dialog function.
@@ -244,7 +377,31 @@ impl CrashArgs {
         println!("{RESPONSE_PREFIX}exit {code}");
         zng_env::exit(0)
     }
+
+    /// Gets if a crash report handler is set.
+    ///
+    /// Dialogs should only offer the option to send a report if this is `true`.
+    ///
+    /// See [`CrashConfig::report`] for more details.
+    pub fn can_send_report(&self) -> bool {
+        REPORT_HANDLER.lock().is_some()
+    }
+
+    /// Calls the crash report handler.
+    ///
+    /// Dialogs must only call this after the user consents to sending the report. Note that this blocks until
+    /// the handler returns, dialogs should call this in a background thread.
+    ///
+    /// Returns an error if no handler is set, or the handler error.
+    pub fn send_report(&self) -> Result<(), Txt> {
+        let handler = REPORT_HANDLER.lock().clone();
+        match handler {
+            Some(h) => h(self),
+            None => Err(Txt::from_static("no crash report handler")),
+        }
+    }
 }
+static REPORT_HANDLER: Mutex<Option<CrashReportHandler>> = Mutex::new(None);
 impl fmt::Display for CrashArgs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "APP CRASHES:\n")?;
@@ -279,10 +436,26 @@ pub struct CrashError {
     pub args: Box<[Txt]>,
     /// Minidump file.
     pub minidump: Option<PathBuf>,
+    /// Minidump files of native crashes in view-processes spawned by the app-process.
+    ///
+    /// The app-process usually survives view-process crashes, it respawns the view-process, so these minidumps are
+    /// only referenced here if the app-process crashes too. The files are saved in the minidump dir, see [`CrashConfig::minidump_dir`].
+    #[serde(default)]
+    pub view_minidumps: Vec<PathBuf>,
     /// Operating system.
     ///
     /// See [`std::env::consts::OS`] for details.
     pub os: Txt,
+    /// Metadata entries.
+    ///
+    /// Includes the entries set by [`CrashConfig::metadata`] and the latest values set by [`set_metadata`] in the app-process.
+    #[serde(default)]
+    pub metadata: Vec<(Txt, Txt)>,
+    /// Latest breadcrumb messages, oldest first.
+    ///
+    /// See [`breadcrumb`] for more details.
+    #[serde(default)]
+    pub breadcrumbs: Vec<Txt>,
 }
 /// Alternate mode `{:#}` prints plain stdout and stderr (no ANSI escape sequences).
 impl fmt::Display for CrashError {
@@ -297,6 +470,18 @@ impl fmt::Display for CrashError {
         if let Some(p) = self.minidump.as_ref() {
             writeln!(f, "minidump: {}", p.display())?
         }
+        for p in &self.view_minidumps {
+            writeln!(f, "view-process minidump: {}", p.display())?
+        }
+        for (key, value) in &self.metadata {
+            writeln!(f, "{key}: {value}")?
+        }
+        if !self.breadcrumbs.is_empty() {
+            writeln!(f, "\nBREADCRUMBS:")?;
+            for b in &self.breadcrumbs {
+                writeln!(f, "   {b}")?
+            }
+        }
         if f.alternate() {
             write!(f, "\nSTDOUT:\n{}\nSTDERR:\n{}\n", self.stdout_plain(), self.stderr_plain())
         } else {
@@ -305,9 +490,38 @@ impl fmt::Display for CrashError {
     }
 }
 impl CrashError {
-    fn new(timestamp: SystemTime, code: Option<i32>, signal: Option<i32>, stdout: Txt, stderr: Txt, args: Box<[Txt]>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        timestamp: SystemTime,
+        code: Option<i32>,
+        signal: Option<i32>,
+        stdout: Txt,
+        stderr: Txt,
+        args: Box<[Txt]>,
+        metadata: &[(Txt, Txt)],
+        log: CrashLog,
+    ) -> Self {
         let mut minidump = None;
 
+        let mut view_minidumps = vec![];
+        for line in stdout.lines() {
+            if let Some(response) = line.strip_prefix(RESPONSE_PREFIX) {
+                if let Some(path) = response.strip_prefix("view-minidump ") {
+                    if let Ok(p) = dunce::canonicalize(PathBuf::from(path)) {
+                        view_minidumps.push(p);
+                    }
+                }
+            }
+        }
+
+        let mut full_log = CrashLog {
+            metadata: metadata.to_vec(),
+            breadcrumbs: log.breadcrumbs,
+        };
+        for (key, value) in log.metadata {
+            full_log.set_metadata(key, value);
+        }
+
         for line in stdout.lines().rev() {
             if let Some(response) = line.strip_prefix(RESPONSE_PREFIX) {
                 if let Some(path) = response.strip_prefix("minidump ") {
@@ -328,7 +542,10 @@ impl CrashError {
             stderr,
             args,
             minidump,
+            view_minidumps,
             os: std::env::consts::OS.into(),
+            metadata: full_log.metadata,
+            breadcrumbs: full_log.breadcrumbs.into(),
         }
     }
 
@@ -779,7 +996,12 @@ impl BacktraceFrame {
     }
 }
 
-fn crash_handler_monitor_process(mut cfg_app: ConfigProcess, mut cfg_dialog: ConfigProcess, has_dialog_handler: bool) -> ! {
+fn crash_handler_monitor_process(
+    mut cfg_app: ConfigProcess,
+    mut cfg_dialog: ConfigProcess,
+    has_dialog_handler: bool,
+    cfg_metadata: Vec<(Txt, Txt)>,
+) -> ! {
     // monitor-process:
     tracing::info!("crash monitor-process is running");
 
@@ -793,16 +1015,22 @@ fn crash_handler_monitor_process(mut cfg_app: ConfigProcess, mut cfg_dialog: Con
         app_crashes: vec![],
         dialog_crash: None,
     };
+    let log_file = std::env::temp_dir().join(format!("zng-crash-log-{}", std::process::id()));
     loop {
+        let _ = std::fs::remove_file(&log_file);
+
         let mut app_process = std::process::Command::new(&exe);
         for cfg in &mut cfg_app {
             cfg(&mut app_process, &dialog_args);
         }
-        match run_process(
+        let app_result = run_process(
             app_process
                 .env(APP_PROCESS, format!("restart-{}", dialog_args.app_crashes.len()))
+                .env(APP_LOG_FILE, &log_file)
                 .args(args.iter()),
-        ) {
+        );
+        let app_log = read_app_crash_log(&log_file);
+        match app_result {
             Ok((status, [stdout, stderr])) => {
                 if status.success() {
                     let code = status.code().unwrap_or(0);
@@ -849,9 +1077,16 @@ fn crash_handler_monitor_process(mut cfg_app: ConfigProcess, mut cfg_dialog: Con
 
                     let timestamp = SystemTime::now();
 
-                    dialog_args
-                        .app_crashes
-                        .push(CrashError::new(timestamp, code, signal, stdout.into(), stderr.into(), args.clone()));
+                    dialog_args.app_crashes.push(CrashError::new(
+                        timestamp,
+                        code,
+                        signal,
+                        stdout.into(),
+                        stderr.into(),
+                        args.clone(),
+                        &cfg_metadata,
+                        app_log,
+                    ));
 
                     // show dialog, retries once if dialog crashes too.
                     for _ in 0..2 {
@@ -955,6 +1190,8 @@ fn crash_handler_monitor_process(mut cfg_app: ConfigProcess, mut cfg_dialog: Con
                                         dlg_stdout.into(),
                                         dlg_stderr.into(),
                                         Box::new([]),
+                                        &cfg_metadata,
+                                        CrashLog::default(),
                                     );
                                     tracing::error!("crash dialog-process crashed, {dialog_crash}");
 
@@ -987,6 +1224,17 @@ fn crash_handler_monitor_process(mut cfg_app: ConfigProcess, mut cfg_dialog: Con
         }
     }
 }
+fn read_app_crash_log(file: &Path) -> CrashLog {
+    let log = match std::fs::read(file) {
+        Ok(json) => serde_json::from_slice(&json).unwrap_or_else(|e| {
+            tracing::error!("error deserializing app-process crash log, {e}");
+            CrashLog::default()
+        }),
+        Err(_) => CrashLog::default(),
+    };
+    let _ = std::fs::remove_file(file);
+    log
+}
 fn run_process(command: &mut std::process::Command) -> std::io::Result<(std::process::ExitStatus, [String; 2])> {
     let mut app_process = command
         .env("RUST_BACKTRACE", "full")
@@ -1042,10 +1290,21 @@ fn capture_and_print(mut stream: impl std::io::Read + Send + 'static, is_err: bo
     })
 }
 
-fn crash_handler_app_process(dump_dir: Option<&Path>) {
+fn crash_handler_app_process(dump_dir: Option<&Path>, breadcrumbs_capacity: usize) {
     tracing::info!("app-process is running");
 
-    std::panic::set_hook(Box::new(panic_handler));
+    if let Some(file) = std::env::var_os(APP_LOG_FILE) {
+        *APP_CRASH_LOG.lock() = Some(AppCrashLog {
+            log: CrashLog::default(),
+            capacity: breadcrumbs_capacity,
+            file: PathBuf::from(file),
+        });
+    }
+
+    std::panic::set_hook(Box::new(|info| {
+        panic_handler(info);
+        write_app_crash_log();
+    }));
     if let Some(dir) = dump_dir {
         if let Err(e) = std::fs::create_dir_all(dir) {
             tracing::error!("failed to create minidump dir, minidump may not collect on crash, {e}");
@@ -1056,9 +1315,16 @@ fn crash_handler_app_process(dump_dir: Option<&Path>) {
     // app-process execution happens after the `crash_handler` function returns.
 }
 
-fn crash_handler_dialog_process(dump_dir: Option<&Path>, dialog: CrashDialogHandler, args_file: String) -> ! {
+fn crash_handler_dialog_process(
+    dump_dir: Option<&Path>,
+    dialog: CrashDialogHandler,
+    report: Option<CrashReportHandler>,
+    args_file: String,
+) -> ! {
     tracing::info!("crash dialog-process is running");
 
+    *REPORT_HANDLER.lock() = report;
+
     std::panic::set_hook(Box::new(panic_handler));
     if let Some(dir) = dump_dir {
         minidump_attach(dir);
@@ -1086,6 +1352,30 @@ fn crash_handler_dialog_process(dump_dir: Option<&Path>, dialog: CrashDialogHand
     .exit(0)
 }
 
+fn crash_handler_view_process(dump_dir: Option<&Path>) {
+    tracing::info!("view-process is monitored by crash handler");
+
+    if let Some(dir) = dump_dir {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            tracing::error!("failed to create minidump dir, minidump may not collect on view-process crash, {e}");
+        }
+        let handler = breakpad_handler::BreakpadHandler::attach(
+            dir,
+            breakpad_handler::InstallOptions::BothHandlers,
+            Box::new(|minidump_path: std::path::PathBuf| {
+                // stdout is inherited from the app-process, so this is captured by the monitor-process.
+                println!("{RESPONSE_PREFIX}view-minidump {}", minidump_path.display());
+            }),
+        );
+        match handler {
+            Ok(h) => *BREAKPAD_HANDLER.lock() = Some(h),
+            Err(e) => tracing::error!("failed to attach view-process minidump handler, {e}"),
+        }
+    }
+
+    // view-process execution happens after the `crash_handler` function returns.
+}
+
 fn panic_handler(info: &std::panic::PanicInfo) {
     let backtrace = std::backtrace::Backtrace::capture();
     let path = crate::widget::WIDGET.trace_path();
@@ -1098,6 +1388,7 @@ fn minidump_attach(dump_dir: &Path) {
         dump_dir,
         breakpad_handler::InstallOptions::BothHandlers,
         Box::new(|minidump_path: std::path::PathBuf| {
+            write_app_crash_log();
             println!("{RESPONSE_PREFIX}minidump {}", minidump_path.display());
         }),
     )
@@ -1159,3 +1450,33 @@ impl fmt::Display for PanicInfo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breadcrumbs_capacity() {
+        let mut log = CrashLog::default();
+        for i in 0..10 {
+            log.push_breadcrumb(i.to_txt(), 3);
+        }
+        assert_eq!(log.breadcrumbs.iter().map(|b| b.as_str()).collect::<Vec<_>>(), ["7", "8", "9"]);
+    }
+
+    #[test]
+    fn breadcrumbs_capacity_zero() {
+        let mut log = CrashLog::default();
+        log.push_breadcrumb("a".into(), 0);
+        assert!(log.breadcrumbs.is_empty());
+    }
+
+    #[test]
+    fn metadata_replace() {
+        let mut log = CrashLog::default();
+        log.set_metadata("a".into(), "1".into());
+        log.set_metadata("b".into(), "2".into());
+        log.set_metadata("a".into(), "3".into());
+        assert_eq!(log.metadata, [(Txt::from("a"), Txt::from("3")), (Txt::from("b"), Txt::from("2"))]);
+    }
+}
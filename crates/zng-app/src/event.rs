@@ -19,6 +19,9 @@ pub use args::*;
 mod command;
 pub use command::*;
 
+mod commands;
+pub use commands::*;
+
 mod events;
 pub use events::*;
 
@@ -1166,11 +1166,11 @@ impl CommandNameExt for Command {
     where
         Self: crate::shortcut::CommandShortcutExt,
     {
-        crate::var::merge_var!(self.name(), self.shortcut(), |name, shortcut| {
+        crate::var::merge_var!(self.name(), self.shortcut_txt(), |name, shortcut| {
             if shortcut.is_empty() {
                 name.clone()
             } else {
-                zng_txt::formatx!("{name} ({})", shortcut[0])
+                zng_txt::formatx!("{name} ({shortcut})")
             }
         })
         .boxed()
@@ -0,0 +1,239 @@
+use zng_txt::Txt;
+use zng_var::Var;
+
+use crate::{
+    shortcut::{CommandShortcutExt as _, Shortcuts},
+    widget::info::WidgetPath,
+};
+
+use super::{Command, CommandInfoExt as _, CommandNameExt as _, CommandScope, EVENTS};
+
+/// Commands discoverability service.
+///
+/// This service enumerates the commands that can be invoked in a context with the display metadata, it is the
+/// data source of command palettes and other command search UIs.
+pub struct COMMANDS;
+impl COMMANDS {
+    /// Gets all commands that have handlers in any scope.
+    ///
+    /// The entries are app scoped, disabled commands are included.
+    pub fn enumerate(&self) -> Vec<CommandEntry> {
+        EVENTS
+            .commands()
+            .into_iter()
+            .filter(|c| {
+                let mut any = c.has_handlers_value();
+                if !any {
+                    c.visit_scopes(|s| any |= s.has_handlers_value());
+                }
+                any
+            })
+            .map(CommandEntry::new)
+            .collect()
+    }
+
+    /// Gets the enabled commands that can be invoked in the `ctx` path.
+    ///
+    /// Each command is scoped in the inner most scope that has handlers for it, the widgets in `ctx` from the leaf widget
+    /// to the root, then the window and then the app scope. This is the same scope that would receive the command
+    /// if invoked by shortcut with the leaf widget focused.
+    pub fn enumerate_scoped(&self, ctx: &WidgetPath) -> Vec<CommandEntry> {
+        let scopes: Vec<CommandScope> = ctx
+            .widgets_path()
+            .iter()
+            .rev()
+            .map(|&id| CommandScope::Widget(id))
+            .chain([CommandScope::Window(ctx.window_id()), CommandScope::App])
+            .collect();
+
+        let mut r = vec![];
+        for cmd in EVENTS.commands() {
+            if let Some(cmd) = scopes.iter().map(|&s| cmd.scoped(s)).find(|c| c.has_handlers_value()) {
+                if cmd.is_enabled_value() {
+                    r.push(CommandEntry::new(cmd));
+                }
+            }
+        }
+        r
+    }
+
+    /// Filter and sort `entries` by fuzzy matching the `pattern` with the command name and info.
+    ///
+    /// Entries that do not match are removed, the best matches are first. If the `pattern` is empty the entries
+    /// are sorted by name.
+    pub fn search(&self, entries: Vec<CommandEntry>, pattern: &str) -> Vec<(CommandEntry, FuzzyMatch)> {
+        let mut r: Vec<_> = entries
+            .into_iter()
+            .filter_map(|e| {
+                let m = match fuzzy_match(pattern, &e.name) {
+                    Some(m) => m,
+                    // info matches rank below name matches.
+                    None => {
+                        let mut m = fuzzy_match(pattern, &e.info)?;
+                        m.score /= 4;
+                        m.indices.clear();
+                        m
+                    }
+                };
+                Some((e, m))
+            })
+            .collect();
+        r.sort_by(|(a, am), (b, bm)| bm.score.cmp(&am.score).then_with(|| a.name.cmp(&b.name)));
+        r
+    }
+}
+
+/// Command with display metadata.
+///
+/// See [`COMMANDS`] for more details.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandEntry {
+    /// The command, scoped in the context.
+    pub command: Command,
+    /// Command [`name`](super::CommandNameExt::name).
+    pub name: Txt,
+    /// Command [`info`](super::CommandInfoExt::info).
+    pub info: Txt,
+    /// Command [`shortcut`](crate::shortcut::CommandShortcutExt::shortcut).
+    pub shortcut: Shortcuts,
+}
+impl CommandEntry {
+    /// Read the metadata of the command.
+    pub fn new(command: Command) -> Self {
+        Self {
+            command,
+            name: command.name().get(),
+            info: command.info().get(),
+            shortcut: command.shortcut().get(),
+        }
+    }
+}
+
+/// Result of [`fuzzy_match`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FuzzyMatch {
+    /// Match quality, greater is better.
+    pub score: i32,
+    /// Char indices of the text that matched each pattern char.
+    pub indices: Vec<usize>,
+}
+
+/// Case insensitive fuzzy match.
+///
+/// The `pattern` chars must all be present in `text` in the same order, whitespace in the pattern is ignored. Matches
+/// at word starts and consecutive matches score higher, skipped text lowers the score.
+///
+/// Returns `None` if the text does not match. An empty pattern matches any text with score `0`.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let mut pattern = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut r = FuzzyMatch::default();
+
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let p = match pattern.peek() {
+            Some(&p) => p,
+            None => break,
+        };
+        if c.to_lowercase().eq(std::iter::once(p)) {
+            pattern.next();
+            r.indices.push(i);
+
+            r.score += 1;
+            if prev_matched {
+                r.score += 4;
+            }
+            let is_word_start = match prev {
+                None => true,
+                Some(prev) => !prev.is_alphanumeric() || (prev.is_lowercase() && c.is_uppercase()),
+            };
+            if is_word_start {
+                r.score += 6;
+            }
+            prev_matched = true;
+        } else {
+            if !r.indices.is_empty() {
+                r.score -= 1;
+            }
+            prev_matched = false;
+        }
+        prev = Some(c);
+    }
+
+    if pattern.peek().is_some() {
+        None
+    } else {
+        Some(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shortcut::shortcut, APP};
+
+    use super::*;
+
+    crate::event::command! {
+        static PALETTE_TEST_CMD = {
+            name: "Palette Test",
+            info: "Command palette test.",
+            shortcut: shortcut!(CTRL+'P'),
+        };
+    }
+
+    #[test]
+    fn enumerate_entry() {
+        let mut app = APP.minimal().run_headless(false);
+
+        assert!(!COMMANDS.enumerate().iter().any(|e| e.command == PALETTE_TEST_CMD));
+
+        let _handle = PALETTE_TEST_CMD.subscribe(true);
+        // commands register on update.
+        let _ = app.update(false);
+        let entry = COMMANDS
+            .enumerate()
+            .into_iter()
+            .find(|e| e.command == PALETTE_TEST_CMD)
+            .expect("expected command entry");
+
+        assert_eq!("Palette Test", entry.name);
+        assert_eq!("Command palette test.", entry.info);
+        assert_eq!(Shortcuts::from(shortcut!(CTRL + 'P')), entry.shortcut);
+
+        let found = COMMANDS.search(vec![entry], "ptest");
+        assert_eq!(1, found.len());
+    }
+
+    #[test]
+    fn fuzzy_match_empty() {
+        assert_eq!(Some(FuzzyMatch::default()), fuzzy_match("", "Copy"));
+        assert_eq!(Some(FuzzyMatch::default()), fuzzy_match(" ", ""));
+    }
+
+    #[test]
+    fn fuzzy_match_indices() {
+        let m = fuzzy_match("sa", "Save As").unwrap();
+        assert_eq!(vec![0, 1], m.indices);
+
+        let m = fuzzy_match("s a", "Select All").unwrap();
+        assert_eq!(vec![0, 7], m.indices);
+
+        assert!(fuzzy_match("sx", "Save As").is_none());
+        assert!(fuzzy_match("as", "Save").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_rank() {
+        let word_starts = fuzzy_match("sa", "Save As").unwrap();
+        let inner = fuzzy_match("sa", "Paste All").unwrap();
+        assert!(word_starts.score > inner.score);
+
+        let consecutive = fuzzy_match("sav", "Save").unwrap();
+        let gaps = fuzzy_match("sav", "Select All Values").unwrap();
+        assert!(consecutive.score > gaps.score);
+    }
+}
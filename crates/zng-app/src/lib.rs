@@ -103,9 +103,9 @@ pub mod __proc_macro_util {
             pub use crate::widget::builder::{
                 getter_var, iter_input_build_actions, nest_group_items, new_dyn_other, new_dyn_ui_node, new_dyn_ui_node_list, new_dyn_var,
                 new_dyn_widget_handler, panic_input, state_var, ui_node_list_to_args, ui_node_to_args, value_to_args, var_to_args,
-                when_condition_expr_var, widget_handler_to_args, AnyArcWidgetHandler, ArcWidgetHandler, Importance, InputKind,
-                PropertyArgs, PropertyId, PropertyInfo, PropertyInput, PropertyInputTypes, PropertyNewArgs, SourceLocation,
-                UiNodeInWhenExprError, UiNodeListInWhenExprError, WgtInfo, WhenInput, WhenInputMember, WhenInputVar,
+                when_condition_expr_var, widget_handler_to_args, AnyArcWidgetHandler, AnyPropertyBuildAction, ArcWidgetHandler, Importance,
+                InputKind, PropertyArgs, PropertyEasingFn, PropertyId, PropertyInfo, PropertyInput, PropertyInputTypes, PropertyNewArgs,
+                SourceLocation, UiNodeInWhenExprError, UiNodeListInWhenExprError, WgtInfo, WhenInput, WhenInputMember, WhenInputVar,
                 WidgetHandlerInWhenExprError, WidgetType,
             };
         }
@@ -177,7 +177,7 @@ pub mod __proc_macro_util {
                 #[doc(hidden)]
                 pub use crate::var::animation::easing::{
                     back, bounce, circ, cubic, cubic_bezier, ease_in, ease_in_out, ease_out, ease_out_in, elastic, expo, linear, none,
-                    quad, quart, quint, reverse, reverse_out, sine, step_ceil, step_floor,
+                    quad, quart, quint, reverse, reverse_out, sine, step_ceil, step_floor, EasingStep, EasingTime,
                 };
             }
         }
@@ -1395,6 +1395,9 @@ impl<E: AppExtension> AppExtended<E> {
 mod running;
 pub use running::*;
 
+mod perf;
+pub use perf::*;
+
 mod private {
     // https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed
     pub trait Sealed {}
@@ -0,0 +1,292 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use zng_app_context::app_local;
+use zng_var::{var, ArcVar, ReadOnlyArcVar, Var as _};
+
+/// Time spent in each pass of an app update cycle.
+///
+/// An app update cycle starts when the app awakes to process raw events, timers or update requests and
+/// ends after the layout and render passes. See [`APP_PERF`] for more details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AppCycleTimings {
+    /// Time spent notifying events.
+    pub events: Duration,
+    /// Time spent in update passes.
+    pub updates: Duration,
+    /// Time spent rebuilding widget info trees.
+    pub info: Duration,
+    /// Time spent in layout passes.
+    pub layout: Duration,
+    /// Time spent in render passes.
+    ///
+    /// Note that this only includes the time to generate the display lists, the view-process renders asynchronously.
+    pub render: Duration,
+}
+impl AppCycleTimings {
+    /// Sum of all passes.
+    pub fn total(&self) -> Duration {
+        self.events + self.updates + self.info + self.layout + self.render
+    }
+
+    fn is_empty(&self) -> bool {
+        self.total() == Duration::ZERO
+    }
+}
+impl fmt::Display for AppCycleTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "total: {:?}, events: {:?}, updates: {:?}, info: {:?}, layout: {:?}, render: {:?}",
+            self.total(),
+            self.events,
+            self.updates,
+            self.info,
+            self.layout,
+            self.render
+        )
+    }
+}
+
+/// Rolling history of app update cycle timings.
+///
+/// See [`APP_PERF.history`] for more details.
+///
+/// [`APP_PERF.history`]: APP_PERF::history
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppPerfHistory {
+    /// Cycle timings, oldest first.
+    pub cycles: Vec<AppCycleTimings>,
+}
+impl AppPerfHistory {
+    /// Average of the cycle timings.
+    pub fn average(&self) -> AppCycleTimings {
+        let n = self.cycles.len() as u32;
+        if n == 0 {
+            return AppCycleTimings::default();
+        }
+        let mut sum = AppCycleTimings::default();
+        for c in &self.cycles {
+            sum.events += c.events;
+            sum.updates += c.updates;
+            sum.info += c.info;
+            sum.layout += c.layout;
+            sum.render += c.render;
+        }
+        AppCycleTimings {
+            events: sum.events / n,
+            updates: sum.updates / n,
+            info: sum.info / n,
+            layout: sum.layout / n,
+            render: sum.render / n,
+        }
+    }
+
+    /// Cycle with the longest total time.
+    pub fn max(&self) -> Option<AppCycleTimings> {
+        self.cycles.iter().max_by_key(|c| c.total()).copied()
+    }
+
+    /// Histogram of the cycle total times.
+    ///
+    /// Returns `bucket_count` counts, each bucket counts the cycles with total time in `[i * bucket_width, (i + 1) * bucket_width)`,
+    /// the last bucket also counts all longer cycles.
+    pub fn histogram(&self, bucket_width: Duration, bucket_count: usize) -> Vec<usize> {
+        let mut r = vec![0; bucket_count];
+        if bucket_count == 0 || bucket_width == Duration::ZERO {
+            return r;
+        }
+        for c in &self.cycles {
+            let i = (c.total().as_nanos() / bucket_width.as_nanos()).min(bucket_count as u128 - 1) as usize;
+            r[i] += 1;
+        }
+        r
+    }
+}
+
+/// App update cycle performance diagnostics.
+///
+/// This service measures the time spent in each pass of the app update cycles, the timings are available
+/// in the [`history`] variable if [`enabled`] and long cycles can be logged using [`long_cycle_warn`], this is
+/// useful for finding *jank* without attaching a profiler.
+///
+/// The service also configures the [`update_loop_budget`], the limit of update passes without render
+/// before the app considers itself stuck in an infinite loop.
+///
+/// [`history`]: APP_PERF::history
+/// [`enabled`]: APP_PERF::enabled
+/// [`long_cycle_warn`]: APP_PERF::long_cycle_warn
+/// [`update_loop_budget`]: APP_PERF::update_loop_budget
+#[allow(non_camel_case_types)]
+pub struct APP_PERF;
+impl APP_PERF {
+    /// If cycle timings are collected in the [`history`].
+    ///
+    /// Is `false` by default.
+    ///
+    /// [`history`]: Self::history
+    pub fn enabled(&self) -> ArcVar<bool> {
+        APP_PERF_SV.read().enabled.clone()
+    }
+
+    /// Maximum number of cycles retained in the [`history`].
+    ///
+    /// Is `300` by default.
+    ///
+    /// [`history`]: Self::history
+    pub fn history_len(&self) -> ArcVar<usize> {
+        APP_PERF_SV.read().history_len.clone()
+    }
+
+    /// Rolling history of the last update cycles.
+    ///
+    /// Only collects cycles that did some work, and only if [`enabled`]. The variable updates at most once every second
+    /// to not cause extra cycles on every frame.
+    ///
+    /// [`enabled`]: Self::enabled
+    pub fn history(&self) -> ReadOnlyArcVar<AppPerfHistory> {
+        APP_PERF_SV.read().history.read_only()
+    }
+
+    /// Logs a tracing warning for every update cycle that takes longer than the threshold.
+    ///
+    /// Is `None` by default. Long cycles are logged even if not [`enabled`].
+    ///
+    /// [`enabled`]: Self::enabled
+    pub fn long_cycle_warn(&self) -> ArcVar<Option<Duration>> {
+        APP_PERF_SV.read().long_cycle_warn.clone()
+    }
+
+    /// Maximum number of update passes without render in a single cycle.
+    ///
+    /// If an update cycle exceeds this count the app is probably stuck in an infinite loop of updates, an error is logged
+    /// with a trace of the most frequent update requests and the app starts skipping updates to render and poll system events.
+    /// The trace starts collecting after half the budget.
+    ///
+    /// Is `1000` by default. Values less than `2` are clamped.
+    pub fn update_loop_budget(&self) -> ArcVar<u16> {
+        APP_PERF_SV.read().update_loop_budget.clone()
+    }
+}
+
+app_local! {
+    static APP_PERF_SV: AppPerfService = AppPerfService {
+        enabled: var(false),
+        history_len: var(300),
+        history: var(AppPerfHistory::default()),
+        long_cycle_warn: var(None),
+        update_loop_budget: var(1000),
+        buffer: VecDeque::new(),
+        last_publish: None,
+    };
+}
+
+struct AppPerfService {
+    enabled: ArcVar<bool>,
+    history_len: ArcVar<usize>,
+    history: ArcVar<AppPerfHistory>,
+    long_cycle_warn: ArcVar<Option<Duration>>,
+    update_loop_budget: ArcVar<u16>,
+    buffer: VecDeque<AppCycleTimings>,
+    last_publish: Option<Instant>,
+}
+
+/// Pass of an app update cycle.
+#[derive(Clone, Copy)]
+pub(crate) enum CyclePass {
+    Events,
+    Updates,
+    Info,
+    Layout,
+    Render,
+}
+
+/// Collects the timings in the running app.
+#[derive(Default)]
+pub(crate) struct PerfMonitor {
+    active: bool,
+    in_cycle: bool,
+    current: AppCycleTimings,
+}
+impl PerfMonitor {
+    /// Start a cycle, if the previous one was not finished continues it.
+    pub fn start_cycle(&mut self) {
+        if !self.in_cycle {
+            self.in_cycle = true;
+            self.current = AppCycleTimings::default();
+            let s = APP_PERF_SV.read();
+            self.active = s.enabled.get() || s.long_cycle_warn.with(Option::is_some);
+        }
+    }
+
+    /// Start measuring a pass.
+    pub fn start(&self) -> Option<Instant> {
+        if self.active {
+            Some(Instant::now())
+        } else {
+            None
+        }
+    }
+
+    /// Finish measuring a pass.
+    pub fn stop(&mut self, start: Option<Instant>, pass: CyclePass) {
+        if let Some(t) = start {
+            let d = t.elapsed();
+            let c = &mut self.current;
+            match pass {
+                CyclePass::Events => c.events += d,
+                CyclePass::Updates => c.updates += d,
+                CyclePass::Info => c.info += d,
+                CyclePass::Layout => c.layout += d,
+                CyclePass::Render => c.render += d,
+            }
+        }
+    }
+
+    /// Finish the cycle, record and publish.
+    pub fn finish_cycle(&mut self) {
+        self.in_cycle = false;
+        if !self.active || self.current.is_empty() {
+            return;
+        }
+        let c = self.current;
+
+        let mut s = APP_PERF_SV.write();
+        if let Some(t) = s.long_cycle_warn.get() {
+            if c.total() > t {
+                tracing::warn!("long app update cycle, {c}");
+            }
+        }
+
+        if s.enabled.get() {
+            let len = s.history_len.get();
+            s.buffer.push_back(c);
+            while s.buffer.len() > len {
+                s.buffer.pop_front();
+            }
+
+            let now = Instant::now();
+            if s.last_publish
+                .map(|t| now.duration_since(t) >= Duration::from_secs(1))
+                .unwrap_or(true)
+            {
+                s.last_publish = Some(now);
+                let history = AppPerfHistory {
+                    cycles: s.buffer.iter().copied().collect(),
+                };
+                s.history.set(history);
+            }
+        } else if !s.buffer.is_empty() {
+            s.buffer.clear();
+            s.last_publish = None;
+        }
+    }
+}
+
+/// Current update loop budget.
+pub(crate) fn update_loop_budget() -> u32 {
+    APP_PERF_SV.read().update_loop_budget.get().max(2) as u32
+}
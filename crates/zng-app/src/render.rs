@@ -508,6 +508,7 @@ impl FrameBuilder {
             // did not reuse, render widget.
 
             reused = false;
+            bounds.inc_render_count();
             undo_prev_outer_transform = None;
 
             frame.widget_data = Some(WidgetData {
@@ -784,7 +785,8 @@ impl FrameBuilder {
     ///
     /// This is valid only when [`is_outer`].
     ///
-    /// When [`push_inner`] is called the widget are is first filled with the backdrop filters.
+    /// When [`push_inner`] is called the widget area is first filled with the backdrop filters, clipped
+    /// to the widget corner radius.
     ///
     /// [`is_outer`]: Self::is_outer
     /// [`push_inner`]: Self::push_inner
@@ -927,8 +929,16 @@ impl FrameBuilder {
                             !data.inner_is_set,
                         );
                         if !data.backdrop_filter.is_empty() {
-                            self.display_list
-                                .push_backdrop_filter(PxRect::from_size(bounds.inner_size()), &data.backdrop_filter);
+                            // clip to the widget corners, so that rounded panels don't blur outside the curve.
+                            let rect = PxRect::from_size(bounds.inner_size());
+                            let corners = wgt_info.border_info().corner_radius();
+                            if corners == PxCornerRadius::zero() {
+                                self.display_list.push_backdrop_filter(rect, &data.backdrop_filter);
+                            } else {
+                                self.display_list.push_clip_rounded_rect(rect, corners, false);
+                                self.display_list.push_backdrop_filter(rect, &data.backdrop_filter);
+                                self.display_list.pop_clip();
+                            }
                         }
                     };
                 }
@@ -1297,6 +1307,51 @@ impl FrameBuilder {
         }
     }
 
+    /// Push a nine-patch image.
+    ///
+    /// The `slice` offsets define the nine regions of the image, in image pixels. The corner regions are drawn in the
+    /// corners of `bounds` at the `widths` size, the edge regions are scaled or tiled along the `bounds` edges using the repeat
+    /// modes and the center region is only drawn if `fill` is set. The render is a single primitive, the tiling is done by the GPU.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push_nine_patch_image(
+        &mut self,
+        bounds: PxRect,
+        widths: PxSideOffsets,
+        image: &impl Img,
+        img_size: PxSize,
+        slice: PxSideOffsets,
+        fill: bool,
+        repeat_horizontal: RepeatMode,
+        repeat_vertical: RepeatMode,
+        rendering: ImageRendering,
+    ) {
+        expect_inner!(self.push_nine_patch_image);
+        warn_empty!(self.push_nine_patch_image(bounds));
+
+        if let (true, Some(r)) = (self.visible, &self.renderer) {
+            let image_id = image.renderer_id(r);
+            self.display_list.push_nine_patch_image(
+                bounds,
+                widths,
+                image_id,
+                img_size,
+                slice,
+                fill,
+                repeat_horizontal,
+                repeat_vertical,
+                rendering,
+            );
+        }
+
+        if self.auto_hit_test {
+            if fill {
+                self.hit_test().push_rect(bounds);
+            } else {
+                self.hit_test().push_border(bounds, widths, PxCornerRadius::zero());
+            }
+        }
+    }
+
     /// Push a nine-patch border with linear gradient source.
     #[allow(clippy::too_many_arguments)]
     pub fn push_border_linear_gradient(
@@ -21,6 +21,7 @@ use crate::{
         TimeoutOrAppDisconnected, EVENTS,
     },
     event_args,
+    perf::{CyclePass, PerfMonitor},
     shortcut::shortcut,
     shortcut::CommandShortcutExt,
     timer::TimersService,
@@ -41,6 +42,7 @@ pub(crate) struct RunningApp<E: AppExtension> {
 
     loop_timer: LoopTimer,
     loop_monitor: LoopMonitor,
+    perf_monitor: PerfMonitor,
 
     pending_view_events: Vec<zng_view_api::Event>,
     pending_view_frame_events: Vec<zng_view_api::window::EventFrameRendered>,
@@ -110,6 +112,7 @@ impl<E: AppExtension> RunningApp<E> {
 
             loop_timer: LoopTimer::default(),
             loop_monitor: LoopMonitor::default(),
+            perf_monitor: PerfMonitor::default(),
 
             pending_view_events: Vec::with_capacity(100),
             pending_view_frame_events: Vec::with_capacity(5),
@@ -140,6 +143,7 @@ impl<E: AppExtension> RunningApp<E> {
         let _scope = tracing::trace_span!("notify_event", event = update.event().name()).entered();
 
         let _t = INSTANT_APP.pause_for_update();
+        let perf = self.perf_monitor.start();
 
         update.event().on_update(&mut update);
 
@@ -153,6 +157,8 @@ impl<E: AppExtension> RunningApp<E> {
         self.extensions.event(&mut update);
         observer.event(&mut update);
         update.call_pos_actions();
+
+        self.perf_monitor.stop(perf, CyclePass::Events);
     }
 
     fn device_id(&mut self, id: zng_view_api::DeviceId) -> DeviceId {
@@ -218,6 +224,10 @@ impl<E: AppExtension> RunningApp<E> {
                 let args = RawHoveredFileCancelledArgs::now(window_id(w_id));
                 self.notify_event(RAW_HOVERED_FILE_CANCELLED_EVENT.new_update(args), observer);
             }
+            Event::AppDragEnded { window, drag, applied } => {
+                let args = RawAppDragEndedArgs::now(window_id(window), drag, applied);
+                self.notify_event(RAW_APP_DRAG_ENDED_EVENT.new_update(args), observer);
+            }
             Event::FocusChanged { prev, new } => {
                 let args = RawWindowFocusArgs::now(prev.map(window_id), new.map(window_id));
                 self.notify_event(RAW_WINDOW_FOCUS_EVENT.new_update(args), observer);
@@ -276,6 +286,57 @@ impl<E: AppExtension> RunningApp<E> {
                 let args = RawTouchpadPressureArgs::now(window_id(w_id), self.device_id(d_id), pressure, stage);
                 self.notify_event(RAW_TOUCHPAD_PRESSURE_EVENT.new_update(args), observer);
             }
+            Event::PinchGesture {
+                window: w_id,
+                device: d_id,
+                delta,
+                phase,
+            } => {
+                let args = RawPinchGestureArgs::now(window_id(w_id), self.device_id(d_id), delta, phase);
+                self.notify_event(RAW_PINCH_GESTURE_EVENT.new_update(args), observer);
+            }
+            Event::RotationGesture {
+                window: w_id,
+                device: d_id,
+                delta,
+                phase,
+            } => {
+                let args = RawRotationGestureArgs::now(window_id(w_id), self.device_id(d_id), delta, phase);
+                self.notify_event(RAW_ROTATION_GESTURE_EVENT.new_update(args), observer);
+            }
+            Event::PanGesture {
+                window: w_id,
+                device: d_id,
+                delta,
+                phase,
+            } => {
+                let args = RawPanGestureArgs::now(window_id(w_id), self.device_id(d_id), delta, phase);
+                self.notify_event(RAW_PAN_GESTURE_EVENT.new_update(args), observer);
+            }
+            Event::GlobalShortcut(id) => {
+                let args = RawGlobalShortcutArgs::now(id);
+                self.notify_event(RAW_GLOBAL_SHORTCUT_EVENT.new_update(args), observer);
+            }
+            Event::TrayIconClicked { tray, button, double } => {
+                let args = RawTrayIconClickedArgs::now(tray, button, double);
+                self.notify_event(RAW_TRAY_ICON_CLICKED_EVENT.new_update(args), observer);
+            }
+            Event::TrayMenuItemClicked { tray, item } => {
+                let args = RawTrayMenuItemClickedArgs::now(tray, item);
+                self.notify_event(RAW_TRAY_MENU_ITEM_CLICKED_EVENT.new_update(args), observer);
+            }
+            Event::NotificationResponded { notification, response } => {
+                let args = RawNotificationRespondedArgs::now(notification, response);
+                self.notify_event(RAW_NOTIFICATION_RESPONDED_EVENT.new_update(args), observer);
+            }
+            Event::SystemMenuItemClicked { window: w_id, item } => {
+                let args = RawSystemMenuItemClickedArgs::now(window_id(w_id), item);
+                self.notify_event(RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT.new_update(args), observer);
+            }
+            Event::AppMenuItemClicked { window: w_id, item } => {
+                let args = RawAppMenuItemClickedArgs::now(window_id(w_id), item);
+                self.notify_event(RAW_APP_MENU_ITEM_CLICKED_EVENT.new_update(args), observer);
+            }
             Event::AxisMotion {
                 window: w_id,
                 device: d_id,
@@ -304,10 +365,24 @@ impl<E: AppExtension> RunningApp<E> {
                 self.notify_event(RAW_SCALE_FACTOR_CHANGED_EVENT.new_update(args), observer);
             }
             Event::MonitorsChanged(monitors) => {
-                let monitors: Vec<_> = monitors.into_iter().map(|(id, info)| (VIEW_PROCESS.monitor_id(id), info)).collect();
+                let monitors: Vec<_> = monitors
+                    .into_iter()
+                    .map(|(id, info)| (VIEW_PROCESS.monitor_id_named(id, &info.name), info))
+                    .collect();
                 let args = RawMonitorsChangedArgs::now(monitors);
                 self.notify_event(RAW_MONITORS_CHANGED_EVENT.new_update(args), observer);
             }
+            Event::FramePresented(ev) => {
+                // convert from system time to app time.
+                let elapsed = std::time::SystemTime::now().duration_since(ev.presented_at).unwrap_or_default();
+                let presented_at = INSTANT.now() - elapsed;
+                let args = RawFramePresentedArgs::now(window_id(ev.window), ev.frame, presented_at, ev.refresh_interval);
+                self.notify_event(RAW_FRAME_PRESENTED_EVENT.new_update(args), observer);
+            }
+            Event::RedrawTick(w_id) => {
+                let args = RawRedrawTickArgs::now(window_id(w_id));
+                self.notify_event(RAW_REDRAW_TICK_EVENT.new_update(args), observer);
+            }
             Event::WindowCloseRequested(w_id) => {
                 let args = RawWindowCloseRequestedArgs::now(window_id(w_id));
                 self.notify_event(RAW_WINDOW_CLOSE_REQUESTED_EVENT.new_update(args), observer);
@@ -369,9 +444,19 @@ impl<E: AppExtension> RunningApp<E> {
                     self.notify_event(RAW_IMAGE_LOAD_ERROR_EVENT.new_update(args), observer);
                 }
             }
-            Event::ImageEncoded { image: id, format, data } => VIEW_PROCESS.on_image_encoded(id, format, data),
-            Event::ImageEncodeError { image: id, format, error } => {
-                VIEW_PROCESS.on_image_encode_error(id, format, error);
+            Event::ImageEncoded {
+                image: id,
+                format,
+                options,
+                data,
+            } => VIEW_PROCESS.on_image_encoded(id, format, options, data),
+            Event::ImageEncodeError {
+                image: id,
+                format,
+                options,
+                error,
+            } => {
+                VIEW_PROCESS.on_image_encode_error(id, format, options, error);
             }
             Event::FrameImageReady {
                 window: w_id,
@@ -420,6 +505,14 @@ impl<E: AppExtension> RunningApp<E> {
                 let args = RawFontChangedArgs::now();
                 self.notify_event(RAW_FONT_CHANGED_EVENT.new_update(args), observer);
             }
+            Event::ClipboardChanged => {
+                let args = RawClipboardChangedArgs::now();
+                self.notify_event(RAW_CLIPBOARD_CHANGED_EVENT.new_update(args), observer);
+            }
+            Event::KeyboardLayoutChanged => {
+                let args = RawKeyboardLayoutChangedArgs::now();
+                self.notify_event(RAW_KEYBOARD_LAYOUT_CHANGED_EVENT.new_update(args), observer);
+            }
             Event::FontAaChanged(aa) => {
                 let args = RawFontAaChangedArgs::now(aa);
                 self.notify_event(RAW_FONT_AA_CHANGED_EVENT.new_update(args), observer);
@@ -488,6 +581,28 @@ impl<E: AppExtension> RunningApp<E> {
                 self.notify_event(KEY_EVENT.new_update(args), observer);
             }
 
+            Event::GamepadConnected { device: d_id, name } => {
+                let args = RawGamepadConnectedArgs::now(self.device_id(d_id), name);
+                self.notify_event(RAW_GAMEPAD_CONNECTED_EVENT.new_update(args), observer);
+            }
+            Event::GamepadDisconnected { device: d_id } => {
+                let args = RawGamepadDisconnectedArgs::now(self.device_id(d_id));
+                self.notify_event(RAW_GAMEPAD_DISCONNECTED_EVENT.new_update(args), observer);
+            }
+            Event::GamepadButton {
+                device: d_id,
+                button,
+                value,
+                state,
+            } => {
+                let args = RawGamepadButtonArgs::now(self.device_id(d_id), button, value, state);
+                self.notify_event(RAW_GAMEPAD_BUTTON_EVENT.new_update(args), observer);
+            }
+            Event::GamepadAxis { device: d_id, axis, value } => {
+                let args = RawGamepadAxisArgs::now(self.device_id(d_id), axis, value);
+                self.notify_event(RAW_GAMEPAD_AXIS_EVENT.new_update(args), observer);
+            }
+
             Event::LowMemory => {}
 
             Event::RecoveredFromComponentPanic { component, recover, panic } => {
@@ -574,7 +689,7 @@ impl<E: AppExtension> RunningApp<E> {
 
                     let monitors: Vec<_> = available_monitors
                         .into_iter()
-                        .map(|(id, info)| (VIEW_PROCESS.monitor_id(id), info))
+                        .map(|(id, info)| (VIEW_PROCESS.monitor_id_named(id, &info.name), info))
                         .collect();
 
                     VARS.animations_enabled().set(animations_config.enabled);
@@ -694,6 +809,7 @@ impl<E: AppExtension> RunningApp<E> {
         }
 
         UPDATES.on_app_awake();
+        self.perf_monitor.start_cycle();
 
         // clear timers.
         let updated_timers = self.loop_timer.awake();
@@ -727,6 +843,12 @@ impl<E: AppExtension> RunningApp<E> {
         }
 
         self.finish_frame(observer);
+        self.perf_monitor.finish_cycle();
+
+        let mut background = false;
+        if !self.has_pending_updates() && !UPDATES.has_pending_layout_or_render() {
+            background = UPDATES.apply_background();
+        }
 
         UPDATES.next_deadline(&mut self.loop_timer);
 
@@ -734,7 +856,7 @@ impl<E: AppExtension> RunningApp<E> {
             UPDATES.on_app_sleep();
             self.exited = true;
             AppControlFlow::Exit
-        } else if self.has_pending_updates() || UPDATES.has_pending_layout_or_render() {
+        } else if background || self.has_pending_updates() || UPDATES.has_pending_layout_or_render() {
             AppControlFlow::Poll
         } else {
             UPDATES.on_app_sleep();
@@ -759,6 +881,7 @@ impl<E: AppExtension> RunningApp<E> {
                     let mut info_widgets = mem::take(&mut self.pending.info_widgets);
 
                     let _t = INSTANT_APP.pause_for_update();
+                    let perf = self.perf_monitor.start();
 
                     {
                         let _s = tracing::debug_span!("ext.info").entered();
@@ -768,6 +891,7 @@ impl<E: AppExtension> RunningApp<E> {
                         let _s = tracing::debug_span!("obs.info").entered();
                         observer.info(&mut info_widgets);
                     }
+                    self.perf_monitor.stop(perf, CyclePass::Info);
                 }
 
                 self.pending |= UPDATES.apply_updates();
@@ -779,6 +903,7 @@ impl<E: AppExtension> RunningApp<E> {
                     let mut update_widgets = mem::take(&mut self.pending.update_widgets);
 
                     let _t = INSTANT_APP.pause_for_update();
+                    let perf = self.perf_monitor.start();
 
                     {
                         let _s = tracing::debug_span!("ext.update_preview").entered();
@@ -808,6 +933,7 @@ impl<E: AppExtension> RunningApp<E> {
                         observer.update();
                     }
                     UPDATES.on_updates();
+                    self.perf_monitor.stop(perf, CyclePass::Updates);
                 }
 
                 any
@@ -829,6 +955,7 @@ impl<E: AppExtension> RunningApp<E> {
 
                 self.loop_monitor.maybe_trace(|| {
                     let _t = INSTANT_APP.pause_for_update();
+                    let perf = self.perf_monitor.start();
 
                     {
                         let _s = tracing::debug_span!("ext.event_preview").entered();
@@ -857,6 +984,7 @@ impl<E: AppExtension> RunningApp<E> {
                         observer.event(&mut update);
                     }
                     update.call_pos_actions();
+                    self.perf_monitor.stop(perf, CyclePass::Events);
                 });
 
                 self.apply_updates(observer);
@@ -881,6 +1009,7 @@ impl<E: AppExtension> RunningApp<E> {
 
             self.loop_monitor.maybe_trace(|| {
                 let _t = INSTANT_APP.pause_for_update();
+                let perf = self.perf_monitor.start();
 
                 {
                     let _s = tracing::debug_span!("ext.layout").entered();
@@ -890,6 +1019,7 @@ impl<E: AppExtension> RunningApp<E> {
                     let _s = tracing::debug_span!("obs.layout").entered();
                     observer.layout(&mut layout_widgets);
                 }
+                self.perf_monitor.stop(perf, CyclePass::Layout);
             });
 
             self.apply_updates(observer);
@@ -903,6 +1033,7 @@ impl<E: AppExtension> RunningApp<E> {
             let mut render_update_widgets = mem::take(&mut self.pending.render_update_widgets);
 
             let _t = INSTANT_APP.pause_for_update();
+            let perf = self.perf_monitor.start();
 
             {
                 let _s = tracing::debug_span!("ext.render").entered();
@@ -912,6 +1043,7 @@ impl<E: AppExtension> RunningApp<E> {
                 let _s = tracing::debug_span!("obs.render").entered();
                 observer.render(&mut render_widgets, &mut render_update_widgets);
             }
+            self.perf_monitor.stop(perf, CyclePass::Render);
         }
 
         self.loop_monitor.finish_frame();
@@ -1021,32 +1153,39 @@ impl zng_var::animation::AnimationTimer for LoopTimer {
 
 #[derive(Default)]
 struct LoopMonitor {
-    update_count: u16,
+    update_count: u32,
+    budget: u32,
     skipped: bool,
     trace: Vec<UpdateTrace>,
 }
 impl LoopMonitor {
     /// Returns `false` if the loop should break.
     pub fn update(&mut self, update_once: impl FnOnce() -> bool) -> bool {
+        if self.update_count == 0 {
+            self.budget = crate::perf::update_loop_budget();
+        }
         self.update_count += 1;
 
-        if self.update_count < 500 {
+        let budget = self.budget;
+        let trace_start = budget / 2;
+        if self.update_count < trace_start {
             update_once()
-        } else if self.update_count < 1000 {
+        } else if self.update_count < budget {
             UpdatesTrace::collect_trace(&mut self.trace, update_once)
-        } else if self.update_count == 1000 {
+        } else if self.update_count == budget {
             self.skipped = true;
             let trace = UpdatesTrace::format_trace(mem::take(&mut self.trace));
             tracing::error!(
-                "updated 1000 times without rendering, probably stuck in an infinite loop\n\
+                "updated {budget} times without rendering, probably stuck in an infinite loop\n\
                  will start skipping updates to render and poll system events\n\
-                 top 20 most frequent update requests (in 500 cycles):\n\
+                 top 20 most frequent update requests (in {} cycles):\n\
                  {trace}\n\
-                    you can use `UpdatesTraceUiNodeExt` and `updates_trace_event` to refine the trace"
+                    you can use `UpdatesTraceUiNodeExt` and `updates_trace_event` to refine the trace",
+                budget - trace_start
             );
             false
-        } else if self.update_count == 1500 {
-            self.update_count = 1001;
+        } else if self.update_count == budget + trace_start {
+            self.update_count = budget + 1;
             false
         } else {
             update_once()
@@ -1054,7 +1193,7 @@ impl LoopMonitor {
     }
 
     pub fn maybe_trace(&mut self, notify_once: impl FnOnce()) {
-        if (500..1000).contains(&self.update_count) {
+        if (self.budget / 2..self.budget).contains(&self.update_count) {
             UpdatesTrace::collect_trace(&mut self.trace, notify_once);
         } else {
             notify_once();
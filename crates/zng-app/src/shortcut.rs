@@ -3,12 +3,13 @@
 //! This is declared on this crate mostly to support shortcuts in commands, shortcut events
 //! are implemented in the input crate.
 
-use std::fmt;
+use std::{collections::HashMap, fmt, sync::Arc};
 
 use bitflags::bitflags;
+use zng_app_context::app_local;
 use zng_txt::{ToTxt, Txt};
 use zng_unique_id::static_id;
-use zng_var::{impl_from_and_into_var, BoxedVar, Var};
+use zng_var::{impl_from_and_into_var, merge_var, var, ArcVar, BoxedVar, Var};
 
 #[doc(hidden)]
 pub use zng_view_api::keyboard::{Key, KeyCode};
@@ -88,6 +89,23 @@ impl fmt::Display for GestureKey {
         }
     }
 }
+impl GestureKey {
+    /// Display text of the key, with physical keys labeled by the keyboard layout `labels`.
+    ///
+    /// Physical keys that have no label are displayed by the key code name, same as the [`Display`] implementation.
+    ///
+    /// [`Display`]: fmt::Display
+    pub fn label_txt(&self, labels: &KeyLabels) -> Txt {
+        if let GestureKey::Code(c) = self {
+            match labels.get(*c) {
+                Some(Key::Char(c) | Key::Dead(Some(c))) => return c.to_uppercase().to_txt(),
+                Some(Key::Str(s)) => return s.to_uppercase().into(),
+                _ => {}
+            }
+        }
+        self.to_txt()
+    }
+}
 /// Accepts only keys that are not [`is_modifier`] and not [`is_composition`].
 ///
 /// [`is_modifier`]: Key::is_modifier
@@ -182,6 +200,30 @@ impl KeyGesture {
             key,
         }
     }
+
+    /// Display text of the gesture, with physical keys labeled by the keyboard layout `labels`.
+    pub fn label_txt(&self, labels: &KeyLabels) -> Txt {
+        let mut r = String::new();
+        let _ = self.fmt_modifiers(&mut r);
+        r.push_str(&self.key.label_txt(labels));
+        r.into()
+    }
+
+    fn fmt_modifiers(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.modifiers.has_super() {
+            write!(f, "Super+")?
+        }
+        if self.modifiers.has_ctrl() {
+            write!(f, "Ctrl+")?
+        }
+        if self.modifiers.has_shift() {
+            write!(f, "Shift+")?
+        }
+        if self.modifiers.has_alt() {
+            write!(f, "Alt+")?
+        }
+        Ok(())
+    }
 }
 impl fmt::Debug for KeyGesture {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -197,19 +239,7 @@ impl fmt::Debug for KeyGesture {
 }
 impl fmt::Display for KeyGesture {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.modifiers.has_super() {
-            write!(f, "Super+")?
-        }
-        if self.modifiers.has_ctrl() {
-            write!(f, "Ctrl+")?
-        }
-        if self.modifiers.has_shift() {
-            write!(f, "Shift+")?
-        }
-        if self.modifiers.has_alt() {
-            write!(f, "Alt+")?
-        }
-
+        self.fmt_modifiers(f)?;
         write!(f, "{}", self.key)
     }
 }
@@ -315,6 +345,12 @@ impl fmt::Display for KeyChord {
         write!(f, "{} {}", self.starter, self.complement)
     }
 }
+impl KeyChord {
+    /// Display text of the chord, with physical keys labeled by the keyboard layout `labels`.
+    pub fn label_txt(&self, labels: &KeyLabels) -> Txt {
+        zng_txt::formatx!("{} {}", self.starter.label_txt(labels), self.complement.label_txt(labels))
+    }
+}
 
 /// Keyboard gesture or chord associated with a command.
 ///
@@ -351,6 +387,17 @@ impl fmt::Display for Shortcut {
     }
 }
 impl Shortcut {
+    /// Display text of the shortcut, with physical keys labeled by the keyboard layout `labels`.
+    ///
+    /// Gestures that use a [`GestureKey::Code`] display the key label instead of the key code name.
+    pub fn label_txt(&self, labels: &KeyLabels) -> Txt {
+        match self {
+            Shortcut::Gesture(g) => g.label_txt(labels),
+            Shortcut::Chord(c) => c.label_txt(labels),
+            Shortcut::Modifier(m) => m.to_txt(),
+        }
+    }
+
     /// Modifiers state required by shortcut.
     pub fn modifiers_state(&self) -> ModifiersState {
         match self {
@@ -501,6 +548,64 @@ impl std::ops::DerefMut for Shortcuts {
     }
 }
 
+/// Labels of physical keys in a keyboard layout.
+///
+/// Physical keys ([`GestureKey::Code`]) are named by their position on a US keyboard, the labels map these
+/// key codes to the [`Key`] generated by the same key in a keyboard layout, so that shortcuts display the glyph
+/// printed on the user keyboard, for example, `KeyCode::KeyQ` is labeled `A` on AZERTY keyboards.
+///
+/// The current keyboard layout labels are tracked by the `KEYBOARD.key_labels` variable in the input crate.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct KeyLabels(Arc<HashMap<KeyCode, Key>>);
+impl KeyLabels {
+    /// New from key code and label pairs.
+    ///
+    /// Labels that are [`Key::Unidentified`] are ignored.
+    pub fn new(labels: impl IntoIterator<Item = (KeyCode, Key)>) -> Self {
+        Self(Arc::new(
+            labels.into_iter().filter(|(_, k)| !matches!(k, Key::Unidentified)).collect(),
+        ))
+    }
+
+    /// Gets the label of the physical key.
+    pub fn get(&self, code: KeyCode) -> Option<&Key> {
+        self.0.get(&code)
+    }
+
+    /// If no key label is available.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of labeled keys.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Iterate over the labeled key codes and labels.
+    pub fn iter(&self) -> impl Iterator<Item = (KeyCode, &Key)> {
+        self.0.iter().map(|(c, k)| (*c, k))
+    }
+}
+impl fmt::Debug for KeyLabels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter()).finish()
+    }
+}
+
+app_local! {
+    static KEY_LABELS: ArcVar<KeyLabels> = var(KeyLabels::default());
+}
+
+/// Variable that tracks the key labels of the current keyboard layout.
+///
+/// The variable is empty until the keyboard manager loads the labels from the view-process, it updates when the
+/// system keyboard layout changes. In apps use the `KEYBOARD.key_labels` read-only variable from the input crate.
+#[doc(hidden)]
+pub fn key_labels_var() -> ArcVar<KeyLabels> {
+    KEY_LABELS.read().clone()
+}
+
 /// Shortcut, gesture parsing error.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseError {
@@ -767,13 +872,20 @@ pub trait CommandShortcutExt {
     fn shortcut(self) -> CommandMetaVar<Shortcuts>;
 
     /// Gets a read-only variable that is the display text for the first shortcut.
+    ///
+    /// Physical keys are labeled using the current keyboard layout, the text updates when the layout changes.
     fn shortcut_txt(self) -> BoxedVar<Txt>
     where
         Self: Sized,
     {
-        self.shortcut()
-            .map(|c| if c.is_empty() { Txt::from("") } else { c[0].to_txt() })
-            .boxed()
+        merge_var!(self.shortcut(), key_labels_var(), |c, labels| {
+            if c.is_empty() {
+                Txt::from("")
+            } else {
+                c[0].label_txt(labels)
+            }
+        })
+        .boxed()
     }
 
     /// Gets a read-write variable that sets a filter for when the [`shortcut`] is valid.
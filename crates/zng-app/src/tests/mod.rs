@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 mod ui_node;
+mod update;
 mod widget;
 
 mod a;
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Instant};
 
 use zng_app_proc_macros::{property, widget};
 use zng_var::ContextInitHandle;
@@ -6,9 +6,9 @@ use zng_var::ContextInitHandle;
 use crate::{
     ui_vec,
     widget::{
-        base::PARALLEL_VAR,
+        base::{PARALLEL_MIN_CHUNK_VAR, PARALLEL_VAR},
         node::{PanelList, UiNode, UiNodeList, UiNodeVec},
-        WidgetUpdateMode,
+        WidgetId, WidgetUpdateMode,
     },
     window::WINDOW,
     APP,
@@ -99,6 +99,68 @@ pub fn par_each_ctx() {
     });
 }
 
+#[test]
+pub fn par_info_min_chunk() {
+    let _app = APP.minimal().run_headless(false);
+
+    let ids: Vec<_> = (0..1000).map(|_| WidgetId::new_unique()).collect();
+
+    for (parallel, min_chunk) in [(false, 1usize), (true, 0), (true, 1), (true, 7), (true, 64), (true, 500), (true, 501)] {
+        let mut test = ListWgt! {
+            children = ids.iter().map(|&id| EmptyWgt! { id; }.boxed()).collect::<UiNodeVec>();
+        };
+
+        let info_ids = WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+            PARALLEL_VAR.with_context_var(ContextInitHandle::new(), parallel, || {
+                PARALLEL_MIN_CHUNK_VAR.with_context_var(ContextInitHandle::new(), min_chunk, || {
+                    WINDOW.test_init(&mut test);
+                    WINDOW.test_info(&mut test);
+                })
+            });
+            WINDOW.info().root().descendants().skip(1).map(|w| w.id()).collect::<Vec<_>>()
+        });
+
+        assert_eq!(ids, info_ids, "parallel: {parallel}, min_chunk: {min_chunk}");
+    }
+}
+
+/// Benchmark of the first info and layout pass of a very wide list, sequential and parallel with different chunk sizes.
+///
+/// Run with `cargo test -p zng-app --release -- --ignored --nocapture par_info_layout_scaling`.
+#[test]
+#[ignore = "benchmark"]
+pub fn par_info_layout_scaling() {
+    let _app = APP.minimal().run_headless(false);
+
+    for (name, parallel, min_chunk) in [
+        ("sequential", false, 1usize),
+        ("parallel", true, 1),
+        ("parallel, min chunk 64", true, 64),
+        ("parallel, min chunk 1024", true, 1024),
+    ] {
+        let mut best = None;
+        for _ in 0..5 {
+            let mut test = ListWgt! {
+                children = (0..10_000).map(|_| EmptyWgt!().boxed()).collect::<UiNodeVec>();
+            };
+            let elapsed = WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+                PARALLEL_VAR.with_context_var(ContextInitHandle::new(), parallel, || {
+                    PARALLEL_MIN_CHUNK_VAR.with_context_var(ContextInitHandle::new(), min_chunk, || {
+                        WINDOW.test_init(&mut test);
+
+                        let start = Instant::now();
+                        WINDOW.test_info(&mut test);
+                        let _ = WINDOW.test_layout(&mut test, None);
+                        start.elapsed()
+                    })
+                })
+            });
+            best = Some(best.map(|b: std::time::Duration| b.min(elapsed)).unwrap_or(elapsed));
+        }
+        println!("{name}: {:?}", best.unwrap());
+    }
+}
+
 #[widget($crate::tests::ui_node_list::ListWgt)]
 pub struct ListWgt(crate::widget::base::WidgetBase);
 impl ListWgt {
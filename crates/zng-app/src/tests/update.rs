@@ -0,0 +1,86 @@
+//! Tests for `UPDATES` background lane.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{
+    handler::app_hn,
+    update::{UpdateArgs, UPDATES},
+    AppControlFlow, APP,
+};
+
+#[test]
+pub fn background_after_normal_updates() {
+    let mut app = APP.minimal().run_headless(false);
+
+    let log = Arc::new(Mutex::new(vec![]));
+
+    UPDATES
+        .on_pre_update(app_hn!(log, |_: &UpdateArgs, _| {
+            let mut log = log.lock();
+            let normal = log.iter().filter(|l| **l == "normal").count();
+            if normal < 5 {
+                log.push("normal");
+                UPDATES.update(None);
+            }
+        }))
+        .perm();
+
+    UPDATES.run(async_log(log.clone(), "background-a")).perm();
+    UPDATES.run(async_log(log.clone(), "background-b")).perm();
+
+    let _ = app.update(false);
+
+    assert_eq!(
+        &*log.lock(),
+        &["normal", "normal", "normal", "normal", "normal", "background-a", "background-b"]
+    );
+}
+
+#[test]
+pub fn background_not_starved() {
+    let mut app = APP.minimal().run_headless(false);
+
+    let log = Arc::new(Mutex::new(vec![]));
+
+    // background requested every cycle while normal updates are also requested every cycle,
+    // background must still run after the normal updates stop.
+    UPDATES
+        .on_pre_update(app_hn!(log, |_: &UpdateArgs, _| {
+            let mut log = log.lock();
+            let normal = log.iter().filter(|l| **l == "normal").count();
+            if normal < 20 {
+                log.push("normal");
+                UPDATES.update(None);
+                UPDATES.update_background(None);
+            }
+        }))
+        .perm();
+    UPDATES.run(async_log(log.clone(), "background")).perm();
+
+    let _ = app.update(false);
+
+    let log = log.lock();
+    assert_eq!(log.len(), 21);
+    assert_eq!(log.last(), Some(&"background"));
+
+    // all background requests coalesced and applied, app can sleep.
+    drop(log);
+    assert_eq!(app.update(false), AppControlFlow::Wait);
+}
+
+#[test]
+pub fn should_yield_on_pending_update() {
+    let mut app = APP.minimal().run_headless(false);
+    let _ = app.update(false);
+
+    assert!(!UPDATES.should_yield());
+    UPDATES.update(None);
+    assert!(UPDATES.should_yield());
+}
+
+async fn async_log(log: Arc<Mutex<Vec<&'static str>>>, msg: &'static str) {
+    UPDATES.yield_background().await;
+    log.lock().push(msg);
+}
@@ -5,8 +5,16 @@
 use zng_app_proc_macros::{property, widget};
 use zng_var::{IntoValue, Var};
 
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
 use crate::{
-    widget::{builder::WidgetBuilder, node::UiNode, WidgetId, WidgetUpdateMode, WIDGET},
+    widget::{builder::WidgetBuilder, node::UiNode, WidgetId, WidgetUpdateMode, WIDGET, WIDGET_TASK_PANIC_EVENT},
     widget_set,
     window::WINDOW,
     APP,
@@ -369,6 +377,39 @@ pub fn wgt_multi_when() {
     });
 }
 
+#[test]
+pub fn widget_user_else_when() {
+    let _app = APP.minimal().run_headless(false);
+
+    WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+        let mut wgt = EmptyWgt! {
+            util::live_trace = "default";
+
+            when *#util::is_state {
+                util::live_trace = "state_0";
+            } else when *#util::is_state {
+                util::live_trace = "state_1";
+            }
+        };
+        WINDOW.test_init(&mut wgt);
+        WINDOW.test_info(&mut wgt);
+
+        assert!(util::traced(&mut wgt, "default"));
+
+        util::set_state(&mut wgt, true);
+        WINDOW.test_update(&mut wgt, None); // state
+        WINDOW.test_update(&mut wgt, None); // when
+
+        assert!(util::traced(&mut wgt, "state_0"));
+        assert!(!util::traced(&mut wgt, "state_1"));
+
+        util::set_state(&mut wgt, false);
+        WINDOW.test_update(&mut wgt, None);
+
+        assert!(util::traced(&mut wgt, "default"));
+    });
+}
+
 /*
  * Tests widget property attributes.
  */
@@ -1119,6 +1160,101 @@ fn macro_rules_generated() {
     };
 }
 
+/*
+ * Tests `#[property(_, animatable)]` metadata.
+ */
+#[property(CONTEXT, animatable, default(0.0))]
+pub fn animatable_value(child: impl UiNode, value: impl crate::var::IntoVar<f32>) -> impl UiNode {
+    let _ = value;
+    child
+}
+
+#[test]
+pub fn property_animatable() {
+    use crate::widget::builder::property_info;
+
+    assert!(property_info!(animatable_value).animatable.is_some());
+    assert!(property_info!(foo_trace).animatable.is_none());
+}
+
+/*
+ * Tests scoped tasks.
+ */
+#[test]
+pub fn spawn_scoped_drop_on_deinit() {
+    let _app = APP.minimal().run_headless(false);
+
+    struct DropFlag(Arc<AtomicBool>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+    let dropped = Arc::new(AtomicBool::new(false));
+    let started = Arc::new(AtomicBool::new(false));
+
+    let mut wgt = EmptyWgt!();
+    WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+        WINDOW.test_init(&mut wgt);
+
+        let flag = DropFlag(dropped.clone());
+        let task_started = started.clone();
+        wgt.with_context(WidgetUpdateMode::Ignore, || {
+            WIDGET.spawn_scoped(async move {
+                let _flag = flag;
+                task_started.store(true, Ordering::Relaxed);
+                std::future::pending::<()>().await;
+            })
+        })
+        .expect("expected widget");
+
+        wait_until(|| started.load(Ordering::Relaxed));
+        assert!(!dropped.load(Ordering::Relaxed));
+
+        WINDOW.test_deinit(&mut wgt);
+    });
+
+    wait_until(|| dropped.load(Ordering::Relaxed));
+}
+
+#[test]
+pub fn spawn_scoped_panic_event() {
+    let mut app = APP.minimal().run_headless(false);
+    let panics = WIDGET_TASK_PANIC_EVENT.receiver();
+
+    let mut wgt = EmptyWgt!();
+    let wgt_id = WINDOW.with_test_context(WidgetUpdateMode::Bubble, || {
+        WINDOW.test_init(&mut wgt);
+        wgt.with_context(WidgetUpdateMode::Ignore, || {
+            WIDGET.spawn_scoped(async {
+                panic!("scoped task panic");
+            });
+            WIDGET.id()
+        })
+        .expect("expected widget")
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let args = loop {
+        let _ = app.update(false);
+        if let Ok(args) = panics.try_recv() {
+            break args;
+        }
+        assert!(Instant::now() < deadline, "expected panic event");
+        std::thread::sleep(Duration::from_millis(1));
+    };
+    assert_eq!(wgt_id, args.widget_id);
+    assert_eq!("scoped task panic", args.panic);
+}
+
+fn wait_until(mut cond: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !cond() {
+        assert!(Instant::now() < deadline, "timeout");
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
 pub mod util {
     use std::{
         cell::Cell,
@@ -16,7 +16,7 @@ use std::{
         Arc,
     },
     task::Waker,
-    time::Duration,
+    time::{Duration, Instant, SystemTime},
 };
 use zng_app_context::app_local;
 use zng_handle::{Handle, HandleOwner, WeakHandle};
@@ -24,11 +24,20 @@ use zng_time::{DInstant, INSTANT, INSTANT_APP};
 use zng_var::{types::WeakArcVar, var, ReadOnlyArcVar, Var, WeakVar};
 
 use crate::{
+    event::{event, event_args},
     handler::{AppHandler, AppHandlerArgs, AppWeakHandle},
     update::UPDATES,
     LoopTimer,
 };
 
+mod schedule;
+pub use schedule::{CronParseError, Schedule};
+
+/// Max wait before the system time is checked again while there are active schedules.
+const WALL_CLOCK_CHECK: Duration = Duration::from_secs(60);
+/// Max difference between the expected and actual system time that is not a system time change.
+const WALL_CLOCK_TOLERANCE: Duration = Duration::from_secs(2);
+
 struct DeadlineHandlerEntry {
     handle: HandleOwner<DeadlineState>,
     handler: Mutex<Box<dyn FnMut(&dyn AppWeakHandle) + Send>>, // not actually locked, just makes this Sync
@@ -41,6 +50,27 @@ struct TimerHandlerEntry {
     pending: Option<Deadline>,                                             // the last expected deadline
 }
 
+struct ScheduleHandlerEntry {
+    handle: HandleOwner<ScheduleState>,
+    handler: Mutex<Box<dyn FnMut(&ScheduleArgs, &dyn AppWeakHandle) + Send>>, // not actually locked, just makes this Sync
+    pending: Option<SystemTime>,                                              // the elapsed scheduled time
+}
+
+struct WallClock {
+    instant: Instant,
+    system: SystemTime,
+    utc_offset: i32,
+}
+impl WallClock {
+    fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            system: SystemTime::now(),
+            utc_offset: chrono::Local::now().offset().local_minus_utc(),
+        }
+    }
+}
+
 struct WaitDeadline {
     deadline: Deadline,
     wakers: Mutex<Vec<Waker>>,
@@ -75,6 +105,8 @@ pub(crate) struct TimersService {
     timers: Vec<TimerVarEntry>,
     deadline_handlers: Vec<DeadlineHandlerEntry>,
     timer_handlers: Vec<TimerHandlerEntry>,
+    schedule_handlers: Vec<ScheduleHandlerEntry>,
+    wall_clock: Option<WallClock>,
     has_pending_handlers: bool,
 }
 impl TimersService {
@@ -85,6 +117,8 @@ impl TimersService {
             timers: vec![],
             deadline_handlers: vec![],
             timer_handlers: vec![],
+            schedule_handlers: vec![],
+            wall_clock: None,
             has_pending_handlers: false,
         }
     }
@@ -156,6 +190,56 @@ impl TimersService {
         handle
     }
 
+    fn on_schedule<H>(&mut self, schedule: Schedule, mut handler: H) -> ScheduleHandle
+    where
+        H: AppHandler<ScheduleArgs>,
+    {
+        let (owner, handle) = ScheduleHandle::new(schedule);
+
+        self.schedule_handlers.push(ScheduleHandlerEntry {
+            handle: owner,
+            handler: Mutex::new(Box::new(move |args, handle| {
+                handler.event(args, &AppHandlerArgs { handle, is_preview: true });
+            })),
+            pending: None,
+        });
+        UPDATES.send_awake();
+        handle
+    }
+
+    /// Deadline that awakes the app at the system `time` or sooner to check for system time changes.
+    fn wall_deadline(time: SystemTime) -> Deadline {
+        let wait = time.duration_since(SystemTime::now()).unwrap_or_default();
+        Deadline(INSTANT.now() + wait.min(WALL_CLOCK_CHECK))
+    }
+
+    /// Notify [`SYSTEM_TIME_CHANGED_EVENT`] if the system time or time zone changed since the last check.
+    ///
+    /// Returns `true` if changed.
+    fn check_wall_clock(&mut self) -> bool {
+        if let Some(prev) = &self.wall_clock {
+            if prev.instant.elapsed() < WALL_CLOCK_TOLERANCE / 2 {
+                return false;
+            }
+        }
+
+        let now = WallClock::now();
+        let mut changed = false;
+        if let Some(prev) = &self.wall_clock {
+            let expected = prev.system + now.instant.saturating_duration_since(prev.instant);
+            let diff = match now.system.duration_since(expected) {
+                Ok(d) => d,
+                Err(e) => e.duration(),
+            };
+            if diff > WALL_CLOCK_TOLERANCE || now.utc_offset != prev.utc_offset {
+                changed = true;
+                SYSTEM_TIME_CHANGED_EVENT.notify(SystemTimeChangedArgs::now(expected, now.system, prev.utc_offset, now.utc_offset));
+            }
+        }
+        self.wall_clock = Some(now);
+        changed
+    }
+
     pub(crate) fn next_deadline(&self, timer: &mut LoopTimer) {
         for wk in &self.deadlines {
             if let Some(var) = wk.upgrade() {
@@ -197,6 +281,14 @@ impl TimersService {
                 }
             }
         }
+
+        for e in &self.schedule_handlers {
+            if !e.handle.is_dropped() {
+                if let Some(next) = *e.handle.data().next.lock() {
+                    timer.register(Self::wall_deadline(next));
+                }
+            }
+        }
     }
 
     /// if the last `apply_updates` observed elapsed timers.
@@ -293,6 +385,37 @@ impl TimersService {
 
             true // retain if stop was not called
         });
+
+        // flag `on_schedule` handlers that need to run.
+        let clock_changed = self.check_wall_clock();
+        let now_sys = SystemTime::now();
+        self.schedule_handlers.retain_mut(|e| {
+            if e.handle.is_dropped() {
+                return false; // cancel
+            }
+
+            let state = e.handle.data();
+            let mut next = state.next.lock();
+            if let Some(t) = *next {
+                if t <= now_sys {
+                    // elapsed, missed times elapse only once.
+                    state.count.fetch_add(1, Ordering::Relaxed);
+                    e.pending = Some(t);
+                    self.has_pending_handlers = true;
+                    *next = state.schedule.next_after(now_sys);
+                } else if clock_changed {
+                    // time zone can change the wall-clock time, recompute,
+                    // system time moved back does not repeat the last elapsed.
+                    let last = state.last.lock().unwrap_or(now_sys);
+                    *next = state.schedule.next_after(last.max(now_sys));
+                }
+            }
+            if let Some(t) = *next {
+                timer.register(Self::wall_deadline(t));
+            }
+
+            e.pending.is_some() || next.is_some() // retain if can still elapse, finished handlers are dropped in [`Self::notify`].
+        });
     }
 
     /// does on_* notifications.
@@ -343,6 +466,26 @@ impl TimersService {
         let mut timers = TIMERS_SV.write();
         handlers.append(&mut timers.timer_handlers);
         timers.timer_handlers = handlers;
+
+        // call `on_schedule` handlers.
+        let mut handlers = mem::take(&mut timers.schedule_handlers);
+        drop(timers);
+        handlers.retain_mut(|h| {
+            if let Some(scheduled) = h.pending.take() {
+                *h.handle.data().last.lock() = Some(scheduled);
+                let args = ScheduleArgs {
+                    timestamp: INSTANT.now(),
+                    scheduled,
+                    wk_handle: h.handle.weak_handle(),
+                };
+                (h.handler.get_mut())(&args, &h.handle.weak_handle());
+            }
+
+            !h.handle.is_dropped() && h.handle.data().next.lock().is_some() // drop if canceled or finished.
+        });
+        let mut timers = TIMERS_SV.write();
+        handlers.append(&mut timers.schedule_handlers);
+        timers.schedule_handlers = handlers;
     }
 }
 
@@ -476,6 +619,35 @@ impl TIMERS {
     {
         TIMERS_SV.write().on_interval(interval, paused, handler)
     }
+
+    /// Register a `handler` that will be called every time the wall-clock `schedule` elapses.
+    ///
+    /// Unlike deadlines and intervals that are relative to the app monotonic clock, schedules are
+    /// evaluated in the system time, the `handler` is called at the scheduled date and time even if the system time or
+    /// time zone changes while the app is running. If the system was suspended or the system time moved forward past
+    /// multiple scheduled times the `handler` is called only once.
+    ///
+    /// ```
+    /// # use zng_app::timer::*;
+    /// # use zng_app::handler::*;
+    /// # fn foo() {
+    /// let handle = TIMERS.on_schedule(Schedule::daily(9, 0), app_hn!(|args: &ScheduleArgs, _| {
+    ///     println!("good morning, scheduled for {:?}", args.scheduled);
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// # Handle
+    ///
+    /// Returns a [`ScheduleHandle`] that can be used to cancel the schedule, either by dropping the handle or by
+    /// calling [`cancel`](ScheduleHandle::cancel). You can also call [`perm`](ScheduleHandle::perm)
+    /// to drop the handle without cancelling. The handler is also dropped after the schedule elapses for the last time.
+    pub fn on_schedule<H>(&self, schedule: impl Into<Schedule>, handler: H) -> ScheduleHandle
+    where
+        H: AppHandler<ScheduleArgs>,
+    {
+        TIMERS_SV.write().on_schedule(schedule.into(), handler)
+    }
 }
 
 impl TIMERS {
@@ -1052,6 +1224,221 @@ impl TimerArgs {
     }
 }
 
+/// Represents a [`on_schedule`](TIMERS::on_schedule) handler.
+///
+/// Drop all clones of this handle to cancel the schedule, or call [`perm`](Self::perm) to drop the handle
+/// without cancelling the schedule.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+#[must_use = "the schedule is canceled if the handler is dropped"]
+pub struct ScheduleHandle(Handle<ScheduleState>);
+struct ScheduleState {
+    schedule: Schedule,
+    next: Mutex<Option<SystemTime>>,
+    last: Mutex<Option<SystemTime>>,
+    count: AtomicUsize,
+}
+impl ScheduleHandle {
+    fn new(schedule: Schedule) -> (HandleOwner<ScheduleState>, Self) {
+        let next = schedule.next_after(SystemTime::now());
+        let (owner, handle) = Handle::new(ScheduleState {
+            schedule,
+            next: Mutex::new(next),
+            last: Mutex::new(None),
+            count: AtomicUsize::new(0),
+        });
+        (owner, ScheduleHandle(handle))
+    }
+
+    /// Create a handle to nothing, the handle is always in the *canceled* state.
+    ///
+    /// Note that `Option<ScheduleHandle>` takes up the same space as `ScheduleHandle` and avoids an allocation.
+    pub fn dummy() -> ScheduleHandle {
+        ScheduleHandle(Handle::dummy(ScheduleState {
+            schedule: Schedule::at(SystemTime::UNIX_EPOCH),
+            next: Mutex::new(None),
+            last: Mutex::new(None),
+            count: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Drops the handle but does **not** drop the handler closure.
+    ///
+    /// The handler closure will be dropped after the schedule elapses for the last time, when the app exits or if it is
+    /// canceled from the inside or using another handle.
+    pub fn perm(self) {
+        self.0.perm();
+    }
+
+    /// If [`perm`](Self::perm) was called in another handle.
+    pub fn is_permanent(&self) -> bool {
+        self.0.is_permanent()
+    }
+
+    /// Drops the handle and forces the handler to drop.
+    ///
+    /// The handler will no longer be called and will drop in the next app update.
+    pub fn cancel(self) {
+        self.0.force_drop();
+    }
+
+    /// If the schedule was canceled or finished.
+    pub fn is_canceled(&self) -> bool {
+        self.0.is_dropped()
+    }
+
+    /// The schedule.
+    pub fn schedule(&self) -> Schedule {
+        self.0.data().schedule.clone()
+    }
+
+    /// The next system time the schedule elapses.
+    ///
+    /// Returns `None` if the schedule will not elapse again.
+    pub fn next(&self) -> Option<SystemTime> {
+        if self.is_canceled() {
+            None
+        } else {
+            *self.0.data().next.lock()
+        }
+    }
+
+    /// The last scheduled time that elapsed.
+    pub fn last(&self) -> Option<SystemTime> {
+        *self.0.data().last.lock()
+    }
+
+    /// Count incremented by one every time the schedule elapses.
+    pub fn count(&self) -> usize {
+        self.0.data().count.load(Ordering::Relaxed)
+    }
+
+    /// Create a weak handle.
+    pub fn downgrade(&self) -> WeakScheduleHandle {
+        WeakScheduleHandle(self.0.downgrade())
+    }
+}
+impl fmt::Debug for ScheduleHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduleHandle")
+            .field("schedule", &self.0.data().schedule)
+            .field("next", &self.next())
+            .field("count", &self.count())
+            .field("handle", &self.0)
+            .field("state", &if self.is_canceled() { "is_canceled" } else { "awaiting" })
+            .finish()
+    }
+}
+
+/// Weak [`ScheduleHandle`]
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct WeakScheduleHandle(WeakHandle<ScheduleState>);
+impl WeakScheduleHandle {
+    /// New weak handle that does not upgrade.
+    pub fn new() -> Self {
+        Self(WeakHandle::new())
+    }
+
+    /// Get the strong handle if the schedule is not canceled.
+    pub fn upgrade(&self) -> Option<ScheduleHandle> {
+        self.0.upgrade().map(ScheduleHandle)
+    }
+}
+
+/// Arguments for an [`on_schedule`](TIMERS::on_schedule) handler.
+///
+/// The field values are about the specific call to handler that received the args, the methods on the other hand
+/// are **connected** with the schedule by a weak reference and always show the up-to-date state.
+#[derive(Clone)]
+pub struct ScheduleArgs {
+    /// When the handler was called.
+    pub timestamp: DInstant,
+
+    /// Scheduled system time that elapsed, is less-or-equal to the current system time.
+    ///
+    /// If the app was suspended or the system time moved forward past multiple scheduled times this is the
+    /// first missed time.
+    pub scheduled: SystemTime,
+
+    wk_handle: WeakHandle<ScheduleState>,
+}
+impl ScheduleArgs {
+    fn handle(&self) -> Option<ScheduleHandle> {
+        self.wk_handle.upgrade().map(ScheduleHandle)
+    }
+
+    /// The next system time the schedule elapses.
+    pub fn next(&self) -> Option<SystemTime> {
+        self.handle().and_then(|h| h.next())
+    }
+
+    /// Count incremented by one every time the schedule elapses.
+    pub fn count(&self) -> usize {
+        self.handle().map(|h| h.count()).unwrap_or(0)
+    }
+
+    /// Cancel the schedule.
+    pub fn cancel(&self) {
+        if let Some(h) = self.handle() {
+            h.cancel();
+        }
+    }
+
+    /// If the schedule was canceled or finished.
+    pub fn is_canceled(&self) -> bool {
+        self.handle().is_none()
+    }
+}
+
+event_args! {
+    /// Arguments for the [`SYSTEM_TIME_CHANGED_EVENT`].
+    pub struct SystemTimeChangedArgs {
+        /// System time expected by the app monotonic clock.
+        pub expected: SystemTime,
+
+        /// Actual system time.
+        pub actual: SystemTime,
+
+        /// Local time zone offset from UTC before the change, in seconds.
+        pub prev_utc_offset: i32,
+
+        /// Local time zone offset from UTC after the change, in seconds.
+        pub utc_offset: i32,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+}
+impl SystemTimeChangedArgs {
+    /// Signed difference from the expected system time to the actual time, in seconds.
+    pub fn offset_secs(&self) -> f64 {
+        match self.actual.duration_since(self.expected) {
+            Ok(d) => d.as_secs_f64(),
+            Err(e) => -e.duration().as_secs_f64(),
+        }
+    }
+
+    /// If the local time zone or daylight saving time offset changed.
+    pub fn is_utc_offset_change(&self) -> bool {
+        self.prev_utc_offset != self.utc_offset
+    }
+}
+
+event! {
+    /// System time or local time zone changed.
+    ///
+    /// The system time is checked on app updates, at most once a second, and at least once a minute while there are active
+    /// [`on_schedule`] handlers. Note that system suspend also causes this event to notify on resume in some operating
+    /// systems, as the monotonic clock does not advance while suspended.
+    ///
+    /// [`on_schedule`]: TIMERS::on_schedule
+    pub static SYSTEM_TIME_CHANGED_EVENT: SystemTimeChangedArgs;
+}
+
 pub(crate) fn deadline_service(deadline: Deadline) -> Pin<Box<dyn std::future::Future<Output = ()> + Send + Sync>> {
     Box::pin(TIMERS.wait_deadline(deadline))
 }
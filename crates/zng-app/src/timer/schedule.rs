@@ -0,0 +1,419 @@
+use std::{fmt, str::FromStr, time::SystemTime};
+
+use chrono::{DateTime, Datelike as _, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Timelike as _, Utc};
+use zng_txt::{formatx, Txt};
+
+/// Wall-clock schedule.
+///
+/// Schedules define moments in the system time, like *daily at 9:00*, they are used by [`TIMERS.on_schedule`] to
+/// implement reminder-style features that must elapse at a specific date and time, independent of how long the app
+/// was running or suspended.
+///
+/// By default the schedule is evaluated in the local time zone, changes to the time zone and daylight saving time
+/// transitions are observed, use [`utc`] to evaluate in UTC instead.
+///
+/// # Cron
+///
+/// Schedules can be parsed from [cron] expressions with five fields `minute hour day-of-month month day-of-week` or
+/// six fields with a leading `second` field. Each field can be `*` or `?` for any value, a value, a range `a-b`,
+/// a step `*/s` or `a-b/s` and a list of these separated by commas. Months and weekdays can also be named using
+/// the first three letters in English, weekdays are `0..=7` from Sunday, with `7` also being Sunday.
+///
+/// If both the day-of-month and day-of-week fields are restricted the schedule elapses when either matches.
+///
+/// The shorthands `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`, `@midnight` and `@hourly` are also supported.
+///
+/// ```
+/// # use zng_app::timer::Schedule;
+/// let weekdays_at_9: Schedule = "0 9 * * MON-FRI".parse().unwrap();
+/// let every_15_min = Schedule::cron("*/15 * * * *").unwrap();
+/// let daily_at_9 = Schedule::daily(9, 0);
+/// ```
+///
+/// [`TIMERS.on_schedule`]: crate::timer::TIMERS::on_schedule
+/// [`utc`]: Self::utc
+/// [cron]: https://en.wikipedia.org/wiki/Cron
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Schedule {
+    kind: ScheduleKind,
+    utc: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ScheduleKind {
+    At(SystemTime),
+    Cron(Box<Cron>),
+}
+impl Schedule {
+    /// Elapses once at the system `time`.
+    pub fn at(time: SystemTime) -> Self {
+        Self {
+            kind: ScheduleKind::At(time),
+            utc: false,
+        }
+    }
+
+    /// Parse a cron expression.
+    ///
+    /// See the [type docs](Self#cron) for the supported syntax.
+    pub fn cron(expr: &str) -> Result<Self, CronParseError> {
+        Ok(Self {
+            kind: ScheduleKind::Cron(Box::new(Cron::parse(expr)?)),
+            utc: false,
+        })
+    }
+
+    /// Elapses every day at the `hour` and `minute`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hour` is not in `0..=23` or `minute` is not in `0..=59`.
+    pub fn daily(hour: u8, minute: u8) -> Self {
+        assert!(hour < 24 && minute < 60, "invalid time of day {hour}:{minute}");
+        Self::cron(&format!("{minute} {hour} * * *")).unwrap()
+    }
+
+    /// Elapses every week at the `weekday`, `hour` and `minute`.
+    ///
+    /// The `weekday` is in `0..=6` from Sunday.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weekday`, `hour` or `minute` are out of range.
+    pub fn weekly(weekday: u8, hour: u8, minute: u8) -> Self {
+        assert!(weekday < 7, "invalid weekday {weekday}");
+        assert!(hour < 24 && minute < 60, "invalid time of day {hour}:{minute}");
+        Self::cron(&format!("{minute} {hour} * * {weekday}")).unwrap()
+    }
+
+    /// Evaluate the schedule in UTC, instead of the local time zone.
+    pub fn utc(mut self) -> Self {
+        self.utc = true;
+        self
+    }
+
+    /// If the schedule is evaluated in UTC.
+    pub fn is_utc(&self) -> bool {
+        self.utc
+    }
+
+    /// Compute the first moment the schedule elapses that is after the `time`.
+    ///
+    /// Returns `None` if the schedule never elapses after `time`.
+    ///
+    /// Note that wall-clock times that do not exist because of a daylight saving time transition elapse at the
+    /// same time shifted by the transition, and wall-clock times that repeat elapse only once.
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        match &self.kind {
+            ScheduleKind::At(t) => {
+                if *t > time {
+                    Some(*t)
+                } else {
+                    None
+                }
+            }
+            ScheduleKind::Cron(c) => {
+                if self.utc {
+                    c.next_after(&Utc, time)
+                } else {
+                    c.next_after(&Local, time)
+                }
+            }
+        }
+    }
+}
+impl fmt::Debug for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut t = f.debug_tuple("Schedule");
+        match &self.kind {
+            ScheduleKind::At(time) => t.field(time),
+            ScheduleKind::Cron(c) => t.field(&c.expr),
+        };
+        if self.utc {
+            t.field(&"UTC");
+        }
+        t.finish()
+    }
+}
+impl FromStr for Schedule {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::cron(s)
+    }
+}
+impl From<SystemTime> for Schedule {
+    fn from(time: SystemTime) -> Self {
+        Self::at(time)
+    }
+}
+
+/// Error parsing a cron expression.
+///
+/// See [`Schedule::cron`] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError {
+    /// Expression that failed to parse.
+    pub expr: Txt,
+    /// Error message.
+    pub error: Txt,
+}
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression {:?}, {}", self.expr, self.error)
+    }
+}
+impl std::error::Error for CronParseError {}
+
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Max days searched for the next match, covers leap days and all weekday alignments.
+const MAX_SEARCH_DAYS: usize = 366 * 8;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Cron {
+    expr: Txt,
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+impl Cron {
+    fn parse(expr: &str) -> Result<Self, CronParseError> {
+        let err = |error: Txt| CronParseError {
+            expr: Txt::from_str(expr),
+            error,
+        };
+
+        let expanded = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            e if e.starts_with('@') => return Err(err(formatx!("unknown shorthand `{e}`"))),
+            e => e,
+        };
+
+        let fields: Vec<_> = expanded.split_whitespace().collect();
+        let (seconds, fields) = match fields.len() {
+            5 => (1, &fields[..]),
+            6 => (
+                parse_field(fields[0], 0, 59, &[]).map_err(|e| err(formatx!("second {e}")))?,
+                &fields[1..],
+            ),
+            n => return Err(err(formatx!("expected 5 or 6 fields, found {n}"))),
+        };
+
+        let mut weekdays = parse_field(fields[4], 0, 7, &WEEKDAYS).map_err(|e| err(formatx!("day-of-week {e}")))?;
+        if weekdays & (1 << 7) != 0 {
+            // 7 is also Sunday
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expr: Txt::from_str(expr),
+            seconds,
+            minutes: parse_field(fields[0], 0, 59, &[]).map_err(|e| err(formatx!("minute {e}")))?,
+            hours: parse_field(fields[1], 0, 23, &[]).map_err(|e| err(formatx!("hour {e}")))?,
+            days: parse_field(fields[2], 1, 31, &[]).map_err(|e| err(formatx!("day-of-month {e}")))?,
+            months: parse_field(fields[3], 1, 12, &MONTHS).map_err(|e| err(formatx!("month {e}")))?,
+            weekdays,
+            any_day: matches!(fields[2], "*" | "?"),
+            any_weekday: matches!(fields[4], "*" | "?"),
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.any_day || self.any_weekday {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+
+    /// First matching time in a day that is at or after `min`.
+    fn next_time_in_day(&self, min: NaiveTime) -> Option<NaiveTime> {
+        for h in min.hour()..24 {
+            if !has(self.hours, h) {
+                continue;
+            }
+            let m_start = if h == min.hour() { min.minute() } else { 0 };
+            for m in m_start..60 {
+                if !has(self.minutes, m) {
+                    continue;
+                }
+                let s_start = if h == min.hour() && m == min.minute() { min.second() } else { 0 };
+                for s in s_start..60 {
+                    if has(self.seconds, s) {
+                        return NaiveTime::from_hms_opt(h, m, s);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn next_after<Tz: TimeZone>(&self, tz: &Tz, time: SystemTime) -> Option<SystemTime> {
+        let start = DateTime::<Utc>::from(time).with_timezone(tz).naive_local();
+        let start = start.with_nanosecond(0)? + TimeDelta::seconds(1);
+
+        let mut date = start.date();
+        let mut min_time = start.time();
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                while let Some(t) = self.next_time_in_day(min_time) {
+                    if let Some(r) = resolve(tz, date.and_time(t)) {
+                        if r > time {
+                            return Some(r);
+                        }
+                    }
+                    if t.num_seconds_from_midnight() == 24 * 60 * 60 - 1 {
+                        break;
+                    }
+                    min_time = t + TimeDelta::seconds(1);
+                }
+            }
+            date = date.succ_opt()?;
+            min_time = NaiveTime::MIN;
+        }
+        None
+    }
+}
+
+/// Convert wall-clock time to system time.
+fn resolve<Tz: TimeZone>(tz: &Tz, time: NaiveDateTime) -> Option<SystemTime> {
+    match tz.from_local_datetime(&time) {
+        LocalResult::Single(t) => Some(t.into()),
+        // repeated wall-clock time, elapses in the first
+        LocalResult::Ambiguous(t, _) => Some(t.into()),
+        // skipped wall-clock time, elapses shifted by the transition
+        LocalResult::None => tz.from_local_datetime(&(time + TimeDelta::hours(1))).earliest().map(Into::into),
+    }
+}
+
+fn has(bits: u64, i: u32) -> bool {
+    bits & (1 << i) != 0
+}
+
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, Txt> {
+    let value = |s: &str| -> Result<u32, Txt> {
+        let v = match s.parse::<u32>() {
+            Ok(v) => v,
+            Err(_) => match names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+                Some(i) => i as u32 + min,
+                None => return Err(formatx!("invalid value `{s}`")),
+            },
+        };
+        if v < min || v > max {
+            return Err(formatx!("value `{v}` not in range {min}..={max}"));
+        }
+        Ok(v)
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => match s.parse::<u32>() {
+                Ok(s) if s > 0 => (r, Some(s)),
+                _ => return Err(formatx!("invalid step `{s}`")),
+            },
+            None => (part, None),
+        };
+        let (start, end) = if range == "*" || range == "?" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            let (a, b) = (value(a)?, value(b)?);
+            if a > b {
+                return Err(formatx!("invalid range `{range}`"));
+            }
+            (a, b)
+        } else {
+            let a = value(range)?;
+            (a, if step.is_some() { max } else { a })
+        };
+        for v in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << v;
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> SystemTime {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap().into()
+    }
+
+    #[test]
+    fn daily_next() {
+        let s = Schedule::daily(9, 0).utc();
+        assert_eq!(Some(utc(2024, 5, 10, 9, 0, 0)), s.next_after(utc(2024, 5, 10, 8, 30, 0)));
+        assert_eq!(Some(utc(2024, 5, 11, 9, 0, 0)), s.next_after(utc(2024, 5, 10, 9, 0, 0)));
+    }
+
+    #[test]
+    fn cron_weekdays() {
+        let s = Schedule::cron("30 9 * * MON-FRI").unwrap().utc();
+        // 2024-05-10 is a Friday
+        assert_eq!(Some(utc(2024, 5, 13, 9, 30, 0)), s.next_after(utc(2024, 5, 10, 10, 0, 0)));
+    }
+
+    #[test]
+    fn cron_seconds_step() {
+        let s = Schedule::cron("*/20 * * * * *").unwrap().utc();
+        assert_eq!(Some(utc(2024, 5, 10, 8, 0, 40)), s.next_after(utc(2024, 5, 10, 8, 0, 21)));
+        assert_eq!(Some(utc(2024, 5, 10, 8, 1, 0)), s.next_after(utc(2024, 5, 10, 8, 0, 40)));
+    }
+
+    #[test]
+    fn cron_leap_day() {
+        let s = Schedule::cron("0 0 29 FEB *").unwrap().utc();
+        assert_eq!(Some(utc(2028, 2, 29, 0, 0, 0)), s.next_after(utc(2024, 3, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn cron_day_or_weekday() {
+        // 1st of month or Sundays
+        let s = Schedule::cron("0 0 1 * SUN").unwrap().utc();
+        // 2024-05-10 is a Friday, next Sunday is 12
+        assert_eq!(Some(utc(2024, 5, 12, 0, 0, 0)), s.next_after(utc(2024, 5, 10, 0, 0, 0)));
+        assert_eq!(Some(utc(2024, 6, 1, 0, 0, 0)), s.next_after(utc(2024, 5, 31, 0, 0, 0)));
+    }
+
+    #[test]
+    fn cron_never() {
+        let s = Schedule::cron("0 0 31 FEB *").unwrap().utc();
+        assert_eq!(None, s.next_after(utc(2024, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn cron_errors() {
+        assert!(Schedule::cron("* * * *").is_err());
+        assert!(Schedule::cron("60 * * * *").is_err());
+        assert!(Schedule::cron("* * * FOO *").is_err());
+        assert!(Schedule::cron("*/0 * * * *").is_err());
+        assert!(Schedule::cron("@often").is_err());
+    }
+
+    #[test]
+    fn at_once() {
+        let t = utc(2024, 5, 10, 9, 0, 0);
+        let s = Schedule::at(t);
+        assert_eq!(Some(t), s.next_after(t - Duration::from_secs(1)));
+        assert_eq!(None, s.next_after(t));
+    }
+}
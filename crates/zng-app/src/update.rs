@@ -2,16 +2,20 @@
 
 use std::{
     collections::{hash_map, HashMap},
-    fmt, mem,
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
     sync::{atomic::AtomicBool, Arc},
-    task::Waker,
+    task::{Poll, Waker},
+    time::Instant,
 };
 
 use parking_lot::Mutex;
 use zng_app_context::app_local;
 use zng_handle::{Handle, HandleOwner, WeakHandle};
 use zng_unique_id::IdSet;
-use zng_var::VARS_APP;
+use zng_var::{Var as _, VARS, VARS_APP};
 
 use crate::{
     event::{AnyEvent, AnyEventArgs, AppDisconnected, EVENTS, EVENTS_SV},
@@ -1198,6 +1202,105 @@ impl UPDATES {
         self
     }
 
+    /// Schedules a low priority update that affects the `target`.
+    ///
+    /// Background updates are only applied when the app has no other pending updates, input events or layout and render
+    /// requests and the current update cycle has not exceeded the [`VARS.frame_duration`], so heavy incremental work
+    /// does not starve input responsiveness. When applied the background requests become normal updates in the next cycle.
+    ///
+    /// Widgets that do incremental work can use [`should_yield`] to check if they should stop and request another background
+    /// update to continue the work.
+    ///
+    /// [`VARS.frame_duration`]: zng_var::VARS::frame_duration
+    /// [`should_yield`]: Self::should_yield
+    pub fn update_background(&self, target: impl Into<Option<WidgetId>>) -> &Self {
+        let mut u = UPDATES_SV.write();
+        let target = target.into();
+        if !u.background.contains(&target) {
+            u.background.push(target);
+        }
+        u.send_awake();
+        self
+    }
+
+    /// Awaits until a background update cycle.
+    ///
+    /// This can be used to yield incremental work in async tasks that run in the app thread, the returned future
+    /// awakes together with the [`update_background`] requests.
+    ///
+    /// [`update_background`]: Self::update_background
+    pub fn yield_background(&self) -> impl Future<Output = ()> + Send + 'static {
+        struct YieldBackground(Option<usize>);
+        impl Future for YieldBackground {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<()> {
+                let mut u = UPDATES_SV.write();
+                match self.0 {
+                    // app tasks are polled on every update, only ready after a background cycle
+                    Some(c) if c != u.background_cycle => Poll::Ready(()),
+                    _ => {
+                        self.0 = Some(u.background_cycle);
+                        if !u.background_wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                            u.background_wakers.push(cx.waker().clone());
+                        }
+                        u.send_awake();
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+        YieldBackground(None)
+    }
+
+    /// If incremental work in the current update should yield to allow the app to process input and render.
+    ///
+    /// Returns `true` if the current update cycle has taken longer than the [`VARS.frame_duration`] or if
+    /// there are pending normal priority updates.
+    ///
+    /// [`VARS.frame_duration`]: zng_var::VARS::frame_duration
+    pub fn should_yield(&self) -> bool {
+        let u = UPDATES_SV.read();
+        if u.update_ext
+            .intersects(UpdateFlags::UPDATE | UpdateFlags::INFO | UpdateFlags::LAYOUT | UpdateFlags::RENDER)
+        {
+            return true;
+        }
+        match u.cycle_start {
+            Some(t) => t.elapsed() >= VARS.frame_duration().get(),
+            None => false,
+        }
+    }
+
+    /// Apply background updates if the cycle budget allows.
+    ///
+    /// Returns `true` if there are still pending background updates.
+    pub(crate) fn apply_background(&self) -> bool {
+        let mut u = UPDATES_SV.write();
+        if u.background.is_empty() && u.background_wakers.is_empty() {
+            return false;
+        }
+        if let Some(t) = u.cycle_start {
+            if t.elapsed() >= VARS.frame_duration().get() {
+                // yield, next cycle without other updates will apply
+                return true;
+            }
+        }
+
+        let background = mem::take(&mut u.background);
+        let wakers = mem::take(&mut u.background_wakers);
+        u.background_cycle = u.background_cycle.wrapping_add(1);
+        drop(u);
+
+        for target in background {
+            self.update_internal(target);
+        }
+        for w in wakers {
+            w.wake();
+        }
+        false
+    }
+
     pub(crate) fn send_awake(&self) {
         UPDATES_SV.write().send_awake();
     }
@@ -1516,6 +1619,11 @@ struct UpdatesService {
     pre_handlers: Mutex<Vec<UpdateHandler>>,
     pos_handlers: Mutex<Vec<UpdateHandler>>,
 
+    background: Vec<Option<WidgetId>>,
+    background_wakers: Vec<Waker>,
+    background_cycle: usize,
+    cycle_start: Option<Instant>,
+
     app_is_awake: bool,
     awake_pending: bool,
 }
@@ -1533,6 +1641,11 @@ impl UpdatesService {
             pre_handlers: Mutex::new(vec![]),
             pos_handlers: Mutex::new(vec![]),
 
+            background: vec![],
+            background_wakers: vec![],
+            background_cycle: 0,
+            cycle_start: None,
+
             app_is_awake: false,
             awake_pending: false,
         }
@@ -1557,6 +1670,7 @@ impl UpdatesService {
     fn app_awake(&mut self, wake: bool) {
         self.awake_pending = false;
         self.app_is_awake = wake;
+        self.cycle_start = if wake { Some(Instant::now()) } else { None };
     }
 }
 
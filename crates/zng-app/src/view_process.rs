@@ -5,6 +5,7 @@ use std::{
     fmt,
     path::PathBuf,
     sync::{self, Arc},
+    time::Duration,
 };
 
 pub mod raw_device_events;
@@ -26,12 +27,17 @@ use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionName, ApiExtensionPayload, ApiExtensionRecvError, ApiExtensions},
     config::{AnimationsConfig, ColorsConfig, FontAntiAliasing, LocaleConfig, MultiClickConfig, TouchConfig},
     dialog::{FileDialog, FileDialogResponse, MsgDialog, MsgDialogResponse},
+    drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
     font::FontOptions,
-    image::{ImageMaskMode, ImagePpi, ImageRequest, ImageTextureId},
+    image::{ColorSpace, ImageEncodeOptions, ImageMaskMode, ImagePpi, ImageRequest, ImageTextureId},
     ipc::{IpcBytes, IpcBytesReceiver},
+    keyboard::{GlobalShortcut, GlobalShortcutError, GlobalShortcutId, Key, KeyCode},
+    notification::{NotificationError, NotificationId, NotificationRequest},
+    sound::{SoundData, SoundError},
+    tray::{TrayIconError, TrayIconId, TrayIconRequest},
     window::{
-        CursorIcon, FocusIndicator, FrameRequest, FrameUpdateRequest, HeadlessOpenData, HeadlessRequest, MonitorInfo, RenderMode,
-        ResizeDirection, VideoMode, WindowButton, WindowRequest, WindowStateAll,
+        CursorIcon, FocusIndicator, FrameRequest, FrameUpdateRequest, HeadlessOpenData, HeadlessRequest, HitTestKind, MonitorInfo,
+        RenderMode, RendererStats, RendererTrim, ResizeDirection, VideoMode, WindowButton, WindowLayer, WindowRequest, WindowStateAll,
     },
     Event, ViewProcessGen, ViewProcessOffline,
 };
@@ -55,6 +61,7 @@ struct ViewProcessService {
     process: zng_view_api::Controller,
     device_ids: HashMap<ApiDeviceId, DeviceId>,
     monitor_ids: HashMap<ApiMonitorId, MonitorId>,
+    monitor_names: Vec<(MonitorId, Txt)>,
 
     data_generation: ViewProcessGen,
 
@@ -176,6 +183,9 @@ impl VIEW_PROCESS {
             partial_pixels: None,
             pixels: None,
             is_mask: false,
+            color_space: ColorSpace::Unknown,
+            frame_delays: vec![],
+            is_vector: false,
             done_signal: SignalOnce::new(),
         })));
         app.loading_images.push(Arc::downgrade(&img.0));
@@ -206,6 +216,83 @@ impl VIEW_PROCESS {
             partial_pixels: None,
             pixels: None,
             is_mask: false,
+            color_space: ColorSpace::Unknown,
+            frame_delays: vec![],
+            is_vector: false,
+            done_signal: SignalOnce::new(),
+        })));
+        app.loading_images.push(Arc::downgrade(&img.0));
+        Ok(img)
+    }
+
+    /// Request decoding of an animation frame.
+    ///
+    /// The `image` must be loaded and [`is_animated`], the `index` is the frame index. This function returns immediately,
+    /// the returned [`ViewImage`] will update when [`Event::ImageLoaded`] or [`Event::ImageLoadError`] events are received.
+    ///
+    /// Returns an error image if the `image` is not loaded in the current view-process instance.
+    ///
+    /// [`is_animated`]: ViewImage::is_animated
+    /// [`Event::ImageLoaded`]: zng_view_api::Event::ImageLoaded
+    /// [`Event::ImageLoadError`]: zng_view_api::Event::ImageLoadError
+    pub fn image_frame(&self, image: &ViewImage, index: usize) -> Result<ViewImage> {
+        let mut app = self.write();
+        let image_id = match image.id() {
+            Some(id) if image.generation() == app.process.generation() => id,
+            _ => return Ok(ViewImage::dummy(Some(Txt::from_static("image not loaded in the view-process")))),
+        };
+        let id = app.process.image_frame(image_id, index)?;
+        let img = ViewImage(Arc::new(RwLock::new(ViewImageData {
+            id: Some(id),
+            app_id: APP.id(),
+            generation: app.process.generation(),
+            size: PxSize::zero(),
+            partial_size: PxSize::zero(),
+            ppi: None,
+            is_opaque: false,
+            partial_pixels: None,
+            pixels: None,
+            is_mask: false,
+            color_space: ColorSpace::Unknown,
+            frame_delays: vec![],
+            is_vector: false,
+            done_signal: SignalOnce::new(),
+        })));
+        app.loading_images.push(Arc::downgrade(&img.0));
+        Ok(img)
+    }
+
+    /// Request rasterization of a vector image at a new size.
+    ///
+    /// The `image` must be loaded and [`is_vector`], the `size` is the raster size in pixels. This function returns immediately,
+    /// the returned [`ViewImage`] will update when [`Event::ImageLoaded`] or [`Event::ImageLoadError`] events are received.
+    ///
+    /// Returns an error image if the `image` is not loaded in the current view-process instance.
+    ///
+    /// [`is_vector`]: ViewImage::is_vector
+    /// [`Event::ImageLoaded`]: zng_view_api::Event::ImageLoaded
+    /// [`Event::ImageLoadError`]: zng_view_api::Event::ImageLoadError
+    pub fn image_raster(&self, image: &ViewImage, size: PxSize) -> Result<ViewImage> {
+        let mut app = self.write();
+        let image_id = match image.id() {
+            Some(id) if image.generation() == app.process.generation() => id,
+            _ => return Ok(ViewImage::dummy(Some(Txt::from_static("image not loaded in the view-process")))),
+        };
+        let id = app.process.image_raster(image_id, size)?;
+        let img = ViewImage(Arc::new(RwLock::new(ViewImageData {
+            id: Some(id),
+            app_id: APP.id(),
+            generation: app.process.generation(),
+            size: PxSize::zero(),
+            partial_size: PxSize::zero(),
+            ppi: None,
+            is_opaque: false,
+            partial_pixels: None,
+            pixels: None,
+            is_mask: false,
+            color_space: ColorSpace::Unknown,
+            frame_delays: vec![],
+            is_vector: false,
             done_signal: SignalOnce::new(),
         })));
         app.loading_images.push(Arc::downgrade(&img.0));
@@ -221,6 +308,70 @@ impl VIEW_PROCESS {
         }
     }
 
+    /// Start playing the `sound`.
+    ///
+    /// The sound plays in the background, only sound start errors are returned.
+    pub fn play_sound(&self, sound: SoundData) -> Result<std::result::Result<(), SoundError>> {
+        self.try_write()?.process.play_sound(sound)
+    }
+
+    /// Register a key combination that generates [`RAW_GLOBAL_SHORTCUT_EVENT`] even when the app is not focused.
+    ///
+    /// If the `id` is already registered it is replaced.
+    ///
+    /// [`RAW_GLOBAL_SHORTCUT_EVENT`]: crate::view_process::raw_events::RAW_GLOBAL_SHORTCUT_EVENT
+    pub fn register_global_shortcut(
+        &self,
+        id: GlobalShortcutId,
+        shortcut: GlobalShortcut,
+    ) -> Result<std::result::Result<(), GlobalShortcutError>> {
+        self.try_write()?.process.register_global_shortcut(id, shortcut)
+    }
+
+    /// Unregister a global shortcut.
+    pub fn unregister_global_shortcut(&self, id: GlobalShortcutId) -> Result<()> {
+        self.try_write()?.process.unregister_global_shortcut(id)
+    }
+
+    /// Gets the label of each physical key in the current keyboard layout.
+    ///
+    /// Returns a [`Key`] for each entry of `codes`, keys that cannot be mapped or are not affected by the
+    /// layout are [`Key::Unidentified`]. The labels change after [`RAW_KEYBOARD_LAYOUT_CHANGED_EVENT`].
+    ///
+    /// The default view-process implements key labels on Windows and Linux, on macOS and other systems all
+    /// keys are [`Key::Unidentified`].
+    ///
+    /// [`RAW_KEYBOARD_LAYOUT_CHANGED_EVENT`]: crate::view_process::raw_events::RAW_KEYBOARD_LAYOUT_CHANGED_EVENT
+    pub fn key_labels(&self, codes: Vec<KeyCode>) -> Result<Vec<Key>> {
+        self.try_write()?.process.key_labels(codes)
+    }
+
+    /// Create or update a tray icon.
+    ///
+    /// The `request.icon` must be an image loaded in the current view-process.
+    pub fn set_tray_icon(&self, request: TrayIconRequest) -> Result<std::result::Result<(), TrayIconError>> {
+        self.try_write()?.process.set_tray_icon(request)
+    }
+
+    /// Remove a tray icon.
+    pub fn close_tray_icon(&self, id: TrayIconId) -> Result<()> {
+        self.try_write()?.process.close_tray_icon(id)
+    }
+
+    /// Show or replace a native notification.
+    ///
+    /// The user response is notified using the [`RAW_NOTIFICATION_RESPONDED_EVENT`].
+    ///
+    /// [`RAW_NOTIFICATION_RESPONDED_EVENT`]: crate::view_process::raw_events::RAW_NOTIFICATION_RESPONDED_EVENT
+    pub fn show_notification(&self, request: NotificationRequest) -> Result<std::result::Result<(), NotificationError>> {
+        self.try_write()?.process.show_notification(request)
+    }
+
+    /// Remove a native notification.
+    pub fn close_notification(&self, id: NotificationId) -> Result<()> {
+        self.try_write()?.process.close_notification(id)
+    }
+
     /// Returns a list of image decoders supported by the view-process backend.
     ///
     /// Each string is the lower-case file extension.
@@ -315,6 +466,7 @@ impl VIEW_PROCESS {
             process,
             device_ids: HashMap::default(),
             monitor_ids: HashMap::default(),
+            monitor_names: vec![],
             loading_images: vec![],
             encoding_images: vec![],
             frame_images: vec![],
@@ -350,6 +502,37 @@ impl VIEW_PROCESS {
         *self.write().monitor_ids.entry(id).or_insert_with(MonitorId::new_unique)
     }
 
+    /// Translate `MonId` to `MonitorId`, reuses the ID of a disconnected monitor with the same `name` if it was unknown.
+    ///
+    /// This keeps the monitor ID stable when the view-process respawns.
+    pub(super) fn monitor_id_named(&self, id: ApiMonitorId, name: &Txt) -> MonitorId {
+        let mut app = self.write();
+        let app = &mut *app;
+        if let Some(m_id) = app.monitor_ids.get(&id) {
+            if !name.is_empty() && !app.monitor_names.iter().any(|(i, _)| i == m_id) {
+                app.monitor_names.push((*m_id, name.clone()));
+            }
+            return *m_id;
+        }
+
+        let monitor_ids = &app.monitor_ids;
+        let reuse = if name.is_empty() {
+            None
+        } else {
+            app.monitor_names
+                .iter()
+                .find(|(m_id, n)| n == name && !monitor_ids.values().any(|i| i == m_id))
+                .map(|(m_id, _)| *m_id)
+        };
+        let m_id = reuse.unwrap_or_else(|| {
+            let m_id = MonitorId::new_unique();
+            app.monitor_names.push((m_id, name.clone()));
+            m_id
+        });
+        app.monitor_ids.insert(id, m_id);
+        m_id
+    }
+
     /// Handle an [`Event::Inited`].
     ///
     /// The view-process becomes online only after this call.
@@ -439,6 +622,9 @@ impl VIEW_PROCESS {
                 img.pixels = Some(Ok(data.pixels));
                 img.partial_pixels = None;
                 img.is_mask = data.is_mask;
+                img.color_space = data.color_space;
+                img.frame_delays = data.frame_delays;
+                img.is_vector = data.is_vector;
                 img.done_signal.set();
             }
             Some(ViewImage(img))
@@ -478,6 +664,9 @@ impl VIEW_PROCESS {
             partial_pixels: None,
             pixels: Some(Ok(data.pixels)),
             is_mask: data.is_mask,
+            color_space: data.color_space,
+            frame_delays: data.frame_delays,
+            is_vector: data.is_vector,
             done_signal: SignalOnce::new_set(),
         })))
     }
@@ -493,16 +682,22 @@ impl VIEW_PROCESS {
         i.map(|i| ViewImage(app.frame_images.swap_remove(i).upgrade().unwrap()))
     }
 
-    pub(super) fn on_image_encoded(&self, id: ImageId, format: Txt, data: IpcBytes) {
-        self.on_image_encode_result(id, format, Ok(data));
+    pub(super) fn on_image_encoded(&self, id: ImageId, format: Txt, options: ImageEncodeOptions, data: IpcBytes) {
+        self.on_image_encode_result(id, format, options, Ok(data));
     }
-    pub(super) fn on_image_encode_error(&self, id: ImageId, format: Txt, error: Txt) {
-        self.on_image_encode_result(id, format, Err(EncodeError::Encode(error)));
+    pub(super) fn on_image_encode_error(&self, id: ImageId, format: Txt, options: ImageEncodeOptions, error: Txt) {
+        self.on_image_encode_result(id, format, options, Err(EncodeError::Encode(error)));
     }
-    fn on_image_encode_result(&self, id: ImageId, format: Txt, result: std::result::Result<IpcBytes, EncodeError>) {
+    fn on_image_encode_result(
+        &self,
+        id: ImageId,
+        format: Txt,
+        options: ImageEncodeOptions,
+        result: std::result::Result<IpcBytes, EncodeError>,
+    ) {
         let mut app = self.write();
         app.encoding_images.retain(move |r| {
-            let done = r.image_id == id && r.format == format;
+            let done = r.image_id == id && r.format == format && r.options == options;
             if done {
                 for sender in &r.listeners {
                     let _ = sender.send(result.clone());
@@ -531,6 +726,7 @@ impl VIEW_PROCESS {
     pub(super) fn on_respawned(&self, _gen: ViewProcessGen) {
         let mut app = self.write();
         app.pending_frames = 0;
+        app.monitor_ids.clear();
         for (_, r) in app.message_dialogs.drain(..) {
             r.respond(MsgDialogResponse::Error(Txt::from_static("respawn")));
         }
@@ -548,7 +744,6 @@ impl ViewProcessService {
         if invalid {
             self.data_generation = gen;
             self.device_ids.clear();
-            self.monitor_ids.clear();
         }
         invalid
     }
@@ -628,6 +823,9 @@ pub struct WindowOpenData {
 
     /// Actual render mode, can be different from the requested mode if it is not available.
     pub render_mode: RenderMode,
+
+    /// Actual output color space, can be different from the requested color space if it is not available.
+    pub color_space: ColorSpace,
 }
 impl WindowOpenData {
     pub(crate) fn new(data: zng_view_api::window::WindowOpenData, map_monitor: impl FnOnce(ApiMonitorId) -> MonitorId) -> Self {
@@ -638,6 +836,7 @@ impl WindowOpenData {
             size: data.size,
             scale_factor: data.scale_factor,
             render_mode: data.render_mode,
+            color_space: data.color_space,
         }
     }
 }
@@ -671,9 +870,9 @@ impl ViewWindow {
         self.0.call(|id, p| p.set_visible(id, visible))
     }
 
-    /// Set if the window is "top-most".
-    pub fn set_always_on_top(&self, always_on_top: bool) -> Result<()> {
-        self.0.call(|id, p| p.set_always_on_top(id, always_on_top))
+    /// Set the window z-order layer.
+    pub fn set_layer(&self, layer: WindowLayer) -> Result<()> {
+        self.0.call(|id, p| p.set_layer(id, layer))
     }
 
     /// Set if the user can drag-move the window.
@@ -798,6 +997,59 @@ impl ViewWindow {
         self.0.call(|id, p| p.open_title_bar_context_menu(id, position))
     }
 
+    /// Set custom entries in the window system menu.
+    ///
+    /// The [`RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT`] notifies when a custom item is clicked.
+    ///
+    /// [`RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT`]: crate::view_process::raw_events::RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT
+    pub fn set_system_menu(&self, menu: zng_view_api::window::SystemMenu) -> Result<()> {
+        self.0.call(|id, p| p.set_system_menu(id, menu))
+    }
+
+    /// Export the application menu bar to the operating system global menu.
+    ///
+    /// The [`RAW_APP_MENU_ITEM_CLICKED_EVENT`] notifies when an item is clicked. Returns [`AppMenuError::NotSupported`]
+    /// if the platform has no global menu.
+    ///
+    /// [`RAW_APP_MENU_ITEM_CLICKED_EVENT`]: crate::view_process::raw_events::RAW_APP_MENU_ITEM_CLICKED_EVENT
+    /// [`AppMenuError::NotSupported`]: zng_view_api::window::AppMenuError::NotSupported
+    pub fn set_app_menu(&self, menu: zng_view_api::window::AppMenu) -> Result<std::result::Result<(), zng_view_api::window::AppMenuError>> {
+        self.0.call(|id, p| p.set_app_menu(id, menu))
+    }
+
+    /// Group the `tabs` windows as native tabs of this window.
+    ///
+    /// If `tabs` is empty removes the window from its tab group. Returns `false` if native window tabbing is not supported.
+    pub fn set_tabs(&self, tabs: &[ViewWindow]) -> Result<bool> {
+        let tabs = tabs.iter().map(|t| t.0.id).collect();
+        self.0.call(|id, p| p.set_window_tabs(id, tabs))
+    }
+
+    /// Enable or disable continuous render mode.
+    ///
+    /// In continuous render mode the window redraws every display refresh while visible and
+    /// the [`RAW_REDRAW_TICK_EVENT`] notifies after each redraw.
+    ///
+    /// [`RAW_REDRAW_TICK_EVENT`]: crate::view_process::raw_events::RAW_REDRAW_TICK_EVENT
+    pub fn set_continuous_render(&self, enabled: bool) -> Result<()> {
+        self.0.call(|id, p| p.set_continuous_render(id, enabled))
+    }
+
+    /// Enable or disable frame presented notifications.
+    ///
+    /// When enabled the [`RAW_FRAME_PRESENTED_EVENT`] notifies after every present of the window, with the
+    /// presentation timestamp and an estimate of the display refresh interval.
+    ///
+    /// [`RAW_FRAME_PRESENTED_EVENT`]: crate::view_process::raw_events::RAW_FRAME_PRESENTED_EVENT
+    pub fn set_present_notify(&self, enabled: bool) -> Result<()> {
+        self.0.call(|id, p| p.set_present_notify(id, enabled))
+    }
+
+    /// Set the custom chrome regions, the regions behave like the native title bar and borders.
+    pub fn set_hit_test_regions(&self, regions: Vec<(PxRect, HitTestKind)>) -> Result<()> {
+        self.0.call(|id, p| p.set_hit_test_regions(id, regions))
+    }
+
     /// Shows a native message dialog for the window.
     ///
     /// The window is not interactive while the dialog is visible and the dialog may be modal in the view-process.
@@ -823,6 +1075,47 @@ impl ViewWindow {
         self.0.call(|id, p| p.access_update(id, update))
     }
 
+    /// Announce the text using the screen reader, if the window accessibility is active.
+    pub fn access_announce(&self, txt: Txt, priority: zng_view_api::access::AccessLivePriority) -> Result<()> {
+        self.0.call(|id, p| p.access_announce(id, txt, priority))
+    }
+
+    /// Start a drag and drop operation, if the window is pressed.
+    ///
+    /// The `image` is displayed under the cursor during the drag, if not set the view-process or operating
+    /// system may use a default image.
+    ///
+    /// A [`RAW_APP_DRAG_ENDED_EVENT`] will be received when the operation finishes.
+    ///
+    /// [`RAW_APP_DRAG_ENDED_EVENT`]: crate::view_process::raw_events::RAW_APP_DRAG_ENDED_EVENT
+    pub fn start_drag_drop(
+        &self,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<&ViewImage>,
+    ) -> Result<std::result::Result<DragDropId, DragDropError>> {
+        self.0.call(|id, p| {
+            let image = match image {
+                Some(img) => {
+                    let img = img.0.read();
+                    if p.generation() != img.generation {
+                        return Err(ViewProcessOffline);
+                    }
+                    img.id
+                }
+                None => None,
+            };
+            p.start_drag_drop(id, data, allowed_effects, image)
+        })
+    }
+
+    /// Cancel a drag and drop operation started by [`start_drag_drop`].
+    ///
+    /// [`start_drag_drop`]: Self::start_drag_drop
+    pub fn cancel_drag_drop(&self, drag_id: DragDropId) -> Result<()> {
+        self.0.call(|id, p| p.cancel_drag_drop(id, drag_id))
+    }
+
     /// Enable or disable IME by setting a cursor area.
     pub fn set_ime_area(&self, area: Option<DipRect>) -> Result<()> {
         self.0.call(|id, p| p.set_ime_area(id, area))
@@ -1108,6 +1401,9 @@ impl ViewRenderer {
                 partial_pixels: None,
                 pixels: None,
                 is_mask: false,
+                color_space: ColorSpace::Unknown,
+                frame_delays: vec![],
+                is_vector: false,
                 done_signal: SignalOnce::new(),
             })));
 
@@ -1131,6 +1427,23 @@ impl ViewRenderer {
         }
     }
 
+    /// Render multiple new frames in sequence.
+    ///
+    /// On headless surfaces the conversion of captured frames overlaps with the render of the next frames, this
+    /// is the fastest way to capture a sequence of frames.
+    pub fn render_batch(&self, frames: Vec<FrameRequest>) -> Result<()> {
+        let _s = tracing::debug_span!("ViewRenderer.render_batch").entered();
+
+        if let Some(w) = self.0.upgrade() {
+            let len = frames.len();
+            w.call(|id, p| p.render_batch(id, frames))?;
+            VIEW_PROCESS.handle_write(w.app_id).pending_frames += len;
+            Ok(())
+        } else {
+            Err(ViewProcessOffline)
+        }
+    }
+
     /// Update the current frame and re-render it.
     pub fn render_update(&self, frame: FrameUpdateRequest) -> Result<()> {
         let _s = tracing::debug_span!("ViewRenderer.render_update").entered();
@@ -1144,6 +1457,20 @@ impl ViewRenderer {
         }
     }
 
+    /// Gets the renderer memory statistics.
+    pub fn stats(&self) -> Result<RendererStats> {
+        self.call(|id, p| p.renderer_stats(id))
+    }
+
+    /// Clear renderer caches to free memory.
+    ///
+    /// Note that the view-process already fully trims renderers on [`LOW_MEMORY_EVENT`].
+    ///
+    /// [`LOW_MEMORY_EVENT`]: crate::view_process::raw_events::LOW_MEMORY_EVENT
+    pub fn trim(&self, trim: RendererTrim) -> Result<()> {
+        self.call(|id, p| p.trim_renderer(id, trim))
+    }
+
     /// Call a render extension with custom encoded payload.
     pub fn render_extension_raw(&self, extension_id: ApiExtensionId, request: ApiExtensionPayload) -> Result<ApiExtensionPayload> {
         if let Some(w) = self.0.upgrade() {
@@ -1209,6 +1536,9 @@ struct ViewImageData {
     partial_pixels: Option<IpcBytes>,
     pixels: Option<std::result::Result<IpcBytes, Txt>>,
     is_mask: bool,
+    color_space: ColorSpace,
+    frame_delays: Vec<Duration>,
+    is_vector: bool,
 
     done_signal: SignalOnce,
 }
@@ -1292,6 +1622,47 @@ impl ViewImage {
         self.0.read().is_mask
     }
 
+    /// Returns the color space of the source image.
+    ///
+    /// The pixels are always converted to sRGB, this is the color profile of the encoded image,
+    /// or [`ColorSpace::Unknown`] if the image is not loaded or the source did not define a profile.
+    pub fn color_space(&self) -> ColorSpace {
+        self.0.read().color_space
+    }
+
+    /// Returns `true` if the image is loaded and has more than one frame.
+    ///
+    /// Animation frames can be decoded using [`VIEW_PROCESS.image_frame`].
+    ///
+    /// [`VIEW_PROCESS.image_frame`]: VIEW_PROCESS::image_frame
+    pub fn is_animated(&self) -> bool {
+        !self.0.read().frame_delays.is_empty()
+    }
+
+    /// Returns the number of animation frames, is `1` for still images and `0` if not loaded.
+    pub fn frame_count(&self) -> usize {
+        let me = self.0.read();
+        if me.frame_delays.is_empty() {
+            matches!(&me.pixels, Some(Ok(_))) as usize
+        } else {
+            me.frame_delays.len()
+        }
+    }
+
+    /// Returns the delay of each animation frame, is empty if the image is not animated.
+    pub fn frame_delays(&self) -> Vec<Duration> {
+        self.0.read().frame_delays.clone()
+    }
+
+    /// Returns `true` if the image is loaded and was decoded from a vector format.
+    ///
+    /// Vector images can be rasterized at any size using [`VIEW_PROCESS.image_raster`].
+    ///
+    /// [`VIEW_PROCESS.image_raster`]: VIEW_PROCESS::image_raster
+    pub fn is_vector(&self) -> bool {
+        self.0.read().is_vector
+    }
+
     /// Copy the partially decoded pixels if the image is progressively decoding
     /// and has not finished decoding.
     ///
@@ -1348,6 +1719,9 @@ impl ViewImage {
                 Some(Ok(IpcBytes::from_slice(&[])))
             },
             is_mask: false,
+            color_space: ColorSpace::Unknown,
+            frame_delays: vec![],
+            is_vector: false,
             done_signal: SignalOnce::new_set(),
         })))
     }
@@ -1363,6 +1737,15 @@ impl ViewImage {
     ///
     /// [`image_encoders`]: VIEW_PROCESS::image_encoders
     pub async fn encode(&self, format: Txt) -> std::result::Result<IpcBytes, EncodeError> {
+        self.encode_with_options(format, ImageEncodeOptions::default()).await
+    }
+
+    /// Tries to encode the image to the format, with custom encoder options.
+    ///
+    /// The `format` must be one of the [`image_encoders`] supported by the view-process backend.
+    ///
+    /// [`image_encoders`]: VIEW_PROCESS::image_encoders
+    pub async fn encode_with_options(&self, format: Txt, options: ImageEncodeOptions) -> std::result::Result<IpcBytes, EncodeError> {
         self.awaiter().await;
 
         if let Some(e) = self.error() {
@@ -1374,15 +1757,20 @@ impl ViewImage {
             if let Some(id) = img.id {
                 let mut app = VIEW_PROCESS.handle_write(img.app_id.unwrap());
 
-                app.process.encode_image(id, format.clone())?;
+                app.process.encode_image(id, format.clone(), options)?;
 
                 let (sender, receiver) = flume::bounded(1);
-                if let Some(entry) = app.encoding_images.iter_mut().find(|r| r.image_id == id && r.format == format) {
+                if let Some(entry) = app
+                    .encoding_images
+                    .iter_mut()
+                    .find(|r| r.image_id == id && r.format == format && r.options == options)
+                {
                     entry.listeners.push(sender);
                 } else {
                     app.encoding_images.push(EncodeRequest {
                         image_id: id,
                         format,
+                        options,
                         listeners: vec![sender],
                     });
                 }
@@ -1454,6 +1842,7 @@ impl WeakViewImage {
 struct EncodeRequest {
     image_id: ImageId,
     format: Txt,
+    options: ImageEncodeOptions,
     listeners: Vec<flume::Sender<std::result::Result<IpcBytes, EncodeError>>>,
 }
 
@@ -1501,6 +1890,9 @@ impl ViewClipboard {
                         partial_pixels: None,
                         pixels: None,
                         is_mask: false,
+                        color_space: ColorSpace::Unknown,
+                        frame_delays: vec![],
+                        is_vector: false,
                         done_signal: SignalOnce::new(),
                     })));
                     app.loading_images.push(Arc::downgrade(&img.0));
@@ -37,6 +37,12 @@ impl DeviceId {
         *ID
     }
 
+    /// Virtual gamepad ID used in gamepad events generated by code.
+    pub fn virtual_gamepad() -> DeviceId {
+        static ID: Lazy<DeviceId> = Lazy::new(DeviceId::new_unique);
+        *ID
+    }
+
     /// Virtual generic device ID used in device events generated by code.
     pub fn virtual_generic() -> DeviceId {
         static ID: Lazy<DeviceId> = Lazy::new(DeviceId::new_unique);
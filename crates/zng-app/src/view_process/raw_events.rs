@@ -14,16 +14,20 @@
 //! [`notify`]: crate::event::Event::notify
 //! [`DeviceId`]: crate::view_process::raw_device_events::DeviceId
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use zng_layout::unit::{DipPoint, DipSize, Factor, PxPoint, PxRect};
+use zng_layout::unit::{AngleDegree, DipPoint, DipSize, DipVector, Factor, PxPoint, PxRect};
 use zng_txt::Txt;
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     config::{AnimationsConfig, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig},
-    keyboard::{Key, KeyCode, KeyLocation, KeyState},
+    drag_drop::{DragDropEffect, DragDropId},
+    gamepad::{GamepadAxis, GamepadButton},
+    keyboard::{GlobalShortcutId, Key, KeyCode, KeyLocation, KeyState},
     mouse::{ButtonState, MouseButton, MouseScrollDelta},
+    notification::{NotificationId, NotificationResponse},
     touch::{TouchPhase, TouchUpdate},
+    tray::TrayIconId,
     window::{EventCause, FrameId, FrameWaitId, HeadlessOpenData, MonitorInfo, WindowStateAll},
     AxisId, Ime,
 };
@@ -31,6 +35,7 @@ use zng_view_api::{
 use crate::{
     event::{event, event_args},
     window::{MonitorId, WindowId},
+    DInstant,
 };
 
 use super::{raw_device_events::DeviceId, ViewHeadless, ViewImage, ViewWindow, WindowOpenData};
@@ -227,6 +232,41 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`RAW_FRAME_PRESENTED_EVENT`].
+    pub struct RawFramePresentedArgs {
+        /// Window that presented.
+        pub window_id: WindowId,
+
+        /// Frame that was presented.
+        pub frame_id: FrameId,
+
+        /// Moment the frame was presented.
+        pub presented_at: DInstant,
+
+        /// Estimated duration between display refreshes.
+        pub refresh_interval: Duration,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_REDRAW_TICK_EVENT`].
+    pub struct RawRedrawTickArgs {
+        /// Window that redrawn.
+        pub window_id: WindowId,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
     /// Arguments for the [`RAW_WINDOW_CLOSE_REQUESTED_EVENT`].
     pub struct RawWindowCloseRequestedArgs {
         /// Window that was requested to close.
@@ -302,6 +342,27 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`RAW_APP_DRAG_ENDED_EVENT`].
+    pub struct RawAppDragEndedArgs {
+        /// Window that started the drag operation.
+        pub window_id: WindowId,
+
+        /// ID of the drag&drop operation.
+        pub drag_id: DragDropId,
+
+        /// Effect applied to the data by the drop target.
+        ///
+        /// Is empty if the operation was cancelled or dropped outside any valid drop target.
+        pub applied: DragDropEffect,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
+
     /// Arguments for the [`RAW_MOUSE_MOVED_EVENT`].
     pub struct RawMouseMovedArgs {
         /// Window the mouse was moved over.
@@ -408,6 +469,246 @@ event_args! {
         }
     }
 
+    /// Arguments for the [`RAW_PINCH_GESTURE_EVENT`].
+    pub struct RawPinchGestureArgs {
+        /// Window that is hovered by the mouse.
+        pub window_id: WindowId,
+
+        /// Device that generated this event.
+        pub device_id: DeviceId,
+
+        /// Scale delta, positive values indicate magnification, negative values indicate shrinking.
+        pub delta: f32,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_ROTATION_GESTURE_EVENT`].
+    pub struct RawRotationGestureArgs {
+        /// Window that is hovered by the mouse.
+        pub window_id: WindowId,
+
+        /// Device that generated this event.
+        pub device_id: DeviceId,
+
+        /// Rotation delta, positive values indicate counter-clockwise rotation.
+        pub delta: AngleDegree,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_PAN_GESTURE_EVENT`].
+    pub struct RawPanGestureArgs {
+        /// Window that is hovered by the mouse.
+        pub window_id: WindowId,
+
+        /// Device that generated this event.
+        pub device_id: DeviceId,
+
+        /// Pan delta.
+        pub delta: DipVector,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_GLOBAL_SHORTCUT_EVENT`].
+    pub struct RawGlobalShortcutArgs {
+        /// Shortcut registered using [`VIEW_PROCESS.register_global_shortcut`].
+        ///
+        /// [`VIEW_PROCESS.register_global_shortcut`]: crate::view_process::VIEW_PROCESS::register_global_shortcut
+        pub shortcut_id: GlobalShortcutId,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_TRAY_ICON_CLICKED_EVENT`].
+    pub struct RawTrayIconClickedArgs {
+        /// Tray icon set using [`VIEW_PROCESS.set_tray_icon`].
+        ///
+        /// [`VIEW_PROCESS.set_tray_icon`]: crate::view_process::VIEW_PROCESS::set_tray_icon
+        pub tray_id: TrayIconId,
+
+        /// Mouse button that was released over the icon.
+        pub button: MouseButton,
+
+        /// If the click was a double-click.
+        pub is_double: bool,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_TRAY_MENU_ITEM_CLICKED_EVENT`].
+    pub struct RawTrayMenuItemClickedArgs {
+        /// Tray icon set using [`VIEW_PROCESS.set_tray_icon`].
+        ///
+        /// [`VIEW_PROCESS.set_tray_icon`]: crate::view_process::VIEW_PROCESS::set_tray_icon
+        pub tray_id: TrayIconId,
+
+        /// Menu item ID.
+        pub item: Txt,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_NOTIFICATION_RESPONDED_EVENT`].
+    pub struct RawNotificationRespondedArgs {
+        /// Notification shown using [`VIEW_PROCESS.show_notification`].
+        ///
+        /// [`VIEW_PROCESS.show_notification`]: crate::view_process::VIEW_PROCESS::show_notification
+        pub notification_id: NotificationId,
+
+        /// User response.
+        pub response: NotificationResponse,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT`].
+    pub struct RawSystemMenuItemClickedArgs {
+        /// Window that owns the system menu.
+        pub window_id: WindowId,
+
+        /// Menu item ID set using [`ViewWindow::set_system_menu`].
+        pub item: Txt,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_APP_MENU_ITEM_CLICKED_EVENT`].
+    pub struct RawAppMenuItemClickedArgs {
+        /// Window that exported the application menu.
+        pub window_id: WindowId,
+
+        /// Menu item ID set using [`ViewWindow::set_app_menu`].
+        pub item: Txt,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_GAMEPAD_CONNECTED_EVENT`].
+    pub struct RawGamepadConnectedArgs {
+        /// Gamepad device.
+        pub device_id: DeviceId,
+
+        /// Gamepad display name.
+        pub name: Txt,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_GAMEPAD_DISCONNECTED_EVENT`].
+    pub struct RawGamepadDisconnectedArgs {
+        /// Gamepad device.
+        pub device_id: DeviceId,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_GAMEPAD_BUTTON_EVENT`].
+    pub struct RawGamepadButtonArgs {
+        /// Gamepad device.
+        pub device_id: DeviceId,
+
+        /// The button.
+        pub button: GamepadButton,
+
+        /// Analog value in the `0.0..=1.0` range, digital buttons are `0.0` or `1.0`.
+        pub value: f32,
+
+        /// If the button is pressed.
+        pub state: ButtonState,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
+    /// Arguments for the [`RAW_GAMEPAD_AXIS_EVENT`].
+    pub struct RawGamepadAxisArgs {
+        /// Gamepad device.
+        pub device_id: DeviceId,
+
+        /// The axis.
+        pub axis: GamepadAxis,
+
+        /// Value in the `-1.0..=1.0` range.
+        pub value: f32,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all();
+        }
+    }
+
     /// Arguments for the [`RAW_AXIS_MOTION_EVENT`].
     pub struct RawAxisMotionArgs {
         /// Window that received the event.
@@ -526,6 +827,26 @@ event_args! {
         }
     }
 
+    /// [`RAW_CLIPBOARD_CHANGED_EVENT`] arguments.
+    pub struct RawClipboardChangedArgs {
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
+
+    /// [`RAW_KEYBOARD_LAYOUT_CHANGED_EVENT`] arguments.
+    pub struct RawKeyboardLayoutChangedArgs {
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
+
     /// Arguments for the [`RAW_FONT_AA_CHANGED_EVENT`].
     pub struct RawFontAaChangedArgs {
         /// The new anti-aliasing config.
@@ -677,6 +998,20 @@ event! {
     /// A window or headless surface initialization failed in the view-process.
     pub static RAW_WINDOW_OR_HEADLESS_OPEN_ERROR_EVENT: RawWindowOrHeadlessOpenErrorArgs;
 
+    /// A window with present notify enabled presented a frame.
+    ///
+    /// See [`ViewWindow::set_present_notify`] for more details.
+    ///
+    /// [`ViewWindow::set_present_notify`]: crate::view_process::ViewWindow::set_present_notify
+    pub static RAW_FRAME_PRESENTED_EVENT: RawFramePresentedArgs;
+
+    /// A window in continuous render mode redrawn.
+    ///
+    /// See [`ViewWindow::set_continuous_render`] for more details.
+    ///
+    /// [`ViewWindow::set_continuous_render`]: crate::view_process::ViewWindow::set_continuous_render
+    pub static RAW_REDRAW_TICK_EVENT: RawRedrawTickArgs;
+
     /// A window was requested to close.
     pub static RAW_WINDOW_CLOSE_REQUESTED_EVENT: RawWindowCloseRequestedArgs;
 
@@ -696,6 +1031,9 @@ event! {
     /// The file is the last one that emitted a [`RAW_HOVERED_FILE_EVENT`].
     pub static RAW_HOVERED_FILE_CANCELLED_EVENT: RawHoveredFileCancelledArgs;
 
+    /// A drag&drop operation started by the app has ended.
+    pub static RAW_APP_DRAG_ENDED_EVENT: RawAppDragEndedArgs;
+
     /// Mouse pointer moved over a window.
     pub static RAW_MOUSE_MOVED_EVENT: RawMouseMovedArgs;
 
@@ -714,6 +1052,45 @@ event! {
     /// Touchpad touched when the mouse was over a window.
     pub static RAW_TOUCHPAD_PRESSURE_EVENT: RawTouchpadPressureArgs;
 
+    /// System recognized a pinch gesture when the mouse was over a window.
+    pub static RAW_PINCH_GESTURE_EVENT: RawPinchGestureArgs;
+
+    /// System recognized a rotation gesture when the mouse was over a window.
+    pub static RAW_ROTATION_GESTURE_EVENT: RawRotationGestureArgs;
+
+    /// System recognized a pan gesture when the mouse was over a window.
+    pub static RAW_PAN_GESTURE_EVENT: RawPanGestureArgs;
+
+    /// A global shortcut was pressed, even if the app is not focused.
+    pub static RAW_GLOBAL_SHORTCUT_EVENT: RawGlobalShortcutArgs;
+
+    /// A tray icon was clicked.
+    pub static RAW_TRAY_ICON_CLICKED_EVENT: RawTrayIconClickedArgs;
+
+    /// A tray icon menu item was clicked.
+    pub static RAW_TRAY_MENU_ITEM_CLICKED_EVENT: RawTrayMenuItemClickedArgs;
+
+    /// A native notification received a user response.
+    pub static RAW_NOTIFICATION_RESPONDED_EVENT: RawNotificationRespondedArgs;
+
+    /// A window system menu custom item was clicked.
+    pub static RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT: RawSystemMenuItemClickedArgs;
+
+    /// A window application menu item was clicked in the global menu bar.
+    pub static RAW_APP_MENU_ITEM_CLICKED_EVENT: RawAppMenuItemClickedArgs;
+
+    /// A gamepad was connected.
+    pub static RAW_GAMEPAD_CONNECTED_EVENT: RawGamepadConnectedArgs;
+
+    /// A gamepad was disconnected.
+    pub static RAW_GAMEPAD_DISCONNECTED_EVENT: RawGamepadDisconnectedArgs;
+
+    /// A gamepad button changed, this event is not targeted to a window.
+    pub static RAW_GAMEPAD_BUTTON_EVENT: RawGamepadButtonArgs;
+
+    /// A gamepad axis moved, this event is not targeted to a window.
+    pub static RAW_GAMEPAD_AXIS_EVENT: RawGamepadAxisArgs;
+
     /// Motion on some analog axis send to a window.
     pub static RAW_AXIS_MOTION_EVENT: RawAxisMotionArgs;
 
@@ -739,6 +1116,21 @@ event! {
     /// Change in system text fonts, install or uninstall.
     pub static RAW_FONT_CHANGED_EVENT: RawFontChangedArgs;
 
+    /// System clipboard content changed.
+    ///
+    /// Note that the view-process may not support this event on the current platform.
+    pub static RAW_CLIPBOARD_CHANGED_EVENT: RawClipboardChangedArgs;
+
+    /// System keyboard layout changed.
+    ///
+    /// Key labels must be reloaded using [`VIEW_PROCESS.key_labels`].
+    ///
+    /// Note that the view-process may not support this event on the current platform, the default view-process
+    /// only notifies on Windows.
+    ///
+    /// [`VIEW_PROCESS.key_labels`]: crate::view_process::VIEW_PROCESS::key_labels
+    pub static RAW_KEYBOARD_LAYOUT_CHANGED_EVENT: RawKeyboardLayoutChangedArgs;
+
     /// Change in system "double-click" config.
     pub static RAW_MULTI_CLICK_CONFIG_CHANGED_EVENT: RawMultiClickConfigChangedArgs;
 
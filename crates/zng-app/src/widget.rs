@@ -14,7 +14,12 @@ use atomic::Atomic;
 use parking_lot::{Mutex, RwLock};
 use std::{
     borrow::Cow,
-    sync::{atomic::Ordering::Relaxed, Arc},
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering::Relaxed},
+        Arc,
+    },
+    task::{Poll, Waker},
 };
 use zng_app_context::context_local;
 use zng_handle::Handle;
@@ -26,10 +31,10 @@ use zng_var::{AnyVar, AnyVarHookArgs, AnyVarValue, ResponseVar, Var, VarHandle,
 use zng_view_api::display_list::ReuseRange;
 
 use crate::{
-    event::{Event, EventArgs, EventHandle, EventHandles},
+    event::{event, event_args, Event, EventArgs, EventHandle, EventHandles},
     handler::{app_hn, app_hn_once, AppHandler, AppHandlerArgs},
     update::{LayoutUpdates, RenderUpdates, UpdateFlags, UpdateOp, UPDATES},
-    window::WINDOW,
+    window::{WindowId, WINDOW},
 };
 
 use self::info::{WidgetBorderInfo, WidgetBoundsInfo, WidgetInfo};
@@ -458,6 +463,35 @@ pub use zng_app_proc_macros::{property, ui_node, widget, widget_mixin};
 /// The same apply for properties referenced in the condition expression, note that all `is_state` properties have a default value so
 /// it is more rare that a default value is not available. If a condition property cannot be generated the entire when block is ignored.
 ///
+/// ## Else When
+///
+/// A `when` block can be followed by `else when` blocks to define an exclusive chain, only the first block in the chain
+/// with a `true` condition applies its assigns, the next blocks are only active if all previous blocks in the chain are `false`.
+///
+/// ```
+/// # use zng_app::{*, widget::{node::*, property}};
+/// # use zng_color::*;
+/// # use zng_var::*;
+/// # use zng_layout::unit::*;
+/// # #[property(CONTEXT)] pub fn background_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode { child }
+/// # #[property(EVENT)] pub fn is_pressed(child: impl UiNode, state: impl IntoVar<bool>) -> impl UiNode { child }
+/// # #[property(EVENT)] pub fn is_hovered(child: impl UiNode, state: impl IntoVar<bool>) -> impl UiNode { child }
+/// # fn main() {
+/// # let _scope = APP.minimal();
+/// # let wgt = zng_app::widget::base::WidgetBase! {
+/// background_color = colors::RED;
+///
+/// when *#is_pressed {
+///     background_color = colors::GREEN;
+/// } else when *#is_hovered {
+///     background_color = colors::BLUE;
+/// }
+/// # }; }
+/// ```
+///
+/// In the example above the background is green when pressed, even if the widget is also hovered, without the `else` the
+/// condition of the second block would need to be `*#is_hovered && !*#is_pressed` to have the same effect.
+///
 /// [`WidgetBase`]: struct@crate::widget::base::WidgetBase
 /// [`WidgetBuilder::push_unset`]: crate::widget::builder::WidgetBuilder::push_unset
 #[macro_export]
@@ -1035,6 +1069,58 @@ impl WIDGET {
         }
     }
 
+    /// Spawn a parallel `task` that is canceled when the widget deinits.
+    ///
+    /// The task runs in the [`task::spawn`] thread-pool, it is dropped at the next `.await` point after the widget deinits,
+    /// so it does not outlive the widget vars and state it captures.
+    ///
+    /// If the task panics the panic is logged and the [`WIDGET_TASK_PANIC_EVENT`] notifies the widget and ancestors.
+    ///
+    /// Note that [`with_handles`] also intercepts the task handle, the task is canceled when the custom handles are cleared.
+    ///
+    /// [`task::spawn`]: zng_task::spawn
+    /// [`with_handles`]: Self::with_handles
+    pub fn spawn_scoped(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let state = Arc::new(ScopedTaskState {
+            canceled: AtomicBool::new(false),
+            waker: Mutex::new(None),
+        });
+        let handle = ScopedTaskHandle(state.clone());
+        if WIDGET_HANDLES_CTX.is_default() {
+            WIDGET_CTX.get().handles.push_task_handle(handle);
+        } else {
+            WIDGET_HANDLES_CTX.get().push_task_handle(handle);
+        }
+
+        let window_id = WINDOW.try_id();
+        let widget_id = self.id();
+        zng_task::spawn(async move {
+            let mut task = Box::pin(task);
+            let scoped = std::future::poll_fn(move |cx| {
+                if state.canceled.load(Relaxed) {
+                    return Poll::Ready(());
+                }
+                *state.waker.lock() = Some(cx.waker().clone());
+                if state.canceled.load(Relaxed) {
+                    return Poll::Ready(());
+                }
+                task.as_mut().poll(cx)
+            });
+
+            if let Err(p) = zng_task::run_catch(scoped).await {
+                let panic = if let Some(s) = p.downcast_ref::<&str>() {
+                    Txt::from_str(s)
+                } else if let Some(s) = p.downcast_ref::<String>() {
+                    Txt::from_str(s)
+                } else {
+                    Txt::from_static("<unknown-panic-message-type>")
+                };
+                tracing::error!("scoped task of widget {widget_id:?} panicked: {panic}");
+                WIDGET_TASK_PANIC_EVENT.notify(WidgetTaskPanicArgs::now(window_id, widget_id, panic));
+            }
+        });
+    }
+
     /// Transform point in the window space to the widget inner bounds.
     pub fn win_point_to_wgt(&self, point: DipPoint) -> Option<PxPoint> {
         let wgt_info = WIDGET.info();
@@ -1222,8 +1308,7 @@ impl WidgetCtx {
     /// If `retain_state` is enabled the state will not be cleared and can still read.
     pub fn deinit(&mut self, retain_state: bool) {
         let ctx = self.0.as_mut().unwrap();
-        ctx.handles.var_handles.lock().clear();
-        ctx.handles.event_handles.lock().clear();
+        ctx.handles.clear();
         ctx.flags.store(UpdateFlags::empty(), Relaxed);
         *ctx.render_reuse.lock() = None;
 
@@ -1299,6 +1384,7 @@ impl WidgetCtxData {
 struct WidgetHandlesCtxData {
     var_handles: Mutex<VarHandles>,
     event_handles: Mutex<EventHandles>,
+    task_handles: Mutex<Vec<ScopedTaskHandle>>,
 }
 
 impl WidgetHandlesCtxData {
@@ -1306,8 +1392,66 @@ impl WidgetHandlesCtxData {
         Self {
             var_handles: Mutex::new(VarHandles::dummy()),
             event_handles: Mutex::new(EventHandles::dummy()),
+            task_handles: Mutex::new(vec![]),
         }
     }
+
+    fn push_task_handle(&self, handle: ScopedTaskHandle) {
+        let mut h = self.task_handles.lock();
+        // drop handles of finished tasks.
+        h.retain(|h| Arc::strong_count(&h.0) > 1);
+        h.push(handle);
+    }
+
+    fn clear(&self) {
+        self.var_handles.lock().clear();
+        self.event_handles.lock().clear();
+        self.task_handles.lock().clear();
+    }
+}
+
+struct ScopedTaskState {
+    canceled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// Cancels the scoped task on drop.
+struct ScopedTaskHandle(Arc<ScopedTaskState>);
+impl Drop for ScopedTaskHandle {
+    fn drop(&mut self) {
+        self.0.canceled.store(true, Relaxed);
+        if let Some(w) = self.0.waker.lock().take() {
+            w.wake();
+        }
+    }
+}
+
+event_args! {
+    /// Arguments for the [`WIDGET_TASK_PANIC_EVENT`].
+    pub struct WidgetTaskPanicArgs {
+        /// Window of the widget, if the task was spawned inside a window.
+        pub window_id: Option<WindowId>,
+
+        /// Widget that spawned the task.
+        pub widget_id: WidgetId,
+
+        /// Panic message.
+        pub panic: Txt,
+
+        ..
+
+        /// Target the widget and ancestors.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_widget(self.widget_id);
+        }
+    }
+}
+
+event! {
+    /// A task spawned using [`WIDGET.spawn_scoped`] panicked.
+    ///
+    /// [`WIDGET.spawn_scoped`]: WIDGET::spawn_scoped
+    pub static WIDGET_TASK_PANIC_EVENT: WidgetTaskPanicArgs;
 }
 
 /// Defines the backing data for [`WIDGET.with_handles`].
@@ -1322,9 +1466,7 @@ impl WidgetHandlesCtx {
 
     /// Drop all handles.
     pub fn clear(&mut self) {
-        let h = self.0.as_ref().unwrap();
-        h.var_handles.lock().clear();
-        h.event_handles.lock().clear();
+        self.0.as_ref().unwrap().clear();
     }
 }
 impl Default for WidgetHandlesCtx {
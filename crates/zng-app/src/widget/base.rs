@@ -1003,6 +1003,18 @@ context_var! {
     /// Is all enabled by default.
     pub static PARALLEL_VAR: Parallel = Parallel::default();
 
+    /// Defines the minimum number of nodes that are processed by each parallel task in node lists of a widget and descendants.
+    ///
+    /// Parallel tasks are split and work-stolen until each task has at least this number of nodes, so lists with
+    /// less than twice this number of nodes are processed sequentially, without splitting the info, layout or render
+    /// builders. Increase this value for wide lists of cheap nodes, where the overhead of splitting the work is more
+    /// than the work.
+    ///
+    /// This variable can be set using the `parallel_min_chunk` property.
+    ///
+    /// Is `1` by default.
+    pub static PARALLEL_MIN_CHUNK_VAR: usize = 1;
+
     /// Defines the hit-test mode for a widget and descendants.
     ///
     /// This variable can be set using the `hit_test_mode` property.
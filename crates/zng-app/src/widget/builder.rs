@@ -6,6 +6,7 @@ use std::{
     collections::{hash_map, HashMap},
     fmt, ops,
     sync::Arc,
+    time::Duration,
 };
 
 #[doc(hidden)]
@@ -52,6 +53,16 @@ pub fn when_condition_expr_var(expr_var: impl Var<bool>) -> BoxedVar<bool> {
     expr_var.boxed()
 }
 
+#[doc(hidden)]
+pub fn when_condition_else_var(state: BoxedVar<bool>, previous: &[BoxedVar<bool>]) -> BoxedVar<bool> {
+    let mut previous = previous.iter();
+    let any_previous = match previous.next() {
+        Some(first) => previous.fold(first.clone(), |a, b| merge_var!(a, b.clone(), |a, b| *a || *b).boxed()),
+        None => return state,
+    };
+    merge_var!(state, any_previous, |s, p| *s && !*p).boxed()
+}
+
 #[doc(hidden)]
 pub struct WgtInfo;
 impl WidgetExt for WgtInfo {
@@ -219,7 +230,8 @@ use zng_app_proc_macros::widget;
 use zng_txt::{formatx, Txt};
 use zng_unique_id::{unique_id_32, IdEntry, IdMap, IdSet};
 use zng_var::{
-    impl_from_and_into_var,
+    animation::easing::{EasingStep, EasingTime},
+    impl_from_and_into_var, merge_var,
     types::{AnyWhenVarBuilder, ContextualizedVar, WeakContextInitHandle},
     AnyVar, AnyVarValue, BoxedAnyVar, BoxedVar, ContextInitHandle, IntoValue, IntoVar, LocalVar, Var, VarValue,
 };
@@ -773,6 +785,13 @@ pub struct PropertyNewArgs {
     pub build_actions_when_data: PropertyBuildActionsWhenData,
 }
 
+/// Function that generates the easing build actions for an animatable property.
+///
+/// The inputs are the easing duration and function, the output is one action for each property input.
+///
+/// See [`PropertyInfo::animatable`] for more details.
+pub type PropertyEasingFn = fn(Duration, Arc<dyn Fn(EasingTime) -> EasingStep + Send + Sync>) -> Vec<Box<dyn AnyPropertyBuildAction>>;
+
 /// Property info.
 ///
 /// You can use the [`property_info!`] macro to retrieve a property's info.
@@ -785,6 +804,11 @@ pub struct PropertyInfo {
     /// Note that all properties can be captured, but if this is `false` they provide an implementation that works standalone.
     pub capture: bool,
 
+    /// Generate the easing build actions for the property inputs.
+    ///
+    /// This is `Some(_)` only if the `#[property(_, animatable)]` was set in the property declaration.
+    pub animatable: Option<PropertyEasingFn>,
+
     /// Unique ID that identifies the property implementation.
     pub id: PropertyId,
     /// Property name.
@@ -1004,7 +1028,7 @@ pub fn panic_input(info: &PropertyInfo, i: usize, kind: InputKind) -> ! {
 
 #[doc(hidden)]
 pub fn var_to_args<T: VarValue>(var: impl IntoVar<T>) -> BoxedVar<T> {
-    var.into_var().boxed()
+    crate::widget::inspector::overridable_var(var.into_var().boxed())
 }
 
 #[doc(hidden)]
@@ -1074,7 +1098,7 @@ pub fn new_dyn_var<'a, T: VarValue>(
         Err(item) => *item.downcast::<BoxedVar<T>>().expect("input did not match expected var types"),
     };
 
-    apply_build_actions(item, actions)
+    crate::widget::inspector::overridable_var(apply_build_actions(item, actions))
 }
 
 #[doc(hidden)]
@@ -2005,7 +2029,9 @@ impl WidgetBuilder {
     /// Instantiate the widget.
     ///
     /// Runs all build actions, but ignores custom build.
-    pub fn default_build(self) -> BoxedUiNode {
+    pub fn default_build(mut self) -> BoxedUiNode {
+        self.apply_easing_all();
+
         #[cfg(feature = "inspector")]
         let builder = self.clone();
 
@@ -2043,6 +2069,44 @@ impl WidgetBuilder {
         building.build(when_init_context_handle)
     }
 }
+impl WidgetBuilder {
+    /// Push easing build actions for each animatable property if the [`easing_all`] property is set.
+    ///
+    /// [`easing_all`]: fn@crate::widget::easing_all
+    fn apply_easing_all(&mut self) {
+        let id = property_id!(crate::widget::easing_all);
+        let importance = match self.property(id) {
+            Some(p) => p.importance,
+            None => return,
+        };
+        let cfg = self.capture_value::<super::EasingAll>(id).unwrap();
+        if cfg.duration == Duration::ZERO {
+            return;
+        }
+
+        let animatable: Vec<_> = self
+            .properties()
+            .filter_map(|p| {
+                let info = p.args.property();
+                if cfg.exclude.contains(&info.id) {
+                    None
+                } else {
+                    Some((info.id, info.animatable?))
+                }
+            })
+            .collect();
+
+        for (id, easing_fn) in animatable {
+            // same action name as the `#[easing]` attribute, explicit easing of the same importance is not replaced.
+            self.push_property_build_action(
+                id,
+                "zng::core::widget::easing",
+                importance,
+                easing_fn(cfg.duration, cfg.easing.clone()),
+            );
+        }
+    }
+}
 impl ops::Deref for WidgetBuilder {
     type Target = WidgetBuilderProperties;
 
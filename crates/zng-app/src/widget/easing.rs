@@ -1,18 +1,110 @@
-use std::{any::Any, sync::Arc, time::Duration};
+use std::{any::Any, fmt, sync::Arc, time::Duration};
 
-use super::builder::*;
+use super::{base::WidgetBase, builder::*};
+use crate::widget::property;
 use zng_layout::unit::*;
 use zng_var::{
     animation::{
-        easing::{EasingStep, EasingTime},
+        easing::{self, EasingStep, EasingTime},
         Transitionable,
     },
+    impl_from_and_into_var,
     types::{ArcWhenVar, ContextualizedVar},
-    BoxedVar, Var, VarValue,
+    BoxedVar, IntoValue, Var, VarValue,
 };
 
 pub use zng_app_proc_macros::easing;
 
+/// Blanket easing applied to all animatable properties of a widget.
+///
+/// See [`easing_all`] for more details.
+///
+/// [`easing_all`]: fn@easing_all
+#[derive(Clone)]
+pub struct EasingAll {
+    /// Easing duration.
+    ///
+    /// No easing is applied if is zero.
+    pub duration: Duration,
+    /// Easing function.
+    pub easing: Arc<dyn Fn(EasingTime) -> EasingStep + Send + Sync>,
+    /// Properties that are not affected.
+    pub exclude: Vec<PropertyId>,
+}
+impl EasingAll {
+    /// New linear easing.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Arc::new(easing::linear),
+            exclude: vec![],
+        }
+    }
+
+    /// No easing.
+    pub fn none() -> Self {
+        Self::new(Duration::ZERO)
+    }
+
+    /// Set the easing function.
+    pub fn with_easing(mut self, easing: impl Fn(EasingTime) -> EasingStep + Send + Sync + 'static) -> Self {
+        self.easing = Arc::new(easing);
+        self
+    }
+
+    /// Exclude the property.
+    ///
+    /// Use the [`property_id!`] macro to get the ID.
+    pub fn exclude(mut self, property_id: PropertyId) -> Self {
+        self.exclude.push(property_id);
+        self
+    }
+}
+impl Default for EasingAll {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+impl fmt::Debug for EasingAll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EasingAll")
+            .field("duration", &self.duration)
+            .field("exclude", &self.exclude)
+            .finish_non_exhaustive()
+    }
+}
+impl PartialEq for EasingAll {
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration && Arc::ptr_eq(&self.easing, &other.easing) && self.exclude == other.exclude
+    }
+}
+impl_from_and_into_var! {
+    /// Linear easing.
+    fn from(duration: Duration) -> EasingAll {
+        EasingAll::new(duration)
+    }
+
+    /// Linear easing with excluded properties.
+    fn from<const N: usize>((duration, exclude): (Duration, [PropertyId; N])) -> EasingAll {
+        EasingAll {
+            exclude: exclude.into(),
+            ..EasingAll::new(duration)
+        }
+    }
+}
+
+/// Applies easing to all animatable properties set on the widget.
+///
+/// All properties declared with the `animatable` flag are eased, this includes properties set by the widget intrinsic and styles.
+/// Properties can be excluded using [`EasingAll::exclude`], properties that set `#[easing(..)]` in the same widget instance
+/// retain their own easing.
+///
+/// Note that only properties with a default assign are affected, properties that are only set in `when` blocks are not eased.
+///
+/// This property is capture-only, it is implemented by the widget builder for all widgets.
+#[property(CONTEXT, capture, default(EasingAll::none()), widget_impl(WidgetBase))]
+pub fn easing_all(easing: impl IntoValue<EasingAll>) {}
+
 type EasingFn = Arc<dyn Fn(EasingTime) -> EasingStep + Send + Sync>;
 
 #[doc(hidden)]
@@ -238,6 +238,74 @@ impl WidgetInfoTree {
         self.0.frame.read().spatial_bounds.to_rect()
     }
 
+    /// Spatial iterator over all widgets with inner bounds that contain the `point`.
+    ///
+    /// See [`WidgetInfo::inner_contains`] for more details.
+    pub fn inner_contains(&self, point: PxPoint) -> impl Iterator<Item = WidgetInfo> {
+        self.root().inner_contains(point)
+    }
+
+    /// Spatial iterator over all widgets with inner bounds that intersects the `rect`.
+    ///
+    /// This can be used to implement selection marquees that select any widget touched by the selection rectangle.
+    pub fn inner_intersects(&self, rect: PxRect) -> impl Iterator<Item = WidgetInfo> {
+        self.root().inner_intersects(rect)
+    }
+
+    /// Spatial iterator over all widgets with inner bounds that are fully inside the `rect`.
+    ///
+    /// This can be used to implement selection marquees that only select widgets fully enveloped by the selection rectangle.
+    pub fn inner_contained(&self, rect: PxRect) -> impl Iterator<Item = WidgetInfo> {
+        self.root().inner_contained(rect)
+    }
+
+    /// Gets all widgets hit by a `point`, sorted by z-index of the hit, front to back.
+    ///
+    /// Note that this is a hit-test on the info tree, it does not consider transparency or views of other processes,
+    /// see [`WidgetInfo::hit_test`] for more details.
+    pub fn hit_test(&self, point: PxPoint) -> HitTestInfo {
+        self.root().hit_test(point)
+    }
+
+    /// Find the widget with center point nearest of `origin` within the `max_distance` and with `orientation` to origin.
+    ///
+    /// See [`WidgetInfo::nearest_oriented`] for more details.
+    pub fn nearest_oriented(&self, origin: PxPoint, max_distance: Px, orientation: Orientation2D) -> Option<WidgetInfo> {
+        self.root().nearest_oriented(origin, max_distance, orientation)
+    }
+
+    /// Find the widget with center point nearest of `origin` within the `max_distance` and with `orientation` to origin,
+    /// and approved by the `filter` closure.
+    ///
+    /// See [`WidgetInfo::nearest_oriented_filtered`] for more details.
+    pub fn nearest_oriented_filtered(
+        &self,
+        origin: PxPoint,
+        max_distance: Px,
+        orientation: Orientation2D,
+        filter: impl FnMut(&WidgetInfo) -> bool,
+    ) -> Option<WidgetInfo> {
+        self.root().nearest_oriented_filtered(origin, max_distance, orientation, filter)
+    }
+
+    /// Find the enabled and visible widget with center point nearest of `origin` within the `max_distance` and with
+    /// `orientation` to origin.
+    ///
+    /// This can be used to implement custom directional navigation for widgets that are not focusable, use
+    /// the focus info tree to find focusable widgets.
+    pub fn nearest_interactive_oriented(&self, origin: PxPoint, max_distance: Px, orientation: Orientation2D) -> Option<WidgetInfo> {
+        self.nearest_oriented_filtered(origin, max_distance, orientation, |w| {
+            w.interactivity().is_enabled() && w.visibility().is_visible()
+        })
+    }
+
+    /// Gets all widgets with inner bounds intersected by a ray, sorted by distance from `origin` to the ray entry point.
+    ///
+    /// See [`WidgetInfo::ray_cast`] for more details.
+    pub fn ray_cast(&self, origin: PxPoint, direction: PxVector, max_distance: Px) -> Vec<(WidgetInfo, Px)> {
+        self.root().ray_cast(origin, direction, max_distance)
+    }
+
     /// Total number of widgets in the tree.
     ///
     /// Is never zero, every tree has at least the root widget.
@@ -396,8 +464,7 @@ struct WidgetBoundsData {
     perspective: f32,
     perspective_origin: Option<PxPoint>,
 
-    measure_metrics: Option<LayoutMetricsSnapshot>,
-    measure_metrics_used: LayoutMask,
+    measure_cache: Vec<MeasureCacheEntry>,
     metrics: Option<LayoutMetricsSnapshot>,
     metrics_used: LayoutMask,
 
@@ -415,8 +482,25 @@ struct WidgetBoundsData {
     is_partially_culled: bool,
     cannot_auto_hide: bool,
     is_collapsed: bool,
+
+    measure_count: u32,
+    layout_count: u32,
+    render_count: u32,
 }
 
+/// Result of a previous measure of a widget.
+#[derive(Debug, Clone)]
+struct MeasureCacheEntry {
+    metrics: LayoutMetricsSnapshot,
+    metrics_used: LayoutMask,
+    inline_requested: bool,
+    outer_size: PxSize,
+    inline: Option<WidgetInlineMeasure>,
+}
+
+/// Maximum number of measure results retained for each widget.
+const MEASURE_CACHE_LEN: usize = 4;
+
 /// Widget render data.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct WidgetRenderInfo {
@@ -754,11 +838,92 @@ impl WidgetBoundsInfo {
         self.0.lock().hit_clips.update_transform(value);
     }
 
-    pub(crate) fn measure_metrics(&self) -> Option<LayoutMetricsSnapshot> {
-        self.0.lock().measure_metrics.clone()
+    /// Gets a cached measure result for the `metrics`, if the entry is found it is made the current measure.
+    ///
+    /// The `inline_requested` must be the same value used to push the entry, the cached inline result can be `None` even if
+    /// inline was requested, if the widget does not support inline.
+    pub(crate) fn measure_cached(
+        &self,
+        metrics: &LayoutMetricsSnapshot,
+        inline_requested: bool,
+    ) -> Option<(PxSize, Option<WidgetInlineMeasure>)> {
+        let mut m = self.0.lock();
+        let i = m
+            .measure_cache
+            .iter()
+            .position(|e| e.inline_requested == inline_requested && e.metrics.masked_eq(metrics, e.metrics_used))?;
+        let entry = m.measure_cache.remove(i);
+        m.measure_outer_size = entry.outer_size;
+        m.measure_inline = entry.inline.clone();
+        let r = (entry.outer_size, entry.inline.clone());
+        m.measure_cache.insert(0, entry);
+        Some(r)
+    }
+
+    /// Insert the current measure result in the cache.
+    pub(crate) fn push_measure_cache(&self, metrics: LayoutMetricsSnapshot, used: LayoutMask, inline_requested: bool) {
+        let mut m = self.0.lock();
+        let entry = MeasureCacheEntry {
+            metrics,
+            metrics_used: used,
+            inline_requested,
+            outer_size: m.measure_outer_size,
+            inline: m.measure_inline.clone(),
+        };
+        m.measure_cache.truncate(MEASURE_CACHE_LEN - 1);
+        m.measure_cache.insert(0, entry);
     }
-    pub(crate) fn measure_metrics_used(&self) -> LayoutMask {
-        self.0.lock().measure_metrics_used
+
+    /// Clear the measure results cached for the widget.
+    ///
+    /// Each widget retains the results of the last few measure passes keyed by the layout metrics used, so that parent panels
+    /// that measure the same child with different constraints don't need to measure the full subtree again. The cache is
+    /// cleared automatically when the widget or a descendant requests layout and when the widget collapses, custom nodes
+    /// that measure differently depending on other state must request layout or clear the cache.
+    pub fn clear_measure_cache(&self) {
+        self.0.lock().measure_cache.clear();
+    }
+
+    /// Number of measure results cached for the widget.
+    pub fn measure_cache_len(&self) -> usize {
+        self.0.lock().measure_cache.len()
+    }
+
+    /// Number of times the widget was measured.
+    ///
+    /// Measure requests resolved by the measure cache are not counted. The count wraps on overflow, diagnostic tools
+    /// can sample this value to find widgets that measure too often.
+    pub fn measure_count(&self) -> u32 {
+        self.0.lock().measure_count
+    }
+
+    /// Number of times the widget layout was computed.
+    ///
+    /// Layout passes skipped because the widget layout was not invalidated are not counted. The count wraps on overflow.
+    pub fn layout_count(&self) -> u32 {
+        self.0.lock().layout_count
+    }
+
+    /// Number of frames that rendered the widget.
+    ///
+    /// Frames that reused the previous widget display items are not counted. The count wraps on overflow.
+    pub fn render_count(&self) -> u32 {
+        self.0.lock().render_count
+    }
+
+    pub(crate) fn inc_measure_count(&self) {
+        let mut m = self.0.lock();
+        m.measure_count = m.measure_count.wrapping_add(1);
+    }
+
+    pub(crate) fn inc_layout_count(&self) {
+        let mut m = self.0.lock();
+        m.layout_count = m.layout_count.wrapping_add(1);
+    }
+
+    pub(crate) fn inc_render_count(&self) {
+        let mut m = self.0.lock();
+        m.render_count = m.render_count.wrapping_add(1);
     }
 
     fn set_outer_size(&self, size: PxSize) {
@@ -834,11 +999,6 @@ impl WidgetBoundsInfo {
         self.0.lock().metrics_used = used;
     }
 
-    pub(crate) fn set_measure_metrics(&self, metrics: Option<LayoutMetricsSnapshot>, used: LayoutMask) {
-        self.0.lock().measure_metrics = metrics;
-        self.0.lock().measure_metrics_used = used;
-    }
-
     pub(crate) fn set_hit_clips(&self, clips: HitTestClips) {
         self.0.lock().hit_clips = clips;
     }
@@ -1676,7 +1836,9 @@ impl WidgetInfo {
     /// Spatial iterator over self and descendants with inner bounds that are fully inside the `rect`.
     pub fn inner_contained(&self, rect: PxRect) -> impl Iterator<Item = WidgetInfo> {
         let rect = rect.to_box2d();
-        self.spatial_iter(move |w| rect.contains_box(&w.inner_bounds().to_box2d()))
+        // descendants can be inside even if self is not.
+        self.spatial_iter(move |w| w.inner_bounds().to_box2d().intersects(&rect))
+            .filter(move |w| rect.contains_box(&w.inner_bounds().to_box2d()))
     }
 
     /// Spatial iterator over self and descendants with center point inside the `area`.
@@ -1722,6 +1884,61 @@ impl WidgetInfo {
         }
     }
 
+    /// Gets self and descendants with inner bounds intersected by a ray that starts at `origin` and extends in the `direction`
+    /// for `max_distance`.
+    ///
+    /// Returns the widgets and the distance from `origin` to the point the ray enters the inner bounds, sorted by the distance,
+    /// widgets that contain the `origin` have distance zero. Use `Px::MAX` on the distance to cast the ray to the end of the
+    /// [`spatial_bounds`]. Returns empty if `direction` is zero.
+    ///
+    /// Note that the ray is tested against the axis aligned inner bounds, not the hit-test shapes of the widget.
+    ///
+    /// [`spatial_bounds`]: WidgetInfoTree::spatial_bounds
+    pub fn ray_cast(&self, origin: PxPoint, direction: PxVector, max_distance: Px) -> Vec<(WidgetInfo, Px)> {
+        let _span = tracing::trace_span!("ray_cast").entered();
+
+        let dir = euclid::vec2::<f32, ()>(direction.x.0 as f32, direction.y.0 as f32);
+        let len = dir.length();
+        if len <= 0.0 || max_distance <= Px(0) {
+            return vec![];
+        }
+        let dir = dir / len;
+
+        let spatial_bounds = self.tree.spatial_bounds().to_box2d();
+        let max_distance = if max_distance == Px::MAX {
+            // far enough to cross the spatial bounds from any origin.
+            let far = |a: Px, b: Px, c: Px| (a.0 as f32 - c.0 as f32).abs().max((b.0 as f32 - c.0 as f32).abs());
+            let dx = far(spatial_bounds.min.x, spatial_bounds.max.x, origin.x);
+            let dy = far(spatial_bounds.min.y, spatial_bounds.max.y, origin.y);
+            Px(dx.hypot(dy).ceil().min(i32::MAX as f32 / 2.0) as i32)
+        } else {
+            max_distance
+        };
+
+        let o = euclid::point2::<f32, ()>(origin.x.0 as f32, origin.y.0 as f32);
+        let end = o + dir * max_distance.0 as f32;
+        let search = PxBox::new(
+            PxPoint::new(Px(o.x.min(end.x).floor() as i32), Px(o.y.min(end.y).floor() as i32)),
+            PxPoint::new(Px(o.x.max(end.x).ceil() as i32 + 1), Px(o.y.max(end.y).ceil() as i32 + 1)),
+        );
+        let search = match search.intersection(&spatial_bounds) {
+            Some(s) => s,
+            None => return vec![],
+        };
+
+        let mut hits: Vec<_> = self
+            .inner_intersects(search.to_rect())
+            .filter_map(|w| {
+                let b = w.inner_bounds().to_box2d();
+                ray_box_entry(o, dir, b, max_distance.0 as f32).map(|d| (w, Px(d as i32)))
+            })
+            .collect();
+
+        hits.sort_by_key(|(_, d)| *d);
+
+        hits
+    }
+
     /// Find the descendant with center point nearest of `origin` within the `max_radius`.
     ///
     /// This method is faster than using sorting the result of [`center_in_distance`], but is slower if any point in distance is acceptable.
@@ -2194,6 +2411,37 @@ impl WidgetDescendantsRange {
     }
 }
 
+/// Distance from `origin` to the point the ray enters the `bounds`, `direction` must be normalized.
+///
+/// Is zero if `origin` is inside bounds, is `None` if the ray does not intersect the bounds within the `max_distance`.
+fn ray_box_entry(origin: euclid::Point2D<f32, ()>, direction: euclid::Vector2D<f32, ()>, bounds: PxBox, max_distance: f32) -> Option<f32> {
+    let mut t_min = 0.0_f32;
+    let mut t_max = max_distance;
+    for (o, d, min, max) in [
+        (origin.x, direction.x, bounds.min.x.0 as f32, bounds.max.x.0 as f32),
+        (origin.y, direction.y, bounds.min.y.0 as f32, bounds.max.y.0 as f32),
+    ] {
+        if d.abs() <= f32::EPSILON {
+            // parallel to the slab
+            if o < min || o > max {
+                return None;
+            }
+        } else {
+            let mut t0 = (min - o) / d;
+            let mut t1 = (max - o) / d;
+            if t0 > t1 {
+                mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+    Some(t_min)
+}
+
 /// A hit-test hit.
 #[derive(Clone, Debug)]
 pub struct HitInfo {
@@ -2271,3 +2519,205 @@ impl HitTestInfo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zng_layout::context::LayoutMetrics;
+
+    use super::*;
+    use crate::{
+        widget::{WidgetCtx, WidgetUpdateMode, WIDGET},
+        window::WINDOW,
+        APP,
+    };
+
+    fn metrics(width: i32) -> LayoutMetricsSnapshot {
+        LayoutMetrics::new(1.fct(), PxSize::new(Px(width), Px(100)), Px(14)).snapshot()
+    }
+
+    fn push(bounds: &WidgetBoundsInfo, width: i32, inline_requested: bool) {
+        bounds.set_measure_outer_size(PxSize::new(Px(width), Px(10)));
+        bounds.push_measure_cache(metrics(width), LayoutMask::CONSTRAINTS, inline_requested);
+    }
+
+    #[test]
+    fn measure_cache_hit() {
+        let bounds = WidgetBoundsInfo::new();
+        push(&bounds, 100, false);
+        push(&bounds, 200, false);
+
+        let (size, inline) = bounds.measure_cached(&metrics(100), false).unwrap();
+        assert_eq!(size, PxSize::new(Px(100), Px(10)));
+        assert!(inline.is_none());
+        assert_eq!(bounds.measure_outer_size(), size);
+    }
+
+    #[test]
+    fn measure_cache_miss() {
+        let bounds = WidgetBoundsInfo::new();
+        push(&bounds, 100, false);
+
+        assert!(bounds.measure_cached(&metrics(300), false).is_none());
+        assert!(bounds.measure_cached(&metrics(100), true).is_none());
+    }
+
+    #[test]
+    fn measure_cache_inline_none() {
+        let bounds = WidgetBoundsInfo::new();
+        bounds.set_measure_inline(None);
+        push(&bounds, 100, true);
+
+        let (size, inline) = bounds.measure_cached(&metrics(100), true).unwrap();
+        assert_eq!(size, PxSize::new(Px(100), Px(10)));
+        assert!(inline.is_none());
+        assert!(bounds.measure_cached(&metrics(100), false).is_none());
+    }
+
+    #[test]
+    fn measure_cache_eviction() {
+        assert_eq!(MEASURE_CACHE_LEN, 4);
+
+        let bounds = WidgetBoundsInfo::new();
+        for w in 1..=5 {
+            push(&bounds, w, false);
+        }
+        assert_eq!(bounds.measure_cache_len(), MEASURE_CACHE_LEN);
+        assert!(bounds.measure_cached(&metrics(1), false).is_none());
+        for w in 2..=5 {
+            assert!(bounds.measure_cached(&metrics(w), false).is_some());
+        }
+
+        // hit moves the entry to the front, the least recently used is evicted.
+        assert!(bounds.measure_cached(&metrics(2), false).is_some());
+        push(&bounds, 6, false);
+        assert!(bounds.measure_cached(&metrics(2), false).is_some());
+        assert!(bounds.measure_cached(&metrics(3), false).is_none());
+
+        bounds.clear_measure_cache();
+        assert_eq!(bounds.measure_cache_len(), 0);
+    }
+
+    fn push_test_widget(builder: &mut WidgetInfoBuilder, name: &'static str, inner: impl FnMut(&mut WidgetInfoBuilder)) {
+        WINDOW.with_test_context(WidgetUpdateMode::Ignore, || {
+            WIDGET.with_context(&mut WidgetCtx::new(WidgetId::named(name)), WidgetUpdateMode::Ignore, || {
+                builder.push_widget(inner)
+            });
+        });
+    }
+
+    /// Tree with widgets `w` (root), `a`, `b`, `b-0`, `c` and `d`, `d` is out of the root bounds.
+    fn spatial_tree() -> WidgetInfoTree {
+        let _scope = APP.minimal();
+        let mut builder = WidgetInfoBuilder::new(
+            Arc::default(),
+            WindowId::named("w"),
+            AccessEnabled::empty(),
+            WidgetId::named("w"),
+            WidgetBoundsInfo::new(),
+            WidgetBorderInfo::new(),
+            1.fct(),
+        );
+        push_test_widget(&mut builder, "a", |_| {});
+        push_test_widget(&mut builder, "b", |b| push_test_widget(b, "b-0", |_| {}));
+        push_test_widget(&mut builder, "c", |_| {});
+        push_test_widget(&mut builder, "d", |_| {});
+        let tree = builder.finalize(None, false);
+
+        let bounds = |name: &str| match name {
+            "w" => (0, 0, 1000, 1000),
+            "a" => (10, 10, 100, 100),
+            "b" => (200, 10, 100, 100),
+            "b-0" => (220, 30, 20, 20),
+            "c" => (10, 200, 300, 50),
+            "d" => (900, 900, 200, 200),
+            n => unreachable!("{n}"),
+        };
+        for w in tree.root().self_and_descendants() {
+            let (x, y, width, height) = bounds(test_name(&w));
+            let size = PxSize::new(Px(width), Px(height));
+            let transform = PxTransform::translation(x as f32, y as f32);
+            let info = w.bounds_info();
+            info.set_outer_size(size);
+            info.set_inner_size(size);
+            info.set_outer_transform(transform, &tree);
+            info.set_inner_transform(transform, &tree, w.id(), w.parent().map(|p| p.inner_bounds()));
+        }
+        tree.after_render_update(FrameId::first());
+
+        tree
+    }
+
+    fn test_name(w: &WidgetInfo) -> &'static str {
+        w.id().name().as_static_str().unwrap()
+    }
+
+    fn names(iter: impl Iterator<Item = WidgetInfo>) -> Vec<&'static str> {
+        let mut r: Vec<_> = iter.map(|w| test_name(&w)).collect();
+        r.sort();
+        r
+    }
+
+    #[test]
+    fn spatial_bounds_include_out_of_bounds() {
+        let tree = spatial_tree();
+        assert!(!tree.get(WidgetId::named("d")).unwrap().bounds_info().is_in_bounds());
+        assert!(tree.get(WidgetId::named("b-0")).unwrap().bounds_info().is_in_bounds());
+        assert_eq!(tree.spatial_bounds(), PxRect::new(PxPoint::zero(), PxSize::new(Px(1100), Px(1100))));
+    }
+
+    #[test]
+    fn inner_contains_point() {
+        let tree = spatial_tree();
+
+        assert_eq!(names(tree.inner_contains(PxPoint::new(Px(50), Px(50)))), vec!["a", "w"]);
+        assert_eq!(names(tree.inner_contains(PxPoint::new(Px(225), Px(35)))), vec!["b", "b-0", "w"]);
+        assert_eq!(names(tree.inner_contains(PxPoint::new(Px(500), Px(500)))), vec!["w"]);
+        // out of root bounds
+        assert_eq!(names(tree.inner_contains(PxPoint::new(Px(1050), Px(1050)))), vec!["d"]);
+        assert!(names(tree.inner_contains(PxPoint::new(Px(-10), Px(-10)))).is_empty());
+    }
+
+    #[test]
+    fn inner_intersects_rect() {
+        let tree = spatial_tree();
+
+        let rect = PxRect::new(PxPoint::new(Px(90), Px(90)), PxSize::new(Px(150), Px(150)));
+        assert_eq!(names(tree.inner_intersects(rect)), vec!["a", "b", "c", "w"]);
+
+        let rect = PxRect::new(PxPoint::new(Px(400), Px(400)), PxSize::new(Px(10), Px(10)));
+        assert_eq!(names(tree.inner_intersects(rect)), vec!["w"]);
+
+        let rect = PxRect::new(PxPoint::new(Px(950), Px(950)), PxSize::new(Px(100), Px(100)));
+        assert_eq!(names(tree.inner_intersects(rect)), vec!["d", "w"]);
+    }
+
+    #[test]
+    fn inner_contained_rect() {
+        let tree = spatial_tree();
+
+        let rect = PxRect::new(PxPoint::new(Px(0), Px(0)), PxSize::new(Px(320), Px(150)));
+        assert_eq!(names(tree.inner_contained(rect)), vec!["a", "b", "b-0"]);
+
+        let rect = PxRect::new(PxPoint::new(Px(215), Px(25)), PxSize::new(Px(30), Px(30)));
+        assert_eq!(names(tree.inner_contained(rect)), vec!["b-0"]);
+
+        let rect = PxRect::new(PxPoint::new(Px(400), Px(400)), PxSize::new(Px(10), Px(10)));
+        assert!(names(tree.inner_contained(rect)).is_empty());
+    }
+
+    #[test]
+    fn ray_cast_sorted_by_distance() {
+        let tree = spatial_tree();
+
+        let hits: Vec<_> = tree
+            .ray_cast(PxPoint::new(Px(0), Px(40)), PxVector::new(Px(1), Px(0)), Px(1000))
+            .into_iter()
+            .map(|(w, d)| (test_name(&w), d))
+            .collect();
+        assert_eq!(hits, vec![("w", Px(0)), ("a", Px(10)), ("b", Px(200)), ("b-0", Px(220))]);
+
+        assert!(tree.ray_cast(PxPoint::new(Px(0), Px(40)), PxVector::zero(), Px(1000)).is_empty());
+        let hits = tree.ray_cast(PxPoint::new(Px(0), Px(40)), PxVector::new(Px(1), Px(0)), Px(5));
+        assert_eq!(hits.len(), 1);
+    }
+}
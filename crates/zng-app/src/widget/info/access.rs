@@ -177,6 +177,13 @@ impl<'a> WidgetAccessInfoBuilder<'a> {
         self.with_access(|a| a.set_state_source(AccessStateSource::ValueText(value)))
     }
 
+    /// Set the caret position and text selection, as string byte indexes in the value text or label.
+    ///
+    /// If `start` and `caret` are equal only the caret position is defined.
+    pub fn set_text_selection(&mut self, start: usize, caret: usize) {
+        self.with_access(|a| a.set_state(AccessState::TextSelection { start, caret }))
+    }
+
     /// Indicate that the widget can change, how the change can be announced, if `atomic`
     /// the entire widget must be re-read, if `busy` the screen reader must wait until the change completes.
     pub fn set_live(&mut self, indicator: LiveIndicator, atomic: bool, busy: bool) {
@@ -791,6 +798,21 @@ impl WidgetAccessInfo {
         get_state!(self.source.ValueText).cloned()
     }
 
+    /// Gets the text selection start and caret, as string byte indexes in the value text or label.
+    ///
+    /// See [`AccessState::TextSelection`] for more details.
+    ///
+    /// [`AccessState::TextSelection`]: zng_view_api::access::AccessState::TextSelection
+    pub fn text_selection(&self) -> Option<(usize, usize)> {
+        self.access().state.iter().find_map(|s| {
+            if let AccessState::TextSelection { start, caret } = s {
+                Some((*start, *caret))
+            } else {
+                None
+            }
+        })
+    }
+
     /// Gets the live indicator, atomic and busy.
     ///
     /// See [`AccessState::Live`] for more details.
@@ -1018,26 +1018,19 @@ impl WidgetMeasure {
         let bounds = WIDGET.bounds();
 
         let snap = metrics.snapshot();
-        if !WIDGET.layout_is_pending(&self.layout_widgets) {
-            let measure_uses = bounds.measure_metrics_used();
-            if bounds.measure_metrics().map(|m| m.masked_eq(&snap, measure_uses)).unwrap_or(false) {
-                let mut reused = false;
-                if let Some(inline) = self.inline() {
-                    if let Some(prev) = bounds.measure_inline() {
-                        *inline = prev;
-                        reused = true;
-                    }
-                } else {
-                    reused = bounds.measure_inline().is_none();
-                }
-
-                if reused {
-                    // LAYOUT.register_metrics_use(measure_uses); // measure does not propagate uses.
-                    return bounds.measure_outer_size();
-                }
+        let inline_requested = self.inline.is_some();
+        if WIDGET.layout_is_pending(&self.layout_widgets) {
+            bounds.clear_measure_cache();
+        } else if let Some((size, prev_inline)) = bounds.measure_cached(&snap, inline_requested) {
+            if let (Some(inline), Some(prev)) = (self.inline(), prev_inline) {
+                *inline = prev;
             }
+            // LAYOUT.register_metrics_use(measure_uses); // measure does not propagate uses.
+            return size;
         }
 
+        bounds.inc_measure_count();
+
         let parent_inline = self.inline.take();
         if LAYOUT.inline_constraints().is_some() {
             self.inline = Some(Default::default());
@@ -1045,7 +1038,6 @@ impl WidgetMeasure {
 
         let (measure_uses, size) = LAYOUT.capture_metrics_use(|| measure(self));
 
-        bounds.set_measure_metrics(Some(snap), measure_uses);
         bounds.set_measure_outer_size(size);
 
         if let Some(inline) = self.inline.take() {
@@ -1068,6 +1060,7 @@ impl WidgetMeasure {
         } else {
             bounds.set_measure_inline(None);
         }
+        bounds.push_measure_cache(snap, measure_uses, inline_requested);
         self.inline = parent_inline;
 
         size
@@ -1211,6 +1204,8 @@ impl WidgetLayout {
             }
         }
 
+        bounds.inc_layout_count();
+
         let parent_needs_ref_count = self.needs_ref_count.take();
         let parent_inline = self.inline.take();
         if LAYOUT.inline_constraints().is_some() && bounds.measure_inline().is_some() {
@@ -1463,7 +1458,7 @@ impl WidgetLayout {
                 info.bounds_info.set_can_auto_hide(true);
                 info.bounds_info.set_inner_offset(PxVector::zero());
                 info.bounds_info.set_child_offset(PxVector::zero());
-                info.bounds_info.set_measure_metrics(None, LayoutMask::empty());
+                info.bounds_info.clear_measure_cache();
                 info.bounds_info.set_metrics(None, LayoutMask::empty());
                 info.bounds_info.set_is_collapsed(true);
                 info.bounds_info.set_rendered(None, &tree);
@@ -1494,7 +1489,7 @@ impl WidgetLayout {
                 info.bounds_info.set_can_auto_hide(true);
                 info.bounds_info.set_inner_offset(PxVector::zero());
                 info.bounds_info.set_child_offset(PxVector::zero());
-                info.bounds_info.set_measure_metrics(None, LayoutMask::empty());
+                info.bounds_info.clear_measure_cache();
                 info.bounds_info.set_metrics(None, LayoutMask::empty());
                 info.bounds_info.set_is_collapsed(true);
             }
@@ -1525,7 +1520,7 @@ impl WidgetLayout {
                     info.bounds_info.set_can_auto_hide(true);
                     info.bounds_info.set_inner_offset(PxVector::zero());
                     info.bounds_info.set_child_offset(PxVector::zero());
-                    info.bounds_info.set_measure_metrics(None, LayoutMask::empty());
+                    info.bounds_info.clear_measure_cache();
                     info.bounds_info.set_metrics(None, LayoutMask::empty());
                     info.bounds_info.set_is_collapsed(true);
                 }
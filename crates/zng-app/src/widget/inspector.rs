@@ -5,7 +5,10 @@
 
 #[cfg(feature = "inspector")]
 mod inspector_only {
-    use std::sync::Arc;
+    use std::{collections::HashMap, sync::Arc};
+
+    use zng_app_context::app_local;
+    use zng_var::{types::WeakArcVar, AnyVar, AnyVarValue, AnyWeakVar, BoxedVar, Var, VarValue, WeakVar};
 
     use crate::widget::{
         builder::{InputKind, PropertyId},
@@ -40,15 +43,142 @@ mod inspector_only {
             }
         })
     }
+
+    /// Wraps a property var input in a var that can be overridden by the inspector.
+    pub(crate) fn overridable_var<T: VarValue>(var: BoxedVar<T>) -> BoxedVar<T> {
+        // widgets can be instantiated outside of an app, the inspector only edits app widgets.
+        if var.is_contextual() || !crate::APP.is_running() || is_overridable(var.var_ptr().raw_pointer() as usize) {
+            return var;
+        }
+
+        let slot = zng_var::var(OverrideSlot {
+            original: var.clone(),
+            active: var,
+            overridden: false,
+        });
+        let var = slot.flat_map(|s| s.active.clone()).boxed();
+
+        let mut slots = OVERRIDE_SLOTS.write();
+        if slots.slots.len() > slots.prune_len {
+            slots.slots.retain(|_, s| s.is_alive());
+            slots.prune_len = (slots.slots.len() * 2).max(1000);
+        }
+        slots.slots.insert(var.var_ptr().raw_pointer() as usize, Box::new(slot.downgrade()));
+
+        var
+    }
+
+    fn is_overridable(var: usize) -> bool {
+        OVERRIDE_SLOTS.read().slots.get(&var).map(|s| s.is_alive()).unwrap_or(false)
+    }
+
+    pub(crate) fn with_override_slot<R>(var: usize, f: impl FnOnce(&dyn super::AnyOverrideSlot) -> R) -> Option<R> {
+        let slots = OVERRIDE_SLOTS.read();
+        let s = slots.slots.get(&var)?;
+        if s.is_alive() {
+            Some(f(&**s))
+        } else {
+            None
+        }
+    }
+
+    app_local! {
+        static OVERRIDE_SLOTS: OverrideSlots = OverrideSlots {
+            slots: HashMap::new(),
+            prune_len: 1000,
+        };
+    }
+
+    struct OverrideSlots {
+        // var pointer -> weak slot
+        slots: HashMap<usize, Box<dyn super::AnyOverrideSlot>>,
+        prune_len: usize,
+    }
+
+    #[derive(Clone)]
+    struct OverrideSlot<T: VarValue> {
+        original: BoxedVar<T>,
+        active: BoxedVar<T>,
+        overridden: bool,
+    }
+    impl<T: VarValue> PartialEq for OverrideSlot<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.overridden == other.overridden && self.active.var_ptr() == other.active.var_ptr()
+        }
+    }
+    impl<T: VarValue> std::fmt::Debug for OverrideSlot<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("OverrideSlot")
+                .field("overridden", &self.overridden)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<T: VarValue> super::AnyOverrideSlot for WeakArcVar<OverrideSlot<T>> {
+        fn is_alive(&self) -> bool {
+            self.strong_count() > 0
+        }
+
+        fn is_overridden(&self) -> bool {
+            self.upgrade().map(|s| s.with(|s| s.overridden)).unwrap_or(false)
+        }
+
+        fn set(&self, value: Box<dyn AnyVarValue>) -> bool {
+            let slot = match self.upgrade() {
+                Some(s) => s,
+                None => return false,
+            };
+            let value = match value.into_any().downcast::<T>() {
+                Ok(v) => *v,
+                Err(_) => return false,
+            };
+            slot.with(|s| {
+                if s.overridden {
+                    // already an override var, just set it.
+                    let _ = s.active.set(value);
+                } else {
+                    slot.set(OverrideSlot {
+                        original: s.original.clone(),
+                        active: zng_var::var(value).boxed(),
+                        overridden: true,
+                    });
+                }
+            });
+            true
+        }
+
+        fn reset(&self) -> bool {
+            match self.upgrade() {
+                Some(slot) => slot.with(|s| {
+                    if s.overridden {
+                        slot.set(OverrideSlot {
+                            original: s.original.clone(),
+                            active: s.original.clone(),
+                            overridden: false,
+                        });
+                        true
+                    } else {
+                        false
+                    }
+                }),
+                None => false,
+            }
+        }
+    }
 }
 #[cfg(feature = "inspector")]
 pub(crate) use inspector_only::*;
 
+#[cfg(not(feature = "inspector"))]
+pub(crate) fn overridable_var<T: VarValue>(var: BoxedVar<T>) -> BoxedVar<T> {
+    var
+}
+
 use parking_lot::RwLock;
 use zng_state_map::StateId;
 use zng_txt::Txt;
 use zng_unique_id::static_id;
-use zng_var::{BoxedAnyVar, BoxedVar, VarValue};
+use zng_var::{AnyVarValue, BoxedAnyVar, BoxedVar, VarValue};
 
 use std::{any::TypeId, collections::HashMap, sync::Arc};
 
@@ -145,6 +275,80 @@ impl InspectorInfo {
             InstanceItem::Intrinsic { .. } => None,
         })
     }
+
+    /// Gets if the property var input can be overridden using [`override_var`].
+    ///
+    /// When built with the `"inspector"` feature all var inputs are instantiated inside an override var, except
+    /// contextual vars.
+    ///
+    /// [`override_var`]: Self::override_var
+    pub fn can_override(&self, property: PropertyId, member: usize) -> bool {
+        self.with_override_slot(property, member, |_| true).unwrap_or(false)
+    }
+
+    /// Gets if the property var input is currently overridden.
+    pub fn is_overridden(&self, property: PropertyId, member: usize) -> bool {
+        self.with_override_slot(property, member, |s| s.is_overridden()).unwrap_or(false)
+    }
+
+    /// Override the property var input value.
+    ///
+    /// The property var is replaced with a new var that is initialized with the `value`, if the input is already
+    /// overridden the `value` is set on the override var. The original var is retained and can be restored using [`reset_override`].
+    ///
+    /// Returns `false` if the property var cannot be overridden or if the `value` type does not match.
+    ///
+    /// [`reset_override`]: Self::reset_override
+    pub fn override_var(&self, property: PropertyId, member: usize, value: Box<dyn AnyVarValue>) -> bool {
+        self.with_override_slot(property, member, move |s| s.set(value)).unwrap_or(false)
+    }
+
+    /// Restore the original property var input.
+    ///
+    /// Returns `true` if the input was overridden.
+    pub fn reset_override(&self, property: PropertyId, member: usize) -> bool {
+        self.with_override_slot(property, member, |s| s.reset()).unwrap_or(false)
+    }
+
+    /// Restore the original var of all overridden property var inputs.
+    ///
+    /// Returns `true` if any input was overridden.
+    pub fn reset_overrides(&self) -> bool {
+        let mut any = false;
+        for (args, _) in self.properties() {
+            for (i, input) in args.property().inputs.iter().enumerate() {
+                if matches!(input.kind, InputKind::Var) {
+                    any |= self.reset_override(args.id(), i);
+                }
+            }
+        }
+        any
+    }
+
+    #[cfg(feature = "inspector")]
+    fn with_override_slot<R>(&self, property: PropertyId, member: usize, f: impl FnOnce(&dyn AnyOverrideSlot) -> R) -> Option<R> {
+        let (args, _) = self.properties().find(|(a, _)| a.id() == property)?;
+        if !matches!(args.property().inputs.get(member)?.kind, InputKind::Var) {
+            return None;
+        }
+        let var = args.var(member).var_ptr().raw_pointer() as usize;
+        with_override_slot(var, f)
+    }
+
+    #[cfg(not(feature = "inspector"))]
+    fn with_override_slot<R>(&self, property: PropertyId, member: usize, f: impl FnOnce(&dyn AnyOverrideSlot) -> R) -> Option<R> {
+        let _ = (property, member, f);
+        None
+    }
+}
+
+/// Type erased override var slot.
+#[cfg_attr(not(feature = "inspector"), allow(dead_code))]
+pub(crate) trait AnyOverrideSlot: Send + Sync {
+    fn is_alive(&self) -> bool;
+    fn is_overridden(&self) -> bool;
+    fn set(&self, value: Box<dyn AnyVarValue>) -> bool;
+    fn reset(&self) -> bool;
 }
 
 /// Extensions methods for [`WidgetInfo`].
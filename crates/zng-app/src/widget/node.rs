@@ -32,7 +32,7 @@ use crate::{
 };
 
 use super::{
-    base::{Parallel, PARALLEL_VAR},
+    base::{Parallel, PARALLEL_MIN_CHUNK_VAR, PARALLEL_VAR},
     info::{WidgetInfoBuilder, WidgetLayout, WidgetMeasure},
     WidgetId, WidgetUpdateMode, WIDGET,
 };
@@ -475,7 +475,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     ///
     /// The behavior of some list implementations depend on this call, manually initializing nodes is an error.
     fn init_all(&mut self) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::INIT) {
+        if is_parallel(self.len(), Parallel::INIT) {
             self.par_each(|_, c| {
                 c.init();
             });
@@ -490,7 +490,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     ///
     /// The behavior of some list implementations depend on this call, manually deiniting nodes is an error.
     fn deinit_all(&mut self) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::DEINIT) {
+        if is_parallel(self.len(), Parallel::DEINIT) {
             self.par_each(|_, c| {
                 c.deinit();
             });
@@ -503,7 +503,7 @@ pub trait UiNodeList: UiNodeListBoxed {
 
     /// Rebuilds the list in a context, all node info is rebuilt.
     fn info_all(&mut self, info: &mut WidgetInfoBuilder) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::INFO) {
+        if is_parallel(self.len(), Parallel::INFO) {
             let p_info = self.par_fold_reduce(
                 || info.parallel_split(),
                 |mut info, _, node| {
@@ -529,7 +529,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     fn update_all(&mut self, updates: &WidgetUpdates, observer: &mut dyn UiNodeListObserver) {
         let _ = observer;
 
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::UPDATE) {
+        if is_parallel(self.len(), Parallel::UPDATE) {
             self.par_each(|_, c| {
                 c.update(updates);
             });
@@ -544,7 +544,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     ///
     /// The behavior of some list implementations depend on this call, manually notifying nodes is an error.
     fn event_all(&mut self, update: &EventUpdate) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::EVENT) {
+        if is_parallel(self.len(), Parallel::EVENT) {
             self.par_each(|_, c| {
                 c.event(update);
             });
@@ -588,7 +588,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     ///
     /// [`for_each`]: UiNodeList::for_each
     fn render_all(&mut self, frame: &mut FrameBuilder) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::RENDER) {
+        if is_parallel(self.len(), Parallel::RENDER) {
             let p_frame = self.par_fold_reduce(
                 || frame.parallel_split(),
                 |mut frame, _, node| {
@@ -615,7 +615,7 @@ pub trait UiNodeList: UiNodeListBoxed {
     ///
     /// [`for_each`]: UiNodeList::for_each
     fn render_update_all(&mut self, update: &mut FrameUpdate) {
-        if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::RENDER) {
+        if is_parallel(self.len(), Parallel::RENDER) {
             let p_update = self.par_fold_reduce(
                 || update.parallel_split(),
                 |mut update, _, node| {
@@ -683,6 +683,14 @@ pub trait UiNodeList: UiNodeListBoxed {
     }
 }
 
+/// If a list `op` of `len` nodes runs in parallel.
+///
+/// Lists that cannot be split in at least two chunks of [`PARALLEL_MIN_CHUNK_VAR`] nodes run in the caller thread, this avoids
+/// the cost of splitting and folding the info, layout and render builders when only one task would run.
+fn is_parallel(len: usize, op: Parallel) -> bool {
+    len > 1 && len / 2 >= PARALLEL_MIN_CHUNK_VAR.get() && PARALLEL_VAR.get().contains(op)
+}
+
 fn default_measure_each<F, S>(self_: &mut impl UiNodeList, wm: &mut WidgetMeasure, measure: F, fold_size: S) -> PxSize
 where
     F: Fn(usize, &mut BoxedUiNode, &mut WidgetMeasure) -> PxSize + Send + Sync,
@@ -700,7 +708,7 @@ where
     F: Fn(usize, &mut BoxedUiNode, &mut WidgetMeasure) -> PxSize + Send + Sync,
     S: Fn(PxSize, PxSize) -> PxSize + Send + Sync,
 {
-    if self_.len() > 1 && PARALLEL_VAR.get().contains(Parallel::LAYOUT) {
+    if is_parallel(self_.len(), Parallel::LAYOUT) {
         // fold a tuple of `(wm, size)`
         let (pwm, size) = self_.par_fold_reduce(
             || (wm.parallel_split(), PxSize::zero()),
@@ -747,7 +755,7 @@ where
     F: Fn(usize, &mut BoxedUiNode, &mut WidgetLayout) -> PxSize + Send + Sync,
     S: Fn(PxSize, PxSize) -> PxSize + Send + Sync,
 {
-    if self_.len() > 1 && PARALLEL_VAR.get().contains(Parallel::LAYOUT) {
+    if is_parallel(self_.len(), Parallel::LAYOUT) {
         // fold a tuple of `(wl, size)`
         let (pwl, size) = self_.par_fold_reduce(
             || (wl.parallel_split(), PxSize::zero()),
@@ -1,20 +1,27 @@
 use std::{
     cmp::Ordering,
-    mem, ops,
+    fmt, mem, ops,
     sync::{
         atomic::{AtomicBool, Ordering::Relaxed},
         Arc,
     },
+    time::Duration,
 };
 
 use parking_lot::Mutex;
 use task::ParallelIteratorExt;
 use zng_app_context::context_local;
-use zng_layout::unit::{Factor, PxSize, PxTransform, PxVector};
+use zng_layout::unit::{euclid, Factor, FactorUnits as _, Px, PxSize, PxTransform, PxVector, TimeUnits as _};
 use zng_state_map::StateId;
 use zng_task::{self as task, rayon::prelude::*};
 use zng_unique_id::static_id;
-use zng_var::{animation::Transitionable, impl_from_and_into_var};
+use zng_var::{
+    animation::{
+        easing::{self, EasingStep, EasingTime},
+        AnimationHandle, Transitionable,
+    },
+    context_var, impl_from_and_into_var, var, AnyVar, ArcVar, Var, VarHandles,
+};
 
 use super::*;
 
@@ -74,9 +81,9 @@ use crate::{
     render::{FrameBuilder, FrameUpdate, FrameValueKey},
     update::{EventUpdate, WidgetUpdates, UPDATES},
     widget::{
-        base::{Parallel, PARALLEL_VAR},
+        base::{Parallel, PARALLEL_MIN_CHUNK_VAR, PARALLEL_VAR},
         info::{WidgetInfo, WidgetInfoBuilder, WidgetLayout, WidgetMeasure},
-        WidgetUpdateMode, WIDGET,
+        AnyVarSubscribe as _, UpdateOp, WidgetUpdateMode, WIDGET,
     },
 };
 
@@ -152,7 +159,12 @@ fn par_each_impl<F>(self_: &mut Vec<BoxedUiNode>, f: F)
 where
     F: Fn(usize, &mut BoxedUiNode) + Send + Sync,
 {
-    self_.par_iter_mut().enumerate().with_ctx().for_each(|(i, n)| f(i, n));
+    self_
+        .par_iter_mut()
+        .enumerate()
+        .with_min_len(PARALLEL_MIN_CHUNK_VAR.get().max(1))
+        .with_ctx()
+        .for_each(|(i, n)| f(i, n));
 }
 
 fn vec_par_fold_reduce<T, I, F, R>(self_: &mut Vec<BoxedUiNode>, identity: I, fold: F, reduce: R) -> T
@@ -181,6 +193,7 @@ where
     self_
         .par_iter_mut()
         .enumerate()
+        .with_min_len(PARALLEL_MIN_CHUNK_VAR.get().max(1))
         .with_ctx()
         .fold(&identity, move |a, (i, n)| fold(a, i, n))
         .reduce(&identity, reduce)
@@ -1977,6 +1990,8 @@ where
 
     z_map: Vec<u64>,
     z_naturally_sorted: bool,
+
+    transition: Option<PanelTransitionState>,
 }
 impl PanelList<DefaultPanelListData> {
     /// New from `list` and default data.
@@ -2002,6 +2017,7 @@ where
             info_id: None,
             z_map: vec![],
             z_naturally_sorted: false,
+            transition: None,
         }
     }
 
@@ -2046,6 +2062,7 @@ where
             info_id: info_id.map(|i| (i, 0, true)),
             z_map: vec![],
             z_naturally_sorted: false,
+            transition: None,
         }
     }
 
@@ -2326,13 +2343,95 @@ where
     /// you can call [`commit`] directly in a `for_each` iteration if that iteration is the
     /// last in the layout pass.
     ///
+    /// This method also starts the [`PANEL_TRANSITION_VAR`] animation if the items layout changed.
+    ///
     /// [`commit`]: PanelListData::commit
     pub fn commit_data(&mut self) -> PanelListDataChanges {
         let mut changes = PanelListDataChanges::empty();
         for data in self.data.iter_mut() {
             changes |= data.get_mut().commit();
         }
-        changes
+        changes | self.commit_transition()
+    }
+
+    fn commit_transition(&mut self) -> PanelListDataChanges {
+        let config = PANEL_TRANSITION_VAR.get();
+        if config.is_disabled() {
+            return match self.transition.take() {
+                Some(t) if t.is_animating() => PanelListDataChanges::DEFINE_REFERENCE_FRAME,
+                _ => PanelListDataChanges::empty(),
+            };
+        }
+
+        let t = self.transition.get_or_insert_with(|| {
+            let progress = var(1.fct());
+            let mut handles = VarHandles::dummy();
+            handles.push(progress.subscribe(UpdateOp::Update, WIDGET.id()));
+            handles.push(progress.subscribe(UpdateOp::RenderUpdate, WIDGET.id()));
+            PanelTransitionState {
+                items: vec![],
+                laid_out: false,
+                progress,
+                animation: AnimationHandle::dummy(),
+                started: false,
+                _handles: handles,
+                opacity_key: FrameValueKey::new_unique(),
+            }
+        });
+        t.items.resize_with(self.list.len(), Default::default);
+
+        let layout: Vec<_> = (0..self.list.len())
+            .map(|i| {
+                let offset = self.data[i].get_mut().child_offset();
+                let size = self
+                    .list
+                    .with_node(i, |c| c.with_context(WidgetUpdateMode::Ignore, || WIDGET.bounds().outer_size()))
+                    .unwrap_or_default();
+                (offset, size)
+            })
+            .collect();
+
+        let laid_out = mem::replace(&mut t.laid_out, true);
+        let start = laid_out && t.items.iter().zip(&layout).any(|(item, l)| item.layout != Some(*l));
+        if !start {
+            if !laid_out {
+                // first layout, no transition
+                for (item, l) in t.items.iter_mut().zip(layout) {
+                    item.layout = Some(l);
+                }
+            }
+            return PanelListDataChanges::empty();
+        }
+
+        let p = t.progress();
+        for (item, l) in t.items.iter_mut().zip(layout) {
+            let (to_offset, to_size) = ItemTransition::to(l);
+            if item.layout.is_some() {
+                let changed = item.layout != Some(l);
+                if changed || item.animating {
+                    // rebase from the current visual
+                    let (offset, size, opacity) = item.visual(p);
+                    item.from_offset = offset;
+                    item.from_size = if config.scale { size } else { to_size };
+                    item.from_opacity = opacity;
+                    item.animating = true;
+                }
+            } else {
+                // inserted
+                item.from_offset = to_offset;
+                item.from_size = to_size;
+                item.from_opacity = if config.fade_in { 0.0 } else { 1.0 };
+                item.animating = true;
+            }
+            item.layout = Some(l);
+        }
+
+        let easing = config.easing.clone();
+        t.animation = t.progress.set_ease(0.fct(), 1.fct(), config.duration, move |t| easing(t));
+        t.started = false;
+
+        // items that animate need a reference frame
+        PanelListDataChanges::DEFINE_REFERENCE_FRAME
     }
 
     /// Key used to define reference frames for each item.
@@ -2401,6 +2500,7 @@ where
 
     fn deinit_all(&mut self) {
         self.list.deinit_all();
+        self.transition = None;
     }
 
     fn info_all(&mut self, info: &mut WidgetInfoBuilder) {
@@ -2434,9 +2534,21 @@ where
     }
 
     fn update_all(&mut self, updates: &WidgetUpdates, observer: &mut dyn UiNodeListObserver) {
+        if let Some(t) = &mut self.transition {
+            if t.progress.is_new() {
+                t.started = true;
+                if t.animation.is_stopped() && t.is_animating() {
+                    // transition finished, render without the transition reference frames
+                    t.items.iter_mut().for_each(|i| i.animating = false);
+                    WIDGET.render();
+                }
+            }
+        }
+
         let mut observer = PanelObserver {
             changed: false,
             data: &mut self.data,
+            transition: self.transition.as_mut().map(|t| &mut t.items),
             observer,
         };
         let resort = Z_INDEX.with(WIDGET.id(), || self.list.update_all(updates, &mut observer));
@@ -2461,26 +2573,17 @@ where
 
     fn render_all(&mut self, frame: &mut FrameBuilder) {
         let offset_key = self.offset_key;
+        let transition = self.transition.take();
+        let tr = transition
+            .as_ref()
+            .filter(|t| t.is_animating())
+            .map(|t| (&t.items[..], t.progress(), t.opacity_key));
+
         if self.z_naturally_sorted && self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::RENDER) {
             let b = self.par_fold_reduce(
                 || frame.parallel_split(),
                 |mut frame, i, child, data| {
-                    let offset = data.child_offset();
-                    if data.define_reference_frame() {
-                        frame.push_reference_frame(
-                            (offset_key, i as u32).into(),
-                            offset_key.bind_child(i as u32, offset.into(), false),
-                            true,
-                            true,
-                            |frame| {
-                                child.render(frame);
-                            },
-                        );
-                    } else {
-                        frame.push_child(offset, |frame| {
-                            child.render(frame);
-                        });
-                    }
+                    render_panel_item(&mut frame, offset_key, i, child, data, tr);
                     frame
                 },
                 |mut a, b| {
@@ -2491,43 +2594,26 @@ where
             frame.parallel_fold(b);
         } else {
             self.for_each_z_sorted(|i, child, data| {
-                let offset = data.child_offset();
-                if data.define_reference_frame() {
-                    frame.push_reference_frame(
-                        (offset_key, i as u32).into(),
-                        offset_key.bind_child(i as u32, offset.into(), false),
-                        true,
-                        true,
-                        |frame| {
-                            child.render(frame);
-                        },
-                    );
-                } else {
-                    frame.push_child(offset, |frame| {
-                        child.render(frame);
-                    });
-                }
+                render_panel_item(frame, offset_key, i, child, data, tr);
             });
         }
+
+        self.transition = transition;
     }
 
     fn render_update_all(&mut self, update: &mut FrameUpdate) {
         let offset_key = self.offset_key;
+        let transition = self.transition.take();
+        let tr = transition
+            .as_ref()
+            .filter(|t| t.is_animating())
+            .map(|t| (&t.items[..], t.progress(), t.opacity_key));
 
         if self.len() > 1 && PARALLEL_VAR.get().contains(Parallel::RENDER) {
             let b = self.par_fold_reduce(
                 || update.parallel_split(),
                 |mut update, i, child, data| {
-                    let offset = data.child_offset();
-                    if data.define_reference_frame() {
-                        update.with_transform(offset_key.update_child(i as u32, offset.into(), false), true, |update| {
-                            child.render_update(update);
-                        });
-                    } else {
-                        update.with_child(offset, |update| {
-                            child.render_update(update);
-                        });
-                    }
+                    render_update_panel_item(&mut update, offset_key, i, child, data, tr);
                     update
                 },
                 |mut a, b| {
@@ -2538,19 +2624,95 @@ where
             update.parallel_fold(b);
         } else {
             self.for_each(|i, child, data| {
-                let offset = data.child_offset();
-                if data.define_reference_frame() {
-                    update.with_transform(offset_key.update_child(i as u32, offset.into(), false), true, |update| {
-                        child.render_update(update);
-                    });
-                } else {
-                    update.with_child(offset, |update| {
-                        child.render_update(update);
-                    });
+                render_update_panel_item(update, offset_key, i, child, data, tr);
+            });
+        }
+
+        self.transition = transition;
+    }
+}
+
+type ItemTransitionRender<'a> = Option<(&'a [ItemTransition], f32, FrameValueKey<f32>)>;
+
+fn render_panel_item<D: PanelListData>(
+    frame: &mut FrameBuilder,
+    offset_key: FrameValueKey<PxTransform>,
+    i: usize,
+    child: &mut BoxedUiNode,
+    data: &mut D,
+    transition: ItemTransitionRender,
+) {
+    if let Some((items, p, opacity_key)) = transition {
+        if let Some(item) = items.get(i).filter(|t| t.animating) {
+            let (transform, opacity) = item.transform(p);
+            frame.push_reference_frame(
+                (offset_key, i as u32).into(),
+                offset_key.bind_child(i as u32, transform, true),
+                true,
+                true,
+                |frame| {
+                    if item.from_opacity < 1.0 {
+                        frame.push_opacity(opacity_key.bind_child(i as u32, opacity, true), |frame| {
+                            child.render(frame);
+                        });
+                    } else {
+                        child.render(frame);
+                    }
+                },
+            );
+            return;
+        }
+    }
+
+    let offset = data.child_offset();
+    if data.define_reference_frame() {
+        frame.push_reference_frame(
+            (offset_key, i as u32).into(),
+            offset_key.bind_child(i as u32, offset.into(), false),
+            true,
+            true,
+            |frame| {
+                child.render(frame);
+            },
+        );
+    } else {
+        frame.push_child(offset, |frame| {
+            child.render(frame);
+        });
+    }
+}
+
+fn render_update_panel_item<D: PanelListData>(
+    update: &mut FrameUpdate,
+    offset_key: FrameValueKey<PxTransform>,
+    i: usize,
+    child: &mut BoxedUiNode,
+    data: &mut D,
+    transition: ItemTransitionRender,
+) {
+    if let Some((items, p, opacity_key)) = transition {
+        if let Some(item) = items.get(i).filter(|t| t.animating) {
+            let (transform, opacity) = item.transform(p);
+            update.with_transform(offset_key.update_child(i as u32, transform, true), true, |update| {
+                if item.from_opacity < 1.0 {
+                    update.update_f32(opacity_key.update_child(i as u32, opacity, true));
                 }
+                child.render_update(update);
             });
+            return;
         }
     }
+
+    let offset = data.child_offset();
+    if data.define_reference_frame() {
+        update.with_transform(offset_key.update_child(i as u32, offset.into(), false), true, |update| {
+            child.render_update(update);
+        });
+    } else {
+        update.with_child(offset, |update| {
+            child.render_update(update);
+        });
+    }
 }
 
 bitflags::bitflags! {
@@ -2643,6 +2805,7 @@ where
 {
     changed: bool,
     data: &'d mut Vec<Mutex<D>>,
+    transition: Option<&'d mut Vec<ItemTransition>>,
     observer: &'d mut dyn UiNodeListObserver,
 }
 impl<'d, D> UiNodeListObserver for PanelObserver<'d, D>
@@ -2656,18 +2819,31 @@ where
     fn reset(&mut self) {
         self.changed = true;
         self.data.clear();
+        if let Some(t) = &mut self.transition {
+            t.clear();
+        }
         self.observer.reset();
     }
 
     fn inserted(&mut self, index: usize) {
         self.changed = true;
         self.data.insert(index, Default::default());
+        if let Some(t) = &mut self.transition {
+            if index <= t.len() {
+                t.insert(index, Default::default());
+            }
+        }
         self.observer.inserted(index);
     }
 
     fn removed(&mut self, index: usize) {
         self.changed = true;
         self.data.remove(index);
+        if let Some(t) = &mut self.transition {
+            if index < t.len() {
+                t.remove(index);
+            }
+        }
         self.observer.removed(index);
     }
 
@@ -2675,6 +2851,200 @@ where
         self.changed = true;
         let item = self.data.remove(removed_index);
         self.data.insert(inserted_index, item);
+        if let Some(t) = &mut self.transition {
+            if removed_index < t.len() {
+                let item = t.remove(removed_index);
+                t.insert(inserted_index.min(t.len()), item);
+            }
+        }
         self.observer.moved(removed_index, inserted_index);
     }
 }
+
+context_var! {
+    /// Defines the layout transition of [`PanelList`] items in a widget and descendants.
+    ///
+    /// This variable can be set using the `panel_transition` property.
+    ///
+    /// Is disabled by default.
+    pub static PANEL_TRANSITION_VAR: PanelTransition = PanelTransition::disabled();
+}
+
+/// Layout transition of [`PanelList`] items.
+///
+/// When enabled the panel items animate from the previous layout to the new layout when children are inserted, removed or moved
+/// or when the panel layout changes, instead of jumping to the new position. The items are laid out in the new position
+/// immediately and a render transform animates from the previous offset and size (*FLIP* animation), inserted items also fade-in.
+/// Removed items are removed immediately, the other items transition to the new position.
+///
+/// The first layout of the panel does not animate. All panels that use [`PanelList`] support this transition, the
+/// config is read from [`PANEL_TRANSITION_VAR`] on [`PanelList::commit_data`].
+#[derive(Clone)]
+pub struct PanelTransition {
+    /// Transition duration.
+    ///
+    /// Default is `200.ms()`.
+    pub duration: Duration,
+    /// Transition easing function.
+    ///
+    /// Default is cubic ease-out.
+    pub easing: Arc<dyn Fn(EasingTime) -> EasingStep + Send + Sync>,
+    /// If inserted items fade-in.
+    ///
+    /// Default is `true`.
+    pub fade_in: bool,
+    /// If resized items animate the size using a scale transform.
+    ///
+    /// Default is `true`.
+    pub scale: bool,
+}
+impl fmt::Debug for PanelTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanelTransition")
+            .field("duration", &self.duration)
+            .field("fade_in", &self.fade_in)
+            .field("scale", &self.scale)
+            .finish_non_exhaustive()
+    }
+}
+impl PartialEq for PanelTransition {
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration
+            && Arc::ptr_eq(&self.easing, &other.easing)
+            && self.fade_in == other.fade_in
+            && self.scale == other.scale
+    }
+}
+impl Default for PanelTransition {
+    fn default() -> Self {
+        Self::new(200.ms(), |t| easing::ease_out(easing::cubic, t))
+    }
+}
+impl PanelTransition {
+    /// New custom transition, with fade-in and scale enabled.
+    pub fn new(duration: Duration, easing: impl Fn(EasingTime) -> EasingStep + Send + Sync + 'static) -> Self {
+        Self {
+            duration,
+            easing: Arc::new(easing),
+            fade_in: true,
+            scale: true,
+        }
+    }
+
+    /// No transition, items jump to the new layout.
+    pub fn disabled() -> Self {
+        Self::new(Duration::ZERO, easing::none)
+    }
+
+    /// If this config represents [`disabled`].
+    ///
+    /// [`disabled`]: Self::disabled
+    pub fn is_disabled(&self) -> bool {
+        self.duration == Duration::ZERO
+    }
+}
+impl_from_and_into_var! {
+    /// Default transition with custom duration.
+    fn from(duration: Duration) -> PanelTransition {
+        PanelTransition {
+            duration,
+            ..Default::default()
+        }
+    }
+
+    /// Returns default config for `true`, [`disabled`] for `false`.
+    ///
+    /// [`disabled`]: PanelTransition::disabled
+    fn from(enabled: bool) -> PanelTransition {
+        if enabled {
+            PanelTransition::default()
+        } else {
+            PanelTransition::disabled()
+        }
+    }
+
+    fn from<F: Fn(EasingTime) -> EasingStep + Send + Sync + 'static>((duration, easing): (Duration, F)) -> PanelTransition {
+        PanelTransition::new(duration, easing)
+    }
+
+    fn from((duration, easing): (Duration, easing::EasingFn)) -> PanelTransition {
+        PanelTransition::new(duration, easing.ease_fn())
+    }
+}
+
+struct PanelTransitionState {
+    items: Vec<ItemTransition>,
+    laid_out: bool,
+    progress: ArcVar<Factor>,
+    animation: AnimationHandle,
+    // if the `progress` updated after the animation started
+    started: bool,
+    _handles: VarHandles,
+    opacity_key: FrameValueKey<f32>,
+}
+impl PanelTransitionState {
+    fn is_animating(&self) -> bool {
+        self.items.iter().any(|i| i.animating)
+    }
+
+    fn progress(&self) -> f32 {
+        if !self.started {
+            0.0
+        } else if self.animation.is_stopped() {
+            1.0
+        } else {
+            self.progress.get().0
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ItemTransition {
+    /// Offset and outer size of the last layout, `None` for inserted items not laid out yet.
+    layout: Option<(PxVector, PxSize)>,
+    from_offset: euclid::Vector2D<f32, Px>,
+    from_size: euclid::Size2D<f32, Px>,
+    from_opacity: f32,
+    animating: bool,
+}
+impl Default for ItemTransition {
+    fn default() -> Self {
+        Self {
+            layout: None,
+            from_offset: euclid::Vector2D::zero(),
+            from_size: euclid::Size2D::zero(),
+            from_opacity: 1.0,
+            animating: false,
+        }
+    }
+}
+impl ItemTransition {
+    fn to((offset, size): (PxVector, PxSize)) -> (euclid::Vector2D<f32, Px>, euclid::Size2D<f32, Px>) {
+        (
+            euclid::vec2(offset.x.0 as f32, offset.y.0 as f32),
+            euclid::size2(size.width.0 as f32, size.height.0 as f32),
+        )
+    }
+
+    /// Visual offset, size and opacity at the transition progress `p`.
+    fn visual(&self, p: f32) -> (euclid::Vector2D<f32, Px>, euclid::Size2D<f32, Px>, f32) {
+        let (to_offset, to_size) = Self::to(self.layout.unwrap_or_default());
+        if !self.animating {
+            return (to_offset, to_size, 1.0);
+        }
+        (
+            self.from_offset.lerp(to_offset, p),
+            self.from_size.lerp(to_size, p),
+            self.from_opacity + (1.0 - self.from_opacity) * p,
+        )
+    }
+
+    /// Render transform and opacity at the transition progress `p`.
+    fn transform(&self, p: f32) -> (PxTransform, f32) {
+        let (_, to_size) = Self::to(self.layout.unwrap_or_default());
+        let (offset, size, opacity) = self.visual(p);
+        let scale = |v: f32, to: f32| if to > 0.0 { v / to } else { 1.0 };
+        let transform = PxTransform::scale(scale(size.width, to_size.width), scale(size.height, to_size.height)).then_translate(offset);
+        (transform, opacity)
+    }
+}
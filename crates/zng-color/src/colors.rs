@@ -7,7 +7,7 @@
 
 use zng_var::context_var;
 
-use crate::{light_dark, LightDark};
+use crate::{theme::SURFACE_COLOR_VAR, LightDark};
 
 use super::Rgba;
 
@@ -73,8 +73,11 @@ context_var! {
 
     /// Seed color for widget background.
     ///
+    /// Is the [`SURFACE_COLOR_VAR`] by default.
+    ///
     /// See also [`LightDarkVarExt`] for helper methods implemented on [`LightDark`] variables.
     ///
     /// [`LightDarkVarExt`]: crate::LightDarkVarExt
-    pub static BASE_COLOR_VAR: LightDark = light_dark(WHITE, BLACK);
+    /// [`SURFACE_COLOR_VAR`]: crate::theme::SURFACE_COLOR_VAR
+    pub static BASE_COLOR_VAR: LightDark = SURFACE_COLOR_VAR;
 }
@@ -512,6 +512,22 @@ impl_from_and_into_var! {
         ColorStop::new_positional(positional_color)
     }
 }
+impl Transitionable for GradientStop {
+    /// Interpolates stops of the same kind, or changes in one step between kinds.
+    fn lerp(self, to: &Self, step: EasingStep) -> Self {
+        match (self, to) {
+            (GradientStop::Color(s), GradientStop::Color(t)) => GradientStop::Color(s.lerp(t, step)),
+            (GradientStop::ColorHint(s), GradientStop::ColorHint(t)) => GradientStop::ColorHint(s.lerp(t, step)),
+            (s, t) => {
+                if step < 1.fct() {
+                    s
+                } else {
+                    t.clone()
+                }
+            }
+        }
+    }
+}
 impl Transitionable for ColorStop {
     fn lerp(self, to: &Self, step: EasingStep) -> Self {
         Self {
@@ -611,6 +627,10 @@ pub struct GradientStops {
 
     /// Last color stop.
     pub end: ColorStop,
+
+    /// Color space the colors are interpolated in.
+    #[serde(default)]
+    pub space: GradientSpace,
 }
 impl fmt::Debug for GradientStops {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -619,13 +639,18 @@ impl fmt::Debug for GradientStops {
                 .field("start", &self.start)
                 .field("middle", &self.middle)
                 .field("end", &self.end)
+                .field("space", &self.space)
                 .finish()
         } else {
             write!(f, "stops![{:?}, ", self.start)?;
             for stop in &self.middle {
                 write!(f, "{stop:?}, ")?;
             }
-            write!(f, "{:?}]", self.end)
+            write!(f, "{:?}]", self.end)?;
+            if self.space != GradientSpace::Srgb {
+                write!(f, ".with_space({:?})", self.space)?;
+            }
+            Ok(())
         }
     }
 }
@@ -643,6 +668,7 @@ impl GradientStops {
                 color: end.into(),
                 offset: 100.pct().into(),
             },
+            space: GradientSpace::Srgb,
         }
     }
 
@@ -672,6 +698,7 @@ impl GradientStops {
                 start: Self::start_missing(),
                 middle: vec![],
                 end: Self::end_missing(colors::BLACK),
+                space: GradientSpace::Srgb,
             }
         } else if colors.len() == 1 {
             let color = colors[0].into();
@@ -682,6 +709,7 @@ impl GradientStops {
                 },
                 middle: vec![],
                 end: Self::end_missing(color),
+                space: GradientSpace::Srgb,
             }
         } else {
             let last = colors.len() - 1;
@@ -709,6 +737,7 @@ impl GradientStops {
                     color: colors[last].into(),
                     offset: 100.pct().into(),
                 },
+                space: GradientSpace::Srgb,
             }
         }
     }
@@ -732,6 +761,7 @@ impl GradientStops {
                 start: Self::start_missing(),
                 middle: vec![],
                 end: Self::end_missing(colors::BLACK),
+                space: GradientSpace::Srgb,
             }
         } else if colors.len() == 1 {
             let tran = 0.5 * tran;
@@ -754,6 +784,7 @@ impl GradientStops {
                     }),
                 ],
                 end,
+                space: GradientSpace::Srgb,
             }
         } else {
             let last = colors.len() - 1;
@@ -802,7 +833,12 @@ impl GradientStops {
                 .into(),
             );
 
-            GradientStops { start, middle, end }
+            GradientStops {
+                start,
+                middle,
+                end,
+                space: GradientSpace::Srgb,
+            }
         }
     }
 
@@ -815,6 +851,7 @@ impl GradientStops {
                 start: Self::start_missing(),
                 middle: vec![],
                 end: Self::end_missing(colors::BLACK),
+                space: GradientSpace::Srgb,
             }
         } else if stops.len() == 1 {
             let start = stops[0].into();
@@ -822,6 +859,7 @@ impl GradientStops {
                 end: Self::end_missing(start.color),
                 start,
                 middle: vec![],
+                space: GradientSpace::Srgb,
             }
         } else {
             let last = stops.len() - 1;
@@ -829,10 +867,17 @@ impl GradientStops {
                 start: stops[0].into(),
                 middle: stops[1..last].iter().map(|&c| GradientStop::Color(c.into())).collect(),
                 end: stops[last].into(),
+                space: GradientSpace::Srgb,
             }
         }
     }
 
+    /// Set the color space the colors are interpolated in.
+    pub fn with_space(mut self, space: GradientSpace) -> Self {
+        self.space = space;
+        self
+    }
+
     /// Set the alpha of all colors in the gradient.
     pub fn set_alpha<A: Into<RgbaComponent>>(&mut self, alpha: A) {
         let alpha = alpha.into();
@@ -1010,10 +1055,12 @@ impl GradientStops {
 
         if actual_length >= 1.0 {
             // 5
-            for stop in render_stops {
+            for stop in render_stops.iter_mut() {
                 stop.offset = (stop.offset - first.offset) / actual_length;
             }
 
+            self.space.subdivide(render_stops);
+
             (first.offset, last.offset) // 5
         } else {
             // 5 - all stops are at the same offset (within 1px)
@@ -1087,6 +1134,137 @@ impl GradientStops {
         self.middle.len() + 2
     }
 }
+impl Transitionable for GradientStops {
+    /// Interpolates each stop if both have the same number of stops, or changes in one step.
+    fn lerp(self, to: &Self, step: EasingStep) -> Self {
+        if self.middle.len() == to.middle.len() {
+            GradientStops {
+                start: self.start.lerp(&to.start, step),
+                middle: self.middle.into_iter().zip(&to.middle).map(|(s, t)| s.lerp(t, step)).collect(),
+                end: self.end.lerp(&to.end, step),
+                space: if step < 1.fct() { self.space } else { to.space },
+            }
+        } else if step < 1.fct() {
+            self
+        } else {
+            to.clone()
+        }
+    }
+}
+
+/// Color space used to interpolate the colors of a gradient.
+///
+/// The renderer only interpolates in the sRGB space, the other spaces are approximated by
+/// inserting intermediary color stops computed in the space.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum GradientSpace {
+    /// Interpolate in the gamma encoded sRGB space.
+    ///
+    /// This is the default, the same space CSS gradients use by default.
+    #[default]
+    Srgb,
+    /// Interpolate in the linear RGB space.
+    ///
+    /// Mixes of saturated colors are brighter, without the dark band of sRGB.
+    LinearRgb,
+    /// Interpolate in the Oklab perceptual space.
+    ///
+    /// Mixes have a perceptually uniform lightness.
+    Oklab,
+}
+impl GradientSpace {
+    /// Number of segments each pair of stops is split into for spaces that are approximated.
+    const SUBDIVISIONS: usize = 8;
+
+    /// Insert intermediary stops to approximate interpolation in `self` space.
+    fn subdivide(self, render_stops: &mut Vec<RenderGradientStop>) {
+        if self == GradientSpace::Srgb || render_stops.len() < 2 {
+            return;
+        }
+
+        let mut out = Vec::with_capacity((render_stops.len() - 1) * Self::SUBDIVISIONS + 1);
+        out.push(render_stops[0]);
+        for pair in render_stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if a.color != b.color && b.offset > a.offset {
+                for i in 1..Self::SUBDIVISIONS {
+                    let f = i as f32 / Self::SUBDIVISIONS as f32;
+                    out.push(RenderGradientStop {
+                        offset: a.offset + (b.offset - a.offset) * f,
+                        color: self.mix(a.color, b.color, f),
+                    });
+                }
+            }
+            out.push(b);
+        }
+        *render_stops = out;
+    }
+
+    /// Interpolate `a` to `b` in `self` space, alpha is interpolated linearly.
+    fn mix(self, a: Rgba, b: Rgba, f: f32) -> Rgba {
+        fn lerp(a: f32, b: f32, f: f32) -> f32 {
+            a + (b - a) * f
+        }
+        let alpha = lerp(a.alpha, b.alpha, f);
+        match self {
+            GradientSpace::Srgb => Rgba::new(lerp(a.red, b.red, f), lerp(a.green, b.green, f), lerp(a.blue, b.blue, f), alpha),
+            GradientSpace::LinearRgb => {
+                let (a, b) = (srgb_to_linear(a), srgb_to_linear(b));
+                linear_to_srgb([lerp(a[0], b[0], f), lerp(a[1], b[1], f), lerp(a[2], b[2], f)], alpha)
+            }
+            GradientSpace::Oklab => {
+                let (a, b) = (linear_to_oklab(srgb_to_linear(a)), linear_to_oklab(srgb_to_linear(b)));
+                let c = oklab_to_linear([lerp(a[0], b[0], f), lerp(a[1], b[1], f), lerp(a[2], b[2], f)]);
+                linear_to_srgb(c, alpha)
+            }
+        }
+    }
+}
+fn srgb_to_linear(c: Rgba) -> [f32; 3] {
+    fn channel(c: f32) -> f32 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    [channel(c.red), channel(c.green), channel(c.blue)]
+}
+
+fn linear_to_srgb(c: [f32; 3], alpha: f32) -> Rgba {
+    fn channel(c: f32) -> f32 {
+        let c = c.clamp(0.0, 1.0);
+        if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        }
+    }
+    Rgba::new(channel(c[0]), channel(c[1]), channel(c[2]), alpha)
+}
+
+fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.41222146 * r + 0.53633255 * g + 0.051445995 * b).cbrt();
+    let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+    let s = (0.08830246 * r + 0.28171885 * g + 0.6299787 * b).cbrt();
+    [
+        0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+        1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+        0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+    ]
+}
+
+fn oklab_to_linear([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = l + 0.39633778 * a + 0.21580376 * b;
+    let m_ = l - 0.105561346 * a - 0.06385417 * b;
+    let s_ = l - 0.08948418 * a - 1.2914855 * b;
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+    [
+        4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+        -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+        -0.0041960863 * l - 0.7034186 * m + 1.7076147 * s,
+    ]
+}
 impl_from_and_into_var! {
     /// [`GradientStops::from_colors`]
     fn from(colors: &[Rgba]) -> GradientStops {
@@ -1236,6 +1414,7 @@ macro_rules! __stops {
             start: $crate::gradient::ColorStop::from($start),
             middle: std::vec![$($crate::gradient::GradientStop::from($middle)),*],
             end: $crate::gradient::ColorStop::from($end),
+            space: $crate::gradient::GradientSpace::Srgb,
         }
     };
 }
@@ -1433,4 +1612,26 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn linear_rgb_space_layout() {
+        let stops = test_layout_stops(stops![colors::BLACK, colors::WHITE].with_space(GradientSpace::LinearRgb));
+        assert_eq!(stops.len(), GradientSpace::SUBDIVISIONS + 1);
+        assert_eq!(stops[0].color, colors::BLACK);
+        assert_eq!(stops[stops.len() - 1].color, colors::WHITE);
+
+        let mid = stops[GradientSpace::SUBDIVISIONS / 2];
+        assert!((mid.offset - 0.5).abs() < 0.0001);
+        // linear light midpoint is brighter than the sRGB midpoint.
+        assert!(mid.color.red > 0.7, "{:?}", mid.color);
+    }
+
+    #[test]
+    fn oklab_round_trip() {
+        let c = Rgba::new(0.2, 0.6, 0.9, 1.0);
+        let r = linear_to_srgb(oklab_to_linear(linear_to_oklab(srgb_to_linear(c))), 1.0);
+        assert!((c.red - r.red).abs() < 0.001, "{r:?}");
+        assert!((c.green - r.green).abs() < 0.001, "{r:?}");
+        assert!((c.blue - r.blue).abs() < 0.001, "{r:?}");
+    }
 }
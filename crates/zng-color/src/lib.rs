@@ -21,7 +21,7 @@ use zng_var::{
     IntoVar, Var, VarValue,
 };
 
-pub use zng_view_api::config::ColorScheme;
+pub use zng_view_api::config::{ColorScheme, HighContrastColors};
 
 #[doc(hidden)]
 pub use zng_color_proc_macros::hex_color;
@@ -31,6 +31,7 @@ pub use zng_layout::unit::{Rgba, RgbaComponent};
 pub mod colors;
 pub mod filter;
 pub mod gradient;
+pub mod theme;
 pub mod web_colors;
 
 mod mix;
@@ -826,6 +827,11 @@ pub fn hsva<H: Into<AngleDegree>, N: Into<Factor>, A: Into<Factor>>(hue: H, satu
 context_var! {
     /// Defines the preferred color scheme in a context.
     pub static COLOR_SCHEME_VAR: ColorScheme = ColorScheme::default();
+
+    /// Defines the high contrast palette in a context.
+    ///
+    /// Is `None` when high contrast mode is not enabled. The window widget sets this to the system palette.
+    pub static HIGH_CONTRAST_VAR: Option<HighContrastColors> = None;
 }
 
 /// RGBA color pair.
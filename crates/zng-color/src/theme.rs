@@ -0,0 +1,388 @@
+//! Theme color tokens and service.
+//!
+//! A theme associates semantic [`ColorRole`] with [`LightDark`] colors, widget styles select colors from
+//! the role context variables, like [`SURFACE_COLOR_VAR`], instead of hard-coding colors, so apps can
+//! re-skin all widgets consistently by changing the [`THEME`] or setting [`THEME_VAR`] in a context.
+
+use std::collections::HashMap;
+
+use zng_app_context::app_local;
+use zng_var::{context_var, merge_var, var, ArcVar, BoxedVar, ContextVar, Var};
+
+use crate::{colors, light_dark, rgb, HighContrastColors, LightDark, Rgba, HIGH_CONTRAST_VAR};
+
+/// Semantic color role.
+///
+/// Each role is a context variable that resolves from the contextual [`THEME_VAR`], see [`ColorRole::var`]
+/// for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ColorRole {
+    /// Window and page background.
+    Background,
+    /// Text and icons over [`Background`](Self::Background).
+    OnBackground,
+    /// Background of widgets that are *containers*, like text inputs, menus and popups.
+    Surface,
+    /// Text and icons over [`Surface`](Self::Surface).
+    OnSurface,
+    /// Background of widgets that need to stand out from the surface, like buttons.
+    SurfaceVariant,
+    /// Fill of prominent widgets, like primary buttons and focused borders.
+    ///
+    /// If the theme does not define this color the [`ACCENT_COLOR_VAR`] is used.
+    ///
+    /// [`ACCENT_COLOR_VAR`]: colors::ACCENT_COLOR_VAR
+    Primary,
+    /// Text and icons over [`Primary`](Self::Primary).
+    OnPrimary,
+    /// Fill of less prominent highlighted widgets.
+    Secondary,
+    /// Text and icons over [`Secondary`](Self::Secondary).
+    OnSecondary,
+    /// Borders and separators.
+    Outline,
+    /// Fill of widgets that indicate an error.
+    Error,
+    /// Text and icons over [`Error`](Self::Error).
+    OnError,
+}
+impl ColorRole {
+    /// All roles.
+    pub const ALL: [ColorRole; 12] = [
+        ColorRole::Background,
+        ColorRole::OnBackground,
+        ColorRole::Surface,
+        ColorRole::OnSurface,
+        ColorRole::SurfaceVariant,
+        ColorRole::Primary,
+        ColorRole::OnPrimary,
+        ColorRole::Secondary,
+        ColorRole::OnSecondary,
+        ColorRole::Outline,
+        ColorRole::Error,
+        ColorRole::OnError,
+    ];
+
+    /// Gets the context variable for the role.
+    pub fn var(self) -> ContextVar<LightDark> {
+        match self {
+            ColorRole::Background => BACKGROUND_COLOR_VAR,
+            ColorRole::OnBackground => ON_BACKGROUND_COLOR_VAR,
+            ColorRole::Surface => SURFACE_COLOR_VAR,
+            ColorRole::OnSurface => ON_SURFACE_COLOR_VAR,
+            ColorRole::SurfaceVariant => SURFACE_VARIANT_COLOR_VAR,
+            ColorRole::Primary => PRIMARY_COLOR_VAR,
+            ColorRole::OnPrimary => ON_PRIMARY_COLOR_VAR,
+            ColorRole::Secondary => SECONDARY_COLOR_VAR,
+            ColorRole::OnSecondary => ON_SECONDARY_COLOR_VAR,
+            ColorRole::Outline => OUTLINE_COLOR_VAR,
+            ColorRole::Error => ERROR_COLOR_VAR,
+            ColorRole::OnError => ON_ERROR_COLOR_VAR,
+        }
+    }
+
+    /// Gets the color from the high contrast palette that is used for the role when high contrast mode is enabled.
+    pub fn high_contrast(self, colors: &HighContrastColors) -> Rgba {
+        match self {
+            ColorRole::Background | ColorRole::Surface => colors.background,
+            ColorRole::OnBackground | ColorRole::OnSurface | ColorRole::Outline => colors.text,
+            ColorRole::SurfaceVariant => colors.button,
+            ColorRole::Primary | ColorRole::Secondary | ColorRole::Error => colors.highlight,
+            ColorRole::OnPrimary | ColorRole::OnSecondary | ColorRole::OnError => colors.highlight_text,
+        }
+    }
+
+    fn init_var(self) -> impl Var<LightDark> {
+        merge_var!(
+            THEME_VAR,
+            colors::ACCENT_COLOR_VAR,
+            HIGH_CONTRAST_VAR,
+            move |theme, accent, high_contrast| {
+                match high_contrast {
+                    Some(c) => {
+                        let c = self.high_contrast(c);
+                        light_dark(c, c)
+                    }
+                    None => theme.get(self).unwrap_or(*accent),
+                }
+            }
+        )
+    }
+}
+
+/// Colors of each [`ColorRole`].
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Theme {
+    /// [`ColorRole::Background`].
+    pub background: LightDark,
+    /// [`ColorRole::OnBackground`].
+    pub on_background: LightDark,
+    /// [`ColorRole::Surface`].
+    pub surface: LightDark,
+    /// [`ColorRole::OnSurface`].
+    pub on_surface: LightDark,
+    /// [`ColorRole::SurfaceVariant`].
+    pub surface_variant: LightDark,
+    /// [`ColorRole::Primary`].
+    ///
+    /// If `None` the [`ACCENT_COLOR_VAR`] is used.
+    ///
+    /// [`ACCENT_COLOR_VAR`]: colors::ACCENT_COLOR_VAR
+    pub primary: Option<LightDark>,
+    /// [`ColorRole::OnPrimary`].
+    pub on_primary: LightDark,
+    /// [`ColorRole::Secondary`].
+    pub secondary: LightDark,
+    /// [`ColorRole::OnSecondary`].
+    pub on_secondary: LightDark,
+    /// [`ColorRole::Outline`].
+    pub outline: LightDark,
+    /// [`ColorRole::Error`].
+    pub error: LightDark,
+    /// [`ColorRole::OnError`].
+    pub on_error: LightDark,
+}
+impl Default for Theme {
+    /// Default theme, matches the colors used by the default widget styles.
+    fn default() -> Self {
+        Self {
+            background: light_dark(rgb(0.9, 0.9, 0.9), rgb(0.1, 0.1, 0.1)),
+            on_background: light_dark(rgb(0.08, 0.08, 0.08), rgb(0.92, 0.92, 0.92)),
+            surface: light_dark(colors::WHITE, colors::BLACK),
+            on_surface: light_dark(colors::BLACK, colors::WHITE),
+            surface_variant: light_dark(rgb(0.82, 0.82, 0.82), rgb(0.18, 0.18, 0.18)),
+            primary: None,
+            on_primary: light_dark(colors::WHITE, colors::WHITE),
+            secondary: light_dark(rgb(0.38, 0.36, 0.44), rgb(0.80, 0.76, 0.86)),
+            on_secondary: light_dark(colors::WHITE, colors::BLACK),
+            outline: light_dark(rgb(0.6, 0.6, 0.6), rgb(0.4, 0.4, 0.4)),
+            error: light_dark(rgb(0.70, 0.15, 0.15), rgb(0.95, 0.55, 0.50)),
+            on_error: light_dark(colors::WHITE, colors::BLACK),
+        }
+    }
+}
+impl Theme {
+    /// Gets the color of the `role`.
+    ///
+    /// Only returns `None` for [`ColorRole::Primary`] when the theme follows the accent color.
+    pub fn get(&self, role: ColorRole) -> Option<LightDark> {
+        Some(match role {
+            ColorRole::Background => self.background,
+            ColorRole::OnBackground => self.on_background,
+            ColorRole::Surface => self.surface,
+            ColorRole::OnSurface => self.on_surface,
+            ColorRole::SurfaceVariant => self.surface_variant,
+            ColorRole::Primary => return self.primary,
+            ColorRole::OnPrimary => self.on_primary,
+            ColorRole::Secondary => self.secondary,
+            ColorRole::OnSecondary => self.on_secondary,
+            ColorRole::Outline => self.outline,
+            ColorRole::Error => self.error,
+            ColorRole::OnError => self.on_error,
+        })
+    }
+
+    /// Sets the color of the `role`.
+    pub fn set(&mut self, role: ColorRole, color: LightDark) {
+        match role {
+            ColorRole::Background => self.background = color,
+            ColorRole::OnBackground => self.on_background = color,
+            ColorRole::Surface => self.surface = color,
+            ColorRole::OnSurface => self.on_surface = color,
+            ColorRole::SurfaceVariant => self.surface_variant = color,
+            ColorRole::Primary => self.primary = Some(color),
+            ColorRole::OnPrimary => self.on_primary = color,
+            ColorRole::Secondary => self.secondary = color,
+            ColorRole::OnSecondary => self.on_secondary = color,
+            ColorRole::Outline => self.outline = color,
+            ColorRole::Error => self.error = color,
+            ColorRole::OnError => self.on_error = color,
+        }
+    }
+
+    /// Builder style [`set`](Self::set).
+    pub fn with(mut self, role: ColorRole, color: impl Into<LightDark>) -> Self {
+        self.set(role, color.into());
+        self
+    }
+
+    /// New theme with the `overrides` applied.
+    pub fn with_overrides(mut self, overrides: &ThemeOverrides) -> Self {
+        for (&role, &color) in overrides.iter() {
+            self.set(role, color);
+        }
+        self
+    }
+}
+
+/// User overrides of theme colors.
+///
+/// See [`THEME.overrides`] for more details.
+///
+/// [`THEME.overrides`]: THEME::overrides
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct ThemeOverrides {
+    roles: HashMap<ColorRole, LightDark>,
+}
+impl ThemeOverrides {
+    /// New empty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the override color of the `role`.
+    pub fn get(&self, role: ColorRole) -> Option<LightDark> {
+        self.roles.get(&role).copied()
+    }
+
+    /// Sets the override color of the `role`, returns the previous override.
+    pub fn insert(&mut self, role: ColorRole, color: LightDark) -> Option<LightDark> {
+        self.roles.insert(role, color)
+    }
+
+    /// Removes the override of the `role`, returns the previous override.
+    pub fn remove(&mut self, role: ColorRole) -> Option<LightDark> {
+        self.roles.remove(&role)
+    }
+
+    /// If there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.roles.is_empty()
+    }
+
+    /// Iterate over overrides.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&ColorRole, &LightDark)> {
+        self.roles.iter()
+    }
+}
+
+/// Theme service.
+///
+/// The service provides the app default [`THEME_VAR`] value, themes can be changed at runtime by setting the [`theme`]
+/// variable and users can customize individual roles using the [`overrides`] variable.
+///
+/// The `save_theme` window property persists the overrides in the `CONFIG` service.
+///
+/// [`theme`]: THEME::theme
+/// [`overrides`]: THEME::overrides
+pub struct THEME;
+impl THEME {
+    /// App base theme.
+    ///
+    /// Set this variable to switch the app theme.
+    pub fn theme(&self) -> ArcVar<Theme> {
+        THEME_SV.read().theme.clone()
+    }
+
+    /// User overrides applied over the [`theme`].
+    ///
+    /// [`theme`]: Self::theme
+    pub fn overrides(&self) -> ArcVar<ThemeOverrides> {
+        THEME_SV.read().overrides.clone()
+    }
+
+    /// Read-only variable that merges the [`theme`] and [`overrides`].
+    ///
+    /// This is the default value of [`THEME_VAR`].
+    ///
+    /// [`theme`]: Self::theme
+    /// [`overrides`]: Self::overrides
+    pub fn actual_theme(&self) -> BoxedVar<Theme> {
+        THEME_SV.read().actual_theme.clone()
+    }
+
+    /// Override the `role` color.
+    pub fn set_override(&self, role: ColorRole, color: impl Into<LightDark>) {
+        let color = color.into();
+        self.overrides().modify(move |o| {
+            if o.get(role) != Some(color) {
+                o.to_mut().insert(role, color);
+            }
+        });
+    }
+
+    /// Remove the `role` override.
+    pub fn reset_override(&self, role: ColorRole) {
+        self.overrides().modify(move |o| {
+            if o.get(role).is_some() {
+                o.to_mut().remove(role);
+            }
+        });
+    }
+}
+
+struct ThemeService {
+    theme: ArcVar<Theme>,
+    overrides: ArcVar<ThemeOverrides>,
+    actual_theme: BoxedVar<Theme>,
+}
+impl ThemeService {
+    fn new() -> Self {
+        let theme = var(Theme::default());
+        let overrides = var(ThemeOverrides::new());
+        let actual_theme = merge_var!(theme.clone(), overrides.clone(), |t, o| t.with_overrides(o))
+            .read_only()
+            .boxed();
+        Self {
+            theme,
+            overrides,
+            actual_theme,
+        }
+    }
+}
+app_local! {
+    static THEME_SV: ThemeService = ThemeService::new();
+}
+
+context_var! {
+    /// Theme in a context.
+    ///
+    /// Is the [`THEME.actual_theme`] by default.
+    ///
+    /// [`THEME.actual_theme`]: THEME::actual_theme
+    pub static THEME_VAR: Theme = THEME.actual_theme();
+
+    /// [`ColorRole::Background`] color in a context.
+    pub static BACKGROUND_COLOR_VAR: LightDark = ColorRole::Background.init_var();
+    /// [`ColorRole::OnBackground`] color in a context.
+    pub static ON_BACKGROUND_COLOR_VAR: LightDark = ColorRole::OnBackground.init_var();
+    /// [`ColorRole::Surface`] color in a context.
+    pub static SURFACE_COLOR_VAR: LightDark = ColorRole::Surface.init_var();
+    /// [`ColorRole::OnSurface`] color in a context.
+    pub static ON_SURFACE_COLOR_VAR: LightDark = ColorRole::OnSurface.init_var();
+    /// [`ColorRole::SurfaceVariant`] color in a context.
+    pub static SURFACE_VARIANT_COLOR_VAR: LightDark = ColorRole::SurfaceVariant.init_var();
+    /// [`ColorRole::Primary`] color in a context.
+    pub static PRIMARY_COLOR_VAR: LightDark = ColorRole::Primary.init_var();
+    /// [`ColorRole::OnPrimary`] color in a context.
+    pub static ON_PRIMARY_COLOR_VAR: LightDark = ColorRole::OnPrimary.init_var();
+    /// [`ColorRole::Secondary`] color in a context.
+    pub static SECONDARY_COLOR_VAR: LightDark = ColorRole::Secondary.init_var();
+    /// [`ColorRole::OnSecondary`] color in a context.
+    pub static ON_SECONDARY_COLOR_VAR: LightDark = ColorRole::OnSecondary.init_var();
+    /// [`ColorRole::Outline`] color in a context.
+    pub static OUTLINE_COLOR_VAR: LightDark = ColorRole::Outline.init_var();
+    /// [`ColorRole::Error`] color in a context.
+    pub static ERROR_COLOR_VAR: LightDark = ColorRole::Error.init_var();
+    /// [`ColorRole::OnError`] color in a context.
+    pub static ON_ERROR_COLOR_VAR: LightDark = ColorRole::OnError.init_var();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides() {
+        let mut overrides = ThemeOverrides::new();
+        let red = light_dark(colors::RED, colors::RED);
+        overrides.insert(ColorRole::Primary, red);
+        overrides.insert(ColorRole::Surface, red);
+
+        let theme = Theme::default().with_overrides(&overrides);
+        assert_eq!(Some(red), theme.get(ColorRole::Primary));
+        assert_eq!(Some(red), theme.get(ColorRole::Surface));
+        assert_eq!(Theme::default().get(ColorRole::Outline), theme.get(ColorRole::Outline));
+    }
+}
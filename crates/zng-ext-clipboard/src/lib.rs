@@ -13,15 +13,16 @@ use core::fmt;
 use std::path::PathBuf;
 
 use zng_app::{
-    event::{command, CommandInfoExt as _, CommandNameExt as _},
+    event::{command, event, event_args, CommandInfoExt as _, CommandNameExt as _},
     shortcut::{shortcut, CommandShortcutExt as _, ShortcutFilter},
-    view_process::{ViewClipboard, VIEW_PROCESS},
+    update::EventUpdate,
+    view_process::{raw_events::RAW_CLIPBOARD_CHANGED_EVENT, ViewClipboard, VIEW_PROCESS},
     AppExtension,
 };
 use zng_app_context::app_local;
 use zng_ext_image::{ImageHasher, ImageVar, Img, IMAGES};
 use zng_txt::Txt;
-use zng_var::{response_var, ResponderVar, ResponseVar};
+use zng_var::{response_done_var, response_var, var, ArcVar, ReadOnlyArcVar, ResponderVar, ResponseVar, Var as _};
 use zng_view_api::ViewProcessOffline;
 use zng_wgt::{wgt_fn, CommandIconExt as _, ICONS};
 
@@ -30,6 +31,12 @@ use zng_view_api::ipc::IpcBytes;
 
 /// Clipboard app extension.
 ///
+/// # Events
+///
+/// Events this extension provides.
+///
+/// * [`CLIPBOARD_CHANGED_EVENT`]
+///
 /// # Services
 ///
 /// Services provided by this extension.
@@ -39,10 +46,38 @@ use zng_view_api::ipc::IpcBytes;
 pub struct ClipboardManager {}
 
 impl AppExtension for ClipboardManager {
+    fn event_preview(&mut self, update: &mut EventUpdate) {
+        if RAW_CLIPBOARD_CHANGED_EVENT.has(update) {
+            let mut clipboard = CLIPBOARD_SV.write();
+            clipboard.text.latest = None;
+            clipboard.image.latest = None;
+            clipboard.file_list.latest = None;
+            clipboard.ext.latest = None;
+
+            if clipboard.history_capacity.get() > 0 {
+                if let Ok(Some(txt)) = clipboard.text.get(|v| v.read_text()) {
+                    clipboard.push_history(ClipboardEntry::Text(txt));
+                } else if let Ok(Some(list)) = clipboard.file_list.get(|v| v.read_file_list()) {
+                    clipboard.push_history(ClipboardEntry::FileList(list));
+                }
+            }
+            drop(clipboard);
+
+            CLIPBOARD_CHANGED_EVENT.notify(ClipboardChangedArgs::now());
+        }
+    }
+
     fn update(&mut self) {
         let mut clipboard = CLIPBOARD_SV.write();
-        clipboard.text.update(|v, txt| v.write_text(txt));
-        clipboard.image.map_update(
+        let clipboard = &mut *clipboard;
+
+        let txt = clipboard.text.request.as_ref().map(|(t, _)| t.clone());
+        if clipboard.text.update(|v, txt| v.write_text(txt)) {
+            clipboard.push_history(ClipboardEntry::Text(txt.unwrap()));
+        }
+
+        let img = clipboard.image.request.as_ref().map(|(i, _)| i.clone());
+        if clipboard.image.map_update(
             |img| {
                 if let Some(img) = img.view() {
                     Ok(img.clone())
@@ -51,22 +86,63 @@ impl AppExtension for ClipboardManager {
                 }
             },
             |v, img| v.write_image(&img),
-        );
-        clipboard.file_list.update(|v, list| v.write_file_list(list));
-        clipboard.ext.update(|v, (data_type, data)| v.write_extension(data_type, data))
+        ) {
+            clipboard.push_history(ClipboardEntry::Image(img.unwrap()));
+        }
+
+        let list = clipboard.file_list.request.as_ref().map(|(l, _)| l.clone());
+        if clipboard.file_list.update(|v, list| v.write_file_list(list)) {
+            clipboard.push_history(ClipboardEntry::FileList(list.unwrap()));
+        }
+
+        let ext = clipboard.ext.request.as_ref().map(|(e, _)| e.clone());
+        if clipboard.ext.update(|v, (data_type, data)| v.write_extension(data_type, data)) {
+            let (data_type, data) = ext.unwrap();
+            clipboard.push_history(ClipboardEntry::Extension { data_type, data });
+        }
     }
 }
 
 app_local! {
-    static CLIPBOARD_SV: ClipboardService = ClipboardService::default();
+    static CLIPBOARD_SV: ClipboardService = ClipboardService {
+        text: ClipboardData::default(),
+        image: ClipboardData::default(),
+        file_list: ClipboardData::default(),
+        ext: ClipboardData::default(),
+        history_capacity: var(0),
+        history: var(vec![]),
+    };
 }
 
-#[derive(Default)]
 struct ClipboardService {
     text: ClipboardData<Txt, Txt>,
     image: ClipboardData<ImageVar, Img>,
     file_list: ClipboardData<Vec<PathBuf>, Vec<PathBuf>>,
     ext: ClipboardData<IpcBytes, (Txt, IpcBytes)>,
+
+    history_capacity: ArcVar<usize>,
+    history: ArcVar<Vec<ClipboardEntry>>,
+}
+impl ClipboardService {
+    /// Insert or move the `entry` to the start of the history.
+    fn push_history(&mut self, entry: ClipboardEntry) {
+        let capacity = self.history_capacity.get();
+        if capacity == 0 {
+            if self.history.with(|h| !h.is_empty()) {
+                self.history.set(vec![]);
+            }
+            return;
+        }
+        self.history.modify(move |h| {
+            if h.first() == Some(&entry) && h.len() <= capacity {
+                return;
+            }
+            let h = h.to_mut();
+            h.retain(|e| e != &entry);
+            h.insert(0, entry);
+            h.truncate(capacity);
+        });
+    }
 }
 struct ClipboardData<O: 'static, I: 'static> {
     latest: Option<Result<Option<O>, ClipboardError>>,
@@ -116,18 +192,20 @@ impl<O: Clone + 'static, I: 'static> ClipboardData<O, I> {
         response
     }
 
+    /// Returns `true` if the clipboard was set.
     pub fn update(
         &mut self,
         setter: impl FnOnce(&ViewClipboard, I) -> Result<Result<(), clipboard_api::ClipboardError>, ViewProcessOffline>,
-    ) {
+    ) -> bool {
         self.map_update(Ok, setter)
     }
 
+    /// Returns `true` if the clipboard was set.
     pub fn map_update<VI>(
         &mut self,
         to_view: impl FnOnce(I) -> Result<VI, ClipboardError>,
         setter: impl FnOnce(&ViewClipboard, VI) -> Result<Result<(), clipboard_api::ClipboardError>, ViewProcessOffline>,
-    ) {
+    ) -> bool {
         self.latest = None;
         if let Some((i, rsp)) = self.request.take() {
             let vi = match to_view(i) {
@@ -135,7 +213,7 @@ impl<O: Clone + 'static, I: 'static> ClipboardData<O, I> {
                 Err(e) => {
                     tracing::error!("clipboard set error, {e:?}");
                     rsp.respond(Err(e));
-                    return;
+                    return false;
                 }
             };
             let r = CLIPBOARD.view().and_then(|v| match setter(v, vi) {
@@ -154,7 +232,11 @@ impl<O: Clone + 'static, I: 'static> ClipboardData<O, I> {
             if let Err(e) = &r {
                 tracing::error!("clipboard set error, {e:?}");
             }
+            let set = matches!(r, Ok(true));
             rsp.respond(r);
+            set
+        } else {
+            false
         }
     }
 }
@@ -271,6 +353,89 @@ impl CLIPBOARD {
     pub fn set_extension(&self, data_type: impl Into<Txt>, data: IpcBytes) -> ResponseVar<Result<bool, ClipboardError>> {
         CLIPBOARD_SV.write().ext.request((data_type.into(), data))
     }
+
+    /// Maximum number of entries retained in the [`history`].
+    ///
+    /// Is `0` by default, history disabled.
+    ///
+    /// [`history`]: Self::history
+    pub fn history_capacity(&self) -> ArcVar<usize> {
+        CLIPBOARD_SV.read().history_capacity.clone()
+    }
+
+    /// In-app clipboard history, the most recent entry first.
+    ///
+    /// The history records all data set by the app, and text or file lists set by other apps if the view-process
+    /// supports [`CLIPBOARD_CHANGED_EVENT`]. The history is only recorded if [`history_capacity`] is not zero.
+    ///
+    /// [`history_capacity`]: Self::history_capacity
+    pub fn history(&self) -> ReadOnlyArcVar<Vec<ClipboardEntry>> {
+        CLIPBOARD_SV.read().history.read_only()
+    }
+
+    /// Remove all entries from the [`history`].
+    ///
+    /// [`history`]: Self::history
+    pub fn clear_history(&self) {
+        CLIPBOARD_SV.read().history.set(vec![]);
+    }
+
+    /// Set the [`history`] entry at the `index` on the clipboard after the current update.
+    ///
+    /// The entry moves to the start of the history. Responds `Ok(false)` if there is no entry at the `index`.
+    ///
+    /// [`history`]: Self::history
+    pub fn set_from_history(&self, index: usize) -> ResponseVar<Result<bool, ClipboardError>> {
+        match self.history().with(|h| h.get(index).cloned()) {
+            Some(ClipboardEntry::Text(txt)) => self.set_text(txt),
+            Some(ClipboardEntry::Image(img)) => self.set_image(img),
+            Some(ClipboardEntry::FileList(list)) => self.set_file_list(list),
+            Some(ClipboardEntry::Extension { data_type, data }) => self.set_extension(data_type, data),
+            None => response_done_var(Ok(false)),
+        }
+    }
+}
+
+/// Clipboard history entry.
+///
+/// See [`CLIPBOARD.history`] for more details.
+///
+/// [`CLIPBOARD.history`]: CLIPBOARD::history
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardEntry {
+    /// Text string.
+    Text(Txt),
+    /// Image.
+    Image(Img),
+    /// File list.
+    FileList(Vec<PathBuf>),
+    /// Custom data.
+    Extension {
+        /// Data type.
+        data_type: Txt,
+        /// Data.
+        data: IpcBytes,
+    },
+}
+
+event_args! {
+    /// Arguments for [`CLIPBOARD_CHANGED_EVENT`].
+    pub struct ClipboardChangedArgs {
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
+}
+
+event! {
+    /// System clipboard content changed.
+    ///
+    /// Note that the view-process may not support this event on the current platform, the default view-process
+    /// only notifies on Windows.
+    pub static CLIPBOARD_CHANGED_EVENT: ClipboardChangedArgs;
 }
 
 command! {
@@ -300,4 +465,18 @@ command! {
         shortcut_filter: ShortcutFilter::FOCUSED | ShortcutFilter::CMD_ENABLED,
         icon: wgt_fn!(|_| ICONS.get("paste")),
     };
+
+    /// Represents the clipboard **paste from history** action.
+    ///
+    /// The command parameter must be an `usize` index in the [`CLIPBOARD.history`]. Command implementers
+    /// usually show a menu with the history entries when the parameter is not set.
+    ///
+    /// [`CLIPBOARD.history`]: CLIPBOARD::history
+    pub static PASTE_FROM_HISTORY_CMD = {
+        name: "Paste from History",
+        info: "Insert content from the clipboard history.",
+        shortcut: [shortcut!(CTRL|SHIFT+'V')],
+        shortcut_filter: ShortcutFilter::FOCUSED | ShortcutFilter::CMD_ENABLED,
+        icon: wgt_fn!(|_| ICONS.get("paste")),
+    };
 }
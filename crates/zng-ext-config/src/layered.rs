@@ -0,0 +1,417 @@
+use crate::task::parking_lot::Mutex;
+use zng_var::MergeVarBuilder;
+
+use super::*;
+
+/// Identifies a config source in a [`LayeredConfig`].
+///
+/// Layers are ordered by precedence, the value of the greatest layer that contains a key is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigLayer {
+    /// App provided defaults.
+    Default,
+    /// System wide config, usually managed by an administrator or policy.
+    System,
+    /// User config.
+    ///
+    /// This is the default write layer.
+    User,
+    /// Config that only lasts for the current session.
+    Session,
+}
+
+/// Represents a config source that merges multiple layered sources.
+///
+/// The config variables read the value of the greatest [`ConfigLayer`] that contains the key, when a config
+/// variable is assigned the value is written to the write layer of the key, the [`ConfigLayer::User`] by default. Note that if the
+/// value is written to a layer that is overridden by a greater layer the variable resets back to the greater layer value.
+///
+/// Use [`origin`] to get the layer that provides the current value of a key, settings UIs can use this to
+/// show keys that are managed by the system and [`reset`] to remove the key from a layer.
+///
+/// The `LayeredConfig` type is an `Arc` internally, so you can keep a cloned reference to it after moving it into
+/// [`CONFIG`] or another combinator config.
+///
+/// Config variables already returned do not update if a layer source is set after, only the layers that contain a key are read.
+///
+/// [`origin`]: Self::origin
+/// [`reset`]: Self::reset
+pub struct LayeredConfig(Arc<Mutex<LayeredConfigData>>);
+impl LayeredConfig {
+    /// New without any layer.
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(LayeredConfigData {
+            layers: vec![],
+            write_layer: ConfigLayer::User,
+            key_write_layer: HashMap::new(),
+            vars: HashMap::new(),
+        })))
+    }
+
+    /// Set the `config` source of the `layer`, replaces the previous source of the layer.
+    pub fn set_layer(&self, layer: ConfigLayer, config: impl AnyConfig) {
+        let mut d = self.0.lock();
+        d.layers.retain(|(l, _)| *l != layer);
+        let i = d.layers.iter().position(|(l, _)| *l < layer).unwrap_or(d.layers.len());
+        d.layers.insert(i, (layer, Box::new(config)));
+    }
+
+    /// Set the layer and return.
+    ///
+    /// See [`set_layer`] for more details.
+    ///
+    /// [`set_layer`]: Self::set_layer
+    pub fn with_layer(self, layer: ConfigLayer, config: impl AnyConfig) -> Self {
+        self.set_layer(layer, config);
+        self
+    }
+
+    /// Set the layer that is written when config variables are assigned.
+    ///
+    /// Is [`ConfigLayer::User`] by default. Keys with a [`set_key_write_layer`] override this.
+    ///
+    /// [`set_key_write_layer`]: Self::set_key_write_layer
+    pub fn set_write_layer(&self, layer: ConfigLayer) {
+        self.0.lock().write_layer = layer;
+    }
+
+    /// Set the layer that is written when the `key` variable is assigned.
+    ///
+    /// If `layer` is `None` the [`set_write_layer`] is used for the key.
+    ///
+    /// [`set_write_layer`]: Self::set_write_layer
+    pub fn set_key_write_layer(&self, key: impl Into<ConfigKey>, layer: Option<ConfigLayer>) {
+        let key = key.into();
+        let mut d = self.0.lock();
+        match layer {
+            Some(l) => {
+                d.key_write_layer.insert(key, l);
+            }
+            None => {
+                d.key_write_layer.remove(&key);
+            }
+        }
+    }
+
+    /// Gets the layer that is written when the `key` variable is assigned.
+    pub fn write_layer(&self, key: &ConfigKey) -> ConfigLayer {
+        self.0.lock().write_layer(key)
+    }
+
+    /// Gets a read-only variable that is the greatest layer that contains the `key`.
+    ///
+    /// Is `None` if no layer contains the key, in this case the config variables are the default value.
+    pub fn origin(&self, key: impl Into<ConfigKey>) -> BoxedVar<Option<ConfigLayer>> {
+        let key = key.into();
+        let mut d = self.0.lock();
+        let mut contains = MergeVarBuilder::new();
+        let mut layers = Vec::with_capacity(d.layers.len());
+        for (layer, cfg) in &mut d.layers {
+            contains.push(cfg.contains_key(key.clone()));
+            layers.push(*layer);
+        }
+        contains.build(move |c| c.iter().position(|&c| c).map(|i| layers[i])).boxed()
+    }
+
+    /// Removes the `key` from the `layer`.
+    ///
+    /// The config variables update to the value of the next layer that contains the key. Returns `true` if the
+    /// layer contained the key.
+    pub fn reset(&self, key: &ConfigKey, layer: ConfigLayer) -> bool {
+        let mut d = self.0.lock();
+        match d.layers.iter_mut().find(|(l, _)| *l == layer) {
+            Some((_, cfg)) => cfg.remove(key),
+            None => false,
+        }
+    }
+
+    /// Removes the `key` from all layers above [`ConfigLayer::Default`].
+    ///
+    /// Returns `true` if any layer contained the key.
+    pub fn reset_to_default(&self, key: &ConfigKey) -> bool {
+        let mut d = self.0.lock();
+        let mut any = false;
+        for (layer, cfg) in &mut d.layers {
+            if *layer > ConfigLayer::Default {
+                any |= cfg.remove(key);
+            }
+        }
+        any
+    }
+}
+impl Default for LayeredConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Clone for LayeredConfig {
+    fn clone(&self) -> Self {
+        LayeredConfig(Arc::clone(&self.0))
+    }
+}
+impl AnyConfig for LayeredConfig {
+    fn status(&self) -> BoxedVar<ConfigStatus> {
+        let d = self.0.lock();
+        let mut status = MergeVarBuilder::new();
+        for (_, cfg) in &d.layers {
+            status.push(cfg.status());
+        }
+        status.build(|s| ConfigStatus::merge_status(s.iter().cloned())).boxed()
+    }
+
+    fn get_raw(&mut self, key: ConfigKey, default: RawConfigValue, shared: bool) -> BoxedVar<RawConfigValue> {
+        let mut d = self.0.lock();
+        let d = &mut *d;
+
+        if shared {
+            if let Some(r) = d.vars.get(&key).and_then(|v| v.upgrade()) {
+                return r.boxed();
+            }
+        }
+        if d.vars.len() > 1000 {
+            d.vars.retain(|_, v| v.strong_count() > 0);
+        }
+
+        // only the layers that contain the key are read, reading a key can insert it in some sources.
+        let mut state = LayeredKey {
+            key: key.clone(),
+            default: default.clone(),
+            shared,
+            layers: Vec::with_capacity(d.layers.len()),
+        };
+        for (layer, cfg) in &mut d.layers {
+            state.layers.push(LayerKey {
+                layer: *layer,
+                contains: cfg.contains_key(key.clone()),
+                value: None,
+            });
+        }
+        let value = state.active().and_then(|i| state.load_value(d, i).map(|(v, _)| (i, v)));
+
+        let res_var = var(value.as_ref().map(|(_, v)| v.get()).unwrap_or(default));
+        if shared {
+            d.vars.insert(key.clone(), res_var.downgrade());
+        }
+
+        let state = Arc::new(Mutex::new(state));
+        let weak_data = Arc::downgrade(&self.0);
+        if let Some((i, v)) = value {
+            hook_layer_value(&v, i, &state, &res_var);
+        }
+
+        // bind layer contains -> res_var.
+        for l in &state.lock().layers {
+            let weak_state = Arc::downgrade(&state);
+            let weak_res_var = res_var.downgrade();
+            let weak_data = weak_data.clone();
+            l.contains
+                .hook(move |_| match (weak_data.upgrade(), weak_state.upgrade(), weak_res_var.upgrade()) {
+                    (Some(d), Some(s), Some(r)) => {
+                        refresh_layered(&d, &s, &r);
+                        true
+                    }
+                    _ => false,
+                })
+                .perm();
+        }
+
+        // write res_var -> write layer, the hook holds the key state alive.
+        let s = state;
+        let weak_res_var = res_var.downgrade();
+        res_var
+            .hook(move |args| {
+                if args.downcast_tags::<MergedTag>().next().is_some() {
+                    return true;
+                }
+                // assigned by user
+                let (d, r) = match (weak_data.upgrade(), weak_res_var.upgrade()) {
+                    (Some(d), Some(r)) => (d, r),
+                    _ => return false,
+                };
+                let layer = d.lock().write_layer(&key);
+                let i = s.lock().layers.iter().position(|l| l.layer == layer);
+                let layer_var = i.and_then(|i| {
+                    let mut d = d.lock();
+                    let loaded = s.lock().load_value(&mut d, i);
+                    drop(d);
+                    if let Some((v, true)) = &loaded {
+                        hook_layer_value(v, i, &s, &r);
+                    }
+                    loaded.map(|(v, _)| v)
+                });
+                match layer_var {
+                    Some(layer_var) => {
+                        let value = args.value().clone();
+                        let r = layer_var.modify(move |v| {
+                            v.set(value);
+                            // cause a write, the layer var can already have the value as default.
+                            v.update();
+                        });
+                        if let Err(e) = r {
+                            tracing::error!("cannot write config {key:?} to {layer:?} layer, {e}");
+                        }
+                    }
+                    None => tracing::error!("cannot write config {key:?}, no {layer:?} layer"),
+                }
+
+                if s.lock().layers.iter().any(|l| l.layer > layer && l.contains.get()) {
+                    // value is overridden by a greater layer, restore.
+                    refresh_layered(&d, &s, &r);
+                }
+
+                true
+            })
+            .perm();
+
+        res_var.boxed()
+    }
+
+    fn contains_key(&mut self, key: ConfigKey) -> BoxedVar<bool> {
+        let mut d = self.0.lock();
+        let mut contains = MergeVarBuilder::new();
+        for (_, cfg) in &mut d.layers {
+            contains.push(cfg.contains_key(key.clone()));
+        }
+        contains.build(|c| c.iter().any(|&c| c)).boxed()
+    }
+
+    fn remove(&mut self, key: &ConfigKey) -> bool {
+        let mut d = self.0.lock();
+        let mut any = false;
+        for (_, cfg) in &mut d.layers {
+            any |= cfg.remove(key);
+        }
+        any
+    }
+
+    fn low_memory(&mut self) {
+        let mut d = self.0.lock();
+        d.vars.retain(|_, v| v.strong_count() > 0);
+        for (_, cfg) in &mut d.layers {
+            cfg.low_memory();
+        }
+    }
+}
+impl Config for LayeredConfig {
+    fn get<T: ConfigValue>(&mut self, key: impl Into<ConfigKey>, default: T) -> BoxedVar<T> {
+        self.get_raw(key.into(), RawConfigValue::serialize(&default).unwrap(), true)
+            .filter_map_bidi(
+                |raw| raw.clone().deserialize().ok(),
+                |v| RawConfigValue::serialize(v).ok(),
+                move || default.clone(),
+            )
+            .boxed()
+    }
+}
+
+struct LayeredConfigData {
+    // sorted by layer, greater first.
+    layers: Vec<(ConfigLayer, Box<dyn AnyConfig>)>,
+    write_layer: ConfigLayer,
+    key_write_layer: HashMap<ConfigKey, ConfigLayer>,
+    vars: HashMap<ConfigKey, WeakArcVar<RawConfigValue>>,
+}
+impl LayeredConfigData {
+    fn write_layer(&self, key: &ConfigKey) -> ConfigLayer {
+        self.key_write_layer.get(key).copied().unwrap_or(self.write_layer)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MergedTag;
+
+// state of a key in all layers.
+struct LayeredKey {
+    key: ConfigKey,
+    default: RawConfigValue,
+    shared: bool,
+    // sorted like the data layers, greater first.
+    layers: Vec<LayerKey>,
+}
+struct LayerKey {
+    layer: ConfigLayer,
+    contains: BoxedVar<bool>,
+    // only loaded after the layer contains the key or is written.
+    value: Option<BoxedVar<RawConfigValue>>,
+}
+impl LayeredKey {
+    /// Index of the greatest layer that contains the key.
+    fn active(&self) -> Option<usize> {
+        self.layers.iter().position(|l| l.contains.get())
+    }
+
+    /// Gets or loads the value var of layer `i`, the flag indicates if the var was loaded by this call.
+    fn load_value(&mut self, data: &mut LayeredConfigData, i: usize) -> Option<(BoxedVar<RawConfigValue>, bool)> {
+        let l = &mut self.layers[i];
+        if let Some(v) = &l.value {
+            return Some((v.clone(), false));
+        }
+        let (_, cfg) = data.layers.iter_mut().find(|(layer, _)| *layer == l.layer)?;
+        let v = cfg.get_raw(self.key.clone(), self.default.clone(), self.shared);
+        l.value = Some(v.clone());
+        Some((v, true))
+    }
+}
+
+// bind layer value -> res_var, while the layer is active.
+fn hook_layer_value(value: &BoxedVar<RawConfigValue>, i: usize, state: &Arc<Mutex<LayeredKey>>, res_var: &ArcVar<RawConfigValue>) {
+    let weak_state = Arc::downgrade(state);
+    let weak_res_var = res_var.downgrade();
+    value
+        .hook(move |args| match (weak_state.upgrade(), weak_res_var.upgrade()) {
+            (Some(s), Some(r)) => {
+                let s = s.lock();
+                if s.layers[i].value.is_none() {
+                    // layer removed the key, the var was dropped.
+                    return false;
+                }
+                if s.active() == Some(i) {
+                    set_merged(&r, args.value().clone());
+                }
+                true
+            }
+            _ => false,
+        })
+        .perm();
+}
+
+// update res_var to the value of the greatest layer that contains the key.
+fn refresh_layered(data: &Mutex<LayeredConfigData>, state: &Arc<Mutex<LayeredKey>>, res_var: &ArcVar<RawConfigValue>) {
+    let active = {
+        let mut s = state.lock();
+        for l in &mut s.layers {
+            if !l.contains.get() {
+                // some sources drop the value var on remove, load again after the key is inserted.
+                l.value = None;
+            }
+        }
+        s.active()
+    };
+    let value = match active {
+        Some(i) => {
+            let mut d = data.lock();
+            let loaded = state.lock().load_value(&mut d, i);
+            drop(d);
+            match loaded {
+                Some((v, is_new)) => {
+                    if is_new {
+                        hook_layer_value(&v, i, state, res_var);
+                    }
+                    v.get()
+                }
+                None => return,
+            }
+        }
+        None => state.lock().default.clone(),
+    };
+    set_merged(res_var, value);
+}
+
+fn set_merged(res_var: &ArcVar<RawConfigValue>, value: RawConfigValue) {
+    res_var.modify(move |v| {
+        if v.as_ref() != &value {
+            v.set(value);
+            v.push_tag(MergedTag);
+        }
+    });
+}
@@ -17,6 +17,9 @@ pub use fallback::*;
 mod json;
 pub use json::*;
 
+mod layered;
+pub use layered::*;
+
 mod swap;
 pub use swap::*;
 
@@ -41,6 +44,11 @@ mod yaml;
 #[cfg(feature = "yaml")]
 pub use self::yaml::*;
 
+#[cfg(feature = "secret")]
+mod secret;
+#[cfg(feature = "secret")]
+pub use self::secret::*;
+
 pub mod settings;
 
 use std::{
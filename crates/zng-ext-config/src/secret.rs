@@ -0,0 +1,269 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::task::parking_lot::Mutex;
+use zng_clone_move::clmv;
+
+use super::*;
+
+/// Represents a config source that stores values encrypted in the operating system credential store.
+///
+/// Values are stored as JSON strings in the Windows Credential Manager, macOS Keychain or Secret Service
+/// on Linux, the config key is the credential user name and the `service` name identifies the app.
+///
+/// This source is intended for tokens and passwords, use it with a [`SwitchConfig`] prefix so that only
+/// keys opted in are stored as secrets:
+///
+/// ```no_run
+/// # use zng_ext_config::*;
+/// # use zng_txt::Txt;
+/// # fn demo() {
+/// CONFIG.load(
+///     SwitchConfig::new()
+///         .with_prefix(SecretConfig::PREFIX, SecretConfig::new("my-app"))
+///         .with_prefix("", JsonConfig::sync("target/tmp/example.config.json")),
+/// );
+///
+/// let token = CONFIG.get("secret::token", Txt::from_static(""));
+/// # }
+/// ```
+///
+/// Values are loaded in a background thread, the config variables update when the value loads.
+pub struct SecretConfig {
+    service: Txt,
+    values: HashMap<ConfigKey, ArcVar<RawConfigValue>>,
+    contains: HashMap<ConfigKey, ArcVar<bool>>,
+    status: Arc<Mutex<SecretStatus>>,
+    status_var: ArcVar<ConfigStatus>,
+}
+impl SecretConfig {
+    /// Recommended key prefix for secret configs.
+    pub const PREFIX: &'static str = "secret::";
+
+    /// New with the `service` name that identifies the app in the credential store.
+    pub fn new(service: impl Into<Txt>) -> Self {
+        Self {
+            service: service.into(),
+            values: HashMap::new(),
+            contains: HashMap::new(),
+            status: Arc::new(Mutex::new(SecretStatus::default())),
+            status_var: var(ConfigStatus::Loaded),
+        }
+    }
+
+    fn entry(service: &str, key: &str) -> Result<keyring::Entry, Arc<dyn std::error::Error + Send + Sync>> {
+        keyring::Entry::new(service, key).map_err(|e| Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>)
+    }
+
+    fn load(service: &str, key: &str) -> Result<Option<RawConfigValue>, Arc<dyn std::error::Error + Send + Sync>> {
+        match Self::entry(service, key)?.get_password() {
+            Ok(s) => match serde_json::from_str(&s) {
+                Ok(v) => Ok(Some(RawConfigValue(v))),
+                Err(e) => Err(Arc::new(e)),
+            },
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(Arc::new(e)),
+        }
+    }
+
+    fn save(service: &str, key: &str, value: &RawConfigValue) -> Result<(), Arc<dyn std::error::Error + Send + Sync>> {
+        let value = serde_json::to_string(&value.0).map_err(|e| Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>)?;
+        Self::entry(service, key)?
+            .set_password(&value)
+            .map_err(|e| Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>)
+    }
+
+    fn delete(service: &str, key: &str) -> Result<(), Arc<dyn std::error::Error + Send + Sync>> {
+        match Self::entry(service, key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(Arc::new(e)),
+        }
+    }
+
+    /// Get or init the contains var, loads from the store on init.
+    fn contains_var(&mut self, key: &ConfigKey) -> ArcVar<bool> {
+        if let Some(c) = self.contains.get(key) {
+            return c.clone();
+        }
+        let c = var(false);
+        self.contains.insert(key.clone(), c.clone());
+
+        if !self.values.contains_key(key) {
+            // get_raw also loads.
+            let service = self.service.clone();
+            SecretStatus::run(
+                &self.status,
+                &self.status_var,
+                true,
+                clmv!(key, c, || {
+                    let r = Self::load(&service, &key)?;
+                    c.set(r.is_some());
+                    Ok(())
+                }),
+            );
+        }
+
+        c
+    }
+}
+impl AnyConfig for SecretConfig {
+    fn status(&self) -> BoxedVar<ConfigStatus> {
+        self.status_var.read_only().boxed()
+    }
+
+    fn get_raw(&mut self, key: ConfigKey, default: RawConfigValue, _shared: bool) -> BoxedVar<RawConfigValue> {
+        if let Some(r) = self.values.get(&key) {
+            return r.clone().boxed();
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct LoadTag;
+
+        let r = var(default);
+        self.values.insert(key.clone(), r.clone());
+        let contains = self.contains_var(&key);
+
+        // load
+        let is_assigned = Arc::new(AtomicBool::new(false));
+        let service = self.service.clone();
+        SecretStatus::run(
+            &self.status,
+            &self.status_var,
+            true,
+            clmv!(key, r, contains, is_assigned, || {
+                if let Some(value) = Self::load(&service, &key)? {
+                    contains.set(true);
+                    r.modify(move |v| {
+                        // don't override a value assigned during load.
+                        if !is_assigned.load(Ordering::Relaxed) && v.as_ref() != &value {
+                            v.set(value);
+                            v.push_tag(LoadTag);
+                        }
+                    });
+                }
+                Ok(())
+            }),
+        );
+
+        // save
+        let service = self.service.clone();
+        let status = self.status.clone();
+        let status_var = self.status_var.clone();
+        r.hook(move |args| {
+            if args.downcast_tags::<LoadTag>().next().is_some() {
+                return true;
+            }
+            is_assigned.store(true, Ordering::Relaxed);
+            contains.set(true);
+
+            let value = args.value().clone();
+            SecretStatus::run(
+                &status,
+                &status_var,
+                false,
+                clmv!(service, key, || Self::save(&service, &key, &value)),
+            );
+            true
+        })
+        .perm();
+
+        r.boxed()
+    }
+
+    fn contains_key(&mut self, key: ConfigKey) -> BoxedVar<bool> {
+        self.contains_var(&key).boxed()
+    }
+
+    fn remove(&mut self, key: &ConfigKey) -> bool {
+        let c = self.contains_var(key);
+        let contained = c.get();
+        c.set(false);
+
+        // the value var is retained, assigning it stores the key again.
+        let service = self.service.clone();
+        SecretStatus::run(&self.status, &self.status_var, false, clmv!(key, || Self::delete(&service, &key)));
+
+        contained
+    }
+
+    fn low_memory(&mut self) {
+        let values = &self.values;
+        self.contains.retain(|k, v| values.contains_key(k) || v.strong_count() > 1);
+    }
+}
+impl Config for SecretConfig {
+    fn get<T: ConfigValue>(&mut self, key: impl Into<ConfigKey>, default: T) -> BoxedVar<T> {
+        self.get_raw(key.into(), RawConfigValue::serialize(&default).unwrap(), true)
+            .filter_map_bidi(
+                |raw| raw.clone().deserialize().ok(),
+                |v| RawConfigValue::serialize(v).ok(),
+                move || default.clone(),
+            )
+            .boxed()
+    }
+}
+
+#[derive(Default)]
+struct SecretStatus {
+    loading: usize,
+    saving: usize,
+    load_errors: ConfigStatusError,
+    save_errors: ConfigStatusError,
+}
+impl SecretStatus {
+    /// Run the blocking credential store `op` in a background thread and track the status.
+    fn run(
+        status: &Arc<Mutex<SecretStatus>>,
+        status_var: &ArcVar<ConfigStatus>,
+        is_load: bool,
+        op: impl FnOnce() -> Result<(), Arc<dyn std::error::Error + Send + Sync>> + Send + 'static,
+    ) {
+        {
+            let mut s = status.lock();
+            if is_load {
+                s.loading += 1;
+            } else {
+                s.saving += 1;
+            }
+            status_var.set(s.status());
+        }
+
+        let status = status.clone();
+        let status_var = status_var.clone();
+        task::spawn_wait(move || {
+            let r = op();
+
+            let mut s = status.lock();
+            if is_load {
+                s.loading -= 1;
+                if let Err(e) = r {
+                    tracing::error!("secret config load error, {e}");
+                    s.load_errors.push(e);
+                }
+            } else {
+                s.saving -= 1;
+                match r {
+                    Ok(()) => s.save_errors.clear(),
+                    Err(e) => {
+                        tracing::error!("secret config save error, {e}");
+                        s.save_errors.push(e);
+                    }
+                }
+            }
+            status_var.set(s.status());
+        });
+    }
+
+    fn status(&self) -> ConfigStatus {
+        if self.loading > 0 {
+            ConfigStatus::Loading
+        } else if self.saving > 0 {
+            ConfigStatus::Saving
+        } else if !self.load_errors.is_empty() {
+            ConfigStatus::LoadErrors(self.load_errors.clone())
+        } else if !self.save_errors.is_empty() {
+            ConfigStatus::SaveErrors(self.save_errors.clone())
+        } else {
+            ConfigStatus::Loaded
+        }
+    }
+}
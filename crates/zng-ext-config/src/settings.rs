@@ -3,14 +3,22 @@
 //! The settings editor widget is not implemented here, this module bridges config implementers with settings UI implementers.
 
 use core::fmt;
-use std::{any::TypeId, cmp::Ordering, mem, ops, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    mem, ops,
+    sync::Arc,
+};
 
 use zng_app_context::app_local;
-use zng_state_map::{OwnedStateMap, StateId, StateMapMut, StateMapRef, StateValue};
-use zng_txt::Txt;
-use zng_var::{impl_from_and_into_var, var, AnyVar, AnyVarHookArgs, AnyVarValue, BoxedAnyVar, BoxedVar, IntoVar, LocalVar, Var};
+use zng_state_map::{static_id, OwnedStateMap, StateId, StateMapMut, StateMapRef, StateValue};
+use zng_txt::{formatx, Txt};
+use zng_var::{
+    impl_from_and_into_var, var, AnyVar, AnyVarHookArgs, AnyVarValue, ArcVar, BoxedAnyVar, BoxedVar, IntoVar, LocalVar, ReadOnlyArcVar, Var,
+};
 
-use crate::{Config, ConfigKey, ConfigValue, FallbackConfigReset, CONFIG};
+use crate::{Config, ConfigKey, ConfigLayer, ConfigValue, FallbackConfigReset, LayeredConfig, CONFIG};
 
 /// Settings metadata service.
 pub struct SETTINGS;
@@ -26,6 +34,22 @@ impl SETTINGS {
         SETTINGS_SV.write().sources_cat.push(Box::new(f))
     }
 
+    /// Reset all settings matched by `filter`.
+    pub fn reset(&self, mut filter: impl FnMut(&ConfigKey, &CategoryId) -> bool) {
+        for (_, settings) in self.get_impl(&mut filter, false) {
+            for s in settings {
+                s.reset();
+            }
+        }
+    }
+
+    /// Gets a variable that indicates a setting flagged with [`SettingBuilder::requires_restart`] has changed.
+    ///
+    /// Note that settings changes are only tracked after they are first loaded, usually by the settings editor.
+    pub fn restart_required(&self) -> ReadOnlyArcVar<bool> {
+        SETTINGS_CHANGES.read().restart_required.read_only()
+    }
+
     /// Select and sort settings matched by `filter`.
     pub fn get(&self, mut filter: impl FnMut(&ConfigKey, &CategoryId) -> bool, sort: bool) -> Vec<(Category, Vec<Setting>)> {
         self.get_impl(&mut filter, sort)
@@ -312,6 +336,7 @@ pub struct Setting {
     value: BoxedAnyVar,
     value_type: TypeId,
     reset: Arc<dyn SettingReset>,
+    changed: BoxedVar<bool>,
 }
 impl Clone for Setting {
     fn clone(&self) -> Self {
@@ -325,6 +350,7 @@ impl Clone for Setting {
             value: self.value.clone(),
             value_type: self.value_type,
             reset: self.reset.clone(),
+            changed: self.changed.clone(),
         }
     }
 }
@@ -397,6 +423,30 @@ impl Setting {
         self.reset.reset(&self.key, &self.value);
     }
 
+    /// Gets a variable that indicates the setting value has changed since it was first loaded in the app process.
+    pub fn is_changed(&self) -> BoxedVar<bool> {
+        self.changed.clone()
+    }
+
+    /// Gets the value range, if set by [`SettingBuilder::range`].
+    pub fn range(&self) -> Option<SettingRange> {
+        self.meta().get_clone(*SETTING_RANGE_ID)
+    }
+
+    /// Gets a variable that tracks the config layer that provides the current value.
+    ///
+    /// Is `None` if the setting was not build with [`SettingBuilder::layered_reset`].
+    pub fn origin(&self) -> Option<BoxedVar<Option<ConfigLayer>>> {
+        self.meta().get_clone(*SETTING_ORIGIN_ID)
+    }
+
+    /// If changes to the setting value only apply after the app restarts.
+    ///
+    /// See [`SettingBuilder::requires_restart`] for more details.
+    pub fn requires_restart(&self) -> bool {
+        self.meta().flagged(*REQUIRES_RESTART_ID)
+    }
+
     /// Gets if the setting should be included in the search and how likely it is to be an exact match (0 is exact).
     ///
     /// If `search` starts with `@key:` matches key case sensitive, otherwise matches name or description in lower case. Note
@@ -455,6 +505,85 @@ struct SettingsService {
     sources_cat: Vec<Box<dyn Fn(&mut CategoriesBuilder) + Send + Sync + 'static>>,
 }
 
+app_local! {
+    static SETTINGS_CHANGES: SettingsChanges = SettingsChanges {
+        tracked: HashMap::new(),
+        restart_changed: HashSet::new(),
+        restart_required: var(false),
+    };
+}
+struct SettingsChanges {
+    // value var is held to keep the hook alive
+    tracked: HashMap<ConfigKey, (BoxedAnyVar, ArcVar<bool>)>,
+    restart_changed: HashSet<ConfigKey>,
+    restart_required: ArcVar<bool>,
+}
+impl SettingsChanges {
+    /// Gets the `is_changed` var for the setting, starts tracking if the `key` was not loaded before.
+    fn track(key: &ConfigKey, value: &BoxedAnyVar, requires_restart: bool) -> BoxedVar<bool> {
+        let mut s = SETTINGS_CHANGES.write();
+        if let Some((_, changed)) = s.tracked.get(key) {
+            return changed.read_only().boxed();
+        }
+
+        let changed = var(false);
+        let initial = value.get_any();
+        let changed_in = changed.clone();
+        let key_in = key.clone();
+        value
+            .hook_any(Box::new(move |args: &AnyVarHookArgs| {
+                let is_changed = !args.value().eq_any(&*initial);
+                changed_in.set(is_changed);
+                if requires_restart {
+                    let mut s = SETTINGS_CHANGES.write();
+                    if is_changed {
+                        s.restart_changed.insert(key_in.clone());
+                    } else {
+                        s.restart_changed.remove(&key_in);
+                    }
+                    let required = !s.restart_changed.is_empty();
+                    s.restart_required.set(required);
+                }
+                true
+            }))
+            .perm();
+
+        s.tracked.insert(key.clone(), (value.clone(), changed.clone()));
+        changed.read_only().boxed()
+    }
+}
+
+static_id! {
+    static ref SETTING_RANGE_ID: StateId<SettingRange>;
+    static ref REQUIRES_RESTART_ID: StateId<()>;
+    static ref SETTING_ORIGIN_ID: StateId<BoxedVar<Option<ConfigLayer>>>;
+}
+
+/// Setting value range.
+///
+/// See [`SettingBuilder::range`] for more details.
+#[derive(Clone)]
+pub struct SettingRange {
+    range: Arc<dyn Any + Send + Sync>,
+    display: Txt,
+}
+impl SettingRange {
+    /// Gets the range, if the value type is `T`.
+    pub fn downcast<T: ConfigValue>(&self) -> Option<&ops::RangeInclusive<T>> {
+        self.range.downcast_ref()
+    }
+
+    /// Display text, `"{min:?}..={max:?}"`.
+    pub fn display(&self) -> &Txt {
+        &self.display
+    }
+}
+impl fmt::Debug for SettingRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SettingRange({})", self.display)
+    }
+}
+
 /// Settings builder.
 pub struct SettingsBuilder<'a> {
     settings: Vec<Setting>,
@@ -603,6 +732,74 @@ impl<'a> SettingBuilder<'a> {
         self.reset = Some(Arc::new(reset));
         self
     }
+
+    /// Use a [`LayeredConfig`] to reset the settings.
+    ///
+    /// The reset removes the key from the write layer, so the value of the next layer that contains the key is used.
+    /// The layer that provides the current value is available in [`Setting::origin`].
+    pub fn layered_reset(&mut self, cfg: &LayeredConfig) -> &mut Self {
+        let origin = cfg.origin(self.config_key.clone());
+        self.reset = Some(Arc::new(LayeredReset { cfg: cfg.clone() }));
+        self.set(*SETTING_ORIGIN_ID, origin)
+    }
+
+    /// Set the inclusive value range.
+    ///
+    /// The value is clamped to the range when read or set using the setting value variable, the range is
+    /// also available in [`Setting::range`] for editors.
+    ///
+    /// Note that this must be called after [`value`] or [`cfg_value`] with a value of the same type.
+    ///
+    /// [`value`]: Self::value
+    /// [`cfg_value`]: Self::cfg_value
+    pub fn range<T: ConfigValue + PartialOrd>(&mut self, min: T, max: T) -> &mut Self {
+        match self.value.take() {
+            Some((value, value_type)) if value_type == TypeId::of::<T>() => {
+                let value = *value.double_boxed_any().downcast::<BoxedVar<T>>().unwrap();
+                let clamp = {
+                    let (min, max) = (min.clone(), max.clone());
+                    move |v: &T| {
+                        if *v < min {
+                            min.clone()
+                        } else if *v > max {
+                            max.clone()
+                        } else {
+                            v.clone()
+                        }
+                    }
+                };
+                let value = value.map_bidi(clamp.clone(), clamp);
+                self.value = Some((value.boxed_any(), value_type));
+            }
+            v => {
+                tracing::error!(
+                    "cannot set range for `{}`, value not set or is not `{}`",
+                    self.config_key,
+                    std::any::type_name::<T>()
+                );
+                self.value = v;
+                return self;
+            }
+        }
+
+        let display = formatx!("{min:?}..={max:?}");
+        self.set(
+            *SETTING_RANGE_ID,
+            SettingRange {
+                range: Arc::new(min..=max),
+                display,
+            },
+        )
+    }
+
+    /// Flag the setting as only applying after the app restarts.
+    ///
+    /// Changes to settings with this flag set [`SETTINGS.restart_required`].
+    ///
+    /// [`SETTINGS.restart_required`]: SETTINGS::restart_required
+    pub fn requires_restart(&mut self) -> &mut Self {
+        self.flag(*REQUIRES_RESTART_ID)
+    }
 }
 impl<'a> Drop for SettingBuilder<'a> {
     fn drop(&mut self) {
@@ -610,6 +807,11 @@ impl<'a> Drop for SettingBuilder<'a> {
             .value
             .take()
             .unwrap_or_else(|| (LocalVar(SettingValueNotSet).boxed_any(), TypeId::of::<SettingValueNotSet>()));
+        let changed = if cfg_type == TypeId::of::<SettingValueNotSet>() {
+            LocalVar(false).boxed()
+        } else {
+            SettingsChanges::track(&self.config_key, &cfg, self.meta.borrow().flagged(*REQUIRES_RESTART_ID))
+        };
         self.settings.push(Setting {
             key: mem::take(&mut self.config_key),
             order: self.order,
@@ -620,6 +822,7 @@ impl<'a> Drop for SettingBuilder<'a> {
             value: cfg,
             value_type: cfg_type,
             reset: self.reset.take().unwrap_or_else(|| Arc::new(SettingValueNotSet)),
+            changed,
         })
     }
 }
@@ -752,11 +955,24 @@ impl SettingReset for FallbackReset {
         }
     }
 }
+struct LayeredReset {
+    cfg: LayeredConfig,
+}
+impl SettingReset for LayeredReset {
+    fn can_reset(&self, key: &ConfigKey, _: &BoxedAnyVar) -> BoxedVar<bool> {
+        let write_layer = self.cfg.write_layer(key);
+        self.cfg.origin(key.clone()).map(move |o| *o == Some(write_layer)).boxed()
+    }
+
+    fn reset(&self, key: &ConfigKey, _: &BoxedAnyVar) {
+        self.cfg.reset(key, self.cfg.write_layer(key));
+    }
+}
 impl SettingReset for Box<dyn AnyVarValue> {
     fn can_reset(&self, _: &ConfigKey, value: &BoxedAnyVar) -> BoxedVar<bool> {
         let mut initial = false;
         value.with_any(&mut |v| {
-            initial = v.eq_any(&**self);
+            initial = !v.eq_any(&**self);
         });
         let map = var(initial);
 
@@ -764,7 +980,7 @@ impl SettingReset for Box<dyn AnyVarValue> {
         let dft = self.clone_boxed();
         value
             .hook_any(Box::new(move |args: &AnyVarHookArgs| {
-                map_in.set(args.value().eq_any(&*dft));
+                map_in.set(!args.value().eq_any(&*dft));
                 true
             }))
             .perm();
@@ -77,7 +77,247 @@ impl ConfigMap for indexmap::IndexMap<ConfigKey, serde_toml::Value> {
 }
 
 /// Represents a config source that synchronizes with a TOML file.
-pub type TomlConfig = SyncConfig<indexmap::IndexMap<ConfigKey, serde_toml::Value>>;
+///
+/// The file is edited in place, comments, key order and formatting of the file are preserved on write.
+pub type TomlConfig = SyncConfig<TomlDocument>;
+
+/// TOML document that preserves comments and formatting.
+///
+/// This is the in memory representation of [`TomlConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct TomlDocument(toml_edit::DocumentMut);
+impl TomlDocument {
+    /// Reference the document.
+    pub fn document(&self) -> &toml_edit::DocumentMut {
+        &self.0
+    }
+}
+impl PartialEq for TomlDocument {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+impl ConfigMap for TomlDocument {
+    fn empty() -> Self {
+        Self::default()
+    }
+
+    fn read(mut file: WatchFile) -> io::Result<Self> {
+        let s = file.string()?;
+        match s.parse::<toml_edit::DocumentMut>() {
+            Ok(d) => Ok(Self(d)),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    fn write(self, file: &mut WriteFile) -> io::Result<()> {
+        let s = self.0.to_string();
+        if s.trim().is_empty() {
+            // see the `IndexMap` impl.
+            file.write_text("#")
+        } else {
+            file.write_text(&s)
+        }
+    }
+
+    fn get_raw(&self, key: &ConfigKey) -> Result<Option<RawConfigValue>, Arc<dyn std::error::Error + Send + Sync>> {
+        match self.0.get(key) {
+            Some(item) => Ok(toml_item_to_json(item)?.map(RawConfigValue)),
+            None => Ok(None),
+        }
+    }
+
+    fn set_raw(map: &mut VarModify<Self>, key: ConfigKey, value: RawConfigValue) -> Result<(), Arc<dyn std::error::Error + Send + Sync>> {
+        if let Some(item) = map.0.get(&key) {
+            if let Ok(Some(v)) = toml_item_to_json(item) {
+                if v == value.0 {
+                    // avoid reformatting values like `1_000`.
+                    return Ok(());
+                }
+            }
+        }
+
+        let value = json_to_toml_edit(value.0)?;
+        let doc = &mut map.to_mut().0;
+        match doc.get_mut(&key) {
+            Some(item) => update_toml_item(item, value),
+            None => {
+                doc.insert(&key, toml_edit::Item::Value(value));
+            }
+        }
+        Ok(())
+    }
+
+    fn contains_key(&self, key: &ConfigKey) -> bool {
+        self.0.contains_key(key)
+    }
+
+    fn remove(map: &mut VarModify<Self>, key: &ConfigKey) {
+        if map.0.contains_key(key) {
+            map.to_mut().0.remove(key);
+        }
+    }
+}
+
+fn toml_item_to_json(item: &toml_edit::Item) -> Result<Option<serde_json::Value>, TomlValueRawError> {
+    let ok = match item {
+        toml_edit::Item::None => return Ok(None),
+        toml_edit::Item::Value(v) => toml_edit_to_json(v)?,
+        toml_edit::Item::Table(t) => toml_table_to_json(t)?,
+        toml_edit::Item::ArrayOfTables(a) => serde_json::Value::Array({
+            let mut r = Vec::with_capacity(a.len());
+            for t in a.iter() {
+                r.push(toml_table_to_json(t)?);
+            }
+            r
+        }),
+    };
+    Ok(Some(ok))
+}
+
+fn toml_table_to_json(table: &toml_edit::Table) -> Result<serde_json::Value, TomlValueRawError> {
+    let mut r = serde_json::Map::with_capacity(table.len());
+    for (k, v) in table.iter() {
+        if let Some(v) = toml_item_to_json(v)? {
+            r.insert(k.to_owned(), v);
+        }
+    }
+    Ok(serde_json::Value::Object(r))
+}
+
+fn toml_edit_to_json(value: &toml_edit::Value) -> Result<serde_json::Value, TomlValueRawError> {
+    let ok = match value {
+        toml_edit::Value::String(s) => serde_json::Value::String(s.value().clone()),
+        toml_edit::Value::Integer(n) => serde_json::Value::Number((*n.value()).into()),
+        toml_edit::Value::Float(f) => match serde_json::Number::from_f64(*f.value()) {
+            Some(f) => serde_json::Value::Number(f),
+            None => return Err(TomlValueRawError::InvalidFloat(*f.value())),
+        },
+        toml_edit::Value::Boolean(b) => serde_json::Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => serde_json::Value::String(d.value().to_string()),
+        toml_edit::Value::Array(a) => serde_json::Value::Array({
+            let mut r = Vec::with_capacity(a.len());
+            for v in a.iter() {
+                r.push(toml_edit_to_json(v)?);
+            }
+            r
+        }),
+        toml_edit::Value::InlineTable(t) => serde_json::Value::Object({
+            let mut r = serde_json::Map::with_capacity(t.len());
+            for (k, v) in t.iter() {
+                r.insert(k.to_owned(), toml_edit_to_json(v)?);
+            }
+            r
+        }),
+    };
+    Ok(ok)
+}
+
+fn json_to_toml_edit(value: serde_json::Value) -> Result<toml_edit::Value, TomlValueRawError> {
+    let ok = match value {
+        serde_json::Value::Null => return Err(TomlValueRawError::Null),
+        serde_json::Value::Bool(b) => toml_edit::Value::from(b),
+        serde_json::Value::Number(n) => {
+            // same as the `toml::Value` conversion.
+            if let Some(n) = n.as_i64() {
+                toml_edit::Value::from(n)
+            } else if let Some(n) = n.as_u64() {
+                if n > i64::MAX as u64 {
+                    return Err(TomlValueRawError::InvalidInt(n));
+                }
+                toml_edit::Value::from(n as i64)
+            } else if let Some(n) = n.as_f64() {
+                toml_edit::Value::from(n)
+            } else {
+                unreachable!()
+            }
+        }
+        serde_json::Value::String(s) => toml_edit::Value::from(s),
+        serde_json::Value::Array(a) => toml_edit::Value::Array({
+            let mut r = toml_edit::Array::new();
+            for v in a {
+                match json_to_toml_edit(v) {
+                    Ok(v) => r.push(v),
+                    Err(TomlValueRawError::Null) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            r
+        }),
+        serde_json::Value::Object(m) => toml_edit::Value::InlineTable({
+            let mut r = toml_edit::InlineTable::new();
+            for (k, v) in m {
+                match json_to_toml_edit(v) {
+                    Ok(v) => {
+                        r.insert(k, v);
+                    }
+                    Err(TomlValueRawError::Null) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            r
+        }),
+    };
+    Ok(ok)
+}
+
+/// Replace the `item` value, retains comments and the table style of the `item`.
+fn update_toml_item(item: &mut toml_edit::Item, value: toml_edit::Value) {
+    match (item, value) {
+        (toml_edit::Item::Table(table), toml_edit::Value::InlineTable(value)) => {
+            table.retain(|k, _| value.contains_key(k));
+            for (k, v) in value {
+                match table.get_mut(&k) {
+                    Some(item) => update_toml_item(item, v),
+                    None => {
+                        table.insert(&k, toml_edit::Item::Value(v));
+                    }
+                }
+            }
+        }
+        (toml_edit::Item::Value(old), value) => update_toml_value(old, value),
+        (item, value) => *item = toml_edit::Item::Value(value),
+    }
+}
+
+/// Replace the `old` value, retains comments, the key order of inline tables and datetimes.
+fn update_toml_value(old: &mut toml_edit::Value, value: toml_edit::Value) {
+    match (old, value) {
+        (toml_edit::Value::InlineTable(table), toml_edit::Value::InlineTable(value)) => {
+            table.retain(|k, _| value.contains_key(k));
+            let len = table.len();
+            for (k, v) in value {
+                match table.get_mut(&k) {
+                    Some(old) => update_toml_value(old, v),
+                    None => {
+                        table.insert(&k, v);
+                    }
+                }
+            }
+            if len > 0 && table.len() > len {
+                // move the space before `}` to the new last entry.
+                let mut values: Vec<_> = table.iter_mut().map(|(_, v)| v).collect();
+                if let Some(s) = values[len - 1].decor().suffix().cloned() {
+                    values[len - 1].decor_mut().set_suffix("");
+                    values.last_mut().unwrap().decor_mut().set_suffix(s);
+                }
+            }
+        }
+        (old, value) => {
+            let value = match (&*old, value) {
+                // datetimes are read as strings, keep the datetime if the new string is still a valid datetime.
+                (toml_edit::Value::Datetime(_), toml_edit::Value::String(s)) => match s.value().parse::<toml_edit::Datetime>() {
+                    Ok(d) => toml_edit::Value::from(d),
+                    Err(_) => toml_edit::Value::String(s),
+                },
+                (_, value) => value,
+            };
+            let decor = old.decor().clone();
+            *old = value;
+            *old.decor_mut() = decor;
+        }
+    }
+}
 
 impl TryFrom<serde_toml::Value> for RawConfigValue {
     type Error = TomlValueRawError;
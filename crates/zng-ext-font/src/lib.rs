@@ -1334,6 +1334,8 @@ struct LoadedFont {
     render_keys: Mutex<Vec<RenderFont>>,
     small_word_cache: RwLock<HashMap<WordCacheKey<[u8; Font::SMALL_WORD_LEN]>, ShapedSegmentData>>,
     word_cache: RwLock<HashMap<WordCacheKey<String>, ShapedSegmentData>>,
+    // glyph and advance of printable ASCII chars, for the monospace fast path.
+    mono_ascii: std::sync::OnceLock<Box<[Option<(u32, f32)>]>>,
 }
 impl fmt::Debug for Font {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1382,6 +1384,7 @@ impl Font {
             render_keys: Mutex::new(vec![]),
             small_word_cache: RwLock::default(),
             word_cache: RwLock::default(),
+            mono_ascii: std::sync::OnceLock::new(),
         }))
     }
 
@@ -167,6 +167,21 @@ impl SegmentedText {
         }
     }
 
+    /// Split the segment that contains the byte `index` in two segments of the same kind and level.
+    ///
+    /// Does nothing if `index` is already a segment boundary, is not a char boundary or is out of bounds. This is
+    /// used to ensure segments do not cross style runs, note that the line wrap algorithm can wrap in between the new
+    /// segments.
+    pub fn split_seg_at(&mut self, index: usize) {
+        if index == 0 || index >= self.text.len() || !self.text.is_char_boundary(index) {
+            return;
+        }
+        if let Err(i) = self.segments.binary_search_by_key(&index, |s| s.end) {
+            let seg = self.segments[i];
+            self.segments.insert(i, TextSegment { end: index, ..seg });
+        }
+    }
+
     /// Contextual direction.
     ///
     /// Note that each segment can override the direction, and even the entire text can be a sequence in
@@ -714,6 +729,23 @@ mod tests {
 
     use crate::{BidiLevel, SegmentedText, TextSegment};
 
+    #[test]
+    fn split_seg_at() {
+        let mut t = SegmentedText::new("Foo bar", LayoutDirection::LTR);
+        let len = t.segs().len();
+
+        t.split_seg_at(3);
+        t.split_seg_at(0);
+        t.split_seg_at(100);
+        assert_eq!(len, t.segs().len());
+
+        t.split_seg_at(5);
+        assert_eq!(len + 1, t.segs().len());
+        let words: Vec<_> = t.iter().map(|(s, _)| s).collect();
+        assert_eq!(vec!["Foo", " ", "b", "ar"], words);
+        assert_eq!(t.segs()[2].kind, t.segs()[3].kind);
+    }
+
     #[test]
     fn segments() {
         let test = "a\nb\r\nc\td ";
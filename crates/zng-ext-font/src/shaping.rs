@@ -1294,6 +1294,208 @@ impl ShapedText {
         caret
     }
 
+    /// Gets the caret in the `line` that is nearest to the `x` offset.
+    ///
+    /// The caret index is snapped to a grapheme boundary of the `text`. If the line does not exist returns the
+    /// end of the last line.
+    pub fn nearest_line_caret(&self, line: usize, x: Px, text: &SegmentedText) -> CaretIndex {
+        match self.line(line) {
+            Some(l) => l.nearest_caret(x, text),
+            None => CaretIndex {
+                index: text.text().len(),
+                line: self.lines_len().saturating_sub(1),
+            },
+        }
+    }
+
+    /// Gets the caret nearest to the `point` in the text.
+    ///
+    /// The caret index is snapped to a grapheme boundary of the `text`.
+    pub fn nearest_caret(&self, point: PxPoint, text: &SegmentedText) -> CaretIndex {
+        match self.nearest_line(point.y) {
+            Some(l) => l.nearest_caret(point.x, text),
+            None => CaretIndex::ZERO,
+        }
+    }
+
+    /// Gets the caret one grapheme to the right of `caret`, in the visual order.
+    ///
+    /// Inside a right-to-left segment this is the previous grapheme in the `text`, at a segment edge the caret moves inside
+    /// the next segment to the right and at the line end moves to the next or previous line, depending on the text base direction.
+    /// Returns the same caret if it cannot move.
+    ///
+    /// The `text` must be the segmented text that was shaped.
+    pub fn caret_right(&self, caret: CaretIndex, text: &SegmentedText) -> CaretIndex {
+        self.visual_caret(caret, text, true, false)
+    }
+
+    /// Gets the caret one grapheme to the left of `caret`, in the visual order.
+    ///
+    /// See [`caret_right`] for more details.
+    ///
+    /// [`caret_right`]: Self::caret_right
+    pub fn caret_left(&self, caret: CaretIndex, text: &SegmentedText) -> CaretIndex {
+        self.visual_caret(caret, text, false, false)
+    }
+
+    /// Gets the caret one word to the right of `caret`, in the visual order.
+    ///
+    /// Inside a right-to-left segment this is the [`prev_word_index`], otherwise it is the [`next_word_index`]. At the
+    /// line end moves to the next or previous line, depending on the text base direction. Returns the same caret if it cannot move.
+    ///
+    /// The `text` must be the segmented text that was shaped.
+    ///
+    /// [`prev_word_index`]: SegmentedText::prev_word_index
+    /// [`next_word_index`]: SegmentedText::next_word_index
+    pub fn caret_word_right(&self, caret: CaretIndex, text: &SegmentedText) -> CaretIndex {
+        self.visual_caret(caret, text, true, true)
+    }
+
+    /// Gets the caret one word to the left of `caret`, in the visual order.
+    ///
+    /// See [`caret_word_right`] for more details.
+    ///
+    /// [`caret_word_right`]: Self::caret_word_right
+    pub fn caret_word_left(&self, caret: CaretIndex, text: &SegmentedText) -> CaretIndex {
+        self.visual_caret(caret, text, false, true)
+    }
+
+    /// Gets the caret at the start of the line of `caret`.
+    ///
+    /// The line is the wrapped line, the start is at the left edge for left-to-right lines and at the right edge for
+    /// right-to-left lines.
+    pub fn caret_line_start(&self, caret: CaretIndex) -> CaretIndex {
+        let caret = self.snap_caret_line(caret);
+        match self.line(caret.line) {
+            Some(l) => CaretIndex {
+                index: l.text_range().start,
+                line: caret.line,
+            },
+            None => caret,
+        }
+    }
+
+    /// Gets the caret at the end of the line of `caret`, before the line-break if any.
+    ///
+    /// See [`caret_line_start`] for more details.
+    ///
+    /// [`caret_line_start`]: Self::caret_line_start
+    pub fn caret_line_end(&self, caret: CaretIndex) -> CaretIndex {
+        let caret = self.snap_caret_line(caret);
+        match self.line(caret.line) {
+            Some(l) => CaretIndex {
+                index: l.text_caret_range().end,
+                line: caret.line,
+            },
+            None => caret,
+        }
+    }
+
+    /// Gets the caret in the line `diff` lines from the line of `caret` that is nearest to the `x` offset.
+    ///
+    /// Editors usually retain the `x` of the caret origin before the first line move, so that the caret returns to the same
+    /// horizontal position after moving over shorter lines. Returns the text start or end if the line is out of bounds.
+    pub fn caret_line_move(&self, caret: CaretIndex, diff: isize, x: Px, text: &SegmentedText) -> CaretIndex {
+        let caret = self.snap_caret_line(caret);
+        let last_line = self.lines_len().saturating_sub(1);
+        match caret.line.checked_add_signed(diff) {
+            Some(l) if l <= last_line => self.nearest_line_caret(l, x, text),
+            Some(_) => CaretIndex {
+                index: text.text().len(),
+                line: last_line,
+            },
+            None => CaretIndex::ZERO,
+        }
+    }
+
+    fn visual_caret(&self, caret: CaretIndex, text: &SegmentedText, right: bool, word: bool) -> CaretIndex {
+        let index = text.snap_grapheme_boundary(caret.index);
+        let caret = self.snap_caret_line(CaretIndex { index, line: caret.line });
+        let line = match self.line(caret.line) {
+            Some(l) => l,
+            None => return caret,
+        };
+
+        // caret segments in the visual order, left-to-right.
+        let mut segs: Vec<_> = line
+            .segs()
+            .filter(|s| !s.kind().is_line_break() && !s.text_range().is_empty())
+            .collect();
+        segs.sort_by_key(|s| s.rect().origin.x);
+
+        let step = |forward: bool, from: usize| {
+            if word {
+                if forward {
+                    text.next_word_index(from)
+                } else {
+                    text.prev_word_index(from)
+                }
+            } else if forward {
+                text.next_insert_index(from)
+            } else {
+                text.prev_insert_index(from)
+            }
+        };
+
+        let seg_i = segs
+            .iter()
+            .position(|s| s.text_range().contains(&index))
+            .or_else(|| segs.iter().position(|s| s.text_end() == index));
+
+        if let Some(i) = seg_i {
+            let seg = &segs[i];
+            let range = seg.text_range();
+            // if moving in the logical order of the segment
+            let forward = seg.direction().is_rtl() != right;
+
+            if forward && index < range.end || !forward && index > range.start {
+                let index = step(forward, index);
+                return self.snap_caret_line(CaretIndex { index, line: caret.line });
+            }
+
+            // at the visual edge of the segment, enter the next segment.
+            let next = if right {
+                segs.get(i + 1)
+            } else {
+                i.checked_sub(1).and_then(|i| segs.get(i))
+            };
+            if let Some(next) = next {
+                let forward = next.direction().is_rtl() != right;
+                let index = if forward {
+                    step(true, next.text_start())
+                } else {
+                    step(false, next.text_end())
+                };
+                return self.snap_caret_line(CaretIndex { index, line: caret.line });
+            }
+        }
+
+        // at the line edge, move to the next or previous line.
+        let next_line = text.base_direction().is_rtl() != right;
+        let next_line = if next_line && caret.index >= line.text_caret_range().end {
+            self.line(caret.line + 1).map(|l| CaretIndex {
+                index: l.text_range().start,
+                line: l.index,
+            })
+        } else if !next_line && caret.index <= line.text_range().start {
+            caret.line.checked_sub(1).and_then(|i| self.line(i)).map(|l| CaretIndex {
+                index: l.text_caret_range().end,
+                line: l.index,
+            })
+        } else if next_line {
+            Some(CaretIndex {
+                index: line.text_caret_range().end,
+                line: caret.line,
+            })
+        } else {
+            Some(CaretIndex {
+                index: line.text_range().start,
+                line: caret.line,
+            })
+        };
+        next_line.unwrap_or(caret)
+    }
+
     /// Gets a full overflow analysis.
     pub fn overflow_info(&self, max_size: PxSize, overflow_suffix_width: Px) -> Option<TextOverflowInfo> {
         // check y overflow
@@ -1825,7 +2027,7 @@ impl ShapedTextBuilder {
         }
     }
 
-    fn shape_text(fonts: &[Font], text: &SegmentedText, config: &TextShapingArgs) -> ShapedText {
+    fn shape_text(fonts: &[Font], runs: &[(ops::Range<usize>, FontList)], text: &SegmentedText, config: &TextShapingArgs) -> ShapedText {
         let mut t = Self {
             out: ShapedText {
                 glyphs: Default::default(),
@@ -1944,7 +2146,7 @@ impl ShapedTextBuilder {
         if let Some(c) = config.obscuring_char {
             t.push_obscured_text(fonts, &config.font_features, &mut word_ctx_key, text, c);
         } else {
-            t.push_text(fonts, &config.font_features, &mut word_ctx_key, text);
+            t.push_text(fonts, runs, &config.font_features, &mut word_ctx_key, text);
         }
 
         t.out.debug_assert_ranges();
@@ -1989,7 +2191,19 @@ impl ShapedTextBuilder {
         self.push_font(&font);
     }
 
-    fn push_text(&mut self, fonts: &[Font], features: &RFontFeatures, word_ctx_key: &mut WordContextKey, text: &SegmentedText) {
+    fn push_text(
+        &mut self,
+        fonts: &[Font],
+        runs: &[(ops::Range<usize>, FontList)],
+        features: &RFontFeatures,
+        word_ctx_key: &mut WordContextKey,
+        text: &SegmentedText,
+    ) {
+        // fonts for the segment that starts at the text byte index.
+        let run_fonts = |start: usize| -> &[Font] { runs.iter().find(|(r, _)| r.contains(&start)).map(|(_, f)| &f[..]).unwrap_or(fonts) };
+        // ligatures cannot join words of different runs.
+        let is_run_boundary = |i: usize| runs.iter().any(|(r, _)| r.start == i || r.end == i);
+
         static LIG: [&[u8]; 4] = [b"liga", b"clig", b"dlig", b"hlig"];
         let ligature_enabled = fonts[0].face().has_ligatures()
             && features.iter().any(|f| {
@@ -2000,27 +2214,35 @@ impl ShapedTextBuilder {
         if ligature_enabled {
             let mut start = 0;
             let mut words_start = None;
+            let mut words_fonts = fonts;
             for (i, info) in text.segs().iter().enumerate() {
                 if info.kind.is_word() && info.kind != TextSegmentKind::Emoji {
+                    if words_start.is_some() && is_run_boundary(start) {
+                        let s = words_start.take().unwrap();
+                        self.push_ligature_words(words_fonts, features, word_ctx_key, text, s, i);
+                    }
                     if words_start.is_none() {
                         words_start = Some(i);
+                        words_fonts = run_fonts(start);
                     }
                 } else {
                     if let Some(s) = words_start.take() {
-                        self.push_ligature_words(fonts, features, word_ctx_key, text, s, i);
+                        self.push_ligature_words(words_fonts, features, word_ctx_key, text, s, i);
                     }
 
                     let seg = &text.text()[start..info.end];
-                    self.push_seg(fonts, features, word_ctx_key, text, seg, *info);
+                    self.push_seg(run_fonts(start), features, word_ctx_key, text, seg, *info);
                 }
                 start = info.end;
             }
             if let Some(s) = words_start.take() {
-                self.push_ligature_words(fonts, features, word_ctx_key, text, s, text.segs().len());
+                self.push_ligature_words(words_fonts, features, word_ctx_key, text, s, text.segs().len());
             }
         } else {
+            let mut start = 0;
             for (seg, info) in text.iter() {
-                self.push_seg(fonts, features, word_ctx_key, text, seg, info);
+                self.push_seg(run_fonts(start), features, word_ctx_key, text, seg, info);
+                start = info.end;
             }
         }
 
@@ -2886,6 +3108,20 @@ impl<'a> ShapedLine<'a> {
         min
     }
 
+    /// Gets the caret in the line that is nearest to the `x` offset.
+    ///
+    /// The caret index is snapped to a grapheme boundary of the `text`.
+    pub fn nearest_caret(&self, x: Px, text: &SegmentedText) -> CaretIndex {
+        let index = match self.nearest_seg(x) {
+            Some(s) => s.nearest_char_index(x, text.text()),
+            None => self.text_range().end,
+        };
+        CaretIndex {
+            index: text.snap_grapheme_boundary(index),
+            line: self.index,
+        }
+    }
+
     /// Gets the line index.
     pub fn index(&self) -> usize {
         self.index
@@ -3025,6 +3261,13 @@ impl<'a> ShapedSegment<'a> {
         self.text.segments.glyphs(self.index)
     }
 
+    /// Range of the segment glyphs in the full shaped text.
+    ///
+    /// See [`ShapedText::glyphs_slice`] for more details.
+    pub fn glyphs_index_range(&self) -> ops::Range<usize> {
+        self.glyphs_range().into()
+    }
+
     /// Glyphs in the word or space.
     ///
     /// The glyphs are in visual order (LTR) within segments, so
@@ -3640,6 +3883,51 @@ impl Font {
         }
     }
 
+    /// Shape without harfbuzz, if the font is monospace without substitutions and the segment is printable ASCII.
+    ///
+    /// Returns `None` if the fast path does not apply or the font does not have a glyph for a char.
+    fn shape_segment_monospace(&self, seg: &str, key: &WordContextKey, features: &[harfbuzz_rs::Feature]) -> Option<ShapedSegmentData> {
+        let face = self.face();
+        if !face.is_monospace()
+            || face.has_ligatures()
+            || face.is_empty()
+            || !features.is_empty()
+            || key.direction != LayoutDirection::LTR
+            || !seg.bytes().all(|b| (b' '..=b'~').contains(&b))
+        {
+            return None;
+        }
+
+        let table = self.0.mono_ascii.get_or_init(|| {
+            let size_scale = self.metrics().size_scale;
+            let font = self.harfbuzz_font();
+            (b' '..=b'~')
+                .map(|c| {
+                    font.get_nominal_glyph(c as char)
+                        .map(|g| (g, font.get_glyph_h_advance(g) as f32 * size_scale))
+                })
+                .collect()
+        });
+
+        let mut x_advance = 0.0;
+        let mut glyphs = Vec::with_capacity(seg.len());
+        for (i, b) in seg.bytes().enumerate() {
+            let (index, advance) = table[(b - b' ') as usize]?;
+            glyphs.push(ShapedGlyph {
+                index,
+                cluster: i as u32,
+                point: (x_advance, 0.0),
+            });
+            x_advance += advance;
+        }
+
+        Some(ShapedSegmentData {
+            glyphs,
+            x_advance,
+            y_advance: 0.0,
+        })
+    }
+
     fn shape_segment<R>(
         &self,
         seg: &str,
@@ -3647,7 +3935,9 @@ impl Font {
         features: &[harfbuzz_rs::Feature],
         out: impl FnOnce(&ShapedSegmentData) -> R,
     ) -> R {
-        if !(1..=WORD_CACHE_MAX_LEN).contains(&seg.len()) || self.face().is_empty() {
+        if let Some(seg) = self.shape_segment_monospace(seg, word_ctx_key, features) {
+            out(&seg)
+        } else if !(1..=WORD_CACHE_MAX_LEN).contains(&seg.len()) || self.face().is_empty() {
             let seg = self.shape_segment_no_cache(seg, word_ctx_key, features);
             out(&seg)
         } else if let Some(small) = Self::to_small_word(seg) {
@@ -3757,7 +4047,7 @@ impl Font {
 
     /// Calculates a [`ShapedText`].
     pub fn shape_text(self: &Font, text: &SegmentedText, config: &TextShapingArgs) -> ShapedText {
-        ShapedTextBuilder::shape_text(&[self.clone()], text, config)
+        ShapedTextBuilder::shape_text(std::slice::from_ref(self), &[], text, config)
     }
 
     /// Sends the sized vector path for a glyph to `sink`.
@@ -3983,7 +4273,16 @@ pub trait OutlineSink {
 impl FontList {
     /// Calculates a [`ShapedText`] using the [best](FontList::best) font in this list and the other fonts as fallback.
     pub fn shape_text(&self, text: &SegmentedText, config: &TextShapingArgs) -> ShapedText {
-        ShapedTextBuilder::shape_text(self, text, config)
+        ShapedTextBuilder::shape_text(self, &[], text, config)
+    }
+
+    /// Calculates a [`ShapedText`] using this font list as default and other font lists for ranges of the text.
+    ///
+    /// The `runs` are byte ranges in the text, the font list of the first run that contains the start of a segment is
+    /// used to shape it. Segments should not cross run boundaries, see [`SegmentedText::split_seg_at`]. Line metrics are
+    /// computed from this font list only, so the fonts of the runs should have the same size.
+    pub fn shape_text_runs(&self, text: &SegmentedText, runs: &[(ops::Range<usize>, FontList)], config: &TextShapingArgs) -> ShapedText {
+        ShapedTextBuilder::shape_text(self, runs, text, config)
     }
 }
 
@@ -4074,7 +4373,9 @@ fn into_harf_direction(d: LayoutDirection) -> harfbuzz_rs::Direction {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Font, FontManager, FontName, FontStretch, FontStyle, FontWeight, SegmentedText, TextShapingArgs, WordContextKey, FONTS};
+    use crate::{
+        CaretIndex, Font, FontManager, FontName, FontStretch, FontStyle, FontWeight, SegmentedText, TextShapingArgs, WordContextKey, FONTS,
+    };
     use zng_app::APP;
     use zng_ext_l10n::lang;
     use zng_layout::{
@@ -4188,6 +4489,57 @@ mod tests {
         assert_eq!(test.size(), expected.size());
     }
 
+    #[test]
+    fn caret_visual_navigation_ltr() {
+        let font = test_font();
+        let text = SegmentedText::new("ab cd\nef", LayoutDirection::LTR);
+        let shaped = font.shape_text(&text, &TextShapingArgs::default());
+
+        let mut caret = CaretIndex::ZERO;
+        let mut right = vec![];
+        for _ in 0..9 {
+            caret = shaped.caret_right(caret, &text);
+            right.push((caret.index, caret.line));
+        }
+        assert_eq!(right, vec![(1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 1), (7, 1), (8, 1), (8, 1)]);
+
+        let mut left = vec![];
+        for _ in 0..9 {
+            caret = shaped.caret_left(caret, &text);
+            left.push((caret.index, caret.line));
+        }
+        assert_eq!(left, vec![(7, 1), (6, 1), (5, 0), (4, 0), (3, 0), (2, 0), (1, 0), (0, 0), (0, 0)]);
+
+        let caret = shaped.caret_word_right(CaretIndex::ZERO, &text);
+        assert_eq!(caret.index, 3);
+        assert_eq!(shaped.caret_word_left(caret, &text).index, 0);
+
+        let caret = CaretIndex { index: 4, line: 0 };
+        assert_eq!(shaped.caret_line_start(caret).index, 0);
+        assert_eq!(shaped.caret_line_end(caret).index, 5);
+        assert_eq!(shaped.caret_line_end(CaretIndex { index: 6, line: 1 }).index, 8);
+    }
+
+    #[test]
+    fn caret_visual_navigation_rtl() {
+        let font = test_font();
+        let text = SegmentedText::new("אבג", LayoutDirection::RTL);
+        let shaped = font.shape_text(
+            &text,
+            &TextShapingArgs {
+                direction: LayoutDirection::RTL,
+                ..Default::default()
+            },
+        );
+
+        // moving left in RTL text is moving forward.
+        let caret = shaped.caret_left(CaretIndex::ZERO, &text);
+        assert_eq!(caret.index, "א".len());
+        let caret = shaped.caret_right(caret, &text);
+        assert_eq!(caret.index, 0);
+        assert_eq!(shaped.caret_right(caret, &text).index, 0);
+    }
+
     #[test]
     fn font_fallback_issue() {
         let mut app = APP.minimal().extend(FontManager::default()).run_headless(false);
@@ -4217,6 +4569,39 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn monospace_fast_path() {
+        let mut app = APP.minimal().extend(FontManager::default()).run_headless(false);
+        let font = app
+            .block_on_fut(
+                async {
+                    FONTS
+                        .normal(&FontName::monospace(), &lang!(und))
+                        .wait_rsp()
+                        .await
+                        .unwrap()
+                        .sized(Px(20), vec![])
+                },
+                60.secs(),
+            )
+            .unwrap();
+        drop(app);
+
+        let key = WordContextKey::new(&lang!("en-US"), LayoutDirection::LTR, &vec![]);
+        let seg = "let a = b[0];";
+        if let Some(fast) = font.shape_segment_monospace(seg, &key, &[]) {
+            let shaped = font.shape_segment_no_cache(seg, &key, &[]);
+            assert_eq!(shaped.x_advance, fast.x_advance);
+            assert_eq!(shaped.glyphs.len(), fast.glyphs.len());
+            for (a, b) in shaped.glyphs.iter().zip(&fast.glyphs) {
+                assert_eq!(a.index, b.index);
+                assert_eq!(a.cluster, b.cluster);
+                assert_eq!(a.point, b.point);
+            }
+        }
+        assert!(font.shape_segment_monospace("£a", &key, &[]).is_none());
+    }
+
     #[test]
     fn cluster_is_byte() {
         let font = test_font();
@@ -112,7 +112,7 @@ impl WATCHER {
     ///
     /// [`sync`]: WATCHER::sync
     pub fn sync_debounce(&self) -> ArcVar<Duration> {
-        WATCHER_SV.read().debounce.clone()
+        WATCHER_SV.read().sync_debounce.clone()
     }
 
     /// Gets a read-write variable that defines the fallback poll watcher interval.
@@ -158,6 +158,18 @@ impl WATCHER {
         WATCHER_SV.write().watch_dir(dir.into(), recursive)
     }
 
+    /// Enable file change events for files inside `dir` that match the `options` filters.
+    ///
+    /// Changes that are only matched by watches with custom [`WatchOptions::debounce`] are notified in a separate
+    /// [`FS_CHANGES_EVENT`] after the custom interval.
+    ///
+    /// See [`watch_dir`] for more details.
+    ///
+    /// [`watch_dir`]: WATCHER::watch_dir
+    pub fn watch_dir_with(&self, dir: impl Into<PathBuf>, recursive: bool, options: WatchOptions) -> WatcherHandle {
+        WATCHER_SV.write().watch_dir_with(dir.into(), recursive, options)
+    }
+
     /// Read a file into a variable, the `init` value will start the variable and the `read` closure will be called
     /// once immediately and every time the file changes, if the closure returns `Some(O)` the variable updates with the new value.
     ///
@@ -350,6 +362,37 @@ impl WATCHER {
         }))
     }
 
+    /// Watch `dir` with `options` and calls `handler` every time a file matched by the options changes.
+    ///
+    /// Note that the event args can contain changes from other watches, use [`WatchOptions::matches`] to filter.
+    ///
+    /// See [`on_dir_changed`] and [`watch_dir_with`] for more details.
+    ///
+    /// [`on_dir_changed`]: WATCHER::on_dir_changed
+    /// [`watch_dir_with`]: WATCHER::watch_dir_with
+    pub fn on_dir_changed_with(
+        &self,
+        dir: impl Into<PathBuf>,
+        recursive: bool,
+        options: WatchOptions,
+        handler: impl AppHandler<FsChangesArgs>,
+    ) -> EventHandle {
+        let mut dir = dir.into();
+        if let Ok(d) = dir.absolutize() {
+            dir = d.into_owned();
+        }
+        let handle = self.watch_dir_with(dir.clone(), recursive, options.clone());
+        FS_CHANGES_EVENT.on_event(FilterAppHandler::new(handler, move |args| {
+            let _handle = &handle;
+            args.events().any(|ev| {
+                ev.paths.iter().any(|p| match p.strip_prefix(&dir) {
+                    Ok(p) => options.matches(p),
+                    Err(_) => false,
+                })
+            })
+        }))
+    }
+
     /// Push a `note` that will be cloned on all subsequent change events until it the returned handle is dropped.
     ///
     /// This can be used to tag all events that happened over a period of time, something you can't do just
@@ -879,6 +922,42 @@ impl FsChange {
     }
 }
 
+/// Options for [`WATCHER.watch_dir_with`].
+///
+/// [`WATCHER.watch_dir_with`]: WATCHER::watch_dir_with
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WatchOptions {
+    /// Glob patterns of paths that are watched, if empty all paths are included.
+    ///
+    /// The patterns are matched with the path relative to the watched directory.
+    pub include: Vec<glob::Pattern>,
+    /// Glob patterns of paths that are not watched, even if included.
+    ///
+    /// The patterns are matched with the path relative to the watched directory.
+    pub exclude: Vec<glob::Pattern>,
+    /// Custom debounce interval for changes matched by the watch.
+    ///
+    /// If `None` the [`WATCHER.debounce`] is used. If a change is matched by multiple watches the
+    /// default debounce is used if any of the watches does not set a custom debounce, otherwise the
+    /// shortest custom debounce is used.
+    ///
+    /// [`WATCHER.debounce`]: WATCHER::debounce
+    pub debounce: Option<Duration>,
+    /// Only notify files that actually changed content.
+    ///
+    /// If `true` the raw changes matched only by digest watches are collected and compared with a hash of
+    /// the file content, only one change is notified per file, a modify data change if the hash changed, a create
+    /// or remove change if the file started or stopped existing. Directory changes are ignored.
+    pub digest: bool,
+}
+impl WatchOptions {
+    /// If the `relative_path` is included and not excluded.
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|g| g.matches_path(relative_path)))
+            && !self.exclude.iter().any(|g| g.matches_path(relative_path))
+    }
+}
+
 event_args! {
      /// [`FS_CHANGES_EVENT`] arguments.
     pub struct FsChangesArgs {
@@ -992,9 +1071,9 @@ struct WatcherService {
 
     watcher: Watchers,
 
-    debounce_oldest: DInstant,
-    debounce_buffer: Vec<FsChange>,
-    debounce_timer: Option<DeadlineHandle>,
+    // debounce buffers by custom debounce, `None` uses `debounce`.
+    debounce_buffers: Vec<DebounceBuffer>,
+    digests: Arc<Mutex<HashMap<PathBuf, u64>>>,
 
     read_to_var: Vec<ReadToVar>,
     sync_with_var: Vec<SyncWithVar>,
@@ -1009,9 +1088,8 @@ impl WatcherService {
             poll_interval: var(1.secs()),
             shutdown_timeout: var(1.minutes()),
             watcher: Watchers::new(),
-            debounce_oldest: INSTANT.now(),
-            debounce_buffer: vec![],
-            debounce_timer: None,
+            debounce_buffers: vec![],
+            digests: Arc::new(Mutex::new(HashMap::new())),
             read_to_var: vec![],
             sync_with_var: vec![],
             notes: vec![],
@@ -1037,10 +1115,10 @@ impl WatcherService {
         if let Some(n) = self.poll_interval.get_new() {
             self.watcher.set_poll_interval(n);
         }
-        if !self.debounce_buffer.is_empty() {
-            if let Some(n) = self.debounce.get_new() {
-                if self.debounce_oldest.elapsed() >= n {
-                    self.notify();
+        if let Some(n) = self.debounce.get_new() {
+            if let Some(i) = self.debounce_buffers.iter().position(|b| b.debounce.is_none()) {
+                if !self.debounce_buffers[i].is_empty() && self.debounce_buffers[i].oldest.elapsed() >= n {
+                    self.notify(None);
                 }
             }
         }
@@ -1054,7 +1132,33 @@ impl WatcherService {
     }
 
     fn watch_dir(&mut self, dir: PathBuf, recursive: bool) -> WatcherHandle {
-        self.watcher.watch_dir(dir, recursive)
+        self.watcher.watch_dir(dir, recursive, None)
+    }
+
+    fn watch_dir_with(&mut self, dir: PathBuf, recursive: bool, options: WatchOptions) -> WatcherHandle {
+        let digest = options.digest;
+        let handle = self.watcher.watch_dir(dir.clone(), recursive, Some(options.clone()));
+
+        if digest && !handle.is_dropped() {
+            // init digests, so that the first change can be compared.
+            let digests = self.digests.clone();
+            let dir = dir.absolutize().map(|d| d.into_owned()).unwrap_or(dir);
+            task::spawn_wait(move || {
+                let walk = walkdir::WalkDir::new(&dir).min_depth(1);
+                let walk = if recursive { walk } else { walk.max_depth(1) };
+                for entry in walk.into_iter().filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    if !entry.file_type().is_file() || !path.strip_prefix(&dir).map(|p| options.matches(p)).unwrap_or(false) {
+                        continue;
+                    }
+                    if let Some(d) = file_digest(path) {
+                        digests.lock().entry(path.to_path_buf()).or_insert(d);
+                    }
+                }
+            });
+        }
+
+        handle
     }
 
     fn read<O: VarValue>(
@@ -1271,14 +1375,14 @@ impl WatcherService {
     }
 
     fn on_watcher(&mut self, r: Result<fs_event::Event, fs_event::Error>) {
-        if let Ok(r) = &r {
-            if !self.watcher.allow(r) {
+        let m = match &r {
+            Ok(r) => match self.watcher.allow(r) {
+                Some(m) => m,
                 // file parent watcher, file not affected.
-                return;
-            }
-        }
-
-        let notify = self.debounce_oldest.elapsed() >= self.debounce.get();
+                None => return,
+            },
+            Err(_) => WatchMatch::default(),
+        };
 
         let mut notes = Vec::with_capacity(self.notes.len());
         self.notes.retain(|n| match n.upgrade() {
@@ -1289,15 +1393,44 @@ impl WatcherService {
             None => false,
         });
 
-        self.debounce_buffer.push(FsChange { notes, event: r });
+        let debounce = m.debounce.unwrap_or_else(|| self.debounce.get());
+        let i = match self.debounce_buffers.iter().position(|b| b.debounce == m.debounce) {
+            Some(i) => i,
+            None => {
+                self.debounce_buffers.push(DebounceBuffer {
+                    debounce: m.debounce,
+                    oldest: DInstant::EPOCH,
+                    changes: vec![],
+                    digest: vec![],
+                    timer: None,
+                });
+                self.debounce_buffers.len() - 1
+            }
+        };
+        let b = &mut self.debounce_buffers[i];
+
+        let notify = b.oldest.elapsed() >= debounce;
+
+        match r {
+            Ok(r) if m.digest && !r.need_rescan() => {
+                for path in r.paths {
+                    match b.digest.iter_mut().find(|(p, _)| p == &path) {
+                        Some((_, n)) => *n = notes.clone(),
+                        None => b.digest.push((path, notes.clone())),
+                    }
+                }
+            }
+            r => b.changes.push(FsChange { notes, event: r }),
+        }
 
         if notify {
-            self.notify();
-        } else if self.debounce_timer.is_none() {
-            self.debounce_timer = Some(TIMERS.on_deadline(
-                self.debounce.get(),
+            self.notify(m.debounce);
+        } else if b.timer.is_none() {
+            let key = m.debounce;
+            b.timer = Some(TIMERS.on_deadline(
+                debounce,
                 app_hn_once!(|_| {
-                    WATCHER_SV.write().on_debounce_timer();
+                    WATCHER_SV.write().on_debounce_timer(key);
                 }),
             ));
         }
@@ -1309,19 +1442,54 @@ impl WatcherService {
         FsChangeNoteHandle(handle)
     }
 
-    fn on_debounce_timer(&mut self) {
-        if !self.debounce_buffer.is_empty() {
-            self.notify();
+    fn on_debounce_timer(&mut self, debounce: Option<Duration>) {
+        if let Some(b) = self.debounce_buffers.iter().find(|b| b.debounce == debounce) {
+            if !b.is_empty() {
+                self.notify(debounce);
+            }
         }
     }
 
-    fn notify(&mut self) {
-        let changes = mem::take(&mut self.debounce_buffer);
+    fn notify(&mut self, debounce: Option<Duration>) {
+        let b = match self.debounce_buffers.iter_mut().find(|b| b.debounce == debounce) {
+            Some(b) => b,
+            None => return,
+        };
+        let mut changes = mem::take(&mut b.changes);
+        let digest = mem::take(&mut b.digest);
         let now = INSTANT.now();
-        self.debounce_oldest = now;
-        self.debounce_timer = None;
+        b.oldest = now;
+        b.timer = None;
+
+        if digest.is_empty() {
+            FS_CHANGES_EVENT.notify(FsChangesArgs::new(now, Default::default(), changes));
+            return;
+        }
 
-        FS_CHANGES_EVENT.notify(FsChangesArgs::new(now, Default::default(), changes));
+        // compare digests in a background thread.
+        let digests = self.digests.clone();
+        task::spawn_wait(move || {
+            for (path, notes) in digest {
+                let new = if path.is_file() { file_digest(&path) } else { None };
+                let prev = match new {
+                    Some(d) => digests.lock().insert(path.clone(), d),
+                    None => digests.lock().remove(&path),
+                };
+                let kind = match (prev, new) {
+                    (None, Some(_)) => fs_event::EventKind::Create(fs_event::CreateKind::File),
+                    (Some(_), None) => fs_event::EventKind::Remove(fs_event::RemoveKind::File),
+                    (Some(a), Some(b)) if a != b => fs_event::EventKind::Modify(fs_event::ModifyKind::Data(fs_event::DataChange::Content)),
+                    _ => continue,
+                };
+                changes.push(FsChange {
+                    notes,
+                    event: Ok(fs_event::Event::new(kind).add_path(path)),
+                });
+            }
+            if !changes.is_empty() {
+                FS_CHANGES_EVENT.notify(FsChangesArgs::now(changes));
+            }
+        });
     }
 
     /// Deinit watcher, returns items to flush without a service lock.
@@ -1330,6 +1498,38 @@ impl WatcherService {
         mem::take(&mut self.sync_with_var)
     }
 }
+struct DebounceBuffer {
+    debounce: Option<Duration>,
+    oldest: DInstant,
+    changes: Vec<FsChange>,
+    // changes matched only by digest watches.
+    digest: Vec<(PathBuf, Vec<Arc<dyn FsChangeNote>>)>,
+    timer: Option<DeadlineHandle>,
+}
+impl DebounceBuffer {
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.digest.is_empty()
+    }
+}
+
+/// Hash of the file content.
+fn file_digest(path: &Path) -> Option<u64> {
+    use std::{hash::Hasher as _, io::Read as _};
+
+    let mut file = fs::File::open(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => hasher.write(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => return None,
+        }
+    }
+    Some(hasher.finish())
+}
+
 fn notify_watcher_handler() -> impl notify::EventHandler {
     let mut ctx = LocalContext::capture();
     move |r| ctx.with_context(|| WATCHER_SV.write().on_watcher(r))
@@ -1663,15 +1863,15 @@ impl Watchers {
     }
 
     fn watch(&mut self, file: PathBuf) -> WatcherHandle {
-        self.watch_insert(file, WatchMode::File(std::ffi::OsString::new()))
+        self.watch_insert(file, WatchMode::File(std::ffi::OsString::new()), None)
     }
 
-    fn watch_dir(&mut self, dir: PathBuf, recursive: bool) -> WatcherHandle {
-        self.watch_insert(dir, if recursive { WatchMode::Descendants } else { WatchMode::Children })
+    fn watch_dir(&mut self, dir: PathBuf, recursive: bool, options: Option<WatchOptions>) -> WatcherHandle {
+        self.watch_insert(dir, if recursive { WatchMode::Descendants } else { WatchMode::Children }, options)
     }
 
     /// path can still contain the file name if mode is `WatchMode::File("")`
-    fn watch_insert(&mut self, mut path: PathBuf, mut mode: WatchMode) -> WatcherHandle {
+    fn watch_insert(&mut self, mut path: PathBuf, mut mode: WatchMode, options: Option<WatchOptions>) -> WatcherHandle {
         use path_absolutize::*;
         path = match path.absolutize() {
             Ok(p) => p.to_path_buf(),
@@ -1693,8 +1893,8 @@ impl Watchers {
 
         let w = self.dirs.entry(path.clone()).or_default();
 
-        for (m, handle) in &w.modes {
-            if m == &mode {
+        for (m, o, handle) in &w.modes {
+            if m == &mode && o == &options {
                 if let Some(h) = handle.weak_handle().upgrade() {
                     return WatcherHandle(h);
                 }
@@ -1726,7 +1926,7 @@ impl Watchers {
             }
         }
 
-        w.modes.push((mode, owner));
+        w.modes.push((mode, options, owner));
 
         WatcherHandle(handle)
     }
@@ -1855,75 +2055,83 @@ impl Watchers {
         }
     }
 
-    fn allow(&mut self, r: &fs_event::Event) -> bool {
+    fn allow(&mut self, r: &fs_event::Event) -> Option<WatchMatch> {
         if let notify::EventKind::Access(_) = r.kind {
             if !r.need_rescan() {
-                return false;
+                return None;
             }
         }
 
+        let mut r_match: Option<WatchMatch> = None;
         for (dir, w) in &mut self.dirs {
             let mut matched = false;
 
-            'modes: for (mode, _) in &w.modes {
-                match mode {
-                    WatchMode::File(f) => {
-                        for path in &r.paths {
-                            if let Some(name) = path.file_name() {
-                                if name == f {
-                                    if let Some(path) = path.parent() {
-                                        if path == dir {
-                                            // matched `dir/exact`
-                                            matched = true;
-                                            break 'modes;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    WatchMode::Children => {
-                        for path in &r.paths {
-                            if let Some(path) = path.parent() {
-                                if path == dir {
-                                    // matched `dir/*`
-                                    matched = true;
-                                    break 'modes;
-                                }
-                            }
-                        }
-                    }
-                    WatchMode::Descendants => {
-                        for path in &r.paths {
-                            if path.starts_with(dir) {
-                                // matched `dir/**`
-                                matched = true;
-                                break 'modes;
-                            }
-                        }
-                    }
+            for (mode, options, _) in &w.modes {
+                let mode_matched = match mode {
+                    WatchMode::File(f) => r.paths.iter().any(|path| {
+                        // matched `dir/exact`
+                        path.file_name() == Some(f.as_os_str()) && path.parent() == Some(dir.as_path())
+                    }),
+                    WatchMode::Children => r.paths.iter().any(|path| {
+                        // matched `dir/*`
+                        path.parent() == Some(dir.as_path()) && Self::options_allow(options, dir, path)
+                    }),
+                    WatchMode::Descendants => r.paths.iter().any(|path| {
+                        // matched `dir/**`
+                        path.starts_with(dir) && Self::options_allow(options, dir, path)
+                    }),
+                };
+                if mode_matched {
+                    matched = true;
+                    let debounce = options.as_ref().and_then(|o| o.debounce);
+                    let digest = options.as_ref().map(|o| o.digest).unwrap_or(false);
+                    r_match = Some(match r_match {
+                        None => WatchMatch { debounce, digest },
+                        Some(m) => WatchMatch {
+                            debounce: match (m.debounce, debounce) {
+                                (Some(a), Some(b)) => Some(a.min(b)),
+                                _ => None,
+                            },
+                            digest: m.digest && digest,
+                        },
+                    });
                 }
             }
 
-            if matched {
-                if mem::take(&mut w.is_in_error_watcher) {
-                    // poll watcher managed to reach the path without error, try to move to the
-                    // more performant system watcher.
-                    Self::inner_unwatch_dir(self.error_watcher.as_mut().unwrap(), dir);
-                    let recursive = w.recursive();
-                    if Self::inner_watch_dir(&mut **self.watcher.get_mut(), dir, recursive).is_err() {
-                        // failed again
-                        Self::inner_watch_error_dir(&mut self.error_watcher, dir, recursive, self.poll_interval);
-                        w.is_in_error_watcher = true;
-                    }
+            if matched && mem::take(&mut w.is_in_error_watcher) {
+                // poll watcher managed to reach the path without error, try to move to the
+                // more performant system watcher.
+                Self::inner_unwatch_dir(self.error_watcher.as_mut().unwrap(), dir);
+                let recursive = w.recursive();
+                if Self::inner_watch_dir(&mut **self.watcher.get_mut(), dir, recursive).is_err() {
+                    // failed again
+                    Self::inner_watch_error_dir(&mut self.error_watcher, dir, recursive, self.poll_interval);
+                    w.is_in_error_watcher = true;
                 }
-                return true;
             }
         }
-        false
+        r_match
+    }
+
+    fn options_allow(options: &Option<WatchOptions>, dir: &Path, path: &Path) -> bool {
+        match options {
+            Some(o) => match path.strip_prefix(dir) {
+                Ok(p) => o.matches(p),
+                Err(_) => false,
+            },
+            None => true,
+        }
     }
 }
 
+#[derive(Default, Clone, Copy)]
+struct WatchMatch {
+    // custom debounce, `None` is default.
+    debounce: Option<Duration>,
+    // all matched watches are digest.
+    digest: bool,
+}
+
 #[derive(PartialEq, Eq)]
 enum WatchMode {
     File(std::ffi::OsString),
@@ -1934,7 +2142,7 @@ enum WatchMode {
 #[derive(Default)]
 struct DirWatcher {
     is_in_error_watcher: bool,
-    modes: Vec<(WatchMode, HandleOwner<()>)>,
+    modes: Vec<(WatchMode, Option<WatchOptions>, HandleOwner<()>)>,
 }
 impl DirWatcher {
     fn recursive(&self) -> bool {
@@ -1942,7 +2150,7 @@ impl DirWatcher {
     }
 
     fn retain(&mut self) -> bool {
-        self.modes.retain(|(_, h)| !h.is_dropped());
+        self.modes.retain(|(_, _, h)| !h.is_dropped());
         !self.modes.is_empty()
     }
 }
@@ -2195,3 +2403,64 @@ fn unlock_ok(file: &impl fs4::FileExt) -> std::io::Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(include: &[&str], exclude: &[&str]) -> WatchOptions {
+        WatchOptions {
+            include: include.iter().map(|p| glob::Pattern::new(p).unwrap()).collect(),
+            exclude: exclude.iter().map(|p| glob::Pattern::new(p).unwrap()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_empty_includes_all() {
+        let o = WatchOptions::default();
+        assert!(o.matches(Path::new("a.txt")));
+        assert!(o.matches(Path::new("sub/a.rs")));
+    }
+
+    #[test]
+    fn matches_include() {
+        let o = options(&["*.txt", "data/**/*.json"], &[]);
+        assert!(o.matches(Path::new("a.txt")));
+        assert!(o.matches(Path::new("data/a.json")));
+        assert!(o.matches(Path::new("data/sub/a.json")));
+        assert!(!o.matches(Path::new("a.rs")));
+        assert!(!o.matches(Path::new("other/a.json")));
+    }
+
+    #[test]
+    fn matches_exclude_over_include() {
+        let o = options(&["**/*.txt"], &["target/**"]);
+        assert!(o.matches(Path::new("a.txt")));
+        assert!(o.matches(Path::new("src/a.txt")));
+        assert!(!o.matches(Path::new("target/a.txt")));
+        assert!(!o.matches(Path::new("target/debug/a.txt")));
+    }
+
+    #[test]
+    fn matches_exclude_only() {
+        let o = options(&[], &["*.tmp"]);
+        assert!(o.matches(Path::new("a.txt")));
+        assert!(!o.matches(Path::new("a.tmp")));
+    }
+
+    #[test]
+    fn options_allow_relative_to_dir() {
+        let dir = Path::new("/watched");
+        let o = Some(options(&["*.txt"], &[]));
+        assert!(Watchers::options_allow(&o, dir, Path::new("/watched/a.txt")));
+        assert!(!Watchers::options_allow(&o, dir, Path::new("/watched/a.rs")));
+        // pattern is not matched with the full path
+        let o_abs = Some(options(&["/watched/*.txt"], &[]));
+        assert!(!Watchers::options_allow(&o_abs, dir, Path::new("/watched/a.txt")));
+        // path outside dir
+        assert!(!Watchers::options_allow(&o, dir, Path::new("/other/a.txt")));
+        // no options
+        assert!(Watchers::options_allow(&None, dir, Path::new("/other/a.rs")));
+    }
+}
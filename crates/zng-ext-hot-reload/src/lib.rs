@@ -23,6 +23,7 @@ use std::{
 pub use cargo::BuildError;
 use node::*;
 
+use serde::{de::DeserializeOwned, Serialize};
 use zng_app::{
     event::{event, event_args},
     handler::async_clmv,
@@ -36,7 +37,7 @@ use zng_task::{parking_lot::Mutex, SignalOnce};
 use zng_txt::Txt;
 use zng_unique_id::hot_reload::HOT_STATICS;
 use zng_unit::TimeUnits as _;
-use zng_var::{ArcVar, ReadOnlyArcVar, ResponseVar, Var as _};
+use zng_var::{ArcVar, ReadOnlyArcVar, ResponseVar, Var, VarValue};
 
 #[doc(inline)]
 pub use zng_unique_id::{hot_static, hot_static_ref, lazy_static};
@@ -468,6 +469,81 @@ impl HOT_RELOAD {
         UPDATES.update(None);
     }
 
+    /// Gets a variable that preserves its value across hot reloads.
+    ///
+    /// The `key` identifies the state, on the first call the variable value is `init`, after a hot reload the
+    /// variable is restored to the last value of a variable with the same key. Use this to keep state inited inside
+    /// hot nodes, like the selected tab or scroll position, while iterating on the UI.
+    ///
+    /// See [`persist_var`] for more details.
+    ///
+    /// [`persist_var`]: Self::persist_var
+    pub fn persistent_var<T>(&self, key: impl Into<Txt>, init: impl FnOnce() -> T) -> ArcVar<T>
+    where
+        T: VarValue + Serialize + DeserializeOwned,
+    {
+        let key = key.into();
+        let value = self.restore(&key).unwrap_or_else(init);
+        let var = zng_var::var(value);
+        Self::save_on_update(key, &var);
+        var
+    }
+
+    /// Mark the `var` as reload-persistent.
+    ///
+    /// If a snapshot of `key` exists the variable is set to it, after every variable update a new snapshot is saved.
+    ///
+    /// The value is serialized to JSON and kept by the static host program, so the value type may change between library
+    /// versions, if the snapshot fails to deserialize it is ignored. Snapshots are kept only in memory, use [`clear_persisted`]
+    /// to reset the state without restarting.
+    ///
+    /// [`clear_persisted`]: Self::clear_persisted
+    pub fn persist_var<T>(&self, key: impl Into<Txt>, var: &impl Var<T>)
+    where
+        T: VarValue + Serialize + DeserializeOwned,
+    {
+        let key = key.into();
+        if let Some(value) = self.restore::<T>(&key) {
+            let _ = var.set(value);
+        }
+        Self::save_on_update(key, var);
+    }
+
+    /// Remove all persisted state snapshots.
+    ///
+    /// Vars already persisting will save a new snapshot on the next update.
+    pub fn clear_persisted(&self) {
+        HOT_RELOAD_SV.write().persisted.clear();
+    }
+
+    fn restore<T: DeserializeOwned>(&self, key: &Txt) -> Option<T> {
+        let sv = HOT_RELOAD_SV.read();
+        let json = sv.persisted.get(key)?;
+        match serde_json::from_str(json) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                tracing::warn!("cannot restore hot reload persisted `{key}`, {e}");
+                None
+            }
+        }
+    }
+
+    fn save_on_update<T>(key: Txt, var: &impl Var<T>)
+    where
+        T: VarValue + Serialize,
+    {
+        var.hook(move |args| {
+            match serde_json::to_string(args.value()) {
+                Ok(json) => {
+                    HOT_RELOAD_SV.write().persisted.insert(key.clone(), json);
+                }
+                Err(e) => tracing::error!("cannot persist `{key}` for hot reload, {e}"),
+            }
+            true
+        })
+        .perm();
+    }
+
     pub(crate) fn lib(&self, manifest_dir: &'static str) -> Option<HotLib> {
         HOT_RELOAD_SV
             .read()
@@ -492,6 +568,7 @@ app_local! {
             status: zng_var::var(vec![]),
             rebuild_requests: vec![] ,
             cancel_requests: vec![] ,
+            persisted: HashMap::new(),
         }
     };
 }
@@ -504,6 +581,8 @@ struct HotReloadService {
     status: ArcVar<Vec<HotStatus>>,
     rebuild_requests: Vec<Txt>,
     cancel_requests: Vec<Txt>,
+    // JSON snapshots of persistent vars
+    persisted: HashMap<Txt, String>,
 }
 impl HotReloadService {
     fn rebuild_reload(&mut self, manifest_dir: Txt, static_patch: &StaticPatch) -> (RebuildLoadVar, SignalOnce) {
@@ -23,10 +23,14 @@ use std::{
 use parking_lot::Mutex;
 use task::io::AsyncReadExt;
 use zng_app::{
+    event::{event, event_args},
     update::EventUpdate,
     view_process::{
-        raw_events::{LOW_MEMORY_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT, RAW_IMAGE_METADATA_LOADED_EVENT},
-        ViewImage, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
+        raw_events::{
+            LOW_MEMORY_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT, RAW_IMAGE_METADATA_LOADED_EVENT,
+            RAW_IMAGE_PARTIALLY_LOADED_EVENT,
+        },
+        EncodeError, ViewImage, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
     },
     widget::UiTaskWidget,
     AppExtension,
@@ -41,12 +45,16 @@ pub use types::*;
 mod render;
 #[doc(inline)]
 pub use render::{render_retain, ImageRenderWindowRoot, ImageRenderWindowsService, IMAGES_WINDOW, IMAGE_RENDER};
-use zng_layout::unit::{ByteLength, ByteUnits};
+use zng_layout::unit::{ByteLength, ByteUnits, Px, PxSize};
 use zng_task::UiTask;
 use zng_txt::{formatx, ToTxt, Txt};
 use zng_unique_id::{IdEntry, IdMap};
-use zng_var::{types::WeakArcVar, var, AnyVar, AnyWeakVar, ArcVar, Var, WeakVar};
-use zng_view_api::{image::ImageRequest, ipc::IpcBytes, ViewProcessOffline};
+use zng_var::{response_var, types::WeakArcVar, var, AnyVar, AnyWeakVar, ArcVar, ResponseVar, Var, WeakVar};
+use zng_view_api::{
+    image::ImageRequest,
+    ipc::{IpcBytes, IpcBytesSender},
+    ViewProcessOffline,
+};
 
 /// Application extension that provides an image cache.
 ///
@@ -62,12 +70,14 @@ impl AppExtension for ImageManager {
         if let Some(args) = RAW_IMAGE_METADATA_LOADED_EVENT.on(update) {
             let images = IMAGES_SV.read();
 
-            if let Some(var) = images
-                .decoding
-                .iter()
-                .map(|t| &t.image)
-                .find(|v| v.with(|img| img.view.get().unwrap() == &args.image))
-            {
+            if let Some(var) = images.find_decoding(&args.image) {
+                var.update();
+            }
+        } else if let Some(args) = RAW_IMAGE_PARTIALLY_LOADED_EVENT.on(update) {
+            let images = IMAGES_SV.read();
+
+            if let Some(var) = images.find_decoding(&args.image) {
+                var.with(Img::update_renderers);
                 var.update();
             }
         } else if let Some(args) = RAW_IMAGE_LOADED_EVENT.on(update) {
@@ -84,7 +94,12 @@ impl AppExtension for ImageManager {
             {
                 let ImageDecodingTask { image, .. } = images.decoding.swap_remove(i);
                 image.update();
-                image.with(|img| img.done_signal.set());
+                image.with(|img| {
+                    // replace partially decoded pixels
+                    img.update_renderers();
+                    img.done_signal.set();
+                });
+                images.enforce_memory_budget();
             }
         } else if let Some(args) = RAW_IMAGE_LOAD_ERROR_EVENT.on(update) {
             let image = &args.image;
@@ -122,6 +137,19 @@ impl AppExtension for ImageManager {
             images.cleanup_not_cached(true);
             images.download_accept.clear();
 
+            // restart progressive loads, the data is sent again on the next update.
+            let mut progressive_interrupted = vec![];
+            for t in &mut images.loading {
+                if let Some(p) = &mut t.progressive {
+                    if p.sender.take().is_some() {
+                        t.image.modify(|v| {
+                            v.to_mut().view.take();
+                        });
+                        progressive_interrupted.push(t.image.var_ptr());
+                    }
+                }
+            }
+
             let decoding_interrupted = mem::take(&mut images.decoding);
             for (img_var, max_decoded_len, downscale, mask) in images
                 .cache
@@ -134,6 +162,10 @@ impl AppExtension for ImageManager {
                         .filter_map(|e| e.image.upgrade().map(|v| (v, e.max_decoded_len, e.downscale, e.mask))),
                 )
             {
+                if progressive_interrupted.contains(&img_var.var_ptr()) {
+                    continue;
+                }
+
                 let img = img_var.get();
 
                 if let Some(view) = img.view.get() {
@@ -218,7 +250,15 @@ impl AppExtension for ImageManager {
                 Ok(d) => {
                     match d.r {
                         Ok(data) => {
-                            if VIEW_PROCESS.is_available() {
+                            if let Some(sender) = t.progressive.and_then(|p| p.finish(&data)) {
+                                // progressive decode already started, send the end signal.
+                                let _ = sender.send(vec![]);
+                                decoding.push(ImageDecodingTask {
+                                    format: d.format,
+                                    data,
+                                    image: t.image,
+                                });
+                            } else if VIEW_PROCESS.is_available() {
                                 // success and we have a view-process.
                                 match VIEW_PROCESS.add_image(ImageRequest {
                                     format: d.format.clone(),
@@ -260,6 +300,8 @@ impl AppExtension for ImageManager {
                             let img = ViewImage::dummy(Some(e));
                             t.image.modify(move |v| {
                                 let v = v.to_mut();
+                                // drop the view image of an interrupted progressive decode.
+                                v.view.take();
                                 v.view.set(img).unwrap();
                                 v.done_signal.set();
                             });
@@ -274,12 +316,17 @@ impl AppExtension for ImageManager {
                     }
                 }
                 Err(task) => {
+                    let mut progressive = t.progressive;
+                    if let Some(p) = &mut progressive {
+                        p.forward(&t.image, t.max_decoded_len, t.downscale, t.mask);
+                    }
                     loading.push(ImageLoadingTask {
                         task: Mutex::new(task),
                         image: t.image,
                         max_decoded_len: t.max_decoded_len,
                         downscale: t.downscale,
                         mask: t.mask,
+                        progressive,
                     });
                 }
             }
@@ -289,9 +336,51 @@ impl AppExtension for ImageManager {
 
     fn update(&mut self) {
         self.update_render();
+
+        let mut images = IMAGES_SV.write();
+        if images.memory_budget.is_new() {
+            images.enforce_memory_budget();
+        }
+    }
+}
+
+event! {
+    /// Image evicted from the [`IMAGES`] cache to stay within the [`IMAGES.memory_budget`] or
+    /// reloaded after an eviction.
+    ///
+    /// [`IMAGES.memory_budget`]: IMAGES::memory_budget
+    pub static IMAGE_CACHE_CHANGED_EVENT: ImageCacheChangedArgs;
+}
+
+event_args! {
+    /// [`IMAGE_CACHE_CHANGED_EVENT`] arguments.
+    pub struct ImageCacheChangedArgs {
+        /// The image cache key.
+        pub key: ImageHash,
+
+        /// The change that happened.
+        pub change: ImageCacheChange,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
     }
 }
 
+/// Possible changes in a [`ImageCacheChangedArgs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageCacheChange {
+    /// The image was removed from the cache to free memory.
+    ///
+    /// Only images that are not pinned and not referenced outside of the cache are evicted.
+    Evicted,
+    /// The image was requested again after it was evicted and is reloading.
+    Reloaded,
+}
+
 app_local! {
     static IMAGES_SV: ImagesService = ImagesService::new();
 }
@@ -302,6 +391,71 @@ struct ImageLoadingTask {
     max_decoded_len: ByteLength,
     downscale: Option<ImageDownscale>,
     mask: Option<ImageMaskMode>,
+    progressive: Option<ProgressiveLoad>,
+}
+
+/// Bytes received so far by a loading task that supports progressive decoding.
+#[derive(Default)]
+struct ProgressiveData {
+    /// Set when the format is known, the bytes are only sent after this is set.
+    format: Option<ImageDataFormat>,
+    data: Vec<u8>,
+}
+
+/// Sends the bytes of a loading task to the view-process as they are received.
+struct ProgressiveLoad {
+    data: Arc<Mutex<ProgressiveData>>,
+    sent: usize,
+    sender: Option<Mutex<IpcBytesSender>>,
+}
+impl ProgressiveLoad {
+    /// Start the progressive decode or send the new bytes.
+    fn forward(
+        &mut self,
+        image: &ArcVar<Img>,
+        max_decoded_len: ByteLength,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+    ) {
+        let d = self.data.lock();
+        if self.sender.is_none() {
+            let format = match &d.format {
+                Some(f) if !d.data.is_empty() && VIEW_PROCESS.is_available() => f.clone(),
+                _ => return,
+            };
+            let (sender, receiver) = zng_view_api::ipc::bytes_channel();
+            match VIEW_PROCESS.add_image_pro(ImageRequest {
+                format,
+                data: receiver,
+                max_decoded_len: max_decoded_len.0 as u64,
+                downscale,
+                mask,
+            }) {
+                Ok(img) => {
+                    image.modify(move |v| {
+                        v.to_mut().view.set(img).unwrap();
+                    });
+                }
+                Err(ViewProcessOffline) => return,
+            }
+            self.sender = Some(Mutex::new(sender));
+            self.sent = 0;
+        }
+
+        if d.data.len() > self.sent {
+            let _ = self.sender.as_ref().unwrap().lock().send(d.data[self.sent..].to_vec());
+            self.sent = d.data.len();
+        }
+    }
+
+    /// Send the remaining bytes of the full `data`, returns the sender if the progressive decode started.
+    fn finish(self, data: &IpcBytes) -> Option<IpcBytesSender> {
+        let sender = self.sender?.into_inner();
+        if data.len() > self.sent {
+            let _ = sender.send(data[self.sent..].to_vec());
+        }
+        Some(sender)
+    }
 }
 
 struct ImageDecodingTask {
@@ -313,6 +467,9 @@ struct ImageDecodingTask {
 struct CacheEntry {
     image: ArcVar<Img>,
     error: AtomicBool,
+    /// Use tick of the last cache request, the least recently used entries are evicted first.
+    last_use: u64,
+    pinned: bool,
     max_decoded_len: ByteLength,
     downscale: Option<ImageDownscale>,
     mask: Option<ImageMaskMode>,
@@ -337,6 +494,10 @@ struct ImagesService {
     cache: IdMap<ImageHash, CacheEntry>,
     not_cached: Vec<NotCachedEntry>,
 
+    memory_budget: ArcVar<ByteLength>,
+    use_tick: u64,
+    evicted: IdMap<ImageHash, ()>,
+
     render: render::ImagesRender,
 }
 impl ImagesService {
@@ -350,6 +511,9 @@ impl ImagesService {
             download_accept: Txt::from_static(""),
             cache: IdMap::new(),
             not_cached: vec![],
+            memory_budget: var(ByteLength::MAX),
+            use_tick: 0,
+            evicted: IdMap::new(),
             render: render::ImagesRender::default(),
         }
     }
@@ -369,6 +533,7 @@ impl ImagesService {
             allow_uri: UriFilter::BlockAll,
         };
 
+        let last_use = self.next_use_tick();
         match self.cache.entry(key) {
             IdEntry::Occupied(e) => Err((image, e.get().image.read_only())),
             IdEntry::Vacant(e) => {
@@ -395,6 +560,8 @@ impl ImagesService {
                 Ok(e.insert(CacheEntry {
                     error: AtomicBool::new(is_error),
                     image: img_var,
+                    last_use,
+                    pinned: false,
                     max_decoded_len: limits.max_decoded_len,
                     downscale,
                     mask: if is_mask { Some(ImageMaskMode::A) } else { None },
@@ -405,6 +572,67 @@ impl ImagesService {
         }
     }
 
+    fn frame(&mut self, image: &Img, index: usize) -> ImageVar {
+        if index == 0 {
+            return var(image.clone()).read_only();
+        }
+
+        let view = match image.view() {
+            Some(v) if v.is_animated() => v,
+            _ => return var(Img::dummy(Some(Txt::from_static("image is not animated")))).read_only(),
+        };
+        if index >= view.frame_count() {
+            return var(Img::dummy(Some(formatx!("frame {index} is out of bounds")))).read_only();
+        }
+
+        match VIEW_PROCESS.image_frame(view, index) {
+            Ok(frame) => {
+                let is_loading = !frame.is_error() && !frame.is_loaded();
+                let format = ImageDataFormat::Bgra8 {
+                    size: view.size(),
+                    ppi: view.ppi(),
+                };
+                let img_var = var(Img::new(frame));
+                if is_loading {
+                    self.decoding.push(ImageDecodingTask {
+                        format,
+                        data: IpcBytes::from_vec(vec![]),
+                        image: img_var.clone(),
+                    });
+                }
+                img_var.read_only()
+            }
+            Err(ViewProcessOffline) => var(Img::dummy(Some(Txt::from_static("view-process offline")))).read_only(),
+        }
+    }
+
+    fn raster(&mut self, image: &Img, size: PxSize) -> ImageVar {
+        let view = match image.view() {
+            Some(v) if v.is_vector() => v,
+            _ => return var(Img::dummy(Some(Txt::from_static("image is not vector")))).read_only(),
+        };
+        if size.width <= Px(0) || size.height <= Px(0) {
+            return var(Img::dummy(Some(formatx!("cannot rasterize to {size:?}")))).read_only();
+        }
+
+        match VIEW_PROCESS.image_raster(view, size) {
+            Ok(raster) => {
+                let is_loading = !raster.is_error() && !raster.is_loaded();
+                let format = ImageDataFormat::Bgra8 { size, ppi: view.ppi() };
+                let img_var = var(Img::new(raster));
+                if is_loading {
+                    self.decoding.push(ImageDecodingTask {
+                        format,
+                        data: IpcBytes::from_vec(vec![]),
+                        image: img_var.clone(),
+                    });
+                }
+                img_var.read_only()
+            }
+            Err(ViewProcessOffline) => var(Img::dummy(Some(Txt::from_static("view-process offline")))).read_only(),
+        }
+    }
+
     fn detach(&mut self, image: ImageVar) -> ImageVar {
         if let Some(key) = &image.with(|i| i.cache_key) {
             let decoded_size = image.with(|img| img.pixels().map(|b| b.len()).unwrap_or(0).bytes());
@@ -512,15 +740,18 @@ impl ImagesService {
         downscale: Option<ImageDownscale>,
         mask: Option<ImageMaskMode>,
     ) -> ImageVar {
+        let last_use = self.next_use_tick();
         match mode {
             ImageCacheMode::Cache => {
-                if let Some(v) = self.cache.get(&key) {
-                    return v.image.read_only();
+                if let Some(e) = self.cache.get_mut(&key) {
+                    e.last_use = last_use;
+                    return e.image.read_only();
                 }
             }
             ImageCacheMode::Retry => {
-                if let Some(e) = self.cache.get(&key) {
+                if let Some(e) = self.cache.get_mut(&key) {
                     if !e.error.load(Ordering::Relaxed) {
+                        e.last_use = last_use;
                         return e.image.read_only();
                     }
                 }
@@ -528,6 +759,10 @@ impl ImagesService {
             ImageCacheMode::Ignore | ImageCacheMode::Reload => {}
         }
 
+        if !matches!(mode, ImageCacheMode::Ignore) && self.evicted.remove(&key).is_some() {
+            IMAGE_CACHE_CHANGED_EVENT.notify(ImageCacheChangedArgs::now(key, ImageCacheChange::Reloaded));
+        }
+
         if !VIEW_PROCESS.is_available() && !self.load_in_headless.get() {
             tracing::warn!("loading dummy image, set `load_in_headless=true` to actually load without renderer");
 
@@ -537,6 +772,8 @@ impl ImagesService {
                 CacheEntry {
                     image: dummy.clone(),
                     error: AtomicBool::new(false),
+                    last_use,
+                    pinned: false,
                     max_decoded_len: limits.max_decoded_len,
                     downscale,
                     mask,
@@ -597,13 +834,15 @@ impl ImagesService {
             #[cfg(feature = "http")]
             ImageSource::Download(uri, accept) => {
                 let accept = accept.unwrap_or_else(|| self.download_accept());
+                let progressive = Arc::new(Mutex::new(ProgressiveData::default()));
 
-                self.load_task(
+                self.load_task_progressive(
                     key,
                     mode,
                     limits.max_decoded_len,
                     downscale,
                     mask,
+                    progressive.clone(),
                     task::run(async move {
                         let mut r = ImageData {
                             format: ImageDataFormat::Unknown,
@@ -626,12 +865,25 @@ impl ImagesService {
                                     }
                                 }
 
-                                match rsp.bytes().await {
-                                    Ok(d) => r.r = Ok(IpcBytes::from_vec(d)),
-                                    Err(e) => {
-                                        r.r = Err(formatx!("download error: {e}"));
+                                progressive.lock().format = Some(r.format.clone());
+
+                                // read the bytes as they are received for progressive decoding,
+                                // the buffer is larger than the internal reader buffer, so it is not used.
+                                let mut buf = vec![0; 64 * 1024];
+                                r.r = loop {
+                                    match rsp.read(&mut buf).await {
+                                        Ok(0) => break Ok(IpcBytes::from_vec(mem::take(&mut progressive.lock().data))),
+                                        Ok(n) => {
+                                            let mut p = progressive.lock();
+                                            if p.data.len() + n > max_encoded_size.0 {
+                                                break Err(formatx!("download size exceeds the limit of `{max_encoded_size}`"));
+                                            }
+                                            p.data.extend_from_slice(&buf[..n]);
+                                            zng_app::update::UPDATES.update(None);
+                                        }
+                                        Err(e) => break Err(formatx!("download error: {e}")),
                                     }
-                                }
+                                };
 
                                 let _ = rsp.consume().await;
                             }
@@ -690,6 +942,65 @@ impl ImagesService {
         self.download_accept.clone()
     }
 
+    /// Find the image that is loading or decoding `image`.
+    fn find_decoding(&self, image: &ViewImage) -> Option<&ArcVar<Img>> {
+        self.decoding
+            .iter()
+            .map(|t| &t.image)
+            .chain(self.loading.iter().filter(|t| t.progressive.is_some()).map(|t| &t.image))
+            .find(|v| v.with(|img| img.view.get() == Some(image)))
+    }
+
+    fn set_pinned(&mut self, key: &ImageHash, pinned: bool) -> bool {
+        if let Some(e) = self.cache.get_mut(key) {
+            e.pinned = pinned;
+            if !pinned {
+                self.enforce_memory_budget();
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_use_tick(&mut self) -> u64 {
+        self.use_tick += 1;
+        self.use_tick
+    }
+
+    /// Evict the least recently used images until the decoded pixels fit the memory budget.
+    fn enforce_memory_budget(&mut self) {
+        let budget = self.memory_budget.get();
+        if budget == ByteLength::MAX {
+            return;
+        }
+
+        let mut used = ByteLength(0);
+        let mut candidates = vec![];
+        for (key, e) in &self.cache {
+            let len = e.image.with(decoded_len);
+            used += len;
+            // only evict images held only by the cache, the memory of images in use would not be freed.
+            if !e.pinned && len > ByteLength(0) && e.image.strong_count() == 1 {
+                candidates.push((e.last_use, *key, len));
+            }
+        }
+        if used <= budget {
+            return;
+        }
+
+        candidates.sort_unstable_by_key(|(last_use, _, _)| *last_use);
+        for (_, key, len) in candidates {
+            if used <= budget {
+                break;
+            }
+            self.cache.remove(&key);
+            self.evicted.insert(key, ());
+            used -= len;
+            IMAGE_CACHE_CHANGED_EVENT.notify(ImageCacheChangedArgs::now(key, ImageCacheChange::Evicted));
+        }
+    }
+
     fn cleanup_not_cached(&mut self, force: bool) {
         if force || self.not_cached.len() > 1000 {
             self.not_cached.retain(|c| c.image.strong_count() > 0);
@@ -705,6 +1016,7 @@ impl ImagesService {
         mask: Option<ImageMaskMode>,
     ) -> ArcVar<Img> {
         self.cleanup_not_cached(false);
+        let last_use = self.next_use_tick();
 
         if let ImageCacheMode::Reload = mode {
             self.cache
@@ -712,6 +1024,8 @@ impl ImagesService {
                 .or_insert_with(|| CacheEntry {
                     image: var(Img::new_none(Some(key))),
                     error: AtomicBool::new(false),
+                    last_use,
+                    pinned: false,
                     max_decoded_len,
                     downscale,
                     mask,
@@ -734,6 +1048,8 @@ impl ImagesService {
                 CacheEntry {
                     image: img.clone(),
                     error: AtomicBool::new(false),
+                    last_use,
+                    pinned: false,
                     max_decoded_len,
                     downscale,
                     mask,
@@ -752,6 +1068,44 @@ impl ImagesService {
         downscale: Option<ImageDownscale>,
         mask: Option<ImageMaskMode>,
         fetch_bytes: impl Future<Output = ImageData> + Send + 'static,
+    ) -> ImageVar {
+        self.load_task_impl(key, mode, max_decoded_len, downscale, mask, None, fetch_bytes)
+    }
+
+    /// Like [`load_task`], but the `fetch_bytes` future also writes the bytes to `progressive` as they are received, and
+    /// requests an update for each write.
+    ///
+    /// [`load_task`]: Self::load_task
+    #[cfg(feature = "http")]
+    #[allow(clippy::too_many_arguments)]
+    fn load_task_progressive(
+        &mut self,
+        key: ImageHash,
+        mode: ImageCacheMode,
+        max_decoded_len: ByteLength,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+        progressive: Arc<Mutex<ProgressiveData>>,
+        fetch_bytes: impl Future<Output = ImageData> + Send + 'static,
+    ) -> ImageVar {
+        let progressive = Some(ProgressiveLoad {
+            data: progressive,
+            sent: 0,
+            sender: None,
+        });
+        self.load_task_impl(key, mode, max_decoded_len, downscale, mask, progressive, fetch_bytes)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn load_task_impl(
+        &mut self,
+        key: ImageHash,
+        mode: ImageCacheMode,
+        max_decoded_len: ByteLength,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+        progressive: Option<ProgressiveLoad>,
+        fetch_bytes: impl Future<Output = ImageData> + Send + 'static,
     ) -> ImageVar {
         let img = self.new_cache_image(key, mode, max_decoded_len, downscale, mask);
         let r = img.read_only();
@@ -762,6 +1116,7 @@ impl ImagesService {
             max_decoded_len,
             downscale,
             mask,
+            progressive,
         });
         zng_app::update::UPDATES.update(None);
 
@@ -797,6 +1152,43 @@ impl IMAGES {
         IMAGES_SV.read().limits.clone()
     }
 
+    /// Maximum memory used by the decoded pixels of cached images.
+    ///
+    /// When the decoded images exceed the budget the least recently requested images are evicted from the cache until
+    /// the memory fits the budget again. Only images that are not [pinned] and are not referenced outside of the cache
+    /// are evicted, the [`IMAGE_CACHE_CHANGED_EVENT`] notifies evictions and reloads of evicted images.
+    ///
+    /// The decoded pixels are also the size of the image texture in the renderer, so the budget limits both RAM and VRAM
+    /// usage. Use the image widget auto-downscale to decode images at the size they are displayed.
+    ///
+    /// Is [`ByteLength::MAX`] by default, no budget.
+    ///
+    /// [pinned]: Self::pin
+    pub fn memory_budget(&self) -> ArcVar<ByteLength> {
+        IMAGES_SV.read().memory_budget.clone()
+    }
+
+    /// Pin the cached image to the cache, pinned images are never evicted to fit the [`memory_budget`].
+    ///
+    /// Returns `true` if the image is cached.
+    ///
+    /// [`memory_budget`]: Self::memory_budget
+    pub fn pin(&self, key: ImageHash) -> bool {
+        IMAGES_SV.write().set_pinned(&key, true)
+    }
+
+    /// Allow the cached image to be evicted again.
+    ///
+    /// Returns `true` if the image is cached.
+    pub fn unpin(&self, key: ImageHash) -> bool {
+        IMAGES_SV.write().set_pinned(&key, false)
+    }
+
+    /// If the cached image is pinned.
+    pub fn is_pinned(&self, key: ImageHash) -> bool {
+        IMAGES_SV.read().cache.get(&key).map(|e| e.pinned).unwrap_or(false)
+    }
+
     /// Returns a dummy image that reports it is loaded or an error.
     pub fn dummy(&self, error: Option<Txt>) -> ImageVar {
         var(Img::dummy(error)).read_only()
@@ -811,6 +1203,9 @@ impl IMAGES {
     ///
     /// Optionally define the HTTP ACCEPT header, if not set all image formats supported by the view-process
     /// backend are accepted.
+    ///
+    /// The bytes are decoded as they are received, the default view-process shows partial PNG and JPEG images,
+    /// see [`Img::is_partially_loaded`] for more details.
     #[cfg(feature = "http")]
     pub fn download(&self, uri: impl task::http::TryUri, accept: Option<Txt>) -> ImageVar {
         match uri.try_uri() {
@@ -937,6 +1332,44 @@ impl IMAGES {
         IMAGES_SV.write().detach(image)
     }
 
+    /// Decode the animation frame `index` of the `image`.
+    ///
+    /// Returns a new image that loads the frame, animation frames are decoded on demand by the view-process and
+    /// are not cached. Frame zero is the `image` itself. Returns an error image if the `image` is not loaded, not animated
+    /// or the `index` is out of bounds.
+    ///
+    /// See [`Img::frame_delays`] for more details.
+    pub fn frame(&self, image: &Img, index: usize) -> ImageVar {
+        IMAGES_SV.write().frame(image, index)
+    }
+
+    /// Rasterize the vector `image` at a new `size`.
+    ///
+    /// Returns a new image that loads the raster, rasters are rendered on demand by the view-process and are not cached.
+    /// Returns an error image if the `image` is not loaded or is not vector.
+    ///
+    /// See [`Img::is_vector`] for more details.
+    pub fn raster(&self, image: &Img, size: PxSize) -> ImageVar {
+        IMAGES_SV.write().raster(image, size)
+    }
+
+    /// Encode the `image` to the `format` with custom encoder `options`.
+    ///
+    /// Returns a response var that updates when the image is loaded and encoded, the encoding runs in the view-process.
+    /// The `format` must be one of the [`available_encoders`].
+    ///
+    /// [`available_encoders`]: Self::available_encoders
+    pub fn encode(&self, image: &Img, format: impl Into<Txt>, options: ImageEncodeOptions) -> ResponseVar<Result<IpcBytes, EncodeError>> {
+        let (responder, response) = response_var();
+        let image = image.clone();
+        let format = format.into();
+        task::spawn(async move {
+            let r = image.encode_with_options(format, options).await;
+            responder.respond(r);
+        });
+        response
+    }
+
     /// Clear cached images that are not referenced outside of the cache.
     pub fn clean_all(&self) {
         let mut img = IMAGES_SV.write();
@@ -951,6 +1384,7 @@ impl IMAGES {
     pub fn purge_all(&self) {
         let mut img = IMAGES_SV.write();
         img.cache.clear();
+        img.evicted.clear();
         img.proxies.iter_mut().for_each(|p| p.clear(true));
     }
 
@@ -971,6 +1405,18 @@ impl IMAGES {
         VIEW_PROCESS.image_encoders().unwrap_or_default()
     }
 }
+/// Memory used by the decoded pixels of the image.
+fn decoded_len(img: &Img) -> ByteLength {
+    match img.view.get() {
+        Some(v) if !v.is_error() => {
+            let size = v.size();
+            let bpp = if v.is_mask() { 1 } else { 4 };
+            (size.width.0.max(0) as usize * size.height.0.max(0) as usize * bpp).bytes()
+        }
+        _ => ByteLength(0),
+    }
+}
+
 struct ImageData {
     format: ImageDataFormat,
     r: std::result::Result<IpcBytes, Txt>,
@@ -2,6 +2,7 @@ use std::{
     env, fmt, fs, io, mem, ops,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use once_cell::sync::OnceCell;
@@ -22,7 +23,7 @@ use zng_view_api::{image::ImageTextureId, ViewProcessOffline};
 
 use crate::render::ImageRenderWindowRoot;
 
-pub use zng_view_api::image::{ImageDataFormat, ImageDownscale, ImageMaskMode, ImagePpi};
+pub use zng_view_api::image::{ColorSpace, ImageDataFormat, ImageDownscale, ImageEncodeOptions, ImageMaskMode, ImagePpi};
 
 /// A custom proxy in [`IMAGES`].
 ///
@@ -95,10 +96,11 @@ pub struct Img {
     render_ids: Arc<Mutex<Vec<RenderImage>>>,
     pub(super) done_signal: SignalOnce,
     pub(super) cache_key: Option<ImageHash>,
+    external: Option<Arc<ExternalImg>>,
 }
 impl PartialEq for Img {
     fn eq(&self, other: &Self) -> bool {
-        self.view == other.view
+        self.view == other.view && self.external == other.external
     }
 }
 impl Img {
@@ -108,6 +110,7 @@ impl Img {
             render_ids: Arc::default(),
             done_signal: SignalOnce::new(),
             cache_key,
+            external: None,
         }
     }
 
@@ -121,6 +124,7 @@ impl Img {
             render_ids: Arc::default(),
             done_signal: sig,
             cache_key: None,
+            external: None,
         }
     }
 
@@ -129,6 +133,31 @@ impl Img {
         Self::new(ViewImage::dummy(error))
     }
 
+    /// New from an external image registered in the `renderer` by a view-process extension.
+    ///
+    /// The `texture_id` must be registered by the view-process extension in the same `renderer`, the default view-process
+    /// implementation provides `ExternalImages::register_texture_image` for this. The extension owns the image resource and
+    /// can update the texture content directly, the image is composited without any CPU copies of the pixels.
+    ///
+    /// The image is always loaded, it has no pixels available in the app-process and can only be rendered in
+    /// windows or surfaces of the same `renderer`, other renderers render nothing.
+    pub fn from_external(renderer: ViewRenderer, texture_id: ImageTextureId, size: PxSize) -> Self {
+        let mut r = Self::new(ViewImage::dummy(None));
+        r.external = Some(Arc::new(ExternalImg {
+            renderer,
+            texture_id,
+            size,
+        }));
+        r
+    }
+
+    /// If the image was created using [`from_external`].
+    ///
+    /// [`from_external`]: Self::from_external
+    pub fn is_external(&self) -> bool {
+        self.external.is_some()
+    }
+
     /// Returns `true` if the is still acquiring or decoding the image bytes.
     pub fn is_loading(&self) -> bool {
         match self.view.get() {
@@ -145,6 +174,18 @@ impl Img {
         }
     }
 
+    /// If the image is still loading, but some pixels are already decoded and can be rendered.
+    ///
+    /// Images are partially loaded when the view-process decodes the bytes as they are received, the default view-process
+    /// decodes interlaced and non-interlaced PNG and progressive and baseline JPEG downloads. The image var updates for each
+    /// partial decode, the missing pixels are transparent.
+    pub fn is_partially_loaded(&self) -> bool {
+        match self.view.get() {
+            Some(v) => v.is_partially_loaded() && !v.is_loaded(),
+            None => false,
+        }
+    }
+
     /// If the image failed to load.
     pub fn is_error(&self) -> bool {
         match self.view.get() {
@@ -166,8 +207,21 @@ impl Img {
         self.done_signal.clone()
     }
 
+    /// Returns the size of the decoded pixels if the image is [`is_partially_loaded`].
+    ///
+    /// The partial pixels are rendered at the full [`size`], the missing rows are transparent.
+    ///
+    /// [`is_partially_loaded`]: Self::is_partially_loaded
+    /// [`size`]: Self::size
+    pub fn partial_size(&self) -> PxSize {
+        self.view.get().map(|v| v.partial_size()).unwrap_or_else(PxSize::zero)
+    }
+
     /// Returns the image size in pixels, or zero if it is not loaded.
     pub fn size(&self) -> PxSize {
+        if let Some(e) = &self.external {
+            return e.size;
+        }
         self.view.get().map(|v| v.size()).unwrap_or_else(PxSize::zero)
     }
 
@@ -178,7 +232,12 @@ impl Img {
     }
 
     /// Returns `true` if the image is fully opaque or it is not loaded.
+    ///
+    /// External images are never considered opaque.
     pub fn is_opaque(&self) -> bool {
+        if self.external.is_some() {
+            return false;
+        }
         self.view.get().map(|v| v.is_opaque()).unwrap_or(true)
     }
 
@@ -187,6 +246,56 @@ impl Img {
         self.view.get().map(|v| v.is_mask()).unwrap_or(false)
     }
 
+    /// Returns the color space of the source image.
+    ///
+    /// The pixels are always converted to sRGB on decode, this is the color profile embedded in the source data,
+    /// or [`ColorSpace::Unknown`] if the image is not loaded or the source did not have a profile.
+    pub fn color_space(&self) -> ColorSpace {
+        self.view.get().map(|v| v.color_space()).unwrap_or_default()
+    }
+
+    /// Returns `true` if the image is loaded and has more than one frame.
+    pub fn is_animated(&self) -> bool {
+        self.view.get().map(|v| v.is_animated()).unwrap_or(false)
+    }
+
+    /// Returns the number of animation frames, is `1` for still images and `0` if not loaded or error.
+    pub fn frame_count(&self) -> usize {
+        if self.external.is_some() {
+            return 1;
+        }
+        self.view.get().map(|v| v.frame_count()).unwrap_or(0)
+    }
+
+    /// Returns the delay of each animation frame, is empty if the image is not animated.
+    ///
+    /// This image is the first frame, the other frames are decoded on demand using [`IMAGES.frame`].
+    ///
+    /// [`IMAGES.frame`]: crate::IMAGES::frame
+    pub fn frame_delays(&self) -> Vec<Duration> {
+        self.view.get().map(|v| v.frame_delays()).unwrap_or_default()
+    }
+
+    /// Returns `true` if the image is loaded and was decoded from a vector format, like SVG.
+    ///
+    /// Vector images are rasterized at their intrinsic size, other sizes can be rasterized on demand using [`IMAGES.raster`].
+    /// The image widget does this automatically, so that vector images stay crisp at any scale factor and layout size.
+    ///
+    /// Note that the default view-process does not decode any vector format, a decoder must be registered
+    /// using `zng_view::extensions::ViewExtensions::image_decoder`.
+    ///
+    /// [`IMAGES.raster`]: crate::IMAGES::raster
+    pub fn is_vector(&self) -> bool {
+        self.view.get().map(|v| v.is_vector()).unwrap_or(false)
+    }
+
+    /// Returns the sum of all [`frame_delays`], the duration of one animation loop.
+    ///
+    /// [`frame_delays`]: Self::frame_delays
+    pub fn duration(&self) -> Duration {
+        self.frame_delays().into_iter().sum()
+    }
+
     /// Connection to the image resource, if it is loaded.
     pub fn view(&self) -> Option<&ViewImage> {
         self.view.get().filter(|&v| v.is_loaded())
@@ -231,8 +340,13 @@ impl Img {
 
     /// Reference the decoded pre-multiplied BGRA8 pixel buffer or A8 if [`is_mask`].
     ///
+    /// External images have no pixels in the app-process, always returns `None` for them.
+    ///
     /// [`is_mask`]: Self::is_mask
     pub fn pixels(&self) -> Option<zng_view_api::ipc::IpcBytes> {
+        if self.external.is_some() {
+            return None;
+        }
         self.view.get().and_then(|v| v.pixels())
     }
 
@@ -277,6 +391,22 @@ impl Img {
         }
     }
 
+    /// Encode the image to the format, with custom encoder options.
+    ///
+    /// See [`ImageEncodeOptions`] for details about how each option applies to each format.
+    pub async fn encode_with_options(
+        &self,
+        format: Txt,
+        options: ImageEncodeOptions,
+    ) -> std::result::Result<zng_view_api::ipc::IpcBytes, EncodeError> {
+        self.done_signal.clone().await;
+        if let Some(e) = self.error() {
+            Err(EncodeError::Encode(e))
+        } else {
+            self.view.get().unwrap().encode_with_options(format, options).await
+        }
+    }
+
     /// Encode and write the image to `path`.
     ///
     /// The image format is guessed from the file extension.
@@ -308,9 +438,28 @@ impl Img {
         task::wait(move || fs::write(path, &data[..])).await
     }
 }
+impl Img {
+    /// Refresh the textures of renderers that use the image, after the view-process decoded more pixels.
+    pub(super) fn update_renderers(&self) {
+        if let Some(view) = self.view.get() {
+            for r in self.render_ids.lock().iter_mut() {
+                // error here means the entire renderer was dropped.
+                let _ = r.renderer.update_image_use(r.image_id, view);
+            }
+        }
+    }
+}
 impl zng_app::render::Img for Img {
     fn renderer_id(&self, renderer: &ViewRenderer) -> ImageTextureId {
-        if self.is_loaded() {
+        if let Some(e) = &self.external {
+            if &e.renderer == renderer {
+                return e.texture_id;
+            } else {
+                tracing::error!("external image used in a different renderer, will return INVALID");
+                return ImageTextureId::INVALID;
+            }
+        }
+        if self.is_loaded() || self.is_partially_loaded() {
             let mut rms = self.render_ids.lock();
             if let Some(rm) = rms.iter().find(|k| &k.renderer == renderer) {
                 return rm.image_id;
@@ -341,6 +490,25 @@ impl zng_app::render::Img for Img {
     }
 }
 
+struct ExternalImg {
+    renderer: ViewRenderer,
+    texture_id: ImageTextureId,
+    size: PxSize,
+}
+impl PartialEq for ExternalImg {
+    fn eq(&self, other: &Self) -> bool {
+        self.texture_id == other.texture_id && self.renderer == other.renderer
+    }
+}
+impl fmt::Debug for ExternalImg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalImg")
+            .field("texture_id", &self.texture_id)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
 struct RenderImage {
     image_id: ImageTextureId,
     renderer: ViewRenderer,
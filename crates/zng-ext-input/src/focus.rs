@@ -268,6 +268,16 @@ impl FocusChangedCause {
             _ => None,
         }
     }
+
+    /// Get focus request reason.
+    ///
+    /// Returns `None` if the focus change is not a direct result of a request, that is a scope redirect or a recovery.
+    pub fn request_reason(self) -> Option<FocusReason> {
+        match self {
+            Self::Request(r) => Some(r.reason),
+            _ => None,
+        }
+    }
 }
 
 event! {
@@ -397,16 +407,16 @@ impl AppExtension for FocusManager {
         if let Some(args) = MOUSE_INPUT_EVENT.on(update) {
             if args.is_mouse_down() {
                 // click
-                request = Some(FocusRequest::direct_or_exit(args.target.widget_id(), true, false));
+                request = Some(FocusRequest::direct_or_exit(args.target.widget_id(), true, false).with_reason(FocusReason::Click));
             }
         } else if let Some(args) = TOUCH_INPUT_EVENT.on(update) {
             if args.is_touch_start() {
                 // start
-                request = Some(FocusRequest::direct_or_exit(args.target.widget_id(), true, false));
+                request = Some(FocusRequest::direct_or_exit(args.target.widget_id(), true, false).with_reason(FocusReason::Click));
             }
         } else if let Some(args) = ACCESS_CLICK_EVENT.on(update) {
             // click
-            request = Some(FocusRequest::direct_or_exit(args.widget_id, true, false));
+            request = Some(FocusRequest::direct_or_exit(args.widget_id, true, false).with_reason(FocusReason::Click));
         } else if let Some(args) = WINDOW_FOCUS_CHANGED_EVENT.on(update) {
             // foreground window maybe changed
             let mut focus = FOCUS_SV.write();
@@ -510,6 +520,13 @@ impl FocusManager {
 
             let reverse = matches!(args.cause.request_target(), Some(FocusTarget::Prev));
             let prev_focus = args.prev_focus.clone();
+
+            if let (Some(reason), Some(prev)) = (args.cause.request_reason(), &prev_focus) {
+                if reason != FocusReason::History && args.new_focus.as_ref() != Some(prev) {
+                    focus.push_history(prev.widget_id());
+                }
+            }
+
             FOCUS_CHANGED_EVENT.notify(args);
 
             // may have focused scope.
@@ -649,6 +666,77 @@ impl FOCUS {
         FOCUS_SV.read().is_highlighting_var.read_only()
     }
 
+    /// Widgets that where focused before the current focus, the most recent is last.
+    ///
+    /// A widget is inserted when focus moves from it by request, focus moves caused by [`back`] are not recorded.
+    /// The history is limited to the last 32 widgets.
+    ///
+    /// [`back`]: Self::back
+    #[must_use]
+    pub fn history(&self) -> ReadOnlyArcVar<Vec<WidgetId>> {
+        FOCUS_SV.read().history_var.read_only()
+    }
+
+    /// Focus the most recent widget in the [`history`] that still exists.
+    ///
+    /// The widget and all more recent entries are removed from the history. Returns `false` if there is no
+    /// widget to return to. Continues highlighting the new focus if the current is highlighted.
+    ///
+    /// This makes a [`focus`](Self::focus) request using [`FocusRequest::direct_or_related`] with [`FocusReason::History`].
+    ///
+    /// [`history`]: Self::history
+    pub fn back(&self) -> bool {
+        let mut f = FOCUS_SV.write();
+        let mut target = None;
+        while let Some(id) = f.history.pop() {
+            if WINDOWS.widget_info(id).is_some() {
+                target = Some(id);
+                break;
+            }
+        }
+        f.history_var.set(f.history.clone());
+        let highlight = f.is_highlighting;
+        drop(f);
+
+        if let Some(id) = target {
+            self.focus(FocusRequest::direct_or_related(id, false, highlight).with_reason(FocusReason::History));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove all entries from the focus [`history`].
+    ///
+    /// [`history`]: Self::history
+    pub fn clear_history(&self) {
+        let mut f = FOCUS_SV.write();
+        f.history.clear();
+        f.history_var.set(vec![]);
+    }
+
+    /// Forget the return focus of a [`LastFocused`] scope.
+    ///
+    /// The next time the scope is focused it behaves like [`FirstDescendant`].
+    ///
+    /// [`LastFocused`]: FocusScopeOnFocus::LastFocused
+    /// [`FirstDescendant`]: FocusScopeOnFocus::FirstDescendant
+    pub fn clear_return_focus(&self, scope_id: impl Into<WidgetId>) {
+        let scope_id = scope_id.into();
+        let mut f = FOCUS_SV.write();
+        if let Some(prev) = f.return_focused.remove(&scope_id) {
+            if let Some(v) = f.return_focused_var.get(&scope_id) {
+                v.set(None);
+            }
+            let scope = WINDOWS
+                .widget_tree(prev.window_id())
+                .ok()
+                .and_then(|t| t.get(scope_id))
+                .map(|w| w.interaction_path());
+            RETURN_FOCUS_CHANGED_EVENT.notify(ReturnFocusChangedArgs::now(scope, Some(prev), None));
+        }
+    }
+
     /// Request a focus update.
     ///
     /// All other focus request methods call this method.
@@ -830,6 +918,16 @@ impl FOCUS {
         let req = FocusRequest::alt(FOCUS_SV.read().is_highlighting);
         self.focus(req);
     }
+
+    /// Request for the focus commands.
+    fn focus_cmd(&self, target: FocusTarget) {
+        let reason = match target {
+            FocusTarget::Alt => FocusReason::Alt,
+            _ => FocusReason::Navigation,
+        };
+        let req = FocusRequest::new(target, FOCUS_SV.read().is_highlighting).with_reason(reason);
+        self.focus(req);
+    }
 }
 
 enum PendingFocusRequest {
@@ -896,6 +994,9 @@ struct FocusService {
     is_highlighting_var: ArcVar<bool>,
     is_highlighting: bool,
 
+    history_var: ArcVar<Vec<WidgetId>>,
+    history: Vec<WidgetId>,
+
     enabled_nav: EnabledNavWithFrame,
 
     pending_window_focus: Option<PendingWindowFocus>,
@@ -927,6 +1028,9 @@ impl FocusService {
             is_highlighting_var: var(false),
             is_highlighting: false,
 
+            history_var: var(vec![]),
+            history: vec![],
+
             enabled_nav: EnabledNavWithFrame::invalid(),
 
             pending_window_focus: None,
@@ -934,6 +1038,16 @@ impl FocusService {
         }
     }
 
+    fn push_history(&mut self, id: WidgetId) {
+        if self.history.last() != Some(&id) {
+            if self.history.len() == 32 {
+                self.history.remove(0);
+            }
+            self.history.push(id);
+            self.history_var.set(self.history.clone());
+        }
+    }
+
     fn auto_highlight(&self, timestamp: DInstant) -> bool {
         if let Some(dur) = self.auto_highlight.get() {
             if timestamp.duration_since(self.last_keyboard_event) <= dur {
@@ -1446,10 +1560,7 @@ impl FocusService {
                     if !widget.is_alt_scope() && widget.scopes().all(|s| !s.is_alt_scope()) {
                         // if not inside ALT, update return for each LastFocused parent scopes.
 
-                        for scope in widget
-                            .scopes()
-                            .filter(|s| s.focus_info().scope_on_focus() == FocusScopeOnFocus::LastFocused)
-                        {
+                        for scope in widget.scopes().filter(|s| s.focus_info().scope_on_focus().is_last_focused()) {
                             let scope = scope.info().interaction_path();
                             let path = widget.info().interaction_path();
                             if let Some(current) = self.return_focused.get_mut(&scope.widget_id()) {
@@ -1501,7 +1612,7 @@ impl FocusService {
                     .scopes()
                     .find(|s| s.info().id() == scope_id)
                 {
-                    if scope.focus_info().scope_on_focus() == FocusScopeOnFocus::LastFocused {
+                    if scope.focus_info().scope_on_focus().is_last_focused() {
                         retain = true; // retain, widget still exists in same scope and scope still is LastFocused.
 
                         let path = widget.interaction_path();
@@ -1516,7 +1627,7 @@ impl FocusService {
                         }
                     }
                 } else if let Some(scope) = info.get(scope_id) {
-                    if scope.focus_info().scope_on_focus() == FocusScopeOnFocus::LastFocused {
+                    if scope.focus_info().scope_on_focus().is_last_focused() {
                         // widget not inside scope anymore, but scope still exists and is valid.
                         if let Some(first) = scope.first_tab_descendant() {
                             // LastFocused goes to the first descendant as fallback.
@@ -1535,7 +1646,7 @@ impl FocusService {
             } else if let Some(parent) = info.get_or_parent(widget_path) {
                 // widget not in window anymore, but a focusable parent is..
                 if let Some(scope) = parent.scopes().find(|s| s.info().id() == scope_id) {
-                    if scope.focus_info().scope_on_focus() == FocusScopeOnFocus::LastFocused {
+                    if scope.focus_info().scope_on_focus().is_last_focused() {
                         // ..and the parent is inside the scope, and the scope is still valid.
                         retain = true;
 
@@ -136,11 +136,11 @@ impl FocusCommands {
 
     pub fn event_preview(&mut self, update: &EventUpdate) {
         macro_rules! handle {
-            ($($CMD:ident($handle:ident) => $method:ident,)+) => {$(
+            ($($CMD:ident($handle:ident) => $target:ident,)+) => {$(
                 if let Some(args) = $CMD.on(update) {
                     args.handle(|args| {
                         if args.enabled && self.$handle.is_enabled() {
-                            FOCUS.$method();
+                            FOCUS.focus_cmd(FocusTarget::$target);
                         } else {
                             FOCUS.on_disabled_cmd();
                         }
@@ -150,15 +150,15 @@ impl FocusCommands {
             )+};
         }
         handle! {
-            FOCUS_NEXT_CMD(next_handle) => focus_next,
-            FOCUS_PREV_CMD(prev_handle) => focus_prev,
-            FOCUS_ALT_CMD(alt_handle) => focus_alt,
-            FOCUS_UP_CMD(up_handle) => focus_up,
-            FOCUS_DOWN_CMD(down_handle) => focus_down,
-            FOCUS_LEFT_CMD(left_handle) => focus_left,
-            FOCUS_RIGHT_CMD(right_handle) => focus_right,
-            FOCUS_ENTER_CMD(enter_handle) => focus_enter,
-            FOCUS_EXIT_CMD(exit_handle) => focus_exit,
+            FOCUS_NEXT_CMD(next_handle) => Next,
+            FOCUS_PREV_CMD(prev_handle) => Prev,
+            FOCUS_ALT_CMD(alt_handle) => Alt,
+            FOCUS_UP_CMD(up_handle) => Up,
+            FOCUS_DOWN_CMD(down_handle) => Down,
+            FOCUS_LEFT_CMD(left_handle) => Left,
+            FOCUS_RIGHT_CMD(right_handle) => Right,
+            FOCUS_ENTER_CMD(enter_handle) => Enter,
+            FOCUS_EXIT_CMD(exit_handle) => Exit,
         }
 
         if let Some(args) = FOCUS_CMD.on(update) {
@@ -208,6 +208,17 @@ pub enum DirectionalNav {
     Contained,
     /// Arrows move the focus inside the scope only, cycles back to opposite edges.
     Cycle,
+    /// Arrows move the focus to the geometrically nearest focusable in the direction, anywhere inside the scope,
+    /// stops at the edges.
+    ///
+    /// Nested [`Continue`] scopes are ignored, the focus moves across containers and scopes as if they were
+    /// not there, nested scopes with other modes are entered but navigate using their own mode. Candidates at
+    /// the same distance are ordered by [`TabIndex`].
+    ///
+    /// This is the navigation style of TV remote and gamepad UIs.
+    ///
+    /// [`Continue`]: Self::Continue
+    Spatial,
 }
 impl fmt::Debug for DirectionalNav {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -219,6 +230,7 @@ impl fmt::Debug for DirectionalNav {
             DirectionalNav::Continue => write!(f, "Continue"),
             DirectionalNav::Contained => write!(f, "Contained"),
             DirectionalNav::Cycle => write!(f, "Cycle"),
+            DirectionalNav::Spatial => write!(f, "Spatial"),
         }
     }
 }
@@ -251,6 +263,12 @@ pub struct FocusRequest {
     ///
     /// [`focus_indicator`]: zng_ext_window::WindowVars::focus_indicator
     pub window_indicator: Option<FocusIndicator>,
+
+    /// What originated the request.
+    ///
+    /// Is [`FocusReason::Api`] by default.
+    #[serde(default)]
+    pub reason: FocusReason,
 }
 
 impl FocusRequest {
@@ -261,6 +279,7 @@ impl FocusRequest {
             highlight,
             force_window_focus: false,
             window_indicator: None,
+            reason: FocusReason::Api,
         }
     }
 
@@ -346,6 +365,37 @@ impl FocusRequest {
         self.window_indicator = Some(indicator);
         self
     }
+
+    /// Sets the [`FocusRequest::reason`].
+    pub fn with_reason(mut self, reason: FocusReason) -> Self {
+        self.reason = reason;
+        self
+    }
+}
+
+/// Represents what originated a [`FocusRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+pub enum FocusReason {
+    /// Request made directly by calling a [`FOCUS`] method.
+    ///
+    /// [`FOCUS`]: crate::focus::FOCUS
+    #[default]
+    Api,
+    /// Keyboard navigation, a focus command like [`FOCUS_NEXT_CMD`] (`Tab`) or [`FOCUS_UP_CMD`] (arrow keys).
+    ///
+    /// [`FOCUS_NEXT_CMD`]: crate::focus::cmd::FOCUS_NEXT_CMD
+    /// [`FOCUS_UP_CMD`]: crate::focus::cmd::FOCUS_UP_CMD
+    Navigation,
+    /// The [`FOCUS_ALT_CMD`] toggled the ALT scope.
+    ///
+    /// [`FOCUS_ALT_CMD`]: crate::focus::cmd::FOCUS_ALT_CMD
+    Alt,
+    /// Mouse press, touch start or accessibility click.
+    Click,
+    /// Return to a previous focus using [`FOCUS.back`].
+    ///
+    /// [`FOCUS.back`]: crate::focus::FOCUS::back
+    History,
 }
 
 /// Focus request target.
@@ -559,6 +609,32 @@ impl FocusInfoTree {
     pub fn contains(&self, widget_id: impl Into<WidgetId>) -> bool {
         self.get(widget_id).is_some()
     }
+
+    /// Find the focusable widget with center point nearest of `origin` within the `max_distance` and with `orientation` to origin.
+    ///
+    /// Unlike the directional navigation of [`FOCUS`] this search ignores focus scopes, it can be used to implement
+    /// custom directional navigation from any point in the window.
+    ///
+    /// [`FOCUS`]: crate::focus::FOCUS
+    pub fn nearest_oriented(&self, origin: PxPoint, max_distance: Px, orientation: Orientation2D) -> Option<WidgetFocusInfo> {
+        self.root().nearest_oriented(origin, max_distance, orientation)
+    }
+
+    /// Find the focusable widget with center point nearest of `origin` within the `max_distance` and with `orientation`
+    /// to origin that passes the `filter`.
+    ///
+    /// See [`nearest_oriented`] for more details.
+    ///
+    /// [`nearest_oriented`]: Self::nearest_oriented
+    pub fn nearest_oriented_filtered(
+        &self,
+        origin: PxPoint,
+        max_distance: Px,
+        orientation: Orientation2D,
+        filter: impl FnMut(WidgetFocusInfo) -> bool,
+    ) -> Option<WidgetFocusInfo> {
+        self.root().nearest_oriented_filtered(origin, max_distance, orientation, filter)
+    }
 }
 
 /// [`WidgetInfo`] extensions that build a [`WidgetFocusInfo`].
@@ -1332,6 +1408,74 @@ impl WidgetFocusInfo {
             .and_then(|s| self.directional_from(&s, from, orientation, false, false))
     }
 
+    /// Gets the scope that defines directional navigation for this widget if it is a [`DirectionalNav::Spatial`] scope.
+    fn spatial_scope(&self) -> Option<WidgetFocusInfo> {
+        for scope in self.scopes() {
+            match scope.focus_info().directional_nav() {
+                DirectionalNav::Continue => continue,
+                DirectionalNav::Spatial => return Some(scope),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    fn spatial_from(&self, scope: &WidgetFocusInfo, origin: PxBox, orientation: Orientation2D) -> Option<WidgetFocusInfo> {
+        let self_id = self.info.id();
+        let scope_id = scope.info.id();
+        let origin_center = origin.center();
+
+        // gap between the ranges, zero if overlaps.
+        fn gap(a_min: Px, a_max: Px, b_min: Px, b_max: Px) -> i64 {
+            if b_max < a_min {
+                (a_min - b_max).0 as i64
+            } else if b_min > a_max {
+                (b_min - a_max).0 as i64
+            } else {
+                0
+            }
+        }
+
+        let mut best_key = (i64::MAX, DistanceKey::NONE_MAX, TabIndex::SKIP);
+        let mut best = None;
+        for w in scope.descendants() {
+            if w.info.id() == self_id || w.info.is_ancestor(&self.info) || self.info.is_ancestor(&w.info) {
+                continue;
+            }
+            if w.is_scope() && w.descendants().next().is_some() {
+                // navigate to the leaf focusables.
+                continue;
+            }
+            if w.self_and_ancestors()
+                .take_while(|w| w.info.id() != scope_id)
+                .any(|w| w.focus_info().skip_directional())
+            {
+                continue;
+            }
+
+            let b = w.info.inner_bounds().to_box2d();
+            let c = b.center();
+            let gap_x = || gap(origin.min.x, origin.max.x, b.min.x, b.max.x);
+            let gap_y = || gap(origin.min.y, origin.max.y, b.min.y, b.max.y);
+            let (main, cross) = match orientation {
+                Orientation2D::Above if c.y < origin.min.y => (origin.min.y - b.max.y, gap_x()),
+                Orientation2D::Right if c.x > origin.max.x => (b.min.x - origin.max.x, gap_y()),
+                Orientation2D::Below if c.y > origin.max.y => (b.min.y - origin.max.y, gap_x()),
+                Orientation2D::Left if c.x < origin.min.x => (origin.min.x - b.max.x, gap_y()),
+                _ => continue,
+            };
+            // aligned candidates are preferred over closer misaligned ones.
+            let dist = main.max(Px(0)).0 as i64 + cross * 2;
+
+            let key = (dist, w.info.distance_key(origin_center), w.focus_info().tab_index());
+            if key < best_key {
+                best_key = key;
+                best = Some(w);
+            }
+        }
+        best
+    }
+
     /// Closest focusable in the same scope above this widget.
     pub fn focusable_up(&self) -> Option<WidgetFocusInfo> {
         self.directional_next(Orientation2D::Above)
@@ -1358,10 +1502,13 @@ impl WidgetFocusInfo {
         self.next_up_from(self.info.inner_bounds().to_box2d())
     }
     fn next_up_from(&self, origin: PxBox) -> Option<WidgetFocusInfo> {
+        if let Some(scope) = self.spatial_scope() {
+            return self.spatial_from(&scope, origin, Orientation2D::Above);
+        }
         if let Some(scope) = self.scope() {
             let scope_info = scope.focus_info();
             match scope_info.directional_nav() {
-                DirectionalNav::None => None,
+                DirectionalNav::None | DirectionalNav::Spatial => None,
                 DirectionalNav::Continue => self.directional_next_from(Orientation2D::Above, origin).or_else(|| {
                     let mut from = scope.info.inner_bounds();
                     from.origin.y -= Px(1);
@@ -1395,10 +1542,13 @@ impl WidgetFocusInfo {
         self.next_right_from(self.info.inner_bounds().to_box2d())
     }
     fn next_right_from(&self, origin: PxBox) -> Option<WidgetFocusInfo> {
+        if let Some(scope) = self.spatial_scope() {
+            return self.spatial_from(&scope, origin, Orientation2D::Right);
+        }
         if let Some(scope) = self.scope() {
             let scope_info = scope.focus_info();
             match scope_info.directional_nav() {
-                DirectionalNav::None => None,
+                DirectionalNav::None | DirectionalNav::Spatial => None,
                 DirectionalNav::Continue => self.directional_next_from(Orientation2D::Right, origin).or_else(|| {
                     let mut from = scope.info.inner_bounds();
                     from.origin.x += from.size.width + Px(1);
@@ -1430,10 +1580,13 @@ impl WidgetFocusInfo {
         self.next_down_from(self.info.inner_bounds().to_box2d())
     }
     fn next_down_from(&self, origin: PxBox) -> Option<WidgetFocusInfo> {
+        if let Some(scope) = self.spatial_scope() {
+            return self.spatial_from(&scope, origin, Orientation2D::Below);
+        }
         if let Some(scope) = self.scope() {
             let scope_info = scope.focus_info();
             match scope_info.directional_nav() {
-                DirectionalNav::None => None,
+                DirectionalNav::None | DirectionalNav::Spatial => None,
                 DirectionalNav::Continue => self.directional_next_from(Orientation2D::Below, origin).or_else(|| {
                     let mut from = scope.info.inner_bounds();
                     from.origin.y += from.size.height + Px(1);
@@ -1465,10 +1618,13 @@ impl WidgetFocusInfo {
         self.next_left_from(self.info.inner_bounds().to_box2d())
     }
     fn next_left_from(&self, origin: PxBox) -> Option<WidgetFocusInfo> {
+        if let Some(scope) = self.spatial_scope() {
+            return self.spatial_from(&scope, origin, Orientation2D::Left);
+        }
         if let Some(scope) = self.scope() {
             let scope_info = scope.focus_info();
             match scope_info.directional_nav() {
-                DirectionalNav::None => None,
+                DirectionalNav::None | DirectionalNav::Spatial => None,
                 DirectionalNav::Continue => self.directional_next_from(Orientation2D::Left, origin).or_else(|| {
                     let mut from = scope.info.inner_bounds();
                     from.origin.x -= Px(1);
@@ -1544,6 +1700,22 @@ impl WidgetFocusInfo {
         skip_self: bool,
         already_found: FocusNavAction,
     ) -> FocusNavAction {
+        if let Some(scope) = self.spatial_scope() {
+            let mut nav = already_found;
+            let from = self.info.inner_bounds().to_box2d();
+            for (o, a) in [
+                (Orientation2D::Above, FocusNavAction::UP),
+                (Orientation2D::Right, FocusNavAction::RIGHT),
+                (Orientation2D::Below, FocusNavAction::DOWN),
+                (Orientation2D::Left, FocusNavAction::LEFT),
+            ] {
+                if !nav.contains(a) && self.spatial_from(&scope, from, o).is_some() {
+                    nav |= a;
+                }
+            }
+            return nav;
+        }
+
         let directional_nav = scope_info.directional_nav();
 
         if directional_nav == DirectionalNav::None {
@@ -1774,6 +1946,15 @@ pub enum FocusScopeOnFocus {
     /// [`LastFocused`]: Self::LastFocused
     LastFocusedIgnoreBounds,
 }
+impl FocusScopeOnFocus {
+    /// If is [`LastFocused`] or [`LastFocusedIgnoreBounds`], the scopes that remember the return focus.
+    ///
+    /// [`LastFocused`]: Self::LastFocused
+    /// [`LastFocusedIgnoreBounds`]: Self::LastFocusedIgnoreBounds
+    pub fn is_last_focused(self) -> bool {
+        matches!(self, Self::LastFocused | Self::LastFocusedIgnoreBounds)
+    }
+}
 impl fmt::Debug for FocusScopeOnFocus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
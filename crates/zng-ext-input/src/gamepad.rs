@@ -0,0 +1,388 @@
+//! Gamepad manager.
+
+use std::{collections::HashMap, num::NonZeroU32};
+
+use zng_app::{
+    event::{event, event_args, AnyEventArgs as _},
+    shortcut::ModifiersState,
+    update::EventUpdate,
+    view_process::{
+        raw_device_events::DeviceId,
+        raw_events::{
+            RawGamepadAxisArgs, RawGamepadButtonArgs, RAW_GAMEPAD_AXIS_EVENT, RAW_GAMEPAD_BUTTON_EVENT, RAW_GAMEPAD_CONNECTED_EVENT,
+            RAW_GAMEPAD_DISCONNECTED_EVENT,
+        },
+        VIEW_PROCESS_INITED_EVENT,
+    },
+    widget::{info::InteractionPath, WidgetId},
+    window::WindowId,
+    AppExtension,
+};
+use zng_app_context::app_local;
+use zng_txt::Txt;
+use zng_var::{var, ArcVar, ReadOnlyArcVar, Var};
+pub use zng_view_api::{
+    gamepad::{GamepadAxis, GamepadButton},
+    mouse::ButtonState,
+};
+
+use crate::{
+    focus::{FocusReason, FocusRequest, FocusTarget, FOCUS},
+    gesture::{ClickArgs, ClickArgsSource, CLICK_EVENT},
+};
+
+event_args! {
+    /// Arguments for [`GAMEPAD_BUTTON_EVENT`].
+    pub struct GamepadButtonArgs {
+        /// Window that has focus.
+        pub window_id: WindowId,
+
+        /// Gamepad device.
+        pub device_id: DeviceId,
+
+        /// The button.
+        pub button: GamepadButton,
+
+        /// Analog value in the `0.0..=1.0` range, digital buttons are `0.0` or `1.0`.
+        pub value: f32,
+
+        /// If the button was pressed or released.
+        pub state: ButtonState,
+
+        /// If the `device_id` is the [`GAMEPAD.primary`] gamepad.
+        ///
+        /// [`GAMEPAD.primary`]: GAMEPAD::primary
+        pub is_primary: bool,
+
+        /// The focused element at the time of the button input.
+        pub target: InteractionPath,
+
+        ..
+
+        /// The [`target`](Self::target).
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.target)
+        }
+    }
+}
+impl GamepadButtonArgs {
+    /// Returns `true` if the widget is enabled in [`target`].
+    ///
+    /// [`target`]: Self::target
+    pub fn is_enabled(&self, widget_id: WidgetId) -> bool {
+        self.target.interactivity_of(widget_id).map(|i| i.is_enabled()).unwrap_or(false)
+    }
+
+    /// Returns `true` if the widget is disabled in [`target`].
+    ///
+    /// [`target`]: Self::target
+    pub fn is_disabled(&self, widget_id: WidgetId) -> bool {
+        self.target.interactivity_of(widget_id).map(|i| i.is_disabled()).unwrap_or(false)
+    }
+}
+
+event! {
+    /// Gamepad button pressed or released while a widget is focused.
+    ///
+    /// This event targets the focused widget, it only notifies the button state changes, the [`GAMEPAD`] service
+    /// provides variables that track all button and axis values.
+    pub static GAMEPAD_BUTTON_EVENT: GamepadButtonArgs;
+}
+
+/// Application extension that provides gamepad events and state.
+///
+/// This extension processes the raw gamepad events, tracking the state of each gamepad and retargeting the button
+/// presses to the focused widget. Unhandled input from the [primary] gamepad moves the focus and clicks the focused
+/// widget, see [`GAMEPAD.nav_enabled`] for details.
+///
+/// # Events
+///
+/// Events this extension provides.
+///
+/// * [`GAMEPAD_BUTTON_EVENT`]
+///
+/// # Services
+///
+/// Services this extension provides.
+///
+/// * [`GAMEPAD`]
+///
+/// [primary]: GAMEPAD::primary
+/// [`GAMEPAD.nav_enabled`]: GAMEPAD::nav_enabled
+#[derive(Default)]
+pub struct GamepadManager {}
+impl AppExtension for GamepadManager {
+    fn event_preview(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = RAW_GAMEPAD_BUTTON_EVENT.on(update) {
+            GAMEPAD_SV.write().on_button(args);
+        } else if let Some(args) = RAW_GAMEPAD_AXIS_EVENT.on(update) {
+            GAMEPAD_SV.write().on_axis(args);
+        } else if let Some(args) = RAW_GAMEPAD_CONNECTED_EVENT.on(update) {
+            let s = GAMEPAD_SV.read();
+            let info = GamepadInfo {
+                device_id: args.device_id,
+                name: args.name.clone(),
+            };
+            s.gamepads.modify(move |g| {
+                if !g.iter().any(|g| g.device_id == info.device_id) {
+                    g.to_mut().push(info);
+                }
+            });
+            let device_id = args.device_id;
+            s.primary.modify(move |p| {
+                if p.is_none() {
+                    *p.to_mut() = Some(device_id);
+                }
+            });
+        } else if let Some(args) = RAW_GAMEPAD_DISCONNECTED_EVENT.on(update) {
+            GAMEPAD_SV.write().on_disconnected(args.device_id);
+        } else if let Some(args) = VIEW_PROCESS_INITED_EVENT.on(update) {
+            if args.is_respawn {
+                // gamepads are notified connected again by the new view-process.
+                let mut s = GAMEPAD_SV.write();
+                for g in s.gamepads.get() {
+                    s.on_disconnected(g.device_id);
+                }
+            }
+        }
+    }
+
+    fn event(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = GAMEPAD_BUTTON_EVENT.on_unhandled(update) {
+            if args.is_primary && args.state == ButtonState::Pressed && GAMEPAD_SV.read().nav_enabled.get() {
+                match args.button {
+                    GamepadButton::DPadUp => focus_nav(FocusTarget::Up),
+                    GamepadButton::DPadDown => focus_nav(FocusTarget::Down),
+                    GamepadButton::DPadLeft => focus_nav(FocusTarget::Left),
+                    GamepadButton::DPadRight => focus_nav(FocusTarget::Right),
+                    GamepadButton::East => focus_nav(FocusTarget::Exit),
+                    GamepadButton::South => {
+                        if !args.target.interactivity().is_blocked() {
+                            CLICK_EVENT.notify(ClickArgs::new(
+                                args.timestamp,
+                                args.propagation().clone(),
+                                args.window_id,
+                                Some(args.device_id),
+                                ClickArgsSource::Gamepad { button: args.button },
+                                NonZeroU32::new(1).unwrap(),
+                                false,
+                                ModifiersState::empty(),
+                                args.target.clone(),
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn focus_nav(target: FocusTarget) {
+    FOCUS.focus(FocusRequest::new(target, true).with_reason(FocusReason::Navigation));
+}
+
+app_local! {
+    static GAMEPAD_SV: GamepadService = GamepadService {
+        gamepads: var(vec![]),
+        primary: var(None),
+        nav_enabled: var(true),
+        stick_threshold: var(0.5),
+        states: HashMap::new(),
+        stick_dir: None,
+    };
+}
+
+struct GamepadService {
+    gamepads: ArcVar<Vec<GamepadInfo>>,
+    primary: ArcVar<Option<DeviceId>>,
+    nav_enabled: ArcVar<bool>,
+    stick_threshold: ArcVar<f32>,
+    states: HashMap<DeviceId, GamepadState>,
+    /// Left stick direction of the primary gamepad, when over threshold.
+    stick_dir: Option<FocusTarget>,
+}
+impl GamepadService {
+    fn state(&mut self, device_id: DeviceId) -> &mut GamepadState {
+        self.states.entry(device_id).or_default()
+    }
+
+    fn on_button(&mut self, args: &RawGamepadButtonArgs) {
+        let pressed = args.state == ButtonState::Pressed;
+        let (is_pressed, value) = self.state(args.device_id).button(args.button);
+        value.set(args.value);
+        if is_pressed.get() == pressed {
+            // analog value change.
+            return;
+        }
+        is_pressed.set(pressed);
+
+        let mut is_primary = self.primary.get() == Some(args.device_id);
+        if pressed && !is_primary {
+            // the last gamepad used is the primary.
+            self.primary.set(Some(args.device_id));
+            self.stick_dir = None;
+            is_primary = true;
+        }
+
+        if let Some(target) = FOCUS.focused().get() {
+            GAMEPAD_BUTTON_EVENT.notify(GamepadButtonArgs::new(
+                args.timestamp,
+                args.propagation().clone(),
+                target.window_id(),
+                args.device_id,
+                args.button,
+                args.value,
+                args.state,
+                is_primary,
+                target,
+            ));
+        }
+    }
+
+    fn on_axis(&mut self, args: &RawGamepadAxisArgs) {
+        let state = self.state(args.device_id);
+        state.axis(args.axis).set(args.value);
+
+        if !matches!(args.axis, GamepadAxis::LeftStickX | GamepadAxis::LeftStickY)
+            || self.primary.get() != Some(args.device_id)
+            || !self.nav_enabled.get()
+        {
+            return;
+        }
+
+        let state = self.state(args.device_id);
+        let x = state.axis(GamepadAxis::LeftStickX).get();
+        let y = state.axis(GamepadAxis::LeftStickY).get();
+        let threshold = self.stick_threshold.get();
+        let dir = if x.abs().max(y.abs()) < threshold {
+            None
+        } else if x.abs() > y.abs() {
+            Some(if x > 0.0 { FocusTarget::Right } else { FocusTarget::Left })
+        } else {
+            // positive is up.
+            Some(if y > 0.0 { FocusTarget::Up } else { FocusTarget::Down })
+        };
+
+        if dir != self.stick_dir {
+            self.stick_dir = dir;
+            if let Some(dir) = dir {
+                if FOCUS.focused().with(|f| f.is_some()) {
+                    focus_nav(dir);
+                }
+            }
+        }
+    }
+
+    fn on_disconnected(&mut self, device_id: DeviceId) {
+        if let Some(s) = self.states.remove(&device_id) {
+            s.reset();
+        }
+        self.gamepads.modify(move |g| {
+            if let Some(i) = g.iter().position(|g| g.device_id == device_id) {
+                g.to_mut().remove(i);
+            }
+        });
+        if self.primary.get() == Some(device_id) {
+            let next = self
+                .gamepads
+                .with(|g| g.iter().find(|g| g.device_id != device_id).map(|g| g.device_id));
+            self.primary.set(next);
+            self.stick_dir = None;
+        }
+    }
+}
+
+#[derive(Default)]
+struct GamepadState {
+    buttons: HashMap<GamepadButton, (ArcVar<bool>, ArcVar<f32>)>,
+    axes: HashMap<GamepadAxis, ArcVar<f32>>,
+}
+impl GamepadState {
+    fn button(&mut self, button: GamepadButton) -> (ArcVar<bool>, ArcVar<f32>) {
+        self.buttons.entry(button).or_insert_with(|| (var(false), var(0.0))).clone()
+    }
+
+    fn axis(&mut self, axis: GamepadAxis) -> ArcVar<f32> {
+        self.axes.entry(axis).or_insert_with(|| var(0.0)).clone()
+    }
+
+    fn reset(&self) {
+        for (p, v) in self.buttons.values() {
+            p.set(false);
+            v.set(0.0);
+        }
+        for v in self.axes.values() {
+            v.set(0.0);
+        }
+    }
+}
+
+/// Connected gamepad.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadInfo {
+    /// Gamepad device.
+    pub device_id: DeviceId,
+    /// Display name.
+    pub name: Txt,
+}
+
+/// Gamepad service.
+///
+/// This service is provided by the [`GamepadManager`] extension.
+///
+/// The service tracks connected gamepads and the state of each button and axis, the values are in variables that update
+/// even when the app is not focused. Note that the default view-process only reports gamepad input if built with the
+/// `"gamepad"` feature.
+///
+/// # Navigation
+///
+/// By default the [primary] gamepad controls the keyboard focus, the directional pad and left stick move the focus
+/// like the arrow keys, the `South` button clicks the focused widget and the `East` button moves the focus to the
+/// parent scope. Widgets can handle [`GAMEPAD_BUTTON_EVENT`] to override this, the navigation only happens if the event
+/// propagation is not stopped.
+///
+/// [primary]: Self::primary
+pub struct GAMEPAD;
+impl GAMEPAD {
+    /// Connected gamepads.
+    pub fn gamepads(&self) -> ReadOnlyArcVar<Vec<GamepadInfo>> {
+        GAMEPAD_SV.read().gamepads.read_only()
+    }
+
+    /// The gamepad that controls the focus navigation.
+    ///
+    /// This is the first connected gamepad, or the last one that pressed a button.
+    pub fn primary(&self) -> ReadOnlyArcVar<Option<DeviceId>> {
+        GAMEPAD_SV.read().primary.read_only()
+    }
+
+    /// If the primary gamepad moves the focus and clicks the focused widget.
+    ///
+    /// Is `true` by default.
+    pub fn nav_enabled(&self) -> ArcVar<bool> {
+        GAMEPAD_SV.read().nav_enabled.clone()
+    }
+
+    /// Left stick deflection that moves the focus, in the `0.0..=1.0` range.
+    ///
+    /// Is `0.5` by default. The stick must return below the threshold before it can move the focus again.
+    pub fn stick_threshold(&self) -> ArcVar<f32> {
+        GAMEPAD_SV.read().stick_threshold.clone()
+    }
+
+    /// Variable that tracks if the gamepad `button` is pressed.
+    pub fn is_pressed(&self, device_id: DeviceId, button: GamepadButton) -> ReadOnlyArcVar<bool> {
+        GAMEPAD_SV.write().state(device_id).button(button).0.read_only()
+    }
+
+    /// Variable that tracks the gamepad `button` analog value, in the `0.0..=1.0` range.
+    pub fn button_value(&self, device_id: DeviceId, button: GamepadButton) -> ReadOnlyArcVar<f32> {
+        GAMEPAD_SV.write().state(device_id).button(button).1.read_only()
+    }
+
+    /// Variable that tracks the gamepad `axis` value, in the `-1.0..=1.0` range.
+    pub fn axis(&self, device_id: DeviceId, axis: GamepadAxis) -> ReadOnlyArcVar<f32> {
+        GAMEPAD_SV.write().state(device_id).axis(axis).read_only()
+    }
+}
@@ -16,7 +16,10 @@ use zng_app::{
         Shortcuts,
     },
     update::EventUpdate,
-    view_process::raw_device_events::DeviceId,
+    view_process::{
+        raw_device_events::DeviceId,
+        raw_events::{RAW_PAN_GESTURE_EVENT, RAW_PINCH_GESTURE_EVENT, RAW_ROTATION_GESTURE_EVENT},
+    },
     widget::{
         info::{HitTestInfo, InteractionPath, WidgetPath},
         WidgetId,
@@ -27,17 +30,19 @@ use zng_app::{
 use zng_app_context::app_local;
 use zng_ext_window::WINDOWS;
 use zng_handle::{Handle, HandleOwner, WeakHandle};
-use zng_layout::unit::DipPoint;
+use zng_layout::unit::{AngleDegree, DipPoint, DipVector, Factor};
 use zng_var::{var, ArcVar, Var};
 use zng_view_api::{
+    gamepad::GamepadButton,
     keyboard::{Key, KeyCode, KeyLocation, KeyState, NativeKeyCode},
     mouse::MouseButton,
+    touch::TouchPhase,
 };
 
 use crate::{
     focus::{FocusRequest, FocusTarget, FOCUS},
     keyboard::{HeadlessAppKeyboardExt, KeyInputArgs, KEY_INPUT_EVENT},
-    mouse::{MouseClickArgs, MOUSE_CLICK_EVENT},
+    mouse::{MouseClickArgs, MOUSE, MOUSE_CLICK_EVENT},
     touch::{TouchLongPressArgs, TouchTapArgs, TOUCH_LONG_PRESS_EVENT, TOUCH_TAP_EVENT},
 };
 
@@ -85,6 +90,14 @@ pub enum ClickArgsSource {
         /// Is `true` if the requested primary action, is `false` is requested context action.
         is_primary: bool,
     },
+
+    /// Click event was generated by the [gamepad button event](crate::gamepad::GAMEPAD_BUTTON_EVENT).
+    ///
+    /// The target is the focused widget, the same way as a `Shortcut` event.
+    Gamepad {
+        /// The gamepad button.
+        button: GamepadButton,
+    },
 }
 
 /// What kind of click a shortcut represents in a [`ClickArgsSource::Shortcut`].
@@ -164,6 +177,100 @@ event_args! {
 
         }
     }
+
+    /// [`PINCH_GESTURE_EVENT`] arguments.
+    pub struct PinchGestureArgs {
+        /// Id of window that received the event.
+        pub window_id: WindowId,
+
+        /// Id of device that generated the event.
+        pub device_id: DeviceId,
+
+        /// Position of the mouse cursor in the window when the gesture happened.
+        pub position: DipPoint,
+
+        /// Scale delta, positive values indicate magnification (zoom-in), negative values indicate shrinking (zoom-out).
+        ///
+        /// See [`scale`] for the scale factor.
+        ///
+        /// [`scale`]: Self::scale
+        pub delta: f32,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        /// The widget under the mouse cursor or the window root widget.
+        pub target: InteractionPath,
+
+        ..
+
+        /// The [`target`].
+        ///
+        /// [`target`]: Self::target
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.target)
+        }
+    }
+
+    /// [`ROTATION_GESTURE_EVENT`] arguments.
+    pub struct RotationGestureArgs {
+        /// Id of window that received the event.
+        pub window_id: WindowId,
+
+        /// Id of device that generated the event.
+        pub device_id: DeviceId,
+
+        /// Position of the mouse cursor in the window when the gesture happened.
+        pub position: DipPoint,
+
+        /// Rotation delta, positive values indicate counter-clockwise rotation.
+        pub delta: AngleDegree,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        /// The widget under the mouse cursor or the window root widget.
+        pub target: InteractionPath,
+
+        ..
+
+        /// The [`target`].
+        ///
+        /// [`target`]: Self::target
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.target)
+        }
+    }
+
+    /// [`PAN_GESTURE_EVENT`] arguments.
+    pub struct PanGestureArgs {
+        /// Id of window that received the event.
+        pub window_id: WindowId,
+
+        /// Id of device that generated the event.
+        pub device_id: DeviceId,
+
+        /// Position of the mouse cursor in the window when the gesture happened.
+        pub position: DipPoint,
+
+        /// Pan delta.
+        pub delta: DipVector,
+
+        /// Gesture state.
+        pub phase: TouchPhase,
+
+        /// The widget under the mouse cursor or the window root widget.
+        pub target: InteractionPath,
+
+        ..
+
+        /// The [`target`].
+        ///
+        /// [`target`]: Self::target
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.target)
+        }
+    }
 }
 impl From<MouseClickArgs> for ClickArgs {
     fn from(args: MouseClickArgs) -> Self {
@@ -242,13 +349,14 @@ impl ClickArgs {
     /// A primary click causes the default widget function interaction.
     ///
     /// Returns `true` if the click source is a left mouse button click or a
-    /// [primary click shortcut](GESTURES::click_focused) or a touch tap.
+    /// [primary click shortcut](GESTURES::click_focused) or a touch tap or a gamepad `South` button press.
     pub fn is_primary(&self) -> bool {
         match &self.source {
             ClickArgsSource::Mouse { button, .. } => *button == MouseButton::Left,
             ClickArgsSource::Touch { is_tap, .. } => *is_tap,
             ClickArgsSource::Shortcut { kind, .. } => *kind == ShortcutClick::Primary,
             ClickArgsSource::Access { is_primary } => *is_primary,
+            ClickArgsSource::Gamepad { button } => *button == GamepadButton::South,
         }
     }
 
@@ -264,6 +372,7 @@ impl ClickArgs {
                 ClickArgsSource::Touch { is_tap, .. } => !*is_tap,
                 ClickArgsSource::Shortcut { kind, .. } => *kind == ShortcutClick::Context,
                 ClickArgsSource::Access { is_primary } => !*is_primary,
+                ClickArgsSource::Gamepad { .. } => false,
             }
     }
 
@@ -321,6 +430,11 @@ impl ClickArgs {
         matches!(&self.source, ClickArgsSource::Access { .. })
     }
 
+    /// If this event was generated by a gamepad device.
+    pub fn is_from_gamepad(&self) -> bool {
+        matches!(&self.source, ClickArgsSource::Gamepad { .. })
+    }
+
     /// Gets the click position, if the click was generated by a device with position.
     ///
     /// The position is in the coordinates of [`target`](ClickArgs::target).
@@ -328,10 +442,39 @@ impl ClickArgs {
         match &self.source {
             ClickArgsSource::Mouse { position, .. } => Some(*position),
             ClickArgsSource::Touch { position, .. } => Some(*position),
-            ClickArgsSource::Shortcut { .. } | ClickArgsSource::Access { .. } => None,
+            ClickArgsSource::Shortcut { .. } | ClickArgsSource::Access { .. } | ClickArgsSource::Gamepad { .. } => None,
         }
     }
 }
+impl PinchGestureArgs {
+    /// Returns `true` if the widget is enabled in [`target`].
+    ///
+    /// [`target`]: Self::target
+    pub fn is_enabled(&self, widget_id: WidgetId) -> bool {
+        self.target.interactivity_of(widget_id).map(|i| i.is_enabled()).unwrap_or(false)
+    }
+
+    /// Scale factor of the gesture update, `1.0 + delta`.
+    pub fn scale(&self) -> Factor {
+        Factor(1.0 + self.delta)
+    }
+}
+impl RotationGestureArgs {
+    /// Returns `true` if the widget is enabled in [`target`].
+    ///
+    /// [`target`]: Self::target
+    pub fn is_enabled(&self, widget_id: WidgetId) -> bool {
+        self.target.interactivity_of(widget_id).map(|i| i.is_enabled()).unwrap_or(false)
+    }
+}
+impl PanGestureArgs {
+    /// Returns `true` if the widget is enabled in [`target`].
+    ///
+    /// [`target`]: Self::target
+    pub fn is_enabled(&self, widget_id: WidgetId) -> bool {
+        self.target.interactivity_of(widget_id).map(|i| i.is_enabled()).unwrap_or(false)
+    }
+}
 
 event! {
     /// Aggregate click event.
@@ -347,6 +490,25 @@ event! {
     ///
     /// [`Shortcut`]: zng_app::shortcut::Shortcut
     pub static SHORTCUT_EVENT: ShortcutArgs;
+
+    /// Pinch gesture recognized by the system, usually a two-finger pinch on a touchpad.
+    ///
+    /// The event targets the widget under the mouse cursor. Note that touchscreen pinch is provided
+    /// by the [`TOUCH_TRANSFORM_EVENT`] computed from raw touch input, this event is only generated
+    /// if the operating system recognizes the gesture and the view-process implements it.
+    ///
+    /// [`TOUCH_TRANSFORM_EVENT`]: crate::touch::TOUCH_TRANSFORM_EVENT
+    pub static PINCH_GESTURE_EVENT: PinchGestureArgs;
+
+    /// Rotation gesture recognized by the system, usually a two-finger rotation on a touchpad.
+    ///
+    /// See [`PINCH_GESTURE_EVENT`] for more details.
+    pub static ROTATION_GESTURE_EVENT: RotationGestureArgs;
+
+    /// Pan gesture recognized by the system.
+    ///
+    /// See [`PINCH_GESTURE_EVENT`] for more details.
+    pub static PAN_GESTURE_EVENT: PanGestureArgs;
 }
 
 /// Application extension that provides aggregate events.
@@ -355,10 +517,16 @@ event! {
 ///
 /// * [`CLICK_EVENT`]
 /// * [`SHORTCUT_EVENT`]
+/// * [`PINCH_GESTURE_EVENT`]
+/// * [`ROTATION_GESTURE_EVENT`]
+/// * [`PAN_GESTURE_EVENT`]
 ///
 /// Services this extension provides.
 ///
 /// * [`GESTURES`]
+/// * [`KEYMAP`]
+///
+/// [`KEYMAP`]: crate::keymap::KEYMAP
 #[derive(Default)]
 pub struct GestureManager {}
 impl AppExtension for GestureManager {
@@ -389,8 +557,42 @@ impl AppExtension for GestureManager {
         } else if let Some(args) = ACCESS_CLICK_EVENT.on_unhandled(update) {
             // Run access click.
             GESTURES_SV.write().on_access(args);
+        } else if let Some(args) = RAW_PINCH_GESTURE_EVENT.on(update) {
+            if let Some((position, target)) = system_gesture_target(args.window_id) {
+                let args = PinchGestureArgs::now(args.window_id, args.device_id, position, args.delta, args.phase, target);
+                PINCH_GESTURE_EVENT.notify(args);
+            }
+        } else if let Some(args) = RAW_ROTATION_GESTURE_EVENT.on(update) {
+            if let Some((position, target)) = system_gesture_target(args.window_id) {
+                let args = RotationGestureArgs::now(args.window_id, args.device_id, position, args.delta, args.phase, target);
+                ROTATION_GESTURE_EVENT.notify(args);
+            }
+        } else if let Some(args) = RAW_PAN_GESTURE_EVENT.on(update) {
+            if let Some((position, target)) = system_gesture_target(args.window_id) {
+                let args = PanGestureArgs::now(args.window_id, args.device_id, position, args.delta, args.phase, target);
+                PAN_GESTURE_EVENT.notify(args);
+            }
         }
     }
+
+    fn update(&mut self) {
+        crate::keymap::update();
+    }
+}
+
+/// Gets the mouse position and hovered widget, or the window root widget if the mouse is not over the window.
+fn system_gesture_target(window_id: WindowId) -> Option<(DipPoint, InteractionPath)> {
+    let position = MOUSE
+        .position()
+        .with(|p| p.as_ref().filter(|p| p.window_id == window_id).map(|p| p.position))
+        .unwrap_or_default();
+
+    let target = match MOUSE.hovered().get() {
+        Some(p) if p.window_id() == window_id => p,
+        _ => WINDOWS.widget_tree(window_id).ok()?.root().interaction_path(),
+    };
+
+    target.unblocked().map(|t| (position, t))
 }
 
 app_local! {
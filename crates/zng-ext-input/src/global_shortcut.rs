@@ -0,0 +1,309 @@
+//! Global shortcuts, detected even when the app is not focused.
+
+use std::fmt;
+
+use zng_app::{
+    event::{event, event_args, AnyEventArgs},
+    shortcut::{GestureKey, KeyGesture, ModifiersState, Shortcut},
+    update::{EventUpdate, UpdateOp, UPDATES},
+    view_process::{raw_events::RAW_GLOBAL_SHORTCUT_EVENT, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT},
+    AppEventSender, AppExtension,
+};
+use zng_app_context::app_local;
+use zng_handle::{Handle, HandleOwner, WeakHandle};
+use zng_txt::Txt;
+use zng_view_api::keyboard::{GlobalShortcut, GlobalShortcutId, GlobalShortcutModifiers, Key, KeyCode};
+
+/// Application extension that provides global shortcuts.
+///
+/// # Events
+///
+/// Events this extension provides.
+///
+/// * [`GLOBAL_SHORTCUT_EVENT`]
+///
+/// # Services
+///
+/// Services this extension provides.
+///
+/// * [`GLOBAL_SHORTCUTS`]
+#[derive(Default)]
+pub struct GlobalShortcutManager {}
+impl AppExtension for GlobalShortcutManager {
+    fn event(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = RAW_GLOBAL_SHORTCUT_EVENT.on(update) {
+            let s = GLOBAL_SHORTCUTS_SV.read();
+            if let Some(e) = s.entries.iter().find(|e| e.id == args.shortcut_id && !e.handle.is_dropped()) {
+                GLOBAL_SHORTCUT_EVENT.notify(GlobalShortcutArgs::new(
+                    args.timestamp,
+                    args.propagation().clone(),
+                    e.shortcut.clone(),
+                ));
+            }
+        } else if let Some(args) = VIEW_PROCESS_INITED_EVENT.on(update) {
+            if args.is_respawn {
+                let mut s = GLOBAL_SHORTCUTS_SV.write();
+                s.entries.retain(|e| !e.handle.is_dropped());
+                for e in &s.entries {
+                    match VIEW_PROCESS.register_global_shortcut(e.id, e.native.clone()) {
+                        Ok(Ok(())) => {}
+                        Ok(Err(err)) => tracing::error!("cannot restore global shortcut after respawn, {err}"),
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        let mut s = GLOBAL_SHORTCUTS_SV.write();
+        if s.entries.iter().any(|e| e.handle.is_dropped()) {
+            s.entries.retain(|e| {
+                let retain = !e.handle.is_dropped();
+                if !retain {
+                    let _ = VIEW_PROCESS.unregister_global_shortcut(e.id);
+                }
+                retain
+            });
+        }
+    }
+}
+
+app_local! {
+    static GLOBAL_SHORTCUTS_SV: GlobalShortcutsService = GlobalShortcutsService {
+        entries: vec![],
+        id_gen: GlobalShortcutId::first(),
+    };
+}
+
+struct GlobalShortcutsService {
+    entries: Vec<GlobalShortcutEntry>,
+    id_gen: GlobalShortcutId,
+}
+
+struct GlobalShortcutEntry {
+    id: GlobalShortcutId,
+    shortcut: Shortcut,
+    native: GlobalShortcut,
+    handle: HandleOwner<Option<AppEventSender>>,
+}
+
+/// Global shortcuts service.
+///
+/// Global shortcuts are key gestures that are detected even when the app is not focused, they are useful for
+/// apps that mostly run in the background or in the system tray.
+///
+/// Global shortcuts are implemented by the view-process, the default view-process implementation
+/// supports global shortcuts on Windows and X11, registration returns [`GlobalShortcutError::NotSupported`]
+/// in other systems.
+///
+/// # Provider
+///
+/// This service is provided by the [`GlobalShortcutManager`] extension.
+#[allow(non_camel_case_types)]
+pub struct GLOBAL_SHORTCUTS;
+impl GLOBAL_SHORTCUTS {
+    /// Register the `shortcut` to generate [`GLOBAL_SHORTCUT_EVENT`] when pressed.
+    ///
+    /// Only [`Shortcut::Gesture`] is supported, the gesture key must be a [`GestureKey::Code`], a [`Key::Char`]
+    /// that is an ASCII letter, digit or space, or a named [`Key`] that has an equivalent [`KeyCode`].
+    ///
+    /// The shortcut is unregistered when all clones of the returned handle are dropped. If the `shortcut`
+    /// is already registered by the app a clone of the existing handle is returned.
+    pub fn register(&self, shortcut: impl Into<Shortcut>) -> Result<GlobalShortcutHandle, GlobalShortcutError> {
+        let shortcut = shortcut.into();
+        let native = match &shortcut {
+            Shortcut::Gesture(g) => native_shortcut(g).ok_or(GlobalShortcutError::InvalidShortcut)?,
+            _ => return Err(GlobalShortcutError::InvalidShortcut),
+        };
+
+        let mut s = GLOBAL_SHORTCUTS_SV.write();
+        if let Some(e) = s.entries.iter().find(|e| e.native == native && !e.handle.is_dropped()) {
+            if let Some(h) = e.handle.weak_handle().upgrade() {
+                return Ok(GlobalShortcutHandle(h));
+            }
+        }
+
+        let id = s.id_gen.incr();
+        match VIEW_PROCESS.register_global_shortcut(id, native.clone()) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(e.into()),
+            Err(_) => return Err(GlobalShortcutError::ViewProcessOffline),
+        }
+
+        let (owner, handle) = Handle::new(Some(UPDATES.sender()));
+        s.entries.push(GlobalShortcutEntry {
+            id,
+            shortcut,
+            native,
+            handle: owner,
+        });
+        Ok(GlobalShortcutHandle(handle))
+    }
+
+    /// Gets all shortcuts currently registered.
+    pub fn shortcuts(&self) -> Vec<Shortcut> {
+        GLOBAL_SHORTCUTS_SV
+            .read()
+            .entries
+            .iter()
+            .filter(|e| !e.handle.is_dropped())
+            .map(|e| e.shortcut.clone())
+            .collect()
+    }
+}
+
+fn native_shortcut(gesture: &KeyGesture) -> Option<GlobalShortcut> {
+    let key_code = match &gesture.key {
+        GestureKey::Code(c) => *c,
+        GestureKey::Key(Key::Char(c)) => match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => format!("Key{c}").parse().ok()?,
+            c @ '0'..='9' => format!("Digit{c}").parse().ok()?,
+            ' ' => KeyCode::Space,
+            _ => return None,
+        },
+        GestureKey::Key(Key::Str(_)) => return None,
+        GestureKey::Key(k) => k.name().parse().ok()?,
+    };
+
+    let mut modifiers = GlobalShortcutModifiers::empty();
+    if gesture.modifiers.intersects(ModifiersState::SHIFT) {
+        modifiers |= GlobalShortcutModifiers::SHIFT;
+    }
+    if gesture.modifiers.intersects(ModifiersState::CTRL) {
+        modifiers |= GlobalShortcutModifiers::CTRL;
+    }
+    if gesture.modifiers.intersects(ModifiersState::ALT) {
+        modifiers |= GlobalShortcutModifiers::ALT;
+    }
+    if gesture.modifiers.intersects(ModifiersState::LOGO) {
+        modifiers |= GlobalShortcutModifiers::LOGO;
+    }
+
+    Some(GlobalShortcut { modifiers, key_code })
+}
+
+/// Represents a global shortcut registration.
+///
+/// The shortcut is unregistered when all clones of the handle are dropped.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(transparent)]
+#[must_use = "the global shortcut is unregistered if the handle is dropped"]
+pub struct GlobalShortcutHandle(Handle<Option<AppEventSender>>);
+impl GlobalShortcutHandle {
+    /// Create dummy handle that is always in the *released* state.
+    pub fn dummy() -> Self {
+        GlobalShortcutHandle(Handle::dummy(None))
+    }
+
+    /// Drops the handle but does **not** unregister.
+    ///
+    /// The shortcut stays registered for the duration of the app or until another handle calls [`release`](Self::release).
+    pub fn perm(self) {
+        self.0.clone().perm();
+    }
+
+    /// If another handle has called [`perm`](Self::perm).
+    pub fn is_permanent(&self) -> bool {
+        self.0.is_permanent()
+    }
+
+    /// Drops the handle and unregisters the shortcut.
+    pub fn release(self) {
+        self.0.clone().force_drop();
+    }
+
+    /// If another handle has called [`release`](Self::release).
+    ///
+    /// The shortcut is already unregistered or will be in the next app update, this is irreversible.
+    pub fn is_released(&self) -> bool {
+        self.0.is_dropped()
+    }
+
+    /// Create a weak handle.
+    pub fn downgrade(&self) -> WeakGlobalShortcutHandle {
+        WeakGlobalShortcutHandle(self.0.downgrade())
+    }
+}
+
+impl Drop for GlobalShortcutHandle {
+    fn drop(&mut self) {
+        // the service unregisters dropped shortcuts on update
+        if let Some(sender) = self.0.data() {
+            let _ = sender.send_update(UpdateOp::Update, None);
+        }
+    }
+}
+
+/// Weak [`GlobalShortcutHandle`].
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct WeakGlobalShortcutHandle(WeakHandle<Option<AppEventSender>>);
+impl WeakGlobalShortcutHandle {
+    /// New weak handle that does not upgrade.
+    pub fn new() -> Self {
+        Self(WeakHandle::new())
+    }
+
+    /// Get the global shortcut handle if it is still registered.
+    pub fn upgrade(&self) -> Option<GlobalShortcutHandle> {
+        self.0.upgrade().map(GlobalShortcutHandle)
+    }
+}
+
+/// Error registering a global shortcut in [`GLOBAL_SHORTCUTS`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum GlobalShortcutError {
+    /// View-process is not running.
+    ViewProcessOffline,
+    /// Shortcut cannot be converted to a global shortcut.
+    ///
+    /// See [`GLOBAL_SHORTCUTS.register`] for more details.
+    ///
+    /// [`GLOBAL_SHORTCUTS.register`]: GLOBAL_SHORTCUTS::register
+    InvalidShortcut,
+    /// View-process implementer or operating system does not support global shortcuts, or does not support the key.
+    NotSupported,
+    /// The shortcut is already registered by another app.
+    AlreadyRegistered,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for GlobalShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalShortcutError::ViewProcessOffline => write!(f, "view-process is not running"),
+            GlobalShortcutError::InvalidShortcut => write!(f, "shortcut cannot be used as a global shortcut"),
+            GlobalShortcutError::NotSupported => write!(f, "global shortcuts not supported"),
+            GlobalShortcutError::AlreadyRegistered => write!(f, "global shortcut already registered by another app"),
+            GlobalShortcutError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for GlobalShortcutError {}
+impl From<zng_view_api::keyboard::GlobalShortcutError> for GlobalShortcutError {
+    fn from(e: zng_view_api::keyboard::GlobalShortcutError) -> Self {
+        match e {
+            zng_view_api::keyboard::GlobalShortcutError::NotSupported => GlobalShortcutError::NotSupported,
+            zng_view_api::keyboard::GlobalShortcutError::AlreadyRegistered => GlobalShortcutError::AlreadyRegistered,
+            zng_view_api::keyboard::GlobalShortcutError::Other(e) => GlobalShortcutError::Other(e),
+        }
+    }
+}
+
+event_args! {
+    /// Arguments for [`GLOBAL_SHORTCUT_EVENT`].
+    pub struct GlobalShortcutArgs {
+        /// The shortcut registered in [`GLOBAL_SHORTCUTS`] that was pressed.
+        pub shortcut: Shortcut,
+
+        ..
+
+        /// The event is only delivered to subscribers.
+        fn delivery_list(&self, _list: &mut UpdateDeliveryList) {}
+    }
+}
+
+event! {
+    /// A shortcut registered in [`GLOBAL_SHORTCUTS`] was pressed, even if the app is not focused.
+    pub static GLOBAL_SHORTCUT_EVENT: GlobalShortcutArgs;
+}
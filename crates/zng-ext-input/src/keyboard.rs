@@ -4,15 +4,15 @@ use std::{collections::HashSet, time::Duration};
 
 use zng_app::{
     event::{event, event_args},
-    shortcut::ModifiersState,
+    shortcut::{key_labels_var, KeyLabels, ModifiersState},
     update::EventUpdate,
     view_process::{
         raw_device_events::DeviceId,
         raw_events::{
-            RawKeyInputArgs, RAW_ANIMATIONS_CONFIG_CHANGED_EVENT, RAW_KEY_INPUT_EVENT, RAW_KEY_REPEAT_CONFIG_CHANGED_EVENT,
-            RAW_WINDOW_FOCUS_EVENT,
+            RawKeyInputArgs, RAW_ANIMATIONS_CONFIG_CHANGED_EVENT, RAW_KEYBOARD_LAYOUT_CHANGED_EVENT, RAW_KEY_INPUT_EVENT,
+            RAW_KEY_REPEAT_CONFIG_CHANGED_EVENT, RAW_WINDOW_FOCUS_EVENT,
         },
-        VIEW_PROCESS_INITED_EVENT,
+        VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
     },
     widget::{info::InteractionPath, WidgetId},
     window::WindowId,
@@ -101,6 +101,19 @@ event_args! {
             list.search_all()
         }
     }
+
+    /// Arguments for [`KEYBOARD_LAYOUT_CHANGED_EVENT`].
+    pub struct KeyboardLayoutChangedArgs {
+        /// Key labels of the new keyboard layout.
+        pub labels: KeyLabels,
+
+        ..
+
+        /// Broadcast to all.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
 }
 impl KeyInputArgs {
     /// Returns `true` if the widget is enabled in [`target`].
@@ -191,6 +204,18 @@ event! {
     ///
     /// This event is provided by the [`KeyboardManager`] extension.
     pub static MODIFIERS_CHANGED_EVENT: ModifiersChangedArgs;
+
+    /// System keyboard layout changed event.
+    ///
+    /// The [`KEYBOARD.key_labels`] variable is already updated when this event notifies. Note that the view-process
+    /// may not support this event on the current platform, the default view-process only notifies on Windows and Linux.
+    ///
+    /// # Provider
+    ///
+    /// This event is provided by the [`KeyboardManager`] extension.
+    ///
+    /// [`KEYBOARD.key_labels`]: KEYBOARD::key_labels
+    pub static KEYBOARD_LAYOUT_CHANGED_EVENT: KeyboardLayoutChangedArgs;
 }
 
 /// Application extension that provides keyboard events targeting the focused widget.
@@ -203,6 +228,7 @@ event! {
 ///
 /// * [`KEY_INPUT_EVENT`]
 /// * [`MODIFIERS_CHANGED_EVENT`]
+/// * [`KEYBOARD_LAYOUT_CHANGED_EVENT`]
 ///
 /// # Services
 ///
@@ -234,6 +260,10 @@ impl AppExtension for KeyboardManager {
             let kb = KEYBOARD_SV.read();
             kb.caret_animation_config
                 .set((args.config.caret_blink_interval, args.config.caret_blink_timeout));
+        } else if RAW_KEYBOARD_LAYOUT_CHANGED_EVENT.has(update) {
+            let labels = load_key_labels();
+            key_labels_var().set(labels.clone());
+            KEYBOARD_LAYOUT_CHANGED_EVENT.notify(KeyboardLayoutChangedArgs::now(labels));
         } else if let Some(args) = RAW_WINDOW_FOCUS_EVENT.on(update) {
             if args.new_focus.is_none() {
                 let mut kb = KEYBOARD_SV.write();
@@ -244,6 +274,8 @@ impl AppExtension for KeyboardManager {
                 kb.last_key_down = None;
             }
         } else if let Some(args) = VIEW_PROCESS_INITED_EVENT.on(update) {
+            key_labels_var().set(load_key_labels());
+
             let mut kb = KEYBOARD_SV.write();
             kb.repeat_config.set(args.key_repeat_config);
             kb.caret_animation_config.set((
@@ -262,6 +294,15 @@ impl AppExtension for KeyboardManager {
     }
 }
 
+/// Query the current keyboard layout labels for all key codes.
+fn load_key_labels() -> KeyLabels {
+    let codes: Vec<_> = KeyCode::all_identified().filter(|c| !c.is_modifier()).collect();
+    match VIEW_PROCESS.key_labels(codes.clone()) {
+        Ok(labels) => KeyLabels::new(codes.into_iter().zip(labels)),
+        Err(_) => KeyLabels::default(),
+    }
+}
+
 /// Keyboard service.
 ///
 /// # Provider
@@ -327,6 +368,29 @@ impl KEYBOARD {
         KEYBOARD_SV.read().sys_caret_animation_config.read_only()
     }
 
+    /// Returns a read-only variable that tracks the labels of physical keys in the current keyboard layout.
+    ///
+    /// The labels are used to display shortcuts that use [`GestureKey::Code`] with the glyph printed on the user keyboard,
+    /// the variable updates when the system keyboard layout changes, before [`KEYBOARD_LAYOUT_CHANGED_EVENT`] notifies.
+    ///
+    /// Note that the labels are only available if the view-process implements key labels for the current platform,
+    /// the default view-process only implements labels on Windows and Linux, on macOS and other systems the variable
+    /// stays empty and physical keys display the key code name.
+    ///
+    /// [`GestureKey::Code`]: zng_app::shortcut::GestureKey::Code
+    pub fn key_labels(&self) -> ReadOnlyArcVar<KeyLabels> {
+        key_labels_var().read_only()
+    }
+
+    /// Gets the label of the physical key in the current keyboard layout.
+    ///
+    /// Returns `None` if the key does not generate a character or labels are not available, see [`key_labels`] for more details.
+    ///
+    /// [`key_labels`]: Self::key_labels
+    pub fn key_label(&self, code: KeyCode) -> Option<Key> {
+        key_labels_var().with(|l| l.get(code).cloned())
+    }
+
     /// Returns a new read-only variable that animates the caret opacity.
     ///
     /// A new animation must be started after each key press. The value is always 1 or 0, no easing is used by default,
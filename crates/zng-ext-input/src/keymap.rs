@@ -0,0 +1,387 @@
+//! User editable command shortcuts.
+//!
+//! The [`KEYMAP`] service tracks the shortcuts of all commands that are in use, allows runtime rebinding with conflict
+//! detection and exposes the user changes as a serializable [`Keymap`] that can be persisted in the app config.
+
+use std::{collections::BTreeMap, fmt};
+
+use zng_app::{
+    event::{Command, CommandScope, EVENTS},
+    shortcut::{CommandShortcutExt as _, Shortcut, Shortcuts},
+};
+use zng_app_context::app_local;
+use zng_ext_window::WINDOWS;
+use zng_txt::Txt;
+use zng_var::{var, AnyVar as _, ArcVar, Var as _};
+
+app_local! {
+    static KEYMAP_SV: KeymapService = KeymapService {
+        keymap: var(Keymap::default()),
+        defaults: vec![],
+        commands_len: 0,
+    };
+}
+
+struct KeymapService {
+    keymap: ArcVar<Keymap>,
+    /// Shortcuts of commands before the keymap applied.
+    defaults: Vec<(Command, Shortcuts)>,
+    commands_len: usize,
+}
+impl KeymapService {
+    fn default_of(&mut self, cmd: Command) -> Shortcuts {
+        match self.defaults.iter().find(|(c, _)| *c == cmd) {
+            Some((_, s)) => s.clone(),
+            None => {
+                let s = cmd.shortcut().get();
+                self.defaults.push((cmd, s.clone()));
+                s
+            }
+        }
+    }
+
+    fn apply(&mut self) {
+        let keymap = self.keymap.get();
+        for cmd in app_commands() {
+            let default = self.default_of(cmd);
+            let shortcut = keymap.overrides.get(cmd.event().name()).cloned().unwrap_or(default);
+            let var = cmd.shortcut();
+            if var.with(|s| s != &shortcut) {
+                let _ = var.set(shortcut);
+            }
+        }
+    }
+}
+
+/// Gets the app scoped commands in use.
+///
+/// Scoped commands are represented by the app scope, the shortcut metadata set on the app scope applies to all scopes.
+fn app_commands() -> Vec<Command> {
+    let mut r = vec![];
+    for cmd in EVENTS.commands() {
+        let cmd = cmd.scoped(CommandScope::App);
+        if !r.contains(&cmd) {
+            r.push(cmd);
+        }
+    }
+    r
+}
+
+/// Called by the gesture manager on update.
+pub(crate) fn update() {
+    let mut s = KEYMAP_SV.write();
+    let len = EVENTS.commands().len();
+    if s.keymap.is_new() || s.commands_len != len {
+        s.commands_len = len;
+        s.apply();
+    }
+}
+
+/// Command shortcuts keymap service.
+///
+/// This service is provided by the [`GestureManager`].
+///
+/// The keymap is the collection of shortcuts of all commands that had handlers in the app, the [`keymap`] variable
+/// contains the user overrides, it is applied to the [`CommandShortcutExt::shortcut`] of the app scoped commands. The
+/// default shortcuts are the command shortcuts before the first override is applied.
+///
+/// # Persistence
+///
+/// The [`Keymap`] is serializable, bind the [`keymap`] variable to a [`CONFIG`] key to persist the user edits.
+///
+/// ```
+/// # use zng_ext_input::keymap::*;
+/// # use zng_var::*;
+/// # fn demo(cfg: ArcVar<Keymap>) {
+/// // cfg = CONFIG.get("keymap", Keymap::default());
+/// KEYMAP.keymap().set_bind(&cfg).perm();
+/// # }
+/// ```
+///
+/// [`GestureManager`]: crate::gesture::GestureManager
+/// [`keymap`]: Self::keymap
+/// [`CommandShortcutExt::shortcut`]: zng_app::shortcut::CommandShortcutExt::shortcut
+/// [`CONFIG`]: https://zng-ui.github.io/doc/zng/config/struct.CONFIG.html
+pub struct KEYMAP;
+impl KEYMAP {
+    /// User shortcut overrides.
+    ///
+    /// Is empty by default.
+    pub fn keymap(&self) -> ArcVar<Keymap> {
+        KEYMAP_SV.read().keymap.clone()
+    }
+
+    /// Gets all app scoped commands in use and their current and default shortcuts.
+    ///
+    /// The current shortcuts already include the latest [`keymap`] value, even if it was not applied to the commands yet.
+    ///
+    /// [`keymap`]: Self::keymap
+    pub fn entries(&self) -> Vec<KeymapEntry> {
+        let mut s = KEYMAP_SV.write();
+        let keymap = s.keymap.get();
+        app_commands()
+            .into_iter()
+            .map(|cmd| {
+                let default = s.default_of(cmd);
+                KeymapEntry {
+                    command: cmd,
+                    shortcut: keymap.overrides.get(cmd.event().name()).cloned().unwrap_or_else(|| default.clone()),
+                    default,
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the default shortcuts of the command.
+    pub fn default_shortcut(&self, cmd: Command) -> Shortcuts {
+        KEYMAP_SV.write().default_of(cmd.scoped(CommandScope::App))
+    }
+
+    /// Gets other commands that are already bound to the `shortcut` in a context that overlaps the `cmd` context.
+    ///
+    /// The context of a command is the set of scopes that have handlers for it, a command with handlers in the app scope
+    /// or without any handlers overlaps all other commands, window and widget scopes overlap other scopes in the same window.
+    pub fn conflicts(&self, cmd: Command, shortcut: &Shortcut) -> Vec<Command> {
+        let cmd = cmd.scoped(CommandScope::App);
+        let cmd_ctx = CommandContext::new(cmd);
+        self.entries()
+            .into_iter()
+            .filter(|e| e.command != cmd && e.shortcut.contains(shortcut))
+            .filter(|e| cmd_ctx.overlaps(&CommandContext::new(e.command)))
+            .map(|e| e.command)
+            .collect()
+    }
+
+    /// Set the command shortcuts if they do not conflict with other commands.
+    ///
+    /// The override is inserted in the [`keymap`] and applied on the next update.
+    ///
+    /// [`keymap`]: Self::keymap
+    pub fn rebind(&self, cmd: Command, shortcuts: impl Into<Shortcuts>) -> Result<(), KeymapConflict> {
+        let shortcuts = shortcuts.into();
+        for s in shortcuts.iter() {
+            let with = self.conflicts(cmd, s);
+            if !with.is_empty() {
+                return Err(KeymapConflict { shortcut: s.clone(), with });
+            }
+        }
+        self.force_rebind(cmd, shortcuts);
+        Ok(())
+    }
+
+    /// Set the command shortcuts, even if they conflict with other commands.
+    pub fn force_rebind(&self, cmd: Command, shortcuts: impl Into<Shortcuts>) {
+        let cmd = cmd.scoped(CommandScope::App);
+        let shortcuts = shortcuts.into();
+        let is_default = self.default_shortcut(cmd) == shortcuts;
+        let name = Txt::from_static(cmd.event().name());
+        KEYMAP_SV.read().keymap.modify(move |k| {
+            if is_default {
+                if k.overrides.contains_key(&name) {
+                    k.to_mut().overrides.remove(&name);
+                }
+            } else if k.overrides.get(&name) != Some(&shortcuts) {
+                k.to_mut().overrides.insert(name, shortcuts);
+            }
+        });
+    }
+
+    /// Restore the default shortcuts of the command.
+    pub fn reset(&self, cmd: Command) {
+        let name = Txt::from_static(cmd.event().name());
+        KEYMAP_SV.read().keymap.modify(move |k| {
+            if k.overrides.contains_key(&name) {
+                k.to_mut().overrides.remove(&name);
+            }
+        });
+    }
+
+    /// Restore the default shortcuts of all commands.
+    pub fn reset_all(&self) {
+        KEYMAP_SV.read().keymap.set(Keymap::default());
+    }
+}
+
+/// Scopes that have handlers for a command.
+struct CommandContext {
+    is_app: bool,
+    windows: Vec<zng_app::window::WindowId>,
+}
+impl CommandContext {
+    fn new(cmd: Command) -> Self {
+        let mut windows = vec![];
+        cmd.visit_scopes(|c| {
+            if !c.has_handlers_value() {
+                return;
+            }
+            let w = match c.scope() {
+                CommandScope::Window(w) => Some(w),
+                CommandScope::Widget(id) => WINDOWS.widget_info(id).map(|w| w.tree().window_id()),
+                CommandScope::App => None,
+            };
+            if let Some(w) = w {
+                if !windows.contains(&w) {
+                    windows.push(w);
+                }
+            }
+        });
+        let is_app = cmd.has_handlers_value() || windows.is_empty();
+        Self { is_app, windows }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.is_app || other.is_app || self.windows.iter().any(|w| other.windows.contains(w))
+    }
+}
+
+/// Represents a command in the [`KEYMAP`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapEntry {
+    /// The app scoped command.
+    pub command: Command,
+    /// Current shortcuts.
+    pub shortcut: Shortcuts,
+    /// Default shortcuts.
+    pub default: Shortcuts,
+}
+impl KeymapEntry {
+    /// If the current shortcut is not the default.
+    pub fn is_overridden(&self) -> bool {
+        self.shortcut != self.default
+    }
+}
+
+/// User overrides of command shortcuts.
+///
+/// See [`KEYMAP`] for more details.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Keymap {
+    /// Shortcuts by command name.
+    ///
+    /// The key is the name of the command static item, for example `"COPY_CMD"`. An empty shortcuts value removes
+    /// the default shortcuts.
+    pub overrides: BTreeMap<Txt, Shortcuts>,
+}
+
+/// Error returned by [`KEYMAP.rebind`].
+///
+/// [`KEYMAP.rebind`]: KEYMAP::rebind
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeymapConflict {
+    /// The shortcut that is already in use.
+    pub shortcut: Shortcut,
+    /// Commands that are bound to the shortcut in an overlapping context.
+    pub with: Vec<Command>,
+}
+impl fmt::Display for KeymapConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shortcut `{}` is already bound to ", self.shortcut)?;
+        let mut sep = "";
+        for c in &self.with {
+            write!(f, "{sep}`{c:?}`")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for KeymapConflict {}
+
+#[cfg(test)]
+mod tests {
+    use zng_app::{
+        event::command,
+        shortcut::{shortcut, CommandShortcutExt as _},
+        window::WindowId,
+        APP,
+    };
+
+    use super::*;
+    use crate::gesture::GestureManager;
+
+    command! {
+        static FOO_CMD = { shortcut: shortcut!(CTRL+'A') };
+        static BAR_CMD = { shortcut: shortcut!(CTRL+'B') };
+        static BAZ_CMD = { shortcut: shortcut!(CTRL+'C') };
+    }
+
+    #[test]
+    fn conflict_app_scope() {
+        let mut app = APP.minimal().run_headless(false);
+        let _foo = FOO_CMD.subscribe(true);
+        let _bar = BAR_CMD.subscribe(true);
+        // commands register on update
+        let _ = app.update(false);
+
+        assert_eq!(KEYMAP.conflicts(BAR_CMD, &shortcut!(CTRL + 'A')), vec![FOO_CMD]);
+
+        let err = KEYMAP.rebind(BAR_CMD, shortcut!(CTRL + 'A')).unwrap_err();
+        assert_eq!(err.shortcut, shortcut!(CTRL + 'A'));
+        assert_eq!(err.with, vec![FOO_CMD]);
+        assert!(KEYMAP.keymap().get().overrides.is_empty());
+    }
+
+    #[test]
+    fn no_conflict_free_shortcut() {
+        let mut app = APP.minimal().extend(GestureManager::default()).run_headless(false);
+        let _foo = FOO_CMD.subscribe(true);
+        let _bar = BAR_CMD.subscribe(true);
+        let _ = app.update(false);
+
+        assert!(KEYMAP.conflicts(BAR_CMD, &shortcut!(CTRL + 'D')).is_empty());
+        // own shortcut is not a conflict
+        assert!(KEYMAP.conflicts(BAR_CMD, &shortcut!(CTRL + 'B')).is_empty());
+
+        KEYMAP.rebind(BAR_CMD, shortcut!(CTRL + 'D')).unwrap();
+        app.update(false).assert_wait();
+        assert_eq!(BAR_CMD.shortcut().get(), Shortcuts::from(shortcut!(CTRL + 'D')));
+        let keymap = KEYMAP.keymap().get();
+        assert_eq!(keymap.overrides.get("BAR_CMD"), Some(&Shortcuts::from(shortcut!(CTRL + 'D'))));
+    }
+
+    #[test]
+    fn conflict_same_window() {
+        let mut app = APP.minimal().run_headless(false);
+        let win = WindowId::named("conflict_same_window");
+        let _foo = FOO_CMD.scoped(win).subscribe(true);
+        let _bar = BAR_CMD.scoped(win).subscribe(true);
+        let _ = app.update(false);
+
+        assert_eq!(KEYMAP.conflicts(BAR_CMD, &shortcut!(CTRL + 'A')), vec![FOO_CMD]);
+    }
+
+    #[test]
+    fn no_conflict_other_window() {
+        let mut app = APP.minimal().run_headless(false);
+        let _foo = FOO_CMD.scoped(WindowId::named("no_conflict_other_window_a")).subscribe(true);
+        let _bar = BAR_CMD.scoped(WindowId::named("no_conflict_other_window_b")).subscribe(true);
+        let _ = app.update(false);
+
+        assert!(KEYMAP.conflicts(BAR_CMD, &shortcut!(CTRL + 'A')).is_empty());
+        KEYMAP.rebind(BAR_CMD, shortcut!(CTRL + 'A')).unwrap();
+    }
+
+    #[test]
+    fn conflict_app_handler_overlaps_window() {
+        let mut app = APP.minimal().run_headless(false);
+        let _foo = FOO_CMD.subscribe(true);
+        let _baz = BAZ_CMD
+            .scoped(WindowId::named("conflict_app_handler_overlaps_window"))
+            .subscribe(true);
+        let _ = app.update(false);
+
+        assert_eq!(KEYMAP.conflicts(BAZ_CMD, &shortcut!(CTRL + 'A')), vec![FOO_CMD]);
+    }
+
+    #[test]
+    fn rebind_to_default_removes_override() {
+        let mut app = APP.minimal().run_headless(false);
+        let _bar = BAR_CMD.subscribe(true);
+
+        KEYMAP.rebind(BAR_CMD, shortcut!(CTRL + 'D')).unwrap();
+        app.update(false).assert_wait();
+        assert!(KEYMAP.keymap().get().overrides.contains_key("BAR_CMD"));
+        KEYMAP.rebind(BAR_CMD, shortcut!(CTRL + 'B')).unwrap();
+        app.update(false).assert_wait();
+        assert!(!KEYMAP.keymap().get().overrides.contains_key("BAR_CMD"));
+    }
+}
@@ -13,8 +13,11 @@
 extern crate bitflags;
 
 pub mod focus;
+pub mod gamepad;
 pub mod gesture;
+pub mod global_shortcut;
 pub mod keyboard;
+pub mod keymap;
 pub mod mouse;
 pub mod pointer_capture;
 pub mod touch;
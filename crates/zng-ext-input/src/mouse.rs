@@ -847,7 +847,7 @@ impl MouseManager {
             entry.repeat_count = 0;
         }
 
-        let capture_info = POINTER_CAPTURE.current_capture_value();
+        let capture_info = POINTER_CAPTURE.current_capture_value_for(device_id);
 
         let now = INSTANT.now();
         let args = MouseInputArgs::new(
@@ -925,7 +925,7 @@ impl MouseManager {
                 Err(_) => {
                     // window not found
                     if let Some(hovered) = self.hovered.take() {
-                        let capture = POINTER_CAPTURE.current_capture_value();
+                        let capture = POINTER_CAPTURE.current_capture_value_for(device_id);
                         let args = MouseHoverArgs::now(
                             window_id,
                             device_id,
@@ -955,7 +955,7 @@ impl MouseManager {
             }
             .unblocked();
 
-            let capture = POINTER_CAPTURE.current_capture_value();
+            let capture = POINTER_CAPTURE.current_capture_value_for(device_id);
 
             // mouse_enter/mouse_leave.
             let hovered_args = if self.hovered != target {
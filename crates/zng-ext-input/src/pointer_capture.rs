@@ -39,6 +39,7 @@ use zng_view_api::{
 /// Events this extension provides.
 ///
 /// * [`POINTER_CAPTURE_EVENT`]
+/// * [`POINTER_CAPTURE_LOST_EVENT`]
 ///
 /// # Services
 ///
@@ -74,6 +75,7 @@ impl AppExtension for PointerCaptureManager {
                     {
                         self.on_first_down(
                             args.window_id,
+                            args.device_id,
                             self.mouse_position
                                 .get(&(args.window_id, args.device_id))
                                 .copied()
@@ -86,7 +88,7 @@ impl AppExtension for PointerCaptureManager {
                         && self.mouse_down.is_empty()
                         && self.touch_down.is_empty()
                     {
-                        self.on_last_up();
+                        self.on_last_up(CaptureLostCause::Released);
                     }
                 }
             }
@@ -98,7 +100,7 @@ impl AppExtension for PointerCaptureManager {
                             && self.touch_down.len() == 1
                             && self.mouse_down.is_empty()
                         {
-                            self.on_first_down(args.window_id, touch.position);
+                            self.on_first_down(args.window_id, args.device_id, touch.position);
                         }
                     }
                     TouchPhase::End | TouchPhase::Cancel => {
@@ -106,7 +108,7 @@ impl AppExtension for PointerCaptureManager {
                             && self.touch_down.is_empty()
                             && self.mouse_down.is_empty()
                         {
-                            self.on_last_up();
+                            self.on_last_up(CaptureLostCause::Released);
                         }
                     }
                     TouchPhase::Move => {}
@@ -119,16 +121,22 @@ impl AppExtension for PointerCaptureManager {
                 }
             }
         } else if let Some(args) = RAW_WINDOW_CLOSE_EVENT.on(update) {
-            self.remove_window(args.window_id);
+            self.remove_window(args.window_id, CaptureLostCause::WindowClosed);
         } else if let Some(args) = RAW_WINDOW_FOCUS_EVENT.on(update) {
             if let Some(w) = args.prev_focus {
-                self.remove_window(w);
+                let keep = match &self.capture {
+                    Some(c) => c.options.keep_on_focus_loss && c.target.window_id() == w,
+                    None => false,
+                };
+                if !keep {
+                    self.remove_window(w, CaptureLostCause::FocusLost);
+                }
             }
         } else if let Some(args) = VIEW_PROCESS_INITED_EVENT.on(update) {
             if args.is_respawn && (!self.mouse_down.is_empty() || !self.touch_down.is_empty()) {
                 self.mouse_down.clear();
                 self.touch_down.clear();
-                self.on_last_up();
+                self.on_last_up(CaptureLostCause::ViewProcessRespawn);
             }
         }
     }
@@ -136,24 +144,30 @@ impl AppExtension for PointerCaptureManager {
     fn update(&mut self) {
         if let Some(current) = &self.capture {
             let mut cap = POINTER_CAPTURE_SV.write();
-            if let Some((widget_id, mode)) = cap.capture_request.take() {
-                if let Ok(true) = WINDOWS.is_focused(current.target.window_id()) {
+            if let Some((widget_id, mode, options)) = cap.capture_request.take() {
+                if options.keep_on_focus_loss || WINDOWS.is_focused(current.target.window_id()) == Ok(true) {
                     // current window pressed
                     if let Some(widget) = WINDOWS.widget_tree(current.target.window_id()).unwrap().get(widget_id) {
                         // request valid
-                        self.set_capture(&mut cap, widget.interaction_path(), mode);
+                        self.set_capture(&mut cap, widget.interaction_path(), mode, options, CaptureLostCause::Replaced);
                     }
                 }
             } else if mem::take(&mut cap.release_requested) && current.mode != CaptureMode::Window {
                 // release capture (back to default capture).
                 let target = current.target.root_path();
-                self.set_capture(&mut cap, InteractionPath::from_enabled(target.into_owned()), CaptureMode::Window);
+                self.set_capture(
+                    &mut cap,
+                    InteractionPath::from_enabled(target.into_owned()),
+                    CaptureMode::Window,
+                    CaptureOptions::default(),
+                    CaptureLostCause::ReleaseRequested,
+                );
             }
         }
     }
 }
 impl PointerCaptureManager {
-    fn remove_window(&mut self, window_id: WindowId) {
+    fn remove_window(&mut self, window_id: WindowId, cause: CaptureLostCause) {
         self.mouse_position.retain(|(w, _), _| *w != window_id);
 
         if !self.mouse_down.is_empty() || !self.touch_down.is_empty() {
@@ -161,36 +175,44 @@ impl PointerCaptureManager {
             self.touch_down.retain(|(w, _, _)| *w != window_id);
 
             if self.mouse_down.is_empty() && self.touch_down.is_empty() {
-                self.on_last_up();
+                self.on_last_up(cause);
             }
         }
     }
 
-    fn on_first_down(&mut self, window_id: WindowId, point: DipPoint) {
+    fn on_first_down(&mut self, window_id: WindowId, device_id: DeviceId, point: DipPoint) {
         if let Ok(info) = WINDOWS.widget_tree(window_id) {
             let mut cap = POINTER_CAPTURE_SV.write();
             cap.release_requested = false;
 
-            if let Some((widget_id, mode)) = cap.capture_request.take() {
-                if let Some(w_info) = info.get(widget_id) {
-                    let point = point.to_px(info.scale_factor());
-                    if w_info.hit_test(point).contains(widget_id) {
-                        // capture for widget
-                        self.set_capture(&mut cap, w_info.interaction_path(), mode);
-                        return;
+            if let Some((widget_id, mode, options)) = cap.capture_request.take() {
+                if options.device.map(|d| d == device_id).unwrap_or(true) {
+                    if let Some(w_info) = info.get(widget_id) {
+                        let point = point.to_px(info.scale_factor());
+                        if w_info.hit_test(point).contains(widget_id) {
+                            // capture for widget
+                            self.set_capture(&mut cap, w_info.interaction_path(), mode, options, CaptureLostCause::Replaced);
+                            return;
+                        }
                     }
                 }
             }
             // default capture
-            self.set_capture(&mut cap, info.root().interaction_path(), CaptureMode::Window);
+            self.set_capture(
+                &mut cap,
+                info.root().interaction_path(),
+                CaptureMode::Window,
+                CaptureOptions::default(),
+                CaptureLostCause::Replaced,
+            );
         }
     }
 
-    fn on_last_up(&mut self) {
+    fn on_last_up(&mut self, cause: CaptureLostCause) {
         let mut cap = POINTER_CAPTURE_SV.write();
         cap.release_requested = false;
         cap.capture_request = None;
-        self.unset_capture(&mut cap);
+        self.unset_capture(&mut cap, cause);
     }
 
     fn continue_capture(&mut self, info: &WidgetInfoTree) {
@@ -200,18 +222,32 @@ impl PointerCaptureManager {
             if let Some(new_path) = widget.new_interaction_path(&InteractionPath::from_enabled(current.target.clone())) {
                 // widget moved inside window tree.
                 let mode = current.mode;
-                self.set_capture(&mut POINTER_CAPTURE_SV.write(), new_path, mode);
+                let options = current.options;
+                self.set_capture(&mut POINTER_CAPTURE_SV.write(), new_path, mode, options, CaptureLostCause::Disabled);
             }
         } else {
             // widget not found. Returns to default capture.
-            self.set_capture(&mut POINTER_CAPTURE_SV.write(), info.root().interaction_path(), CaptureMode::Window);
+            self.set_capture(
+                &mut POINTER_CAPTURE_SV.write(),
+                info.root().interaction_path(),
+                CaptureMode::Window,
+                CaptureOptions::default(),
+                CaptureLostCause::WidgetRemoved,
+            );
         }
     }
 
-    fn set_capture(&mut self, cap: &mut PointerCaptureService, target: InteractionPath, mode: CaptureMode) {
-        let new = target.enabled().map(|target| CaptureInfo { target, mode });
+    fn set_capture(
+        &mut self,
+        cap: &mut PointerCaptureService,
+        target: InteractionPath,
+        mode: CaptureMode,
+        options: CaptureOptions,
+        cause: CaptureLostCause,
+    ) {
+        let new = target.enabled().map(|target| CaptureInfo { target, mode, options });
         if new.is_none() {
-            self.unset_capture(cap);
+            self.unset_capture(cap, CaptureLostCause::Disabled);
             return;
         }
         if new != self.capture {
@@ -219,18 +255,35 @@ impl PointerCaptureManager {
             self.capture.clone_from(&new);
             cap.capture_value.clone_from(&new);
             cap.capture.set(new.clone());
+            Self::notify_lost(&prev, &new, cause);
             POINTER_CAPTURE_EVENT.notify(PointerCaptureArgs::now(prev, new));
         }
     }
 
-    fn unset_capture(&mut self, cap: &mut PointerCaptureService) {
+    fn unset_capture(&mut self, cap: &mut PointerCaptureService, cause: CaptureLostCause) {
         if self.capture.is_some() {
             let prev = self.capture.take();
             cap.capture_value = None;
             cap.capture.set(None);
+            Self::notify_lost(&prev, &None, cause);
             POINTER_CAPTURE_EVENT.notify(PointerCaptureArgs::now(prev, None));
         }
     }
+
+    fn notify_lost(prev: &Option<CaptureInfo>, new: &Option<CaptureInfo>, cause: CaptureLostCause) {
+        if let Some(prev) = prev {
+            if prev.mode == CaptureMode::Window {
+                return;
+            }
+            let lost = match new {
+                Some(new) => new.target.widget_id() != prev.target.widget_id() || new.mode == CaptureMode::Window,
+                None => true,
+            };
+            if lost {
+                POINTER_CAPTURE_LOST_EVENT.notify(PointerCaptureLostArgs::now(prev.clone(), cause));
+            }
+        }
+    }
 }
 
 /// Mouse and touch capture service.
@@ -243,6 +296,9 @@ impl PointerCaptureManager {
 /// The capture will last for as long as any of the mouse buttons or touch contacts are pressed, the widget is visible
 /// and the window is focused.
 ///
+/// Use [`capture`](POINTER_CAPTURE::capture) with [`CaptureOptions`] to only redirect events from a specific device or to
+/// keep the capture when the window loses focus, the [`POINTER_CAPTURE_LOST_EVENT`] notifies when and why a widget capture ended.
+///
 /// Windows capture by default, this cannot be disabled. For other widgets this is optional.
 ///
 /// # Provider
@@ -260,9 +316,7 @@ impl POINTER_CAPTURE {
     ///
     /// The capture will be set only if the widget is pressed.
     pub fn capture_widget(&self, widget_id: WidgetId) {
-        let mut m = POINTER_CAPTURE_SV.write();
-        m.capture_request = Some((widget_id, CaptureMode::Widget));
-        UPDATES.update(None);
+        self.capture(widget_id, CaptureMode::Widget, CaptureOptions::default());
     }
 
     /// Set a widget to be the root of a capture subtree.
@@ -272,8 +326,44 @@ impl POINTER_CAPTURE {
     ///
     /// The capture will be set only if the widget is pressed.
     pub fn capture_subtree(&self, widget_id: WidgetId) {
+        self.capture(widget_id, CaptureMode::Subtree, CaptureOptions::default());
+    }
+
+    /// Set a widget to redirect all mouse and touch events to, even if the window loses focus.
+    ///
+    /// This is the capture mode used by drag interactions like sliders, the capture survives window drags and focus moves,
+    /// it is only released when all mouse buttons and touch contacts are released, the widget is removed or disabled, the window
+    /// closes or [`release_capture`] is called.
+    ///
+    /// The capture will be set only if the widget is pressed.
+    ///
+    /// [`release_capture`]: Self::release_capture
+    pub fn capture_exclusive(&self, widget_id: WidgetId) {
+        self.capture(
+            widget_id,
+            CaptureMode::Widget,
+            CaptureOptions {
+                device: None,
+                keep_on_focus_loss: true,
+            },
+        );
+    }
+
+    /// Request capture for the widget with custom `options`.
+    ///
+    /// If `mode` is [`CaptureMode::Window`] this is the same as [`release_capture`].
+    ///
+    /// The capture will be set only if the widget is pressed, if [`CaptureOptions::device`] is set the widget must
+    /// be pressed by that device.
+    ///
+    /// [`release_capture`]: Self::release_capture
+    pub fn capture(&self, widget_id: WidgetId, mode: CaptureMode, options: CaptureOptions) {
+        if let CaptureMode::Window = mode {
+            self.release_capture();
+            return;
+        }
         let mut m = POINTER_CAPTURE_SV.write();
-        m.capture_request = Some((widget_id, CaptureMode::Subtree));
+        m.capture_request = Some((widget_id, mode, options));
         UPDATES.update(None);
     }
 
@@ -291,6 +381,11 @@ impl POINTER_CAPTURE {
     pub(crate) fn current_capture_value(&self) -> Option<CaptureInfo> {
         POINTER_CAPTURE_SV.read().capture_value.clone()
     }
+
+    /// Latest capture that applies to events from the `device_id`.
+    pub(crate) fn current_capture_value_for(&self, device_id: DeviceId) -> Option<CaptureInfo> {
+        self.current_capture_value().filter(|c| c.allows_device(device_id))
+    }
 }
 
 /// Mouse and touch capture mode.
@@ -347,6 +442,10 @@ pub struct CaptureInfo {
     pub target: WidgetPath,
     /// Capture mode, see [`allows`](Self::allows) for more details.
     pub mode: CaptureMode,
+    /// Capture options.
+    ///
+    /// Is always the default for capture mode `Window`.
+    pub options: CaptureOptions,
 }
 impl CaptureInfo {
     /// If the widget is allowed by the current capture.
@@ -378,6 +477,57 @@ impl CaptureInfo {
             }
         }
     }
+
+    /// If events from the device are redirected by this capture.
+    ///
+    /// Events from other devices are not captured, see [`CaptureOptions::device`] for more details.
+    pub fn allows_device(&self, device_id: DeviceId) -> bool {
+        self.options.device.map(|d| d == device_id).unwrap_or(true)
+    }
+}
+
+/// Options for [`POINTER_CAPTURE.capture`].
+///
+/// [`POINTER_CAPTURE.capture`]: POINTER_CAPTURE::capture
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureOptions {
+    /// Only capture events from this mouse or touch device.
+    ///
+    /// If set the widget must be pressed by the device to get the capture, events from other devices target
+    /// the widgets normally, without any capture.
+    ///
+    /// Is `None` by default, all devices are captured.
+    pub device: Option<DeviceId>,
+    /// Keep the capture when the window loses focus.
+    ///
+    /// By default the capture is released when the window loses focus, if this is `true` the capture survives window drags
+    /// and focus moves, this is required for drag interactions that can move the pointer across windows.
+    pub keep_on_focus_loss: bool,
+}
+
+/// Reason a widget lost pointer capture.
+///
+/// See [`PointerCaptureLostArgs`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureLostCause {
+    /// All mouse buttons and touch contacts were released.
+    Released,
+    /// The [`POINTER_CAPTURE.release_capture`] was called.
+    ///
+    /// [`POINTER_CAPTURE.release_capture`]: POINTER_CAPTURE::release_capture
+    ReleaseRequested,
+    /// Another widget captured the pointer.
+    Replaced,
+    /// The window lost focus.
+    FocusLost,
+    /// The window closed.
+    WindowClosed,
+    /// The widget was removed from the window.
+    WidgetRemoved,
+    /// The widget or an ancestor was disabled.
+    Disabled,
+    /// The view-process crashed and respawned, all pressed state was reset.
+    ViewProcessRespawn,
 }
 
 app_local! {
@@ -392,13 +542,18 @@ app_local! {
 struct PointerCaptureService {
     capture_value: Option<CaptureInfo>,
     capture: ArcVar<Option<CaptureInfo>>,
-    capture_request: Option<(WidgetId, CaptureMode)>,
+    capture_request: Option<(WidgetId, CaptureMode, CaptureOptions)>,
     release_requested: bool,
 }
 
 event! {
     /// Mouse and touch capture changed event.
     pub static POINTER_CAPTURE_EVENT: PointerCaptureArgs;
+
+    /// Widget mouse and touch capture lost event.
+    ///
+    /// This event notifies before the [`POINTER_CAPTURE_EVENT`] when a `Widget` or `Subtree` capture ends.
+    pub static POINTER_CAPTURE_LOST_EVENT: PointerCaptureLostArgs;
 }
 
 event_args! {
@@ -424,6 +579,23 @@ event_args! {
             }
         }
     }
+
+    /// [`POINTER_CAPTURE_LOST_EVENT`] arguments.
+    pub struct PointerCaptureLostArgs {
+        /// The capture that was lost.
+        pub capture: CaptureInfo,
+        /// Why the capture was lost.
+        pub cause: CaptureLostCause,
+
+        ..
+
+        /// The [`capture`] target path.
+        ///
+        /// [`capture`]: Self::capture
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.capture.target);
+        }
+    }
 }
 
 impl PointerCaptureArgs {
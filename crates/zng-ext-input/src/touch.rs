@@ -1281,7 +1281,7 @@ impl TouchManager {
                 None => return, // entire window blocked
             };
 
-            let capture_info = POINTER_CAPTURE.current_capture_value();
+            let capture_info = POINTER_CAPTURE.current_capture_value_for(args.device_id);
 
             let (gesture_handle, velocity) = match update.phase {
                 TouchPhase::Start => {
@@ -1454,7 +1454,7 @@ impl TouchManager {
                     }
                 }
 
-                let capture_info = POINTER_CAPTURE.current_capture_value();
+                let capture_info = POINTER_CAPTURE.current_capture_value_for(args.device_id);
 
                 let mut touched_events = vec![];
 
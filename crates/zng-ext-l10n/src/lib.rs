@@ -81,6 +81,35 @@ impl AppExtension for L10nManager {
 /// let msg = l10n!("file/id.attribute", "Hello {$name}!");
 /// ```
 ///
+/// ## Arguments
+///
+/// The arguments can be text, numbers, `bool` or variables of these types, the message updates when any argument variable updates.
+/// Number arguments select the plural category using the rules of the message language, the `NUMBER` function is also available
+/// to select ordinal categories and format numbers.
+///
+/// ```
+/// # use zng_ext_l10n::*;
+/// # use zng_var::*;
+/// # let _scope = zng_app::APP.minimal();
+/// let n = var(1u32);
+/// let msg = l10n!("items", "{$n ->
+///     [one] One item
+///    *[other] {$n} items
+/// }");
+/// let n = var(1u32);
+/// let msg = l10n!("rank", "{NUMBER($n, type: \"ordinal\") ->
+///     [one] {$n}st
+///     [two] {$n}nd
+///     [few] {$n}rd
+///    *[other] {$n}th
+/// }");
+/// let is_new = true;
+/// let msg = l10n!("status", "{$is_new ->
+///     [true] New
+///    *[false] Saved
+/// }");
+/// ```
+///
 /// ## Key
 ///
 /// This message key can be just a Fluent identifier, `"id"`, a Fluent attribute identifier can be added `"id.attr"`, and finally
@@ -208,6 +237,64 @@ impl L10N {
         self.load(L10nDir::open(dir))
     }
 
+    /// Load the localization files from a tar archive in memory.
+    ///
+    /// The archive files must follow the same layout of [`load_dir`], `"{lang}.ftl"` and `"{lang}/*.ftl"` files.
+    ///
+    /// [`load_dir`]: Self::load_dir
+    pub fn load_tar(&self, data: impl AsRef<[u8]>) {
+        self.load(L10nTar::load(data))
+    }
+
+    /// Add a language pack `source` that is used together with the [`load`] source.
+    ///
+    /// Packs with greater `priority` are used first, the [`load`] source has priority `0` and is overridden by packs of the
+    /// same priority, packs of the same priority loaded later override packs loaded earlier. Resources are selected by file,
+    /// if a pack provides a lang file it replaces the file of lower priority sources, otherwise the next source is used.
+    ///
+    /// If a pack with the same `name` is already loaded it is replaced. All active variables and handles will update to
+    /// use the new pack, the [`packs`] variable also updates.
+    ///
+    /// [`load`]: Self::load
+    /// [`packs`]: Self::packs
+    pub fn load_pack(&self, name: impl Into<Txt>, priority: i32, source: impl L10nSource) {
+        L10N_SV.write().load_pack(name.into(), priority, source);
+    }
+
+    /// Add a language pack from a directory.
+    ///
+    /// See [`load_pack`] and [`load_dir`] for more details.
+    ///
+    /// [`load_pack`]: Self::load_pack
+    /// [`load_dir`]: Self::load_dir
+    pub fn load_pack_dir(&self, name: impl Into<Txt>, priority: i32, dir: impl Into<PathBuf>) {
+        self.load_pack(name, priority, L10nDir::open(dir))
+    }
+
+    /// Add a language pack from a tar archive in memory.
+    ///
+    /// See [`load_pack`] and [`load_tar`] for more details.
+    ///
+    /// [`load_pack`]: Self::load_pack
+    /// [`load_tar`]: Self::load_tar
+    pub fn load_pack_tar(&self, name: impl Into<Txt>, priority: i32, data: impl AsRef<[u8]>) {
+        self.load_pack(name, priority, L10nTar::load(data))
+    }
+
+    /// Remove the language pack.
+    ///
+    /// All active variables and handles will update to use the remaining sources.
+    pub fn unload_pack(&self, name: &str) {
+        L10N_SV.write().unload_pack(name);
+    }
+
+    /// Gets a read-only variable that lists the loaded language pack names, in priority order.
+    ///
+    /// The variable updates when packs are loaded or unloaded.
+    pub fn packs(&self) -> ReadOnlyArcVar<Vec<Txt>> {
+        L10N_SV.read().packs()
+    }
+
     /// Available localization files.
     ///
     /// The value maps lang to one or more files, the files can be `{dir}/{lang}.flt` or `{dir}/{lang}/*.flt`.
@@ -267,6 +354,68 @@ impl L10N {
         L10N_SV.read().sys_lang()
     }
 
+    /// Gets a read-write variable that enables pseudo-localization of all messages.
+    ///
+    /// When enabled all message text, including fallback messages, is transformed to accented and expanded characters,
+    /// the format arguments are not transformed. This helps identify text that is not localized and layouts that
+    /// do not accommodate longer translations.
+    ///
+    /// Is `false` by default.
+    pub fn pseudo(&self) -> ArcVar<bool> {
+        L10N_SV.read().pseudo()
+    }
+
+    /// Enable or disable [`pseudo`] localization.
+    ///
+    /// [`pseudo`]: Self::pseudo
+    pub fn set_pseudo(&self, enabled: bool) {
+        self.pseudo().set(enabled);
+    }
+
+    /// Gets a read-write variable that enables collection of [`missing_keys`].
+    ///
+    /// Is `false` by default.
+    ///
+    /// [`missing_keys`]: Self::missing_keys
+    pub fn collect_missing(&self) -> ArcVar<bool> {
+        L10N_SV.read().collect_missing()
+    }
+
+    /// Enable or disable [`missing_keys`] collection.
+    ///
+    /// [`missing_keys`]: Self::missing_keys
+    pub fn set_collect_missing(&self, enabled: bool) {
+        self.collect_missing().set(enabled);
+    }
+
+    /// Gets a read-only variable that lists the message keys that were requested but not found in the loaded lang resources.
+    ///
+    /// Keys are only collected when [`collect_missing`] is enabled and the lang resource file is loaded, messages
+    /// that are not found because the resource is loading or does not exist are not collected.
+    ///
+    /// [`collect_missing`]: Self::collect_missing
+    pub fn missing_keys(&self) -> ReadOnlyArcVar<Vec<L10nMissingKey>> {
+        L10N_SV.read().missing_keys().read_only()
+    }
+
+    /// Clear the [`missing_keys`] list.
+    ///
+    /// [`missing_keys`]: Self::missing_keys
+    pub fn clear_missing_keys(&self) {
+        L10N_SV.read().missing_keys().set(vec![]);
+    }
+
+    /// Write a report of the current [`missing_keys`] to `file`.
+    ///
+    /// The report is plain text, one key per line, grouped by lang.
+    ///
+    /// [`missing_keys`]: Self::missing_keys
+    pub async fn save_missing_keys(&self, file: impl Into<PathBuf>) -> std::io::Result<()> {
+        let file = file.into();
+        let report = missing_keys_report(&self.missing_keys().get());
+        task::wait(move || std::fs::write(file, report.as_bytes())).await
+    }
+
     /// Gets a variable that is a localized message in the localization context
     /// where the variable is first used. The variable will update when the contextual language changes.
     ///
@@ -403,6 +552,53 @@ impl L10N {
     }
 }
 
+///<span data-del-macro-root></span> Declares message arguments for [`L10nMessageBuilder::args`] and [`L10N.localized_message`].
+///
+/// # Syntax
+///
+/// Macro expects named arguments `arg = <arg>,..`, the argument values can be anything that the [`l10n!`] macro accepts,
+/// text, numbers, `bool` or variables of these types. The localized message variable updates when any argument variable
+/// updates.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_ext_l10n::*;
+/// # use zng_var::*;
+/// # let _scope = zng_app::APP.minimal();
+/// let count = var(3u32);
+/// let msg = L10N
+///     .message("", "items", "", "{$n -> [one] One {$kind} *[other] {$n} {$kind}s}")
+///     .args(l10n_args!(n = count.clone(), kind = "item"))
+///     .build();
+///
+/// let msg = L10N.localized_message(lang!(en), "", "items", "", "{$n} items", l10n_args!(n = 5));
+/// ```
+///
+/// [`L10N.localized_message`]: L10N::localized_message
+#[macro_export]
+macro_rules! l10n_args {
+    ($($arg:ident = $arg_expr:expr),* $(,)?) => {
+        {
+            let args: std::vec::Vec<_> = std::vec![$(
+                $crate::__l10n_arg(std::stringify!($arg), {
+                    use $crate::IntoL10nVar;
+                    (&mut &mut $crate::L10nSpecialize(Some($arg_expr))).to_l10n_var()
+                })
+            ),*];
+            args
+        }
+    };
+    ($($error:tt)*) => {
+        std::compile_error!(r#"expected (arg = expr, ..)"#)
+    }
+}
+
+#[doc(hidden)]
+pub fn __l10n_arg(name: &'static str, value: impl Var<L10nArgument>) -> (Txt, BoxedVar<L10nArgument>) {
+    (Txt::from_static(name), value.boxed())
+}
+
 /// <span data-del-macro-root></span> Compile-time validated [`Lang`] value.
 ///
 /// The language is parsed during compile and any errors are emitted as compile time errors.
@@ -455,6 +651,25 @@ pub trait L10nSource: Send + 'static {
     fn lang_resource_status(&mut self, lang: Lang, file: Txt) -> BoxedVar<LangResourceStatus>;
 }
 
+fn missing_keys_report(keys: &[L10nMissingKey]) -> Txt {
+    let mut keys: Vec<_> = keys.iter().collect();
+    keys.sort_by(|a, b| a.lang.cmp(&b.lang).then_with(|| a.to_string().cmp(&b.to_string())));
+
+    let mut r = String::new();
+    let mut lang = None;
+    for k in keys {
+        if lang != Some(&k.lang) {
+            if lang.is_some() {
+                r.push('\n');
+            }
+            r.push_str(&format!("# {}\n", k.lang));
+            lang = Some(&k.lang);
+        }
+        r.push_str(&format!("{k}\n"));
+    }
+    Txt::from_str(&r)
+}
+
 fn from_unic_char_direction(d: unic_langid::CharacterDirection) -> LayoutDirection {
     match d {
         unic_langid::CharacterDirection::LTR => LayoutDirection::LTR,
@@ -10,35 +10,91 @@ use std::{
 use parking_lot::Mutex;
 use zng_app_context::app_local;
 use zng_txt::Txt;
-use zng_var::{
-    merge_var, types::ArcCowVar, var, ArcEq, ArcVar, BoxedVar, BoxedWeakVar, LocalVar, MergeVarBuilder, ReadOnlyArcVar, Var, WeakVar,
-};
+use zng_var::{merge_var, types::ArcCowVar, var, ArcEq, ArcVar, BoxedVar, BoxedWeakVar, MergeVarBuilder, ReadOnlyArcVar, Var, WeakVar};
 use zng_view_api::config::LocaleConfig;
 
-use crate::{FluentParserErrors, L10nArgument, L10nSource, Lang, LangMap, LangResource, LangResourceStatus, Langs, SwapL10nSource};
+use crate::{
+    sources::{SharedL10nSource, StackL10nSource},
+    FluentParserErrors, L10nArgument, L10nMissingKey, L10nSource, Lang, LangMap, LangResource, LangResourceStatus, Langs, NilL10nSource,
+    SwapL10nSource,
+};
 
 pub(super) struct L10nService {
     source: Mutex<SwapL10nSource>, // Mutex for `Sync` only.
+    base_source: SharedL10nSource,
+    // sorted by priority, greater first.
+    packs: Vec<(Txt, i32, SharedL10nSource)>,
+    packs_var: ArcVar<Vec<Txt>>,
     sys_lang: ArcVar<Langs>,
     app_lang: ArcCowVar<Langs, ArcVar<Langs>>,
 
     perm_res: Vec<BoxedVar<Option<ArcEq<fluent::FluentResource>>>>,
     bundles: HashMap<(Langs, Txt), BoxedWeakVar<ArcFluentBundle>>,
+
+    pseudo: ArcVar<bool>,
+    missing: MissingKeys,
 }
 impl L10nService {
     pub fn new() -> Self {
         let sys_lang = var(Langs::default());
         Self {
             source: Mutex::new(SwapL10nSource::new()),
+            base_source: SharedL10nSource::new(NilL10nSource),
+            packs: vec![],
+            packs_var: var(vec![]),
             app_lang: sys_lang.cow(),
             sys_lang,
             perm_res: vec![],
             bundles: HashMap::new(),
+            pseudo: var(false),
+            missing: MissingKeys {
+                enabled: var(false),
+                keys: var(vec![]),
+            },
         }
     }
 
     pub fn load(&mut self, source: impl L10nSource) {
-        self.source.get_mut().load(source);
+        self.base_source = SharedL10nSource::new(source);
+        self.update_source();
+    }
+
+    pub fn load_pack(&mut self, name: Txt, priority: i32, source: impl L10nSource) {
+        self.packs.retain(|(n, _, _)| n != &name);
+        // same priority packs loaded later are used first.
+        let i = self.packs.iter().position(|(_, p, _)| *p <= priority).unwrap_or(self.packs.len());
+        self.packs.insert(i, (name, priority, SharedL10nSource::new(source)));
+        self.update_source();
+    }
+
+    pub fn unload_pack(&mut self, name: &str) {
+        let len = self.packs.len();
+        self.packs.retain(|(n, _, _)| n != name);
+        if len != self.packs.len() {
+            self.update_source();
+        }
+    }
+
+    pub fn packs(&self) -> ReadOnlyArcVar<Vec<Txt>> {
+        self.packs_var.read_only()
+    }
+
+    fn update_source(&mut self) {
+        // base source has priority `0`, packs of same priority override it.
+        let mut sources = Vec::with_capacity(self.packs.len() + 1);
+        let mut base = Some(self.base_source.clone());
+        for (_, priority, source) in &self.packs {
+            if *priority < 0 {
+                if let Some(b) = base.take() {
+                    sources.push(b);
+                }
+            }
+            sources.push(source.clone());
+        }
+        sources.extend(base);
+        self.source.get_mut().load(StackL10nSource(sources));
+
+        self.packs_var.set(self.packs.iter().map(|(n, _, _)| n.clone()).collect::<Vec<_>>());
     }
 
     pub fn available_langs(&mut self) -> BoxedVar<Arc<LangMap<HashMap<Txt, PathBuf>>>> {
@@ -57,6 +113,18 @@ impl L10nService {
         self.app_lang.clone()
     }
 
+    pub fn pseudo(&self) -> ArcVar<bool> {
+        self.pseudo.clone()
+    }
+
+    pub fn collect_missing(&self) -> ArcVar<bool> {
+        self.missing.enabled.clone()
+    }
+
+    pub fn missing_keys(&self) -> ArcVar<Vec<L10nMissingKey>> {
+        self.missing.keys.clone()
+    }
+
     pub fn localized_message(
         &mut self,
         langs: Langs,
@@ -69,19 +137,27 @@ impl L10nService {
         if langs.is_empty() {
             return if args.is_empty() {
                 // no lang, no args
-                LocalVar(fallback).boxed()
-            } else {
-                // no lang, but args can change
-                fluent_args_var(args)
-                    .map(move |args| {
-                        let args = args.lock();
-                        format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&*args))
+                self.pseudo
+                    .map(move |&p| {
+                        if p {
+                            format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, None, true)
+                        } else {
+                            fallback.clone()
+                        }
                     })
                     .boxed()
+            } else {
+                // no lang, but args can change
+                merge_var!(fluent_args_var(args), self.pseudo.clone(), move |args, &p| {
+                    let args = args.lock();
+                    format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&*args), p)
+                })
+                .boxed()
             };
         }
 
         let bundle = self.resource_bundle(langs, file.clone());
+        let missing = self.missing.clone();
 
         if args.is_empty() {
             // no args, but message can change
@@ -107,7 +183,12 @@ impl L10nService {
                             return Txt::from_str(r.as_ref());
                         }
                     }
-                    fallback.clone()
+                    missing.record(b, &file, &id, &attribute);
+                    if b.pseudo {
+                        format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, None, true)
+                    } else {
+                        fallback.clone()
+                    }
                 })
                 .boxed()
         } else if args.len() == 1 {
@@ -141,7 +222,8 @@ impl L10nService {
                     }
                 }
 
-                format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&args))
+                missing.record(b, &file, &id, &attribute);
+                format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&args), b.pseudo)
             })
             .boxed()
         } else {
@@ -171,8 +253,9 @@ impl L10nService {
                     }
                 }
 
+                missing.record(b, &file, &id, &attribute);
                 let args = args.lock();
-                format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&*args))
+                format_fallback(file.as_str(), id.as_str(), attribute.as_str(), &fallback, Some(&*args), b.pseudo)
             })
             .boxed()
         }
@@ -185,48 +268,54 @@ impl L10nService {
                     return r;
                 }
                 let (langs, file) = e.key();
-                let r = Self::new_resource_bundle(self.source.get_mut(), langs, file);
+                let r = Self::new_resource_bundle(self.source.get_mut(), &self.pseudo, langs, file);
                 e.insert(r.downgrade());
                 r
             }
             hash_map::Entry::Vacant(e) => {
                 let (langs, file) = e.key();
-                let r = Self::new_resource_bundle(self.source.get_mut(), langs, file);
+                let r = Self::new_resource_bundle(self.source.get_mut(), &self.pseudo, langs, file);
                 e.insert(r.downgrade());
                 r
             }
         }
     }
-    fn new_resource_bundle(source: &mut SwapL10nSource, langs: &Langs, file: &Txt) -> BoxedVar<ArcFluentBundle> {
-        if langs.len() == 1 {
-            let lang = langs[0].clone();
-            let res = source.lang_resource(lang.clone(), file.clone());
-            res.map(move |r| {
-                let mut bundle = ConcurrentFluentBundle::new_concurrent(vec![lang.0.clone()]);
-                if let Some(r) = r {
-                    bundle.add_resource_overriding(r.0.clone());
-                }
-                ArcFluentBundle(Arc::new(bundle))
-            })
-            .boxed()
+    fn new_resource_bundle(source: &mut SwapL10nSource, pseudo: &ArcVar<bool>, langs: &Langs, file: &Txt) -> BoxedVar<ArcFluentBundle> {
+        // resources in override order, least preferred first.
+        let res = if langs.len() == 1 {
+            source
+                .lang_resource(langs[0].clone(), file.clone())
+                .map(|r| vec![r.clone()])
+                .boxed()
         } else {
             debug_assert!(langs.len() > 1);
 
-            let langs = langs.0.clone();
-
             let mut res = MergeVarBuilder::new();
             for l in langs.iter().rev() {
                 res.push(source.lang_resource(l.clone(), file.clone()));
             }
-            res.build(move |res| {
-                let mut bundle = ConcurrentFluentBundle::new_concurrent(langs.iter().map(|l| l.0.clone()).collect());
-                for r in res.iter().flatten() {
-                    bundle.add_resource_overriding(r.0.clone());
-                }
-                ArcFluentBundle(Arc::new(bundle))
-            })
-            .boxed()
-        }
+            res.build(|res| res.iter().cloned().collect::<Vec<_>>()).boxed()
+        };
+
+        let langs = langs.0.clone();
+        merge_var!(res, pseudo.clone(), move |res, &pseudo| {
+            let mut bundle = ConcurrentFluentBundle::new_concurrent(langs.iter().map(|l| l.0.clone()).collect());
+            add_number_fn(&mut bundle, langs.first().map(|l| l.0.clone()).unwrap_or_default());
+            if pseudo {
+                bundle.set_transform(Some(pseudo_transform));
+            }
+            let mut has_resources = false;
+            for r in res.iter().flatten() {
+                bundle.add_resource_overriding(r.0.clone());
+                has_resources = true;
+            }
+            ArcFluentBundle {
+                bundle: Arc::new(bundle),
+                has_resources,
+                pseudo,
+            }
+        })
+        .boxed()
     }
 
     pub fn lang_resource(&mut self, lang: Lang, file: Txt) -> LangResource {
@@ -265,7 +354,12 @@ app_local! {
 type ConcurrentFluentBundle = fluent::bundle::FluentBundle<Arc<fluent::FluentResource>, intl_memoizer::concurrent::IntlLangMemoizer>;
 
 #[derive(Clone)]
-struct ArcFluentBundle(Arc<ConcurrentFluentBundle>);
+struct ArcFluentBundle {
+    bundle: Arc<ConcurrentFluentBundle>,
+    // if any lang resource is loaded.
+    has_resources: bool,
+    pseudo: bool,
+}
 impl fmt::Debug for ArcFluentBundle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ArcFluentBundle")
@@ -273,14 +367,44 @@ impl fmt::Debug for ArcFluentBundle {
 }
 impl PartialEq for ArcFluentBundle {
     fn eq(&self, other: &Self) -> bool {
-        Arc::ptr_eq(&self.0, &other.0)
+        Arc::ptr_eq(&self.bundle, &other.bundle)
     }
 }
 impl ops::Deref for ArcFluentBundle {
     type Target = ConcurrentFluentBundle;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.bundle
+    }
+}
+
+fn pseudo_transform(s: &str) -> Cow<str> {
+    fluent_pseudo::transform(s, false, true)
+}
+
+#[derive(Clone)]
+struct MissingKeys {
+    enabled: ArcVar<bool>,
+    keys: ArcVar<Vec<L10nMissingKey>>,
+}
+impl MissingKeys {
+    /// Record the key if collecting and the message is not found in a loaded bundle.
+    fn record(&self, bundle: &ArcFluentBundle, file: &Txt, id: &Txt, attribute: &Txt) {
+        if !bundle.has_resources || !self.enabled.get() {
+            // not loaded yet or not collecting
+            return;
+        }
+        let key = L10nMissingKey {
+            lang: Lang(bundle.locales[0].clone()),
+            file: file.clone(),
+            id: id.clone(),
+            attribute: attribute.clone(),
+        };
+        self.keys.modify(move |k| {
+            if !k.contains(&key) {
+                k.to_mut().push(key);
+            }
+        });
     }
 }
 
@@ -297,7 +421,7 @@ impl fmt::Display for FluentErrors {
     }
 }
 
-fn format_fallback(file: &str, id: &str, attribute: &str, fallback: &Txt, args: Option<&fluent::FluentArgs>) -> Txt {
+fn format_fallback(file: &str, id: &str, attribute: &str, fallback: &Txt, args: Option<&fluent::FluentArgs>, pseudo: bool) -> Txt {
     let mut fallback_pattern = None;
 
     let entry = format!("k={fallback}");
@@ -322,7 +446,11 @@ fn format_fallback(file: &str, id: &str, attribute: &str, fallback: &Txt, args:
     };
 
     let mut errors = vec![];
-    let blank = fluent::FluentBundle::<fluent::FluentResource>::new(vec![]);
+    let mut blank = fluent::FluentBundle::<fluent::FluentResource>::new(vec![]);
+    add_number_fn(&mut blank, crate::lang!("en-US").0);
+    if pseudo {
+        blank.set_transform(Some(pseudo_transform));
+    }
     let txt = blank.format_pattern(&fallback, args, &mut errors);
 
     if !errors.is_empty() {
@@ -369,3 +497,128 @@ impl<'a> fmt::Display for DisplayKey<'a> {
         Ok(())
     }
 }
+
+/// Adds the Fluent `NUMBER` function.
+///
+/// The function formats numbers using the named options, the `type: "ordinal"` option returns the ordinal plural
+/// category of the number in the `lang`, so it can be used as a selector.
+fn add_number_fn<R, M>(bundle: &mut fluent::bundle::FluentBundle<R, M>, lang: unic_langid::LanguageIdentifier) {
+    use fluent::{types::FluentNumber, FluentValue};
+    use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+
+    // rules are defined for the language or the language and region.
+    let language = unic_langid::LanguageIdentifier::from_parts(lang.language, None, None, &[]);
+    let ordinal = PluralRules::create(lang, PluralRuleType::ORDINAL)
+        .or_else(|_| PluralRules::create(language, PluralRuleType::ORDINAL))
+        .ok();
+    let r = bundle.add_function("NUMBER", move |positional, named| match positional.first() {
+        Some(FluentValue::Number(n)) => number(n.clone(), named, ordinal.as_ref()),
+        _ => FluentValue::Error,
+    });
+    if let Err(e) = r {
+        tracing::error!("cannot add fluent NUMBER function, {e}");
+    }
+
+    fn number<'a>(mut n: FluentNumber, named: &fluent::FluentArgs, ordinal: Option<&PluralRules>) -> FluentValue<'a> {
+        if let Some(FluentValue::String(t)) = named.get("type") {
+            if t == "ordinal" {
+                let category = match ordinal.and_then(|r| r.select(n.value).ok()) {
+                    Some(PluralCategory::ZERO) => "zero",
+                    Some(PluralCategory::ONE) => "one",
+                    Some(PluralCategory::TWO) => "two",
+                    Some(PluralCategory::FEW) => "few",
+                    Some(PluralCategory::MANY) => "many",
+                    Some(PluralCategory::OTHER) | None => "other",
+                };
+                return FluentValue::String(category.into());
+            }
+        }
+        n.options.merge(named);
+        FluentValue::Number(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zng_app::APP;
+
+    use super::*;
+    use crate::{L10nManager, L10N};
+
+    #[test]
+    fn pseudo_transform_accents() {
+        let r = pseudo_transform("Hello World");
+        assert_ne!(r, "Hello World");
+        assert!(!r.is_ascii());
+        assert!(r.chars().count() >= "Hello World".chars().count());
+    }
+
+    #[test]
+    fn format_fallback_not_pseudo() {
+        let mut args = fluent::FluentArgs::new();
+        args.set("name", "World");
+        let r = format_fallback("", "msg", "", &Txt::from_static("Hello {$name}!"), Some(&args), false);
+        assert!(r.contains("Hello"));
+        assert!(r.contains("World"));
+    }
+
+    #[test]
+    fn format_fallback_pseudo_keeps_args() {
+        let mut args = fluent::FluentArgs::new();
+        args.set("name", "World");
+        let r = format_fallback("", "msg", "", &Txt::from_static("Hello {$name}!"), Some(&args), true);
+        assert!(!r.contains("Hello"));
+        assert!(r.contains("World"));
+    }
+
+    #[test]
+    fn number_fn_ordinal() {
+        let rank = Txt::from_static(
+            "{NUMBER($n, type: \"ordinal\") ->
+    [one] {$n}st
+    [two] {$n}nd
+    [few] {$n}rd
+   *[other] {$n}th
+}",
+        );
+        for (n, expected) in [(1, "1st"), (2, "2nd"), (3, "3rd"), (4, "4th"), (11, "11th"), (22, "22nd")] {
+            let mut args = fluent::FluentArgs::new();
+            args.set("n", n);
+            let r = format_fallback("", "rank", "", &rank, Some(&args), false);
+            assert_eq!(expected, r.replace(['\u{2068}', '\u{2069}'], ""), "n={n}");
+        }
+    }
+
+    #[test]
+    fn number_fn_options() {
+        let mut args = fluent::FluentArgs::new();
+        args.set("n", 1.5);
+        let r = format_fallback(
+            "",
+            "n",
+            "",
+            &Txt::from_static("{NUMBER($n, minimumFractionDigits: 3)}"),
+            Some(&args),
+            false,
+        );
+        assert_eq!("1.500", r.replace(['\u{2068}', '\u{2069}'], ""));
+    }
+
+    #[test]
+    fn pseudo_toggle_updates_message() {
+        let mut app = APP.minimal().extend(L10nManager::default()).run_headless(false);
+
+        let msg = L10N.localized_message(Langs::default(), "", "msg", "", "Hello", vec![]);
+        assert_eq!(msg.get(), "Hello");
+
+        L10N.set_pseudo(true);
+        app.update(false).assert_wait();
+        let pseudo = msg.get();
+        assert_ne!(pseudo, "Hello");
+        assert!(!pseudo.is_ascii());
+
+        L10N.set_pseudo(false);
+        app.update(false).assert_wait();
+        assert_eq!(msg.get(), "Hello");
+    }
+}
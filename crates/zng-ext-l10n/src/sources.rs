@@ -1,15 +1,16 @@
 use std::{
     collections::HashMap,
-    io,
+    io::{self, Read as _},
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
 };
 
+use parking_lot::Mutex;
 use zng_clone_move::clmv;
 use zng_ext_fs_watcher::WATCHER;
 use zng_txt::Txt;
-use zng_var::{types::WeakArcVar, var, AnyVar, ArcEq, ArcVar, BoxedVar, BoxedWeakVar, LocalVar, Var, VarHandle, WeakVar};
+use zng_var::{types::WeakArcVar, var, AnyVar, ArcEq, ArcVar, BoxedVar, BoxedWeakVar, LocalVar, MergeVarBuilder, Var, VarHandle, WeakVar};
 
 use crate::{FluentParserErrors, L10nSource, Lang, LangMap, LangResourceStatus};
 
@@ -231,17 +232,228 @@ fn load_file(status: ArcVar<LangResourceStatus>, dir: &Path, lang: &Lang, file:
     r.boxed()
 }
 
+/// Represents localization resources loaded from a tar archive.
+///
+/// The expected archive layout is the same as [`L10nDir`], `{lang}.ftl` for lang only and `{lang}/file.ftl` for lang with file,
+/// the files can also be inside a root directory. All files are parsed on load, the resources do not change.
+pub struct L10nTar {
+    available: Arc<LangMap<HashMap<Txt, PathBuf>>>,
+    status: LangResourceStatus,
+    res: HashMap<(Lang, Txt), Result<ArcEq<fluent::FluentResource>, Arc<FluentParserErrors>>>,
+}
+impl L10nTar {
+    /// Read and parse the localization files from the tar archive `data`.
+    pub fn load(data: impl AsRef<[u8]>) -> Self {
+        let mut r = Self {
+            available: Arc::default(),
+            status: LangResourceStatus::Loaded,
+            res: HashMap::new(),
+        };
+        let mut available = LangMap::new();
+        if let Err(e) = r.read(data.as_ref(), &mut available) {
+            tracing::error!("error reading l10n tar, {e}");
+            r.status = LangResourceStatus::Errors(vec![Arc::new(e)]);
+        }
+        r.available = Arc::new(available);
+        r
+    }
+
+    fn read(&mut self, data: &[u8], available: &mut LangMap<HashMap<Txt, PathBuf>>) -> io::Result<()> {
+        const EXT: unicase::Ascii<&'static str> = unicase::Ascii::new("ftl");
+
+        let mut archive = tar::Archive::new(data);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry.path()?.into_owned();
+
+            let (name, ext) = match path.file_name().and_then(|n| n.to_str()).and_then(|n| n.rsplit_once('.')) {
+                Some(n) => n,
+                None => continue,
+            };
+            if !ext.is_ascii() || unicase::Ascii::new(ext) != EXT {
+                continue;
+            }
+
+            // match {lang}/file.ftl first, then {lang}.ftl
+            let dir_lang = path
+                .parent()
+                .and_then(|p| p.file_name())
+                .and_then(|n| n.to_str())
+                .and_then(|n| Lang::from_str(n).ok());
+            let (lang, file) = match dir_lang {
+                Some(lang) => (lang, Txt::from_str(name)),
+                None => match Lang::from_str(name) {
+                    Ok(lang) => (lang, Txt::from_str("")),
+                    Err(e) => {
+                        tracing::debug!("file {} is not l10n, {e}", path.display());
+                        continue;
+                    }
+                },
+            };
+
+            let mut flt = String::new();
+            entry.read_to_string(&mut flt)?;
+            let res = match fluent::FluentResource::try_new(flt) {
+                Ok(r) => Ok(ArcEq::new(r)),
+                Err(e) => {
+                    let e = FluentParserErrors(e.1);
+                    tracing::error!("error parsing fluent resource, {e}");
+                    Err(Arc::new(e))
+                }
+            };
+
+            tracing::debug!("found {}", path.display());
+            available
+                .get_exact_or_insert(lang.clone(), Default::default)
+                .insert(file.clone(), path);
+            self.res.insert((lang, file), res);
+        }
+        Ok(())
+    }
+}
+impl L10nSource for L10nTar {
+    fn available_langs(&mut self) -> BoxedVar<Arc<LangMap<HashMap<Txt, PathBuf>>>> {
+        LocalVar(self.available.clone()).boxed()
+    }
+
+    fn available_langs_status(&mut self) -> BoxedVar<LangResourceStatus> {
+        LocalVar(self.status.clone()).boxed()
+    }
+
+    fn lang_resource(&mut self, lang: Lang, file: Txt) -> BoxedVar<Option<ArcEq<fluent::FluentResource>>> {
+        let r = match self.res.get(&(lang, file)) {
+            Some(Ok(r)) => Some(r.clone()),
+            _ => None,
+        };
+        LocalVar(r).boxed()
+    }
+
+    fn lang_resource_status(&mut self, lang: Lang, file: Txt) -> BoxedVar<LangResourceStatus> {
+        let s = match self.res.get(&(lang, file)) {
+            Some(Ok(_)) => LangResourceStatus::Loaded,
+            Some(Err(e)) => LangResourceStatus::Errors(vec![e.clone()]),
+            None => LangResourceStatus::NotAvailable,
+        };
+        LocalVar(s).boxed()
+    }
+}
+
+/// Source shared between the service and the current stack.
+#[derive(Clone)]
+pub(crate) struct SharedL10nSource(Arc<Mutex<Box<dyn L10nSource>>>);
+impl SharedL10nSource {
+    pub fn new(source: impl L10nSource) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(source))))
+    }
+}
+
+/// Source that combines multiple sources, the first source that provides a resource is used.
+pub(crate) struct StackL10nSource(pub Vec<SharedL10nSource>);
+impl L10nSource for StackL10nSource {
+    fn available_langs(&mut self) -> BoxedVar<Arc<LangMap<HashMap<Txt, PathBuf>>>> {
+        if self.0.len() == 1 {
+            return self.0[0].0.lock().available_langs();
+        }
+        let mut langs = MergeVarBuilder::new();
+        for s in &self.0 {
+            langs.push(s.0.lock().available_langs());
+        }
+        langs
+            .build(|langs| {
+                let mut r: LangMap<HashMap<Txt, PathBuf>> = LangMap::new();
+                for source in langs.iter() {
+                    for (lang, files) in source.iter() {
+                        let r = r.get_exact_or_insert(lang.clone(), Default::default);
+                        for (file, path) in files {
+                            r.entry(file.clone()).or_insert_with(|| path.clone());
+                        }
+                    }
+                }
+                Arc::new(r)
+            })
+            .boxed()
+    }
+
+    fn available_langs_status(&mut self) -> BoxedVar<LangResourceStatus> {
+        if self.0.len() == 1 {
+            return self.0[0].0.lock().available_langs_status();
+        }
+        let mut status = MergeVarBuilder::new();
+        for s in &self.0 {
+            status.push(s.0.lock().available_langs_status());
+        }
+        status
+            .build(|status| {
+                let mut errors = vec![];
+                let mut loaded = false;
+                for s in status.iter() {
+                    match s {
+                        LangResourceStatus::Loading => return LangResourceStatus::Loading,
+                        LangResourceStatus::Errors(e) => errors.extend(e.iter().cloned()),
+                        LangResourceStatus::Loaded => loaded = true,
+                        LangResourceStatus::NotAvailable => {}
+                    }
+                }
+                if !errors.is_empty() {
+                    LangResourceStatus::Errors(errors)
+                } else if loaded {
+                    LangResourceStatus::Loaded
+                } else {
+                    LangResourceStatus::NotAvailable
+                }
+            })
+            .boxed()
+    }
+
+    fn lang_resource(&mut self, lang: Lang, file: Txt) -> BoxedVar<Option<ArcEq<fluent::FluentResource>>> {
+        if self.0.len() == 1 {
+            return self.0[0].0.lock().lang_resource(lang, file);
+        }
+        let mut res = MergeVarBuilder::new();
+        for s in &self.0 {
+            res.push(s.0.lock().lang_resource(lang.clone(), file.clone()));
+        }
+        res.build(|res| res.iter().flatten().next().cloned()).boxed()
+    }
+
+    fn lang_resource_status(&mut self, lang: Lang, file: Txt) -> BoxedVar<LangResourceStatus> {
+        if self.0.len() == 1 {
+            return self.0[0].0.lock().lang_resource_status(lang, file);
+        }
+        let mut status = MergeVarBuilder::new();
+        for s in &self.0 {
+            status.push(s.0.lock().lang_resource_status(lang.clone(), file.clone()));
+        }
+        status
+            .build(|status| {
+                status
+                    .iter()
+                    .find(|s| !matches!(s, LangResourceStatus::NotAvailable))
+                    .cloned()
+                    .unwrap_or(LangResourceStatus::NotAvailable)
+            })
+            .boxed()
+    }
+}
+
 /// Represents localization source that can swap the actual source without disconnecting variables
 /// taken on resources.
 ///
 /// Note that [`L10N.load`] already uses this source internally.
 ///
 /// [`L10N.load`]: super::L10N::load
+#[allow(clippy::type_complexity)]
 pub struct SwapL10nSource {
     actual: Box<dyn L10nSource>,
 
     available_langs: ArcVar<Arc<LangMap<HashMap<Txt, PathBuf>>>>,
     available_langs_status: ArcVar<LangResourceStatus>,
+    // actual vars held and bound to the available vars.
+    actual_langs: (BoxedVar<Arc<LangMap<HashMap<Txt, PathBuf>>>>, VarHandle),
+    actual_langs_status: (BoxedVar<LangResourceStatus>, VarHandle),
 
     res: HashMap<(Lang, Txt), SwapFile>,
 }
@@ -252,6 +464,8 @@ impl SwapL10nSource {
             actual: Box::new(NilL10nSource),
             available_langs: var(Arc::default()),
             available_langs_status: var(LangResourceStatus::NotAvailable),
+            actual_langs: (LocalVar(Arc::default()).boxed(), VarHandle::dummy()),
+            actual_langs_status: (LocalVar(LangResourceStatus::NotAvailable).boxed(), VarHandle::dummy()),
             res: HashMap::new(),
         }
     }
@@ -265,11 +479,13 @@ impl SwapL10nSource {
 
         let actual_langs = self.actual.available_langs();
         self.available_langs.set_from(&actual_langs);
-        actual_langs.bind(&self.available_langs).perm();
+        let handle = actual_langs.bind(&self.available_langs);
+        self.actual_langs = (actual_langs, handle);
 
         let actual_status = self.actual.available_langs_status();
         self.available_langs_status.set_from(&actual_status);
-        actual_status.bind(&self.available_langs_status).perm();
+        let handle = actual_status.bind(&self.available_langs_status);
+        self.actual_langs_status = (actual_status, handle);
 
         for ((lang, file), f) in &mut self.res {
             if let Some(res) = f.res.upgrade() {
@@ -5,7 +5,7 @@ use once_cell::sync::Lazy;
 use zng_ext_fs_watcher::WatcherReadStatus;
 use zng_layout::context::LayoutDirection;
 use zng_txt::Txt;
-use zng_var::{context_var, impl_from_and_into_var, ArcEq, ArcVar, BoxedVar, IntoVar, LocalVar, ReadOnlyArcVar, Var, VarValue};
+use zng_var::{context_var, impl_from_and_into_var, ArcEq, ArcVar, BoxedVar, ContextVar, IntoVar, LocalVar, ReadOnlyArcVar, Var, VarValue};
 
 use crate::{lang, service::L10N_SV, L10N};
 
@@ -156,6 +156,15 @@ impl L10nMessageBuilder {
         self.args.push((name, value.into_var().boxed()));
         self
     }
+    /// Add many format arg variables.
+    ///
+    /// Use the [`l10n_args!`] macro to declare the args.
+    ///
+    /// [`l10n_args!`]: crate::l10n_args
+    pub fn args(mut self, args: impl IntoIterator<Item = (Txt, BoxedVar<L10nArgument>)>) -> Self {
+        self.args.extend(args);
+        self
+    }
     #[doc(hidden)]
     pub fn l10n_arg(self, name: &'static str, value: impl Var<L10nArgument>) -> Self {
         self.arg(Txt::from_static(name), value)
@@ -209,7 +218,10 @@ impl_from_and_into_var! {
     fn from(number: FluentNumber) -> L10nArgument {
         L10nArgument::Number(number)
     }
-
+    /// Converts to `"true"` or `"false"`, the value can be used in Fluent select expressions.
+    fn from(b: bool) -> L10nArgument {
+        L10nArgument::Txt(if b { Txt::from_static("true") } else { Txt::from_static("false") })
+    }
 }
 macro_rules! impl_from_and_into_var_number {
     ($($literal:tt),+) => {
@@ -264,6 +276,27 @@ impl<T: VarValue + Into<L10nArgument>> IntoL10nVar for &mut L10nSpecialize<ArcVa
         self.0.take().unwrap().map_into()
     }
 }
+impl<T: VarValue + Into<L10nArgument>> IntoL10nVar for &mut L10nSpecialize<ReadOnlyArcVar<T>> {
+    type Var = ReadOnlyArcVar<L10nArgument>;
+
+    fn to_l10n_var(&mut self) -> Self::Var {
+        self.0.take().unwrap().map_into()
+    }
+}
+impl<T: VarValue + Into<L10nArgument>> IntoL10nVar for &mut L10nSpecialize<BoxedVar<T>> {
+    type Var = BoxedVar<L10nArgument>;
+
+    fn to_l10n_var(&mut self) -> Self::Var {
+        self.0.take().unwrap().map_into().boxed()
+    }
+}
+impl<T: VarValue + Into<L10nArgument>> IntoL10nVar for &mut L10nSpecialize<ContextVar<T>> {
+    type Var = BoxedVar<L10nArgument>;
+
+    fn to_l10n_var(&mut self) -> Self::Var {
+        self.0.take().unwrap().map_into().boxed()
+    }
+}
 impl<V: Var<L10nArgument>> IntoL10nVar for &mut &mut L10nSpecialize<V> {
     type Var = V;
 
@@ -637,6 +670,38 @@ impl<V: PartialEq> PartialEq for LangMap<V> {
 }
 impl<V: Eq> Eq for LangMap<V> {}
 
+/// Message key that was requested but not found in the loaded lang resource.
+///
+/// See [`L10N.missing_keys`] for more details.
+///
+/// [`L10N.missing_keys`]: crate::L10N::missing_keys
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct L10nMissingKey {
+    /// Most preferred lang of the request.
+    pub lang: Lang,
+    /// Resource file name, empty for the lang root file.
+    pub file: Txt,
+    /// Message identifier.
+    pub id: Txt,
+    /// Message attribute, empty if the message value was requested.
+    pub attribute: Txt,
+}
+impl fmt::Display for L10nMissingKey {
+    /// Formats the key in the same syntax used by [`l10n!`], `"file/id.attribute"`.
+    ///
+    /// [`l10n!`]: crate::l10n
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.file.is_empty() {
+            write!(f, "{}/", self.file)?
+        }
+        write!(f, "{}", self.id)?;
+        if !self.attribute.is_empty() {
+            write!(f, ".{}", self.attribute)?;
+        }
+        Ok(())
+    }
+}
+
 /// Errors found parsing a fluent resource file.
 #[derive(Clone, Debug)]
 pub struct FluentParserErrors(pub Vec<fluent_syntax::parser::ParserError>);
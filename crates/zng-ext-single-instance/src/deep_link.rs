@@ -0,0 +1,208 @@
+use std::io;
+
+use zng_app::event::{event, event_args};
+use zng_txt::{ToTxt as _, Txt};
+
+use crate::AppInstanceArgs;
+
+event_args! {
+    /// Arguments for [`APP_DEEP_LINK_EVENT`].
+    pub struct AppDeepLinkArgs {
+        /// The full URI, for example `"myapp://auth/callback?code=123"`.
+        pub uri: Txt,
+
+        /// The URI scheme, lowercase, for example `"myapp"`.
+        pub scheme: Txt,
+
+        /// Instance count. Is zero if the link was in the arguments of the current process, in single instance mode
+        /// it is the [`AppInstanceArgs::count`] of the instance that forwarded the link.
+        pub count: usize,
+
+        ..
+
+        fn delivery_list(&self, _list: &mut UpdateDeliveryList) { }
+    }
+}
+impl AppDeepLinkArgs {
+    /// If the link was in the arguments of the currently executing process (main).
+    pub fn is_current(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Gets the part of the URI after the `scheme:`, with the `//` authority prefix removed.
+    ///
+    /// For `"myapp://auth/callback?code=123"` returns `"auth/callback?code=123"`. Returns an empty string if
+    /// the `uri` does not start with the `scheme:`.
+    pub fn path(&self) -> &str {
+        let rest = match self.uri.get(..self.scheme.len()) {
+            Some(s) if s.eq_ignore_ascii_case(&self.scheme) => self.uri[s.len()..].strip_prefix(':').unwrap_or(""),
+            _ => "",
+        };
+        rest.strip_prefix("//").unwrap_or(rest)
+    }
+}
+
+event! {
+    /// Custom URI scheme link event.
+    ///
+    /// This event notifies for each argument in [`APP_INSTANCE_EVENT`] that is an URI with a scheme registered
+    /// using [`register_uri_scheme`]. In single instance mode links that start a new instance are forwarded to
+    /// the running instance, so this event can be used for OAuth callbacks and document links.
+    ///
+    /// Note that on macOS links are not received, see [`register_uri_scheme`] for more details.
+    ///
+    /// [`APP_INSTANCE_EVENT`]: crate::APP_INSTANCE_EVENT
+    pub static APP_DEEP_LINK_EVENT: AppDeepLinkArgs;
+}
+
+/// Register the current executable as the handler of `myapp://` URIs for the current user and enables [`APP_DEEP_LINK_EVENT`]
+/// for the `scheme`.
+///
+/// This function must be called before the app runs to receive the links in the current process arguments,
+/// the operating system registration is updated on every call, so the last executable to call it handles the links.
+///
+/// # Platforms
+///
+/// On Windows the scheme is registered in the current user classes registry key, on Linux a `.desktop` file is created in
+/// the user applications dir and set as the default `x-scheme-handler` using `xdg-mime`.
+///
+/// macOS is not supported. The registration always returns an `Unsupported` error, schemes must be declared in the
+/// app bundle `Info.plist` (`CFBundleURLTypes`) and macOS delivers links using the `kAEGetURL` Apple Event, not the process
+/// arguments, this event is not handled, so the [`APP_DEEP_LINK_EVENT`] is only raised for links explicitly passed as arguments.
+///
+/// # Errors
+///
+/// Returns an error if the `scheme` is not valid or if the operating system registration fails. The event is enabled for
+/// any valid `scheme`, even if the registration fails.
+pub fn register_uri_scheme(scheme: impl Into<Txt>) -> io::Result<()> {
+    let scheme = scheme.into().to_lowercase().to_txt();
+    let mut chars = scheme.chars();
+    let valid = chars.next().map(|c| c.is_ascii_alphabetic()).unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && scheme.len() > 1;
+    if !valid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid URI scheme {scheme:?}"),
+        ));
+    }
+
+    {
+        let mut s = URI_SCHEMES.lock();
+        if !s.contains(&scheme) {
+            s.push(scheme.clone());
+        }
+    }
+
+    let exe = std::env::current_exe().and_then(dunce::canonicalize)?;
+    platform::register(&scheme, &exe)
+}
+
+/// Notify [`APP_DEEP_LINK_EVENT`] for each URI argument with a registered scheme.
+pub(crate) fn notify_links(args: &AppInstanceArgs) {
+    let schemes = URI_SCHEMES.lock();
+    if schemes.is_empty() {
+        return;
+    }
+    for arg in args.args.iter().skip(1) {
+        if let Some((scheme, _)) = arg.split_once(':') {
+            if let Some(scheme) = schemes.iter().find(|s| s.eq_ignore_ascii_case(scheme)) {
+                APP_DEEP_LINK_EVENT.notify(AppDeepLinkArgs::now(arg.clone(), scheme.clone(), args.count));
+            }
+        }
+    }
+}
+
+static URI_SCHEMES: parking_lot::Mutex<Vec<Txt>> = parking_lot::Mutex::new(vec![]);
+
+#[cfg(windows)]
+mod platform {
+    use std::{io, path::Path};
+
+    use windows_sys::Win32::System::Registry::{RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ};
+
+    pub fn register(scheme: &str, exe: &Path) -> io::Result<()> {
+        let key = format!(r"Software\Classes\{scheme}");
+        set_value(&key, None, &format!("URL:{scheme}"))?;
+        set_value(&key, Some("URL Protocol"), "")?;
+        set_value(
+            &format!(r"{key}\shell\open\command"),
+            None,
+            &format!("\"{}\" \"%1\"", exe.display()),
+        )
+    }
+
+    fn set_value(key: &str, name: Option<&str>, value: &str) -> io::Result<()> {
+        fn wide(s: &str) -> Vec<u16> {
+            s.encode_utf16().chain([0]).collect()
+        }
+        let key = wide(key);
+        let name = name.map(wide);
+        let value = wide(value);
+        // SAFETY: strings are null terminated and the data len is in bytes.
+        let r = unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                name.as_ref().map(|n| n.as_ptr()).unwrap_or(std::ptr::null()),
+                REG_SZ,
+                value.as_ptr() as _,
+                (value.len() * 2) as u32,
+            )
+        };
+        if r == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::from_raw_os_error(r as i32))
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
+mod platform {
+    use std::{io, path::Path};
+
+    pub fn register(scheme: &str, exe: &Path) -> io::Result<()> {
+        let data_dir = match std::env::var_os("XDG_DATA_HOME") {
+            Some(d) if !d.is_empty() => std::path::PathBuf::from(d),
+            _ => match std::env::var_os("HOME") {
+                Some(h) => Path::new(&h).join(".local/share"),
+                None => return Err(io::Error::new(io::ErrorKind::NotFound, "cannot find user data dir")),
+            },
+        };
+        let apps_dir = data_dir.join("applications");
+        std::fs::create_dir_all(&apps_dir)?;
+
+        let name = exe.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let file_name = format!("zng-uri-{scheme}.desktop");
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName={name}\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{scheme};\n",
+            exe.display()
+        );
+        std::fs::write(apps_dir.join(&file_name), entry)?;
+
+        let status = std::process::Command::new("xdg-mime")
+            .arg("default")
+            .arg(&file_name)
+            .arg(format!("x-scheme-handler/{scheme}"))
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("xdg-mime {status}")))
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd")))]
+mod platform {
+    use std::{io, path::Path};
+
+    pub fn register(scheme: &str, exe: &Path) -> io::Result<()> {
+        let _ = (scheme, exe);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "URI schemes must be declared in the app bundle on this platform",
+        ))
+    }
+}
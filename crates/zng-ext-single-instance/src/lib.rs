@@ -9,17 +9,22 @@
 
 use std::{
     io::{Read, Write},
+    path::PathBuf,
     time::Duration,
 };
 
 use zng_app::{
     event::{event, event_args},
     handler::{async_app_hn, clmv},
-    AppExtension,
+    update::EventUpdate,
+    AppExtended, AppExtension,
 };
 use zng_ext_fs_watcher::WATCHER;
 use zng_txt::{ToTxt, Txt};
 
+mod deep_link;
+pub use deep_link::*;
+
 /// Single instance event manager.
 ///
 /// # Events
@@ -27,77 +32,91 @@ use zng_txt::{ToTxt, Txt};
 /// Events this extension provides.
 ///
 /// * [`APP_INSTANCE_EVENT`]
+/// * [`APP_DEEP_LINK_EVENT`]
 #[derive(Default)]
 pub struct SingleInstanceManager {}
 impl AppExtension for SingleInstanceManager {
     fn init(&mut self) {
         let args: Box<[_]> = std::env::args().map(Txt::from).collect();
-        APP_INSTANCE_EVENT.notify(AppInstanceArgs::now(args, 0usize));
-
-        let name = match SINGLE_INSTANCE.lock().as_ref().map(|l| l.name.clone()) {
-            Some(n) => n,
-            None => return, // app is running in a special process, like a crash dialog
-        };
-
-        let args_file = std::env::temp_dir().join(name);
-        let mut count = 1usize;
-        WATCHER
-            .on_file_changed(
-                &args_file,
-                async_app_hn!(args_file, |_, _| {
-                    let args = zng_task::wait(clmv!(args_file, || {
-                        for i in 0..5 {
-                            if i > 0 {
-                                std::thread::sleep(Duration::from_millis(200));
-                            }
+        let cwd = std::env::current_dir().unwrap_or_default();
+        APP_INSTANCE_EVENT.notify(AppInstanceArgs::now(args, cwd, 0usize));
 
-                            // take args
-                            // read all text and truncates the file
-                            match std::fs::File::options().read(true).write(true).open(&args_file) {
-                                Ok(mut file) => {
-                                    let mut s = String::new();
-                                    if let Err(e) = file.read_to_string(&mut s) {
-                                        tracing::error!("error reading args (retry {i}), {e}");
-                                        continue;
-                                    }
-                                    file.set_len(0).unwrap();
-                                    return s;
+        let names: Vec<Txt> = SINGLE_INSTANCE.lock().iter().map(|l| l.name.clone()).collect();
+        // names is empty if the app is running in a special process, like a crash dialog
+
+        let count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(1));
+        for name in names {
+            watch_args(name, count.clone());
+        }
+    }
+
+    fn event(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = APP_INSTANCE_EVENT.on(update) {
+            deep_link::notify_links(args);
+        }
+    }
+}
+
+fn watch_args(name: Txt, count: std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+    let args_file = std::env::temp_dir().join(name.as_str());
+    WATCHER
+        .on_file_changed(
+            &args_file,
+            async_app_hn!(args_file, count, |_, _| {
+                let args = zng_task::wait(clmv!(args_file, || {
+                    for i in 0..5 {
+                        if i > 0 {
+                            std::thread::sleep(Duration::from_millis(200));
+                        }
+
+                        // take args
+                        // read all text and truncates the file
+                        match std::fs::File::options().read(true).write(true).open(&args_file) {
+                            Ok(mut file) => {
+                                let mut s = String::new();
+                                if let Err(e) = file.read_to_string(&mut s) {
+                                    tracing::error!("error reading args (retry {i}), {e}");
+                                    continue;
                                 }
-                                Err(e) => {
-                                    if e.kind() == std::io::ErrorKind::NotFound {
-                                        return String::new();
-                                    }
-                                    tracing::error!("error reading args (retry {i}), {e}")
+                                file.set_len(0).unwrap();
+                                return s;
+                            }
+                            Err(e) => {
+                                if e.kind() == std::io::ErrorKind::NotFound {
+                                    return String::new();
                                 }
+                                tracing::error!("error reading args (retry {i}), {e}")
                             }
                         }
-                        String::new()
-                    }))
-                    .await;
-
-                    // parse args
-                    for line in args.lines() {
-                        let line = line.trim();
-                        if line.is_empty() {
-                            continue;
-                        }
+                    }
+                    String::new()
+                }))
+                .await;
 
-                        let args = match serde_json::from_str::<Box<[Txt]>>(line) {
-                            Ok(args) => args,
-                            Err(e) => {
-                                tracing::error!("invalid args, {e}");
-                                Box::new([])
-                            }
-                        };
+                // parse args
+                for line in args.lines() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
 
-                        APP_INSTANCE_EVENT.notify(AppInstanceArgs::now(args, count));
+                    let msg = match serde_json::from_str::<InstanceMsg>(line) {
+                        Ok(msg) => msg,
+                        Err(e) => {
+                            tracing::error!("invalid args, {e}");
+                            InstanceMsg {
+                                args: Box::new([]),
+                                cwd: PathBuf::new(),
+                            }
+                        }
+                    };
 
-                        count += 1;
-                    }
-                }),
-            )
-            .perm();
-    }
+                    let count = count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    APP_INSTANCE_EVENT.notify(AppInstanceArgs::now(msg.args, msg.cwd, count));
+                }
+            }),
+        )
+        .perm();
 }
 
 event_args! {
@@ -108,6 +127,11 @@ event_args! {
         /// See [`std::env::args`] for more details.
         pub args: Box<[Txt]>,
 
+        /// Current working directory of the app instance.
+        ///
+        /// Relative paths in `args` are relative to this directory.
+        pub cwd: PathBuf,
+
         /// Instance count. Is zero for the current process, in single instance mode
         /// increments for each subsequent attempt to instantiate the app.
         pub count: usize,
@@ -124,6 +148,75 @@ impl AppInstanceArgs {
     pub fn is_current(&self) -> bool {
         self.count == 0
     }
+
+    /// Existing file or directory paths in the arguments.
+    ///
+    /// Skips the first argument (executable) and arguments that start with `-`, resolves relative paths
+    /// using [`cwd`] and returns only paths that exist.
+    ///
+    /// [`cwd`]: Self::cwd
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.args
+            .iter()
+            .skip(1)
+            .filter(|a| !a.starts_with('-'))
+            .map(|a| self.cwd.join(a.as_str()))
+            .filter(|p| p.exists())
+            .map(|p| dunce::canonicalize(&p).unwrap_or(p))
+            .collect()
+    }
+}
+
+/// Extension methods for [`AppExtended`].
+pub trait AppSingleInstanceExt {
+    /// Enable single instance mode for all app processes with the same `app_id`.
+    ///
+    /// By default single instance mode only applies to app instances started with the same executable file, the
+    /// `app_id` should be an unique identifier like `"com.my.app"`, it extends the single instance mode to any app that uses the same
+    /// identifier, for example, multiple installed copies of the app. If another instance is already running, the current process
+    /// forwards its arguments and current directory to it and exits, the running instance notifies [`APP_INSTANCE_EVENT`]
+    /// and the default app raises its main window.
+    ///
+    /// Note that the default single instance mode by executable path is still active.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called more than once with the same `app_id` in the same process.
+    fn single_instance(self, app_id: impl Into<Txt>) -> Self;
+}
+impl<E: AppExtension> AppSingleInstanceExt for AppExtended<E> {
+    fn single_instance(self, app_id: impl Into<Txt>) -> Self {
+        let mut lock = SINGLE_INSTANCE.lock();
+        if lock.is_empty() {
+            // app is running in a special process, like a crash dialog
+            return self;
+        }
+        let app_id: String = app_id
+            .into()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        let name = zng_txt::formatx!("zng-si-id-{app_id}");
+        assert!(
+            !lock.iter().any(|l| l.name == name),
+            "single_instance already called with {name:?} in this process"
+        );
+        let l = single_instance::SingleInstance::new(&name).expect("failed to create single instance lock");
+        if l.is_single() {
+            lock.push(SingleInstanceData { _lock: l, name });
+        } else {
+            tracing::info!("another instance with same id running, will send args and exit");
+            drop(lock);
+            send_args(name);
+        }
+        self
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct InstanceMsg {
+    args: Box<[Txt]>,
+    cwd: PathBuf,
 }
 
 event! {
@@ -141,7 +234,7 @@ zng_env::on_process_start!(|args| {
     }
 
     let mut lock = SINGLE_INSTANCE.lock();
-    assert!(lock.is_none(), "single_instance already called in this process");
+    assert!(lock.is_empty(), "single_instance already called in this process");
 
     let name = std::env::current_exe()
         .and_then(dunce::canonicalize)
@@ -161,39 +254,46 @@ zng_env::on_process_start!(|args| {
     let l = single_instance::SingleInstance::new(&name).expect("failed to create single instance lock");
 
     if l.is_single() {
-        *lock = Some(SingleInstanceData { _lock: l, name });
+        lock.push(SingleInstanceData { _lock: l, name });
     } else {
         tracing::info!("another instance running, will send args and exit");
+        send_args(name);
+    }
+});
 
-        let args: Box<[_]> = std::env::args().collect();
-        let args = format!("\n{}\n", serde_json::to_string(&args).unwrap());
+/// Send the current process args to the running instance and exit the process.
+fn send_args(name: Txt) -> ! {
+    let msg = InstanceMsg {
+        args: std::env::args().map(Txt::from).collect(),
+        cwd: std::env::current_dir().unwrap_or_default(),
+    };
+    let args = format!("\n{}\n", serde_json::to_string(&msg).unwrap());
 
-        let try_write = move || -> std::io::Result<()> {
-            let mut file = std::fs::File::options()
-                .create(true)
-                .append(true)
-                .open(std::env::temp_dir().join(name.as_str()))?;
-            file.write_all(args.as_bytes())
-        };
+    let try_write = move || -> std::io::Result<()> {
+        let mut file = std::fs::File::options()
+            .create(true)
+            .append(true)
+            .open(std::env::temp_dir().join(name.as_str()))?;
+        file.write_all(args.as_bytes())
+    };
 
-        for i in 0..5 {
-            if i > 0 {
-                std::thread::sleep(std::time::Duration::from_millis(300));
-            }
-            match try_write() {
-                Ok(_) => zng_env::exit(0),
-                Err(e) => {
-                    eprintln!("error writing args (retries: {i}), {e}");
-                }
+    for i in 0..5 {
+        if i > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+        }
+        match try_write() {
+            Ok(_) => zng_env::exit(0),
+            Err(e) => {
+                eprintln!("error writing args (retries: {i}), {e}");
             }
         }
-        zng_env::exit(1);
     }
-});
+    zng_env::exit(1);
+}
 
 struct SingleInstanceData {
     _lock: single_instance::SingleInstance,
     name: Txt,
 }
 
-static SINGLE_INSTANCE: parking_lot::Mutex<Option<SingleInstanceData>> = parking_lot::Mutex::new(None);
+static SINGLE_INSTANCE: parking_lot::Mutex<Vec<SingleInstanceData>> = parking_lot::Mutex::new(vec![]);
@@ -0,0 +1,118 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Sound service, system notification sounds and simple WAV playback.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use zng_app::view_process::VIEW_PROCESS;
+use zng_app_context::app_local;
+use zng_txt::Txt;
+use zng_var::{var, ArcVar, Var as _};
+use zng_view_api::{ipc::IpcBytes, sound as sound_api};
+
+pub use zng_view_api::sound::SystemSound;
+
+app_local! {
+    static SOUND_SV: SoundService = SoundService { muted: var(false) };
+}
+
+struct SoundService {
+    muted: ArcVar<bool>,
+}
+
+/// Sound service.
+///
+/// This service plays system notification sounds and simple WAV sounds using the view-process, it is
+/// intended for auditory feedback of dialogs, notifications and accessibility features, not as a full audio stack.
+///
+/// This service needs a running view-process to actually play sounds, in a headless app
+/// without renderer (no view-process) the service will always return [`SoundError::ViewProcessOffline`].
+pub struct SOUND;
+impl SOUND {
+    /// Variable that defines if all sounds requested from this service are ignored.
+    ///
+    /// Is `false` by default.
+    pub fn muted(&self) -> ArcVar<bool> {
+        SOUND_SV.read().muted.clone()
+    }
+
+    /// Start playing the `sound`.
+    ///
+    /// The sound plays in the background, only errors starting the playback are returned. If [`muted`] the sound
+    /// is not played and returns `Ok(())`.
+    ///
+    /// [`muted`]: Self::muted
+    pub fn play(&self, sound: impl Into<Sound>) -> Result<(), SoundError> {
+        if SOUND_SV.read().muted.get() {
+            return Ok(());
+        }
+        let r = match VIEW_PROCESS.play_sound(sound.into().0) {
+            Ok(r) => r.map_err(SoundError::from),
+            Err(_) => Err(SoundError::ViewProcessOffline),
+        };
+        if let Err(e) = &r {
+            tracing::debug!("cannot play sound, {e}");
+        }
+        r
+    }
+}
+
+/// Sound that can be played by the [`SOUND`] service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sound(sound_api::SoundData);
+impl Sound {
+    /// System notification sound.
+    pub fn system(sound: SystemSound) -> Self {
+        Self(sound_api::SoundData::System(sound))
+    }
+
+    /// Complete WAV file data.
+    pub fn wav(data: IpcBytes) -> Self {
+        Self(sound_api::SoundData::Wav(data))
+    }
+}
+impl From<SystemSound> for Sound {
+    fn from(sound: SystemSound) -> Self {
+        Sound::system(sound)
+    }
+}
+
+/// Error playing a sound.
+///
+/// The [`SOUND`] service already logs the error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoundError {
+    /// No view-process available to process the request.
+    ViewProcessOffline,
+    /// View-process or operating system does not support sound output or the sound format.
+    NotSupported,
+    /// Other error.
+    ///
+    /// The string can be a debug description of the error, only suitable for logging.
+    Other(Txt),
+}
+impl std::error::Error for SoundError {}
+impl fmt::Display for SoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoundError::ViewProcessOffline => write!(f, "no view-process available to process the request"),
+            SoundError::NotSupported => write!(f, "view-process or operating system does not support the sound"),
+            SoundError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl From<sound_api::SoundError> for SoundError {
+    fn from(e: sound_api::SoundError) -> Self {
+        match e {
+            sound_api::SoundError::NotSupported => SoundError::NotSupported,
+            sound_api::SoundError::Other(e) => SoundError::Other(e),
+        }
+    }
+}
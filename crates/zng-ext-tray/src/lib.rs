@@ -0,0 +1,376 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! System tray icon service, events and types.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use zng_app::{
+    event::{event, event_args, AnyEventArgs as _},
+    update::EventUpdate,
+    view_process::{
+        raw_events::{RAW_TRAY_ICON_CLICKED_EVENT, RAW_TRAY_MENU_ITEM_CLICKED_EVENT},
+        VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
+    },
+    AppExtension,
+};
+use zng_app_context::app_local;
+use zng_ext_image::{ImageSource, ImageVar, IMAGES};
+use zng_handle::{Handle, HandleOwner, WeakHandle};
+use zng_txt::Txt;
+use zng_var::{AnyVar as _, BoxedVar, IntoVar, LocalVar, Var};
+use zng_view_api::{mouse::MouseButton, tray as tray_api};
+
+pub use zng_view_api::tray::{TrayIconId, TrayMenuItem};
+
+/// Application extension that provides tray icons.
+///
+/// # Events
+///
+/// Events this extension provides.
+///
+/// * [`TRAY_ICON_CLICK_EVENT`]
+/// * [`TRAY_MENU_ITEM_CLICK_EVENT`]
+///
+/// # Services
+///
+/// Services this extension provides.
+///
+/// * [`TRAY_ICONS`]
+#[derive(Default)]
+pub struct TrayIconManager {}
+impl AppExtension for TrayIconManager {
+    fn event(&mut self, update: &mut EventUpdate) {
+        if let Some(args) = RAW_TRAY_ICON_CLICKED_EVENT.on(update) {
+            if TRAY_ICONS_SV.read().is_open(args.tray_id) {
+                TRAY_ICON_CLICK_EVENT.notify(TrayIconClickArgs::new(
+                    args.timestamp,
+                    args.propagation().clone(),
+                    args.tray_id,
+                    args.button,
+                    args.is_double,
+                ));
+            }
+        } else if let Some(args) = RAW_TRAY_MENU_ITEM_CLICKED_EVENT.on(update) {
+            if TRAY_ICONS_SV.read().is_open(args.tray_id) {
+                TRAY_MENU_ITEM_CLICK_EVENT.notify(TrayMenuItemClickArgs::new(
+                    args.timestamp,
+                    args.propagation().clone(),
+                    args.tray_id,
+                    args.item.clone(),
+                ));
+            }
+        } else if VIEW_PROCESS_INITED_EVENT.on(update).is_some() {
+            // send icons opened before init or restore after respawn
+            for e in TRAY_ICONS_SV.read().icons.iter().filter(|e| !e.handle.is_dropped()) {
+                if let Err(err) = e.send() {
+                    tracing::error!("cannot open tray icon, {err}");
+                }
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        TRAY_ICONS_SV.write().icons.retain_mut(|e| {
+            if e.handle.is_dropped() {
+                let _ = VIEW_PROCESS.close_tray_icon(e.handle.data().0);
+                return false;
+            }
+
+            let mut send = false;
+            if let Some(source) = e.icon_source.get_new() {
+                e.icon = IMAGES.cache(source);
+                send = true;
+            }
+            send |= e.icon.is_new() || e.tooltip.is_new() || e.menu.is_new();
+
+            if send {
+                if let Err(err) = e.send() {
+                    tracing::error!("cannot update tray icon, {err}");
+                }
+            }
+            true
+        });
+    }
+}
+
+app_local! {
+    static TRAY_ICONS_SV: TrayIconsService = TrayIconsService {
+        icons: vec![],
+        id_gen: TrayIconId::first(),
+    };
+}
+
+struct TrayIconsService {
+    icons: Vec<TrayIconEntry>,
+    id_gen: TrayIconId,
+}
+impl TrayIconsService {
+    fn is_open(&self, id: TrayIconId) -> bool {
+        self.icons.iter().any(|e| e.handle.data().0 == id && !e.handle.is_dropped())
+    }
+}
+
+struct TrayIconEntry {
+    handle: HandleOwner<TrayIconHandleData>,
+    tooltip: BoxedVar<Txt>,
+    icon_source: BoxedVar<ImageSource>,
+    icon: ImageVar,
+    menu: BoxedVar<Vec<TrayMenuItem>>,
+}
+impl TrayIconEntry {
+    fn send(&self) -> Result<(), TrayIconError> {
+        let request = tray_api::TrayIconRequest {
+            id: self.handle.data().0,
+            tooltip: self.tooltip.get(),
+            icon: self.icon.with(|img| img.view().and_then(|v| v.id())),
+            menu: self.menu.get(),
+        };
+        match VIEW_PROCESS.set_tray_icon(request) {
+            Ok(r) => r.map_err(Into::into),
+            Err(_) => Err(TrayIconError::ViewProcessOffline),
+        }
+    }
+}
+
+/// Tray icons service.
+///
+/// Tray icons are small icons shown in the system tray or notification area of the taskbar, they are
+/// usually used by apps that mostly run in the background.
+///
+/// Tray icons are implemented by the view-process, the default view-process implementation supports
+/// tray icons on Windows and on Linux desktops that implement the StatusNotifierItem protocol (KDE Plasma, GNOME with
+/// the AppIndicator extension and most panels), [`TRAY_ICONS.open`] returns [`TrayIconError::NotSupported`] on macOS
+/// and other systems.
+///
+/// On Linux the tray host opens the menu on right click, so [`TRAY_ICON_CLICK_EVENT`] only notifies right clicks
+/// for icons without menu, double-clicks are also not notified.
+///
+/// # Provider
+///
+/// This service is provided by the [`TrayIconManager`] extension.
+///
+/// [`TRAY_ICONS.open`]: TRAY_ICONS::open
+#[allow(non_camel_case_types)]
+pub struct TRAY_ICONS;
+impl TRAY_ICONS {
+    /// Open a tray icon.
+    ///
+    /// The icon is updated when the `tray` variables update and is removed when all clones of the returned
+    /// handle are dropped. Clicks on the icon notify [`TRAY_ICON_CLICK_EVENT`] and clicks on menu items
+    /// notify [`TRAY_MENU_ITEM_CLICK_EVENT`].
+    ///
+    /// If the view-process is still starting the icon opens after it inits, in this case errors are only logged.
+    pub fn open(&self, tray: TrayIcon) -> Result<TrayIconHandle, TrayIconError> {
+        let mut s = TRAY_ICONS_SV.write();
+        let id = s.id_gen.incr();
+        let (owner, handle) = Handle::new(TrayIconHandleData(id));
+
+        let icon = IMAGES.cache(tray.icon.get());
+        let e = TrayIconEntry {
+            handle: owner,
+            tooltip: tray.tooltip,
+            icon_source: tray.icon,
+            icon,
+            menu: tray.menu,
+        };
+        if !VIEW_PROCESS.is_available() {
+            return Err(TrayIconError::ViewProcessOffline);
+        } else if VIEW_PROCESS.is_online() {
+            e.send()?;
+        }
+        // else will send on init
+        s.icons.push(e);
+
+        Ok(TrayIconHandle(handle))
+    }
+}
+
+/// Tray icon definition.
+///
+/// See [`TRAY_ICONS.open`] for more details.
+///
+/// [`TRAY_ICONS.open`]: TRAY_ICONS::open
+pub struct TrayIcon {
+    /// Text shown when the icon is hovered.
+    pub tooltip: BoxedVar<Txt>,
+    /// Icon image, requested from [`IMAGES`].
+    ///
+    /// The image is shown when it finishes loading.
+    pub icon: BoxedVar<ImageSource>,
+    /// Menu items opened when the icon is right clicked.
+    ///
+    /// No menu is opened if empty, the default is empty.
+    pub menu: BoxedVar<Vec<TrayMenuItem>>,
+}
+impl TrayIcon {
+    /// New tray icon without menu.
+    pub fn new(tooltip: impl IntoVar<Txt>, icon: impl IntoVar<ImageSource>) -> Self {
+        Self {
+            tooltip: tooltip.into_var().boxed(),
+            icon: icon.into_var().boxed(),
+            menu: LocalVar(vec![]).boxed(),
+        }
+    }
+
+    /// Set the menu items.
+    pub fn menu(mut self, menu: impl IntoVar<Vec<TrayMenuItem>>) -> Self {
+        self.menu = menu.into_var().boxed();
+        self
+    }
+}
+impl fmt::Debug for TrayIcon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrayIcon")
+            .field("tooltip", &self.tooltip.get())
+            .field("menu", &self.menu.get())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct TrayIconHandleData(TrayIconId);
+
+/// Represents an open tray icon.
+///
+/// The tray icon is removed when all clones of the handle are dropped.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[must_use = "the tray icon is removed if the handle is dropped"]
+pub struct TrayIconHandle(Handle<TrayIconHandleData>);
+impl TrayIconHandle {
+    /// The tray icon ID, used to identify the icon in events.
+    pub fn id(&self) -> TrayIconId {
+        self.0.data().0
+    }
+
+    /// Drops the handle but does **not** remove the icon.
+    ///
+    /// The icon stays open for the duration of the app or until another handle calls [`close`](Self::close).
+    pub fn perm(self) {
+        self.0.perm();
+    }
+
+    /// If another handle has called [`perm`](Self::perm).
+    pub fn is_permanent(&self) -> bool {
+        self.0.is_permanent()
+    }
+
+    /// Drops the handle and removes the icon.
+    pub fn close(self) {
+        self.0.force_drop();
+    }
+
+    /// If another handle has called [`close`](Self::close).
+    ///
+    /// The icon is already removed or will be in the next app update, this is irreversible.
+    pub fn is_closed(&self) -> bool {
+        self.0.is_dropped()
+    }
+
+    /// Create a weak handle.
+    pub fn downgrade(&self) -> WeakTrayIconHandle {
+        WeakTrayIconHandle(self.0.downgrade())
+    }
+}
+
+/// Weak [`TrayIconHandle`].
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct WeakTrayIconHandle(WeakHandle<TrayIconHandleData>);
+impl WeakTrayIconHandle {
+    /// New weak handle that does not upgrade.
+    pub fn new() -> Self {
+        Self(WeakHandle::new())
+    }
+
+    /// Get the tray icon handle if it is still open.
+    pub fn upgrade(&self) -> Option<TrayIconHandle> {
+        self.0.upgrade().map(TrayIconHandle)
+    }
+}
+
+/// Error opening a tray icon in [`TRAY_ICONS`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrayIconError {
+    /// View-process is not running.
+    ViewProcessOffline,
+    /// View-process implementer or operating system does not support tray icons.
+    NotSupported,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for TrayIconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrayIconError::ViewProcessOffline => write!(f, "view-process is not running"),
+            TrayIconError::NotSupported => write!(f, "tray icons not supported"),
+            TrayIconError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for TrayIconError {}
+impl From<tray_api::TrayIconError> for TrayIconError {
+    fn from(e: tray_api::TrayIconError) -> Self {
+        match e {
+            tray_api::TrayIconError::NotSupported => TrayIconError::NotSupported,
+            tray_api::TrayIconError::Other(e) => TrayIconError::Other(e),
+        }
+    }
+}
+
+event_args! {
+    /// Arguments for [`TRAY_ICON_CLICK_EVENT`].
+    pub struct TrayIconClickArgs {
+        /// Tray icon that was clicked.
+        pub tray_id: TrayIconId,
+
+        /// Mouse button that was released over the icon.
+        pub button: MouseButton,
+
+        /// If the click was a double-click.
+        pub is_double: bool,
+
+        ..
+
+        /// The event is only delivered to subscribers.
+        fn delivery_list(&self, _list: &mut UpdateDeliveryList) {}
+    }
+
+    /// Arguments for [`TRAY_MENU_ITEM_CLICK_EVENT`].
+    pub struct TrayMenuItemClickArgs {
+        /// Tray icon that owns the menu.
+        pub tray_id: TrayIconId,
+
+        /// The [`TrayMenuItem::Item`] ID.
+        pub item: Txt,
+
+        ..
+
+        /// The event is only delivered to subscribers.
+        fn delivery_list(&self, _list: &mut UpdateDeliveryList) {}
+    }
+}
+impl TrayIconClickArgs {
+    /// If the [`button`](Self::button) is the primary.
+    pub fn is_primary(&self) -> bool {
+        self.button == MouseButton::Left
+    }
+
+    /// If the [`button`](Self::button) is the context button.
+    pub fn is_context(&self) -> bool {
+        self.button == MouseButton::Right
+    }
+}
+
+event! {
+    /// A tray icon opened by [`TRAY_ICONS`] was clicked.
+    pub static TRAY_ICON_CLICK_EVENT: TrayIconClickArgs;
+
+    /// A tray icon menu item was clicked.
+    pub static TRAY_MENU_ITEM_CLICK_EVENT: TrayMenuItemClickArgs;
+}
@@ -13,7 +13,8 @@
 #![allow(clippy::type_complexity)]
 
 use std::{
-    any::Any,
+    any::{Any, TypeId},
+    collections::HashMap,
     fmt, mem,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -258,10 +259,34 @@ impl UNDO {
     ///
     /// The transaction can be immediately undone or committed.
     pub fn transaction(&self, actions: impl FnOnce()) -> UndoTransaction {
+        self.transaction_impl(None, actions)
+    }
+
+    /// Run `actions` in a new named undo scope, capturing all undo actions inside it into a new
+    /// [`UndoTransaction`] that commits as a single group entry with the `info`.
+    ///
+    /// The `info` is available for nested actions in [`transaction_info`].
+    ///
+    /// [`transaction_info`]: Self::transaction_info
+    pub fn transaction_named(&self, info: impl UndoInfo, actions: impl FnOnce()) -> UndoTransaction {
+        self.transaction_impl(Some(info.into_dyn()), actions)
+    }
+
+    /// Gets the info of the inner most named transaction in the current context.
+    ///
+    /// See [`transaction_named`] for more details.
+    ///
+    /// [`transaction_named`]: Self::transaction_named
+    pub fn transaction_info(&self) -> Option<Arc<dyn UndoInfo>> {
+        UNDO_SCOPE_CTX.get().info.clone()
+    }
+
+    fn transaction_impl(&self, info: Option<Arc<dyn UndoInfo>>, actions: impl FnOnce()) -> UndoTransaction {
         let mut scope = UndoScope::default();
         let parent_scope = UNDO_SCOPE_CTX.get();
         *scope.enabled.get_mut() = parent_scope.enabled.load(Ordering::Relaxed);
         *scope.id.get_mut() = parent_scope.id.load(Ordering::Relaxed);
+        scope.info = info.clone().or_else(|| parent_scope.info.clone());
 
         let t_scope = Arc::new(scope);
         let _panic_undo = RunOnDrop::new(clmv!(t_scope, || {
@@ -276,7 +301,7 @@ impl UNDO {
         let scope = scope.unwrap();
         let undo = mem::take(&mut *scope.undo.lock());
 
-        UndoTransaction { undo }
+        UndoTransaction { info, undo }
     }
 
     /// Run `actions` as a [`transaction`] and commits as a group if the result is `Ok(O)` and at least one
@@ -325,6 +350,7 @@ impl UNDO {
         let parent_scope = UNDO_SCOPE_CTX.get();
         *scope.enabled.get_mut() = false;
         *scope.id.get_mut() = parent_scope.id.load(Ordering::Relaxed);
+        scope.info = parent_scope.info.clone();
 
         UNDO_SCOPE_CTX.with_context(&mut Some(Arc::new(scope)), f)
     }
@@ -415,6 +441,261 @@ impl UNDO {
     pub fn redo_stack(&self) -> UndoStackInfo {
         UndoStackInfo::redo(&UNDO_SCOPE_CTX.get(), UNDO_INTERVAL_VAR.get())
     }
+
+    /// Gets the coalescing rule for consecutive undo actions of type `A`.
+    ///
+    /// The type is the [`UndoAction`] type or the data type `D` for full ops registered using [`register_full_op`].
+    ///
+    /// [`register_full_op`]: Self::register_full_op
+    pub fn coalesce<A: Any>(&self) -> UndoCoalesce {
+        UNDO_SV.read().coalesce.get(&TypeId::of::<A>()).copied().unwrap_or_default()
+    }
+
+    /// Sets the coalescing rule for consecutive undo actions of type `A`.
+    ///
+    /// The type is the [`UndoAction`] type or the data type `D` for full ops registered using [`register_full_op`].
+    ///
+    /// [`register_full_op`]: Self::register_full_op
+    pub fn set_coalesce<A: Any>(&self, rule: UndoCoalesce) {
+        let mut sv = UNDO_SV.write();
+        if rule == UndoCoalesce::default() {
+            sv.coalesce.remove(&TypeId::of::<A>());
+        } else {
+            sv.coalesce.insert(TypeId::of::<A>(), rule);
+        }
+    }
+
+    /// Register an already executed action that can be saved, for undo in the current scope.
+    ///
+    /// The `op` implements undo and redo for the action described by `data`. See [`save_stack`] for more details.
+    ///
+    /// [`save_stack`]: Self::save_stack
+    pub fn register_persistent_op(&self, data: UndoActionData, op: impl FnMut(UndoOp) + Send + 'static) {
+        self.register(UndoRedoPersistentOp { data, op: Box::new(op) })
+    }
+
+    /// Run the `op` once with [`UndoOp::Redo`] and register it for undo in the current scope.
+    ///
+    /// The `op` implements undo and redo for the action described by `data`. See [`save_stack`] for more details.
+    ///
+    /// [`save_stack`]: Self::save_stack
+    pub fn run_persistent_op(&self, data: UndoActionData, op: impl FnMut(UndoOp) + Send + 'static) {
+        self.run(UndoRedoPersistentOp { data, op: Box::new(op) })
+    }
+
+    /// Register a closure that recreates the undo/redo op for saved actions of the `kind`.
+    ///
+    /// The `restore` closure is called by [`restore_stack`] for each saved action with the same [`UndoActionData::kind`],
+    /// it must return the `op` that implements undo and redo for the action or `None` if the action data is invalid.
+    ///
+    /// [`restore_stack`]: Self::restore_stack
+    pub fn register_restore(
+        &self,
+        kind: impl Into<Txt>,
+        restore: impl Fn(&UndoActionData) -> Option<Box<dyn FnMut(UndoOp) + Send>> + Send + Sync + 'static,
+    ) {
+        UNDO_SV.write().restore.insert(kind.into(), Arc::new(restore));
+    }
+
+    /// Save the undo and redo stacks of the current scope.
+    ///
+    /// Only actions that implement [`UndoAction::save`] and [`RedoAction::save`] are saved, if an action cannot be saved
+    /// only the actions after it are included, so that the restored stacks stay consistent. Actions registered using
+    /// [`register_persistent_op`] and groups of saveable actions can be saved.
+    ///
+    /// The data can be serialized with the document, and restored using [`restore_stack`] after the document
+    /// is reopened, so that undo survives app restart.
+    ///
+    /// [`register_persistent_op`]: Self::register_persistent_op
+    /// [`restore_stack`]: Self::restore_stack
+    pub fn save_stack(&self) -> UndoStackData {
+        let ctx = UNDO_SCOPE_CTX.get();
+        let mut undo = ctx.undo.lock();
+        let mut redo = ctx.redo.lock();
+
+        let newest = undo.last().map(|e| e.timestamp).unwrap_or(DInstant::EPOCH);
+        let mut undo_data = vec![];
+        for e in undo.iter_mut().rev() {
+            match e.action.save() {
+                Some(d) => undo_data.push((newest.saturating_duration_since(e.timestamp), d)),
+                None => break,
+            }
+        }
+        undo_data.reverse();
+
+        let newest = redo.first().map(|e| e.timestamp).unwrap_or(DInstant::EPOCH);
+        let mut redo_data = vec![];
+        for e in redo.iter_mut().rev() {
+            match e.action.save() {
+                Some(d) => redo_data.push((newest.saturating_duration_since(e.timestamp), d)),
+                None => break,
+            }
+        }
+        redo_data.reverse();
+
+        UndoStackData {
+            undo: undo_data,
+            redo: redo_data,
+        }
+    }
+
+    /// Replace the undo and redo stacks of the current scope with actions restored from `data`.
+    ///
+    /// Actions are recreated by the closures registered using [`register_restore`], if an action cannot be restored
+    /// only the actions after it are restored. Returns `true` if all actions where restored.
+    ///
+    /// The restored actions are timestamped relative to each other as they were when saved, so that
+    /// [`undo`] covers the same actions, as long as the app has run long enough to fit the timestamps.
+    ///
+    /// [`register_restore`]: Self::register_restore
+    /// [`undo`]: Self::undo
+    pub fn restore_stack(&self, data: UndoStackData) -> bool {
+        let anchor = INSTANT.now();
+        let anchor = anchor
+            .checked_sub(UNDO_INTERVAL_VAR.get().saturating_add(Duration::from_millis(1)))
+            .unwrap_or(DInstant::EPOCH);
+        let timestamp = |age: Duration| anchor.checked_sub(age).unwrap_or(DInstant::EPOCH);
+
+        let mut all = true;
+
+        let mut undo = vec![];
+        for (age, d) in data.undo.into_iter().rev() {
+            match UndoRedoPersistentOp::restore(d) {
+                Some(action) => {
+                    let timestamp = timestamp(age);
+                    undo.push(UndoEntry {
+                        timestamp,
+                        action: action.into_undo(timestamp),
+                    })
+                }
+                None => {
+                    all = false;
+                    break;
+                }
+            }
+        }
+        undo.reverse();
+
+        let mut redo = vec![];
+        for (age, d) in data.redo.into_iter().rev() {
+            match UndoRedoPersistentOp::restore(d) {
+                Some(action) => {
+                    let timestamp = timestamp(age);
+                    redo.push(RedoEntry {
+                        timestamp,
+                        action: action.into_redo(timestamp),
+                    })
+                }
+                None => {
+                    all = false;
+                    break;
+                }
+            }
+        }
+        redo.reverse();
+
+        let ctx = UNDO_SCOPE_CTX.get();
+        *ctx.undo.lock() = undo;
+        *ctx.redo.lock() = redo;
+
+        all
+    }
+}
+
+/// Coalescing rule for consecutive undo actions of the same type.
+///
+/// See [`UNDO.set_coalesce`] for more details.
+///
+/// [`UNDO.set_coalesce`]: UNDO::set_coalesce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoCoalesce {
+    /// Only [`UndoAction::merge`] decides.
+    ///
+    /// This is the default rule.
+    Merge {
+        /// Interval used to compute [`UndoActionMergeArgs::within_undo_interval`].
+        ///
+        /// If `None` uses the [`UNDO.undo_interval`].
+        ///
+        /// [`UNDO.undo_interval`]: UNDO::undo_interval
+        interval: Option<Duration>,
+    },
+    /// Never merge, the [`UndoAction::merge`] is not called.
+    Never,
+    /// Calls [`UndoAction::merge`], if the action does not merge coalesce the actions into a single group entry.
+    ///
+    /// Actions only coalesce if the previous action has the same type and was registered within the `interval`.
+    Group {
+        /// Maximum interval between actions.
+        ///
+        /// If `None` uses the [`UNDO.undo_interval`].
+        ///
+        /// [`UNDO.undo_interval`]: UNDO::undo_interval
+        interval: Option<Duration>,
+        /// Only coalesce if the [`UndoInfo::description`] of the actions is equal.
+        same_description: bool,
+    },
+}
+impl Default for UndoCoalesce {
+    fn default() -> Self {
+        Self::Merge { interval: None }
+    }
+}
+
+/// Saved undo action.
+///
+/// See [`UNDO.save_stack`] for more details.
+///
+/// [`UNDO.save_stack`]: UNDO::save_stack
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UndoActionData {
+    /// Identifies the closure that restores the action.
+    ///
+    /// See [`UNDO.register_restore`] for more details.
+    ///
+    /// [`UNDO.register_restore`]: UNDO::register_restore
+    pub kind: Txt,
+    /// Display description of the action.
+    pub description: Txt,
+    /// Action data, any format the action restore closure can parse.
+    pub data: Txt,
+    /// Saved actions of a group.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<UndoActionData>,
+}
+impl UndoActionData {
+    /// New action data.
+    pub fn new(kind: impl Into<Txt>, description: impl Into<Txt>, data: impl Into<Txt>) -> Self {
+        Self {
+            kind: kind.into(),
+            description: description.into(),
+            data: data.into(),
+            children: vec![],
+        }
+    }
+}
+
+/// Saved undo and redo stacks of an undo scope.
+///
+/// See [`UNDO.save_stack`] for more details.
+///
+/// [`UNDO.save_stack`]: UNDO::save_stack
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UndoStackData {
+    /// Saved undo actions and the time elapsed from the action registration to the newest action registration.
+    ///
+    /// The latest undo action is the last entry.
+    pub undo: Vec<(Duration, UndoActionData)>,
+    /// Saved redo actions and the time elapsed from the action registration to the newest action registration.
+    ///
+    /// The latest undone action is the last entry.
+    pub redo: Vec<(Duration, UndoActionData)>,
+}
+impl UndoStackData {
+    /// If no action was saved.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
 }
 
 /// Snapshot of the undo or redo stacks in an [`UNDO`] scope.
@@ -597,6 +878,15 @@ pub trait UndoAction: Send + Any {
     /// [`UNDO.undo_select`]: UNDO::undo_select
     /// [`UNDO.undo`]: UNDO::undo
     fn merge(self: Box<Self>, args: UndoActionMergeArgs) -> Result<Box<dyn UndoAction>, (Box<dyn UndoAction>, Box<dyn UndoAction>)>;
+
+    /// Gets the data that can restore the action.
+    ///
+    /// Is `None` by default. See [`UNDO.save_stack`] for more details.
+    ///
+    /// [`UNDO.save_stack`]: UNDO::save_stack
+    fn save(&mut self) -> Option<UndoActionData> {
+        None
+    }
 }
 
 /// Arguments for [`UndoAction::merge`].
@@ -621,6 +911,15 @@ pub trait RedoAction: Send + Any {
 
     /// Redo action and returns a [`UndoAction`] that undoes it.
     fn redo(self: Box<Self>) -> Box<dyn UndoAction>;
+
+    /// Gets the data that can restore the action.
+    ///
+    /// Is `None` by default. See [`UNDO.save_stack`] for more details.
+    ///
+    /// [`UNDO.save_stack`]: UNDO::save_stack
+    fn save(&mut self) -> Option<UndoActionData> {
+        None
+    }
 }
 
 /// Represents an undo/redo action.
@@ -714,6 +1013,7 @@ impl<'r> fmt::Debug for UndoFullOp<'r> {
 /// [`UNDO.transaction`]: UNDO::transaction
 #[must_use = "dropping the transaction undoes all captured actions"]
 pub struct UndoTransaction {
+    info: Option<Arc<dyn UndoInfo>>,
     undo: Vec<UndoEntry>,
 }
 impl UndoTransaction {
@@ -722,8 +1022,22 @@ impl UndoTransaction {
         self.undo.is_empty()
     }
 
+    /// Gets the transaction info, if it was created by [`UNDO.transaction_named`].
+    ///
+    /// [`UNDO.transaction_named`]: UNDO::transaction_named
+    pub fn info(&self) -> Option<&Arc<dyn UndoInfo>> {
+        self.info.as_ref()
+    }
+
     /// Push all undo actions captured by the transaction into the current undo scope.
+    ///
+    /// Named transactions push a single group action, the same as [`commit_group`] with the transaction info.
+    ///
+    /// [`commit_group`]: Self::commit_group
     pub fn commit(mut self) {
+        if let Some(info) = self.info.take() {
+            return self.commit_group(info);
+        }
         let mut undo = mem::take(&mut self.undo);
         let now = INSTANT.now();
         for u in &mut undo {
@@ -746,6 +1060,7 @@ impl UndoTransaction {
         UNDO.register(UndoGroup {
             info: info.into_dyn(),
             undo: mem::take(&mut self.undo),
+            coalesce_key: None,
         })
     }
 
@@ -888,6 +1203,7 @@ impl Default for WidgetUndoScope {
 
 struct UndoScope {
     id: Atomic<Option<WidgetId>>,
+    info: Option<Arc<dyn UndoInfo>>,
     undo: Mutex<Vec<UndoEntry>>,
     redo: Mutex<Vec<RedoEntry>>,
     enabled: AtomicBool,
@@ -896,6 +1212,7 @@ impl Default for UndoScope {
     fn default() -> Self {
         Self {
             id: Default::default(),
+            info: None,
             undo: Default::default(),
             redo: Default::default(),
             enabled: AtomicBool::new(true),
@@ -934,25 +1251,77 @@ impl UndoScope {
         }
     }
 
-    fn register(&self, action: Box<dyn UndoAction>) {
+    fn register(&self, mut action: Box<dyn UndoAction>) {
         self.with_enabled_undo_redo(|undo, redo| {
             let now = INSTANT.now();
             if let Some(prev) = undo.pop() {
+                let key = coalesce_key(&mut *action);
+                let (rule, undo_interval) = {
+                    let sv = UNDO_SV.read();
+                    (sv.coalesce.get(&key).copied().unwrap_or_default(), sv.undo_interval.get())
+                };
+                let elapsed = now.duration_since(prev.timestamp);
+
+                let (interval, group) = match rule {
+                    UndoCoalesce::Merge { interval } => (interval, None),
+                    UndoCoalesce::Never => {
+                        undo.push(prev);
+                        undo.push(UndoEntry { timestamp: now, action });
+                        redo.clear();
+                        return;
+                    }
+                    UndoCoalesce::Group {
+                        interval,
+                        same_description,
+                    } => (interval, Some(same_description)),
+                };
+                let within_interval = elapsed <= interval.unwrap_or(undo_interval);
+
                 match prev.action.merge(UndoActionMergeArgs {
                     next: action,
                     prev_timestamp: prev.timestamp,
-                    within_undo_interval: now.duration_since(prev.timestamp) <= UNDO_SV.read().undo_interval.get(),
+                    within_undo_interval: within_interval,
                 }) {
                     Ok(merged) => undo.push(UndoEntry {
                         timestamp: now,
                         action: merged,
                     }),
-                    Err((p, action)) => {
-                        undo.push(UndoEntry {
-                            timestamp: prev.timestamp,
-                            action: p,
-                        });
-                        undo.push(UndoEntry { timestamp: now, action });
+                    Err((mut p, mut action)) => {
+                        let coalesce = match group {
+                            Some(same_description) => {
+                                within_interval
+                                    && coalesce_key(&mut *p) == key
+                                    && (!same_description || p.info().description() == action.info().description())
+                            }
+                            None => false,
+                        };
+                        if coalesce {
+                            let next = UndoEntry { timestamp: now, action };
+                            let action: Box<dyn UndoAction> = match p.as_any().downcast_mut::<UndoGroup>() {
+                                Some(g) if g.coalesce_key == Some(key) => {
+                                    g.undo.push(next);
+                                    p
+                                }
+                                _ => Box::new(UndoGroup {
+                                    info: p.info(),
+                                    undo: vec![
+                                        UndoEntry {
+                                            timestamp: prev.timestamp,
+                                            action: p,
+                                        },
+                                        next,
+                                    ],
+                                    coalesce_key: Some(key),
+                                }),
+                            };
+                            undo.push(UndoEntry { timestamp: now, action });
+                        } else {
+                            undo.push(UndoEntry {
+                                timestamp: prev.timestamp,
+                                action: p,
+                            });
+                            undo.push(UndoEntry { timestamp: now, action });
+                        }
                     }
                 }
             } else {
@@ -1023,9 +1392,25 @@ struct RedoEntry {
     pub action: Box<dyn RedoAction>,
 }
 
+/// Gets the type that identifies the action for [`UNDO.coalesce`].
+fn coalesce_key(action: &mut dyn UndoAction) -> TypeId {
+    let action = action.as_any();
+    if let Some(op) = action.downcast_mut::<UndoRedoFullOp>() {
+        return (*op.data).type_id();
+    }
+    if let Some(key) = action.downcast_mut::<UndoGroup>().and_then(|g| g.coalesce_key) {
+        return key;
+    }
+    (*action).type_id()
+}
+
+/// Group action kind in [`UndoActionData`].
+const GROUP_KIND: &str = "zng-undo-group";
+
 struct UndoGroup {
     info: Arc<dyn UndoInfo>,
     undo: Vec<UndoEntry>,
+    coalesce_key: Option<TypeId>,
 }
 impl UndoAction for UndoGroup {
     fn undo(self: Box<Self>) -> Box<dyn RedoAction> {
@@ -1036,7 +1421,19 @@ impl UndoAction for UndoGroup {
                 action: undo.action.undo(),
             });
         }
-        Box::new(RedoGroup { info: self.info, redo })
+        Box::new(RedoGroup {
+            info: self.info,
+            redo,
+            coalesce_key: self.coalesce_key,
+        })
+    }
+
+    fn save(&mut self) -> Option<UndoActionData> {
+        let mut data = UndoActionData::new(GROUP_KIND, self.info.description(), "");
+        for u in &mut self.undo {
+            data.children.push(u.action.save()?);
+        }
+        Some(data)
     }
 
     fn info(&mut self) -> Arc<dyn UndoInfo> {
@@ -1054,6 +1451,7 @@ impl UndoAction for UndoGroup {
 struct RedoGroup {
     info: Arc<dyn UndoInfo>,
     redo: Vec<RedoEntry>,
+    coalesce_key: Option<TypeId>,
 }
 impl RedoAction for RedoGroup {
     fn redo(self: Box<Self>) -> Box<dyn UndoAction> {
@@ -1064,7 +1462,20 @@ impl RedoAction for RedoGroup {
                 action: redo.action.redo(),
             });
         }
-        Box::new(UndoGroup { info: self.info, undo })
+        Box::new(UndoGroup {
+            info: self.info,
+            undo,
+            coalesce_key: self.coalesce_key,
+        })
+    }
+
+    fn save(&mut self) -> Option<UndoActionData> {
+        // saved in undo order
+        let mut data = UndoActionData::new(GROUP_KIND, self.info.description(), "");
+        for r in self.redo.iter_mut().rev() {
+            data.children.push(r.action.save()?);
+        }
+        Some(data)
     }
 
     fn info(&mut self) -> Arc<dyn UndoInfo> {
@@ -1112,12 +1523,12 @@ struct UndoRedoFullOp {
 impl UndoAction for UndoRedoFullOp {
     fn info(&mut self) -> Arc<dyn UndoInfo> {
         let mut info = None;
-        (self.op)(&mut self.data, UndoFullOp::Info { info: &mut info });
+        (self.op)(&mut *self.data, UndoFullOp::Info { info: &mut info });
         info.unwrap_or_else(|| Arc::new("action"))
     }
 
     fn undo(mut self: Box<Self>) -> Box<dyn RedoAction> {
-        (self.op)(&mut self.data, UndoFullOp::Op(UndoOp::Undo));
+        (self.op)(&mut *self.data, UndoFullOp::Op(UndoOp::Undo));
         self
     }
 
@@ -1128,9 +1539,9 @@ impl UndoAction for UndoRedoFullOp {
         if let Some(u) = args.next.as_any().downcast_mut::<Self>() {
             let mut merged = false;
             (self.op)(
-                &mut self.data,
+                &mut *self.data,
                 UndoFullOp::Merge {
-                    next_data: &mut u.data,
+                    next_data: &mut *u.data,
                     prev_timestamp: args.prev_timestamp,
                     within_undo_interval: args.within_undo_interval,
                     merged: &mut merged,
@@ -1153,19 +1564,123 @@ impl UndoAction for UndoRedoFullOp {
 impl RedoAction for UndoRedoFullOp {
     fn info(&mut self) -> Arc<dyn UndoInfo> {
         let mut info = None;
-        (self.op)(&mut self.data, UndoFullOp::Info { info: &mut info });
+        (self.op)(&mut *self.data, UndoFullOp::Info { info: &mut info });
         info.unwrap_or_else(|| Arc::new("action"))
     }
 
     fn redo(mut self: Box<Self>) -> Box<dyn UndoAction> {
-        (self.op)(&mut self.data, UndoFullOp::Op(UndoOp::Redo));
+        (self.op)(&mut *self.data, UndoFullOp::Op(UndoOp::Redo));
+        self
+    }
+}
+
+struct UndoRedoPersistentOp {
+    data: UndoActionData,
+    op: Box<dyn FnMut(UndoOp) + Send>,
+}
+impl UndoRedoPersistentOp {
+    fn restore(data: UndoActionData) -> Option<UndoGroupOrOp> {
+        if data.kind == GROUP_KIND {
+            let mut undo = Vec::with_capacity(data.children.len());
+            for c in data.children {
+                undo.push(Self::restore(c)?);
+            }
+            Some(UndoGroupOrOp::Group {
+                info: Arc::new(data.description),
+                undo,
+            })
+        } else {
+            let restore = UNDO_SV.read().restore.get(&data.kind).cloned()?;
+            let op = restore(&data)?;
+            Some(UndoGroupOrOp::Op(Self { data, op }))
+        }
+    }
+}
+impl UndoAction for UndoRedoPersistentOp {
+    fn undo(mut self: Box<Self>) -> Box<dyn RedoAction> {
+        (self.op)(UndoOp::Undo);
+        self
+    }
+
+    fn info(&mut self) -> Arc<dyn UndoInfo> {
+        Arc::new(self.data.description.clone())
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn merge(self: Box<Self>, args: UndoActionMergeArgs) -> Result<Box<dyn UndoAction>, (Box<dyn UndoAction>, Box<dyn UndoAction>)> {
+        Err((self, args.next))
+    }
+
+    fn save(&mut self) -> Option<UndoActionData> {
+        Some(self.data.clone())
+    }
+}
+impl RedoAction for UndoRedoPersistentOp {
+    fn redo(mut self: Box<Self>) -> Box<dyn UndoAction> {
+        (self.op)(UndoOp::Redo);
         self
     }
+
+    fn info(&mut self) -> Arc<dyn UndoInfo> {
+        Arc::new(self.data.description.clone())
+    }
+
+    fn save(&mut self) -> Option<UndoActionData> {
+        Some(self.data.clone())
+    }
+}
+
+/// Restored action.
+enum UndoGroupOrOp {
+    Op(UndoRedoPersistentOp),
+    Group { info: Arc<dyn UndoInfo>, undo: Vec<UndoGroupOrOp> },
+}
+impl UndoGroupOrOp {
+    fn into_undo(self, timestamp: DInstant) -> Box<dyn UndoAction> {
+        match self {
+            UndoGroupOrOp::Op(op) => Box::new(op),
+            UndoGroupOrOp::Group { info, undo } => Box::new(UndoGroup {
+                info,
+                undo: undo
+                    .into_iter()
+                    .map(|a| UndoEntry {
+                        timestamp,
+                        action: a.into_undo(timestamp),
+                    })
+                    .collect(),
+                coalesce_key: None,
+            }),
+        }
+    }
+
+    fn into_redo(self, timestamp: DInstant) -> Box<dyn RedoAction> {
+        match self {
+            UndoGroupOrOp::Op(op) => Box::new(op),
+            UndoGroupOrOp::Group { info, undo } => Box::new(RedoGroup {
+                info,
+                // redo runs in reverse
+                redo: undo
+                    .into_iter()
+                    .rev()
+                    .map(|a| RedoEntry {
+                        timestamp,
+                        action: a.into_redo(timestamp),
+                    })
+                    .collect(),
+                coalesce_key: None,
+            }),
+        }
+    }
 }
 
 struct UndoService {
     undo_limit: BoxedVar<u32>,
     undo_interval: BoxedVar<Duration>,
+    coalesce: HashMap<TypeId, UndoCoalesce>,
+    restore: HashMap<Txt, Arc<dyn Fn(&UndoActionData) -> Option<Box<dyn FnMut(UndoOp) + Send>> + Send + Sync>>,
 }
 
 impl Default for UndoService {
@@ -1173,6 +1688,8 @@ impl Default for UndoService {
         Self {
             undo_limit: var(u32::MAX).boxed(),
             undo_interval: KEYBOARD.repeat_config().map(|c| c.start_delay + c.interval).cow().boxed(),
+            coalesce: HashMap::new(),
+            restore: HashMap::new(),
         }
     }
 }
@@ -1379,6 +1896,7 @@ impl UndoSelect for UndoSelectLtEq {
 #[cfg(test)]
 mod tests {
     use zng_app::APP;
+    use zng_txt::{formatx, ToTxt as _};
 
     use super::*;
 
@@ -1509,6 +2027,121 @@ mod tests {
         assert_eq!(&[1, 2], &data.lock()[..]);
     }
 
+    #[test]
+    fn transaction_named() {
+        let _a = APP.minimal();
+        let data = Arc::new(Mutex::new(vec![]));
+
+        let t = UNDO.transaction_named("push 1, 2", || {
+            assert_eq!(UNDO.transaction_info().unwrap().description(), "push 1, 2");
+            push_1_2(&data);
+        });
+        assert!(UNDO.transaction_info().is_none());
+
+        t.commit();
+        assert_eq!(UNDO.undo_stack().stack.len(), 1);
+
+        UNDO.undo_select(1);
+        assert_eq!(&[] as &[u8], &data.lock()[..]);
+
+        UNDO.redo_select(1);
+        assert_eq!(&[1, 2], &data.lock()[..]);
+    }
+
+    #[test]
+    fn coalesce_group() {
+        let _a = APP.minimal();
+        let data = Arc::new(Mutex::new(vec![]));
+
+        UNDO.set_coalesce::<PushAction>(UndoCoalesce::Group {
+            interval: Some(Duration::MAX),
+            same_description: true,
+        });
+
+        for item in 1..=3 {
+            UNDO.register(PushAction { data: data.clone(), item });
+            data.lock().push(item);
+        }
+        assert_eq!(UNDO.undo_stack().stack.len(), 1);
+
+        UNDO.undo_select(1);
+        assert_eq!(&[] as &[u8], &data.lock()[..]);
+
+        UNDO.redo_select(1);
+        assert_eq!(&[1, 2, 3], &data.lock()[..]);
+    }
+
+    #[test]
+    fn coalesce_never() {
+        let _a = APP.minimal();
+
+        UNDO.set_coalesce::<u8>(UndoCoalesce::Never);
+
+        for _ in 0..2 {
+            UNDO.register_full_op(0u8, |_, op| {
+                if let UndoFullOp::Merge { merged, .. } = op {
+                    *merged = true;
+                }
+            });
+        }
+        assert_eq!(UNDO.undo_stack().stack.len(), 2);
+    }
+
+    fn push_persistent(data: &Arc<Mutex<Vec<u8>>>, item: u8) {
+        UNDO.run_persistent_op(
+            UndoActionData::new("push", formatx!("push {item}"), item.to_txt()),
+            push_op(data.clone(), item),
+        );
+    }
+
+    fn push_op(data: Arc<Mutex<Vec<u8>>>, item: u8) -> Box<dyn FnMut(UndoOp) + Send> {
+        Box::new(move |op| match op {
+            UndoOp::Undo => assert_eq!(data.lock().pop(), Some(item)),
+            UndoOp::Redo => data.lock().push(item),
+        })
+    }
+
+    #[test]
+    fn save_restore() {
+        let _a = APP.minimal();
+        let data = Arc::new(Mutex::new(vec![]));
+
+        push_persistent(&data, 1);
+        UNDO.group("push 2, 3", || {
+            push_persistent(&data, 2);
+            push_persistent(&data, 3);
+        });
+        push_persistent(&data, 4);
+        UNDO.undo_select(1);
+        assert_eq!(&[1, 2, 3], &data.lock()[..]);
+
+        let saved = UNDO.save_stack();
+        assert_eq!(saved.undo.len(), 2);
+        assert_eq!(saved.redo.len(), 1);
+
+        UNDO.clear();
+        UNDO.register_restore(
+            "push",
+            clmv!(data, |d| {
+                let item = d.data.parse().ok()?;
+                Some(push_op(data.clone(), item))
+            }),
+        );
+        assert!(UNDO.restore_stack(saved));
+
+        UNDO.redo_select(1);
+        assert_eq!(&[1, 2, 3, 4], &data.lock()[..]);
+
+        UNDO.undo_select(2);
+        assert_eq!(&[1], &data.lock()[..]);
+
+        UNDO.undo_select(1);
+        assert_eq!(&[] as &[u8], &data.lock()[..]);
+
+        UNDO.redo_select(2);
+        assert_eq!(&[1, 2, 3], &data.lock()[..]);
+    }
+
     fn push_1_sleep_2(data: &Arc<Mutex<Vec<u8>>>) {
         UNDO.run_op(
             "push 1",
@@ -3,16 +3,18 @@
 use std::{mem, sync::Arc};
 
 use zng_app::{
-    access::{ACCESS_DEINITED_EVENT, ACCESS_INITED_EVENT},
+    access::{ACCESS_ANNOUNCE_EVENT, ACCESS_DEINITED_EVENT, ACCESS_INITED_EVENT},
     app_hn_once,
-    event::{AnyEventArgs, CommandHandle},
+    event::{AnyEventArgs, Command, CommandHandle, CommandNameExt as _},
     render::{FrameBuilder, FrameUpdate},
+    shortcut::CommandShortcutExt as _,
     timer::TIMERS,
     update::{EventUpdate, InfoUpdates, LayoutUpdates, RenderUpdates, WidgetUpdates, UPDATES},
     view_process::{
         raw_events::{
-            RawWindowFocusArgs, RAW_COLORS_CONFIG_CHANGED_EVENT, RAW_FRAME_RENDERED_EVENT, RAW_HEADLESS_OPEN_EVENT, RAW_IME_EVENT,
-            RAW_WINDOW_CHANGED_EVENT, RAW_WINDOW_FOCUS_EVENT, RAW_WINDOW_OPEN_EVENT, RAW_WINDOW_OR_HEADLESS_OPEN_ERROR_EVENT,
+            RawWindowFocusArgs, RAW_APP_MENU_ITEM_CLICKED_EVENT, RAW_COLORS_CONFIG_CHANGED_EVENT, RAW_FRAME_PRESENTED_EVENT,
+            RAW_FRAME_RENDERED_EVENT, RAW_HEADLESS_OPEN_EVENT, RAW_IME_EVENT, RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT, RAW_WINDOW_CHANGED_EVENT,
+            RAW_WINDOW_FOCUS_EVENT, RAW_WINDOW_OPEN_EVENT, RAW_WINDOW_OR_HEADLESS_OPEN_ERROR_EVENT,
         },
         ViewHeadless, ViewRenderer, ViewWindow, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
     },
@@ -34,21 +36,21 @@ use zng_layout::{
         PxSize, PxToDip, PxVector, TimeUnits,
     },
 };
-use zng_var::{AnyVar, ReadOnlyArcVar, Var, VarHandle, VarHandles};
+use zng_var::{AnyVar, ArcVar, ReadOnlyArcVar, Var, VarHandle, VarHandles};
 use zng_view_api::{
     config::{ColorScheme, FontAntiAliasing},
     window::{
-        EventCause, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, FrameWaitId, HeadlessRequest, RenderMode, WindowRequest,
-        WindowState, WindowStateAll,
+        EventCause, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, FrameWaitId, HeadlessRequest, HitTestKind, RenderMode,
+        WindowRequest, WindowState, WindowStateAll,
     },
     Ime, ViewProcessOffline,
 };
 
 use crate::{
     cmd::{WindowCommands, MINIMIZE_CMD, RESTORE_CMD},
-    AutoSize, FrameCaptureMode, FrameImageReadyArgs, HeadlessMonitor, MonitorInfo, StartPosition, WidgetInfoImeArea, WindowChangedArgs,
-    WindowIcon, WindowRoot, WindowVars, FRAME_IMAGE_READY_EVENT, MONITORS, MONITORS_CHANGED_EVENT, WINDOWS, WINDOW_CHANGED_EVENT,
-    WINDOW_FOCUS,
+    AutoSize, FrameCaptureMode, FrameImageReadyArgs, FramePresentedArgs, HeadlessMonitor, MonitorInfo, StartPosition, WidgetInfoImeArea,
+    WindowChangedArgs, WindowIcon, WindowRoot, WindowVars, FRAME_IMAGE_READY_EVENT, FRAME_PRESENTED_EVENT, MONITORS,
+    MONITORS_CHANGED_EVENT, WINDOWS, WINDOW_CHANGED_EVENT, WINDOW_FOCUS,
 };
 
 struct ImageResources {
@@ -108,6 +110,8 @@ struct HeadedCtrl {
     cancel_ime_handle: CommandHandle,
     open_title_menu_handle: CommandHandle,
     drag_move_handle: CommandHandle,
+    system_menu_cmds: Vec<Command>,
+    app_menu_cmds: Vec<Command>,
 }
 impl HeadedCtrl {
     pub fn new(vars: &WindowVars, commands: WindowCommands, content: WindowRoot) -> Self {
@@ -140,9 +144,45 @@ impl HeadedCtrl {
             cancel_ime_handle: CommandHandle::dummy(),
             open_title_menu_handle: CommandHandle::dummy(),
             drag_move_handle: CommandHandle::dummy(),
+            system_menu_cmds: vec![],
+            app_menu_cmds: vec![],
         }
     }
 
+    /// Build the view-process system menu and the commands list that maps the clicked item IDs.
+    fn system_menu_view(&mut self) -> zng_view_api::window::SystemMenu {
+        self.system_menu_cmds.clear();
+        self.vars.system_menu().with(|m| m.to_view(&mut self.system_menu_cmds))
+    }
+
+    /// Build the view-process app menu and the commands list that maps the clicked item IDs.
+    fn app_menu_view(&mut self) -> zng_view_api::window::AppMenu {
+        self.app_menu_cmds.clear();
+        self.vars.app_menu().with(|m| m.to_view(&mut self.app_menu_cmds))
+    }
+
+    /// Send the app menu and update the exported status.
+    fn send_app_menu(view: &ViewWindow, menu: zng_view_api::window::AppMenu, is_exported: ArcVar<bool>) {
+        let is_empty = menu.is_empty();
+        match view.set_app_menu(menu) {
+            Ok(Ok(())) => is_exported.set(!is_empty),
+            Ok(Err(e)) => {
+                if !matches!(e, zng_view_api::window::AppMenuError::NotSupported) {
+                    tracing::error!("cannot export app menu, {e}");
+                }
+                is_exported.set(false);
+            }
+            Err(_) => {} // view-process respawning, sent again on open
+        }
+    }
+
+    fn hit_test_regions_px(&self) -> Vec<(PxRect, HitTestKind)> {
+        let scale_factor = self.vars.scale_factor().get();
+        self.vars
+            .hit_test_regions()
+            .with(|r| r.iter().map(|(r, k)| (r.to_px(scale_factor), *k)).collect())
+    }
+
     fn update_gen(&mut self, update: impl FnOnce(&ViewWindow) + Send + 'static) {
         if let Some(view) = &self.window {
             // view is online, just update.
@@ -307,6 +347,45 @@ impl HeadedCtrl {
                 })
             }
 
+            if self.vars.system_menu().is_new() || self.system_menu_cmds.iter().any(|c| c.name().is_new() || c.is_enabled().is_new()) {
+                let menu = self.system_menu_view();
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_system_menu(menu);
+                })
+            }
+
+            if self.vars.app_menu().is_new()
+                || self
+                    .app_menu_cmds
+                    .iter()
+                    .any(|c| c.name().is_new() || c.is_enabled().is_new() || c.shortcut().is_new())
+            {
+                let menu = self.app_menu_view();
+                let is_exported = self.vars.0.is_app_menu_exported.clone();
+                self.update_gen(move |view| {
+                    Self::send_app_menu(view, menu, is_exported);
+                })
+            }
+
+            if let Some(enabled) = self.vars.continuous_render().get_new() {
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_continuous_render(enabled);
+                })
+            }
+
+            if let Some(enabled) = self.vars.present_notify().get_new() {
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_present_notify(enabled);
+                })
+            }
+
+            if self.vars.hit_test_regions().is_new() || self.vars.scale_factor().is_new() {
+                let regions = self.hit_test_regions_px();
+                self.update_gen(move |view| {
+                    let _: Ignore = view.set_hit_test_regions(regions);
+                })
+            }
+
             if let Some(reason) = self.vars.system_shutdown_warn().get_new() {
                 self.update_gen(move |view| {
                     let _: Ignore = view.set_system_shutdown_warn(reason);
@@ -487,9 +566,9 @@ impl HeadedCtrl {
             });
         }
 
-        if let Some(top) = self.vars.always_on_top().get_new() {
+        if let Some(layer) = self.vars.layer().get_new() {
             self.update_gen(move |view| {
-                let _: Ignore = view.set_always_on_top(top);
+                let _: Ignore = view.set_layer(layer);
             });
         }
 
@@ -709,6 +788,7 @@ impl HeadedCtrl {
                 self.drag_move_handle = super::cmd::DRAG_MOVE_RESIZE_CMD.scoped(WINDOW.id()).subscribe(true);
 
                 self.vars.0.render_mode.set(args.data.render_mode);
+                self.vars.0.actual_color_space.set(args.data.color_space);
                 self.vars.state().set(args.data.state.state);
                 self.actual_state = Some(args.data.state.state);
                 self.vars.0.restore_state.set(args.data.state.restore_state);
@@ -735,9 +815,29 @@ impl HeadedCtrl {
                     .or_else(|| self.parent_accent_color.as_ref().map(|t| t.get()))
                     .unwrap_or_else(|| WINDOWS.system_colors_config().accent.into());
                 self.vars.0.actual_accent_color.set(accent);
+                self.vars.0.actual_high_contrast.set(WINDOWS.system_colors_config().high_contrast);
 
                 UPDATES.layout_window(args.window_id).render_window(args.window_id);
 
+                let regions = self.hit_test_regions_px();
+                if !regions.is_empty() {
+                    let _: Ignore = args.window.set_hit_test_regions(regions);
+                }
+                if self.vars.continuous_render().get() {
+                    let _: Ignore = args.window.set_continuous_render(true);
+                }
+                if self.vars.present_notify().get() {
+                    let _: Ignore = args.window.set_present_notify(true);
+                }
+                let menu = self.system_menu_view();
+                if !menu.is_default() {
+                    let _: Ignore = args.window.set_system_menu(menu);
+                }
+                let menu = self.app_menu_view();
+                if !menu.is_empty() {
+                    Self::send_app_menu(&args.window, menu, self.vars.0.is_app_menu_exported.clone());
+                }
+
                 for update in mem::take(&mut self.delayed_view_updates) {
                     update(&args.window);
                 }
@@ -757,6 +857,33 @@ impl HeadedCtrl {
                 .or_else(|| self.parent_accent_color.as_ref().map(|t| t.get()))
                 .unwrap_or_else(|| args.config.accent.into());
             self.vars.0.actual_accent_color.set(color);
+            self.vars.0.actual_high_contrast.set(args.config.high_contrast);
+        } else if let Some(args) = ACCESS_ANNOUNCE_EVENT.on(update) {
+            let is_target = match args.window_id {
+                Some(id) => id == WINDOW.id(),
+                None => WINDOWS.is_focused(WINDOW.id()).unwrap_or(false),
+            };
+            if is_target && self.vars.0.access_enabled.get().contains(AccessEnabled::VIEW) {
+                if let Some(view) = &self.window {
+                    let _: Ignore = view.access_announce(args.txt.clone(), args.priority);
+                }
+            }
+        } else if let Some(args) = RAW_SYSTEM_MENU_ITEM_CLICKED_EVENT.on(update) {
+            if args.window_id == WINDOW.id() {
+                if let Some(cmd) = args.item.parse::<usize>().ok().and_then(|i| self.system_menu_cmds.get(i)) {
+                    if cmd.is_enabled().get() {
+                        cmd.notify();
+                    }
+                }
+            }
+        } else if let Some(args) = RAW_APP_MENU_ITEM_CLICKED_EVENT.on(update) {
+            if args.window_id == WINDOW.id() {
+                if let Some(cmd) = args.item.parse::<usize>().ok().and_then(|i| self.app_menu_cmds.get(i)) {
+                    if cmd.is_enabled().get() {
+                        cmd.notify();
+                    }
+                }
+            }
         } else if let Some(args) = RAW_WINDOW_OR_HEADLESS_OPEN_ERROR_EVENT.on(update) {
             let w_id = WINDOW.id();
             if args.window_id == w_id && self.window.is_none() && self.waiting_view {
@@ -1128,7 +1255,7 @@ impl HeadedCtrl {
             video_mode: self.vars.video_mode().get(),
             visible: self.vars.visible().get(),
             taskbar_visible: self.vars.taskbar_visible().get(),
-            always_on_top: self.vars.always_on_top().get(),
+            layer: self.vars.layer().get(),
             movable: self.vars.movable().get(),
             resizable: self.vars.resizable().get(),
             enabled_buttons: self.vars.enabled_buttons().get(),
@@ -1147,6 +1274,7 @@ impl HeadedCtrl {
             transparent: self.transparent,
             capture_mode: matches!(self.vars.frame_capture_mode().get(), FrameCaptureMode::All),
             render_mode: self.render_mode.unwrap_or_else(|| WINDOWS.default_render_mode().get()),
+            color_space: self.vars.color_space().get(),
 
             focus: self.start_focused,
             focus_indicator: self.vars.focus_indicator().get(),
@@ -1254,7 +1382,7 @@ impl HeadedCtrl {
             video_mode: self.vars.video_mode().get(),
             visible: self.vars.visible().get(),
             taskbar_visible: self.vars.taskbar_visible().get(),
-            always_on_top: self.vars.always_on_top().get(),
+            layer: self.vars.layer().get(),
             movable: self.vars.movable().get(),
             resizable: self.vars.resizable().get(),
             enabled_buttons: self.vars.enabled_buttons().get(),
@@ -1273,6 +1401,7 @@ impl HeadedCtrl {
             transparent: self.transparent,
             capture_mode: matches!(self.vars.frame_capture_mode().get(), FrameCaptureMode::All),
             render_mode: self.render_mode.unwrap_or_else(|| WINDOWS.default_render_mode().get()),
+            color_space: self.vars.color_space().get(),
 
             focus: WINDOWS.is_focused(WINDOW.id()).unwrap_or(false),
             focus_indicator: self.vars.focus_indicator().get(),
@@ -2019,6 +2148,18 @@ impl ContentCtrl {
                 let args = FrameImageReadyArgs::new(args.timestamp, args.propagation().clone(), args.window_id, args.frame_id, image);
                 FRAME_IMAGE_READY_EVENT.notify(args);
             }
+        } else if let Some(args) = RAW_FRAME_PRESENTED_EVENT.on(update) {
+            if args.window_id == WINDOW.id() {
+                let args = FramePresentedArgs::new(
+                    args.timestamp,
+                    args.propagation().clone(),
+                    args.window_id,
+                    args.frame_id,
+                    args.presented_at,
+                    args.refresh_interval,
+                );
+                FRAME_PRESENTED_EVENT.notify(args);
+            }
         } else {
             self.commands.event(&self.vars, update);
         }
@@ -11,7 +11,7 @@ use zng_layout::unit::{Dip, DipRect, DipSize, DipToPx, Factor, FactorUnits, Ppi,
 use zng_txt::{ToTxt, Txt};
 use zng_unique_id::IdMap;
 use zng_var::{impl_from_and_into_var, var, ArcVar, ReadOnlyArcVar, Var, VarValue};
-use zng_view_api::window::VideoMode;
+use zng_view_api::{image::ColorSpace, ipc::IpcBytes, window::VideoMode};
 
 use crate::WINDOWS;
 
@@ -120,7 +120,11 @@ impl MonitorsService {
     pub(super) fn on_pre_event(update: &EventUpdate) {
         if let Some(args) = RAW_SCALE_FACTOR_CHANGED_EVENT.on(update) {
             if let Some(m) = MONITORS_SV.read().monitors.get(&args.monitor_id) {
-                m.scale_factor.set(args.scale_factor);
+                if m.scale_factor.get() != args.scale_factor {
+                    m.scale_factor.set(args.scale_factor);
+                    let args = MonitorsChangedArgs::new(args.timestamp, args.propagation().clone(), vec![], vec![], vec![args.monitor_id]);
+                    MONITORS_CHANGED_EVENT.notify(args);
+                }
             }
         } else if let Some(args) = RAW_MONITORS_CHANGED_EVENT.on(update) {
             MONITORS_SV.write().on_monitors_changed(args);
@@ -219,8 +223,11 @@ pub struct MonitorInfo {
     name: ArcVar<Txt>,
     position: ArcVar<PxPoint>,
     size: ArcVar<PxSize>,
+    work_area: ArcVar<PxRect>,
     video_modes: ArcVar<Vec<VideoMode>>,
     scale_factor: ArcVar<Factor>,
+    color_space: ArcVar<ColorSpace>,
+    icc_profile: ArcVar<Option<IpcBytes>>,
     ppi: ArcVar<Ppi>,
 }
 impl fmt::Debug for MonitorInfo {
@@ -237,8 +244,11 @@ impl MonitorInfo {
             name: var(info.name.to_txt()),
             position: var(info.position),
             size: var(info.size),
+            work_area: var(info.work_area),
             scale_factor: var(info.scale_factor),
             video_modes: var(info.video_modes),
+            color_space: var(info.color_space),
+            icc_profile: var(info.icc_profile),
             ppi: var(Ppi::default()),
         }
     }
@@ -256,11 +266,19 @@ impl MonitorInfo {
             | check_set(&self.name, info.name.to_txt())
             | check_set(&self.position, info.position)
             | check_set(&self.size, info.size)
+            | check_set(&self.work_area, info.work_area)
             | check_set(&self.scale_factor, info.scale_factor)
             | check_set(&self.video_modes, info.video_modes)
+            | check_set(&self.color_space, info.color_space)
+            | check_set(&self.icc_profile, info.icc_profile)
     }
 
     /// Unique ID.
+    ///
+    /// The ID is stable for the app process lifetime, if a monitor is disconnected and connected again, or if the view-process
+    /// respawns, the monitor gets the same ID if it has the same [`name`].
+    ///
+    /// [`name`]: Self::name
     pub fn id(&self) -> MonitorId {
         self.id
     }
@@ -282,6 +300,12 @@ impl MonitorInfo {
     pub fn size(&self) -> ReadOnlyArcVar<PxSize> {
         self.size.read_only()
     }
+    /// Area of the monitor region not covered by system bars (taskbar, dock), in the virtual screen, in pixels.
+    ///
+    /// Is the full monitor region if the system does not provide this info.
+    pub fn work_area(&self) -> ReadOnlyArcVar<PxRect> {
+        self.work_area.read_only()
+    }
 
     /// Exclusive fullscreen video modes.
     pub fn video_modes(&self) -> ReadOnlyArcVar<Vec<VideoMode>> {
@@ -294,6 +318,23 @@ impl MonitorInfo {
     pub fn scale_factor(&self) -> ReadOnlyArcVar<Factor> {
         self.scale_factor.read_only()
     }
+
+    /// The monitor color space, classified from the [`icc_profile`].
+    ///
+    /// Is [`ColorSpace::Unknown`] if the system does not provide monitor color profiles.
+    ///
+    /// [`icc_profile`]: Self::icc_profile
+    pub fn color_space(&self) -> ReadOnlyArcVar<ColorSpace> {
+        self.color_space.read_only()
+    }
+
+    /// The monitor ICC color profile data, if provided by the system.
+    ///
+    /// Can update if the user changes system settings.
+    pub fn icc_profile(&self) -> ReadOnlyArcVar<Option<IpcBytes>> {
+        self.icc_profile.read_only()
+    }
+
     /// Pixel-per-inch config var.
     pub fn ppi(&self) -> ArcVar<Ppi> {
         self.ppi.clone()
@@ -329,8 +370,11 @@ impl MonitorInfo {
             name: var("<fallback>".into()),
             position: var(PxPoint::zero()),
             size: var(defaults.size.to_px(fct)),
+            work_area: var(PxRect::from_size(defaults.size.to_px(fct))),
             video_modes: var(vec![]),
             scale_factor: var(fct),
+            color_space: var(ColorSpace::Unknown),
+            icc_profile: var(None),
             ppi: var(Ppi::default()),
         }
     }
@@ -428,7 +472,8 @@ event_args! {
 
         /// Modified monitors.
         ///
-        /// The monitor metadata is tracked using variables that are now flagged new.
+        /// The monitor metadata is tracked using variables that are now flagged new. This includes
+        /// (fractional) scale factor changes.
         pub modified: Vec<MonitorId>,
 
         ..
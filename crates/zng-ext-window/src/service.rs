@@ -9,8 +9,8 @@ use zng_app::{
     view_process::{
         self,
         raw_events::{
-            RAW_COLORS_CONFIG_CHANGED_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT, RAW_WINDOW_CLOSE_EVENT,
-            RAW_WINDOW_CLOSE_REQUESTED_EVENT, RAW_WINDOW_FOCUS_EVENT,
+            RAW_APP_DRAG_ENDED_EVENT, RAW_COLORS_CONFIG_CHANGED_EVENT, RAW_IMAGE_LOADED_EVENT, RAW_IMAGE_LOAD_ERROR_EVENT,
+            RAW_WINDOW_CLOSE_EVENT, RAW_WINDOW_CLOSE_REQUESTED_EVENT, RAW_WINDOW_FOCUS_EVENT,
         },
         ViewImage, ViewRenderer, ViewWindowOrHeadless, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT,
     },
@@ -24,7 +24,7 @@ use zng_app::{
 };
 use zng_app_context::app_local;
 
-use zng_color::{colors::ACCENT_COLOR_VAR, COLOR_SCHEME_VAR};
+use zng_color::{colors::ACCENT_COLOR_VAR, COLOR_SCHEME_VAR, HIGH_CONTRAST_VAR};
 use zng_ext_image::{ImageRenderWindowRoot, ImageRenderWindowsService, ImageVar, Img};
 use zng_layout::unit::TimeUnits as _;
 use zng_layout::unit::{Factor, FactorUnits, LengthUnits, PxRect};
@@ -41,17 +41,18 @@ use zng_var::{
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     config::ColorsConfig,
+    drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
     image::ImageMaskMode,
-    window::{RenderMode, WindowState},
+    window::{RenderMode, RendererStats, RendererTrim, WindowState},
     ViewProcessOffline,
 };
 use zng_wgt::node::with_context_var;
 
 use crate::{
     cmd::WindowCommands, control::WindowCtrl, CloseWindowResult, FrameCaptureMode, HeadlessMonitor, StartPosition, ViewExtensionError,
-    WindowCloseArgs, WindowCloseRequestedArgs, WindowFocusChangedArgs, WindowLoadingHandle, WindowNotFound, WindowOpenArgs, WindowRoot,
-    WindowVars, FRAME_IMAGE_READY_EVENT, MONITORS, WINDOW_CLOSE_EVENT, WINDOW_CLOSE_REQUESTED_EVENT, WINDOW_FOCUS_CHANGED_EVENT,
-    WINDOW_LOAD_EVENT, WINDOW_VARS_ID,
+    WindowCloseArgs, WindowCloseRequestedArgs, WindowFocusChangedArgs, WindowGroup, WindowLoadingHandle, WindowNotFound, WindowOpenArgs,
+    WindowRoot, WindowVars, FRAME_IMAGE_READY_EVENT, MONITORS, WINDOW_CLOSE_EVENT, WINDOW_CLOSE_REQUESTED_EVENT,
+    WINDOW_FOCUS_CHANGED_EVENT, WINDOW_LOAD_EVENT, WINDOW_VARS_ID,
 };
 
 app_local! {
@@ -84,6 +85,12 @@ pub(super) struct WindowsService {
     latest_colors_cfg: ColorsConfig,
 
     view_window_tasks: Vec<ViewWindowTask>,
+
+    drag_drops: Vec<(WindowId, DragDropId, ResponderVar<Result<DragDropEffect, DragDropError>>)>,
+
+    groups: Vec<WindowGroup>,
+    group_requests: Vec<Vec<WindowId>>,
+    group_pending: Vec<Vec<WindowId>>,
 }
 impl WindowsService {
     fn new() -> Self {
@@ -106,6 +113,10 @@ impl WindowsService {
             loading_deadline: None,
             latest_colors_cfg: ColorsConfig::default(),
             view_window_tasks: vec![],
+            drag_drops: vec![],
+            groups: vec![],
+            group_requests: vec![],
+            group_pending: vec![],
         }
     }
 
@@ -196,6 +207,138 @@ impl WindowsService {
         });
     }
 
+    fn group_index(&self, window_id: WindowId) -> Option<usize> {
+        self.groups.iter().position(|g| g.windows.contains(&window_id))
+    }
+
+    fn set_group_vars(&self, group: &WindowGroup) {
+        for id in &group.windows {
+            if let Some(info) = self.windows_info.get(id) {
+                info.vars.0.group.set(Some(group.clone()));
+            }
+        }
+    }
+
+    /// Fallback tab selection, moves `new` to the position and size of `prev` and swaps visibility.
+    fn show_tab(&self, prev: WindowId, new: WindowId) {
+        if let (Some(prev), Some(new)) = (self.windows_info.get(&prev), self.windows_info.get(&new)) {
+            new.vars.position().set(prev.vars.actual_position().get());
+            new.vars.size().set(prev.vars.actual_size().get());
+            new.vars.visible().set(true);
+            prev.vars.visible().set(false);
+        }
+    }
+
+    /// Select the window tab in its group, returns `true` if the window was hidden by the fallback group.
+    fn select_tab(&mut self, window_id: WindowId) -> bool {
+        if let Some(i) = self.group_index(window_id) {
+            let prev = self.groups[i].selected;
+            if prev != window_id {
+                let is_native = self.groups[i].is_native;
+                if !is_native {
+                    self.show_tab(prev, window_id);
+                }
+                self.groups[i].selected = window_id;
+                self.set_group_vars(&self.groups[i]);
+                return !is_native;
+            }
+        }
+        false
+    }
+
+    fn remove_from_group(&mut self, window_id: WindowId) {
+        let i = match self.group_index(window_id) {
+            Some(i) => i,
+            None => return,
+        };
+        let mut group = self.groups.swap_remove(i);
+        group.windows.retain(|w| *w != window_id);
+
+        if group.selected == window_id {
+            let next = group.windows[0];
+            if !group.is_native {
+                self.show_tab(window_id, next);
+            }
+            group.selected = next;
+        }
+
+        if let Some(info) = self.windows_info.get(&window_id) {
+            info.vars.0.group.set(None);
+            if group.is_native {
+                if let Some(ViewWindowOrHeadless::Window(w)) = &info.view {
+                    let _ = w.set_tabs(&[]);
+                }
+            } else {
+                info.vars.visible().set(true);
+            }
+        }
+
+        if group.windows.len() > 1 {
+            self.set_group_vars(&group);
+            self.groups.push(group);
+        } else {
+            for id in &group.windows {
+                if let Some(info) = self.windows_info.get(id) {
+                    info.vars.0.group.set(None);
+                }
+            }
+        }
+    }
+
+    fn fulfill_group(&mut self, mut windows: Vec<WindowId>) {
+        let mut unique = IdSet::default();
+        windows.retain(|w| self.windows_info.contains_key(w) && unique.insert(*w));
+
+        let mut views = Vec::with_capacity(windows.len());
+        if windows.len() > 1 {
+            for id in &windows {
+                let info = &self.windows_info[id];
+                match &info.view {
+                    Some(ViewWindowOrHeadless::Window(w)) => views.push(Some(w.clone())),
+                    None if info.mode.is_headed() && VIEW_PROCESS.is_available() => {
+                        // wait view open, see `set_view`.
+                        self.group_pending.push(windows);
+                        return;
+                    }
+                    _ => views.push(None),
+                }
+            }
+        }
+
+        for id in &windows {
+            self.remove_from_group(*id);
+        }
+        if windows.len() < 2 {
+            return;
+        }
+
+        let is_native = if views.iter().all(Option::is_some) {
+            let views: Vec<_> = views.into_iter().flatten().collect();
+            views[0].set_tabs(&views[1..]).unwrap_or(false)
+        } else {
+            false
+        };
+
+        let group = WindowGroup {
+            selected: windows[0],
+            windows,
+            is_native,
+        };
+        if !is_native {
+            let host = &self.windows_info[&group.selected].vars;
+            let (position, size) = (host.actual_position().get(), host.actual_size().get());
+            host.visible().set(true);
+            for id in &group.windows[1..] {
+                let vars = &self.windows_info[id].vars;
+                vars.position().set(position);
+                vars.size().set(size);
+                vars.visible().set(false);
+            }
+        }
+        self.set_group_vars(&group);
+        self.groups.push(group);
+    }
+
     fn take_requests(
         &mut self,
     ) -> (
@@ -482,7 +625,7 @@ impl WINDOWS {
 
     /// Generate an image from a rectangular selection of the current rendered frame of the window.
     ///
-    /// The image is not loaded at the moment of return, it will update when it is loaded.
+    /// The `rect` is in the window pixels, the image is not loaded at the moment of return, it will update when it is loaded.
     ///
     /// If the window is not found the error is reported in the image error.
     ///
@@ -493,6 +636,54 @@ impl WINDOWS {
             .frame_image_impl(window_id.into(), |vr| vr.frame_image_rect(rect, mask))
     }
 
+    /// Capture an image of the last rendered frame of the window, or of a `region` of it.
+    ///
+    /// The pixels are read back from the renderer of headed windows and headless windows with renderer. The `region` is in
+    /// the window pixels, if not set the full frame is captured. Returns a response var that updates when the image is loaded.
+    ///
+    /// If the window is not found or has no renderer the error is reported in the [image error].
+    ///
+    /// [image error]: zng_ext_image::Img::error
+    pub fn capture_frame(&self, window_id: impl Into<WindowId>, region: Option<PxRect>) -> ResponseVar<Img> {
+        let img = match region {
+            Some(r) => self.frame_image_rect(window_id, r, None),
+            None => self.frame_image(window_id, None),
+        };
+        let (responder, response) = response_var();
+        zng_task::spawn(async move {
+            img.get().wait_done().await;
+            responder.respond(img.get());
+        });
+        response
+    }
+
+    /// Gets the renderer memory statistics of the window.
+    ///
+    /// Returns all zeros if the window is headless without renderer or the view-process is not available.
+    ///
+    /// Returns an error if the `window_id` is not one of the open windows or is only an open request.
+    pub fn renderer_stats(&self, window_id: impl Into<WindowId>) -> Result<RendererStats, WindowNotFound> {
+        let window_id = window_id.into();
+        match WINDOWS_SV.read().windows_info.get(&window_id) {
+            Some(w) => Ok(w.view.as_ref().and_then(|v| v.renderer().stats().ok()).unwrap_or_default()),
+            None => Err(WindowNotFound(window_id)),
+        }
+    }
+
+    /// Clear the renderer caches of all open windows to free memory.
+    ///
+    /// The view-process already fully trims all renderers on [`LOW_MEMORY_EVENT`], this can be used to
+    /// trim after the app closes a view with many images or glyphs, or when the app itself detects memory pressure.
+    ///
+    /// [`LOW_MEMORY_EVENT`]: zng_app::view_process::raw_events::LOW_MEMORY_EVENT
+    pub fn trim_renderers(&self, trim: RendererTrim) {
+        for w in WINDOWS_SV.read().windows_info.values() {
+            if let Some(v) = &w.view {
+                let _ = v.renderer().trim(trim);
+            }
+        }
+    }
+
     /// Returns a shared reference the variables that control the window.
     ///
     /// Returns an error if the `window_id` is not one of the open windows or is only an open request.
@@ -654,7 +845,6 @@ impl WINDOWS {
     ///
     /// Note that the window is not focused, the [`focus`] operation also moves the window to the front.
     ///
-    /// [`always_on_top`]: WindowVars::always_on_top
     /// [`focus`]: Self::focus
     pub fn bring_to_top(&self, window_id: impl Into<WindowId>) -> Result<(), WindowNotFound> {
         let window_id = window_id.into();
@@ -844,8 +1034,21 @@ impl WINDOWS {
 
     /// Update the reference to view window the renderer associated with the window.
     pub(super) fn set_view(&self, id: WindowId, view: ViewWindowOrHeadless) {
-        if let Some(info) = WINDOWS_SV.write().windows_info.get_mut(&id) {
+        let mut wns = WINDOWS_SV.write();
+        if let Some(info) = wns.windows_info.get_mut(&id) {
             info.view = Some(view);
+
+            // retry groups waiting the view and restore native groups after respawn.
+            let mut regroup = mem::take(&mut wns.group_pending);
+            if let Some(i) = wns.group_index(id) {
+                if wns.groups[i].is_native {
+                    regroup.push(wns.groups[i].windows.clone());
+                }
+            }
+            if !regroup.is_empty() {
+                wns.group_requests.extend(regroup);
+                UPDATES.update(None);
+            }
         }
     }
 
@@ -906,6 +1109,7 @@ impl WINDOWS {
                         new = Some(new_focus);
                     }
                 }
+                let _ = wns.select_tab(new_focus);
             }
 
             if prev.is_some() || new.is_some() {
@@ -924,8 +1128,23 @@ impl WINDOWS {
             }
         } else if let Some(args) = RAW_COLORS_CONFIG_CHANGED_EVENT.on(update) {
             WINDOWS_SV.write().latest_colors_cfg = args.config;
+        } else if let Some(args) = RAW_APP_DRAG_ENDED_EVENT.on(update) {
+            let mut sv = WINDOWS_SV.write();
+            if let Some(i) = sv
+                .drag_drops
+                .iter()
+                .position(|(w, d, _)| *w == args.window_id && *d == args.drag_id)
+            {
+                let (_, _, r) = sv.drag_drops.swap_remove(i);
+                r.respond(Ok(args.applied));
+            }
         } else if let Some(args) = VIEW_PROCESS_INITED_EVENT.on(update) {
-            WINDOWS_SV.write().latest_colors_cfg = args.colors_config;
+            let mut sv = WINDOWS_SV.write();
+            sv.latest_colors_cfg = args.colors_config;
+            for (_, _, r) in sv.drag_drops.drain(..) {
+                r.respond(Ok(DragDropEffect::empty()));
+            }
+            drop(sv);
 
             // we skipped request fulfillment until this event.
             UPDATES.update(None);
@@ -1007,7 +1226,14 @@ impl WINDOWS {
                     let id = w.ctx.id();
                     w.close();
 
-                    let info = WINDOWS_SV.write().windows_info.remove(&id).unwrap();
+                    let mut wns = WINDOWS_SV.write();
+                    wns.remove_from_group(id);
+                    wns.group_pending.retain_mut(|g| {
+                        g.retain(|w| *w != id);
+                        g.len() > 1
+                    });
+                    let info = wns.windows_info.remove(&id).unwrap();
+                    drop(wns);
 
                     info.vars.0.is_open.set(false);
 
@@ -1081,7 +1307,7 @@ impl WINDOWS {
             return;
         }
 
-        let ((open, mut open_tasks, close, focus, bring_to_top, view_tasks), colors_cfg) = {
+        let ((open, mut open_tasks, close, mut focus, bring_to_top, view_tasks), colors_cfg) = {
             let mut wns = WINDOWS_SV.write();
             (wns.take_requests(), wns.latest_colors_cfg)
         };
@@ -1186,6 +1412,22 @@ impl WINDOWS {
             }
         }
 
+        // fulfill group requests
+        {
+            let mut wns = WINDOWS_SV.write();
+            for r in mem::take(&mut wns.group_requests) {
+                wns.fulfill_group(r);
+            }
+            if let Some(w_id) = focus {
+                if wns.select_tab(w_id) {
+                    // focus after the window is visible.
+                    wns.focus_request = Some(w_id);
+                    UPDATES.update(None);
+                    focus = None;
+                }
+            }
+        }
+
         // fulfill focus request
         if let Some(w_id) = focus {
             Self::with_detached_windows(|windows, _| {
@@ -1334,6 +1576,40 @@ impl WINDOWS {
     }
 }
 
+/// Window tab groups.
+impl WINDOWS {
+    /// Group the windows as tabs of one window.
+    ///
+    /// The first window is the selected tab, windows are removed from any previous group. If only one window is
+    /// given it is removed from its group, see also [`ungroup`]. Use [`focus`] to select a tab, the closed windows
+    /// are removed from the group.
+    ///
+    /// On macOS the view-process groups the windows using native window tabbing, on other platforms or in headless mode the
+    /// group is a portable fallback, the windows are kept at the same position and size and only the selected window is visible,
+    /// the app must present a tab strip, for example using [`WindowVars::group`] in each window content. Note that
+    /// the fallback controls the [`WindowVars::visible`] of the grouped windows.
+    ///
+    /// The group is applied on the next update, if a window is still opening, the group is applied after it opens.
+    ///
+    /// [`ungroup`]: Self::ungroup
+    /// [`focus`]: Self::focus
+    pub fn group(&self, windows: impl IntoIterator<Item = WindowId>) -> Result<(), WindowNotFound> {
+        let windows: Vec<_> = windows.into_iter().collect();
+        let mut w = WINDOWS_SV.write();
+        if let Some(id) = windows.iter().find(|id| !w.windows_info.contains_key(*id)) {
+            return Err(WindowNotFound(*id));
+        }
+        w.group_requests.push(windows);
+        UPDATES.update(None);
+        Ok(())
+    }
+
+    /// Remove the window from its tab group, it becomes a separate window again.
+    pub fn ungroup(&self, window_id: impl Into<WindowId>) -> Result<(), WindowNotFound> {
+        self.group([window_id.into()])
+    }
+}
+
 /// Native dialogs.
 impl WINDOWS {
     /// Show a native message dialog for the window.
@@ -1383,6 +1659,37 @@ impl WINDOWS {
     }
 }
 
+/// Native drag&drop.
+impl WINDOWS {
+    /// Start a native drag&drop operation from the window.
+    ///
+    /// The operation only starts if a mouse button or touch contact is pressed on the window. The `image` is
+    /// displayed under the cursor during the drag, if not set the operating system may use a default image.
+    ///
+    /// The response var updates once when the operation ends, with the effect applied by the drop target or
+    /// an empty effect if it was cancelled. Responds with an error if the view-process cannot start
+    /// the operation or does not support the data types.
+    pub fn native_drag_drop(
+        &self,
+        window_id: impl Into<WindowId>,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<Img>,
+    ) -> ResponseVar<Result<DragDropEffect, DragDropError>> {
+        let window_id = window_id.into();
+        let (responder, rsp) = response_var();
+        WINDOWS_SV.write().view_window_task(window_id, move |win| match win {
+            Some(win) => match win.start_drag_drop(data, allowed_effects, image.as_ref().and_then(|i| i.view())) {
+                Ok(Ok(drag_id)) => WINDOWS_SV.write().drag_drops.push((window_id, drag_id, responder)),
+                Ok(Err(e)) => responder.respond(Err(e)),
+                Err(e) => responder.respond(Err(DragDropError::CannotStart(formatx!("{e}")))),
+            },
+            None => responder.respond(Err(DragDropError::CannotStart(Txt::from_static("native window not found")))),
+        });
+        rsp
+    }
+}
+
 /// Window data visible in [`Windows`], detached so we can make the window visible inside the window content.
 struct AppWindowInfo {
     id: WindowId,
@@ -1481,6 +1788,7 @@ impl AppWindowTask {
             let vars = WINDOW.vars();
             let child = with_context_var(child, ACCENT_COLOR_VAR, vars.actual_accent_color());
             let child = with_context_var(child, COLOR_SCHEME_VAR, vars.actual_color_scheme());
+            let child = with_context_var(child, HIGH_CONTRAST_VAR, vars.actual_high_contrast());
             window.child = child.boxed();
         });
 
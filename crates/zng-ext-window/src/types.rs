@@ -2,30 +2,34 @@ use std::{
     fmt,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use zng_app::{
-    event::{event, event_args},
+    event::{event, event_args, Command, CommandNameExt as _},
+    shortcut::{CommandShortcutExt as _, GestureKey, Shortcut},
     update::UpdateOp,
     widget::{
         node::{BoxedUiNode, UiNode},
         WidgetId,
     },
     window::{WindowId, WINDOW},
-    AppEventSender, Deadline,
+    AppEventSender, DInstant, Deadline,
 };
 use zng_ext_image::{ImageSource, ImageVar, Img};
 use zng_layout::unit::{DipPoint, DipSize, Point, PxPoint};
-use zng_txt::Txt;
+use zng_txt::{ToTxt as _, Txt};
 use zng_unique_id::IdSet;
-use zng_var::impl_from_and_into_var;
+use zng_var::{impl_from_and_into_var, Var as _};
 use zng_view_api::{
     image::{ImageDataFormat, ImageMaskMode},
     window::{CursorIcon, EventCause, FrameId},
     ViewProcessOffline,
 };
 
-pub use zng_view_api::window::{FocusIndicator, RenderMode, VideoMode, WindowButton, WindowState};
+pub use zng_view_api::window::{
+    FocusIndicator, HitTestKind, RenderMode, RendererStats, RendererTrim, VideoMode, WindowButton, WindowLayer, WindowState,
+};
 
 use crate::{HeadlessMonitor, WINDOW_Ext as _, WINDOWS};
 
@@ -564,6 +568,34 @@ event_args! {
         }
     }
 
+    /// [`FRAME_PRESENTED_EVENT`] args.
+    pub struct FramePresentedArgs {
+        /// Window ID.
+        pub window_id: WindowId,
+
+        /// Frame that was presented.
+        pub frame_id: FrameId,
+
+        /// Moment the frame was presented on screen.
+        ///
+        /// This is the moment the view-process finished presenting the frame, media widgets can compare
+        /// it with the media clock to sync, and animations can use it to step to the next presentation.
+        pub presented_at: DInstant,
+
+        /// Estimated duration between display refreshes.
+        ///
+        /// When the window is presenting every refresh this is a moving average of the presentation intervals,
+        /// otherwise it is the refresh interval of the monitor that contains the window.
+        pub refresh_interval: Duration,
+
+        ..
+
+        /// Broadcast to all widgets.
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.search_all()
+        }
+    }
+
     /// [`WINDOW_CLOSE_REQUESTED_EVENT`] args.
     ///
     /// Requesting `propagation().stop()` on this event cancels the window close.
@@ -733,6 +765,13 @@ event! {
     /// [`WINDOWS.frame_image`]: crate::WINDOWS::frame_image
     /// [`WindowVars::frame_capture_mode`]: crate::WindowVars::frame_capture_mode
     pub static FRAME_IMAGE_READY_EVENT: FrameImageReadyArgs;
+
+    /// A window frame was presented on screen.
+    ///
+    /// This event only notifies for windows with [`WindowVars::present_notify`] enabled.
+    ///
+    /// [`WindowVars::present_notify`]: crate::WindowVars::present_notify
+    pub static FRAME_PRESENTED_EVENT: FramePresentedArgs;
 }
 
 /// Response message of [`close`] and [`close_together`].
@@ -842,3 +881,216 @@ impl std::error::Error for ViewExtensionError {
         }
     }
 }
+
+/// Custom entries of the window system menu.
+///
+/// See [`WindowVars::system_menu`] for more details.
+///
+/// [`WindowVars::system_menu`]: crate::WindowVars::system_menu
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SystemMenu {
+    /// Custom items, appended after the default items.
+    pub items: Vec<SystemMenuItem>,
+    /// If the default items (restore, move, size, minimize, maximize, close) are removed.
+    pub hide_default: bool,
+}
+impl SystemMenu {
+    /// New menu with custom `items` appended after the default items.
+    pub fn new(items: Vec<SystemMenuItem>) -> Self {
+        Self {
+            items,
+            hide_default: false,
+        }
+    }
+
+    /// If the menu has no custom items and shows the default items.
+    pub fn is_default(&self) -> bool {
+        self.items.is_empty() && !self.hide_default
+    }
+
+    /// Convert to the view-process menu, collects the commands in the order of the item IDs.
+    pub(crate) fn to_view(&self, cmds: &mut Vec<Command>) -> zng_view_api::window::SystemMenu {
+        fn convert(items: &[SystemMenuItem], cmds: &mut Vec<Command>) -> Vec<zng_view_api::window::SystemMenuItem> {
+            items
+                .iter()
+                .map(|item| match item {
+                    SystemMenuItem::Command(cmd) => {
+                        let id = cmds.len().to_txt();
+                        cmds.push(*cmd);
+                        zng_view_api::window::SystemMenuItem::Item {
+                            id,
+                            label: cmd.name().get(),
+                            enabled: cmd.is_enabled().get(),
+                            checked: false,
+                        }
+                    }
+                    SystemMenuItem::Submenu { label, items: sub } => zng_view_api::window::SystemMenuItem::Submenu {
+                        label: label.clone(),
+                        enabled: true,
+                        items: convert(sub, cmds),
+                    },
+                    SystemMenuItem::Separator => zng_view_api::window::SystemMenuItem::Separator,
+                })
+                .collect()
+        }
+        zng_view_api::window::SystemMenu {
+            items: convert(&self.items, cmds),
+            hide_default: self.hide_default,
+        }
+    }
+}
+impl_from_and_into_var! {
+    fn from(items: Vec<SystemMenuItem>) -> SystemMenu {
+        SystemMenu::new(items)
+    }
+    fn from(cmd: Command) -> SystemMenu {
+        SystemMenu::new(vec![SystemMenuItem::Command(cmd)])
+    }
+}
+
+/// Entry of a [`SystemMenu`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemMenuItem {
+    /// Command item.
+    ///
+    /// The item label is the command name, it is enabled when the command is enabled and clicking it notifies the command.
+    Command(Command),
+    /// Sub-menu.
+    Submenu {
+        /// Sub-menu label.
+        label: Txt,
+        /// Sub-menu items.
+        items: Vec<SystemMenuItem>,
+    },
+    /// Separator line.
+    Separator,
+}
+impl From<Command> for SystemMenuItem {
+    fn from(cmd: Command) -> Self {
+        SystemMenuItem::Command(cmd)
+    }
+}
+
+/// Window tab group.
+///
+/// See [`WINDOWS.group`] for more details.
+///
+/// [`WINDOWS.group`]: crate::WINDOWS::group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowGroup {
+    /// Windows in the group, in tab order.
+    pub windows: Vec<WindowId>,
+    /// Selected tab.
+    pub selected: WindowId,
+    /// If the group is implemented by native window tabbing.
+    ///
+    /// If `false` the group is the portable fallback, all windows are kept at the same position and size
+    /// and only the selected window is visible, the app must present the tabs.
+    pub is_native: bool,
+}
+
+/// Application menu bar exported to the operating system global menu.
+///
+/// See [`WindowVars::app_menu`] for more details.
+///
+/// [`WindowVars::app_menu`]: crate::WindowVars::app_menu
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AppMenu {
+    /// Top level items, usually sub-menus like "File" and "Edit".
+    pub items: Vec<AppMenuItem>,
+}
+impl AppMenu {
+    /// New menu.
+    pub fn new(items: Vec<AppMenuItem>) -> Self {
+        Self { items }
+    }
+
+    /// If the menu has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Convert to the view-process menu, collects the commands in the order of the item IDs.
+    pub(crate) fn to_view(&self, cmds: &mut Vec<Command>) -> zng_view_api::window::AppMenu {
+        fn convert(items: &[AppMenuItem], cmds: &mut Vec<Command>) -> Vec<zng_view_api::window::AppMenuItem> {
+            items
+                .iter()
+                .map(|item| match item {
+                    AppMenuItem::Command(cmd) => {
+                        let id = cmds.len().to_txt();
+                        cmds.push(*cmd);
+                        let shortcut = cmd.shortcut().with(|s| {
+                            s.iter().find_map(|s| match s {
+                                Shortcut::Gesture(g) => match &g.key {
+                                    GestureKey::Key(k) => {
+                                        let mut modifiers = zng_view_api::keyboard::GlobalShortcutModifiers::empty();
+                                        if g.modifiers.has_shift() {
+                                            modifiers |= zng_view_api::keyboard::GlobalShortcutModifiers::SHIFT;
+                                        }
+                                        if g.modifiers.has_ctrl() {
+                                            modifiers |= zng_view_api::keyboard::GlobalShortcutModifiers::CTRL;
+                                        }
+                                        if g.modifiers.has_alt() {
+                                            modifiers |= zng_view_api::keyboard::GlobalShortcutModifiers::ALT;
+                                        }
+                                        if g.modifiers.has_super() {
+                                            modifiers |= zng_view_api::keyboard::GlobalShortcutModifiers::LOGO;
+                                        }
+                                        Some(zng_view_api::window::MenuShortcut { modifiers, key: k.clone() })
+                                    }
+                                    GestureKey::Code(_) => None,
+                                },
+                                _ => None,
+                            })
+                        });
+                        zng_view_api::window::AppMenuItem::Item {
+                            id,
+                            label: cmd.name().get(),
+                            enabled: cmd.is_enabled().get(),
+                            checked: false,
+                            shortcut,
+                        }
+                    }
+                    AppMenuItem::Submenu { label, items: sub } => zng_view_api::window::AppMenuItem::Submenu {
+                        label: label.clone(),
+                        enabled: true,
+                        items: convert(sub, cmds),
+                    },
+                    AppMenuItem::Separator => zng_view_api::window::AppMenuItem::Separator,
+                })
+                .collect()
+        }
+        zng_view_api::window::AppMenu {
+            items: convert(&self.items, cmds),
+        }
+    }
+}
+impl_from_and_into_var! {
+    fn from(items: Vec<AppMenuItem>) -> AppMenu {
+        AppMenu::new(items)
+    }
+}
+
+/// Entry of an [`AppMenu`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppMenuItem {
+    /// Command item.
+    ///
+    /// The item label is the command name, it is enabled when the command is enabled, shows the first
+    /// command key gesture shortcut and clicking it notifies the command.
+    Command(Command),
+    /// Sub-menu.
+    Submenu {
+        /// Sub-menu label.
+        label: Txt,
+        /// Sub-menu items.
+        items: Vec<AppMenuItem>,
+    },
+    /// Separator line.
+    Separator,
+}
+impl From<Command> for AppMenuItem {
+    fn from(cmd: Command) -> Self {
+        AppMenuItem::Command(cmd)
+    }
+}
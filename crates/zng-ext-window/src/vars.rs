@@ -12,11 +12,12 @@ use zng_txt::Txt;
 use zng_unique_id::IdSet;
 use zng_var::{merge_var, var, var_from, ArcVar, BoxedVar, ReadOnlyArcVar, Var};
 use zng_view_api::{
-    config::{ColorScheme, ColorsConfig},
-    window::{CursorIcon, FocusIndicator, RenderMode, VideoMode, WindowButton, WindowState},
+    config::{ColorScheme, ColorsConfig, HighContrastColors},
+    image::ColorSpace,
+    window::{CursorIcon, FocusIndicator, HitTestKind, RenderMode, VideoMode, WindowButton, WindowLayer, WindowState},
 };
 
-use crate::{AutoSize, CursorSource, FrameCaptureMode, MonitorQuery, WindowIcon};
+use crate::{AppMenu, AutoSize, CursorSource, FrameCaptureMode, MonitorQuery, SystemMenu, WindowGroup, WindowIcon};
 
 pub(super) struct WindowVarsData {
     chrome: ArcVar<bool>,
@@ -52,11 +53,14 @@ pub(super) struct WindowVarsData {
     pub(super) restore_rect: ArcVar<DipRect>,
 
     enabled_buttons: ArcVar<WindowButton>,
+    hit_test_regions: ArcVar<Vec<(DipRect, HitTestKind)>>,
+    continuous_render: ArcVar<bool>,
+    present_notify: ArcVar<bool>,
 
     resizable: ArcVar<bool>,
     movable: ArcVar<bool>,
 
-    always_on_top: ArcVar<bool>,
+    layer: ArcVar<WindowLayer>,
 
     visible: ArcVar<bool>,
     taskbar_visible: ArcVar<bool>,
@@ -69,15 +73,22 @@ pub(super) struct WindowVarsData {
     pub(super) actual_color_scheme: ArcVar<ColorScheme>,
     accent_color: ArcVar<Option<LightDark>>,
     pub(super) actual_accent_color: ArcVar<LightDark>,
+    pub(super) actual_high_contrast: ArcVar<Option<HighContrastColors>>,
 
     pub(super) is_open: ArcVar<bool>,
     pub(super) is_loaded: ArcVar<bool>,
 
     frame_capture_mode: ArcVar<FrameCaptureMode>,
     pub(super) render_mode: ArcVar<RenderMode>,
+    color_space: ArcVar<ColorSpace>,
+    pub(super) actual_color_space: ArcVar<ColorSpace>,
 
     pub(super) access_enabled: ArcVar<AccessEnabled>,
     system_shutdown_warn: ArcVar<Txt>,
+    system_menu: ArcVar<SystemMenu>,
+    app_menu: ArcVar<AppMenu>,
+    pub(super) is_app_menu_exported: ArcVar<bool>,
+    pub(super) group: ArcVar<Option<WindowGroup>>,
 }
 
 /// Variables that configure the opening or open window.
@@ -124,6 +135,9 @@ impl WindowVars {
             )),
 
             enabled_buttons: var(WindowButton::all()),
+            hit_test_regions: var(vec![]),
+            continuous_render: var(false),
+            present_notify: var(false),
 
             min_size: var(Size::new(192, 48)),
             max_size: var(Size::new(100.pct(), 100.pct())),
@@ -134,7 +148,7 @@ impl WindowVars {
             resizable: var(true),
             movable: var(true),
 
-            always_on_top: var(false),
+            layer: var(WindowLayer::Normal),
 
             visible: var(true),
             taskbar_visible: var(true),
@@ -147,15 +161,22 @@ impl WindowVars {
             actual_color_scheme: var(system_colors.scheme),
             accent_color: var(None),
             actual_accent_color: var(system_colors.accent.into()),
+            actual_high_contrast: var(system_colors.high_contrast),
 
             is_open: var(true),
             is_loaded: var(false),
 
             frame_capture_mode: var(FrameCaptureMode::Sporadic),
             render_mode: var(default_render_mode),
+            color_space: var(ColorSpace::Srgb),
+            actual_color_space: var(ColorSpace::Srgb),
 
             access_enabled: var(AccessEnabled::empty()),
             system_shutdown_warn: var(Txt::from("")),
+            system_menu: var(SystemMenu::default()),
+            app_menu: var(AppMenu::default()),
+            is_app_menu_exported: var(false),
+            group: var(None),
         });
         Self(vars)
     }
@@ -517,13 +538,55 @@ impl WindowVars {
         self.0.enabled_buttons.clone()
     }
 
-    /// Defines if the window should always stay on top of other windows.
+    /// Defines regions of the window that behave like the native window chrome.
     ///
-    /// Note this only applies to other windows that are not also "always-on-top".
+    /// Windows that disable [`chrome`] and draw their own title bar and borders can define regions that move or resize
+    /// the window natively, the system also enables OS specific gestures for these regions, like Windows Snap Layouts
+    /// for the maximize button. The rectangles are relative to the window content, the first region that contains the
+    /// cursor is used.
     ///
-    /// The default value is `false`.
-    pub fn always_on_top(&self) -> ArcVar<bool> {
-        self.0.always_on_top.clone()
+    /// Note that outside of Windows the button kinds are only a hint, the app must still implement the button actions.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`chrome`]: Self::chrome
+    pub fn hit_test_regions(&self) -> ArcVar<Vec<(DipRect, HitTestKind)>> {
+        self.0.hit_test_regions.clone()
+    }
+
+    /// Defines if the view-process redraws the window continuously, synced with the display refresh rate.
+    ///
+    /// When enabled the window redraws every display refresh while it is visible and the [`RAW_REDRAW_TICK_EVENT`]
+    /// notifies after each redraw. This is only needed by render extensions that draw custom animations, normal
+    /// app animations request frames as needed.
+    ///
+    /// Is only supported by headed windows. The default value is `false`.
+    ///
+    /// [`RAW_REDRAW_TICK_EVENT`]: zng_app::view_process::raw_events::RAW_REDRAW_TICK_EVENT
+    pub fn continuous_render(&self) -> ArcVar<bool> {
+        self.0.continuous_render.clone()
+    }
+
+    /// Defines if the [`FRAME_PRESENTED_EVENT`] notifies for this window.
+    ///
+    /// When enabled the view-process notifies after every present of the window, the event args include the
+    /// presentation timestamp and an estimate of the display refresh interval, media widgets can use this
+    /// to sync with the display and animations can use it to step adaptively to the frame rate.
+    ///
+    /// Is only supported by headed windows. The default value is `false`.
+    ///
+    /// [`FRAME_PRESENTED_EVENT`]: crate::FRAME_PRESENTED_EVENT
+    pub fn present_notify(&self) -> ArcVar<bool> {
+        self.0.present_notify.clone()
+    }
+
+    /// Defines if the window should always stay on top or bottom of other windows.
+    ///
+    /// Note this only applies to other windows that are not also in the same layer.
+    ///
+    /// The default value is [`WindowLayer::Normal`].
+    pub fn layer(&self) -> ArcVar<WindowLayer> {
+        self.0.layer.clone()
     }
 
     /// Defines if the window is visible on the screen and in the task-bar.
@@ -628,6 +691,18 @@ impl WindowVars {
         self.0.actual_accent_color.read_only()
     }
 
+    /// System high contrast palette, if high contrast mode is enabled.
+    ///
+    /// When high contrast is enabled the [`actual_accent_color`] is the palette highlight color, unless it is overridden.
+    ///
+    /// The window widget also sets [`HIGH_CONTRAST_VAR`] to this variable.
+    ///
+    /// [`actual_accent_color`]: Self::actual_accent_color
+    /// [`HIGH_CONTRAST_VAR`]: zng_color::HIGH_CONTRAST_VAR
+    pub fn actual_high_contrast(&self) -> ReadOnlyArcVar<Option<HighContrastColors>> {
+        self.0.actual_high_contrast.read_only()
+    }
+
     /// If the window is open.
     ///
     /// This is a read-only variable, it starts set to `true` and will update only once,
@@ -685,6 +760,30 @@ impl WindowVars {
         self.0.render_mode.read_only()
     }
 
+    /// Requested output color space.
+    ///
+    /// Can be [`ColorSpace::Srgb`] or [`ColorSpace::ScRgb`] (extended sRGB), other values are handled as sRGB. The view-process
+    /// may not support extended sRGB output, the actual color space is reported in [`actual_color_space`].
+    ///
+    /// Note that the default view-process implementation does not support extended sRGB output yet, with it the
+    /// actual color space is always [`ColorSpace::Srgb`].
+    ///
+    /// The request is only applied when the view window opens, or reopens after a view-process respawn.
+    ///
+    /// The default value is [`ColorSpace::Srgb`].
+    ///
+    /// [`actual_color_space`]: Self::actual_color_space
+    pub fn color_space(&self) -> ArcVar<ColorSpace> {
+        self.0.color_space.clone()
+    }
+
+    /// Actual output color space.
+    ///
+    /// Updates after the view window opens.
+    pub fn actual_color_space(&self) -> ReadOnlyArcVar<ColorSpace> {
+        self.0.actual_color_space.read_only()
+    }
+
     /// If an accessibility service has requested info from this window.
     ///
     /// You can enable this in the app-process using [`enable_access`], the
@@ -723,6 +822,55 @@ impl WindowVars {
     pub fn system_shutdown_warn(&self) -> ArcVar<Txt> {
         self.0.system_shutdown_warn.clone()
     }
+
+    /// Custom items of the window system menu.
+    ///
+    /// The system menu is the menu that opens when the window icon is clicked or on [`OPEN_TITLE_BAR_CONTEXT_MENU_CMD`], the
+    /// items are appended after the default items, command items use the command name and enabled state and notify the command
+    /// when clicked.
+    ///
+    /// Is empty by default.
+    ///
+    /// ## Current Limitations
+    ///
+    /// The current `zng::view_process` or `zng-view` only implements this feature on Windows, other platforms ignore the custom menu.
+    ///
+    /// [`OPEN_TITLE_BAR_CONTEXT_MENU_CMD`]: crate::cmd::OPEN_TITLE_BAR_CONTEXT_MENU_CMD
+    pub fn system_menu(&self) -> ArcVar<SystemMenu> {
+        self.0.system_menu.clone()
+    }
+
+    /// Application menu bar exported to the operating system global menu.
+    ///
+    /// On macOS the menu is shown in the screen menu bar while the window is focused, on Linux X11 the menu is
+    /// exported using the DBus appmenu protocol used by Unity and KDE Plasma. Command items use the command name, enabled
+    /// state and shortcut and notify the command when clicked, the view-process menu is updated when these change.
+    ///
+    /// Note that on macOS the first sub-menu is always titled with the application name.
+    ///
+    /// Is empty by default. Use [`is_app_menu_exported`] to check if the menu is visible in the global menu, widgets
+    /// should show the menu inside the window when it is not exported.
+    ///
+    /// [`is_app_menu_exported`]: Self::is_app_menu_exported
+    pub fn app_menu(&self) -> ArcVar<AppMenu> {
+        self.0.app_menu.clone()
+    }
+
+    /// If the [`app_menu`] is not empty and is exported to the operating system global menu.
+    ///
+    /// [`app_menu`]: Self::app_menu
+    pub fn is_app_menu_exported(&self) -> ReadOnlyArcVar<bool> {
+        self.0.is_app_menu_exported.read_only()
+    }
+
+    /// Tab group of the window.
+    ///
+    /// Is `None` if the window is not grouped, see [`WINDOWS.group`] for more details.
+    ///
+    /// [`WINDOWS.group`]: crate::WINDOWS::group
+    pub fn group(&self) -> ReadOnlyArcVar<Option<WindowGroup>> {
+        self.0.group.read_only()
+    }
 }
 impl PartialEq for WindowVars {
     fn eq(&self, other: &Self) -> bool {
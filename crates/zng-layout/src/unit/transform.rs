@@ -135,6 +135,16 @@ impl Transform {
     pub fn new_scale_xy<X: Into<Factor>, Y: Into<Factor>>(x: X, y: Y) -> Transform {
         Transform::identity().scale_xy(x, y)
     }
+
+    /// Create a 3d scale transform on the Z dimension.
+    pub fn new_scale_z<Z: Into<Factor>>(z: Z) -> Transform {
+        Transform::new_scale_3d(1.0, 1.0, z)
+    }
+
+    /// Create a 3d scale transform.
+    pub fn new_scale_3d<X: Into<Factor>, Y: Into<Factor>, Z: Into<Factor>>(x: X, y: Y, z: Z) -> Transform {
+        Transform::identity().scale_3d(x, y, z)
+    }
 }
 
 impl Transform {
@@ -273,6 +283,17 @@ impl Transform {
         let s = scale.into();
         self.scale_xy(s, s)
     }
+    /// Change `self` to apply a ***z*** scale after its transformation.
+    ///
+    /// Note that the Z scale only has a visual effect on widgets translated on the Z axis inside a 3D context.
+    pub fn scale_z<Z: Into<Factor>>(self, z: Z) -> Self {
+        self.scale_3d(1.0, 1.0, z)
+    }
+    /// Change `self` to apply a 3d scale after its transformation.
+    pub fn scale_3d<X: Into<Factor>, Y: Into<Factor>, Z: Into<Factor>>(mut self, x: X, y: Y, z: Z) -> Self {
+        self.then_transform(PxTransform::scale_3d(x.into().0, y.into().0, z.into().0));
+        self
+    }
 
     /// Change `self` 3d perspective distance.
     pub fn perspective<D: Into<Length>>(mut self, d: D) -> Self {
@@ -245,6 +245,15 @@ impl<T> Receiver<T> {
     pub fn drain(&self) -> flume::Drain<T> {
         self.0.drain()
     }
+
+    /// Convert the receiver into an async stream of the incoming values.
+    ///
+    /// The stream ends when all senders have been dropped. See [`VarStreamExt`] for a way to connect the stream to a variable.
+    ///
+    /// [`VarStreamExt`]: crate::VarStreamExt
+    pub fn into_stream(self) -> flume::r#async::RecvStream<'static, T> {
+        self.0.into_stream()
+    }
 }
 
 /// Create a channel with no maximum capacity.
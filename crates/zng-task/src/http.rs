@@ -22,9 +22,11 @@
 //! [`isahc`]: https://docs.rs/isahc
 
 mod cache;
+mod download;
 mod util;
 
 pub use cache::*;
+pub use download::*;
 
 use std::convert::TryFrom;
 use std::error::Error as StdError;
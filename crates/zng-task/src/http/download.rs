@@ -0,0 +1,860 @@
+use std::{
+    fmt, mem,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use zng_app_context::app_local;
+use zng_clone_move::clmv;
+use zng_time::{DInstant, INSTANT};
+use zng_txt::{formatx, ToTxt as _, Txt};
+use zng_unit::*;
+use zng_var::{var, ArcVar, ReadOnlyArcVar, Var};
+
+use super::{header, Client, Error, Request, StatusCode, TryHeaderName, TryHeaderValue, TryUri, Uri};
+use crate::io::AsyncWriteExt as _;
+
+/// Download manager service.
+///
+/// Downloads are enqueued with a target file path, the service runs up to [`max_parallel`] downloads at a time and
+/// exposes the state, progress and speed of each download as variables. Downloads can be paused and resumed, the
+/// partial data is kept in a `{target}.part` file and the download continues with a range request. Failed transfers
+/// are retried automatically.
+///
+/// This is the common machinery for app updaters and asset fetching.
+///
+/// ```no_run
+/// # use zng_task::http::*;
+/// # async fn demo() -> Result<(), Error> {
+/// let d = DOWNLOADS.enqueue(DownloadRequest::new("https://httpbin.org/bytes/1024", "target/tmp/bytes.bin")?);
+/// let progress = d.progress();
+/// match d.wait().await {
+///     DownloadState::Completed => println!("downloaded {:?}", progress.get().0),
+///     s => println!("{s:?}"),
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// The service uses its own [`Client`] without cache, use [`set_client`] to replace it.
+///
+/// [`max_parallel`]: DOWNLOADS::max_parallel
+/// [`set_client`]: DOWNLOADS::set_client
+pub struct DOWNLOADS;
+impl DOWNLOADS {
+    /// Read-write variable that defines the maximum number of downloads running at the same time.
+    ///
+    /// Is `4` by default.
+    pub fn max_parallel(&self) -> ArcVar<usize> {
+        DOWNLOADS_SV.read().max_parallel.clone()
+    }
+
+    /// Read-write variable that defines the maximum total download speed, in bytes per second.
+    ///
+    /// The limit is shared equally between the running downloads, the share is recomputed and applied to all running
+    /// downloads every time a download starts or stops and when the limit changes. Is `0` by default, meaning no limit.
+    pub fn max_speed(&self) -> ArcVar<ByteLength> {
+        DOWNLOADS_SV.read().max_speed.clone()
+    }
+
+    /// Read-write variable that defines the interval awaited before each retry.
+    ///
+    /// The interval is multiplied by the retry attempt number. Is `1.secs()` by default.
+    pub fn retry_interval(&self) -> ArcVar<Duration> {
+        DOWNLOADS_SV.read().retry_interval.clone()
+    }
+
+    /// Replace the HTTP client used by new downloads.
+    pub fn set_client(&self, client: Client) {
+        DOWNLOADS_SV.write().client = Some(client);
+    }
+
+    /// Enqueue a download.
+    ///
+    /// The download starts as soon as a [`max_parallel`] slot is available.
+    ///
+    /// [`max_parallel`]: DOWNLOADS::max_parallel
+    pub fn enqueue(&self, request: DownloadRequest) -> Download {
+        let d = Download(Arc::new(DownloadData {
+            request,
+            ctrl: Mutex::new(DownloadCtrl::Queued),
+            state: var(DownloadState::Queued),
+            progress: var((0.bytes(), 0.bytes())),
+            speed: var(0.bytes()),
+            max_speed: AtomicU64::new(0),
+        }));
+
+        let mut s = DOWNLOADS_SV.write();
+        s.list.push(d.clone());
+        s.downloads.set(s.list.clone());
+        s.schedule();
+
+        d
+    }
+
+    /// Read-only variable that lists all enqueued downloads, including finished downloads.
+    pub fn downloads(&self) -> ReadOnlyArcVar<Vec<Download>> {
+        DOWNLOADS_SV.read().downloads.read_only()
+    }
+
+    /// Remove completed and canceled downloads from [`downloads`].
+    ///
+    /// [`downloads`]: DOWNLOADS::downloads
+    pub fn clear_finished(&self) {
+        let mut s = DOWNLOADS_SV.write();
+        s.list
+            .retain(|d| !matches!(&*d.0.ctrl.lock(), DownloadCtrl::Completed | DownloadCtrl::Canceled));
+        s.downloads.set(s.list.clone());
+    }
+}
+
+app_local! {
+    static DOWNLOADS_SV: DownloadsService = DownloadsService::new();
+}
+
+struct DownloadsService {
+    client: Option<Client>,
+    max_parallel: ArcVar<usize>,
+    max_speed: ArcVar<ByteLength>,
+    retry_interval: ArcVar<Duration>,
+    downloads: ArcVar<Vec<Download>>,
+    list: Vec<Download>,
+}
+impl DownloadsService {
+    fn new() -> Self {
+        let max_parallel = var(4);
+        max_parallel
+            .hook(|_| {
+                // new limit applies after the var update.
+                crate::spawn(async {
+                    DOWNLOADS_SV.write().schedule();
+                });
+                true
+            })
+            .perm();
+
+        let max_speed = var(0.bytes());
+        max_speed
+            .hook(|_| {
+                crate::spawn(async {
+                    DOWNLOADS_SV.read().share_speed();
+                });
+                true
+            })
+            .perm();
+
+        Self {
+            client: None,
+            max_parallel,
+            max_speed,
+            retry_interval: var(1.secs()),
+            downloads: var(vec![]),
+            list: vec![],
+        }
+    }
+
+    /// Start queued downloads up to the parallel limit.
+    fn schedule(&mut self) {
+        let max = self.max_parallel.get().max(1);
+        let mut running = self.list.iter().filter(|d| d.0.ctrl.lock().is_running()).count();
+        let mut start = vec![];
+        for d in &self.list {
+            if running >= max {
+                break;
+            }
+            if let DownloadCtrl::Queued = &*d.0.ctrl.lock() {
+                start.push(d.clone());
+                running += 1;
+            }
+        }
+        if !start.is_empty() {
+            let retry_interval = self.retry_interval.get();
+            let client = self.client.get_or_insert_with(Client::new).clone();
+            for d in start {
+                d.start(client.clone(), retry_interval);
+            }
+        }
+
+        // the running set may have changed because downloads started or finished.
+        self.share_speed();
+    }
+
+    /// Apply the `max_speed` share to all running downloads.
+    fn share_speed(&self) {
+        let running: Vec<_> = self.list.iter().filter(|d| d.0.ctrl.lock().is_running()).collect();
+        let share = match self.max_speed.get().0 {
+            0 => 0,
+            s => (s / running.len().max(1)).max(1),
+        };
+        for d in running {
+            let max_speed = match (share, d.0.request.max_speed.0) {
+                (0, r) => r,
+                (s, 0) => s,
+                (s, r) => s.min(r),
+            };
+            d.0.max_speed.store(max_speed as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Represents a download request.
+///
+/// Use [`DOWNLOADS.enqueue`] to start the download.
+///
+/// [`DOWNLOADS.enqueue`]: DOWNLOADS::enqueue
+#[derive(Debug, Clone)]
+pub struct DownloadRequest {
+    uri: Uri,
+    target: PathBuf,
+    headers: Vec<(header::HeaderName, header::HeaderValue)>,
+    retries: u32,
+    max_speed: ByteLength,
+}
+impl DownloadRequest {
+    /// New GET request that downloads the `uri` to the `target` file.
+    pub fn new(uri: impl TryUri, target: impl Into<PathBuf>) -> Result<Self, Error> {
+        Ok(Self {
+            uri: uri.try_uri()?,
+            target: target.into(),
+            headers: vec![],
+            retries: 3,
+            max_speed: 0.bytes(),
+        })
+    }
+
+    /// Appends a header to the request.
+    pub fn header(mut self, name: impl TryHeaderName, value: impl TryHeaderValue) -> Result<Self, Error> {
+        self.headers.push((name.try_header_name()?, value.try_header_value()?));
+        Ok(self)
+    }
+
+    /// Set the number of times the download is retried after a transfer error.
+    ///
+    /// Is `3` by default.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the maximum download speed of this download, in bytes per second.
+    ///
+    /// If [`DOWNLOADS.max_speed`] is also set the lesser limit is used. Is `0` by default, meaning no limit.
+    ///
+    /// [`DOWNLOADS.max_speed`]: DOWNLOADS::max_speed
+    pub fn max_speed(mut self, max: ByteLength) -> Self {
+        self.max_speed = max;
+        self
+    }
+
+    /// The download URI.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// The target file.
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// The file that receives the data before the download completes.
+    ///
+    /// Is the target path with the `.part` extension added.
+    pub fn part_file(&self) -> PathBuf {
+        let mut p = self.target.clone().into_os_string();
+        p.push(".part");
+        PathBuf::from(p)
+    }
+}
+
+/// State of a [`Download`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadState {
+    /// Awaiting a parallel slot.
+    Queued,
+    /// Transferring data.
+    Downloading,
+    /// Awaiting to retry after an error.
+    Retrying {
+        /// Retry attempt, starting from `1`.
+        attempt: u32,
+        /// Error that caused the retry.
+        error: Txt,
+    },
+    /// Paused by request, can resume.
+    Paused,
+    /// Downloaded to the target file.
+    Completed,
+    /// Failed after all retries, can resume.
+    Failed(Txt),
+    /// Canceled by request, the partial data was deleted.
+    Canceled,
+}
+impl DownloadState {
+    /// If is `Completed`, `Failed` or `Canceled`.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed(_) | Self::Canceled)
+    }
+}
+
+/// Represents an enqueued download.
+///
+/// Use [`DOWNLOADS.enqueue`] to create a download. The download continues if this handle is dropped.
+///
+/// [`DOWNLOADS.enqueue`]: DOWNLOADS::enqueue
+#[derive(Clone)]
+pub struct Download(Arc<DownloadData>);
+impl PartialEq for Download {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Eq for Download {}
+impl fmt::Debug for Download {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Download")
+            .field("uri", &self.0.request.uri)
+            .field("target", &self.0.request.target)
+            .field("state", &self.0.state.get())
+            .finish_non_exhaustive()
+    }
+}
+impl Download {
+    /// The download request.
+    pub fn request(&self) -> &DownloadRequest {
+        &self.0.request
+    }
+
+    /// Read-only variable that tracks the download state.
+    pub fn state(&self) -> ReadOnlyArcVar<DownloadState> {
+        self.0.state.read_only()
+    }
+
+    /// Read-only variable that tracks the downloaded bytes and total bytes.
+    ///
+    /// The total is zero if the server did not provide the content length.
+    pub fn progress(&self) -> ReadOnlyArcVar<(ByteLength, ByteLength)> {
+        self.0.progress.read_only()
+    }
+
+    /// Read-only variable that tracks the download speed in bytes per second.
+    pub fn speed(&self) -> ReadOnlyArcVar<ByteLength> {
+        self.0.speed.read_only()
+    }
+
+    /// Pause the download.
+    ///
+    /// The partial data is kept, [`resume`] continues the download with a range request if the server supports it.
+    ///
+    /// [`resume`]: Self::resume
+    pub fn pause(&self) {
+        let mut ctrl = self.0.ctrl.lock();
+        match &mut *ctrl {
+            DownloadCtrl::Queued => {
+                *ctrl = DownloadCtrl::Paused;
+                self.0.state.set(DownloadState::Paused);
+            }
+            DownloadCtrl::Running { stop, request } => {
+                *request = Some(StopRequest::Pause);
+                *stop = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Resume a paused or failed download.
+    ///
+    /// The download is enqueued again.
+    pub fn resume(&self) {
+        {
+            let mut ctrl = self.0.ctrl.lock();
+            match &*ctrl {
+                DownloadCtrl::Paused | DownloadCtrl::Failed => {
+                    *ctrl = DownloadCtrl::Queued;
+                    self.0.state.set(DownloadState::Queued);
+                }
+                _ => return,
+            }
+        }
+        DOWNLOADS_SV.write().schedule();
+    }
+
+    /// Cancel the download and delete the partial data.
+    pub fn cancel(&self) {
+        let mut ctrl = self.0.ctrl.lock();
+        match &mut *ctrl {
+            DownloadCtrl::Queued | DownloadCtrl::Paused | DownloadCtrl::Failed => {
+                *ctrl = DownloadCtrl::Canceled;
+                self.0.state.set(DownloadState::Canceled);
+                remove_part_file(self.0.request.part_file());
+            }
+            DownloadCtrl::Running { stop, request } => {
+                *request = Some(StopRequest::Cancel);
+                *stop = None;
+            }
+            DownloadCtrl::Completed | DownloadCtrl::Canceled => {}
+        }
+    }
+
+    /// Awaits until the download is finished.
+    ///
+    /// Note that the download can still be resumed after it is `Failed`.
+    pub async fn wait(&self) -> DownloadState {
+        self.0.state.wait_value(|s| s.is_finished()).await;
+        self.0.state.get()
+    }
+
+    fn start(&self, client: Client, retry_interval: Duration) {
+        let (stop, stop_receiver) = flume::bounded::<()>(1);
+        *self.0.ctrl.lock() = DownloadCtrl::Running {
+            stop: Some(stop),
+            request: None,
+        };
+        self.0.state.set(DownloadState::Downloading);
+
+        let d = self.clone();
+        crate::spawn(async move {
+            let data = &d.0;
+            let r = futures_lite::future::or(async { Some(data.run(&client, retry_interval).await) }, async {
+                // returns when a stop is requested.
+                let _ = stop_receiver.recv_async().await;
+                None
+            })
+            .await;
+
+            let request = match mem::replace(&mut *data.ctrl.lock(), DownloadCtrl::Queued) {
+                DownloadCtrl::Running { request, .. } => request,
+                _ => None,
+            };
+            let (ctrl, state) = match (r, request) {
+                (Some(Ok(())), _) => (DownloadCtrl::Completed, DownloadState::Completed),
+                (_, Some(StopRequest::Pause)) => (DownloadCtrl::Paused, DownloadState::Paused),
+                (_, Some(StopRequest::Cancel)) | (None, None) => {
+                    remove_part_file(data.request.part_file());
+                    (DownloadCtrl::Canceled, DownloadState::Canceled)
+                }
+                (Some(Err(e)), None) => {
+                    tracing::debug!("download {:?} failed, {e}", data.request.uri);
+                    (DownloadCtrl::Failed, DownloadState::Failed(e))
+                }
+            };
+            *data.ctrl.lock() = ctrl;
+            data.speed.set(0.bytes());
+            data.state.set(state);
+
+            DOWNLOADS_SV.write().schedule();
+        });
+    }
+}
+
+struct DownloadData {
+    request: DownloadRequest,
+    ctrl: Mutex<DownloadCtrl>,
+    state: ArcVar<DownloadState>,
+    progress: ArcVar<(ByteLength, ByteLength)>,
+    speed: ArcVar<ByteLength>,
+    // current share of the max speed, in bytes per second, `0` is no limit.
+    max_speed: AtomicU64,
+}
+impl DownloadData {
+    /// Download with retries.
+    async fn run(&self, client: &Client, retry_interval: Duration) -> Result<(), Txt> {
+        let mut attempt = 0;
+        loop {
+            match self.transfer(client).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    if attempt >= self.request.retries {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                    tracing::debug!("download {:?} retry {attempt}, {e}", self.request.uri);
+                    self.speed.set(0.bytes());
+                    self.state.set(DownloadState::Retrying { attempt, error: e });
+                    crate::deadline(retry_interval * attempt).await;
+                    self.state.set(DownloadState::Downloading);
+                }
+            }
+        }
+    }
+
+    /// Download once, continues from the part file.
+    async fn transfer(&self, client: &Client) -> Result<(), Txt> {
+        let part = self.request.part_file();
+        let offset = crate::wait(clmv!(part, || std::fs::metadata(part).map(|m| m.len()).unwrap_or(0))).await;
+
+        let mut request = Request::get(self.request.uri.clone()).map_err(|e| e.to_txt())?;
+        for (name, value) in &self.request.headers {
+            request = request.header(name.clone(), value.clone()).map_err(|e| e.to_txt())?;
+        }
+        if offset > 0 {
+            request = request.header(header::RANGE, format!("bytes={offset}-")).map_err(|e| e.to_txt())?;
+        }
+
+        let mut response = client.send(request.build()).await.map_err(|e| e.to_txt())?;
+        let append = match response.status() {
+            StatusCode::PARTIAL_CONTENT => true,
+            StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                // part file already has all the data.
+                let len = (offset as usize).bytes();
+                self.progress.set((len, len));
+                return self.finish_part(part).await;
+            }
+            s if s.is_success() => false,
+            s => return Err(formatx!("server responded {s}")),
+        };
+
+        let mut received = if append { offset as usize } else { 0 };
+        let total = response.content_len().map(|l| l.0 + received).unwrap_or(0);
+        self.progress.set((received.bytes(), total.bytes()));
+
+        let mut file = crate::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&part)
+            .await
+            .map_err(|e| e.to_txt())?;
+
+        let mut buf = vec![0; 64 * 1024];
+        let mut last_update = INSTANT.now();
+        let mut last_received = received;
+        let mut throttle = Throttle::new();
+        loop {
+            let n = response.read(&mut buf).await.map_err(|e| e.to_txt())?;
+            if n == 0 {
+                break;
+            }
+            file.write_all(&buf[..n]).await.map_err(|e| e.to_txt())?;
+            received += n;
+
+            throttle.wait(n as u64, self.max_speed.load(Ordering::Relaxed)).await;
+
+            let elapsed = last_update.elapsed();
+            if elapsed >= 200.ms() {
+                self.progress.set((received.bytes(), total.bytes()));
+                self.speed
+                    .set((((received - last_received) as f64 / elapsed.as_secs_f64()).round() as usize).bytes());
+                last_update = INSTANT.now();
+                last_received = received;
+            }
+        }
+        file.flush().await.map_err(|e| e.to_txt())?;
+        drop(file);
+
+        self.progress.set((received.bytes(), received.max(total).bytes()));
+        self.finish_part(part).await
+    }
+
+    async fn finish_part(&self, part: PathBuf) -> Result<(), Txt> {
+        let target = self.request.target.clone();
+        crate::wait(move || std::fs::rename(part, target)).await.map_err(|e| e.to_txt())
+    }
+}
+
+/// Limits the transfer speed by awaiting after each read.
+///
+/// The limit can change during the transfer, the measure restarts when it does.
+struct Throttle {
+    limit: u64,
+    start: DInstant,
+    bytes: u64,
+}
+impl Throttle {
+    fn new() -> Self {
+        Self {
+            limit: 0,
+            start: INSTANT.now(),
+            bytes: 0,
+        }
+    }
+
+    async fn wait(&mut self, read: u64, limit: u64) {
+        if self.limit != limit {
+            self.limit = limit;
+            self.start = INSTANT.now();
+            self.bytes = 0;
+        }
+        if limit == 0 {
+            return;
+        }
+        self.bytes += read;
+        let expected = Duration::from_secs_f64(self.bytes as f64 / limit as f64);
+        let elapsed = self.start.elapsed();
+        if expected > elapsed {
+            crate::deadline(expected - elapsed).await;
+        }
+    }
+}
+
+enum DownloadCtrl {
+    Queued,
+    Running {
+        // dropped to stop the task.
+        stop: Option<flume::Sender<()>>,
+        request: Option<StopRequest>,
+    },
+    Paused,
+    Failed,
+    Completed,
+    Canceled,
+}
+impl DownloadCtrl {
+    fn is_running(&self) -> bool {
+        matches!(self, Self::Running { .. })
+    }
+}
+
+enum StopRequest {
+    Pause,
+    Cancel,
+}
+
+fn remove_part_file(part: PathBuf) {
+    crate::spawn_wait(move || {
+        if let Err(e) = std::fs::remove_file(&part) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::error!("cannot remove {part:?}, {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead as _, BufReader, Write as _},
+        net::{TcpListener, TcpStream},
+        sync::atomic::AtomicUsize,
+        time::Instant,
+    };
+
+    use zng_app_context::{AppId, LocalContext};
+    use zng_var::VARS_APP;
+
+    use super::*;
+    use crate::http::util::TestTempDir;
+
+    /// Apply var updates until `cond` is `true`.
+    #[track_caller]
+    fn pump_until(mut cond: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(20);
+        loop {
+            VARS_APP.apply_updates();
+            if cond() {
+                return;
+            }
+            assert!(Instant::now() < deadline, "timeout");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Minimal HTTP server, calls `handler` with the request index and range start for each request.
+    fn serve(handler: impl Fn(usize, Option<usize>, &mut TcpStream) + Send + Sync + 'static) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}/file", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicUsize::new(0));
+        let handler = Arc::new(handler);
+        std::thread::spawn(clmv!(count, || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => break,
+                };
+                let i = count.fetch_add(1, Ordering::Relaxed);
+                std::thread::spawn(clmv!(handler, || {
+                    let mut range = None;
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).is_ok() {
+                        let l = line.trim().to_ascii_lowercase();
+                        if l.is_empty() {
+                            break;
+                        }
+                        if let Some(r) = l.strip_prefix("range: bytes=") {
+                            range = r.trim_end_matches('-').parse().ok();
+                        }
+                        line.clear();
+                    }
+                    handler(i, range, &mut stream);
+                }));
+            }
+        }));
+        (uri, count)
+    }
+
+    fn respond(stream: &mut TcpStream, status: &str, body: &[u8]) {
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            body.len()
+        );
+        let _ = stream.write_all(body);
+    }
+
+    fn content() -> Vec<u8> {
+        (0..1024u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn queue_limit() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let tmp = TestTempDir::new("download_queue_limit");
+
+        let (release, release_rcv) = flume::unbounded::<()>();
+        let (uri, _) = serve(move |_, _, s| {
+            let _ = release_rcv.recv_timeout(Duration::from_secs(20));
+            respond(s, "200 OK", &content());
+        });
+
+        DOWNLOADS.max_parallel().set(1usize);
+        VARS_APP.apply_updates();
+
+        let a = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), tmp.join("a.bin")).unwrap());
+        let b = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), tmp.join("b.bin")).unwrap());
+        pump_until(|| a.state().get() == DownloadState::Downloading);
+        assert_eq!(b.state().get(), DownloadState::Queued);
+
+        release.send(()).unwrap();
+        pump_until(|| a.state().get() == DownloadState::Completed);
+        pump_until(|| b.state().get() == DownloadState::Downloading);
+
+        release.send(()).unwrap();
+        pump_until(|| b.state().get() == DownloadState::Completed);
+
+        assert_eq!(std::fs::read(tmp.join("a.bin")).unwrap(), content());
+        assert_eq!(std::fs::read(tmp.join("b.bin")).unwrap(), content());
+    }
+
+    #[test]
+    fn resume_after_pause() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let tmp = TestTempDir::new("download_resume_after_pause");
+
+        let ranges = Arc::new(Mutex::new(vec![]));
+        let (hold, hold_rcv) = flume::unbounded::<()>();
+        let (uri, _) = serve(clmv!(ranges, |_, range, s| {
+            ranges.lock().push(range);
+            let content = content();
+            match range {
+                None => {
+                    // send half and hold until the test ends.
+                    let _ = write!(
+                        s,
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                        content.len()
+                    );
+                    let _ = s.write_all(&content[..512]);
+                    let _ = s.flush();
+                    let _ = hold_rcv.recv_timeout(Duration::from_secs(20));
+                }
+                Some(offset) => respond(s, "206 Partial Content", &content[offset..]),
+            }
+        }));
+
+        let target = tmp.join("file.bin");
+        let d = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), &target).unwrap());
+        let part = d.request().part_file();
+        pump_until(|| std::fs::metadata(&part).map(|m| m.len() == 512).unwrap_or(false));
+
+        d.pause();
+        pump_until(|| d.state().get() == DownloadState::Paused);
+        assert!(part.exists());
+
+        d.resume();
+        pump_until(|| d.state().get() == DownloadState::Completed);
+
+        assert_eq!(*ranges.lock(), vec![None, Some(512)]);
+        assert_eq!(std::fs::read(&target).unwrap(), content());
+        assert!(!part.exists());
+        drop(hold);
+    }
+
+    #[test]
+    fn retry_recover() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let tmp = TestTempDir::new("download_retry_recover");
+
+        let (uri, count) = serve(|i, _, s| {
+            if i == 0 {
+                respond(s, "500 Internal Server Error", b"");
+            } else {
+                respond(s, "200 OK", &content());
+            }
+        });
+
+        DOWNLOADS.retry_interval().set(10.ms());
+        VARS_APP.apply_updates();
+
+        let target = tmp.join("file.bin");
+        let d = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), &target).unwrap().retries(2));
+        pump_until(|| d.state().get().is_finished());
+
+        assert_eq!(d.state().get(), DownloadState::Completed);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(std::fs::read(&target).unwrap(), content());
+    }
+
+    #[test]
+    fn retry_fail() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let tmp = TestTempDir::new("download_retry_fail");
+
+        let (uri, count) = serve(|_, _, s| respond(s, "500 Internal Server Error", b""));
+
+        DOWNLOADS.retry_interval().set(10.ms());
+        VARS_APP.apply_updates();
+
+        let d = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), tmp.join("file.bin")).unwrap().retries(2));
+        pump_until(|| d.state().get().is_finished());
+
+        assert!(matches!(d.state().get(), DownloadState::Failed(_)));
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn speed_share_applies_to_running() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let tmp = TestTempDir::new("download_speed_share");
+
+        let (release, release_rcv) = flume::unbounded::<()>();
+        let (uri, _) = serve(move |_, _, s| {
+            let _ = release_rcv.recv_timeout(Duration::from_secs(20));
+            respond(s, "200 OK", &content());
+        });
+
+        DOWNLOADS.max_speed().set(1000.bytes());
+        VARS_APP.apply_updates();
+
+        let a = DOWNLOADS.enqueue(DownloadRequest::new(uri.as_str(), tmp.join("a.bin")).unwrap());
+        assert_eq!(a.0.max_speed.load(Ordering::Relaxed), 1000);
+
+        let b = DOWNLOADS.enqueue(
+            DownloadRequest::new(uri.as_str(), tmp.join("b.bin"))
+                .unwrap()
+                .max_speed(300.bytes()),
+        );
+        assert_eq!(a.0.max_speed.load(Ordering::Relaxed), 500);
+        assert_eq!(b.0.max_speed.load(Ordering::Relaxed), 300);
+
+        DOWNLOADS.max_speed().set(0.bytes());
+        pump_until(|| a.0.max_speed.load(Ordering::Relaxed) == 0);
+        assert_eq!(b.0.max_speed.load(Ordering::Relaxed), 300);
+
+        DOWNLOADS.max_speed().set(1000.bytes());
+        pump_until(|| a.0.max_speed.load(Ordering::Relaxed) == 500);
+
+        b.cancel();
+        pump_until(|| a.0.max_speed.load(Ordering::Relaxed) == 1000);
+        VARS_APP.apply_updates();
+        assert_eq!(b.state().get(), DownloadState::Canceled);
+
+        // release all, the canceled request may still be holding a server thread.
+        drop(release);
+        pump_until(|| a.state().get().is_finished());
+    }
+}
@@ -56,6 +56,9 @@ mod rayon_ctx;
 
 pub use rayon_ctx::*;
 
+mod var_stream;
+pub use var_stream::*;
+
 pub use ui::*;
 
 /// Spawn a parallel async task, this function is not blocking and the `task` starts executing immediately.
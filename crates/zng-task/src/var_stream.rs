@@ -0,0 +1,322 @@
+//! Async stream adapters into variables.
+
+use std::{mem, sync::Arc};
+
+use futures_lite::{Stream, StreamExt as _};
+use parking_lot::Mutex;
+use zng_var::{ObservableVec, Var, VarModify, VarValue, WeakVar};
+
+/// Defines how the items of a stream are applied to a variable.
+///
+/// See [`VarStreamExt::set_from_stream`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamVarMode {
+    /// The stream is polled as fast as it produces items, only the latest item is applied per app update.
+    ///
+    /// For [`VecVarStreamExt::extend_from_stream`] all items received during an app update are pushed together.
+    ///
+    /// This is the default, it is the best mode for streams of state, like a watch channel.
+    #[default]
+    Coalesce,
+    /// The next item is only requested from the stream after the previous item is applied to the variable.
+    ///
+    /// Every item causes a variable update, producers that are faster than the app are throttled by
+    /// the stream backpressure. Note that the app must be updating for the stream to advance.
+    Backpressure,
+}
+
+/// Represents a stream driving a variable.
+///
+/// The stream stops driving the variable when this handle is dropped, use [`perm`] to drive
+/// the variable until the stream ends or the variable is dropped.
+///
+/// [`perm`]: Self::perm
+#[must_use = "the stream stops driving the variable when the handle is dropped"]
+pub struct StreamVarHandle(Option<flume::Sender<()>>);
+impl StreamVarHandle {
+    /// Handle to no stream.
+    pub fn dummy() -> Self {
+        StreamVarHandle(None)
+    }
+
+    /// If the handle is not actually registered.
+    pub fn is_dummy(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Drop the handle without stopping the stream.
+    pub fn perm(mut self) {
+        mem::forget(self.0.take());
+    }
+
+    /// If the stream is still driving the variable.
+    ///
+    /// Is `false` after the stream ends, the variable is dropped or the handle is stopped.
+    pub fn is_running(&self) -> bool {
+        self.0.as_ref().map(|s| !s.is_disconnected()).unwrap_or(false)
+    }
+
+    /// Drops the handle and stops the stream.
+    pub fn stop(self) {}
+}
+impl std::fmt::Debug for StreamVarHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamVarHandle")
+            .field("is_dummy", &self.is_dummy())
+            .field("is_running", &self.is_running())
+            .finish()
+    }
+}
+
+/// Extension methods for driving a variable from an async stream.
+pub trait VarStreamExt<T: VarValue>: Var<T> {
+    /// Spawns a task that sets the variable to each item produced by the `stream`.
+    ///
+    /// The stream is polled in the [`spawn`] thread-pool, the items are applied to the variable in app
+    /// updates according with the `mode`. The task ends when the stream ends, when the variable is dropped or when
+    /// the returned handle is dropped.
+    ///
+    /// This can be used to connect websocket, watch channels and other async sources to UI state.
+    ///
+    /// ```
+    /// # use zng_task::*;
+    /// # use zng_var::*;
+    /// # fn demo(status_stream: impl futures_lite::Stream<Item = &'static str> + Send + 'static) {
+    /// let status = var("connecting");
+    /// status.set_from_stream(status_stream, StreamVarMode::Coalesce).perm();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn`]: crate::spawn
+    fn set_from_stream<S>(&self, stream: S, mode: StreamVarMode) -> StreamVarHandle
+    where
+        S: Stream<Item = T> + Send + 'static;
+}
+impl<T: VarValue, V: Var<T>> VarStreamExt<T> for V {
+    fn set_from_stream<S>(&self, stream: S, mode: StreamVarMode) -> StreamVarHandle
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        drive_stream(self, stream, mode, true, |v, mut items| {
+            if let Some(item) = items.pop() {
+                v.set(item);
+            }
+        })
+    }
+}
+
+/// Extension methods for driving an [`ObservableVec`] variable from an async stream of items.
+pub trait VecVarStreamExt<T: VarValue>: Var<ObservableVec<T>> {
+    /// Spawns a task that pushes each item produced by the `stream` to the vec.
+    ///
+    /// In [`StreamVarMode::Coalesce`] all items received during an app update are pushed in one variable update,
+    /// no item is skipped in any mode.
+    ///
+    /// See [`VarStreamExt::set_from_stream`] for more details.
+    fn extend_from_stream<S>(&self, stream: S, mode: StreamVarMode) -> StreamVarHandle
+    where
+        S: Stream<Item = T> + Send + 'static;
+}
+impl<T: VarValue, V: Var<ObservableVec<T>>> VecVarStreamExt<T> for V {
+    fn extend_from_stream<S>(&self, stream: S, mode: StreamVarMode) -> StreamVarHandle
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        drive_stream(self, stream, mode, false, |v, items| {
+            if !items.is_empty() {
+                v.to_mut().extend(items);
+            }
+        })
+    }
+}
+
+fn drive_stream<T, I, V, S>(
+    var: &V,
+    stream: S,
+    mode: StreamVarMode,
+    latest_only: bool,
+    apply: fn(&mut VarModify<T>, Vec<I>),
+) -> StreamVarHandle
+where
+    T: VarValue,
+    I: Send + 'static,
+    V: Var<T>,
+    S: Stream<Item = I> + Send + 'static,
+{
+    if var.capabilities().is_always_read_only() {
+        tracing::error!("cannot drive read-only variable from stream");
+        return StreamVarHandle::dummy();
+    }
+
+    let weak_var = var.clone().actual_var().downgrade();
+    let (stop_sender, stop_receiver) = flume::bounded::<()>(1);
+
+    let drive = async move {
+        let mut stream = std::pin::pin!(stream);
+        // items received but not applied yet.
+        let pending = Arc::new(Mutex::new(Vec::new()));
+
+        while let Some(item) = stream.next().await {
+            let var = match weak_var.upgrade() {
+                Some(v) => v,
+                None => break,
+            };
+
+            let schedule = {
+                let mut p = pending.lock();
+                let schedule = p.is_empty();
+                if latest_only {
+                    p.clear();
+                }
+                p.push(item);
+                schedule
+            };
+            if !schedule {
+                // coalesce with already scheduled modify.
+                continue;
+            }
+
+            // the sender is dropped after the modify applies.
+            let (applied_sender, applied_receiver) = flume::bounded::<()>(1);
+            let pending = pending.clone();
+            let r = var.modify(move |v| {
+                let items = mem::take(&mut *pending.lock());
+                apply(v, items);
+                drop(applied_sender);
+            });
+            drop(var);
+            if r.is_err() {
+                break;
+            }
+
+            if let StreamVarMode::Backpressure = mode {
+                let _ = applied_receiver.recv_async().await;
+            }
+        }
+    };
+
+    crate::spawn(async move {
+        futures_lite::future::or(drive, async move {
+            // returns when the handle is dropped.
+            let _ = stop_receiver.recv_async().await;
+        })
+        .await
+    });
+
+    StreamVarHandle(Some(stop_sender))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use zng_app_context::{AppId, LocalContext};
+    use zng_var::{var, VARS_APP};
+
+    use super::*;
+
+    /// Apply var updates until `cond` is `true`.
+    #[track_caller]
+    fn pump_until(mut cond: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            VARS_APP.apply_updates();
+            if cond() {
+                return;
+            }
+            assert!(Instant::now() < deadline, "timeout");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn set_from_stream_coalesce() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(0);
+        let (sender, receiver) = flume::unbounded();
+        let handle = v.set_from_stream(receiver.into_stream(), StreamVarMode::Coalesce);
+        assert!(!handle.is_dummy());
+
+        for i in 1..=10 {
+            sender.send(i).unwrap();
+        }
+        drop(sender);
+
+        pump_until(|| !handle.is_running());
+        VARS_APP.apply_updates();
+        assert_eq!(v.get(), 10);
+    }
+
+    #[test]
+    fn set_from_stream_backpressure() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(0);
+        let seen = Arc::new(Mutex::new(vec![]));
+        let _hook = v.hook({
+            let seen = seen.clone();
+            move |a| {
+                seen.lock().push(*a.value());
+                true
+            }
+        });
+
+        let handle = v.set_from_stream(futures_lite::stream::iter(1..=5), StreamVarMode::Backpressure);
+        pump_until(|| !handle.is_running());
+        VARS_APP.apply_updates();
+
+        assert_eq!(*seen.lock(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(v.get(), 5);
+    }
+
+    #[test]
+    fn extend_from_stream_keeps_all_items() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(ObservableVec::<u32>::new());
+        let handle = v.extend_from_stream(futures_lite::stream::iter(0..100), StreamVarMode::Coalesce);
+        pump_until(|| !handle.is_running());
+        VARS_APP.apply_updates();
+
+        assert_eq!(v.with(|v| v.iter().copied().collect::<Vec<_>>()), (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stop_on_handle_drop() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(0);
+        let (sender, receiver) = flume::unbounded::<i32>();
+        let handle = v.set_from_stream(receiver.into_stream(), StreamVarMode::Coalesce);
+        assert!(handle.is_running());
+
+        drop(handle);
+        // the task drops the stream
+        pump_until(|| sender.is_disconnected());
+    }
+
+    #[test]
+    fn stop_on_var_drop() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(0);
+        let (sender, receiver) = flume::unbounded();
+        let handle = v.set_from_stream(receiver.into_stream(), StreamVarMode::Coalesce);
+        drop(v);
+
+        let _ = sender.send(1);
+        pump_until(|| !handle.is_running());
+        assert!(sender.is_disconnected());
+    }
+
+    #[test]
+    fn read_only_var_is_dummy() {
+        let _app = LocalContext::start_app(AppId::new_unique());
+
+        let v = var(0).read_only();
+        let handle = v.set_from_stream(futures_lite::stream::iter(1..=5), StreamVarMode::Coalesce);
+        assert!(handle.is_dummy());
+        assert!(!handle.is_running());
+    }
+}
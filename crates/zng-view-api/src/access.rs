@@ -295,6 +295,20 @@ pub enum AccessState {
     /// [`Value`]: Self::Value
     ValueText(Txt),
 
+    /// Defines the caret position and text selection in the [`ValueText`] or [`Label`] text.
+    ///
+    /// The indexes are string byte indexes, at the start of a grapheme and UTF-8 char, like in [`AccessCmd::SelectText`].
+    /// If `start` and `caret` are equal there is no selection, only the caret position.
+    ///
+    /// [`ValueText`]: Self::ValueText
+    /// [`Label`]: Self::Label
+    TextSelection {
+        /// Selection start.
+        start: usize,
+        /// Selection end, where the caret is positioned.
+        caret: usize,
+    },
+
     /// Indicate that the widget can change.
     Live {
         /// How the changes must be notified.
@@ -396,6 +410,38 @@ zng_var::impl_from_and_into_var! {
     fn from(some: LiveIndicator) -> Option<LiveIndicator>;
 }
 
+/// Priority of an accessibility announcement.
+///
+/// See [`Api::access_announce`] for more details.
+///
+/// [`Api::access_announce`]: crate::Api::access_announce
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AccessLivePriority {
+    /// Announcement should be presented at the next graceful opportunity, such as at the end of
+    /// speaking the current sentence or when the user pauses typing.
+    Polite,
+    /// Announcement should be presented immediately, interrupting the current speech.
+    Assertive,
+}
+impl Default for AccessLivePriority {
+    /// `Polite`.
+    fn default() -> Self {
+        Self::Polite
+    }
+}
+#[cfg(feature = "var")]
+zng_var::impl_from_and_into_var! {
+    fn from(some: AccessLivePriority) -> Option<AccessLivePriority>;
+}
+impl From<AccessLivePriority> for LiveIndicator {
+    fn from(value: AccessLivePriority) -> Self {
+        match value {
+            AccessLivePriority::Polite => LiveIndicator::Polite,
+            AccessLivePriority::Assertive => LiveIndicator::Assertive,
+        }
+    }
+}
+
 /// Sort direction.
 ///
 /// See [`AccessState::Sort`] for more details.
@@ -174,14 +174,84 @@ pub struct ColorsConfig {
     ///
     /// Accent color preference.
     ///
-    /// Expect a saturated color that contrasts with the text color.
+    /// Expect a saturated color that contrasts with the text color. In high contrast mode this is the
+    /// [`HighContrastColors::highlight`] color.
     pub accent: Rgba,
+    /// High contrast palette, if the system high contrast mode is enabled.
+    #[serde(default)]
+    pub high_contrast: Option<HighContrastColors>,
 }
 impl Default for ColorsConfig {
     fn default() -> Self {
         Self {
             scheme: Default::default(),
             accent: Rgba::new(10, 10, 200, 255),
+            high_contrast: None,
+        }
+    }
+}
+
+/// System colors of the high contrast mode.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighContrastColors {
+    /// Window background.
+    pub background: Rgba,
+    /// Normal text.
+    pub text: Rgba,
+    /// Selected or highlighted item background.
+    pub highlight: Rgba,
+    /// Selected or highlighted item text.
+    pub highlight_text: Rgba,
+    /// Hyperlink text.
+    pub link: Rgba,
+    /// Disabled text.
+    pub disabled_text: Rgba,
+    /// Button background.
+    pub button: Rgba,
+    /// Button text.
+    pub button_text: Rgba,
+}
+impl HighContrastColors {
+    /// Black text on white background.
+    pub fn light() -> Self {
+        let black = Rgba::new(0, 0, 0, 255);
+        let white = Rgba::new(255, 255, 255, 255);
+        Self {
+            background: white,
+            text: black,
+            highlight: Rgba::new(55, 0, 110, 255),
+            highlight_text: white,
+            link: Rgba::new(0, 0, 159, 255),
+            disabled_text: Rgba::new(96, 0, 0, 255),
+            button: white,
+            button_text: black,
+        }
+    }
+
+    /// White text on black background.
+    pub fn dark() -> Self {
+        let black = Rgba::new(0, 0, 0, 255);
+        let white = Rgba::new(255, 255, 255, 255);
+        Self {
+            background: black,
+            text: white,
+            highlight: Rgba::new(26, 235, 255, 255),
+            highlight_text: black,
+            link: Rgba::new(255, 255, 0, 255),
+            disabled_text: Rgba::new(63, 242, 63, 255),
+            button: black,
+            button_text: white,
+        }
+    }
+
+    /// Gets the color scheme that matches the palette background.
+    pub fn scheme(&self) -> ColorScheme {
+        let b = self.background;
+        let luma = 0.2126 * b.red + 0.7152 * b.green + 0.0722 * b.blue;
+        if luma < 0.5 {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
         }
     }
 }
@@ -278,6 +278,36 @@ impl DisplayListBuilder {
         })
     }
 
+    /// Push a nine-patch image.
+    ///
+    /// The `slice` offsets define the nine regions of the image, in image pixels. The corners are drawn at `widths`
+    /// size, the edges are scaled or tiled along the bounds using the repeat modes, the center is only drawn if `fill` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push_nine_patch_image(
+        &mut self,
+        bounds: PxRect,
+        widths: PxSideOffsets,
+        image_id: ImageTextureId,
+        image_size: PxSize,
+        slice: PxSideOffsets,
+        fill: bool,
+        repeat_horizontal: RepeatMode,
+        repeat_vertical: RepeatMode,
+        rendering: ImageRendering,
+    ) {
+        self.list.push(DisplayItem::NinePatchImage {
+            bounds,
+            widths,
+            image_id,
+            image_size,
+            slice,
+            fill,
+            repeat_horizontal,
+            repeat_vertical,
+            rendering,
+        })
+    }
+
     /// Push a text run.
     pub fn push_text(
         &mut self,
@@ -835,6 +865,17 @@ pub enum DisplayItem {
         repeat_horizontal: RepeatMode,
         repeat_vertical: RepeatMode,
     },
+    NinePatchImage {
+        bounds: PxRect,
+        widths: PxSideOffsets,
+        image_id: ImageTextureId,
+        image_size: PxSize,
+        slice: PxSideOffsets,
+        fill: bool,
+        repeat_horizontal: RepeatMode,
+        repeat_vertical: RepeatMode,
+        rendering: ImageRendering,
+    },
 
     Text {
         clip_rect: PxRect,
@@ -0,0 +1,63 @@
+//! Drag&drop types.
+
+use std::{fmt, path::PathBuf};
+
+use zng_txt::Txt;
+
+use crate::ipc::IpcBytes;
+
+crate::declare_id! {
+    /// Identifies an ongoing drag&drop operation started by the app-process.
+    ///
+    /// The View Process defines the ID.
+    pub struct DragDropId(_);
+}
+
+/// Drag&drop data.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DragDropData {
+    /// Text string.
+    ///
+    /// View-process can convert between [`String`] and the text formats of the platform.
+    Text(Txt),
+    /// File or directory path.
+    Path(PathBuf),
+    /// Any data format supported only by the specific view-process implementation.
+    Extension {
+        /// Type key, must be in a format defined by the view-process.
+        data_type: Txt,
+        /// The raw data.
+        data: IpcBytes,
+    },
+}
+
+bitflags::bitflags! {
+    /// Drag&drop drop effect on the data source.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+    pub struct DragDropEffect: u8 {
+        /// Indicates that the dragged data will be copied from its present location to the drop location.
+        const COPY = 0b001;
+        /// Indicates that the dragged data will be moved from its present location to the drop location.
+        const MOVE = 0b010;
+        /// Indicates that some form of relationship or connection will be created between the source and drop locations.
+        const LINK = 0b100;
+    }
+}
+
+/// Error for drag start.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DragDropError {
+    /// View-process implementer does not support any of the provided data types.
+    NotSupported,
+    /// Cannot start dragging.
+    CannotStart(Txt),
+}
+impl fmt::Display for DragDropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DragDropError::NotSupported => write!(f, "not supported"),
+            DragDropError::CannotStart(txt) => write!(f, "cannot start, {txt}"),
+        }
+    }
+}
+impl std::error::Error for DragDropError {}
@@ -0,0 +1,75 @@
+//! Gamepad types.
+
+use serde::{Deserialize, Serialize};
+
+/// Gamepad button.
+///
+/// The button names follow the position of the buttons in a standard controller layout, `South` is the
+/// *A* button on Xbox controllers and the *Cross* button on PlayStation controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// Bottom action button.
+    South,
+    /// Right action button.
+    East,
+    /// Top action button.
+    North,
+    /// Left action button.
+    West,
+    /// Left bumper.
+    LeftTrigger,
+    /// Left analog trigger.
+    LeftTrigger2,
+    /// Right bumper.
+    RightTrigger,
+    /// Right analog trigger.
+    RightTrigger2,
+    /// Select or back button.
+    Select,
+    /// Start or menu button.
+    Start,
+    /// Vendor logo button.
+    Mode,
+    /// Left stick press.
+    LeftThumb,
+    /// Right stick press.
+    RightThumb,
+    /// Directional pad up.
+    DPadUp,
+    /// Directional pad down.
+    DPadDown,
+    /// Directional pad left.
+    DPadLeft,
+    /// Directional pad right.
+    DPadRight,
+    /// Button not mapped to the standard layout, with the device dependent code.
+    Other(u32),
+}
+impl GamepadButton {
+    /// If is one of the directional pad buttons.
+    pub fn is_dpad(self) -> bool {
+        matches!(
+            self,
+            GamepadButton::DPadUp | GamepadButton::DPadDown | GamepadButton::DPadLeft | GamepadButton::DPadRight
+        )
+    }
+}
+
+/// Gamepad analog axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    /// Left stick horizontal, positive to the right.
+    LeftStickX,
+    /// Left stick vertical, positive is up.
+    LeftStickY,
+    /// Left extra axis, usually a trigger.
+    LeftZ,
+    /// Right stick horizontal, positive to the right.
+    RightStickX,
+    /// Right stick vertical, positive is up.
+    RightStickY,
+    /// Right extra axis, usually a trigger.
+    RightZ,
+    /// Axis not mapped to the standard layout, with the device dependent code.
+    Other(u32),
+}
@@ -1,6 +1,6 @@
 //! Image types.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use zng_txt::Txt;
@@ -20,6 +20,27 @@ crate::declare_id! {
     pub struct ImageTextureId(_);
 }
 
+/// Options for [`Api::encode_image`].
+///
+/// [`Api::encode_image`]: crate::Api::encode_image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub struct ImageEncodeOptions {
+    /// Encoder quality, from 0 (worst) to 100 (best).
+    ///
+    /// For lossy formats this is the visual quality, for PNG this selects the compression effort.
+    /// If `None` uses the encoder default.
+    pub quality: Option<u8>,
+    /// Request lossless encoding.
+    ///
+    /// For formats that are always lossless (PNG, WebP, BMP) this is ignored, for AVIF it encodes at maximum quality,
+    /// for JPEG the request fails.
+    pub lossless: bool,
+    /// Embed an sRGB ICC profile, if the format supports it.
+    ///
+    /// Decoded image pixels are always converted to sRGB, embedding the profile helps color managed viewers.
+    pub icc: bool,
+}
+
 /// Defines how the A8 image mask pixels are to be derived from a source mask image.
 #[derive(Debug, Copy, Clone, Serialize, PartialEq, Eq, Hash, Deserialize, Default)]
 pub enum ImageMaskMode {
@@ -238,6 +259,34 @@ fn ppi_key(ppi: Option<ImagePpi>) -> Option<(u16, u16)> {
     ppi.map(|s| ((s.x * 3.0) as u16, (s.y * 3.0) as u16))
 }
 
+/// Color space of an image, monitor or window surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum ColorSpace {
+    /// Color space is not known, usually handled as [`Srgb`].
+    ///
+    /// [`Srgb`]: Self::Srgb
+    #[default]
+    Unknown,
+    /// Standard RGB, the color space used by most images and displays.
+    Srgb,
+    /// Display P3, the wide gamut color space of many modern phone cameras and displays.
+    DisplayP3,
+    /// Adobe RGB (1998), a wide gamut color space used by some cameras and photo editing workflows.
+    AdobeRgb,
+    /// ITU-R BT.2020, the wide gamut color space of HDR video.
+    Bt2020,
+    /// Linear extended sRGB, values outside of the `0.0..=1.0` range represent colors outside of the sRGB gamut.
+    ScRgb,
+    /// Other color space, defined by an ICC profile.
+    Other,
+}
+impl ColorSpace {
+    /// If the color space has a wider gamut than sRGB.
+    pub fn is_wide_gamut(self) -> bool {
+        matches!(self, Self::DisplayP3 | Self::AdobeRgb | Self::Bt2020 | Self::ScRgb)
+    }
+}
+
 /// Represents a successfully decoded image.
 ///
 /// See [`Event::ImageLoaded`].
@@ -255,6 +304,24 @@ pub struct ImageLoadedData {
     pub is_opaque: bool,
     /// If the `pixels` are in a single channel (A8).
     pub is_mask: bool,
+    /// Color space of the source image.
+    ///
+    /// The `pixels` are always converted to sRGB, this is the color space declared by the image color profile metadata.
+    pub color_space: ColorSpace,
+    /// Delay of each frame, if the image is animated.
+    ///
+    /// The image `pixels` are the first frame, other frames are decoded on demand using [`Api::image_frame`].
+    /// Is empty if the image is not animated and for frame images.
+    ///
+    /// [`Api::image_frame`]: crate::Api::image_frame
+    pub frame_delays: Vec<Duration>,
+    /// If the image was decoded from a vector format.
+    ///
+    /// The image `pixels` are rasterized at the intrinsic size of the image, other sizes are rasterized on demand
+    /// using [`Api::image_raster`]. Is `false` for raster images and for the images returned by [`Api::image_raster`].
+    ///
+    /// [`Api::image_raster`]: crate::Api::image_raster
+    pub is_vector: bool,
     /// Reference to the BGRA8 pre-multiplied image pixels or the A8 pixels if `is_mask`.
     pub pixels: IpcBytes,
 }
@@ -266,6 +333,9 @@ impl fmt::Debug for ImageLoadedData {
             .field("ppi", &self.ppi)
             .field("is_opaque", &self.is_opaque)
             .field("is_mask", &self.is_mask)
+            .field("color_space", &self.color_space)
+            .field("frame_delays", &self.frame_delays)
+            .field("is_vector", &self.is_vector)
             .field("pixels", &format_args!("<{} bytes shared memory>", self.pixels.len()))
             .finish()
     }
@@ -2045,6 +2045,59 @@ pub enum KeyState {
     Released,
 }
 
+crate::declare_id! {
+    /// Identifies a global shortcut registered by the app-process.
+    ///
+    /// The App Process defines the ID.
+    pub struct GlobalShortcutId(_);
+}
+
+bitflags::bitflags! {
+    /// Modifier keys of a [`GlobalShortcut`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct GlobalShortcutModifiers: u8 {
+        /// Any "shift" key.
+        const SHIFT = 0b0001;
+        /// Any "control" key.
+        const CTRL = 0b0010;
+        /// Any "alt" key.
+        const ALT = 0b0100;
+        /// Any "logo" key, the Windows key or the Command key.
+        const LOGO = 0b1000;
+    }
+}
+
+/// Key combination that is detected even when the app is not focused.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GlobalShortcut {
+    /// Modifier keys that must be pressed.
+    pub modifiers: GlobalShortcutModifiers,
+    /// The physical key.
+    pub key_code: KeyCode,
+}
+
+/// Error registering a [`GlobalShortcut`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GlobalShortcutError {
+    /// View-process implementer or operating system does not support global shortcuts, or does not support the key.
+    NotSupported,
+    /// The shortcut is already registered by another app.
+    AlreadyRegistered,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for GlobalShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalShortcutError::NotSupported => write!(f, "not supported"),
+            GlobalShortcutError::AlreadyRegistered => write!(f, "already registered"),
+            GlobalShortcutError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for GlobalShortcutError {}
+
 // monomorphize
 fn key_clone(key: &Key) -> Key {
     match key {
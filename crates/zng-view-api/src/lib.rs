@@ -35,12 +35,17 @@ pub mod clipboard;
 pub mod config;
 pub mod dialog;
 pub mod display_list;
+pub mod drag_drop;
 pub mod font;
+pub mod gamepad;
 pub mod image;
 pub mod ipc;
 pub mod keyboard;
 pub mod mouse;
+pub mod notification;
+pub mod sound;
 pub mod touch;
+pub mod tray;
 pub mod window;
 
 mod types;
@@ -58,11 +63,12 @@ use std::fmt;
 use api_extension::{ApiExtensionId, ApiExtensionPayload};
 use clipboard::{ClipboardData, ClipboardError};
 use dialog::DialogId;
+use drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId};
 use font::{FontFaceId, FontId, FontOptions, FontVariationName};
-use image::{ImageId, ImageMaskMode, ImageRequest, ImageTextureId};
+use image::{ImageEncodeOptions, ImageId, ImageMaskMode, ImageRequest, ImageTextureId};
 use ipc::{IpcBytes, IpcBytesReceiver};
 use window::WindowId;
-use zng_unit::{DipPoint, DipRect, DipSize, Factor, Px, PxRect};
+use zng_unit::{DipPoint, DipRect, DipSize, Factor, Px, PxRect, PxSize};
 
 /// Packaged API request.
 #[derive(Debug)]
@@ -284,6 +290,9 @@ declare_api! {
     /// This is a real renderer but not connected to any window, you can requests pixels to get the
     /// rendered frames.
     ///
+    /// The view-process may reuse the graphics context of closed headless surfaces, opening many short
+    /// lived surfaces in sequence is inexpensive.
+    ///
     /// Sends an [`Event::HeadlessOpened`] once the context and renderer have finished initializing or a
     /// [`Event::WindowOrHeadlessOpenError`] if it failed.
     pub fn open_headless(&mut self, request: window::HeadlessRequest);
@@ -299,8 +308,8 @@ declare_api! {
     /// Set window visible.
     pub fn set_visible(&mut self, id: WindowId, visible: bool);
 
-    /// Set if the window is "top-most".
-    pub fn set_always_on_top(&mut self, id: WindowId, always_on_top: bool);
+    /// Set the window z-order layer.
+    pub fn set_layer(&mut self, id: WindowId, layer: window::WindowLayer);
 
     /// Set if the user can drag-move the window when it is in `Normal` mode.
     pub fn set_movable(&mut self, id: WindowId, movable: bool);
@@ -359,6 +368,52 @@ declare_api! {
     /// Open the system title bar context menu.
     pub fn open_title_bar_context_menu(&mut self, id: WindowId, position: DipPoint);
 
+    /// Set custom entries in the window system menu.
+    ///
+    /// The [`Event::SystemMenuItemClicked`] notifies when a custom item is clicked. Set to the default value
+    /// to restore the default system menu.
+    pub fn set_system_menu(&mut self, id: WindowId, menu: window::SystemMenu);
+
+    /// Export the application menu bar of the window to the operating system global menu.
+    ///
+    /// On macOS the menu is shown in the screen menu bar when the window is focused, on Linux X11 the menu is
+    /// registered with the `com.canonical.AppMenu.Registrar` DBus service. The [`Event::AppMenuItemClicked`] notifies
+    /// when an item is clicked. Set to an empty menu to remove.
+    ///
+    /// Returns [`AppMenuError::NotSupported`] if the platform has no global menu, in this case the app should
+    /// show the menu inside the window.
+    ///
+    /// [`AppMenuError::NotSupported`]: window::AppMenuError::NotSupported
+    pub fn set_app_menu(&mut self, id: WindowId, menu: window::AppMenu) -> Result<(), window::AppMenuError>;
+
+    /// Group the `tabs` windows as native tabs of the window.
+    ///
+    /// If `tabs` is empty removes the window from its tab group. Returns `false` if native window tabbing
+    /// is not supported by the view-process.
+    pub fn set_window_tabs(&mut self, id: WindowId, tabs: Vec<WindowId>) -> bool;
+
+    /// Enable or disable continuous render mode.
+    ///
+    /// In continuous render mode the window redraws every display refresh while it is visible and sends an [`Event::RedrawTick`]
+    /// after each redraw. Render extensions can also request continuous redraw.
+    pub fn set_continuous_render(&mut self, id: WindowId, enabled: bool);
+
+    /// Enable or disable frame presented notifications.
+    ///
+    /// When enabled the window sends an [`Event::FramePresented`] after every present, with the presentation
+    /// timestamp and an estimate of the display refresh interval.
+    pub fn set_present_notify(&mut self, id: WindowId, enabled: bool);
+
+    /// Set the custom chrome regions of the window.
+    ///
+    /// Windows that draw their own title bar and borders can declare regions that behave like the native chrome, the
+    /// window manager moves or resizes the window when these regions are pressed, and also enables OS specific
+    /// gestures, like Windows Snap Layouts for the [`MaximizeButton`]. Regions are in the window client area,
+    /// the first region that contains the cursor is used, an empty list removes all regions.
+    ///
+    /// [`MaximizeButton`]: window::HitTestKind::MaximizeButton
+    pub fn set_hit_test_regions(&mut self, id: WindowId, regions: Vec<(PxRect, window::HitTestKind)>);
+
     /// Cache an image resource.
     ///
     /// The image is decoded asynchronously, the events [`Event::ImageMetadataLoaded`], [`Event::ImageLoaded`]
@@ -392,6 +447,31 @@ declare_api! {
     /// [`delete_image_use`]: Api::delete_image_use
     pub fn forget_image(&mut self, id: ImageId);
 
+    /// Decode a frame of an animated image.
+    ///
+    /// The image `id` must be loaded and have [`ImageLoadedData::frame_delays`], the `index` is the frame index,
+    /// frame zero is the image itself. Returns the new image ID, the frame image decodes asynchronously just like
+    /// an [`add_image`] request, the events [`Event::ImageLoaded`] or [`Event::ImageLoadError`] will be send.
+    ///
+    /// Frames are decoded on demand, the encoded data is retained while the animated image is in the cache.
+    /// Requesting frames in sequence is faster as many animation formats can only be decoded in sequence.
+    ///
+    /// [`ImageLoadedData::frame_delays`]: image::ImageLoadedData::frame_delays
+    /// [`add_image`]: Api::add_image
+    pub fn image_frame(&mut self, id: ImageId, index: usize) -> ImageId;
+
+    /// Rasterize a vector image at a new size.
+    ///
+    /// The image `id` must be loaded and be [`ImageLoadedData::is_vector`], the `size` is the new raster size in pixels.
+    /// Returns the new image ID, the raster image decodes asynchronously just like an [`add_image`] request, the events
+    /// [`Event::ImageLoaded`] or [`Event::ImageLoadError`] will be send.
+    ///
+    /// The encoded data is retained while the vector image is in the cache.
+    ///
+    /// [`ImageLoadedData::is_vector`]: image::ImageLoadedData::is_vector
+    /// [`add_image`]: Api::add_image
+    pub fn image_raster(&mut self, id: ImageId, size: PxSize) -> ImageId;
+
     /// Add an image resource to the window renderer.
     ///
     /// Returns the new image texture ID. If the `image_id` is not loaded returns the [`INVALID`] image ID.
@@ -424,13 +504,13 @@ declare_api! {
 
     /// Encode the image into the `format`.
     ///
-    /// The format must be one of the values returned by [`image_encoders`].
+    /// The format must be one of the values returned by [`image_encoders`], the `options` configure the encoder.
     ///
     /// Returns immediately. The encoded data will be send as the event
     /// [`Event::ImageEncoded`] or [`Event::ImageEncodeError`].
     ///
     /// [`image_encoders`]: Api::image_encoders
-    pub fn encode_image(&mut self, id: ImageId, format: Txt);
+    pub fn encode_image(&mut self, id: ImageId, format: Txt, options: ImageEncodeOptions);
 
     /// Add a raw font resource to the window renderer.
     ///
@@ -477,18 +557,41 @@ declare_api! {
     /// Returns `0` if the window is not found.
     pub fn frame_image_rect(&mut self, id: WindowId, rect: PxRect, mask: Option<ImageMaskMode>) -> ImageId;
 
+    /// Gets the renderer memory statistics of the window or surface.
+    ///
+    /// Returns all zeros if the window is not found.
+    pub fn renderer_stats(&mut self, id: WindowId) -> window::RendererStats;
+
+    /// Clear the renderer caches to free memory.
+    ///
+    /// The renderer already fully trims on system [`Event::LowMemory`], this can be used to trim when the app itself
+    /// detects memory pressure or has just closed a view with many images or glyphs.
+    pub fn trim_renderer(&mut self, id: WindowId, trim: window::RendererTrim);
+
     /// Set the video mode used when the window is in exclusive fullscreen.
     pub fn set_video_mode(&mut self, id: WindowId, mode: window::VideoMode);
 
     /// Render a new frame.
     pub fn render(&mut self, id: WindowId, frame: window::FrameRequest);
 
+    /// Render multiple new frames in sequence.
+    ///
+    /// An [`Event::FrameRendered`] is send for each frame, in order. On headless surfaces the captured frames
+    /// are read after each render and converted together in a worker thread, the events are send after the last
+    /// frame renders. This is the fastest way to capture a sequence of frames, for example, to export an animation.
+    pub fn render_batch(&mut self, id: WindowId, frames: Vec<window::FrameRequest>);
+
     /// Update the current frame and re-render it.
     pub fn render_update(&mut self, id: WindowId, frame: window::FrameUpdateRequest);
 
     /// Update the window's accessibility info tree.
     pub fn access_update(&mut self, id: WindowId, update: access::AccessTreeUpdate);
 
+    /// Announce the text using the screen reader, if the window accessibility is active.
+    ///
+    /// The view-process implements this using a live region node that is not associated with any widget.
+    pub fn access_announce(&mut self, id: WindowId, txt: Txt, priority: access::AccessLivePriority);
+
     /// Shows a native message dialog for the window.
     ///
     /// Returns an ID that identifies the response event.
@@ -505,7 +608,106 @@ declare_api! {
     /// Set the clipboard content.
     pub fn write_clipboard(&mut self, data: ClipboardData) -> Result<(), ClipboardError>;
 
+    /// Register a key combination that generates [`Event::GlobalShortcut`] even when the app is not focused.
+    ///
+    /// If the `id` is already registered it is replaced.
+    pub fn register_global_shortcut(
+        &mut self,
+        id: keyboard::GlobalShortcutId,
+        shortcut: keyboard::GlobalShortcut,
+    ) -> Result<(), keyboard::GlobalShortcutError>;
+
+    /// Unregister a global shortcut.
+    pub fn unregister_global_shortcut(&mut self, id: keyboard::GlobalShortcutId);
+
+    /// Gets the label of each physical key in the current keyboard layout.
+    ///
+    /// Returns a [`Key`] for each entry of `codes`, in the same order. Keys that generate characters are
+    /// [`Key::Char`] or [`Key::Dead`] with the character generated without modifiers, keys that cannot be mapped
+    /// or are not affected by the layout are [`Key::Unidentified`].
+    ///
+    /// # Platform Support
+    ///
+    /// The default view-process implementation supports:
+    ///
+    /// * Windows: labels from the keyboard layout of the view-process thread, [`Event::KeyboardLayoutChanged`] notifies
+    ///   when any window receives `WM_INPUTLANGCHANGE`.
+    /// * Linux and BSD, X11: labels from the active layout of the core keyboard using `libxkbcommon`,
+    ///   [`Event::KeyboardLayoutChanged`] notifies when the keyboard group or keymap changes.
+    /// * Linux and BSD, Wayland: labels from the first layout in the GNOME input sources config, or from the
+    ///   `XKB_DEFAULT_LAYOUT` environment variable and system default in other desktops. Layout changes are only
+    ///   notified on GNOME.
+    /// * macOS: not implemented, returns [`Key::Unidentified`] for all codes and layout changes are not notified.
+    /// * Other systems: not implemented, returns [`Key::Unidentified`] for all codes.
+    ///
+    /// [`Event::KeyboardLayoutChanged`]: crate::Event::KeyboardLayoutChanged
+    /// [`Key`]: keyboard::Key
+    /// [`Key::Char`]: keyboard::Key::Char
+    /// [`Key::Dead`]: keyboard::Key::Dead
+    /// [`Key::Unidentified`]: keyboard::Key::Unidentified
+    pub fn key_labels(&mut self, codes: Vec<keyboard::KeyCode>) -> Vec<keyboard::Key>;
+
+    /// Create or update a tray icon.
+    ///
+    /// If a tray icon with the same ID is already open it is updated to the new request.
+    ///
+    /// The default view-process implementation supports tray icons on Windows and on Linux desktops that provide
+    /// the `org.kde.StatusNotifierWatcher` DBus service, it returns [`TrayIconError::NotSupported`] on macOS and
+    /// other systems.
+    ///
+    /// [`TrayIconError::NotSupported`]: tray::TrayIconError::NotSupported
+    pub fn set_tray_icon(&mut self, request: tray::TrayIconRequest) -> Result<(), tray::TrayIconError>;
+
+    /// Remove a tray icon.
+    pub fn close_tray_icon(&mut self, id: tray::TrayIconId);
+
+    /// Show a native notification.
+    ///
+    /// The user response is notified using [`Event::NotificationResponded`]. If a notification with the same ID
+    /// is already open it is replaced.
+    pub fn show_notification(&mut self, request: notification::NotificationRequest) -> Result<(), notification::NotificationError>;
+
+    /// Remove a native notification.
+    pub fn close_notification(&mut self, id: notification::NotificationId);
+
+    /// Start playing a sound.
+    ///
+    /// The sound plays in the background, this method returns as soon as the playback starts. If a WAV sound
+    /// is already playing it may be stopped by the new sound.
+    pub fn play_sound(&mut self, sound: sound::SoundData) -> Result<(), sound::SoundError>;
+
+    /// Start a drag and drop operation, if the window is pressed.
+    ///
+    /// The `image` is an optional loaded image that is displayed under the cursor during the drag operation,
+    /// if not set the view-process or operating system may use a default image.
+    ///
+    /// Returns the new drag operation ID, an [`Event::AppDragEnded`] will be send when the operation finishes.
+    ///
+    /// The default view-process implements this on Windows, macOS and Linux X11, it returns [`DragDropError::NotSupported`]
+    /// on Linux Wayland.
+    ///
+    /// [`DragDropError::NotSupported`]: drag_drop::DragDropError::NotSupported
+    pub fn start_drag_drop(
+        &mut self,
+        id: WindowId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<ImageId>,
+    ) -> Result<DragDropId, DragDropError>;
+
+    /// Cancel a drag and drop operation started by [`start_drag_drop`].
+    ///
+    /// Note that macOS does not support cancellation, the default view-process refuses all drop targets instead, the
+    /// operation ends when the user releases the pointer.
+    ///
+    /// [`start_drag_drop`]: Api::start_drag_drop
+    pub fn cancel_drag_drop(&mut self, id: WindowId, drag_id: DragDropId);
+
     /// Enable or disable IME by setting a cursor area.
+    ///
+    /// The `area` is the text insert area in the window, the IME candidate window is positioned so that it does
+    /// not cover it. Set to `None` to disable IME for the window. The view-process sends [`Event::Ime`] with preview
+    /// (composition) and commit updates while IME is enabled.
     pub fn set_ime_area(&mut self, id: WindowId, area: Option<DipRect>);
 
     /// Attempt to set a system wide shutdown warning associated with the window.
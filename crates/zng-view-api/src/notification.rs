@@ -0,0 +1,74 @@
+//! Native notification types.
+
+use std::fmt;
+
+use zng_txt::Txt;
+
+crate::declare_id! {
+    /// Identifies a native notification.
+    ///
+    /// The App Process defines the ID.
+    pub struct NotificationId(_);
+}
+
+/// Native notification.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NotificationRequest {
+    /// Notification ID.
+    pub id: NotificationId,
+    /// Notification title.
+    pub title: Txt,
+    /// Notification message.
+    pub body: Txt,
+    /// Action buttons.
+    ///
+    /// Operating systems may limit the number of actions or not support actions at all.
+    pub actions: Vec<NotificationAction>,
+}
+
+/// Native notification action button.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NotificationAction {
+    /// Action identifier, returned in [`NotificationResponse::Action`] when the action is clicked.
+    pub id: Txt,
+    /// Action text.
+    pub label: Txt,
+}
+impl NotificationAction {
+    /// New action.
+    pub fn new(id: impl Into<Txt>, label: impl Into<Txt>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
+/// User response to a native notification.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum NotificationResponse {
+    /// Notification was clicked.
+    Activated,
+    /// A [`NotificationAction`] was clicked.
+    Action(Txt),
+    /// Notification was closed by the user or expired.
+    Dismissed,
+}
+
+/// Error showing a native notification.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum NotificationError {
+    /// View-process implementer or operating system does not support native notifications.
+    NotSupported,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationError::NotSupported => write!(f, "not supported"),
+            NotificationError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for NotificationError {}
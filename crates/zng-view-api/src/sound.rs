@@ -0,0 +1,51 @@
+//! Sound types.
+
+use std::fmt;
+
+use zng_txt::Txt;
+
+use crate::ipc::IpcBytes;
+
+/// System notification sound.
+///
+/// View-process implementers map these to the closest system sound, or to a simple beep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum SystemSound {
+    /// Default notification *bell*.
+    Bell,
+    /// Information message sound.
+    Info,
+    /// Warning message sound.
+    Warn,
+    /// Error message sound.
+    Error,
+    /// Question message sound.
+    Question,
+}
+
+/// Sound that can be played by the view-process.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SoundData {
+    /// System notification sound.
+    System(SystemSound),
+    /// Complete WAV file.
+    Wav(IpcBytes),
+}
+
+/// Error playing a sound.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SoundError {
+    /// View-process implementer does not support the sound data or does not support sound output.
+    NotSupported,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for SoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoundError::NotSupported => write!(f, "not supported"),
+            SoundError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for SoundError {}
@@ -0,0 +1,96 @@
+//! System tray icon types.
+
+use std::fmt;
+
+use zng_txt::Txt;
+
+use crate::image::ImageId;
+
+crate::declare_id! {
+    /// Identifies a tray icon.
+    ///
+    /// The App Process defines the ID.
+    pub struct TrayIconId(_);
+}
+
+/// Tray icon configuration.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TrayIconRequest {
+    /// Tray icon ID.
+    pub id: TrayIconId,
+    /// Text shown when the icon is hovered.
+    pub tooltip: Txt,
+    /// Icon image.
+    pub icon: Option<ImageId>,
+    /// Menu items opened when the icon is right clicked.
+    ///
+    /// If empty no menu is opened.
+    pub menu: Vec<TrayMenuItem>,
+}
+
+/// Tray icon menu item.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum TrayMenuItem {
+    /// Clickable menu item.
+    Item {
+        /// Item identifier, returned in [`Event::TrayMenuItemClicked`] when the item is clicked.
+        ///
+        /// [`Event::TrayMenuItemClicked`]: crate::Event::TrayMenuItemClicked
+        id: Txt,
+        /// Item text.
+        label: Txt,
+        /// If the item can be clicked.
+        enabled: bool,
+        /// If the item shows a check mark.
+        checked: bool,
+    },
+    /// Sub-menu.
+    Submenu {
+        /// Sub-menu text.
+        label: Txt,
+        /// If the sub-menu can be opened.
+        enabled: bool,
+        /// Sub-menu items.
+        items: Vec<TrayMenuItem>,
+    },
+    /// Separator line.
+    Separator,
+}
+impl TrayMenuItem {
+    /// New enabled and unchecked item.
+    pub fn item(id: impl Into<Txt>, label: impl Into<Txt>) -> Self {
+        Self::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+            checked: false,
+        }
+    }
+
+    /// New enabled sub-menu.
+    pub fn submenu(label: impl Into<Txt>, items: Vec<TrayMenuItem>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            enabled: true,
+            items,
+        }
+    }
+}
+
+/// Error creating or updating a tray icon.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum TrayIconError {
+    /// View-process implementer or operating system does not support tray icons.
+    NotSupported,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for TrayIconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrayIconError::NotSupported => write!(f, "not supported"),
+            TrayIconError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for TrayIconError {}
@@ -5,17 +5,23 @@ use crate::{
     api_extension::{ApiExtensionId, ApiExtensionPayload, ApiExtensions},
     config::{AnimationsConfig, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig},
     dialog::{DialogId, FileDialogResponse, MsgDialogResponse},
-    image::{ImageId, ImageLoadedData, ImagePpi},
+    drag_drop::{DragDropEffect, DragDropId},
+    gamepad::{GamepadAxis, GamepadButton},
+    image::{ImageEncodeOptions, ImageId, ImageLoadedData, ImagePpi},
     ipc::IpcBytes,
-    keyboard::{Key, KeyCode, KeyLocation, KeyState},
+    keyboard::{GlobalShortcutId, Key, KeyCode, KeyLocation, KeyState},
     mouse::{ButtonId, ButtonState, MouseButton, MouseScrollDelta},
+    notification::{NotificationId, NotificationResponse},
     touch::{TouchPhase, TouchUpdate},
-    window::{EventFrameRendered, FrameId, HeadlessOpenData, MonitorId, MonitorInfo, WindowChanged, WindowId, WindowOpenData},
+    tray::TrayIconId,
+    window::{
+        EventFramePresented, EventFrameRendered, FrameId, HeadlessOpenData, MonitorId, MonitorInfo, WindowChanged, WindowId, WindowOpenData,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
 use zng_txt::Txt;
-use zng_unit::{DipPoint, PxRect, PxSize, Rgba};
+use zng_unit::{AngleDegree, DipPoint, DipVector, PxRect, PxSize, Rgba};
 
 macro_rules! declare_id {
     ($(
@@ -144,7 +150,8 @@ pub struct Inited {
 pub enum Ime {
     /// Preview an IME insert at the last non-preview caret/selection.
     ///
-    /// The associated values are the preview string and caret/selection inside the preview string.
+    /// The associated values are the preview (composition) string and caret/selection inside the preview string.
+    /// The caret/selection indexes are char byte offsets, if the IME does not define a caret it is at the end of the preview.
     ///
     /// The preview must visually replace the last non-preview selection or insert at the last non-preview
     /// caret index. If the preview string is empty the preview must be cancelled.
@@ -188,6 +195,19 @@ pub enum Event {
     /// `EventsCleared` is not send after this event.
     FrameRendered(EventFrameRendered),
 
+    /// A frame was presented in a window with [`Api::set_present_notify`] enabled.
+    ///
+    /// [`Api::set_present_notify`]: crate::Api::set_present_notify
+    FramePresented(EventFramePresented),
+
+    /// Window redrawn in continuous render mode.
+    ///
+    /// This event is send after every redraw of windows with [`Api::set_continuous_render`] enabled, it is synced
+    /// with the display refresh rate and can be used to drive animations.
+    ///
+    /// [`Api::set_continuous_render`]: crate::Api::set_continuous_render
+    RedrawTick(WindowId),
+
     /// Window moved, resized, or minimized/maximized etc.
     ///
     /// This event aggregates events moves, resizes and other state changes into a
@@ -222,6 +242,21 @@ pub enum Event {
     /// There will be a single event triggered even if multiple files were hovered.
     HoveredFileCancelled(WindowId),
 
+    /// A drag&drop operation started by the app-process has ended.
+    ///
+    /// The `applied` effect is empty if the operation was cancelled or the data was dropped
+    /// outside any valid drop target.
+    AppDragEnded {
+        /// Window that started the drag operation.
+        window: WindowId,
+        /// Drag ID returned by [`Api::start_drag_drop`].
+        ///
+        /// [`Api::start_drag_drop`]: crate::Api::start_drag_drop
+        drag: DragDropId,
+        /// Effect applied to the data by the drop target.
+        applied: DragDropEffect,
+    },
+
     /// App window(s) focus changed.
     FocusChanged {
         /// Window that lost focus.
@@ -338,6 +373,110 @@ pub enum Event {
         /// Click level.
         stage: i64,
     },
+    /// Two-finger pinch gesture recognized by the system, usually on a touchpad.
+    ///
+    /// The view-process only generates this event if the operating system recognizes the gesture,
+    /// raw touch events are still send for touchscreens. The default view-process implements this for macOS and iOS,
+    /// on Windows precision touchpads the system converts pinch to [`MouseWheel`] with the `CTRL` modifier.
+    ///
+    /// [`MouseWheel`]: Self::MouseWheel
+    PinchGesture {
+        /// Window that was hovered by the cursor when the gesture happened.
+        window: WindowId,
+        /// Device that generated the gesture.
+        device: DeviceId,
+        /// Scale delta, positive values indicate magnification (zoom-in), negative values indicate shrinking (zoom-out).
+        ///
+        /// The scale factor for the update is `1.0 + delta`.
+        delta: f32,
+        /// Gesture state.
+        phase: TouchPhase,
+    },
+    /// Two-finger rotation gesture recognized by the system, usually on a touchpad.
+    ///
+    /// See [`PinchGesture`] for more details.
+    ///
+    /// [`PinchGesture`]: Self::PinchGesture
+    RotationGesture {
+        /// Window that was hovered by the cursor when the gesture happened.
+        window: WindowId,
+        /// Device that generated the gesture.
+        device: DeviceId,
+        /// Rotation delta, positive values indicate counter-clockwise rotation.
+        delta: AngleDegree,
+        /// Gesture state.
+        phase: TouchPhase,
+    },
+    /// Two-finger pan gesture recognized by the system.
+    ///
+    /// See [`PinchGesture`] for more details.
+    ///
+    /// [`PinchGesture`]: Self::PinchGesture
+    PanGesture {
+        /// Window that was hovered by the cursor when the gesture happened.
+        window: WindowId,
+        /// Device that generated the gesture.
+        device: DeviceId,
+        /// Pan delta.
+        delta: DipVector,
+        /// Gesture state.
+        phase: TouchPhase,
+    },
+    /// A global shortcut registered with [`Api::register_global_shortcut`] was pressed.
+    ///
+    /// [`Api::register_global_shortcut`]: crate::Api::register_global_shortcut
+    GlobalShortcut(GlobalShortcutId),
+    /// A tray icon created with [`Api::set_tray_icon`] was clicked.
+    ///
+    /// [`Api::set_tray_icon`]: crate::Api::set_tray_icon
+    TrayIconClicked {
+        /// Tray icon.
+        tray: TrayIconId,
+        /// Mouse button that was released over the icon.
+        button: MouseButton,
+        /// If the click was a double-click.
+        double: bool,
+    },
+    /// A tray icon menu item was clicked.
+    TrayMenuItemClicked {
+        /// Tray icon.
+        tray: TrayIconId,
+        /// The [`TrayMenuItem::Item`] ID.
+        ///
+        /// [`TrayMenuItem::Item`]: crate::tray::TrayMenuItem::Item
+        item: Txt,
+    },
+    /// A native notification shown with [`Api::show_notification`] received a user response.
+    ///
+    /// [`Api::show_notification`]: crate::Api::show_notification
+    NotificationResponded {
+        /// Notification.
+        notification: NotificationId,
+        /// User response.
+        response: NotificationResponse,
+    },
+    /// System clipboard content changed.
+    ///
+    /// View-process implementers may not support this event, the default view-process only notifies this event on Windows.
+    ClipboardChanged,
+    /// A window system menu custom item was clicked.
+    SystemMenuItemClicked {
+        /// Window.
+        window: WindowId,
+        /// The [`SystemMenuItem::Item`] ID.
+        ///
+        /// [`SystemMenuItem::Item`]: crate::window::SystemMenuItem::Item
+        item: Txt,
+    },
+    /// A window application menu item was clicked in the global menu bar.
+    AppMenuItemClicked {
+        /// Window.
+        window: WindowId,
+        /// The [`AppMenuItem::Item`] ID.
+        ///
+        /// [`AppMenuItem::Item`]: crate::window::AppMenuItem::Item
+        item: Txt,
+    },
     /// Motion on some analog axis. May report data redundant to other, more specific events.
     AxisMotion {
         /// Window that was focused when the motion was realized.
@@ -423,6 +562,8 @@ pub enum Event {
         image: ImageId,
         /// The format of the encoded data.
         format: Txt,
+        /// The encoder options.
+        options: ImageEncodeOptions,
         /// The encoded image data.
         data: IpcBytes,
     },
@@ -432,6 +573,8 @@ pub enum Event {
         image: ImageId,
         /// The encoded format that was requested.
         format: Txt,
+        /// The encoder options that were requested.
+        options: ImageEncodeOptions,
         /// The error message.
         error: Txt,
     },
@@ -465,6 +608,14 @@ pub enum Event {
     LocaleChanged(LocaleConfig),
     /// System color scheme or colors changed.
     ColorsConfigChanged(ColorsConfig),
+    /// System keyboard layout changed.
+    ///
+    /// Key labels must be reloaded using [`Api::key_labels`].
+    ///
+    /// Notified on Windows and Linux by the default view-process, see [`Api::key_labels`] for details.
+    ///
+    /// [`Api::key_labels`]: crate::Api::key_labels
+    KeyboardLayoutChanged,
 
     /* Raw device events */
     /// Device added or installed.
@@ -516,6 +667,42 @@ pub enum Event {
         /// If the key was pressed or released.
         state: KeyState,
     },
+    /// Gamepad connected.
+    ///
+    /// Gamepads that are already connected when the view-process starts are also notified using this event.
+    GamepadConnected {
+        /// Gamepad device.
+        device: DeviceId,
+        /// Gamepad display name.
+        name: Txt,
+    },
+    /// Gamepad disconnected.
+    GamepadDisconnected {
+        /// Gamepad device.
+        device: DeviceId,
+    },
+    /// Gamepad button changed.
+    ///
+    /// Note that this event may repeat with the same state for analog buttons, the `value` changes.
+    GamepadButton {
+        /// Gamepad device.
+        device: DeviceId,
+        /// The button.
+        button: GamepadButton,
+        /// Analog button value in the `0.0..=1.0` range, digital buttons are `0.0` or `1.0`.
+        value: f32,
+        /// If the button is pressed.
+        state: ButtonState,
+    },
+    /// Gamepad axis moved.
+    GamepadAxis {
+        /// Gamepad device.
+        device: DeviceId,
+        /// The axis.
+        axis: GamepadAxis,
+        /// Axis value in the `-1.0..=1.0` range.
+        value: f32,
+    },
     /// User responded to a native message dialog.
     MsgDialogResponse(DialogId, MsgDialogResponse),
     /// User responded to a native file dialog.
@@ -635,6 +822,56 @@ impl Event {
                 *delta_y += n_delta_y;
             }
 
+            // system gestures.
+            (
+                PinchGesture {
+                    window,
+                    device,
+                    delta,
+                    phase: TouchPhase::Move,
+                },
+                PinchGesture {
+                    window: n_window,
+                    device: n_device,
+                    delta: n_delta,
+                    phase: TouchPhase::Move,
+                },
+            ) if *window == n_window && *device == n_device => {
+                *delta += n_delta;
+            }
+            (
+                RotationGesture {
+                    window,
+                    device,
+                    delta,
+                    phase: TouchPhase::Move,
+                },
+                RotationGesture {
+                    window: n_window,
+                    device: n_device,
+                    delta: n_delta,
+                    phase: TouchPhase::Move,
+                },
+            ) if *window == n_window && *device == n_device => {
+                delta.0 += n_delta.0;
+            }
+            (
+                PanGesture {
+                    window,
+                    device,
+                    delta,
+                    phase: TouchPhase::Move,
+                },
+                PanGesture {
+                    window: n_window,
+                    device: n_device,
+                    delta: n_delta,
+                    phase: TouchPhase::Move,
+                },
+            ) if *window == n_window && *device == n_device => {
+                *delta += n_delta;
+            }
+
             // raw wheel scroll.
             (
                 DeviceMouseWheel {
@@ -665,6 +902,18 @@ impl Event {
                 *delta_y += n_delta_y;
             }
 
+            // gamepad axis.
+            (
+                GamepadAxis { device, axis, value },
+                GamepadAxis {
+                    device: n_device,
+                    axis: n_axis,
+                    value: n_value,
+                },
+            ) if *device == n_device && *axis == n_axis => {
+                *value = n_value;
+            }
+
             // touch
             (
                 Touch { window, device, touches },
@@ -738,8 +987,14 @@ impl Event {
                 }
                 *scale_factor = n_scale_factor;
             }
+            // redraw tick.
+            (RedrawTick(window), RedrawTick(n_window)) if *window == n_window => {}
             // fonts changed.
             (FontsChanged, FontsChanged) => {}
+            // clipboard changed.
+            (ClipboardChanged, ClipboardChanged) => {}
+            // keyboard layout changed.
+            (KeyboardLayoutChanged, KeyboardLayoutChanged) => {}
             // text aa.
             (FontAaChanged(config), FontAaChanged(n_config)) => {
                 *config = n_config;
@@ -8,9 +8,10 @@ use zng_txt::Txt;
 use crate::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     display_list::{DisplayList, FrameValueUpdate},
-    image::{ImageId, ImageLoadedData, ImageMaskMode},
+    image::{ColorSpace, ImageId, ImageLoadedData, ImageMaskMode},
+    ipc::IpcBytes,
 };
-use zng_unit::{Dip, DipPoint, DipRect, DipSize, DipToPx as _, Factor, Px, PxPoint, PxSize, PxToDip, PxTransform, Rgba};
+use zng_unit::{Dip, DipPoint, DipRect, DipSize, DipToPx as _, Factor, Px, PxPoint, PxRect, PxSize, PxToDip, PxTransform, Rgba};
 
 crate::declare_id! {
     /// Window ID in channel.
@@ -28,7 +29,8 @@ crate::declare_id! {
     ///
     /// In the App Process this is mapped to an unique id, but does not survived View crashes.
     ///
-    /// The View Process defines the ID.
+    /// The View Process defines the ID. The ID of a disconnected monitor is reused if a monitor
+    /// with the same name connects.
     pub struct MonitorId(_);
 
     /// Identifies a frame request for collaborative resize in [`WindowChanged`].
@@ -130,6 +132,10 @@ pub struct MonitorInfo {
     pub position: PxPoint,
     /// Width/height of the monitor region in the virtual screen, in pixels.
     pub size: PxSize,
+    /// Area of the monitor region not covered by system bars (taskbar, dock), in the virtual screen, in pixels.
+    ///
+    /// Is the full monitor region if the system does not provide this info.
+    pub work_area: PxRect,
     /// The monitor scale factor.
     pub scale_factor: Factor,
     /// Exclusive fullscreen video modes.
@@ -137,6 +143,11 @@ pub struct MonitorInfo {
 
     /// If could determine this monitor is the primary.
     pub is_primary: bool,
+
+    /// Color space of the monitor, if could determine.
+    pub color_space: ColorSpace,
+    /// Monitor ICC color profile, if available.
+    pub icc_profile: Option<IpcBytes>,
 }
 impl MonitorInfo {
     /// Returns the `size` descaled using the `scale_factor`.
@@ -215,6 +226,9 @@ pub struct WindowOpenData {
 
     /// Actual render mode, can be different from the requested mode if it is not available.
     pub render_mode: RenderMode,
+
+    /// Actual surface color space, can be different from the requested color space if it is not available.
+    pub color_space: ColorSpace,
 }
 
 /// Information about a successfully opened headless surface.
@@ -233,6 +247,60 @@ pub enum FocusIndicator {
     Info,
 }
 
+/// Renderer memory statistics, in bytes.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RendererStats {
+    /// Shared texture atlases, small images are stored in these textures.
+    ///
+    /// This does not include the [`glyph_cache`], it is stored in the same textures, but is reported separately.
+    ///
+    /// [`glyph_cache`]: Self::glyph_cache
+    pub atlas_textures: u64,
+    /// Estimated memory of the rasterized glyphs in the atlas textures.
+    ///
+    /// This is computed from the glyphs of the cached frames, glyphs rasterized for previous frames can
+    /// remain in the atlas until the cache is trimmed.
+    pub glyph_cache: u64,
+    /// Large images that have a dedicated texture.
+    pub standalone_textures: u64,
+    /// Cached picture tiles.
+    pub picture_tile_textures: u64,
+    /// Intermediary render targets.
+    pub render_target_textures: u64,
+    /// Cache of data used by shaders.
+    pub gpu_cache_textures: u64,
+    /// Vertex data, depth targets, upload buffers and other textures.
+    pub other_textures: u64,
+    /// Renderer CPU memory, the GPU cache mirror and render tasks.
+    pub heap: u64,
+}
+impl RendererStats {
+    /// Sum of all values.
+    pub fn total(&self) -> u64 {
+        self.atlas_textures
+            + self.glyph_cache
+            + self.standalone_textures
+            + self.picture_tile_textures
+            + self.render_target_textures
+            + self.gpu_cache_textures
+            + self.other_textures
+            + self.heap
+    }
+}
+
+/// Renderer cache trim request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RendererTrim {
+    /// Clear the rasterized glyphs cache.
+    ///
+    /// Glyphs are rasterized again on demand.
+    Glyphs,
+    /// Clear all renderer caches, the same as the renderer does on system low memory.
+    ///
+    /// The next frame will have to rebuild all of its resources.
+    All,
+}
+
 /// Frame image capture request.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FrameCapture {
@@ -370,8 +438,8 @@ pub struct WindowRequest {
     pub visible: bool,
     /// Window taskbar icon visibility.
     pub taskbar_visible: bool,
-    /// If the window is "top-most".
-    pub always_on_top: bool,
+    /// Window z-order layer.
+    pub layer: WindowLayer,
     /// If the user can move the window.
     pub movable: bool,
     /// If the user can resize the window.
@@ -394,6 +462,14 @@ pub struct WindowRequest {
     /// Render mode preference for this window.
     pub render_mode: RenderMode,
 
+    /// Surface color space preference for this window.
+    ///
+    /// Only [`ColorSpace::Srgb`] and [`ColorSpace::ScRgb`] are expected, other values are handled as sRGB.
+    ///
+    /// Note that the default view-process implementation does not support extended sRGB output yet, the
+    /// renderer only outputs 8-bit sRGB.
+    pub color_space: ColorSpace,
+
     /// Focus request indicator on init.
     pub focus_indicator: Option<FocusIndicator>,
 
@@ -748,6 +824,21 @@ pub struct CursorImage {
     pub hotspot: PxPoint,
 }
 
+/// Defines the z-order layer of a window in relation to other windows.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum WindowLayer {
+    /// Window is ordered normally, moves to the front when focused.
+    #[default]
+    Normal,
+    /// Window stays on top of other windows that are not also always-on-top, a.k.a. "top-most".
+    AlwaysOnTop,
+    /// Window stays below other windows that are not also always-on-bottom, a.k.a. "desktop widget".
+    ///
+    /// On Windows the window also does not activate (take focus) when clicked. On Wayland this is not supported,
+    /// the window behaves like `Normal`.
+    AlwaysOnBottom,
+}
+
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ResizeDirection {
@@ -808,6 +899,27 @@ impl ResizeDirection {
     }
 }
 
+/// Defines the native behavior of a window region when the window draws its own chrome.
+///
+/// See [`Api::set_hit_test_regions`] for more details.
+///
+/// [`Api::set_hit_test_regions`]: crate::Api::set_hit_test_regions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum HitTestKind {
+    /// Region behaves like the system title bar, pressing and dragging moves the window.
+    Drag,
+    /// Region behaves like the system window border, pressing and dragging resizes the window in the direction.
+    Resize(ResizeDirection),
+    /// Region behaves like the system minimize button.
+    MinimizeButton,
+    /// Region behaves like the system maximize/restore button.
+    ///
+    /// On Windows this enables the Snap Layouts flyout.
+    MaximizeButton,
+    /// Region behaves like the system close button.
+    CloseButton,
+}
+
 /// Window state.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Default)]
 pub enum WindowState {
@@ -848,6 +960,27 @@ pub struct EventFrameRendered {
     pub frame_image: Option<ImageLoadedData>,
 }
 
+/// [`Event::FramePresented`] payload.
+///
+/// [`Event::FramePresented`]: crate::Event::FramePresented
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventFramePresented {
+    /// Window that presented.
+    pub window: WindowId,
+    /// Last frame rendered, the frame that was presented.
+    pub frame: FrameId,
+    /// System time just after the present.
+    ///
+    /// Note that this is the system time of the view-process, it is the same clock as the app-process
+    /// in the same machine.
+    pub presented_at: std::time::SystemTime,
+    /// Estimated duration between display refreshes.
+    ///
+    /// The estimate is the interval between consecutive presents when presenting every refresh, otherwise
+    /// it is the refresh rate of the monitor that contains the window.
+    pub refresh_interval: std::time::Duration,
+}
+
 /// [`Event::WindowChanged`] payload.
 ///
 /// [`Event::WindowChanged`]: crate::Event::WindowChanged
@@ -1012,3 +1145,130 @@ bitflags::bitflags! {
         const MAXIMIZE  = 1 << 2;
     }
 }
+
+/// Custom entries in the window system menu.
+///
+/// The system menu is the window menu opened by right clicking the title bar or by pressing `Alt+Space` on Windows.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SystemMenu {
+    /// Custom items.
+    ///
+    /// The items are appended after the default items, separated by a line.
+    pub items: Vec<SystemMenuItem>,
+    /// Remove the default items, like "Move", "Minimize" and "Close".
+    pub hide_default: bool,
+}
+impl SystemMenu {
+    /// If the menu is the default system menu.
+    pub fn is_default(&self) -> bool {
+        self.items.is_empty() && !self.hide_default
+    }
+}
+
+/// Window system menu item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SystemMenuItem {
+    /// Clickable menu item.
+    Item {
+        /// Item identifier, returned in [`Event::SystemMenuItemClicked`] when the item is clicked.
+        ///
+        /// [`Event::SystemMenuItemClicked`]: crate::Event::SystemMenuItemClicked
+        id: Txt,
+        /// Item text.
+        label: Txt,
+        /// If the item can be clicked.
+        enabled: bool,
+        /// If the item shows a check mark.
+        checked: bool,
+    },
+    /// Sub-menu.
+    Submenu {
+        /// Sub-menu text.
+        label: Txt,
+        /// If the sub-menu can be opened.
+        enabled: bool,
+        /// Sub-menu items.
+        items: Vec<SystemMenuItem>,
+    },
+    /// Separator line.
+    Separator,
+}
+
+/// Application menu bar exported to the operating system global menu.
+///
+/// The global menu is the macOS menu bar at the top of the screen or the Unity/KDE menu bar that shows
+/// the menu of the focused window, exported using the `com.canonical.dbusmenu` DBus interface.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AppMenu {
+    /// Top level items, usually all are [`AppMenuItem::Submenu`].
+    ///
+    /// If empty the global menu is removed.
+    pub items: Vec<AppMenuItem>,
+}
+impl AppMenu {
+    /// If the menu has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Application menu bar item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AppMenuItem {
+    /// Clickable menu item.
+    Item {
+        /// Item identifier, returned in [`Event::AppMenuItemClicked`] when the item is clicked.
+        ///
+        /// [`Event::AppMenuItemClicked`]: crate::Event::AppMenuItemClicked
+        id: Txt,
+        /// Item text.
+        label: Txt,
+        /// If the item can be clicked.
+        enabled: bool,
+        /// If the item shows a check mark.
+        checked: bool,
+        /// Keyboard shortcut displayed in the item.
+        ///
+        /// Note that the view-process may also invoke the item when the shortcut is pressed, the app-process
+        /// must not handle the same key press again if the item clicked event is received.
+        shortcut: Option<MenuShortcut>,
+    },
+    /// Sub-menu.
+    Submenu {
+        /// Sub-menu text.
+        label: Txt,
+        /// If the sub-menu can be opened.
+        enabled: bool,
+        /// Sub-menu items.
+        items: Vec<AppMenuItem>,
+    },
+    /// Separator line.
+    Separator,
+}
+
+/// Key combination displayed in an [`AppMenuItem`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MenuShortcut {
+    /// Modifier keys.
+    pub modifiers: crate::keyboard::GlobalShortcutModifiers,
+    /// The key.
+    pub key: crate::keyboard::Key,
+}
+
+/// Error setting an [`AppMenu`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AppMenuError {
+    /// View-process implementer, operating system or desktop environment does not provide a global menu.
+    NotSupported,
+    /// Other error.
+    Other(Txt),
+}
+impl fmt::Display for AppMenuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppMenuError::NotSupported => write!(f, "not supported"),
+            AppMenuError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl std::error::Error for AppMenuError {}
@@ -0,0 +1,650 @@
+//! Application global menu bar.
+//!
+//! Implemented using `NSApp.mainMenu` on macOS and the `com.canonical.dbusmenu` DBus interface on Linux X11,
+//! not supported on other systems.
+
+use zng_view_api::window::{AppMenu, AppMenuError, WindowId};
+
+use crate::AppEventSender;
+
+pub(crate) struct AppMenus {
+    #[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(unused))]
+    app_sender: AppEventSender,
+    /// Menus by window, on macOS the menu of the focused window is the `NSApp.mainMenu`.
+    #[cfg(target_os = "macos")]
+    menus: Vec<(WindowId, AppMenu)>,
+    #[cfg(target_os = "linux")]
+    dbus: Option<dbus_menu::DbusMenus>,
+}
+impl AppMenus {
+    pub fn new(app_sender: AppEventSender) -> Self {
+        Self {
+            app_sender,
+            #[cfg(target_os = "macos")]
+            menus: vec![],
+            #[cfg(target_os = "linux")]
+            dbus: None,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn set(&mut self, id: WindowId, window: &winit::window::Window, menu: AppMenu) -> Result<(), AppMenuError> {
+        self.menus.retain(|(w, _)| *w != id);
+        if menu.is_empty() {
+            macos_menu::clear(id);
+        } else {
+            if window.has_focus() {
+                macos_menu::apply(&self.app_sender, id, &menu);
+            }
+            self.menus.push((id, menu));
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn focused(&mut self, id: WindowId) {
+        if let Some((_, menu)) = self.menus.iter().find(|(w, _)| *w == id) {
+            macos_menu::apply(&self.app_sender, id, menu);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn close(&mut self, id: WindowId) {
+        self.menus.retain(|(w, _)| *w != id);
+        macos_menu::clear(id);
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set(&mut self, id: WindowId, window: &winit::window::Window, menu: AppMenu) -> Result<(), AppMenuError> {
+        use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+
+        let xid = match window.window_handle().map(|h| h.as_raw()) {
+            Ok(RawWindowHandle::Xlib(w)) => w.window as u32,
+            Ok(RawWindowHandle::Xcb(w)) => w.window.get(),
+            // KDE on Wayland uses the `org_kde_kwin_appmenu` protocol, not implemented
+            _ => return Err(AppMenuError::NotSupported),
+        };
+        if self.dbus.is_none() {
+            if menu.is_empty() {
+                return Ok(());
+            }
+            self.dbus = Some(dbus_menu::DbusMenus::connect()?);
+        }
+        self.dbus.as_mut().unwrap().set(&self.app_sender, id, xid, menu)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn focused(&mut self, _: WindowId) {
+        // the desktop environment tracks the focused window
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn close(&mut self, id: WindowId) {
+        if let Some(d) = &mut self.dbus {
+            d.close(id);
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    pub fn set(&mut self, _: WindowId, _: &winit::window::Window, _: AppMenu) -> Result<(), AppMenuError> {
+        Err(AppMenuError::NotSupported)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    pub fn focused(&mut self, _: WindowId) {}
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    pub fn close(&mut self, _: WindowId) {}
+}
+
+#[cfg(target_os = "macos")]
+mod macos_menu {
+    use std::cell::RefCell;
+
+    use objc2::{declare_class, msg_send_id, mutability, rc::Retained, sel, ClassType, DeclaredClass};
+    use objc2_app_kit::{NSApplication, NSControlStateValueOff, NSControlStateValueOn, NSEventModifierFlags, NSMenu, NSMenuItem};
+    use objc2_foundation::{MainThreadMarker, NSObject, NSString};
+    use zng_txt::Txt;
+    use zng_view_api::{
+        keyboard::{GlobalShortcutModifiers, Key},
+        window::{AppMenu, AppMenuItem, MenuShortcut, WindowId},
+        Event,
+    };
+
+    use crate::{AppEvent, AppEventSender};
+
+    struct ActiveMenu {
+        app_sender: AppEventSender,
+        window: WindowId,
+        /// Item IDs by `NSMenuItem.tag`.
+        ids: Vec<Txt>,
+        target: Retained<MenuTarget>,
+    }
+
+    thread_local! {
+        /// State is shared with the [`MenuTarget`] action.
+        static ACTIVE: RefCell<Option<ActiveMenu>> = const { RefCell::new(None) };
+    }
+
+    declare_class!(
+        struct MenuTarget;
+
+        unsafe impl ClassType for MenuTarget {
+            type Super = NSObject;
+            type Mutability = mutability::MainThreadOnly;
+            const NAME: &'static str = "ZngAppMenuTarget";
+        }
+
+        impl DeclaredClass for MenuTarget {}
+
+        unsafe impl MenuTarget {
+            #[method(itemClicked:)]
+            fn item_clicked(&self, sender: &NSMenuItem) {
+                // SAFETY: the sender is a valid menu item.
+                let tag = unsafe { sender.tag() };
+                ACTIVE.with_borrow(|a| {
+                    if let Some(a) = a {
+                        if let Some(item) = usize::try_from(tag).ok().and_then(|i| a.ids.get(i)) {
+                            let _ = a.app_sender.send(AppEvent::Notify(Event::AppMenuItemClicked {
+                                window: a.window,
+                                item: item.clone(),
+                            }));
+                        }
+                    }
+                });
+            }
+        }
+    );
+    impl MenuTarget {
+        fn new(mtm: MainThreadMarker) -> Retained<Self> {
+            let this = mtm.alloc::<Self>().set_ivars(());
+            // SAFETY: `NSObject::init` has no preconditions.
+            unsafe { msg_send_id![super(this), init] }
+        }
+    }
+
+    pub fn apply(app_sender: &AppEventSender, window: WindowId, menu: &AppMenu) {
+        let mtm = MainThreadMarker::new().expect("app menu must be set in the main thread");
+        ACTIVE.with_borrow_mut(|a| {
+            let target = match a.take() {
+                Some(a) => a.target,
+                None => MenuTarget::new(mtm),
+            };
+            let mut ids = vec![];
+            let bar = build_menu(mtm, "", &menu.items, &target, &mut ids);
+            NSApplication::sharedApplication(mtm).setMainMenu(Some(&bar));
+            *a = Some(ActiveMenu {
+                app_sender: app_sender.clone(),
+                window,
+                ids,
+                target,
+            });
+        });
+    }
+
+    pub fn clear(window: WindowId) {
+        ACTIVE.with_borrow_mut(|a| {
+            if a.as_ref().map(|a| a.window == window).unwrap_or(false) {
+                *a = None;
+                if let Some(mtm) = MainThreadMarker::new() {
+                    NSApplication::sharedApplication(mtm).setMainMenu(None);
+                }
+            }
+        })
+    }
+
+    fn build_menu(mtm: MainThreadMarker, title: &str, items: &[AppMenuItem], target: &MenuTarget, ids: &mut Vec<Txt>) -> Retained<NSMenu> {
+        // SAFETY: new menu, title is a valid string.
+        let menu = unsafe { NSMenu::initWithTitle(mtm.alloc(), &NSString::from_str(title)) };
+        // enabled state is set by the app-process
+        menu.setAutoenablesItems(false);
+
+        for item in items {
+            let ns_item = match item {
+                AppMenuItem::Item {
+                    id,
+                    label,
+                    enabled,
+                    checked,
+                    shortcut,
+                } => {
+                    let (key, modifiers) = shortcut.as_ref().map(key_equivalent).unwrap_or_default();
+                    // SAFETY: new item, action is implemented by the target.
+                    let ns_item = unsafe {
+                        NSMenuItem::initWithTitle_action_keyEquivalent(
+                            mtm.alloc(),
+                            &NSString::from_str(label),
+                            Some(sel!(itemClicked:)),
+                            &NSString::from_str(&key),
+                        )
+                    };
+                    // SAFETY: the target outlives the menu, it is only replaced in `apply`.
+                    unsafe {
+                        ns_item.setTarget(Some(target));
+                        ns_item.setTag(ids.len() as _);
+                        ns_item.setKeyEquivalentModifierMask(modifiers);
+                        ns_item.setState(if *checked { NSControlStateValueOn } else { NSControlStateValueOff });
+                    }
+                    ns_item.setEnabled(*enabled);
+                    ids.push(id.clone());
+                    ns_item
+                }
+                AppMenuItem::Submenu { label, enabled, items } => {
+                    let sub = build_menu(mtm, label, items, target, ids);
+                    // SAFETY: new item without action.
+                    let ns_item = unsafe {
+                        NSMenuItem::initWithTitle_action_keyEquivalent(mtm.alloc(), &NSString::from_str(label), None, &NSString::new())
+                    };
+                    ns_item.setSubmenu(Some(&sub));
+                    ns_item.setEnabled(*enabled);
+                    ns_item
+                }
+                AppMenuItem::Separator => NSMenuItem::separatorItem(mtm),
+            };
+            menu.addItem(&ns_item);
+        }
+
+        menu
+    }
+
+    fn key_equivalent(s: &MenuShortcut) -> (String, NSEventModifierFlags) {
+        let key = match &s.key {
+            Key::Char(c) => c.to_lowercase().to_string(),
+            Key::Enter => "\r".to_owned(),
+            Key::Tab => "\t".to_owned(),
+            Key::Backspace => "\u{8}".to_owned(),
+            Key::Escape => "\u{1b}".to_owned(),
+            Key::Delete => "\u{7f}".to_owned(),
+            Key::Space => " ".to_owned(),
+            // NSF1FunctionKey..
+            Key::F1 => "\u{F704}".to_owned(),
+            Key::F2 => "\u{F705}".to_owned(),
+            Key::F3 => "\u{F706}".to_owned(),
+            Key::F4 => "\u{F707}".to_owned(),
+            Key::F5 => "\u{F708}".to_owned(),
+            Key::F6 => "\u{F709}".to_owned(),
+            Key::F7 => "\u{F70A}".to_owned(),
+            Key::F8 => "\u{F70B}".to_owned(),
+            Key::F9 => "\u{F70C}".to_owned(),
+            Key::F10 => "\u{F70D}".to_owned(),
+            Key::F11 => "\u{F70E}".to_owned(),
+            Key::F12 => "\u{F70F}".to_owned(),
+            // shortcut shows only in the app-process
+            _ => return (String::new(), NSEventModifierFlags::empty()),
+        };
+        let mut modifiers = NSEventModifierFlags::empty();
+        if s.modifiers.contains(GlobalShortcutModifiers::SHIFT) {
+            modifiers |= NSEventModifierFlags::NSEventModifierFlagShift;
+        }
+        if s.modifiers.contains(GlobalShortcutModifiers::CTRL) {
+            modifiers |= NSEventModifierFlags::NSEventModifierFlagControl;
+        }
+        if s.modifiers.contains(GlobalShortcutModifiers::ALT) {
+            modifiers |= NSEventModifierFlags::NSEventModifierFlagOption;
+        }
+        if s.modifiers.contains(GlobalShortcutModifiers::LOGO) {
+            modifiers |= NSEventModifierFlags::NSEventModifierFlagCommand;
+        }
+        (key, modifiers)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) mod dbus_menu {
+    use std::collections::HashMap;
+
+    use zbus::{
+        blocking::Connection,
+        interface,
+        object_server::SignalContext,
+        zvariant::{ObjectPath, OwnedValue, StructureBuilder, Value},
+    };
+    use zng_txt::Txt;
+    use zng_view_api::{
+        keyboard::{GlobalShortcutModifiers, Key},
+        tray::TrayIconId,
+        window::{AppMenu, AppMenuError, AppMenuItem, WindowId},
+        Event,
+    };
+
+    use crate::{AppEvent, AppEventSender};
+
+    /// Exported menus in the session bus.
+    pub struct DbusMenus {
+        connection: Connection,
+        menus: Vec<(WindowId, String)>,
+    }
+    impl DbusMenus {
+        pub fn connect() -> Result<Self, AppMenuError> {
+            let connection = Connection::session().map_err(|e| AppMenuError::Other(e.to_string().into()))?;
+
+            // Unity and KDE Plasma (X11) provide the registrar, other desktops don't show global menus
+            let has_registrar = connection
+                .call_method(
+                    Some("org.freedesktop.DBus"),
+                    "/org/freedesktop/DBus",
+                    Some("org.freedesktop.DBus"),
+                    "NameHasOwner",
+                    &("com.canonical.AppMenu.Registrar",),
+                )
+                .and_then(|r| r.body().deserialize::<bool>())
+                .unwrap_or(false);
+            if !has_registrar {
+                return Err(AppMenuError::NotSupported);
+            }
+
+            Ok(Self { connection, menus: vec![] })
+        }
+
+        pub fn set(&mut self, app_sender: &AppEventSender, id: WindowId, xid: u32, menu: AppMenu) -> Result<(), AppMenuError> {
+            let map_err = |e: zbus::Error| AppMenuError::Other(e.to_string().into());
+
+            if menu.is_empty() {
+                self.close(id);
+                return Ok(());
+            }
+
+            if let Some((_, path)) = self.menus.iter().find(|(w, _)| *w == id) {
+                let iface = self
+                    .connection
+                    .object_server()
+                    .interface::<_, DbusMenu>(path.as_str())
+                    .map_err(map_err)?;
+                let revision = iface.get_mut().update(&menu.items);
+                zbus::block_on(DbusMenu::layout_updated(iface.signal_context(), revision, 0)).map_err(map_err)?;
+            } else {
+                let path = format!("/MenuBar/{}", id.get());
+                let iface = DbusMenu::new(app_sender.clone(), MenuOwner::Window(id), &menu.items);
+                self.connection.object_server().at(path.as_str(), iface).map_err(map_err)?;
+                let obj_path = ObjectPath::try_from(path.as_str()).map_err(|e| map_err(e.into()))?;
+                self.connection
+                    .call_method(
+                        Some("com.canonical.AppMenu.Registrar"),
+                        "/com/canonical/AppMenu/Registrar",
+                        Some("com.canonical.AppMenu.Registrar"),
+                        "RegisterWindow",
+                        &(xid, obj_path),
+                    )
+                    .map_err(map_err)?;
+                self.menus.push((id, path));
+            }
+            Ok(())
+        }
+
+        pub fn close(&mut self, id: WindowId) {
+            if let Some(i) = self.menus.iter().position(|(w, _)| *w == id) {
+                let (_, path) = self.menus.swap_remove(i);
+                let _ = self.connection.object_server().remove::<DbusMenu, _>(path.as_str());
+            }
+        }
+    }
+
+    /// Flat menu node, the root is the node `0`.
+    struct Node {
+        /// App item ID, if the node is a clickable item.
+        id: Option<Txt>,
+        props: HashMap<String, OwnedValue>,
+        children: Vec<i32>,
+    }
+
+    fn flatten(items: &[AppMenuItem]) -> Vec<Node> {
+        fn push(nodes: &mut Vec<Node>, items: &[AppMenuItem]) -> Vec<i32> {
+            let mut children = Vec::with_capacity(items.len());
+            for item in items {
+                let i = nodes.len();
+                children.push(i as i32);
+                let mut props = HashMap::new();
+                let mut id = None;
+                nodes.push(Node {
+                    id: None,
+                    props: HashMap::new(),
+                    children: vec![],
+                });
+                let mut sub = vec![];
+                match item {
+                    AppMenuItem::Item {
+                        id: item_id,
+                        label,
+                        enabled,
+                        checked,
+                        shortcut,
+                    } => {
+                        props.insert("label".to_owned(), str_value(&mnemonic_escape(label)));
+                        props.insert("enabled".to_owned(), OwnedValue::from(*enabled));
+                        if *checked {
+                            props.insert("toggle-type".to_owned(), str_value("checkmark"));
+                            props.insert("toggle-state".to_owned(), OwnedValue::from(1i32));
+                        }
+                        if let Some(s) = shortcut {
+                            if let Some(key) = shortcut_key(&s.key) {
+                                let mut keys = vec![];
+                                for (flag, name) in [
+                                    (GlobalShortcutModifiers::CTRL, "Control"),
+                                    (GlobalShortcutModifiers::ALT, "Alt"),
+                                    (GlobalShortcutModifiers::SHIFT, "Shift"),
+                                    (GlobalShortcutModifiers::LOGO, "Super"),
+                                ] {
+                                    if s.modifiers.contains(flag) {
+                                        keys.push(name.to_owned());
+                                    }
+                                }
+                                keys.push(key);
+                                if let Ok(v) = Value::from(vec![keys]).try_to_owned() {
+                                    props.insert("shortcut".to_owned(), v);
+                                }
+                            }
+                        }
+                        id = Some(item_id.clone());
+                    }
+                    AppMenuItem::Submenu { label, enabled, items } => {
+                        props.insert("label".to_owned(), str_value(&mnemonic_escape(label)));
+                        props.insert("enabled".to_owned(), OwnedValue::from(*enabled));
+                        props.insert("children-display".to_owned(), str_value("submenu"));
+                        sub = push(nodes, items);
+                    }
+                    AppMenuItem::Separator => {
+                        props.insert("type".to_owned(), str_value("separator"));
+                    }
+                }
+                let n = &mut nodes[i];
+                n.id = id;
+                n.props = props;
+                n.children = sub;
+            }
+            children
+        }
+
+        let mut nodes = vec![Node {
+            id: None,
+            props: HashMap::from([("children-display".to_owned(), str_value("submenu"))]),
+            children: vec![],
+        }];
+        let root_children = push(&mut nodes, items);
+        nodes[0].children = root_children;
+        nodes
+    }
+
+    fn str_value(s: &str) -> OwnedValue {
+        Value::from(s).try_to_owned().unwrap()
+    }
+
+    /// DBus menu labels use `_` to mark mnemonics.
+    fn mnemonic_escape(label: &str) -> String {
+        label.replace('_', "__")
+    }
+
+    fn shortcut_key(key: &Key) -> Option<String> {
+        let k = match key {
+            Key::Char(c) => c.to_uppercase().to_string(),
+            Key::Enter => "Return".to_owned(),
+            Key::Tab => "Tab".to_owned(),
+            Key::Backspace => "BackSpace".to_owned(),
+            Key::Escape => "Escape".to_owned(),
+            Key::Delete => "Delete".to_owned(),
+            Key::Space => "space".to_owned(),
+            Key::Home => "Home".to_owned(),
+            Key::End => "End".to_owned(),
+            Key::PageUp => "Page_Up".to_owned(),
+            Key::PageDown => "Page_Down".to_owned(),
+            Key::F1 => "F1".to_owned(),
+            Key::F2 => "F2".to_owned(),
+            Key::F3 => "F3".to_owned(),
+            Key::F4 => "F4".to_owned(),
+            Key::F5 => "F5".to_owned(),
+            Key::F6 => "F6".to_owned(),
+            Key::F7 => "F7".to_owned(),
+            Key::F8 => "F8".to_owned(),
+            Key::F9 => "F9".to_owned(),
+            Key::F10 => "F10".to_owned(),
+            Key::F11 => "F11".to_owned(),
+            Key::F12 => "F12".to_owned(),
+            _ => return None,
+        };
+        Some(k)
+    }
+
+    /// Menu that receives the item clicks.
+    pub enum MenuOwner {
+        /// Window global menu.
+        Window(WindowId),
+        /// Tray icon menu.
+        Tray(TrayIconId),
+    }
+
+    /// Implements `com.canonical.dbusmenu` for one window or tray icon.
+    pub struct DbusMenu {
+        app_sender: AppEventSender,
+        owner: MenuOwner,
+        revision: u32,
+        nodes: Vec<Node>,
+    }
+    impl DbusMenu {
+        pub fn new(app_sender: AppEventSender, owner: MenuOwner, items: &[AppMenuItem]) -> Self {
+            Self {
+                app_sender,
+                owner,
+                revision: 1,
+                nodes: flatten(items),
+            }
+        }
+
+        /// Replace the items, returns the new layout revision.
+        pub fn update(&mut self, items: &[AppMenuItem]) -> u32 {
+            self.revision += 1;
+            self.nodes = flatten(items);
+            self.revision
+        }
+
+        fn node_props(&self, id: i32, names: &[String]) -> HashMap<String, OwnedValue> {
+            match self.nodes.get(id as usize) {
+                Some(n) => n
+                    .props
+                    .iter()
+                    .filter(|(k, _)| names.is_empty() || names.contains(k))
+                    .map(|(k, v)| (k.clone(), v.try_clone().unwrap()))
+                    .collect(),
+                None => HashMap::new(),
+            }
+        }
+
+        fn layout(&self, id: i32, depth: i32, names: &[String]) -> (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>) {
+            let children = match self.nodes.get(id as usize) {
+                Some(n) if depth != 0 => n
+                    .children
+                    .iter()
+                    .map(|&c| {
+                        let (c_id, c_props, c_children) = self.layout(c, depth - 1, names);
+                        let s = StructureBuilder::new()
+                            .add_field(c_id)
+                            .add_field(c_props)
+                            .add_field(c_children)
+                            .build();
+                        Value::from(s).try_to_owned().unwrap()
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            (id, self.node_props(id, names), children)
+        }
+    }
+    #[interface(name = "com.canonical.dbusmenu")]
+    impl DbusMenu {
+        #[allow(clippy::type_complexity)]
+        fn get_layout(
+            &self,
+            parent_id: i32,
+            recursion_depth: i32,
+            property_names: Vec<String>,
+        ) -> (u32, (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>)) {
+            (self.revision, self.layout(parent_id, recursion_depth, &property_names))
+        }
+
+        fn get_group_properties(&self, ids: Vec<i32>, property_names: Vec<String>) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+            ids.into_iter().map(|id| (id, self.node_props(id, &property_names))).collect()
+        }
+
+        fn get_property(&self, id: i32, name: String) -> zbus::fdo::Result<OwnedValue> {
+            self.nodes
+                .get(id as usize)
+                .and_then(|n| n.props.get(&name))
+                .map(|v| v.try_clone().unwrap())
+                .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("no property `{name}` for item `{id}`")))
+        }
+
+        fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+            if event_id == "clicked" {
+                if let Some(item) = self.nodes.get(id as usize).and_then(|n| n.id.clone()) {
+                    let ev = match self.owner {
+                        MenuOwner::Window(window) => Event::AppMenuItemClicked { window, item },
+                        MenuOwner::Tray(tray) => Event::TrayMenuItemClicked { tray, item },
+                    };
+                    let _ = self.app_sender.send(AppEvent::Notify(ev));
+                }
+            }
+        }
+
+        fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+            let mut errors = vec![];
+            for (id, event_id, data, timestamp) in events {
+                if (id as usize) < self.nodes.len() {
+                    self.event(id, event_id, data, timestamp);
+                } else {
+                    errors.push(id);
+                }
+            }
+            errors
+        }
+
+        fn about_to_show(&self, _id: i32) -> bool {
+            // layout is always up-to-date
+            false
+        }
+
+        fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+            (vec![], vec![])
+        }
+
+        #[zbus(signal)]
+        pub async fn layout_updated(ctx: &SignalContext<'_>, revision: u32, parent: i32) -> zbus::Result<()>;
+
+        #[zbus(property)]
+        fn version(&self) -> u32 {
+            3
+        }
+
+        #[zbus(property)]
+        fn text_direction(&self) -> &str {
+            "ltr"
+        }
+
+        #[zbus(property)]
+        fn status(&self) -> &str {
+            "normal"
+        }
+
+        #[zbus(property)]
+        fn icon_theme_path(&self) -> Vec<String> {
+            vec![]
+        }
+    }
+}
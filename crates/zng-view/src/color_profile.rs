@@ -0,0 +1,232 @@
+//! Color profile detection and conversion.
+
+use moxcms::{ColorProfile, DataColorSpace, Layout, TransformOptions, Xyzd};
+use winit::monitor::MonitorHandle;
+use zng_view_api::image::ColorSpace;
+
+/// Classify the ICC `profile`.
+pub(crate) fn icc_color_space(profile: &[u8]) -> ColorSpace {
+    match ColorProfile::new_from_slice(profile) {
+        Ok(p) => color_space(&p),
+        Err(_) => ColorSpace::Unknown,
+    }
+}
+
+fn color_space(profile: &ColorProfile) -> ColorSpace {
+    if profile.color_space != DataColorSpace::Rgb {
+        return ColorSpace::Other;
+    }
+
+    fn near(a: Xyzd, b: Xyzd) -> bool {
+        (a.x - b.x).abs() < 0.01 && (a.y - b.y).abs() < 0.01 && (a.z - b.z).abs() < 0.01
+    }
+
+    let known = [
+        (ColorSpace::Srgb, ColorProfile::new_srgb()),
+        (ColorSpace::DisplayP3, ColorProfile::new_display_p3()),
+        (ColorSpace::AdobeRgb, ColorProfile::new_adobe_rgb()),
+        (ColorSpace::Bt2020, ColorProfile::new_bt2020()),
+    ];
+    for (cs, k) in known {
+        if near(profile.red_colorant, k.red_colorant)
+            && near(profile.green_colorant, k.green_colorant)
+            && near(profile.blue_colorant, k.blue_colorant)
+        {
+            return cs;
+        }
+    }
+    ColorSpace::Other
+}
+
+/// Convert the `image` pixels from the ICC `profile` to sRGB.
+///
+/// Returns the converted image and the source color space. Images with an sRGB or invalid profile are not converted.
+pub(crate) fn convert_to_srgb(image: image::DynamicImage, profile: &[u8]) -> (image::DynamicImage, ColorSpace) {
+    let src = match ColorProfile::new_from_slice(profile) {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::debug!("ignoring invalid image ICC profile, {e}");
+            return (image, ColorSpace::Unknown);
+        }
+    };
+    let cs = color_space(&src);
+    if matches!(cs, ColorSpace::Srgb | ColorSpace::Other) || image.color().channel_count() < 3 {
+        return (image, cs);
+    }
+
+    let dst = ColorProfile::new_srgb();
+    let (w, h) = (image.width(), image.height());
+    let has_alpha = image.color().has_alpha();
+    let (layout, pixels) = if has_alpha {
+        (Layout::Rgba, image.into_rgba8().into_raw())
+    } else {
+        (Layout::Rgb, image.into_rgb8().into_raw())
+    };
+
+    let mut out = vec![0; pixels.len()];
+    let r = src
+        .create_transform_8bit(layout, &dst, layout, TransformOptions::default())
+        .and_then(|t| t.transform(&pixels, &mut out));
+    let out = match r {
+        Ok(()) => out,
+        Err(e) => {
+            tracing::error!("cannot convert image to sRGB, {e}");
+            pixels
+        }
+    };
+
+    let image = if has_alpha {
+        image::DynamicImage::ImageRgba8(image::ImageBuffer::from_raw(w, h, out).unwrap())
+    } else {
+        image::DynamicImage::ImageRgb8(image::ImageBuffer::from_raw(w, h, out).unwrap())
+    };
+    (image, cs)
+}
+
+/// Encoded sRGB ICC profile.
+pub(crate) fn srgb_icc_profile() -> Vec<u8> {
+    ColorProfile::new_srgb().encode().unwrap_or_default()
+}
+
+/// Gets the monitor ICC profile.
+#[cfg(windows)]
+pub(crate) fn monitor_icc_profile(handle: &MonitorHandle) -> Option<Vec<u8>> {
+    use windows_sys::Win32::{
+        Graphics::Gdi::{CreateDCW, DeleteDC},
+        UI::ColorSystem::GetICMProfileW,
+    };
+    use winit::platform::windows::MonitorHandleExtWindows as _;
+
+    let device: Vec<u16> = handle.native_id().encode_utf16().chain([0]).collect();
+
+    // SAFETY: strings are null terminated and the DC is deleted.
+    let path = unsafe {
+        let hdc = CreateDCW(device.as_ptr(), std::ptr::null(), std::ptr::null(), std::ptr::null());
+        if hdc == 0 {
+            return None;
+        }
+        let mut len = 0u32;
+        GetICMProfileW(hdc, &mut len, std::ptr::null_mut());
+        let mut path = vec![0u16; len as usize];
+        let ok = len > 0 && GetICMProfileW(hdc, &mut len, path.as_mut_ptr()) != 0;
+        DeleteDC(hdc);
+        if !ok {
+            return None;
+        }
+        path
+    };
+    let len = path.iter().position(|&c| c == 0).unwrap_or(path.len());
+    std::fs::read(String::from_utf16_lossy(&path[..len])).ok()
+}
+
+/// Gets the monitor ICC profile.
+#[cfg(target_os = "macos")]
+pub(crate) fn monitor_icc_profile(handle: &MonitorHandle) -> Option<Vec<u8>> {
+    use winit::platform::macos::MonitorHandleExtMacOS as _;
+
+    let screen = handle.ns_screen()? as *const objc2_app_kit::NSScreen;
+    // SAFETY: winit returns a valid NSScreen pointer, it is only used in this call.
+    unsafe {
+        let color_space = (*screen).colorSpace()?;
+        let data = color_space.ICCProfileData()?;
+        Some(data.bytes().to_vec())
+    }
+}
+
+/// Gets the monitor ICC profile.
+///
+/// Reads the `_ICC_PROFILE` root window property set by color managers on X11, Wayland is not supported.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn monitor_icc_profile(handle: &MonitorHandle) -> Option<Vec<u8>> {
+    use x11_dl::{xlib, xrandr};
+
+    if std::env::var_os("WAYLAND_DISPLAY").is_some_and(|d| !d.is_empty()) {
+        return None;
+    }
+
+    let position = handle.position();
+
+    let xlib = xlib::Xlib::open().ok()?;
+    let randr = xrandr::Xrandr::open().ok()?;
+    // SAFETY: null opens the default display, all resources are freed and the display is closed.
+    unsafe {
+        let display = (xlib.XOpenDisplay)(std::ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = (xlib.XDefaultRootWindow)(display);
+
+        // the profile of the first monitor is `_ICC_PROFILE`, the others are `_ICC_PROFILE_{i}`,
+        // in the order of the X screens (RandR monitors).
+        let mut index = None;
+        let mut len = 0;
+        let monitors = (randr.XRRGetMonitors)(display, root, xlib::True, &mut len);
+        if !monitors.is_null() {
+            index = std::slice::from_raw_parts(monitors, len.max(0) as usize)
+                .iter()
+                .position(|m| m.x == position.x && m.y == position.y);
+            (randr.XRRFreeMonitors)(monitors);
+        }
+
+        let mut r = None;
+        if let Some(i) = index {
+            let name = if i == 0 {
+                "_ICC_PROFILE\0".to_owned()
+            } else {
+                format!("_ICC_PROFILE_{i}\0")
+            };
+            let atom = (xlib.XInternAtom)(display, name.as_ptr() as _, xlib::True);
+            if atom != 0 {
+                let mut actual_type = 0;
+                let mut actual_format = 0;
+                let (mut n, mut after) = (0, 0);
+                let mut prop: *mut u8 = std::ptr::null_mut();
+                let ok = (xlib.XGetWindowProperty)(
+                    display,
+                    root,
+                    atom,
+                    0,
+                    // length in 32-bit multiples, profiles are rarely larger than a few MB.
+                    16 * 1024 * 1024 / 4,
+                    xlib::False,
+                    xlib::AnyPropertyType as _,
+                    &mut actual_type,
+                    &mut actual_format,
+                    &mut n,
+                    &mut after,
+                    &mut prop,
+                );
+                if ok == xlib::Success as i32 && !prop.is_null() && actual_format == 8 && n > 0 {
+                    r = Some(std::slice::from_raw_parts(prop, n as usize).to_vec());
+                }
+                if !prop.is_null() {
+                    (xlib.XFree)(prop as _);
+                }
+            }
+        }
+
+        (xlib.XCloseDisplay)(display);
+        r
+    }
+}
+
+/// Gets the monitor ICC profile.
+#[cfg(not(any(
+    windows,
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) fn monitor_icc_profile(handle: &MonitorHandle) -> Option<Vec<u8>> {
+    let _ = handle;
+    None
+}
@@ -1,6 +1,7 @@
 use zng_view_api::config::{
     AnimationsConfig, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig,
 };
+use zng_view_api::keyboard::{Key, KeyCode};
 
 #[cfg(windows)]
 mod windows;
@@ -27,6 +28,14 @@ mod dconf;
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
+mod xkb;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
 use dconf as platform;
 
 mod other;
@@ -69,6 +78,10 @@ pub fn locale_config() -> LocaleConfig {
     platform::locale_config()
 }
 
+pub fn key_labels(codes: Vec<KeyCode>) -> Vec<Key> {
+    platform::key_labels(codes)
+}
+
 /// Return handle must be called on exit.
 #[must_use]
 pub fn spawn_listener(event_loop: crate::AppEventSender) -> Option<Box<dyn FnOnce()>> {
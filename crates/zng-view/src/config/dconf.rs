@@ -2,7 +2,10 @@ use std::{io::BufRead as _, time::Duration};
 
 use zng_unit::{Rgba, TimeUnits as _};
 use zng_view_api::{
-    config::{AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig},
+    config::{
+        AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, HighContrastColors, KeyRepeatConfig, LocaleConfig, MultiClickConfig,
+        TouchConfig,
+    },
     Event,
 };
 
@@ -84,7 +87,24 @@ pub fn colors_config() -> ColorsConfig {
         _ => ColorsConfig::default().accent,
     };
 
-    ColorsConfig { scheme, accent }
+    if dconf_bool("/org/gnome/desktop/a11y/interface/high-contrast").unwrap_or(false) {
+        // GNOME high contrast theme does not expose the palette
+        let high_contrast = match scheme {
+            ColorScheme::Light => HighContrastColors::light(),
+            ColorScheme::Dark => HighContrastColors::dark(),
+        };
+        return ColorsConfig {
+            scheme,
+            accent: high_contrast.highlight,
+            high_contrast: Some(high_contrast),
+        };
+    }
+
+    ColorsConfig {
+        scheme,
+        accent,
+        high_contrast: None,
+    }
 }
 
 pub fn locale_config() -> LocaleConfig {
@@ -96,7 +116,9 @@ fn on_change(key: &str, s: &crate::AppEventSender) {
     // println!("{key}"); // to discover keys, uncomment and change the config in system config app.
 
     match key {
-        "/org/gnome/desktop/interface/color-scheme" | "/org/gnome/desktop/interface/gtk-theme" => {
+        "/org/gnome/desktop/interface/color-scheme"
+        | "/org/gnome/desktop/interface/gtk-theme"
+        | "/org/gnome/desktop/a11y/interface/high-contrast" => {
             let _ = s.send(AppEvent::Notify(Event::ColorsConfigChanged(colors_config())));
         }
         "/org/gnome/desktop/peripherals/keyboard/delay" | "/org/gnome/desktop/peripherals/keyboard/repeat-interval" => {
@@ -110,6 +132,9 @@ fn on_change(key: &str, s: &crate::AppEventSender) {
         | "/org/gnome/desktop/interface/cursor-blink" => {
             let _ = s.send(AppEvent::Notify(Event::AnimationsConfigChanged(animations_config())));
         }
+        k if k.starts_with("/org/gnome/desktop/input-sources/") => {
+            let _ = s.send(AppEvent::Notify(Event::KeyboardLayoutChanged));
+        }
         _ => {}
     }
 }
@@ -164,7 +189,21 @@ fn dconf(key: &str) -> Option<String> {
     }
 }
 
+pub fn key_labels(codes: Vec<zng_view_api::keyboard::KeyCode>) -> Vec<zng_view_api::keyboard::Key> {
+    // Wayland clients cannot query the active layout, GNOME lists it first in the most recently used input sources
+    let layout = if super::xkb::is_wayland() {
+        dconf("/org/gnome/desktop/input-sources/mru-sources")
+            .and_then(|s| super::xkb::parse_gnome_input_source(&s))
+            .or_else(|| dconf("/org/gnome/desktop/input-sources/sources").and_then(|s| super::xkb::parse_gnome_input_source(&s)))
+    } else {
+        None
+    };
+    super::xkb::key_labels(codes, layout)
+}
+
 pub fn spawn_listener(event_loop: crate::AppEventSender) -> Option<Box<dyn FnOnce()>> {
+    super::xkb::spawn_layout_listener(event_loop.clone());
+
     let mut w = std::process::Command::new("dconf");
     w.arg("watch")
         .arg("/")
@@ -1,7 +1,10 @@
+use objc2::rc::Retained;
 use objc2_app_kit::*;
 use objc2_foundation::*;
 use zng_unit::{Rgba, TimeUnits as _};
-use zng_view_api::config::{AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, MultiClickConfig, TouchConfig};
+use zng_view_api::config::{
+    AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, HighContrastColors, KeyRepeatConfig, MultiClickConfig, TouchConfig,
+};
 
 pub fn font_aa() -> FontAntiAliasing {
     super::other::font_aa()
@@ -57,7 +60,35 @@ pub fn colors_config() -> ColorsConfig {
         Rgba::new(a.redComponent(), a.greenComponent(), a.blueComponent(), a.alphaComponent())
     };
 
-    ColorsConfig { scheme, accent }
+    let high_contrast = if unsafe { NSWorkspace::sharedWorkspace().accessibilityDisplayShouldIncreaseContrast() } {
+        // system colors are dynamic, convert to read the components.
+        let color = |c: Retained<NSColor>| unsafe {
+            match c.colorUsingColorSpace(&NSColorSpace::sRGBColorSpace()) {
+                Some(c) => Rgba::new(c.redComponent(), c.greenComponent(), c.blueComponent(), c.alphaComponent()),
+                None => Rgba::new(0, 0, 0, 255),
+            }
+        };
+        unsafe {
+            Some(HighContrastColors {
+                background: color(NSColor::windowBackgroundColor()),
+                text: color(NSColor::textColor()),
+                highlight: color(NSColor::selectedContentBackgroundColor()),
+                highlight_text: color(NSColor::selectedTextColor()),
+                link: color(NSColor::linkColor()),
+                disabled_text: color(NSColor::disabledControlTextColor()),
+                button: color(NSColor::controlColor()),
+                button_text: color(NSColor::controlTextColor()),
+            })
+        }
+    } else {
+        None
+    };
+
+    ColorsConfig {
+        scheme,
+        accent,
+        high_contrast,
+    }
 }
 
 #[cfg(not(windows))]
@@ -65,6 +96,10 @@ pub fn locale_config() -> zng_view_api::config::LocaleConfig {
     super::other::locale_config()
 }
 
+pub fn key_labels(codes: Vec<zng_view_api::keyboard::KeyCode>) -> Vec<zng_view_api::keyboard::Key> {
+    super::other::key_labels(codes)
+}
+
 pub fn spawn_listener(l: crate::AppEventSender) -> Option<Box<dyn FnOnce()>> {
     super::other::spawn_listener(l)
 }
@@ -39,6 +39,11 @@ pub fn locale_config() -> zng_view_api::config::LocaleConfig {
     }
 }
 
+pub fn key_labels(codes: Vec<zng_view_api::keyboard::KeyCode>) -> Vec<zng_view_api::keyboard::Key> {
+    warn("key_labels");
+    vec![zng_view_api::keyboard::Key::Unidentified; codes.len()]
+}
+
 pub fn spawn_listener(_: crate::AppEventSender) -> Option<Box<dyn FnOnce()>> {
     tracing::warn!("config events not implemented for {}", std::env::consts::OS);
     None
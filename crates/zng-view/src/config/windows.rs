@@ -1,6 +1,7 @@
 use zng_unit::Rgba;
 use zng_view_api::config::{
-    AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, KeyRepeatConfig, LocaleConfig, MultiClickConfig, TouchConfig,
+    AnimationsConfig, ColorScheme, ColorsConfig, FontAntiAliasing, HighContrastColors, KeyRepeatConfig, LocaleConfig, MultiClickConfig,
+    TouchConfig,
 };
 
 /// Create a hidden window that listens to Windows config change events.
@@ -23,6 +24,7 @@ fn config_listener(event_loop: crate::AppEventSender) {
         Win32::{
             Foundation::GetLastError,
             System::{
+                DataExchange::{AddClipboardFormatListener, RemoveClipboardFormatListener},
                 Power::{RegisterPowerSettingNotification, UnregisterPowerSettingNotification},
                 SystemServices::GUID_SESSION_DISPLAY_STATUS,
             },
@@ -83,6 +85,14 @@ fn config_listener(event_loop: crate::AppEventSender) {
         RegisterPowerSettingNotification(window, &GUID_SESSION_DISPLAY_STATUS, 0)
     };
 
+    let clipboard_listener = unsafe {
+        //
+        AddClipboardFormatListener(window) != 0
+    };
+    if !clipboard_listener {
+        tracing::error!("cannot listen to clipboard changes, error 0x{:x}", unsafe { GetLastError() });
+    }
+
     let mut colors_cfg = colors_config();
 
     let r = util::set_raw_windows_event_handler(window, u32::from_ne_bytes(*b"cevl") as _, move |_, msg, wparam, lparam| {
@@ -92,6 +102,17 @@ fn config_listener(event_loop: crate::AppEventSender) {
         };
         match msg {
             WM_FONTCHANGE => notify(Event::FontsChanged),
+            WM_CLIPBOARDUPDATE => notify(Event::ClipboardChanged),
+            WM_SYSCOLORCHANGE => {
+                // high contrast palette changed
+                let cfg = colors_config();
+                if cfg != colors_cfg {
+                    colors_cfg = cfg;
+                    notify(Event::ColorsConfigChanged(cfg))
+                } else {
+                    None
+                }
+            }
             WM_SETTINGCHANGE => match wparam as _ {
                 SPI_SETFONTSMOOTHING | SPI_SETFONTSMOOTHINGTYPE => notify(Event::FontAaChanged(font_aa())),
                 SPI_SETDOUBLECLICKTIME | SPI_SETDOUBLECLKWIDTH | SPI_SETDOUBLECLKHEIGHT => {
@@ -139,6 +160,11 @@ fn config_listener(event_loop: crate::AppEventSender) {
                         UnregisterPowerSettingNotification(h);
                     };
                 }
+                if clipboard_listener {
+                    unsafe {
+                        RemoveClipboardFormatListener(window);
+                    }
+                }
                 None
             }
             _ => None,
@@ -359,7 +385,36 @@ pub fn colors_config() -> ColorsConfig {
         ok != 0 && hc.dwFlags & HCF_HIGHCONTRASTON == HCF_HIGHCONTRASTON
     }
 
-    let scheme = if should_apps_use_dark_mode() && !is_high_contrast() {
+    fn high_contrast_colors() -> HighContrastColors {
+        use windows_sys::Win32::Graphics::Gdi::*;
+
+        let color = |i| {
+            // COLORREF is 0x00BBGGRR
+            let c = unsafe { GetSysColor(i) };
+            Rgba::new((c & 0xFF) as u8, ((c >> 8) & 0xFF) as u8, ((c >> 16) & 0xFF) as u8, 255)
+        };
+        HighContrastColors {
+            background: color(COLOR_WINDOW),
+            text: color(COLOR_WINDOWTEXT),
+            highlight: color(COLOR_HIGHLIGHT),
+            highlight_text: color(COLOR_HIGHLIGHTTEXT),
+            link: color(COLOR_HOTLIGHT),
+            disabled_text: color(COLOR_GRAYTEXT),
+            button: color(COLOR_BTNFACE),
+            button_text: color(COLOR_BTNTEXT),
+        }
+    }
+
+    if is_high_contrast() {
+        let high_contrast = high_contrast_colors();
+        return ColorsConfig {
+            scheme: high_contrast.scheme(),
+            accent: high_contrast.highlight,
+            high_contrast: Some(high_contrast),
+        };
+    }
+
+    let scheme = if should_apps_use_dark_mode() {
         ColorScheme::Dark
     } else {
         ColorScheme::Light
@@ -371,7 +426,11 @@ pub fn colors_config() -> ColorsConfig {
         .map(|a| Rgba::new(a.R, a.G, a.B, a.A))
         .unwrap_or_else(|| ColorsConfig::default().accent);
 
-    ColorsConfig { scheme, accent }
+    ColorsConfig {
+        scheme,
+        accent,
+        high_contrast: None,
+    }
 }
 
 pub(crate) fn locale_config() -> LocaleConfig {
@@ -418,3 +477,53 @@ pub(crate) fn locale_config() -> LocaleConfig {
         langs: String::from_utf16_lossy(&buffer).split('\0').map(Txt::from_str).collect(),
     }
 }
+
+pub(crate) fn key_labels(codes: Vec<zng_view_api::keyboard::KeyCode>) -> Vec<zng_view_api::keyboard::Key> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+    use winit::platform::scancode::PhysicalKeyExtScancode as _;
+    use zng_view_api::keyboard::Key;
+
+    // SAFETY: function has no preconditions, `0` gets the layout of the current thread.
+    let hkl = unsafe { GetKeyboardLayout(0) };
+    // no modifiers pressed.
+    let state = [0u8; 256];
+
+    codes
+        .into_iter()
+        .map(|code| {
+            let scancode = match crate::util::key_code_to_winit_key_code(code).and_then(|k| k.to_scancode()) {
+                Some(s) => s,
+                None => return Key::Unidentified,
+            };
+            // SAFETY: function has no preconditions.
+            let vk = unsafe { MapVirtualKeyExW(scancode, MAPVK_VSC_TO_VK_EX, hkl) };
+            if vk == 0 {
+                return Key::Unidentified;
+            }
+
+            let mut buf = [0u16; 8];
+            // SAFETY: `state` is 256 bytes and `buf` length is correct.
+            // flag `0x4` does not change the keyboard state (Windows 10 1607+), so dead keys are not left pending.
+            let len = unsafe { ToUnicodeEx(vk, scancode, state.as_ptr(), buf.as_mut_ptr(), buf.len() as _, 0x4, hkl) };
+            let is_dead = len < 0;
+            let len = (len.unsigned_abs() as usize).min(buf.len());
+
+            let s: String = char::decode_utf16(buf[..len].iter().copied())
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (None, _) => Key::Unidentified,
+                (Some(c), _) if c.is_control() => Key::Unidentified,
+                (Some(c), None) => {
+                    if is_dead {
+                        Key::Dead(Some(c))
+                    } else {
+                        Key::Char(c)
+                    }
+                }
+                (Some(_), Some(_)) => Key::Str(s.into()),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,313 @@
+//! Keyboard layout labels using `libxkbcommon`.
+
+use std::{
+    ffi::{c_char, CString},
+    ptr,
+};
+
+use winit::platform::scancode::PhysicalKeyExtScancode as _;
+use xkbcommon_dl::{
+    x11::{xkb_x11_setup_xkb_extension_flags, xkbcommon_x11_option},
+    xkb_context, xkb_context_flags, xkb_keymap, xkb_keymap_compile_flags, xkb_rule_names, xkb_state, xkbcommon_option, XkbCommon,
+};
+use zng_view_api::{
+    keyboard::{Key, KeyCode},
+    Event,
+};
+
+use crate::{AppEvent, AppEventSender};
+
+/// `XkbUseCoreKbd` device spec.
+const USE_CORE_KBD: u32 = 0x0100;
+
+/// Gets the label of each key in the current layout.
+///
+/// On X11 the active layout of the core keyboard is used, on Wayland the `layout` is used if set, otherwise the
+/// default layout defined by the `XKB_DEFAULT_LAYOUT` environment variable or the system default.
+pub fn key_labels(codes: Vec<KeyCode>, layout: Option<(String, String)>) -> Vec<Key> {
+    let state = match XkbState::new(layout) {
+        Some(s) => s,
+        None => return vec![Key::Unidentified; codes.len()],
+    };
+    codes.into_iter().map(|c| state.label(c)).collect()
+}
+
+/// Notify [`Event::KeyboardLayoutChanged`] when the X11 keyboard group or keymap changes.
+///
+/// Does nothing on Wayland.
+pub fn spawn_layout_listener(app_sender: AppEventSender) {
+    if is_wayland() {
+        return;
+    }
+    let r = std::thread::Builder::new().name("xkb-listener".into()).spawn(move || {
+        let xlib = match x11_dl::xlib::Xlib::open() {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+        let mut event_base = 0;
+        let mut error_base = 0;
+        let mut major = 1;
+        let mut minor = 0;
+        let mut reason = 0;
+        // SAFETY: all pointers are valid, the display is only used in this thread.
+        unsafe {
+            let display = (xlib.XkbOpenDisplay)(
+                ptr::null_mut(),
+                &mut event_base,
+                &mut error_base,
+                &mut major,
+                &mut minor,
+                &mut reason,
+            );
+            if display.is_null() {
+                return;
+            }
+            (xlib.XkbSelectEventDetails)(
+                display,
+                USE_CORE_KBD,
+                x11_dl::xlib::XkbStateNotify as _,
+                x11_dl::xlib::XkbGroupStateMask,
+                x11_dl::xlib::XkbGroupStateMask,
+            );
+            let keymap_mask = x11_dl::xlib::XkbNewKeyboardNotifyMask | x11_dl::xlib::XkbMapNotifyMask;
+            (xlib.XkbSelectEvents)(display, USE_CORE_KBD, keymap_mask, keymap_mask);
+
+            let mut ev: x11_dl::xlib::XEvent = std::mem::zeroed();
+            loop {
+                (xlib.XNextEvent)(display, &mut ev);
+                // coalesced in the app-process
+                if ev.get_type() == event_base && app_sender.send(AppEvent::Notify(Event::KeyboardLayoutChanged)).is_err() {
+                    break;
+                }
+            }
+            (xlib.XCloseDisplay)(display);
+        }
+    });
+    if let Err(e) = r {
+        tracing::error!("cannot monitor keyboard layout, {e}");
+    }
+}
+
+pub fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+struct XkbState {
+    xkb: &'static XkbCommon,
+    context: *mut xkb_context,
+    keymap: *mut xkb_keymap,
+    state: *mut xkb_state,
+}
+impl XkbState {
+    fn new(layout: Option<(String, String)>) -> Option<Self> {
+        let mut s = Self::context()?;
+        // SAFETY: the context is valid.
+        let ok = unsafe { (!is_wayland() && s.init_x11()) || s.init_names(layout) };
+        if ok {
+            Some(s)
+        } else {
+            tracing::error!("cannot load keyboard layout");
+            None
+        }
+    }
+
+    /// New without keymap.
+    fn context() -> Option<Self> {
+        let xkb = match xkbcommon_option() {
+            Some(x) => x,
+            None => {
+                tracing::error!("cannot load libxkbcommon");
+                return None;
+            }
+        };
+        // SAFETY: function has no preconditions.
+        let context = unsafe { (xkb.xkb_context_new)(xkb_context_flags::XKB_CONTEXT_NO_FLAGS) };
+        if context.is_null() {
+            return None;
+        }
+        Some(Self {
+            xkb,
+            context,
+            keymap: ptr::null_mut(),
+            state: ptr::null_mut(),
+        })
+    }
+
+    /// Load the keymap and state of the X11 core keyboard.
+    unsafe fn init_x11(&mut self) -> bool {
+        let (Some(xkb_x11), Ok(xlib), Ok(xlib_xcb)) = (
+            xkbcommon_x11_option(),
+            x11_dl::xlib::Xlib::open(),
+            x11_dl::xlib_xcb::Xlib_xcb::open(),
+        ) else {
+            return false;
+        };
+        let display = (xlib.XOpenDisplay)(ptr::null());
+        if display.is_null() {
+            return false;
+        }
+        let connection = (xlib_xcb.XGetXCBConnection)(display);
+        let ok = (xkb_x11.xkb_x11_setup_xkb_extension)(
+            connection,
+            1,
+            0,
+            xkb_x11_setup_xkb_extension_flags::XKB_X11_SETUP_XKB_EXTENSION_NO_FLAGS,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        ) != 0;
+        if ok {
+            let device = (xkb_x11.xkb_x11_get_core_keyboard_device_id)(connection);
+            if device >= 0 {
+                self.keymap = (xkb_x11.xkb_x11_keymap_new_from_device)(
+                    self.context,
+                    connection,
+                    device,
+                    xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+                );
+                if !self.keymap.is_null() {
+                    // state includes the active group (layout)
+                    self.state = (xkb_x11.xkb_x11_state_new_from_device)(self.keymap, connection, device);
+                }
+            }
+        }
+        (xlib.XCloseDisplay)(display);
+        !self.state.is_null()
+    }
+
+    /// Load the keymap from RMLVO names, unset names are read from the `XKB_DEFAULT_*` environment variables.
+    unsafe fn init_names(&mut self, layout: Option<(String, String)>) -> bool {
+        if !self.keymap.is_null() {
+            (self.xkb.xkb_keymap_unref)(self.keymap);
+            self.keymap = ptr::null_mut();
+        }
+        let (layout, variant) = match layout {
+            Some((l, v)) => (CString::new(l).ok(), CString::new(v).ok()),
+            None => (None, None),
+        };
+        let opt_ptr = |s: &Option<CString>| s.as_ref().map(|s| s.as_ptr()).unwrap_or(ptr::null());
+        let names = xkb_rule_names {
+            rules: ptr::null(),
+            model: ptr::null(),
+            layout: opt_ptr(&layout),
+            variant: opt_ptr(&variant),
+            options: ptr::null(),
+        };
+        self.keymap = (self.xkb.xkb_keymap_new_from_names)(self.context, &names, xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS);
+        if self.keymap.is_null() {
+            return false;
+        }
+        self.state = (self.xkb.xkb_state_new)(self.keymap);
+        !self.state.is_null()
+    }
+
+    fn label(&self, code: KeyCode) -> Key {
+        let scancode = match crate::util::key_code_to_winit_key_code(code).and_then(|k| k.to_scancode()) {
+            Some(s) => s,
+            None => return Key::Unidentified,
+        };
+        // evdev scancode to X11 keycode
+        let keycode = scancode + 8;
+
+        // SAFETY: the state is valid, `buf` length is correct.
+        let (sym, s) = unsafe {
+            let sym = (self.xkb.xkb_state_key_get_one_sym)(self.state, keycode);
+            let mut buf = [0 as c_char; 32];
+            let len = (self.xkb.xkb_state_key_get_utf8)(self.state, keycode, buf.as_mut_ptr(), buf.len());
+            let len = (len.max(0) as usize).min(buf.len() - 1);
+            let bytes: Vec<u8> = buf[..len].iter().map(|&c| c as u8).collect();
+            (sym, String::from_utf8_lossy(&bytes).into_owned())
+        };
+
+        if (XKB_KEY_DEAD_FIRST..=XKB_KEY_DEAD_LAST).contains(&sym) {
+            return Key::Dead(dead_char(sym));
+        }
+
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => Key::Unidentified,
+            (Some(c), _) if c.is_control() => Key::Unidentified,
+            (Some(c), None) => Key::Char(c),
+            (Some(_), Some(_)) => Key::Str(s.into()),
+        }
+    }
+}
+impl Drop for XkbState {
+    fn drop(&mut self) {
+        // SAFETY: pointers are valid or null, unref accepts null.
+        unsafe {
+            (self.xkb.xkb_state_unref)(self.state);
+            (self.xkb.xkb_keymap_unref)(self.keymap);
+            (self.xkb.xkb_context_unref)(self.context);
+        }
+    }
+}
+
+const XKB_KEY_DEAD_FIRST: u32 = 0xfe50;
+const XKB_KEY_DEAD_LAST: u32 = 0xfe8f;
+
+/// Spacing character of common dead keys.
+fn dead_char(sym: u32) -> Option<char> {
+    let c = match sym {
+        0xfe50 => '`',
+        0xfe51 => '´',
+        0xfe52 => '^',
+        0xfe53 => '~',
+        0xfe54 => '¯',
+        0xfe55 => '˘',
+        0xfe56 => '˙',
+        0xfe57 => '¨',
+        0xfe58 => '˚',
+        0xfe59 => '˝',
+        0xfe5a => 'ˇ',
+        0xfe5b => '¸',
+        0xfe5c => '˛',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// Parse the first `xkb` entry of a GNOME `input-sources` list, like `[('xkb', 'fr+azerty'), ('ibus', 'anthy')]`.
+///
+/// Returns the layout and variant.
+pub fn parse_gnome_input_source(sources: &str) -> Option<(String, String)> {
+    let (_, s) = sources.split_once("('xkb', '")?;
+    let (s, _) = s.split_once('\'')?;
+    let (layout, variant) = s.split_once('+').unwrap_or((s, ""));
+    Some((layout.to_owned(), variant.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gnome_input_source() {
+        assert_eq!(
+            Some(("fr".to_owned(), "azerty".to_owned())),
+            parse_gnome_input_source("[('xkb', 'fr+azerty'), ('xkb', 'us')]")
+        );
+        assert_eq!(
+            Some(("us".to_owned(), String::new())),
+            parse_gnome_input_source("[('ibus', 'anthy'), ('xkb', 'us')]")
+        );
+        assert_eq!(None, parse_gnome_input_source("@a(ss) []"));
+    }
+
+    #[test]
+    fn labels_from_names() {
+        let mut s = match XkbState::context() {
+            Some(s) => s,
+            // libxkbcommon not installed
+            None => return,
+        };
+        // SAFETY: the context is valid.
+        assert!(unsafe { s.init_names(Some(("fr".to_owned(), String::new()))) });
+
+        assert_eq!(Key::Char('a'), s.label(KeyCode::KeyQ));
+        assert_eq!(Key::Char('z'), s.label(KeyCode::KeyW));
+        assert_eq!(Key::Dead(Some('^')), s.label(KeyCode::BracketLeft));
+        assert_eq!(Key::Unidentified, s.label(KeyCode::Enter));
+    }
+}
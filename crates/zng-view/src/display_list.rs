@@ -1,12 +1,15 @@
-use std::{cell::Cell, mem};
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+};
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use webrender::api as wr;
-use zng_unit::{PxCornerRadius, PxRect, PxTransform, Rgba};
+use zng_unit::{Px, PxCornerRadius, PxRect, PxTransform, Rgba};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     display_list::{DisplayItem, DisplayList, FilterOp, FrameValue, FrameValueId, FrameValueUpdate, NinePatchSource, SegmentId},
-    font::{GlyphIndex, GlyphInstance},
+    font::{FontId, GlyphIndex, GlyphInstance},
     window::FrameId,
     GradientStop,
 };
@@ -223,6 +226,34 @@ struct CachedDisplayList {
     used: Cell<bool>,
 }
 
+/// Webrender item cache entry of a reused range.
+struct ItemGroup {
+    key: wr::ItemKey,
+    spatial_id: wr::SpatialId,
+    clip_chain_id: wr::ClipChainId,
+    prim_flags: wr::PrimitiveFlags,
+    /// Display list build that retained the items.
+    build: u32,
+}
+
+/// Tracks the reused ranges that are retained in the Webrender item cache.
+#[derive(Default)]
+struct ItemGroups {
+    build: u32,
+    groups: FxHashMap<(FrameId, usize, usize), ItemGroup>,
+    free_keys: Vec<wr::ItemKey>,
+    cache_size: usize,
+}
+impl ItemGroups {
+    fn new_key(&mut self) -> Option<wr::ItemKey> {
+        self.free_keys.pop().or_else(|| {
+            let key = wr::ItemKey::try_from(self.cache_size).ok()?;
+            self.cache_size += 1;
+            Some(key)
+        })
+    }
+}
+
 /// View process side cache of [`DisplayList`] frames for a pipeline.
 pub struct DisplayListCache {
     pipeline_id: wr::PipelineId,
@@ -234,6 +265,9 @@ pub struct DisplayListCache {
     bindings: FxHashMap<FrameValueId, (FrameId, usize)>,
 
     wr_list: Option<wr::DisplayListBuilder>,
+    item_groups: RefCell<ItemGroups>,
+
+    glyph_sizes: FxHashMap<FontId, Px>,
 }
 impl DisplayListCache {
     /// New empty.
@@ -246,7 +280,45 @@ impl DisplayListCache {
             space_and_clip: Some(SpaceAndClip::new(pipeline_id)),
             bindings: FxHashMap::default(),
             wr_list: Some(wr::DisplayListBuilder::new(pipeline_id)),
+            item_groups: RefCell::default(),
+            glyph_sizes: FxHashMap::default(),
+        }
+    }
+
+    /// Register the font size, used by [`glyph_cache_estimate`].
+    ///
+    /// [`glyph_cache_estimate`]: Self::glyph_cache_estimate
+    pub fn register_font(&mut self, font_id: FontId, glyph_size: Px) {
+        self.glyph_sizes.insert(font_id, glyph_size);
+    }
+
+    /// Unregister the font size.
+    pub fn unregister_font(&mut self, font_id: FontId) {
+        self.glyph_sizes.remove(&font_id);
+    }
+
+    /// Estimate the texture memory of the glyphs rasterized for the cached frames, in bytes.
+    ///
+    /// Each distinct glyph is counted as a BGRA8 square of the font size.
+    pub fn glyph_cache_estimate(&self) -> u64 {
+        let mut seen = FxHashSet::default();
+        let mut len = 0u64;
+        for list in self.lists.values() {
+            for item in &list.list {
+                if let DisplayItem::Text { font_id, glyphs, .. } = item {
+                    let size = match self.glyph_sizes.get(font_id) {
+                        Some(s) => s.0.max(0) as u64,
+                        None => continue,
+                    };
+                    for g in glyphs.iter() {
+                        if seen.insert((*font_id, g.index)) {
+                            len += size * size * 4;
+                        }
+                    }
+                }
+            }
         }
+        len
     }
 
     /// Keys namespace.
@@ -258,10 +330,13 @@ impl DisplayListCache {
         let mut list = self.wr_list.take().unwrap();
         let sc = self.space_and_clip.take().unwrap();
         list.begin();
+        let groups = self.item_groups.get_mut();
+        groups.build = groups.build.wrapping_add(1);
         (list, sc)
     }
 
     fn end_wr(&mut self, mut list: wr::DisplayListBuilder, mut sc: SpaceAndClip) -> wr::BuiltDisplayList {
+        list.set_cache_size(self.item_groups.get_mut().cache_size);
         let r = list.end().1;
         self.wr_list = Some(list);
         sc.clear(self.pipeline_id);
@@ -298,17 +373,95 @@ impl DisplayListCache {
                 tracing::error!("invalid reuse range ({start}..{end}) ignored, offset: {offset}");
                 &[]
             });
-            for item in range {
-                display_item_to_webrender(item, wr_list, ext, sc, self, true);
+            if is_cacheable(range) {
+                self.reuse_cached((frame_id, start, end), range, wr_list, ext, sc);
+            } else {
+                for item in range {
+                    display_item_to_webrender(item, wr_list, ext, sc, self, true);
+                }
             }
         } else {
             tracing::error!("did not find reuse frame {frame_id:?}");
         }
     }
 
+    /// Push the `range` items retained by Webrender in a previous display list, or convert and retain the items.
+    ///
+    /// The converted items only depend on the current space, clip chain and primitive flags, if these are the same
+    /// the Webrender cached items are the same as a new conversion.
+    fn reuse_cached(
+        &self,
+        range_id: (FrameId, usize, usize),
+        range: &[DisplayItem],
+        wr_list: &mut wr::DisplayListBuilder,
+        ext: &mut dyn DisplayListExtension,
+        sc: &mut SpaceAndClip,
+    ) {
+        // define the clip chain outside the group, so that all items just reference it.
+        let clip_chain_id = sc.clip_chain_id(wr_list);
+        let spatial_id = sc.spatial_id();
+        let prim_flags = sc.primitive_flags();
+
+        let mut groups = self.item_groups.borrow_mut();
+        let build = groups.build;
+        let key = match groups.groups.get_mut(&range_id) {
+            Some(g) if g.spatial_id == spatial_id && g.clip_chain_id == clip_chain_id && g.prim_flags == prim_flags => {
+                wr_list.push_reuse_items(g.key);
+                return;
+            }
+            // already retained by this display list in another space or clip, cannot replace.
+            Some(g) if g.build == build => None,
+            Some(g) => Some(g.key),
+            None => groups.new_key(),
+        };
+        drop(groups);
+
+        let key = match key {
+            Some(k) => k,
+            None => {
+                for item in range {
+                    display_item_to_webrender(item, wr_list, ext, sc, self, true);
+                }
+                return;
+            }
+        };
+
+        wr_list.start_item_group();
+        for item in range {
+            display_item_to_webrender(item, wr_list, ext, sc, self, true);
+        }
+
+        let mut groups = self.item_groups.borrow_mut();
+        if wr_list.finish_item_group(key) {
+            groups.groups.insert(
+                range_id,
+                ItemGroup {
+                    key,
+                    spatial_id,
+                    clip_chain_id,
+                    prim_flags,
+                    build,
+                },
+            );
+        } else {
+            groups.groups.remove(&range_id);
+            groups.free_keys.push(key);
+        }
+    }
+
     fn insert(&mut self, list: DisplayList) {
         self.lists.retain(|_, l| l.used.take());
 
+        let groups = self.item_groups.get_mut();
+        let free_keys = &mut groups.free_keys;
+        groups.groups.retain(|(frame_id, ..), g| {
+            let retain = self.lists.contains_key(frame_id);
+            if !retain {
+                free_keys.push(g.key);
+            }
+            retain
+        });
+
         let (frame_id, list, segments) = list.into_parts();
 
         for (i, item) in list.iter().enumerate() {
@@ -428,6 +581,17 @@ fn display_list_build(
     cache.end_wr(wr_list, sc)
 }
 
+/// If the items only depend on the current space, clip chain and primitive flags and cannot be updated.
+fn is_cacheable(items: &[DisplayItem]) -> bool {
+    !items.is_empty()
+        && items.iter().all(|item| match item {
+            DisplayItem::Text { color, .. } | DisplayItem::Color { color, .. } => matches!(color, FrameValue::Value(_)),
+            DisplayItem::NinePatchBorder { source, .. } => matches!(source, NinePatchSource::Image { .. }),
+            DisplayItem::Border { .. } | DisplayItem::NinePatchImage { .. } | DisplayItem::Image { .. } | DisplayItem::Line { .. } => true,
+            _ => false,
+        })
+}
+
 fn display_item_to_webrender(
     item: &DisplayItem,
     wr_list: &mut wr::DisplayListBuilder,
@@ -721,6 +885,40 @@ fn display_item_to_webrender(
             );
         }
 
+        DisplayItem::NinePatchImage {
+            bounds,
+            widths,
+            image_id,
+            image_size,
+            slice,
+            fill,
+            repeat_horizontal,
+            repeat_vertical,
+            rendering,
+        } => {
+            let wr_bounds = bounds.to_wr();
+            let clip = sc.clip_chain_id(wr_list);
+            wr_list.push_border(
+                &wr::CommonItemProperties {
+                    clip_rect: wr_bounds,
+                    clip_chain_id: clip,
+                    spatial_id: sc.spatial_id(),
+                    flags: sc.primitive_flags(),
+                },
+                wr_bounds,
+                widths.to_wr(),
+                wr::BorderDetails::NinePatch(wr::NinePatchBorder {
+                    source: wr::NinePatchBorderSource::Image(wr::ImageKey(cache.id_namespace(), image_id.get()), rendering.to_wr()),
+                    width: image_size.width.0,
+                    height: image_size.height.0,
+                    slice: slice.to_wr_device(),
+                    fill: *fill,
+                    repeat_horizontal: repeat_horizontal.to_wr(),
+                    repeat_vertical: repeat_vertical.to_wr(),
+                }),
+            );
+        }
+
         DisplayItem::Image {
             clip_rect,
             image_id,
@@ -973,3 +1171,84 @@ fn cast_gradient_stops_to_wr(stops: &[GradientStop]) -> &[wr::GradientStop] {
     // SAFETY: GradientStop has the same layout as webrender_api (f32, [f32; 4])
     unsafe { std::mem::transmute(stops) }
 }
+
+#[cfg(test)]
+mod tests {
+    use zng_unit::{PxPoint, PxSize};
+    use zng_view_api::{
+        display_list::{DisplayListBuilder, ReuseRange},
+        LineOrientation, LineStyle,
+    };
+
+    use super::*;
+
+    fn rect(x: i32) -> PxRect {
+        PxRect::new(PxPoint::new(Px(x), Px(0)), PxSize::new(Px(10), Px(10)))
+    }
+
+    fn frame(frame_id: FrameId, range: &ReuseRange, clip: bool) -> DisplayList {
+        let mut list = DisplayListBuilder::new(frame_id);
+        list.push_color(rect(0), FrameValue::Value(Rgba::new(1.0, 0.0, 0.0, 1.0)));
+        if clip {
+            list.push_clip_rect(rect(0), false);
+        }
+        list.push_reuse_range(range);
+        if clip {
+            list.pop_clip();
+        }
+        list.finalize()
+    }
+
+    fn items(list: wr::BuiltDisplayListIter) -> Vec<String> {
+        let mut list = list;
+        let mut r = vec![];
+        while let Some(item) = list.next() {
+            r.push(format!("{:?}", item.item()));
+        }
+        r
+    }
+
+    #[test]
+    fn reuse_retained_items() {
+        let mut cache = DisplayListCache::new(wr::PipelineId::dummy(), wr::IdNamespace(1));
+
+        let frame1 = FrameId::first();
+        let mut list = DisplayListBuilder::new(frame1);
+        list.push_color(rect(0), FrameValue::Value(Rgba::new(1.0, 0.0, 0.0, 1.0)));
+        let start = list.start_reuse_range();
+        list.push_color(rect(10), FrameValue::Value(Rgba::new(0.0, 1.0, 0.0, 1.0)));
+        list.push_line(
+            rect(20),
+            Rgba::new(0.0, 0.0, 1.0, 1.0),
+            LineStyle::Solid,
+            LineOrientation::Horizontal,
+        );
+        let range = list.finish_reuse_range(start);
+        let f1 = display_list_to_webrender(list.finalize(), &mut (), &mut cache);
+        assert!(f1.cache_data_iter().next().is_none());
+
+        // first reuse converts and retains the items.
+        let frame2 = frame1.next();
+        let f2 = display_list_to_webrender(frame(frame2, &range, false), &mut (), &mut cache);
+        assert!(f2.cache_data_iter().next().is_some());
+
+        // next reuse in the same space and clip only references the retained items.
+        let frame3 = frame2.next();
+        let f3 = display_list_to_webrender(frame(frame3, &range, false), &mut (), &mut cache);
+        assert!(f3.cache_data_iter().next().is_none());
+        assert!(f3.items_data().len() < f1.items_data().len());
+
+        let expected = items(f1.iter());
+        let mut scene = wr::DisplayListWithCache::new_from_list(f2);
+        assert_eq!(expected, items(scene.iter()));
+        scene.update(f3);
+        assert_eq!(expected, items(scene.iter()));
+
+        // reuse in a new clip chain retains the items again.
+        let frame4 = frame3.next();
+        let f4 = display_list_to_webrender(frame(frame4, &range, true), &mut (), &mut cache);
+        assert!(f4.cache_data_iter().next().is_some());
+        scene.update(f4);
+        assert!(items(scene.iter()).len() > expected.len());
+    }
+}
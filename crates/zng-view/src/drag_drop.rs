@@ -0,0 +1,1384 @@
+//! Native drag&drop source.
+//!
+//! Implemented using `DoDragDrop` on Windows, dragging sessions on macOS and the XDND protocol on X11. Wayland
+//! is not supported, the protocol requires the serial of the pointer press that started the drag and winit does not
+//! expose it.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use zng_view_api::{
+    drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
+    window::WindowId,
+};
+
+use crate::{image_cache::Image, AppEventSender};
+
+/// An ongoing drag&drop operation started by the app-process.
+///
+/// The operation notifies `Event::AppDragEnded` when it finishes.
+pub(crate) struct DragSource {
+    cancel: Arc<AtomicBool>,
+    #[cfg(windows)]
+    pending: Option<windows::PendingDragDrop>,
+    #[cfg(target_os = "macos")]
+    _source: objc2::rc::Retained<macos::DragSourceDelegate>,
+}
+impl DragSource {
+    /// Start dragging from the `window`.
+    ///
+    /// On Windows the operation only starts on [`take_pending`], the system drag loop blocks the thread
+    /// so it must only run after the request response is send.
+    ///
+    /// [`take_pending`]: Self::take_pending
+    #[allow(unused_variables)]
+    pub fn start(
+        window: &winit::window::Window,
+        window_id: WindowId,
+        drag_id: DragDropId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<Image>,
+        app_sender: AppEventSender,
+    ) -> Result<Self, DragDropError> {
+        if data.is_empty() {
+            return Err(DragDropError::CannotStart("no data".into()));
+        }
+        if allowed_effects.is_empty() {
+            return Err(DragDropError::CannotStart("no allowed effects".into()));
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        #[cfg(windows)]
+        {
+            let pending = windows::PendingDragDrop::new(window_id, drag_id, data, allowed_effects, image, cancel.clone())?;
+            Ok(Self {
+                cancel,
+                pending: Some(pending),
+            })
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let source = macos::start(window, window_id, drag_id, data, allowed_effects, image, cancel.clone(), app_sender)?;
+            Ok(Self { cancel, _source: source })
+        }
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        {
+            use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+            match window.window_handle().map(|h| h.as_raw()) {
+                Ok(RawWindowHandle::Xlib(_) | RawWindowHandle::Xcb(_)) => {}
+                _ => {
+                    tracing::warn!("drag&drop source is only implemented for X11");
+                    return Err(DragDropError::NotSupported);
+                }
+            }
+            // the drag image is not part of the XDND protocol
+            x11::start(window_id, drag_id, data, allowed_effects, cancel.clone(), app_sender)?;
+            Ok(Self { cancel })
+        }
+
+        #[cfg(not(any(
+            windows,
+            target_os = "macos",
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )))]
+        {
+            tracing::warn!("drag&drop source not implemented on {}", std::env::consts::OS);
+            Err(DragDropError::NotSupported)
+        }
+    }
+
+    /// Request cancellation.
+    ///
+    /// On macOS the system does not allow cancelling, the operation continues until the user releases the
+    /// pointer, but no drop target accepts the data.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Take the operation that must run in the main thread.
+    #[cfg(windows)]
+    pub fn take_pending(&mut self) -> Option<windows::PendingDragDrop> {
+        self.pending.take()
+    }
+}
+
+#[cfg(windows)]
+pub(crate) mod windows {
+    #![allow(non_snake_case)]
+    #![allow(non_upper_case_globals)]
+
+    use std::{
+        ffi::c_void,
+        sync::{
+            atomic::{AtomicBool, AtomicU32, Ordering},
+            Arc,
+        },
+    };
+
+    use windows_sys::{
+        core::{GUID, HRESULT},
+        Win32::{
+            Foundation::{
+                BOOL, DRAGDROP_S_CANCEL, DRAGDROP_S_DROP, DRAGDROP_S_USEDEFAULTCURSORS, E_NOINTERFACE, HGLOBAL, POINT, SIZE, S_OK,
+            },
+            Graphics::Gdi::{CreateDIBSection, DeleteObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS},
+            System::{
+                Com::{CoCreateInstance, CLSCTX_INPROC_SERVER, DVASPECT_CONTENT, FORMATETC, STGMEDIUM, STGMEDIUM_0, TYMED_HGLOBAL},
+                DataExchange::RegisterClipboardFormatW,
+                Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+                Ole::{DoDragDrop, OleInitialize, CF_HDROP, CF_UNICODETEXT, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE},
+                SystemServices::{MK_LBUTTON, MK_RBUTTON},
+            },
+            UI::{
+                Input::KeyboardAndMouse::{GetAsyncKeyState, VK_LBUTTON, VK_RBUTTON},
+                Shell::{SHCreateDataObject, DROPFILES, SHDRAGIMAGE},
+            },
+        },
+    };
+    use zng_txt::Txt;
+    use zng_view_api::{
+        drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
+        window::WindowId,
+        Event,
+    };
+
+    use crate::image_cache::Image;
+
+    /// Drag&drop operation that must run in the main thread after the request response is send.
+    pub(crate) struct PendingDragDrop {
+        window_id: WindowId,
+        drag_id: DragDropId,
+        data_object: *mut IDataObject,
+        allowed_effects: u32,
+        cancel: Arc<AtomicBool>,
+    }
+    impl PendingDragDrop {
+        pub(super) fn new(
+            window_id: WindowId,
+            drag_id: DragDropId,
+            data: Vec<DragDropData>,
+            allowed_effects: DragDropEffect,
+            image: Option<Image>,
+            cancel: Arc<AtomicBool>,
+        ) -> Result<Self, DragDropError> {
+            // winit already initializes OLE for drop targets, repeated calls only increment a counter.
+            // SAFETY: called in the main thread.
+            unsafe { OleInitialize(std::ptr::null_mut()) };
+
+            let mut data_object: *mut IDataObject = std::ptr::null_mut();
+            // SAFETY: creates an empty data object.
+            let r = unsafe {
+                SHCreateDataObject(
+                    std::ptr::null(),
+                    0,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    &IID_IDataObject,
+                    &mut data_object as *mut _ as _,
+                )
+            };
+            if r != S_OK || data_object.is_null() {
+                return Err(DragDropError::CannotStart(Txt::from(format!(
+                    "cannot create data object, error: 0x{r:x}"
+                ))));
+            }
+            let r = Self {
+                window_id,
+                drag_id,
+                data_object,
+                allowed_effects: to_drop_effect(allowed_effects),
+                cancel,
+            };
+
+            let mut paths = vec![];
+            let mut any = false;
+            for d in data {
+                match d {
+                    DragDropData::Text(t) => {
+                        let mut bytes = Vec::with_capacity((t.len() + 1) * 2);
+                        for c in t.encode_utf16().chain([0]) {
+                            bytes.extend_from_slice(&c.to_ne_bytes());
+                        }
+                        any |= r.set_data(CF_UNICODETEXT, &bytes);
+                    }
+                    DragDropData::Path(p) => paths.push(p),
+                    DragDropData::Extension { data_type, data } => {
+                        let name: Vec<u16> = data_type.encode_utf16().chain([0]).collect();
+                        // SAFETY: name is null terminated.
+                        let format = unsafe { RegisterClipboardFormatW(name.as_ptr()) };
+                        if format == 0 {
+                            tracing::error!("cannot register drag&drop format {data_type:?}");
+                        } else {
+                            any |= r.set_data(format as u16, &data[..]);
+                        }
+                    }
+                }
+            }
+            if !paths.is_empty() {
+                // DROPFILES header followed by a double null terminated list of null terminated wide paths.
+                let header_len = std::mem::size_of::<DROPFILES>();
+                let mut bytes = vec![0; header_len];
+                for p in &paths {
+                    use std::os::windows::ffi::OsStrExt as _;
+                    for c in p.as_os_str().encode_wide().chain([0]) {
+                        bytes.extend_from_slice(&c.to_ne_bytes());
+                    }
+                }
+                bytes.extend_from_slice(&[0, 0]);
+                let header = DROPFILES {
+                    pFiles: header_len as u32,
+                    pt: POINT { x: 0, y: 0 },
+                    fNC: 0,
+                    fWide: 1,
+                };
+                // SAFETY: bytes has space for the header.
+                unsafe { std::ptr::write_unaligned(bytes.as_mut_ptr() as *mut DROPFILES, header) };
+                any |= r.set_data(CF_HDROP, &bytes);
+            }
+            if !any {
+                return Err(DragDropError::CannotStart("no data can be converted to a clipboard format".into()));
+            }
+
+            if let Some(img) = image {
+                r.set_image(&img);
+            }
+
+            Ok(r)
+        }
+
+        fn set_data(&self, format: u16, bytes: &[u8]) -> bool {
+            // SAFETY: the memory is allocated with the required size and ownership is transferred to the data object.
+            unsafe {
+                let mem: HGLOBAL = GlobalAlloc(GMEM_MOVEABLE, bytes.len().max(1));
+                if mem.is_null() {
+                    tracing::error!("cannot allocate drag&drop data");
+                    return false;
+                }
+                let ptr = GlobalLock(mem);
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr as *mut u8, bytes.len());
+                GlobalUnlock(mem);
+
+                let format = FORMATETC {
+                    cfFormat: format,
+                    ptd: std::ptr::null_mut(),
+                    dwAspect: DVASPECT_CONTENT as _,
+                    lindex: -1,
+                    tymed: TYMED_HGLOBAL as _,
+                };
+                let medium = STGMEDIUM {
+                    tymed: TYMED_HGLOBAL as _,
+                    u: STGMEDIUM_0 { hGlobal: mem },
+                    pUnkForRelease: std::ptr::null_mut(),
+                };
+                let set_data = (*(*self.data_object).lpVtbl).SetData;
+                let r = set_data(self.data_object, &format, &medium, 1);
+                if r != S_OK {
+                    GlobalFree(mem);
+                    tracing::error!("cannot set drag&drop data, error: 0x{r:x}");
+                    return false;
+                }
+                true
+            }
+        }
+
+        fn set_image(&self, img: &Image) {
+            if img.descriptor().format != webrender::api::ImageFormat::BGRA8 {
+                // mask image
+                return;
+            }
+            let size = img.size();
+            let (width, height) = (size.width.0, size.height.0);
+            if width <= 0 || height <= 0 {
+                return;
+            }
+
+            // SAFETY: the DIB is allocated with the image size, the helper takes ownership of the bitmap on success.
+            unsafe {
+                let mut helper: *mut IDragSourceHelper = std::ptr::null_mut();
+                let r = CoCreateInstance(
+                    &CLSID_DragDropHelper,
+                    std::ptr::null_mut(),
+                    CLSCTX_INPROC_SERVER,
+                    &IID_IDragSourceHelper,
+                    &mut helper as *mut _ as _,
+                );
+                if r != S_OK || helper.is_null() {
+                    tracing::error!("cannot create drag image helper, error: 0x{r:x}");
+                    return;
+                }
+
+                let mut info: BITMAPINFO = std::mem::zeroed();
+                info.bmiHeader = BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    // top-down
+                    biHeight: -height,
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB as _,
+                    biSizeImage: 0,
+                    biXPelsPerMeter: 0,
+                    biYPelsPerMeter: 0,
+                    biClrUsed: 0,
+                    biClrImportant: 0,
+                };
+                let mut bits: *mut c_void = std::ptr::null_mut();
+                let bitmap = CreateDIBSection(0 as _, &info, DIB_RGB_COLORS, &mut bits, 0 as _, 0);
+                if bitmap == 0 as _ || bits.is_null() {
+                    tracing::error!("cannot create drag image bitmap");
+                } else {
+                    // both are premultiplied BGRA8
+                    let pixels = img.pixels();
+                    std::ptr::copy_nonoverlapping(pixels.as_ptr(), bits as *mut u8, pixels.len());
+
+                    let drag_image = SHDRAGIMAGE {
+                        sizeDragImage: SIZE { cx: width, cy: height },
+                        ptOffset: POINT {
+                            x: width / 2,
+                            y: height / 2,
+                        },
+                        hbmpDragImage: bitmap,
+                        // CLR_NONE
+                        crColorKey: 0xFFFFFFFF,
+                    };
+                    let init = (*(*helper).lpVtbl).InitializeFromBitmap;
+                    let r = init(helper, &drag_image, self.data_object);
+                    if r != S_OK {
+                        DeleteObject(bitmap);
+                        tracing::error!("cannot set drag image, error: 0x{r:x}");
+                    }
+                }
+
+                let release = (*(*helper).lpVtbl).parent.Release;
+                release(helper as _);
+            }
+        }
+
+        /// Run the system drag loop, returns after the data is dropped or the operation is cancelled.
+        ///
+        /// The `poll` closure is called often during the loop, it must process the app requests.
+        pub fn run(self, mut poll: impl FnMut()) -> Event {
+            let mut applied = DragDropEffect::empty();
+
+            // SAFETY: only queries the state.
+            let pressed =
+                unsafe { GetAsyncKeyState(VK_LBUTTON as _) as u16 & 0x8000 != 0 || GetAsyncKeyState(VK_RBUTTON as _) as u16 & 0x8000 != 0 };
+            if pressed && !self.cancel.load(Ordering::Relaxed) {
+                let poll: &mut dyn FnMut() = &mut poll;
+                // SAFETY: the source only calls `poll` during `DoDragDrop`, it is unset before return.
+                let poll = unsafe { std::mem::transmute::<&mut (dyn FnMut() + '_), *mut (dyn FnMut() + 'static)>(poll) };
+                let source = Box::into_raw(Box::new(DropSource {
+                    lpVtbl: &DROP_SOURCE_VTBL,
+                    refs: AtomicU32::new(1),
+                    cancel: self.cancel.clone(),
+                    poll: Some(poll),
+                }));
+
+                let mut effect = 0;
+                // SAFETY: the data object and source are valid COM objects.
+                let r = unsafe { DoDragDrop(self.data_object as _, source as _, self.allowed_effects, &mut effect) };
+                // SAFETY: source is still referenced by us.
+                unsafe {
+                    (*source).poll = None;
+                    drop_source_release(source);
+                }
+
+                if r == DRAGDROP_S_DROP {
+                    applied = from_drop_effect(effect);
+                } else if r != DRAGDROP_S_CANCEL {
+                    tracing::error!("drag&drop failed, error: 0x{r:x}");
+                }
+            }
+
+            Event::AppDragEnded {
+                window: self.window_id,
+                drag: self.drag_id,
+                applied,
+            }
+        }
+    }
+    impl Drop for PendingDragDrop {
+        fn drop(&mut self) {
+            // SAFETY: data_object is a valid COM object owned by us.
+            unsafe {
+                let release = (*(*self.data_object).lpVtbl).parent.Release;
+                release(self.data_object as _);
+            }
+        }
+    }
+
+    fn to_drop_effect(effect: DragDropEffect) -> u32 {
+        let mut r = 0;
+        if effect.contains(DragDropEffect::COPY) {
+            r |= DROPEFFECT_COPY;
+        }
+        if effect.contains(DragDropEffect::MOVE) {
+            r |= DROPEFFECT_MOVE;
+        }
+        if effect.contains(DragDropEffect::LINK) {
+            r |= DROPEFFECT_LINK;
+        }
+        r
+    }
+
+    fn from_drop_effect(effect: u32) -> DragDropEffect {
+        let mut r = DragDropEffect::empty();
+        if effect & DROPEFFECT_COPY != 0 {
+            r |= DragDropEffect::COPY;
+        }
+        if effect & DROPEFFECT_MOVE != 0 {
+            r |= DragDropEffect::MOVE;
+        }
+        if effect & DROPEFFECT_LINK != 0 {
+            r |= DragDropEffect::LINK;
+        }
+        r
+    }
+
+    fn guid_eq(a: &GUID, b: &GUID) -> bool {
+        a.data1 == b.data1 && a.data2 == b.data2 && a.data3 == b.data3 && a.data4 == b.data4
+    }
+
+    #[repr(C)]
+    pub struct IUnknownVtbl {
+        pub QueryInterface: unsafe extern "system" fn(This: *mut c_void, riid: *const GUID, ppvObject: *mut *mut c_void) -> HRESULT,
+        pub AddRef: unsafe extern "system" fn(This: *mut c_void) -> u32,
+        pub Release: unsafe extern "system" fn(This: *mut c_void) -> u32,
+    }
+
+    /// Partial `IDataObject` vtable, only up to `SetData`.
+    #[repr(C)]
+    pub struct IDataObjectVtbl {
+        pub parent: IUnknownVtbl,
+        pub GetData: *const c_void,
+        pub GetDataHere: *const c_void,
+        pub QueryGetData: *const c_void,
+        pub GetCanonicalFormatEtc: *const c_void,
+        pub SetData: unsafe extern "system" fn(
+            This: *mut IDataObject,
+            pformatetc: *const FORMATETC,
+            pmedium: *const STGMEDIUM,
+            fRelease: BOOL,
+        ) -> HRESULT,
+    }
+
+    #[repr(C)]
+    pub struct IDataObject {
+        pub lpVtbl: *const IDataObjectVtbl,
+    }
+
+    #[repr(C)]
+    pub struct IDragSourceHelperVtbl {
+        pub parent: IUnknownVtbl,
+        pub InitializeFromBitmap:
+            unsafe extern "system" fn(This: *mut IDragSourceHelper, pshdi: *const SHDRAGIMAGE, pDataObject: *mut IDataObject) -> HRESULT,
+        pub InitializeFromWindow: *const c_void,
+    }
+
+    #[repr(C)]
+    pub struct IDragSourceHelper {
+        pub lpVtbl: *const IDragSourceHelperVtbl,
+    }
+
+    /// `IDropSource` implementation.
+    #[repr(C)]
+    struct DropSource {
+        lpVtbl: *const IDropSourceVtbl,
+        refs: AtomicU32,
+        cancel: Arc<AtomicBool>,
+        poll: Option<*mut dyn FnMut()>,
+    }
+
+    #[repr(C)]
+    struct IDropSourceVtbl {
+        parent: IUnknownVtbl,
+        QueryContinueDrag: unsafe extern "system" fn(This: *mut DropSource, fEscapePressed: BOOL, grfKeyState: u32) -> HRESULT,
+        GiveFeedback: unsafe extern "system" fn(This: *mut DropSource, dwEffect: u32) -> HRESULT,
+    }
+
+    static DROP_SOURCE_VTBL: IDropSourceVtbl = IDropSourceVtbl {
+        parent: IUnknownVtbl {
+            QueryInterface: drop_source_query_interface,
+            AddRef: drop_source_add_ref,
+            Release: drop_source_release_raw,
+        },
+        QueryContinueDrag: drop_source_query_continue_drag,
+        GiveFeedback: drop_source_give_feedback,
+    };
+
+    unsafe extern "system" fn drop_source_query_interface(this: *mut c_void, riid: *const GUID, ppv: *mut *mut c_void) -> HRESULT {
+        if guid_eq(&*riid, &IID_IUnknown) || guid_eq(&*riid, &IID_IDropSource) {
+            drop_source_add_ref(this);
+            *ppv = this;
+            S_OK
+        } else {
+            *ppv = std::ptr::null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn drop_source_add_ref(this: *mut c_void) -> u32 {
+        (*(this as *mut DropSource)).refs.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    unsafe extern "system" fn drop_source_release_raw(this: *mut c_void) -> u32 {
+        drop_source_release(this as _)
+    }
+
+    unsafe fn drop_source_release(this: *mut DropSource) -> u32 {
+        let refs = (*this).refs.fetch_sub(1, Ordering::Release) - 1;
+        if refs == 0 {
+            std::sync::atomic::fence(Ordering::Acquire);
+            drop(Box::from_raw(this));
+        }
+        refs
+    }
+
+    unsafe extern "system" fn drop_source_query_continue_drag(this: *mut DropSource, escape_pressed: BOOL, key_state: u32) -> HRESULT {
+        if let Some(poll) = (*this).poll {
+            (*poll)();
+        }
+        if escape_pressed != 0 || (*this).cancel.load(Ordering::Relaxed) {
+            DRAGDROP_S_CANCEL
+        } else if key_state & (MK_LBUTTON | MK_RBUTTON) as u32 == 0 {
+            DRAGDROP_S_DROP
+        } else {
+            S_OK
+        }
+    }
+
+    unsafe extern "system" fn drop_source_give_feedback(_: *mut DropSource, _: u32) -> HRESULT {
+        DRAGDROP_S_USEDEFAULTCURSORS
+    }
+
+    const IID_IUnknown: GUID = GUID {
+        data1: 0x00000000,
+        data2: 0x0000,
+        data3: 0x0000,
+        data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+    };
+
+    const IID_IDataObject: GUID = GUID {
+        data1: 0x0000010e,
+        data2: 0x0000,
+        data3: 0x0000,
+        data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+    };
+
+    const IID_IDropSource: GUID = GUID {
+        data1: 0x00000121,
+        data2: 0x0000,
+        data3: 0x0000,
+        data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+    };
+
+    const CLSID_DragDropHelper: GUID = GUID {
+        data1: 0x4657278a,
+        data2: 0x411b,
+        data3: 0x11d2,
+        data4: [0x83, 0x9a, 0x00, 0xc0, 0x4f, 0xd9, 0x18, 0xd0],
+    };
+
+    const IID_IDragSourceHelper: GUID = GUID {
+        data1: 0xde5bf786,
+        data2: 0x477a,
+        data3: 0x11d2,
+        data4: [0x83, 0x9d, 0x00, 0xc0, 0x4f, 0xd9, 0x18, 0xd0],
+    };
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use objc2::{
+        declare_class, msg_send_id, mutability,
+        rc::Retained,
+        runtime::{AnyObject, NSObjectProtocol, ProtocolObject},
+        ClassType, DeclaredClass,
+    };
+    use objc2_app_kit::{
+        NSApplication, NSDragOperation, NSDraggingContext, NSDraggingItem, NSDraggingSession, NSDraggingSource, NSEvent,
+        NSEventModifierFlags, NSEventType, NSImage, NSPasteboardItem, NSPasteboardTypeString, NSPasteboardWriting,
+    };
+    use objc2_foundation::{MainThreadMarker, NSArray, NSData, NSObject, NSPoint, NSRect, NSSize, NSString, NSURL};
+    use zng_txt::Txt;
+    use zng_view_api::{
+        drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
+        image::ImageEncodeOptions,
+        window::WindowId,
+        Event,
+    };
+
+    use crate::{image_cache::Image, AppEvent, AppEventSender};
+
+    pub(crate) struct Ivars {
+        app_sender: AppEventSender,
+        window: WindowId,
+        drag: DragDropId,
+        allowed: NSDragOperation,
+        cancel: Arc<AtomicBool>,
+    }
+
+    declare_class!(
+        pub(crate) struct DragSourceDelegate;
+
+        unsafe impl ClassType for DragSourceDelegate {
+            type Super = NSObject;
+            type Mutability = mutability::MainThreadOnly;
+            const NAME: &'static str = "ZngDragSourceDelegate";
+        }
+
+        impl DeclaredClass for DragSourceDelegate {
+            type Ivars = Ivars;
+        }
+
+        unsafe impl NSObjectProtocol for DragSourceDelegate {}
+
+        unsafe impl NSDraggingSource for DragSourceDelegate {
+            #[method(draggingSession:sourceOperationMaskForDraggingContext:)]
+            fn source_operation_mask(&self, _: &NSDraggingSession, _: NSDraggingContext) -> NSDragOperation {
+                let ivars = self.ivars();
+                if ivars.cancel.load(Ordering::Relaxed) {
+                    NSDragOperation::NSDragOperationNone
+                } else {
+                    ivars.allowed
+                }
+            }
+
+            #[method(draggingSession:endedAtPoint:operation:)]
+            fn ended(&self, _: &NSDraggingSession, _: NSPoint, operation: NSDragOperation) {
+                let ivars = self.ivars();
+                let _ = ivars.app_sender.send(AppEvent::Notify(Event::AppDragEnded {
+                    window: ivars.window,
+                    drag: ivars.drag,
+                    applied: from_drag_operation(operation),
+                }));
+            }
+        }
+    );
+    impl DragSourceDelegate {
+        fn new(mtm: MainThreadMarker, ivars: Ivars) -> Retained<Self> {
+            let this = mtm.alloc::<Self>().set_ivars(ivars);
+            // SAFETY: `NSObject::init` has no preconditions.
+            unsafe { msg_send_id![super(this), init] }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn start(
+        window: &winit::window::Window,
+        window_id: WindowId,
+        drag_id: DragDropId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<Image>,
+        cancel: Arc<AtomicBool>,
+        app_sender: AppEventSender,
+    ) -> Result<Retained<DragSourceDelegate>, DragDropError> {
+        let mtm = MainThreadMarker::new().expect("drag&drop must start in the main thread");
+        let ns_window =
+            crate::util::winit_to_ns_window(window).ok_or_else(|| DragDropError::CannotStart(Txt::from_static("no native window")))?;
+        let view = ns_window
+            .contentView()
+            .ok_or_else(|| DragDropError::CannotStart(Txt::from_static("no native view")))?;
+
+        // dragging sessions must start from a mouse event, the current event is usually the press or move that
+        // caused the app-process request, if not a drag event is generated at the current cursor position.
+        let app = NSApplication::sharedApplication(mtm);
+        let event = match app.currentEvent() {
+            // SAFETY: only reads the event type.
+            Some(e)
+                if matches!(
+                    unsafe { e.r#type() },
+                    NSEventType::NSEventTypeLeftMouseDown | NSEventType::NSEventTypeLeftMouseDragged
+                ) =>
+            {
+                e
+            }
+            _ => {
+                let location = ns_window.mouseLocationOutsideOfEventStream();
+                // SAFETY: all arguments are valid, the context argument is unused.
+                unsafe {
+                    NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+                        NSEventType::NSEventTypeLeftMouseDragged,
+                        location,
+                        NSEventModifierFlags(0),
+                        0.0,
+                        ns_window.windowNumber(),
+                        None,
+                        0,
+                        1,
+                        1.0,
+                    )
+                }
+                .ok_or_else(|| DragDropError::CannotStart(Txt::from_static("cannot create drag event")))?
+            }
+        };
+        // SAFETY: the event is a mouse event.
+        let cursor = view.convertPoint_fromView(unsafe { event.locationInWindow() }, None);
+
+        let ns_image = image.and_then(|i| {
+            let mut png = vec![];
+            if let Err(e) = i.encode(image::ImageFormat::Png, ImageEncodeOptions::default(), &mut png) {
+                tracing::error!("cannot encode drag image, {e}");
+                return None;
+            }
+            let data = NSData::with_bytes(&png);
+            NSImage::initWithData(NSImage::alloc(), &data)
+        });
+        let frame = match &ns_image {
+            Some(i) => {
+                let size = i.size();
+                NSRect::new(NSPoint::new(cursor.x - size.width / 2.0, cursor.y - size.height / 2.0), size)
+            }
+            None => NSRect::new(cursor, NSSize::new(1.0, 1.0)),
+        };
+
+        let mut items = vec![];
+        for d in data {
+            let writer: Retained<ProtocolObject<dyn NSPasteboardWriting>> = match d {
+                DragDropData::Text(t) => {
+                    let item = NSPasteboardItem::new();
+                    // SAFETY: the type is a static string.
+                    unsafe { item.setString_forType(&NSString::from_str(&t), NSPasteboardTypeString) };
+                    ProtocolObject::from_retained(item)
+                }
+                DragDropData::Path(p) => {
+                    let url = NSURL::fileURLWithPath(&NSString::from_str(&p.to_string_lossy()));
+                    ProtocolObject::from_retained(url)
+                }
+                DragDropData::Extension { data_type, data } => {
+                    let item = NSPasteboardItem::new();
+                    // SAFETY: the type is a custom type string.
+                    unsafe { item.setData_forType(&NSData::with_bytes(&data[..]), &NSString::from_str(&data_type)) };
+                    ProtocolObject::from_retained(item)
+                }
+            };
+            let item = NSDraggingItem::initWithPasteboardWriter(NSDraggingItem::alloc(), &writer);
+            // SAFETY: the contents is an image.
+            unsafe { item.setDraggingFrame_contents(frame, ns_image.as_deref().map(AsRef::<AnyObject>::as_ref)) };
+            items.push(item);
+        }
+
+        let source = DragSourceDelegate::new(
+            mtm,
+            Ivars {
+                app_sender,
+                window: window_id,
+                drag: drag_id,
+                allowed: to_drag_operation(allowed_effects),
+                cancel,
+            },
+        );
+        let items = NSArray::from_vec(items);
+        let _session = view.beginDraggingSessionWithItems_event_source(&items, &event, ProtocolObject::from_ref(&*source));
+
+        Ok(source)
+    }
+
+    fn to_drag_operation(effect: DragDropEffect) -> NSDragOperation {
+        let mut r = NSDragOperation::NSDragOperationNone;
+        if effect.contains(DragDropEffect::COPY) {
+            r |= NSDragOperation::NSDragOperationCopy;
+        }
+        if effect.contains(DragDropEffect::MOVE) {
+            r |= NSDragOperation::NSDragOperationMove;
+        }
+        if effect.contains(DragDropEffect::LINK) {
+            r |= NSDragOperation::NSDragOperationLink;
+        }
+        r
+    }
+
+    fn from_drag_operation(op: NSDragOperation) -> DragDropEffect {
+        let mut r = DragDropEffect::empty();
+        if op.contains(NSDragOperation::NSDragOperationCopy) {
+            r |= DragDropEffect::COPY;
+        }
+        if op.contains(NSDragOperation::NSDragOperationMove) {
+            r |= DragDropEffect::MOVE;
+        }
+        if op.contains(NSDragOperation::NSDragOperationLink) {
+            r |= DragDropEffect::LINK;
+        }
+        r
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod x11 {
+    use std::{
+        ffi::CString,
+        os::raw::{c_int, c_long, c_uchar, c_uint, c_ulong},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+
+    use x11_dl::xlib;
+    use zng_txt::Txt;
+    use zng_view_api::{
+        drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
+        window::WindowId,
+        Event,
+    };
+
+    use crate::{AppEvent, AppEventSender};
+
+    /// Highest XDND protocol version implemented.
+    const XDND_VERSION: c_long = 5;
+
+    /// Pointer polling interval.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Max wait for `XdndFinished` after drop.
+    const FINISH_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Start the XDND source in a background thread.
+    ///
+    /// The source uses a dedicated connection and hidden window that owns the `XdndSelection`. Winit
+    /// does not expose the pointer grab, so the pointer is polled.
+    pub(super) fn start(
+        window_id: WindowId,
+        drag_id: DragDropId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        cancel: Arc<AtomicBool>,
+        app_sender: AppEventSender,
+    ) -> Result<(), DragDropError> {
+        let source = Source::open(data, allowed_effects)?;
+        std::thread::Builder::new()
+            .name("drag-source".into())
+            .stack_size(256 * 1024)
+            .spawn(move || {
+                let applied = source.run(&cancel);
+                drop(source);
+                let _ = app_sender.send(AppEvent::Notify(Event::AppDragEnded {
+                    window: window_id,
+                    drag: drag_id,
+                    applied,
+                }));
+            })
+            .map_err(|e| DragDropError::CannotStart(Txt::from(e.to_string())))?;
+        Ok(())
+    }
+
+    struct Atoms {
+        aware: xlib::Atom,
+        selection: xlib::Atom,
+        enter: xlib::Atom,
+        position: xlib::Atom,
+        status: xlib::Atom,
+        leave: xlib::Atom,
+        drop: xlib::Atom,
+        finished: xlib::Atom,
+        type_list: xlib::Atom,
+        action_copy: xlib::Atom,
+        action_move: xlib::Atom,
+        action_link: xlib::Atom,
+        targets: xlib::Atom,
+        utf8_string: xlib::Atom,
+        text_plain_utf8: xlib::Atom,
+        text_plain: xlib::Atom,
+        uri_list: xlib::Atom,
+    }
+
+    /// Target window state.
+    struct Target {
+        window: xlib::Window,
+        version: c_long,
+        /// Waiting `XdndStatus` for the last position.
+        waiting_status: bool,
+        /// Position changed while waiting status.
+        position_pending: bool,
+        accepted: bool,
+        action: xlib::Atom,
+    }
+
+    struct Source {
+        xlib: xlib::Xlib,
+        display: *mut xlib::Display,
+        root: xlib::Window,
+        window: xlib::Window,
+        atoms: Atoms,
+        /// Data by target type atom.
+        data: Vec<(xlib::Atom, Vec<u8>)>,
+        allowed_effects: DragDropEffect,
+    }
+    // SAFETY: the display connection is only used by one thread at a time.
+    unsafe impl Send for Source {}
+    impl Source {
+        fn open(data: Vec<DragDropData>, allowed_effects: DragDropEffect) -> Result<Self, DragDropError> {
+            let xlib = xlib::Xlib::open().map_err(|_| DragDropError::NotSupported)?;
+            // SAFETY: winit also calls this before opening the display, repeated calls are ignored.
+            unsafe { (xlib.XInitThreads)() };
+            // SAFETY: null opens the default display.
+            let display = unsafe { (xlib.XOpenDisplay)(std::ptr::null()) };
+            if display.is_null() {
+                return Err(DragDropError::CannotStart(Txt::from_static("cannot open X11 display")));
+            }
+            // SAFETY: display is valid.
+            let root = unsafe { (xlib.XDefaultRootWindow)(display) };
+            // SAFETY: display and root are valid, the window is never mapped.
+            let window = unsafe { (xlib.XCreateSimpleWindow)(display, root, -10, -10, 1, 1, 0, 0, 0) };
+
+            let atom = |name: &str| {
+                let name = CString::new(name).unwrap();
+                // SAFETY: display is valid and name is null terminated.
+                unsafe { (xlib.XInternAtom)(display, name.as_ptr(), xlib::False) }
+            };
+            let atoms = Atoms {
+                aware: atom("XdndAware"),
+                selection: atom("XdndSelection"),
+                enter: atom("XdndEnter"),
+                position: atom("XdndPosition"),
+                status: atom("XdndStatus"),
+                leave: atom("XdndLeave"),
+                drop: atom("XdndDrop"),
+                finished: atom("XdndFinished"),
+                type_list: atom("XdndTypeList"),
+                action_copy: atom("XdndActionCopy"),
+                action_move: atom("XdndActionMove"),
+                action_link: atom("XdndActionLink"),
+                targets: atom("TARGETS"),
+                utf8_string: atom("UTF8_STRING"),
+                text_plain_utf8: atom("text/plain;charset=utf-8"),
+                text_plain: atom("text/plain"),
+                uri_list: atom("text/uri-list"),
+            };
+
+            let mut uri_list = String::new();
+            let mut converted = vec![];
+            for d in data {
+                match d {
+                    DragDropData::Text(t) => {
+                        for a in [atoms.utf8_string, atoms.text_plain_utf8, atoms.text_plain] {
+                            converted.push((a, t.as_bytes().to_vec()));
+                        }
+                    }
+                    DragDropData::Path(p) => {
+                        uri_list.push_str("file://");
+                        uri_list.push_str(&percent_encode(p.as_os_str().as_encoded_bytes()));
+                        uri_list.push_str("\r\n");
+                    }
+                    DragDropData::Extension { data_type, data } => match CString::new(data_type.as_str()) {
+                        Ok(_) => converted.push((atom(&data_type), data.to_vec())),
+                        Err(_) => tracing::error!("invalid drag&drop data type {data_type:?}"),
+                    },
+                }
+            }
+            if !uri_list.is_empty() {
+                converted.push((atoms.uri_list, uri_list.into_bytes()));
+            }
+
+            let r = Self {
+                xlib,
+                display,
+                root,
+                window,
+                atoms,
+                data: converted,
+                allowed_effects,
+            };
+            if r.data.is_empty() {
+                return Err(DragDropError::NotSupported);
+            }
+
+            let types: Vec<c_ulong> = r.data.iter().map(|(a, _)| *a).collect();
+            // SAFETY: all handles are valid, types is a list of atoms.
+            unsafe {
+                (r.xlib.XChangeProperty)(
+                    r.display,
+                    r.window,
+                    r.atoms.type_list,
+                    xlib::XA_ATOM,
+                    32,
+                    xlib::PropModeReplace,
+                    types.as_ptr() as *const c_uchar,
+                    types.len() as c_int,
+                );
+                (r.xlib.XSetSelectionOwner)(r.display, r.atoms.selection, r.window, xlib::CurrentTime);
+                (r.xlib.XFlush)(r.display);
+            }
+
+            Ok(r)
+        }
+
+        fn run(&self, cancel: &AtomicBool) -> DragDropEffect {
+            let (_, _, mask) = self.query_pointer();
+            if mask & (xlib::Button1Mask | xlib::Button3Mask) == 0 {
+                // not pressed
+                return DragDropEffect::empty();
+            }
+
+            let mut target: Option<Target> = None;
+            let mut last_pos = (c_int::MIN, c_int::MIN);
+            loop {
+                self.process_events(&mut target, &mut false);
+
+                if cancel.load(Ordering::Relaxed) {
+                    if let Some(t) = target.take() {
+                        self.send(t.window, self.atoms.leave, [self.window as c_long, 0, 0, 0, 0]);
+                    }
+                    return DragDropEffect::empty();
+                }
+
+                let (x, y, mask) = self.query_pointer();
+
+                if mask & (xlib::Button1Mask | xlib::Button3Mask) == 0 {
+                    // released
+                    return match target.take() {
+                        Some(t) if t.accepted => self.drop_on(t),
+                        Some(t) => {
+                            self.send(t.window, self.atoms.leave, [self.window as c_long, 0, 0, 0, 0]);
+                            DragDropEffect::empty()
+                        }
+                        None => DragDropEffect::empty(),
+                    };
+                }
+
+                let moved = last_pos != (x, y);
+                if moved {
+                    last_pos = (x, y);
+
+                    let new_target = self.find_target(x, y);
+                    if new_target.map(|(w, _)| w) != target.as_ref().map(|t| t.window) {
+                        if let Some(t) = target.take() {
+                            self.send(t.window, self.atoms.leave, [self.window as c_long, 0, 0, 0, 0]);
+                        }
+                        if let Some((window, version)) = new_target {
+                            let version = version.min(XDND_VERSION);
+                            let more_types = if self.data.len() > 3 { 1 } else { 0 };
+                            let mut types = [0; 3];
+                            for (t, (a, _)) in types.iter_mut().zip(&self.data) {
+                                *t = *a as c_long;
+                            }
+                            self.send(
+                                window,
+                                self.atoms.enter,
+                                [self.window as c_long, (version << 24) | more_types, types[0], types[1], types[2]],
+                            );
+                            target = Some(Target {
+                                window,
+                                version,
+                                waiting_status: false,
+                                position_pending: false,
+                                accepted: false,
+                                action: 0,
+                            });
+                        }
+                    }
+                }
+
+                if let Some(t) = &mut target {
+                    if moved {
+                        t.position_pending = true;
+                    }
+                    if t.position_pending && !t.waiting_status {
+                        t.position_pending = false;
+                        t.waiting_status = true;
+                        self.send(
+                            t.window,
+                            self.atoms.position,
+                            [
+                                self.window as c_long,
+                                0,
+                                ((x as c_long) << 16) | (y as c_long & 0xFFFF),
+                                xlib::CurrentTime as c_long,
+                                self.preferred_action() as c_long,
+                            ],
+                        );
+                    }
+                }
+
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+
+        /// Send drop and wait finished.
+        fn drop_on(&self, mut target: Target) -> DragDropEffect {
+            self.send(
+                target.window,
+                self.atoms.drop,
+                [self.window as c_long, 0, xlib::CurrentTime as c_long, 0, 0],
+            );
+
+            let deadline = Instant::now() + FINISH_TIMEOUT;
+            let mut finished = false;
+            let mut t = Some(target);
+            while !finished && Instant::now() < deadline {
+                self.process_events(&mut t, &mut finished);
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            target = t.unwrap();
+            if finished && target.accepted {
+                self.action_to_effect(target.action)
+            } else {
+                DragDropEffect::empty()
+            }
+        }
+
+        fn process_events(&self, target: &mut Option<Target>, finished: &mut bool) {
+            // SAFETY: display is valid.
+            while unsafe { (self.xlib.XPending)(self.display) } > 0 {
+                // SAFETY: zeroed is a valid XEvent, display is valid.
+                let mut ev = unsafe { std::mem::zeroed::<xlib::XEvent>() };
+                unsafe { (self.xlib.XNextEvent)(self.display, &mut ev) };
+
+                match ev.get_type() {
+                    xlib::SelectionRequest => {
+                        // SAFETY: checked event type.
+                        let req = unsafe { ev.selection_request };
+                        self.respond_selection(&req);
+                    }
+                    xlib::ClientMessage => {
+                        // SAFETY: checked event type.
+                        let msg = unsafe { ev.client_message };
+                        let Some(t) = target else {
+                            continue;
+                        };
+                        if msg.data.get_long(0) as xlib::Window != t.window {
+                            continue;
+                        }
+                        if msg.message_type == self.atoms.status {
+                            t.waiting_status = false;
+                            t.accepted = msg.data.get_long(1) & 1 != 0;
+                            t.action = if t.version >= 2 {
+                                msg.data.get_long(4) as xlib::Atom
+                            } else {
+                                self.atoms.action_copy
+                            };
+                        } else if msg.message_type == self.atoms.finished {
+                            *finished = true;
+                            if t.version >= 5 {
+                                t.accepted = msg.data.get_long(1) & 1 != 0;
+                                t.action = msg.data.get_long(2) as xlib::Atom;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn respond_selection(&self, req: &xlib::XSelectionRequestEvent) {
+            let property = if req.property == 0 { req.target } else { req.property };
+            let mut responded = false;
+            if req.selection == self.atoms.selection {
+                if req.target == self.atoms.targets {
+                    let mut targets: Vec<c_ulong> = vec![self.atoms.targets];
+                    targets.extend(self.data.iter().map(|(a, _)| *a));
+                    // SAFETY: all handles are valid, targets is a list of atoms.
+                    unsafe {
+                        (self.xlib.XChangeProperty)(
+                            self.display,
+                            req.requestor,
+                            property,
+                            xlib::XA_ATOM,
+                            32,
+                            xlib::PropModeReplace,
+                            targets.as_ptr() as *const c_uchar,
+                            targets.len() as c_int,
+                        )
+                    };
+                    responded = true;
+                } else if let Some((_, data)) = self.data.iter().find(|(a, _)| *a == req.target) {
+                    // SAFETY: all handles are valid, data is bytes.
+                    unsafe {
+                        (self.xlib.XChangeProperty)(
+                            self.display,
+                            req.requestor,
+                            property,
+                            req.target,
+                            8,
+                            xlib::PropModeReplace,
+                            data.as_ptr(),
+                            data.len() as c_int,
+                        )
+                    };
+                    responded = true;
+                }
+            }
+
+            let mut notify = xlib::XEvent {
+                selection: xlib::XSelectionEvent {
+                    type_: xlib::SelectionNotify,
+                    serial: 0,
+                    send_event: xlib::True,
+                    display: self.display,
+                    requestor: req.requestor,
+                    selection: req.selection,
+                    target: req.target,
+                    property: if responded { property } else { 0 },
+                    time: req.time,
+                },
+            };
+            // SAFETY: all handles are valid.
+            unsafe {
+                (self.xlib.XSendEvent)(self.display, req.requestor, xlib::False, xlib::NoEventMask, &mut notify);
+                (self.xlib.XFlush)(self.display);
+            }
+        }
+
+        fn query_pointer(&self) -> (c_int, c_int, c_uint) {
+            let (mut root, mut child) = (0, 0);
+            let (mut x, mut y, mut win_x, mut win_y) = (0, 0, 0, 0);
+            let mut mask = 0;
+            // SAFETY: display and root are valid.
+            unsafe {
+                (self.xlib.XQueryPointer)(
+                    self.display,
+                    self.root,
+                    &mut root,
+                    &mut child,
+                    &mut x,
+                    &mut y,
+                    &mut win_x,
+                    &mut win_y,
+                    &mut mask,
+                )
+            };
+            (x, y, mask)
+        }
+
+        /// Find the XDND aware window at the root position and the protocol version.
+        fn find_target(&self, x: c_int, y: c_int) -> Option<(xlib::Window, c_long)> {
+            let mut window = self.root;
+            loop {
+                let (mut dx, mut dy, mut child) = (0, 0, 0);
+                // SAFETY: display and windows are valid.
+                let ok = unsafe { (self.xlib.XTranslateCoordinates)(self.display, self.root, window, x, y, &mut dx, &mut dy, &mut child) };
+                if ok == 0 || child == 0 {
+                    return None;
+                }
+                window = child;
+                if let Some(version) = self.xdnd_aware(window) {
+                    return Some((window, version));
+                }
+            }
+        }
+
+        fn xdnd_aware(&self, window: xlib::Window) -> Option<c_long> {
+            let mut actual_type = 0;
+            let mut actual_format = 0;
+            let (mut n, mut after) = (0, 0);
+            let mut prop: *mut c_uchar = std::ptr::null_mut();
+            // SAFETY: display and window are valid, prop is freed.
+            unsafe {
+                let r = (self.xlib.XGetWindowProperty)(
+                    self.display,
+                    window,
+                    self.atoms.aware,
+                    0,
+                    1,
+                    xlib::False,
+                    xlib::AnyPropertyType as _,
+                    &mut actual_type,
+                    &mut actual_format,
+                    &mut n,
+                    &mut after,
+                    &mut prop,
+                );
+                let version = if r == xlib::Success as c_int && !prop.is_null() && n > 0 && actual_format == 32 {
+                    Some(*(prop as *const c_long))
+                } else {
+                    None
+                };
+                if !prop.is_null() {
+                    (self.xlib.XFree)(prop as _);
+                }
+                version
+            }
+        }
+
+        fn preferred_action(&self) -> xlib::Atom {
+            if self.allowed_effects.contains(DragDropEffect::COPY) {
+                self.atoms.action_copy
+            } else if self.allowed_effects.contains(DragDropEffect::MOVE) {
+                self.atoms.action_move
+            } else {
+                self.atoms.action_link
+            }
+        }
+
+        fn action_to_effect(&self, action: xlib::Atom) -> DragDropEffect {
+            let effect = if action == self.atoms.action_move {
+                DragDropEffect::MOVE
+            } else if action == self.atoms.action_link {
+                DragDropEffect::LINK
+            } else {
+                DragDropEffect::COPY
+            };
+            effect & self.allowed_effects
+        }
+
+        fn send(&self, target: xlib::Window, message_type: xlib::Atom, data: [c_long; 5]) {
+            // SAFETY: zeroed is a valid XEvent.
+            let mut ev = unsafe { std::mem::zeroed::<xlib::XEvent>() };
+            // SAFETY: the event is initialized as a client message.
+            unsafe {
+                let msg = &mut ev.client_message;
+                msg.type_ = xlib::ClientMessage;
+                msg.display = self.display;
+                msg.window = target;
+                msg.message_type = message_type;
+                msg.format = 32;
+                for (i, d) in data.into_iter().enumerate() {
+                    msg.data.set_long(i, d);
+                }
+                (self.xlib.XSendEvent)(self.display, target, xlib::False, xlib::NoEventMask, &mut ev);
+                (self.xlib.XFlush)(self.display);
+            }
+        }
+    }
+    impl Drop for Source {
+        fn drop(&mut self) {
+            // SAFETY: display and window are valid and owned.
+            unsafe {
+                (self.xlib.XDestroyWindow)(self.display, self.window);
+                (self.xlib.XCloseDisplay)(self.display);
+            }
+        }
+    }
+
+    fn percent_encode(path: &[u8]) -> String {
+        use std::fmt::Write as _;
+
+        let mut r = String::with_capacity(path.len());
+        for &b in path {
+            if b.is_ascii_alphanumeric() || matches!(b, b'/' | b'-' | b'_' | b'.' | b'~') {
+                r.push(b as char);
+            } else {
+                let _ = write!(&mut r, "%{b:02X}");
+            }
+        }
+        r
+    }
+}
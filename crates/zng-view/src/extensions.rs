@@ -5,15 +5,19 @@
 //! the view-process without needing to fork it or re-implement the entire view API from scratch.
 //!
 
+use std::mem;
 use std::rc::Rc;
 use std::{any::Any, sync::Arc};
 
 use webrender::api::{
-    units::TexelRect, AsyncBlobImageRasterizer, BlobImageHandler, BlobImageParams, BlobImageRequest, BlobImageResult, DocumentId,
-    ExternalImageId, PipelineId,
+    units::{ImageDirtyRect, TexelRect},
+    AsyncBlobImageRasterizer, BlobImageHandler, BlobImageParams, BlobImageRequest, BlobImageResult, DocumentId, ExternalImageData,
+    ExternalImageId, ExternalImageType, ImageBufferKind, ImageDescriptor, ImageKey, PipelineId,
 };
 use webrender::{DebugFlags, RenderApi};
+use zng_txt::Txt;
 use zng_unit::{Factor, PxSize};
+use zng_view_api::image::{ImagePpi, ImageTextureId};
 use zng_view_api::window::RenderMode;
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionName, ApiExtensionPayload, ApiExtensions},
@@ -259,6 +263,14 @@ pub struct RedrawArgs<'a> {
     ///
     /// The context is current, and Webrender has already redraw.
     pub context: &'a mut dyn OpenGlContext,
+
+    /// Request another redraw after this one, synced with the display refresh rate.
+    ///
+    /// Extensions that render custom animations can keep setting this to `true` to render continuously
+    /// while the window is visible. Extensions can only request a redraw, the value starts `true` if the window
+    /// is in continuous render mode or a previous extension requested it, setting it to `false` does not cancel
+    /// these requests. Headless surfaces ignore this request.
+    pub redraw_again: bool,
 }
 
 /// Represents a Webrender blob handler that can coexist with other blob handlers on the same renderer.
@@ -311,6 +323,66 @@ pub trait BlobExtension: Send + Any {
     fn enable_multithreading(&mut self, enable: bool);
 }
 
+/// Represents a custom image decoder.
+///
+/// Image decoders add formats to the view-process image cache, formats handled by a registered decoder take
+/// precedence over the built-in decoders. See [`ViewExtensions::image_decoder`] for more details.
+///
+/// Decoders of vector formats like SVG must return `true` in [`is_vector`], the encoded data of vector images
+/// is retained in the cache and rasterized again on demand, so that the app-process can request new rasters at the
+/// exact pixel size needed for the current layout and scale factor.
+///
+/// The default view-process does not bundle any vector decoder, an SVG decoder can be implemented using a
+/// crate like `resvg`.
+///
+/// [`is_vector`]: ImageDecoderExtension::is_vector
+pub trait ImageDecoderExtension: Send + Sync + Any {
+    /// Lower case file extensions and MIME sub-types (the part after `image/`) supported by the decoder.
+    ///
+    /// Example: `["svg", "svgz", "svg+xml"]`.
+    fn formats(&self) -> Vec<Txt>;
+
+    /// Returns `true` if the encoded `data` is in a format supported by the decoder.
+    ///
+    /// This is called for images of unknown format that are not supported by the built-in decoders.
+    fn matches(&self, data: &[u8]) -> bool {
+        let _ = data;
+        false
+    }
+
+    /// If the decoder is for a vector format.
+    fn is_vector(&self) -> bool {
+        false
+    }
+
+    /// Decode the image.
+    ///
+    /// This is called in a worker thread.
+    fn decode(&self, args: ImageDecodeArgs) -> Result<DecodedImage, Txt>;
+}
+
+/// Arguments for [`ImageDecoderExtension::decode`].
+pub struct ImageDecodeArgs<'a> {
+    /// Encoded image.
+    pub data: &'a [u8],
+    /// Format file extension or MIME sub-type, is empty if the format was detected by [`ImageDecoderExtension::matches`].
+    pub format: &'a Txt,
+    /// Raster size for vector images.
+    ///
+    /// If `None` the image must be decoded at its intrinsic size.
+    pub size: Option<PxSize>,
+}
+
+/// Image decoded by an [`ImageDecoderExtension`].
+pub struct DecodedImage {
+    /// Pixel size.
+    pub size: PxSize,
+    /// RGBA8 pixels, not pre-multiplied, in the sRGB color space.
+    pub pixels: Vec<u8>,
+    /// Pixel-per-inch metadata.
+    pub ppi: Option<ImagePpi>,
+}
+
 /// Arguments for [`ViewExtension::init`].
 pub struct ViewExtensionInitArgs {
     /// Sender of [`Event::ExtensionEvent`] events.
@@ -471,9 +543,24 @@ pub struct RendererInitedArgs<'a> {
 }
 
 /// Tracks extension external images for a renderer.
+///
+/// # Texture Images
+///
+/// Textures can be registered as images using [`register_texture_image`], this returns an [`ImageTextureId`] that
+/// the app-process can use as any other image, the extension must send the ID to the app-process, usually as a
+/// [`RendererExtension::command`] response, the app-process can then use `Img::from_external` to present the texture as
+/// an image, it composites without any CPU copies of the pixels.
+///
+/// [`register_texture_image`]: Self::register_texture_image
 #[derive(Default)]
 pub struct ExternalImages {
     images: Vec<Arc<crate::image_cache::ImageData>>,
+    // (id, current, previous), previous is kept alive in case the renderer is still using it.
+    texture_images: Vec<(
+        ImageTextureId,
+        Arc<crate::image_cache::ImageData>,
+        Option<Arc<crate::image_cache::ImageData>>,
+    )>,
 }
 impl ExternalImages {
     /// Register an OpenGL texture.
@@ -515,6 +602,87 @@ impl ExternalImages {
         }
     }
 
+    /// Register an OpenGL texture as an image resource in the renderer.
+    ///
+    /// Returns an ID that can be used by the app-process in display lists of the same renderer as any other image.
+    ///
+    /// The `texture` must be generated by the same GL API used by the renderer. Note that you must manage the `texture` lifetime,
+    /// [`unregister_texture_image`] only releases the image resource.
+    ///
+    /// [`unregister_texture_image`]: Self::unregister_texture_image
+    pub fn register_texture_image(
+        &mut self,
+        api: &mut RenderApi,
+        document_id: DocumentId,
+        descriptor: ImageDescriptor,
+        uv: TexelRect,
+        texture: gleam::gl::GLuint,
+    ) -> ImageTextureId {
+        let img = Arc::new(crate::image_cache::ImageData::NativeTexture { uv, texture });
+        let key = api.generate_image_key();
+        let id = ImageTextureId::from_raw(key.1);
+
+        let mut txn = webrender::Transaction::new();
+        txn.add_image(key, descriptor, texture_image_data(&img), None);
+        api.send_transaction(document_id, txn);
+
+        self.texture_images.push((id, img, None));
+        id
+    }
+
+    /// Replace the texture of an image registered using [`register_texture_image`].
+    ///
+    /// This must be called every time the texture content changes, for example, on every video frame, so that
+    /// the renderer composites the new content.
+    ///
+    /// [`register_texture_image`]: Self::register_texture_image
+    pub fn update_texture_image(
+        &mut self,
+        api: &mut RenderApi,
+        document_id: DocumentId,
+        id: ImageTextureId,
+        descriptor: ImageDescriptor,
+        uv: TexelRect,
+        texture: gleam::gl::GLuint,
+    ) {
+        if let Some((_, img, prev)) = self.texture_images.iter_mut().find(|(i, _, _)| *i == id) {
+            let changed = match &**img {
+                crate::image_cache::ImageData::NativeTexture { uv: u, texture: t } => *u != uv || *t != texture,
+                _ => true,
+            };
+            if changed {
+                let new = Arc::new(crate::image_cache::ImageData::NativeTexture { uv, texture });
+                *prev = Some(mem::replace(img, new));
+            }
+
+            let mut txn = webrender::Transaction::new();
+            txn.update_image(
+                ImageKey(api.get_namespace_id(), id.get()),
+                descriptor,
+                texture_image_data(img),
+                &ImageDirtyRect::All,
+            );
+            api.send_transaction(document_id, txn);
+        }
+    }
+
+    /// Unregister an image registered using [`register_texture_image`].
+    ///
+    /// The app-process must not use the `id` after this call.
+    ///
+    /// [`register_texture_image`]: Self::register_texture_image
+    pub fn unregister_texture_image(&mut self, api: &mut RenderApi, document_id: DocumentId, id: ImageTextureId) {
+        if let Some(i) = self.texture_images.iter().position(|(i, _, _)| *i == id) {
+            let (_, img, prev) = self.texture_images.swap_remove(i);
+            let mut txn = webrender::Transaction::new();
+            txn.delete_image(ImageKey(api.get_namespace_id(), id.get()));
+            api.send_transaction(document_id, txn);
+            // keep alive in case the renderer is using a texture from a previous frame.
+            self.images.push(img);
+            self.images.extend(prev);
+        }
+    }
+
     fn register(&mut self, img: crate::image_cache::ImageData) -> ExternalImageId {
         let img = Arc::new(img);
         let id = ExternalImageId(Arc::as_ptr(&img) as u64);
@@ -523,6 +691,14 @@ impl ExternalImages {
     }
 }
 
+fn texture_image_data(img: &Arc<crate::image_cache::ImageData>) -> webrender::api::ImageData {
+    webrender::api::ImageData::External(ExternalImageData {
+        id: ExternalImageId(Arc::as_ptr(img) as u64),
+        channel_index: 0,
+        image_type: ExternalImageType::TextureHandle(ImageBufferKind::Texture2D),
+    })
+}
+
 /// Arguments for [`RendererExtension::renderer_deinited`].
 pub struct RendererDeinitedArgs<'a> {
     /// The document ID of the main content, already deinited.
@@ -644,6 +820,12 @@ pub struct RendererCommandArgs<'a> {
     /// The render API used by the window or surface.
     pub api: &'a mut RenderApi,
 
+    /// The document ID of the main content.
+    pub document_id: DocumentId,
+
+    /// External images registry for the `renderer`.
+    pub external_images: &'a mut ExternalImages,
+
     /// The command request.
     pub request: ApiExtensionPayload,
 
@@ -662,11 +844,15 @@ pub struct RendererCommandArgs<'a> {
 /// View extensions register.
 pub struct ViewExtensions {
     exts: Vec<Box<dyn ViewExtension>>,
+    image_decoders: Vec<Arc<dyn ImageDecoderExtension>>,
 }
 impl ViewExtensions {
     /// New empty.
     pub(crate) fn new() -> Self {
-        Self { exts: vec![] }
+        Self {
+            exts: vec![],
+            image_decoders: vec![],
+        }
     }
 
     /// Register an extension with the ID that will be assigned to it.
@@ -776,6 +962,50 @@ impl ViewExtensions {
         self
     }
 
+    /// Register a GPU canvas renderer.
+    ///
+    /// The `new` closure is called to create a renderer for each canvas instance requested by the app-process.
+    /// See the [`gpu_canvas`] module for more details.
+    ///
+    /// [`gpu_canvas`]: crate::gpu_canvas
+    pub fn gpu_canvas<R: crate::gpu_canvas::GpuCanvasRenderer>(
+        &mut self,
+        name: impl Into<ApiExtensionName>,
+        new: impl FnMut() -> R + Send + 'static,
+    ) -> &mut Self {
+        let factory = crate::gpu_canvas::GpuCanvasExt::new_factory(new);
+        self.renderer(name, move |id| crate::gpu_canvas::GpuCanvasExt::new(id, factory.clone()))
+    }
+
+    /// Register a native child host.
+    ///
+    /// The `new` closure is called to create a host for each native child instance requested by the app-process.
+    /// See the [`native_child`] module for more details.
+    ///
+    /// [`native_child`]: crate::native_child
+    pub fn native_child<H: crate::native_child::NativeChildHost>(
+        &mut self,
+        name: impl Into<ApiExtensionName>,
+        new: impl FnMut() -> H + Send + 'static,
+    ) -> &mut Self {
+        self.register(move |id| crate::native_child::NativeChildViewExt::new(name.into(), id, new))
+    }
+
+    /// Register an image decoder.
+    ///
+    /// The decoder is used for all images in the [`formats`] it declares, decoders registered later take precedence.
+    /// See [`ImageDecoderExtension`] for more details.
+    ///
+    /// [`formats`]: ImageDecoderExtension::formats
+    pub fn image_decoder(&mut self, decoder: impl ImageDecoderExtension) -> &mut Self {
+        self.image_decoders.insert(0, Arc::new(decoder));
+        self
+    }
+
+    pub(crate) fn image_decoders(&self) -> Vec<Arc<dyn ImageDecoderExtension>> {
+        self.image_decoders.clone()
+    }
+
     pub(crate) fn api_extensions(&self) -> ApiExtensions {
         let mut r = ApiExtensions::new();
         for ext in &self.exts {
@@ -831,6 +1061,8 @@ impl ViewExtensions {
     /// Add `other` to self.
     pub fn append(&mut self, mut other: ViewExtensions) {
         self.exts.append(&mut other.exts);
+        other.image_decoders.append(&mut self.image_decoders);
+        self.image_decoders = other.image_decoders;
     }
 }
 
@@ -0,0 +1,177 @@
+//! Gamepad input.
+//!
+//! Implemented using `gilrs` when the `"gamepad"` feature is enabled, a background thread polls the controllers
+//! and sends [`GamepadEvent`] to the event loop that maps the native gamepad IDs to [`DeviceId`].
+
+use zng_txt::Txt;
+use zng_view_api::{
+    gamepad::{GamepadAxis, GamepadButton},
+    mouse::ButtonState,
+    DeviceId, Event,
+};
+
+use crate::AppEventSender;
+
+/// Gamepad event with the native gamepad ID.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "gamepad"), allow(unused))]
+pub(crate) enum GamepadEvent {
+    Connected(usize, Txt),
+    Disconnected(usize),
+    Button(usize, GamepadButton, f32, ButtonState),
+    Axis(usize, GamepadAxis, f32),
+}
+
+/// Maps native gamepad IDs.
+#[derive(Default)]
+pub(crate) struct Gamepads {
+    devices: Vec<(DeviceId, usize)>,
+}
+impl Gamepads {
+    /// Spawn the gamepad polling thread.
+    #[cfg(feature = "gamepad")]
+    pub fn start(&mut self, app_sender: AppEventSender) {
+        let r = std::thread::Builder::new().name("gamepad".into()).spawn(move || {
+            let mut gilrs = match gilrs::Gilrs::new() {
+                Ok(g) => g,
+                Err(gilrs::Error::NotImplemented(g)) => {
+                    tracing::warn!("gamepad input not implemented for the platform");
+                    g
+                }
+                Err(e) => {
+                    tracing::error!("cannot init gamepad input, {e}");
+                    return;
+                }
+            };
+
+            for (id, g) in gilrs.gamepads() {
+                let ev = GamepadEvent::Connected(id.into(), Txt::from_str(g.name()));
+                if app_sender.send(crate::AppEvent::Gamepad(ev)).is_err() {
+                    return;
+                }
+            }
+
+            loop {
+                let ev = match gilrs.next_event_blocking(None) {
+                    Some(e) => e,
+                    None => continue,
+                };
+                let id = usize::from(ev.id);
+                let ev = match ev.event {
+                    gilrs::EventType::Connected => GamepadEvent::Connected(id, Txt::from_str(gilrs.gamepad(ev.id).name())),
+                    gilrs::EventType::Disconnected => GamepadEvent::Disconnected(id),
+                    gilrs::EventType::ButtonPressed(b, code) => {
+                        let value = gilrs.gamepad(ev.id).button_data(b).map(|d| d.value()).unwrap_or(1.0);
+                        GamepadEvent::Button(id, button_to_zng(b, code), value, ButtonState::Pressed)
+                    }
+                    gilrs::EventType::ButtonReleased(b, code) => {
+                        let value = gilrs.gamepad(ev.id).button_data(b).map(|d| d.value()).unwrap_or(0.0);
+                        GamepadEvent::Button(id, button_to_zng(b, code), value, ButtonState::Released)
+                    }
+                    gilrs::EventType::ButtonChanged(b, value, code) => {
+                        let state = if gilrs.gamepad(ev.id).is_pressed(b) {
+                            ButtonState::Pressed
+                        } else {
+                            ButtonState::Released
+                        };
+                        GamepadEvent::Button(id, button_to_zng(b, code), value, state)
+                    }
+                    gilrs::EventType::AxisChanged(a, value, code) => match axis_to_zng(a, code) {
+                        Some(a) => GamepadEvent::Axis(id, a, value),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                if app_sender.send(crate::AppEvent::Gamepad(ev)).is_err() {
+                    return;
+                }
+            }
+        });
+        if let Err(e) = r {
+            tracing::error!("cannot spawn gamepad thread, {e}");
+        }
+    }
+
+    /// Gamepad input is not enabled.
+    #[cfg(not(feature = "gamepad"))]
+    pub fn start(&mut self, app_sender: AppEventSender) {
+        let _ = app_sender;
+    }
+
+    /// Convert the event.
+    pub fn on_event(&mut self, device_id_gen: &mut DeviceId, ev: GamepadEvent) -> Event {
+        match ev {
+            GamepadEvent::Connected(id, name) => {
+                let device = self.device_id(device_id_gen, id);
+                Event::GamepadConnected { device, name }
+            }
+            GamepadEvent::Disconnected(id) => {
+                let device = self.device_id(device_id_gen, id);
+                self.devices.retain(|(_, i)| *i != id);
+                Event::GamepadDisconnected { device }
+            }
+            GamepadEvent::Button(id, button, value, state) => Event::GamepadButton {
+                device: self.device_id(device_id_gen, id),
+                button,
+                value,
+                state,
+            },
+            GamepadEvent::Axis(id, axis, value) => Event::GamepadAxis {
+                device: self.device_id(device_id_gen, id),
+                axis,
+                value,
+            },
+        }
+    }
+
+    fn device_id(&mut self, device_id_gen: &mut DeviceId, native: usize) -> DeviceId {
+        if let Some((id, _)) = self.devices.iter().find(|(_, n)| *n == native) {
+            *id
+        } else {
+            let id = device_id_gen.incr();
+            self.devices.push((id, native));
+            id
+        }
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn button_to_zng(b: gilrs::Button, code: gilrs::ev::Code) -> GamepadButton {
+    use gilrs::Button::*;
+    match b {
+        South => GamepadButton::South,
+        East => GamepadButton::East,
+        North => GamepadButton::North,
+        West => GamepadButton::West,
+        LeftTrigger => GamepadButton::LeftTrigger,
+        LeftTrigger2 => GamepadButton::LeftTrigger2,
+        RightTrigger => GamepadButton::RightTrigger,
+        RightTrigger2 => GamepadButton::RightTrigger2,
+        Select => GamepadButton::Select,
+        Start => GamepadButton::Start,
+        Mode => GamepadButton::Mode,
+        LeftThumb => GamepadButton::LeftThumb,
+        RightThumb => GamepadButton::RightThumb,
+        DPadUp => GamepadButton::DPadUp,
+        DPadDown => GamepadButton::DPadDown,
+        DPadLeft => GamepadButton::DPadLeft,
+        DPadRight => GamepadButton::DPadRight,
+        _ => GamepadButton::Other(code.into_u32()),
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn axis_to_zng(a: gilrs::Axis, code: gilrs::ev::Code) -> Option<GamepadAxis> {
+    use gilrs::Axis::*;
+    match a {
+        LeftStickX => Some(GamepadAxis::LeftStickX),
+        LeftStickY => Some(GamepadAxis::LeftStickY),
+        LeftZ => Some(GamepadAxis::LeftZ),
+        RightStickX => Some(GamepadAxis::RightStickX),
+        RightStickY => Some(GamepadAxis::RightStickY),
+        RightZ => Some(GamepadAxis::RightZ),
+        // gilrs also reports the D-pad as buttons.
+        DPadX | DPadY => None,
+        _ => Some(GamepadAxis::Other(code.into_u32())),
+    }
+}
@@ -0,0 +1,499 @@
+//! Global shortcuts.
+//!
+//! Implemented using `RegisterHotKey` on Windows and `XGrabKey` on X11, not supported on other systems.
+
+use zng_view_api::keyboard::{GlobalShortcut, GlobalShortcutError, GlobalShortcutId};
+
+use crate::AppEventSender;
+
+#[cfg(windows)]
+pub(crate) struct GlobalShortcuts {
+    app_sender: AppEventSender,
+    /// Message-only window that receives `WM_HOTKEY`, created on the first registration.
+    hwnd: windows_sys::Win32::Foundation::HWND,
+    registered: std::collections::HashMap<GlobalShortcutId, i32>,
+}
+#[cfg(windows)]
+impl GlobalShortcuts {
+    pub fn new(app_sender: AppEventSender) -> Self {
+        Self {
+            app_sender,
+            hwnd: 0,
+            registered: Default::default(),
+        }
+    }
+
+    pub fn register(&mut self, id: GlobalShortcutId, shortcut: GlobalShortcut) -> Result<(), GlobalShortcutError> {
+        use windows_sys::Win32::UI::Input::KeyboardAndMouse::*;
+        use winit::platform::scancode::PhysicalKeyExtScancode as _;
+        use zng_view_api::keyboard::GlobalShortcutModifiers;
+
+        let scancode = crate::util::key_code_to_winit_key_code(shortcut.key_code)
+            .and_then(|k| k.to_scancode())
+            .ok_or(GlobalShortcutError::NotSupported)?;
+        // SAFETY: function has no preconditions.
+        let vk = unsafe { MapVirtualKeyW(scancode, MAPVK_VSC_TO_VK_EX) };
+        if vk == 0 {
+            return Err(GlobalShortcutError::NotSupported);
+        }
+
+        let mut modifiers = MOD_NOREPEAT;
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::SHIFT) {
+            modifiers |= MOD_SHIFT;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::CTRL) {
+            modifiers |= MOD_CONTROL;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::ALT) {
+            modifiers |= MOD_ALT;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::LOGO) {
+            modifiers |= MOD_WIN;
+        }
+
+        self.unregister(id);
+        self.init_window()?;
+
+        let native_id = HOTKEY_TARGETS.with_borrow_mut(|t| {
+            // apps can only use IDs in the 0x0000..0xBFFF range
+            let native_id = (1..0xC000).find(|i| !t.1.contains_key(i)).unwrap_or(0);
+            if native_id != 0 {
+                t.1.insert(native_id, id);
+            }
+            native_id
+        });
+        if native_id == 0 {
+            return Err(GlobalShortcutError::Other("too many global shortcuts".into()));
+        }
+
+        // SAFETY: the window handle is valid, the window is owned by this thread.
+        if unsafe { RegisterHotKey(self.hwnd, native_id, modifiers, vk) } == 0 {
+            let e = std::io::Error::last_os_error();
+            HOTKEY_TARGETS.with_borrow_mut(|t| t.1.remove(&native_id));
+            return if e.raw_os_error() == Some(windows_sys::Win32::Foundation::ERROR_HOTKEY_ALREADY_REGISTERED as i32) {
+                Err(GlobalShortcutError::AlreadyRegistered)
+            } else {
+                Err(GlobalShortcutError::Other(e.to_string().into()))
+            };
+        }
+
+        self.registered.insert(id, native_id);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, id: GlobalShortcutId) {
+        if let Some(native_id) = self.registered.remove(&id) {
+            // SAFETY: the hotkey was registered in the same window.
+            unsafe { windows_sys::Win32::UI::Input::KeyboardAndMouse::UnregisterHotKey(self.hwnd, native_id) };
+            HOTKEY_TARGETS.with_borrow_mut(|t| t.1.remove(&native_id));
+        }
+    }
+
+    fn init_window(&mut self) -> Result<(), GlobalShortcutError> {
+        use windows_sys::Win32::{System::LibraryLoader::GetModuleHandleW, UI::WindowsAndMessaging::*};
+
+        if self.hwnd != 0 {
+            return Ok(());
+        }
+
+        let class_name: Vec<u16> = "zng-view-global-shortcuts\0".encode_utf16().collect();
+        // SAFETY: null gets the current module.
+        let hinstance = unsafe { GetModuleHandleW(std::ptr::null()) };
+        let class = WNDCLASSW {
+            style: 0,
+            lpfnWndProc: Some(hotkey_wnd_proc),
+            cbClsExtra: 0,
+            cbWndExtra: 0,
+            hInstance: hinstance,
+            hIcon: 0,
+            hCursor: 0,
+            hbrBackground: 0,
+            lpszMenuName: std::ptr::null(),
+            lpszClassName: class_name.as_ptr(),
+        };
+        // SAFETY: class is valid, `RegisterClassW` copies the strings.
+        unsafe { RegisterClassW(&class) };
+
+        // SAFETY: the class was registered, HWND_MESSAGE creates an invisible message-only window.
+        let hwnd = unsafe {
+            CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                std::ptr::null(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                HWND_MESSAGE,
+                0,
+                hinstance,
+                std::ptr::null(),
+            )
+        };
+        if hwnd == 0 {
+            return Err(GlobalShortcutError::Other(std::io::Error::last_os_error().to_string().into()));
+        }
+
+        let sender = self.app_sender.clone();
+        HOTKEY_TARGETS.with_borrow_mut(|t| t.0 = Some(sender));
+        self.hwnd = hwnd;
+        Ok(())
+    }
+}
+#[cfg(windows)]
+thread_local! {
+    /// Sender and map of native ID to app ID, used by the [`hotkey_wnd_proc`].
+    static HOTKEY_TARGETS: std::cell::RefCell<(Option<AppEventSender>, std::collections::HashMap<i32, GlobalShortcutId>)> = Default::default();
+}
+#[cfg(windows)]
+unsafe extern "system" fn hotkey_wnd_proc(
+    hwnd: windows_sys::Win32::Foundation::HWND,
+    msg: u32,
+    wparam: windows_sys::Win32::Foundation::WPARAM,
+    lparam: windows_sys::Win32::Foundation::LPARAM,
+) -> windows_sys::Win32::Foundation::LRESULT {
+    use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+    if msg == WM_HOTKEY {
+        HOTKEY_TARGETS.with_borrow(|(sender, ids)| {
+            if let (Some(sender), Some(id)) = (sender, ids.get(&(wparam as i32))) {
+                let _ = sender.send(crate::AppEvent::Notify(zng_view_api::Event::GlobalShortcut(*id)));
+            }
+        });
+        return 0;
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) struct GlobalShortcuts {
+    app_sender: AppEventSender,
+    /// Connection is opened on the first registration, is `Err` if X11 is not available.
+    connection: Option<Result<std::sync::Arc<x11_shortcuts::Connection>, GlobalShortcutError>>,
+    registered: std::collections::HashMap<GlobalShortcutId, (std::os::raw::c_uint, std::os::raw::c_uint)>,
+}
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl GlobalShortcuts {
+    pub fn new(app_sender: AppEventSender) -> Self {
+        Self {
+            app_sender,
+            connection: None,
+            registered: Default::default(),
+        }
+    }
+
+    pub fn register(&mut self, id: GlobalShortcutId, shortcut: GlobalShortcut) -> Result<(), GlobalShortcutError> {
+        use winit::platform::scancode::PhysicalKeyExtScancode as _;
+        use x11_dl::xlib;
+        use zng_view_api::keyboard::GlobalShortcutModifiers;
+
+        // X11 keycodes are evdev scancodes offset by 8.
+        let keycode = crate::util::key_code_to_winit_key_code(shortcut.key_code)
+            .and_then(|k| k.to_scancode())
+            .ok_or(GlobalShortcutError::NotSupported)?
+            + 8;
+
+        let mut modifiers = 0;
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::SHIFT) {
+            modifiers |= xlib::ShiftMask;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::CTRL) {
+            modifiers |= xlib::ControlMask;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::ALT) {
+            modifiers |= xlib::Mod1Mask;
+        }
+        if shortcut.modifiers.contains(GlobalShortcutModifiers::LOGO) {
+            modifiers |= xlib::Mod4Mask;
+        }
+
+        self.unregister(id);
+
+        let sender = &self.app_sender;
+        let c = self
+            .connection
+            .get_or_insert_with(|| x11_shortcuts::Connection::open(sender.clone()))
+            .clone()?;
+
+        c.grab(id, keycode, modifiers)?;
+        self.registered.insert(id, (keycode, modifiers));
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, id: GlobalShortcutId) {
+        if let Some((keycode, modifiers)) = self.registered.remove(&id) {
+            if let Some(Ok(c)) = &self.connection {
+                c.ungrab(keycode, modifiers);
+            }
+        }
+    }
+}
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl Drop for GlobalShortcuts {
+    fn drop(&mut self) {
+        if let Some(Ok(c)) = &self.connection {
+            c.shutdown();
+        }
+    }
+}
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod x11_shortcuts {
+    use std::{
+        collections::HashMap,
+        os::raw::{c_int, c_uint},
+        sync::{
+            atomic::{AtomicU8, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use x11_dl::xlib;
+    use zng_txt::Txt;
+    use zng_view_api::keyboard::{GlobalShortcutError, GlobalShortcutId};
+
+    use crate::AppEventSender;
+
+    /// Lock keys are part of the key event state, grab all combinations so that the shortcut works with Caps Lock and Num Lock.
+    const LOCK_MASKS: [c_uint; 4] = [0, xlib::LockMask, xlib::Mod2Mask, xlib::LockMask | xlib::Mod2Mask];
+
+    /// Error code of the last failed grab request.
+    static GRAB_ERROR: AtomicU8 = AtomicU8::new(0);
+
+    /// Error handler installed during grab requests.
+    ///
+    /// Xlib error handlers are process wide, the handler is only installed while the grab requests
+    /// are synced, in the main thread.
+    unsafe extern "C" fn grab_error_handler(_: *mut xlib::Display, e: *mut xlib::XErrorEvent) -> c_int {
+        GRAB_ERROR.store((*e).error_code, Ordering::Relaxed);
+        0
+    }
+
+    /// Dedicated X11 connection, events are read in a background thread.
+    pub(super) struct Connection {
+        xlib: xlib::Xlib,
+        display: *mut xlib::Display,
+        root: xlib::Window,
+        /// Hidden window that receives the exit message.
+        exit_window: xlib::Window,
+        exit_atom: xlib::Atom,
+        grabs: Mutex<HashMap<(c_uint, c_uint), GlobalShortcutId>>,
+    }
+    // SAFETY: Xlib is initialized with `XInitThreads` before the display is opened.
+    unsafe impl Send for Connection {}
+    // SAFETY: Xlib is initialized with `XInitThreads` before the display is opened.
+    unsafe impl Sync for Connection {}
+
+    impl Connection {
+        pub fn open(app_sender: AppEventSender) -> Result<Arc<Self>, GlobalShortcutError> {
+            if std::env::var_os("WAYLAND_DISPLAY").is_some_and(|d| !d.is_empty()) {
+                // Wayland does not allow global key grabs
+                return Err(GlobalShortcutError::NotSupported);
+            }
+
+            let xlib = xlib::Xlib::open().map_err(|_| GlobalShortcutError::NotSupported)?;
+            // SAFETY: winit also calls this before opening the display, repeated calls are ignored.
+            unsafe { (xlib.XInitThreads)() };
+            // SAFETY: null opens the default display.
+            let display = unsafe { (xlib.XOpenDisplay)(std::ptr::null()) };
+            if display.is_null() {
+                return Err(GlobalShortcutError::NotSupported);
+            }
+            // SAFETY: display is valid.
+            let root = unsafe { (xlib.XDefaultRootWindow)(display) };
+            // SAFETY: display and root are valid, the window is never mapped.
+            let exit_window = unsafe { (xlib.XCreateSimpleWindow)(display, root, -10, -10, 1, 1, 0, 0, 0) };
+            // SAFETY: display is valid and the name is null terminated.
+            let exit_atom = unsafe { (xlib.XInternAtom)(display, c"ZNG_GLOBAL_SHORTCUTS_EXIT".as_ptr() as _, xlib::False) };
+
+            let c = Arc::new(Self {
+                xlib,
+                display,
+                root,
+                exit_window,
+                exit_atom,
+                grabs: Mutex::default(),
+            });
+
+            let thread_c = c.clone();
+            let r = std::thread::Builder::new()
+                .name("global-shortcuts".into())
+                .stack_size(256 * 1024)
+                .spawn(move || thread_c.run(app_sender));
+            if let Err(e) = r {
+                return Err(GlobalShortcutError::Other(e.to_string().into()));
+            }
+
+            Ok(c)
+        }
+
+        fn run(&self, app_sender: AppEventSender) {
+            loop {
+                // SAFETY: zeroed is a valid XEvent, display is valid.
+                let mut ev = unsafe { std::mem::zeroed::<xlib::XEvent>() };
+                unsafe { (self.xlib.XNextEvent)(self.display, &mut ev) };
+
+                if ev.get_type() == xlib::ClientMessage {
+                    // SAFETY: checked event type.
+                    let msg = unsafe { ev.client_message };
+                    if msg.window == self.exit_window && msg.message_type == self.exit_atom {
+                        break;
+                    }
+                } else if ev.get_type() == xlib::KeyPress {
+                    // SAFETY: checked event type.
+                    let key = unsafe { ev.key };
+                    let state = key.state & (xlib::ShiftMask | xlib::ControlMask | xlib::Mod1Mask | xlib::Mod4Mask);
+                    let id = self.grabs.lock().unwrap().get(&(key.keycode, state)).copied();
+                    if let Some(id) = id {
+                        if app_sender
+                            .send(crate::AppEvent::Notify(zng_view_api::Event::GlobalShortcut(id)))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        pub fn grab(&self, id: GlobalShortcutId, keycode: c_uint, modifiers: c_uint) -> Result<(), GlobalShortcutError> {
+            let mut grabs = self.grabs.lock().unwrap();
+            if grabs.contains_key(&(keycode, modifiers)) {
+                return Err(GlobalShortcutError::AlreadyRegistered);
+            }
+
+            GRAB_ERROR.store(0, Ordering::Relaxed);
+            // SAFETY: display and root are valid, the error handler is restored after the requests are synced.
+            unsafe {
+                let prev_handler = (self.xlib.XSetErrorHandler)(Some(grab_error_handler));
+                for lock in LOCK_MASKS {
+                    (self.xlib.XGrabKey)(
+                        self.display,
+                        keycode as _,
+                        modifiers | lock,
+                        self.root,
+                        xlib::False,
+                        xlib::GrabModeAsync,
+                        xlib::GrabModeAsync,
+                    );
+                }
+                (self.xlib.XSync)(self.display, xlib::False);
+                (self.xlib.XSetErrorHandler)(prev_handler);
+            }
+
+            match GRAB_ERROR.load(Ordering::Relaxed) {
+                0 => {
+                    grabs.insert((keycode, modifiers), id);
+                    Ok(())
+                }
+                code => {
+                    // remove the lock combinations that succeeded
+                    for lock in LOCK_MASKS {
+                        // SAFETY: display and root are valid.
+                        unsafe { (self.xlib.XUngrabKey)(self.display, keycode as _, modifiers | lock, self.root) };
+                    }
+                    // SAFETY: display is valid.
+                    unsafe { (self.xlib.XFlush)(self.display) };
+
+                    if code == xlib::BadAccess {
+                        // another client has grabbed the key
+                        Err(GlobalShortcutError::AlreadyRegistered)
+                    } else {
+                        Err(GlobalShortcutError::Other(Txt::from(format!("XGrabKey error code {code}"))))
+                    }
+                }
+            }
+        }
+
+        /// Stop the events thread, the display is closed when the thread exits.
+        pub fn shutdown(&self) {
+            // SAFETY: zeroed is a valid XEvent.
+            let mut ev = unsafe { std::mem::zeroed::<xlib::XEvent>() };
+            // SAFETY: the event is initialized as a client message to the own window, display is valid.
+            unsafe {
+                let msg = &mut ev.client_message;
+                msg.type_ = xlib::ClientMessage;
+                msg.display = self.display;
+                msg.window = self.exit_window;
+                msg.message_type = self.exit_atom;
+                msg.format = 32;
+                (self.xlib.XSendEvent)(self.display, self.exit_window, xlib::False, xlib::NoEventMask, &mut ev);
+                (self.xlib.XFlush)(self.display);
+            }
+        }
+
+        pub fn ungrab(&self, keycode: c_uint, modifiers: c_uint) {
+            let mut grabs = self.grabs.lock().unwrap();
+            if grabs.remove(&(keycode, modifiers)).is_some() {
+                for lock in LOCK_MASKS {
+                    // SAFETY: display and root are valid.
+                    unsafe { (self.xlib.XUngrabKey)(self.display, keycode as _, modifiers | lock, self.root) };
+                }
+                // SAFETY: display is valid.
+                unsafe { (self.xlib.XFlush)(self.display) };
+            }
+        }
+    }
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            // SAFETY: display and window are valid and owned, the events thread has exited.
+            unsafe {
+                (self.xlib.XDestroyWindow)(self.display, self.exit_window);
+                (self.xlib.XCloseDisplay)(self.display);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) struct GlobalShortcuts {}
+#[cfg(not(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+impl GlobalShortcuts {
+    pub fn new(_: AppEventSender) -> Self {
+        Self {}
+    }
+
+    pub fn register(&mut self, _: GlobalShortcutId, _: GlobalShortcut) -> Result<(), GlobalShortcutError> {
+        Err(GlobalShortcutError::NotSupported)
+    }
+
+    pub fn unregister(&mut self, _: GlobalShortcutId) {}
+}
@@ -0,0 +1,357 @@
+//! GPU canvas extension.
+//!
+//! A GPU canvas is a texture that is rendered by custom code in the view-process and composited by Webrender
+//! like any other image, without any CPU copies of the pixels. This is the integration point for custom 3D content,
+//! the texture is shared in the same OpenGL context used by the renderer.
+//!
+//! Implement [`GpuCanvasRenderer`] and register it using [`ViewExtensions::gpu_canvas`], the app-process
+//! uses the `zng-wgt-gpu-canvas` crate to instantiate a canvas by the registered name.
+//!
+//! ```
+//! use zng_view::{extensions::ViewExtensions, gleam::gl, gpu_canvas::*};
+//!
+//! struct ClearRenderer;
+//! impl GpuCanvasRenderer for ClearRenderer {
+//!     fn render(&mut self, args: &mut GpuCanvasRenderArgs) {
+//!         args.gl.clear_color(0.0, 0.5, 0.0, 1.0);
+//!         args.gl.clear(gl::COLOR_BUFFER_BIT);
+//!     }
+//! }
+//!
+//! fn register(exts: &mut ViewExtensions) {
+//!     exts.gpu_canvas("my-crate.clear-canvas", || ClearRenderer);
+//! }
+//! ```
+//!
+//! The `register` function must be called on view-process init, using the [`view_process_extension!`] macro.
+//!
+//! [`view_process_extension!`]: crate::view_process_extension!
+//!
+//! # Wgpu & Vulkan
+//!
+//! The canvas texture is an OpenGL texture, so renderers that use other APIs need to render in the same
+//! context or use interop. The wgpu GLES backend can wrap the current context and import the [`GpuCanvasRenderArgs::texture`],
+//! Vulkan renderers can share memory with the texture using the `GL_EXT_memory_object` extensions.
+//!
+//! [`ViewExtensions::gpu_canvas`]: crate::extensions::ViewExtensions::gpu_canvas
+
+use std::{any::Any, rc::Rc, sync::Arc};
+
+use gleam::gl;
+use parking_lot::Mutex;
+use webrender::api::{units::TexelRect, ImageDescriptor, ImageDescriptorFlags, ImageFormat};
+use zng_unit::{Factor, Px, PxSize};
+use zng_view_api::{
+    api_extension::{ApiExtensionId, ApiExtensionPayload},
+    image::ImageTextureId,
+};
+
+use crate::extensions::{RedrawArgs, RendererCommandArgs, RendererDeinitedArgs, RendererExtension};
+
+/// Custom renderer of a GPU canvas.
+///
+/// A new renderer is created for each canvas instance. See the [module] level documentation for more details.
+///
+/// [module]: crate::gpu_canvas
+pub trait GpuCanvasRenderer: Any {
+    /// Called after the canvas texture is created.
+    fn init(&mut self, args: &mut GpuCanvasArgs) {
+        let _ = args;
+    }
+
+    /// Called after the canvas texture is resized.
+    ///
+    /// The texture content is undefined after resize, [`render`] is called after this.
+    ///
+    /// [`render`]: Self::render
+    fn resize(&mut self, args: &mut GpuCanvasArgs) {
+        let _ = args;
+    }
+
+    /// Render the canvas content.
+    ///
+    /// This is called during the window redraw, synced with the display refresh rate, after the canvas
+    /// opens or resizes and continuously while [`GpuCanvasRenderArgs::render_again`] is set.
+    fn render(&mut self, args: &mut GpuCanvasRenderArgs);
+
+    /// Called just before the canvas texture is deleted.
+    fn deinit(&mut self, args: &mut GpuCanvasArgs) {
+        let _ = args;
+    }
+}
+
+/// Arguments for [`GpuCanvasRenderer`] init, resize and deinit.
+pub struct GpuCanvasArgs<'a> {
+    /// OpenGL API of the renderer, the context is current.
+    pub gl: &'a Rc<dyn gl::Gl>,
+    /// The canvas texture.
+    pub texture: gl::GLuint,
+    /// Size of the texture.
+    pub size: PxSize,
+}
+
+/// Arguments for [`GpuCanvasRenderer::render`].
+pub struct GpuCanvasRenderArgs<'a> {
+    /// OpenGL API of the renderer, the context is current.
+    pub gl: &'a Rc<dyn gl::Gl>,
+    /// The canvas texture.
+    pub texture: gl::GLuint,
+    /// Framebuffer with the [`texture`] as the color attachment, it is bound and the viewport is set to the full texture.
+    ///
+    /// Note that the texture is presented using the OpenGL convention, the first row is the bottom of the image.
+    ///
+    /// [`texture`]: Self::texture
+    pub framebuffer: gl::GLuint,
+    /// Size of the texture.
+    pub size: PxSize,
+    /// Scale factor of the window or surface.
+    pub scale_factor: Factor,
+
+    /// Request another render in the next redraw, synced with the display refresh rate.
+    ///
+    /// Renderers that animate can keep setting this to `true` to render continuously while the window is visible.
+    pub render_again: bool,
+}
+
+/// Request sent by the app-process.
+#[derive(serde::Deserialize)]
+enum GpuCanvasRequest {
+    Open { id: u64, size: PxSize },
+    Resize { id: u64, size: PxSize },
+    Close { id: u64 },
+}
+
+/// Response sent to the app-process.
+#[derive(serde::Serialize)]
+enum GpuCanvasResponse {
+    Opened(ImageTextureId),
+    Done,
+    NotFound,
+}
+
+type RendererFactory = Arc<Mutex<dyn FnMut() -> Box<dyn GpuCanvasRenderer> + Send>>;
+
+struct Canvas {
+    id: u64,
+    texture: gl::GLuint,
+    framebuffer: gl::GLuint,
+    size: PxSize,
+    image: ImageTextureId,
+    renderer: Box<dyn GpuCanvasRenderer>,
+    render: bool,
+}
+
+/// Renderer extension that manages the canvases of a registered [`GpuCanvasRenderer`].
+pub(crate) struct GpuCanvasExt {
+    id: ApiExtensionId,
+    new_renderer: RendererFactory,
+    canvases: Vec<Canvas>,
+}
+impl GpuCanvasExt {
+    pub(crate) fn new_factory<R: GpuCanvasRenderer>(mut new: impl FnMut() -> R + Send + 'static) -> RendererFactory {
+        Arc::new(Mutex::new(move || Box::new(new()) as Box<dyn GpuCanvasRenderer>))
+    }
+
+    pub(crate) fn new(id: ApiExtensionId, new_renderer: RendererFactory) -> Self {
+        Self {
+            id,
+            new_renderer,
+            canvases: vec![],
+        }
+    }
+
+    fn descriptor(size: PxSize) -> ImageDescriptor {
+        ImageDescriptor::new(size.width.0, size.height.0, ImageFormat::RGBA8, ImageDescriptorFlags::empty())
+    }
+
+    fn uv(size: PxSize) -> TexelRect {
+        // flip Y, the texture is rendered using OpenGL convention.
+        TexelRect::new(0.0, size.height.0 as f32, size.width.0 as f32, 0.0)
+    }
+
+    fn alloc_texture(gl: &Rc<dyn gl::Gl>, texture: gl::GLuint, size: PxSize) {
+        gl.bind_texture(gl::TEXTURE_2D, texture);
+        gl.tex_image_2d(
+            gl::TEXTURE_2D,
+            0,
+            gl::RGBA8 as _,
+            size.width.0,
+            size.height.0,
+            0,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            None,
+        );
+        gl.bind_texture(gl::TEXTURE_2D, 0);
+    }
+}
+impl RendererExtension for GpuCanvasExt {
+    fn is_init_only(&self) -> bool {
+        false
+    }
+
+    fn command(&mut self, args: &mut RendererCommandArgs) -> ApiExtensionPayload {
+        let request = match args.request.deserialize::<GpuCanvasRequest>() {
+            Ok(r) => r,
+            Err(e) => return ApiExtensionPayload::invalid_request(self.id, e),
+        };
+
+        args.context.make_current();
+        let gl = args.context.gl().clone();
+
+        let response = match request {
+            GpuCanvasRequest::Open { id, size } => {
+                let size = size.max(PxSize::splat(Px(1)));
+
+                if let Some(i) = self.canvases.iter().position(|c| c.id == id) {
+                    // reopen
+                    let mut c = self.canvases.swap_remove(i);
+                    c.renderer.deinit(&mut GpuCanvasArgs {
+                        gl: &gl,
+                        texture: c.texture,
+                        size: c.size,
+                    });
+                    args.external_images.unregister_texture_image(args.api, args.document_id, c.image);
+                    gl.delete_framebuffers(&[c.framebuffer]);
+                    gl.delete_textures(&[c.texture]);
+                }
+
+                let texture = gl.gen_textures(1)[0];
+                gl.bind_texture(gl::TEXTURE_2D, texture);
+                gl.tex_parameter_i(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as _);
+                gl.tex_parameter_i(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as _);
+                gl.tex_parameter_i(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as _);
+                gl.tex_parameter_i(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as _);
+                Self::alloc_texture(&gl, texture, size);
+
+                let mut prev_framebuffer = [0];
+                unsafe {
+                    gl.get_integer_v(gl::FRAMEBUFFER_BINDING, &mut prev_framebuffer);
+                }
+                let framebuffer = gl.gen_framebuffers(1)[0];
+                gl.bind_framebuffer(gl::FRAMEBUFFER, framebuffer);
+                gl.framebuffer_texture_2d(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, texture, 0);
+                gl.bind_framebuffer(gl::FRAMEBUFFER, prev_framebuffer[0] as _);
+
+                let image = args.external_images.register_texture_image(
+                    args.api,
+                    args.document_id,
+                    Self::descriptor(size),
+                    Self::uv(size),
+                    texture,
+                );
+
+                let mut renderer = (self.new_renderer.lock())();
+                renderer.init(&mut GpuCanvasArgs { gl: &gl, texture, size });
+
+                self.canvases.push(Canvas {
+                    id,
+                    texture,
+                    framebuffer,
+                    size,
+                    image,
+                    renderer,
+                    render: true,
+                });
+                *args.redraw = true;
+
+                GpuCanvasResponse::Opened(image)
+            }
+            GpuCanvasRequest::Resize { id, size } => {
+                let size = size.max(PxSize::splat(Px(1)));
+                if let Some(c) = self.canvases.iter_mut().find(|c| c.id == id) {
+                    if c.size != size {
+                        c.size = size;
+                        Self::alloc_texture(&gl, c.texture, size);
+                        args.external_images.update_texture_image(
+                            args.api,
+                            args.document_id,
+                            c.image,
+                            Self::descriptor(size),
+                            Self::uv(size),
+                            c.texture,
+                        );
+                        c.renderer.resize(&mut GpuCanvasArgs {
+                            gl: &gl,
+                            texture: c.texture,
+                            size,
+                        });
+                        c.render = true;
+                        *args.redraw = true;
+                    }
+                    GpuCanvasResponse::Done
+                } else {
+                    GpuCanvasResponse::NotFound
+                }
+            }
+            GpuCanvasRequest::Close { id } => {
+                if let Some(i) = self.canvases.iter().position(|c| c.id == id) {
+                    let mut c = self.canvases.swap_remove(i);
+                    c.renderer.deinit(&mut GpuCanvasArgs {
+                        gl: &gl,
+                        texture: c.texture,
+                        size: c.size,
+                    });
+                    args.external_images.unregister_texture_image(args.api, args.document_id, c.image);
+                    gl.delete_framebuffers(&[c.framebuffer]);
+                    gl.delete_textures(&[c.texture]);
+                    GpuCanvasResponse::Done
+                } else {
+                    GpuCanvasResponse::NotFound
+                }
+            }
+        };
+
+        ApiExtensionPayload::serialize(&response).unwrap()
+    }
+
+    fn redraw(&mut self, args: &mut RedrawArgs) {
+        if !self.canvases.iter().any(|c| c.render) {
+            return;
+        }
+
+        let gl = args.context.gl().clone();
+
+        let mut prev_framebuffer = [0];
+        let mut prev_viewport = [0; 4];
+        unsafe {
+            gl.get_integer_v(gl::FRAMEBUFFER_BINDING, &mut prev_framebuffer);
+            gl.get_integer_v(gl::VIEWPORT, &mut prev_viewport);
+        }
+
+        for c in self.canvases.iter_mut().filter(|c| c.render) {
+            gl.bind_framebuffer(gl::FRAMEBUFFER, c.framebuffer);
+            gl.viewport(0, 0, c.size.width.0, c.size.height.0);
+
+            let mut r_args = GpuCanvasRenderArgs {
+                gl: &gl,
+                texture: c.texture,
+                framebuffer: c.framebuffer,
+                size: c.size,
+                scale_factor: args.scale_factor,
+                render_again: false,
+            };
+            c.renderer.render(&mut r_args);
+            c.render = r_args.render_again;
+        }
+
+        gl.bind_framebuffer(gl::FRAMEBUFFER, prev_framebuffer[0] as _);
+        gl.viewport(prev_viewport[0], prev_viewport[1], prev_viewport[2], prev_viewport[3]);
+
+        // the renderer already redraw, redraw again to present the new content.
+        args.redraw_again = true;
+    }
+
+    fn renderer_deinited(&mut self, args: &mut RendererDeinitedArgs) {
+        let gl = args.context.gl().clone();
+        for mut c in self.canvases.drain(..) {
+            c.renderer.deinit(&mut GpuCanvasArgs {
+                gl: &gl,
+                texture: c.texture,
+                size: c.size,
+            });
+            gl.delete_framebuffers(&[c.framebuffer]);
+            gl.delete_textures(&[c.texture]);
+        }
+    }
+}
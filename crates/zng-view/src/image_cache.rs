@@ -1,4 +1,8 @@
-use std::{fmt, sync::Arc};
+use std::{
+    fmt,
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
 
 use webrender::api::{ImageDescriptor, ImageDescriptorFlags, ImageFormat};
 use winit::{
@@ -8,12 +12,17 @@ use winit::{
 use zng_txt::{formatx, ToTxt, Txt};
 use zng_unit::{Px, PxPoint, PxSize};
 use zng_view_api::{
-    image::{ImageDataFormat, ImageDownscale, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi, ImageRequest},
+    image::{
+        ColorSpace, ImageDataFormat, ImageDownscale, ImageEncodeOptions, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi, ImageRequest,
+    },
     ipc::{IpcBytes, IpcBytesReceiver},
     Event,
 };
 
-use crate::{AppEvent, AppEventSender};
+use crate::{
+    extensions::{ImageDecodeArgs, ImageDecoderExtension},
+    AppEvent, AppEventSender,
+};
 use rustc_hash::FxHashMap;
 
 #[allow(unexpected_cfgs)]
@@ -55,21 +64,176 @@ pub(crate) const DECODERS: &[&str] = &[
     "dds",
 ];
 
+/// Encoded data of an animated image, frames are decoded on demand.
+struct ImageAnimation {
+    data: IpcBytes,
+    format: image::ImageFormat,
+    downscale: Option<ImageDownscale>,
+    mask: Option<ImageMaskMode>,
+    /// Frame requests for the decoder thread, `(index, frame_id)`.
+    decoder: Option<mpsc::Sender<(usize, ImageId)>>,
+}
+impl ImageAnimation {
+    fn new(data: IpcBytes, format: image::ImageFormat, downscale: Option<ImageDownscale>, mask: Option<ImageMaskMode>) -> Self {
+        Self {
+            data,
+            format,
+            downscale,
+            mask,
+            decoder: None,
+        }
+    }
+}
+
+/// Implements `AsRef<[u8]>` for the decoders `Cursor`.
+struct AnimationData(IpcBytes);
+impl AsRef<[u8]> for AnimationData {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+/// Encoded data of a vector image, rasterized on demand.
+struct VectorImage {
+    data: IpcBytes,
+    format: Txt,
+    decoder: Arc<dyn ImageDecoderExtension>,
+    max_decoded_len: u64,
+    mask: Option<ImageMaskMode>,
+}
+
+/// Image decoders registered by view extensions.
+struct CustomDecoders {
+    decoders: Vec<Arc<dyn ImageDecoderExtension>>,
+    vectors: Mutex<FxHashMap<ImageId, VectorImage>>,
+}
+impl CustomDecoders {
+    /// Find a decoder for the declared format.
+    fn find(&self, fmt: &ImageDataFormat) -> Option<(Arc<dyn ImageDecoderExtension>, Txt)> {
+        if self.decoders.is_empty() {
+            return None;
+        }
+        let name = match fmt {
+            ImageDataFormat::FileExtension(ext) => Txt::from(ext.to_lowercase()),
+            ImageDataFormat::MimeType(t) => Txt::from(t.strip_prefix("image/")?.to_lowercase()),
+            _ => return None,
+        };
+        let d = self.decoders.iter().find(|d| d.formats().contains(&name))?;
+        Some((d.clone(), name))
+    }
+
+    /// Find a decoder that matches the data of unknown format.
+    fn find_match(&self, data: &[u8]) -> Option<(Arc<dyn ImageDecoderExtension>, Txt)> {
+        let d = self.decoders.iter().find(|d| d.matches(data))?;
+        Some((d.clone(), Txt::from_static("")))
+    }
+
+    /// Decode and register vector images for rasterization on demand. Returns the image and if it is vector.
+    #[allow(clippy::too_many_arguments)]
+    fn decode(
+        &self,
+        id: ImageId,
+        decoder: Arc<dyn ImageDecoderExtension>,
+        format: Txt,
+        data: IpcBytes,
+        max_decoded_len: u64,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+    ) -> Result<(RawLoadedImg, bool), Txt> {
+        let is_vector = decoder.is_vector();
+
+        let (mut img, mut ppi) = Self::decode_raw(&*decoder, &data, &format, None, max_decoded_len)?;
+        if let Some(d) = downscale {
+            let size = PxSize::new(Px(img.width() as i32), Px(img.height() as i32));
+            let fit_size = d.resize_dimensions(size);
+            if is_vector && matches!(d, ImageDownscale::Fit(_)) && fit_size.width < size.width {
+                (img, ppi) = Self::decode_raw(&*decoder, &data, &format, Some(fit_size), max_decoded_len)?;
+            } else {
+                img = ImageCache::image_downscale(img, d);
+            }
+        }
+
+        if is_vector {
+            self.vectors.lock().unwrap().insert(
+                id,
+                VectorImage {
+                    data,
+                    format,
+                    decoder,
+                    max_decoded_len,
+                    mask,
+                },
+            );
+        }
+
+        let mut r = ImageCache::convert_decoded(img, mask);
+        r.2 = ppi;
+        Ok((r, is_vector))
+    }
+
+    fn decode_raw(
+        decoder: &dyn ImageDecoderExtension,
+        data: &[u8],
+        format: &Txt,
+        size: Option<PxSize>,
+        max_decoded_len: u64,
+    ) -> Result<(image::DynamicImage, Option<ImagePpi>), Txt> {
+        fn check_len(size: PxSize, max_decoded_len: u64) -> Result<(), Txt> {
+            let decoded_len = size.width.0.max(0) as u64 * size.height.0.max(0) as u64 * 4;
+            if decoded_len > max_decoded_len {
+                Err(formatx!(
+                    "image {size:?} needs to allocate {decoded_len} bytes, but max allowed size is {max_decoded_len} bytes",
+                ))
+            } else {
+                Ok(())
+            }
+        }
+
+        if let Some(s) = size {
+            check_len(s, max_decoded_len)?;
+        }
+        let img = decoder.decode(ImageDecodeArgs { data, format, size })?;
+        check_len(img.size, max_decoded_len)?;
+
+        let expected_len = img.size.width.0.max(0) as usize * img.size.height.0.max(0) as usize * 4;
+        if img.pixels.len() != expected_len {
+            return Err(formatx!(
+                "decoded pixels.len() is not width * height * 4, expected {expected_len}, found {}",
+                img.pixels.len()
+            ));
+        }
+        let buf = image::RgbaImage::from_raw(img.size.width.0 as u32, img.size.height.0 as u32, img.pixels).unwrap();
+        Ok((image::DynamicImage::ImageRgba8(buf), img.ppi))
+    }
+}
+
 /// Decode and cache image resources.
 pub(crate) struct ImageCache {
     app_sender: AppEventSender,
     images: FxHashMap<ImageId, Image>,
+    animations: Arc<Mutex<FxHashMap<ImageId, ImageAnimation>>>,
+    custom: Arc<CustomDecoders>,
     image_id_gen: ImageId,
 }
 impl ImageCache {
-    pub fn new(app_sender: AppEventSender) -> Self {
+    pub fn new(app_sender: AppEventSender, decoders: Vec<Arc<dyn ImageDecoderExtension>>) -> Self {
         Self {
             app_sender,
             images: FxHashMap::default(),
+            animations: Arc::default(),
+            custom: Arc::new(CustomDecoders {
+                decoders,
+                vectors: Mutex::default(),
+            }),
             image_id_gen: ImageId::first(),
         }
     }
 
+    /// Formats supported by custom decoders.
+    pub fn custom_formats(&self) -> Vec<Txt> {
+        self.custom.decoders.iter().flat_map(|d| d.formats()).collect()
+    }
+
     pub fn add(
         &mut self,
         ImageRequest {
@@ -83,73 +247,107 @@ impl ImageCache {
         let id = self.image_id_gen.incr();
 
         let app_sender = self.app_sender.clone();
+        let animations = self.animations.clone();
+        let custom = self.custom.clone();
         rayon::spawn(move || {
-            let r = match format {
-                ImageDataFormat::Bgra8 { size, ppi } => {
-                    let expected_len = size.width.0 as usize * size.height.0 as usize * 4;
-                    if data.len() != expected_len {
-                        Err(formatx!(
-                            "pixels.len() is not width * height * 4, expected {expected_len}, found {}",
-                            data.len()
-                        ))
-                    } else if mask.is_some() {
-                        let (pixels, size, _, is_opaque, _) = Self::convert_decoded(
-                            image::DynamicImage::ImageLuma8(
-                                image::ImageBuffer::from_raw(size.width.0 as _, size.height.0 as _, data.to_vec()).unwrap(),
-                            ),
-                            mask,
-                        );
-                        Ok((pixels, size, ppi, is_opaque, true))
-                    } else {
-                        let is_opaque = data.chunks_exact(4).all(|c| c[3] == 255);
-                        Ok((data, size, ppi, is_opaque, false))
-                    }
-                }
-                ImageDataFormat::A8 { size } => {
-                    let expected_len = size.width.0 as usize * size.height.0 as usize;
-                    if data.len() != expected_len {
-                        Err(formatx!(
-                            "pixels.len() is not width * height, expected {expected_len}, found {}",
-                            data.len()
-                        ))
-                    } else if mask.is_none() {
-                        let (pixels, size, _, is_opaque, _) = Self::convert_decoded(
-                            image::DynamicImage::ImageLuma8(
-                                image::ImageBuffer::from_raw(size.width.0 as _, size.height.0 as _, data.to_vec()).unwrap(),
-                            ),
-                            None,
-                        );
-                        Ok((pixels, size, None, is_opaque, false))
-                    } else {
-                        let is_opaque = data.iter().all(|&c| c == 255);
-                        Ok((data, size, None, is_opaque, true))
+            let mut color_space = ColorSpace::Unknown;
+            let mut frame_delays = vec![];
+            let mut is_vector = false;
+            let r = if let Some((decoder, name)) = custom.find(&format) {
+                custom
+                    .decode(id, decoder, name, data, max_decoded_len, downscale, mask)
+                    .map(|(r, v)| {
+                        is_vector = v;
+                        r
+                    })
+            } else {
+                match format {
+                    ImageDataFormat::Bgra8 { size, ppi } => {
+                        let expected_len = size.width.0 as usize * size.height.0 as usize * 4;
+                        if data.len() != expected_len {
+                            Err(formatx!(
+                                "pixels.len() is not width * height * 4, expected {expected_len}, found {}",
+                                data.len()
+                            ))
+                        } else if mask.is_some() {
+                            let (pixels, size, _, is_opaque, _) = Self::convert_decoded(
+                                image::DynamicImage::ImageLuma8(
+                                    image::ImageBuffer::from_raw(size.width.0 as _, size.height.0 as _, data.to_vec()).unwrap(),
+                                ),
+                                mask,
+                            );
+                            Ok((pixels, size, ppi, is_opaque, true))
+                        } else {
+                            let is_opaque = data.chunks_exact(4).all(|c| c[3] == 255);
+                            Ok((data, size, ppi, is_opaque, false))
+                        }
                     }
-                }
-                fmt => match Self::get_format_and_size(&fmt, &data[..]) {
-                    Ok((fmt, mut size)) => {
-                        let decoded_len = size.width.0 as u64 * size.height.0 as u64 * 4;
-                        if decoded_len > max_decoded_len {
+                    ImageDataFormat::A8 { size } => {
+                        let expected_len = size.width.0 as usize * size.height.0 as usize;
+                        if data.len() != expected_len {
                             Err(formatx!(
-                                "image {size:?} needs to allocate {decoded_len} bytes, but max allowed size is {max_decoded_len} bytes",
+                                "pixels.len() is not width * height, expected {expected_len}, found {}",
+                                data.len()
                             ))
+                        } else if mask.is_none() {
+                            let (pixels, size, _, is_opaque, _) = Self::convert_decoded(
+                                image::DynamicImage::ImageLuma8(
+                                    image::ImageBuffer::from_raw(size.width.0 as _, size.height.0 as _, data.to_vec()).unwrap(),
+                                ),
+                                None,
+                            );
+                            Ok((pixels, size, None, is_opaque, false))
                         } else {
-                            if let Some(d) = downscale {
-                                size = d.resize_dimensions(size);
-                            }
-                            let _ = app_sender.send(AppEvent::Notify(Event::ImageMetadataLoaded {
-                                image: id,
-                                size,
-                                ppi: None,
-                                is_mask: false,
-                            }));
-                            match Self::image_decode(&data[..], fmt, downscale) {
-                                Ok(img) => Ok(Self::convert_decoded(img, mask)),
-                                Err(e) => Err(e.to_txt()),
-                            }
+                            let is_opaque = data.iter().all(|&c| c == 255);
+                            Ok((data, size, None, is_opaque, true))
                         }
                     }
-                    Err(e) => Err(e),
-                },
+                    fmt => match Self::get_format_and_size(&fmt, &data[..]) {
+                        Ok((fmt, mut size)) => {
+                            let decoded_len = size.width.0 as u64 * size.height.0 as u64 * 4;
+                            if decoded_len > max_decoded_len {
+                                Err(formatx!(
+                                    "image {size:?} needs to allocate {decoded_len} bytes, but max allowed size is {max_decoded_len} bytes",
+                                ))
+                            } else {
+                                if let Some(d) = downscale {
+                                    size = d.resize_dimensions(size);
+                                }
+                                let _ = app_sender.send(AppEvent::Notify(Event::ImageMetadataLoaded {
+                                    image: id,
+                                    size,
+                                    ppi: None,
+                                    is_mask: false,
+                                }));
+                                match Self::image_decode(&data[..], fmt, downscale) {
+                                    Ok((img, cs)) => {
+                                        color_space = cs;
+                                        frame_delays = Self::animation_delays(&data, fmt);
+                                        if !frame_delays.is_empty() {
+                                            animations
+                                                .lock()
+                                                .unwrap()
+                                                .insert(id, ImageAnimation::new(data.clone(), fmt, downscale, mask));
+                                        }
+                                        Ok(Self::convert_decoded(img, mask))
+                                    }
+                                    Err(e) => Err(e.to_txt()),
+                                }
+                            }
+                        }
+                        Err(e) => match custom.find_match(&data[..]) {
+                            Some((decoder, name)) => {
+                                custom
+                                    .decode(id, decoder, name, data, max_decoded_len, downscale, mask)
+                                    .map(|(r, v)| {
+                                        is_vector = v;
+                                        r
+                                    })
+                            }
+                            None => Err(e),
+                        },
+                    },
+                }
             };
 
             match r {
@@ -161,6 +359,9 @@ impl ImageCache {
                         ppi,
                         is_opaque,
                         is_mask,
+                        color_space,
+                        frame_delays,
+                        is_vector,
                     }));
                 }
                 Err(e) => {
@@ -184,12 +385,15 @@ impl ImageCache {
     ) -> ImageId {
         let id = self.image_id_gen.incr();
         let app_sender = self.app_sender.clone();
+        let animations = self.animations.clone();
+        let custom = self.custom.clone();
         rayon::spawn(move || {
             // crate `images` does not do progressive decode.
             let mut full = vec![];
             let mut size = None;
             let mut ppi = None;
             let mut is_encoded = true;
+            let mut custom_decoder = custom.find(&format);
 
             let mut format = match format {
                 ImageDataFormat::Bgra8 { size: s, ppi: p } => {
@@ -208,6 +412,9 @@ impl ImageCache {
                 ImageDataFormat::Unknown => None,
             };
 
+            // length of `full` in the last partial decode attempt.
+            let mut partial_len = 0;
+
             let mut pending = true;
             while pending {
                 match data.recv() {
@@ -231,6 +438,27 @@ impl ImageCache {
                                         let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: id, error }));
                                         return;
                                     }
+
+                                    let _ = app_sender.send(AppEvent::Notify(Event::ImageMetadataLoaded {
+                                        image: id,
+                                        size: downscale.map(|d| d.resize_dimensions(s)).unwrap_or(s),
+                                        ppi: None,
+                                        is_mask: false,
+                                    }));
+                                }
+                            }
+
+                            // partial decode is a full decode of the available bytes, so only retry after
+                            // the data grows by half, to limit the number of decodes of large images.
+                            if pending
+                                && size.is_some()
+                                && custom_decoder.is_none()
+                                && full.len() - partial_len >= PARTIAL_DECODE_MIN_LEN
+                                && full.len() >= partial_len + partial_len / 2
+                            {
+                                partial_len = full.len();
+                                if let Some(partial) = Self::image_decode_partial(&full, fmt, downscale, mask) {
+                                    let _ = app_sender.send(AppEvent::ImagePartiallyLoaded(ImagePartiallyLoadedData { id, ..partial }));
                                 }
                             }
                         } else if is_encoded {
@@ -244,9 +472,40 @@ impl ImageCache {
                 }
             }
 
-            if let Some(fmt) = format {
+            if custom_decoder.is_none() && format.is_none() && is_encoded {
+                custom_decoder = custom.find_match(&full);
+            }
+            if let Some((decoder, name)) = custom_decoder {
+                match custom.decode(id, decoder, name, IpcBytes::from_vec(full), max_decoded_len, downscale, mask) {
+                    Ok(((pixels, size, ppi, is_opaque, is_mask), is_vector)) => {
+                        let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
+                            id,
+                            pixels,
+                            size,
+                            ppi,
+                            is_opaque,
+                            is_mask,
+                            color_space: ColorSpace::Unknown,
+                            frame_delays: vec![],
+                            is_vector,
+                        }));
+                    }
+                    Err(error) => {
+                        let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: id, error }));
+                    }
+                }
+            } else if let Some(fmt) = format {
                 match Self::image_decode(&full[..], fmt, downscale) {
-                    Ok(img) => {
+                    Ok((img, color_space)) => {
+                        let full = IpcBytes::from_vec(full);
+                        let frame_delays = Self::animation_delays(&full, fmt);
+                        if !frame_delays.is_empty() {
+                            animations
+                                .lock()
+                                .unwrap()
+                                .insert(id, ImageAnimation::new(full, fmt, downscale, mask));
+                        }
+
                         let (pixels, size, ppi, is_opaque, is_mask) = Self::convert_decoded(img, mask);
                         let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
                             id,
@@ -255,6 +514,9 @@ impl ImageCache {
                             ppi,
                             is_opaque,
                             is_mask,
+                            color_space,
+                            frame_delays,
+                            is_vector: false,
                         }));
                     }
                     Err(e) => {
@@ -274,6 +536,9 @@ impl ImageCache {
                     ppi,
                     is_opaque,
                     is_mask: false,
+                    color_space: ColorSpace::Unknown,
+                    frame_delays: vec![],
+                    is_vector: false,
                 }));
             } else {
                 let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError {
@@ -285,8 +550,336 @@ impl ImageCache {
         id
     }
 
+    /// Decode the pixels available in the incomplete `buf`.
+    ///
+    /// Only interlaced and non-interlaced PNG and baseline and progressive JPEG are decoded, the missing rows of
+    /// non-interlaced images are transparent.
+    fn image_decode_partial(
+        buf: &[u8],
+        format: image::ImageFormat,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+    ) -> Option<ImagePartiallyLoadedData> {
+        let (mut image, decoded_height) = match format {
+            image::ImageFormat::Png => Self::png_decode_partial(buf)?,
+            // JPEG decoder fills the missing data
+            image::ImageFormat::Jpeg => {
+                let image = image::load_from_memory_with_format(buf, format).ok()?;
+                let h = image.height();
+                (image, h)
+            }
+            _ => return None,
+        };
+
+        let full_height = image.height();
+        if let Some(s) = downscale {
+            image = Self::image_downscale(image, s);
+        }
+        let (pixels, size, _, is_opaque, is_mask) = Self::convert_decoded(image, mask);
+        let partial_height = (decoded_height as u64 * size.height.0 as u64 / full_height.max(1) as u64) as i32;
+
+        Some(ImagePartiallyLoadedData {
+            id: ImageId::INVALID,
+            size,
+            partial_size: PxSize::new(size.width, Px(partial_height)),
+            is_opaque,
+            is_mask,
+            pixels,
+        })
+    }
+
+    /// Decode the available rows, returns the image with the full size and the number of decoded rows.
+    ///
+    /// Interlaced images decode all the rows in the first pass, *splatting* the pixels to fill the missing pixels.
+    fn png_decode_partial(buf: &[u8]) -> Option<(image::DynamicImage, u32)> {
+        let mut decoder = png::Decoder::new(std::io::Cursor::new(buf));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().ok()?;
+        let (color, _) = reader.output_color_type();
+        let (width, height) = {
+            let info = reader.info();
+            (info.width, info.height)
+        };
+        let samples = color.samples();
+        let stride = width as usize * samples;
+        let mut pixels = vec![0; stride * height as usize];
+
+        let mut decoded_height = 0;
+        let mut null_line = 0;
+        while let Ok(Some(row)) = reader.next_interlaced_row() {
+            match row.interlace() {
+                png::InterlaceInfo::Adam7(info) => {
+                    png::splat_interlaced_row(&mut pixels, stride, row.data(), info, (samples * 8) as u8);
+                    decoded_height = height;
+                }
+                png::InterlaceInfo::Null(_) => {
+                    let start = null_line * stride;
+                    pixels[start..start + stride].copy_from_slice(row.data());
+                    null_line += 1;
+                    decoded_height = null_line as u32;
+                }
+            }
+        }
+        if decoded_height == 0 {
+            return None;
+        }
+
+        use image::DynamicImage::*;
+        let image = match color {
+            png::ColorType::Grayscale => ImageLuma8(image::ImageBuffer::from_raw(width, height, pixels)?),
+            png::ColorType::GrayscaleAlpha => ImageLumaA8(image::ImageBuffer::from_raw(width, height, pixels)?),
+            png::ColorType::Rgb => ImageRgb8(image::ImageBuffer::from_raw(width, height, pixels)?),
+            png::ColorType::Rgba => ImageRgba8(image::ImageBuffer::from_raw(width, height, pixels)?),
+            // `normalize_to_color8` expands indexed colors.
+            png::ColorType::Indexed => return None,
+        };
+
+        if decoded_height < height && !matches!(color, png::ColorType::Rgba | png::ColorType::GrayscaleAlpha) {
+            // missing rows must be transparent
+            let mut image = image.into_rgba8();
+            for p in image.rows_mut().skip(decoded_height as usize).flatten() {
+                p.0 = [0; 4];
+            }
+            Some((ImageRgba8(image), decoded_height))
+        } else {
+            Some((image, decoded_height))
+        }
+    }
+
     pub fn forget(&mut self, id: ImageId) {
         self.images.remove(&id);
+        self.animations.lock().unwrap().remove(&id);
+        self.custom.vectors.lock().unwrap().remove(&id);
+    }
+
+    /// Rasterize the vector image `id` at a new `size`.
+    pub fn raster(&mut self, id: ImageId, size: PxSize) -> ImageId {
+        let raster_id = self.image_id_gen.incr();
+
+        let vectors = self.custom.vectors.lock().unwrap();
+        let v = match vectors.get(&id) {
+            Some(v) => v,
+            None => {
+                let error = formatx!("image `{id:?}` is not vector");
+                let _ = self
+                    .app_sender
+                    .send(AppEvent::Notify(Event::ImageLoadError { image: raster_id, error }));
+                return raster_id;
+            }
+        };
+
+        let data = v.data.clone();
+        let format = v.format.clone();
+        let decoder = v.decoder.clone();
+        let (max_decoded_len, mask) = (v.max_decoded_len, v.mask);
+        let app_sender = self.app_sender.clone();
+        rayon::spawn(
+            move || match CustomDecoders::decode_raw(&*decoder, &data, &format, Some(size), max_decoded_len) {
+                Ok((img, ppi)) => {
+                    let (pixels, size, _, is_opaque, is_mask) = Self::convert_decoded(img, mask);
+                    let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
+                        id: raster_id,
+                        pixels,
+                        size,
+                        ppi,
+                        is_opaque,
+                        is_mask,
+                        color_space: ColorSpace::Unknown,
+                        frame_delays: vec![],
+                        is_vector: false,
+                    }));
+                }
+                Err(error) => {
+                    let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: raster_id, error }));
+                }
+            },
+        );
+
+        raster_id
+    }
+
+    /// Decode the animation frame `index` of the image `id`.
+    pub fn frame(&mut self, id: ImageId, index: usize) -> ImageId {
+        let frame_id = self.image_id_gen.incr();
+
+        let mut animations = self.animations.lock().unwrap();
+        let a = match animations.get_mut(&id) {
+            Some(a) => a,
+            None => {
+                let error = formatx!("image `{id:?}` is not animated");
+                let _ = self
+                    .app_sender
+                    .send(AppEvent::Notify(Event::ImageLoadError { image: frame_id, error }));
+                return frame_id;
+            }
+        };
+
+        if let Some(s) = &a.decoder {
+            if s.send((index, frame_id)).is_ok() {
+                return frame_id;
+            }
+        }
+
+        // start decoder thread, it exits when the animation is forgotten.
+        let (sender, receiver) = mpsc::channel();
+        sender.send((index, frame_id)).unwrap();
+        a.decoder = Some(sender);
+
+        let data = a.data.clone();
+        let (format, downscale, mask) = (a.format, a.downscale, a.mask);
+        let app_sender = self.app_sender.clone();
+        let r = std::thread::Builder::new()
+            .name("image-animation".into())
+            .spawn(move || Self::decode_frames(receiver, data, format, downscale, mask, app_sender));
+        if let Err(e) = r {
+            tracing::error!("cannot spawn animation decoder thread, {e}");
+            a.decoder = None;
+            let _ = self.app_sender.send(AppEvent::Notify(Event::ImageLoadError {
+                image: frame_id,
+                error: e.to_txt(),
+            }));
+        }
+
+        frame_id
+    }
+
+    fn decode_frames(
+        requests: mpsc::Receiver<(usize, ImageId)>,
+        data: IpcBytes,
+        format: image::ImageFormat,
+        downscale: Option<ImageDownscale>,
+        mask: Option<ImageMaskMode>,
+        app_sender: AppEventSender,
+    ) {
+        use image::ImageDecoder as _;
+
+        // frames from `AnimationDecoder` do not apply the color profile.
+        let icc_profile = image::ImageReader::with_format(std::io::Cursor::new(AnimationData(data.clone())), format)
+            .into_decoder()
+            .ok()
+            .and_then(|mut d| d.icc_profile().ok().flatten());
+
+        let mut frames = None;
+        let mut next = 0;
+        while let Ok((index, frame_id)) = requests.recv() {
+            if index < next {
+                frames = None;
+            }
+            if frames.is_none() {
+                next = 0;
+                match Self::animation_frames(data.clone(), format) {
+                    Ok(Some(f)) => frames = Some(f),
+                    Ok(None) => {
+                        let error = Txt::from_static("image is not animated");
+                        let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: frame_id, error }));
+                        continue;
+                    }
+                    Err(e) => {
+                        let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError {
+                            image: frame_id,
+                            error: e.to_txt(),
+                        }));
+                        continue;
+                    }
+                }
+            }
+
+            let mut frame = Err(formatx!("frame {index} is out of bounds"));
+            if let Some(f) = &mut frames {
+                while next <= index {
+                    match f.next() {
+                        Some(Ok(f)) => {
+                            next += 1;
+                            if next > index {
+                                frame = Ok(f);
+                            }
+                        }
+                        Some(Err(e)) => {
+                            frame = Err(e.to_txt());
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+            }
+
+            match frame {
+                Ok(f) => {
+                    let mut img = image::DynamicImage::ImageRgba8(f.into_buffer());
+                    if let Some(icc) = &icc_profile {
+                        img = crate::color_profile::convert_to_srgb(img, icc).0;
+                    }
+                    if let Some(s) = downscale {
+                        img = Self::image_downscale(img, s);
+                    }
+                    let (pixels, size, ppi, is_opaque, is_mask) = Self::convert_decoded(img, mask);
+                    let _ = app_sender.send(AppEvent::ImageLoaded(ImageLoadedData {
+                        id: frame_id,
+                        pixels,
+                        size,
+                        ppi,
+                        is_opaque,
+                        is_mask,
+                        color_space: ColorSpace::Srgb,
+                        frame_delays: vec![],
+                        is_vector: false,
+                    }));
+                }
+                Err(error) => {
+                    // restart on the next request.
+                    frames = None;
+                    let _ = app_sender.send(AppEvent::Notify(Event::ImageLoadError { image: frame_id, error }));
+                }
+            }
+        }
+    }
+
+    /// Gets the delay of each frame if the image is animated.
+    fn animation_delays(data: &IpcBytes, format: image::ImageFormat) -> Vec<Duration> {
+        let mut r = vec![];
+        if let Ok(Some(frames)) = Self::animation_frames(data.clone(), format) {
+            for f in frames {
+                match f {
+                    Ok(f) => r.push(f.delay().into()),
+                    Err(e) => {
+                        tracing::error!("error decoding animation frames, {e}");
+                        break;
+                    }
+                }
+            }
+        }
+        if r.len() < 2 {
+            r.clear();
+        }
+        r
+    }
+
+    /// Gets the animation frames decoder, if the format supports animation and the image is animated.
+    fn animation_frames(data: IpcBytes, format: image::ImageFormat) -> image::ImageResult<Option<image::Frames<'static>>> {
+        use image::AnimationDecoder as _;
+
+        let data = std::io::Cursor::new(AnimationData(data));
+        let frames = match format {
+            image::ImageFormat::Gif => Some(image::codecs::gif::GifDecoder::new(data)?.into_frames()),
+            image::ImageFormat::Png => {
+                let d = image::codecs::png::PngDecoder::new(data)?;
+                if d.is_apng()? {
+                    Some(d.apng()?.into_frames())
+                } else {
+                    None
+                }
+            }
+            image::ImageFormat::WebP => {
+                let d = image::codecs::webp::WebPDecoder::new(data)?;
+                if d.has_animation() {
+                    Some(d.into_frames())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        Ok(frames)
     }
 
     pub fn get(&self, id: ImageId) -> Option<&Image> {
@@ -318,6 +911,46 @@ impl ImageCache {
         let _ = self.app_sender.send(AppEvent::Notify(Event::ImageLoaded(data)));
     }
 
+    /// Called after a progressive decode has decoded more pixels.
+    pub(crate) fn partially_loaded(&mut self, data: ImagePartiallyLoadedData) {
+        let mut flags = ImageDescriptorFlags::empty();
+        if data.is_opaque {
+            flags |= ImageDescriptorFlags::IS_OPAQUE
+        }
+
+        // the renderer uses the full size image, the missing rows are transparent.
+        self.images.insert(
+            data.id,
+            Image(Arc::new(ImageData::RawData {
+                size: data.size,
+                pixels: data.pixels.clone(),
+                descriptor: ImageDescriptor::new(
+                    data.size.width.0,
+                    data.size.height.0,
+                    if data.is_mask { ImageFormat::R8 } else { ImageFormat::BGRA8 },
+                    flags,
+                ),
+                ppi: None,
+            })),
+        );
+
+        let partial_pixels = if data.partial_size == data.size {
+            data.pixels
+        } else {
+            let pixel_len = if data.is_mask { 1 } else { 4 };
+            let len = data.partial_size.width.0 as usize * data.partial_size.height.0 as usize * pixel_len;
+            IpcBytes::from_slice(&data.pixels[..len])
+        };
+        let _ = self.app_sender.send(AppEvent::Notify(Event::ImagePartiallyLoaded {
+            image: data.id,
+            partial_size: data.partial_size,
+            ppi: None,
+            is_opaque: data.is_opaque,
+            is_mask: data.is_mask,
+            partial_pixels,
+        }));
+    }
+
     fn get_format_and_size(fmt: &ImageDataFormat, data: &[u8]) -> Result<(image::ImageFormat, PxSize), Txt> {
         let fmt = match fmt {
             ImageDataFormat::FileExtension(ext) => image::ImageFormat::from_extension(ext.as_str()),
@@ -343,35 +976,54 @@ impl ImageCache {
         }
     }
 
-    fn image_decode(buf: &[u8], format: image::ImageFormat, downscale: Option<ImageDownscale>) -> image::ImageResult<image::DynamicImage> {
+    /// Decode and convert to sRGB, returns the image and the source color space.
+    fn image_decode(
+        buf: &[u8],
+        format: image::ImageFormat,
+        downscale: Option<ImageDownscale>,
+    ) -> image::ImageResult<(image::DynamicImage, ColorSpace)> {
+        use image::ImageDecoder as _;
+
         let buf = std::io::Cursor::new(buf);
 
         let mut reader = image::io::Reader::new(buf);
         reader.set_format(format);
         reader.no_limits();
-        let mut image = reader.decode()?;
+        let mut decoder = reader.into_decoder()?;
+        let icc_profile = decoder.icc_profile().ok().flatten();
+        let mut image = image::DynamicImage::from_decoder(decoder)?;
+
+        let mut color_space = ColorSpace::Unknown;
+        if let Some(icc) = icc_profile {
+            (image, color_space) = crate::color_profile::convert_to_srgb(image, &icc);
+        }
 
         if let Some(s) = downscale {
-            let (img_w, img_h) = (image.width(), image.height());
-            match s {
-                ImageDownscale::Fit(s) => {
-                    let w = img_w.min(s.width.0 as u32);
-                    let h = img_h.min(s.height.0 as u32);
-                    if w != img_w || h != img_h {
-                        image = image.resize(w, h, image::imageops::FilterType::Triangle);
-                    }
+            image = Self::image_downscale(image, s);
+        }
+
+        Ok((image, color_space))
+    }
+
+    fn image_downscale(mut image: image::DynamicImage, s: ImageDownscale) -> image::DynamicImage {
+        let (img_w, img_h) = (image.width(), image.height());
+        match s {
+            ImageDownscale::Fit(s) => {
+                let w = img_w.min(s.width.0 as u32);
+                let h = img_h.min(s.height.0 as u32);
+                if w != img_w || h != img_h {
+                    image = image.resize(w, h, image::imageops::FilterType::Triangle);
                 }
-                ImageDownscale::Fill(s) => {
-                    let w = img_w.min(s.width.0 as u32);
-                    let h = img_h.min(s.height.0 as u32);
-                    if w != img_w && h != img_h {
-                        image = image.resize_to_fill(w, h, image::imageops::FilterType::Triangle);
-                    }
+            }
+            ImageDownscale::Fill(s) => {
+                let w = img_w.min(s.width.0 as u32);
+                let h = img_h.min(s.height.0 as u32);
+                if w != img_w && h != img_h {
+                    image = image.resize_to_fill(w, h, image::imageops::FilterType::Triangle);
                 }
             }
         }
-
-        Ok(image)
+        image
     }
 
     fn convert_decoded(image: image::DynamicImage, mask: Option<ImageMaskMode>) -> RawLoadedImg {
@@ -805,12 +1457,15 @@ impl ImageCache {
         )
     }
 
-    pub fn encode(&self, id: ImageId, format: Txt) {
+    pub fn encode(&self, id: ImageId, format: Txt, options: ImageEncodeOptions) {
         if !ENCODERS.contains(&format.as_str()) {
             let error = formatx!("cannot encode `{id:?}` to `{format}`, unknown format");
-            let _ = self
-                .app_sender
-                .send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+            let _ = self.app_sender.send(AppEvent::Notify(Event::ImageEncodeError {
+                image: id,
+                format,
+                options,
+                error,
+            }));
             return;
         }
 
@@ -822,25 +1477,34 @@ impl ImageCache {
             let sender = self.app_sender.clone();
             rayon::spawn(move || {
                 let mut data = vec![];
-                match img.encode(fmt, &mut data) {
+                match img.encode(fmt, options, &mut data) {
                     Ok(_) => {
                         let _ = sender.send(AppEvent::Notify(Event::ImageEncoded {
                             image: id,
                             format,
+                            options,
                             data: IpcBytes::from_vec(data),
                         }));
                     }
                     Err(e) => {
                         let error = formatx!("failed to encode `{id:?}` to `{format}`, {e}");
-                        let _ = sender.send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+                        let _ = sender.send(AppEvent::Notify(Event::ImageEncodeError {
+                            image: id,
+                            format,
+                            options,
+                            error,
+                        }));
                     }
                 }
             })
         } else {
             let error = formatx!("cannot encode `{id:?}` to `{format}`, image not found");
-            let _ = self
-                .app_sender
-                .send(AppEvent::Notify(Event::ImageEncodeError { image: id, format, error }));
+            let _ = self.app_sender.send(AppEvent::Notify(Event::ImageEncodeError {
+                image: id,
+                format,
+                options,
+                error,
+            }));
         }
     }
 
@@ -851,6 +1515,23 @@ impl ImageCache {
 
 /// (pixels, size, ppi, is_opaque, is_mask)
 type RawLoadedImg = (IpcBytes, PxSize, Option<ImagePpi>, bool, bool);
+
+/// Minimal number of new bytes received before a new partial decode is attempted.
+const PARTIAL_DECODE_MIN_LEN: usize = 16 * 1024;
+
+/// Pixels decoded so far by a progressive decode.
+#[derive(Debug)]
+pub(crate) struct ImagePartiallyLoadedData {
+    id: ImageId,
+    /// Full image size.
+    size: PxSize,
+    /// Size of the decoded rows.
+    partial_size: PxSize,
+    is_opaque: bool,
+    is_mask: bool,
+    /// Full size pixels, the missing rows are transparent.
+    pixels: IpcBytes,
+}
 pub(crate) enum ImageData {
     RawData {
         size: PxSize,
@@ -977,7 +1658,8 @@ impl Image {
         }
     }
 
-    pub fn encode(&self, format: image::ImageFormat, buffer: &mut Vec<u8>) -> image::ImageResult<()> {
+    #[allow(unexpected_cfgs)]
+    pub fn encode(&self, format: image::ImageFormat, options: ImageEncodeOptions, buffer: &mut Vec<u8>) -> image::ImageResult<()> {
         let (size, pixels, ppi) = match &*self.0 {
             ImageData::RawData { size, pixels, ppi, .. } => (size, pixels, ppi),
             ImageData::NativeTexture { .. } => unreachable!(),
@@ -992,52 +1674,62 @@ impl Image {
 
         use image::*;
 
-        if self.0.is_mask() {
-            let width = size.width.0 as u32;
-            let height = size.height.0 as u32;
-            let is_opaque = self.0.is_opaque();
+        let width = size.width.0 as u32;
+        let height = size.height.0 as u32;
+        let is_opaque = self.0.is_opaque();
+
+        let mut img = if self.0.is_mask() {
             let r8 = pixels[..].to_vec();
+            image::DynamicImage::ImageLuma8(image::ImageBuffer::from_raw(width, height, r8).unwrap())
+        } else {
+            let mut buf = pixels[..].to_vec();
+            // BGRA to RGBA
+            buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
+            image::DynamicImage::ImageRgba8(image::ImageBuffer::from_raw(width, height, buf).unwrap())
+        };
+        if is_opaque {
+            img = image::DynamicImage::ImageRgb8(img.to_rgb8());
+        }
 
-            let mut img = image::DynamicImage::ImageLuma8(image::ImageBuffer::from_raw(width, height, r8).unwrap());
-            if is_opaque {
-                img = image::DynamicImage::ImageRgb8(img.to_rgb8());
+        fn set_icc(encoder: &mut impl ImageEncoder, options: &ImageEncodeOptions) {
+            if options.icc {
+                if let Err(e) = encoder.set_icc_profile(crate::color_profile::srgb_icc_profile()) {
+                    tracing::debug!("cannot embed ICC profile, {e}");
+                }
             }
-            img.write_to(&mut std::io::Cursor::new(buffer), format)?;
-
-            return Ok(());
         }
 
-        // invert rows, `image` only supports top-to-bottom buffers.
-        let mut buf = pixels[..].to_vec();
-        // BGRA to RGBA
-        buf.chunks_exact_mut(4).for_each(|c| c.swap(0, 2));
-        let rgba = buf;
-
-        let width = size.width.0 as u32;
-        let height = size.height.0 as u32;
-        let is_opaque = self.0.is_opaque();
-
         match format {
             ImageFormat::Jpeg => {
-                let mut jpg = codecs::jpeg::JpegEncoder::new(buffer);
+                if options.lossless {
+                    return Err(ImageError::Unsupported(error::UnsupportedError::from_format_and_kind(
+                        ImageFormat::Jpeg.into(),
+                        error::UnsupportedErrorKind::GenericFeature("lossless".to_owned()),
+                    )));
+                }
+                let mut jpg = codecs::jpeg::JpegEncoder::new_with_quality(buffer, options.quality.unwrap_or(75).clamp(1, 100));
                 if let Some(ppi) = ppi {
                     jpg.set_pixel_density(codecs::jpeg::PixelDensity {
                         density: (ppi.x as u16, ppi.y as u16),
                         unit: codecs::jpeg::PixelDensityUnit::Inches,
                     });
                 }
-                jpg.encode(&rgba, width, height, ColorType::Rgba8.into())?;
+                set_icc(&mut jpg, &options);
+                jpg.write_image(img.as_bytes(), width, height, img.color().into())?;
             }
             ImageFormat::Png => {
-                let mut img = image::DynamicImage::ImageRgba8(image::ImageBuffer::from_raw(width, height, rgba).unwrap());
-                if is_opaque {
-                    img = image::DynamicImage::ImageRgb8(img.to_rgb8());
-                }
-                if let Some(ppi) = ppi {
-                    let mut png_bytes = vec![];
-
-                    img.write_to(&mut std::io::Cursor::new(&mut png_bytes), ImageFormat::Png)?;
+                let compression = match options.quality {
+                    None => codecs::png::CompressionType::default(),
+                    Some(q) if q < 34 => codecs::png::CompressionType::Fast,
+                    Some(q) if q < 67 => codecs::png::CompressionType::Default,
+                    Some(_) => codecs::png::CompressionType::Best,
+                };
+                let mut png_bytes = vec![];
+                let mut png = codecs::png::PngEncoder::new_with_quality(&mut png_bytes, compression, codecs::png::FilterType::default());
+                set_icc(&mut png, &options);
+                png.write_image(img.as_bytes(), width, height, img.color().into())?;
 
+                if let Some(ppi) = ppi {
                     let mut png = img_parts::png::Png::from_bytes(png_bytes.into()).unwrap();
 
                     let chunk_kind = *b"pHYs";
@@ -1059,16 +1751,24 @@ impl Image {
 
                     png.encoder().write_to(buffer)?;
                 } else {
-                    img.write_to(&mut std::io::Cursor::new(buffer), ImageFormat::Png)?;
+                    *buffer = png_bytes;
                 }
             }
+            ImageFormat::WebP => {
+                // only lossless is implemented by the `image` crate.
+                let mut webp = codecs::webp::WebPEncoder::new_lossless(buffer);
+                set_icc(&mut webp, &options);
+                webp.write_image(img.as_bytes(), width, height, img.color().into())?;
+            }
+            #[cfg(feature = "avif")]
+            ImageFormat::Avif => {
+                let quality = if options.lossless { 100 } else { options.quality.unwrap_or(80) };
+                let mut avif = codecs::avif::AvifEncoder::new_with_speed_quality(buffer, 4, quality);
+                set_icc(&mut avif, &options);
+                avif.write_image(img.as_bytes(), width, height, img.color().into())?;
+            }
             _ => {
-                // other formats that we don't with custom PPI meta.
-
-                let mut img = image::DynamicImage::ImageRgba8(image::ImageBuffer::from_raw(width, height, rgba).unwrap());
-                if is_opaque {
-                    img = image::DynamicImage::ImageRgb8(img.to_rgb8());
-                }
+                // other formats that we don't with custom PPI meta or options.
                 img.write_to(&mut std::io::Cursor::new(buffer), format)?;
             }
         }
@@ -1240,7 +1940,7 @@ mod capture {
     use zng_txt::formatx;
     use zng_unit::{Factor, PxRect};
     use zng_view_api::{
-        image::{ImageDataFormat, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi, ImageRequest},
+        image::{ColorSpace, ImageDataFormat, ImageId, ImageLoadedData, ImageMaskMode, ImagePpi, ImageRequest},
         ipc::IpcBytes,
         window::{FrameId, WindowId},
         Event,
@@ -1329,6 +2029,26 @@ mod capture {
             data
         }
 
+        /// Read the frame pixels for a capture request in the FrameRequest, the returned closure
+        /// converts the pixels to image data and can run in another thread.
+        ///
+        /// The image is not inserted in the cache, send the data as an [`AppEvent::ImageLoaded`] to insert.
+        pub fn frame_image_data_deferred(
+            &mut self,
+            gl: &dyn gleam::gl::Gl,
+            rect: PxRect,
+            scale_factor: Factor,
+            mask: Option<ImageMaskMode>,
+        ) -> impl FnOnce() -> ImageLoadedData + Send + 'static {
+            let id = self.image_id_gen.incr();
+            let pixels_flipped = Self::read_frame_pixels(gl, rect);
+            move || {
+                let mut data = Self::convert_frame_pixels(pixels_flipped, rect, scale_factor, mask);
+                data.id = id;
+                data
+            }
+        }
+
         fn frame_image_data_impl(
             &mut self,
             gl: &dyn gleam::gl::Gl,
@@ -1336,14 +2056,45 @@ mod capture {
             scale_factor: Factor,
             mask: Option<ImageMaskMode>,
         ) -> ImageLoadedData {
-            let pixels_flipped = gl.read_pixels(
+            let pixels_flipped = Self::read_frame_pixels(gl, rect);
+            let mut data = Self::convert_frame_pixels(pixels_flipped, rect, scale_factor, mask);
+
+            data.id = self.add(ImageRequest {
+                format: if data.is_mask {
+                    ImageDataFormat::A8 { size: data.size }
+                } else {
+                    ImageDataFormat::Bgra8 {
+                        size: data.size,
+                        ppi: data.ppi,
+                    }
+                },
+                data: data.pixels.clone(),
+                max_decoded_len: u64::MAX,
+                downscale: None,
+                mask,
+            });
+
+            data
+        }
+
+        fn read_frame_pixels(gl: &dyn gleam::gl::Gl, rect: PxRect) -> Vec<u8> {
+            gl.read_pixels(
                 rect.origin.x.0,
                 rect.origin.y.0,
                 rect.size.width.0,
                 rect.size.height.0,
                 gleam::gl::BGRA,
                 gleam::gl::UNSIGNED_BYTE,
-            );
+            )
+        }
+
+        /// Convert the pixels read from the frame buffer, the returned data has an invalid ID.
+        fn convert_frame_pixels(
+            pixels_flipped: Vec<u8>,
+            rect: PxRect,
+            scale_factor: Factor,
+            mask: Option<ImageMaskMode>,
+        ) -> ImageLoadedData {
             let mut buf = vec![0u8; pixels_flipped.len()];
             assert_eq!(rect.size.width.0 as usize * rect.size.height.0 as usize * 4, buf.len());
             let stride = 4 * rect.size.width.0 as usize;
@@ -1362,44 +2113,31 @@ mod capture {
                     Some(mask),
                 );
 
-                let id = self.add(ImageRequest {
-                    format: ImageDataFormat::A8 { size },
-                    data: pixels.clone(),
-                    max_decoded_len: u64::MAX,
-                    downscale: None,
-                    mask: Some(mask),
-                });
-
                 ImageLoadedData {
-                    id,
+                    id: ImageId::INVALID,
                     size,
                     ppi,
                     is_opaque,
                     is_mask,
+                    color_space: ColorSpace::Srgb,
+                    frame_delays: vec![],
+                    is_vector: false,
                     pixels,
                 }
             } else {
                 let is_opaque = buf.chunks_exact(4).all(|bgra| bgra[3] == 255);
 
-                let data = IpcBytes::from_vec(buf);
                 let ppi = 96.0 * scale_factor.0;
-                let ppi = Some(ImagePpi::splat(ppi));
-                let size = rect.size;
-
-                let id = self.add(ImageRequest {
-                    format: ImageDataFormat::Bgra8 { size, ppi },
-                    data: data.clone(),
-                    max_decoded_len: u64::MAX,
-                    downscale: None,
-                    mask,
-                });
 
                 ImageLoadedData {
-                    id,
-                    size,
-                    ppi,
+                    id: ImageId::INVALID,
+                    size: rect.size,
+                    ppi: Some(ImagePpi::splat(ppi)),
                     is_opaque,
-                    pixels: data,
+                    color_space: ColorSpace::Srgb,
+                    frame_delays: vec![],
+                    is_vector: false,
+                    pixels: IpcBytes::from_vec(buf),
                     is_mask: false,
                 }
             }
@@ -78,7 +78,9 @@
 //! * `"zng-view.webrender_debug"`: `{ flags: DebugFlags, profiler_ui: String }`, sets Webrender debug flags.
 //!     - The `zng-wgt-webrender-debug` implements a property that uses this extension.
 //!
-//! You can also inject your own extensions, see the [`extensions`] module for more details.
+//! You can also inject your own extensions, see the [`extensions`] module for more details. Custom GPU rendered
+//! content can be implemented using the [`gpu_canvas`] extension point and native controls can be embedded
+//! using the [`native_child`] extension point.
 //!
 //! [`zng-view-prebuilt`]: https://crates.io/crates/zng-view-prebuilt/
 //!
@@ -108,17 +110,27 @@ use winit::{
     platform::modifier_supplement::KeyEventExtModifierSupplement,
 };
 
+mod app_menu;
+mod color_profile;
 mod config;
 mod display_list;
+mod drag_drop;
+mod gamepad;
 mod gl;
+mod global_shortcut;
 mod image_cache;
+mod notification;
 mod px_wr;
+mod sound;
 mod surface;
+mod tray;
 mod util;
 mod window;
 use surface::*;
 
 pub mod extensions;
+pub mod gpu_canvas;
+pub mod native_child;
 
 /// Webrender build used in the view-process.
 #[doc(no_inline)]
@@ -128,23 +140,28 @@ pub use webrender;
 #[doc(no_inline)]
 pub use gleam;
 
+/// Raw window handle types used by native child hosts.
+#[doc(no_inline)]
+pub use raw_window_handle;
+
 use webrender::api::*;
 use window::Window;
 use zng_txt::Txt;
-use zng_unit::{Dip, DipPoint, DipRect, DipSize, Factor, Px, PxPoint, PxRect, PxToDip};
+use zng_unit::{AngleDegree, Dip, DipPoint, DipRect, DipSize, DipVector, Factor, Px, PxPoint, PxRect, PxSize, PxToDip};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     dialog::{DialogId, FileDialog, MsgDialog, MsgDialogResponse},
+    drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
-    image::{ImageId, ImageLoadedData, ImageMaskMode, ImageRequest, ImageTextureId},
+    image::{ColorSpace, ImageEncodeOptions, ImageId, ImageLoadedData, ImageMaskMode, ImageRequest, ImageTextureId},
     ipc::{IpcBytes, IpcBytesReceiver},
     keyboard::{Key, KeyCode, KeyState},
     mouse::ButtonId,
     touch::{TouchId, TouchUpdate},
     window::{
         CursorIcon, CursorImage, EventCause, EventFrameRendered, FocusIndicator, FrameRequest, FrameUpdateRequest, FrameWaitId,
-        HeadlessOpenData, HeadlessRequest, MonitorId, MonitorInfo, VideoMode, WindowChanged, WindowId, WindowOpenData, WindowRequest,
-        WindowState, WindowStateAll,
+        HeadlessOpenData, HeadlessRequest, MonitorId, MonitorInfo, RendererStats, RendererTrim, VideoMode, WindowChanged, WindowId,
+        WindowLayer, WindowOpenData, WindowRequest, WindowState, WindowStateAll,
     },
     Inited, *,
 };
@@ -152,8 +169,12 @@ use zng_view_api::{
 use rustc_hash::FxHashMap;
 
 #[cfg(feature = "ipc")]
-zng_env::on_process_start!(|_| {
+zng_env::on_process_start!(|args| {
     if std::env::var("ZNG_VIEW_NO_INIT_START").is_err() {
+        if args.yield_count == 0 && args.next_handlers_count > 0 && ViewConfig::from_env().is_some() {
+            // let other handlers like the crash handler setup the view-process first.
+            return args.yield_once();
+        }
         view_process_main();
     }
 });
@@ -310,20 +331,30 @@ pub(crate) struct App {
     response_sender: ipc::ResponseSender,
     event_sender: ipc::EventSender,
     image_cache: ImageCache,
+    sound: sound::SoundPlayer,
+    global_shortcuts: global_shortcut::GlobalShortcuts,
+    tray_icons: tray::TrayIcons,
+    notifications: notification::Notifications,
+    app_menus: app_menu::AppMenus,
 
     gen: ViewProcessGen,
     device_events: bool,
 
     windows: Vec<Window>,
     surfaces: Vec<Surface>,
+    surface_pool: SurfacePool,
 
     monitor_id_gen: MonitorId,
     pub monitors: Vec<(MonitorId, MonitorHandle)>,
+    known_monitors: Vec<util::KnownMonitor>,
 
     device_id_gen: DeviceId,
     devices: Vec<(DeviceId, winit::event::DeviceId)>,
+    gamepads: gamepad::Gamepads,
 
     dialog_id_gen: DialogId,
+    drag_drop_id_gen: DragDropId,
+    drag_sources: Vec<(DragDropId, drag_drop::DragSource)>,
 
     resize_frame_wait_id_gen: FrameWaitId,
 
@@ -416,7 +447,15 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
         }
 
         match event {
-            WindowEvent::RedrawRequested => self.windows[i].redraw(),
+            WindowEvent::RedrawRequested => {
+                self.windows[i].redraw();
+                if let Some(ev) = self.windows[i].take_presented() {
+                    self.notify(Event::FramePresented(ev));
+                }
+                if self.windows[i].is_continuous_render() {
+                    self.notify(Event::RedrawTick(id));
+                }
+            }
             WindowEvent::Resized(_) => {
                 let size = if let Some(size) = self.windows[i].resized() {
                     size
@@ -577,6 +616,7 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
             WindowEvent::Focused(mut focused) => {
                 if self.windows[i].focused_changed(&mut focused) {
                     if focused {
+                        self.app_menus.focused(id);
                         self.notify(Event::FocusChanged { prev: None, new: Some(id) });
                     } else {
                         self.pending_modifiers_focus_clear = true;
@@ -700,13 +740,24 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                 device_id, state, button, ..
             } => {
                 linux_modal_dialog_bail!();
-                let d_id = self.device_id(device_id);
-                self.notify(Event::MouseInput {
-                    window: id,
-                    device: d_id,
-                    state: util::element_state_to_button_state(state),
-                    button: util::winit_mouse_button_to_zng(button),
-                });
+                // the press and release of a region drag are handled by the system, like on Windows.
+                #[cfg(not(windows))]
+                let region_drag = button == winit::event::MouseButton::Left
+                    && match state {
+                        winit::event::ElementState::Pressed => self.windows[i].hit_test_pressed(),
+                        winit::event::ElementState::Released => self.windows[i].hit_test_released(),
+                    };
+                #[cfg(windows)]
+                let region_drag = false;
+                if !region_drag {
+                    let d_id = self.device_id(device_id);
+                    self.notify(Event::MouseInput {
+                        window: id,
+                        device: d_id,
+                        state: util::element_state_to_button_state(state),
+                        button: util::winit_mouse_button_to_zng(button),
+                    });
+                }
             }
             WindowEvent::TouchpadPressure {
                 device_id,
@@ -774,6 +825,9 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
                 } else {
                     MonitorId::INVALID
                 };
+                if let Some(m) = self.known_monitors.iter_mut().find(|m| m.id == monitor) {
+                    m.scale_factor = scale_factor;
+                }
 
                 if is_monitor_change {
                     self.notify(Event::WindowChanged(WindowChanged::monitor_changed(
@@ -818,10 +872,37 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
             WindowEvent::ThemeChanged(_) => {}
             WindowEvent::Occluded(_) => {}
             WindowEvent::ActivationTokenDone { .. } => {}
-            WindowEvent::PinchGesture { .. } => {}
-            WindowEvent::RotationGesture { .. } => {}
+            WindowEvent::PinchGesture { device_id, delta, phase } => {
+                linux_modal_dialog_bail!();
+                let d_id = self.device_id(device_id);
+                self.notify(Event::PinchGesture {
+                    window: id,
+                    device: d_id,
+                    delta: delta as f32,
+                    phase: util::winit_touch_phase_to_zng(phase),
+                });
+            }
+            WindowEvent::RotationGesture { device_id, delta, phase } => {
+                linux_modal_dialog_bail!();
+                let d_id = self.device_id(device_id);
+                self.notify(Event::RotationGesture {
+                    window: id,
+                    device: d_id,
+                    delta: AngleDegree(delta),
+                    phase: util::winit_touch_phase_to_zng(phase),
+                });
+            }
+            WindowEvent::PanGesture { device_id, delta, phase } => {
+                linux_modal_dialog_bail!();
+                let d_id = self.device_id(device_id);
+                self.notify(Event::PanGesture {
+                    window: id,
+                    device: d_id,
+                    delta: DipVector::new(Dip::new_f32(delta.x / scale_factor.0), Dip::new_f32(delta.y / scale_factor.0)),
+                    phase: util::winit_touch_phase_to_zng(phase),
+                });
+            }
             WindowEvent::DoubleTapGesture { .. } => {}
-            WindowEvent::PanGesture { .. } => {}
         }
 
         winit_loop_guard.unset(&mut self.winit_loop);
@@ -834,22 +915,21 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
     fn user_event(&mut self, winit_loop: &ActiveEventLoop, ev: AppEvent) {
         let mut winit_loop_guard = self.winit_loop.set(winit_loop);
         match ev {
-            AppEvent::Request => {
-                while let Ok(req) = self.request_recv.try_recv() {
-                    match req {
-                        RequestEvent::Request(req) => {
-                            let rsp = self.respond(req);
-                            if rsp.must_be_send() && self.response_sender.send(rsp).is_err() {
-                                // lost connection to app-process
-                                self.exited = true;
-                                self.winit_loop.exit();
-                            }
-                        }
-                        RequestEvent::FrameReady(wid, msg) => self.on_frame_ready(wid, msg),
-                    }
+            AppEvent::Request => self.process_requests(),
+            AppEvent::Notify(ev) => self.notify(ev),
+            #[cfg(windows)]
+            AppEvent::RunDragDrop(drag_id) => {
+                if let Some(pending) = self
+                    .drag_sources
+                    .iter_mut()
+                    .find(|(id, _)| *id == drag_id)
+                    .and_then(|(_, s)| s.take_pending())
+                {
+                    // system drag loop, continues processing requests so that the app-process is not blocked
+                    let ev = pending.run(|| self.process_requests());
+                    self.notify(ev);
                 }
             }
-            AppEvent::Notify(ev) => self.notify(ev),
             AppEvent::WinitFocused(window_id, focused) => self.window_event(winit_loop, window_id, WindowEvent::Focused(focused)),
             AppEvent::RefreshMonitors => self.refresh_monitors(),
             AppEvent::ParentProcessExited => {
@@ -859,15 +939,27 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
             AppEvent::ImageLoaded(data) => {
                 self.image_cache.loaded(data);
             }
+            AppEvent::ImagePartiallyLoaded(data) => {
+                self.image_cache.partially_loaded(data);
+            }
             AppEvent::MonitorPowerChanged => {
                 // if a window opens in power-off it is blank until redraw.
+                let mut presented = vec![];
                 for w in &mut self.windows {
                     w.redraw();
+                    presented.extend(w.take_presented());
+                }
+                for ev in presented {
+                    self.notify(Event::FramePresented(ev));
                 }
             }
             AppEvent::InitDeviceEvents(enabled) => {
                 self.init_device_events(enabled, Some(winit_loop));
             }
+            AppEvent::Gamepad(ev) => {
+                let ev = self.gamepads.on_event(&mut self.device_id_gen, ev);
+                self.notify(ev);
+            }
         }
         winit_loop_guard.unset(&mut self.winit_loop);
     }
@@ -947,6 +1039,7 @@ impl winit::application::ApplicationHandler<AppEvent> for App {
         for s in &mut self.surfaces {
             s.on_low_memory();
         }
+        self.surface_pool.clear();
         self.exts.on_low_memory();
         self.notify(Event::LowMemory);
 
@@ -1040,11 +1133,12 @@ impl App {
                                             }
                                         }
                                         RequestEvent::FrameReady(id, msg) => {
-                                            let r = if let Some(s) = self.app.surfaces.iter_mut().find(|s| s.id() == id) {
-                                                Some(s.on_frame_ready(msg, &mut self.app.image_cache))
-                                            } else {
-                                                None
-                                            };
+                                            let r = self
+                                                .app
+                                                .surfaces
+                                                .iter_mut()
+                                                .find(|s| s.id() == id)
+                                                .and_then(|s| s.on_frame_ready(msg, &mut self.app.image_cache));
                                             if let Some((frame_id, image)) = r {
                                                 self.app.notify(Event::FrameRendered(EventFrameRendered {
                                                     window: id,
@@ -1075,10 +1169,16 @@ impl App {
                             AppEvent::ImageLoaded(data) => {
                                 self.app.image_cache.loaded(data);
                             }
+                            AppEvent::ImagePartiallyLoaded(data) => {
+                                self.app.image_cache.partially_loaded(data);
+                            }
                             AppEvent::MonitorPowerChanged => {} // headless
                             AppEvent::InitDeviceEvents(enabled) => {
                                 self.app.init_device_events(enabled, None);
                             }
+                            AppEvent::Gamepad(_) => {} // headless
+                            #[cfg(windows)]
+                            AppEvent::RunDragDrop(_) => {} // headless
                         },
                         Err(_) => {
                             self.app.exited = true;
@@ -1136,6 +1236,7 @@ impl App {
     ) -> Self {
         ext.renderer("zng-view.webrender_debug", extensions::RendererDebugExt::new);
         ext.init(&app_sender);
+        let image_decoders = ext.image_decoders();
         let mut idle = IdleTrace(None);
         idle.enter();
         App {
@@ -1144,7 +1245,12 @@ impl App {
             exts: ext,
             idle,
             gl_manager: GlContextManager::default(),
-            image_cache: ImageCache::new(app_sender.clone()),
+            image_cache: ImageCache::new(app_sender.clone(), image_decoders),
+            sound: sound::SoundPlayer::default(),
+            global_shortcuts: global_shortcut::GlobalShortcuts::new(app_sender.clone()),
+            tray_icons: tray::TrayIcons::new(app_sender.clone()),
+            notifications: notification::Notifications::new(app_sender.clone()),
+            app_menus: app_menu::AppMenus::new(app_sender.clone()),
             app_sender,
             request_recv,
             response_sender,
@@ -1154,11 +1260,16 @@ impl App {
             device_events: false,
             windows: vec![],
             surfaces: vec![],
+            surface_pool: SurfacePool::default(),
             monitors: vec![],
+            known_monitors: vec![],
             monitor_id_gen: MonitorId::INVALID,
             devices: vec![],
             device_id_gen: DeviceId::INVALID,
+            gamepads: gamepad::Gamepads::default(),
             dialog_id_gen: DialogId::INVALID,
+            drag_drop_id_gen: DragDropId::INVALID,
+            drag_sources: vec![],
             resize_frame_wait_id_gen: FrameWaitId::INVALID,
             coalescing_event: None,
             cursor_entered_expect_move: Vec::with_capacity(1),
@@ -1279,23 +1390,48 @@ impl App {
     }
 
     fn refresh_monitors(&mut self) {
-        let mut monitors = Vec::with_capacity(self.monitors.len());
+        let fresh: Vec<_> = self.winit_loop.available_monitors().collect();
 
         let mut changed = false;
+        self.monitors.retain(|(_, h)| {
+            let retain = fresh.contains(h);
+            changed |= !retain;
+            retain
+        });
 
-        for (fresh_handle, (id, handle)) in self.winit_loop.available_monitors().zip(&self.monitors) {
-            let id = if &fresh_handle == handle {
-                *id
+        let mut scale_changed = vec![];
+        for handle in fresh {
+            if let Some((id, _)) = self.monitors.iter().find(|(_, h)| h == &handle) {
+                let id = *id;
+                let scale_factor = handle.scale_factor();
+                if let Some(m) = self.known_monitors.iter_mut().find(|m| m.id == id) {
+                    if m.scale_factor != scale_factor {
+                        m.scale_factor = scale_factor;
+                        scale_changed.push((id, handle, scale_factor));
+                    }
+                }
             } else {
                 changed = true;
-                self.monitor_id_gen.incr()
-            };
-            monitors.push((id, fresh_handle))
+                let id = self.new_monitor_id(&handle);
+                self.monitors.push((id, handle));
+            }
         }
 
-        if changed {
-            self.monitors = monitors;
+        for (monitor, handle, scale_factor) in scale_changed {
+            let windows = self
+                .windows
+                .iter()
+                .filter(|w| w.monitor().as_ref() == Some(&handle))
+                .map(|w| w.id())
+                .collect();
+            self.notify(Event::ScaleFactorChanged {
+                monitor,
+                windows,
+                scale_factor: scale_factor as f32,
+            });
+        }
 
+        if changed {
             let monitors = self.available_monitors();
             self.notify(Event::MonitorsChanged(monitors));
         }
@@ -1312,23 +1448,47 @@ impl App {
                 frame: r.frame_id,
                 frame_image: r.image,
             }));
+            if let Some(ev) = w.take_presented() {
+                let _ = self.event_sender.send(Event::FramePresented(ev));
+            }
 
             if r.first_frame {
                 let size = w.size();
                 self.notify(Event::WindowChanged(WindowChanged::resized(window_id, size, EventCause::App, None)));
             }
         } else if let Some(s) = self.surfaces.iter_mut().find(|w| w.id() == window_id) {
-            let (frame_id, image) = s.on_frame_ready(msg, &mut self.image_cache);
+            if let Some((frame_id, image)) = s.on_frame_ready(msg, &mut self.image_cache) {
+                self.notify(Event::FrameRendered(EventFrameRendered {
+                    window: window_id,
+                    frame: frame_id,
+                    frame_image: image,
+                }))
+            }
+        }
+    }
 
-            self.notify(Event::FrameRendered(EventFrameRendered {
-                window: window_id,
-                frame: frame_id,
-                frame_image: image,
-            }))
+    /// Respond all pending requests.
+    fn process_requests(&mut self) {
+        while let Ok(req) = self.request_recv.try_recv() {
+            match req {
+                RequestEvent::Request(req) => {
+                    let rsp = self.respond(req);
+                    if rsp.must_be_send() && self.response_sender.send(rsp).is_err() {
+                        // lost connection to app-process
+                        self.exited = true;
+                        self.winit_loop.exit();
+                    }
+                }
+                RequestEvent::FrameReady(wid, msg) => self.on_frame_ready(wid, msg),
+            }
         }
     }
 
     pub(crate) fn notify(&mut self, event: Event) {
+        if let Event::AppDragEnded { drag, .. } = &event {
+            self.drag_sources.retain(|(id, _)| id != drag);
+        }
+
         let now = Instant::now();
         if let Some((mut coal, timestamp)) = self.coalescing_event.take() {
             let r = if now.saturating_duration_since(timestamp) >= Duration::from_millis(16) {
@@ -1426,12 +1586,33 @@ impl App {
         if let Some((id, _)) = self.monitors.iter().find(|(_, h)| h == handle) {
             *id
         } else {
-            let id = self.monitor_id_gen.incr();
+            let id = self.new_monitor_id(handle);
             self.monitors.push((id, handle.clone()));
             id
         }
     }
 
+    /// Gets an ID for a monitor handle that is not in `self.monitors`.
+    ///
+    /// Reuses the ID of a disconnected monitor with the same hardware identifier, or the same name and position
+    /// if the identifier is not available, so that monitor IDs are stable when the monitor is unplugged and plugged back in.
+    fn new_monitor_id(&mut self, handle: &MonitorHandle) -> MonitorId {
+        let serial = util::monitor_serial(handle);
+        let monitors = &self.monitors;
+        if let Some(m) = self
+            .known_monitors
+            .iter_mut()
+            .find(|m| !monitors.iter().any(|(id, _)| *id == m.id) && m.matches(handle, &serial))
+        {
+            m.update(handle);
+            return m.id;
+        }
+
+        let id = self.monitor_id_gen.incr();
+        self.known_monitors.push(util::KnownMonitor::new(id, handle, serial));
+        id
+    }
+
     fn device_id(&mut self, device_id: winit::event::DeviceId) -> DeviceId {
         if let Some((id, _)) = self.devices.iter().find(|(_, id)| *id == device_id) {
             *id
@@ -1487,6 +1668,7 @@ impl App {
             config,
             &self.winit_loop,
             &mut self.gl_manager,
+            &self.surface_pool,
             self.exts.new_renderer(),
             self.app_sender.clone(),
         );
@@ -1523,6 +1705,9 @@ impl Api for App {
         self.headless = headless;
 
         self.app_sender.send(AppEvent::InitDeviceEvents(device_events)).unwrap();
+        if !headless {
+            self.gamepads.start(self.app_sender.clone());
+        }
 
         let available_monitors = self.available_monitors();
         self.notify(Event::Inited(Inited {
@@ -1581,6 +1766,7 @@ impl Api for App {
                     max_size: DipSize::new(Dip::MAX, Dip::MAX),
                     chrome_visible: false,
                 },
+                color_space: ColorSpace::Srgb,
             };
 
             self.notify(Event::WindowOpened(id, msg));
@@ -1609,6 +1795,7 @@ impl Api for App {
                 scale_factor: win.scale_factor(),
                 render_mode: win.render_mode(),
                 state: win.state(),
+                color_space: ColorSpace::Srgb,
             };
 
             self.windows.push(win);
@@ -1632,6 +1819,7 @@ impl Api for App {
         self.assert_started();
         if let Some(i) = self.windows.iter().position(|w| w.id() == id) {
             let _ = self.windows.swap_remove(i);
+            self.app_menus.close(id);
         }
         if let Some(i) = self.surfaces.iter().position(|w| w.id() == id) {
             let _ = self.surfaces.swap_remove(i);
@@ -1646,8 +1834,8 @@ impl Api for App {
         self.with_window(id, |w| w.set_visible(visible), || ())
     }
 
-    fn set_always_on_top(&mut self, id: WindowId, always_on_top: bool) {
-        self.with_window(id, |w| w.set_always_on_top(always_on_top), || ())
+    fn set_layer(&mut self, id: WindowId, layer: WindowLayer) {
+        self.with_window(id, |w| w.set_layer(layer), || ())
     }
 
     fn set_movable(&mut self, id: WindowId, movable: bool) {
@@ -1731,6 +1919,52 @@ impl Api for App {
         self.with_window(id, |w| w.open_title_bar_context_menu(position), || ())
     }
 
+    fn set_system_menu(&mut self, id: WindowId, menu: zng_view_api::window::SystemMenu) {
+        self.with_window(id, |w| w.set_system_menu(menu), || ())
+    }
+
+    fn set_app_menu(&mut self, id: WindowId, menu: zng_view_api::window::AppMenu) -> Result<(), zng_view_api::window::AppMenuError> {
+        if self.headless {
+            return Err(zng_view_api::window::AppMenuError::NotSupported);
+        }
+        self.assert_started();
+        match self.windows.iter().find(|w| w.id() == id) {
+            Some(w) => self.app_menus.set(id, w.winit_window(), menu),
+            None => {
+                tracing::error!("headed window `{id:?}` not found, will return fallback result");
+                Err(zng_view_api::window::AppMenuError::Other("window not found".into()))
+            }
+        }
+    }
+
+    fn set_window_tabs(&mut self, id: WindowId, tabs: Vec<WindowId>) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(w) = self.windows.iter().find(|w| w.id() == id) {
+                let tabs: Vec<_> = tabs.iter().filter_map(|t| self.windows.iter().find(|w| w.id() == *t)).collect();
+                w.set_tabs(&tabs);
+            }
+            true
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = (id, tabs);
+            false
+        }
+    }
+
+    fn set_continuous_render(&mut self, id: WindowId, enabled: bool) {
+        self.with_window(id, |w| w.set_continuous_render(enabled), || ())
+    }
+
+    fn set_present_notify(&mut self, id: WindowId, enabled: bool) {
+        self.with_window(id, |w| w.set_present_notify(enabled), || ())
+    }
+
+    fn set_hit_test_regions(&mut self, id: WindowId, regions: Vec<(PxRect, zng_view_api::window::HitTestKind)>) {
+        self.with_window(id, |w| w.set_hit_test_regions(regions), || ())
+    }
+
     fn set_cursor(&mut self, id: WindowId, icon: Option<CursorIcon>) {
         self.with_window(id, |w| w.set_cursor(icon), || ())
     }
@@ -1740,12 +1974,45 @@ impl Api for App {
         self.with_window(id, |w| w.set_cursor_image(icon), || ());
     }
 
+    fn start_drag_drop(
+        &mut self,
+        id: WindowId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<ImageId>,
+    ) -> Result<DragDropId, DragDropError> {
+        let image = image.and_then(|i| self.image_cache.get(i)).cloned();
+        let drag_id = self.drag_drop_id_gen.incr();
+        let app_sender = self.app_sender.clone();
+        let source = self.with_window(
+            id,
+            |w| w.start_drag_drop(drag_id, data, allowed_effects, image, app_sender),
+            || Err(DragDropError::CannotStart(Txt::from_static("window not found"))),
+        )?;
+        self.drag_sources.push((drag_id, source));
+        #[cfg(windows)]
+        let _ = self.app_sender.send(AppEvent::RunDragDrop(drag_id));
+        Ok(drag_id)
+    }
+
+    fn cancel_drag_drop(&mut self, _: WindowId, drag_id: DragDropId) {
+        if let Some((_, s)) = self.drag_sources.iter().find(|(id, _)| *id == drag_id) {
+            s.cancel();
+        }
+    }
+
     fn set_ime_area(&mut self, id: WindowId, area: Option<DipRect>) {
         self.with_window(id, |w| w.set_ime_area(area), || ())
     }
 
     fn image_decoders(&mut self) -> Vec<Txt> {
-        image_cache::DECODERS.iter().map(|&s| Txt::from_static(s)).collect()
+        let mut r: Vec<Txt> = image_cache::DECODERS.iter().map(|&s| Txt::from_static(s)).collect();
+        for f in self.image_cache.custom_formats() {
+            if !r.contains(&f) {
+                r.push(f);
+            }
+        }
+        r
     }
 
     fn image_encoders(&mut self) -> Vec<Txt> {
@@ -1764,8 +2031,16 @@ impl Api for App {
         self.image_cache.forget(id)
     }
 
-    fn encode_image(&mut self, id: ImageId, format: Txt) {
-        self.image_cache.encode(id, format)
+    fn image_frame(&mut self, id: ImageId, index: usize) -> ImageId {
+        self.image_cache.frame(id, index)
+    }
+
+    fn image_raster(&mut self, id: ImageId, size: PxSize) -> ImageId {
+        self.image_cache.raster(id, size)
+    }
+
+    fn encode_image(&mut self, id: ImageId, format: Txt, options: ImageEncodeOptions) {
+        self.image_cache.encode(id, format, options)
     }
 
     fn use_image(&mut self, id: WindowId, image_id: ImageId) -> ImageTextureId {
@@ -1815,6 +2090,14 @@ impl Api for App {
         self.with_window(id, |w| w.set_capture_mode(enabled), || ())
     }
 
+    fn renderer_stats(&mut self, id: WindowId) -> RendererStats {
+        with_window_or_surface!(self, id, |w| w.renderer_stats(), || RendererStats::default())
+    }
+
+    fn trim_renderer(&mut self, id: WindowId, trim: RendererTrim) {
+        with_window_or_surface!(self, id, |w| w.trim_renderer(trim), || ())
+    }
+
     fn frame_image(&mut self, id: WindowId, mask: Option<ImageMaskMode>) -> ImageId {
         with_window_or_surface!(self, id, |w| w.frame_image(&mut self.image_cache, mask), || ImageId::INVALID)
     }
@@ -1829,6 +2112,10 @@ impl Api for App {
         with_window_or_surface!(self, id, |w| w.render(frame), || ())
     }
 
+    fn render_batch(&mut self, id: WindowId, frames: Vec<FrameRequest>) {
+        with_window_or_surface!(self, id, |w| w.render_batch(frames), || ())
+    }
+
     fn render_update(&mut self, id: WindowId, frame: FrameUpdateRequest) {
         with_window_or_surface!(self, id, |w| w.render_update(frame), || ())
     }
@@ -1839,6 +2126,12 @@ impl Api for App {
         }
     }
 
+    fn access_announce(&mut self, id: WindowId, txt: Txt, priority: access::AccessLivePriority) {
+        if let Some(s) = self.windows.iter_mut().find(|s| s.id() == id) {
+            s.access_announce(txt, priority, &self.app_sender);
+        }
+    }
+
     fn message_dialog(&mut self, id: WindowId, dialog: MsgDialog) -> DialogId {
         let r_id = self.dialog_id_gen.incr();
         if let Some(s) = self.windows.iter_mut().find(|s| s.id() == id) {
@@ -1911,7 +2204,7 @@ impl Api for App {
 
                 if let Some(img) = self.image_cache.get(id) {
                     let mut bmp = vec![];
-                    img.encode(::image::ImageFormat::Bmp, &mut bmp)
+                    img.encode(::image::ImageFormat::Bmp, ImageEncodeOptions::default(), &mut bmp)
                         .map_err(|e| clipboard::ClipboardError::Other(formatx!("{e:?}")))?;
                     clipboard_win::set(clipboard_win::formats::Bitmap, bmp).map_err(util::clipboard_win_to_clip)
                 } else {
@@ -1991,6 +2284,55 @@ impl Api for App {
         }
     }
 
+    fn register_global_shortcut(
+        &mut self,
+        id: zng_view_api::keyboard::GlobalShortcutId,
+        shortcut: zng_view_api::keyboard::GlobalShortcut,
+    ) -> Result<(), zng_view_api::keyboard::GlobalShortcutError> {
+        if self.headless {
+            return Err(zng_view_api::keyboard::GlobalShortcutError::NotSupported);
+        }
+        self.global_shortcuts.register(id, shortcut)
+    }
+
+    fn unregister_global_shortcut(&mut self, id: zng_view_api::keyboard::GlobalShortcutId) {
+        self.global_shortcuts.unregister(id)
+    }
+
+    fn key_labels(&mut self, codes: Vec<zng_view_api::keyboard::KeyCode>) -> Vec<zng_view_api::keyboard::Key> {
+        config::key_labels(codes)
+    }
+
+    fn set_tray_icon(&mut self, request: zng_view_api::tray::TrayIconRequest) -> Result<(), zng_view_api::tray::TrayIconError> {
+        if self.headless {
+            return Err(zng_view_api::tray::TrayIconError::NotSupported);
+        }
+        let icon = request.icon.and_then(|id| self.image_cache.get(id));
+        self.tray_icons.set(request, icon)
+    }
+
+    fn close_tray_icon(&mut self, id: zng_view_api::tray::TrayIconId) {
+        self.tray_icons.close(id)
+    }
+
+    fn show_notification(
+        &mut self,
+        request: zng_view_api::notification::NotificationRequest,
+    ) -> Result<(), zng_view_api::notification::NotificationError> {
+        if self.headless {
+            return Err(zng_view_api::notification::NotificationError::NotSupported);
+        }
+        self.notifications.show(request)
+    }
+
+    fn close_notification(&mut self, id: zng_view_api::notification::NotificationId) {
+        self.notifications.close(id)
+    }
+
+    fn play_sound(&mut self, sound: zng_view_api::sound::SoundData) -> Result<(), zng_view_api::sound::SoundError> {
+        self.sound.play(sound)
+    }
+
     fn set_system_shutdown_warn(&mut self, id: WindowId, reason: Txt) {
         self.with_window(id, move |w| w.set_system_shutdown_warn(reason), || ())
     }
@@ -2056,12 +2398,23 @@ pub(crate) enum AppEvent {
     /// Image finished decoding, must call [`ImageCache::loaded`].
     ImageLoaded(ImageLoadedData),
 
+    /// Image progressive decode has decoded more pixels, must call [`ImageCache::partially_loaded`].
+    ImagePartiallyLoaded(image_cache::ImagePartiallyLoadedData),
+
     /// Send after init with `device_events`.
     InitDeviceEvents(bool),
 
+    /// Gamepad input, must call [`gamepad::Gamepads::on_event`].
+    #[cfg_attr(not(feature = "gamepad"), allow(unused))]
+    Gamepad(gamepad::GamepadEvent),
+
     /// Send when monitor was turned on/off by the OS, need to redraw all screens to avoid blank issue.
     #[allow(unused)]
     MonitorPowerChanged,
+
+    /// Run the system drag loop for the drag source, must be send after the start request responds.
+    #[cfg(windows)]
+    RunDragDrop(DragDropId),
 }
 
 /// Message inserted in the request loop from the view-process.
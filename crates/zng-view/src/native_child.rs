@@ -0,0 +1,391 @@
+//! Native child window extension.
+//!
+//! A native child is an OS window (HWND, NSView, X11 window) created as a child of a headed window and positioned
+//! and clipped to match the bounds of a widget in the app-process. This is the integration point for content that
+//! must be rendered by the operating system or by other toolkits, like a WebView or a native video player.
+//!
+//! Implement [`NativeChildHost`] and register it using [`ViewExtensions::native_child`], the app-process uses the
+//! `zng-wgt-native-child` crate to instantiate a host by the registered name.
+//!
+//! ```
+//! use zng_view::{extensions::ViewExtensions, native_child::*};
+//!
+//! #[derive(Default)]
+//! struct MyControl {
+//!     // native handle..
+//! }
+//! impl NativeChildHost for MyControl {
+//!     fn open(&mut self, args: &mut NativeChildOpenArgs) -> Result<(), String> {
+//!         // create the native control using `args.parent` as the parent window.
+//!         let _ = args;
+//!         Ok(())
+//!     }
+//!
+//!     fn set_bounds(&mut self, args: &mut NativeChildBoundsArgs) {
+//!         // move and clip the native control.
+//!         let _ = args;
+//!     }
+//!
+//!     fn set_visible(&mut self, visible: bool) {
+//!         let _ = visible;
+//!     }
+//! }
+//!
+//! fn register(exts: &mut ViewExtensions) {
+//!     exts.native_child("my-crate.control", MyControl::default);
+//! }
+//! ```
+//!
+//! The `register` function must be called on view-process init, using the [`view_process_extension!`] macro.
+//!
+//! [`view_process_extension!`]: crate::view_process_extension!
+//!
+//! # Airspace
+//!
+//! The native child is composited by the operating system on top of the window content, so it is always rendered
+//! over any other widget, including popups and layers that overlap the host widget. The host [`clip`] is computed by the
+//! app-process to keep the child inside scroll viewports and other clipping ancestors, but overlapping content must be
+//! avoided by the app, usually by hiding the host widget while a popup is open.
+//!
+//! # Focus & Input
+//!
+//! Keyboard and pointer input that targets the native child is received directly by the child, the parent window does
+//! not receive these events and the app-process focus is not aware of focus inside the child. The host must call
+//! [`NativeChildEventSender::focused`] when the child receives keyboard focus, so that the app-process can focus the host widget,
+//! and [`NativeChildEventSender::tab_out`] when the user presses TAB at the end of the child focus scope, so that the app-process
+//! can move focus to the next widget. The app-process calls [`NativeChildHost::focus`] when the host widget receives focus.
+//!
+//! [`ViewExtensions::native_child`]: crate::extensions::ViewExtensions::native_child
+//! [`clip`]: NativeChildBoundsArgs::clip
+
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
+
+use raw_window_handle::{HasDisplayHandle as _, HasWindowHandle as _, RawDisplayHandle, RawWindowHandle};
+use zng_unit::{Factor, PxRect};
+use zng_view_api::api_extension::{ApiExtensionId, ApiExtensionName, ApiExtensionPayload};
+
+use crate::extensions::{
+    ExtensionEventSender, ViewExtension, ViewExtensionInitArgs, WindowCommandArgs, WindowDeinitedArgs, WindowExtension,
+};
+
+/// Host of a native child window.
+///
+/// A new host is created for each widget instance. See the [module] level documentation for more details.
+///
+/// [module]: crate::native_child
+pub trait NativeChildHost: Any {
+    /// Create the native child window.
+    ///
+    /// Returns an error message if the child cannot be created, the error is logged in the app-process.
+    fn open(&mut self, args: &mut NativeChildOpenArgs) -> Result<(), String>;
+
+    /// Move, resize and clip the native child.
+    ///
+    /// Called every time the host widget bounds or clip changes.
+    fn set_bounds(&mut self, args: &mut NativeChildBoundsArgs);
+
+    /// Show or hide the native child.
+    ///
+    /// The child is hidden when the host widget is collapsed or hidden.
+    fn set_visible(&mut self, visible: bool);
+
+    /// Move keyboard focus to the native child.
+    ///
+    /// Called when the host widget receives focus in the app-process.
+    fn focus(&mut self) {}
+
+    /// Custom command request from the app-process.
+    ///
+    /// Hosts can use this to implement an API specific for the child content, like navigating a WebView.
+    fn command(&mut self, request: ApiExtensionPayload) -> ApiExtensionPayload {
+        let _ = request;
+        ApiExtensionPayload::empty()
+    }
+
+    /// Destroy the native child.
+    ///
+    /// Called when the host widget deinits or the parent window closes.
+    fn close(&mut self) {}
+}
+
+/// Arguments for [`NativeChildHost::open`].
+pub struct NativeChildOpenArgs<'a> {
+    /// The parent window.
+    pub window: &'a winit::window::Window,
+    /// Raw handle of the parent window.
+    pub parent: RawWindowHandle,
+    /// Raw handle of the display connection.
+    pub display: RawDisplayHandle,
+    /// Initial bounds and clip.
+    pub bounds: NativeChildBoundsArgs<'a>,
+    /// If the child is initially visible.
+    pub visible: bool,
+    /// Custom payload defined by the app-process widget.
+    pub payload: ApiExtensionPayload,
+    /// Sender of focus and custom events to the app-process widget.
+    pub events: NativeChildEventSender,
+}
+
+/// Arguments for [`NativeChildHost::set_bounds`].
+pub struct NativeChildBoundsArgs<'a> {
+    /// The parent window.
+    pub window: &'a winit::window::Window,
+    /// Bounds of the host widget, in the parent window client area.
+    pub bounds: PxRect,
+    /// Visible part of the host widget, in the parent window client area.
+    ///
+    /// Is the same as `bounds` if the host widget is not clipped, can be empty if the widget is fully clipped.
+    pub clip: PxRect,
+    /// Scale factor of the parent window.
+    pub scale_factor: Factor,
+}
+
+/// Sends events from a native child to the host widget in the app-process.
+#[derive(Clone)]
+pub struct NativeChildEventSender {
+    sender: ExtensionEventSender,
+    id: u64,
+}
+impl NativeChildEventSender {
+    /// Notify that the native child received keyboard focus.
+    pub fn focused(&self) {
+        self.send(NativeChildEvent::Focused { id: self.id });
+    }
+
+    /// Notify that the user navigated out of the native child using the keyboard.
+    ///
+    /// If `reverse` is `true` the focus moves to the previous widget (SHIFT+TAB), otherwise moves to the next.
+    pub fn tab_out(&self, reverse: bool) {
+        self.send(NativeChildEvent::TabOut { id: self.id, reverse });
+    }
+
+    /// Send a custom event to the host widget.
+    pub fn custom(&self, payload: ApiExtensionPayload) {
+        self.send(NativeChildEvent::Custom { id: self.id, payload });
+    }
+
+    fn send(&self, event: NativeChildEvent) {
+        let _ = self.sender.send(ApiExtensionPayload::serialize(&event).unwrap());
+    }
+}
+
+/// Request sent by the app-process.
+#[derive(serde::Deserialize)]
+enum NativeChildRequest {
+    Open {
+        id: u64,
+        bounds: PxRect,
+        clip: PxRect,
+        visible: bool,
+        payload: ApiExtensionPayload,
+    },
+    SetBounds {
+        id: u64,
+        bounds: PxRect,
+        clip: PxRect,
+    },
+    SetVisible {
+        id: u64,
+        visible: bool,
+    },
+    Focus {
+        id: u64,
+    },
+    Command {
+        id: u64,
+        payload: ApiExtensionPayload,
+    },
+    Close {
+        id: u64,
+    },
+}
+
+/// Response sent to the app-process.
+#[derive(serde::Serialize)]
+enum NativeChildResponse {
+    Opened,
+    Done,
+    Command(ApiExtensionPayload),
+    NotFound,
+    Error(String),
+}
+
+/// Event sent to the app-process.
+#[derive(serde::Serialize)]
+enum NativeChildEvent {
+    Focused { id: u64 },
+    TabOut { id: u64, reverse: bool },
+    Custom { id: u64, payload: ApiExtensionPayload },
+}
+
+type HostFactory = Arc<Mutex<dyn FnMut() -> Box<dyn NativeChildHost> + Send>>;
+
+/// View extension that creates a [`NativeChildExt`] for each window.
+pub(crate) struct NativeChildViewExt {
+    name: ApiExtensionName,
+    id: ApiExtensionId,
+    new_host: HostFactory,
+    event_sender: Option<ExtensionEventSender>,
+}
+impl NativeChildViewExt {
+    pub(crate) fn new<H: NativeChildHost>(name: ApiExtensionName, id: ApiExtensionId, mut new: impl FnMut() -> H + Send + 'static) -> Self {
+        Self {
+            name,
+            id,
+            new_host: Arc::new(Mutex::new(move || Box::new(new()) as Box<dyn NativeChildHost>)),
+            event_sender: None,
+        }
+    }
+}
+impl ViewExtension for NativeChildViewExt {
+    fn init(&mut self, args: ViewExtensionInitArgs) {
+        self.event_sender = Some(args.event_sender);
+    }
+
+    fn name(&self) -> &ApiExtensionName {
+        &self.name
+    }
+
+    fn window(&mut self) -> Option<Box<dyn WindowExtension>> {
+        Some(Box::new(NativeChildExt {
+            id: self.id,
+            new_host: self.new_host.clone(),
+            event_sender: self.event_sender.clone()?,
+            hosts: vec![],
+        }))
+    }
+}
+
+/// Window extension that manages the native children of a registered [`NativeChildHost`].
+struct NativeChildExt {
+    id: ApiExtensionId,
+    new_host: HostFactory,
+    event_sender: ExtensionEventSender,
+    hosts: Vec<(u64, Box<dyn NativeChildHost>)>,
+}
+impl NativeChildExt {
+    fn host(&mut self, id: u64) -> Option<&mut Box<dyn NativeChildHost>> {
+        self.hosts.iter_mut().find(|(i, _)| *i == id).map(|(_, h)| h)
+    }
+}
+impl WindowExtension for NativeChildExt {
+    fn is_init_only(&self) -> bool {
+        false
+    }
+
+    fn command(&mut self, args: &mut WindowCommandArgs) -> ApiExtensionPayload {
+        let request = match args.request.deserialize::<NativeChildRequest>() {
+            Ok(r) => r,
+            Err(e) => return ApiExtensionPayload::invalid_request(self.id, e),
+        };
+
+        let response = match request {
+            NativeChildRequest::Open {
+                id,
+                bounds,
+                clip,
+                visible,
+                payload,
+            } => {
+                if let Some(i) = self.hosts.iter().position(|(i, _)| *i == id) {
+                    // reopen
+                    let (_, mut h) = self.hosts.swap_remove(i);
+                    h.close();
+                }
+
+                let handles = args
+                    .window
+                    .window_handle()
+                    .and_then(|w| args.window.display_handle().map(|d| (w.as_raw(), d.as_raw())));
+                match handles {
+                    Ok((parent, display)) => {
+                        let mut host = (self.new_host.lock().unwrap())();
+                        let r = host.open(&mut NativeChildOpenArgs {
+                            window: args.window,
+                            parent,
+                            display,
+                            bounds: NativeChildBoundsArgs {
+                                window: args.window,
+                                bounds,
+                                clip,
+                                scale_factor: Factor(args.window.scale_factor() as f32),
+                            },
+                            visible,
+                            payload,
+                            events: NativeChildEventSender {
+                                sender: self.event_sender.clone(),
+                                id,
+                            },
+                        });
+                        match r {
+                            Ok(()) => {
+                                self.hosts.push((id, host));
+                                NativeChildResponse::Opened
+                            }
+                            Err(e) => NativeChildResponse::Error(e),
+                        }
+                    }
+                    Err(e) => NativeChildResponse::Error(format!("cannot get parent window handle, {e}")),
+                }
+            }
+            NativeChildRequest::SetBounds { id, bounds, clip } => {
+                let window = args.window;
+                if let Some(h) = self.host(id) {
+                    h.set_bounds(&mut NativeChildBoundsArgs {
+                        window,
+                        bounds,
+                        clip,
+                        scale_factor: Factor(window.scale_factor() as f32),
+                    });
+                    NativeChildResponse::Done
+                } else {
+                    NativeChildResponse::NotFound
+                }
+            }
+            NativeChildRequest::SetVisible { id, visible } => {
+                if let Some(h) = self.host(id) {
+                    h.set_visible(visible);
+                    NativeChildResponse::Done
+                } else {
+                    NativeChildResponse::NotFound
+                }
+            }
+            NativeChildRequest::Focus { id } => {
+                if let Some(h) = self.host(id) {
+                    h.focus();
+                    NativeChildResponse::Done
+                } else {
+                    NativeChildResponse::NotFound
+                }
+            }
+            NativeChildRequest::Command { id, payload } => {
+                if let Some(h) = self.host(id) {
+                    NativeChildResponse::Command(h.command(payload))
+                } else {
+                    NativeChildResponse::NotFound
+                }
+            }
+            NativeChildRequest::Close { id } => {
+                if let Some(i) = self.hosts.iter().position(|(i, _)| *i == id) {
+                    let (_, mut h) = self.hosts.swap_remove(i);
+                    h.close();
+                    NativeChildResponse::Done
+                } else {
+                    NativeChildResponse::NotFound
+                }
+            }
+        };
+
+        ApiExtensionPayload::serialize(&response).unwrap()
+    }
+
+    fn window_deinited(&mut self, args: &mut WindowDeinitedArgs) {
+        let _ = args;
+        for (_, mut h) in self.hosts.drain(..) {
+            h.close();
+        }
+    }
+}
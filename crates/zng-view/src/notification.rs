@@ -0,0 +1,247 @@
+//! Native notifications.
+//!
+//! Implemented using the `org.freedesktop.Notifications` DBus interface on Linux and BSD, not supported on other systems.
+
+use zng_view_api::notification::{NotificationError, NotificationId, NotificationRequest};
+
+use crate::AppEventSender;
+
+pub(crate) struct Notifications {
+    #[cfg_attr(
+        not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )),
+        allow(unused)
+    )]
+    app_sender: AppEventSender,
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    dbus: Option<dbus_notify::DbusNotifications>,
+}
+impl Notifications {
+    pub fn new(app_sender: AppEventSender) -> Self {
+        Self {
+            app_sender,
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ))]
+            dbus: None,
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn show(&mut self, request: NotificationRequest) -> Result<(), NotificationError> {
+        if self.dbus.is_none() {
+            self.dbus = Some(dbus_notify::DbusNotifications::connect(self.app_sender.clone())?);
+        }
+        self.dbus.as_mut().unwrap().show(request)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn close(&mut self, id: NotificationId) {
+        if let Some(d) = &mut self.dbus {
+            d.close(id);
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    pub fn show(&mut self, _: NotificationRequest) -> Result<(), NotificationError> {
+        Err(NotificationError::NotSupported)
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    pub fn close(&mut self, _: NotificationId) {}
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod dbus_notify {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use zbus::{
+        blocking::{Connection, Proxy},
+        zvariant::Value,
+    };
+    use zng_view_api::{
+        notification::{NotificationError, NotificationId, NotificationRequest, NotificationResponse},
+        Event,
+    };
+
+    use crate::{AppEvent, AppEventSender};
+
+    const DESTINATION: &str = "org.freedesktop.Notifications";
+    const PATH: &str = "/org/freedesktop/Notifications";
+    const INTERFACE: &str = "org.freedesktop.Notifications";
+
+    /// Action key invoked when the notification body is clicked.
+    const DEFAULT_ACTION: &str = "default";
+
+    /// Open notifications, app ID and server ID.
+    type OpenList = Arc<Mutex<Vec<(NotificationId, u32)>>>;
+
+    pub struct DbusNotifications {
+        connection: Connection,
+        open: OpenList,
+    }
+    impl DbusNotifications {
+        pub fn connect(app_sender: AppEventSender) -> Result<Self, NotificationError> {
+            let map_err = |e: zbus::Error| NotificationError::Other(e.to_string().into());
+
+            let connection = Connection::session().map_err(map_err)?;
+            let proxy = Proxy::new(&connection, DESTINATION, PATH, INTERFACE).map_err(map_err)?;
+            let signals = proxy.receive_all_signals().map_err(map_err)?;
+
+            let open = OpenList::default();
+            let thread_open = open.clone();
+            std::thread::Builder::new()
+                .name("notifications".into())
+                .spawn(move || {
+                    for msg in signals {
+                        let header = msg.header();
+                        let member = header.member().map(|m| m.as_str()).unwrap_or_default();
+                        let (server_id, response) = match member {
+                            "ActionInvoked" => match msg.body().deserialize::<(u32, String)>() {
+                                Ok((id, action)) if action == DEFAULT_ACTION => (id, NotificationResponse::Activated),
+                                Ok((id, action)) => (id, NotificationResponse::Action(action.into())),
+                                Err(_) => continue,
+                            },
+                            "NotificationClosed" => match msg.body().deserialize::<(u32, u32)>() {
+                                Ok((id, _reason)) => (id, NotificationResponse::Dismissed),
+                                Err(_) => continue,
+                            },
+                            _ => continue,
+                        };
+
+                        let id = {
+                            let mut open = thread_open.lock().unwrap();
+                            match open.iter().position(|(_, s)| *s == server_id) {
+                                Some(i) => {
+                                    let (id, _) = open[i];
+                                    if let NotificationResponse::Dismissed = &response {
+                                        open.swap_remove(i);
+                                    }
+                                    id
+                                }
+                                // not our notification or already responded
+                                None => continue,
+                            }
+                        };
+                        if app_sender
+                            .send(AppEvent::Notify(Event::NotificationResponded {
+                                notification: id,
+                                response,
+                            }))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                })
+                .map_err(|e| NotificationError::Other(e.to_string().into()))?;
+
+            Ok(Self { connection, open })
+        }
+
+        pub fn show(&mut self, request: NotificationRequest) -> Result<(), NotificationError> {
+            let replaces_id = self
+                .open
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(id, _)| *id == request.id)
+                .map(|(_, s)| *s)
+                .unwrap_or(0);
+
+            let mut actions = vec![DEFAULT_ACTION, ""];
+            for a in &request.actions {
+                actions.push(a.id.as_str());
+                actions.push(a.label.as_str());
+            }
+            let hints = HashMap::<&str, Value>::new();
+
+            let server_id = self
+                .connection
+                .call_method(
+                    Some(DESTINATION),
+                    PATH,
+                    Some(INTERFACE),
+                    "Notify",
+                    &(
+                        zng_env::about().app.as_str(),
+                        replaces_id,
+                        "",
+                        request.title.as_str(),
+                        request.body.as_str(),
+                        actions,
+                        hints,
+                        -1i32,
+                    ),
+                )
+                .and_then(|r| r.body().deserialize::<u32>())
+                .map_err(|e| NotificationError::Other(e.to_string().into()))?;
+
+            let mut open = self.open.lock().unwrap();
+            open.retain(|(id, _)| *id != request.id);
+            open.push((request.id, server_id));
+            Ok(())
+        }
+
+        pub fn close(&mut self, id: NotificationId) {
+            let server_id = {
+                let mut open = self.open.lock().unwrap();
+                match open.iter().position(|(i, _)| *i == id) {
+                    Some(i) => open.swap_remove(i).1,
+                    None => return,
+                }
+            };
+            let _ = self
+                .connection
+                .call_method(Some(DESTINATION), PATH, Some(INTERFACE), "CloseNotification", &(server_id,));
+        }
+    }
+}
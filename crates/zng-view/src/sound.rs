@@ -0,0 +1,142 @@
+//! System sounds and WAV playback.
+//!
+//! Implemented using the system APIs on Windows and macOS, on Linux the `canberra-gtk-play`, `paplay` and `aplay`
+//! command line utilities are used if they are installed.
+
+use zng_view_api::sound::{SoundData, SoundError};
+
+#[derive(Default)]
+pub(crate) struct SoundPlayer {
+    /// PlaySound reads from the buffer during async playback.
+    #[cfg(windows)]
+    playing_wav: Option<zng_view_api::ipc::IpcBytes>,
+    /// NSSound stops playing if dropped.
+    #[cfg(target_os = "macos")]
+    playing_wav: Option<objc2::rc::Retained<objc2_app_kit::NSSound>>,
+}
+impl SoundPlayer {
+    #[cfg(windows)]
+    pub fn play(&mut self, sound: SoundData) -> Result<(), SoundError> {
+        match sound {
+            SoundData::System(s) => {
+                use windows_sys::Win32::{System::Diagnostics::Debug::MessageBeep, UI::WindowsAndMessaging::*};
+                use zng_view_api::sound::SystemSound;
+
+                let style = match s {
+                    SystemSound::Bell => MB_OK,
+                    SystemSound::Info => MB_ICONINFORMATION,
+                    SystemSound::Warn => MB_ICONWARNING,
+                    SystemSound::Error => MB_ICONERROR,
+                    SystemSound::Question => MB_ICONQUESTION,
+                };
+                // SAFETY: function has no preconditions.
+                if unsafe { MessageBeep(style) } == 0 {
+                    return Err(SoundError::Other(std::io::Error::last_os_error().to_string().into()));
+                }
+                Ok(())
+            }
+            SoundData::Wav(data) => {
+                use windows_sys::Win32::Media::Audio::*;
+
+                // SAFETY: null stops the current sound, after this it is safe to drop the previous buffer.
+                unsafe { PlaySoundW(std::ptr::null(), 0, 0) };
+                self.playing_wav = None;
+
+                // SAFETY: the data buffer is held until the next call.
+                if unsafe { PlaySoundW(data.as_ptr() as _, 0, SND_MEMORY | SND_ASYNC | SND_NODEFAULT) } == 0 {
+                    return Err(SoundError::Other("cannot play WAV data".into()));
+                }
+                self.playing_wav = Some(data);
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn play(&mut self, sound: SoundData) -> Result<(), SoundError> {
+        match sound {
+            SoundData::System(_) => {
+                // SAFETY: function has no preconditions.
+                unsafe { objc2_app_kit::NSBeep() };
+                Ok(())
+            }
+            SoundData::Wav(data) => {
+                use objc2::ClassType as _;
+                use objc2_app_kit::NSSound;
+
+                if let Some(s) = self.playing_wav.take() {
+                    // SAFETY: sound is valid.
+                    unsafe { s.stop() };
+                }
+
+                let data = objc2_foundation::NSData::with_bytes(&data[..]);
+                // SAFETY: data is a valid NSData.
+                let sound = unsafe { NSSound::initWithData(NSSound::alloc(), &data) };
+                match sound {
+                    // SAFETY: sound is valid.
+                    Some(s) if unsafe { s.play() } => {
+                        self.playing_wav = Some(s);
+                        Ok(())
+                    }
+                    Some(_) => Err(SoundError::Other("cannot play WAV data".into())),
+                    None => Err(SoundError::Other("cannot decode WAV data".into())),
+                }
+            }
+        }
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    pub fn play(&mut self, sound: SoundData) -> Result<(), SoundError> {
+        match sound {
+            SoundData::System(s) => {
+                use zng_view_api::sound::SystemSound;
+
+                let id = match s {
+                    SystemSound::Bell => "bell",
+                    SystemSound::Info => "dialog-information",
+                    SystemSound::Warn => "dialog-warning",
+                    SystemSound::Error => "dialog-error",
+                    SystemSound::Question => "dialog-question",
+                };
+                spawn_player(&[("canberra-gtk-play", &["--id", id])], None)
+            }
+            SoundData::Wav(data) => spawn_player(&[("paplay", &[]), ("aplay", &["-q", "-"])], Some(data)),
+        }
+    }
+}
+
+/// Spawns the first player program found, writes `data` to the stdin and waits the process exit in a background thread.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn spawn_player(players: &[(&str, &[&str])], data: Option<zng_view_api::ipc::IpcBytes>) -> Result<(), SoundError> {
+    use std::{
+        io::Write as _,
+        process::{Command, Stdio},
+    };
+
+    for (program, args) in players {
+        let child = Command::new(program)
+            .args(*args)
+            .stdin(if data.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                let data = data.clone();
+                let program = program.to_string();
+                let r = std::thread::Builder::new().name("sound-player".into()).spawn(move || {
+                    if let (Some(data), Some(mut stdin)) = (data, child.stdin.take()) {
+                        if let Err(e) = stdin.write_all(&data[..]) {
+                            tracing::error!("cannot write sound to `{program}`, {e}");
+                        }
+                    }
+                    let _ = child.wait();
+                });
+                return r.map(|_| ()).map_err(|e| SoundError::Other(e.to_string().into()));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(SoundError::Other(e.to_string().into())),
+        }
+    }
+    Err(SoundError::NotSupported)
+}
@@ -1,4 +1,14 @@
-use std::{collections::VecDeque, fmt};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt, io,
+    mem::ManuallyDrop,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use tracing::span::EnteredSpan;
 use webrender::{
@@ -11,8 +21,11 @@ use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
     image::{ImageId, ImageLoadedData, ImageMaskMode, ImageTextureId},
-    window::{FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, HeadlessRequest, RenderMode, WindowId},
-    ViewProcessGen,
+    window::{
+        EventFrameRendered, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, HeadlessRequest, RenderMode, RendererStats,
+        RendererTrim, WindowId,
+    },
+    Event, ViewProcessGen,
 };
 
 use crate::{
@@ -25,7 +38,7 @@ use crate::{
     image_cache::{Image, ImageCache, ImageUseMap, WrImageCache},
     px_wr::PxToWr as _,
     util::{frame_render_reasons, frame_update_render_reasons, PxToWinit},
-    AppEventSender, FrameReadyMsg, WrNotifier,
+    AppEvent, AppEventSender, FrameReadyMsg, WrNotifier,
 };
 
 /// A headless "window".
@@ -37,7 +50,9 @@ pub(crate) struct Surface {
     size: DipSize,
     scale_factor: Factor,
 
-    context: GlContext,
+    /// Returned to the `pool` on drop.
+    context: ManuallyDrop<GlContext>,
+    pool: SurfacePool,
     renderer: Option<Renderer>,
     renderer_exts: Vec<(ApiExtensionId, Box<dyn RendererExtension>)>,
     external_images: extensions::ExternalImages,
@@ -46,9 +61,14 @@ pub(crate) struct Surface {
     display_list_cache: DisplayListCache,
     clear_color: Option<Rgba>,
 
-    pending_frames: VecDeque<(FrameId, FrameCapture, Option<EnteredSpan>)>,
+    /// Frames sent to the renderer, `true` if the frame is part of a batch.
+    pending_frames: VecDeque<(FrameId, FrameCapture, Option<EnteredSpan>, bool)>,
+    batch: Option<FrameBatch>,
     rendered_frame_id: FrameId,
     resized: bool,
+
+    event_sender: AppEventSender,
+    capture_worker: Option<CaptureWorker>,
 }
 impl fmt::Debug for Surface {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -66,12 +86,17 @@ impl Surface {
         cfg: HeadlessRequest,
         winit_loop: &ActiveEventLoop,
         gl_manager: &mut GlContextManager,
+        pool: &SurfacePool,
         mut renderer_exts: Vec<(ApiExtensionId, Box<dyn RendererExtension>)>,
         event_sender: AppEventSender,
     ) -> Self {
         let id = cfg.id;
 
-        let mut context = gl_manager.create_headless(id, winit_loop, cfg.render_mode, &event_sender);
+        let mut context = match pool.take(cfg.render_mode) {
+            Some(c) => c,
+            None => gl_manager.create_headless(id, winit_loop, cfg.render_mode, &event_sender),
+        };
+        context.make_current();
 
         let size = cfg.size.to_px(cfg.scale_factor);
         context.resize(size.to_winit());
@@ -93,6 +118,9 @@ impl Surface {
             // extensions expect this to be set.
             workers: Some(crate::util::wr_workers()),
 
+            // required by `renderer_stats`.
+            size_of_op: Some(crate::util::wr_size_of_op),
+
             //panic_on_gl_error: true,
             ..Default::default()
         };
@@ -116,7 +144,7 @@ impl Surface {
         let device_size = cfg.size.to_px(cfg.scale_factor).to_wr_device();
 
         let (mut renderer, sender) =
-            webrender::create_webrender_instance(context.gl().clone(), WrNotifier::create(id, event_sender), opts, None).unwrap();
+            webrender::create_webrender_instance(context.gl().clone(), WrNotifier::create(id, event_sender.clone()), opts, None).unwrap();
         renderer.set_external_image_handler(WrImageCache::new_boxed());
 
         let mut external_images = extensions::ExternalImages::default();
@@ -148,7 +176,8 @@ impl Surface {
             size: cfg.size,
             scale_factor: cfg.scale_factor,
 
-            context,
+            context: ManuallyDrop::new(context),
+            pool: pool.clone(),
             renderer: Some(renderer),
             renderer_exts,
             external_images,
@@ -157,8 +186,12 @@ impl Surface {
             clear_color: None,
 
             pending_frames: VecDeque::new(),
+            batch: None,
             rendered_frame_id: FrameId::INVALID,
             resized: true,
+
+            event_sender,
+            capture_worker: None,
         }
     }
 
@@ -235,13 +268,16 @@ impl Surface {
                 .collect(),
         );
         self.api.send_transaction(self.document_id, txn);
-        FontId::from_raw(key.1)
+        let font_id = FontId::from_raw(key.1);
+        self.display_list_cache.register_font(font_id, glyph_size);
+        font_id
     }
 
     pub fn delete_font(&mut self, font_id: FontId) {
         let mut txn = webrender::Transaction::new();
         txn.delete_font_instance(FontInstanceKey(self.api.get_namespace_id(), font_id.get()));
         self.api.send_transaction(self.document_id, txn);
+        self.display_list_cache.unregister_font(font_id);
     }
 
     fn push_resize(&mut self, txn: &mut Transaction) {
@@ -253,6 +289,21 @@ impl Surface {
     }
 
     pub fn render(&mut self, frame: FrameRequest) {
+        self.render_impl(frame, false);
+    }
+
+    /// Render the frames in sequence, the captured frames are converted in one worker job after the last frame renders.
+    pub fn render_batch(&mut self, frames: Vec<FrameRequest>) {
+        if frames.is_empty() {
+            return;
+        }
+        self.batch.get_or_insert_with(FrameBatch::default).remaining += frames.len();
+        for frame in frames {
+            self.render_impl(frame, true);
+        }
+    }
+
+    fn render_impl(&mut self, frame: FrameRequest, batched: bool) {
         let _span = tracing::trace_span!("render").entered();
 
         let render_reasons = frame_render_reasons(&frame);
@@ -293,7 +344,7 @@ impl Surface {
 
         let frame_scope =
             tracing::trace_span!("<frame>", ?frame.id, capture = ?frame.capture, from_update = false, thread = "<webrender>").entered();
-        self.pending_frames.push_back((frame.id, frame.capture, Some(frame_scope)));
+        self.pending_frames.push_back((frame.id, frame.capture, Some(frame_scope), batched));
 
         self.api.send_transaction(self.document_id, txn);
     }
@@ -352,20 +403,19 @@ impl Surface {
         };
 
         self.pending_frames
-            .push_back((frame.id, frame.capture, Some(frame_scope.entered())));
+            .push_back((frame.id, frame.capture, Some(frame_scope.entered()), false));
 
         self.api.send_transaction(self.document_id, txn);
     }
 
-    pub fn on_frame_ready(&mut self, msg: FrameReadyMsg, images: &mut ImageCache) -> (FrameId, Option<ImageLoadedData>) {
-        let (frame_id, capture, _) = self
-            .pending_frames
-            .pop_front()
-            .unwrap_or((self.rendered_frame_id, FrameCapture::None, None));
+    /// Returns the frame rendered event args, or `None` if the event will be send by the capture worker.
+    pub fn on_frame_ready(&mut self, msg: FrameReadyMsg, images: &mut ImageCache) -> Option<(FrameId, Option<ImageLoadedData>)> {
+        let (frame_id, capture, _, batched) =
+            self.pending_frames
+                .pop_front()
+                .unwrap_or((self.rendered_frame_id, FrameCapture::None, None, false));
         self.rendered_frame_id = frame_id;
 
-        let mut captured_data = None;
-
         let mut ext_args = FrameReadyArgs {
             frame_id,
             redraw: msg.composite_needed || capture != FrameCapture::None,
@@ -375,8 +425,11 @@ impl Surface {
             ext_args.redraw |= msg.composite_needed || capture != FrameCapture::None;
         }
 
+        let mut captured = None;
+
         if ext_args.redraw || msg.composite_needed || capture != FrameCapture::None {
-            self.context.make_current();
+            let context = &mut *self.context;
+            context.make_current();
             let renderer = self.renderer.as_mut().unwrap();
 
             let size = self.size.to_px(self.scale_factor);
@@ -391,7 +444,8 @@ impl Surface {
                 ext.redraw(&mut RedrawArgs {
                     scale_factor: self.scale_factor,
                     size,
-                    context: &mut self.context,
+                    context,
+                    redraw_again: false,
                 });
             }
 
@@ -401,15 +455,85 @@ impl Surface {
                 FrameCapture::Mask(m) => Some(Some(m)),
             };
             if let Some(mask) = capture {
-                captured_data = Some(images.frame_image_data(
-                    &**self.context.gl(),
-                    PxRect::from_size(self.size.to_px(self.scale_factor)),
-                    self.scale_factor,
-                    mask,
-                ));
+                // only the GL readback happens here, the pixel conversion runs in the worker
+                // thread, overlapping with the render of the next frame.
+                let convert = images.frame_image_data_deferred(&**context.gl(), PxRect::from_size(size), self.scale_factor, mask);
+                captured = Some(Box::new(convert) as DeferredCapture);
             }
         }
-        (frame_id, captured_data)
+
+        if batched {
+            let batch = self.batch.get_or_insert_with(FrameBatch::default);
+            batch.rendered.push((frame_id, captured));
+            batch.remaining = batch.remaining.saturating_sub(1);
+            if batch.remaining == 0 {
+                let rendered = self.batch.take().unwrap().rendered;
+                let window = self.id;
+                let event_sender = self.event_sender.clone();
+                self.run_capture_job(move || {
+                    for (frame_id, convert) in rendered {
+                        let frame_image = convert.map(|c| {
+                            let data = c();
+                            let _ = event_sender.send(AppEvent::ImageLoaded(data.clone()));
+                            data
+                        });
+                        let _ = event_sender.send(AppEvent::Notify(Event::FrameRendered(EventFrameRendered {
+                            window,
+                            frame: frame_id,
+                            frame_image,
+                        })));
+                    }
+                });
+            }
+            return None;
+        }
+
+        if let Some(convert) = captured {
+            let window = self.id;
+            let event_sender = self.event_sender.clone();
+            self.run_capture_job(move || {
+                let data = convert();
+                let _ = event_sender.send(AppEvent::ImageLoaded(data.clone()));
+                let _ = event_sender.send(AppEvent::Notify(Event::FrameRendered(EventFrameRendered {
+                    window,
+                    frame: frame_id,
+                    frame_image: Some(data),
+                })));
+            });
+            return None;
+        }
+
+        if let Some(w) = &self.capture_worker {
+            if w.is_busy() {
+                // send after the pending captures to keep the frame order.
+                let window = self.id;
+                let event_sender = self.event_sender.clone();
+                w.run(move || {
+                    let _ = event_sender.send(AppEvent::Notify(Event::FrameRendered(EventFrameRendered {
+                        window,
+                        frame: frame_id,
+                        frame_image: None,
+                    })));
+                });
+                return None;
+            }
+        }
+
+        Some((frame_id, None))
+    }
+
+    /// Run the `job` in the capture worker, or in the current thread if the worker thread cannot be spawned.
+    fn run_capture_job(&mut self, job: impl FnOnce() + Send + 'static) {
+        if self.capture_worker.is_none() {
+            match CaptureWorker::new(self.id) {
+                Ok(w) => self.capture_worker = Some(w),
+                Err(e) => tracing::error!("cannot spawn capture worker, {e}"),
+            }
+        }
+        match &self.capture_worker {
+            Some(w) => w.run(job),
+            None => job(),
+        }
     }
 
     pub fn frame_image(&mut self, images: &mut ImageCache, mask: Option<ImageMaskMode>) -> ImageId {
@@ -439,10 +563,12 @@ impl Surface {
                 r = Some(ext.command(&mut RendererCommandArgs {
                     renderer: self.renderer.as_mut().unwrap(),
                     api: &mut self.api,
+                    document_id: self.document_id,
+                    external_images: &mut self.external_images,
                     request,
                     window: None,
                     redraw: &mut redraw,
-                    context: &mut self.context,
+                    context: &mut *self.context,
                 }));
                 break;
             }
@@ -454,7 +580,8 @@ impl Surface {
                 ext.redraw(&mut RedrawArgs {
                     scale_factor: self.scale_factor,
                     size,
-                    context: &mut self.context,
+                    context: &mut *self.context,
+                    redraw_again: false,
                 });
             }
         }
@@ -462,8 +589,16 @@ impl Surface {
         r.unwrap_or_else(|| ApiExtensionPayload::unknown_extension(extension_id))
     }
 
+    pub fn renderer_stats(&self) -> RendererStats {
+        crate::util::wr_renderer_stats(self.renderer.as_ref().unwrap(), self.display_list_cache.glyph_cache_estimate())
+    }
+
+    pub fn trim_renderer(&mut self, trim: RendererTrim) {
+        crate::util::wr_trim(&self.api, trim);
+    }
+
     pub(crate) fn on_low_memory(&mut self) {
-        self.api.notify_memory_pressure();
+        crate::util::wr_trim(&self.api, RendererTrim::All);
 
         for (_, ext) in &mut self.renderer_exts {
             ext.low_memory();
@@ -478,9 +613,89 @@ impl Drop for Surface {
             ext.renderer_deinited(&mut RendererDeinitedArgs {
                 document_id: self.document_id,
                 pipeline_id: self.pipeline_id,
-                context: &mut self.context,
+                context: &mut *self.context,
                 window: None,
             })
         }
+
+        // SAFETY: context is not used after this.
+        let context = unsafe { ManuallyDrop::take(&mut self.context) };
+        self.pool.put(context);
+    }
+}
+
+/// Pool of GL contexts from closed surfaces.
+///
+/// Creating a headless context is expensive, the contexts are reused by new surfaces of the same render mode.
+#[derive(Default, Clone)]
+pub(crate) struct SurfacePool(Rc<RefCell<Vec<GlContext>>>);
+impl SurfacePool {
+    /// Maximum number of idle contexts retained.
+    const MAX_IDLE: usize = 4;
+
+    fn take(&self, render_mode: RenderMode) -> Option<GlContext> {
+        let mut contexts = self.0.borrow_mut();
+        let i = contexts.iter().rposition(|c| c.render_mode() == render_mode)?;
+        Some(contexts.remove(i))
+    }
+
+    fn put(&self, context: GlContext) {
+        let mut contexts = self.0.borrow_mut();
+        if contexts.len() == Self::MAX_IDLE {
+            contexts.remove(0);
+        }
+        contexts.push(context);
+    }
+
+    /// Drop all idle contexts.
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+impl fmt::Debug for SurfacePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SurfacePool").field("len", &self.0.borrow().len()).finish()
+    }
+}
+
+/// Captured frame pixels pending conversion.
+type DeferredCapture = Box<dyn FnOnce() -> ImageLoadedData + Send>;
+
+/// Frames of `render_batch` requests.
+#[derive(Default)]
+struct FrameBatch {
+    /// Number of batched frames not rendered yet.
+    remaining: usize,
+    /// Rendered frames, the events are send after the last frame renders.
+    rendered: Vec<(FrameId, Option<DeferredCapture>)>,
+}
+
+/// Thread that converts captured frames and sends the frame rendered events, in order.
+struct CaptureWorker {
+    sender: flume::Sender<Box<dyn FnOnce() + Send>>,
+    pending: Arc<AtomicUsize>,
+}
+impl CaptureWorker {
+    fn new(id: WindowId) -> io::Result<Self> {
+        let (sender, recv) = flume::unbounded::<Box<dyn FnOnce() + Send>>();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let p = pending.clone();
+        std::thread::Builder::new().name(format!("capture-{}", id.get())).spawn(move || {
+            while let Ok(job) = recv.recv() {
+                job();
+                p.fetch_sub(1, Ordering::Release);
+            }
+        })?;
+        Ok(Self { sender, pending })
+    }
+
+    /// If there are jobs pending.
+    fn is_busy(&self) -> bool {
+        self.pending.load(Ordering::Acquire) > 0
+    }
+
+    fn run(&self, job: impl FnOnce() + Send + 'static) {
+        self.pending.fetch_add(1, Ordering::Release);
+        let _ = self.sender.send(Box::new(job));
     }
 }
@@ -0,0 +1,618 @@
+//! System tray icons.
+//!
+//! Implemented using `Shell_NotifyIconW` on Windows and the `org.kde.StatusNotifierItem` DBus interface on Linux,
+//! not supported on other systems.
+
+use zng_view_api::tray::{TrayIconError, TrayIconId, TrayIconRequest};
+
+use crate::{image_cache::Image, AppEventSender};
+
+pub(crate) struct TrayIcons {
+    #[cfg_attr(not(any(windows, target_os = "linux")), allow(unused))]
+    app_sender: AppEventSender,
+    #[cfg(target_os = "linux")]
+    sni: sni_tray::SniTrays,
+}
+impl TrayIcons {
+    pub fn new(app_sender: AppEventSender) -> Self {
+        Self {
+            app_sender,
+            #[cfg(target_os = "linux")]
+            sni: sni_tray::SniTrays::default(),
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn set(&mut self, request: TrayIconRequest, icon: Option<&Image>) -> Result<(), TrayIconError> {
+        windows_tray::set(&self.app_sender, request, icon)
+    }
+
+    #[cfg(windows)]
+    pub fn close(&mut self, id: TrayIconId) {
+        windows_tray::close(id)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set(&mut self, request: TrayIconRequest, icon: Option<&Image>) -> Result<(), TrayIconError> {
+        self.sni.set(&self.app_sender, request, icon)
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn close(&mut self, id: TrayIconId) {
+        self.sni.close(id)
+    }
+
+    #[cfg(not(any(windows, target_os = "linux")))]
+    pub fn set(&mut self, _: TrayIconRequest, _: Option<&Image>) -> Result<(), TrayIconError> {
+        Err(TrayIconError::NotSupported)
+    }
+
+    #[cfg(not(any(windows, target_os = "linux")))]
+    pub fn close(&mut self, _: TrayIconId) {}
+}
+#[cfg(windows)]
+impl Drop for TrayIcons {
+    fn drop(&mut self) {
+        windows_tray::close_all();
+    }
+}
+
+#[cfg(windows)]
+mod windows_tray {
+    use std::cell::RefCell;
+
+    use windows_sys::Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+        Graphics::Gdi::{CreateBitmap, DeleteObject},
+        System::LibraryLoader::GetModuleHandleW,
+        UI::{Shell::*, WindowsAndMessaging::*},
+    };
+    use zng_txt::Txt;
+    use zng_view_api::{
+        mouse::MouseButton,
+        tray::{TrayIconError, TrayIconId, TrayIconRequest, TrayMenuItem},
+        Event,
+    };
+
+    use crate::{image_cache::Image, AppEvent, AppEventSender};
+
+    /// Tray icon callback message.
+    const WM_TRAY_ICON: u32 = WM_USER + 1;
+
+    struct TrayState {
+        app_sender: AppEventSender,
+        /// Message-only window that receives the tray icon callback messages.
+        hwnd: HWND,
+        /// Message broadcast when the taskbar is recreated, tray icons must be added again.
+        taskbar_created: u32,
+        icons: Vec<(TrayIconRequest, HICON)>,
+    }
+
+    thread_local! {
+        /// State is shared with the [`tray_wnd_proc`].
+        static TRAY: RefCell<Option<TrayState>> = const { RefCell::new(None) };
+    }
+
+    pub fn set(app_sender: &AppEventSender, request: TrayIconRequest, icon: Option<&Image>) -> Result<(), TrayIconError> {
+        TRAY.with_borrow_mut(|t| {
+            if t.is_none() {
+                *t = Some(TrayState::new(app_sender.clone())?);
+            }
+            let t = t.as_mut().unwrap();
+
+            let hicon = icon.map(create_hicon).unwrap_or(0);
+            if let Some(i) = t.icons.iter().position(|(r, _)| r.id == request.id) {
+                let (_, old_hicon) = std::mem::replace(&mut t.icons[i], (request, hicon));
+                let r = notify_icon(t.hwnd, NIM_MODIFY, &t.icons[i].0, hicon);
+                destroy_hicon(old_hicon);
+                r
+            } else {
+                let r = notify_icon(t.hwnd, NIM_ADD, &request, hicon);
+                if r.is_ok() {
+                    t.icons.push((request, hicon));
+                } else {
+                    destroy_hicon(hicon);
+                }
+                r
+            }
+        })
+    }
+
+    pub fn close(id: TrayIconId) {
+        TRAY.with_borrow_mut(|t| {
+            if let Some(t) = t {
+                if let Some(i) = t.icons.iter().position(|(r, _)| r.id == id) {
+                    let (request, hicon) = t.icons.remove(i);
+                    let _ = notify_icon(t.hwnd, NIM_DELETE, &request, 0);
+                    destroy_hicon(hicon);
+                }
+            }
+        })
+    }
+
+    pub fn close_all() {
+        TRAY.with_borrow_mut(|t| {
+            if let Some(t) = t {
+                for (request, hicon) in t.icons.drain(..) {
+                    let _ = notify_icon(t.hwnd, NIM_DELETE, &request, 0);
+                    destroy_hicon(hicon);
+                }
+            }
+        })
+    }
+
+    impl TrayState {
+        fn new(app_sender: AppEventSender) -> Result<Self, TrayIconError> {
+            let class_name: Vec<u16> = "zng-view-tray-icons\0".encode_utf16().collect();
+            // SAFETY: null gets the current module.
+            let hinstance = unsafe { GetModuleHandleW(std::ptr::null()) };
+            let class = WNDCLASSW {
+                style: 0,
+                lpfnWndProc: Some(tray_wnd_proc),
+                cbClsExtra: 0,
+                cbWndExtra: 0,
+                hInstance: hinstance,
+                hIcon: 0,
+                hCursor: 0,
+                hbrBackground: 0,
+                lpszMenuName: std::ptr::null(),
+                lpszClassName: class_name.as_ptr(),
+            };
+            // SAFETY: class is valid, `RegisterClassW` copies the strings.
+            unsafe { RegisterClassW(&class) };
+
+            // SAFETY: the class was registered, HWND_MESSAGE creates an invisible message-only window.
+            let hwnd = unsafe {
+                CreateWindowExW(
+                    0,
+                    class_name.as_ptr(),
+                    std::ptr::null(),
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    HWND_MESSAGE,
+                    0,
+                    hinstance,
+                    std::ptr::null(),
+                )
+            };
+            if hwnd == 0 {
+                return Err(TrayIconError::Other(std::io::Error::last_os_error().to_string().into()));
+            }
+
+            let taskbar_created: Vec<u16> = "TaskbarCreated\0".encode_utf16().collect();
+            // SAFETY: string is null terminated.
+            let taskbar_created = unsafe { RegisterWindowMessageW(taskbar_created.as_ptr()) };
+
+            Ok(Self {
+                app_sender,
+                hwnd,
+                taskbar_created,
+                icons: vec![],
+            })
+        }
+    }
+
+    fn notify_icon(hwnd: HWND, message: NOTIFY_ICON_MESSAGE, request: &TrayIconRequest, hicon: HICON) -> Result<(), TrayIconError> {
+        // SAFETY: all zeros is a valid empty NOTIFYICONDATAW.
+        let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+        data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+        data.hWnd = hwnd;
+        data.uID = request.id.get();
+        data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+        data.uCallbackMessage = WM_TRAY_ICON;
+        data.hIcon = hicon;
+        for (d, c) in data.szTip[..data.szTip.len() - 1].iter_mut().zip(request.tooltip.encode_utf16()) {
+            *d = c;
+        }
+
+        // SAFETY: data is valid.
+        if unsafe { Shell_NotifyIconW(message, &data) } == 0 {
+            Err(TrayIconError::Other("cannot update tray icon".into()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create_hicon(img: &Image) -> HICON {
+        let size = img.size();
+        let (width, height) = (size.width.0, size.height.0);
+        if width <= 0 || height <= 0 || img.descriptor().format != webrender::api::ImageFormat::BGRA8 {
+            return 0;
+        }
+
+        // SAFETY: pixels are BGRA8 with the image size, mask is 1bpp with word aligned rows.
+        unsafe {
+            let color = CreateBitmap(width, height, 1, 32, img.pixels().as_ptr() as _);
+            let mask_row = ((width as usize + 15) / 16) * 2;
+            let mask_bits = vec![0u8; mask_row * height as usize];
+            let mask = CreateBitmap(width, height, 1, 1, mask_bits.as_ptr() as _);
+
+            let info = ICONINFO {
+                fIcon: 1,
+                xHotspot: 0,
+                yHotspot: 0,
+                hbmMask: mask,
+                hbmColor: color,
+            };
+            let hicon = CreateIconIndirect(&info);
+
+            DeleteObject(color);
+            DeleteObject(mask);
+
+            if hicon == 0 {
+                tracing::error!("failed to convert image to tray icon, {}", std::io::Error::last_os_error());
+            }
+            hicon
+        }
+    }
+
+    fn destroy_hicon(hicon: HICON) {
+        if hicon != 0 {
+            // SAFETY: icon was created by `create_hicon`.
+            unsafe { DestroyIcon(hicon) };
+        }
+    }
+
+    fn show_menu(hwnd: HWND, items: &[TrayMenuItem]) -> Option<Txt> {
+        fn append_items(menu: HMENU, items: &[TrayMenuItem], ids: &mut Vec<Txt>) {
+            for item in items {
+                match item {
+                    TrayMenuItem::Item {
+                        id,
+                        label,
+                        enabled,
+                        checked,
+                    } => {
+                        ids.push(id.clone());
+                        let mut flags = MF_STRING;
+                        if !enabled {
+                            flags |= MF_GRAYED;
+                        }
+                        if *checked {
+                            flags |= MF_CHECKED;
+                        }
+                        let label: Vec<u16> = label.encode_utf16().chain([0]).collect();
+                        // SAFETY: label is null terminated, command IDs start at 1.
+                        unsafe { AppendMenuW(menu, flags, ids.len(), label.as_ptr()) };
+                    }
+                    TrayMenuItem::Submenu { label, enabled, items } => {
+                        // SAFETY: function has no preconditions.
+                        let submenu = unsafe { CreatePopupMenu() };
+                        append_items(submenu, items, ids);
+                        let mut flags = MF_POPUP;
+                        if !enabled {
+                            flags |= MF_GRAYED;
+                        }
+                        let label: Vec<u16> = label.encode_utf16().chain([0]).collect();
+                        // SAFETY: label is null terminated, submenu is destroyed with the parent menu.
+                        unsafe { AppendMenuW(menu, flags, submenu as usize, label.as_ptr()) };
+                    }
+                    TrayMenuItem::Separator => {
+                        // SAFETY: separators have no text.
+                        unsafe { AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null()) };
+                    }
+                }
+            }
+        }
+
+        let mut ids = vec![];
+        // SAFETY: menu is destroyed after tracking, the window must be foreground for the menu to close on focus loss.
+        let cmd = unsafe {
+            let menu = CreatePopupMenu();
+            append_items(menu, items, &mut ids);
+
+            let mut pos = POINT { x: 0, y: 0 };
+            GetCursorPos(&mut pos);
+            SetForegroundWindow(hwnd);
+            let cmd = TrackPopupMenu(
+                menu,
+                TPM_RETURNCMD | TPM_NONOTIFY | TPM_RIGHTBUTTON,
+                pos.x,
+                pos.y,
+                0,
+                hwnd,
+                std::ptr::null(),
+            );
+            PostMessageW(hwnd, WM_NULL, 0, 0);
+            DestroyMenu(menu);
+            cmd
+        };
+
+        if cmd > 0 {
+            ids.into_iter().nth(cmd as usize - 1)
+        } else {
+            None
+        }
+    }
+
+    unsafe extern "system" fn tray_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if msg == WM_TRAY_ICON {
+            let id = TrayIconId::from_raw(wparam as u32);
+            let click = match lparam as u32 {
+                WM_LBUTTONUP => Some((MouseButton::Left, false)),
+                WM_LBUTTONDBLCLK => Some((MouseButton::Left, true)),
+                WM_RBUTTONUP => Some((MouseButton::Right, false)),
+                WM_MBUTTONUP => Some((MouseButton::Middle, false)),
+                _ => None,
+            };
+            if let Some((button, double)) = click {
+                // state is not borrowed while the menu is open, the menu loop dispatches messages
+                let target = TRAY.with(|t| {
+                    let t = t.try_borrow().ok()?;
+                    let t = t.as_ref()?;
+                    let (request, _) = t.icons.iter().find(|(r, _)| r.id == id)?;
+                    Some((t.app_sender.clone(), request.menu.clone()))
+                });
+                if let Some((sender, menu)) = target {
+                    let _ = sender.send(AppEvent::Notify(Event::TrayIconClicked { tray: id, button, double }));
+                    if button == MouseButton::Right && !menu.is_empty() {
+                        if let Some(item) = show_menu(hwnd, &menu) {
+                            let _ = sender.send(AppEvent::Notify(Event::TrayMenuItemClicked { tray: id, item }));
+                        }
+                    }
+                }
+            }
+            return 0;
+        }
+
+        let is_taskbar_created = TRAY.with(|t| {
+            t.try_borrow()
+                .ok()
+                .and_then(|t| t.as_ref().map(|t| t.taskbar_created != 0 && t.taskbar_created == msg))
+                .unwrap_or(false)
+        });
+        if is_taskbar_created {
+            TRAY.with_borrow(|t| {
+                if let Some(t) = t {
+                    for (request, hicon) in &t.icons {
+                        let _ = notify_icon(t.hwnd, NIM_ADD, request, *hicon);
+                    }
+                }
+            });
+            return 0;
+        }
+
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sni_tray {
+    use zbus::{blocking::Connection, interface, object_server::SignalContext, zvariant::OwnedObjectPath};
+    use zng_view_api::{
+        mouse::MouseButton,
+        tray::{TrayIconError, TrayIconId, TrayIconRequest, TrayMenuItem},
+        window::AppMenuItem,
+        Event,
+    };
+
+    use crate::{
+        app_menu::dbus_menu::{DbusMenu, MenuOwner},
+        image_cache::Image,
+        AppEvent, AppEventSender,
+    };
+
+    const WATCHER: &str = "org.kde.StatusNotifierWatcher";
+    const ITEM_PATH: &str = "/StatusNotifierItem";
+    const MENU_PATH: &str = "/StatusNotifierItem/Menu";
+
+    /// ARGB32 images, width, height and pixels.
+    type Pixmaps = Vec<(i32, i32, Vec<u8>)>;
+
+    /// Exported tray icons, each icon has its own session bus connection.
+    ///
+    /// The tray host removes the icon when the connection closes.
+    #[derive(Default)]
+    pub struct SniTrays {
+        icons: Vec<(TrayIconId, Connection)>,
+    }
+    impl SniTrays {
+        pub fn set(&mut self, app_sender: &AppEventSender, request: TrayIconRequest, icon: Option<&Image>) -> Result<(), TrayIconError> {
+            let menu = app_menu_items(&request.menu);
+            let pixmaps: Pixmaps = icon.and_then(icon_pixmap).into_iter().collect();
+
+            if let Some((_, connection)) = self.icons.iter().find(|(id, _)| *id == request.id) {
+                let server = connection.object_server();
+
+                let item = server.interface::<_, StatusNotifierItem>(ITEM_PATH).map_err(map_err)?;
+                {
+                    let mut item = item.get_mut();
+                    item.tooltip = request.tooltip.to_string();
+                    item.pixmaps = pixmaps;
+                    item.has_menu = !menu.is_empty();
+                }
+                zbus::block_on(StatusNotifierItem::new_icon(item.signal_context())).map_err(map_err)?;
+                zbus::block_on(StatusNotifierItem::new_tool_tip(item.signal_context())).map_err(map_err)?;
+
+                let menu_iface = server.interface::<_, DbusMenu>(MENU_PATH).map_err(map_err)?;
+                let revision = menu_iface.get_mut().update(&menu);
+                zbus::block_on(DbusMenu::layout_updated(menu_iface.signal_context(), revision, 0)).map_err(map_err)?;
+            } else {
+                let connection = Connection::session().map_err(map_err)?;
+
+                // KDE Plasma and most panels provide the watcher, GNOME requires the AppIndicator extension
+                let has_watcher = connection
+                    .call_method(
+                        Some("org.freedesktop.DBus"),
+                        "/org/freedesktop/DBus",
+                        Some("org.freedesktop.DBus"),
+                        "NameHasOwner",
+                        &(WATCHER,),
+                    )
+                    .and_then(|r| r.body().deserialize::<bool>())
+                    .unwrap_or(false);
+                if !has_watcher {
+                    return Err(TrayIconError::NotSupported);
+                }
+
+                connection
+                    .object_server()
+                    .at(MENU_PATH, DbusMenu::new(app_sender.clone(), MenuOwner::Tray(request.id), &menu))
+                    .map_err(map_err)?;
+                let item = StatusNotifierItem {
+                    app_sender: app_sender.clone(),
+                    id: request.id,
+                    tooltip: request.tooltip.to_string(),
+                    pixmaps,
+                    has_menu: !menu.is_empty(),
+                };
+                connection.object_server().at(ITEM_PATH, item).map_err(map_err)?;
+
+                // the host reads the item at `ITEM_PATH` of the connection unique name
+                let service = connection.unique_name().map(|n| n.to_string()).unwrap_or_default();
+                connection
+                    .call_method(
+                        Some(WATCHER),
+                        "/StatusNotifierWatcher",
+                        Some(WATCHER),
+                        "RegisterStatusNotifierItem",
+                        &(service,),
+                    )
+                    .map_err(map_err)?;
+
+                self.icons.push((request.id, connection));
+            }
+            Ok(())
+        }
+
+        pub fn close(&mut self, id: TrayIconId) {
+            self.icons.retain(|(i, _)| *i != id);
+        }
+    }
+
+    fn map_err(e: zbus::Error) -> TrayIconError {
+        TrayIconError::Other(e.to_string().into())
+    }
+
+    fn app_menu_items(items: &[TrayMenuItem]) -> Vec<AppMenuItem> {
+        items
+            .iter()
+            .map(|item| match item {
+                TrayMenuItem::Item {
+                    id,
+                    label,
+                    enabled,
+                    checked,
+                } => AppMenuItem::Item {
+                    id: id.clone(),
+                    label: label.clone(),
+                    enabled: *enabled,
+                    checked: *checked,
+                    shortcut: None,
+                },
+                TrayMenuItem::Submenu { label, enabled, items } => AppMenuItem::Submenu {
+                    label: label.clone(),
+                    enabled: *enabled,
+                    items: app_menu_items(items),
+                },
+                TrayMenuItem::Separator => AppMenuItem::Separator,
+            })
+            .collect()
+    }
+
+    fn icon_pixmap(img: &Image) -> Option<(i32, i32, Vec<u8>)> {
+        let size = img.size();
+        if size.width.0 <= 0 || size.height.0 <= 0 || img.descriptor().format != webrender::api::ImageFormat::BGRA8 {
+            return None;
+        }
+        // BGRA to ARGB32 in network byte order
+        let pixels = img.pixels().chunks_exact(4).flat_map(|c| [c[3], c[2], c[1], c[0]]).collect();
+        Some((size.width.0, size.height.0, pixels))
+    }
+
+    /// Implements `org.kde.StatusNotifierItem` for one tray icon.
+    struct StatusNotifierItem {
+        app_sender: AppEventSender,
+        id: TrayIconId,
+        tooltip: String,
+        pixmaps: Pixmaps,
+        has_menu: bool,
+    }
+    impl StatusNotifierItem {
+        fn click(&self, button: MouseButton) {
+            let _ = self.app_sender.send(AppEvent::Notify(Event::TrayIconClicked {
+                tray: self.id,
+                button,
+                double: false,
+            }));
+        }
+    }
+    #[interface(name = "org.kde.StatusNotifierItem")]
+    impl StatusNotifierItem {
+        fn activate(&self, _x: i32, _y: i32) {
+            self.click(MouseButton::Left);
+        }
+
+        fn secondary_activate(&self, _x: i32, _y: i32) {
+            self.click(MouseButton::Middle);
+        }
+
+        /// Only called by the host if the item has no menu.
+        fn context_menu(&self, _x: i32, _y: i32) {
+            self.click(MouseButton::Right);
+        }
+
+        fn scroll(&self, _delta: i32, _orientation: String) {}
+
+        #[zbus(signal)]
+        async fn new_icon(ctx: &SignalContext<'_>) -> zbus::Result<()>;
+
+        #[zbus(signal)]
+        async fn new_tool_tip(ctx: &SignalContext<'_>) -> zbus::Result<()>;
+
+        #[zbus(property)]
+        fn category(&self) -> &str {
+            "ApplicationStatus"
+        }
+
+        #[zbus(property)]
+        fn id(&self) -> String {
+            zng_env::about().app.to_string()
+        }
+
+        #[zbus(property)]
+        fn title(&self) -> String {
+            zng_env::about().app.to_string()
+        }
+
+        #[zbus(property)]
+        fn status(&self) -> &str {
+            "Active"
+        }
+
+        #[zbus(property)]
+        fn window_id(&self) -> u32 {
+            0
+        }
+
+        #[zbus(property)]
+        fn icon_name(&self) -> &str {
+            ""
+        }
+
+        #[zbus(property)]
+        fn icon_pixmap(&self) -> Pixmaps {
+            self.pixmaps.clone()
+        }
+
+        #[zbus(property)]
+        fn tool_tip(&self) -> (String, Pixmaps, String, String) {
+            (String::new(), vec![], self.tooltip.clone(), String::new())
+        }
+
+        #[zbus(property)]
+        fn item_is_menu(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn menu(&self) -> OwnedObjectPath {
+            // "/NO_DBUSMENU" is the KDE convention for items without menu
+            let path = if self.has_menu { MENU_PATH } else { "/NO_DBUSMENU" };
+            OwnedObjectPath::try_from(path).unwrap()
+        }
+    }
+}
@@ -2,24 +2,28 @@ use std::any::Any;
 use std::backtrace::Backtrace;
 use std::cell::RefCell;
 use std::marker::PhantomData;
+use std::sync::Mutex;
 use std::{cell::Cell, sync::Arc};
 use std::{fmt, ops};
 
 use rayon::ThreadPoolBuilder;
+use rustc_hash::FxHashMap;
 use webrender::api as wr;
+use winit::dpi::PhysicalPosition;
 use winit::event_loop::ActiveEventLoop;
 use winit::{event::ElementState, monitor::MonitorHandle};
 use zng_txt::{ToTxt, Txt};
 use zng_unit::*;
 use zng_view_api::access::AccessNodeId;
 use zng_view_api::clipboard as clipboard_api;
+use zng_view_api::ipc::IpcBytes;
 use zng_view_api::keyboard::{KeyLocation, NativeKeyCode};
-use zng_view_api::window::{FrameCapture, FrameRequest, FrameUpdateRequest, ResizeDirection, WindowButton};
+use zng_view_api::window::{FrameCapture, FrameRequest, FrameUpdateRequest, RendererStats, RendererTrim, ResizeDirection, WindowButton};
 use zng_view_api::{
     keyboard::{Key, KeyCode, KeyState},
     mouse::{ButtonState, MouseButton, MouseScrollDelta},
     touch::{TouchForce, TouchPhase},
-    window::{CursorIcon, MonitorInfo, VideoMode},
+    window::{CursorIcon, MonitorId, MonitorInfo, VideoMode},
 };
 
 /// Sets a window subclass that calls a raw event handler.
@@ -290,16 +294,292 @@ impl CursorToWinit for CursorIcon {
 pub(crate) fn monitor_handle_to_info(handle: &MonitorHandle) -> MonitorInfo {
     let position = handle.position().to_px();
     let size = handle.size().to_px();
+    let icc_profile = crate::color_profile::monitor_icc_profile(handle);
     MonitorInfo {
         name: Txt::from_str(&handle.name().unwrap_or_default()),
         position,
         size,
+        work_area: monitor_work_area(handle).unwrap_or_else(|| PxRect::new(position, size)),
         scale_factor: Factor(handle.scale_factor() as _),
         video_modes: handle.video_modes().map(glutin_video_mode_to_video_mode).collect(),
         is_primary: false,
+        color_space: icc_profile
+            .as_deref()
+            .map(crate::color_profile::icc_color_space)
+            .unwrap_or_default(),
+        icc_profile: icc_profile.map(IpcBytes::from_vec),
     }
 }
 
+/// Monitor that was seen at least once, used to keep IDs stable when the monitor is reconnected.
+pub(crate) struct KnownMonitor {
+    pub id: MonitorId,
+    /// Hardware identifier, see [`monitor_serial`].
+    serial: Option<String>,
+    name: String,
+    position: PhysicalPosition<i32>,
+    pub scale_factor: f64,
+}
+impl KnownMonitor {
+    pub fn new(id: MonitorId, handle: &MonitorHandle, serial: Option<String>) -> Self {
+        Self {
+            id,
+            serial,
+            name: handle.name().unwrap_or_default(),
+            position: handle.position(),
+            scale_factor: handle.scale_factor(),
+        }
+    }
+
+    /// If the monitor is the same as the handle.
+    ///
+    /// Monitors are matched by the hardware identifier when available for both, otherwise by name and position.
+    pub fn matches(&self, handle: &MonitorHandle, serial: &Option<String>) -> bool {
+        match (&self.serial, serial) {
+            (Some(a), Some(b)) => a == b,
+            _ => !self.name.is_empty() && Some(&self.name) == handle.name().as_ref() && self.position == handle.position(),
+        }
+    }
+
+    /// Update the name and position, they may change between connections.
+    pub fn update(&mut self, handle: &MonitorHandle) {
+        self.name = handle.name().unwrap_or_default();
+        self.position = handle.position();
+        self.scale_factor = handle.scale_factor();
+    }
+}
+
+/// Gets a hardware identifier for the monitor.
+///
+/// On Windows this is the device interface name, that includes the EDID manufacturer and product codes and
+/// the connection instance. On macOS it is the vendor, model and serial number. On X11 it is derived from the EDID.
+#[cfg(windows)]
+pub(crate) fn monitor_serial(handle: &MonitorHandle) -> Option<String> {
+    use windows_sys::Win32::{
+        Graphics::Gdi::{EnumDisplayDevicesW, DISPLAY_DEVICEW},
+        UI::WindowsAndMessaging::EDD_GET_DEVICE_INTERFACE_NAME,
+    };
+    use winit::platform::windows::MonitorHandleExtWindows as _;
+
+    let device: Vec<u16> = handle.native_id().encode_utf16().chain([0]).collect();
+    // SAFETY: `cb` is set and the device name is null terminated.
+    unsafe {
+        let mut info: DISPLAY_DEVICEW = std::mem::zeroed();
+        info.cb = std::mem::size_of::<DISPLAY_DEVICEW>() as u32;
+        if EnumDisplayDevicesW(device.as_ptr(), 0, &mut info, EDD_GET_DEVICE_INTERFACE_NAME) == 0 {
+            return None;
+        }
+        let len = info.DeviceID.iter().position(|&c| c == 0).unwrap_or(info.DeviceID.len());
+        if len == 0 {
+            None
+        } else {
+            Some(String::from_utf16_lossy(&info.DeviceID[..len]))
+        }
+    }
+}
+
+/// Gets a hardware identifier for the monitor.
+///
+/// On Windows this is the device interface name, that includes the EDID manufacturer and product codes and
+/// the connection instance. On macOS it is the vendor, model and serial number. On X11 it is derived from the EDID.
+#[cfg(target_os = "macos")]
+pub(crate) fn monitor_serial(handle: &MonitorHandle) -> Option<String> {
+    use winit::platform::macos::MonitorHandleExtMacOS as _;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGDisplayVendorNumber(display: u32) -> u32;
+        fn CGDisplayModelNumber(display: u32) -> u32;
+        fn CGDisplaySerialNumber(display: u32) -> u32;
+    }
+
+    let display = handle.native_id();
+    // SAFETY: only queries display info, returns zero for unknown displays.
+    let (vendor, model, serial) = unsafe {
+        (
+            CGDisplayVendorNumber(display),
+            CGDisplayModelNumber(display),
+            CGDisplaySerialNumber(display),
+        )
+    };
+    if serial == 0 {
+        // built-in displays and some external displays don't have a serial number
+        None
+    } else {
+        Some(format!("{vendor:x}-{model:x}-{serial:x}"))
+    }
+}
+
+/// Gets a hardware identifier for the monitor.
+///
+/// On Windows this is the device interface name, that includes the EDID manufacturer and product codes and
+/// the connection instance. On macOS it is the vendor, model and serial number. On X11 it is derived from the EDID.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn monitor_serial(handle: &MonitorHandle) -> Option<String> {
+    use x11_dl::{xlib, xrandr};
+
+    if std::env::var_os("WAYLAND_DISPLAY").is_some_and(|d| !d.is_empty()) {
+        return None;
+    }
+
+    // winit X11 monitor ID is the RandR CRTC
+    #[allow(clippy::useless_conversion)]
+    let crtc = xrandr::RRCrtc::from(winit::platform::x11::MonitorHandleExtX11::native_id(handle));
+
+    let xlib = xlib::Xlib::open().ok()?;
+    let randr = xrandr::Xrandr::open().ok()?;
+    // SAFETY: null opens the default display, all resources are freed and the display is closed.
+    unsafe {
+        let display = (xlib.XOpenDisplay)(std::ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = (xlib.XDefaultRootWindow)(display);
+        let edid_atom = (xlib.XInternAtom)(display, c"EDID".as_ptr() as _, xlib::True);
+
+        let mut r = None;
+        let res = (randr.XRRGetScreenResourcesCurrent)(display, root);
+        if !res.is_null() && edid_atom != 0 {
+            let info = (randr.XRRGetCrtcInfo)(display, res, crtc);
+            if !info.is_null() {
+                if (*info).noutput > 0 {
+                    let output = *(*info).outputs;
+
+                    let mut actual_type = 0;
+                    let mut actual_format = 0;
+                    let (mut n, mut after) = (0, 0);
+                    let mut prop: *mut u8 = std::ptr::null_mut();
+                    let ok = (randr.XRRGetOutputProperty)(
+                        display,
+                        output,
+                        edid_atom,
+                        0,
+                        128,
+                        xlib::False,
+                        xlib::False,
+                        xlib::AnyPropertyType as _,
+                        &mut actual_type,
+                        &mut actual_format,
+                        &mut n,
+                        &mut after,
+                        &mut prop,
+                    );
+                    if ok == xlib::Success as i32 && !prop.is_null() && actual_format == 8 {
+                        r = edid_serial(std::slice::from_raw_parts(prop, n as usize));
+                    }
+                    if !prop.is_null() {
+                        (xlib.XFree)(prop as _);
+                    }
+                }
+                (randr.XRRFreeCrtcInfo)(info);
+            }
+            (randr.XRRFreeScreenResources)(res);
+        }
+        (xlib.XCloseDisplay)(display);
+        r
+    }
+}
+
+/// Gets a hardware identifier for the monitor.
+#[cfg(not(any(
+    windows,
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+pub(crate) fn monitor_serial(handle: &MonitorHandle) -> Option<String> {
+    let _ = handle;
+    None
+}
+
+/// Identifier from the EDID manufacturer, product and serial fields, plus the serial string descriptor if present.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn edid_serial(edid: &[u8]) -> Option<String> {
+    use std::fmt::Write as _;
+
+    if edid.len() < 128 || edid[..8] != [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00] {
+        return None;
+    }
+    let mut r = String::new();
+    for b in &edid[8..16] {
+        let _ = write!(&mut r, "{b:02x}");
+    }
+    for d in edid[54..126].chunks_exact(18) {
+        if d[..5] == [0, 0, 0, 0xFF, 0] {
+            let serial = String::from_utf8_lossy(&d[5..]);
+            r.push('-');
+            r.push_str(serial.trim_end_matches(['\n', ' ']));
+        }
+    }
+    Some(r)
+}
+
+/// Gets the monitor area not covered by system bars.
+#[cfg(windows)]
+fn monitor_work_area(handle: &MonitorHandle) -> Option<PxRect> {
+    use windows_sys::Win32::Graphics::Gdi::{GetMonitorInfoW, MONITORINFO};
+    use winit::platform::windows::MonitorHandleExtWindows as _;
+
+    // SAFETY: `cbSize` is set and the struct is fully initialized.
+    unsafe {
+        let mut info: MONITORINFO = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+        if GetMonitorInfoW(handle.hmonitor() as _, &mut info) == 0 {
+            return None;
+        }
+        let r = info.rcWork;
+        Some(PxRect::new(
+            PxPoint::new(Px(r.left), Px(r.top)),
+            PxSize::new(Px(r.right - r.left), Px(r.bottom - r.top)),
+        ))
+    }
+}
+
+/// Gets the monitor area not covered by system bars.
+#[cfg(target_os = "macos")]
+fn monitor_work_area(handle: &MonitorHandle) -> Option<PxRect> {
+    use winit::platform::macos::MonitorHandleExtMacOS as _;
+
+    let screen = handle.ns_screen()? as *const objc2_app_kit::NSScreen;
+    // SAFETY: winit returns a valid NSScreen pointer, it is only used in this call.
+    let (frame, visible) = unsafe { ((*screen).frame(), (*screen).visibleFrame()) };
+
+    // Cocoa coordinates are bottom-up, compute the insets relative to the screen frame.
+    let fct = handle.scale_factor();
+    let left = (visible.origin.x - frame.origin.x) * fct;
+    let top = ((frame.origin.y + frame.size.height) - (visible.origin.y + visible.size.height)) * fct;
+    let position = handle.position();
+    Some(PxRect::new(
+        PxPoint::new(Px(position.x + left.round() as i32), Px(position.y + top.round() as i32)),
+        PxSize::new(
+            Px((visible.size.width * fct).round() as i32),
+            Px((visible.size.height * fct).round() as i32),
+        ),
+    ))
+}
+
+/// Gets the monitor area not covered by system bars.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn monitor_work_area(handle: &MonitorHandle) -> Option<PxRect> {
+    let _ = handle;
+    None
+}
+
 pub(crate) fn glutin_video_mode_to_video_mode(v: winit::monitor::VideoModeHandle) -> VideoMode {
     let size = v.size();
     VideoMode {
@@ -375,6 +655,20 @@ pub(crate) fn winit_to_hwnd(window: &winit::window::Window) -> isize {
     }
 }
 
+#[cfg(target_os = "macos")]
+pub(crate) fn winit_to_ns_window(window: &winit::window::Window) -> Option<objc2::rc::Retained<objc2_app_kit::NSWindow>> {
+    use raw_window_handle::HasWindowHandle as _;
+
+    match window.window_handle().ok()?.as_raw() {
+        raw_window_handle::RawWindowHandle::AppKit(h) => {
+            // SAFETY: winit provides a valid NSView pointer for the lifetime of the window.
+            let view = unsafe { &*(h.ns_view.as_ptr() as *const objc2_app_kit::NSView) };
+            view.window()
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn winit_key_location_to_zng(t: winit::keyboard::KeyLocation) -> KeyLocation {
     match t {
         winit::keyboard::KeyLocation::Standard => KeyLocation::Standard,
@@ -915,6 +1209,215 @@ pub(crate) fn winit_key_code_to_key_code(key: WinitKeyCode) -> KeyCode {
     }
 }
 
+#[cfg(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn key_code_to_winit_key_code(key: KeyCode) -> Option<WinitKeyCode> {
+    let k = match key {
+        KeyCode::Backquote => WinitKeyCode::Backquote,
+        KeyCode::Backslash => WinitKeyCode::Backslash,
+        KeyCode::BracketLeft => WinitKeyCode::BracketLeft,
+        KeyCode::BracketRight => WinitKeyCode::BracketRight,
+        KeyCode::Comma => WinitKeyCode::Comma,
+        KeyCode::Digit0 => WinitKeyCode::Digit0,
+        KeyCode::Digit1 => WinitKeyCode::Digit1,
+        KeyCode::Digit2 => WinitKeyCode::Digit2,
+        KeyCode::Digit3 => WinitKeyCode::Digit3,
+        KeyCode::Digit4 => WinitKeyCode::Digit4,
+        KeyCode::Digit5 => WinitKeyCode::Digit5,
+        KeyCode::Digit6 => WinitKeyCode::Digit6,
+        KeyCode::Digit7 => WinitKeyCode::Digit7,
+        KeyCode::Digit8 => WinitKeyCode::Digit8,
+        KeyCode::Digit9 => WinitKeyCode::Digit9,
+        KeyCode::Equal => WinitKeyCode::Equal,
+        KeyCode::IntlBackslash => WinitKeyCode::IntlBackslash,
+        KeyCode::IntlRo => WinitKeyCode::IntlRo,
+        KeyCode::IntlYen => WinitKeyCode::IntlYen,
+        KeyCode::KeyA => WinitKeyCode::KeyA,
+        KeyCode::KeyB => WinitKeyCode::KeyB,
+        KeyCode::KeyC => WinitKeyCode::KeyC,
+        KeyCode::KeyD => WinitKeyCode::KeyD,
+        KeyCode::KeyE => WinitKeyCode::KeyE,
+        KeyCode::KeyF => WinitKeyCode::KeyF,
+        KeyCode::KeyG => WinitKeyCode::KeyG,
+        KeyCode::KeyH => WinitKeyCode::KeyH,
+        KeyCode::KeyI => WinitKeyCode::KeyI,
+        KeyCode::KeyJ => WinitKeyCode::KeyJ,
+        KeyCode::KeyK => WinitKeyCode::KeyK,
+        KeyCode::KeyL => WinitKeyCode::KeyL,
+        KeyCode::KeyM => WinitKeyCode::KeyM,
+        KeyCode::KeyN => WinitKeyCode::KeyN,
+        KeyCode::KeyO => WinitKeyCode::KeyO,
+        KeyCode::KeyP => WinitKeyCode::KeyP,
+        KeyCode::KeyQ => WinitKeyCode::KeyQ,
+        KeyCode::KeyR => WinitKeyCode::KeyR,
+        KeyCode::KeyS => WinitKeyCode::KeyS,
+        KeyCode::KeyT => WinitKeyCode::KeyT,
+        KeyCode::KeyU => WinitKeyCode::KeyU,
+        KeyCode::KeyV => WinitKeyCode::KeyV,
+        KeyCode::KeyW => WinitKeyCode::KeyW,
+        KeyCode::KeyX => WinitKeyCode::KeyX,
+        KeyCode::KeyY => WinitKeyCode::KeyY,
+        KeyCode::KeyZ => WinitKeyCode::KeyZ,
+        KeyCode::Minus => WinitKeyCode::Minus,
+        KeyCode::Period => WinitKeyCode::Period,
+        KeyCode::Quote => WinitKeyCode::Quote,
+        KeyCode::Semicolon => WinitKeyCode::Semicolon,
+        KeyCode::Slash => WinitKeyCode::Slash,
+        KeyCode::AltLeft => WinitKeyCode::AltLeft,
+        KeyCode::AltRight => WinitKeyCode::AltRight,
+        KeyCode::Backspace => WinitKeyCode::Backspace,
+        KeyCode::CapsLock => WinitKeyCode::CapsLock,
+        KeyCode::ContextMenu => WinitKeyCode::ContextMenu,
+        KeyCode::CtrlLeft => WinitKeyCode::ControlLeft,
+        KeyCode::CtrlRight => WinitKeyCode::ControlRight,
+        KeyCode::Enter => WinitKeyCode::Enter,
+        KeyCode::SuperLeft => WinitKeyCode::SuperLeft,
+        KeyCode::SuperRight => WinitKeyCode::SuperRight,
+        KeyCode::ShiftLeft => WinitKeyCode::ShiftLeft,
+        KeyCode::ShiftRight => WinitKeyCode::ShiftRight,
+        KeyCode::Space => WinitKeyCode::Space,
+        KeyCode::Tab => WinitKeyCode::Tab,
+        KeyCode::Convert => WinitKeyCode::Convert,
+        KeyCode::KanaMode => WinitKeyCode::KanaMode,
+        KeyCode::Lang1 => WinitKeyCode::Lang1,
+        KeyCode::Lang2 => WinitKeyCode::Lang2,
+        KeyCode::Lang3 => WinitKeyCode::Lang3,
+        KeyCode::Lang4 => WinitKeyCode::Lang4,
+        KeyCode::Lang5 => WinitKeyCode::Lang5,
+        KeyCode::NonConvert => WinitKeyCode::NonConvert,
+        KeyCode::Delete => WinitKeyCode::Delete,
+        KeyCode::End => WinitKeyCode::End,
+        KeyCode::Help => WinitKeyCode::Help,
+        KeyCode::Home => WinitKeyCode::Home,
+        KeyCode::Insert => WinitKeyCode::Insert,
+        KeyCode::PageDown => WinitKeyCode::PageDown,
+        KeyCode::PageUp => WinitKeyCode::PageUp,
+        KeyCode::ArrowDown => WinitKeyCode::ArrowDown,
+        KeyCode::ArrowLeft => WinitKeyCode::ArrowLeft,
+        KeyCode::ArrowRight => WinitKeyCode::ArrowRight,
+        KeyCode::ArrowUp => WinitKeyCode::ArrowUp,
+        KeyCode::NumLock => WinitKeyCode::NumLock,
+        KeyCode::Numpad0 => WinitKeyCode::Numpad0,
+        KeyCode::Numpad1 => WinitKeyCode::Numpad1,
+        KeyCode::Numpad2 => WinitKeyCode::Numpad2,
+        KeyCode::Numpad3 => WinitKeyCode::Numpad3,
+        KeyCode::Numpad4 => WinitKeyCode::Numpad4,
+        KeyCode::Numpad5 => WinitKeyCode::Numpad5,
+        KeyCode::Numpad6 => WinitKeyCode::Numpad6,
+        KeyCode::Numpad7 => WinitKeyCode::Numpad7,
+        KeyCode::Numpad8 => WinitKeyCode::Numpad8,
+        KeyCode::Numpad9 => WinitKeyCode::Numpad9,
+        KeyCode::NumpadAdd => WinitKeyCode::NumpadAdd,
+        KeyCode::NumpadBackspace => WinitKeyCode::NumpadBackspace,
+        KeyCode::NumpadClear => WinitKeyCode::NumpadClear,
+        KeyCode::NumpadClearEntry => WinitKeyCode::NumpadClearEntry,
+        KeyCode::NumpadComma => WinitKeyCode::NumpadComma,
+        KeyCode::NumpadDecimal => WinitKeyCode::NumpadDecimal,
+        KeyCode::NumpadDivide => WinitKeyCode::NumpadDivide,
+        KeyCode::NumpadEnter => WinitKeyCode::NumpadEnter,
+        KeyCode::NumpadEqual => WinitKeyCode::NumpadEqual,
+        KeyCode::NumpadHash => WinitKeyCode::NumpadHash,
+        KeyCode::NumpadMemoryAdd => WinitKeyCode::NumpadMemoryAdd,
+        KeyCode::NumpadMemoryClear => WinitKeyCode::NumpadMemoryClear,
+        KeyCode::NumpadMemoryRecall => WinitKeyCode::NumpadMemoryRecall,
+        KeyCode::NumpadMemoryStore => WinitKeyCode::NumpadMemoryStore,
+        KeyCode::NumpadMemorySubtract => WinitKeyCode::NumpadMemorySubtract,
+        KeyCode::NumpadMultiply => WinitKeyCode::NumpadMultiply,
+        KeyCode::NumpadParenLeft => WinitKeyCode::NumpadParenLeft,
+        KeyCode::NumpadParenRight => WinitKeyCode::NumpadParenRight,
+        KeyCode::NumpadStar => WinitKeyCode::NumpadStar,
+        KeyCode::NumpadSubtract => WinitKeyCode::NumpadSubtract,
+        KeyCode::Escape => WinitKeyCode::Escape,
+        KeyCode::Fn => WinitKeyCode::Fn,
+        KeyCode::FnLock => WinitKeyCode::FnLock,
+        KeyCode::PrintScreen => WinitKeyCode::PrintScreen,
+        KeyCode::ScrollLock => WinitKeyCode::ScrollLock,
+        KeyCode::Pause => WinitKeyCode::Pause,
+        KeyCode::BrowserBack => WinitKeyCode::BrowserBack,
+        KeyCode::BrowserFavorites => WinitKeyCode::BrowserFavorites,
+        KeyCode::BrowserForward => WinitKeyCode::BrowserForward,
+        KeyCode::BrowserHome => WinitKeyCode::BrowserHome,
+        KeyCode::BrowserRefresh => WinitKeyCode::BrowserRefresh,
+        KeyCode::BrowserSearch => WinitKeyCode::BrowserSearch,
+        KeyCode::BrowserStop => WinitKeyCode::BrowserStop,
+        KeyCode::Eject => WinitKeyCode::Eject,
+        KeyCode::LaunchApp1 => WinitKeyCode::LaunchApp1,
+        KeyCode::LaunchApp2 => WinitKeyCode::LaunchApp2,
+        KeyCode::LaunchMail => WinitKeyCode::LaunchMail,
+        KeyCode::MediaPlayPause => WinitKeyCode::MediaPlayPause,
+        KeyCode::MediaSelect => WinitKeyCode::MediaSelect,
+        KeyCode::MediaStop => WinitKeyCode::MediaStop,
+        KeyCode::MediaTrackNext => WinitKeyCode::MediaTrackNext,
+        KeyCode::MediaTrackPrevious => WinitKeyCode::MediaTrackPrevious,
+        KeyCode::Power => WinitKeyCode::Power,
+        KeyCode::Sleep => WinitKeyCode::Sleep,
+        KeyCode::AudioVolumeDown => WinitKeyCode::AudioVolumeDown,
+        KeyCode::AudioVolumeMute => WinitKeyCode::AudioVolumeMute,
+        KeyCode::AudioVolumeUp => WinitKeyCode::AudioVolumeUp,
+        KeyCode::WakeUp => WinitKeyCode::WakeUp,
+        KeyCode::Meta => WinitKeyCode::Meta,
+        KeyCode::Hyper => WinitKeyCode::Hyper,
+        KeyCode::Turbo => WinitKeyCode::Turbo,
+        KeyCode::Abort => WinitKeyCode::Abort,
+        KeyCode::Resume => WinitKeyCode::Resume,
+        KeyCode::Suspend => WinitKeyCode::Suspend,
+        KeyCode::Again => WinitKeyCode::Again,
+        KeyCode::Copy => WinitKeyCode::Copy,
+        KeyCode::Cut => WinitKeyCode::Cut,
+        KeyCode::Find => WinitKeyCode::Find,
+        KeyCode::Open => WinitKeyCode::Open,
+        KeyCode::Paste => WinitKeyCode::Paste,
+        KeyCode::Props => WinitKeyCode::Props,
+        KeyCode::Select => WinitKeyCode::Select,
+        KeyCode::Undo => WinitKeyCode::Undo,
+        KeyCode::Hiragana => WinitKeyCode::Hiragana,
+        KeyCode::Katakana => WinitKeyCode::Katakana,
+        KeyCode::F1 => WinitKeyCode::F1,
+        KeyCode::F2 => WinitKeyCode::F2,
+        KeyCode::F3 => WinitKeyCode::F3,
+        KeyCode::F4 => WinitKeyCode::F4,
+        KeyCode::F5 => WinitKeyCode::F5,
+        KeyCode::F6 => WinitKeyCode::F6,
+        KeyCode::F7 => WinitKeyCode::F7,
+        KeyCode::F8 => WinitKeyCode::F8,
+        KeyCode::F9 => WinitKeyCode::F9,
+        KeyCode::F10 => WinitKeyCode::F10,
+        KeyCode::F11 => WinitKeyCode::F11,
+        KeyCode::F12 => WinitKeyCode::F12,
+        KeyCode::F13 => WinitKeyCode::F13,
+        KeyCode::F14 => WinitKeyCode::F14,
+        KeyCode::F15 => WinitKeyCode::F15,
+        KeyCode::F16 => WinitKeyCode::F16,
+        KeyCode::F17 => WinitKeyCode::F17,
+        KeyCode::F18 => WinitKeyCode::F18,
+        KeyCode::F19 => WinitKeyCode::F19,
+        KeyCode::F20 => WinitKeyCode::F20,
+        KeyCode::F21 => WinitKeyCode::F21,
+        KeyCode::F22 => WinitKeyCode::F22,
+        KeyCode::F23 => WinitKeyCode::F23,
+        KeyCode::F24 => WinitKeyCode::F24,
+        KeyCode::F25 => WinitKeyCode::F25,
+        KeyCode::F26 => WinitKeyCode::F26,
+        KeyCode::F27 => WinitKeyCode::F27,
+        KeyCode::F28 => WinitKeyCode::F28,
+        KeyCode::F29 => WinitKeyCode::F29,
+        KeyCode::F30 => WinitKeyCode::F30,
+        KeyCode::F31 => WinitKeyCode::F31,
+        KeyCode::F32 => WinitKeyCode::F32,
+        KeyCode::F33 => WinitKeyCode::F33,
+        KeyCode::F34 => WinitKeyCode::F34,
+        KeyCode::F35 => WinitKeyCode::F35,
+        _ => return None,
+    };
+    Some(k)
+}
+
 use winit::keyboard::PhysicalKey as WinitPhysicalKey;
 pub(crate) fn winit_physical_key_to_key_code(key: WinitPhysicalKey) -> KeyCode {
     match key {
@@ -1132,6 +1635,73 @@ pub(crate) fn wr_workers() -> Arc<rayon::ThreadPool> {
     Arc::new(worker.unwrap())
 }
 
+/// Heap size function for Webrender memory reports.
+///
+/// Returns the usable size of the allocation, assumes the system allocator is used.
+pub(crate) unsafe extern "C" fn wr_size_of_op(ptr: *const std::ffi::c_void) -> usize {
+    if ptr.is_null() {
+        return 0;
+    }
+
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::Memory::{GetProcessHeap, HeapSize};
+        match HeapSize(GetProcessHeap(), 0, ptr) {
+            usize::MAX => 0,
+            s => s,
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        libc::malloc_size(ptr)
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        libc::malloc_usable_size(ptr as *mut _)
+    }
+
+    #[cfg(not(any(windows, unix)))]
+    {
+        0
+    }
+}
+
+/// Gets the renderer memory stats, `glyph_cache` is the estimated glyphs memory, it is subtracted from the atlas textures.
+pub(crate) fn wr_renderer_stats(renderer: &webrender::Renderer, glyph_cache: u64) -> RendererStats {
+    let r = renderer.report_memory(std::ptr::null_mut());
+    let atlas_textures = r.atlas_textures as u64;
+    let glyph_cache = glyph_cache.min(atlas_textures);
+    RendererStats {
+        atlas_textures: atlas_textures - glyph_cache,
+        glyph_cache,
+        standalone_textures: r.standalone_textures as u64,
+        picture_tile_textures: r.picture_tile_textures as u64,
+        render_target_textures: r.render_target_textures as u64,
+        gpu_cache_textures: r.gpu_cache_textures as u64,
+        other_textures: (r.vertex_data_textures
+            + r.depth_target_textures
+            + r.texture_upload_pbos
+            + r.swap_chain
+            + r.render_texture_hosts
+            + r.upload_staging_textures) as u64,
+        heap: (r.gpu_cache_cpu_mirror + r.render_tasks) as u64,
+    }
+}
+
+pub(crate) fn wr_trim(api: &webrender::RenderApi, trim: RendererTrim) {
+    match trim {
+        RendererTrim::Glyphs => api.send_debug_cmd(webrender::DebugCommand::ClearCaches(
+            webrender::ClearCache::GLYPHS | webrender::ClearCache::GLYPH_DIMENSIONS,
+        )),
+        RendererTrim::All => {
+            api.notify_memory_pressure();
+            api.send_debug_cmd(webrender::DebugCommand::ClearCaches(webrender::ClearCache::all()));
+        }
+    }
+}
+
 #[cfg(not(windows))]
 pub(crate) fn arboard_to_clip(e: arboard::Error) -> clipboard_api::ClipboardError {
     match e {
@@ -1161,14 +1731,36 @@ fn accesskit_point_to_px(p: accesskit::Point) -> PxPoint {
     PxPoint::new(accesskit_to_px(p.x), accesskit_to_px(p.y))
 }
 
+/// Flag set in the ID of inline text box nodes generated for text nodes with [`AccessState::TextSelection`].
+///
+/// [`AccessState::TextSelection`]: zng_view_api::access::AccessState::TextSelection
+const ACCESS_TEXT_BOX_FLAG: u64 = 1 << 63;
+
+/// ID of the live region node generated for announcements.
+const ACCESS_ANNOUNCE_ID: accesskit::NodeId = accesskit::NodeId(u64::MAX);
+
+/// AccessKit tree state, shared with the action handler.
+pub(crate) type AccessKitStateRef = Arc<Mutex<AccessKitState>>;
+
+#[derive(Default)]
+pub(crate) struct AccessKitState {
+    /// Character byte lengths of the inline text box of each text node, used
+    /// to convert AccessKit character indexes back to string byte indexes.
+    text_boxes: FxHashMap<AccessNodeId, Box<[u8]>>,
+    root: Option<AccessNodeId>,
+    focused: Option<AccessNodeId>,
+    announce: Option<(Txt, zng_view_api::access::AccessLivePriority)>,
+}
+
 pub(crate) fn accesskit_to_event(
     window_id: zng_view_api::window::WindowId,
     request: accesskit::ActionRequest,
+    state: &AccessKitStateRef,
 ) -> Option<zng_view_api::Event> {
     use accesskit::Action;
     use zng_view_api::access::*;
 
-    let target = AccessNodeId(request.target.0);
+    let target = AccessNodeId(request.target.0 & !ACCESS_TEXT_BOX_FLAG);
 
     Some(zng_view_api::Event::AccessCommand {
         window: window_id,
@@ -1227,9 +1819,18 @@ pub(crate) fn accesskit_to_event(
             }
             Action::SetTextSelection => {
                 if let Some(accesskit::ActionData::SetTextSelection(s)) = request.data {
+                    let state = state.lock().unwrap();
+                    let to_byte_index = |p: accesskit::TextPosition| {
+                        let id = AccessNodeId(p.node.0 & !ACCESS_TEXT_BOX_FLAG);
+                        let i = match state.text_boxes.get(&id) {
+                            Some(lens) => lens.iter().take(p.character_index).map(|&l| l as usize).sum(),
+                            None => p.character_index,
+                        };
+                        (id, i)
+                    };
                     AccessCmd::SelectText {
-                        start: (AccessNodeId(s.anchor.node.0), s.anchor.character_index),
-                        caret: (AccessNodeId(s.focus.node.0), s.focus.character_index),
+                        start: to_byte_index(s.anchor),
+                        caret: to_byte_index(s.focus),
                     }
                 } else {
                     return None;
@@ -1244,11 +1845,21 @@ pub(crate) fn accesskit_to_event(
     })
 }
 
-pub(crate) fn access_tree_update_to_kit(update: zng_view_api::access::AccessTreeUpdate) -> accesskit::TreeUpdate {
+pub(crate) fn access_tree_update_to_kit(
+    update: zng_view_api::access::AccessTreeUpdate,
+    state: &AccessKitStateRef,
+) -> accesskit::TreeUpdate {
     let mut nodes = Vec::with_capacity(update.updates.iter().map(|t| t.len()).sum());
 
+    let mut state = state.lock().unwrap();
+    if let Some(root) = update.full_root {
+        state.text_boxes.clear();
+        state.root = Some(root);
+    }
+    state.focused = Some(update.focused);
+
     for update in update.updates {
-        access_node_to_kit(update.root(), &mut nodes);
+        access_node_to_kit(update.root(), &mut nodes, &mut state);
     }
 
     accesskit::TreeUpdate {
@@ -1261,6 +1872,7 @@ pub(crate) fn access_tree_update_to_kit(update: zng_view_api::access::AccessTree
 fn access_node_to_kit(
     node: zng_view_api::access::AccessNodeRef,
     output: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    state: &mut AccessKitState,
 ) -> accesskit::NodeId {
     let node_id = access_id_to_kit(node.id);
     let node_role = node.role.map(access_role_to_kit).unwrap_or(accesskit::Role::Unknown);
@@ -1342,6 +1954,10 @@ fn access_node_to_kit(
         builder.set_default_action_verb(v);
     }
 
+    // text and selection
+    let mut text = None;
+    let mut text_selection = None;
+
     // add state
     for state in &node.state {
         use zng_view_api::access::{self, AccessState::*};
@@ -1388,7 +2004,12 @@ fn access_node_to_kit(
                     builder.set_invalid(accesskit::Invalid::True)
                 }
             }
-            Label(s) => builder.set_name(s.clone().into_owned().into_boxed_str()),
+            Label(s) => {
+                builder.set_name(s.clone().into_owned().into_boxed_str());
+                if text.is_none() {
+                    text = Some(s);
+                }
+            }
             Level(n) => builder.set_level(n.get() as usize),
             Modal => builder.set_modal(),
             MultiSelectable => builder.set_multiselectable(),
@@ -1407,7 +2028,11 @@ fn access_node_to_kit(
             ValueMax(m) => builder.set_max_numeric_value(*m),
             ValueMin(m) => builder.set_min_numeric_value(*m),
             Value(v) => builder.set_numeric_value(*v),
-            ValueText(v) => builder.set_value(v.clone().into_owned().into_boxed_str()),
+            ValueText(v) => {
+                builder.set_value(v.clone().into_owned().into_boxed_str());
+                text = Some(v);
+            }
+            TextSelection { start, caret } => text_selection = Some((*start, *caret)),
             Live { indicator, atomic, busy } => {
                 builder.set_live(match indicator {
                     access::LiveIndicator::Assertive => accesskit::Live::Assertive,
@@ -1464,10 +2089,59 @@ fn access_node_to_kit(
         }
     }
 
+    // add inline text box for caret and selection
+    if let (Some(text), Some((start, caret))) = (text, text_selection) {
+        let box_id = accesskit::NodeId(node.id.0 | ACCESS_TEXT_BOX_FLAG);
+
+        let mut char_lens = Vec::with_capacity(text.len());
+        let mut start_i = None;
+        let mut caret_i = None;
+        for (i, (byte_i, c)) in text.char_indices().enumerate() {
+            if byte_i == start {
+                start_i = Some(i);
+            }
+            if byte_i == caret {
+                caret_i = Some(i);
+            }
+            char_lens.push(c.len_utf8() as u8);
+        }
+        let end_i = char_lens.len();
+
+        let mut text_box = accesskit::NodeBuilder::new(accesskit::Role::InlineTextBox);
+        text_box.set_value(text.clone().into_owned().into_boxed_str());
+        text_box.set_character_lengths(char_lens.clone());
+        if !node.size.is_empty() {
+            text_box.set_bounds(accesskit::Rect::new(0.0, 0.0, node.size.width.0 as f64, node.size.height.0 as f64));
+        }
+        output.push((box_id, text_box.build()));
+
+        builder.set_text_selection(accesskit::TextSelection {
+            anchor: accesskit::TextPosition {
+                node: box_id,
+                character_index: start_i.unwrap_or(end_i),
+            },
+            focus: accesskit::TextPosition {
+                node: box_id,
+                character_index: caret_i.unwrap_or(end_i),
+            },
+        });
+        builder.push_child(box_id);
+
+        state.text_boxes.insert(node.id, char_lens.into_boxed_slice());
+    } else {
+        state.text_boxes.remove(&node.id);
+    }
+
+    // add announcer live region
+    if state.root == Some(node.id) {
+        builder.push_child(ACCESS_ANNOUNCE_ID);
+        output.push((ACCESS_ANNOUNCE_ID, access_announce_node(state.announce.as_ref())));
+    }
+
     // add descendants
     if node.children.is_empty() {
         for child in node.children() {
-            let child_id = access_node_to_kit(child, output);
+            let child_id = access_node_to_kit(child, output, state);
             builder.push_child(child_id);
         }
     } else {
@@ -1475,7 +2149,7 @@ fn access_node_to_kit(
             builder.push_child(access_id_to_kit(*id));
         }
         for child in node.children() {
-            let _ = access_node_to_kit(child, output);
+            let _ = access_node_to_kit(child, output, state);
         }
     }
 
@@ -1484,6 +2158,40 @@ fn access_node_to_kit(
     node_id
 }
 
+/// Updates the announcer live region.
+///
+/// Returns `None` if the tree is not inited.
+pub(crate) fn access_announce_to_kit(
+    announce: Option<(Txt, zng_view_api::access::AccessLivePriority)>,
+    state: &AccessKitStateRef,
+) -> Option<accesskit::TreeUpdate> {
+    let mut state = state.lock().unwrap();
+    state.root?;
+    state.announce = announce;
+    Some(accesskit::TreeUpdate {
+        nodes: vec![(ACCESS_ANNOUNCE_ID, access_announce_node(state.announce.as_ref()))],
+        tree: None,
+        focus: access_id_to_kit(state.focused?),
+    })
+}
+fn access_announce_node(announce: Option<&(Txt, zng_view_api::access::AccessLivePriority)>) -> accesskit::Node {
+    use zng_view_api::access::AccessLivePriority;
+
+    let (txt, priority) = match announce {
+        Some((t, p)) => (t.as_str(), *p),
+        None => ("", AccessLivePriority::Polite),
+    };
+    let (role, live) = match priority {
+        AccessLivePriority::Polite => (accesskit::Role::Status, accesskit::Live::Polite),
+        AccessLivePriority::Assertive => (accesskit::Role::Alert, accesskit::Live::Assertive),
+    };
+    let mut builder = accesskit::NodeBuilder::new(role);
+    builder.set_live(live);
+    builder.set_live_atomic();
+    builder.set_name(txt);
+    builder.build()
+}
+
 fn access_id_to_kit(id: AccessNodeId) -> accesskit::NodeId {
     accesskit::NodeId(id.0)
 }
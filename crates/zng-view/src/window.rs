@@ -8,6 +8,7 @@ use std::{
         Arc,
     },
     thread,
+    time::{Duration, Instant, SystemTime},
 };
 
 use tracing::span::EnteredSpan;
@@ -25,11 +26,13 @@ use zng_txt::{ToTxt, Txt};
 use zng_unit::{DipPoint, DipRect, DipSize, DipToPx, Factor, Px, PxPoint, PxRect, PxToDip, PxVector, Rgba};
 use zng_view_api::{
     api_extension::{ApiExtensionId, ApiExtensionPayload},
+    drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId},
     font::{FontFaceId, FontId, FontOptions, FontVariationName},
-    image::{ImageId, ImageLoadedData, ImageMaskMode, ImageTextureId},
+    image::{ColorSpace, ImageId, ImageLoadedData, ImageMaskMode, ImageTextureId},
     window::{
-        CursorIcon, FocusIndicator, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, RenderMode, ResizeDirection, VideoMode,
-        WindowButton, WindowId, WindowRequest, WindowState, WindowStateAll,
+        CursorIcon, EventFramePresented, FocusIndicator, FrameCapture, FrameId, FrameRequest, FrameUpdateRequest, HitTestKind, RenderMode,
+        RendererStats, RendererTrim, ResizeDirection, SystemMenu, VideoMode, WindowButton, WindowId, WindowLayer, WindowRequest,
+        WindowState, WindowStateAll,
     },
     DeviceId, Event, ViewProcessGen,
 };
@@ -41,6 +44,7 @@ use zng_view_api::keyboard::{Key, KeyCode, KeyState};
 
 use crate::{
     display_list::{display_list_to_webrender, DisplayListCache},
+    drag_drop::DragSource,
     extensions::{
         self, BlobExtensionsImgHandler, DisplayListExtAdapter, FrameReadyArgs, RedrawArgs, RendererCommandArgs, RendererConfigArgs,
         RendererDeinitedArgs, RendererExtension, RendererInitedArgs, WindowCommandArgs, WindowConfigArgs, WindowDeinitedArgs,
@@ -92,7 +96,9 @@ pub(crate) struct Window {
     prev_monitor: Option<MonitorHandle>,
 
     visible: bool,
-    is_always_on_top: bool,
+    layer: WindowLayer,
+    #[cfg(windows)]
+    is_always_on_bottom: Arc<AtomicBool>,
     waiting_first_frame: bool,
     steal_init_focus: bool,
     init_focus_request: Option<FocusIndicator>,
@@ -104,6 +110,9 @@ pub(crate) struct Window {
     cursor_pos: DipPoint,
     cursor_device: DeviceId,
     cursor_over: bool,
+    /// Primary button press started a drag move or resize from a hit-test region.
+    #[cfg(not(windows))]
+    region_drag: bool,
 
     touch_pos: Vec<((DeviceId, u64), DipPoint)>,
 
@@ -114,16 +123,26 @@ pub(crate) struct Window {
     modal_dialog_active: Arc<AtomicBool>,
 
     access: Option<accesskit_winit::Adapter>, // None if has panicked
+    access_state: crate::util::AccessKitStateRef,
 
     ime_area: Option<DipRect>,
     #[cfg(windows)]
     ime_open: bool,
     #[cfg(windows)]
     has_shutdown_warn: bool,
+    #[cfg(windows)]
+    system_menu_ids: Arc<std::sync::Mutex<Vec<Txt>>>,
 
     cursor: Option<CursorIcon>,
     cursor_img: Option<CustomCursor>,
 
+    hit_test_regions: Arc<parking_lot::Mutex<Vec<(PxRect, HitTestKind)>>>,
+    continuous_render: bool,
+    present_notify: bool,
+    last_present: Option<Instant>,
+    refresh_interval: Option<Duration>,
+    presented: Option<EventFramePresented>,
+
     #[cfg(any(
         target_os = "linux",
         target_os = "dragonfly",
@@ -244,6 +263,9 @@ impl Window {
             tracing::warn!("ignoring `RenderMode::Software` because did not build with \"software\" feature");
             render_mode = RenderMode::Integrated;
         }
+        if !matches!(cfg.color_space, ColorSpace::Srgb | ColorSpace::Unknown) {
+            tracing::debug!("ignoring `{:?}` color space request, renderer only outputs sRGB", cfg.color_space);
+        }
 
         for (id, ext) in &mut window_exts {
             ext.configure(&mut WindowConfigArgs {
@@ -264,18 +286,30 @@ impl Window {
             !ext.is_init_only()
         });
 
+        let hit_test_regions = Arc::new(parking_lot::Mutex::new(vec![]));
+        #[cfg(windows)]
+        let is_always_on_bottom = Arc::new(AtomicBool::new(false));
+        #[cfg(windows)]
+        let system_menu_ids = Arc::new(std::sync::Mutex::new(vec![]));
+
         // * Extend the winit Windows window to not block the Alt+F4 key press.
         // * Check if the window is actually keyboard focused until first focus.
         // * Block system shutdown if a block is set.
+        // * Custom chrome hit-test regions.
+        // * Keep always-on-bottom windows at the bottom.
+        // * Custom system menu items.
         #[cfg(windows)]
         {
             let event_sender = event_sender.clone();
+            let hit_test_regions = hit_test_regions.clone();
+            let is_always_on_bottom = is_always_on_bottom.clone();
+            let system_menu_ids = system_menu_ids.clone();
 
             let mut first_focus = false;
 
             let window_id = winit_window.id();
             let hwnd = crate::util::winit_to_hwnd(&winit_window);
-            crate::util::set_raw_windows_event_handler(hwnd, u32::from_ne_bytes(*b"alf4") as _, move |_, msg, wparam, _| {
+            crate::util::set_raw_windows_event_handler(hwnd, u32::from_ne_bytes(*b"alf4") as _, move |_, msg, wparam, lparam| {
                 if !first_focus && unsafe { windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow() } == hwnd {
                     // Windows sends a `WM_SETFOCUS` when the window open, even if the user changed focus to something
                     // else before the process opens the window so that the window title bar shows the unfocused visual and
@@ -307,6 +341,10 @@ impl Window {
                             return Some(0);
                         }
                     }
+                    windows_sys::Win32::UI::WindowsAndMessaging::WM_INPUTLANGCHANGE => {
+                        // sent to all top-level windows of the thread, coalesced in the app-process.
+                        let _ = event_sender.send(AppEvent::Notify(Event::KeyboardLayoutChanged));
+                    }
                     windows_sys::Win32::UI::WindowsAndMessaging::WM_QUERYENDSESSION => {
                         let mut reason = [0u16; 256];
                         let mut reason_size = reason.len() as u32;
@@ -327,6 +365,58 @@ impl Window {
                             return Some(0);
                         }
                     }
+                    windows_sys::Win32::UI::WindowsAndMessaging::WM_NCHITTEST => {
+                        let regions = hit_test_regions.lock();
+                        if !regions.is_empty() {
+                            let mut pt = windows_sys::Win32::Foundation::POINT {
+                                x: (lparam & 0xFFFF) as i16 as i32,
+                                y: ((lparam >> 16) & 0xFFFF) as i16 as i32,
+                            };
+                            unsafe { windows_sys::Win32::Graphics::Gdi::ScreenToClient(hwnd, &mut pt) };
+                            let pt = PxPoint::new(Px(pt.x), Px(pt.y));
+                            if let Some((_, kind)) = regions.iter().find(|(r, _)| r.contains(pt)) {
+                                use windows_sys::Win32::UI::WindowsAndMessaging::*;
+                                let r = match kind {
+                                    HitTestKind::Drag => HTCAPTION,
+                                    HitTestKind::Resize(d) => match d {
+                                        ResizeDirection::East => HTRIGHT,
+                                        ResizeDirection::North => HTTOP,
+                                        ResizeDirection::NorthEast => HTTOPRIGHT,
+                                        ResizeDirection::NorthWest => HTTOPLEFT,
+                                        ResizeDirection::South => HTBOTTOM,
+                                        ResizeDirection::SouthEast => HTBOTTOMRIGHT,
+                                        ResizeDirection::SouthWest => HTBOTTOMLEFT,
+                                        ResizeDirection::West => HTLEFT,
+                                    },
+                                    HitTestKind::MinimizeButton => HTMINBUTTON,
+                                    HitTestKind::MaximizeButton => HTMAXBUTTON,
+                                    HitTestKind::CloseButton => HTCLOSE,
+                                };
+                                return Some(r as _);
+                            }
+                        }
+                    }
+                    windows_sys::Win32::UI::WindowsAndMessaging::WM_SYSCOMMAND => {
+                        let cmd = wparam & 0xFFF0;
+                        if (SYSTEM_MENU_ID_START..0xF000).contains(&cmd) {
+                            let i = (cmd - SYSTEM_MENU_ID_START) / 16;
+                            if let Some(item) = system_menu_ids.lock().unwrap().get(i).cloned() {
+                                let _ = event_sender.send(AppEvent::Notify(Event::SystemMenuItemClicked { window: id, item }));
+                                return Some(0);
+                            }
+                        }
+                    }
+                    windows_sys::Win32::UI::WindowsAndMessaging::WM_WINDOWPOSCHANGING => {
+                        if is_always_on_bottom.load(Ordering::Relaxed) {
+                            use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+                            // SAFETY: lparam is a valid WINDOWPOS pointer for this message.
+                            let pos = unsafe { &mut *(lparam as *mut WINDOWPOS) };
+                            if pos.flags & SWP_NOZORDER == 0 {
+                                pos.hwndInsertAfter = HWND_BOTTOM;
+                            }
+                        }
+                    }
                     _ => {}
                 }
 
@@ -361,6 +451,9 @@ impl Window {
             // extensions expect this to be set.
             workers: Some(crate::util::wr_workers()),
 
+            // required by `renderer_stats`.
+            size_of_op: Some(crate::util::wr_size_of_op),
+
             //panic_on_gl_error: true,
             ..Default::default()
         };
@@ -407,6 +500,7 @@ impl Window {
 
         drop(wr_scope);
 
+        let access_state = crate::util::AccessKitStateRef::default();
         let access = accesskit_winit::Adapter::with_direct_handlers(
             &winit_window,
             AccessActivateHandler {
@@ -416,6 +510,7 @@ impl Window {
             AccessActionSender {
                 id,
                 event_sender: event_sender.clone(),
+                state: access_state.clone(),
             },
             AccessDeactivateHandler { id, event_sender },
         );
@@ -445,7 +540,9 @@ impl Window {
             steal_init_focus: cfg.focus,
             init_focus_request: cfg.focus_indicator,
             visible: cfg.visible,
-            is_always_on_top: false,
+            layer: WindowLayer::Normal,
+            #[cfg(windows)]
+            is_always_on_bottom,
             taskbar_visible: true,
             movable: cfg.movable,
             pending_frames: VecDeque::new(),
@@ -454,18 +551,29 @@ impl Window {
             touch_pos: vec![],
             cursor_device: DeviceId::INVALID,
             cursor_over: false,
+            #[cfg(not(windows))]
+            region_drag: false,
             clear_color: None,
             focused: None,
             modal_dialog_active: Arc::new(AtomicBool::new(false)),
             render_mode,
             access: Some(access),
+            access_state,
             ime_area: cfg.ime_area,
             #[cfg(windows)]
             ime_open: false,
             #[cfg(windows)]
             has_shutdown_warn: false,
+            #[cfg(windows)]
+            system_menu_ids,
             cursor: None,
             cursor_img: None,
+            hit_test_regions,
+            continuous_render: false,
+            present_notify: false,
+            last_present: None,
+            refresh_interval: None,
+            presented: None,
 
             #[cfg(any(
                 target_os = "linux",
@@ -481,8 +589,8 @@ impl Window {
             win.set_inner_position(win.state.restore_rect.origin);
         }
 
-        if cfg.always_on_top {
-            win.set_always_on_top(true);
+        if cfg.layer != WindowLayer::Normal {
+            win.set_layer(cfg.layer);
         }
 
         win.cursor = cfg.cursor;
@@ -540,6 +648,10 @@ impl Window {
         self.window.id()
     }
 
+    pub fn winit_window(&self) -> &GWindow {
+        &self.window
+    }
+
     /// Latest rendered frame.
     pub fn frame_id(&self) -> FrameId {
         self.rendered_frame_id
@@ -663,13 +775,35 @@ impl Window {
         }
     }
 
-    pub fn set_always_on_top(&mut self, always_on_top: bool) {
-        self.window.set_window_level(if always_on_top {
-            winit::window::WindowLevel::AlwaysOnTop
-        } else {
-            winit::window::WindowLevel::Normal
+    pub fn set_layer(&mut self, layer: WindowLayer) {
+        #[cfg(windows)]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+            let is_bottom = layer == WindowLayer::AlwaysOnBottom;
+            self.is_always_on_bottom.store(is_bottom, Ordering::Relaxed);
+
+            let hwnd = crate::util::winit_to_hwnd(&self.window);
+            // SAFETY: hwnd is a valid window.
+            unsafe {
+                let style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+                let new_style = if is_bottom {
+                    style | WS_EX_NOACTIVATE
+                } else {
+                    style & !WS_EX_NOACTIVATE
+                };
+                if style != new_style {
+                    SetWindowLongW(hwnd, GWL_EXSTYLE, new_style as i32);
+                }
+            }
+        }
+
+        self.window.set_window_level(match layer {
+            WindowLayer::Normal => winit::window::WindowLevel::Normal,
+            WindowLayer::AlwaysOnTop => winit::window::WindowLevel::AlwaysOnTop,
+            WindowLayer::AlwaysOnBottom => winit::window::WindowLevel::AlwaysOnBottom,
         });
-        self.is_always_on_top = always_on_top;
+        self.layer = layer;
     }
 
     pub fn set_movable(&mut self, movable: bool) {
@@ -684,7 +818,7 @@ impl Window {
     pub fn bring_to_top(&mut self) {
         use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
-        if !self.is_always_on_top {
+        if self.layer == WindowLayer::Normal {
             let hwnd = crate::util::winit_to_hwnd(&self.window);
 
             unsafe {
@@ -703,9 +837,9 @@ impl Window {
 
     #[cfg(not(windows))]
     pub fn bring_to_top(&mut self) {
-        if !self.is_always_on_top {
-            self.set_always_on_top(true);
-            self.set_always_on_top(false);
+        if self.layer == WindowLayer::Normal {
+            self.set_layer(WindowLayer::AlwaysOnTop);
+            self.set_layer(WindowLayer::Normal);
         }
     }
 
@@ -1225,6 +1359,42 @@ impl Window {
         }
     }
 
+    /// Set custom chrome regions.
+    pub fn set_hit_test_regions(&mut self, regions: Vec<(PxRect, HitTestKind)>) {
+        *self.hit_test_regions.lock() = regions;
+    }
+
+    /// Starts a drag move or resize if the cursor is over an hit-test region.
+    ///
+    /// Must be called on primary mouse press, returns `true` if started, in this case the press and
+    /// the matching release must not be send to the app-process, the system handles them like it does
+    /// on Windows. On Windows the regions are implemented by the system, this method is only needed for
+    /// other platforms.
+    #[cfg(not(windows))]
+    pub fn hit_test_pressed(&mut self) -> bool {
+        let pos = self.cursor_pos.to_px(self.scale_factor());
+        let kind = self.hit_test_regions.lock().iter().find(|(r, _)| r.contains(pos)).map(|(_, k)| *k);
+        self.region_drag = match kind {
+            Some(HitTestKind::Drag) => {
+                self.drag_move();
+                true
+            }
+            Some(HitTestKind::Resize(direction)) => {
+                self.drag_resize(direction);
+                true
+            }
+            // buttons are only a hint outside of Windows, the app implements the button action.
+            _ => false,
+        };
+        self.region_drag
+    }
+
+    /// Must be called on primary mouse release, returns `true` if the press started a region drag.
+    #[cfg(not(windows))]
+    pub fn hit_test_released(&mut self) -> bool {
+        std::mem::take(&mut self.region_drag)
+    }
+
     /// Set enabled chrome buttons.
     pub fn set_enabled_buttons(&self, buttons: WindowButton) {
         self.window.set_enabled_buttons(buttons.to_winit());
@@ -1235,6 +1405,130 @@ impl Window {
         self.window.show_window_menu(pos.to_winit())
     }
 
+    /// Set custom system menu items.
+    #[cfg(windows)]
+    pub fn set_system_menu(&mut self, menu: SystemMenu) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::*;
+
+        fn append_items(menu: HMENU, items: &[zng_view_api::window::SystemMenuItem], ids: &mut Vec<Txt>) {
+            use zng_view_api::window::SystemMenuItem;
+            for item in items {
+                match item {
+                    SystemMenuItem::Item {
+                        id,
+                        label,
+                        enabled,
+                        checked,
+                    } => {
+                        let cmd = SYSTEM_MENU_ID_START + ids.len() * 16;
+                        if cmd >= 0xF000 {
+                            tracing::error!("too many system menu items");
+                            return;
+                        }
+                        ids.push(id.clone());
+                        let mut flags = MF_STRING;
+                        if !enabled {
+                            flags |= MF_GRAYED;
+                        }
+                        if *checked {
+                            flags |= MF_CHECKED;
+                        }
+                        let label: Vec<u16> = label.encode_utf16().chain([0]).collect();
+                        // SAFETY: label is null terminated, command IDs are in the app range of system commands.
+                        unsafe { AppendMenuW(menu, flags, cmd, label.as_ptr()) };
+                    }
+                    SystemMenuItem::Submenu { label, enabled, items } => {
+                        // SAFETY: function has no preconditions.
+                        let submenu = unsafe { CreatePopupMenu() };
+                        append_items(submenu, items, ids);
+                        let mut flags = MF_POPUP;
+                        if !enabled {
+                            flags |= MF_GRAYED;
+                        }
+                        let label: Vec<u16> = label.encode_utf16().chain([0]).collect();
+                        // SAFETY: label is null terminated, submenu is destroyed with the parent menu.
+                        unsafe { AppendMenuW(menu, flags, submenu as usize, label.as_ptr()) };
+                    }
+                    SystemMenuItem::Separator => {
+                        // SAFETY: separators have no text.
+                        unsafe { AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null()) };
+                    }
+                }
+            }
+        }
+
+        let hwnd = crate::util::winit_to_hwnd(&self.window);
+        let mut ids = self.system_menu_ids.lock().unwrap();
+        ids.clear();
+
+        // SAFETY: reverting destroys the current copy and restores the default menu, the copy is owned by the window.
+        unsafe {
+            GetSystemMenu(hwnd, 1);
+            if menu.is_default() {
+                return;
+            }
+            let sys_menu = GetSystemMenu(hwnd, 0);
+            if sys_menu == 0 {
+                tracing::error!("cannot get window system menu");
+                return;
+            }
+            if menu.hide_default {
+                while GetMenuItemCount(sys_menu) > 0 {
+                    DeleteMenu(sys_menu, 0, MF_BYPOSITION);
+                }
+            } else if !menu.items.is_empty() {
+                AppendMenuW(sys_menu, MF_SEPARATOR, 0, std::ptr::null());
+            }
+            append_items(sys_menu, &menu.items, &mut ids);
+        }
+    }
+
+    /// Set custom system menu items.
+    #[cfg(not(windows))]
+    pub fn set_system_menu(&mut self, menu: SystemMenu) {
+        if !menu.is_default() {
+            tracing::warn!("system menu customization not implemented on {}", std::env::consts::OS);
+        }
+    }
+
+    /// Group the `tabs` windows as native tabs of this window, or remove this window from its tab group if `tabs` is empty.
+    #[cfg(target_os = "macos")]
+    pub fn set_tabs(&self, tabs: &[&Window]) {
+        use objc2_app_kit::NSWindowOrderingMode;
+
+        let host = match crate::util::winit_to_ns_window(&self.window) {
+            Some(w) => w,
+            None => return,
+        };
+        if tabs.is_empty() {
+            if let Some(group) = host.tabGroup() {
+                // SAFETY: the window is in the group, it becomes a separate window.
+                unsafe { group.removeWindow(&host) };
+            }
+            return;
+        }
+        for tab in tabs {
+            if let Some(tab) = crate::util::winit_to_ns_window(&tab.window) {
+                if !std::ptr::eq(&*tab, &*host) {
+                    // SAFETY: both are valid windows, the tab is moved from any previous group.
+                    unsafe { host.addTabbedWindow_ordered(&tab, NSWindowOrderingMode::NSWindowAbove) };
+                }
+            }
+        }
+    }
+
+    /// Start dragging `data` from the window.
+    pub fn start_drag_drop(
+        &mut self,
+        drag_id: DragDropId,
+        data: Vec<DragDropData>,
+        allowed_effects: DragDropEffect,
+        image: Option<Image>,
+        app_sender: AppEventSender,
+    ) -> Result<DragSource, DragDropError> {
+        DragSource::start(&self.window, self.id, drag_id, data, allowed_effects, image, app_sender)
+    }
+
     fn apply_state(&mut self, new_state: WindowStateAll, force: bool) {
         if self.state.chrome_visible != new_state.chrome_visible {
             self.window.set_decorations(new_state.chrome_visible);
@@ -1370,19 +1664,29 @@ impl Window {
                 .collect(),
         );
         self.api.send_transaction(self.document_id, txn);
-        FontId::from_raw(key.1)
+        let font_id = FontId::from_raw(key.1);
+        self.display_list_cache.register_font(font_id, glyph_size);
+        font_id
     }
 
     pub fn delete_font(&mut self, font_id: FontId) {
         let mut txn = webrender::Transaction::new();
         txn.delete_font_instance(FontInstanceKey(self.api.get_namespace_id(), font_id.get()));
         self.api.send_transaction(self.document_id, txn);
+        self.display_list_cache.unregister_font(font_id);
     }
 
     pub fn set_capture_mode(&mut self, enabled: bool) {
         self.capture_mode = enabled;
     }
 
+    /// Start rendering the frames in sequence, windows present each frame.
+    pub fn render_batch(&mut self, frames: Vec<FrameRequest>) {
+        for frame in frames {
+            self.render(frame);
+        }
+    }
+
     /// Start rendering a new frame.
     ///
     /// The [callback](#callback) will be called when the frame is ready to be [presented](Self::present).
@@ -1593,18 +1897,86 @@ impl Window {
         let r = renderer.render(size.to_wr_device(), 0).unwrap();
         span.record("stats", &tracing::field::debug(&r.stats));
 
+        let mut redraw_again = self.continuous_render;
         for (_, ext) in &mut self.renderer_exts {
-            ext.redraw(&mut RedrawArgs {
+            let mut args = RedrawArgs {
                 scale_factor,
                 size,
                 context: &mut self.context,
-            });
+                redraw_again,
+            };
+            ext.redraw(&mut args);
+            redraw_again |= args.redraw_again;
         }
 
         let _ = renderer.flush_pipeline_info();
 
         self.window.pre_present_notify();
         self.context.swap_buffers();
+
+        if self.present_notify {
+            self.on_presented();
+        }
+
+        if redraw_again && self.visible && self.state.state != WindowState::Minimized {
+            self.window.request_redraw();
+        }
+    }
+
+    /// Enable or disable continuous redraw.
+    pub fn set_continuous_render(&mut self, enabled: bool) {
+        if self.continuous_render != enabled {
+            self.continuous_render = enabled;
+            if enabled {
+                self.window.request_redraw();
+            }
+        }
+    }
+
+    /// If continuous redraw is enabled.
+    pub fn is_continuous_render(&self) -> bool {
+        self.continuous_render
+    }
+
+    /// Enable or disable [`Event::FramePresented`] after each present.
+    pub fn set_present_notify(&mut self, enabled: bool) {
+        self.present_notify = enabled;
+        self.last_present = None;
+        self.refresh_interval = None;
+        self.presented = None;
+    }
+
+    /// Take the [`Event::FramePresented`] payload for the last present, if it was not taken yet.
+    pub fn take_presented(&mut self) -> Option<EventFramePresented> {
+        self.presented.take()
+    }
+
+    fn on_presented(&mut self) {
+        let now = Instant::now();
+
+        let monitor_interval = self
+            .window
+            .current_monitor()
+            .and_then(|m| m.refresh_rate_millihertz())
+            .filter(|&mhz| mhz > 0)
+            .map(|mhz| Duration::from_secs_f64(1000.0 / mhz as f64))
+            .unwrap_or(Duration::from_micros(16_667));
+
+        // presents within 1.5 refreshes are consecutive, estimate using a moving average of the intervals.
+        let interval = match (self.last_present, self.refresh_interval) {
+            (Some(prev), Some(est)) if now - prev < est + est / 2 => (est * 7 + (now - prev)) / 8,
+            (Some(prev), None) if now - prev < monitor_interval + monitor_interval / 2 => now - prev,
+            _ => monitor_interval,
+        };
+        self.last_present = Some(now);
+        self.refresh_interval = Some(interval);
+
+        self.presented = Some(EventFramePresented {
+            window: self.id,
+            frame: self.rendered_frame_id,
+            presented_at: SystemTime::now(),
+            refresh_interval: interval,
+        });
     }
 
     pub fn is_rendering_frame(&self) -> bool {
@@ -1695,6 +2067,8 @@ impl Window {
                 let r = ext.command(&mut RendererCommandArgs {
                     renderer: self.renderer.as_mut().unwrap(),
                     api: &mut self.api,
+                    document_id: self.document_id,
+                    external_images: &mut self.external_images,
                     request,
                     window: Some(&self.window),
                     context: &mut self.context,
@@ -1855,8 +2229,9 @@ impl Window {
         if let Some(a) = &mut self.access {
             // SAFETY: we drop `access` in case of panic.
             let mut a = std::panic::AssertUnwindSafe(a);
+            let state = std::panic::AssertUnwindSafe(&self.access_state);
             let panic = crate::util::catch_suppress(move || {
-                a.update_if_active(|| crate::util::access_tree_update_to_kit(update));
+                a.update_if_active(|| crate::util::access_tree_update_to_kit(update, &state));
             });
             if let Err(p) = panic {
                 self.access = None;
@@ -1870,8 +2245,43 @@ impl Window {
         }
     }
 
+    /// Announce the text using the live region node.
+    pub fn access_announce(&mut self, txt: Txt, priority: zng_view_api::access::AccessLivePriority, event_sender: &AppEventSender) {
+        if let Some(a) = &mut self.access {
+            // clear first, so that the same text is announced again.
+            let clear = crate::util::access_announce_to_kit(None, &self.access_state);
+            let announce = crate::util::access_announce_to_kit(Some((txt, priority)), &self.access_state);
+
+            // SAFETY: we drop `access` in case of panic.
+            let mut a = std::panic::AssertUnwindSafe(a);
+            let panic = crate::util::catch_suppress(move || {
+                if let (Some(clear), Some(announce)) = (clear, announce) {
+                    a.update_if_active(|| clear);
+                    a.update_if_active(|| announce);
+                }
+            });
+            if let Err(p) = panic {
+                self.access = None;
+
+                let _ = event_sender.send(AppEvent::Notify(Event::RecoveredFromComponentPanic {
+                    component: Txt::from_static("accesskit_winit::Adapter::update_if_active"),
+                    recover: Txt::from_static("accessibility disabled for this window instance"),
+                    panic: p.to_txt(),
+                }));
+            }
+        }
+    }
+
+    pub fn renderer_stats(&self) -> RendererStats {
+        crate::util::wr_renderer_stats(self.renderer.as_ref().unwrap(), self.display_list_cache.glyph_cache_estimate())
+    }
+
+    pub fn trim_renderer(&mut self, trim: RendererTrim) {
+        crate::util::wr_trim(&self.api, trim);
+    }
+
     pub(crate) fn on_low_memory(&mut self) {
-        self.api.notify_memory_pressure();
+        crate::util::wr_trim(&self.api, RendererTrim::All);
 
         for (_, ext) in &mut self.renderer_exts {
             ext.low_memory();
@@ -1988,11 +2398,16 @@ impl accesskit::DeactivationHandler for AccessDeactivateHandler {
 struct AccessActionSender {
     id: WindowId,
     event_sender: AppEventSender,
+    state: crate::util::AccessKitStateRef,
 }
 impl accesskit::ActionHandler for AccessActionSender {
     fn do_action(&mut self, request: accesskit::ActionRequest) {
-        if let Some(ev) = crate::util::accesskit_to_event(self.id, request) {
+        if let Some(ev) = crate::util::accesskit_to_event(self.id, request, &self.state) {
             let _ = self.event_sender.send(AppEvent::Notify(ev));
         }
     }
 }
+
+/// First command ID of custom system menu items, IDs increment by 16 because the system uses the low 4 bits.
+#[cfg(windows)]
+const SYSTEM_MENU_ID_START: usize = 0x1000;
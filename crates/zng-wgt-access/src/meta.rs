@@ -1,11 +1,14 @@
-use zng_app::widget::info::access::WidgetAccessInfoBuilder;
+use zng_app::{
+    access::ACCESS,
+    widget::info::{access::WidgetAccessInfoBuilder, WIDGET_INFO_CHANGED_EVENT},
+};
 use zng_ext_l10n::Lang;
 use zng_wgt::prelude::*;
 
 use std::num::NonZeroU32;
 
 pub use zng_view_api::access::{
-    AccessCmdName, AccessRole, AutoComplete, CurrentKind, Invalid, LiveIndicator, Orientation, Popup, SortDirection,
+    AccessCmdName, AccessLivePriority, AccessRole, AutoComplete, CurrentKind, Invalid, LiveIndicator, Orientation, Popup, SortDirection,
 };
 
 /// Sets the widget kind for accessibility services.
@@ -412,6 +415,59 @@ pub fn live(
     })
 }
 
+/// Announce the widget text content when it changes.
+///
+/// The labels and value texts of the widget and descendants are announced using [`ACCESS.announce_in`] every time
+/// the text changes after an info rebuild, including the first non-empty text. Use this in widgets like toast notifications
+/// and validation errors. Unlike [`live`] this does not depend on the screen reader implementation of live regions.
+///
+/// If set to `None` nothing is announced.
+///
+/// [`ACCESS.announce_in`]: ACCESS::announce_in
+/// [`live`]: fn@live
+#[property(CONTEXT)]
+pub fn access_live(child: impl UiNode, priority: impl IntoVar<Option<AccessLivePriority>>) -> impl UiNode {
+    let priority = priority.into_var();
+    let mut announced = Txt::from_static("");
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&priority).sub_event(&WIDGET_INFO_CHANGED_EVENT);
+        }
+        UiNodeOp::Deinit => {
+            announced = Txt::from_static("");
+        }
+        UiNodeOp::Event { update } => {
+            if let Some(args) = WIDGET_INFO_CHANGED_EVENT.on(update) {
+                if args.window_id != WINDOW.id() {
+                    return;
+                }
+                if let (Some(priority), Some(info)) = (priority.get(), WIDGET.info().access()) {
+                    let mut txt = String::new();
+                    for wgt in info.info().self_and_descendants() {
+                        if let Some(a) = wgt.access() {
+                            for t in [a.label(), a.value_text()].into_iter().flatten() {
+                                if !t.is_empty() {
+                                    if !txt.is_empty() {
+                                        txt.push(' ');
+                                    }
+                                    txt.push_str(&t);
+                                }
+                            }
+                        }
+                    }
+                    if announced != txt.as_str() {
+                        announced = Txt::from_str(&txt);
+                        if !announced.is_empty() {
+                            ACCESS.announce_in(args.window_id, announced.clone(), priority);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
 fn with_access_state<T: VarValue>(
     child: impl UiNode,
     state: impl IntoVar<T>,
@@ -25,6 +25,14 @@ pub use zng_wgt_text::__formatx;
 /// Render text styled using ANSI escape sequences.
 ///
 /// Supports color, weight, italic and more, see [`AnsiStyle`] for the full style supported.
+///
+/// # Streams
+///
+/// If [`txt_stream`] is set the widget shows the lines of the stream instead of the `txt`. Lines appended to the stream
+/// and lines removed from the start are applied incrementally, this is the recommended way of showing
+/// live logs, see [`ansi_txt_stream`] for more details.
+///
+/// [`txt_stream`]: fn@txt_stream
 #[widget($crate::AnsiText {
     ($txt:literal) => {
         txt = $crate::__formatx!($txt);
@@ -53,9 +61,14 @@ impl AnsiText {
         };
 
         self.widget_builder().push_build_action(|wgt| {
-            let txt = wgt.capture_var_or_default(property_id!(txt));
-            let child = ansi_node(txt);
-            wgt.set_child(child.boxed());
+            let child = match wgt.capture_var::<ObservableVec<Txt>>(property_id!(txt_stream)) {
+                Some(lines) => ansi_stream_node(lines).boxed(),
+                None => {
+                    let txt = wgt.capture_var_or_default(property_id!(txt));
+                    ansi_node(txt).boxed()
+                }
+            };
+            wgt.set_child(child);
         });
     }
 
@@ -266,6 +279,71 @@ mod ansi_parse {
     }
 
     impl AnsiStyle {
+        /// Gets the SGR escape sequence that sets this style from the default style.
+        pub(super) fn sgr(&self) -> String {
+            fn color(c: AnsiColor, base: u8, bright_base: u8, extended: u8) -> String {
+                let named = |i: u8| {
+                    if i < 8 {
+                        (base + i).to_string()
+                    } else {
+                        (bright_base + i - 8).to_string()
+                    }
+                };
+                match c {
+                    AnsiColor::Black => named(0),
+                    AnsiColor::Red => named(1),
+                    AnsiColor::Green => named(2),
+                    AnsiColor::Yellow => named(3),
+                    AnsiColor::Blue => named(4),
+                    AnsiColor::Magenta => named(5),
+                    AnsiColor::Cyan => named(6),
+                    AnsiColor::White => named(7),
+                    AnsiColor::BrightBlack => named(8),
+                    AnsiColor::BrightRed => named(9),
+                    AnsiColor::BrightGreen => named(10),
+                    AnsiColor::BrightYellow => named(11),
+                    AnsiColor::BrightBlue => named(12),
+                    AnsiColor::BrightMagenta => named(13),
+                    AnsiColor::BrightCyan => named(14),
+                    AnsiColor::BrightWhite => named(15),
+                    AnsiColor::Ansi256(c) => format!("{extended};5;{c}"),
+                    AnsiColor::TrueColor(r, g, b) => format!("{extended};2;{r};{g};{b}"),
+                }
+            }
+
+            let default = Self::default();
+            let mut codes = vec![];
+            match self.weight {
+                AnsiWeight::Normal => {}
+                AnsiWeight::Bold => codes.push("1".to_owned()),
+                AnsiWeight::Faint => codes.push("2".to_owned()),
+            }
+            for (flag, code) in [
+                (self.italic, "3"),
+                (self.underline, "4"),
+                (self.blink, "5"),
+                (self.invert_color, "7"),
+                (self.hidden, "8"),
+                (self.strikethrough, "9"),
+            ] {
+                if flag {
+                    codes.push(code.to_owned());
+                }
+            }
+            if self.color != default.color {
+                codes.push(color(self.color, 30, 90, 38));
+            }
+            if self.background_color != default.background_color {
+                codes.push(color(self.background_color, 40, 100, 48));
+            }
+
+            if codes.is_empty() {
+                String::new()
+            } else {
+                format!("\x1b[{}m", codes.join(";"))
+            }
+        }
+
         fn set(&mut self, esc_codes: &str) {
             let mut esc_codes = esc_codes.split(';');
             while let Some(code) = esc_codes.next() {
@@ -635,6 +713,29 @@ mod ansi_fn {
     }
 }
 
+fn generate_line(
+    line: &str,
+    index: usize,
+    page_index: usize,
+    text_fn: &WidgetFn<TextFnArgs>,
+    line_fn: &WidgetFn<LineFnArgs>,
+) -> BoxedUiNode {
+    let text = ansi_parse::AnsiTextParser::new(line)
+        .filter_map(|txt| {
+            text_fn.call_checked(TextFnArgs {
+                txt: txt.txt.to_txt(),
+                style: txt.style,
+            })
+        })
+        .collect();
+
+    line_fn(LineFnArgs {
+        index: index as u32,
+        page_index: page_index as u32,
+        text,
+    })
+}
+
 fn generate_ansi(txt: &impl Var<Txt>) -> BoxedUiNode {
     use ansi_fn::*;
     use std::mem;
@@ -650,20 +751,7 @@ fn generate_ansi(txt: &impl Var<Txt>) -> BoxedUiNode {
         let mut lines = Vec::with_capacity(50);
 
         for (i, line) in txt.lines().enumerate() {
-            let text = ansi_parse::AnsiTextParser::new(line)
-                .filter_map(|txt| {
-                    text_fn.call_checked(TextFnArgs {
-                        txt: txt.txt.to_txt(),
-                        style: txt.style,
-                    })
-                })
-                .collect();
-
-            lines.push(line_fn(LineFnArgs {
-                index: i as u32,
-                page_index: lines.len() as u32,
-                text,
-            }));
+            lines.push(generate_line(line, i, lines.len(), &text_fn, &line_fn));
 
             if lines.len() == lines_per_page {
                 let lines = mem::replace(&mut lines, Vec::with_capacity(50));
@@ -726,6 +814,358 @@ pub fn ansi_node(txt: impl IntoVar<Txt>) -> impl UiNode {
     })
 }
 
+pub use ansi_stream::*;
+mod ansi_stream {
+    use std::{mem, ops};
+
+    use zng_var::types::VecChange;
+
+    use super::*;
+
+    /// ANSI text lines stream.
+    ///
+    /// If set the [`AnsiText!`] shows the lines instead of the `txt`. Each item is a line without the line break. Lines
+    /// inserted at the end and removed from the start of the vec are applied incrementally, only the affected pages
+    /// are regenerated, any other change regenerates all pages. To limit the scrollback remove lines from the start
+    /// before inserting new lines in the same update, otherwise the changes are collapsed to a clear.
+    ///
+    /// Note that the [`PANEL_FN_VAR`] is not used for streams, the pages are always layout in a vertical stack. Page
+    /// views must be widgets to be updated incrementally.
+    ///
+    /// See [`ansi_txt_stream`] for a way to feed the lines from a channel.
+    ///
+    /// [`AnsiText!`]: struct@crate::AnsiText
+    #[property(CHILD, capture, widget_impl(AnsiText))]
+    pub fn txt_stream(lines: impl IntoVar<ObservableVec<Txt>>) {}
+
+    /// New lines variable for [`txt_stream`] fed by the `receiver`.
+    ///
+    /// The `receiver` text chunks are split in lines, chunks can end in the middle of a line or of an escape sequence, the
+    /// incomplete line is only shown after the line break is received or after the channel disconnects. The style active at
+    /// the end of a line continues in the next line, the SGR escape sequence that restores it is inserted at the start of the line. Lines are removed from the start
+    /// to keep the stream with a maximum of `max_lines`, use `usize::MAX` for unlimited scrollback.
+    ///
+    /// The variable is updated at most once per app update, chunks received in between updates are applied together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # zng_wgt::enable_widget_macros!();
+    /// # use zng_wgt::prelude::*;
+    /// # use zng_wgt_ansi_text::*;
+    /// # fn main() {
+    /// let (sender, receiver) = task::channel::unbounded();
+    /// let log = AnsiText! {
+    ///     txt_stream = ansi_txt_stream(receiver, 10_000);
+    /// };
+    ///
+    /// sender.send(Txt::from("\x1b[32mINFO\x1b[0m started\n")).unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`txt_stream`]: fn@txt_stream
+    pub fn ansi_txt_stream(receiver: task::channel::Receiver<Txt>, max_lines: usize) -> ReadOnlyArcVar<ObservableVec<Txt>> {
+        let lines = var(ObservableVec::new());
+        let wk_lines = lines.downgrade();
+        task::spawn(async move {
+            let mut splitter = LineSplitter::default();
+            let mut new_lines = vec![];
+            loop {
+                let chunk = receiver.recv().await.ok();
+                let disconnected = chunk.is_none();
+                for chunk in chunk.into_iter().chain(receiver.drain()) {
+                    splitter.push(&chunk, &mut new_lines);
+                }
+                if disconnected {
+                    splitter.finish(&mut new_lines);
+                }
+
+                if !new_lines.is_empty() {
+                    let lines = match wk_lines.upgrade() {
+                        Some(l) => l,
+                        None => break,
+                    };
+                    let mut new_lines = mem::take(&mut new_lines);
+                    lines.modify(move |l| {
+                        let l = l.to_mut();
+                        if new_lines.len() > max_lines {
+                            new_lines.drain(..new_lines.len() - max_lines);
+                        }
+                        let len = l.len() + new_lines.len();
+                        if len > max_lines {
+                            l.drain(..len - max_lines);
+                        }
+                        l.append(&mut new_lines);
+                    });
+                    // the vec changes only track inserts after removes in the same update
+                    lines.wait_update().await;
+                }
+
+                if disconnected {
+                    break;
+                }
+            }
+        });
+        lines.read_only()
+    }
+
+    /// Splits text chunks in lines, carrying over the SGR style.
+    #[derive(Default)]
+    pub(super) struct LineSplitter {
+        partial: String,
+        style: AnsiStyle,
+    }
+    impl LineSplitter {
+        /// Push a chunk, complete lines are pushed to `out`.
+        pub(super) fn push(&mut self, chunk: &str, out: &mut Vec<Txt>) {
+            self.partial.push_str(chunk);
+            if let Some(i) = self.partial.rfind('\n') {
+                let partial = mem::take(&mut self.partial);
+                for line in partial[..i].split('\n') {
+                    out.push(self.line(line));
+                }
+                self.partial.push_str(&partial[i + 1..]);
+            }
+        }
+
+        /// Push the incomplete line, if any.
+        pub(super) fn finish(&mut self, out: &mut Vec<Txt>) {
+            if !self.partial.is_empty() {
+                let partial = mem::take(&mut self.partial);
+                out.push(self.line(&partial));
+            }
+        }
+
+        fn line(&mut self, line: &str) -> Txt {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            // each line is parsed separately, so prefix the style active at the end of the previous line
+            let line = Txt::from(format!("{}{line}", self.style.sgr()));
+            let mut parser = ansi_parse::AnsiTextParser::new(&line);
+            for _ in &mut parser {}
+            self.style = parser.style;
+            line
+        }
+    }
+
+    /// Implements the incremental ANSI parsing and view generation of a lines stream, configured by contextual properties.
+    ///
+    /// See [`txt_stream`] for more details.
+    ///
+    /// [`txt_stream`]: fn@txt_stream
+    pub fn ansi_stream_node(lines: impl IntoVar<ObservableVec<Txt>>) -> impl UiNode {
+        let lines = lines.into_var();
+        let mut pages = StreamPages::default();
+        match_node(NilUiNode.boxed(), move |c, op| match op {
+            UiNodeOp::Init => {
+                WIDGET
+                    .sub_var(&lines)
+                    .sub_var(&TEXT_FN_VAR)
+                    .sub_var(&LINE_FN_VAR)
+                    .sub_var(&PAGE_FN_VAR)
+                    .sub_var(&LINES_PER_PAGE_VAR)
+                    .sub_var(&BLINK_INTERVAL_VAR);
+
+                *c.child() = lines.with(|l| pages.generate(l));
+            }
+            UiNodeOp::Deinit => {
+                c.deinit();
+                *c.child() = NilUiNode.boxed();
+                pages = StreamPages::default();
+            }
+            UiNodeOp::Update { .. } => {
+                let rebuild = TEXT_FN_VAR.is_new()
+                    || LINE_FN_VAR.is_new()
+                    || PAGE_FN_VAR.is_new()
+                    || LINES_PER_PAGE_VAR.is_new()
+                    || BLINK_INTERVAL_VAR.is_new()
+                    || (lines.is_new() && !lines.with(|l| pages.apply(l)));
+
+                if rebuild {
+                    c.child().deinit();
+                    *c.child() = lines.with(|l| pages.generate(l));
+                    c.child().init();
+                    WIDGET.update_info().layout().render();
+                }
+            }
+            _ => {}
+        })
+    }
+
+    #[derive(Default)]
+    struct StreamPages {
+        list: Option<EditableUiNodeListRef>,
+        /// Global index of the first line.
+        first_line: usize,
+        /// Global index of the first page.
+        first_page: usize,
+        /// Line count.
+        len: usize,
+        /// Page widget and line count.
+        pages: Vec<(Option<WidgetId>, usize)>,
+    }
+    impl StreamPages {
+        fn generate(&mut self, lines: &ObservableVec<Txt>) -> BoxedUiNode {
+            let lines_per_page = LINES_PER_PAGE_VAR.get().max(1) as usize;
+
+            self.first_line = 0;
+            self.first_page = 0;
+            self.len = lines.len();
+            self.pages.clear();
+
+            let mut nodes = Vec::with_capacity(self.len / lines_per_page + 1);
+            let mut start = 0;
+            while start < self.len {
+                let end = (start + lines_per_page).min(self.len);
+                let mut page = self.generate_page(lines, start..end, self.pages.len());
+                self.pages.push((page_id(&mut page), end - start));
+                nodes.push(page);
+                start = end;
+            }
+
+            let list = EditableUiNodeList::from_vec(nodes);
+            // incremental updates need to identify the page widgets
+            self.list = if self.pages.iter().all(|(id, _)| id.is_some()) {
+                Some(list.reference())
+            } else {
+                None
+            };
+            Stack! {
+                direction = StackDirection::top_to_bottom();
+                children = list;
+            }
+            .boxed()
+        }
+
+        fn generate_page(&self, lines: &ObservableVec<Txt>, range: ops::Range<usize>, page_index: usize) -> BoxedUiNode {
+            let text_fn = TEXT_FN_VAR.get();
+            let line_fn = LINE_FN_VAR.get();
+            let first = range.start;
+            let lines = lines[range]
+                .iter()
+                .enumerate()
+                .map(|(i, l)| generate_line(l, self.first_line + first + i, i, &text_fn, &line_fn))
+                .collect();
+            PAGE_FN_VAR.get()(PageFnArgs {
+                index: (self.first_page + page_index) as u32,
+                lines,
+            })
+        }
+
+        /// Apply the `lines` changes, returns `false` if cannot apply incrementally.
+        fn apply(&mut self, lines: &ObservableVec<Txt>) -> bool {
+            let list = match &self.list {
+                Some(l) if l.alive() => l.clone(),
+                _ => return false,
+            };
+            let changes = lines.changes();
+            if changes.is_empty() {
+                // replaced
+                return false;
+            }
+
+            let lines_per_page = LINES_PER_PAGE_VAR.get().max(1) as usize;
+            let mut remove = vec![];
+            let mut first_dirty = false;
+            let mut dirty_from = self.pages.len();
+
+            for change in changes {
+                match *change {
+                    VecChange::Remove { index: 0, count } if count <= self.len => {
+                        self.len -= count;
+                        self.first_line += count;
+                        let mut count = count;
+                        while count > 0 {
+                            let (id, n) = &mut self.pages[0];
+                            if *n <= count {
+                                count -= *n;
+                                remove.extend(*id);
+                                self.pages.remove(0);
+                                self.first_page += 1;
+                                dirty_from = dirty_from.saturating_sub(1);
+                                first_dirty = false;
+                            } else {
+                                *n -= count;
+                                count = 0;
+                                first_dirty = true;
+                            }
+                        }
+                    }
+                    VecChange::Insert { index, count } if index == self.len => {
+                        self.len += count;
+                        let mut count = count;
+                        if let Some((_, n)) = self.pages.last_mut() {
+                            if *n < lines_per_page {
+                                let fill = count.min(lines_per_page - *n);
+                                *n += fill;
+                                count -= fill;
+                                dirty_from = dirty_from.min(self.pages.len() - 1);
+                            }
+                        }
+                        while count > 0 {
+                            let n = count.min(lines_per_page);
+                            dirty_from = dirty_from.min(self.pages.len());
+                            self.pages.push((None, n));
+                            count -= n;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+            if self.len != lines.len() {
+                return false;
+            }
+            if dirty_from == 0 {
+                first_dirty = false;
+            }
+
+            let mut dirty = vec![];
+            let mut start = 0;
+            for (i, (_, n)) in self.pages.iter().enumerate() {
+                if (i == 0 && first_dirty) || i >= dirty_from {
+                    dirty.push((i, start..start + n));
+                }
+                start += n;
+            }
+
+            let mut insert_first = None;
+            let mut push = vec![];
+            for (i, range) in dirty {
+                let mut page = self.generate_page(lines, range, i);
+                let id = match page_id(&mut page) {
+                    Some(id) => id,
+                    None => return false,
+                };
+                remove.extend(self.pages[i].0.replace(id));
+                if i == 0 && first_dirty {
+                    insert_first = Some(page);
+                } else {
+                    push.push(page);
+                }
+            }
+
+            if !remove.is_empty() {
+                list.retain(move |p| {
+                    p.with_context(WidgetUpdateMode::Ignore, || !remove.contains(&WIDGET.id()))
+                        .unwrap_or(true)
+                });
+            }
+            if let Some(p) = insert_first {
+                list.insert(0, p);
+            }
+            for p in push {
+                list.push(p);
+            }
+            WIDGET.layout().render();
+
+            true
+        }
+    }
+
+    fn page_id(page: &mut BoxedUiNode) -> Option<WidgetId> {
+        page.with_context(WidgetUpdateMode::Ignore, || WIDGET.id())
+    }
+}
+
 static X_TERM_256: [(u8, u8, u8); 256] = [
     (0, 0, 0),
     (128, 0, 0),
@@ -984,3 +1424,63 @@ static X_TERM_256: [(u8, u8, u8); 256] = [
     (228, 228, 228),
     (238, 238, 238),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(chunks: &[&str]) -> Vec<Txt> {
+        let mut splitter = ansi_stream::LineSplitter::default();
+        let mut out = vec![];
+        for c in chunks {
+            splitter.push(c, &mut out);
+        }
+        splitter.finish(&mut out);
+        out
+    }
+
+    fn runs(line: &str) -> Vec<(String, AnsiColor, AnsiWeight)> {
+        ansi_parse::AnsiTextParser::new(line)
+            .map(|t| (t.txt.to_owned(), t.style.color, t.style.weight))
+            .collect()
+    }
+
+    #[test]
+    fn stream_split_lines() {
+        let lines = split(&["a\nb", "c\r", "\nd"]);
+        assert_eq!(lines, ["a", "bc", "d"]);
+    }
+
+    #[test]
+    fn stream_split_escape() {
+        let lines = split(&["\x1b[3", "1mred", "\x1b[", "0m plain\n"]);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            runs(&lines[0]),
+            [
+                ("red".to_owned(), AnsiColor::Red, AnsiWeight::Normal),
+                (" plain".to_owned(), AnsiColor::White, AnsiWeight::Normal)
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_sgr_carry_over() {
+        let lines = split(&["\x1b[1;32mgreen\n", "still green\n", "\x1b[0mplain\n", "plain\n"]);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(runs(&lines[0]), [("green".to_owned(), AnsiColor::Green, AnsiWeight::Bold)]);
+        assert_eq!(runs(&lines[1]), [("still green".to_owned(), AnsiColor::Green, AnsiWeight::Bold)]);
+        assert_eq!(runs(&lines[2]), [("plain".to_owned(), AnsiColor::White, AnsiWeight::Normal)]);
+        assert_eq!(lines[3], "plain");
+    }
+
+    #[test]
+    fn stream_sgr_carry_over_extended() {
+        let lines = split(&["\x1b[38;2;1;2;3m\x1b[101mx\n", "y"]);
+        let mut parser = ansi_parse::AnsiTextParser::new(&lines[1]);
+        let run = parser.next().unwrap();
+        assert_eq!(run.txt, "y");
+        assert_eq!(run.style.color, AnsiColor::TrueColor(1, 2, 3));
+        assert_eq!(run.style.background_color, AnsiColor::BrightRed);
+    }
+}
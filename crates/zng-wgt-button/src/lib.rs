@@ -13,7 +13,7 @@ zng_wgt::enable_widget_macros!();
 
 use std::{any::TypeId, ops};
 
-use colors::{ACCENT_COLOR_VAR, BASE_COLOR_VAR};
+use colors::BASE_COLOR_VAR;
 use zng_app::event::CommandParam;
 use zng_var::ReadOnlyContextVar;
 use zng_wgt::{base_color, border, corner_radius, is_disabled, prelude::*};
@@ -193,10 +193,7 @@ pub fn default_cmd_child_fn(cmd: Command) -> impl UiNode {
 pub fn default_cmd_tooltip_fn(args: CmdTooltipArgs) -> impl UiNode {
     let info = args.cmd.info();
     let has_info = info.map(|s| !s.is_empty());
-    let shortcut = args.cmd.shortcut().map(|s| match s.first() {
-        Some(s) => s.to_txt(),
-        None => Txt::from(""),
-    });
+    let shortcut = args.cmd.shortcut_txt();
     let has_shortcut = shortcut.map(|s| !s.is_empty());
     Tip! {
         child = Text! {
@@ -296,7 +293,7 @@ impl DefaultStyle {
             corner_radius = 4;
             child_align = Align::CENTER;
 
-            base_color = light_dark(rgb(0.82, 0.82, 0.82), rgb(0.18, 0.18, 0.18));
+            base_color = theme::SURFACE_VARIANT_COLOR_VAR;
 
             #[easing(150.ms())]
             background_color = BASE_COLOR_VAR.rgba();
@@ -338,7 +335,7 @@ impl PrimaryStyle {
         widget_set! {
             self;
 
-            base_color = ACCENT_COLOR_VAR.map(|c| c.shade(-2));
+            base_color = theme::PRIMARY_COLOR_VAR.map(|c| c.shade(-2));
             zng_wgt_text::font_weight = zng_ext_font::FontWeight::BOLD;
         }
     }
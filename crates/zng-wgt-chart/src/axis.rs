@@ -0,0 +1,205 @@
+use zng_wgt::prelude::*;
+
+/// Value range of a chart axis.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum AxisRange {
+    /// Range that fits all series values, expanded to the nearest ticks.
+    #[default]
+    Auto,
+    /// Fixed range.
+    Fixed {
+        /// Value at the start of the axis.
+        min: f64,
+        /// Value at the end of the axis.
+        max: f64,
+    },
+}
+impl AxisRange {
+    /// New fixed range.
+    pub fn fixed(min: f64, max: f64) -> Self {
+        Self::Fixed { min, max }
+    }
+
+    /// Gets the actual range for the series values range.
+    ///
+    /// The `tick_count` is the target number of ticks, auto ranges are expanded to fit exactly the ticks so that the
+    /// range only changes when the values cross a tick.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zng_wgt_chart::*;
+    /// assert_eq!(AxisRange::Auto.resolve(3.0, 97.0, 5), (0.0, 100.0));
+    /// assert_eq!(AxisRange::fixed(0.0, 50.0).resolve(3.0, 97.0, 5), (0.0, 50.0));
+    /// ```
+    pub fn resolve(self, values_min: f64, values_max: f64, tick_count: usize) -> (f64, f64) {
+        match self {
+            AxisRange::Auto => {
+                let (min, max) = if values_min.is_finite() && values_max.is_finite() && values_min <= values_max {
+                    (values_min, values_max)
+                } else {
+                    (0.0, 1.0)
+                };
+                let (min, max) = if min == max { (min - 0.5, max + 0.5) } else { (min, max) };
+                let step = nice_step(min, max, tick_count);
+                ((min / step).floor() * step, (max / step).ceil() * step)
+            }
+            AxisRange::Fixed { min, max } => (min, max),
+        }
+    }
+}
+
+/// Gets a *nice* tick step, `1`, `2` or `5` times a power of ten, that divides the range in about `tick_count` parts.
+pub fn nice_step(min: f64, max: f64, tick_count: usize) -> f64 {
+    let raw = (max - min).abs() / tick_count.max(1) as f64;
+    if !raw.is_finite() || raw <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let fraction = raw / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Gets the tick values inside the range, the ticks are multiples of the [`nice_step`].
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt_chart::*;
+/// assert_eq!(nice_ticks(0.0, 100.0, 5), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+/// assert_eq!(nice_ticks(-10.0, 25.0, 4), vec![-10.0, 0.0, 10.0, 20.0]);
+/// ```
+pub fn nice_ticks(min: f64, max: f64, tick_count: usize) -> Vec<f64> {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    let step = nice_step(min, max, tick_count);
+    // tolerate rounding errors, so that the range limits are included.
+    let first = (min / step - 1e-9).ceil() as i64;
+    let last = (max / step + 1e-9).floor() as i64;
+    if last < first || last - first > 1000 {
+        return vec![];
+    }
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+/// Gets the number of decimals needed to format ticks of the `step`.
+pub(crate) fn step_decimals(step: f64) -> usize {
+    if step.is_finite() && step > 0.0 {
+        (-step.log10().floor()).max(0.0) as usize
+    } else {
+        0
+    }
+}
+
+/// Identifies a chart axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChartAxis {
+    /// Horizontal axis.
+    X,
+    /// Vertical axis.
+    Y,
+}
+
+/// Arguments for the [`tick_label_fn`].
+///
+/// [`tick_label_fn`]: fn@crate::tick_label_fn
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickLabelArgs {
+    /// The axis.
+    pub axis: ChartAxis,
+    /// Tick value.
+    pub value: f64,
+    /// Value formatted with the decimals needed for the tick step.
+    pub label: Txt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nice_step_magnitudes() {
+        assert_eq!(1.0, nice_step(0.0, 5.0, 5));
+        assert_eq!(2.0, nice_step(0.0, 8.0, 5));
+        assert_eq!(5.0, nice_step(0.0, 20.0, 5));
+        assert_eq!(10.0, nice_step(0.0, 35.0, 5));
+        assert_eq!(200.0, nice_step(0.0, 1000.0, 5));
+        assert!((nice_step(0.0, 0.1, 5) - 0.02).abs() < 1e-12);
+        assert_eq!(nice_step(0.0, 100.0, 5), nice_step(100.0, 0.0, 5));
+    }
+
+    #[test]
+    fn nice_step_degenerate() {
+        assert_eq!(1.0, nice_step(3.0, 3.0, 5));
+        assert_eq!(1.0, nice_step(0.0, f64::NAN, 5));
+        assert_eq!(1.0, nice_step(0.0, f64::INFINITY, 5));
+        // zero tick count is treated as one.
+        assert_eq!(nice_step(0.0, 10.0, 1), nice_step(0.0, 10.0, 0));
+    }
+
+    #[test]
+    fn nice_ticks_include_limits() {
+        assert_eq!(vec![0.0, 0.5, 1.0], nice_ticks(0.0, 1.0, 2));
+        assert_eq!(vec![-20.0, -10.0, 0.0], nice_ticks(-25.0, 0.0, 3));
+        // inverted range.
+        assert_eq!(nice_ticks(0.0, 100.0, 5), nice_ticks(100.0, 0.0, 5));
+
+        let ticks = nice_ticks(0.0, 0.3, 3);
+        assert_eq!(4, ticks.len());
+        assert!((ticks[3] - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn nice_ticks_degenerate() {
+        assert_eq!(vec![0.0], nice_ticks(0.0, 0.0, 5));
+        assert!(nice_ticks(0.0, f64::INFINITY, 5).is_empty());
+        // too many ticks for the range.
+        assert!(nice_ticks(0.0, 1e12, 5).len() <= 6);
+    }
+
+    #[test]
+    fn resolve_auto() {
+        assert_eq!((0.0, 100.0), AxisRange::Auto.resolve(3.0, 97.0, 5));
+        assert_eq!((-40.0, 20.0), AxisRange::Auto.resolve(-33.0, 12.0, 5));
+        // single value is expanded.
+        let (min, max) = AxisRange::Auto.resolve(5.0, 5.0, 5);
+        assert!((min - 4.4).abs() < 1e-9 && (max - 5.6).abs() < 1e-9);
+        // no values.
+        assert_eq!((0.0, 1.0), AxisRange::Auto.resolve(f64::INFINITY, f64::NEG_INFINITY, 5));
+    }
+
+    #[test]
+    fn resolve_auto_fits_ticks() {
+        for (min, max, count) in [(3.0, 97.0, 5), (-0.7, 0.9, 4), (1234.0, 5678.0, 7), (-1e6, -2.0, 3)] {
+            let (r_min, r_max) = AxisRange::Auto.resolve(min, max, count);
+            assert!(r_min <= min && r_max >= max);
+            let ticks = nice_ticks(r_min, r_max, count);
+            assert!((ticks[0] - r_min).abs() < 1e-9 * r_min.abs().max(1.0));
+            assert!((ticks[ticks.len() - 1] - r_max).abs() < 1e-9 * r_max.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn resolve_fixed() {
+        assert_eq!((0.0, 50.0), AxisRange::fixed(0.0, 50.0).resolve(3.0, 97.0, 5));
+        assert_eq!((10.0, -10.0), AxisRange::fixed(10.0, -10.0).resolve(0.0, 1.0, 5));
+    }
+
+    #[test]
+    fn step_decimals_for_labels() {
+        assert_eq!(0, step_decimals(1.0));
+        assert_eq!(0, step_decimals(200.0));
+        assert_eq!(1, step_decimals(0.5));
+        assert_eq!(2, step_decimals(0.02));
+        assert_eq!(0, step_decimals(0.0));
+        assert_eq!(0, step_decimals(f64::NAN));
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use zng_wgt::prelude::*;
+
+/// How a [`ChartSeries`] is presented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SeriesKind {
+    /// Line that connects the values.
+    Line,
+    /// Line that connects the values, with the area between the line and zero filled.
+    Area,
+    /// Bar from zero to each value.
+    ///
+    /// Bars of multiple series are placed side by side.
+    Bar,
+}
+
+/// Data series presented by a [`Chart!`].
+///
+/// The series values are an [`ObservableVec<f64>`] variable, the chart tracks the changes and only regenerates
+/// the display items of the changed ranges, so appending values to a large series is cheap. Non-finite values
+/// are skipped.
+///
+/// The X value of each item is `x_start + index * x_step`, for time series the X is usually the time since a
+/// reference instant, in a unit appropriate for the sample interval.
+///
+/// [`Chart!`]: struct@crate::Chart
+#[derive(Clone)]
+pub struct ChartSeries {
+    /// Series values.
+    pub values: BoxedVar<ObservableVec<f64>>,
+    /// How the values are presented.
+    pub kind: SeriesKind,
+    /// X value of the first item.
+    ///
+    /// Is `0.0` by default.
+    pub x_start: f64,
+    /// X distance between items.
+    ///
+    /// Is `1.0` by default.
+    pub x_step: f64,
+    /// Line, area and bar color.
+    ///
+    /// Areas are filled with this color at 40% opacity.
+    pub color: Rgba,
+    /// Width of the line.
+    ///
+    /// Is `2.dip()` by default.
+    pub line_width: Length,
+    /// Display name of the series.
+    pub label: Txt,
+}
+impl ChartSeries {
+    /// New series.
+    pub fn new(kind: SeriesKind, values: impl IntoVar<ObservableVec<f64>>) -> Self {
+        Self {
+            values: values.into_var().boxed(),
+            kind,
+            x_start: 0.0,
+            x_step: 1.0,
+            color: colors::AZURE,
+            line_width: 2.dip(),
+            label: Txt::from_static(""),
+        }
+    }
+
+    /// New line series.
+    pub fn line(values: impl IntoVar<ObservableVec<f64>>) -> Self {
+        Self::new(SeriesKind::Line, values)
+    }
+
+    /// New area series.
+    pub fn area(values: impl IntoVar<ObservableVec<f64>>) -> Self {
+        Self::new(SeriesKind::Area, values)
+    }
+
+    /// New bar series.
+    pub fn bar(values: impl IntoVar<ObservableVec<f64>>) -> Self {
+        Self::new(SeriesKind::Bar, values)
+    }
+
+    /// Set the X value of the first item and the X distance between items.
+    pub fn with_x(mut self, start: f64, step: f64) -> Self {
+        self.x_start = start;
+        self.x_step = step;
+        self
+    }
+
+    /// Set the color.
+    pub fn with_color(mut self, color: impl Into<Rgba>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the line width.
+    pub fn with_line_width(mut self, width: impl Into<Length>) -> Self {
+        self.line_width = width.into();
+        self
+    }
+
+    /// Set the display name.
+    pub fn with_label(mut self, label: impl Into<Txt>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Gets the X value of the item at the `index`.
+    pub fn x(&self, index: usize) -> f64 {
+        self.x_start + index as f64 * self.x_step
+    }
+}
+impl fmt::Debug for ChartSeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChartSeries")
+            .field("kind", &self.kind)
+            .field("x_start", &self.x_start)
+            .field("x_step", &self.x_step)
+            .field("color", &self.color)
+            .field("line_width", &self.line_width)
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+impl PartialEq for ChartSeries {
+    fn eq(&self, other: &Self) -> bool {
+        self.values.var_ptr() == other.values.var_ptr()
+            && self.kind == other.kind
+            && self.x_start == other.x_start
+            && self.x_step == other.x_step
+            && self.color == other.color
+            && self.line_width == other.line_width
+            && self.label == other.label
+    }
+}
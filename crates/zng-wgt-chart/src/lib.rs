@@ -0,0 +1,683 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Chart widget, properties and nodes.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use zng_app::render::ReuseRange;
+use zng_var::types::VecChange;
+use zng_wgt::prelude::*;
+use zng_wgt_text::Text;
+
+mod axis;
+pub use axis::*;
+
+mod data;
+pub use data::*;
+
+/// Line, area and bar chart.
+///
+/// The chart presents the [`series`] over a grid with labeled ticks on the X and Y axis, the axis ranges
+/// fit the series values by default, see [`x_range`] and [`y_range`] to set a fixed range.
+///
+/// # Rendering
+///
+/// The series are rendered using display items only, lines are rotated rectangles and areas are composed of rectangles
+/// and clipped rotated rectangles, so the chart is fully rendered by the GPU. The display items are grouped in
+/// reusable ranges of items, when a series values update only the ranges affected by the change are regenerated,
+/// for example, appending values to a time series only regenerates the last range unless the axis range changes.
+///
+/// # Shorthand
+///
+/// The `Chart!` macro provides a shorthand init that sets `series` property directly.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt_chart::*;
+/// # use zng_wgt::prelude::*;
+/// # fn main() { }
+/// # fn shorthand_demo(values: ArcVar<ObservableVec<f64>>) -> impl UiNode {
+/// Chart!(vec![ChartSeries::line(values)])
+/// # }
+/// ```
+///
+/// [`series`]: fn@series
+/// [`x_range`]: fn@x_range
+/// [`y_range`]: fn@y_range
+#[widget($crate::Chart {
+    ($series:expr) => {
+        series = $series;
+    }
+})]
+pub struct Chart(WidgetBase);
+
+context_var! {
+    /// Range of the X axis.
+    pub static X_RANGE_VAR: AxisRange = AxisRange::Auto;
+
+    /// Range of the Y axis.
+    pub static Y_RANGE_VAR: AxisRange = AxisRange::Auto;
+
+    /// Target number of ticks in each axis.
+    ///
+    /// Is `5` by default.
+    pub static TICK_COUNT_VAR: usize = 5;
+
+    /// Widget function for the tick labels.
+    pub static TICK_LABEL_FN_VAR: WidgetFn<TickLabelArgs> = WidgetFn::new(default_tick_label_fn);
+
+    /// Color of the grid lines.
+    pub static GRID_COLOR_VAR: Rgba = colors::GRAY.with_alpha(40.pct());
+}
+
+/// Range of the X axis.
+///
+/// Is [`AxisRange::Auto`] by default.
+///
+/// Sets the [`X_RANGE_VAR`].
+#[property(CONTEXT, default(X_RANGE_VAR), widget_impl(Chart))]
+pub fn x_range(child: impl UiNode, range: impl IntoVar<AxisRange>) -> impl UiNode {
+    with_context_var(child, X_RANGE_VAR, range)
+}
+
+/// Range of the Y axis.
+///
+/// Is [`AxisRange::Auto`] by default.
+///
+/// Sets the [`Y_RANGE_VAR`].
+#[property(CONTEXT, default(Y_RANGE_VAR), widget_impl(Chart))]
+pub fn y_range(child: impl UiNode, range: impl IntoVar<AxisRange>) -> impl UiNode {
+    with_context_var(child, Y_RANGE_VAR, range)
+}
+
+/// Target number of ticks in each axis.
+///
+/// The actual ticks are *nice* values near the target count, see [`nice_ticks`] for details.
+///
+/// Sets the [`TICK_COUNT_VAR`].
+#[property(CONTEXT, default(TICK_COUNT_VAR), widget_impl(Chart))]
+pub fn tick_count(child: impl UiNode, count: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, TICK_COUNT_VAR, count)
+}
+
+/// Widget function for the tick labels.
+///
+/// Sets the [`TICK_LABEL_FN_VAR`].
+#[property(CONTEXT+1, default(TICK_LABEL_FN_VAR), widget_impl(Chart))]
+pub fn tick_label_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<TickLabelArgs>>) -> impl UiNode {
+    with_context_var(child, TICK_LABEL_FN_VAR, wgt_fn)
+}
+
+/// Color of the grid lines.
+///
+/// Sets the [`GRID_COLOR_VAR`].
+#[property(CONTEXT, default(GRID_COLOR_VAR), widget_impl(Chart))]
+pub fn grid_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+    with_context_var(child, GRID_COLOR_VAR, color)
+}
+
+/// Default [`TICK_LABEL_FN_VAR`].
+///
+/// Returns a `Text!` with the label.
+pub fn default_tick_label_fn(args: TickLabelArgs) -> impl UiNode {
+    Text!(args.label)
+}
+
+/// The data series.
+///
+/// Note that this property replaces the widget child.
+///
+/// # Examples
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_chart::*;
+/// # fn main() { }
+/// # fn demo() -> impl UiNode {
+/// let cpu = var(ObservableVec::<f64>::new());
+/// let mem = var(ObservableVec::<f64>::new());
+///
+/// // append samples, only the last display items are regenerated.
+/// cpu.modify(|v| v.to_mut().push(12.0));
+///
+/// Chart! {
+///     series = vec![
+///         ChartSeries::area(cpu).with_label("CPU").with_x(0.0, 0.5),
+///         ChartSeries::line(mem).with_label("Memory").with_x(0.0, 0.5).with_color(colors::ORANGE),
+///     ];
+///     y_range = AxisRange::fixed(0.0, 100.0);
+/// }
+/// # }
+/// ```
+#[property(CHILD, widget_impl(Chart))]
+pub fn series(_child: impl UiNode, series: impl IntoVar<Vec<ChartSeries>>) -> impl UiNode {
+    let series = series.into_var();
+
+    let mut data_handles = VarHandles::dummy();
+    let mut caches = Vec::<SeriesCache>::new();
+    let mut axes = Axes::default();
+    let mut plot = PxRect::zero();
+    let mut label_offsets = Vec::<PxVector>::new();
+    let labels_id = SpatialFrameId::new_unique();
+
+    match_node_list(UiNodeVec::new(), move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&series)
+                .sub_var(&X_RANGE_VAR)
+                .sub_var(&Y_RANGE_VAR)
+                .sub_var(&TICK_COUNT_VAR)
+                .sub_var(&TICK_LABEL_FN_VAR)
+                .sub_var_render(&GRID_COLOR_VAR);
+
+            series.with(|s| {
+                let id = WIDGET.id();
+                for s in s {
+                    data_handles.push(s.values.subscribe(UpdateOp::Update, id));
+                }
+                caches = s.iter().map(|_| SeriesCache::new()).collect();
+                axes = Axes::new(s);
+            });
+            *c.children() = axes.labels();
+        }
+        UiNodeOp::Deinit => {
+            c.deinit_all();
+            c.children().clear();
+            data_handles.clear();
+            caches.clear();
+        }
+        UiNodeOp::Update { updates } => {
+            let mut values_changed = false;
+            if series.is_new() {
+                data_handles.clear();
+                series.with(|s| {
+                    let id = WIDGET.id();
+                    for s in s {
+                        data_handles.push(s.values.subscribe(UpdateOp::Update, id));
+                    }
+                    caches = s.iter().map(|_| SeriesCache::new()).collect();
+                });
+                values_changed = true;
+                WIDGET.layout().render();
+            } else {
+                series.with(|s| {
+                    for (s, cache) in s.iter().zip(&mut caches) {
+                        if let Some(i) = s.values.with_new(first_changed) {
+                            // a segment depends on the previous item.
+                            cache.invalidate_from(i.saturating_sub(1));
+                            values_changed = true;
+                        }
+                    }
+                });
+                if values_changed {
+                    WIDGET.render();
+                }
+            }
+
+            if values_changed || X_RANGE_VAR.is_new() || Y_RANGE_VAR.is_new() || TICK_COUNT_VAR.is_new() || TICK_LABEL_FN_VAR.is_new() {
+                let new_axes = series.with(|s| Axes::new(s));
+                if new_axes != axes || TICK_LABEL_FN_VAR.is_new() {
+                    if new_axes.x != axes.x || new_axes.y != axes.y {
+                        caches.iter_mut().for_each(SeriesCache::invalidate);
+                    }
+                    axes = new_axes;
+
+                    c.deinit_all();
+                    *c.children() = axes.labels();
+                    c.init_all();
+                    c.delegated();
+                    WIDGET.update_info().layout().render();
+                    return;
+                }
+            }
+
+            c.update_all(updates, &mut ());
+        }
+        UiNodeOp::Measure { desired_size, .. } => {
+            c.delegated();
+            let default = DipSize::new(Dip::new(300), Dip::new(150)).to_px(LAYOUT.scale_factor());
+            *desired_size = LAYOUT.constraints().fill_size_or(default);
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            wl.require_child_ref_frame();
+
+            let default = DipSize::new(Dip::new(300), Dip::new(150)).to_px(LAYOUT.scale_factor());
+            let size = LAYOUT.constraints().fill_size_or(default);
+
+            let label_sizes: Vec<_> = (0..c.children().len())
+                .map(|i| LAYOUT.with_constraints(PxConstraints2d::new_unbounded(), || c.with_node(i, |n| n.layout(wl))))
+                .collect();
+            let (x_labels, y_labels) = label_sizes.split_at(axes.x_ticks.len().min(label_sizes.len()));
+
+            let gap = Dip::new(4).to_px(LAYOUT.scale_factor());
+            let left = y_labels.iter().map(|s| s.width + gap).max().unwrap_or_default();
+            let bottom = x_labels.iter().map(|s| s.height + gap).max().unwrap_or_default();
+            let top = y_labels.iter().map(|s| s.height / Px(2)).max().unwrap_or_default();
+            let right = x_labels.last().map(|s| s.width / Px(2)).unwrap_or_default();
+            let new_plot = PxRect::new(
+                PxPoint::new(left, top),
+                PxSize::new((size.width - left - right).max(Px(0)), (size.height - top - bottom).max(Px(0))),
+            );
+            if plot != new_plot {
+                plot = new_plot;
+                caches.iter_mut().for_each(SeriesCache::invalidate);
+                WIDGET.render();
+            }
+
+            series.with(|s| {
+                for (s, cache) in s.iter().zip(&mut caches) {
+                    let w = s.line_width.layout_x().max(Px(1));
+                    if cache.line_width != w {
+                        cache.line_width = w;
+                        cache.invalidate();
+                        WIDGET.render();
+                    }
+                }
+            });
+
+            let map = PlotMap::new(plot, &axes);
+            let offsets: Vec<_> = axes
+                .x_ticks
+                .iter()
+                .zip(x_labels)
+                .map(|(v, s)| PxVector::new(Px(map.x(*v) as i32) - s.width / Px(2), plot.max_y() + gap))
+                .chain(
+                    axes.y_ticks
+                        .iter()
+                        .zip(y_labels)
+                        .map(|(v, s)| PxVector::new(left - gap - s.width, Px(map.y(*v) as i32) - s.height / Px(2))),
+                )
+                .collect();
+            if label_offsets != offsets {
+                label_offsets = offsets;
+                WIDGET.render();
+            }
+
+            *final_size = size;
+        }
+        UiNodeOp::Render { frame } => {
+            let map = PlotMap::new(plot, &axes);
+
+            let grid = FrameValue::Value(GRID_COLOR_VAR.get());
+            for x in &axes.x_ticks {
+                let x = Px(map.x(*x) as i32);
+                frame.push_color(PxRect::new(PxPoint::new(x, plot.min_y()), PxSize::new(Px(1), plot.height())), grid);
+            }
+            for y in &axes.y_ticks {
+                let y = Px(map.y(*y) as i32);
+                frame.push_color(PxRect::new(PxPoint::new(plot.min_x(), y), PxSize::new(plot.width(), Px(1))), grid);
+            }
+
+            frame.push_clip_rect(plot, false, false, |frame| {
+                series.with(|s| {
+                    let bar_count = s.iter().filter(|s| s.kind == SeriesKind::Bar).count();
+                    let mut bar_index = 0;
+                    for (s, cache) in s.iter().zip(&mut caches) {
+                        let bar = if s.kind == SeriesKind::Bar {
+                            bar_index += 1;
+                            Some((bar_index - 1, bar_count))
+                        } else {
+                            None
+                        };
+                        render_series(frame, s, cache, &map, bar);
+                    }
+                });
+            });
+
+            for (i, o) in label_offsets.iter().enumerate() {
+                frame.push_reference_frame((labels_id, i as u32).into(), FrameValue::Value((*o).into()), true, false, |frame| {
+                    c.with_node(i, |n| n.render(frame));
+                });
+            }
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            for (i, o) in label_offsets.iter().enumerate() {
+                update.with_transform_value(&(*o).into(), |update| {
+                    c.with_node(i, |n| n.render_update(update));
+                });
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Number of items in each reusable range of display items.
+const CHUNK_LEN: usize = 128;
+
+/// Gets the index of the first item changed in the last update.
+fn first_changed(values: &ObservableVec<f64>) -> usize {
+    let changes = values.changes();
+    if changes.is_empty() {
+        // replaced
+        return 0;
+    }
+    changes
+        .iter()
+        .map(|c| match c {
+            VecChange::Remove { index, .. } | VecChange::Insert { index, .. } => *index,
+            VecChange::Move { from_index, to_index } => (*from_index).min(*to_index),
+            VecChange::Clear => 0,
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Display items of a series, grouped for reuse.
+struct SeriesCache {
+    chunks: Vec<Option<ReuseRange>>,
+    line_id: SpatialFrameId,
+    area_id: SpatialFrameId,
+    line_width: Px,
+}
+impl SeriesCache {
+    fn new() -> Self {
+        Self {
+            chunks: vec![],
+            line_id: SpatialFrameId::new_unique(),
+            area_id: SpatialFrameId::new_unique(),
+            line_width: Px(0),
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.chunks.clear();
+    }
+
+    fn invalidate_from(&mut self, index: usize) {
+        self.chunks.truncate(index / CHUNK_LEN);
+    }
+}
+
+/// Axis ranges and ticks.
+#[derive(Clone, Debug, PartialEq, Default)]
+struct Axes {
+    x: (f64, f64),
+    y: (f64, f64),
+    x_ticks: Vec<f64>,
+    y_ticks: Vec<f64>,
+    x_decimals: usize,
+    y_decimals: usize,
+}
+impl Axes {
+    fn new(series: &[ChartSeries]) -> Self {
+        let mut x = (f64::INFINITY, f64::NEG_INFINITY);
+        let mut y = (f64::INFINITY, f64::NEG_INFINITY);
+        for s in series {
+            s.values.with(|v| {
+                for (i, value) in v.iter().enumerate() {
+                    if value.is_finite() {
+                        let vx = s.x(i);
+                        x = (x.0.min(vx), x.1.max(vx));
+                        y = (y.0.min(*value), y.1.max(*value));
+                    }
+                }
+                if !v.is_empty() {
+                    if s.kind != SeriesKind::Line {
+                        // include the zero baseline
+                        y = (y.0.min(0.0), y.1.max(0.0));
+                    }
+                    if s.kind == SeriesKind::Bar {
+                        // bars are centered on the x
+                        let half = s.x_step.abs() / 2.0;
+                        x = (x.0 - half, x.1 + half);
+                    }
+                }
+            });
+        }
+
+        let count = TICK_COUNT_VAR.get();
+        let x = X_RANGE_VAR.get().resolve(x.0, x.1, count);
+        let y = Y_RANGE_VAR.get().resolve(y.0, y.1, count);
+        Self {
+            x,
+            y,
+            x_ticks: nice_ticks(x.0, x.1, count),
+            y_ticks: nice_ticks(y.0, y.1, count),
+            x_decimals: step_decimals(nice_step(x.0, x.1, count)),
+            y_decimals: step_decimals(nice_step(y.0, y.1, count)),
+        }
+    }
+
+    /// Tick label widgets, X labels then Y labels.
+    fn labels(&self) -> UiNodeVec {
+        let label_fn = TICK_LABEL_FN_VAR.get();
+        let x = self.x_ticks.iter().map(|v| (ChartAxis::X, *v, self.x_decimals));
+        let y = self.y_ticks.iter().map(|v| (ChartAxis::Y, *v, self.y_decimals));
+        x.chain(y)
+            .map(|(axis, value, decimals)| {
+                label_fn(TickLabelArgs {
+                    axis,
+                    value,
+                    label: formatx!("{value:.decimals$}"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Maps values to the plot area.
+struct PlotMap {
+    plot: euclid::Rect<f32, Px>,
+    x: (f64, f64),
+    y: (f64, f64),
+}
+impl PlotMap {
+    fn new(plot: PxRect, axes: &Axes) -> Self {
+        Self {
+            plot: plot.cast(),
+            x: axes.x,
+            y: axes.y,
+        }
+    }
+
+    fn x(&self, x: f64) -> f32 {
+        let len = self.x.1 - self.x.0;
+        let f = if len == 0.0 { 0.0 } else { (x - self.x.0) / len };
+        self.plot.min_x() + self.plot.width() * f as f32
+    }
+
+    fn y(&self, y: f64) -> f32 {
+        let len = self.y.1 - self.y.0;
+        let f = if len == 0.0 { 0.0 } else { (y - self.y.0) / len };
+        self.plot.max_y() - self.plot.height() * f as f32
+    }
+
+    fn point(&self, x: f64, y: f64) -> euclid::Point2D<f32, Px> {
+        euclid::point2(self.x(x), self.y(y))
+    }
+
+    /// Y of the zero baseline, clamped to the plot area.
+    fn baseline(&self) -> f32 {
+        self.y(0.0).clamp(self.plot.min_y(), self.plot.max_y())
+    }
+}
+
+/// Render the series, `bar` is the index of the bar series and the number of bar series.
+fn render_series(frame: &mut FrameBuilder, s: &ChartSeries, cache: &mut SeriesCache, map: &PlotMap, bar: Option<(usize, usize)>) {
+    let width = cache.line_width.0 as f32;
+    let fill = Rgba {
+        alpha: s.color.alpha * 0.4,
+        ..s.color
+    };
+
+    s.values.with(|v| {
+        let len = v.len();
+        cache.chunks.resize_with(len.div_ceil(CHUNK_LEN), || None);
+        let (line_id, area_id) = (cache.line_id, cache.area_id);
+
+        for (c, chunk) in cache.chunks.iter_mut().enumerate() {
+            let range = c * CHUNK_LEN..((c + 1) * CHUNK_LEN).min(len);
+            frame.push_reuse(chunk, |frame| {
+                for i in range {
+                    if !v[i].is_finite() {
+                        continue;
+                    }
+                    let a = map.point(s.x(i), v[i]);
+                    if let Some((index, count)) = bar {
+                        let slot = (map.x(s.x_step.abs()) - map.x(0.0)) * 0.8;
+                        let w = slot / count as f32;
+                        let x = a.x - slot / 2.0 + w * index as f32;
+                        let base = map.baseline();
+                        push_rect(frame, x, a.y.min(base), w, (a.y - base).abs(), s.color);
+                        continue;
+                    }
+
+                    if let Some(b) = v.get(i + 1).filter(|b| b.is_finite()) {
+                        let b = map.point(s.x(i + 1), *b);
+                        if s.kind == SeriesKind::Area {
+                            push_area(frame, (area_id, i as u32 * 2), a, b, map.baseline(), fill);
+                        }
+                        push_segment(frame, (line_id, i as u32), a, b, width, s.color);
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn push_rect(frame: &mut FrameBuilder, x: f32, y: f32, width: f32, height: f32, color: Rgba) {
+    let rect: PxRect = euclid::rect::<f32, Px>(x, y, width, height).round().cast();
+    if !rect.is_empty() {
+        frame.push_color(rect, FrameValue::Value(color));
+    }
+}
+
+/// Line segment as a rotated rectangle.
+fn push_segment(
+    frame: &mut FrameBuilder,
+    id: (SpatialFrameId, u32),
+    a: euclid::Point2D<f32, Px>,
+    b: euclid::Point2D<f32, Px>,
+    width: f32,
+    color: Rgba,
+) {
+    let d = b - a;
+    let len = d.length();
+    if len <= 0.0 {
+        return;
+    }
+    let transform = PxTransform::rotation(0.0, 0.0, euclid::Angle::radians(d.y.atan2(d.x))).then_translate(a.to_vector());
+    frame.push_reference_frame(id.into(), FrameValue::Value(transform), false, false, |frame| {
+        push_rect(frame, 0.0, -width / 2.0, len, width, color);
+    });
+}
+
+/// Area between a line segment and the `baseline`.
+fn push_area(
+    frame: &mut FrameBuilder,
+    id: (SpatialFrameId, u32),
+    a: euclid::Point2D<f32, Px>,
+    b: euclid::Point2D<f32, Px>,
+    baseline: f32,
+    color: Rgba,
+) {
+    if (a.y - baseline) * (b.y - baseline) < 0.0 {
+        // crosses the baseline, fill each side
+        let c = euclid::point2(a.x + (b.x - a.x) * (baseline - a.y) / (b.y - a.y), baseline);
+        push_area_part(frame, id, a, c, baseline, color);
+        push_area_part(frame, (id.0, id.1 + 1), c, b, baseline, color);
+    } else {
+        push_area_part(frame, id, a, b, baseline, color);
+    }
+}
+
+/// Area between a line segment that does not cross the `baseline` and the baseline.
+///
+/// The area is a rectangle from the baseline to the nearest point plus a right triangle under the segment, the
+/// triangle is a rectangle rotated to align with the segment, clipped by the triangle bounds.
+fn push_area_part(
+    frame: &mut FrameBuilder,
+    id: (SpatialFrameId, u32),
+    a: euclid::Point2D<f32, Px>,
+    b: euclid::Point2D<f32, Px>,
+    baseline: f32,
+    color: Rgba,
+) {
+    let width = b.x - a.x;
+    if width <= 0.0 {
+        return;
+    }
+    let above = a.y.max(b.y) <= baseline;
+    let (near, far) = if above {
+        (a.y.max(b.y), a.y.min(b.y))
+    } else {
+        (a.y.min(b.y), a.y.max(b.y))
+    };
+
+    push_rect(frame, a.x, near.min(baseline), width, (baseline - near).abs(), color);
+
+    let height = far - near;
+    if height.abs() < 0.5 {
+        return;
+    }
+    let clip: PxRect = euclid::rect::<f32, Px>(a.x, near.min(far), width, height.abs()).round().cast();
+    let d = b - a;
+    let len = d.length();
+    let transform = PxTransform::rotation(0.0, 0.0, euclid::Angle::radians(d.y.atan2(d.x))).then_translate(a.to_vector());
+    frame.push_clip_rect(clip, false, false, |frame| {
+        frame.push_reference_frame(id.into(), FrameValue::Value(transform), false, false, |frame| {
+            // the rotated Y points away from the segment, down if the area is under the segment.
+            let h = height.abs() + 1.0;
+            push_rect(frame, 0.0, if above { 0.0 } else { -h }, len, h, color);
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> PlotMap {
+        let axes = Axes {
+            x: (0.0, 10.0),
+            y: (-50.0, 50.0),
+            ..Default::default()
+        };
+        PlotMap::new(PxRect::new(PxPoint::new(Px(10), Px(20)), PxSize::new(Px(100), Px(200))), &axes)
+    }
+
+    #[test]
+    fn plot_map_scale() {
+        let map = map();
+        assert_eq!(10.0, map.x(0.0));
+        assert_eq!(60.0, map.x(5.0));
+        assert_eq!(110.0, map.x(10.0));
+
+        // y grows up.
+        assert_eq!(220.0, map.y(-50.0));
+        assert_eq!(120.0, map.y(0.0));
+        assert_eq!(20.0, map.y(50.0));
+
+        assert_eq!(euclid::point2(35.0, 170.0), map.point(2.5, -25.0));
+    }
+
+    #[test]
+    fn plot_map_baseline() {
+        assert_eq!(120.0, map().baseline());
+
+        let mut above = map();
+        above.y = (10.0, 20.0);
+        assert_eq!(220.0, above.baseline());
+
+        let mut below = map();
+        below.y = (-20.0, -10.0);
+        assert_eq!(20.0, below.baseline());
+    }
+
+    #[test]
+    fn plot_map_empty_range() {
+        let mut map = map();
+        map.x = (3.0, 3.0);
+        map.y = (3.0, 3.0);
+        assert_eq!(10.0, map.x(100.0));
+        assert_eq!(220.0, map.y(100.0));
+    }
+}
@@ -0,0 +1,273 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Command palette widget and properties.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use zng_app::event::{CommandEntry, FuzzyMatch, COMMANDS};
+use zng_ext_input::{
+    focus::FOCUS,
+    keyboard::{Key, KeyInputArgs, KeyState, KEY_INPUT_EVENT},
+};
+use zng_wgt::{corner_radius, prelude::*};
+use zng_wgt_container::{padding, Container};
+use zng_wgt_fill::background_color;
+use zng_wgt_filter::opacity;
+use zng_wgt_input::{cursor, focus::focus_scope, gesture::on_click, mouse::on_mouse_enter, CursorIcon};
+use zng_wgt_layer::{
+    popup::{PopupState, CONTEXT_CAPTURE_VAR, POPUP},
+    AnchorMode, AnchorOffset, AnchorSize,
+};
+use zng_wgt_scroll::{Scroll, ScrollMode};
+use zng_wgt_size_offset::{max_height, width};
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_text::Text;
+use zng_wgt_text_input::TextInput;
+
+command! {
+    /// Represents the **command palette** action.
+    ///
+    /// The [`command_palette`] property handles this command by opening a [`CommandPalette!`] popup.
+    ///
+    /// [`command_palette`]: fn@command_palette
+    /// [`CommandPalette!`]: struct@CommandPalette
+    pub static COMMAND_PALETTE_CMD = {
+        name: "Command Palette",
+        shortcut: [shortcut!(CTRL|SHIFT+'P'), shortcut!(F1)],
+    };
+}
+
+/// Handle the [`COMMAND_PALETTE_CMD`] in the widget scope by opening a [`CommandPalette!`] popup.
+///
+/// Set this property in the window to enable the command palette for the window, the palette lists the
+/// commands that are enabled in the context of the focused widget at the time it is opened.
+///
+/// [`CommandPalette!`]: struct@CommandPalette
+#[property(EVENT, default(false))]
+pub fn command_palette(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    let enabled = enabled.into_var();
+    let mut handle = CommandHandle::dummy();
+    let mut pop_state = var(PopupState::Closed).read_only();
+
+    match_node(child, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&enabled);
+            handle = COMMAND_PALETTE_CMD.scoped(WIDGET.id()).subscribe(enabled.get());
+        }
+        UiNodeOp::Deinit => {
+            handle = CommandHandle::dummy();
+            POPUP.close(&pop_state);
+        }
+        UiNodeOp::Event { update } => {
+            c.event(update);
+            if let Some(args) = COMMAND_PALETTE_CMD.scoped(WIDGET.id()).on_unhandled(update) {
+                args.propagation().stop();
+                if matches!(pop_state.get(), PopupState::Closed) {
+                    pop_state = POPUP.open_config(CommandPalette!(), PALETTE_ANCHOR_VAR, CONTEXT_CAPTURE_VAR.get());
+                }
+            }
+        }
+        UiNodeOp::Update { .. } => {
+            if let Some(e) = enabled.get_new() {
+                handle.set_enabled(e);
+            }
+        }
+        _ => {}
+    })
+}
+
+context_var! {
+    /// Position of the command palette popup opened by [`command_palette`].
+    ///
+    /// Is centered at the top of the widget by default.
+    ///
+    /// [`command_palette`]: fn@command_palette
+    pub static PALETTE_ANCHOR_VAR: AnchorMode = AnchorMode {
+        min_size: AnchorSize::Unbounded,
+        ..AnchorMode::popup(AnchorOffset::in_top())
+    };
+}
+
+/// Set the position of the command palette popup opened by [`command_palette`].
+///
+/// This property sets the [`PALETTE_ANCHOR_VAR`].
+///
+/// [`command_palette`]: fn@command_palette
+#[property(CONTEXT, default(PALETTE_ANCHOR_VAR))]
+pub fn command_palette_anchor(child: impl UiNode, mode: impl IntoVar<AnchorMode>) -> impl UiNode {
+    with_context_var(child, PALETTE_ANCHOR_VAR, mode)
+}
+
+/// Command palette.
+///
+/// Lists the commands enabled in the context of the widget focused when the palette is inited, the user can
+/// fuzzy search by command name and info, navigate using the arrow keys and invoke the selected command with `Enter`
+/// or by clicking.
+///
+/// When open as a popup the palette closes itself on `Escape` and when a command is invoked.
+///
+/// See [`COMMANDS`] for more details about the command enumeration and search.
+#[widget($crate::CommandPalette)]
+pub struct CommandPalette(WidgetBase);
+impl CommandPalette {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            focus_scope = true;
+            width = 500;
+            padding = 4;
+            corner_radius = 4;
+            background_color = light_dark(rgb(0.9, 0.9, 0.9), rgb(0.1, 0.1, 0.1));
+        }
+        self.widget_builder().push_build_action(|wgt| {
+            wgt.set_child(command_palette_node());
+        });
+    }
+}
+
+/// Implements the [`CommandPalette!`] inner widgets.
+///
+/// [`CommandPalette!`]: struct@CommandPalette
+pub fn command_palette_node() -> impl UiNode {
+    let search = var(Txt::from(""));
+    let selected = var(0usize);
+    let mut results = var(vec![]).boxed();
+    let mut palette_id = None;
+
+    match_node(NilUiNode.boxed(), move |c, op| match op {
+        UiNodeOp::Init => {
+            let id = WIDGET.id();
+            palette_id = Some(id);
+
+            let entries = match FOCUS.focused().get() {
+                Some(p) if !p.contains(id) => COMMANDS.enumerate_scoped(p.as_path()),
+                _ => COMMANDS.enumerate_scoped(&WINDOW.info().root().path()),
+            };
+            results = search.map(move |s| COMMANDS.search(entries.clone(), s)).boxed();
+            WIDGET.sub_var(&results).sub_event(&KEY_INPUT_EVENT);
+
+            *c.child() = palette_view(search.clone(), results.clone(), selected.clone(), id).boxed();
+        }
+        UiNodeOp::Deinit => {
+            c.deinit();
+            *c.child() = NilUiNode.boxed();
+        }
+        UiNodeOp::Update { .. } => {
+            if results.is_new() {
+                selected.set(0usize);
+            }
+        }
+        UiNodeOp::Event { update } => {
+            // preview, the search input must not handle the navigation keys.
+            if let Some(args) = KEY_INPUT_EVENT.on_unhandled(update) {
+                if args.state == KeyState::Pressed && args.modifiers.is_empty() {
+                    on_palette_key(args, &results, &selected, palette_id.unwrap());
+                }
+            }
+            c.event(update);
+        }
+        _ => {}
+    })
+}
+
+fn on_palette_key(
+    args: &KeyInputArgs,
+    results: &BoxedVar<Vec<(CommandEntry, FuzzyMatch)>>,
+    selected: &ArcVar<usize>,
+    palette_id: WidgetId,
+) {
+    let len = results.with(|r| r.len());
+    match &args.key {
+        Key::ArrowDown if len > 0 => {
+            args.propagation().stop();
+            selected.set((selected.get() + 1) % len);
+        }
+        Key::ArrowUp if len > 0 => {
+            args.propagation().stop();
+            selected.set((selected.get() + len - 1) % len);
+        }
+        Key::Enter => {
+            args.propagation().stop();
+            if let Some((e, _)) = results.with(|r| r.get(selected.get()).cloned()) {
+                invoke(palette_id, e);
+            }
+        }
+        Key::Escape => {
+            args.propagation().stop();
+            POPUP.close_id(palette_id);
+        }
+        _ => {}
+    }
+}
+
+fn invoke(palette_id: WidgetId, entry: CommandEntry) {
+    POPUP.close_id(palette_id);
+    entry.command.notify();
+}
+
+fn palette_view(
+    search: ArcVar<Txt>,
+    results: BoxedVar<Vec<(CommandEntry, FuzzyMatch)>>,
+    selected: ArcVar<usize>,
+    palette_id: WidgetId,
+) -> impl UiNode {
+    Container! {
+        child_top = TextInput! {
+            txt = search;
+            style_fn = zng_wgt_text_input::SearchStyle!();
+            placeholder_txt = "search commands";
+            zng_wgt_input::focus::focus_on_init = true;
+        }, 4;
+        child = Scroll! {
+            mode = ScrollMode::VERTICAL;
+            max_height = 400;
+            child_align = Align::FILL_TOP;
+            child = presenter(
+                results,
+                wgt_fn!(selected, |results: Vec<(CommandEntry, FuzzyMatch)>| {
+                    Stack! {
+                        direction = StackDirection::top_to_bottom();
+                        children = results
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, (e, _))| palette_item(i, e, selected.clone(), palette_id).boxed())
+                            .collect::<UiNodeVec>();
+                    }
+                }),
+            );
+        };
+    }
+}
+
+fn palette_item(index: usize, entry: CommandEntry, selected: ArcVar<usize>, palette_id: WidgetId) -> impl UiNode {
+    let shortcut = entry.shortcut.first().map(|s| s.to_txt()).unwrap_or_default();
+    Container! {
+        padding = (2, 4);
+        cursor = CursorIcon::Pointer;
+        background_color = merge_var!(selected.clone(), colors::ACCENT_COLOR_VAR.rgba(), move |&s, &c| {
+            if s == index {
+                c.with_alpha(40.pct())
+            } else {
+                c.transparent()
+            }
+        });
+        child = Text!(entry.name.clone());
+        child_end = Text! {
+            txt = shortcut;
+            opacity = 70.pct();
+        }, 10;
+        on_mouse_enter = hn!(selected, |_| {
+            selected.set(index);
+        });
+        on_click = hn!(|_| {
+            invoke(palette_id, entry.clone());
+        });
+    }
+}
@@ -46,7 +46,7 @@ pub fn child(child: impl UiNode) {}
 /// Margin space around the content of a widget.
 ///
 /// This property is [`margin`](fn@margin) with nest group `CHILD_LAYOUT`.
-#[property(CHILD_LAYOUT, default(0), widget_impl(Container))]
+#[property(CHILD_LAYOUT, animatable, default(0), widget_impl(Container))]
 pub fn padding(child: impl UiNode, padding: impl IntoVar<SideOffsets>) -> impl UiNode {
     margin(child, padding)
 }
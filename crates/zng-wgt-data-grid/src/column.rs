@@ -0,0 +1,350 @@
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use zng_wgt::prelude::*;
+use zng_wgt_access::SortDirection;
+
+/// Function that compares two rows, see [`DataColumn::with_sort`].
+pub type SortFn<T> = Arc<dyn Fn(&T, &T) -> Ordering + Send + Sync>;
+
+/// Column definition of a [`DataGrid!`].
+///
+/// The columns are defined by a variable, the grid updates when the columns change. If the variable is read-write
+/// user resize and reorder of columns is applied to the variable.
+///
+/// [`DataGrid!`]: struct@crate::DataGrid
+pub struct DataColumn<T: VarValue> {
+    /// Header text.
+    pub header: Txt,
+    /// Column width.
+    ///
+    /// Relative values are relative to the grid available width. The user resize sets this to an exact value.
+    ///
+    /// Is `100.dip()` by default.
+    pub width: Length,
+    /// Minimum column width.
+    ///
+    /// Is `20.dip()` by default.
+    pub min_width: Length,
+    /// If the user can resize the column by dragging the header right edge.
+    ///
+    /// Is `true` by default.
+    pub resizable: bool,
+    /// If the user can move the column by dragging the header.
+    ///
+    /// Is `true` by default.
+    pub reorderable: bool,
+    /// Function that generates the column cells.
+    pub cell_fn: WidgetFn<DataCellArgs<T>>,
+    /// Function that compares rows, if set the user can sort the rows by this column by clicking the header.
+    pub sort_fn: Option<SortFn<T>>,
+}
+impl<T: VarValue> DataColumn<T> {
+    /// New column with the default width.
+    pub fn new(header: impl Into<Txt>, cell_fn: WidgetFn<DataCellArgs<T>>) -> Self {
+        Self {
+            header: header.into(),
+            width: 100.dip(),
+            min_width: 20.dip(),
+            resizable: true,
+            reorderable: true,
+            cell_fn,
+            sort_fn: None,
+        }
+    }
+
+    /// Set the column width.
+    pub fn with_width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the minimum column width.
+    pub fn with_min_width(mut self, min_width: impl Into<Length>) -> Self {
+        self.min_width = min_width.into();
+        self
+    }
+
+    /// Set if the user can resize and reorder the column.
+    pub fn with_user_edit(mut self, resizable: bool, reorderable: bool) -> Self {
+        self.resizable = resizable;
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Enable sorting by this column using the compare function.
+    pub fn with_sort(mut self, sort_fn: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static) -> Self {
+        self.sort_fn = Some(Arc::new(sort_fn));
+        self
+    }
+
+    /// Enable sorting by this column using the key function.
+    pub fn with_sort_key<K: Ord>(self, key_fn: impl Fn(&T) -> K + Send + Sync + 'static) -> Self {
+        self.with_sort(move |a, b| key_fn(a).cmp(&key_fn(b)))
+    }
+
+    /// If the column can sort.
+    pub fn can_sort(&self) -> bool {
+        self.sort_fn.is_some()
+    }
+}
+impl<T: VarValue> Clone for DataColumn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            width: self.width.clone(),
+            min_width: self.min_width.clone(),
+            resizable: self.resizable,
+            reorderable: self.reorderable,
+            cell_fn: self.cell_fn.clone(),
+            sort_fn: self.sort_fn.clone(),
+        }
+    }
+}
+impl<T: VarValue> fmt::Debug for DataColumn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataColumn")
+            .field("header", &self.header)
+            .field("width", &self.width)
+            .field("min_width", &self.min_width)
+            .field("resizable", &self.resizable)
+            .field("reorderable", &self.reorderable)
+            .field("can_sort", &self.can_sort())
+            .finish_non_exhaustive()
+    }
+}
+impl<T: VarValue> PartialEq for DataColumn<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header
+            && self.width == other.width
+            && self.min_width == other.min_width
+            && self.resizable == other.resizable
+            && self.reorderable == other.reorderable
+            && self.cell_fn == other.cell_fn
+            && match (&self.sort_fn, &other.sort_fn) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+    }
+}
+
+/// Arguments for the [`DataColumn::cell_fn`].
+///
+/// Row widgets are recycled, the cells must bind to the variables to present the current row.
+#[derive(Clone)]
+pub struct DataCellArgs<T: VarValue> {
+    /// Index of the column.
+    pub column: usize,
+    /// Index of the row in the source rows.
+    pub row: BoxedVar<usize>,
+    /// The row data.
+    pub data: BoxedVar<T>,
+    /// If the cell is selected, either because the row is selected or the cell is in the selected range.
+    pub is_selected: BoxedVar<bool>,
+}
+
+/// Arguments for the [`header_fn`].
+///
+/// [`header_fn`]: fn@crate::header_fn
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataHeaderArgs {
+    /// Index of the column.
+    pub column: usize,
+    /// Column header text.
+    pub header: Txt,
+    /// If the column can sort.
+    pub can_sort: bool,
+    /// Current sort direction of the column.
+    pub sort: Option<SortDirection>,
+}
+
+/// Represents the sorting of a [`DataGrid!`].
+///
+/// [`DataGrid!`]: struct@crate::DataGrid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnSort {
+    /// Index of the sort column.
+    pub column: usize,
+    /// Sort direction.
+    pub direction: SortDirection,
+}
+impl ColumnSort {
+    /// Sort ascending by the column.
+    pub fn ascending(column: usize) -> Self {
+        Self {
+            column,
+            direction: SortDirection::Ascending,
+        }
+    }
+
+    /// Sort descending by the column.
+    pub fn descending(column: usize) -> Self {
+        Self {
+            column,
+            direction: SortDirection::Descending,
+        }
+    }
+
+    /// Next sort after the user clicks the `column` header.
+    ///
+    /// Clicking a new column sorts ascending, clicking the sort column toggles the direction.
+    pub fn toggle(current: Option<Self>, column: usize) -> Self {
+        match current {
+            Some(s) if s.column == column && s.direction == SortDirection::Ascending => Self::descending(column),
+            _ => Self::ascending(column),
+        }
+    }
+}
+
+/// Columns offsets and widths computed for a layout pass.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub(crate) struct ColumnsLayout {
+    pub x: Vec<Px>,
+    pub width: Vec<Px>,
+    pub frozen: usize,
+}
+impl ColumnsLayout {
+    pub fn new<T: VarValue>(columns: &[DataColumn<T>], frozen: usize) -> Self {
+        let mut x = Vec::with_capacity(columns.len());
+        let mut width = Vec::with_capacity(columns.len());
+        let mut offset = Px(0);
+        for c in columns {
+            let w = c.width.layout_x().max(c.min_width.layout_x()).max(Px(0));
+            x.push(offset);
+            width.push(w);
+            offset += w;
+        }
+        Self {
+            x,
+            width,
+            frozen: frozen.min(columns.len()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn total_width(&self) -> Px {
+        match (self.x.last(), self.width.last()) {
+            (Some(x), Some(w)) => *x + *w,
+            _ => Px(0),
+        }
+    }
+
+    /// Width of the frozen columns.
+    pub fn frozen_width(&self) -> Px {
+        if self.frozen == 0 {
+            Px(0)
+        } else {
+            self.x[self.frozen - 1] + self.width[self.frozen - 1]
+        }
+    }
+
+    /// Gets the rendered offset of the column, `frozen_x` is the current offset of the frozen columns.
+    pub fn offset(&self, column: usize, frozen_x: Px) -> Px {
+        if column < self.frozen {
+            self.x[column] + frozen_x
+        } else {
+            self.x[column]
+        }
+    }
+
+    /// Gets the column at the `x` offset, `frozen_x` is the current offset of the frozen columns.
+    pub fn column_at(&self, x: Px, frozen_x: Px) -> Option<usize> {
+        let in_frozen = x - frozen_x;
+        if in_frozen >= Px(0) && in_frozen < self.frozen_width() {
+            return (0..self.frozen).find(|&i| in_frozen >= self.x[i] && in_frozen < self.x[i] + self.width[i]);
+        }
+        (self.frozen..self.len()).find(|&i| x >= self.x[i] && x < self.x[i] + self.width[i])
+    }
+
+    /// Gets the column with the right edge near the `x` offset.
+    pub fn edge_at(&self, x: Px, frozen_x: Px, tolerance: Px) -> Option<usize> {
+        (0..self.len()).rev().find(|&i| {
+            let edge = self.offset(i, frozen_x) + self.width[i];
+            (x - edge).abs() <= tolerance
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zng_app_context::AppId;
+
+    use super::*;
+
+    fn column(width: impl Into<Length>) -> DataColumn<u32> {
+        DataColumn::new("", WidgetFn::nil()).with_width(width)
+    }
+
+    fn layout(columns: &[DataColumn<u32>], frozen: usize, scale_factor: Factor) -> ColumnsLayout {
+        let _app = LocalContext::start_app(AppId::new_unique());
+        let metrics = LayoutMetrics::new(scale_factor, PxSize::new(Px(1000), Px(500)), Px(16));
+        LAYOUT.with_context(metrics, || ColumnsLayout::new(columns, frozen))
+    }
+
+    fn test_columns() -> Vec<DataColumn<u32>> {
+        vec![
+            column(Px(100)),
+            column(Px(50)),
+            column(Px(5)).with_min_width(Px(20)),
+            column(10.pct()),
+        ]
+    }
+
+    #[test]
+    fn columns_sizing() {
+        let l = layout(&test_columns(), 0, 1.fct());
+
+        assert_eq!(l.width, [Px(100), Px(50), Px(20), Px(100)]);
+        assert_eq!(l.x, [Px(0), Px(100), Px(150), Px(170)]);
+        assert_eq!(l.total_width(), Px(270));
+        assert_eq!(l.frozen_width(), Px(0));
+    }
+
+    #[test]
+    fn columns_sizing_scale_factor() {
+        let columns = vec![column(100.dip()).with_min_width(20.dip()), column(5.dip()).with_min_width(20.dip())];
+        let l = layout(&columns, 0, 2.fct());
+
+        assert_eq!(l.width, [Px(200), Px(40)]);
+        assert_eq!(l.total_width(), Px(240));
+    }
+
+    #[test]
+    fn columns_frozen() {
+        let l = layout(&test_columns(), 1, 1.fct());
+        assert_eq!(l.frozen_width(), Px(100));
+
+        // scrolled right by 60, the frozen column is rendered at the scroll offset.
+        assert_eq!(l.offset(0, Px(60)), Px(60));
+        assert_eq!(l.offset(1, Px(60)), Px(100));
+        assert_eq!(l.column_at(Px(70), Px(60)), Some(0));
+        assert_eq!(l.column_at(Px(170), Px(60)), Some(3));
+        assert_eq!(l.column_at(Px(120), Px(0)), Some(1));
+        assert_eq!(l.column_at(Px(300), Px(0)), None);
+
+        let l = layout(&test_columns(), 10, 1.fct());
+        assert_eq!(l.frozen, 4);
+        assert_eq!(l.frozen_width(), Px(270));
+    }
+
+    #[test]
+    fn columns_edge_at() {
+        let l = layout(&test_columns(), 0, 1.fct());
+
+        assert_eq!(l.edge_at(Px(149), Px(0), Px(2)), Some(1));
+        assert_eq!(l.edge_at(Px(272), Px(0), Px(2)), Some(3));
+        assert_eq!(l.edge_at(Px(125), Px(0), Px(2)), None);
+    }
+
+    #[test]
+    fn columns_empty() {
+        let l = layout(&[], 2, 1.fct());
+        assert_eq!(l.len(), 0);
+        assert_eq!(l.total_width(), Px(0));
+        assert_eq!(l.frozen_width(), Px(0));
+        assert_eq!(l.column_at(Px(0), Px(0)), None);
+    }
+}
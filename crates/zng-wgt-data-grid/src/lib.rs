@@ -0,0 +1,814 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Data grid widget, properties and nodes.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use std::sync::Arc;
+
+use zng_ext_input::mouse::{MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT};
+use zng_wgt::prelude::*;
+use zng_wgt_access::{access_role, col_index, row_index, AccessRole, SortDirection};
+use zng_wgt_container::Container;
+use zng_wgt_input::{
+    cursor,
+    pointer_capture::{capture_pointer, CaptureMode},
+    CursorIcon, CursorSource,
+};
+use zng_wgt_scroll::{
+    virtual_list::{self, VirtualItemArgs},
+    WidgetInfoExt as _, SCROLL,
+};
+use zng_wgt_text::Text;
+
+mod column;
+pub use column::*;
+
+mod select;
+pub use select::*;
+
+/// Tabular data presenter with user sortable, resizable and reorderable columns.
+///
+/// The grid presents a list of rows of type `T` in columns defined by [`DataColumn<T>`], the first row is a header
+/// with the column titles that stays visible at the top of the parent scroll viewport. The [`frozen_columns`] stay
+/// visible at the left of the viewport when the grid scrolls horizontally.
+///
+/// # User Edit
+///
+/// Clicking a column header sorts the rows by the column, if the column has a sort function, clicking again inverts
+/// the sort direction, the current sort is in the [`sort`] variable. Dragging the right edge of a column header resizes
+/// the column and dragging the header moves the column, these edits are applied to the `columns` variable
+/// if it is read-write.
+///
+/// Clicking a cell selects it according to the [`selection_mode`], the current selection is in the [`selection`] variable.
+///
+/// # Virtualization
+///
+/// Rows are virtualized, only the rows near the parent scroll viewport are instantiated and row widgets are recycled as
+/// the grid scrolls, all rows have the same [`row_height`]. The grid must be inside a `Scroll!` to virtualize.
+///
+/// # Shorthand
+///
+/// The `DataGrid!` macro provides a shorthand init that sets `grid` property directly.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt_data_grid::*;
+/// # use zng_wgt::prelude::*;
+/// # fn main() { }
+/// # fn shorthand_demo<T: VarValue>(columns: Vec<DataColumn<T>>, rows: Vec<T>) -> impl UiNode {
+/// DataGrid!(::<T>, columns, rows)
+/// # }
+/// ```
+///
+/// Note that the first argument is a *turbo-fish* that defines the row type and is required.
+///
+/// [`frozen_columns`]: fn@frozen_columns
+/// [`sort`]: fn@sort
+/// [`selection_mode`]: fn@selection_mode
+/// [`selection`]: fn@selection
+/// [`row_height`]: fn@row_height
+#[widget($crate::DataGrid {
+    (::<$T:ty>, $columns:expr, $rows:expr $(,)?) => {
+        grid::<$T> = {
+            columns: $columns,
+            rows: $rows,
+        };
+    }
+})]
+pub struct DataGrid(WidgetBase);
+impl DataGrid {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            access_role = AccessRole::Grid;
+            sort = var(None);
+            selection = var(GridSelection::None);
+        }
+    }
+}
+
+context_var! {
+    /// Current sort of the rows.
+    pub static SORT_VAR: Option<ColumnSort> = None;
+
+    /// Current selection.
+    pub static SELECTION_VAR: GridSelection = GridSelection::None;
+
+    /// How the user can select cells.
+    pub static SELECTION_MODE_VAR: SelectionMode = SelectionMode::Single;
+
+    /// Number of columns that stay visible when the grid scrolls horizontally.
+    pub static FROZEN_COLUMNS_VAR: usize = 0;
+
+    /// Height of the header and each row.
+    ///
+    /// Is `28.dip()` by default.
+    pub static ROW_HEIGHT_VAR: Length = 28.dip();
+
+    /// Widget function for the column headers.
+    pub static HEADER_FN_VAR: WidgetFn<DataHeaderArgs> = WidgetFn::new(default_header_fn);
+
+    /// Background color of selected cells.
+    pub static SELECTION_COLOR_VAR: Rgba = colors::AZURE.with_alpha(30.pct());
+}
+
+/// Current sort of the rows.
+///
+/// If the variable is read-write the user can sort by clicking the column headers. The sort is `None` by default,
+/// that presents the rows in the source order.
+///
+/// Sets the [`SORT_VAR`].
+#[property(CONTEXT, default(SORT_VAR), widget_impl(DataGrid))]
+pub fn sort(child: impl UiNode, sort: impl IntoVar<Option<ColumnSort>>) -> impl UiNode {
+    with_context_var(child, SORT_VAR, sort)
+}
+
+/// Current selection.
+///
+/// If the variable is read-write the user can select by clicking the cells. Note that selected rows are identified
+/// by the source row index, the selection should be cleared when the rows are replaced.
+///
+/// Sets the [`SELECTION_VAR`].
+#[property(CONTEXT, default(SELECTION_VAR), widget_impl(DataGrid))]
+pub fn selection(child: impl UiNode, selection: impl IntoVar<GridSelection>) -> impl UiNode {
+    with_context_var(child, SELECTION_VAR, selection)
+}
+
+/// How the user can select cells.
+///
+/// Is [`SelectionMode::Single`] by default.
+///
+/// Sets the [`SELECTION_MODE_VAR`].
+#[property(CONTEXT, default(SELECTION_MODE_VAR), widget_impl(DataGrid))]
+pub fn selection_mode(child: impl UiNode, mode: impl IntoVar<SelectionMode>) -> impl UiNode {
+    with_context_var(child, SELECTION_MODE_VAR, mode)
+}
+
+/// Number of columns that stay visible when the grid scrolls horizontally.
+///
+/// The first columns are *frozen* at the left of the parent scroll viewport, the other columns scroll under them.
+///
+/// Is `0` by default.
+///
+/// Sets the [`FROZEN_COLUMNS_VAR`].
+#[property(CONTEXT, default(FROZEN_COLUMNS_VAR), widget_impl(DataGrid))]
+pub fn frozen_columns(child: impl UiNode, count: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, FROZEN_COLUMNS_VAR, count)
+}
+
+/// Height of the header and each row.
+///
+/// All rows have the same height, this is required for virtualization.
+///
+/// Sets the [`ROW_HEIGHT_VAR`].
+#[property(CONTEXT, default(ROW_HEIGHT_VAR), widget_impl(DataGrid))]
+pub fn row_height(child: impl UiNode, height: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, ROW_HEIGHT_VAR, height)
+}
+
+/// Widget function for the column headers.
+///
+/// Sets the [`HEADER_FN_VAR`].
+#[property(CONTEXT+1, default(HEADER_FN_VAR), widget_impl(DataGrid))]
+pub fn header_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<DataHeaderArgs>>) -> impl UiNode {
+    with_context_var(child, HEADER_FN_VAR, wgt_fn)
+}
+
+/// Background color of selected cells.
+///
+/// Sets the [`SELECTION_COLOR_VAR`].
+#[property(CONTEXT, default(SELECTION_COLOR_VAR), widget_impl(DataGrid))]
+pub fn selection_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+    with_context_var(child, SELECTION_COLOR_VAR, color)
+}
+
+/// Default [`HEADER_FN_VAR`].
+///
+/// Returns a `Text!` with the header and an arrow that indicates the sort direction.
+pub fn default_header_fn(args: DataHeaderArgs) -> impl UiNode {
+    let txt = match args.sort {
+        Some(SortDirection::Ascending) => formatx!("{} ▲", args.header),
+        Some(SortDirection::Descending) => formatx!("{} ▼", args.header),
+        None => args.header,
+    };
+    Text!(txt)
+}
+
+/// The columns and rows.
+///
+/// The `columns` define the header and the cell widgets of each column, if the variable is read-write the
+/// user can resize and reorder the columns. The `rows` are presented sorted by the [`sort`].
+///
+/// Note that this property replaces the widget child.
+///
+/// # Examples
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_data_grid::*;
+/// # fn main() { }
+/// # fn cell(a: DataCellArgs<(Txt, u32)>) -> impl UiNode { NilUiNode }
+/// # fn demo(rows: Vec<(Txt, u32)>) -> impl UiNode {
+/// DataGrid! {
+///     grid::<(Txt, u32)> = {
+///         columns: var(vec![
+///             DataColumn::new("Name", wgt_fn!(|a| cell(a))).with_sort_key(|r: &(Txt, u32)| r.0.clone()),
+///             DataColumn::new("Size", wgt_fn!(|a| cell(a))).with_sort_key(|r: &(Txt, u32)| r.1),
+///         ]),
+///         rows: rows,
+///     };
+///     frozen_columns = 1;
+///     selection_mode = SelectionMode::Multi;
+/// }
+/// # }
+/// ```
+///
+/// [`sort`]: fn@sort
+#[property(CHILD, widget_impl(DataGrid))]
+pub fn grid<T: VarValue>(_child: impl UiNode, columns: impl IntoVar<Vec<DataColumn<T>>>, rows: impl IntoVar<Vec<T>>) -> impl UiNode {
+    let columns = columns.into_var().boxed();
+    let rows = rows.into_var();
+
+    let view = var(ObservableVec::<GridRow<T>>::new());
+    let layout = var(ColumnsLayout::default());
+    let cell_fns = columns.map(|c| c.iter().map(|c| c.cell_fn.clone()).collect::<Vec<_>>());
+
+    let row_fn = wgt_fn!(cell_fns, layout, view, |a: VirtualItemArgs<GridRow<T>>| {
+        grid_row(a, cell_fns.clone(), layout.read_only(), view.read_only())
+    });
+    let body = with_context_var(
+        virtual_list::node(view.clone(), row_fn),
+        virtual_list::ITEM_HEIGHT_VAR,
+        ROW_HEIGHT_VAR,
+    );
+    let header = grid_header(columns.clone(), layout.read_only());
+
+    let body_id = SpatialFrameId::new_unique();
+    let header_key = FrameValueKey::new_unique();
+    let mut header_height = Px(0);
+    let mut body_height = Px(0);
+    let mut sort_fn = None::<SortFn<T>>;
+
+    match_node_list(ui_vec![header, body], move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&rows)
+                .sub_var(&columns)
+                .sub_var(&SORT_VAR)
+                .sub_var_layout(&FROZEN_COLUMNS_VAR)
+                .sub_var_layout(&ROW_HEIGHT_VAR)
+                .sub_var_render_update(&SCROLL.vertical_offset());
+
+            let sort = SORT_VAR.get();
+            sort_fn = columns.with(|c| column_sort_fn(c, sort));
+            view.set(rows.with(|r| sorted_view(r, sort, &sort_fn)));
+        }
+        UiNodeOp::Update { .. } => {
+            if columns.is_new() {
+                WIDGET.layout();
+            }
+
+            let sort = SORT_VAR.get();
+            let new_sort_fn = columns.with(|c| column_sort_fn(c, sort));
+            let sort_fn_changed = match (&sort_fn, &new_sort_fn) {
+                (None, None) => false,
+                (Some(a), Some(b)) => !Arc::ptr_eq(a, b),
+                _ => true,
+            };
+            if rows.is_new() || SORT_VAR.is_new() || sort_fn_changed {
+                sort_fn = new_sort_fn;
+                view.set(rows.with(|r| sorted_view(r, sort, &sort_fn)));
+            }
+        }
+        UiNodeOp::Measure { wm, desired_size } => {
+            let constraints = LAYOUT.constraints();
+            let header = ROW_HEIGHT_VAR.layout_y();
+            let width = columns.with(|c| ColumnsLayout::new(c, FROZEN_COLUMNS_VAR.get())).total_width();
+            let body = LAYOUT.with_constraints(constraints.with_less_y(header).with_new_min(Px(0), Px(0)), || {
+                c.with_node(1, |n| n.measure(wm))
+            });
+            *desired_size = constraints.fill_size_or(PxSize::new(width.max(body.width), header + body.height));
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            wl.require_child_ref_frame();
+
+            let columns_layout = columns.with(|c| ColumnsLayout::new(c, FROZEN_COLUMNS_VAR.get()));
+            let constraints = LAYOUT.constraints();
+            let width = constraints.x.clamp(constraints.x.fill_or(columns_layout.total_width()));
+            if layout.with(|l| l != &columns_layout) {
+                layout.set(columns_layout);
+            }
+
+            let header = LAYOUT.with_constraints(PxConstraints2d::new_exact(width, ROW_HEIGHT_VAR.layout_y()), || {
+                c.with_node(0, |n| n.layout(wl))
+            });
+            let body = LAYOUT.with_constraints(
+                constraints.with_exact_x(width).with_less_y(header.height).with_new_min_y(Px(0)),
+                || c.with_node(1, |n| n.layout(wl)),
+            );
+            if header_height != header.height {
+                header_height = header.height;
+                WIDGET.render();
+            }
+            body_height = body.height;
+
+            *final_size = constraints.clamp_size(PxSize::new(width, header.height + body.height));
+        }
+        UiNodeOp::Render { frame } => {
+            let body_offset = PxTransform::from(PxVector::new(Px(0), header_height));
+            frame.push_reference_frame(body_id.into(), FrameValue::Value(body_offset), true, false, |frame| {
+                c.with_node(1, |n| n.render(frame));
+            });
+
+            // header is rendered last, over the rows that scrolled under it.
+            let sticky = sticky_header_offset(frame.transform(), body_height);
+            frame.push_reference_frame(header_key.into(), header_key.bind(sticky, false), true, true, |frame| {
+                c.with_node(0, |n| n.render(frame));
+            });
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            let body_offset = PxTransform::from(PxVector::new(Px(0), header_height));
+            update.with_transform_value(&body_offset, |update| {
+                c.with_node(1, |n| n.render_update(update));
+            });
+
+            let sticky = sticky_header_offset(update.transform(), body_height);
+            update.with_transform(header_key.update(sticky, false), true, |update| {
+                c.with_node(0, |n| n.render_update(update));
+            });
+        }
+        _ => {}
+    })
+}
+
+/// Row in the sorted view.
+#[derive(Clone, Debug, PartialEq)]
+struct GridRow<T> {
+    /// Index in the source rows.
+    index: usize,
+    data: T,
+}
+
+fn column_sort_fn<T: VarValue>(columns: &[DataColumn<T>], sort: Option<ColumnSort>) -> Option<SortFn<T>> {
+    sort.and_then(|s| columns.get(s.column)).and_then(|c| c.sort_fn.clone())
+}
+
+fn sorted_view<T: VarValue>(rows: &[T], sort: Option<ColumnSort>, sort_fn: &Option<SortFn<T>>) -> ObservableVec<GridRow<T>> {
+    let mut view: Vec<_> = rows
+        .iter()
+        .enumerate()
+        .map(|(index, data)| GridRow { index, data: data.clone() })
+        .collect();
+    if let (Some(s), Some(f)) = (sort, sort_fn) {
+        view.sort_by(|a, b| match s.direction {
+            SortDirection::Ascending => f(&a.data, &b.data),
+            SortDirection::Descending => f(&b.data, &a.data),
+        });
+    }
+    view.into()
+}
+
+/// Top-left of the parent scroll viewport in the space of `transform`, clamped to not be negative.
+fn viewport_offset(transform: &PxTransform) -> PxVector {
+    let viewport = SCROLL.try_id().and_then(|id| WINDOW.info().get(id)).and_then(|w| w.viewport());
+    match (viewport, transform.inverse()) {
+        (Some(v), Some(t)) => match t.outer_transformed(v.to_box2d()) {
+            Some(v) => PxVector::new(v.min.x.max(Px(0)), v.min.y.max(Px(0))),
+            None => PxVector::zero(),
+        },
+        _ => PxVector::zero(),
+    }
+}
+
+fn sticky_header_offset(transform: &PxTransform, body_height: Px) -> PxTransform {
+    let y = viewport_offset(transform).y.min(body_height);
+    PxTransform::from(PxVector::new(Px(0), y))
+}
+
+/// Header row widget.
+fn grid_header<T: VarValue>(columns: BoxedVar<Vec<DataColumn<T>>>, layout: ReadOnlyArcVar<ColumnsLayout>) -> impl UiNode {
+    let headers = merge_var!(HEADER_FN_VAR, columns.clone(), SORT_VAR, |f, c, s| {
+        let args: Vec<_> = c
+            .iter()
+            .enumerate()
+            .map(|(i, c)| DataHeaderArgs {
+                column: i,
+                header: c.header.clone(),
+                can_sort: c.can_sort(),
+                sort: s.filter(|s| s.column == i).map(|s| s.direction),
+            })
+            .collect();
+        (f.clone(), args)
+    });
+    let cells = cells_node(
+        headers,
+        |(header_fn, args)| {
+            let mut cells = UiNodeVec::new();
+            for a in args {
+                let mut cell = header_fn.call(a.clone()).boxed();
+                if let Some(d) = a.sort {
+                    cell = zng_wgt_access::sort(cell, d).boxed();
+                }
+                cells.push(Container! {
+                    child = cell;
+                    access_role = AccessRole::ColumnHeader;
+                    col_index = a.column;
+                    child_align = Align::START;
+                    padding = (0, 4);
+                });
+            }
+            cells
+        },
+        layout.clone(),
+        colors::BASE_COLOR_VAR.shade(1),
+        |_| false,
+    );
+
+    let cursor_icon = var(CursorSource::from(CursorIcon::Default));
+    let mut drag = HeaderDrag::None;
+    let node = match_node(
+        cells,
+        clmv!(cursor_icon, |c, op| match op {
+            UiNodeOp::Init => {
+                WIDGET.sub_event(&MOUSE_INPUT_EVENT).sub_event(&MOUSE_MOVE_EVENT);
+            }
+            UiNodeOp::Deinit => {
+                drag = HeaderDrag::None;
+            }
+            UiNodeOp::Event { update } => {
+                c.event(update);
+
+                if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                    let p = match WIDGET.win_point_to_wgt(args.position) {
+                        Some(p) => p,
+                        None => return,
+                    };
+                    let frozen_x = viewport_offset(&WIDGET.info().inner_transform()).x;
+                    let icon = match drag {
+                        HeaderDrag::None => {
+                            let edge = layout.with(|l| l.edge_at(p.x, frozen_x, RESIZE_TOLERANCE));
+                            if edge.is_some_and(|i| columns.with(|c| c.get(i).is_some_and(|c| c.resizable))) {
+                                CursorIcon::ColResize
+                            } else {
+                                CursorIcon::Default
+                            }
+                        }
+                        HeaderDrag::Press { column, x } => {
+                            if (p.x - x).abs() > RESIZE_TOLERANCE && columns.with(|c| c.get(column).is_some_and(|c| c.reorderable)) {
+                                drag = HeaderDrag::Move { column };
+                                CursorIcon::Grabbing
+                            } else {
+                                CursorIcon::Default
+                            }
+                        }
+                        HeaderDrag::Resize { column, x, width } => {
+                            let width = (width + p.x - x).max(Px(0));
+                            let width = Length::Dip(width.to_dip(WINDOW.info().scale_factor()));
+                            let _ = columns.modify(move |c| {
+                                if let Some(c) = c.to_mut().get_mut(column) {
+                                    c.width = width;
+                                }
+                            });
+                            CursorIcon::ColResize
+                        }
+                        HeaderDrag::Move { .. } => CursorIcon::Grabbing,
+                    };
+                    if cursor_icon.with(|c| c != &CursorSource::from(icon)) {
+                        cursor_icon.set(icon);
+                    }
+                } else if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                    if !args.is_primary() {
+                        return;
+                    }
+                    let p = match WIDGET.win_point_to_wgt(args.position) {
+                        Some(p) => p,
+                        None => return,
+                    };
+                    let frozen_x = viewport_offset(&WIDGET.info().inner_transform()).x;
+
+                    if args.is_mouse_down() {
+                        let edge = layout
+                            .with(|l| l.edge_at(p.x, frozen_x, RESIZE_TOLERANCE).map(|i| (i, l.width[i])))
+                            .filter(|(i, _)| columns.with(|c| c.get(*i).is_some_and(|c| c.resizable)));
+                        drag = if let Some((column, width)) = edge {
+                            HeaderDrag::Resize { column, x: p.x, width }
+                        } else if let Some(column) = layout.with(|l| l.column_at(p.x, frozen_x)) {
+                            HeaderDrag::Press { column, x: p.x }
+                        } else {
+                            HeaderDrag::None
+                        };
+                    } else if args.is_mouse_up() {
+                        match std::mem::replace(&mut drag, HeaderDrag::None) {
+                            HeaderDrag::Press { column, .. } => {
+                                if columns.with(|c| c.get(column).is_some_and(|c| c.can_sort())) {
+                                    let _ = SORT_VAR.set(Some(ColumnSort::toggle(SORT_VAR.get(), column)));
+                                }
+                            }
+                            HeaderDrag::Move { column } => {
+                                let len = layout.with(|l| l.len());
+                                let to = layout
+                                    .with(|l| l.column_at(p.x, frozen_x))
+                                    .unwrap_or(if p.x < Px(0) { 0 } else { len - 1 });
+                                if to != column && to < len {
+                                    let _ = columns.modify(move |c| {
+                                        let c = c.to_mut();
+                                        let col = c.remove(column);
+                                        c.insert(to, col);
+                                    });
+                                    if let Some(s) = SORT_VAR.get() {
+                                        let moved = moved_index(s.column, column, to);
+                                        if moved != s.column {
+                                            let _ = SORT_VAR.set(Some(ColumnSort { column: moved, ..s }));
+                                        }
+                                    }
+                                }
+                                cursor_icon.set(CursorIcon::Default);
+                            }
+                            HeaderDrag::Resize { .. } | HeaderDrag::None => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }),
+    );
+
+    Container! {
+        child = node;
+        access_role = AccessRole::Row;
+        capture_pointer = CaptureMode::Subtree;
+        cursor = cursor_icon;
+    }
+}
+
+/// Distance from the column edge that starts a resize.
+const RESIZE_TOLERANCE: Px = Px(4);
+
+#[derive(Clone, Copy)]
+enum HeaderDrag {
+    None,
+    /// Pressed on the column header, can become a click or a move.
+    Press {
+        column: usize,
+        x: Px,
+    },
+    /// Resizing the column.
+    Resize {
+        column: usize,
+        x: Px,
+        width: Px,
+    },
+    /// Moving the column.
+    Move {
+        column: usize,
+    },
+}
+
+/// New index of the item at `i` after an item moves from `from` to `to`.
+fn moved_index(i: usize, from: usize, to: usize) -> usize {
+    if i == from {
+        to
+    } else if from < to && i > from && i <= to {
+        i - 1
+    } else if to < from && i >= to && i < from {
+        i + 1
+    } else {
+        i
+    }
+}
+
+/// Data row widget.
+fn grid_row<T: VarValue>(
+    args: VirtualItemArgs<GridRow<T>>,
+    cell_fns: impl Var<Vec<WidgetFn<DataCellArgs<T>>>>,
+    layout: ReadOnlyArcVar<ColumnsLayout>,
+    view: ReadOnlyArcVar<ObservableVec<GridRow<T>>>,
+) -> impl UiNode {
+    let display = args.index;
+    let row = args.item.map(|r| r.index).boxed();
+    let data = args.item.map(|r| r.data.clone()).boxed();
+
+    let cells = cells_node(
+        cell_fns,
+        clmv!(display, row, data, |cell_fns| {
+            let mut cells = UiNodeVec::new();
+            for (i, f) in cell_fns.iter().enumerate() {
+                let is_selected = merge_var!(SELECTION_VAR, display.clone(), row.clone(), move |s, d, r| {
+                    s.contains_cell(CellPos::new(*d, i), *r)
+                });
+                let cell = f.call(DataCellArgs {
+                    column: i,
+                    row: row.clone(),
+                    data: data.clone(),
+                    is_selected: is_selected.boxed(),
+                });
+                cells.push(Container! {
+                    child = cell;
+                    access_role = AccessRole::GridCell;
+                    col_index = i;
+                });
+            }
+            cells
+        }),
+        layout.clone(),
+        colors::BASE_COLOR_VAR.rgba(),
+        clmv!(display, row, |i| SELECTION_VAR
+            .with(|s| s.contains_cell(CellPos::new(display.get(), i), row.get()))),
+    );
+
+    let node = match_node(
+        cells,
+        clmv!(display, row, |c, op| match op {
+            UiNodeOp::Init => {
+                WIDGET
+                    .sub_event(&MOUSE_INPUT_EVENT)
+                    .sub_var_render(&SELECTION_VAR)
+                    .sub_var_render(&display)
+                    .sub_var_render(&row);
+            }
+            UiNodeOp::Event { update } => {
+                c.event(update);
+
+                if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                    if !args.is_primary() || !args.is_mouse_down() {
+                        return;
+                    }
+                    let mode = SELECTION_MODE_VAR.get();
+                    if let SelectionMode::None = mode {
+                        return;
+                    }
+                    let p = match WIDGET.win_point_to_wgt(args.position) {
+                        Some(p) => p,
+                        None => return,
+                    };
+                    let frozen_x = viewport_offset(&WIDGET.info().inner_transform()).x;
+                    if let Some(column) = layout.with(|l| l.column_at(p.x, frozen_x)) {
+                        let pos = CellPos::new(display.get(), column);
+                        let view: Vec<_> = view.with(|v| v.iter().map(|r| r.index).collect());
+                        let (ctrl, shift) = (args.modifiers.has_ctrl(), args.modifiers.has_shift());
+                        let selection = SELECTION_VAR.with(|s| s.clicked(mode, pos, &view, ctrl, shift));
+                        let _ = SELECTION_VAR.set(selection);
+                    }
+                }
+            }
+            _ => {}
+        }),
+    );
+
+    Container! {
+        child = node;
+        access_role = AccessRole::Row;
+        row_index = display;
+    }
+}
+
+/// Lays out one cell per column and renders the frozen cells over the other cells.
+///
+/// The cells are rebuilt by `build` when `build_var` updates.
+fn cells_node<B: VarValue>(
+    build_var: impl Var<B>,
+    build: impl Fn(&B) -> UiNodeVec + Send + 'static,
+    layout: ReadOnlyArcVar<ColumnsLayout>,
+    background: impl Var<Rgba>,
+    is_selected: impl Fn(usize) -> bool + Send + 'static,
+) -> impl UiNode {
+    let cell_id = SpatialFrameId::new_unique();
+    let frozen_key = FrameValueKey::new_unique();
+    let mut rendered_x = vec![];
+    // the columns var updates on every resize, only rebuild if the cells actually changed.
+    let mut built = None::<B>;
+
+    match_node_list(UiNodeVec::new(), move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&build_var)
+                .sub_var_layout(&layout)
+                .sub_var_layout(&ROW_HEIGHT_VAR)
+                .sub_var_render(&background)
+                .sub_var_render(&SELECTION_COLOR_VAR)
+                .sub_var_render_update(&SCROLL.horizontal_offset());
+
+            let b = build_var.get();
+            *c.children() = build(&b);
+            built = Some(b);
+        }
+        UiNodeOp::Deinit => {
+            c.deinit_all();
+            *c.children() = UiNodeVec::new();
+            built = None;
+        }
+        UiNodeOp::Update { updates } => match build_var.get_new() {
+            Some(b) if built.as_ref() != Some(&b) => {
+                c.deinit_all();
+                *c.children() = build(&b);
+                c.init_all();
+                built = Some(b);
+                WIDGET.update_info().layout().render();
+            }
+            _ => c.update_all(updates, &mut ()),
+        },
+        UiNodeOp::Measure { desired_size, .. } => {
+            c.delegated();
+            let width = layout.with(|l| l.total_width());
+            *desired_size = LAYOUT.constraints().fill_size_or(PxSize::new(width, ROW_HEIGHT_VAR.layout_y()));
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            wl.require_child_ref_frame();
+
+            let height = ROW_HEIGHT_VAR.layout_y();
+            layout.with(|l| {
+                c.for_each(|i, cell| {
+                    let width = l.width.get(i).copied().unwrap_or_default();
+                    LAYOUT.with_constraints(PxConstraints2d::new_exact(width, height), || cell.layout(wl));
+                });
+                if rendered_x != l.x {
+                    WIDGET.render();
+                }
+                *final_size = LAYOUT.constraints().fill_size_or(PxSize::new(l.total_width(), height));
+            });
+        }
+        UiNodeOp::Render { frame } => {
+            c.delegated();
+
+            let size = WIDGET.bounds().inner_size();
+            let frozen_x = viewport_offset(frame.transform()).x;
+            let background = background.get();
+            let selection = SELECTION_COLOR_VAR.get();
+            let children = c.children();
+
+            layout.with(|l| {
+                rendered_x.clone_from(&l.x);
+                let len = l.len().min(children.len());
+
+                frame.push_color(PxRect::from_size(size), FrameValue::Value(background));
+                for i in l.frozen..len {
+                    let offset = PxTransform::from(PxVector::new(l.x[i], Px(0)));
+                    if is_selected(i) {
+                        let rect = PxRect::new(PxPoint::new(l.x[i], Px(0)), PxSize::new(l.width[i], size.height));
+                        frame.push_color(rect, FrameValue::Value(selection));
+                    }
+                    frame.push_reference_frame((cell_id, i as u32).into(), FrameValue::Value(offset), true, false, |frame| {
+                        children.with_node(i, |n| n.render(frame));
+                    });
+                }
+
+                if l.frozen > 0 {
+                    let offset = PxTransform::from(PxVector::new(frozen_x, Px(0)));
+                    frame.push_reference_frame(frozen_key.into(), frozen_key.bind(offset, false), true, true, |frame| {
+                        frame.push_color(
+                            PxRect::from_size(PxSize::new(l.frozen_width(), size.height)),
+                            FrameValue::Value(background),
+                        );
+                        for i in 0..l.frozen.min(len) {
+                            let offset = PxTransform::from(PxVector::new(l.x[i], Px(0)));
+                            if is_selected(i) {
+                                let rect = PxRect::new(PxPoint::new(l.x[i], Px(0)), PxSize::new(l.width[i], size.height));
+                                frame.push_color(rect, FrameValue::Value(selection));
+                            }
+                            frame.push_reference_frame((cell_id, i as u32).into(), FrameValue::Value(offset), true, false, |frame| {
+                                children.with_node(i, |n| n.render(frame));
+                            });
+                        }
+                    });
+                }
+            });
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            c.delegated();
+
+            let frozen_x = viewport_offset(update.transform()).x;
+            let children = c.children();
+
+            layout.with(|l| {
+                let len = l.len().min(children.len());
+                for i in l.frozen..len {
+                    let offset = PxTransform::from(PxVector::new(l.x[i], Px(0)));
+                    update.with_transform_value(&offset, |update| {
+                        children.with_node(i, |n| n.render_update(update));
+                    });
+                }
+                if l.frozen > 0 {
+                    let offset = PxTransform::from(PxVector::new(frozen_x, Px(0)));
+                    update.with_transform(frozen_key.update(offset, false), true, |update| {
+                        for i in 0..l.frozen.min(len) {
+                            let offset = PxTransform::from(PxVector::new(l.x[i], Px(0)));
+                            update.with_transform_value(&offset, |update| {
+                                children.with_node(i, |n| n.render_update(update));
+                            });
+                        }
+                    });
+                }
+            });
+        }
+        _ => {}
+    })
+}
@@ -0,0 +1,189 @@
+use std::ops::RangeInclusive;
+
+/// Selection model of a [`DataGrid!`].
+///
+/// [`DataGrid!`]: struct@crate::DataGrid
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// User cannot select.
+    None,
+    /// User can select one row.
+    #[default]
+    Single,
+    /// User can select multiple rows, `CTRL+click` toggles a row and `SHIFT+click` selects a range of rows.
+    Multi,
+    /// User can select a rectangular range of cells, `SHIFT+click` extends the range.
+    Cells,
+}
+
+/// Position of a cell in the displayed grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellPos {
+    /// Displayed row index, after sorting.
+    pub row: usize,
+    /// Column index.
+    pub column: usize,
+}
+impl CellPos {
+    /// New position.
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+}
+
+/// Rectangular range of cells selected in a [`DataGrid!`].
+///
+/// [`DataGrid!`]: struct@crate::DataGrid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellRange {
+    /// Cell where the selection started.
+    pub anchor: CellPos,
+    /// Cell where the selection ends, can be before the anchor.
+    pub end: CellPos,
+}
+impl CellRange {
+    /// Range of a single cell.
+    pub fn cell(pos: CellPos) -> Self {
+        Self { anchor: pos, end: pos }
+    }
+
+    /// Displayed rows in the range.
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.row.min(self.end.row)..=self.anchor.row.max(self.end.row)
+    }
+
+    /// Columns in the range.
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.anchor.column.min(self.end.column)..=self.anchor.column.max(self.end.column)
+    }
+
+    /// If the cell is in the range.
+    pub fn contains(&self, pos: CellPos) -> bool {
+        self.rows().contains(&pos.row) && self.columns().contains(&pos.column)
+    }
+}
+
+/// Selection of a [`DataGrid!`].
+///
+/// Selected rows are identified by the index in the source rows, so that they remain selected when the rows are sorted,
+/// selected cells are identified by the displayed position.
+///
+/// [`DataGrid!`]: struct@crate::DataGrid
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum GridSelection {
+    /// Nothing selected.
+    #[default]
+    None,
+    /// Rows selected.
+    Rows {
+        /// Source row indexes, sorted.
+        rows: Vec<usize>,
+        /// Displayed row index of the last row clicked, used as the start of `SHIFT+click` ranges.
+        anchor: Option<usize>,
+    },
+    /// Cells selected.
+    Cells(CellRange),
+}
+impl GridSelection {
+    /// If nothing is selected.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Rows { rows, .. } => rows.is_empty(),
+            Self::Cells(_) => false,
+        }
+    }
+
+    /// Selected source rows.
+    pub fn rows(&self) -> &[usize] {
+        match self {
+            Self::Rows { rows, .. } => rows,
+            _ => &[],
+        }
+    }
+
+    /// If the source row is selected.
+    pub fn contains_row(&self, row: usize) -> bool {
+        self.rows().binary_search(&row).is_ok()
+    }
+
+    /// If the cell is selected, `row` is the source row index of the displayed row in `pos`.
+    pub fn contains_cell(&self, pos: CellPos, row: usize) -> bool {
+        match self {
+            Self::None => false,
+            Self::Rows { rows, .. } => rows.binary_search(&row).is_ok(),
+            Self::Cells(r) => r.contains(pos),
+        }
+    }
+
+    /// Selection after the user clicks the cell at `pos`.
+    ///
+    /// The `view` maps displayed row indexes to source row indexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zng_wgt_data_grid::*;
+    /// let view = [2, 0, 1];
+    /// let s = GridSelection::None.clicked(SelectionMode::Multi, CellPos::new(0, 0), &view, false, false);
+    /// let s = s.clicked(SelectionMode::Multi, CellPos::new(1, 0), &view, false, true);
+    /// assert_eq!(s.rows(), &[0, 2]);
+    ///
+    /// let s = s.clicked(SelectionMode::Multi, CellPos::new(2, 0), &view, true, false);
+    /// assert_eq!(s.rows(), &[0, 1, 2]);
+    /// ```
+    pub fn clicked(&self, mode: SelectionMode, pos: CellPos, view: &[usize], ctrl: bool, shift: bool) -> Self {
+        let row = match view.get(pos.row) {
+            Some(r) => *r,
+            None => return self.clone(),
+        };
+        match mode {
+            SelectionMode::None => self.clone(),
+            SelectionMode::Single => Self::Rows {
+                rows: vec![row],
+                anchor: Some(pos.row),
+            },
+            SelectionMode::Multi => match self {
+                Self::Rows {
+                    rows,
+                    anchor: Some(anchor),
+                } if shift => {
+                    let start = (*anchor).min(pos.row);
+                    let end = (*anchor).max(pos.row).min(view.len() - 1);
+                    let mut rows: Vec<_> = if ctrl { rows.clone() } else { vec![] };
+                    rows.extend_from_slice(&view[start..=end]);
+                    rows.sort_unstable();
+                    rows.dedup();
+                    Self::Rows {
+                        rows,
+                        anchor: Some(*anchor),
+                    }
+                }
+                Self::Rows { rows, .. } if ctrl => {
+                    let mut rows = rows.clone();
+                    match rows.binary_search(&row) {
+                        Ok(i) => {
+                            rows.remove(i);
+                        }
+                        Err(i) => rows.insert(i, row),
+                    }
+                    Self::Rows {
+                        rows,
+                        anchor: Some(pos.row),
+                    }
+                }
+                _ => Self::Rows {
+                    rows: vec![row],
+                    anchor: Some(pos.row),
+                },
+            },
+            SelectionMode::Cells => match self {
+                Self::Cells(r) if shift => Self::Cells(CellRange {
+                    anchor: r.anchor,
+                    end: pos,
+                }),
+                _ => Self::Cells(CellRange::cell(pos)),
+            },
+        }
+    }
+}
@@ -14,6 +14,9 @@ use std::{any::TypeId, sync::Arc};
 
 use zng_wgt::prelude::*;
 
+mod paged;
+pub use paged::*;
+
 /// Arguments for the [`DataView!`] widget.
 ///
 /// [`DataView!`]: struct@DataView
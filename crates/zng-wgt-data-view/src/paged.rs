@@ -0,0 +1,694 @@
+use parking_lot::Mutex;
+use std::{fmt, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use zng_wgt::prelude::*;
+
+use crate::DataView;
+
+/// Future returned by [`DataSource::load_page`].
+pub type DataSourceFuture<T> = Pin<Box<dyn Future<Output = Result<DataPage<T>, Txt>> + Send>>;
+
+/// Asynchronous paged data source.
+///
+/// Data sources are used by [`PagedData`] to load pages of items, the paged data implements the loading,
+/// retry and refresh state machine.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_data_view::*;
+/// struct Numbers;
+/// impl DataSource for Numbers {
+///     type Item = u32;
+///
+///     fn load_page(&self, request: PageRequest) -> DataSourceFuture<u32> {
+///         Box::pin(async move {
+///             let start = (request.index * request.size) as u32;
+///             let items = (start..start + request.size as u32).collect();
+///             Ok(DataPage::new(items, request.index == 9))
+///         })
+///     }
+/// }
+///
+/// let numbers: PagedData<u32> = PagedData::new(Numbers, 20);
+/// ```
+pub trait DataSource: Send + Sync + 'static {
+    /// Item type.
+    type Item: VarValue;
+
+    /// Load the requested page.
+    ///
+    /// Returns the page items or an error message, the request is retried according to the [`RetryBackoff`]
+    /// of the paged data on error.
+    fn load_page(&self, request: PageRequest) -> DataSourceFuture<Self::Item>;
+}
+
+/// Represents a page request in [`DataSource::load_page`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Page index, zero is the first page.
+    pub index: usize,
+    /// Continuation token returned by the previous page, is `None` for the first page.
+    pub cursor: Option<Txt>,
+    /// Expected number of items in the page.
+    pub size: usize,
+}
+
+/// Represents a page loaded by [`DataSource::load_page`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataPage<T> {
+    /// Page items.
+    pub items: Vec<T>,
+    /// Continuation token for the next page request.
+    pub next_cursor: Option<Txt>,
+    /// If this is the last page.
+    pub is_last: bool,
+}
+impl<T> DataPage<T> {
+    /// New page without continuation token.
+    pub fn new(items: Vec<T>, is_last: bool) -> Self {
+        Self {
+            items,
+            next_cursor: None,
+            is_last,
+        }
+    }
+
+    /// Set the continuation token.
+    pub fn with_cursor(mut self, next_cursor: impl Into<Txt>) -> Self {
+        self.next_cursor = Some(next_cursor.into());
+        self
+    }
+}
+
+/// Automatic retry config of [`PagedData`].
+///
+/// The delay before each retry is the `initial` delay multiplied by the `multiplier` for each previous attempt,
+/// up to the `max` delay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryBackoff {
+    /// Maximum number of retries before the error status.
+    ///
+    /// Default is `3`.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    ///
+    /// Default is `500.ms()`.
+    pub initial: Duration,
+    /// Maximum delay between retries.
+    ///
+    /// Default is `30.secs()`.
+    pub max: Duration,
+    /// Delay multiplier for each retry.
+    ///
+    /// Default is `2.0`.
+    pub multiplier: f32,
+}
+impl Default for RetryBackoff {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial: 500.ms(),
+            max: 30.secs(),
+            multiplier: 2.0,
+        }
+    }
+}
+impl RetryBackoff {
+    /// No automatic retry, the first error is the error status.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the delay before the retry after `attempt` failed retries, or `None` if should not retry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zng_wgt::prelude::*;
+    /// # use zng_wgt_data_view::*;
+    /// let retry = RetryBackoff::default();
+    ///
+    /// assert_eq!(retry.delay(0), Some(500.ms()));
+    /// assert_eq!(retry.delay(2), Some(2.secs()));
+    /// assert_eq!(retry.delay(3), None);
+    /// ```
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        let delay = self.initial.as_secs_f32() * self.multiplier.max(1.0).powi(attempt as i32);
+        Some(Duration::from_secs_f32(delay.min(self.max.as_secs_f32())))
+    }
+}
+
+/// Loading status of a [`PagedData`].
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum DataStatus {
+    /// No page is loading and there are more pages to load.
+    #[default]
+    Idle,
+    /// A next page is loading.
+    Loading,
+    /// The first page is reloading, the current items are replaced when it loads.
+    Refreshing,
+    /// A page request failed and will retry after a delay.
+    Retrying {
+        /// Error message of the last attempt.
+        error: Txt,
+        /// Retry attempt, starts at `1`.
+        attempt: u32,
+        /// Delay before the retry.
+        delay: Duration,
+    },
+    /// A page request failed after all retry attempts.
+    ///
+    /// The request can be retried using [`PagedData::retry`] or [`RETRY_CMD`].
+    Error(Txt),
+    /// The last page is loaded.
+    Complete,
+}
+impl DataStatus {
+    /// If is [`Loading`], [`Refreshing`] or [`Retrying`].
+    ///
+    /// [`Loading`]: Self::Loading
+    /// [`Refreshing`]: Self::Refreshing
+    /// [`Retrying`]: Self::Retrying
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading | Self::Refreshing | Self::Retrying { .. })
+    }
+
+    /// If is [`Error`].
+    ///
+    /// [`Error`]: Self::Error
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error(_))
+    }
+
+    /// If is [`Complete`].
+    ///
+    /// [`Complete`]: Self::Complete
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Self::Complete)
+    }
+
+    /// Gets the error message if is [`Error`] or [`Retrying`].
+    ///
+    /// [`Error`]: Self::Error
+    /// [`Retrying`]: Self::Retrying
+    pub fn error(&self) -> Option<&Txt> {
+        match self {
+            Self::Retrying { error, .. } | Self::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Items loaded in pages from a [`DataSource`].
+///
+/// The paged data loads one page at a time, the loaded items are appended to the [`items`] variable and the
+/// loading state is tracked in the [`status`] variable. Failed requests are retried automatically with backoff,
+/// after all retries fail the status is [`DataStatus::Error`] and the request can be retried manually.
+///
+/// Use the [`paged_data`] property to expose the status in a widget context and to implement the
+/// [`LOAD_MORE_CMD`], [`REFRESH_CMD`] and [`RETRY_CMD`] commands.
+///
+/// [`items`]: Self::items
+/// [`status`]: Self::status
+/// [`paged_data`]: fn@paged_data
+pub struct PagedData<T: VarValue>(Arc<PagedDataInner<T>>);
+struct PagedDataInner<T: VarValue> {
+    source: Box<dyn DataSource<Item = T>>,
+    page_size: usize,
+    retry: Mutex<RetryBackoff>,
+    items: ArcVar<Vec<T>>,
+    status: ArcVar<DataStatus>,
+    state: Mutex<PagedState>,
+}
+#[derive(Default)]
+struct PagedState {
+    next: PageRequestState,
+    // incremented on refresh, results of older requests are ignored.
+    generation: u64,
+    busy: bool,
+    complete: bool,
+    failed: Option<(PageRequest, bool)>,
+}
+#[derive(Default)]
+struct PageRequestState {
+    index: usize,
+    cursor: Option<Txt>,
+}
+impl<T: VarValue> Clone for PagedData<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<T: VarValue> fmt::Debug for PagedData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PagedData")
+            .field("page_size", &self.0.page_size)
+            .field("status", &self.0.status.get())
+            .finish_non_exhaustive()
+    }
+}
+impl<T: VarValue> PartialEq for PagedData<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl<T: VarValue> PagedData<T> {
+    /// New paged data that requests pages of `page_size` items from the `source`.
+    ///
+    /// No page is loaded until [`load_more`] is called.
+    ///
+    /// [`load_more`]: Self::load_more
+    pub fn new(source: impl DataSource<Item = T>, page_size: usize) -> Self {
+        Self(Arc::new(PagedDataInner {
+            source: Box::new(source),
+            page_size: page_size.max(1),
+            retry: Mutex::new(RetryBackoff::default()),
+            items: var(vec![]),
+            status: var(DataStatus::Idle),
+            state: Mutex::new(PagedState::default()),
+        }))
+    }
+
+    /// Set the automatic retry config.
+    pub fn with_retry(self, retry: RetryBackoff) -> Self {
+        *self.0.retry.lock() = retry;
+        self
+    }
+
+    /// The loaded items.
+    pub fn items(&self) -> ReadOnlyArcVar<Vec<T>> {
+        self.0.items.read_only()
+    }
+
+    /// The loading status.
+    pub fn status(&self) -> ReadOnlyArcVar<DataStatus> {
+        self.0.status.read_only()
+    }
+
+    /// Request the next page.
+    ///
+    /// Does nothing if a page is already loading, the last page is loaded or the last request failed.
+    pub fn load_more(&self) {
+        let mut state = self.0.state.lock();
+        if state.busy || state.complete || state.failed.is_some() {
+            return;
+        }
+        let request = PageRequest {
+            index: state.next.index,
+            cursor: state.next.cursor.clone(),
+            size: self.0.page_size,
+        };
+        self.start(&mut state, request, false);
+    }
+
+    /// Reload the first page.
+    ///
+    /// The current items are retained until the first page loads, then they are replaced. Any loading
+    /// request is ignored.
+    pub fn refresh(&self) {
+        let mut state = self.0.state.lock();
+        state.generation = state.generation.wrapping_add(1);
+        state.failed = None;
+        let request = PageRequest {
+            index: 0,
+            cursor: None,
+            size: self.0.page_size,
+        };
+        self.start(&mut state, request, true);
+    }
+
+    /// Retry the failed request.
+    ///
+    /// Does nothing if the status is not [`DataStatus::Error`].
+    pub fn retry(&self) {
+        let mut state = self.0.state.lock();
+        if let Some((request, refresh)) = state.failed.take() {
+            self.start(&mut state, request, refresh);
+        }
+    }
+
+    fn start(&self, state: &mut PagedState, request: PageRequest, refresh: bool) {
+        state.busy = true;
+        let generation = state.generation;
+        self.0
+            .status
+            .set(if refresh { DataStatus::Refreshing } else { DataStatus::Loading });
+
+        let inner = self.0.clone();
+        task::spawn(async move {
+            let mut attempt = 0;
+            loop {
+                let r = inner.source.load_page(request.clone()).await;
+
+                let delay = {
+                    let mut state = inner.state.lock();
+                    if state.generation != generation {
+                        // refreshed
+                        return;
+                    }
+                    match r {
+                        Ok(page) => {
+                            state.busy = false;
+                            state.complete = page.is_last;
+                            state.next = PageRequestState {
+                                index: request.index + 1,
+                                cursor: page.next_cursor,
+                            };
+                            let items = page.items;
+                            if refresh {
+                                inner.items.set(items);
+                            } else {
+                                inner.items.modify(move |v| v.to_mut().extend(items));
+                            }
+                            inner.status.set(if page.is_last { DataStatus::Complete } else { DataStatus::Idle });
+                            return;
+                        }
+                        Err(error) => {
+                            let delay = inner.retry.lock().delay(attempt);
+                            if let Some(delay) = delay {
+                                attempt += 1;
+                                inner.status.set(DataStatus::Retrying { error, attempt, delay });
+                                delay
+                            } else {
+                                tracing::debug!("page {} request failed, {error}", request.index);
+                                state.busy = false;
+                                state.failed = Some((request, refresh));
+                                inner.status.set(DataStatus::Error(error));
+                                return;
+                            }
+                        }
+                    }
+                };
+
+                task::deadline(delay).await;
+                if inner.state.lock().generation != generation {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+command! {
+    /// Represents the **load more** action of a [`PagedData`].
+    ///
+    /// The [`paged_data`] property implements this command scoped on the widget, it can be notified by
+    /// a footer widget when it scrolls into view to implement infinite scrolling.
+    ///
+    /// [`paged_data`]: fn@paged_data
+    pub static LOAD_MORE_CMD = {
+        name: "Load More",
+        info: "Load the next page of items.",
+    };
+
+    /// Represents the **refresh** action of a [`PagedData`].
+    ///
+    /// The [`paged_data`] property implements this command scoped on the widget, it can be notified by
+    /// a *pull-to-refresh* gesture or a refresh button.
+    ///
+    /// [`paged_data`]: fn@paged_data
+    pub static REFRESH_CMD = {
+        name: "Refresh",
+        info: "Reload the items.",
+        shortcut: shortcut!(F5),
+        shortcut_filter: ShortcutFilter::FOCUSED | ShortcutFilter::CMD_ENABLED,
+    };
+
+    /// Represents the **retry** action of a [`PagedData`] after an error.
+    ///
+    /// The [`paged_data`] property implements this command scoped on the widget, it is only enabled when
+    /// the status is [`DataStatus::Error`].
+    ///
+    /// [`paged_data`]: fn@paged_data
+    pub static RETRY_CMD = {
+        name: "Retry",
+        info: "Retry the failed request.",
+    };
+}
+
+context_var! {
+    /// Loading status of the [`paged_data`] in the context.
+    ///
+    /// Is [`DataStatus::Idle`] by default.
+    ///
+    /// [`paged_data`]: fn@paged_data
+    pub static DATA_STATUS_VAR: DataStatus = DataStatus::Idle;
+}
+
+/// Paged data of the widget.
+///
+/// This property sets the [`DATA_STATUS_VAR`] to the `data` status, so that loading indicators and error messages
+/// in the widget can show the status. It also implements the [`LOAD_MORE_CMD`], [`REFRESH_CMD`] and [`RETRY_CMD`]
+/// scoped on the widget and loads the first page on init if no page was loaded.
+///
+/// Note that this property does not present the items, use the [`PagedData::items`] to generate the view.
+///
+/// # Examples
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_data_view::*;
+/// # fn main() { }
+/// # fn demo(data: PagedData<Txt>) -> impl UiNode {
+/// DataView! {
+///     paged_data = data.clone();
+///     view::<Vec<Txt>> = {
+///         data: data.items(),
+///         update: hn!(|a: &DataViewArgs<Vec<Txt>>| {
+///             // generate items view.
+///         }),
+///     };
+/// }
+/// # }
+/// ```
+///
+/// A descendant widget can notify `REFRESH_CMD.scoped(data_view_id)` to refresh the data, for example, from a refresh button or
+/// a *pull-to-refresh* gesture.
+#[property(CONTEXT, widget_impl(DataView))]
+pub fn paged_data<T: VarValue>(child: impl UiNode, data: impl IntoValue<PagedData<T>>) -> impl UiNode {
+    let data = data.into();
+    let status = data.status();
+    let child = with_context_var(child, DATA_STATUS_VAR, status.clone());
+
+    let mut load_more = CommandHandle::dummy();
+    let mut refresh = CommandHandle::dummy();
+    let mut retry = CommandHandle::dummy();
+
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&status);
+
+            let scope = WIDGET.id();
+            let s = status.get();
+            load_more = LOAD_MORE_CMD.scoped(scope).subscribe(matches!(s, DataStatus::Idle));
+            refresh = REFRESH_CMD.scoped(scope).subscribe(true);
+            retry = RETRY_CMD.scoped(scope).subscribe(s.is_error());
+
+            if s == DataStatus::Idle && data.items().with(Vec::is_empty) {
+                data.load_more();
+            }
+        }
+        UiNodeOp::Deinit => {
+            load_more = CommandHandle::dummy();
+            refresh = CommandHandle::dummy();
+            retry = CommandHandle::dummy();
+        }
+        UiNodeOp::Update { .. } => {
+            if let Some(s) = status.get_new() {
+                load_more.set_enabled(matches!(s, DataStatus::Idle));
+                retry.set_enabled(s.is_error());
+            }
+        }
+        UiNodeOp::Event { update } => {
+            let scope = WIDGET.id();
+            if let Some(args) = LOAD_MORE_CMD.scoped(scope).on(update) {
+                args.handle_enabled(&load_more, |_| data.load_more());
+            } else if let Some(args) = REFRESH_CMD.scoped(scope).on(update) {
+                args.handle_enabled(&refresh, |_| data.refresh());
+            } else if let Some(args) = RETRY_CMD.scoped(scope).on(update) {
+                args.handle_enabled(&retry, |_| data.retry());
+            }
+        }
+        _ => {}
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use zng_app::{HeadlessApp, APP};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestSource {
+        fail: Arc<AtomicU32>,
+        requests: Arc<Mutex<Vec<PageRequest>>>,
+    }
+    impl DataSource for TestSource {
+        type Item = u32;
+
+        fn load_page(&self, request: PageRequest) -> DataSourceFuture<u32> {
+            self.requests.lock().push(request.clone());
+            let fail = self
+                .fail
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |f| f.checked_sub(1))
+                .is_ok();
+            Box::pin(async move {
+                if fail {
+                    return Err(Txt::from("test error"));
+                }
+                let start = (request.index * request.size) as u32;
+                let items = (start..start + request.size as u32).collect();
+                Ok(DataPage::new(items, request.index == 2).with_cursor(format!("c{}", request.index + 1)))
+            })
+        }
+    }
+
+    fn fast_retry(max_retries: u32) -> RetryBackoff {
+        RetryBackoff {
+            max_retries,
+            initial: 1.ms(),
+            max: 1.ms(),
+            multiplier: 1.0,
+        }
+    }
+
+    fn wait(app: &mut HeadlessApp, data: &PagedData<u32>) -> DataStatus {
+        let _ = app.update(false);
+        let status = data.status();
+        app.run_task(async move { status.wait_value(|s| !s.is_loading()).await }).unwrap();
+        data.status().get()
+    }
+
+    #[test]
+    fn paging() {
+        let mut app = APP.minimal().run_headless(false);
+        let source = TestSource::default();
+        let data = PagedData::new(source.clone(), 2);
+
+        data.load_more();
+        assert_eq!(wait(&mut app, &data), DataStatus::Idle);
+        assert_eq!(data.items().get(), [0, 1]);
+
+        data.load_more();
+        assert_eq!(wait(&mut app, &data), DataStatus::Idle);
+        data.load_more();
+        assert_eq!(wait(&mut app, &data), DataStatus::Complete);
+        assert_eq!(data.items().get(), [0, 1, 2, 3, 4, 5]);
+
+        // complete, does not request
+        data.load_more();
+        let _ = app.update(false);
+
+        let requests = source.requests.lock().clone();
+        assert_eq!(
+            requests,
+            [
+                PageRequest {
+                    index: 0,
+                    cursor: None,
+                    size: 2
+                },
+                PageRequest {
+                    index: 1,
+                    cursor: Some(Txt::from("c1")),
+                    size: 2
+                },
+                PageRequest {
+                    index: 2,
+                    cursor: Some(Txt::from("c2")),
+                    size: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn refresh() {
+        let mut app = APP.minimal().run_headless(false);
+        let data = PagedData::new(TestSource::default(), 2);
+
+        data.load_more();
+        wait(&mut app, &data);
+        data.load_more();
+        wait(&mut app, &data);
+        assert_eq!(data.items().get(), [0, 1, 2, 3]);
+
+        data.refresh();
+        assert_eq!(wait(&mut app, &data), DataStatus::Idle);
+        assert_eq!(data.items().get(), [0, 1]);
+    }
+
+    #[test]
+    fn retry_backoff_recover() {
+        let mut app = APP.minimal().run_headless(false);
+        let source = TestSource::default();
+        source.fail.store(2, Ordering::Relaxed);
+        let data = PagedData::new(source.clone(), 2).with_retry(fast_retry(3));
+
+        data.load_more();
+        assert_eq!(wait(&mut app, &data), DataStatus::Idle);
+        assert_eq!(data.items().get(), [0, 1]);
+        assert_eq!(source.requests.lock().len(), 3);
+    }
+
+    #[test]
+    fn retry_backoff_error() {
+        let mut app = APP.minimal().run_headless(false);
+        let source = TestSource::default();
+        source.fail.store(2, Ordering::Relaxed);
+        let data = PagedData::new(source.clone(), 2).with_retry(fast_retry(1));
+
+        data.load_more();
+        assert_eq!(wait(&mut app, &data), DataStatus::Error(Txt::from("test error")));
+        assert_eq!(source.requests.lock().len(), 2);
+
+        // failed, does not request
+        data.load_more();
+        assert_eq!(source.requests.lock().len(), 2);
+
+        data.retry();
+        assert_eq!(wait(&mut app, &data), DataStatus::Idle);
+        assert_eq!(data.items().get(), [0, 1]);
+        assert_eq!(source.requests.lock()[2].index, 0);
+    }
+
+    #[test]
+    fn backoff_delay() {
+        let retry = RetryBackoff::default();
+        assert_eq!(retry.delay(0), Some(500.ms()));
+        assert_eq!(retry.delay(1), Some(1.secs()));
+        assert_eq!(retry.delay(2), Some(2.secs()));
+        assert_eq!(retry.delay(3), None);
+
+        let retry = RetryBackoff {
+            max_retries: 10,
+            initial: 10.secs(),
+            max: 30.secs(),
+            multiplier: 10.0,
+        };
+        assert_eq!(retry.delay(0), Some(10.secs()));
+        assert_eq!(retry.delay(1), Some(30.secs()));
+        assert_eq!(retry.delay(9), Some(30.secs()));
+
+        let retry = RetryBackoff {
+            multiplier: 0.5,
+            ..RetryBackoff::default()
+        };
+        assert_eq!(retry.delay(2), Some(500.ms()));
+
+        assert_eq!(RetryBackoff::none().delay(0), None);
+    }
+}
@@ -0,0 +1,391 @@
+//! In-app file dialog, used when native dialogs are not available.
+
+use std::path::{Path, PathBuf};
+
+use zng_ext_font::FontWeight;
+use zng_ext_input::gesture::ClickArgs;
+use zng_ext_l10n::l10n;
+use zng_view_api::dialog::{FileDialog, FileDialogKind, FileDialogResponse};
+use zng_wgt::{corner_radius, prelude::*};
+use zng_wgt_button::Button;
+use zng_wgt_container::Container;
+use zng_wgt_fill::background_color;
+use zng_wgt_input::{cursor, gesture::click_shortcut, gesture::on_click, CursorIcon};
+use zng_wgt_scroll::{Scroll, ScrollMode};
+use zng_wgt_size_offset::{height, width};
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_text::Text;
+use zng_wgt_text_input::TextInput;
+
+pub(crate) fn show(window_id: WindowId, dialog: FileDialog, confirm_overwrite: bool) -> ResponseVar<FileDialogResponse> {
+    let (responder, response) = response_var();
+
+    let starting_dir = if dialog.starting_dir.as_os_str().is_empty() {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        dialog.starting_dir.clone()
+    };
+    let s = DialogState {
+        window_id,
+        id: WidgetId::new_unique(),
+        kind: dialog.kind,
+        confirm_overwrite,
+        filters: dialog
+            .iter_filters()
+            .map(|(n, p)| (Txt::from_str(n), p.map(Txt::from_str).collect()))
+            .collect(),
+        filter: var(0),
+        dir: var(starting_dir),
+        entries: var(vec![]),
+        selection: var(vec![]),
+        name: var(dialog.starting_name.clone()),
+        confirm: var(None),
+        responder,
+    };
+
+    let title = if dialog.title.is_empty() {
+        match dialog.kind {
+            FileDialogKind::OpenFile | FileDialogKind::OpenFiles => l10n!("FileDialog.open-title", "Open File").boxed(),
+            FileDialogKind::SelectFolder | FileDialogKind::SelectFolders => l10n!("FileDialog.folder-title", "Select Folder").boxed(),
+            FileDialogKind::SaveFile => l10n!("FileDialog.save-title", "Save File").boxed(),
+        }
+    } else {
+        LocalVar(dialog.title).boxed()
+    };
+
+    if !crate::insert_modal(window_id, s.id, dialog_view(s.clone(), title)) {
+        s.responder.respond(FileDialogResponse::Error(Txt::from_static(
+            "cannot show in-app dialog, window has no dialog host",
+        )));
+    }
+
+    response
+}
+
+#[derive(Clone)]
+struct DialogState {
+    window_id: WindowId,
+    id: WidgetId,
+    kind: FileDialogKind,
+    confirm_overwrite: bool,
+    filters: Vec<(Txt, Vec<Txt>)>,
+    filter: ArcVar<usize>,
+    dir: ArcVar<PathBuf>,
+    entries: ArcVar<Vec<DirEntry>>,
+    selection: ArcVar<Vec<PathBuf>>,
+    name: ArcVar<Txt>,
+    confirm: ArcVar<Option<PathBuf>>,
+    responder: ResponderVar<FileDialogResponse>,
+}
+impl DialogState {
+    fn is_folder(&self) -> bool {
+        matches!(self.kind, FileDialogKind::SelectFolder | FileDialogKind::SelectFolders)
+    }
+
+    fn load(&self) {
+        let dir = self.dir.get();
+        let extensions = self.filters.get(self.filter.get()).map(|(_, e)| e.clone());
+        let dirs_only = self.is_folder();
+        let entries = self.entries.clone();
+        task::spawn(async move {
+            let list = task::wait(move || read_dir(&dir, extensions.as_deref(), dirs_only)).await;
+            entries.set(list);
+        });
+    }
+
+    fn respond(&self, response: FileDialogResponse) {
+        crate::remove_modal(self.window_id, self.id);
+        self.responder.respond(response);
+    }
+
+    fn accept(&self) {
+        match self.kind {
+            FileDialogKind::OpenFile | FileDialogKind::OpenFiles => {
+                let selection = self.selection.get();
+                if !selection.is_empty() {
+                    self.respond(FileDialogResponse::Selected(selection));
+                }
+            }
+            FileDialogKind::SelectFolder | FileDialogKind::SelectFolders => {
+                self.respond(FileDialogResponse::Selected(vec![self.dir.get()]));
+            }
+            FileDialogKind::SaveFile => {
+                let name = self.name.get();
+                if name.is_empty() {
+                    return;
+                }
+                let path = self.dir.with(|d| d.join(name.as_str()));
+                if self.confirm_overwrite && path.exists() {
+                    self.confirm.set(Some(path));
+                } else {
+                    self.respond(FileDialogResponse::Selected(vec![path]));
+                }
+            }
+        }
+    }
+
+    fn entry_click(&self, entry: &DirEntry, is_double: bool) {
+        if entry.is_dir {
+            self.dir.set(entry.path.clone());
+            self.selection.set(vec![]);
+            return;
+        }
+        match self.kind {
+            FileDialogKind::OpenFiles if !is_double => {
+                let path = entry.path.clone();
+                self.selection.modify(move |s| {
+                    if let Some(i) = s.iter().position(|p| *p == path) {
+                        s.to_mut().remove(i);
+                    } else {
+                        s.to_mut().push(path);
+                    }
+                });
+            }
+            FileDialogKind::SaveFile => {
+                self.name.set(entry.name.clone());
+                self.selection.set(vec![entry.path.clone()]);
+            }
+            _ => self.selection.set(vec![entry.path.clone()]),
+        }
+        if is_double {
+            // let the selection update apply first.
+            let s = self.clone();
+            UPDATES.run_hn_once(app_hn_once!(|_| s.accept())).perm();
+        }
+    }
+
+    fn go_up(&self) {
+        if let Some(parent) = self.dir.with(|d| d.parent().map(Path::to_path_buf)) {
+            self.dir.set(parent);
+            self.selection.set(vec![]);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DirEntry {
+    path: PathBuf,
+    name: Txt,
+    is_dir: bool,
+}
+
+fn read_dir(dir: &Path, extensions: Option<&[Txt]>, dirs_only: bool) -> Vec<DirEntry> {
+    let mut r = vec![];
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for e in entries.flatten() {
+            let path = e.path();
+            let is_dir = path.is_dir();
+            if !is_dir {
+                if dirs_only {
+                    continue;
+                }
+                if let Some(exts) = extensions {
+                    let matches = exts
+                        .iter()
+                        .any(|x| x == "*" || path.extension().map(|e| e.eq_ignore_ascii_case(x.as_str())).unwrap_or(false));
+                    if !matches {
+                        continue;
+                    }
+                }
+            }
+            r.push(DirEntry {
+                name: Txt::from_str(&e.file_name().to_string_lossy()),
+                path,
+                is_dir,
+            });
+        }
+    }
+    r.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    r
+}
+
+fn dialog_view(s: DialogState, title: BoxedVar<Txt>) -> impl UiNode {
+    let name_input = if let FileDialogKind::SaveFile = s.kind {
+        TextInput! {
+            txt = s.name.clone();
+            placeholder_txt = l10n!("FileDialog.name-placeholder", "File name");
+        }
+        .boxed()
+    } else {
+        NilUiNode.boxed()
+    };
+    let view = Container! {
+        width = 500;
+        padding = 10;
+        corner_radius = 4;
+        background_color = light_dark(rgb(245, 245, 245), rgb(30, 30, 30));
+
+        child_top = Stack! {
+            direction = StackDirection::top_to_bottom();
+            spacing = 6;
+            children = ui_vec![
+                Text! {
+                    txt = title;
+                    font_weight = FontWeight::BOLD;
+                },
+                Container! {
+                    child_start = Button! {
+                        child = Text!("⮤");
+                        on_click = hn!(s, |_| s.go_up());
+                    }, 4;
+                    child = Text!(s.dir.map(|d| d.display().to_txt()));
+                },
+                filters_view(&s),
+            ];
+        }, 6;
+
+        child = Scroll! {
+            mode = ScrollMode::VERTICAL;
+            height = 300;
+            child_align = Align::FILL_TOP;
+            child = presenter(
+                s.entries.clone(),
+                wgt_fn!(s, |entries: Vec<DirEntry>| {
+                    Stack! {
+                        direction = StackDirection::top_to_bottom();
+                        children = entries.into_iter().map(|e| entry_view(&s, e).boxed()).collect::<UiNodeVec>();
+                    }
+                }),
+            );
+        };
+
+        child_bottom = Container! {
+            child = name_input;
+            child_end = Stack! {
+                direction = StackDirection::left_to_right();
+                spacing = 4;
+                children = ui_vec![
+                    Button! {
+                        child = Text!(l10n!("FileDialog.cancel", "Cancel"));
+                        click_shortcut = shortcut!(Escape);
+                        on_click = hn!(s, |args: &ClickArgs| {
+                            args.propagation().stop();
+                            s.respond(FileDialogResponse::Cancel);
+                        });
+                    },
+                    Button! {
+                        child = Text!(match s.kind {
+                            FileDialogKind::OpenFile | FileDialogKind::OpenFiles => l10n!("FileDialog.open", "Open").boxed(),
+                            FileDialogKind::SelectFolder | FileDialogKind::SelectFolders => l10n!("FileDialog.select", "Select").boxed(),
+                            FileDialogKind::SaveFile => l10n!("FileDialog.save", "Save").boxed(),
+                        });
+                        click_shortcut = shortcut!(Enter);
+                        on_click = hn!(s, |args: &ClickArgs| {
+                            args.propagation().stop();
+                            s.accept();
+                        });
+                    },
+                ];
+            }, 6;
+        }, 6;
+
+        child_over = presenter(s.confirm.clone(), wgt_fn!(s, |path: Option<PathBuf>| {
+            match path {
+                Some(path) => confirm_view(&s, path).boxed(),
+                None => NilUiNode.boxed(),
+            }
+        }));
+    };
+
+    match_node(view, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&s.dir).sub_var(&s.filter);
+            s.load();
+        }
+        UiNodeOp::Update { .. } => {
+            if s.dir.is_new() || s.filter.is_new() {
+                s.load();
+            }
+        }
+        _ => {}
+    })
+}
+
+fn filters_view(s: &DialogState) -> impl UiNode {
+    if s.filters.len() < 2 || s.is_folder() {
+        return NilUiNode.boxed();
+    }
+    let s = s.clone();
+    Stack! {
+        direction = StackDirection::left_to_right();
+        spacing = 4;
+        children = s
+            .filters
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                Button! {
+                    child = Text! {
+                        txt = name.clone();
+                        font_weight = s.filter.map(move |&f| if f == i { FontWeight::BOLD } else { FontWeight::NORMAL });
+                    };
+                    style_fn = zng_wgt_button::LightStyle!();
+                    on_click = hn!(s, |_| s.filter.set(i));
+                }
+                .boxed()
+            })
+            .collect::<UiNodeVec>();
+    }
+    .boxed()
+}
+
+fn entry_view(s: &DialogState, entry: DirEntry) -> impl UiNode {
+    let s = s.clone();
+    let is_selected = s.selection.map(clmv!(entry, |sel| sel.contains(&entry.path)));
+    let label = if entry.is_dir {
+        formatx!("{}/", entry.name)
+    } else {
+        entry.name.clone()
+    };
+    Container! {
+        padding = (2, 4);
+        cursor = CursorIcon::Pointer;
+        background_color = merge_var!(is_selected, colors::ACCENT_COLOR_VAR.rgba(), |&s, &c| {
+            if s {
+                c.with_alpha(40.pct())
+            } else {
+                c.transparent()
+            }
+        });
+        child = Text!(label);
+        on_click = hn!(s, |args: &ClickArgs| {
+            s.entry_click(&entry, args.is_double());
+        });
+    }
+}
+
+fn confirm_view(s: &DialogState, path: PathBuf) -> impl UiNode {
+    let s = s.clone();
+    let name = path.file_name().map(|n| Txt::from_str(&n.to_string_lossy())).unwrap_or_default();
+    Container! {
+        padding = 10;
+        background_color = light_dark(rgb(245, 245, 245), rgb(30, 30, 30));
+        child_align = Align::CENTER;
+        child = Text!(l10n!("FileDialog.confirm-overwrite", "\"{$name}\" already exists, replace it?", name = name));
+        child_bottom = Stack! {
+            direction = StackDirection::left_to_right();
+            spacing = 4;
+            zng_wgt::align = Align::END;
+            children = ui_vec![
+                Button! {
+                    child = Text!(l10n!("FileDialog.no", "No"));
+                    on_click = hn!(s, |args: &ClickArgs| {
+                        args.propagation().stop();
+                        s.confirm.set(None);
+                    });
+                },
+                Button! {
+                    child = Text!(l10n!("FileDialog.replace", "Replace"));
+                    on_click = hn!(s, path, |args: &ClickArgs| {
+                        args.propagation().stop();
+                        s.respond(FileDialogResponse::Selected(vec![path.clone()]));
+                    });
+                },
+            ];
+        }, 6;
+    }
+}
@@ -0,0 +1,543 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Dialog service and widgets.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use std::{any::Any, future::IntoFuture, path::PathBuf, pin::Pin, sync::Mutex};
+
+use zng_ext_input::focus::{DirectionalNav, TabNav, FOCUS};
+use zng_ext_window::WINDOWS;
+use zng_view_api::dialog::{FileDialog, FileDialogKind};
+use zng_wgt::{base_color, border, corner_radius, modal, prelude::*};
+use zng_wgt_access::{access_role, AccessRole};
+use zng_wgt_container::{padding, Container};
+use zng_wgt_fill::background_color;
+use zng_wgt_input::focus::{directional_nav, focus_on_init, focus_scope, tab_nav};
+use zng_wgt_layer::{LayerIndex, LAYERS};
+use zng_wgt_size_offset::min_width;
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_style::{impl_style_fn, style_fn, Style, StyleMix};
+
+mod fallback;
+
+pub use zng_view_api::dialog::FileDialogResponse;
+
+/// Dialogs service.
+///
+/// Shows file dialogs using the native dialogs provided by the view-process, if the native dialog fails to open
+/// an in-app dialog is shown in the window instead. Windows must have the [`dialog_host`] node to show in-app dialogs,
+/// the default `APP.defaults()` sets this node in all windows.
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_dialog::*;
+/// # async fn demo() {
+/// match DIALOG.open_file().filter("Images", ["png", "jpg"]).multiple().await {
+///     FileDialogResponse::Selected(paths) => tracing::info!("selected {paths:?}"),
+///     FileDialogResponse::Cancel => {}
+///     FileDialogResponse::Error(e) => tracing::error!("cannot open file dialog, {e}"),
+/// }
+/// # }
+/// ```
+///
+/// The service also shows custom modal dialogs, see [`show`] for more details.
+///
+/// [`show`]: Self::show
+pub struct DIALOG;
+impl DIALOG {
+    /// Show a custom modal in-app `dialog`.
+    ///
+    /// The `dialog` is usually a [`Dialog!`] widget, it is shown in the context window or in the focused window,
+    /// over a backdrop that blocks interaction with the rest of the window and traps the keyboard focus. The dialog
+    /// closes when [`respond`] is called from inside it, the keyboard focus returns to the widget that was focused
+    /// before the dialog opened.
+    ///
+    /// If the window already shows a dialog the new dialog is queued or stacked over it, depending on the [`queue_policy`].
+    ///
+    /// Returns a response var that updates once with the response value. Note that if the window closes before
+    /// the dialog responds the response never updates.
+    ///
+    /// ```
+    /// # use zng_wgt::prelude::*;
+    /// # use zng_wgt_dialog::*;
+    /// # async fn demo() {
+    /// let delete: bool = DIALOG
+    ///     .show(Dialog! {
+    ///         child = zng_wgt_text::Text!("Delete the file?");
+    ///         on_dialog_accept = hn!(|_| DIALOG.respond(true));
+    ///         on_dialog_cancel = hn!(|_| DIALOG.respond(false));
+    ///     })
+    ///     .wait_into_rsp()
+    ///     .await;
+    /// # }
+    /// ```
+    ///
+    /// [`Dialog!`]: struct@Dialog
+    /// [`respond`]: Self::respond
+    /// [`queue_policy`]: Self::queue_policy
+    pub fn show<T: VarValue>(&self, dialog: impl UiNode) -> ResponseVar<T> {
+        let (responder, response) = response_var::<T>();
+        let window_id = match WINDOW.try_id().or_else(parent_window) {
+            Some(id) => id,
+            None => {
+                tracing::error!("cannot show dialog, no open window");
+                return response;
+            }
+        };
+
+        let mut s = DIALOG_SV.write();
+        s.queue.push(QueuedDialog {
+            entry: DialogEntry {
+                window_id,
+                id: WidgetId::new_unique(),
+                responder: Box::new(responder),
+                return_focus: None,
+            },
+            dialog: Mutex::new(dialog.boxed()),
+        });
+        s.show_queued();
+
+        response
+    }
+
+    /// Respond to the custom dialog that contains the current widget and close it.
+    ///
+    /// This method must be called from inside a dialog shown by [`show`], usually in an event handler of a button
+    /// or of the [`on_dialog_accept`] and [`on_dialog_cancel`] properties. The `response` type must be the same
+    /// type requested by [`show`].
+    ///
+    /// [`show`]: Self::show
+    /// [`on_dialog_accept`]: fn@on_dialog_accept
+    /// [`on_dialog_cancel`]: fn@on_dialog_cancel
+    pub fn respond<T: VarValue>(&self, response: T) {
+        let path = match WIDGET.try_id() {
+            Some(_) => WIDGET.info().path(),
+            None => {
+                tracing::error!("DIALOG.respond called outside of a widget context");
+                return;
+            }
+        };
+
+        let mut s = DIALOG_SV.write();
+        let entry = match s.open.iter().rposition(|e| e.window_id == path.window_id() && path.contains(e.id)) {
+            Some(i) => s.open.remove(i),
+            None => {
+                tracing::error!("DIALOG.respond called outside of a dialog");
+                return;
+            }
+        };
+
+        match entry.responder.downcast::<ResponderVar<T>>() {
+            Ok(r) => r.respond(response),
+            Err(_) => tracing::error!("dialog response is not of the requested type `{}`", std::any::type_name::<T>()),
+        }
+        if let Some(h) = s.host(entry.window_id) {
+            h.remove(entry.id);
+        }
+        if let Some(id) = entry.return_focus {
+            FOCUS.focus_widget_or_related(id, false, false);
+        }
+
+        s.show_queued();
+    }
+
+    /// Defines how custom dialogs are shown when the window already shows a dialog.
+    ///
+    /// Is [`DialogQueue::Queue`] by default.
+    pub fn queue_policy(&self) -> ArcVar<DialogQueue> {
+        DIALOG_SV.read().queue_policy.clone()
+    }
+
+    /// Builder for a dialog that picks one file for reading.
+    pub fn open_file(&self) -> FileDialogBuilder {
+        FileDialogBuilder::new(FileDialogKind::OpenFile)
+    }
+
+    /// Builder for a dialog that picks one directory.
+    pub fn open_folder(&self) -> FileDialogBuilder {
+        FileDialogBuilder::new(FileDialogKind::SelectFolder)
+    }
+
+    /// Builder for a dialog that picks one file for writing.
+    ///
+    /// The dialog asks for confirmation if the file already exists.
+    pub fn save_file(&self) -> FileDialogBuilder {
+        FileDialogBuilder::new(FileDialogKind::SaveFile)
+    }
+}
+
+/// File dialog builder.
+///
+/// Use the [`DIALOG`] service to create builders. The builder can be awaited directly or the dialog can be
+/// shown using [`show`] that returns a response var.
+///
+/// [`show`]: Self::show
+#[derive(Debug, Clone)]
+pub struct FileDialogBuilder {
+    dialog: FileDialog,
+    window_id: Option<WindowId>,
+    native: bool,
+    confirm_overwrite: bool,
+}
+impl FileDialogBuilder {
+    fn new(kind: FileDialogKind) -> Self {
+        Self {
+            dialog: FileDialog {
+                kind,
+                ..Default::default()
+            },
+            window_id: None,
+            native: true,
+            confirm_overwrite: true,
+        }
+    }
+
+    /// Set the dialog title.
+    pub fn title(mut self, title: impl Into<Txt>) -> Self {
+        self.dialog.title = title.into();
+        self
+    }
+
+    /// Set the directory selected when the dialog opens.
+    pub fn starting_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dialog.starting_dir = dir.into();
+        self
+    }
+
+    /// Set the starting file name.
+    pub fn default_name(mut self, name: impl Into<Txt>) -> Self {
+        self.dialog.starting_name = name.into();
+        self
+    }
+
+    /// Push a file extension filter.
+    ///
+    /// The extensions are not glob patterns, they must be an extension without the dot prefix or `*` for all files.
+    pub fn filter<S: AsRef<str>>(mut self, display_name: &str, extensions: impl IntoIterator<Item = S>) -> Self {
+        let extensions: Vec<S> = extensions.into_iter().collect();
+        self.dialog.push_filter(display_name, &extensions);
+        self
+    }
+
+    /// Allow selecting multiple files or directories.
+    ///
+    /// Does nothing for save dialogs.
+    pub fn multiple(mut self) -> Self {
+        self.dialog.kind = match self.dialog.kind {
+            FileDialogKind::OpenFile => FileDialogKind::OpenFiles,
+            FileDialogKind::SelectFolder => FileDialogKind::SelectFolders,
+            k => k,
+        };
+        self
+    }
+
+    /// Set the parent window.
+    ///
+    /// If not set the focused window is used, or any open window if no window is focused.
+    pub fn window(mut self, window_id: impl Into<WindowId>) -> Self {
+        self.window_id = Some(window_id.into());
+        self
+    }
+
+    /// Enable or disable the native dialog.
+    ///
+    /// Is enabled by default, if disabled the in-app dialog is always used.
+    pub fn native(mut self, enabled: bool) -> Self {
+        self.native = enabled;
+        self
+    }
+
+    /// Enable or disable the overwrite confirmation of save dialogs.
+    ///
+    /// Is enabled by default. Note that native dialogs always confirm overwrite, this only affects the in-app dialog.
+    pub fn confirm_overwrite(mut self, enabled: bool) -> Self {
+        self.confirm_overwrite = enabled;
+        self
+    }
+
+    /// Show the dialog.
+    ///
+    /// Returns a response var that updates once when the user responds.
+    pub fn show(self) -> ResponseVar<FileDialogResponse> {
+        let window_id = match self.window_id.or_else(parent_window) {
+            Some(id) => id,
+            None => return response_done_var(FileDialogResponse::Error(Txt::from_static("no open window"))),
+        };
+
+        if !self.native {
+            return fallback::show(window_id, self.dialog, self.confirm_overwrite);
+        }
+
+        let (responder, response) = response_var();
+        let native = WINDOWS.native_file_dialog(window_id, self.dialog.clone());
+        UPDATES
+            .run(async move {
+                match native.wait_into_rsp().await {
+                    FileDialogResponse::Error(e) => {
+                        tracing::debug!("native file dialog error, {e}, will use in-app dialog");
+                        let r = fallback::show(window_id, self.dialog, self.confirm_overwrite).wait_into_rsp().await;
+                        responder.respond(r);
+                    }
+                    r => responder.respond(r),
+                }
+            })
+            .perm();
+        response
+    }
+}
+impl IntoFuture for FileDialogBuilder {
+    type Output = FileDialogResponse;
+    type IntoFuture = Pin<Box<dyn std::future::Future<Output = FileDialogResponse> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let r = self.show();
+        Box::pin(async move { r.wait_into_rsp().await })
+    }
+}
+
+fn parent_window() -> Option<WindowId> {
+    WINDOWS.focused_window_id().or_else(|| {
+        let s = DIALOG_SV.read();
+        s.hosts.iter().find(|(_, h)| h.alive()).map(|(id, _)| *id)
+    })
+}
+
+/// Defines how custom dialogs are shown when the window already shows a dialog.
+///
+/// See [`DIALOG.queue_policy`] for more details.
+///
+/// [`DIALOG.queue_policy`]: DIALOG::queue_policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DialogQueue {
+    /// Dialog shows after all previous dialogs of the window respond.
+    #[default]
+    Queue,
+    /// Dialog shows immediately, over the previous dialogs.
+    Stack,
+}
+
+app_local! {
+    static DIALOG_SV: DialogService = DialogService {
+        hosts: vec![],
+        queue_policy: var(DialogQueue::Queue),
+        queue: vec![],
+        open: vec![],
+    };
+}
+
+struct DialogService {
+    hosts: Vec<(WindowId, EditableUiNodeListRef)>,
+    queue_policy: ArcVar<DialogQueue>,
+    queue: Vec<QueuedDialog>,
+    open: Vec<DialogEntry>,
+}
+impl DialogService {
+    fn host(&self, window_id: WindowId) -> Option<EditableUiNodeListRef> {
+        self.hosts
+            .iter()
+            .find(|(id, h)| *id == window_id && h.alive())
+            .map(|(_, h)| h.clone())
+    }
+
+    fn show_queued(&mut self) {
+        let policy = self.queue_policy.get();
+        let mut i = 0;
+        while i < self.queue.len() {
+            let window_id = self.queue[i].entry.window_id;
+            let host = match self.host(window_id) {
+                Some(h) if policy == DialogQueue::Stack || !self.open.iter().any(|e| e.window_id == window_id) => h,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            let QueuedDialog { mut entry, dialog } = self.queue.remove(i);
+            entry.return_focus = FOCUS
+                .focused()
+                .with(|p| p.as_ref().filter(|p| p.window_id() == window_id).map(|p| p.widget_id()));
+            host.push(modal_backdrop(entry.id, dialog.into_inner().unwrap()));
+            self.open.push(entry);
+        }
+    }
+}
+
+struct DialogEntry {
+    window_id: WindowId,
+    id: WidgetId,
+    /// `ResponderVar<T>`
+    responder: Box<dyn Any + Send + Sync>,
+    return_focus: Option<WidgetId>,
+}
+
+struct QueuedDialog {
+    entry: DialogEntry,
+    dialog: Mutex<BoxedUiNode>,
+}
+
+/// Dialogs host.
+///
+/// Inserts a layer in the window that shows the in-app dialogs. This node must be set in the window root,
+/// the default `APP.defaults()` sets it for all windows using a root extender.
+pub fn dialog_host(child: impl UiNode) -> impl UiNode {
+    let stack_id = WidgetId::new_unique();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            let dialogs = EditableUiNodeList::new();
+            let host = dialogs.reference();
+            LAYERS.insert(
+                LayerIndex::TOP_MOST,
+                Stack! {
+                    id = stack_id;
+                    direction = StackDirection::none();
+                    children = dialogs;
+                },
+            );
+            let mut s = DIALOG_SV.write();
+            s.hosts.push((WINDOW.id(), host));
+            s.show_queued();
+        }
+        UiNodeOp::Deinit => {
+            let id = WINDOW.id();
+            let mut s = DIALOG_SV.write();
+            s.hosts.retain(|(w, _)| *w != id);
+            s.open.retain(|e| e.window_id != id);
+            s.queue.retain(|e| e.entry.window_id != id);
+            LAYERS.remove(stack_id);
+        }
+        _ => {}
+    })
+}
+
+/// Insert the modal in-app `dialog` in the window host.
+///
+/// Returns `false` if the window has no dialog host.
+fn insert_modal(window_id: WindowId, id: WidgetId, dialog: impl UiNode) -> bool {
+    match DIALOG_SV.read().host(window_id) {
+        Some(h) => {
+            h.push(modal_backdrop(id, dialog));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Modal backdrop that traps focus in the `dialog`.
+fn modal_backdrop(id: WidgetId, dialog: impl UiNode) -> impl UiNode {
+    Container! {
+        id;
+        modal = true;
+        focus_scope = true;
+        focus_on_init = true;
+        tab_nav = TabNav::Cycle;
+        directional_nav = DirectionalNav::Cycle;
+        background_color = colors::BLACK.with_alpha(30.pct());
+        child_align = Align::CENTER;
+        child = dialog;
+    }
+}
+
+/// Remove an in-app dialog inserted by [`insert_modal`].
+fn remove_modal(window_id: WindowId, id: WidgetId) {
+    if let Some(h) = DIALOG_SV.read().host(window_id) {
+        h.remove(id);
+    }
+}
+
+command! {
+    /// Represents the dialog **accept** action.
+    ///
+    /// The [`Dialog!`] widget handles this command scoped on the widget, see [`on_dialog_accept`] for more details.
+    ///
+    /// [`Dialog!`]: struct@Dialog
+    /// [`on_dialog_accept`]: fn@on_dialog_accept
+    pub static DIALOG_ACCEPT_CMD = {
+        name: "Accept",
+        shortcut: shortcut!(Enter),
+    };
+
+    /// Represents the dialog **cancel** action.
+    ///
+    /// The [`Dialog!`] widget handles this command scoped on the widget, see [`on_dialog_cancel`] for more details.
+    ///
+    /// [`Dialog!`]: struct@Dialog
+    /// [`on_dialog_cancel`]: fn@on_dialog_cancel
+    pub static DIALOG_CANCEL_CMD = {
+        name: "Cancel",
+        shortcut: shortcut!(Escape),
+    };
+}
+
+command_property! {
+    /// On dialog accept command.
+    ///
+    /// Receives [`DIALOG_ACCEPT_CMD`] command events scoped on the widget, the command is
+    /// triggered by the `Enter` key when the focus is inside the widget.
+    pub fn dialog_accept {
+        cmd: DIALOG_ACCEPT_CMD.scoped(WIDGET.id()),
+    }
+
+    /// On dialog cancel command.
+    ///
+    /// Receives [`DIALOG_CANCEL_CMD`] command events scoped on the widget, the command is
+    /// triggered by the `Escape` key when the focus is inside the widget.
+    pub fn dialog_cancel {
+        cmd: DIALOG_CANCEL_CMD.scoped(WIDGET.id()),
+    }
+}
+
+/// Custom dialog widget.
+///
+/// This widget is a styleable container that can be shown using [`DIALOG.show`]. Set [`on_dialog_accept`] and
+/// [`on_dialog_cancel`] to handle the `Enter` and `Escape` keys and call [`DIALOG.respond`] to close the dialog.
+///
+/// [`DIALOG.show`]: DIALOG::show
+/// [`DIALOG.respond`]: DIALOG::respond
+/// [`on_dialog_accept`]: fn@on_dialog_accept
+/// [`on_dialog_cancel`]: fn@on_dialog_cancel
+#[widget($crate::Dialog {
+    ($child:expr) => {
+        child = $child;
+    };
+})]
+pub struct Dialog(StyleMix<Container>);
+impl Dialog {
+    fn widget_intrinsic(&mut self) {
+        self.style_intrinsic(STYLE_FN_VAR, property_id!(self::style_fn));
+        widget_set! {
+            self;
+            access_role = AccessRole::Dialog;
+            style_base_fn = style_fn!(|_| DefaultStyle!());
+        }
+    }
+}
+impl_style_fn!(Dialog);
+
+/// Dialog default style.
+#[widget($crate::DefaultStyle)]
+pub struct DefaultStyle(Style);
+impl DefaultStyle {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            replace = true;
+            padding = 10;
+            min_width = 300;
+            corner_radius = 4;
+            base_color = light_dark(rgb(245, 245, 245), rgb(30, 30, 30));
+            background_color = colors::BASE_COLOR_VAR.rgba();
+            border = {
+                widths: 1.px(),
+                sides: colors::BASE_COLOR_VAR.shade_into(1)
+            };
+        }
+    }
+}
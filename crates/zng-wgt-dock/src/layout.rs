@@ -0,0 +1,480 @@
+use serde::{Deserialize, Serialize};
+use zng_wgt::prelude::*;
+
+/// Direction of the nodes of a [`DockNode::Split`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockAxis {
+    /// Nodes are placed left-to-right.
+    Horizontal,
+    /// Nodes are placed top-to-bottom.
+    Vertical,
+}
+
+/// Node in a [`DockLayout`] tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DockNode {
+    /// Group of panels presented as tabs, only the selected panel is visible.
+    Tabs {
+        /// Panel IDs.
+        panels: Vec<Txt>,
+        /// Index of the selected panel.
+        selected: usize,
+    },
+    /// Nodes presented side by side, separated by splitters the user can drag.
+    Split {
+        /// Direction the nodes are placed.
+        axis: DockAxis,
+        /// Child nodes.
+        nodes: Vec<DockNode>,
+        /// Relative size of each node, the user can change this by dragging the splitters.
+        weights: Vec<f32>,
+    },
+}
+impl DockNode {
+    /// New tab group, the first panel is selected.
+    pub fn tabs(panels: impl IntoIterator<Item = impl Into<Txt>>) -> Self {
+        Self::Tabs {
+            panels: panels.into_iter().map(Into::into).collect(),
+            selected: 0,
+        }
+    }
+
+    /// New split with equal weights.
+    pub fn split(axis: DockAxis, nodes: impl IntoIterator<Item = DockNode>) -> Self {
+        let nodes: Vec<_> = nodes.into_iter().collect();
+        Self::Split {
+            axis,
+            weights: vec![1.0; nodes.len()],
+            nodes,
+        }
+    }
+
+    /// Gets the descendant node at the `path` of child indexes.
+    pub fn at(&self, path: &[usize]) -> Option<&DockNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((i, path)) => match self {
+                Self::Split { nodes, .. } => nodes.get(*i)?.at(path),
+                Self::Tabs { .. } => None,
+            },
+        }
+    }
+
+    /// Gets the descendant node at the `path` of child indexes.
+    pub fn at_mut(&mut self, path: &[usize]) -> Option<&mut DockNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((i, path)) => match self {
+                Self::Split { nodes, .. } => nodes.get_mut(*i)?.at_mut(path),
+                Self::Tabs { .. } => None,
+            },
+        }
+    }
+
+    /// Gets the path of the tab group that contains the `panel`.
+    pub fn find(&self, panel: &str) -> Option<Vec<usize>> {
+        match self {
+            Self::Tabs { panels, .. } => panels.iter().any(|p| p == panel).then(Vec::new),
+            Self::Split { nodes, .. } => nodes.iter().enumerate().find_map(|(i, n)| {
+                let mut path = n.find(panel)?;
+                path.insert(0, i);
+                Some(path)
+            }),
+        }
+    }
+
+    fn collect_panels(&self, out: &mut Vec<Txt>) {
+        match self {
+            Self::Tabs { panels, .. } => out.extend(panels.iter().cloned()),
+            Self::Split { nodes, .. } => nodes.iter().for_each(|n| n.collect_panels(out)),
+        }
+    }
+
+    /// Clone without the weights and selected index, used to detect changes that require a rebuild.
+    pub(crate) fn shape(&self) -> DockNode {
+        match self {
+            Self::Tabs { panels, .. } => Self::Tabs {
+                panels: panels.clone(),
+                selected: 0,
+            },
+            Self::Split { axis, nodes, .. } => Self::Split {
+                axis: *axis,
+                nodes: nodes.iter().map(Self::shape).collect(),
+                weights: vec![],
+            },
+        }
+    }
+
+    /// Remove empty groups and splits, collapse splits with a single node.
+    ///
+    /// Returns `None` if the node is empty.
+    fn normalize(self) -> Option<DockNode> {
+        match self {
+            Self::Tabs { panels, selected } => {
+                if panels.is_empty() {
+                    None
+                } else {
+                    let selected = selected.min(panels.len() - 1);
+                    Some(Self::Tabs { panels, selected })
+                }
+            }
+            Self::Split { axis, nodes, weights } => {
+                let mut new_nodes = Vec::with_capacity(nodes.len());
+                let mut new_weights = Vec::with_capacity(nodes.len());
+                for (i, n) in nodes.into_iter().enumerate() {
+                    if let Some(n) = n.normalize() {
+                        let w = weights.get(i).copied().filter(|w| w.is_finite() && *w > 0.0).unwrap_or(1.0);
+                        match n {
+                            // flatten nested splits in the same direction.
+                            Self::Split {
+                                axis: a,
+                                nodes: n_nodes,
+                                weights: n_weights,
+                            } if a == axis => {
+                                let total: f32 = n_weights.iter().sum();
+                                new_weights.extend(n_weights.iter().map(|nw| w * nw / total));
+                                new_nodes.extend(n_nodes);
+                            }
+                            n => {
+                                new_nodes.push(n);
+                                new_weights.push(w);
+                            }
+                        }
+                    }
+                }
+                match new_nodes.len() {
+                    0 => None,
+                    1 => new_nodes.pop(),
+                    _ => Some(Self::Split {
+                        axis,
+                        nodes: new_nodes,
+                        weights: new_weights,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Side of a dock target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockSide {
+    /// Add as a tab.
+    Center,
+    /// Split and place at the left.
+    Left,
+    /// Split and place at the right.
+    Right,
+    /// Split and place at the top.
+    Top,
+    /// Split and place at the bottom.
+    Bottom,
+}
+impl DockSide {
+    /// Gets the split axis and if the new node is placed after the target.
+    fn split(self) -> Option<(DockAxis, bool)> {
+        match self {
+            DockSide::Center => None,
+            DockSide::Left => Some((DockAxis::Horizontal, false)),
+            DockSide::Right => Some((DockAxis::Horizontal, true)),
+            DockSide::Top => Some((DockAxis::Vertical, false)),
+            DockSide::Bottom => Some((DockAxis::Vertical, true)),
+        }
+    }
+
+    /// Gets the part of `rect` that is covered by a panel docked on this side.
+    pub fn preview(self, rect: PxRect) -> PxRect {
+        let half = rect.size / Px(2);
+        match self {
+            DockSide::Center => rect,
+            DockSide::Left => PxRect::new(rect.origin, PxSize::new(half.width, rect.size.height)),
+            DockSide::Right => PxRect::new(
+                PxPoint::new(rect.origin.x + half.width, rect.origin.y),
+                PxSize::new(rect.size.width - half.width, rect.size.height),
+            ),
+            DockSide::Top => PxRect::new(rect.origin, PxSize::new(rect.size.width, half.height)),
+            DockSide::Bottom => PxRect::new(
+                PxPoint::new(rect.origin.x, rect.origin.y + half.height),
+                PxSize::new(rect.size.width, rect.size.height - half.height),
+            ),
+        }
+    }
+}
+
+/// Where a panel is docked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockTarget {
+    /// In or beside the tab group that contains the panel.
+    Panel {
+        /// A panel in the target group.
+        panel: Txt,
+        /// Side of the group.
+        side: DockSide,
+    },
+    /// Beside all other panels.
+    Root(DockSide),
+}
+
+/// Panel presented in its own window.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FloatingPanel {
+    /// Panel ID.
+    pub panel: Txt,
+    /// Window position.
+    pub position: Point,
+    /// Window size.
+    pub size: Size,
+}
+
+/// Layout of the panels of a [`Dock!`].
+///
+/// The layout can be serialized, bind the layout variable to a [`CONFIG`] key to persist the layout edits of the user.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt_dock::*;
+/// let mut layout = DockLayout::new(DockNode::split(
+///     DockAxis::Horizontal,
+///     [DockNode::tabs(["files", "search"]), DockNode::tabs(["editor"])],
+/// ));
+///
+/// layout.dock("search", DockTarget::Panel { panel: "editor".into(), side: DockSide::Bottom });
+/// assert_eq!(layout.root.as_ref().unwrap().find("search"), Some(vec![1, 1]));
+///
+/// layout.remove("files");
+/// assert_eq!(layout.root.as_ref().unwrap().find("search"), Some(vec![1]));
+/// ```
+///
+/// [`Dock!`]: struct@crate::Dock
+/// [`CONFIG`]: https://zng-ui.github.io/doc/zng/config/struct.CONFIG.html
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DockLayout {
+    /// Docked panels.
+    pub root: Option<DockNode>,
+    /// Panels presented in their own window.
+    pub floating: Vec<FloatingPanel>,
+}
+impl DockLayout {
+    /// New layout with all panels docked.
+    pub fn new(root: DockNode) -> Self {
+        Self {
+            root: Some(root).and_then(DockNode::normalize),
+            floating: vec![],
+        }
+    }
+
+    /// All panels in the layout.
+    pub fn panels(&self) -> Vec<Txt> {
+        let mut out = vec![];
+        if let Some(r) = &self.root {
+            r.collect_panels(&mut out);
+        }
+        out.extend(self.floating.iter().map(|f| f.panel.clone()));
+        out
+    }
+
+    /// If the panel is in the layout.
+    pub fn contains(&self, panel: &str) -> bool {
+        self.is_floating(panel) || self.root.as_ref().and_then(|r| r.find(panel)).is_some()
+    }
+
+    /// If the panel is in its own window.
+    pub fn is_floating(&self, panel: &str) -> bool {
+        self.floating.iter().any(|f| f.panel == panel)
+    }
+
+    /// Remove the panel from the layout.
+    ///
+    /// Returns `true` if the panel was in the layout.
+    pub fn remove(&mut self, panel: &str) -> bool {
+        let len = self.floating.len();
+        self.floating.retain(|f| f.panel != panel);
+        if self.floating.len() != len {
+            return true;
+        }
+
+        if let Some(root) = &mut self.root {
+            if let Some(path) = root.find(panel) {
+                if let Some(DockNode::Tabs { panels, selected }) = root.at_mut(&path) {
+                    let i = panels.iter().position(|p| p == panel).unwrap();
+                    panels.remove(i);
+                    if *selected > i {
+                        *selected -= 1;
+                    }
+                }
+                self.root = self.root.take().and_then(DockNode::normalize);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Move or insert the panel in the `target` position.
+    ///
+    /// If the target panel is not docked the panel is docked at the right of all panels.
+    pub fn dock(&mut self, panel: impl Into<Txt>, target: DockTarget) {
+        let panel = panel.into();
+        let mut target = target;
+        if let DockTarget::Panel { panel: t, side } = &mut target {
+            if t == &panel {
+                // target another panel of the same group, the dragged panel is removed first.
+                let other = match self.root.as_ref().and_then(|r| r.at(&r.find(t)?)) {
+                    Some(DockNode::Tabs { panels, .. }) if *side != DockSide::Center => panels.iter().find(|p| *p != &panel).cloned(),
+                    _ => None,
+                };
+                match other {
+                    Some(o) => *t = o,
+                    // already there
+                    None => return,
+                }
+            }
+        }
+
+        self.remove(&panel);
+
+        let root = match self.root.take() {
+            Some(r) => r,
+            None => {
+                self.root = Some(DockNode::tabs([panel]));
+                return;
+            }
+        };
+
+        let (mut root, path, side) = match target {
+            DockTarget::Panel { panel: t, side } => match root.find(&t) {
+                Some(path) => (root, path, side),
+                None => (root, vec![], DockSide::Right),
+            },
+            DockTarget::Root(side) => (root, vec![], side),
+        };
+
+        let target = root.at_mut(&path).unwrap();
+        match side.split() {
+            None => match target {
+                DockNode::Tabs { panels, selected } => {
+                    *selected = (*selected + 1).min(panels.len());
+                    panels.insert(*selected, panel);
+                }
+                DockNode::Split { .. } => {
+                    let node = std::mem::replace(target, DockNode::tabs([""; 0]));
+                    *target = DockNode::split(DockAxis::Horizontal, [node, DockNode::tabs([panel])]);
+                }
+            },
+            Some((axis, after)) => {
+                let node = std::mem::replace(target, DockNode::tabs([""; 0]));
+                let new = DockNode::tabs([panel]);
+                *target = DockNode::split(axis, if after { [node, new] } else { [new, node] });
+            }
+        }
+
+        self.root = root.normalize();
+    }
+
+    /// Move or insert the panel in its own window.
+    pub fn float(&mut self, panel: impl Into<Txt>, position: impl Into<Point>, size: impl Into<Size>) {
+        let panel = panel.into();
+        self.remove(&panel);
+        self.floating.push(FloatingPanel {
+            panel,
+            position: position.into(),
+            size: size.into(),
+        });
+    }
+
+    /// Select the panel in its tab group.
+    pub fn select(&mut self, panel: &str) {
+        if let Some(root) = &mut self.root {
+            if let Some(path) = root.find(panel) {
+                if let Some(DockNode::Tabs { panels, selected }) = root.at_mut(&path) {
+                    *selected = panels.iter().position(|p| p == panel).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Clone without the weights and selected tabs.
+    pub(crate) fn shape(&self) -> Option<DockNode> {
+        self.root.as_ref().map(DockNode::shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> DockLayout {
+        let mut layout = DockLayout::new(DockNode::split(
+            DockAxis::Horizontal,
+            [
+                DockNode::tabs(["files", "search"]),
+                DockNode::split(
+                    DockAxis::Vertical,
+                    [DockNode::tabs(["editor"]), DockNode::tabs(["terminal", "output"])],
+                ),
+            ],
+        ));
+        layout.select("output");
+        layout.float("log", (10, 20), (300, 200));
+        if let Some(DockNode::Split { weights, .. }) = &mut layout.root {
+            weights[0] = 0.25;
+        }
+        layout
+    }
+
+    fn round_trip(layout: &DockLayout) -> DockLayout {
+        let json = serde_json::to_string(layout).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let layout = layout();
+        let r = round_trip(&layout);
+        assert_eq!(layout, r);
+
+        // selected tab, weights and floating window are persisted.
+        assert_eq!(
+            Some(&DockNode::Tabs {
+                panels: vec!["terminal".into(), "output".into()],
+                selected: 1,
+            }),
+            r.root.as_ref().unwrap().at(&[1, 1])
+        );
+        match &r.root {
+            Some(DockNode::Split { weights, .. }) => assert_eq!(&vec![0.25, 1.0], weights),
+            n => panic!("expected split, found {n:?}"),
+        }
+        assert!(r.is_floating("log"));
+        assert_eq!(Point::from((10, 20)), r.floating[0].position);
+        assert_eq!(Size::from((300, 200)), r.floating[0].size);
+    }
+
+    #[test]
+    fn serde_round_trip_after_edits() {
+        let mut layout = layout();
+        layout.dock(
+            "log",
+            DockTarget::Panel {
+                panel: "editor".into(),
+                side: DockSide::Center,
+            },
+        );
+        layout.dock("files", DockTarget::Root(DockSide::Bottom));
+        layout.remove("terminal");
+
+        let r = round_trip(&layout);
+        assert_eq!(layout, r);
+        assert_eq!(layout.panels(), r.panels());
+        assert!(!r.is_floating("log"));
+        assert_eq!(r.root.as_ref().unwrap().find("log"), r.root.as_ref().unwrap().find("editor"));
+    }
+
+    #[test]
+    fn serde_round_trip_empty() {
+        let layout = DockLayout::default();
+        let r = round_trip(&layout);
+        assert_eq!(layout, r);
+        assert!(r.panels().is_empty());
+    }
+}
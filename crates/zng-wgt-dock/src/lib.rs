@@ -0,0 +1,765 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Docking layout widget, properties and nodes.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use zng_ext_input::mouse::{MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT};
+use zng_ext_window::{WINDOW_Ext as _, WindowCloseRequestedArgs, WindowRoot, WINDOWS};
+use zng_wgt::{clip_to_bounds, prelude::*};
+use zng_wgt_container::Container;
+use zng_wgt_fill::background_color;
+use zng_wgt_input::{
+    cursor,
+    pointer_capture::{capture_pointer, CaptureMode},
+    CursorIcon,
+};
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_text::Text;
+use zng_wgt_window::Window;
+
+mod layout;
+pub use layout::*;
+
+/// Docking layout manager, panels presented in tab groups that the user can drag to rearrange.
+///
+/// The dock presents the panels of a [`DockLayout`], docked panels are arranged in nested splits of tab groups and
+/// floating panels are presented in their own tool windows, parented to the dock window.
+///
+/// # User Edit
+///
+/// Dragging a tab shows a preview of the drop target, dropping over a tab group adds the panel to the group and dropping
+/// near the edges of a group or of the dock splits the area. Dropping outside of the dock moves the panel to its own
+/// window, dragging the tab of a floating window back into the dock docks it again and closing a floating window docks
+/// it at the right. Dragging the gaps between split areas resizes the areas.
+///
+/// The edits are applied to the layout variable if it is read-write.
+///
+/// # Persistence
+///
+/// The [`DockLayout`] is serializable, bind the layout to a config key to restore the user layout on the next run.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_dock::*;
+/// # fn main() { }
+/// # fn panel(id: Txt) -> impl UiNode { NilUiNode }
+/// # fn demo(layout: impl IntoVar<DockLayout>) -> impl UiNode {
+/// // layout = CONFIG.get("dock.layout", default_layout),
+/// Dock! {
+///     dock_layout = layout;
+///     panel_fn = wgt_fn!(|a: DockPanelArgs| panel(a.id));
+/// }
+/// # }
+/// ```
+///
+/// # Shorthand
+///
+/// The `Dock!` macro provides a shorthand init that sets `dock_layout` property directly.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt_dock::*;
+/// # use zng_wgt::prelude::*;
+/// # fn main() { }
+/// # fn shorthand_demo(layout: impl IntoVar<DockLayout>) -> impl UiNode {
+/// Dock!(layout)
+/// # }
+/// ```
+#[widget($crate::Dock {
+    ($layout:expr) => {
+        dock_layout = $layout;
+    }
+})]
+pub struct Dock(WidgetBase);
+impl Dock {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            clip_to_bounds = true;
+        }
+    }
+}
+
+context_var! {
+    /// Widget function for the panel content.
+    pub static PANEL_FN_VAR: WidgetFn<DockPanelArgs> = WidgetFn::nil();
+
+    /// Widget function for the panel tabs.
+    pub static TAB_FN_VAR: WidgetFn<DockTabArgs> = WidgetFn::new(default_tab_fn);
+
+    /// Width of the gap between split areas, the user can drag the gap to resize the areas.
+    ///
+    /// Is `4.dip()` by default.
+    pub static SPLITTER_WIDTH_VAR: Length = 4.dip();
+
+    /// Color of the drop target preview.
+    pub static PREVIEW_COLOR_VAR: Rgba = colors::AZURE.with_alpha(30.pct());
+}
+
+/// Widget function for the panel content.
+///
+/// The panel widget is generated once per panel ID and moved between tab groups and windows as the user
+/// rearranges the layout.
+///
+/// Sets the [`PANEL_FN_VAR`].
+#[property(CONTEXT+1, default(PANEL_FN_VAR), widget_impl(Dock))]
+pub fn panel_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<DockPanelArgs>>) -> impl UiNode {
+    with_context_var(child, PANEL_FN_VAR, wgt_fn)
+}
+
+/// Widget function for the panel tabs.
+///
+/// Sets the [`TAB_FN_VAR`].
+#[property(CONTEXT+1, default(TAB_FN_VAR), widget_impl(Dock))]
+pub fn tab_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<DockTabArgs>>) -> impl UiNode {
+    with_context_var(child, TAB_FN_VAR, wgt_fn)
+}
+
+/// Width of the gap between split areas.
+///
+/// Sets the [`SPLITTER_WIDTH_VAR`].
+#[property(CONTEXT, default(SPLITTER_WIDTH_VAR), widget_impl(Dock))]
+pub fn splitter_width(child: impl UiNode, width: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, SPLITTER_WIDTH_VAR, width)
+}
+
+/// Color of the drop target preview.
+///
+/// Sets the [`PREVIEW_COLOR_VAR`].
+#[property(CONTEXT, default(PREVIEW_COLOR_VAR), widget_impl(Dock))]
+pub fn preview_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+    with_context_var(child, PREVIEW_COLOR_VAR, color)
+}
+
+/// Arguments for the [`panel_fn`].
+///
+/// [`panel_fn`]: fn@panel_fn
+#[derive(Clone, Debug, PartialEq)]
+pub struct DockPanelArgs {
+    /// Panel ID.
+    pub id: Txt,
+}
+
+/// Arguments for the [`tab_fn`].
+///
+/// [`tab_fn`]: fn@tab_fn
+#[derive(Clone)]
+pub struct DockTabArgs {
+    /// Panel ID.
+    pub id: Txt,
+    /// If the panel is the selected tab of its group, is always `true` for floating panels.
+    pub is_selected: BoxedVar<bool>,
+}
+
+/// Default [`TAB_FN_VAR`].
+///
+/// Returns a `Text!` with the panel ID.
+pub fn default_tab_fn(args: DockTabArgs) -> impl UiNode {
+    Text!(args.id)
+}
+
+/// The panels layout.
+///
+/// If the variable is read-write the user can rearrange the panels, see [`Dock!`] for details.
+///
+/// Note that this property replaces the widget child.
+///
+/// [`Dock!`]: struct@Dock
+#[property(CHILD, widget_impl(Dock))]
+pub fn dock_layout(_child: impl UiNode, layout: impl IntoVar<DockLayout>) -> impl UiNode {
+    let ctx = DockCtx {
+        layout: layout.into_var().boxed(),
+        panels: Arc::default(),
+        windows: Arc::default(),
+        drag: var(None),
+    };
+    let mut shape = None;
+    let mut preview = None::<PxRect>;
+
+    match_node(NilUiNode.boxed(), move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&ctx.layout)
+                .sub_var(&ctx.drag)
+                .sub_var(&PANEL_FN_VAR)
+                .sub_var(&TAB_FN_VAR)
+                .sub_var_render(&PREVIEW_COLOR_VAR);
+
+            ctx.sync_panels();
+            shape = ctx.layout.with(DockLayout::shape);
+            *c.child() = dock_view(&ctx, shape.as_ref());
+            ctx.sync_windows();
+        }
+        UiNodeOp::Deinit => {
+            c.deinit();
+            *c.child() = NilUiNode.boxed();
+            ctx.close_windows();
+            ctx.panels.lock().clear();
+            ctx.drag.set(None);
+            preview = None;
+        }
+        UiNodeOp::Update { .. } => {
+            let reset = PANEL_FN_VAR.is_new() || TAB_FN_VAR.is_new();
+            if reset || ctx.layout.is_new() {
+                if reset {
+                    c.child().deinit();
+                    *c.child() = NilUiNode.boxed();
+                    ctx.close_windows();
+                    ctx.panels.lock().clear();
+                }
+                ctx.sync_panels();
+
+                // weights and selected tab are applied without rebuilding.
+                let new_shape = ctx.layout.with(DockLayout::shape);
+                if reset || new_shape != shape {
+                    shape = new_shape;
+                    c.child().deinit();
+                    *c.child() = dock_view(&ctx, shape.as_ref());
+                    c.child().init();
+                    c.delegated();
+                    WIDGET.update_info().layout().render();
+                }
+                ctx.sync_windows();
+            }
+
+            if let Some(drag) = ctx.drag.get_new() {
+                let mut new_preview = None;
+                if let Some(drag) = drag {
+                    let action = drop_action(&drag);
+                    if drag.released {
+                        ctx.drag.set(None);
+                        match action {
+                            Some(DropAction::Dock(target, _)) => {
+                                let _ = ctx.layout.modify(move |l| l.to_mut().dock(drag.panel, target));
+                            }
+                            Some(DropAction::Float(position, size)) => {
+                                if ctx.layout.with(|l| !l.is_floating(&drag.panel)) {
+                                    let _ = ctx.layout.modify(move |l| l.to_mut().float(drag.panel, position, size));
+                                }
+                            }
+                            None => {}
+                        }
+                    } else if let Some(DropAction::Dock(_, rect)) = action {
+                        new_preview = Some(rect);
+                    }
+                }
+                if preview != new_preview {
+                    preview = new_preview;
+                    WIDGET.render();
+                }
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            c.render(frame);
+
+            if let Some(rect) = preview.and_then(window_to_inner) {
+                frame.push_color(rect, FrameValue::Value(PREVIEW_COLOR_VAR.get()));
+            }
+        }
+        _ => {}
+    })
+}
+
+static_id! {
+    // Panels of a tab group widget.
+    static ref DOCK_GROUP_ID: StateId<Vec<Txt>>;
+}
+
+/// Distance from the dock edges that targets a [`DockTarget::Root`] side.
+const ROOT_EDGE: Dip = Dip::new(24);
+
+/// Distance the pointer must move from the press point to start dragging a tab.
+const DRAG_THRESHOLD: Px = Px(6);
+
+/// Minimum size of split areas resized by the user.
+const MIN_AREA: Dip = Dip::new(32);
+
+/// State shared by the dock and the floating windows.
+#[derive(Clone)]
+struct DockCtx {
+    layout: BoxedVar<DockLayout>,
+    /// Panel content, the nodes are moved to the tab group or window that presents the panel.
+    panels: Arc<Mutex<HashMap<Txt, ArcNode<BoxedUiNode>>>>,
+    /// Floating panel windows.
+    windows: Arc<Mutex<HashMap<Txt, WindowId>>>,
+    /// Tab drag, in the dock or in a floating window.
+    drag: ArcVar<Option<DockDrag>>,
+}
+impl DockCtx {
+    /// Generate the panels added to the layout and drop the panels removed.
+    fn sync_panels(&self) {
+        let ids = self.layout.with(DockLayout::panels);
+        let mut panels = self.panels.lock();
+        panels.retain(|id, _| ids.contains(id));
+        let panel_fn = PANEL_FN_VAR.get();
+        for id in ids {
+            panels
+                .entry(id.clone())
+                .or_insert_with(|| ArcNode::new(panel_fn(DockPanelArgs { id })));
+        }
+    }
+
+    /// Open the windows of the floating panels and close the windows of the panels that are no longer floating.
+    fn sync_windows(&self) {
+        let floating = self.layout.with(|l| l.floating.clone());
+        let mut windows = self.windows.lock();
+
+        let closed: Vec<_> = windows
+            .keys()
+            .filter(|p| !floating.iter().any(|f| &f.panel == *p))
+            .cloned()
+            .collect();
+        for panel in closed {
+            if let Some(id) = windows.remove(&panel) {
+                let _ = WINDOWS.close(id);
+            }
+        }
+
+        let dock_window = WINDOW.id();
+        let tab_fn = TAB_FN_VAR.get();
+        for f in floating {
+            if !windows.contains_key(&f.panel) {
+                let id = WindowId::new_unique();
+                windows.insert(f.panel.clone(), id);
+                WINDOWS.open_id(id, floating_window(self.clone(), f, dock_window, tab_fn.clone()));
+            }
+        }
+    }
+
+    /// Close all floating windows without changing the layout.
+    fn close_windows(&self) {
+        for (_, id) in self.windows.lock().drain() {
+            let _ = WINDOWS.close(id);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DockDrag {
+    panel: Txt,
+    /// Pointer position in the virtual screen.
+    point: PxPoint,
+    released: bool,
+}
+
+enum DropAction {
+    /// Dock at the target, the rect is the preview in the window space.
+    Dock(DockTarget, PxRect),
+    /// Float at the position and size.
+    Float(Point, Size),
+}
+
+/// Compute the drop action for the drag, must be called in the dock context.
+fn drop_action(drag: &DockDrag) -> Option<DropAction> {
+    let vars = WINDOW.vars();
+    let scale = vars.scale_factor().get();
+    let point = drag.point - vars.global_position().get().to_vector();
+
+    let dock = WIDGET.info();
+    let bounds = dock.inner_bounds();
+    if !bounds.contains(point) {
+        let position = vars.actual_position().get() + point.to_dip(scale).to_vector();
+        let size = dock
+            .descendants()
+            .find(|w| w.meta().get(*DOCK_GROUP_ID).is_some_and(|p| p.contains(&drag.panel)))
+            .map(|w| w.inner_bounds().size.to_dip(scale))
+            .unwrap_or_else(|| DipSize::new(Dip::new(300), Dip::new(200)));
+        return Some(DropAction::Float(position.into(), size.into()));
+    }
+
+    let edge = ROOT_EDGE.to_px(scale);
+    if let Some(side) = nearest_side(point, bounds, |d, _| d < edge) {
+        return Some(DropAction::Dock(DockTarget::Root(side), side.preview(bounds)));
+    }
+
+    for w in dock.descendants() {
+        if let Some(panels) = w.meta().get(*DOCK_GROUP_ID) {
+            let rect = w.inner_bounds();
+            if rect.contains(point) {
+                let side = nearest_side(point, rect, |d, len| d.0 * 4 < len.0).unwrap_or(DockSide::Center);
+                let panel = panels[0].clone();
+                return Some(DropAction::Dock(DockTarget::Panel { panel, side }, side.preview(rect)));
+            }
+        }
+    }
+    None
+}
+
+/// Gets the side of `rect` nearest to `point` if `is_near(distance, length)`.
+fn nearest_side(point: PxPoint, rect: PxRect, is_near: impl Fn(Px, Px) -> bool) -> Option<DockSide> {
+    let (w, h) = (rect.size.width, rect.size.height);
+    [
+        (point.x - rect.min_x(), w, DockSide::Left),
+        (rect.max_x() - point.x, w, DockSide::Right),
+        (point.y - rect.min_y(), h, DockSide::Top),
+        (rect.max_y() - point.y, h, DockSide::Bottom),
+    ]
+    .into_iter()
+    .filter(|(d, len, _)| is_near(*d, *len))
+    .min_by_key(|(d, _, _)| *d)
+    .map(|(_, _, s)| s)
+}
+
+/// Convert the rect from the window space to the widget inner space.
+fn window_to_inner(rect: PxRect) -> Option<PxRect> {
+    let t = WIDGET.info().inner_transform().inverse()?;
+    t.outer_transformed(rect.to_box2d()).map(|b| b.to_rect())
+}
+
+/// Pointer position in the virtual screen.
+fn screen_point(position: DipPoint) -> PxPoint {
+    let vars = WINDOW.vars();
+    vars.global_position().get() + position.to_px(vars.scale_factor().get()).to_vector()
+}
+
+fn dock_view(ctx: &DockCtx, shape: Option<&DockNode>) -> BoxedUiNode {
+    match shape {
+        Some(n) => node_view(ctx, n, vec![]),
+        None => NilUiNode.boxed(),
+    }
+}
+
+fn node_view(ctx: &DockCtx, node: &DockNode, path: Vec<usize>) -> BoxedUiNode {
+    match node {
+        DockNode::Tabs { panels, .. } => group_view(ctx, panels.clone(), path).boxed(),
+        DockNode::Split { axis, nodes, .. } => {
+            let nodes = nodes
+                .iter()
+                .enumerate()
+                .map(|(i, n)| {
+                    let mut path = path.clone();
+                    path.push(i);
+                    node_view(ctx, n, path)
+                })
+                .collect();
+            split_view(ctx, *axis, nodes, path).boxed()
+        }
+    }
+}
+
+/// Tab group widget.
+fn group_view(ctx: &DockCtx, panels: Vec<Txt>, path: Vec<usize>) -> impl UiNode {
+    let selected = ctx.layout.map(move |l| match l.root.as_ref().and_then(|r| r.at(&path)) {
+        Some(DockNode::Tabs { panels, selected }) => panels.get(*selected).cloned(),
+        _ => None,
+    });
+
+    let tab_fn = TAB_FN_VAR.get();
+    let tabs: UiNodeVec = panels
+        .iter()
+        .map(|id| {
+            let is_selected = selected.map(clmv!(id, |s| s.as_ref() == Some(&id))).boxed();
+            tab_view(ctx.clone(), id.clone(), is_selected, tab_fn.clone()).boxed()
+        })
+        .collect();
+
+    let slots = ctx.panels.clone();
+    let content = presenter(
+        selected,
+        WidgetFn::new(move |id: Option<Txt>| match id.and_then(|id| slots.lock().get(&id).cloned()) {
+            Some(slot) => slot.take_on_init().boxed(),
+            None => NilUiNode.boxed(),
+        }),
+    );
+    let content = match_node(content, move |_, op| {
+        if let UiNodeOp::Info { info } = op {
+            info.set_meta(*DOCK_GROUP_ID, panels.clone());
+        }
+    });
+
+    Container! {
+        child = content;
+        child_top = tab_strip(tabs), 0;
+        clip_to_bounds = true;
+    }
+}
+
+fn tab_strip(tabs: UiNodeVec) -> impl UiNode {
+    Stack! {
+        direction = StackDirection::left_to_right();
+        children = tabs;
+        background_color = colors::BASE_COLOR_VAR.shade(1);
+    }
+}
+
+/// Tab widget, starts a drag on press and move.
+fn tab_view(ctx: DockCtx, id: Txt, is_selected: BoxedVar<bool>, tab_fn: WidgetFn<DockTabArgs>) -> impl UiNode {
+    let tab = tab_fn(DockTabArgs {
+        id: id.clone(),
+        is_selected: is_selected.clone(),
+    });
+
+    let mut press = None::<PxPoint>;
+    let node = match_node(
+        tab,
+        clmv!(is_selected, |c, op| match op {
+            UiNodeOp::Init => {
+                WIDGET.sub_event(&MOUSE_INPUT_EVENT).sub_event(&MOUSE_MOVE_EVENT);
+            }
+            UiNodeOp::Deinit => {
+                press = None;
+            }
+            UiNodeOp::Event { update } => {
+                c.event(update);
+
+                if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                    if let Some(p) = press {
+                        let pos = args.position.to_px(WINDOW.info().scale_factor());
+                        let is_dragging = ctx.drag.with(|d| d.as_ref().is_some_and(|d| d.panel == id));
+                        if is_dragging || (pos.x - p.x).abs() > DRAG_THRESHOLD || (pos.y - p.y).abs() > DRAG_THRESHOLD {
+                            ctx.drag.set(Some(DockDrag {
+                                panel: id.clone(),
+                                point: screen_point(args.position),
+                                released: false,
+                            }));
+                        }
+                    }
+                } else if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                    if !args.is_primary() {
+                        return;
+                    }
+                    if args.is_mouse_down() {
+                        press = Some(args.position.to_px(WINDOW.info().scale_factor()));
+                        if !is_selected.get() {
+                            let id = id.clone();
+                            let _ = ctx.layout.modify(move |l| l.to_mut().select(&id));
+                        }
+                    } else if args.is_mouse_up() && press.take().is_some() && ctx.drag.with(|d| d.is_some()) {
+                        ctx.drag.set(Some(DockDrag {
+                            panel: id.clone(),
+                            point: screen_point(args.position),
+                            released: true,
+                        }));
+                    }
+                }
+            }
+            _ => {}
+        }),
+    );
+
+    Container! {
+        child = node;
+        padding = (2, 8);
+        background_color = merge_var!(is_selected, colors::BASE_COLOR_VAR.rgba(), |&s, &c| if s { c } else { c.with_alpha(0.pct()) });
+        capture_pointer = CaptureMode::Widget;
+    }
+}
+
+/// Split areas, with splitter widgets between the nodes.
+fn split_view(ctx: &DockCtx, axis: DockAxis, nodes: Vec<BoxedUiNode>, path: Vec<usize>) -> impl UiNode {
+    let len = nodes.len();
+    // sizes of the areas in the last layout, used by the splitters.
+    let sizes = Arc::new(Mutex::new(vec![Px(0); len]));
+    let mut children = UiNodeVec::new();
+    for (i, node) in nodes.into_iter().enumerate() {
+        if i > 0 {
+            children.push(splitter(ctx.layout.clone(), axis, path.clone(), i - 1, sizes.clone()).boxed());
+        }
+        children.push(node);
+    }
+
+    let layout = ctx.layout.clone();
+    let frame_id = SpatialFrameId::new_unique();
+    let mut offsets = Vec::<PxVector>::with_capacity(children.len());
+
+    match_node_list(children, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var_layout(&layout).sub_var_layout(&SPLITTER_WIDTH_VAR);
+        }
+        UiNodeOp::Measure { desired_size, .. } => {
+            c.delegated();
+            *desired_size = LAYOUT.constraints().fill_size();
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            wl.require_child_ref_frame();
+
+            let size = LAYOUT.constraints().fill_size();
+            let (main, gap) = match axis {
+                DockAxis::Horizontal => (size.width, SPLITTER_WIDTH_VAR.layout_x()),
+                DockAxis::Vertical => (size.height, SPLITTER_WIDTH_VAR.layout_y()),
+            };
+            let available = (main - Px(gap.0 * (len as i32 - 1))).max(Px(0));
+
+            let weights = layout.with(|l| match l.root.as_ref().and_then(|r| r.at(&path)) {
+                Some(DockNode::Split { weights, .. }) if weights.len() == len => weights.clone(),
+                _ => vec![1.0; len],
+            });
+            let total: f32 = weights.iter().sum();
+
+            let mut sizes = sizes.lock();
+            let mut used = Px(0);
+            for (i, w) in weights.iter().enumerate() {
+                sizes[i] = if i == len - 1 {
+                    available - used
+                } else {
+                    Px((available.0 as f32 * w / total) as i32)
+                };
+                used += sizes[i];
+            }
+
+            offsets.clear();
+            let mut offset = Px(0);
+            for i in 0..c.children().len() {
+                let l = if i % 2 == 0 { sizes[i / 2] } else { gap };
+                let (s, o) = match axis {
+                    DockAxis::Horizontal => (PxSize::new(l, size.height), PxVector::new(offset, Px(0))),
+                    DockAxis::Vertical => (PxSize::new(size.width, l), PxVector::new(Px(0), offset)),
+                };
+                LAYOUT.with_constraints(PxConstraints2d::new_exact(s.width, s.height), || c.with_node(i, |n| n.layout(wl)));
+                offsets.push(o);
+                offset += l;
+            }
+
+            *final_size = size;
+        }
+        UiNodeOp::Render { frame } => {
+            for (i, o) in offsets.iter().enumerate() {
+                let t = PxTransform::from(*o);
+                frame.push_reference_frame((frame_id, i as u32).into(), FrameValue::Value(t), true, false, |frame| {
+                    c.with_node(i, |n| n.render(frame));
+                });
+            }
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            for (i, o) in offsets.iter().enumerate() {
+                update.with_transform_value(&PxTransform::from(*o), |update| {
+                    c.with_node(i, |n| n.render_update(update));
+                });
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Gap widget between the areas `index` and `index + 1` of the split at `path`, dragging it resizes the areas.
+fn splitter(layout: BoxedVar<DockLayout>, axis: DockAxis, path: Vec<usize>, index: usize, sizes: Arc<Mutex<Vec<Px>>>) -> impl UiNode {
+    let main = move |p: DipPoint| {
+        let p = p.to_px(WINDOW.info().scale_factor());
+        match axis {
+            DockAxis::Horizontal => p.x,
+            DockAxis::Vertical => p.y,
+        }
+    };
+    // pointer start, size of the areas before and after.
+    let mut drag = None::<(Px, Px, Px)>;
+    let node = match_node(FillUiNode, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_event(&MOUSE_INPUT_EVENT).sub_event(&MOUSE_MOVE_EVENT);
+        }
+        UiNodeOp::Deinit => {
+            drag = None;
+        }
+        UiNodeOp::Event { update } => {
+            if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                if let Some((start, before, after)) = drag {
+                    let total = before + after;
+                    let min = MIN_AREA.to_px(WINDOW.info().scale_factor()).min(Px(total.0 / 2));
+                    let before = (before + main(args.position) - start).max(min).min(total - min);
+                    let ratio = before.0 as f32 / total.0.max(1) as f32;
+
+                    let path = path.clone();
+                    let _ = layout.modify(move |l| {
+                        if let Some(DockNode::Split { nodes, weights, .. }) = l.to_mut().root.as_mut().and_then(|r| r.at_mut(&path)) {
+                            weights.resize(nodes.len(), 1.0);
+                            let sum = weights[index] + weights[index + 1];
+                            weights[index] = sum * ratio;
+                            weights[index + 1] = sum - weights[index];
+                        }
+                    });
+                }
+            } else if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                if !args.is_primary() {
+                    return;
+                }
+                if args.is_mouse_down() {
+                    let sizes = sizes.lock();
+                    drag = Some((main(args.position), sizes[index], sizes[index + 1]));
+                } else if args.is_mouse_up() {
+                    drag = None;
+                }
+            }
+        }
+        _ => {}
+    });
+
+    Container! {
+        child = node;
+        capture_pointer = CaptureMode::Widget;
+        cursor = match axis {
+            DockAxis::Horizontal => CursorIcon::ColResize,
+            DockAxis::Vertical => CursorIcon::RowResize,
+        };
+    }
+}
+
+/// Tool window that presents a floating panel.
+async fn floating_window(ctx: DockCtx, panel: FloatingPanel, dock_window: WindowId, tab_fn: WidgetFn<DockTabArgs>) -> WindowRoot {
+    let FloatingPanel { panel, position, size } = panel;
+
+    // user moves and resizes are applied to the layout.
+    let layout = ctx.layout.clone();
+    let position = var(position);
+    position
+        .hook(clmv!(layout, panel, |a| {
+            let position = a.value().clone();
+            let _ = layout.modify(clmv!(panel, |l| {
+                if let Some(f) = l.to_mut().floating.iter_mut().find(|f| f.panel == panel) {
+                    f.position = position;
+                }
+            }));
+            true
+        }))
+        .perm();
+    let size = var(size);
+    size.hook(clmv!(panel, |a| {
+        let size = a.value().clone();
+        let _ = layout.modify(clmv!(panel, |l| {
+            if let Some(f) = l.to_mut().floating.iter_mut().find(|f| f.panel == panel) {
+                f.size = size;
+            }
+        }));
+        true
+    }))
+    .perm();
+
+    let tab = tab_view(ctx.clone(), panel.clone(), LocalVar(true).boxed(), tab_fn);
+    let content = match ctx.panels.lock().get(&panel) {
+        Some(slot) => slot.take_on_init().boxed(),
+        None => NilUiNode.boxed(),
+    };
+
+    let title = panel.clone();
+    Window! {
+        title;
+        parent = Some(dock_window);
+        position;
+        size;
+        child_top = tab_strip(ui_vec![tab]), 0;
+        child = content;
+        on_close_requested = hn!(|args: &WindowCloseRequestedArgs| {
+            if args.windows.contains(&dock_window) {
+                // closing with the dock window, keep floating
+                return;
+            }
+            let mut windows = ctx.windows.lock();
+            if windows.get(&panel) == Some(&WINDOW.id()) {
+                // closed by the user, dock back
+                windows.remove(&panel);
+                let panel = panel.clone();
+                let _ = ctx.layout.modify(move |l| l.to_mut().dock(panel, DockTarget::Root(DockSide::Right)));
+            }
+        });
+    }
+}
@@ -62,7 +62,7 @@ pub fn background_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<()>>) ->
 /// This property applies a [`node::flood`] as [`background`].
 ///
 /// [`background`]: fn@background
-#[property(FILL, default(colors::BLACK.transparent()))]
+#[property(FILL, animatable, default(colors::BLACK.transparent()))]
 pub fn background_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
     background(child, node::flood(color))
 }
@@ -229,7 +229,7 @@ pub fn foreground_highlight(
 /// This property applies a [`node::flood`] as [`foreground`].
 ///
 /// [`foreground`]: fn@foreground
-#[property(FILL, default(colors::BLACK.transparent()))]
+#[property(FILL, animatable, default(colors::BLACK.transparent()))]
 pub fn foreground_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
     foreground(child, node::flood(color))
 }
@@ -33,13 +33,14 @@ pub fn filter(child: impl UiNode, filter: impl IntoVar<Filter>) -> impl UiNode {
 /// This property allows setting multiple filters at once, there is also a property for every
 /// filter for easier value updating.
 ///
-/// The filters are applied to everything rendered behind the widget.
+/// The filters are applied to everything rendered behind the widget, clipped to the widget [`corner_radius`].
 ///
 /// # Performance
 ///
 /// The performance for setting specific filter properties versus this one is the same.
 ///
 /// [`opacity`]: fn@opacity
+/// [`corner_radius`]: fn@zng_wgt::corner_radius
 #[property(CONTEXT, default(Filter::default()))]
 pub fn backdrop_filter(child: impl UiNode, filter: impl IntoVar<Filter>) -> impl UiNode {
     backdrop_filter_any(child, filter)
@@ -100,6 +101,8 @@ pub fn blur(child: impl UiNode, radius: impl IntoVar<Length>) -> impl UiNode {
 
 /// Blur the everything behind the widget.
 ///
+/// This can be used to create *frosted glass* panels, set a semi-transparent background together with this property.
+///
 /// This property is a shorthand way of setting [`backdrop_filter`] to [`Filter::new_blur`] using variable mapping.
 ///
 /// [`backdrop_filter`]: fn@backdrop_filter
@@ -314,7 +317,7 @@ pub fn backdrop_color_matrix(child: impl UiNode, matrix: impl IntoVar<ColorMatri
 ///
 /// [`filter`]: fn@filter
 /// [`Filter::new_opacity`]: zng_color::filter::Filter::new_opacity
-#[property(CONTEXT, default(1.0))]
+#[property(CONTEXT, animatable, default(1.0))]
 pub fn opacity(child: impl UiNode, alpha: impl IntoVar<Factor>) -> impl UiNode {
     opacity_impl(child, alpha, false)
 }
@@ -326,7 +329,7 @@ pub fn opacity(child: impl UiNode, alpha: impl IntoVar<Factor>) -> impl UiNode {
 ///
 /// [`child_filter`]: fn@child_filter
 /// [`Filter::new_opacity`]: zng_color::filter::Filter::new_opacity
-#[property(CHILD_CONTEXT, default(1.0))]
+#[property(CHILD_CONTEXT, animatable, default(1.0))]
 pub fn child_opacity(child: impl UiNode, alpha: impl IntoVar<Factor>) -> impl UiNode {
     opacity_impl(child, alpha, true)
 }
@@ -0,0 +1,330 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! GPU canvas widget for use with `zng-view` view-process.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use zng_app::{
+    render::ImageRendering,
+    view_process::{VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT},
+};
+use zng_ext_image::Img;
+use zng_ext_window::{WINDOWS, WINDOW_LOAD_EVENT};
+use zng_view_api::{
+    api_extension::{ApiExtensionId, ApiExtensionName},
+    image::ImageTextureId,
+};
+use zng_wgt::prelude::*;
+
+/// Presents a texture rendered by custom GPU code in the view-process.
+///
+/// The canvas content is rendered by a [`renderer`] registered in the view-process, the default view-process implementation
+/// provides the `zng_view::gpu_canvas` extension point for this. The canvas texture is sized to fill the widget,
+/// in physical pixels, and is composited without any CPU copies of the pixels.
+///
+/// Renders nothing if the view-process does not implement the [`renderer`] extension.
+///
+/// [`renderer`]: fn@renderer
+#[widget($crate::GpuCanvas)]
+pub struct GpuCanvas(WidgetBase);
+impl GpuCanvas {
+    fn widget_intrinsic(&mut self) {
+        self.widget_builder().push_build_action(|wgt| {
+            let renderer = wgt.capture_var_or_default(property_id!(renderer));
+            wgt.set_child(node(renderer));
+        });
+    }
+}
+
+/// Name of the GPU canvas renderer extension registered in the view-process.
+#[property(CONTEXT, capture, widget_impl(GpuCanvas))]
+pub fn renderer(name: impl IntoVar<Txt>) {}
+
+/// Request sent to the view-process extension.
+#[derive(serde::Serialize)]
+enum GpuCanvasRequest {
+    Open { id: u64, size: PxSize },
+    Resize { id: u64, size: PxSize },
+    Close { id: u64 },
+}
+
+/// Response from the view-process extension.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+enum GpuCanvasResponse {
+    Opened(ImageTextureId),
+    Done,
+    NotFound,
+}
+
+/// Request the [`CanvasState`] needs to send.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CanvasAction {
+    Open(PxSize),
+    Resize(PxSize),
+}
+
+/// Tracks the canvas open in the view-process, only one request is pending at a time.
+#[derive(Default)]
+struct CanvasState {
+    /// Texture and size of the open canvas.
+    opened: Option<(ImageTextureId, PxSize)>,
+    /// Size sent in the pending request.
+    pending: Option<PxSize>,
+    /// Latest layout size.
+    size: PxSize,
+    /// Open failed, does not retry until reset.
+    failed: bool,
+}
+impl CanvasState {
+    /// Set the layout size, returns the request to send if the size changed.
+    fn layout(&mut self, size: PxSize) -> Option<CanvasAction> {
+        self.size = size;
+        self.next()
+    }
+
+    /// Apply the response of the pending request, returns the next request if the size changed while waiting.
+    fn respond(&mut self, response: Option<GpuCanvasResponse>) -> Option<CanvasAction> {
+        let size = self.pending.take().unwrap_or(self.size);
+        match response {
+            Some(GpuCanvasResponse::Opened(texture_id)) => self.opened = Some((texture_id, size)),
+            Some(GpuCanvasResponse::Done) => {
+                if let Some((_, s)) = &mut self.opened {
+                    *s = size;
+                }
+            }
+            // view-process lost the canvas, reopen
+            Some(GpuCanvasResponse::NotFound) => self.opened = None,
+            None => {
+                self.opened = None;
+                self.failed = true;
+            }
+        }
+        self.next()
+    }
+
+    /// Forget the canvas, after respawn or renderer change.
+    fn reset(&mut self) {
+        self.opened = None;
+        self.pending = None;
+        self.failed = false;
+    }
+
+    fn next(&mut self) -> Option<CanvasAction> {
+        if self.pending.is_some() || self.failed || self.size.width <= Px(0) || self.size.height <= Px(0) {
+            return None;
+        }
+        let action = match self.opened {
+            Some((_, s)) if s == self.size => return None,
+            Some(_) => CanvasAction::Resize(self.size),
+            None => CanvasAction::Open(self.size),
+        };
+        self.pending = Some(self.size);
+        Some(action)
+    }
+}
+
+/// GPU canvas node.
+///
+/// The `renderer` is the name of the view-process extension that renders the canvas, see [`GpuCanvas!`] for more details.
+///
+/// [`GpuCanvas!`]: struct@GpuCanvas
+pub fn node(renderer: impl IntoVar<Txt>) -> impl UiNode {
+    let renderer = renderer.into_var();
+
+    let mut state = CanvasState::default();
+    let mut ext_id = None::<ApiExtensionId>;
+    let mut response = None::<(ResponseVar<Option<GpuCanvasResponse>>, VarHandle)>;
+    let mut img = None::<Img>;
+
+    /// Send the request in a blocking task, the UI thread does not wait the view-process.
+    fn request(ext_id: ApiExtensionId, request: GpuCanvasRequest) -> ResponseVar<Option<GpuCanvasResponse>> {
+        let window_id = WINDOW.id();
+        task::wait_respond(move || match WINDOWS.view_render_extension(window_id, ext_id, &request) {
+            Ok(r) => Some(r),
+            Err(e) => {
+                tracing::debug!("gpu canvas request failed, {e}");
+                None
+            }
+        })
+    }
+    fn send(ext_id: ApiExtensionId, action: CanvasAction) -> (ResponseVar<Option<GpuCanvasResponse>>, VarHandle) {
+        let id = WIDGET.id().get();
+        let r = request(
+            ext_id,
+            match action {
+                CanvasAction::Open(size) => GpuCanvasRequest::Open { id, size },
+                CanvasAction::Resize(size) => GpuCanvasRequest::Resize { id, size },
+            },
+        );
+        let handle = r.subscribe(UpdateOp::Update, WIDGET.id());
+        (r, handle)
+    }
+    fn resolve_ext_id(renderer: &impl Var<Txt>) -> Option<ApiExtensionId> {
+        match ApiExtensionName::new(renderer.get()) {
+            Ok(name) => VIEW_PROCESS.extension_id(name).ok().flatten(),
+            Err(e) => {
+                tracing::error!("invalid gpu canvas renderer name, {e}");
+                None
+            }
+        }
+    }
+
+    match_node_leaf(move |op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&renderer)
+                .sub_event(&VIEW_PROCESS_INITED_EVENT)
+                .sub_event(&WINDOW_LOAD_EVENT);
+        }
+        UiNodeOp::Deinit => {
+            if let Some(ext_id) = ext_id.filter(|_| state.opened.is_some() || state.pending.is_some()) {
+                // fire and forget
+                let _ = request(ext_id, GpuCanvasRequest::Close { id: WIDGET.id().get() });
+            }
+            state.reset();
+            response = None;
+            img = None;
+        }
+        UiNodeOp::Event { update } => {
+            if VIEW_PROCESS_INITED_EVENT.has(update) {
+                // textures are lost on respawn.
+                state.reset();
+                ext_id = None;
+                response = None;
+                img = None;
+                WIDGET.layout();
+            } else if let Some(args) = WINDOW_LOAD_EVENT.on(update) {
+                if args.window_id == WINDOW.id() && state.opened.is_none() {
+                    // retry, the renderer may not have been ready before
+                    state.reset();
+                    response = None;
+                    WIDGET.layout();
+                }
+            }
+        }
+        UiNodeOp::Update { .. } => {
+            if renderer.is_new() {
+                if let Some(ext_id) = ext_id.filter(|_| state.opened.is_some() || state.pending.is_some()) {
+                    let _ = request(ext_id, GpuCanvasRequest::Close { id: WIDGET.id().get() });
+                }
+                state.reset();
+                ext_id = None;
+                response = None;
+                img = None;
+                WIDGET.layout();
+            } else if let Some(rsp) = response.as_ref().and_then(|(r, _)| r.rsp()) {
+                response = None;
+                let prev = state.opened;
+                let next = state.respond(rsp);
+                if state.opened != prev {
+                    img = None;
+                    WIDGET.render();
+                }
+                if let (Some(action), Some(ext_id)) = (next, ext_id) {
+                    response = Some(send(ext_id, action));
+                }
+            }
+        }
+        UiNodeOp::Measure { desired_size, .. } => {
+            *desired_size = LAYOUT.constraints().fill_size();
+        }
+        UiNodeOp::Layout { final_size, .. } => {
+            *final_size = LAYOUT.constraints().fill_size();
+            if state.size == *final_size && (state.opened.is_some() || response.is_some()) {
+                return;
+            }
+            if state.size != *final_size {
+                WIDGET.render();
+            }
+
+            if ext_id.is_none() {
+                ext_id = resolve_ext_id(&renderer);
+            }
+            if let Some(id) = ext_id {
+                if let Some(action) = state.layout(*final_size) {
+                    response = Some(send(id, action));
+                }
+            } else {
+                state.size = *final_size;
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            if let Some((texture_id, size)) = state.opened {
+                if img.is_none() {
+                    if let Some(r) = frame.renderer() {
+                        img = Some(Img::from_external(r.clone(), texture_id, size));
+                    }
+                }
+                if let Some(img) = &img {
+                    let size = state.size;
+                    frame.push_image(PxRect::from_size(size), size, size, PxSize::zero(), img, ImageRendering::Auto);
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(w: i32, h: i32) -> PxSize {
+        PxSize::new(Px(w), Px(h))
+    }
+
+    #[test]
+    fn open_once() {
+        let mut s = CanvasState::default();
+        assert_eq!(None, s.layout(size(0, 10)));
+        assert_eq!(Some(CanvasAction::Open(size(10, 10))), s.layout(size(10, 10)));
+        // pending
+        assert_eq!(None, s.layout(size(10, 10)));
+        assert_eq!(None, s.respond(Some(GpuCanvasResponse::Opened(ImageTextureId::from_raw(1)))));
+        assert_eq!(Some((ImageTextureId::from_raw(1), size(10, 10))), s.opened);
+        // no change
+        assert_eq!(None, s.layout(size(10, 10)));
+    }
+
+    #[test]
+    fn resize_only_on_change() {
+        let mut s = CanvasState::default();
+        s.layout(size(10, 10));
+        s.respond(Some(GpuCanvasResponse::Opened(ImageTextureId::from_raw(1))));
+
+        assert_eq!(Some(CanvasAction::Resize(size(20, 10))), s.layout(size(20, 10)));
+        // size changes again while pending, sends after response
+        assert_eq!(None, s.layout(size(30, 10)));
+        assert_eq!(Some(CanvasAction::Resize(size(30, 10))), s.respond(Some(GpuCanvasResponse::Done)));
+        assert_eq!(Some((ImageTextureId::from_raw(1), size(20, 10))), s.opened);
+        assert_eq!(None, s.respond(Some(GpuCanvasResponse::Done)));
+        assert_eq!(Some((ImageTextureId::from_raw(1), size(30, 10))), s.opened);
+        assert_eq!(None, s.layout(size(30, 10)));
+    }
+
+    #[test]
+    fn reopen_not_found() {
+        let mut s = CanvasState::default();
+        s.layout(size(10, 10));
+        s.respond(Some(GpuCanvasResponse::Opened(ImageTextureId::from_raw(1))));
+
+        assert_eq!(Some(CanvasAction::Resize(size(20, 20))), s.layout(size(20, 20)));
+        assert_eq!(Some(CanvasAction::Open(size(20, 20))), s.respond(Some(GpuCanvasResponse::NotFound)));
+    }
+
+    #[test]
+    fn no_retry_after_error() {
+        let mut s = CanvasState::default();
+        assert_eq!(Some(CanvasAction::Open(size(10, 10))), s.layout(size(10, 10)));
+        assert_eq!(None, s.respond(None));
+        assert_eq!(None, s.layout(size(20, 20)));
+
+        s.reset();
+        assert_eq!(Some(CanvasAction::Open(size(20, 20))), s.layout(size(20, 20)));
+    }
+}
@@ -28,6 +28,7 @@ impl Grid {
                 w.capture_ui_node_list_or_empty(property_id!(Self::rows)),
                 w.capture_var_or_else(property_id!(Self::auto_grow_fn), WidgetFn::nil),
                 w.capture_var_or_else(property_id!(Self::auto_grow_mode), AutoGrowMode::rows),
+                w.capture_var_or_default(property_id!(Self::auto_flow)),
                 w.capture_var_or_default(property_id!(Self::spacing)),
             );
             w.set_child(child);
@@ -46,10 +47,11 @@ impl Grid {
 /// Cells can select their own column, row, column-span and row-span using the properties in the [`Cell!`] widget.
 /// Note that you don't need to use the cell widget, only the [`cell`] properties.
 ///
-/// If the column or row index is set to [`usize::MAX`] the widget is positioned using the
-/// logical index *i*, the column *i % columns* and the row *i / columns*.
+/// If the column or row index is set to [`usize::MAX`] the widget is positioned according to the [`auto_flow`], by default using
+/// the logical index *i*, the column *i % columns* and the row *i / columns*.
 ///
 /// [`Cell!`]: struct@Cell
+/// [`auto_flow`]: fn@auto_flow
 #[property(CHILD, capture, widget_impl(Grid))]
 pub fn cells(cells: impl UiNodeList) {}
 
@@ -112,6 +114,12 @@ pub fn auto_grow_fn(auto_grow: impl IntoVar<WidgetFn<AutoGrowFnArgs>>) {}
 #[property(CONTEXT, capture, default(AutoGrowMode::rows()), widget_impl(Grid))]
 pub fn auto_grow_mode(mode: impl IntoVar<AutoGrowMode>) {}
 
+/// Defines how cells without explicit column or row index are placed.
+///
+/// Is `GridFlow::Row` by default, see [`GridFlow`] for more details.
+#[property(CONTEXT, capture, default(GridFlow::Row), widget_impl(Grid))]
+pub fn auto_flow(flow: impl IntoVar<GridFlow>) {}
+
 /// Space in-between cells.
 #[property(LAYOUT, capture, default(GridSpacing::default()), widget_impl(Grid))]
 pub fn spacing(spacing: impl IntoVar<GridSpacing>) {}
@@ -126,6 +134,7 @@ pub fn node(
     rows: impl UiNodeList,
     auto_grow_fn: impl IntoVar<WidgetFn<AutoGrowFnArgs>>,
     auto_grow_mode: impl IntoVar<AutoGrowMode>,
+    auto_flow: impl IntoVar<GridFlow>,
     spacing: impl IntoVar<GridSpacing>,
 ) -> impl UiNode {
     let auto_columns: Vec<BoxedUiNode> = vec![];
@@ -138,6 +147,7 @@ pub fn node(
     let spacing = spacing.into_var();
     let auto_grow_fn = auto_grow_fn.into_var();
     let auto_grow_mode = auto_grow_mode.into_var();
+    let auto_flow = auto_flow.into_var();
 
     let mut grid = GridLayout::default();
     let mut is_measured = false;
@@ -145,9 +155,13 @@ pub fn node(
 
     match_node_list(children, move |c, op| match op {
         UiNodeOp::Init => {
-            WIDGET.sub_var(&auto_grow_fn).sub_var(&auto_grow_mode).sub_var_layout(&spacing);
+            WIDGET
+                .sub_var(&auto_grow_fn)
+                .sub_var(&auto_grow_mode)
+                .sub_var(&auto_flow)
+                .sub_var_layout(&spacing);
             c.init_all();
-            grid.update_entries(c.children(), auto_grow_mode.get(), &auto_grow_fn);
+            grid.update_entries(c.children(), auto_grow_mode.get(), auto_flow.get(), &auto_grow_fn);
         }
         UiNodeOp::Deinit => {
             c.deinit_all();
@@ -168,8 +182,11 @@ pub fn node(
                 }
                 any = true;
             }
+            if auto_flow.is_new() {
+                any = true;
+            }
             if any {
-                grid.update_entries(c.children(), auto_grow_mode.get(), &auto_grow_fn);
+                grid.update_entries(c.children(), auto_grow_mode.get(), auto_flow.get(), &auto_grow_fn);
                 WIDGET.layout();
             }
         }
@@ -229,7 +246,7 @@ pub fn node(
             cells.layout_each(
                 wl,
                 |i, cell, o, wl| {
-                    let cell_info = cell::CellInfo::get_wgt(cell).actual(i, grid.columns.len());
+                    let cell_info = grid.cells[i];
 
                     if cell_info.column >= grid.columns.len() || cell_info.row >= grid.rows.len() {
                         wl.collapse_child(cells_offset + i);
@@ -393,6 +410,130 @@ pub struct AutoGrowFnArgs {
     pub index: usize,
 }
 
+/// Grid auto-placement flow.
+///
+/// Defines how cells without explicit column or row index are placed, see [`auto_flow`] for more details.
+///
+/// [`auto_flow`]: fn@auto_flow
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GridFlow {
+    /// Cells are placed using the logical index *i*, the column *i % columns* and the row *i / columns*.
+    ///
+    /// This is the default value.
+    #[default]
+    Row,
+    /// Cells are placed using the logical index *i*, the column *i / rows* and the row *i % rows*.
+    ///
+    /// This flow is best used with [`AutoGrowMode::columns`].
+    Column,
+    /// Cells are placed in the first free position that fits the cell span, filling each row.
+    ///
+    /// Cells are placed in order, explicit cells are placed first, each auto cell can backfill holes left by
+    /// explicit cells or by previous larger cells. If only the column or row index is set the cell is placed
+    /// in the first free position of that column or row.
+    RowDense,
+    /// Cells are placed in the first free position that fits the cell span, filling each column.
+    ///
+    /// This is the [`RowDense`] flow transposed, it is best used with [`AutoGrowMode::columns`].
+    ///
+    /// [`RowDense`]: Self::RowDense
+    ColumnDense,
+}
+impl GridFlow {
+    /// If the flow fills each column.
+    pub fn is_column(self) -> bool {
+        matches!(self, Self::Column | Self::ColumnDense)
+    }
+
+    /// If the flow backfills holes.
+    pub fn is_dense(self) -> bool {
+        matches!(self, Self::RowDense | Self::ColumnDense)
+    }
+
+    /// Compute the actual column and row of the `cells`.
+    ///
+    /// The `columns_len` and `rows_len` are the grid columns and rows count, only the count in the
+    /// filled axis is used, cells can be placed after the end of the other axis.
+    pub fn place(self, cells: &mut [cell::CellInfo], columns_len: usize, rows_len: usize) {
+        fn transpose(c: &mut cell::CellInfo) {
+            mem::swap(&mut c.column, &mut c.row);
+            mem::swap(&mut c.column_span, &mut c.row_span);
+        }
+
+        let is_column = self.is_column();
+        let len = if is_column { rows_len } else { columns_len }.max(1);
+        if is_column {
+            cells.iter_mut().for_each(transpose);
+        }
+
+        if self.is_dense() {
+            place_dense(cells, len);
+        } else {
+            for (i, c) in cells.iter_mut().enumerate() {
+                *c = c.actual(i, len);
+            }
+        }
+
+        if is_column {
+            cells.iter_mut().for_each(transpose);
+        }
+    }
+}
+
+/// Row dense placement with `len` columns.
+fn place_dense(cells: &mut [cell::CellInfo], len: usize) {
+    // row-major occupancy, grows rows as needed.
+    let mut occupied: Vec<bool> = vec![];
+    let is_free = |occupied: &Vec<bool>, column: usize, row: usize, span: (usize, usize)| {
+        if column + span.0 > len {
+            return false;
+        }
+        for r in row..row + span.1 {
+            for c in column..column + span.0 {
+                if occupied.get(r * len + c).copied().unwrap_or(false) {
+                    return false;
+                }
+            }
+        }
+        true
+    };
+    let mark = |occupied: &mut Vec<bool>, column: usize, row: usize, span: (usize, usize)| {
+        let end = (row + span.1) * len;
+        if occupied.len() < end {
+            occupied.resize(end, false);
+        }
+        for r in row..row + span.1 {
+            for c in column..(column + span.0).min(len) {
+                occupied[r * len + c] = true;
+            }
+        }
+    };
+
+    for c in cells.iter_mut() {
+        if c.column != usize::MAX && c.row != usize::MAX {
+            c.column = c.column.min(len - 1);
+            mark(&mut occupied, c.column, c.row, (c.column_span.max(1), c.row_span.max(1)));
+        }
+    }
+    for c in cells.iter_mut() {
+        if c.column != usize::MAX && c.row != usize::MAX {
+            continue;
+        }
+        let span = (c.column_span.clamp(1, len), c.row_span.max(1));
+        if c.column != usize::MAX {
+            c.column = c.column.min(len - span.0);
+            c.row = (0..).find(|&r| is_free(&occupied, c.column, r, span)).unwrap();
+        } else if c.row != usize::MAX {
+            c.column = (0..=len - span.0).find(|&col| is_free(&occupied, col, c.row, span)).unwrap_or(0);
+        } else {
+            let i = (0..).find(|&i| is_free(&occupied, i % len, i / len, span)).unwrap();
+            c.column = i % len;
+            c.row = i / len;
+        }
+        mark(&mut occupied, c.column, c.row, span);
+    }
+}
+
 /// Grid auto-grow direction.
 ///
 /// The associated value is the maximum columns or rows that are allowed in the grid.
@@ -1075,6 +1216,8 @@ impl Default for RowLayout {
 struct GridLayout {
     columns: Vec<ColumnLayout>,
     rows: Vec<RowLayout>,
+    flow: GridFlow,
+    cells: Vec<cell::CellInfo>, // actual cell placement
 }
 impl GridLayout {
     fn is_collapse(&self) -> bool {
@@ -1087,23 +1230,46 @@ impl GridLayout {
     }
 
     /// add/remove info entries, auto-grow/shrink
-    fn update_entries(&mut self, children: &mut GridChildren, auto_mode: AutoGrowMode, auto_grow_fn: &impl Var<WidgetFn<AutoGrowFnArgs>>) {
+    fn update_entries(
+        &mut self,
+        children: &mut GridChildren,
+        auto_mode: AutoGrowMode,
+        auto_flow: GridFlow,
+        auto_grow_fn: &impl Var<WidgetFn<AutoGrowFnArgs>>,
+    ) {
+        self.flow = auto_flow;
+
         // max needed column or row in the auto_mode axis.
         let mut max_custom = 0;
         let mut max_auto_placed_i = 0;
-        children[2].for_each(|i, c| {
-            let info = c.with_context(WidgetUpdateMode::Ignore, cell::CellInfo::get).unwrap_or_default();
+        if auto_flow == GridFlow::Row {
+            children[2].for_each(|i, c| {
+                let info = c.with_context(WidgetUpdateMode::Ignore, cell::CellInfo::get).unwrap_or_default();
 
-            let n = match auto_mode {
-                AutoGrowMode::Rows(_) => info.row,
-                AutoGrowMode::Columns(_) => info.column,
-            };
-            if n == usize::MAX {
-                max_auto_placed_i = i;
-            } else {
+                let n = match auto_mode {
+                    AutoGrowMode::Rows(_) => info.row,
+                    AutoGrowMode::Columns(_) => info.column,
+                };
+                if n == usize::MAX {
+                    max_auto_placed_i = i;
+                } else {
+                    max_custom = max_custom.max(n);
+                }
+            });
+        } else {
+            self.cells.clear();
+            children[2].for_each(|_, c| {
+                self.cells.push(cell::CellInfo::get_wgt(c));
+            });
+            auto_flow.place(&mut self.cells, children[0].len(), children[1].len());
+            for info in &self.cells {
+                let n = match auto_mode {
+                    AutoGrowMode::Rows(_) => info.row,
+                    AutoGrowMode::Columns(_) => info.column,
+                };
                 max_custom = max_custom.max(n);
             }
-        });
+        }
 
         let mut imaginary_cols = 0;
         let mut imaginary_rows = 0;
@@ -1239,6 +1405,12 @@ impl GridLayout {
         let rows = children.next().unwrap();
         let cells = children.next().unwrap();
 
+        self.cells.clear();
+        cells.for_each(|_, c| {
+            self.cells.push(cell::CellInfo::get_wgt(c));
+        });
+        self.flow.place(&mut self.cells, self.columns.len(), self.rows.len());
+
         // layout exact columns&rows, mark others for next passes.
 
         let mut has_default = false;
@@ -1313,7 +1485,6 @@ impl GridLayout {
         // - For `leftover` columns&rows when the grid is not fill or exact size, to get the `1.lft()` length.
         // - For leftover x default a second pass later in case the constrained leftover causes a different default.
         let mut has_leftover_x_default = false;
-        let columns_len = self.columns.len();
         if has_default || (fill_x.is_none() && has_leftover_cols) || (fill_y.is_none() && has_leftover_rows) {
             let c = LAYOUT.constraints();
 
@@ -1322,7 +1493,10 @@ impl GridLayout {
                 if cell_info.column_span > 1 || cell_info.row_span > 1 {
                     return; // continue;
                 }
-                let cell_info = cell_info.actual(i, columns_len);
+                let cell_info = self.cells[i];
+                if cell_info.column >= self.columns.len() || cell_info.row >= self.rows.len() {
+                    return; // continue; collapsed
+                }
 
                 let col = &mut self.columns[cell_info.column];
                 let row = &mut self.rows[cell_info.row];
@@ -1645,7 +1819,10 @@ impl GridLayout {
                     return; // continue;
                 }
 
-                let cell_info = cell_info.actual(i, columns_len);
+                let cell_info = self.cells[i];
+                if cell_info.column >= self.columns.len() || cell_info.row >= self.rows.len() {
+                    return; // continue; collapsed
+                }
 
                 let col = &mut self.columns[cell_info.column];
                 let row = &mut self.rows[cell_info.row];
@@ -1704,3 +1881,83 @@ fn downcast_auto(cols_or_rows: &mut BoxedUiNodeList) -> &mut Vec<BoxedUiNode> {
 
 /// [[columns, auto_columns], [rows, auto_rows], cells]
 type GridChildren = Vec<BoxedUiNodeList>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTO: usize = usize::MAX;
+
+    fn cell(column: usize, row: usize, column_span: usize, row_span: usize) -> cell::CellInfo {
+        cell::CellInfo {
+            column,
+            column_span,
+            row,
+            row_span,
+        }
+    }
+
+    fn place(flow: GridFlow, cells: &mut [cell::CellInfo], columns: usize, rows: usize) -> Vec<(usize, usize)> {
+        flow.place(cells, columns, rows);
+        cells.iter().map(|c| (c.column, c.row)).collect()
+    }
+
+    #[test]
+    fn row_flow() {
+        let mut cells = [
+            cell(AUTO, AUTO, 1, 1),
+            cell(AUTO, AUTO, 1, 1),
+            cell(AUTO, AUTO, 1, 1),
+            cell(AUTO, AUTO, 1, 1),
+        ];
+        assert_eq!(place(GridFlow::Row, &mut cells, 3, 0), [(0, 0), (1, 0), (2, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn row_dense_backfill() {
+        let mut cells = [cell(AUTO, AUTO, 2, 1), cell(AUTO, AUTO, 2, 1), cell(AUTO, AUTO, 1, 1)];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(0, 0), (0, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn row_dense_explicit_first() {
+        let mut cells = [
+            cell(AUTO, AUTO, 1, 1),
+            cell(AUTO, AUTO, 1, 1),
+            cell(1, 0, 1, 1),
+            cell(AUTO, AUTO, 1, 1),
+        ];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(0, 0), (2, 0), (1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn row_dense_row_span() {
+        let mut cells = [cell(AUTO, AUTO, 1, 2), cell(AUTO, AUTO, 2, 1), cell(AUTO, AUTO, 2, 1)];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(0, 0), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn row_dense_fixed_column() {
+        let mut cells = [cell(2, 0, 1, 1), cell(2, AUTO, 1, 1), cell(AUTO, AUTO, 1, 1)];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(2, 0), (2, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn row_dense_fixed_row() {
+        let mut cells = [cell(0, 1, 1, 1), cell(AUTO, 1, 1, 1), cell(AUTO, AUTO, 1, 1)];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(0, 1), (1, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn row_dense_span_clamp() {
+        let mut cells = [cell(AUTO, AUTO, 1, 1), cell(AUTO, AUTO, 5, 1)];
+        assert_eq!(place(GridFlow::RowDense, &mut cells, 3, 0), [(0, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn column_dense_backfill() {
+        let mut cells = [cell(AUTO, AUTO, 1, 2), cell(AUTO, AUTO, 1, 2), cell(AUTO, AUTO, 1, 1)];
+        assert_eq!(place(GridFlow::ColumnDense, &mut cells, 0, 3), [(0, 0), (1, 0), (0, 2)]);
+        assert_eq!((cells[0].column_span, cells[0].row_span), (1, 2));
+    }
+}
@@ -99,6 +99,11 @@ context_var! {
     /// Widget function for the content shown when the image is still loading.
     pub static IMAGE_LOADING_FN_VAR: WidgetFn<ImgLoadingArgs> = WidgetFn::nil();
 
+    /// Preview rendered while the image is still loading.
+    ///
+    /// Is [`ImagePlaceholder::None`] by default.
+    pub static IMAGE_PLACEHOLDER_VAR: ImagePlaceholder = ImagePlaceholder::None;
+
     /// Custom image load and decode limits.
     ///
     /// Set to `None` to use the [`IMAGES.limits`].
@@ -111,6 +116,11 @@ context_var! {
     /// Is `None` by default.
     pub static IMAGE_DOWNSCALE_VAR: Option<ImageDownscale> = None;
 
+    /// If the image is downscaled during decode to the maximum size allowed by the layout.
+    ///
+    /// Is `false` by default.
+    pub static IMAGE_AUTO_DOWNSCALE_VAR: bool = false;
+
     /// The image layout mode.
     ///
     /// Is [`ImageFit::Contain`] by default.
@@ -153,6 +163,21 @@ context_var! {
     ///
     /// is `Size::zero` by default.
     pub static IMAGE_REPEAT_SPACING_VAR: Size = Size::zero();
+
+    /// If animated images are playing.
+    ///
+    /// Is `true` by default.
+    pub static IMAGE_PLAYING_VAR: bool = true;
+
+    /// If animated images restart after the last frame.
+    ///
+    /// Is `true` by default.
+    pub static IMAGE_LOOP_VAR: bool = true;
+
+    /// Animation frame index of animated images.
+    ///
+    /// Is `0` by default.
+    pub static IMAGE_FRAME_VAR: usize = 0;
 }
 
 /// Sets the [`ImageFit`] of all inner images.
@@ -355,6 +380,57 @@ pub fn img_downscale(child: impl UiNode, downscale: impl IntoVar<Option<ImageDow
     with_context_var(child, IMAGE_DOWNSCALE_VAR, downscale)
 }
 
+/// Decode images downscaled to the maximum size allowed by the widget layout constraints.
+///
+/// When enabled the image request is deferred to the first layout, the image is requested with an [`ImageDownscale`]
+/// that fills the maximum size allowed by the layout constraints, unbounded dimensions are not downscaled. If the layout
+/// grows the image is requested again at the larger size, it is not requested again when the layout shrinks. The size
+/// is rounded up to a multiple of 128 pixels so that similar sizes share the same cache entry.
+///
+/// This property is recommended for large galleries combined with [`IMAGES.memory_budget`], it decodes images at the resolution
+/// they are displayed, reducing memory use. Note that each downscale size is a different cache entry. If [`img_downscale`] is set
+/// it is used instead.
+///
+/// This property sets the [`IMAGE_AUTO_DOWNSCALE_VAR`].
+///
+/// [`IMAGES.memory_budget`]: zng_ext_image::IMAGES::memory_budget
+/// [`img_downscale`]: fn@img_downscale
+#[property(CONTEXT, default(IMAGE_AUTO_DOWNSCALE_VAR), widget_impl(Image))]
+pub fn img_auto_downscale(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, IMAGE_AUTO_DOWNSCALE_VAR, enabled)
+}
+
+/// Sets if animated images are playing in all inner images.
+///
+/// When set to `false` the animation pauses at the current frame, when set back to `true` it resumes from the same frame.
+///
+/// This property sets the [`IMAGE_PLAYING_VAR`].
+#[property(CONTEXT, default(IMAGE_PLAYING_VAR), widget_impl(Image))]
+pub fn img_playing(child: impl UiNode, playing: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, IMAGE_PLAYING_VAR, playing)
+}
+
+/// Sets if animated images restart after the last frame in all inner images.
+///
+/// When set to `false` the animation stops at the last frame.
+///
+/// This property sets the [`IMAGE_LOOP_VAR`].
+#[property(CONTEXT, default(IMAGE_LOOP_VAR), widget_impl(Image))]
+pub fn img_loop(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, IMAGE_LOOP_VAR, enabled)
+}
+
+/// Animation frame index of all inner animated images.
+///
+/// If the variable is read-write it is updated with the current frame index while the animation plays, setting it
+/// to a different index seeks the animation to that frame. Indexes out of bounds are clamped to the last frame.
+///
+/// This property sets the [`IMAGE_FRAME_VAR`].
+#[property(CONTEXT, default(IMAGE_FRAME_VAR), widget_impl(Image))]
+pub fn img_frame(child: impl UiNode, index: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, IMAGE_FRAME_VAR, index)
+}
+
 /// If the [`CONTEXT_IMAGE_VAR`] is an error.
 #[property(LAYOUT, widget_impl(Image))]
 pub fn is_error(child: impl UiNode, state: impl IntoVar<bool>) -> impl UiNode {
@@ -403,6 +479,37 @@ pub fn img_loading_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<ImgLoadi
     with_context_var(child, IMAGE_LOADING_FN_VAR, wgt_fn)
 }
 
+/// Sets a preview that is rendered while the image is still loading.
+///
+/// The placeholder is a [BlurHash](https://blurha.sh) or [ThumbHash](https://evanw.github.io/thumbhash) string, usually provided
+/// by the server with the image URI, it is decoded to a blurry preview that fills the image area until the image is fully loaded,
+/// partially loaded images are rendered over the placeholder.
+///
+/// The placeholder is rendered under the image and over the [`img_loading_fn`] content.
+///
+/// This property sets the [`IMAGE_PLACEHOLDER_VAR`].
+///
+/// # Examples
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_image::*;
+/// # fn main() {
+/// # let _ =
+/// Image! {
+///     source = "https://httpbin.org/image/jpeg";
+///     img_placeholder = "LEHV6nWB2yk8pyo0adR*.7kCMdnj";
+/// }
+/// # ; }
+/// ```
+///
+/// [`img_loading_fn`]: fn@img_loading_fn
+#[property(CONTEXT, default(IMAGE_PLACEHOLDER_VAR), widget_impl(Image))]
+pub fn img_placeholder(child: impl UiNode, placeholder: impl IntoVar<ImagePlaceholder>) -> impl UiNode {
+    with_context_var(child, IMAGE_PLACEHOLDER_VAR, placeholder)
+}
+
 /// Arguments for [`img_loading_fn`].
 ///
 /// [`img_loading_fn`]: fn@img_loading_fn
@@ -15,6 +15,9 @@ use zng_wgt::prelude::*;
 mod image_properties;
 pub use image_properties::*;
 
+mod placeholder;
+pub use placeholder::*;
+
 pub mod mask;
 
 use zng_wgt_access::{access_role, AccessRole};
@@ -23,8 +26,13 @@ pub mod node;
 
 /// Image presenter.
 ///
-/// This widget loads a still image from a variety of sources and presents it.
+/// This widget loads an image from a variety of sources and presents it.
+///
+/// Animated images play by default, see [`img_playing`], [`img_loop`] and [`img_frame`] for playback control.
 ///
+/// [`img_playing`]: fn@img_playing
+/// [`img_loop`]: fn@img_loop
+/// [`img_frame`]: fn@img_frame
 #[widget($crate::Image {
     ($source:expr) => {
         source = $source;
@@ -50,6 +58,8 @@ pub fn source(source: impl IntoVar<ImageSource>) {}
 
 fn on_build(wgt: &mut WidgetBuilding) {
     let node = node::image_presenter();
+    let node = node::image_animation(node);
+    let node = node::image_placeholder_presenter(node);
     let node = node::image_error_presenter(node);
     let node = node::image_loading_presenter(node);
     wgt.set_child(node);
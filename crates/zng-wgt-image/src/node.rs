@@ -1,14 +1,15 @@
 //! UI nodes used for building the image widget.
 
-use std::mem;
+use std::{mem, sync::Arc};
 
-use zng_ext_image::{ImageCacheMode, ImagePpi, ImageRenderArgs, IMAGES};
+use zng_ext_image::{ImageCacheMode, ImageDataFormat, ImageDownscale, ImagePpi, ImageRenderArgs, ImageVar, IMAGES};
 use zng_wgt_stack::stack_nodes_layout_by;
 
 use super::image_properties::{
-    ImageFit, ImgErrorArgs, ImgLoadingArgs, IMAGE_ALIGN_VAR, IMAGE_CACHE_VAR, IMAGE_CROP_VAR, IMAGE_DOWNSCALE_VAR, IMAGE_ERROR_FN_VAR,
-    IMAGE_FIT_VAR, IMAGE_LIMITS_VAR, IMAGE_LOADING_FN_VAR, IMAGE_OFFSET_VAR, IMAGE_RENDERING_VAR, IMAGE_SCALE_FACTOR_VAR,
-    IMAGE_SCALE_PPI_VAR, IMAGE_SCALE_VAR,
+    ImageFit, ImgErrorArgs, ImgLoadingArgs, IMAGE_ALIGN_VAR, IMAGE_AUTO_DOWNSCALE_VAR, IMAGE_CACHE_VAR, IMAGE_CROP_VAR,
+    IMAGE_DOWNSCALE_VAR, IMAGE_ERROR_FN_VAR, IMAGE_FIT_VAR, IMAGE_FRAME_VAR, IMAGE_LIMITS_VAR, IMAGE_LOADING_FN_VAR, IMAGE_LOOP_VAR,
+    IMAGE_OFFSET_VAR, IMAGE_PLACEHOLDER_VAR, IMAGE_PLAYING_VAR, IMAGE_RENDERING_VAR, IMAGE_SCALE_FACTOR_VAR, IMAGE_SCALE_PPI_VAR,
+    IMAGE_SCALE_VAR,
 };
 
 use super::*;
@@ -23,15 +24,44 @@ fn no_context_image() -> Img {
     Img::dummy(Some(Txt::from_static("no image source in context")))
 }
 
+static_id! {
+    // Layout constraints max size of the image presenter, set when auto-downscale is enabled.
+    static ref AUTO_DOWNSCALE_SIZE_ID: StateId<PxSize>;
+}
+
+fn is_auto_downscale() -> bool {
+    IMAGE_AUTO_DOWNSCALE_VAR.get() && IMAGE_DOWNSCALE_VAR.with(Option::is_none)
+}
+
+/// Max size allowed by the constraints, rounded up to a multiple of 128, or `Px::MAX` for unbounded dimensions.
+fn auto_downscale_size(constraints: PxConstraints2d) -> PxSize {
+    let round = |c: PxConstraints| match c.max() {
+        Some(m) => Px((m.0.max(1) + 127) / 128 * 128),
+        None => Px::MAX,
+    };
+    PxSize::new(round(constraints.x), round(constraints.y))
+}
+
+fn auto_downscale(size: PxSize) -> Option<ImageDownscale> {
+    match (size.width == Px::MAX, size.height == Px::MAX) {
+        (true, true) => None,
+        (false, false) => Some(ImageDownscale::Fill(size)),
+        // fit the bounded dimension
+        _ => Some(ImageDownscale::Fit(size)),
+    }
+}
+
 /// Requests an image from [`IMAGES`] and sets [`CONTEXT_IMAGE_VAR`].
 ///
-/// Caches the image if [`img_cache`] is `true` in the context.
+/// Caches the image if [`img_cache`] is `true` in the context. If [`img_auto_downscale`] is enabled the image
+/// is requested on layout, downscaled to the [`image_presenter`] layout constraints.
 ///
 /// The image is not rendered by this property, the [`image_presenter`] renders the image in [`CONTEXT_IMAGE_VAR`].
 ///
 /// In a widget this should be placed inside context properties and before event properties.
 ///
 /// [`img_cache`]: fn@crate::img_cache
+/// [`img_auto_downscale`]: fn@crate::img_auto_downscale
 /// [`IMAGES`]: zng_ext_image::IMAGES
 pub fn image_source(child: impl UiNode, source: impl IntoVar<ImageSource>) -> impl UiNode {
     let source = source.into_var();
@@ -39,10 +69,23 @@ pub fn image_source(child: impl UiNode, source: impl IntoVar<ImageSource>) -> im
     let child = with_context_var(child, CONTEXT_IMAGE_VAR, ctx_img.read_only());
     let mut img = var(Img::dummy(None)).read_only();
     let mut _ctx_binding = None;
+    let mut auto_size = None::<PxSize>;
 
     match_node(child, move |child, op| match op {
         UiNodeOp::Init => {
-            WIDGET.sub_var(&source).sub_var(&IMAGE_CACHE_VAR).sub_var(&IMAGE_DOWNSCALE_VAR);
+            WIDGET
+                .sub_var(&source)
+                .sub_var(&IMAGE_CACHE_VAR)
+                .sub_var(&IMAGE_DOWNSCALE_VAR)
+                .sub_var(&IMAGE_AUTO_DOWNSCALE_VAR);
+
+            if is_auto_downscale() {
+                // request on layout
+                auto_size = None;
+                ctx_img.set(Img::dummy(None));
+                WIDGET.layout();
+                return;
+            }
 
             let mode = if IMAGE_CACHE_VAR.get() {
                 ImageCacheMode::Cache
@@ -67,11 +110,18 @@ pub fn image_source(child: impl UiNode, source: impl IntoVar<ImageSource>) -> im
             ctx_img.set(no_context_image());
             img = var(no_context_image()).read_only();
             _ctx_binding = None;
+            auto_size = None;
         }
         UiNodeOp::Update { .. } => {
-            if source.is_new() || IMAGE_DOWNSCALE_VAR.is_new() {
+            if source.is_new() || IMAGE_DOWNSCALE_VAR.is_new() || IMAGE_AUTO_DOWNSCALE_VAR.is_new() {
                 // source update:
 
+                auto_size = None;
+                if is_auto_downscale() {
+                    WIDGET.layout();
+                    return;
+                }
+
                 let mut source = source.get();
 
                 if let ImageSource::Render(_, args) = &mut source {
@@ -104,7 +154,10 @@ pub fn image_source(child: impl UiNode, source: impl IntoVar<ImageSource>) -> im
 
                         let source = source.get();
                         let limits = IMAGE_LIMITS_VAR.get();
-                        let downscale = IMAGE_DOWNSCALE_VAR.get();
+                        let downscale = match auto_size {
+                            Some(s) => auto_downscale(s),
+                            None => IMAGE_DOWNSCALE_VAR.get(),
+                        };
                         IMAGES.image(source, ImageCacheMode::Cache, limits, downscale, None)
                     };
 
@@ -113,6 +166,185 @@ pub fn image_source(child: impl UiNode, source: impl IntoVar<ImageSource>) -> im
                 }
             }
         }
+        UiNodeOp::Layout { wl, final_size } => {
+            if !is_auto_downscale() {
+                return;
+            }
+
+            *final_size = child.layout(wl);
+
+            let size = WIDGET
+                .get_state(*AUTO_DOWNSCALE_SIZE_ID)
+                .unwrap_or_else(|| auto_downscale_size(LAYOUT.constraints()));
+            if auto_size.map(|s| size.width > s.width || size.height > s.height).unwrap_or(true) {
+                // first layout or layout grew
+                auto_size = Some(size);
+
+                let mut source = source.get();
+                if let ImageSource::Render(_, args) = &mut source {
+                    *args = Some(ImageRenderArgs { parent: Some(WINDOW.id()) });
+                }
+                let mode = if IMAGE_CACHE_VAR.get() {
+                    ImageCacheMode::Cache
+                } else {
+                    ImageCacheMode::Ignore
+                };
+                img = IMAGES.image(source, mode, IMAGE_LIMITS_VAR.get(), auto_downscale(size), None);
+
+                ctx_img.set_from(&img);
+                _ctx_binding = Some(img.bind(&ctx_img));
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Presents the animation frames of the [`CONTEXT_IMAGE_VAR`].
+///
+/// If the image is animated the [`CONTEXT_IMAGE_VAR`] in the `child` context is the current frame. The playback is
+/// controlled by [`IMAGE_PLAYING_VAR`], [`IMAGE_LOOP_VAR`] and [`IMAGE_FRAME_VAR`]. Frames are decoded on demand,
+/// only the current and next frame are retained.
+///
+/// Frame delays less than 10ms are played as 100ms, the same as web browsers do.
+///
+/// The image widget adds this node around the [`image_presenter`] node.
+pub fn image_animation(child: impl UiNode) -> impl UiNode {
+    let frame_img = var(no_context_image());
+    let child = with_context_var(child, CONTEXT_IMAGE_VAR, frame_img.read_only());
+
+    struct Playback {
+        src: Img,
+        // frame index presented.
+        shown: usize,
+        // next frame, presented when loaded and the timer elapses.
+        pending: Option<(usize, ImageVar)>,
+        timer: Option<DeadlineVar>,
+    }
+    impl Playback {
+        fn request(&mut self, index: usize) {
+            let img = IMAGES.frame(&self.src, index);
+            img.subscribe(UpdateOp::Update, WIDGET.id()).perm();
+            self.pending = Some((index, img));
+        }
+
+        fn schedule_next(&mut self) {
+            let count = self.src.frame_count();
+            let next = if self.shown + 1 < count {
+                self.shown + 1
+            } else if IMAGE_LOOP_VAR.get() {
+                0
+            } else {
+                return;
+            };
+
+            let mut delay = self.src.frame_delays().get(self.shown).copied().unwrap_or_default();
+            if delay < 10.ms() {
+                delay = 100.ms();
+            }
+            let timer = TIMERS.deadline(delay);
+            timer.subscribe(UpdateOp::Update, WIDGET.id()).perm();
+            self.timer = Some(timer);
+
+            self.request(next);
+        }
+
+        fn seek(&mut self, index: usize) {
+            self.timer = None;
+            self.pending = None;
+            let index = index.min(self.src.frame_count().saturating_sub(1));
+            if index == self.shown {
+                if IMAGE_PLAYING_VAR.get() {
+                    self.schedule_next();
+                }
+            } else {
+                self.request(index);
+            }
+        }
+
+        /// Present the pending frame if it is ready.
+        fn advance(&mut self, frame_img: &ArcVar<Img>) {
+            if let Some((index, img)) = &self.pending {
+                let is_ready = !img.with(Img::is_loading) && self.timer.as_ref().map(|t| t.get().has_elapsed()).unwrap_or(true);
+                if is_ready {
+                    let index = *index;
+                    let img = img.get();
+                    self.pending = None;
+                    self.timer = None;
+                    if img.is_error() {
+                        // stop playback, the source image is still shown.
+                        return;
+                    }
+
+                    frame_img.set(img);
+                    self.shown = index;
+                    let _ = IMAGE_FRAME_VAR.set(index);
+
+                    if IMAGE_PLAYING_VAR.get() {
+                        self.schedule_next();
+                    }
+                }
+            }
+        }
+    }
+    let mut playback = None::<Playback>;
+
+    let reset = move |frame_img: &ArcVar<Img>, playback: &mut Option<Playback>| {
+        let src = CONTEXT_IMAGE_VAR.get();
+        frame_img.set(src.clone());
+        *playback = None;
+        if src.is_animated() {
+            let mut p = Playback {
+                src,
+                shown: 0,
+                pending: None,
+                timer: None,
+            };
+            p.seek(IMAGE_FRAME_VAR.get());
+            p.advance(frame_img);
+            *playback = Some(p);
+        }
+    };
+
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&CONTEXT_IMAGE_VAR)
+                .sub_var(&IMAGE_PLAYING_VAR)
+                .sub_var(&IMAGE_LOOP_VAR)
+                .sub_var(&IMAGE_FRAME_VAR);
+            reset(&frame_img, &mut playback);
+        }
+        UiNodeOp::Deinit => {
+            playback = None;
+            frame_img.set(no_context_image());
+        }
+        UiNodeOp::Update { .. } => {
+            if CONTEXT_IMAGE_VAR.is_new() {
+                reset(&frame_img, &mut playback);
+            } else if let Some(p) = &mut playback {
+                if let Some(index) = IMAGE_FRAME_VAR.get_new() {
+                    if index != p.shown && p.pending.as_ref().map(|(i, _)| *i != index).unwrap_or(true) {
+                        p.seek(index);
+                    }
+                }
+                if let Some(playing) = IMAGE_PLAYING_VAR.get_new() {
+                    if playing {
+                        if p.pending.is_none() {
+                            p.schedule_next();
+                        }
+                    } else if p.timer.is_some() {
+                        // pause, `timer` is only set for playback requests.
+                        p.timer = None;
+                        p.pending = None;
+                    }
+                }
+                if IMAGE_LOOP_VAR.is_new() && IMAGE_PLAYING_VAR.get() && p.pending.is_none() {
+                    // resume if stopped at the last frame.
+                    p.schedule_next();
+                }
+                p.advance(&frame_img);
+            }
+        }
         _ => {}
     })
 }
@@ -180,6 +412,87 @@ pub fn image_loading_presenter(child: impl UiNode) -> impl UiNode {
     })
 }
 
+/// Renders the [`IMAGE_PLACEHOLDER_VAR`] under the `child` while the [`CONTEXT_IMAGE_VAR`] is loading.
+///
+/// The placeholder fills the `child` size, if the image size is not known yet the placeholder aspect ratio
+/// is used to size the node.
+///
+/// The image widget adds this node around the [`image_animation`] node.
+pub fn image_placeholder_presenter(child: impl UiNode) -> impl UiNode {
+    let mut placeholder = None::<(ImageVar, VarHandle)>;
+    let mut size = PxSize::zero();
+
+    let refresh = |placeholder: &mut Option<(ImageVar, VarHandle)>, reload: bool| {
+        if !CONTEXT_IMAGE_VAR.with(Img::is_loading) {
+            *placeholder = None;
+        } else if placeholder.is_none() || reload {
+            *placeholder = IMAGE_PLACEHOLDER_VAR.with(ImagePlaceholder::decode).map(|(size, pixels)| {
+                let img = IMAGES.image(
+                    ImageSource::from_data(Arc::new(pixels), ImageDataFormat::Bgra8 { size, ppi: None }),
+                    ImageCacheMode::Ignore,
+                    None,
+                    None,
+                    None,
+                );
+                let handle = img.subscribe(UpdateOp::Layout, WIDGET.id());
+                (img, handle)
+            });
+        }
+    };
+    let fill_size = |placeholder: &Option<(ImageVar, VarHandle)>, child_size: PxSize| {
+        if let Some((img, _)) = placeholder {
+            let placeholder_size = img.with(Img::size);
+            if !placeholder_size.is_empty() && CONTEXT_IMAGE_VAR.with(Img::size).is_empty() {
+                let c = LAYOUT.constraints();
+                let min_size = c.clamp_size(placeholder_size);
+                return c.fill_size_or(c.with_min_size(min_size).fill_ratio(placeholder_size));
+            }
+        }
+        child_size
+    };
+
+    match_node(child, move |child, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&IMAGE_PLACEHOLDER_VAR).sub_var(&CONTEXT_IMAGE_VAR);
+            refresh(&mut placeholder, true);
+        }
+        UiNodeOp::Deinit => {
+            placeholder = None;
+        }
+        UiNodeOp::Update { .. } => {
+            let reload = IMAGE_PLACEHOLDER_VAR.is_new();
+            if reload || CONTEXT_IMAGE_VAR.is_new() {
+                refresh(&mut placeholder, reload);
+                WIDGET.layout().render();
+            }
+        }
+        UiNodeOp::Measure { wm, desired_size } => {
+            let child_size = child.measure(wm);
+            *desired_size = fill_size(&placeholder, child_size);
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            let child_size = child.layout(wl);
+            let s = fill_size(&placeholder, child_size);
+            if size != s {
+                size = s;
+                WIDGET.render();
+            }
+            *final_size = s;
+        }
+        UiNodeOp::Render { frame } => {
+            if let Some((img, _)) = &placeholder {
+                img.with(|img| {
+                    if img.is_loaded() && !size.is_empty() {
+                        frame.push_image(PxRect::from_size(size), size, size, PxSize::zero(), img, IMAGE_RENDERING_VAR.get());
+                    }
+                });
+            }
+            child.render(frame);
+        }
+        _ => {}
+    })
+}
+
 /// Renders the [`CONTEXT_IMAGE_VAR`] if set.
 ///
 /// This is the inner-most node of an image widget, it is fully configured by context variables:
@@ -193,6 +506,11 @@ pub fn image_loading_presenter(child: impl UiNode) -> impl UiNode {
 /// * [`IMAGE_ALIGN_VAR`]: Defines the image alignment in the presenter final size.
 /// * [`IMAGE_RENDERING_VAR`]: Defines the image resize algorithm used in the GPU.
 /// * [`IMAGE_OFFSET_VAR`]: Defines an offset applied to the image after all measure and arrange.
+///
+/// Vector images are rasterized again at the exact render size every time the size changes, the image
+/// is laid out using the intrinsic size, only the rendered pixels change. See [`Img::is_vector`] for more details.
+///
+/// [`Img::is_vector`]: zng_ext_image::Img::is_vector
 pub fn image_presenter() -> impl UiNode {
     let mut img_size = PxSize::zero();
     let mut render_clip = PxRect::zero();
@@ -202,6 +520,15 @@ pub fn image_presenter() -> impl UiNode {
     let mut render_offset = PxVector::zero();
     let spatial_id = SpatialFrameId::new_unique();
 
+    struct VectorRaster {
+        size: PxSize,
+        img: ImageVar,
+        _handle: VarHandle,
+    }
+    // requested raster and the previous loaded raster, shown while the new raster loads.
+    let mut raster = None::<VectorRaster>;
+    let mut prev_raster = None::<ImageVar>;
+
     match_node_leaf(move |op| match op {
         UiNodeOp::Init => {
             WIDGET
@@ -219,13 +546,20 @@ pub fn image_presenter() -> impl UiNode {
 
             img_size = CONTEXT_IMAGE_VAR.with(Img::size);
         }
+        UiNodeOp::Deinit => {
+            raster = None;
+            prev_raster = None;
+        }
         UiNodeOp::Update { .. } => {
             if let Some(img) = CONTEXT_IMAGE_VAR.get_new() {
+                raster = None;
+                prev_raster = None;
+
                 let ig_size = img.size();
-                if img_size != ig_size {
+                if img_size != ig_size || img.is_vector() {
                     img_size = ig_size;
                     WIDGET.layout();
-                } else if img.is_loaded() {
+                } else if img.is_loaded() || img.is_partially_loaded() {
                     WIDGET.render();
                 }
             }
@@ -264,6 +598,10 @@ pub fn image_presenter() -> impl UiNode {
 
             let metrics = LAYOUT.metrics();
 
+            if IMAGE_AUTO_DOWNSCALE_VAR.get() {
+                WIDGET.set_state(*AUTO_DOWNSCALE_SIZE_ID, auto_downscale_size(metrics.constraints()));
+            }
+
             let mut scale = IMAGE_SCALE_VAR.get();
             if IMAGE_SCALE_PPI_VAR.get() {
                 let sppi = metrics.screen_ppi();
@@ -384,6 +722,31 @@ pub fn image_presenter() -> impl UiNode {
                 });
             }
 
+            // Part 5 - Vector Raster
+            let raster_size = r_tile_size;
+            if raster_size != img_size
+                && !raster_size.is_empty()
+                && raster.as_ref().map(|r| r.size != raster_size).unwrap_or(true)
+                && CONTEXT_IMAGE_VAR.with(Img::is_vector)
+            {
+                let img = CONTEXT_IMAGE_VAR.with(|img| IMAGES.raster(img, raster_size));
+                let handle = img.subscribe(UpdateOp::Render, WIDGET.id());
+                if let Some(r) = raster.take() {
+                    if r.img.with(Img::is_loaded) {
+                        prev_raster = Some(r.img);
+                    }
+                }
+                raster = Some(VectorRaster {
+                    size: raster_size,
+                    img,
+                    _handle: handle,
+                });
+            } else if raster_size == img_size && raster.is_some() {
+                raster = None;
+                prev_raster = None;
+                WIDGET.render();
+            }
+
             if render_clip != r_clip
                 || render_img_size != r_img_size
                 || render_offset != r_offset
@@ -401,8 +764,20 @@ pub fn image_presenter() -> impl UiNode {
             *final_size = wgt_size;
         }
         UiNodeOp::Render { frame } => {
+            let mut raster_img = None;
+            if let Some(r) = &raster {
+                let img = r.img.get();
+                if img.is_loaded() {
+                    prev_raster = None;
+                    raster_img = Some(img);
+                } else if let Some(p) = &prev_raster {
+                    raster_img = Some(p.get());
+                }
+            }
+
             CONTEXT_IMAGE_VAR.with(|img| {
-                if img.is_loaded() && !img_size.is_empty() && !render_clip.is_empty() {
+                let img = raster_img.as_ref().unwrap_or(img);
+                if (img.is_loaded() || img.is_partially_loaded()) && !img_size.is_empty() && !render_clip.is_empty() {
                     if render_offset != PxVector::zero() {
                         let transform = PxTransform::from(render_offset);
                         frame.push_reference_frame(spatial_id.into(), FrameValue::Value(transform), true, false, |frame| {
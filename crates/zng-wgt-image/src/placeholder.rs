@@ -0,0 +1,398 @@
+use std::{f32::consts::PI, fmt};
+
+use base64::Engine as _;
+use zng_wgt::prelude::*;
+
+/// Compact image preview shown while the actual image loads.
+///
+/// Placeholders are tiny strings computed from the image by the server, usually sent with the image URI in
+/// gallery or feed listings, they are decoded to a blurry low resolution preview of the image that is rendered
+/// until the image is loaded.
+///
+/// Strings convert to [`BlurHash`].
+///
+/// See [`img_placeholder`] for more details.
+///
+/// [`BlurHash`]: Self::BlurHash
+/// [`img_placeholder`]: fn@crate::img_placeholder
+#[derive(Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ImagePlaceholder {
+    /// No placeholder.
+    #[default]
+    None,
+    /// A [BlurHash](https://blurha.sh) string.
+    ///
+    /// The hash does not encode the image aspect ratio, it is decoded as a square preview that is stretched to the image size.
+    BlurHash(Txt),
+    /// A [ThumbHash](https://evanw.github.io/thumbhash) encoded in base64.
+    ///
+    /// The hash encodes the approximate aspect ratio and alpha.
+    ThumbHash(Txt),
+}
+impl fmt::Debug for ImagePlaceholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "ImagePlaceholder::")?;
+        }
+        match self {
+            Self::None => write!(f, "None"),
+            Self::BlurHash(h) => f.debug_tuple("BlurHash").field(h).finish(),
+            Self::ThumbHash(h) => f.debug_tuple("ThumbHash").field(h).finish(),
+        }
+    }
+}
+impl ImagePlaceholder {
+    /// If is [`None`].
+    ///
+    /// [`None`]: Self::None
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Decode the preview pixels.
+    ///
+    /// Returns the preview size and the pre-multiplied BGRA8 pixels, or `None` if there is no placeholder or
+    /// the hash is not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zng_wgt_image::*;
+    /// # use zng_wgt::prelude::*;
+    /// let (size, pixels) = ImagePlaceholder::from("LEHV6nWB2yk8pyo0adR*.7kCMdnj").decode().unwrap();
+    ///
+    /// assert_eq!(size, PxSize::splat(Px(32)));
+    /// assert_eq!(pixels.len(), 32 * 32 * 4);
+    /// assert!(ImagePlaceholder::from("invalid").decode().is_none());
+    ///
+    /// let (size, pixels) = ImagePlaceholder::ThumbHash("1QcSHQRnh493V4dIh4eXh1h4kJUI".into()).decode().unwrap();
+    /// assert_eq!(pixels.len(), size.width.0 as usize * size.height.0 as usize * 4);
+    /// ```
+    pub fn decode(&self) -> Option<(PxSize, Vec<u8>)> {
+        match self {
+            Self::None => None,
+            Self::BlurHash(h) => blur_hash_decode(h, 32, 32).map(|p| (PxSize::splat(Px(32)), p)),
+            Self::ThumbHash(h) => {
+                let hash = base64::engine::general_purpose::STANDARD_NO_PAD
+                    .decode(h.trim_end_matches('='))
+                    .ok()?;
+                thumb_hash_decode(&hash)
+            }
+        }
+    }
+}
+impl_from_and_into_var! {
+    fn from(blur_hash: &'static str) -> ImagePlaceholder {
+        ImagePlaceholder::BlurHash(Txt::from_static(blur_hash))
+    }
+    fn from(blur_hash: Txt) -> ImagePlaceholder {
+        ImagePlaceholder::BlurHash(blur_hash)
+    }
+    fn from(blur_hash: String) -> ImagePlaceholder {
+        ImagePlaceholder::BlurHash(blur_hash.into())
+    }
+}
+
+fn srgb_to_linear(v: u8) -> f32 {
+    let v = v as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let v = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (v * 255.0 + 0.5) as u8
+}
+
+fn blur_hash_decode(hash: &str, width: usize, height: usize) -> Option<Vec<u8>> {
+    const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+    let base83 = |s: &[u8]| {
+        s.iter().try_fold(0u32, |v, c| {
+            let d = CHARS.iter().position(|b| b == c)? as u32;
+            Some(v * 83 + d)
+        })
+    };
+
+    let hash = hash.as_bytes();
+    if hash.len() < 6 {
+        return None;
+    }
+    let size_flag = base83(&hash[..1])? as usize;
+    let (nx, ny) = (size_flag % 9 + 1, size_flag / 9 + 1);
+    if hash.len() != 4 + 2 * nx * ny {
+        return None;
+    }
+    let max_value = (base83(&hash[1..2])? + 1) as f32 / 166.0;
+
+    let mut colors = Vec::with_capacity(nx * ny);
+    let dc = base83(&hash[2..6])?;
+    colors.push([
+        srgb_to_linear((dc >> 16) as u8),
+        srgb_to_linear((dc >> 8) as u8),
+        srgb_to_linear(dc as u8),
+    ]);
+    for i in 1..nx * ny {
+        let ac = base83(&hash[4 + i * 2..6 + i * 2])?;
+        let q = |v: u32| {
+            let v = (v as f32 - 9.0) / 9.0;
+            v.signum() * v.powi(2) * max_value
+        };
+        colors.push([q(ac / (19 * 19)), q((ac / 19) % 19), q(ac % 19)]);
+    }
+
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let mut c = [0.0; 3];
+            for j in 0..ny {
+                let fy = (PI * y as f32 * j as f32 / height as f32).cos();
+                for i in 0..nx {
+                    let f = (PI * x as f32 * i as f32 / width as f32).cos() * fy;
+                    let color = colors[i + j * nx];
+                    for (c, v) in c.iter_mut().zip(color) {
+                        *c += v * f;
+                    }
+                }
+            }
+            pixels.extend_from_slice(&[linear_to_srgb(c[2]), linear_to_srgb(c[1]), linear_to_srgb(c[0]), 255]);
+        }
+    }
+    Some(pixels)
+}
+
+fn thumb_hash_decode(hash: &[u8]) -> Option<(PxSize, Vec<u8>)> {
+    if hash.len() < 5 {
+        return None;
+    }
+    let header24 = hash[0] as u32 | (hash[1] as u32) << 8 | (hash[2] as u32) << 16;
+    let header16 = hash[3] as u32 | (hash[4] as u32) << 8;
+    let l_dc = (header24 & 63) as f32 / 63.0;
+    let p_dc = ((header24 >> 6) & 63) as f32 / 31.5 - 1.0;
+    let q_dc = ((header24 >> 12) & 63) as f32 / 31.5 - 1.0;
+    let l_scale = ((header24 >> 18) & 31) as f32 / 31.0;
+    let has_alpha = (header24 >> 23) != 0;
+    let p_scale = ((header16 >> 3) & 63) as f32 / 63.0;
+    let q_scale = ((header16 >> 9) & 63) as f32 / 63.0;
+    let is_landscape = (header16 >> 15) != 0;
+    let lx_raw = if is_landscape {
+        if has_alpha {
+            5
+        } else {
+            7
+        }
+    } else {
+        header16 & 7
+    } as usize;
+    let ly_raw = if is_landscape {
+        header16 & 7
+    } else if has_alpha {
+        5
+    } else {
+        7
+    } as usize;
+    let (lx, ly) = (lx_raw.max(3), ly_raw.max(3));
+    if lx_raw == 0 || ly_raw == 0 {
+        return None;
+    }
+    let ac_start = if has_alpha { 6 } else { 5 };
+    if hash.len() < ac_start {
+        return None;
+    }
+    let (a_dc, a_scale) = if has_alpha {
+        ((hash[5] & 15) as f32 / 15.0, (hash[5] >> 4) as f32 / 15.0)
+    } else {
+        (1.0, 0.0)
+    };
+
+    // read the varying factors, boost saturation by 1.25x to compensate for quantization.
+    let mut ac_index = 0;
+    let mut decode_channel = |nx: usize, ny: usize, scale: f32| {
+        let mut ac = vec![];
+        for cy in 0..ny {
+            let mut cx = if cy > 0 { 0 } else { 1 };
+            while cx * ny < nx * (ny - cy) {
+                let b = *hash.get(ac_start + (ac_index >> 1))?;
+                ac.push((((b >> ((ac_index & 1) << 2)) & 15) as f32 / 7.5 - 1.0) * scale);
+                ac_index += 1;
+                cx += 1;
+            }
+        }
+        Some(ac)
+    };
+    let l_ac = decode_channel(lx, ly, l_scale)?;
+    let p_ac = decode_channel(3, 3, p_scale * 1.25)?;
+    let q_ac = decode_channel(3, 3, q_scale * 1.25)?;
+    let a_ac = if has_alpha { decode_channel(5, 5, a_scale)? } else { vec![] };
+
+    let ratio = lx_raw as f32 / ly_raw as f32;
+    let (w, h) = if ratio > 1.0 {
+        (32, (32.0 / ratio).round() as usize)
+    } else {
+        ((32.0 * ratio).round() as usize, 32)
+    };
+    let (w, h) = (w.max(1), h.max(1));
+
+    let mut pixels = Vec::with_capacity(w * h * 4);
+    let mut fx = vec![0.0; lx.max(if has_alpha { 5 } else { 3 })];
+    let mut fy = vec![0.0; ly.max(if has_alpha { 5 } else { 3 })];
+    for y in 0..h {
+        for (cy, f) in fy.iter_mut().enumerate() {
+            *f = (PI / h as f32 * (y as f32 + 0.5) * cy as f32).cos();
+        }
+        for x in 0..w {
+            for (cx, f) in fx.iter_mut().enumerate() {
+                *f = (PI / w as f32 * (x as f32 + 0.5) * cx as f32).cos();
+            }
+
+            let (mut l, mut p, mut q, mut a) = (l_dc, p_dc, q_dc, a_dc);
+
+            let mut j = 0;
+            for (cy, fy) in fy[..ly].iter().enumerate() {
+                let fy2 = fy * 2.0;
+                let mut cx = if cy > 0 { 0 } else { 1 };
+                while cx * ly < lx * (ly - cy) {
+                    l += l_ac[j] * fx[cx] * fy2;
+                    j += 1;
+                    cx += 1;
+                }
+            }
+
+            let mut j = 0;
+            for (cy, fy) in fy[..3].iter().enumerate() {
+                let fy2 = fy * 2.0;
+                for fx in &fx[(cy == 0) as usize..3 - cy] {
+                    let f = fx * fy2;
+                    p += p_ac[j] * f;
+                    q += q_ac[j] * f;
+                    j += 1;
+                }
+            }
+
+            if has_alpha {
+                let mut j = 0;
+                for (cy, fy) in fy[..5].iter().enumerate() {
+                    let fy2 = fy * 2.0;
+                    for fx in &fx[(cy == 0) as usize..5 - cy] {
+                        a += a_ac[j] * fx * fy2;
+                        j += 1;
+                    }
+                }
+            }
+
+            let b = l - 2.0 / 3.0 * p;
+            let r = (3.0 * l - b + q) / 2.0;
+            let g = r - q;
+            let a = a.clamp(0.0, 1.0);
+            let c = |v: f32| (v.clamp(0.0, 1.0) * a * 255.0) as u8;
+            pixels.extend_from_slice(&[c(b), c(g), c(r), (a * 255.0) as u8]);
+        }
+    }
+
+    Some((PxSize::new(Px(w as i32), Px(h as i32)), pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assert the decoded BGRA `pixels` match the reference decoder output, within rounding differences.
+    fn assert_pixels(placeholder: ImagePlaceholder, expected_size: (i32, i32), expected: &[((i32, i32), [u8; 4])]) {
+        let (size, pixels) = placeholder.decode().unwrap();
+        assert_eq!(PxSize::new(Px(expected_size.0), Px(expected_size.1)), size);
+        assert_eq!((size.width.0 * size.height.0 * 4) as usize, pixels.len());
+
+        for ((x, y), e) in expected {
+            let i = ((y * size.width.0 + x) * 4) as usize;
+            let p = &pixels[i..i + 4];
+            assert!(
+                p.iter().zip(e).all(|(a, b)| a.abs_diff(*b) <= 1),
+                "pixel ({x}, {y}) is {p:?}, expected {e:?}"
+            );
+        }
+    }
+
+    // expected pixels are from a port of the reference TypeScript decoders, converted to BGRA.
+
+    #[test]
+    fn blur_hash_reference() {
+        assert_pixels(
+            ImagePlaceholder::from("LEHV6nWB2yk8pyo0adR*.7kCMdnj"),
+            (32, 32),
+            &[
+                ((0, 0), [177, 164, 135, 255]),
+                ((16, 16), [108, 125, 158, 255]),
+                ((31, 31), [147, 142, 133, 255]),
+                ((31, 0), [181, 166, 137, 255]),
+            ],
+        );
+        assert_pixels(
+            ImagePlaceholder::from("LGF5]+Yk^6#M@-5c,1J5@[or[Q6."),
+            (32, 32),
+            &[
+                ((0, 0), [163, 118, 176, 255]),
+                ((16, 16), [130, 126, 107, 255]),
+                ((31, 31), [98, 97, 143, 255]),
+                ((31, 0), [129, 84, 225, 255]),
+            ],
+        );
+    }
+
+    #[test]
+    fn blur_hash_invalid() {
+        // too short.
+        assert!(ImagePlaceholder::from("LEHV6").decode().is_none());
+        // length does not match the components count.
+        assert!(ImagePlaceholder::from("LEHV6nWB2yk8pyo0adR*.7kCMdn").decode().is_none());
+        // not base83.
+        assert!(ImagePlaceholder::from("LEHV6nWB2yk8pyo0adR*.7kCMd\"j").decode().is_none());
+        assert!(ImagePlaceholder::None.decode().is_none());
+    }
+
+    #[test]
+    fn thumb_hash_reference() {
+        // portrait, opaque.
+        assert_pixels(
+            ImagePlaceholder::ThumbHash("1QcSHQRnh493V4dIh4eXh1h4kJUI".into()),
+            (23, 32),
+            &[
+                ((0, 0), [113, 77, 64, 255]),
+                ((11, 16), [88, 109, 140, 255]),
+                ((22, 31), [39, 4, 0, 255]),
+            ],
+        );
+        // landscape, with alpha, padded base64.
+        assert_pixels(
+            ImagePlaceholder::ThumbHash("LEuaA5V6CzBVep/E6Q4zWH2ix+wRNls=".into()),
+            (32, 19),
+            &[
+                ((0, 0), [0, 145, 112, 145]),
+                ((16, 9), [255, 0, 49, 255]),
+                ((31, 18), [0, 255, 255, 255]),
+            ],
+        );
+        // portrait, with alpha.
+        assert_pixels(
+            ImagePlaceholder::ThumbHash("kyWMBB6zeJZph1qleJZph1qleJZph1qleJZph1ql".into()),
+            (26, 32),
+            &[((0, 0), [105, 159, 0, 203]), ((13, 16), [0, 0, 0, 0]), ((25, 31), [0, 1, 0, 1])],
+        );
+    }
+
+    #[test]
+    fn thumb_hash_invalid() {
+        // not base64.
+        assert!(ImagePlaceholder::ThumbHash("not base64!".into()).decode().is_none());
+        // header only.
+        assert!(ImagePlaceholder::ThumbHash("1QcSHQ".into()).decode().is_none());
+        // missing AC components.
+        assert!(ImagePlaceholder::ThumbHash("1QcSHQRnh493".into()).decode().is_none());
+    }
+}
@@ -1,7 +1,7 @@
 //! Common commands.
 //!
 
-use zng_ext_clipboard::{COPY_CMD, CUT_CMD, PASTE_CMD};
+use zng_ext_clipboard::{COPY_CMD, CUT_CMD, PASTE_CMD, PASTE_FROM_HISTORY_CMD};
 use zng_wgt::{prelude::*, ICONS};
 
 command! {
@@ -139,6 +139,16 @@ command_property! {
         cmd: PASTE_CMD.scoped(WIDGET.id()),
     }
 
+    /// On paste from history command.
+    ///
+    /// Receives [`PASTE_FROM_HISTORY_CMD`] command events scoped on the widget. The command parameter is
+    /// an index in the `CLIPBOARD.history`.
+    ///
+    /// [`PASTE_FROM_HISTORY_CMD`]: zng_ext_clipboard::PASTE_FROM_HISTORY_CMD
+    pub fn paste_from_history {
+        cmd: PASTE_FROM_HISTORY_CMD.scoped(WIDGET.id()),
+    }
+
     /// On settings command.
     ///
     /// Receives [`SETTINGS_CMD`] command events scoped on the widget.
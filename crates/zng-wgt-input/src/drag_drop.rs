@@ -0,0 +1,670 @@
+//! Drag&drop properties, events and service.
+
+use std::{fmt, sync::Arc};
+
+use zng_ext_image::{ImageSource, ImageVar, Img, IMAGES};
+use zng_ext_input::{
+    mouse::{MOUSE, MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT},
+    pointer_capture::POINTER_CAPTURE,
+};
+use zng_ext_window::{WINDOW_Ext as _, WINDOWS};
+use zng_wgt::prelude::*;
+
+pub use zng_view_api::drag_drop::{DragDropData, DragDropEffect};
+
+context_var! {
+    /// Effects allowed for data dragged from [`drag_source`] and [`draggable`] widgets.
+    ///
+    /// Is [`DragDropEffect::COPY`] by default.
+    ///
+    /// [`drag_source`]: fn@drag_source
+    /// [`draggable`]: fn@draggable
+    pub static DRAG_ALLOWED_EFFECTS_VAR: DragDropEffect = DragDropEffect::COPY;
+
+    /// Widget function that generates the preview shown under the cursor while dragging from [`draggable`] widgets.
+    ///
+    /// Is [`WidgetFn::nil`] by default, no preview.
+    ///
+    /// [`draggable`]: fn@draggable
+    pub static DRAG_PREVIEW_FN_VAR: WidgetFn<DragPreviewArgs> = WidgetFn::nil();
+
+    /// Image shown under the cursor while dragging from [`drag_source`] widgets, or from [`draggable`] widgets
+    /// after the cursor leaves the window.
+    ///
+    /// Is `None` by default, the operating system may show a default image.
+    ///
+    /// [`drag_source`]: fn@drag_source
+    /// [`draggable`]: fn@draggable
+    pub static DRAG_IMAGE_VAR: Option<ImageSource> = None;
+}
+
+/// Defines the effects allowed for data dragged from [`drag_source`] and [`draggable`] widgets in the widget and descendants.
+///
+/// This property sets the [`DRAG_ALLOWED_EFFECTS_VAR`].
+///
+/// [`drag_source`]: fn@drag_source
+/// [`draggable`]: fn@draggable
+#[property(CONTEXT, default(DRAG_ALLOWED_EFFECTS_VAR))]
+pub fn drag_allowed_effects(child: impl UiNode, effects: impl IntoVar<DragDropEffect>) -> impl UiNode {
+    with_context_var(child, DRAG_ALLOWED_EFFECTS_VAR, effects)
+}
+
+/// Defines the preview shown under the cursor while dragging from [`draggable`] widgets in the widget and descendants.
+///
+/// The preview is layered by the window layers implementation, it is not hit-testable.
+///
+/// This property sets the [`DRAG_PREVIEW_FN_VAR`].
+///
+/// [`draggable`]: fn@draggable
+#[property(CONTEXT, default(DRAG_PREVIEW_FN_VAR))]
+pub fn drag_preview_fn(child: impl UiNode, preview: impl IntoVar<WidgetFn<DragPreviewArgs>>) -> impl UiNode {
+    with_context_var(child, DRAG_PREVIEW_FN_VAR, preview)
+}
+
+/// Defines the image shown under the cursor while dragging from [`drag_source`] widgets in the widget and descendants.
+///
+/// The image is loaded when the source widget inits, it is only shown if it has loaded when the drag starts. Note that
+/// the XDND protocol used on Linux does not support drag images.
+///
+/// The image is also used by [`draggable`] widgets when the operation continues as a native drag&drop.
+///
+/// This property sets the [`DRAG_IMAGE_VAR`].
+///
+/// [`drag_source`]: fn@drag_source
+/// [`draggable`]: fn@draggable
+#[property(CONTEXT, default(DRAG_IMAGE_VAR))]
+pub fn drag_image(child: impl UiNode, image: impl IntoVar<Option<ImageSource>>) -> impl UiNode {
+    with_context_var(child, DRAG_IMAGE_VAR, image)
+}
+
+/// Enables dragging `data` from the widget using the primary mouse button.
+///
+/// The drag operation starts when the primary button is pressed on the widget and the cursor moves more than
+/// the system [multi-click area]. The operation is native, the data can be dropped in other apps, the effects
+/// allowed are defined by [`drag_allowed_effects`] and the image shown under the cursor is defined by [`drag_image`].
+///
+/// The widget is not draggable if `data` is empty or if the widget is disabled.
+///
+/// See [`draggable`] for in-app drag&drop.
+///
+/// [multi-click area]: zng_view_api::config::MultiClickConfig::area
+/// [`drag_allowed_effects`]: fn@drag_allowed_effects
+/// [`drag_image`]: fn@drag_image
+/// [`draggable`]: fn@draggable
+#[property(CONTEXT, default(Vec::<DragDropData>::new()))]
+pub fn drag_source(child: impl UiNode, data: impl IntoVar<Vec<DragDropData>>) -> impl UiNode {
+    let data = data.into_var();
+    let mut press_pos = None::<DipPoint>;
+    let mut image = None::<ImageVar>;
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_event(&MOUSE_INPUT_EVENT)
+                .sub_event(&MOUSE_MOVE_EVENT)
+                .sub_var(&DRAG_IMAGE_VAR);
+            image = load_drag_image();
+        }
+        UiNodeOp::Deinit => {
+            press_pos = None;
+            image = None;
+        }
+        UiNodeOp::Update { .. } => {
+            if DRAG_IMAGE_VAR.is_new() {
+                image = load_drag_image();
+            }
+        }
+        UiNodeOp::Event { update } => {
+            if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                if args.is_primary() && args.is_mouse_down() && args.is_enabled(WIDGET.id()) {
+                    press_pos = Some(args.position);
+                } else if args.is_mouse_up() {
+                    press_pos = None;
+                }
+            } else if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                if let Some(pos) = press_pos {
+                    if exceeds_drag_threshold(pos, args.position) {
+                        press_pos = None;
+                        let data = data.get();
+                        if !data.is_empty() {
+                            let image = loaded_drag_image(&image);
+                            let _ = WINDOWS.native_drag_drop(WINDOW.id(), data, DRAG_ALLOWED_EFFECTS_VAR.get(), image);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Enables dragging `data` from the widget to [`drop_target`] widgets in the app.
+///
+/// The drag operation starts when the primary button is pressed on the widget and the cursor moves more than
+/// the system [multi-click area]. While dragging the [`DRAG_HOVERED_EVENT`] notifies the drop target under the
+/// cursor and the [`drag_preview_fn`] is shown under the cursor, on release the [`DROP_EVENT`] notifies the drop
+/// target and the [`DRAG_END_EVENT`] notifies the source widget.
+///
+/// If the cursor leaves the window while dragging the in-app operation ends and continues as a native drag&drop, so the
+/// data can be dropped in other windows and apps. The native operation uses the [`drag_image`] and the [`DRAG_END_EVENT`]
+/// notifies the effect applied by the native drop target, see [`drag_source`] for details about native drag&drop support.
+///
+/// The widget is not draggable if `data` is empty or if the widget is disabled.
+///
+/// [multi-click area]: zng_view_api::config::MultiClickConfig::area
+/// [`drop_target`]: fn@drop_target
+/// [`drag_preview_fn`]: fn@drag_preview_fn
+/// [`drag_image`]: fn@drag_image
+/// [`drag_source`]: fn@drag_source
+#[property(CONTEXT, default(Vec::<DragDropData>::new()))]
+pub fn draggable(child: impl UiNode, data: impl IntoVar<Vec<DragDropData>>) -> impl UiNode {
+    let data = data.into_var();
+    let mut press_pos = None::<DipPoint>;
+    let mut drag = None::<DragState>;
+    let mut image = None::<ImageVar>;
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_event(&MOUSE_INPUT_EVENT)
+                .sub_event(&MOUSE_MOVE_EVENT)
+                .sub_var(&DRAG_IMAGE_VAR);
+            image = load_drag_image();
+        }
+        UiNodeOp::Deinit => {
+            press_pos = None;
+            image = None;
+            if let Some(d) = drag.take() {
+                d.end(DragDropEffect::empty());
+            }
+        }
+        UiNodeOp::Update { .. } => {
+            if DRAG_IMAGE_VAR.is_new() {
+                image = load_drag_image();
+            }
+        }
+        UiNodeOp::Event { update } => {
+            if let Some(args) = MOUSE_INPUT_EVENT.on_unhandled(update) {
+                if args.is_primary() && args.is_mouse_down() && args.is_enabled(WIDGET.id()) {
+                    press_pos = Some(args.position);
+                } else if args.is_mouse_up() {
+                    press_pos = None;
+                    if let Some(d) = drag.take() {
+                        args.propagation().stop();
+                        d.drop(args.position);
+                    }
+                }
+            } else if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                if let Some(d) = drag.take() {
+                    let size = WINDOW.vars().actual_size().get();
+                    let p = args.position;
+                    if p.x < Dip::new(0) || p.y < Dip::new(0) || p.x > size.width || p.y > size.height {
+                        d.continue_native(loaded_drag_image(&image));
+                    } else {
+                        drag.insert(d).hover(p, Some(&args.target));
+                    }
+                } else if let Some(pos) = press_pos {
+                    if exceeds_drag_threshold(pos, args.position) {
+                        press_pos = None;
+                        let data = data.get();
+                        if !data.is_empty() {
+                            POINTER_CAPTURE.capture_widget(WIDGET.id());
+                            let mut d = DragState::start(data, args.position);
+                            d.hover(args.position, Some(&args.target));
+                            drag = Some(d);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Defines the widget as a drop target for [`draggable`] data.
+///
+/// The `filter` is called when data is dragged over the widget or descendants, it returns the effects the widget accepts
+/// for the data, if the effects intersect the allowed effects of the source the widget is the drop target, otherwise the
+/// closest ancestor drop target that accepts the data is the target.
+///
+/// The drop target receives the [`DRAG_HOVERED_EVENT`] and [`DROP_EVENT`], see [`on_drag_enter`], [`on_drag_leave`],
+/// [`is_drag_hovered`] and [`on_drop`] for more details.
+///
+/// [`draggable`]: fn@draggable
+/// [`on_drag_enter`]: fn@on_drag_enter
+/// [`on_drag_leave`]: fn@on_drag_leave
+/// [`is_drag_hovered`]: fn@is_drag_hovered
+/// [`on_drop`]: fn@on_drop
+#[property(CONTEXT, default(DragDropEffect::empty()))]
+pub fn drop_target(child: impl UiNode, filter: impl IntoVar<DropFilter>) -> impl UiNode {
+    let filter = filter.into_var();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var_info(&filter);
+        }
+        UiNodeOp::Info { info } => {
+            info.set_meta(*DROP_FILTER_ID, filter.get());
+        }
+        _ => {}
+    })
+}
+
+/// If [`draggable`] data is dragged over the [`drop_target`] widget or a descendant and the target accepts the data.
+///
+/// [`draggable`]: fn@draggable
+/// [`drop_target`]: fn@drop_target
+#[property(EVENT)]
+pub fn is_drag_hovered(child: impl UiNode, state: impl IntoVar<bool>) -> impl UiNode {
+    event_state(child, state, false, DRAG_HOVERED_EVENT, |args| {
+        if args.is_drag_enter() {
+            Some(true)
+        } else if args.is_drag_leave() {
+            Some(false)
+        } else {
+            None
+        }
+    })
+}
+
+event_property! {
+    /// Draggable data entered the drop target widget or a descendant.
+    pub fn drag_enter {
+        event: DRAG_HOVERED_EVENT,
+        args: DragHoveredArgs,
+        filter: |args| args.is_drag_enter(),
+    }
+
+    /// Draggable data left the drop target widget or a descendant.
+    ///
+    /// Note that this event also notifies when the drag operation ends, before the [`on_drop`] event.
+    ///
+    /// [`on_drop`]: fn@on_drop
+    pub fn drag_leave {
+        event: DRAG_HOVERED_EVENT,
+        args: DragHoveredArgs,
+        filter: |args| args.is_drag_leave(),
+    }
+
+    /// Draggable data dropped on the drop target widget or a descendant.
+    pub fn drop {
+        event: DROP_EVENT,
+        args: DropArgs,
+    }
+
+    /// Drag operation started by the draggable widget has ended.
+    ///
+    /// Check [`DragEndArgs::applied`] to see if the data was dropped.
+    pub fn drag_end {
+        event: DRAG_END_EVENT,
+        args: DragEndArgs,
+    }
+}
+
+/// Starts loading the [`DRAG_IMAGE_VAR`] image.
+fn load_drag_image() -> Option<ImageVar> {
+    DRAG_IMAGE_VAR.get().map(|s| IMAGES.cache(s))
+}
+
+/// Gets the image if it has loaded.
+fn loaded_drag_image(image: &Option<ImageVar>) -> Option<Img> {
+    image.as_ref().map(|i| i.get()).filter(|i| i.is_loaded())
+}
+
+fn exceeds_drag_threshold(press: DipPoint, position: DipPoint) -> bool {
+    let area = MOUSE.multi_click_config().get().area;
+    (position.x - press.x).abs() > area.width || (position.y - press.y).abs() > area.height
+}
+
+/// Arguments for [`DRAG_PREVIEW_FN_VAR`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragPreviewArgs {
+    /// Dragged data.
+    pub data: Vec<DragDropData>,
+}
+
+/// Drop target filter.
+///
+/// Returns the effects accepted for the dragged data, see [`drop_target`] for more details.
+///
+/// The filter can be converted from [`DragDropEffect`], in this case it accepts any data.
+///
+/// [`drop_target`]: fn@drop_target
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct DropFilter(Arc<dyn Fn(&[DragDropData]) -> DragDropEffect + Send + Sync>);
+impl DropFilter {
+    /// New from a closure that returns the effects accepted for the data.
+    pub fn new(filter: impl Fn(&[DragDropData]) -> DragDropEffect + Send + Sync + 'static) -> Self {
+        Self(Arc::new(filter))
+    }
+
+    /// Filter that accepts any data with the `effects`.
+    pub fn accept(effects: DragDropEffect) -> Self {
+        Self::new(move |_| effects)
+    }
+
+    /// Filter that accepts only [`DragDropData::Text`] with the `effects`.
+    pub fn text(effects: DragDropEffect) -> Self {
+        Self::new(move |d| {
+            if d.iter().any(|d| matches!(d, DragDropData::Text(_))) {
+                effects
+            } else {
+                DragDropEffect::empty()
+            }
+        })
+    }
+
+    /// Filter that accepts only [`DragDropData::Path`] with the `effects`.
+    pub fn path(effects: DragDropEffect) -> Self {
+        Self::new(move |d| {
+            if d.iter().any(|d| matches!(d, DragDropData::Path(_))) {
+                effects
+            } else {
+                DragDropEffect::empty()
+            }
+        })
+    }
+
+    /// Call the filter.
+    pub fn call(&self, data: &[DragDropData]) -> DragDropEffect {
+        (self.0)(data)
+    }
+}
+impl fmt::Debug for DropFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DropFilter(_)")
+    }
+}
+impl PartialEq for DropFilter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl_from_and_into_var! {
+    fn from(effects: DragDropEffect) -> DropFilter {
+        DropFilter::accept(effects)
+    }
+}
+
+static_id! {
+    static ref DROP_FILTER_ID: StateId<DropFilter>;
+}
+
+/// In-app drag&drop service.
+///
+/// See [`draggable`] and [`drop_target`] for more details about in-app drag&drop.
+///
+/// [`draggable`]: fn@draggable
+/// [`drop_target`]: fn@drop_target
+#[allow(non_camel_case_types)]
+pub struct DRAG_DROP;
+impl DRAG_DROP {
+    /// Current in-app drag operation.
+    ///
+    /// The variable updates when the operation starts, when the cursor moves and is `None` when the operation ends.
+    pub fn dragging(&self) -> ReadOnlyArcVar<Option<DragInfo>> {
+        DRAG_DROP_SV.read().dragging.read_only()
+    }
+}
+
+/// Info about an ongoing in-app drag operation.
+///
+/// See [`DRAG_DROP.dragging`] for more details.
+///
+/// [`DRAG_DROP.dragging`]: DRAG_DROP::dragging
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragInfo {
+    /// Window where the operation started.
+    pub window_id: WindowId,
+    /// The draggable widget.
+    pub source: WidgetId,
+    /// Dragged data.
+    pub data: Vec<DragDropData>,
+    /// Effects allowed by the source.
+    pub allowed_effects: DragDropEffect,
+    /// Cursor position in the window.
+    pub position: DipPoint,
+    /// Current drop target.
+    pub target: Option<WidgetId>,
+    /// Effect applied if the data is dropped in the current target.
+    pub effect: DragDropEffect,
+    /// Preview widget function, from the source context.
+    pub preview: WidgetFn<DragPreviewArgs>,
+}
+
+app_local! {
+    static DRAG_DROP_SV: DragDropService = DragDropService { dragging: var(None) };
+}
+
+struct DragDropService {
+    dragging: ArcVar<Option<DragInfo>>,
+}
+
+/// Ongoing drag operation, lives in the draggable node.
+struct DragState {
+    source: InteractionPath,
+    data: Vec<DragDropData>,
+    allowed_effects: DragDropEffect,
+    target: Option<InteractionPath>,
+    effect: DragDropEffect,
+}
+impl DragState {
+    fn start(data: Vec<DragDropData>, position: DipPoint) -> Self {
+        let s = Self {
+            source: WIDGET.info().interaction_path(),
+            data,
+            allowed_effects: DRAG_ALLOWED_EFFECTS_VAR.get(),
+            target: None,
+            effect: DragDropEffect::empty(),
+        };
+        DRAG_DROP_SV.read().dragging.set(Some(DragInfo {
+            window_id: WINDOW.id(),
+            source: s.source.widget_id(),
+            data: s.data.clone(),
+            allowed_effects: s.allowed_effects,
+            position,
+            target: None,
+            effect: DragDropEffect::empty(),
+            preview: DRAG_PREVIEW_FN_VAR.get(),
+        }));
+        s
+    }
+
+    /// Update the drop target for the hovered widget.
+    fn hover(&mut self, position: DipPoint, hovered: Option<&InteractionPath>) {
+        let tree = WINDOW.info();
+        let (target, effect) = hovered
+            .and_then(|h| tree.get(h.widget_id()))
+            .and_then(|w| {
+                w.self_and_ancestors().find_map(|w| {
+                    let filter = w.meta().get(*DROP_FILTER_ID)?;
+                    if !w.interactivity().is_enabled() {
+                        return None;
+                    }
+                    let effect = preferred_effect(filter.call(&self.data) & self.allowed_effects)?;
+                    Some((w.interaction_path(), effect))
+                })
+            })
+            .unzip();
+        let effect = effect.unwrap_or_else(DragDropEffect::empty);
+
+        if target != self.target {
+            let prev_target = std::mem::replace(&mut self.target, target.clone());
+            DRAG_HOVERED_EVENT.notify(DragHoveredArgs::now(self.data.clone(), position, prev_target, target));
+        }
+        self.effect = effect;
+
+        let target = self.target.as_ref().map(|t| t.widget_id());
+        DRAG_DROP_SV.read().dragging.modify(move |d| {
+            if let Some(d) = d.to_mut() {
+                d.position = position;
+                d.target = target;
+                d.effect = effect;
+            }
+        });
+    }
+
+    /// Drop on the current target and end.
+    fn drop(mut self, position: DipPoint) {
+        let applied = self.effect;
+        if let Some(target) = self.target.take() {
+            DRAG_HOVERED_EVENT.notify(DragHoveredArgs::now(self.data.clone(), position, Some(target.clone()), None));
+            if !applied.is_empty() {
+                DROP_EVENT.notify(DropArgs::now(target, self.data.clone(), position, applied));
+            }
+        }
+        self.end(applied);
+    }
+
+    /// Cursor left the window, end the in-app operation and continue as a native operation.
+    fn continue_native(mut self, image: Option<Img>) {
+        if let Some(target) = self.target.take() {
+            DRAG_HOVERED_EVENT.notify(DragHoveredArgs::now(self.data.clone(), DipPoint::zero(), Some(target), None));
+        }
+        DRAG_DROP_SV.read().dragging.set(None);
+        // the native operation consumes the button release.
+        POINTER_CAPTURE.release_capture();
+
+        let rsp = WINDOWS.native_drag_drop(WINDOW.id(), self.data, self.allowed_effects, image);
+        let source = self.source;
+        UPDATES
+            .run(async move {
+                let applied = rsp.wait_into_rsp().await.unwrap_or_else(|e| {
+                    tracing::debug!("cannot continue drag as native drag&drop, {e}");
+                    DragDropEffect::empty()
+                });
+                DRAG_END_EVENT.notify(DragEndArgs::now(source, applied));
+            })
+            .perm();
+    }
+
+    fn end(self, applied: DragDropEffect) {
+        if let Some(target) = self.target {
+            DRAG_HOVERED_EVENT.notify(DragHoveredArgs::now(self.data, DipPoint::zero(), Some(target), None));
+        }
+        DRAG_DROP_SV.read().dragging.set(None);
+        DRAG_END_EVENT.notify(DragEndArgs::now(self.source, applied));
+    }
+}
+
+/// Select one effect, in the order of preference move, copy, link.
+fn preferred_effect(effects: DragDropEffect) -> Option<DragDropEffect> {
+    [DragDropEffect::MOVE, DragDropEffect::COPY, DragDropEffect::LINK]
+        .into_iter()
+        .find(|e| effects.contains(*e))
+}
+
+event_args! {
+    /// Arguments for [`DRAG_HOVERED_EVENT`].
+    pub struct DragHoveredArgs {
+        /// Dragged data.
+        pub data: Vec<DragDropData>,
+
+        /// Cursor position in the window.
+        pub position: DipPoint,
+
+        /// Previous drop target.
+        pub prev_target: Option<InteractionPath>,
+
+        /// New drop target.
+        pub target: Option<InteractionPath>,
+
+        ..
+
+        /// The [`prev_target`] and [`target`].
+        ///
+        /// [`prev_target`]: Self::prev_target
+        /// [`target`]: Self::target
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            if let Some(p) = &self.prev_target {
+                list.insert_wgt(p);
+            }
+            if let Some(p) = &self.target {
+                list.insert_wgt(p);
+            }
+        }
+    }
+
+    /// Arguments for [`DROP_EVENT`].
+    pub struct DropArgs {
+        /// The drop target.
+        pub target: InteractionPath,
+
+        /// Dropped data.
+        pub data: Vec<DragDropData>,
+
+        /// Cursor position in the window.
+        pub position: DipPoint,
+
+        /// Effect that must be applied by the drop target.
+        pub effect: DragDropEffect,
+
+        ..
+
+        /// The [`target`].
+        ///
+        /// [`target`]: Self::target
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.target);
+        }
+    }
+
+    /// Arguments for [`DRAG_END_EVENT`].
+    pub struct DragEndArgs {
+        /// The draggable widget.
+        pub source: InteractionPath,
+
+        /// Effect applied by the drop target, is empty if the operation was cancelled or the data dropped
+        /// outside of a drop target.
+        pub applied: DragDropEffect,
+
+        ..
+
+        /// The [`source`].
+        ///
+        /// [`source`]: Self::source
+        fn delivery_list(&self, list: &mut UpdateDeliveryList) {
+            list.insert_wgt(&self.source);
+        }
+    }
+}
+impl DragHoveredArgs {
+    /// Returns `true` if the [`WIDGET`] was not the drop target or a descendant, but now is.
+    ///
+    /// [`WIDGET`]: zng_app::widget::WIDGET
+    pub fn is_drag_enter(&self) -> bool {
+        !self.was_over() && self.is_over()
+    }
+
+    /// Returns `true` if the [`WIDGET`] was the drop target or a descendant, but now isn't.
+    ///
+    /// [`WIDGET`]: zng_app::widget::WIDGET
+    pub fn is_drag_leave(&self) -> bool {
+        self.was_over() && !self.is_over()
+    }
+
+    /// Returns `true` if the [`WIDGET`] is in [`prev_target`].
+    ///
+    /// [`prev_target`]: Self::prev_target
+    /// [`WIDGET`]: zng_app::widget::WIDGET
+    pub fn was_over(&self) -> bool {
+        self.prev_target.as_ref().map(|p| p.contains(WIDGET.id())).unwrap_or(false)
+    }
+
+    /// Returns `true` if the [`WIDGET`] is in [`target`].
+    ///
+    /// [`target`]: Self::target
+    /// [`WIDGET`]: zng_app::widget::WIDGET
+    pub fn is_over(&self) -> bool {
+        self.target.as_ref().map(|p| p.contains(WIDGET.id())).unwrap_or(false)
+    }
+}
+
+event! {
+    /// In-app drag operation entered or left a drop target.
+    pub static DRAG_HOVERED_EVENT: DragHoveredArgs;
+
+    /// In-app drag operation dropped on a drop target.
+    pub static DROP_EVENT: DropArgs;
+
+    /// In-app drag operation ended.
+    pub static DRAG_END_EVENT: DragEndArgs;
+}
@@ -4,11 +4,11 @@
 //! Prefer using these events over the events directly tied to an input device.
 
 use zng_app::shortcut::Shortcuts;
-use zng_ext_input::gesture::{ShortcutClick, CLICK_EVENT, GESTURES};
+use zng_ext_input::gesture::{ShortcutClick, CLICK_EVENT, GESTURES, PAN_GESTURE_EVENT, PINCH_GESTURE_EVENT, ROTATION_GESTURE_EVENT};
 use zng_view_api::access::AccessCmdName;
 use zng_wgt::prelude::*;
 
-pub use zng_ext_input::gesture::ClickArgs;
+pub use zng_ext_input::gesture::{ClickArgs, PanGestureArgs, PinchGestureArgs, RotationGestureArgs};
 
 event_property! {
     /// On widget click from any source and of any click count and the widget is enabled.
@@ -122,6 +122,37 @@ event_property! {
         filter: |args| args.is_context() && args.is_enabled(WIDGET.id()),
         with: access_click,
     }
+
+    /// On system pinch gesture over the widget and the widget is enabled.
+    ///
+    /// This event is only generated for touchpad gestures recognized by the operating system, use
+    /// [`on_touch_transform`] for touchscreen pinch.
+    ///
+    /// [`on_touch_transform`]: fn@crate::touch::on_touch_transform
+    pub fn pinch_gesture {
+        event: PINCH_GESTURE_EVENT,
+        args: PinchGestureArgs,
+        filter: |args| args.is_enabled(WIDGET.id()),
+    }
+
+    /// On system rotation gesture over the widget and the widget is enabled.
+    ///
+    /// This event is only generated for touchpad gestures recognized by the operating system, use
+    /// [`on_touch_transform`] for touchscreen rotation.
+    ///
+    /// [`on_touch_transform`]: fn@crate::touch::on_touch_transform
+    pub fn rotation_gesture {
+        event: ROTATION_GESTURE_EVENT,
+        args: RotationGestureArgs,
+        filter: |args| args.is_enabled(WIDGET.id()),
+    }
+
+    /// On system pan gesture over the widget and the widget is enabled.
+    pub fn pan_gesture {
+        event: PAN_GESTURE_EVENT,
+        args: PanGestureArgs,
+        filter: |args| args.is_enabled(WIDGET.id()),
+    }
 }
 
 /// Keyboard shortcuts that focus and clicks this widget.
@@ -2,7 +2,7 @@
 
 use zng_ext_input::{
     mouse::MOUSE_INPUT_EVENT,
-    pointer_capture::{PointerCaptureArgs, POINTER_CAPTURE, POINTER_CAPTURE_EVENT},
+    pointer_capture::{PointerCaptureArgs, PointerCaptureLostArgs, POINTER_CAPTURE, POINTER_CAPTURE_EVENT, POINTER_CAPTURE_LOST_EVENT},
     touch::TOUCH_INPUT_EVENT,
 };
 use zng_wgt::prelude::*;
@@ -29,6 +29,13 @@ event_property! {
         event: POINTER_CAPTURE_EVENT,
         args: PointerCaptureArgs,
     }
+
+    /// Widget lost `Widget` or `Subtree` mouse and touch capture, with the cause.
+    pub fn pointer_capture_lost {
+        event: POINTER_CAPTURE_LOST_EVENT,
+        args: PointerCaptureLostArgs,
+        filter: |args| args.capture.target.widget_id() == WIDGET.id(),
+    }
 }
 
 /// Capture mouse and touch for the widget on press.
@@ -85,6 +85,9 @@ fn panels(error: &CrashError) -> impl UiNode {
     if error.has_panic_widget() {
         options.push(ErrorPanel::Widget);
     }
+    if !error.breadcrumbs.is_empty() {
+        options.push(ErrorPanel::Breadcrumbs);
+    }
     if error.minidump.is_some() {
         options.push(ErrorPanel::Minidump);
         active = ErrorPanel::Minidump;
@@ -118,6 +121,7 @@ enum ErrorPanel {
     StderrPlain,
     Panic,
     Widget,
+    Breadcrumbs,
     Minidump,
 }
 impl ErrorPanel {
@@ -130,6 +134,7 @@ impl ErrorPanel {
             ErrorPanel::StderrPlain => "Stderr (plain)",
             ErrorPanel::Panic => "Panic",
             ErrorPanel::Widget => "Widget",
+            ErrorPanel::Breadcrumbs => "Breadcrumbs",
             ErrorPanel::Minidump => "Minidump",
         }
         .into()
@@ -144,13 +149,14 @@ impl ErrorPanel {
             ErrorPanel::StderrPlain => std_plain_panel(error.stderr_plain(), "stderr").boxed(),
             ErrorPanel::Panic => panic_panel(error.find_panic().unwrap()).boxed(),
             ErrorPanel::Widget => widget_panel(error.find_panic().unwrap().widget_path).boxed(),
+            ErrorPanel::Breadcrumbs => plain_panel(error.breadcrumbs.join("\n").into(), "breadcrumbs").boxed(),
             ErrorPanel::Minidump => minidump_panel(error.minidump.clone().unwrap()).boxed(),
         }
     }
 }
 
 fn summary_panel(error: &CrashError) -> impl UiNode {
-    let s = formatx!(
+    let mut s = formatx!(
         "Timestamp: {}\nExit Code: {}\nSignal: {}\nStderr: {} bytes\nStdout: {} bytes\nPanic: {}\nMinidump: {}\n\nArgs: {:?}\nOS: {}",
         error.unix_time(),
         match error.code {
@@ -175,6 +181,13 @@ fn summary_panel(error: &CrashError) -> impl UiNode {
         error.args,
         error.os,
     );
+    for p in &error.view_minidumps {
+        let path = p.display().to_string();
+        s.push_str(&format!("\nView-Process Minidump: {}", path.trim_start_matches(r"\\?\")));
+    }
+    for (key, value) in &error.metadata {
+        s.push_str(&format!("\n{key}: {value}"));
+    }
     plain_panel(s, "summary")
 }
 
@@ -316,6 +329,48 @@ async fn remove_path(enabled: ArcVar<bool>, path: PathBuf) {
 
     enabled.set(true);
 }
+async fn send_report(enabled: ArcVar<bool>, args: CrashArgs) {
+    enabled.set(false);
+
+    let consent = WINDOWS
+        .native_message_dialog(
+            WINDOW.id(),
+            zng_view_api::dialog::MsgDialog {
+                title: "Send Report".into(),
+                message: "Send the crash report to the app developers?\n\n\
+                          The report includes the app output, crash details and the minidump if available."
+                    .into(),
+                icon: zng_view_api::dialog::MsgDialogIcon::Info,
+                buttons: zng_view_api::dialog::MsgDialogButtons::YesNo,
+            },
+        )
+        .wait_into_rsp()
+        .await;
+    if consent != zng_view_api::dialog::MsgDialogResponse::Yes {
+        enabled.set(true);
+        return;
+    }
+
+    match task::wait(move || args.send_report()).await {
+        Ok(()) => {
+            WINDOWS
+                .native_message_dialog(
+                    WINDOW.id(),
+                    zng_view_api::dialog::MsgDialog {
+                        title: "Send Report".into(),
+                        message: "Crash report sent.".into(),
+                        ..Default::default()
+                    },
+                )
+                .wait_into_rsp()
+                .await;
+        }
+        Err(e) => {
+            error_message(formatx!("Failed to send crash report.\n{e}")).await;
+            enabled.set(true);
+        }
+    }
+}
 async fn error_message(message: Txt) {
     WINDOWS
         .native_message_dialog(
@@ -348,11 +403,26 @@ fn plain_panel(txt: Txt, config_key: &'static str) -> impl UiNode {
 }
 
 fn commands(args: CrashArgs) -> impl UiNode {
+    let send_report = if args.can_send_report() {
+        let enabled = var(true);
+        Button! {
+            child = Text!("Send Report");
+            tooltip = Tip!(Text!("Send the crash report to the app developers"));
+            on_click = async_hn!(enabled, args, |_| {
+                send_report(enabled, args).await;
+            });
+            enabled;
+        }
+        .boxed()
+    } else {
+        NilUiNode.boxed()
+    };
     Stack! {
         spacing = 5;
         direction = StackDirection::start_to_end();
         align = Align::END;
         children = ui_vec![
+            send_report,
             Button! {
                 child = Text!("Restart App");
                 on_click = hn_once!(args, |_| {
@@ -388,3 +388,235 @@ pub fn show_directional_query(child: impl UiNode, orientation: impl IntoVar<Opti
         _ => {}
     })
 }
+
+/// Flashes the inner bounds of widgets that rendered in the last frame.
+///
+/// Only the deepest widgets that rendered are highlighted, ancestors of a widget that renders also render to reuse the
+/// display items of the other descendants. The highlight is cleared after a short time.
+///
+/// # Window Only
+///
+/// This property only works if set in a window, if set in another widget it will log an error and not render anything.
+#[property(CONTEXT, default(false))]
+pub fn show_repaints(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    let enabled = enabled.into_var();
+    let mut valid = false;
+    let mut render_counts = IdMap::<WidgetId, u32>::default();
+    let mut flashes = vec![];
+    let mut _clear_handle = DeadlineHandle::dummy();
+
+    match_node(child, move |child, op| match op {
+        UiNodeOp::Init => {
+            valid = WIDGET.parent_id().is_none();
+            if valid {
+                WIDGET.sub_var_render(&enabled);
+            } else {
+                tracing::error!("property `show_repaints` is only valid in a window");
+            }
+        }
+        UiNodeOp::Deinit => {
+            render_counts.clear();
+            flashes.clear();
+            _clear_handle = DeadlineHandle::dummy();
+        }
+        UiNodeOp::Render { frame } => {
+            child.render(frame);
+
+            if !valid || !enabled.get() {
+                render_counts.clear();
+                return;
+            }
+
+            // the window root also renders when this node requests a render to clear,
+            // only descendants are inspected.
+            let is_first = render_counts.is_empty();
+            let mut rendered = IdSet::default();
+            let tree = WINDOW.info();
+            for wgt in tree.root().descendants() {
+                let count = wgt.bounds_info().render_count();
+                let prev = render_counts.insert(wgt.id(), count);
+                let is_new = match prev {
+                    Some(p) => p != count,
+                    None => !is_first,
+                };
+                if is_new && wgt.visibility().is_visible() {
+                    rendered.insert(wgt.id());
+                }
+            }
+            render_counts.retain(|id, _| tree.get(*id).is_some());
+
+            flashes.clear();
+            for id in rendered.iter() {
+                let wgt = tree.get(*id).unwrap();
+                if wgt.children().all(|c| !rendered.contains(&c.id())) {
+                    let bounds = wgt.inner_bounds();
+                    if !bounds.size.is_empty() {
+                        flashes.push(bounds);
+                    }
+                }
+            }
+
+            if !flashes.is_empty() {
+                let widths = PxSideOffsets::new_all_same(Dip::new(1).to_px(frame.scale_factor()));
+                let sides = BorderSides::solid(web_colors::MAGENTA);
+                let fill = web_colors::MAGENTA.with_alpha(20.pct());
+                frame.with_hit_tests_disabled(|frame| {
+                    for bounds in &flashes {
+                        frame.push_color(*bounds, FrameValue::Value(fill));
+                        frame.push_border(*bounds, widths, sides, PxCornerRadius::zero());
+                    }
+                });
+
+                let id = WIDGET.id();
+                _clear_handle = TIMERS.on_deadline(
+                    300.ms(),
+                    app_hn_once!(|_| {
+                        UPDATES.render(id);
+                    }),
+                );
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Draws a translucent fill over the inner bounds of every rendered widget.
+///
+/// Each widget adds a layer to the fill, so areas covered by many stacked widgets are more opaque, this can be used
+/// to find deep nesting and hidden widgets that still render under other content.
+///
+/// # Window Only
+///
+/// This property only works if set in a window, if set in another widget it will log an error and not render anything.
+#[property(CONTEXT, default(false))]
+pub fn show_overdraw(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    let enabled = enabled.into_var();
+    let mut valid = false;
+
+    match_node(child, move |child, op| match op {
+        UiNodeOp::Init => {
+            valid = WIDGET.parent_id().is_none();
+            if valid {
+                WIDGET.sub_var_render(&enabled);
+            } else {
+                tracing::error!("property `show_overdraw` is only valid in a window");
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            child.render(frame);
+
+            if valid && enabled.get() {
+                let fill = FrameValue::Value(web_colors::RED.with_alpha(8.pct()));
+                frame.with_hit_tests_disabled(|frame| {
+                    for wgt in WINDOW.info().root().self_and_descendants() {
+                        if wgt.visibility().is_visible() {
+                            let bounds = wgt.inner_bounds();
+                            if !bounds.size.is_empty() {
+                                frame.push_color(bounds, fill);
+                            }
+                        }
+                    }
+                });
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Highlights widgets that measure or layout every second, the color changes from yellow to red with the number of
+/// measure and layout passes per second.
+///
+/// Widgets that update layout continuously without a visual change indicate a *layout thrash*, usually caused by
+/// properties that request layout on every update or panels that measure children with changing constraints.
+///
+/// # Window Only
+///
+/// This property only works if set in a window, if set in another widget it will log an error and not render anything.
+#[property(CONTEXT, default(false))]
+pub fn show_layout_thrash(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    let enabled = enabled.into_var();
+    let mut valid = false;
+    let mut timer = None::<TimerVar>;
+    let mut layout_counts = IdMap::<WidgetId, u32>::default();
+    let mut rates = vec![];
+
+    match_node(child, move |child, op| match op {
+        UiNodeOp::Init => {
+            valid = WIDGET.parent_id().is_none();
+            if valid {
+                WIDGET.sub_var(&enabled);
+                if enabled.get() {
+                    let t = TIMERS.interval(1.secs(), false);
+                    WIDGET.sub_var(&t);
+                    timer = Some(t);
+                }
+            } else {
+                tracing::error!("property `show_layout_thrash` is only valid in a window");
+            }
+        }
+        UiNodeOp::Deinit => {
+            timer = None;
+            layout_counts.clear();
+            rates.clear();
+        }
+        UiNodeOp::Update { .. } => {
+            if !valid {
+                return;
+            }
+            if let Some(e) = enabled.get_new() {
+                layout_counts.clear();
+                rates.clear();
+                if e {
+                    let t = TIMERS.interval(1.secs(), false);
+                    WIDGET.sub_var(&t);
+                    timer = Some(t);
+                } else {
+                    timer = None;
+                }
+                WIDGET.render();
+            }
+
+            if timer.as_ref().map(|t| t.is_new()).unwrap_or(false) {
+                let is_first = layout_counts.is_empty();
+                let tree = WINDOW.info();
+                let mut new_rates = vec![];
+                for wgt in tree.root().self_and_descendants() {
+                    let bounds = wgt.bounds_info();
+                    let count = bounds.measure_count().wrapping_add(bounds.layout_count());
+                    if let Some(prev) = layout_counts.insert(wgt.id(), count) {
+                        let rate = count.wrapping_sub(prev);
+                        if rate > 0 && !is_first {
+                            let b = wgt.inner_bounds();
+                            if !b.size.is_empty() {
+                                new_rates.push((b, rate));
+                            }
+                        }
+                    }
+                }
+                layout_counts.retain(|id, _| tree.get(*id).is_some());
+
+                if rates != new_rates {
+                    rates = new_rates;
+                    WIDGET.render();
+                }
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            child.render(frame);
+
+            if valid && enabled.get() {
+                let widths = PxSideOffsets::new_all_same(Dip::new(1).to_px(frame.scale_factor()));
+                frame.with_hit_tests_disabled(|frame| {
+                    for (bounds, rate) in &rates {
+                        // 1/s is yellow, 60/s or more is red.
+                        let f = (*rate as f32 / 60.0).min(1.0);
+                        let color = zng_color::lerp_rgba(colors::YELLOW, colors::RED, f.fct());
+                        frame.push_color(*bounds, FrameValue::Value(color.with_alpha(0.15 + 0.3 * f)));
+                        frame.push_border(*bounds, widths, BorderSides::solid(color), PxCornerRadius::zero());
+                    }
+                });
+            }
+        }
+        _ => {}
+    })
+}
@@ -14,21 +14,37 @@ use crate::INSPECT_CMD;
 
 mod data_model;
 mod inspector_window;
+mod value_editor;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 struct Config {
     adorn_selected: bool,
     select_focused: bool,
+    show_repaints: bool,
+    show_overdraw: bool,
+    show_layout_thrash: bool,
 }
 impl Default for Config {
     fn default() -> Self {
         Self {
             adorn_selected: true,
             select_focused: false,
+            show_repaints: false,
+            show_overdraw: false,
+            show_layout_thrash: false,
         }
     }
 }
 
+/// Diagnostic overlays drawn in the inspected window.
+#[derive(Clone)]
+struct Overlays {
+    repaints: BoxedVar<bool>,
+    overdraw: BoxedVar<bool>,
+    layout_thrash: BoxedVar<bool>,
+}
+
 /// Node set on the window to inspect.
 pub fn inspect_node(can_inspect: impl IntoVar<bool>) -> impl UiNode {
     let mut inspected_tree = None::<data_model::InspectedTree>;
@@ -48,6 +64,14 @@ pub fn inspect_node(can_inspect: impl IntoVar<bool>) -> impl UiNode {
     );
     let adorn_selected = config.map_ref_bidi(|c| &c.adorn_selected, |c| &mut c.adorn_selected);
     let select_focused = config.map_ref_bidi(|c| &c.select_focused, |c| &mut c.select_focused);
+    let overlays = Overlays {
+        repaints: config.map_ref_bidi(|c| &c.show_repaints, |c| &mut c.show_repaints).boxed(),
+        overdraw: config.map_ref_bidi(|c| &c.show_overdraw, |c| &mut c.show_overdraw).boxed(),
+        layout_thrash: config
+            .map_ref_bidi(|c| &c.show_layout_thrash, |c| &mut c.show_layout_thrash)
+            .boxed(),
+    };
+    let window_overlays = overlays.clone();
 
     let can_inspect = can_inspect.into_var();
     let mut cmd_handle = CommandHandle::dummy();
@@ -111,9 +135,25 @@ pub fn inspect_node(can_inspect: impl IntoVar<bool>) -> impl UiNode {
                     let inspected = WINDOW.id();
                     WINDOWS.focus_or_open(
                         inspector,
-                        async_clmv!(inspected_tree, selected_wgt, hit_select, adorn_selected, select_focused, {
-                            inspector_window::new(inspected, inspected_tree, selected_wgt, hit_select, adorn_selected, select_focused)
-                        }),
+                        async_clmv!(
+                            inspected_tree,
+                            selected_wgt,
+                            hit_select,
+                            adorn_selected,
+                            select_focused,
+                            window_overlays,
+                            {
+                                inspector_window::new(
+                                    inspected,
+                                    inspected_tree,
+                                    selected_wgt,
+                                    hit_select,
+                                    adorn_selected,
+                                    select_focused,
+                                    window_overlays,
+                                )
+                            }
+                        ),
                     );
                 }
             }
@@ -125,6 +165,9 @@ pub fn inspect_node(can_inspect: impl IntoVar<bool>) -> impl UiNode {
     }));
 
     let child = self::adorn_selected(child, selected_wgt, adorn_selected);
+    let child = crate::debug::show_repaints(child, overlays.repaints);
+    let child = crate::debug::show_overdraw(child, overlays.overdraw);
+    let child = crate::debug::show_layout_thrash(child, overlays.layout_thrash);
     select_on_click(child, hit_select)
 }
 
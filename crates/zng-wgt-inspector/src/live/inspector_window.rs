@@ -2,8 +2,8 @@ use std::mem;
 
 use zng_app::widget::{
     border::{BorderSide, BorderSides},
-    builder::{Importance, PropertyArgs, PropertyInfo, WidgetType},
-    inspector::{InspectorActualVars, InstanceItem},
+    builder::{Importance, InputKind, PropertyArgs, PropertyInfo, WidgetType},
+    inspector::InstanceItem,
     OnVarArgs,
 };
 use zng_color::Rgba;
@@ -32,7 +32,7 @@ use zng_wgt_wrap::Wrap;
 
 use super::data_model::*;
 
-use super::HitSelect;
+use super::{HitSelect, Overlays};
 
 /// New inspector window.
 pub(super) fn new(
@@ -42,6 +42,7 @@ pub(super) fn new(
     hit_select: impl Var<HitSelect>,
     adorn_selected: impl Var<bool>,
     select_focused: impl Var<bool>,
+    overlays: Overlays,
 ) -> WindowRoot {
     let parent = WINDOWS.vars(inspected).unwrap().parent().get().unwrap_or(inspected);
 
@@ -95,7 +96,7 @@ pub(super) fn new(
             let _ = selected_wgt.set(None);
         });
         child = Container! {
-            child_top = menu(hit_select, adorn_selected, select_focused, wgt_filter.clone()), 0;
+            child_top = menu(hit_select, adorn_selected, select_focused, overlays, wgt_filter.clone()), 0;
             child = Scroll! {
                 toggle::selector = toggle::Selector::single_opt(selected_wgt.clone());
                 child = tree_view(inspected_tree, wgt_filter.clone());
@@ -140,12 +141,12 @@ static_id! {
 
 context_var! {
     static TREE_ITEM_BKG_HOVERED_VAR: Rgba = rgb(0.21, 0.21, 0.21);
-    static TREE_ITEM_BKG_CHECKED_VAR: Rgba = rgb(0.29, 0.29, 0.29);
+    pub(super) static TREE_ITEM_BKG_CHECKED_VAR: Rgba = rgb(0.29, 0.29, 0.29);
     static TREE_ITEM_LINE_VAR: Rgba = rgb(0.21, 0.21, 0.21);
     static WIDGET_ID_COLOR_VAR: Rgba = colors::GRAY;
     static WIDGET_MACRO_COLOR_VAR: Rgba = colors::AZURE;
     static PROPERTY_COLOR_VAR: Rgba = colors::YELLOW;
-    static PROPERTY_VALUE_COLOR_VAR: Rgba = colors::ROSE.lighten(50.pct());
+    pub(super) static PROPERTY_VALUE_COLOR_VAR: Rgba = colors::ROSE.lighten(50.pct());
     static NEST_GROUP_COLOR_VAR: Rgba = colors::GRAY;
     static SELECTED_BKG_VAR: Rgba = rgb(0.15, 0.15, 0.15);
     static MENU_BKG_VAR: Rgba = rgb(0.13, 0.13, 0.13);
@@ -156,6 +157,7 @@ fn menu(
     hit_test_select: impl Var<HitSelect>,
     adorn_selected: impl Var<bool>,
     select_focused: impl Var<bool>,
+    overlays: Overlays,
     search: impl Var<Txt>,
 ) -> impl UiNode {
     Container! {
@@ -219,8 +221,24 @@ fn menu(
                     checked = var(false);
                     checked_popup = {
                         let screenshot_idle = var(true);
-                        wgt_fn!(screenshot_idle, |_| {
+                        wgt_fn!(screenshot_idle, overlays, |_| {
                             zng_wgt_menu::context::ContextMenu!(ui_vec![
+                                Toggle! {
+                                    child = Text!("Repaint Flashing");
+                                    tooltip = Tip!(Text!("highlight widgets that render in each frame"));
+                                    checked = overlays.repaints.clone();
+                                },
+                                Toggle! {
+                                    child = Text!("Overdraw Heatmap");
+                                    tooltip = Tip!(Text!("highlight areas covered by many rendered widgets"));
+                                    checked = overlays.overdraw.clone();
+                                },
+                                Toggle! {
+                                    child = Text!("Layout Thrash");
+                                    tooltip = Tip!(Text!("highlight widgets by measure and layout passes per second"));
+                                    checked = overlays.layout_thrash.clone();
+                                },
+                                zng_wgt_rule_line::hr::Hr!(),
                                 Button! {
                                     child = Text!("Save Screenshot");
                                     zng_wgt_menu::icon = zng_wgt::ICONS.get("save");
@@ -485,7 +503,7 @@ fn inspector_info_view(info: InspectedInfo) -> impl UiNode {
                     current_group = Some(p_info.group);
                 }
 
-                group_items.push(property_view(&info.actual_vars, &**args, p_info, *captured, user_assigned));
+                group_items.push(property_view(&info, &**args, p_info, *captured, user_assigned));
             }
             InstanceItem::Intrinsic { group, name } => {
                 if current_group.as_ref() != Some(group) {
@@ -540,7 +558,7 @@ fn value_background(value: &BoxedVar<Txt>) -> impl Var<Rgba> {
 }
 
 fn property_view(
-    actual_vars: &InspectorActualVars,
+    wgt_info: &InspectedInfo,
     args: &dyn PropertyArgs,
     info: PropertyInfo,
     captured: bool,
@@ -550,14 +568,23 @@ fn property_view(
         Text! {
             txt = info.name;
             font_color = PROPERTY_COLOR_VAR;
-            tooltip = Tip!(Text!(if captured {
-                "captured property"
-            } else {
-                "property"
+            tooltip = Tip!(Text!(match (captured, info.animatable.is_some()) {
+                (true, true) => "captured animatable property",
+                (true, false) => "captured property",
+                (false, true) => "animatable property",
+                (false, false) => "property",
             }));
         },
         Text!(" = "),
     ];
+    let actual_vars = &wgt_info.actual_vars;
+    let editor = |i: usize| {
+        if matches!(info.inputs[i].kind, InputKind::Var) {
+            super::value_editor::value_editor(wgt_info, info.id, i, args.var(i))
+        } else {
+            None
+        }
+    };
     if info.inputs.len() == 1 {
         let value = actual_vars.get_debug(info.id, 0).unwrap_or_else(|| args.live_debug(0));
         let flash = value_background(&value);
@@ -573,6 +600,9 @@ fn property_view(
             }))
         });
         children.push(Text!(";"));
+        if let Some(e) = editor(0) {
+            children.push(e);
+        }
     } else {
         children.push(Text!("{{\n"));
         for (i, input) in info.inputs.iter().enumerate() {
@@ -586,6 +616,9 @@ fn property_view(
                 font_color = PROPERTY_VALUE_COLOR_VAR;
                 background_color = flash;
             });
+            if let Some(e) = editor(i) {
+                children.push(e);
+            }
             children.push(Text!(",\n"));
         }
         children.push(Text!("}};"));
@@ -639,6 +672,34 @@ fn info_watchers(wgt: &InspectedWidget) -> impl UiNode {
         font_color = PROPERTY_VALUE_COLOR_VAR;
         background_color = flash;
     });
+
+    children.push(Text! {
+        txt = ",\npasses_per_sec: ";
+    });
+    let mut prev: Option<[u32; 3]> = None;
+    let value = merge_var!(TIMERS.interval(1.secs(), false), wgt.info(), move |_, i| {
+        let b = i.bounds_info();
+        let counts = [b.measure_count(), b.layout_count(), b.render_count()];
+        let r = match prev {
+            Some([m, l, r]) => formatx!(
+                "{{ measure: {}, layout: {}, render: {} }}",
+                counts[0].wrapping_sub(m),
+                counts[1].wrapping_sub(l),
+                counts[2].wrapping_sub(r)
+            ),
+            None => Txt::from_static("{ .. }"),
+        };
+        prev = Some(counts);
+        r
+    })
+    .boxed();
+    let flash = value_background(&value);
+    children.push(Text! {
+        txt = value;
+        font_color = PROPERTY_VALUE_COLOR_VAR;
+        background_color = flash;
+        tooltip = Tip!(Text!("measure, layout and render passes in the last second"));
+    });
     children.push(Text! {
         txt = ",";
     });
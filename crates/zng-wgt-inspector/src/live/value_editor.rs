@@ -0,0 +1,301 @@
+//! Property value editors.
+
+use std::sync::Arc;
+
+use zng_app::widget::builder::PropertyId;
+use zng_color::Rgba;
+use zng_ext_input::mouse::{MouseInputArgs, MouseMoveArgs};
+use zng_var::AnyVar;
+use zng_wgt::{margin, prelude::*, visibility, Wgt};
+use zng_wgt_button::Button;
+use zng_wgt_container::{padding, Container};
+use zng_wgt_fill::background_color;
+use zng_wgt_input::{
+    is_cap_mouse_pressed,
+    mouse::{on_mouse_down, on_mouse_move},
+    pointer_capture::capture_pointer,
+};
+use zng_wgt_size_offset::{height, size, width};
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_text::Text;
+use zng_wgt_text_input::TextInput;
+use zng_wgt_toggle::{self as toggle, Toggle};
+use zng_wgt_tooltip::{tooltip, Tip};
+
+use super::data_model::InspectedInfo;
+use super::inspector_window::{PROPERTY_VALUE_COLOR_VAR, TREE_ITEM_BKG_CHECKED_VAR};
+
+/// Editor for the property var input, if the input can be overridden and the value type has an editor.
+///
+/// The editor overrides the property var in the inspected widget instance.
+pub(super) fn value_editor(info: &InspectedInfo, property: PropertyId, member: usize, input: &dyn AnyVar) -> Option<BoxedUiNode> {
+    if !info.can_override(property, member) {
+        return None;
+    }
+
+    let overridden = var(info.is_overridden(property, member));
+    let editor = Override {
+        info: info.clone(),
+        property,
+        member,
+        overridden: overridden.clone(),
+    };
+
+    let e = if let Some(v) = downcast::<Txt>(input) {
+        txt_editor(v, editor.clone())
+    } else if let Some(v) = downcast::<bool>(input) {
+        bool_editor(v, editor.clone())
+    } else if let Some(v) = downcast::<Rgba>(input) {
+        rgba_editor(v, editor.clone())
+    } else if let Some(v) = downcast::<Length>(input) {
+        if !matches!(v.get(), Length::Dip(_)) {
+            return None;
+        }
+        number_editor(
+            v.map(|l| match l {
+                Length::Dip(d) => d.to_f32() as f64,
+                _ => 0.0,
+            })
+            .boxed(),
+            false,
+            true,
+            editor.clone().map(|v: f64| Length::Dip(Dip::new_f32(v as f32))),
+        )
+    } else {
+        number(input, &editor)?
+    };
+
+    Some(
+        Stack! {
+            direction = StackDirection::left_to_right();
+            spacing = 4;
+            margin = (0, 4);
+            children = ui_vec![
+                e,
+                Button! {
+                    style_fn = zng_wgt_button::LightStyle!();
+                    padding = (0, 3);
+                    visibility = overridden.map_into();
+                    tooltip = Tip!(Text!("reset override"));
+                    child = Text!("↺");
+                    on_click = hn!(|_| {
+                        editor.reset();
+                    });
+                },
+            ];
+        }
+        .boxed(),
+    )
+}
+
+fn number(input: &dyn AnyVar, editor: &Override) -> Option<BoxedUiNode> {
+    macro_rules! number {
+        ($($T:ty => $is_int:expr, $is_signed:expr;)+) => {
+            $(
+                if let Some(v) = downcast::<$T>(input) {
+                    return Some(number_editor(
+                        v.map(|&n| n as f64).boxed(),
+                        $is_int,
+                        $is_signed,
+                        editor.clone().map(|n: f64| n as $T),
+                    ));
+                }
+            )+
+        };
+    }
+    number! {
+        f32 => false, true;
+        f64 => false, true;
+        i8 => true, true;
+        i16 => true, true;
+        i32 => true, true;
+        i64 => true, true;
+        isize => true, true;
+        u8 => true, false;
+        u16 => true, false;
+        u32 => true, false;
+        u64 => true, false;
+        usize => true, false;
+    }
+    None
+}
+
+fn downcast<T: VarValue>(var: &dyn AnyVar) -> Option<BoxedVar<T>> {
+    if var.var_type_id() == std::any::TypeId::of::<T>() {
+        var.clone_any().double_boxed_any().downcast::<BoxedVar<T>>().ok().map(|v| *v)
+    } else {
+        None
+    }
+}
+
+/// Sets the override value of a property input.
+#[derive(Clone)]
+struct Override {
+    info: InspectedInfo,
+    property: PropertyId,
+    member: usize,
+    overridden: ArcVar<bool>,
+}
+impl Override {
+    fn set<T: VarValue>(&self, value: T) {
+        let ok = self.info.override_var(self.property, self.member, Box::new(value));
+        self.overridden.set(ok || self.info.is_overridden(self.property, self.member));
+    }
+
+    fn reset(&self) {
+        self.info.reset_override(self.property, self.member);
+        self.overridden.set(false);
+    }
+
+    fn map<I, T: VarValue>(self, map: impl Fn(I) -> T + Send + Sync + 'static) -> impl Fn(I) + Send + Sync + 'static {
+        move |i| self.set(map(i))
+    }
+}
+
+fn txt_editor(value: BoxedVar<Txt>, editor: Override) -> BoxedUiNode {
+    let txt = var(value.get());
+    value.bind(&txt).perm();
+    txt.hook(move |a| {
+        let txt = a.value();
+        if value.with(|v| v != txt) {
+            editor.set(txt.clone());
+        }
+        true
+    })
+    .perm();
+
+    TextInput! {
+        txt;
+        padding = (0, 2);
+        width = 160;
+    }
+    .boxed()
+}
+
+fn bool_editor(value: BoxedVar<bool>, editor: Override) -> BoxedUiNode {
+    let checked = var(value.get());
+    value.bind(&checked).perm();
+    checked
+        .hook(move |a| {
+            let c = *a.value();
+            if value.get() != c {
+                editor.set(c);
+            }
+            true
+        })
+        .perm();
+
+    Toggle! {
+        style_fn = toggle::CheckStyle!();
+        checked;
+    }
+    .boxed()
+}
+
+fn number_editor(value: BoxedVar<f64>, is_int: bool, is_signed: bool, set: impl Fn(f64) + Send + Sync + 'static) -> BoxedUiNode {
+    // slider range is relative to the initial value.
+    let init = value.get().abs();
+    let max = if is_int { (init * 2.0).max(10.0) } else { (init * 2.0).max(1.0) };
+    let min = if is_signed { -max } else { 0.0 };
+
+    let set = Arc::new(move |n: f64| set(if is_int { n.round() } else { n }));
+
+    let n = var(value.get());
+    value.bind(&n).perm();
+    n.hook(clmv!(set, |a| {
+        let n = *a.value();
+        if value.get() != n {
+            set(n);
+        }
+        true
+    }))
+    .perm();
+
+    Stack! {
+        direction = StackDirection::left_to_right();
+        spacing = 4;
+        children_align = Align::LEFT;
+        children = ui_vec![
+            TextInput! {
+                txt_parse = n.clone();
+                padding = (0, 2);
+                width = 60;
+            },
+            slider(n.boxed(), min, max, move |n| set(n)),
+        ];
+    }
+    .boxed()
+}
+
+fn rgba_editor(value: BoxedVar<Rgba>, editor: Override) -> BoxedUiNode {
+    let channel = |get: fn(&Rgba) -> f32, with: fn(Rgba, f32) -> Rgba| {
+        let ch = value.map(move |c| get(c) as f64).boxed();
+        slider(
+            ch,
+            0.0,
+            1.0,
+            clmv!(value, editor, |n| {
+                editor.set(with(value.get(), n as f32));
+            }),
+        )
+    };
+
+    Stack! {
+        direction = StackDirection::left_to_right();
+        spacing = 4;
+        children_align = Align::LEFT;
+        children = ui_vec![
+            Wgt! {
+                size = 1.em();
+                background_color = value.clone();
+            },
+            Stack! {
+                direction = StackDirection::top_to_bottom();
+                spacing = 2;
+                children = ui_vec![
+                    channel(|c| c.red, |c, n| c.with_red(n)),
+                    channel(|c| c.green, |c, n| c.with_green(n)),
+                    channel(|c| c.blue, |c, n| c.with_blue(n)),
+                    channel(|c| c.alpha, |c, n| c.with_alpha(n)),
+                ];
+            },
+        ];
+    }
+    .boxed()
+}
+
+/// Horizontal slider, calls `set` when pressed and dragged.
+fn slider(value: BoxedVar<f64>, min: f64, max: f64, set: impl Fn(f64) + Send + Sync + 'static) -> impl UiNode {
+    let set = Arc::new(move |args_x: Option<Px>| {
+        if let Some(x) = args_x {
+            let width = WIDGET.bounds().inner_size().width;
+            if width > Px(0) {
+                let f = (x.0 as f64 / width.0 as f64).clamp(0.0, 1.0);
+                set(min + f * (max - min));
+            }
+        }
+    });
+    let pressed = var(false);
+
+    Container! {
+        width = 100;
+        height = 0.7.em();
+        child_align = Align::FILL_LEFT;
+        background_color = TREE_ITEM_BKG_CHECKED_VAR;
+        capture_pointer = true;
+        is_cap_mouse_pressed = pressed.clone();
+        tooltip = Tip!(Text!(value.map_debug()));
+        child = Wgt! {
+            width = value.map(move |&v| Length::Factor(Factor(((v - min) / (max - min)).clamp(0.0, 1.0) as f32)));
+            background_color = PROPERTY_VALUE_COLOR_VAR;
+        };
+        on_mouse_down = hn!(set, |args: &MouseInputArgs| {
+            set(args.position_wgt().map(|p| p.x));
+        });
+        on_mouse_move = hn!(|args: &MouseMoveArgs| {
+            if pressed.get() {
+                set(args.position_wgt().map(|p| p.x));
+            }
+        });
+    }
+}
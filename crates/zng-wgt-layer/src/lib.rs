@@ -16,9 +16,11 @@ use zng_app::widget::border::CORNER_RADIUS_VAR;
 use zng_app::widget::info::WIDGET_INFO_CHANGED_EVENT;
 use zng_ext_input::mouse::MOUSE;
 use zng_ext_input::touch::TOUCH;
-use zng_ext_window::WINDOW_Ext as _;
+use zng_ext_window::{WINDOW_Ext as _, MONITORS};
 use zng_var::{animation, ContextInitHandle, ReadOnlyContextVar};
-use zng_wgt::prelude::*;
+use zng_wgt::{hit_test_mode, prelude::*, HitTestMode};
+use zng_wgt_container::Container;
+use zng_wgt_input::drag_drop::{DragPreviewArgs, DRAG_DROP};
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -167,12 +169,14 @@ impl LAYERS {
         let mut anchor_info = None;
         let mut offset = (PxPoint::zero(), PxPoint::zero());
         let mut cursor_bounds = None;
+        let mut cursor_place_rect = PxRect::zero();
         let mut interactivity = false;
+        let placement = var(AnchorPlacement::default());
 
         let transform_key = FrameValueKey::new_unique();
         let mut corner_radius_ctx_handle = None;
 
-        let widget = with_anchor_id(widget, anchor.clone().boxed());
+        let widget = with_anchor_id(widget, anchor.clone().boxed(), placement.read_only());
 
         let widget = match_widget(widget.boxed(), move |widget, op| match op {
             UiNodeOp::Init => {
@@ -432,6 +436,8 @@ impl LAYERS {
                                         cursor_bounds = None;
                                     }
 
+                                    cursor_place_rect = cursor_rect;
+
                                     let o = (place, origin);
                                     if offset != o {
                                         offset = o;
@@ -496,72 +502,16 @@ impl LAYERS {
                 if let Some((bounds_info, border_info)) = &anchor_info {
                     let mode = mode.get();
                     if !mode.visibility || bounds_info.rendered().is_some() {
-                        let mut push_reference_frame = |mut transform: PxTransform, is_translate_only: bool| {
-                            if mode.viewport_bound {
-                                transform = adjust_viewport_bound(transform, widget);
-                            }
-                            frame.push_reference_frame(
+                        let cursor = (cursor_place_rect, cursor_bounds);
+                        match anchor_transform(&mode, bounds_info, border_info, offset, cursor, widget, &placement) {
+                            Some((transform, is_translate_only)) => frame.push_reference_frame(
                                 transform_key.into(),
                                 transform_key.bind(transform, true),
                                 is_translate_only,
                                 false,
                                 |frame| widget.render(frame),
-                            );
-                        };
-
-                        match mode.transform {
-                            AnchorTransform::InnerOffset(_) => {
-                                let place_in_window = bounds_info.inner_transform().transform_point(offset.0).unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-
-                                push_reference_frame(PxTransform::from(offset), true);
-                            }
-                            AnchorTransform::InnerBorderOffset(_) => {
-                                let place_in_window = border_info
-                                    .inner_transform(bounds_info)
-                                    .transform_point(offset.0)
-                                    .unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-
-                                push_reference_frame(PxTransform::from(offset), true);
-                            }
-                            AnchorTransform::OuterOffset(_) => {
-                                let place_in_window = bounds_info.outer_transform().transform_point(offset.0).unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-
-                                push_reference_frame(PxTransform::from(offset), true);
-                            }
-                            AnchorTransform::Cursor { .. } | AnchorTransform::CursorOnce { .. } => {
-                                let (mut place, origin) = offset;
-
-                                if let Some(b) = cursor_bounds {
-                                    // transform `place` to bounds space, clamp to bounds, transform back to window space.
-                                    let transform = bounds_info.inner_transform();
-                                    if let Some(inverse) = transform.inverse() {
-                                        if let Some(p) = inverse.transform_point(place) {
-                                            let bound_p = PxPoint::new(p.x.clamp(b.min_x(), b.max_x()), p.y.clamp(b.min_y(), b.max_y()));
-                                            if p != bound_p {
-                                                if let Some(p) = transform.transform_point(bound_p) {
-                                                    place = p;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                let offset = place - origin;
-
-                                push_reference_frame(PxTransform::from(offset), true);
-                            }
-                            AnchorTransform::InnerTransform => {
-                                push_reference_frame(bounds_info.inner_transform(), false);
-                            }
-                            AnchorTransform::InnerBorderTransform => {
-                                push_reference_frame(border_info.inner_transform(bounds_info), false);
-                            }
-                            AnchorTransform::OuterTransform => {
-                                push_reference_frame(bounds_info.outer_transform(), false);
-                            }
-                            _ => widget.render(frame),
+                            ),
+                            None => widget.render(frame),
                         }
                     }
                 }
@@ -570,46 +520,12 @@ impl LAYERS {
                 if let Some((bounds_info, border_info)) = &anchor_info {
                     let mode = mode.get();
                     if !mode.visibility || bounds_info.rendered().is_some() {
-                        let mut with_transform = |mut transform: PxTransform| {
-                            if mode.viewport_bound {
-                                transform = adjust_viewport_bound(transform, widget);
+                        let cursor = (cursor_place_rect, cursor_bounds);
+                        match anchor_transform(&mode, bounds_info, border_info, offset, cursor, widget, &placement) {
+                            Some((transform, _)) => {
+                                update.with_transform(transform_key.update(transform, true), false, |update| widget.render_update(update))
                             }
-                            update.with_transform(transform_key.update(transform, true), false, |update| widget.render_update(update));
-                        };
-
-                        match mode.transform {
-                            AnchorTransform::InnerOffset(_) => {
-                                let place_in_window = bounds_info.inner_transform().transform_point(offset.0).unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-                                with_transform(PxTransform::from(offset));
-                            }
-                            AnchorTransform::InnerBorderOffset(_) => {
-                                let place_in_window = border_info
-                                    .inner_transform(bounds_info)
-                                    .transform_point(offset.0)
-                                    .unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-                                with_transform(PxTransform::from(offset));
-                            }
-                            AnchorTransform::OuterOffset(_) => {
-                                let place_in_window = bounds_info.outer_transform().transform_point(offset.0).unwrap_or_default();
-                                let offset = place_in_window - offset.1;
-                                with_transform(PxTransform::from(offset));
-                            }
-                            AnchorTransform::Cursor { .. } | AnchorTransform::CursorOnce { .. } => {
-                                let offset = offset.0 - offset.1;
-                                with_transform(PxTransform::from(offset));
-                            }
-                            AnchorTransform::InnerTransform => {
-                                with_transform(bounds_info.inner_transform());
-                            }
-                            AnchorTransform::InnerBorderTransform => {
-                                with_transform(border_info.inner_transform(bounds_info));
-                            }
-                            AnchorTransform::OuterTransform => {
-                                with_transform(bounds_info.outer_transform());
-                            }
-                            _ => widget.render_update(update),
+                            None => widget.render_update(update),
                         }
                     }
                 }
@@ -684,6 +600,16 @@ impl LAYERS {
         ANCHOR_ID_VAR.read_only()
     }
 
+    /// Gets a read-only var that tracks the actual placement in an anchored layered widget context.
+    ///
+    /// The placement is updated after each render, it can be used to position an arrow that points to
+    /// the anchor, see [`anchor_arrow`] for more details.
+    ///
+    /// [`anchor_arrow`]: fn@anchor_arrow
+    pub fn anchor_placement(&self) -> ReadOnlyContextVar<AnchorPlacement> {
+        ANCHOR_PLACEMENT_VAR.read_only()
+    }
+
     fn cleanup(&self) {
         WINDOW.with_state(|s| {
             s.req(*WINDOW_LAYERS_ID).items.retain(|n| n.is_widget());
@@ -691,30 +617,230 @@ impl LAYERS {
     }
 }
 
-fn adjust_viewport_bound(transform: PxTransform, widget: &mut impl UiNode) -> PxTransform {
-    let window_bounds = WINDOW.vars().actual_size_px().get();
-    let wgt_bounds = PxBox::from(
-        widget
-            .with_context(WidgetUpdateMode::Ignore, || WIDGET.bounds().outer_size())
-            .unwrap_or_else(PxSize::zero),
-    );
-    let wgt_bounds = transform.outer_transformed(wgt_bounds).unwrap_or_default();
+/// Computes the layered widget transform for the anchor `mode` and updates the `placement`.
+///
+/// Returns `None` for [`AnchorTransform::None`].
+fn anchor_transform(
+    mode: &AnchorMode,
+    bounds_info: &WidgetBoundsInfo,
+    border_info: &WidgetBorderInfo,
+    offset: (PxPoint, PxPoint),
+    (mut cursor_rect, cursor_bounds): (PxRect, Option<PxRect>),
+    widget: &mut impl UiNode,
+    placement: &ArcVar<AnchorPlacement>,
+) -> Option<(PxTransform, bool)> {
+    let layer_size = widget
+        .with_context(WidgetUpdateMode::Ignore, || WIDGET.bounds().outer_size())
+        .unwrap_or_else(PxSize::zero);
+    let area = collision_area(mode.work_area_bound);
+
+    let (transform, is_translate_only, p) = match mode.transform {
+        AnchorTransform::InnerOffset(_) => {
+            let (t, p) = offset_collision(
+                mode,
+                &bounds_info.inner_transform(),
+                PxRect::from_size(bounds_info.inner_size()),
+                offset,
+                layer_size,
+                area,
+            );
+            (t, true, p)
+        }
+        AnchorTransform::InnerBorderOffset(_) => {
+            let (t, p) = offset_collision(
+                mode,
+                &border_info.inner_transform(bounds_info),
+                PxRect::from_size(border_info.inner_size(bounds_info)),
+                offset,
+                layer_size,
+                area,
+            );
+            (t, true, p)
+        }
+        AnchorTransform::OuterOffset(_) => {
+            let (t, p) = offset_collision(
+                mode,
+                &bounds_info.outer_transform(),
+                PxRect::from_size(bounds_info.outer_size()),
+                offset,
+                layer_size,
+                area,
+            );
+            (t, true, p)
+        }
+        AnchorTransform::Cursor { .. } | AnchorTransform::CursorOnce { .. } => {
+            let (mut place, origin) = offset;
+
+            if let Some(b) = cursor_bounds {
+                // transform `place` to bounds space, clamp to bounds, transform back to window space.
+                let transform = bounds_info.inner_transform();
+                if let Some(inverse) = transform.inverse() {
+                    if let Some(p) = inverse.transform_point(place) {
+                        let bound_p = PxPoint::new(p.x.clamp(b.min_x(), b.max_x()), p.y.clamp(b.min_y(), b.max_y()));
+                        if p != bound_p {
+                            if let Some(p) = transform.transform_point(bound_p) {
+                                cursor_rect.origin += p - place;
+                                place = p;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let (t, p) = offset_collision(mode, &PxTransform::identity(), cursor_rect, (place, origin), layer_size, area);
+            (t, true, p)
+        }
+        AnchorTransform::InnerTransform => {
+            let (t, slide) = viewport_bound(mode, bounds_info.inner_transform(), layer_size, area);
+            (
+                t,
+                false,
+                AnchorPlacement {
+                    slide,
+                    ..Default::default()
+                },
+            )
+        }
+        AnchorTransform::InnerBorderTransform => {
+            let (t, slide) = viewport_bound(mode, border_info.inner_transform(bounds_info), layer_size, area);
+            (
+                t,
+                false,
+                AnchorPlacement {
+                    slide,
+                    ..Default::default()
+                },
+            )
+        }
+        AnchorTransform::OuterTransform => {
+            let (t, slide) = viewport_bound(mode, bounds_info.outer_transform(), layer_size, area);
+            (
+                t,
+                false,
+                AnchorPlacement {
+                    slide,
+                    ..Default::default()
+                },
+            )
+        }
+        AnchorTransform::None => return None,
+    };
+
+    if placement.with(|c| c != &p) {
+        placement.set(p);
+    }
+
+    Some((transform, is_translate_only))
+}
+
+/// Gets the area the layered widget must fit, in the window space.
+fn collision_area(work_area_bound: bool) -> PxRect {
+    let vars = WINDOW.vars();
+    let window_area = PxRect::from_size(vars.actual_size_px().get());
+    if work_area_bound {
+        if let Some(m) = vars.actual_monitor().get().and_then(|id| MONITORS.monitor(id)) {
+            let mut work_area = m.work_area().get();
+            work_area.origin -= vars.global_position().get().to_vector();
+            return window_area.intersection(&work_area).unwrap_or(window_area);
+        }
+    }
+    window_area
+}
 
-    let x_underflow = -wgt_bounds.min.x.min(Px(0));
-    let x_overflow = (wgt_bounds.max.x - window_bounds.width).max(Px(0));
-    let y_underflow = -wgt_bounds.min.y.min(Px(0));
-    let y_overflow = (wgt_bounds.max.y - window_bounds.height).max(Px(0));
+/// Computes the transform for the `place` and `origin` offset, flipping and sliding the placement to fit in the collision `area`.
+///
+/// The `place` point is in the `place_ref` space, that is transformed to window space by `place_transform`.
+fn offset_collision(
+    mode: &AnchorMode,
+    place_transform: &PxTransform,
+    place_ref: PxRect,
+    (mut place, mut origin): (PxPoint, PxPoint),
+    layer_size: PxSize,
+    area: PxRect,
+) -> (PxTransform, AnchorPlacement) {
+    let layer_bounds = |place: PxPoint, origin: PxPoint| {
+        let place_in_window = place_transform.transform_point(place).unwrap_or_default();
+        PxRect::new((place_in_window - origin).to_point(), layer_size)
+    };
+    fn overflow(min: Px, max: Px, area_min: Px, area_max: Px) -> Px {
+        (area_min - min).max(Px(0)) + (max - area_max).max(Px(0))
+    }
+
+    let mut r = AnchorPlacement::default();
+    let mut bounds = layer_bounds(place, origin);
+
+    if mode.flip {
+        let x_overflow = overflow(bounds.min_x(), bounds.max_x(), area.min_x(), area.max_x());
+        if x_overflow > Px(0) {
+            let flipped_place = PxPoint::new(place_ref.min_x() + place_ref.max_x() - place.x, place.y);
+            let flipped_origin = PxPoint::new(layer_size.width - origin.x, origin.y);
+            let flipped = layer_bounds(flipped_place, flipped_origin);
+            if overflow(flipped.min_x(), flipped.max_x(), area.min_x(), area.max_x()) < x_overflow {
+                place = flipped_place;
+                origin = flipped_origin;
+                bounds = flipped;
+                r.flip_x = true;
+            }
+        }
+
+        let y_overflow = overflow(bounds.min_y(), bounds.max_y(), area.min_y(), area.max_y());
+        if y_overflow > Px(0) {
+            let flipped_place = PxPoint::new(place.x, place_ref.min_y() + place_ref.max_y() - place.y);
+            let flipped_origin = PxPoint::new(origin.x, layer_size.height - origin.y);
+            let flipped = layer_bounds(flipped_place, flipped_origin);
+            if overflow(flipped.min_y(), flipped.max_y(), area.min_y(), area.max_y()) < y_overflow {
+                place = flipped_place;
+                origin = flipped_origin;
+                bounds = flipped;
+                r.flip_y = true;
+            }
+        }
+    }
+
+    let (transform, slide) = viewport_bound(mode, PxTransform::from(bounds.origin.to_vector()), layer_size, area);
+
+    r.slide = slide;
+    r.place = origin - slide;
+    r.side = if place.y >= place_ref.max_y() && origin.y <= Px(0) {
+        Some(AnchorSide::Top)
+    } else if place.y <= place_ref.min_y() && origin.y >= layer_size.height {
+        Some(AnchorSide::Bottom)
+    } else if place.x >= place_ref.max_x() && origin.x <= Px(0) {
+        Some(AnchorSide::Left)
+    } else if place.x <= place_ref.min_x() && origin.x >= layer_size.width {
+        Some(AnchorSide::Right)
+    } else {
+        None
+    };
+
+    (transform, r)
+}
+
+/// Slides the `transform` so that the layered widget is inside the collision `area`, if the `mode` is `viewport_bound`.
+fn viewport_bound(mode: &AnchorMode, transform: PxTransform, layer_size: PxSize, area: PxRect) -> (PxTransform, PxVector) {
+    if !mode.viewport_bound {
+        return (transform, PxVector::zero());
+    }
+
+    let wgt_bounds = transform.outer_transformed(PxBox::from(layer_size)).unwrap_or_default();
+    let area = area.to_box2d();
+
+    let x_underflow = (area.min.x - wgt_bounds.min.x).max(Px(0));
+    let x_overflow = (wgt_bounds.max.x - area.max.x).max(Px(0));
+    let y_underflow = (area.min.y - wgt_bounds.min.y).max(Px(0));
+    let y_overflow = (wgt_bounds.max.y - area.max.y).max(Px(0));
 
     let x = x_underflow - x_overflow;
     let y = y_underflow - y_overflow;
 
     let correction = PxVector::new(x, y);
 
-    transform.then_translate(correction.cast())
+    (transform.then_translate(correction.cast()), correction)
 }
 
-fn with_anchor_id(child: impl UiNode, anchor: BoxedVar<WidgetId>) -> impl UiNode {
+fn with_anchor_id(child: impl UiNode, anchor: BoxedVar<WidgetId>, placement: ReadOnlyArcVar<AnchorPlacement>) -> impl UiNode {
     let mut ctx = Some(Arc::new(anchor.map(|id| Some(*id))));
+    let mut placement_ctx = Some(Arc::new(placement.boxed()));
     let mut id = None;
     match_widget(child, move |c, op| {
         let mut is_deinit = false;
@@ -727,7 +853,10 @@ fn with_anchor_id(child: impl UiNode, anchor: BoxedVar<WidgetId>) -> impl UiNode
             }
             _ => {}
         }
-        ANCHOR_ID_VAR.with_context(id.clone().expect("node not inited"), &mut ctx, || c.op(op));
+        let ctx_id = id.clone().expect("node not inited");
+        ANCHOR_ID_VAR.with_context(ctx_id.clone(), &mut ctx, || {
+            ANCHOR_PLACEMENT_VAR.with_context(ctx_id, &mut placement_ctx, || c.op(op))
+        });
 
         if is_deinit {
             id = None;
@@ -737,6 +866,7 @@ fn with_anchor_id(child: impl UiNode, anchor: BoxedVar<WidgetId>) -> impl UiNode
 
 context_var! {
     static ANCHOR_ID_VAR: Option<WidgetId> = None;
+    static ANCHOR_PLACEMENT_VAR: AnchorPlacement = AnchorPlacement::default();
 }
 
 static_id! {
@@ -1238,9 +1368,29 @@ pub struct AnchorMode {
     /// After the `transform` and `size` are resolved the transform is adjusted so that the layered widget is
     /// fully visible in the window.
     ///
+    /// The widget slides along each axis that overflows the collision area, this is applied after [`flip`].
+    ///
     /// Has no effect if [`AnchorTransform::None`].
+    ///
+    /// [`flip`]: Self::flip
     pub viewport_bound: bool,
 
+    /// If the placement is mirrored to the opposite side of the anchor in each axis that overflows the collision area,
+    /// the flipped placement is only used if it overflows less.
+    ///
+    /// Only applies to the offset and cursor transforms, the [`AnchorPlacement`] of the widget indicates
+    /// if it was flipped.
+    pub flip: bool,
+
+    /// If the collision area used by [`flip`] and [`viewport_bound`] is also bound by the monitor work area,
+    /// that is, the monitor area not covered by system bars like the taskbar.
+    ///
+    /// If `false` the collision area is the window viewport only.
+    ///
+    /// [`flip`]: Self::flip
+    /// [`viewport_bound`]: Self::viewport_bound
+    pub work_area_bound: bool,
+
     /// If the widget is only layout if the anchor widget is not [`Collapsed`] and is only rendered
     /// if the anchor widget is rendered.
     ///
@@ -1267,6 +1417,8 @@ impl AnchorMode {
             min_size: AnchorSize::Window,
             max_size: AnchorSize::Window,
             viewport_bound: false,
+            flip: false,
+            work_area_bound: false,
             visibility: false,
             interactivity: false,
             corner_radius: false,
@@ -1281,6 +1433,8 @@ impl AnchorMode {
             max_size: AnchorSize::InnerSize,
             visibility: true,
             viewport_bound: false,
+            flip: false,
+            work_area_bound: false,
             interactivity: false,
             corner_radius: true,
         }
@@ -1294,6 +1448,8 @@ impl AnchorMode {
             max_size: AnchorSize::Window,
             visibility: true,
             viewport_bound: true,
+            flip: true,
+            work_area_bound: true,
             interactivity: true,
             corner_radius: false,
         }
@@ -1310,6 +1466,8 @@ impl AnchorMode {
             min_size: AnchorSize::Unbounded,
             max_size: AnchorSize::Window,
             viewport_bound: true,
+            flip: true,
+            work_area_bound: true,
             corner_radius: false,
             visibility: true,
             interactivity: false,
@@ -1327,12 +1485,33 @@ impl AnchorMode {
             min_size: AnchorSize::Unbounded,
             max_size: AnchorSize::Window,
             viewport_bound: true,
+            flip: true,
+            work_area_bound: true,
             corner_radius: false,
             visibility: true,
             interactivity: false,
         }
     }
 
+    /// Mode where the widget follows the cursor, used by drag&drop previews.
+    pub fn drag_preview() -> Self {
+        AnchorMode {
+            transform: AnchorTransform::Cursor {
+                offset: AnchorOffset::out_bottom_in_left(),
+                include_touch: false,
+                bounds: None,
+            },
+            min_size: AnchorSize::Unbounded,
+            max_size: AnchorSize::Window,
+            viewport_bound: false,
+            flip: false,
+            work_area_bound: false,
+            corner_radius: false,
+            visibility: false,
+            interactivity: false,
+        }
+    }
+
     /// Mode where the widget behaves like a context-menu anchored to the cursor.
     pub fn context_menu() -> Self {
         AnchorMode {
@@ -1344,6 +1523,8 @@ impl AnchorMode {
             min_size: AnchorSize::Unbounded,
             max_size: AnchorSize::Window,
             viewport_bound: true,
+            flip: true,
+            work_area_bound: true,
             corner_radius: false,
             visibility: true,
             interactivity: false,
@@ -1357,6 +1538,8 @@ impl AnchorMode {
             min_size: AnchorSize::Unbounded,
             max_size: AnchorSize::Window,
             viewport_bound: true,
+            flip: true,
+            work_area_bound: true,
             corner_radius: false,
             visibility: true,
             interactivity: false,
@@ -1412,6 +1595,18 @@ impl AnchorMode {
         self.viewport_bound = viewport_bound;
         self
     }
+
+    /// Returns the mode with `flip` set.
+    pub fn with_flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    /// Returns the mode with `work_area_bound` set.
+    pub fn with_work_area_bound(mut self, work_area_bound: bool) -> Self {
+        self.work_area_bound = work_area_bound;
+        self
+    }
 }
 impl Default for AnchorMode {
     /// Transform `InnerOffset` top-left, size infinite, copy visibility and corner-radius.
@@ -1421,6 +1616,8 @@ impl Default for AnchorMode {
             min_size: AnchorSize::Unbounded,
             max_size: AnchorSize::Unbounded,
             viewport_bound: false,
+            flip: false,
+            work_area_bound: false,
             visibility: true,
             interactivity: false,
             corner_radius: true,
@@ -1452,6 +1649,45 @@ impl_from_and_into_var! {
     }
 }
 
+/// Side of the layered widget that faces the anchor.
+///
+/// See [`AnchorPlacement::side`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AnchorSide {
+    /// Layered widget is below the anchor place point.
+    Top,
+    /// Layered widget is above the anchor place point.
+    Bottom,
+    /// Layered widget is after the anchor place point.
+    Left,
+    /// Layered widget is before the anchor place point.
+    Right,
+}
+
+/// Actual placement of an anchored layered widget, after collision adjustments.
+///
+/// The placement is available in the layered widget context using [`LAYERS.anchor_placement`], it updates after render.
+///
+/// [`LAYERS.anchor_placement`]: LAYERS::anchor_placement
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnchorPlacement {
+    /// If the placement was mirrored horizontally to avoid overflow.
+    pub flip_x: bool,
+    /// If the placement was mirrored vertically to avoid overflow.
+    pub flip_y: bool,
+    /// Correction applied to keep the layered widget inside the collision area, after flip.
+    pub slide: PxVector,
+    /// The anchor place point in the layered widget outer bounds space.
+    ///
+    /// This is the [`AnchorOffset::origin`] point adjusted by flip and slide, an arrow element can point to it.
+    pub place: PxPoint,
+    /// Side of the layered widget that faces the anchor place point.
+    ///
+    /// Is `None` if the layered widget is not fully outside the anchor place reference area or if the
+    /// transform is not an offset or cursor transform.
+    pub side: Option<AnchorSide>,
+}
+
 /// Node that implements the layers, must be inserted in the [`NestGroup::EVENT`] group by the window implementer.
 ///
 /// [`NestGroup::EVENT`]: zng_app::widget::builder::NestGroup::EVENT
@@ -1472,7 +1708,7 @@ pub fn layers_node(child: impl UiNode) -> impl UiNode {
     let sorting_layers = SortingList::new(layers, sort);
     let children = ui_vec![child].chain(sorting_layers);
 
-    match_node_list(children, move |c, op| match op {
+    let children = match_node_list(children, move |c, op| match op {
         UiNodeOp::Init => {
             WINDOW.with_state_mut(|mut s| {
                 s.set(*WINDOW_LAYERS_ID, LayersCtx { items: layered.clone() });
@@ -1508,6 +1744,48 @@ pub fn layers_node(child: impl UiNode) -> impl UiNode {
             c.children().1.render_update_all(update);
         }
         _ => {}
+    });
+    drag_preview_node(children)
+}
+
+/// Shows the preview of in-app drag operations started in the window.
+fn drag_preview_node(child: impl UiNode) -> impl UiNode {
+    let mut preview_id = None::<WidgetId>;
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&DRAG_DROP.dragging());
+        }
+        UiNodeOp::Deinit => {
+            preview_id = None;
+        }
+        UiNodeOp::Update { .. } => {
+            DRAG_DROP.dragging().with_new(|d| {
+                let d = d.as_ref().filter(|d| d.window_id == WINDOW.id() && !d.preview.is_nil());
+                match (d, preview_id) {
+                    (Some(d), None) => {
+                        let id = WidgetId::new_unique();
+                        preview_id = Some(id);
+                        let preview = d.preview.call(DragPreviewArgs { data: d.data.clone() });
+                        LAYERS.insert_anchored(
+                            LayerIndex::ADORNER,
+                            d.source,
+                            AnchorMode::drag_preview(),
+                            Container! {
+                                id;
+                                hit_test_mode = HitTestMode::Disabled;
+                                child = preview;
+                            },
+                        );
+                    }
+                    (None, Some(id)) => {
+                        preview_id = None;
+                        LAYERS.remove(id);
+                    }
+                    _ => {}
+                }
+            });
+        }
+        _ => {}
     })
 }
 
@@ -1569,6 +1847,74 @@ pub fn adorner(child: impl UiNode, adorner: impl UiNode) -> impl UiNode {
     adorner_fn(child, WidgetFn::singleton(adorner))
 }
 
+/// Arrow element that points to the anchor place point of the layered widget.
+///
+/// The `arrow` node is positioned outside the widget inner bounds, centered on the [`AnchorPlacement::place`] point
+/// on the [`AnchorPlacement::side`] that faces the anchor, it repositions when the placement flips or slides. The arrow
+/// is not rendered if there is no facing side. The arrow node can use [`LAYERS.anchor_placement`] to orient itself.
+///
+/// This property must be set on an anchored layered widget, like a popup or tooltip.
+///
+/// [`LAYERS.anchor_placement`]: LAYERS::anchor_placement
+#[property(FILL, default(NilUiNode))]
+pub fn anchor_arrow(child: impl UiNode, arrow: impl UiNode) -> impl UiNode {
+    let mut arrow_offset = PxVector::zero();
+    let transform_key = FrameValueKey::new_unique();
+
+    match_node_list(ui_vec![child, arrow], move |children, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var_layout(&ANCHOR_PLACEMENT_VAR);
+        }
+        UiNodeOp::Measure { wm, desired_size } => {
+            *desired_size = children.with_node(0, |n| n.measure(wm));
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            let size = children.with_node(0, |n| n.layout(wl));
+            let arrow_size = LAYOUT.with_constraints(PxConstraints2d::new_unbounded(), || children.with_node(1, |n| n.layout(wl)));
+
+            let o = ANCHOR_PLACEMENT_VAR.with(|p| {
+                // `place` is in the outer bounds space.
+                let place = p.place - WIDGET.bounds().inner_offset();
+                let max = (size - arrow_size).max(PxSize::zero());
+                let x = (place.x - arrow_size.width / Px(2)).max(Px(0)).min(max.width);
+                let y = (place.y - arrow_size.height / Px(2)).max(Px(0)).min(max.height);
+                match p.side {
+                    Some(AnchorSide::Top) => PxVector::new(x, -arrow_size.height),
+                    Some(AnchorSide::Bottom) => PxVector::new(x, size.height),
+                    Some(AnchorSide::Left) => PxVector::new(-arrow_size.width, y),
+                    Some(AnchorSide::Right) => PxVector::new(size.width, y),
+                    None => PxVector::zero(),
+                }
+            });
+            if arrow_offset != o {
+                arrow_offset = o;
+                WIDGET.render_update();
+            }
+
+            *final_size = size;
+        }
+        UiNodeOp::Render { frame } => {
+            children.with_node(0, |n| n.render(frame));
+            if ANCHOR_PLACEMENT_VAR.with(|p| p.side.is_some()) {
+                frame.push_reference_frame(
+                    transform_key.into(),
+                    transform_key.bind(arrow_offset.into(), false),
+                    true,
+                    false,
+                    |frame| children.with_node(1, |n| n.render(frame)),
+                );
+            }
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            children.with_node(0, |n| n.render_update(update));
+            update.with_transform(transform_key.update(arrow_offset.into(), false), false, |update| {
+                children.with_node(1, |n| n.render_update(update))
+            });
+        }
+        _ => {}
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1588,4 +1934,35 @@ mod tests {
         idx -= 2;
         assert_eq!(idx, m1);
     }
+
+    #[test]
+    pub fn offset_collision_flip() {
+        let mode = AnchorMode::popup(AnchorOffset::out_bottom());
+        let anchor = PxTransform::from(PxVector::new(Px(100), Px(180)));
+        let anchor_size = PxSize::new(Px(50), Px(20));
+        let layer_size = PxSize::new(Px(80), Px(40));
+        let area = PxRect::from_size(PxSize::new(Px(300), Px(200)));
+
+        let place = PxPoint::new(Px(25), Px(20));
+        let origin = PxPoint::new(Px(40), Px(0));
+
+        let (transform, placement) = offset_collision(&mode, &anchor, PxRect::from_size(anchor_size), (place, origin), layer_size, area);
+
+        assert!(!placement.flip_x);
+        assert!(placement.flip_y);
+        assert_eq!(Some(AnchorSide::Bottom), placement.side);
+        assert_eq!(PxPoint::new(Px(40), Px(40)), placement.place);
+        assert_eq!(Some(PxPoint::new(Px(85), Px(140))), transform.transform_point(PxPoint::zero()));
+
+        let (_, placement) = offset_collision(
+            &mode.with_flip(false),
+            &anchor,
+            PxRect::from_size(anchor_size),
+            (place, origin),
+            layer_size,
+            area,
+        );
+        assert!(!placement.flip_y);
+        assert_eq!(PxVector::new(Px(0), Px(-40)), placement.slide);
+    }
 }
@@ -75,6 +75,8 @@ impl Markdown {
 /// Implements the markdown parsing and view generation, configured by contextual properties.
 pub fn markdown_node(md: impl IntoVar<Txt>) -> impl UiNode {
     let md = md.into_var();
+    let md_var = md.clone().boxed();
+    let mut task_handles = VarHandles::dummy();
     match_node(NilUiNode.boxed(), move |c, op| match op {
         UiNodeOp::Init => {
             WIDGET
@@ -92,16 +94,20 @@ pub fn markdown_node(md: impl IntoVar<Txt>) -> impl UiNode {
                 .sub_var(&RULE_FN_VAR)
                 .sub_var(&BLOCK_QUOTE_FN_VAR)
                 .sub_var(&TABLE_FN_VAR)
+                .sub_var(&FOOTNOTE_REF_FN_VAR)
+                .sub_var(&FOOTNOTE_DEF_FN_VAR)
                 .sub_var(&TABLE_CELL_FN_VAR)
                 .sub_var(&PANEL_FN_VAR)
                 .sub_var(&IMAGE_RESOLVER_VAR)
-                .sub_var(&LINK_RESOLVER_VAR);
+                .sub_var(&LINK_RESOLVER_VAR)
+                .sub_var(&NODE_RESOLVER_VAR);
 
-            *c.child() = md.with(|md| markdown_view_fn(md.as_str())).boxed();
+            *c.child() = md.with(|md| markdown_view_fn(md.as_str(), &md_var, &mut task_handles)).boxed();
         }
         UiNodeOp::Deinit => {
             c.deinit();
             *c.child() = NilUiNode.boxed();
+            task_handles.clear();
         }
         UiNodeOp::Info { info } => {
             info.flag_meta(*MARKDOWN_INFO_ID);
@@ -124,14 +130,18 @@ pub fn markdown_node(md: impl IntoVar<Txt>) -> impl UiNode {
                 || RULE_FN_VAR.is_new()
                 || BLOCK_QUOTE_FN_VAR.is_new()
                 || TABLE_FN_VAR.is_new()
+                || FOOTNOTE_REF_FN_VAR.is_new()
+                || FOOTNOTE_DEF_FN_VAR.is_new()
                 || TABLE_CELL_FN_VAR.is_new()
                 || PANEL_FN_VAR.is_new()
                 || IMAGE_RESOLVER_VAR.is_new()
                 || LINK_RESOLVER_VAR.is_new()
+                || NODE_RESOLVER_VAR.is_new()
             {
                 c.delegated();
                 c.child().deinit();
-                *c.child() = md.with(|md| markdown_view_fn(md.as_str())).boxed();
+                task_handles.clear();
+                *c.child() = md.with(|md| markdown_view_fn(md.as_str(), &md_var, &mut task_handles)).boxed();
                 c.child().init();
                 WIDGET.update_info().layout().render();
             }
@@ -140,7 +150,7 @@ pub fn markdown_node(md: impl IntoVar<Txt>) -> impl UiNode {
     })
 }
 
-fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
+fn markdown_view_fn<'a>(md: &'a str, md_var: &BoxedVar<Txt>, task_handles: &mut VarHandles) -> impl UiNode {
     use pulldown_cmark::*;
     use resolvers::*;
     use view_fn::*;
@@ -168,13 +178,14 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
 
     let image_resolver = IMAGE_RESOLVER_VAR.get();
     let link_resolver = LINK_RESOLVER_VAR.get();
+    let node_resolver = NODE_RESOLVER_VAR.get();
 
     struct ListInfo {
         block_start: usize,
         inline_start: usize,
         first_num: Option<u64>,
         item_num: Option<u64>,
-        item_checked: Option<bool>,
+        item_checked: Option<BoxedVar<bool>>,
     }
     let mut blocks = vec![];
     let mut inlines = vec![];
@@ -194,7 +205,10 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
 
     let mut last_txt_end = '\0';
 
-    for item in Parser::new_with_broken_link_callback(md, Options::all(), Some(&mut |b: BrokenLink<'a>| Some((b.reference, "".into())))) {
+    for (item, range) in
+        Parser::new_with_broken_link_callback(md, Options::all(), Some(&mut |b: BrokenLink<'a>| Some((b.reference, "".into()))))
+            .into_offset_iter()
+    {
         match item {
             Event::Start(tag) => match tag {
                 Tag::Paragraph => {
@@ -275,7 +289,7 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
                     strong += 1;
                 }
                 Tag::Strikethrough => {
-                    strong += 1;
+                    strikethrough += 1;
                 }
                 Tag::Link {
                     link_type,
@@ -326,13 +340,17 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
                     if txt.ends_with('\n') {
                         txt.pop();
                     }
-                    blocks.push(code_block_view(CodeBlockFnArgs {
+                    let args = CodeBlockFnArgs {
                         lang: match kind {
                             CodeBlockKind::Indented => Txt::from_str(""),
                             CodeBlockKind::Fenced(l) => l.to_txt(),
                         },
                         txt: txt.into(),
-                    }))
+                    };
+                    blocks.push(match node_resolver.code_block_fn(&args.lang) {
+                        Some(f) => f(args),
+                        None => code_block_view(args),
+                    })
                 }
                 TagEnd::List(_) => {
                     if let Some(list) = list_info.pop() {
@@ -360,7 +378,7 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
                             num,
                             checked: list.item_checked.take(),
                         };
-                        list_items.push(list_item_bullet_view(bullet_args));
+                        list_items.push(list_item_bullet_view(bullet_args.clone()));
                         list_items.push(list_item_view(ListItemFnArgs {
                             bullet: bullet_args,
                             items: inlines.drain(list.inline_start..).collect(),
@@ -435,11 +453,16 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
                     }
                     if !inlines.is_empty() {
                         let items = inlines.drain(inlines_start..).collect();
-                        if let Some(lnk) = link_view.call_checked(LinkFnArgs {
+                        let args = LinkFnArgs {
                             url,
                             title: title.to_txt(),
                             items,
-                        }) {
+                        };
+                        let lnk = match node_resolver.link_fn(&args.url) {
+                            Some(f) => f.call_checked(args),
+                            None => link_view.call_checked(args),
+                        };
+                        if let Some(lnk) = lnk {
                             inlines.push(lnk);
                         }
                     }
@@ -550,7 +573,7 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
             }
             Event::TaskListMarker(c) => {
                 if let Some(l) = &mut list_info.last_mut() {
-                    l.item_checked = Some(c);
+                    l.item_checked = Some(task_list_var(c, range, md_var, task_handles));
                 }
             }
             Event::InlineMath(_) => {}
@@ -560,3 +583,27 @@ fn markdown_view_fn<'a>(md: &'a str) -> impl UiNode {
 
     PANEL_FN_VAR.get()(PanelFnArgs { items: blocks.into() })
 }
+
+/// New checked var for a task list item, if the markdown `md_var` can be modified the var writes back to the `[ ]` marker in `range`.
+fn task_list_var(checked: bool, range: std::ops::Range<usize>, md_var: &BoxedVar<Txt>, task_handles: &mut VarHandles) -> BoxedVar<bool> {
+    if md_var.capabilities().is_always_read_only() {
+        return LocalVar(checked).boxed();
+    }
+
+    let checked = var(checked);
+    let md_var = md_var.clone();
+    task_handles.push(checked.hook(move |args| {
+        let marker = if *args.value() { "[x]" } else { "[ ]" };
+        let range = range.clone();
+        let _ = md_var.modify(move |md| {
+            if let Some(i) = md.get(range.clone()).and_then(|m| m.find('[')) {
+                let start = range.start + i;
+                if md.get(start..start + 3).map(|m| m != marker).unwrap_or(false) {
+                    md.to_mut().to_mut().replace_range(start..start + 3, marker);
+                }
+            }
+        });
+        true
+    }));
+    checked.boxed()
+}
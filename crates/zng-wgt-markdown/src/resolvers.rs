@@ -19,7 +19,7 @@ use zng_wgt_size_offset::*;
 use zng_wgt_stack::{Stack, StackDirection};
 use zng_wgt_text::{self as text, Text};
 
-use super::Markdown;
+use super::{CodeBlockFnArgs, LinkFnArgs, Markdown};
 
 use path_absolutize::*;
 
@@ -34,6 +34,9 @@ context_var! {
 
     /// Scroll mode used by anchor links.
     pub static LINK_SCROLL_MODE_VAR: ScrollToMode = ScrollToMode::minimal(10);
+
+    /// Markdown custom node resolver.
+    pub static NODE_RESOLVER_VAR: NodeResolver = NodeResolver::default();
 }
 
 /// Markdown image resolver.
@@ -61,6 +64,16 @@ pub fn link_resolver(child: impl UiNode, resolver: impl IntoVar<LinkResolver>) -
     with_context_var(child, LINK_RESOLVER_VAR, resolver)
 }
 
+/// Markdown custom node resolver.
+///
+/// This can be used to replace code blocks of a language or links of an URL scheme with custom widgets.
+///
+/// Sets the [`NODE_RESOLVER_VAR`].
+#[property(CONTEXT, default(NODE_RESOLVER_VAR), widget_impl(Markdown))]
+pub fn node_resolver(child: impl UiNode, resolver: impl IntoVar<NodeResolver>) -> impl UiNode {
+    with_context_var(child, NODE_RESOLVER_VAR, resolver)
+}
+
 /// Scroll-to mode used by anchor links.
 #[property(CONTEXT, default(LINK_SCROLL_MODE_VAR), widget_impl(Markdown))]
 pub fn link_scroll_mode(child: impl UiNode, mode: impl IntoVar<ScrollToMode>) -> impl UiNode {
@@ -192,6 +205,102 @@ impl PartialEq for LinkResolver {
     }
 }
 
+/// Markdown custom node resolver.
+///
+/// Maps fenced code block languages and link URL schemes to widget functions that are used instead of
+/// the [`code_block_fn`] and [`link_fn`]. The language is matched with the first word of the code block info string and
+/// the scheme is matched with the URL after the [`link_resolver`], both ignoring ASCII case.
+///
+/// See [`NODE_RESOLVER_VAR`] for more details.
+///
+/// # Examples
+///
+/// The example replaces `mermaid` code blocks and `app:` links with custom widgets.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_markdown::*;
+/// # fn main() {
+/// let md = Markdown! {
+///     txt = "```mermaid\ngraph TD; A-->B;\n```\n\n[Settings](app:settings)";
+///     node_resolver = NodeResolver::default()
+///         .with_code_block("mermaid", wgt_fn!(|args: CodeBlockFnArgs| {
+///             // render diagram
+///             zng_wgt_text::Text!(args.txt)
+///         }))
+///         .with_link_scheme("app", wgt_fn!(|args: LinkFnArgs| {
+///             // in-app navigation
+///             zng_wgt_wrap::Wrap!(args.items)
+///         }));
+/// };
+/// # }
+/// ```
+///
+/// [`code_block_fn`]: fn@crate::code_block_fn
+/// [`link_fn`]: fn@crate::link_fn
+/// [`link_resolver`]: fn@crate::link_resolver
+#[derive(Clone, Default, PartialEq)]
+pub struct NodeResolver {
+    code_blocks: Vec<(Txt, WidgetFn<CodeBlockFnArgs>)>,
+    link_schemes: Vec<(Txt, WidgetFn<LinkFnArgs>)>,
+}
+impl NodeResolver {
+    /// Use `wgt_fn` for code blocks of the `lang`.
+    ///
+    /// Replaces the previous function set for the same language.
+    pub fn with_code_block(mut self, lang: impl Into<Txt>, wgt_fn: WidgetFn<CodeBlockFnArgs>) -> Self {
+        let lang = lang.into();
+        self.code_blocks.retain(|(l, _)| !l.eq_ignore_ascii_case(&lang));
+        self.code_blocks.push((lang, wgt_fn));
+        self
+    }
+
+    /// Use `wgt_fn` for links with URLs of the `scheme`.
+    ///
+    /// The `scheme` must not include the `:` separator. Replaces the previous function set for the same scheme.
+    pub fn with_link_scheme(mut self, scheme: impl Into<Txt>, wgt_fn: WidgetFn<LinkFnArgs>) -> Self {
+        let scheme = scheme.into();
+        self.link_schemes.retain(|(s, _)| !s.eq_ignore_ascii_case(&scheme));
+        self.link_schemes.push((scheme, wgt_fn));
+        self
+    }
+
+    /// If no custom node is set.
+    pub fn is_empty(&self) -> bool {
+        self.code_blocks.is_empty() && self.link_schemes.is_empty()
+    }
+
+    /// Get the custom code block function for the `lang` info string.
+    pub fn code_block_fn(&self, lang: &str) -> Option<WidgetFn<CodeBlockFnArgs>> {
+        let lang = lang.split([' ', ',', '\t']).next().unwrap_or_default();
+        if lang.is_empty() {
+            return None;
+        }
+        self.code_blocks
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(lang))
+            .map(|(_, f)| f.clone())
+    }
+
+    /// Get the custom link function for the `url` scheme.
+    pub fn link_fn(&self, url: &str) -> Option<WidgetFn<LinkFnArgs>> {
+        let (scheme, _) = url.split_once(':')?;
+        self.link_schemes
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(scheme))
+            .map(|(_, f)| f.clone())
+    }
+}
+impl fmt::Debug for NodeResolver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NodeResolver")
+            .field("code_blocks", &self.code_blocks.iter().map(|(l, _)| l).collect::<Vec<_>>())
+            .field("link_schemes", &self.link_schemes.iter().map(|(s, _)| s).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 event! {
     /// Event raised by markdown links when clicked.
     pub static LINK_EVENT: LinkArgs;
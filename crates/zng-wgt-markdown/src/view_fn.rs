@@ -116,7 +116,7 @@ pub struct ListFnArgs {
 }
 
 /// Arguments for a markdown list item bullet, check mark or number.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct ListItemBulletFnArgs {
     /// Nested list depth, starting from zero for items in the outer-list.
     pub depth: u32,
@@ -124,8 +124,13 @@ pub struct ListItemBulletFnArgs {
     /// If the list is *ordered*, the item number.
     pub num: Option<u64>,
 
-    /// If the list is checked. `Some(true)` is `[x]` and `Some(false)` is `[ ]`.
-    pub checked: Option<bool>,
+    /// If the item is a task, the checked state. `true` is `[x]` and `false` is `[ ]`.
+    ///
+    /// If the markdown [`txt`] is a read-write variable this variable can be set to toggle the task, the markdown
+    /// text is updated to the new marker. If the markdown text is read-only this variable is also read-only.
+    ///
+    /// [`txt`]: struct@crate::Markdown#method.txt
+    pub checked: Option<BoxedVar<bool>>,
 }
 
 /// Arguments for a markdown list item view.
@@ -393,6 +398,14 @@ pub fn table_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<TableFnArgs>>)
     with_context_var(child, TABLE_FN_VAR, wgt_fn)
 }
 
+/// Widget function that converts [`TableCellFnArgs`] to widgets.
+///
+/// Sets the [`TABLE_CELL_FN_VAR`].
+#[property(CONTEXT, default(TABLE_CELL_FN_VAR), widget_impl(Markdown))]
+pub fn table_cell_fn(child: impl UiNode, wgt_fn: impl IntoVar<WidgetFn<TableCellFnArgs>>) -> impl UiNode {
+    with_context_var(child, TABLE_CELL_FN_VAR, wgt_fn)
+}
+
 /// Widget function that converts [`PanelFnArgs`] to a widget.
 ///
 /// This generates the panel that contains all markdown blocks, it is the child of the [`Markdown!`] widget.
@@ -590,18 +603,31 @@ pub fn default_list_fn(args: ListFnArgs) -> impl UiNode {
 
 /// Default list item bullet, check mark or number view.
 ///
+/// Task check marks toggle on click if the [`ListItemBulletFnArgs::checked`] variable can be modified.
+///
 /// See [`LIST_ITEM_BULLET_FN_VAR`] for more details.
 pub fn default_list_item_bullet_fn(args: ListItemBulletFnArgs) -> impl UiNode {
     if let Some(checked) = args.checked {
+        let interactive = !checked.capabilities().is_always_read_only();
         Text! {
             grid::cell::at = grid::cell::AT_AUTO;
             align = Align::TOP;
             txt = " ✓ ";
-            font_color = FONT_COLOR_VAR.map(move |c| if checked { *c } else { c.transparent() });
+            font_color = merge_var!(FONT_COLOR_VAR, checked.clone(), |c, &checked| if checked { *c } else { c.transparent() });
             background_color = FONT_COLOR_VAR.map(|c| c.with_alpha(10.pct()));
             corner_radius = 4;
             scale = 0.8.fct();
             offset = (-(0.1.fct()), 0);
+
+            access_role = AccessRole::CheckBox;
+            access::checked = checked.map(|&c| Some(c));
+            zng_wgt_input::cursor = if interactive { zng_wgt_input::CursorIcon::Pointer } else { zng_wgt_input::CursorIcon::Default };
+            zng_wgt_input::gesture::on_click = hn!(|args: &ClickArgs| {
+                if interactive {
+                    args.propagation().stop();
+                    let _ = checked.set(!checked.get());
+                }
+            });
         }
         .boxed()
     } else if let Some(n) = args.num {
@@ -0,0 +1,343 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Masonry panel, properties and nodes.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use zng_app::widget::node::PanelListRange;
+use zng_layout::unit::{GridSpacing, PxGridSpacing};
+use zng_wgt::{
+    node::{with_index_len_node, with_index_node, with_rev_index_node},
+    prelude::*,
+};
+
+/// Masonry layout.
+///
+/// Children are laid out in columns of equal width, each child is placed in the shortest column at the
+/// time it is laid out, in the logical order, this minimizes the height imbalance of the columns for items of
+/// varied height, like image galleries and pin boards.
+///
+/// The number of columns is set by [`columns`], or if it is zero, the number of [`column_width`] columns that fit the
+/// available width.
+///
+/// # Virtualization
+///
+/// The panel needs to layout every child to find the columns, for large lists set the `lazy` property on each
+/// item with an estimated placeholder size, only the items visible in the parent scroll are inited and the
+/// placeholders reserve space for the others. Items outside the viewport are also not rendered.
+///
+/// [`columns`]: fn@columns
+/// [`column_width`]: fn@column_width
+#[widget($crate::Masonry {
+    ($children:expr) => {
+        children = $children;
+    };
+})]
+pub struct Masonry(WidgetBase);
+impl Masonry {
+    fn widget_intrinsic(&mut self) {
+        self.widget_builder().push_build_action(|wgt| {
+            let child = node(
+                wgt.capture_ui_node_list_or_empty(property_id!(Self::children)),
+                wgt.capture_var_or_default(property_id!(Self::columns)),
+                wgt.capture_var_or_else(property_id!(Self::column_width), || 200.dip()),
+                wgt.capture_var_or_default(property_id!(Self::spacing)),
+            );
+            wgt.set_child(child);
+        });
+    }
+}
+
+/// Masonry items.
+#[property(CHILD, capture, default(ui_vec![]), widget_impl(Masonry))]
+pub fn children(children: impl UiNodeList) {}
+
+/// Number of columns.
+///
+/// If set to `0` the number of columns is computed from the available width and the [`column_width`].
+///
+/// Is `0` by default.
+///
+/// [`column_width`]: fn@column_width
+#[property(LAYOUT, capture, widget_impl(Masonry))]
+pub fn columns(count: impl IntoVar<u32>) {}
+
+/// Minimum width of columns when [`columns`] is `0`.
+///
+/// The available width is divided in as many columns of at least this width as possible, the columns are then
+/// stretched to fill the width. If the available width is not bounded this is the exact column width. Relative
+/// lengths are relative to the available width.
+///
+/// Is `200.dip()` by default.
+///
+/// [`columns`]: fn@columns
+#[property(LAYOUT, capture, widget_impl(Masonry))]
+pub fn column_width(width: impl IntoVar<Length>) {}
+
+/// Space in between columns and items in the same column.
+#[property(LAYOUT, capture, widget_impl(Masonry))]
+pub fn spacing(spacing: impl IntoVar<GridSpacing>) {}
+
+/// Masonry node.
+///
+/// Can be used directly to layout widgets without declaring a masonry widget info. This node is the child
+/// of the `Masonry!` widget.
+pub fn node(
+    children: impl UiNodeList,
+    columns: impl IntoVar<u32>,
+    column_width: impl IntoVar<Length>,
+    spacing: impl IntoVar<GridSpacing>,
+) -> impl UiNode {
+    let children = PanelList::new(children).track_info_range(*PANEL_LIST_ID);
+    let columns = columns.into_var();
+    let column_width = column_width.into_var();
+    let spacing = spacing.into_var();
+
+    match_node_list(children, move |children, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var_layout(&columns)
+                .sub_var_layout(&column_width)
+                .sub_var_layout(&spacing);
+        }
+        UiNodeOp::Update { updates } => {
+            let mut any = false;
+            children.update_all(updates, &mut any);
+
+            if any {
+                WIDGET.layout();
+            }
+        }
+        UiNodeOp::Measure { wm, desired_size } => {
+            children.delegated();
+            let spacing = spacing.layout();
+            let cols = MasonryColumns::new(columns.get(), &column_width.get(), spacing);
+            let children = children.children();
+
+            let mut heights = vec![Px(0); cols.len];
+            LAYOUT.with_constraints(cols.child_constraints(), || {
+                children.for_each(|_, c, _| {
+                    let size = c.measure(wm);
+                    cols.place(&mut heights, size);
+                });
+            });
+
+            *desired_size = cols.panel_size(&heights);
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            children.delegated();
+            let spacing = spacing.layout();
+            let cols = MasonryColumns::new(columns.get(), &column_width.get(), spacing);
+            let children = children.children();
+
+            let mut heights = vec![Px(0); cols.len];
+            LAYOUT.with_constraints(cols.child_constraints(), || {
+                // parallel layout widgets
+                children.layout_each(
+                    wl,
+                    |_, c, o, wl| {
+                        if c.is_widget() {
+                            let (size, define_ref_frame) = wl.with_child(|wl| c.layout(wl));
+                            o.define_reference_frame = define_ref_frame;
+                            size
+                        } else {
+                            PxSize::zero()
+                        }
+                    },
+                    |_, _| PxSize::zero(),
+                );
+
+                // layout other nodes and place everything in the shortest column.
+                children.for_each(|_, c, o| {
+                    let size = match c.with_context(WidgetUpdateMode::Ignore, || WIDGET.bounds().outer_size()) {
+                        Some(wgt_size) => wgt_size,
+                        None => {
+                            let (size, define_ref_frame) = wl.with_child(|wl| c.layout(wl));
+                            o.define_reference_frame = define_ref_frame;
+                            size
+                        }
+                    };
+
+                    match cols.place(&mut heights, size) {
+                        Some(offset) => o.child_offset = offset,
+                        None => {
+                            o.child_offset = PxVector::zero();
+                            o.define_reference_frame = false;
+                        }
+                    }
+                });
+            });
+
+            children.commit_data().request_render();
+
+            *final_size = cols.panel_size(&heights);
+        }
+        _ => {}
+    })
+}
+
+/// Columns resolved for a layout pass.
+struct MasonryColumns {
+    len: usize,
+    width: Px,
+    spacing: PxGridSpacing,
+}
+impl MasonryColumns {
+    fn new(columns: u32, column_width: &Length, spacing: PxGridSpacing) -> Self {
+        let c = LAYOUT.constraints().x;
+        let min_width = column_width.layout_x().max(Px(1));
+
+        let (len, width) = match c.fill_or_exact().or(c.max()) {
+            Some(available) => {
+                let len = if columns == 0 {
+                    ((available + spacing.column).0 / (min_width + spacing.column).0).max(1) as usize
+                } else {
+                    columns as usize
+                };
+                let gaps = spacing.column * Px(len as i32 - 1);
+                (len, Px((available - gaps).0 / len as i32).max(Px(0)))
+            }
+            None => (columns.max(1) as usize, min_width),
+        };
+
+        Self { len, width, spacing }
+    }
+
+    fn child_constraints(&self) -> PxConstraints2d {
+        PxConstraints2d::new_unbounded().with_new_exact_x(self.width)
+    }
+
+    /// Place the item in the shortest column, returns the item offset or `None` if the item is collapsed.
+    fn place(&self, heights: &mut [Px], size: PxSize) -> Option<PxVector> {
+        if size.is_empty() {
+            return None;
+        }
+
+        // the first shortest column.
+        let (i, y) = heights.iter().copied().enumerate().min_by_key(|(_, h)| *h).unwrap();
+        let y = if y > Px(0) { y + self.spacing.row } else { y };
+        heights[i] = y + size.height;
+
+        let x = (self.width + self.spacing.column) * Px(i as i32);
+        Some(PxVector::new(x, y))
+    }
+
+    fn panel_size(&self, heights: &[Px]) -> PxSize {
+        let width = self.width * Px(self.len as i32) + self.spacing.column * Px(self.len as i32 - 1);
+        let height = heights.iter().copied().max().unwrap_or_default();
+        LAYOUT.constraints().fill_size_or(PxSize::new(width, height))
+    }
+}
+
+static_id! {
+    static ref PANEL_LIST_ID: StateId<PanelListRange>;
+}
+
+/// Get the child index in the parent masonry.
+///
+/// The child index is zero-based.
+#[property(CONTEXT)]
+pub fn get_index(child: impl UiNode, state: impl IntoVar<usize>) -> impl UiNode {
+    let state = state.into_var();
+    with_index_node(child, *PANEL_LIST_ID, move |id| {
+        let _ = state.set(id.unwrap_or(0));
+    })
+}
+
+/// Get the child index and number of children.
+#[property(CONTEXT)]
+pub fn get_index_len(child: impl UiNode, state: impl IntoVar<(usize, usize)>) -> impl UiNode {
+    let state = state.into_var();
+    with_index_len_node(child, *PANEL_LIST_ID, move |id_len| {
+        let _ = state.set(id_len.unwrap_or((0, 0)));
+    })
+}
+
+/// Get the child index, starting from the last child at `0`.
+#[property(CONTEXT)]
+pub fn get_rev_index(child: impl UiNode, state: impl IntoVar<usize>) -> impl UiNode {
+    let state = state.into_var();
+    with_rev_index_node(child, *PANEL_LIST_ID, move |id| {
+        let _ = state.set(id.unwrap_or(0));
+    })
+}
+
+/// Extension methods for [`WidgetInfo`] that may represent a [`Masonry!`] instance.
+///
+/// [`Masonry!`]: struct@Masonry
+/// [`WidgetInfo`]: zng_app::widget::info::WidgetInfo
+pub trait WidgetInfoMasonryExt {
+    /// Gets the masonry children, if this widget is a [`Masonry!`] instance.
+    ///
+    /// [`Masonry!`]: struct@Masonry
+    fn masonry_children(&self) -> Option<zng_app::widget::info::iter::Children>;
+}
+impl WidgetInfoMasonryExt for WidgetInfo {
+    fn masonry_children(&self) -> Option<zng_app::widget::info::iter::Children> {
+        PanelListRange::get(self, *PANEL_LIST_ID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(len: usize) -> MasonryColumns {
+        MasonryColumns {
+            len,
+            width: Px(100),
+            spacing: PxGridSpacing::new(Px(10), Px(5)),
+        }
+    }
+
+    #[test]
+    pub fn place_shortest_column() {
+        let cols = columns(3);
+        let mut heights = vec![Px(0); cols.len];
+
+        let offsets: Vec<_> = [50, 30, 40, 20, 10]
+            .into_iter()
+            .map(|h| cols.place(&mut heights, PxSize::new(Px(100), Px(h))).unwrap())
+            .collect();
+
+        assert_eq!(
+            offsets,
+            [
+                PxVector::new(Px(0), Px(0)),
+                PxVector::new(Px(110), Px(0)),
+                PxVector::new(Px(220), Px(0)),
+                PxVector::new(Px(110), Px(35)),
+                PxVector::new(Px(220), Px(45)),
+            ]
+        );
+        assert_eq!(heights, [Px(50), Px(55), Px(55)]);
+    }
+
+    #[test]
+    pub fn place_tie_first_column() {
+        let cols = columns(2);
+        let mut heights = vec![Px(20), Px(20)];
+
+        let offset = cols.place(&mut heights, PxSize::new(Px(100), Px(10))).unwrap();
+
+        assert_eq!(offset, PxVector::new(Px(0), Px(25)));
+        assert_eq!(heights, [Px(35), Px(20)]);
+    }
+
+    #[test]
+    pub fn place_collapsed() {
+        let cols = columns(2);
+        let mut heights = vec![Px(20), Px(0)];
+
+        assert!(cols.place(&mut heights, PxSize::zero()).is_none());
+        assert_eq!(heights, [Px(20), Px(0)]);
+
+        let offset = cols.place(&mut heights, PxSize::new(Px(100), Px(10))).unwrap();
+        assert_eq!(offset, PxVector::new(Px(110), Px(0)));
+    }
+}
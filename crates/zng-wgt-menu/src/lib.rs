@@ -15,6 +15,7 @@ use colors::BASE_COLOR_VAR;
 use zng_ext_font::FontNames;
 use zng_ext_input::{focus::FOCUS, mouse::ClickMode};
 use zng_ext_l10n::lang;
+use zng_ext_window::{AppMenu, WINDOW_Ext as _};
 use zng_wgt::{align, base_color, is_disabled, margin, prelude::*};
 use zng_wgt_access::{access_role, AccessRole};
 use zng_wgt_button::BUTTON;
@@ -88,6 +89,41 @@ pub fn panel_fn(child: impl UiNode, panel: impl IntoVar<WidgetFn<zng_wgt_panel::
     with_context_var(child, PANEL_FN_VAR, panel)
 }
 
+/// Export the menu to the operating system global menu bar.
+///
+/// The `menu` is set in the [`WindowVars::app_menu`] and the [`Menu!`] is collapsed while the menu is exported, on platforms
+/// without a global menu bar the in-window menu is shown. Note that the exported menu is defined by commands, not by the
+/// widget children, the items should match the [`SubMenu!`] headers and command buttons of the in-window menu.
+///
+/// [`WindowVars::app_menu`]: zng_ext_window::WindowVars::app_menu
+/// [`Menu!`]: struct@Menu
+/// [`SubMenu!`]: struct@sub::SubMenu
+#[property(CONTEXT, default(AppMenu::default()), widget_impl(Menu))]
+pub fn global_menu(child: impl UiNode, menu: impl IntoVar<AppMenu>) -> impl UiNode {
+    let menu = menu.into_var();
+    let is_exported = var(false);
+    let child = zng_wgt::visibility(
+        child,
+        is_exported.map(|&e| if e { Visibility::Collapsed } else { Visibility::Visible }),
+    );
+    let mut _handles = VarHandles::dummy();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            let vars = WINDOW.vars();
+            vars.app_menu().set_from(&menu);
+            _handles.push(menu.bind(&vars.app_menu()));
+            is_exported.set_from(&vars.is_app_menu_exported());
+            _handles.push(vars.is_app_menu_exported().bind(&is_exported));
+        }
+        UiNodeOp::Deinit => {
+            _handles.clear();
+            WINDOW.vars().app_menu().set(AppMenu::default());
+            is_exported.set(false);
+        }
+        _ => {}
+    })
+}
+
 /// Default [`Menu!`] style.
 ///
 /// Gives the button a *menu-item* look.
@@ -0,0 +1,338 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Native child window widget for use with `zng-view` view-process.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use zng_app::{
+    view_process::{raw_events::RAW_EXTENSION_EVENT, VIEW_PROCESS, VIEW_PROCESS_INITED_EVENT},
+    widget::info::VISIBILITY_CHANGED_EVENT,
+};
+use zng_ext_input::focus::{FocusRequest, FOCUS, FOCUS_CHANGED_EVENT};
+use zng_ext_window::{WINDOWS, WINDOW_LOAD_EVENT};
+use zng_view_api::api_extension::{ApiExtensionId, ApiExtensionName, ApiExtensionPayload};
+use zng_wgt::prelude::*;
+use zng_wgt_input::focus::FocusableMix;
+
+/// Hosts a native child window positioned and clipped by the widget.
+///
+/// The native child is created by a [`host`] registered in the view-process, the default view-process implementation
+/// provides the `zng_view::native_child` extension point for this. The child is a native window (HWND, NSView, X11 window)
+/// parented to the widget window, it is moved and clipped to match the widget bounds every render and is hidden
+/// when the widget is hidden or collapsed.
+///
+/// The widget is focusable, keyboard focus is forwarded to the native child when the widget is focused and the
+/// widget is focused when the native child receives focus.
+///
+/// Note that the native child is composited by the operating system on top of all window content,
+/// any widget that overlaps it, including popups, is rendered behind the native child.
+///
+/// Renders nothing if the view-process does not implement the [`host`] extension.
+///
+/// [`host`]: fn@host
+#[widget($crate::NativeChild)]
+pub struct NativeChild(FocusableMix<WidgetBase>);
+impl NativeChild {
+    fn widget_intrinsic(&mut self) {
+        self.widget_builder().push_build_action(|wgt| {
+            let host = wgt.capture_var_or_default(property_id!(host));
+            wgt.set_child(node(host, ApiExtensionPayload::empty, |_| {}));
+        });
+    }
+}
+
+/// Name of the native child host extension registered in the view-process.
+#[property(CONTEXT, capture, widget_impl(NativeChild))]
+pub fn host(name: impl IntoVar<Txt>) {}
+
+/// Request sent to the view-process extension.
+#[derive(serde::Serialize)]
+enum NativeChildRequest {
+    Open {
+        id: u64,
+        bounds: PxRect,
+        clip: PxRect,
+        visible: bool,
+        payload: ApiExtensionPayload,
+    },
+    SetBounds {
+        id: u64,
+        bounds: PxRect,
+        clip: PxRect,
+    },
+    SetVisible {
+        id: u64,
+        visible: bool,
+    },
+    Focus {
+        id: u64,
+    },
+    Command {
+        id: u64,
+        payload: ApiExtensionPayload,
+    },
+    Close {
+        id: u64,
+    },
+}
+
+/// Response from the view-process extension.
+#[derive(serde::Deserialize)]
+enum NativeChildResponse {
+    Opened,
+    Done,
+    Command(ApiExtensionPayload),
+    NotFound,
+    Error(String),
+}
+
+/// Event from the view-process extension.
+#[derive(serde::Deserialize)]
+enum NativeChildEvent {
+    Focused { id: u64 },
+    TabOut { id: u64, reverse: bool },
+    Custom { id: u64, payload: ApiExtensionPayload },
+}
+
+static_id! {
+    // Extension of the native child opened by the widget.
+    static ref NATIVE_CHILD_EXT_ID: StateId<ApiExtensionId>;
+}
+
+fn request(ext_id: ApiExtensionId, request: NativeChildRequest) -> Option<NativeChildResponse> {
+    match WINDOWS.view_window_extension(WINDOW.id(), ext_id, &request) {
+        Ok(r) => Some(r),
+        Err(e) => {
+            tracing::debug!("native child request failed, {e}");
+            None
+        }
+    }
+}
+
+/// Send a custom command to the native child hosted by the current widget.
+///
+/// The `payload` is received by the view-process host, the response is the host response. Returns `None` if
+/// the native child is not open or the request failed.
+///
+/// Must be called in the context of a widget that uses [`node`].
+pub fn command(payload: ApiExtensionPayload) -> Option<ApiExtensionPayload> {
+    let ext_id = WIDGET.get_state(*NATIVE_CHILD_EXT_ID)?;
+    match request(
+        ext_id,
+        NativeChildRequest::Command {
+            id: WIDGET.id().get(),
+            payload,
+        },
+    )? {
+        NativeChildResponse::Command(r) => Some(r),
+        _ => None,
+    }
+}
+
+/// Native child host node.
+///
+/// The `host` is the name of the view-process extension that creates the native child, see [`NativeChild!`] for more details.
+/// The `open_payload` closure is called to generate the custom payload sent to the host on open, the `on_event` closure
+/// is called for each custom event sent by the host.
+///
+/// Custom commands can be send to the host using [`command`].
+///
+/// [`NativeChild!`]: struct@NativeChild
+pub fn node(
+    host: impl IntoVar<Txt>,
+    mut open_payload: impl FnMut() -> ApiExtensionPayload + Send + 'static,
+    mut on_event: impl FnMut(ApiExtensionPayload) + Send + 'static,
+) -> impl UiNode {
+    let host = host.into_var();
+
+    struct Opened {
+        ext_id: ApiExtensionId,
+        bounds: PxRect,
+        clip: PxRect,
+        visible: bool,
+    }
+    let mut opened = None::<Opened>;
+
+    fn close(opened: &mut Option<Opened>) {
+        if let Some(o) = opened.take() {
+            WIDGET.with_state_mut(|mut s| {
+                if let state_map::StateMapEntry::Occupied(e) = s.entry(*NATIVE_CHILD_EXT_ID) {
+                    e.remove();
+                }
+            });
+            let _ = request(o.ext_id, NativeChildRequest::Close { id: WIDGET.id().get() });
+        }
+    }
+    fn set_visible(opened: &mut Option<Opened>, visible: bool) {
+        if let Some(o) = opened {
+            if o.visible != visible {
+                o.visible = visible;
+                let r = request(
+                    o.ext_id,
+                    NativeChildRequest::SetVisible {
+                        id: WIDGET.id().get(),
+                        visible,
+                    },
+                );
+                if !matches!(r, Some(NativeChildResponse::Done)) {
+                    *opened = None;
+                    WIDGET.layout();
+                }
+            }
+        }
+    }
+    fn set_bounds(opened: &mut Option<Opened>, transform: &PxTransform) {
+        if let Some(o) = opened {
+            let bounds = transform
+                .outer_transformed(PxBox::from_size(WIDGET.bounds().inner_size()))
+                .unwrap_or_default()
+                .to_rect();
+            // clip by ancestors, like scroll viewports.
+            let mut clip = bounds;
+            for a in WIDGET.info().ancestors() {
+                clip = clip.intersection(&a.inner_bounds()).unwrap_or_default();
+            }
+
+            if o.bounds != bounds || o.clip != clip {
+                o.bounds = bounds;
+                o.clip = clip;
+                let r = request(
+                    o.ext_id,
+                    NativeChildRequest::SetBounds {
+                        id: WIDGET.id().get(),
+                        bounds,
+                        clip,
+                    },
+                );
+                if !matches!(r, Some(NativeChildResponse::Done)) {
+                    *opened = None;
+                    WIDGET.layout();
+                }
+            }
+        }
+    }
+
+    match_node_leaf(move |op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&host)
+                .sub_event(&VIEW_PROCESS_INITED_EVENT)
+                .sub_event(&WINDOW_LOAD_EVENT)
+                .sub_event(&RAW_EXTENSION_EVENT)
+                .sub_event(&FOCUS_CHANGED_EVENT)
+                .sub_event(&VISIBILITY_CHANGED_EVENT);
+        }
+        UiNodeOp::Deinit => {
+            close(&mut opened);
+        }
+        UiNodeOp::Event { update } => {
+            if VIEW_PROCESS_INITED_EVENT.has(update) {
+                // native children are lost on respawn.
+                opened = None;
+                WIDGET.layout();
+            } else if let Some(args) = WINDOW_LOAD_EVENT.on(update) {
+                if args.window_id == WINDOW.id() && opened.is_none() {
+                    WIDGET.layout();
+                }
+            } else if let Some(args) = RAW_EXTENSION_EVENT.on(update) {
+                if let Some(o) = &opened {
+                    if args.extension_id == o.ext_id {
+                        let id = WIDGET.id();
+                        match args.payload.deserialize::<NativeChildEvent>() {
+                            Ok(NativeChildEvent::Focused { id: i }) if i == id.get() => {
+                                if !FOCUS.focused().with(|f| matches!(f, Some(p) if p.widget_id() == id)) {
+                                    FOCUS.focus_widget(id, false);
+                                }
+                            }
+                            Ok(NativeChildEvent::TabOut { id: i, reverse }) if i == id.get() => {
+                                FOCUS.focus(FocusRequest::direct(id, true).with_force_window_focus());
+                                if reverse {
+                                    FOCUS.focus_prev();
+                                } else {
+                                    FOCUS.focus_next();
+                                }
+                            }
+                            Ok(NativeChildEvent::Custom { id: i, payload }) if i == id.get() => {
+                                on_event(payload);
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::error!("invalid native child event, {e}"),
+                        }
+                    }
+                }
+            } else if let Some(args) = FOCUS_CHANGED_EVENT.on(update) {
+                if args.is_focus(WIDGET.id()) {
+                    if let Some(o) = &opened {
+                        let _ = request(o.ext_id, NativeChildRequest::Focus { id: WIDGET.id().get() });
+                    }
+                }
+            } else if let Some(args) = VISIBILITY_CHANGED_EVENT.on(update) {
+                if let Some(v) = args.changed.get(&WIDGET.id()) {
+                    set_visible(&mut opened, v.is_visible());
+                }
+            }
+        }
+        UiNodeOp::Update { .. } => {
+            if host.is_new() {
+                close(&mut opened);
+                WIDGET.layout();
+            }
+        }
+        UiNodeOp::Measure { desired_size, .. } => {
+            *desired_size = LAYOUT.constraints().fill_size();
+        }
+        UiNodeOp::Layout { final_size, .. } => {
+            *final_size = LAYOUT.constraints().fill_size();
+
+            if opened.is_none() {
+                let name = match ApiExtensionName::new(host.get()) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        tracing::error!("invalid native child host name, {e}");
+                        return;
+                    }
+                };
+                if let Some(ext_id) = VIEW_PROCESS.extension_id(name.clone()).ok().flatten() {
+                    // open hidden, the bounds are only known on render.
+                    match request(
+                        ext_id,
+                        NativeChildRequest::Open {
+                            id: WIDGET.id().get(),
+                            bounds: PxRect::zero(),
+                            clip: PxRect::zero(),
+                            visible: false,
+                            payload: open_payload(),
+                        },
+                    ) {
+                        Some(NativeChildResponse::Opened) => {
+                            WIDGET.set_state(*NATIVE_CHILD_EXT_ID, ext_id);
+                            opened = Some(Opened {
+                                ext_id,
+                                bounds: PxRect::zero(),
+                                clip: PxRect::zero(),
+                                visible: false,
+                            });
+                            WIDGET.render();
+                        }
+                        Some(NativeChildResponse::Error(e)) => tracing::error!("native child host {name:?} failed to open, {e}"),
+                        Some(_) => tracing::error!("unexpected response from native child host {name:?}"),
+                        None => {}
+                    }
+                }
+            }
+        }
+        UiNodeOp::Render { frame } => {
+            set_bounds(&mut opened, frame.transform());
+            set_visible(&mut opened, frame.is_visible());
+        }
+        UiNodeOp::RenderUpdate { update } => {
+            set_bounds(&mut opened, update.transform());
+        }
+        _ => {}
+    })
+}
@@ -5,9 +5,12 @@
 //! [`ScrollToTopCommand`]: crate::widgets::scroll::cmd::ScrollToTopCommand
 //! [`ScrollToLeftmostCommand`]: crate::widgets::scroll::cmd::ScrollToLeftmostCommand
 
+use std::time::Duration;
+
 use super::*;
 use zng_app::event::{CommandArgs, CommandParam};
 use zng_ext_window::WINDOWS;
+use zng_var::animation::easing::{EasingStep, EasingTime};
 use zng_wgt::ICONS;
 
 command! {
@@ -181,7 +184,8 @@ command! {
     /// This command requires a parameter to work, it can be a [`ScrollToRequest`] instance, or a
     /// [`ScrollToTarget`], or the [`WidgetId`] of a descendant of the scroll, or a [`Rect`] resolved in the scrollable space.
     ///
-    /// You can use the [`scroll_to`] function to invoke this command in all parent scrolls automatically.
+    /// You can use the [`scroll_to`] function to invoke this command in all parent scrolls automatically, the
+    /// function chains the request so that nested scrolls showcase the target.
     ///
     /// [`WidgetId`]: zng_wgt::prelude::WidgetId
     /// [`Rect`]: zng_wgt::prelude::Rect
@@ -349,6 +353,13 @@ pub struct ScrollToRequest {
     /// Note that the viewport size can change due to a scrollbar visibility changing, this size
     /// change is not accounted for when calculating minimal.
     pub zoom: Option<Factor>,
+
+    /// Optional smooth scrolling override.
+    ///
+    /// If not set the [`smooth_scrolling`] config of the scroll is used.
+    ///
+    /// [`smooth_scrolling`]: fn@crate::smooth_scrolling
+    pub smooth: Option<SmoothScrolling>,
 }
 impl ScrollToRequest {
     /// Pack the request into a command parameter.
@@ -373,6 +384,7 @@ impl ScrollToRequest {
                 },
                 mode: ScrollToMode::default(),
                 zoom: None,
+                smooth: None,
             })
         }
     }
@@ -410,6 +422,12 @@ pub enum ScrollToMode {
         widget_point: Point,
         /// A point relative to the scroll viewport.
         scroll_point: Point,
+        /// Extra margin around the widget that is kept visible.
+        ///
+        /// If the widget inner rect plus margin fits the viewport the offset is adjusted minimally so that it
+        /// is fully visible, this only has an effect for points that are not the center.
+        #[serde(default)]
+        margin: SideOffsets,
     },
 }
 impl ScrollToMode {
@@ -451,6 +469,34 @@ impl ScrollToMode {
         ScrollToMode::Center {
             widget_point: widget_point.into(),
             scroll_point: scroll_point.into(),
+            margin: SideOffsets::zero(),
+        }
+    }
+
+    /// Replace the margin around the widget that is kept visible.
+    pub fn with_margin(mut self, margin: impl Into<SideOffsets>) -> Self {
+        match &mut self {
+            ScrollToMode::Minimal { margin: m } => *m = margin.into(),
+            ScrollToMode::Center { margin: m, .. } => *m = margin.into(),
+        }
+        self
+    }
+
+    /// Scroll to using a custom smooth scrolling animation.
+    pub fn animated(self, duration: Duration, easing: impl Fn(EasingTime) -> EasingStep + Send + Sync + 'static) -> ScrollToOptions {
+        ScrollToOptions {
+            mode: self,
+            smooth: Some(SmoothScrolling::new(duration, easing)),
+        }
+    }
+
+    /// Scroll to without animation, even if [`smooth_scrolling`] is enabled.
+    ///
+    /// [`smooth_scrolling`]: fn@crate::smooth_scrolling
+    pub fn instant(self) -> ScrollToOptions {
+        ScrollToOptions {
+            mode: self,
+            smooth: Some(SmoothScrolling::disabled()),
         }
     }
 }
@@ -464,37 +510,74 @@ impl_from_and_into_var! {
     fn from(some: ScrollToMode) -> Option<ScrollToMode>;
 }
 
+/// Scroll-to mode and animation.
+///
+/// See [`scroll_to`] for more details.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScrollToOptions {
+    /// How much the scroll position will change to showcase the target widget.
+    pub mode: ScrollToMode,
+    /// Optional smooth scrolling override.
+    ///
+    /// If not set the [`smooth_scrolling`] config of each scroll is used.
+    ///
+    /// [`smooth_scrolling`]: fn@crate::smooth_scrolling
+    pub smooth: Option<SmoothScrolling>,
+}
+impl From<ScrollToMode> for ScrollToOptions {
+    fn from(mode: ScrollToMode) -> Self {
+        ScrollToOptions { mode, smooth: None }
+    }
+}
+
 /// Scroll all parent [`is_scroll`] widgets of `target` so that it becomes visible.
 ///
 /// This function is a helper for searching for the `target` in all windows and sending [`SCROLL_TO_CMD`] for all required scroll widgets.
 /// Does nothing if the `target` is not found.
 ///
+/// The inner-most scroll scrolls to the `target` using the `mode`, each outer scroll scrolls to the target
+/// position inside the inner scroll viewport after the inner scroll, so nested scrolls chain to showcase the target.
+/// Each scroll also excludes its [`scroll_to_padding`] from the viewport, so the target is not covered by sticky headers.
+///
+/// The `options` can be a [`ScrollToMode`] or [`ScrollToOptions`] that also overrides the animation, for example,
+/// `ScrollToMode::center().with_margin(20).animated(300.ms(), easing::linear)`.
+///
 /// [`is_scroll`]: WidgetInfoExt::is_scroll
-pub fn scroll_to(target: impl ScrollToTargetProvider, mode: impl Into<ScrollToMode>) {
-    scroll_to_impl(target.find_target(), mode.into(), None)
+/// [`scroll_to_padding`]: fn@crate::scroll_to_padding
+pub fn scroll_to(target: impl ScrollToTargetProvider, options: impl Into<ScrollToOptions>) {
+    scroll_to_impl(target.find_target(), options.into(), None)
 }
 
 /// Like [`scroll_to`], but also adjusts the zoom scale.
-pub fn scroll_to_zoom(target: impl ScrollToTargetProvider, mode: impl Into<ScrollToMode>, zoom: impl Into<Factor>) {
-    scroll_to_impl(target.find_target(), mode.into(), Some(zoom.into()))
+pub fn scroll_to_zoom(target: impl ScrollToTargetProvider, options: impl Into<ScrollToOptions>, zoom: impl Into<Factor>) {
+    scroll_to_impl(target.find_target(), options.into(), Some(zoom.into()))
 }
 
-fn scroll_to_impl(target: Option<WidgetInfo>, mode: ScrollToMode, zoom: Option<Factor>) {
+fn scroll_to_impl(target: Option<WidgetInfo>, options: ScrollToOptions, zoom: Option<Factor>) {
     if let Some(target) = target {
-        let mut t = target.id();
-        for a in target.ancestors() {
-            if a.is_scroll() {
-                SCROLL_TO_CMD.scoped(a.id()).notify_param(ScrollToRequest {
-                    target: ScrollToTarget::Descendant(t),
-                    mode: mode.clone(),
+        if let Some(scroll) = target.ancestors().find(|a| a.is_scroll()) {
+            // the inner-most scroll forwards the request to the parent scrolls after it applies it
+            SCROLL_TO_CMD.scoped(scroll.id()).notify_param(ChainedScrollTo {
+                request: ScrollToRequest {
+                    target: ScrollToTarget::Descendant(target.id()),
+                    mode: options.mode,
                     zoom,
-                });
-                t = a.id();
-            }
+                    smooth: options.smooth,
+                },
+                window_rect: None,
+            });
         }
     }
 }
 
+/// Scroll-to request that is forwarded to the parent scroll after it is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChainedScrollTo {
+    pub request: ScrollToRequest,
+    /// Future target bounds in the window space, replaces the request target.
+    pub window_rect: Option<PxRect>,
+}
+
 /// Scroll at the direction and velocity (dip/sec) until the end or another auto scroll request.
 ///
 /// Zero stops auto scrolling.
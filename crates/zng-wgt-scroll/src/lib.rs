@@ -18,6 +18,7 @@ pub mod cmd;
 pub mod node;
 pub mod scrollbar;
 pub mod thumb;
+pub mod virtual_list;
 
 mod scroll_properties;
 pub use scroll_properties::*;
@@ -32,6 +33,8 @@ pub use lazy_prop::*;
 pub use scrollbar::Scrollbar;
 #[doc(inline)]
 pub use thumb::Thumb;
+#[doc(inline)]
+pub use virtual_list::VirtualList;
 
 use zng_ext_input::focus::FocusScopeOnFocus;
 use zng_wgt_container::{child_align, Container};
@@ -138,6 +141,7 @@ fn on_build(wgt: &mut WidgetBuilding) {
         let child = node::scroll_touch_node(child);
         let child = node::zoom_commands_node(child);
         let child = node::auto_scroll_node(child);
+        let child = node::drag_scroll_node(child);
         node::scroll_wheel_node(child)
     });
 
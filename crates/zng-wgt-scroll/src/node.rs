@@ -1,7 +1,7 @@
 //! UI nodes used for building the scroll widget.
 //!
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use parking_lot::Mutex;
 use zng_app::{
@@ -15,11 +15,13 @@ use zng_ext_input::{
     mouse::{ButtonState, MouseButton, MouseScrollDelta, MOUSE_INPUT_EVENT, MOUSE_WHEEL_EVENT},
     touch::{TouchPhase, TOUCH_TRANSFORM_EVENT},
 };
+use zng_ext_window::WINDOW_Ext as _;
 use zng_wgt::prelude::{
     gradient::{ExtendMode, RenderGradientStop},
     *,
 };
 use zng_wgt_container::Container;
+use zng_wgt_input::drag_drop::DRAG_DROP;
 use zng_wgt_layer::{AnchorMode, LayerIndex, LAYERS};
 
 use super::cmd::*;
@@ -38,6 +40,7 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
     let mut content_scale = 1.fct();
     let mut auto_hide_extra = PxSideOffsets::zero();
     let mut last_render_offset = PxVector::zero();
+    let mut rubber_band_offset = PxVector::zero();
     let mut scroll_info = None;
     let mut scroll_info = move || {
         scroll_info
@@ -52,6 +55,9 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
                 .sub_var_layout(&SCROLL_VERTICAL_OFFSET_VAR)
                 .sub_var_layout(&SCROLL_HORIZONTAL_OFFSET_VAR)
                 .sub_var_layout(&SCROLL_SCALE_VAR)
+                .sub_var_layout(&OVERSCROLL_VERTICAL_OFFSET_VAR)
+                .sub_var_layout(&OVERSCROLL_HORIZONTAL_OFFSET_VAR)
+                .sub_var_layout(&SCROLL_PHYSICS_VAR)
                 .sub_var_layout(&child_align);
         }
 
@@ -184,19 +190,47 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
 
             let mut ct_offset = PxVector::zero();
 
+            // content requested an offset shift to stay anchored, convert the previous offset plus shift to the new factor.
+            let anchor = SCROLL.take_anchor_offset();
+
             if mode.contains(ScrollMode::VERTICAL) && content_size.height > vp_size.height {
-                let v_offset = SCROLL_VERTICAL_OFFSET_VAR.get();
+                let mut v_offset = SCROLL_VERTICAL_OFFSET_VAR.get();
+                if anchor.y != Px(0) {
+                    let max = content_size.height - viewport_size.height;
+                    let y = -content_offset.y + anchor.y;
+                    v_offset = (y.0 as f32 / max.0 as f32).clamp(0.0, 1.0).fct();
+                    SCROLL_VERTICAL_OFFSET_VAR.set(v_offset).unwrap();
+                }
                 ct_offset.y = (viewport_size.height - content_size.height) * v_offset;
             } else {
                 ct_offset.y = align_offset.y;
             }
             if mode.contains(ScrollMode::HORIZONTAL) && content_size.width > vp_size.width {
-                let h_offset = SCROLL_HORIZONTAL_OFFSET_VAR.get();
+                let mut h_offset = SCROLL_HORIZONTAL_OFFSET_VAR.get();
+                if anchor.x != Px(0) {
+                    let max = content_size.width - viewport_size.width;
+                    let x = -content_offset.x + anchor.x;
+                    h_offset = (x.0 as f32 / max.0 as f32).clamp(0.0, 1.0).fct();
+                    SCROLL_HORIZONTAL_OFFSET_VAR.set(h_offset).unwrap();
+                }
                 ct_offset.x = (viewport_size.width - content_size.width) * h_offset;
             } else {
                 ct_offset.x = align_offset.x;
             }
 
+            let band = if SCROLL_PHYSICS_VAR.with(|p| p.overscroll == OverscrollMode::RubberBand) {
+                PxVector::new(
+                    rubber_band(OVERSCROLL_HORIZONTAL_OFFSET_VAR.get(), viewport_size.width),
+                    rubber_band(OVERSCROLL_VERTICAL_OFFSET_VAR.get(), viewport_size.height),
+                )
+            } else {
+                PxVector::zero()
+            };
+            if band != rubber_band_offset {
+                rubber_band_offset = band;
+                WIDGET.render_update();
+            }
+
             if ct_offset != content_offset {
                 content_offset = ct_offset;
 
@@ -255,10 +289,11 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
             culling_rect.min.x -= auto_hide_extra.left;
             let culling_rect = frame.transform().outer_transformed(culling_rect).unwrap_or(culling_rect).to_rect();
 
+            let offset = content_offset + rubber_band_offset;
             let transform = if content_scale != 1.fct() {
-                PxTransform::scale(content_scale.0, content_scale.0).then_translate(content_offset.cast())
+                PxTransform::scale(content_scale.0, content_scale.0).then_translate(offset.cast())
             } else {
-                content_offset.into()
+                offset.into()
             };
             frame.push_reference_frame(binding_key.into(), binding_key.bind(transform, true), true, false, |frame| {
                 frame.with_auto_hide_rect(culling_rect, |frame| {
@@ -269,10 +304,11 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
         UiNodeOp::RenderUpdate { update } => {
             scroll_info().set_viewport_transform(*update.transform());
 
+            let offset = content_offset + rubber_band_offset;
             let transform = if content_scale != 1.fct() {
-                PxTransform::scale(content_scale.0, content_scale.0).then_translate(content_offset.cast())
+                PxTransform::scale(content_scale.0, content_scale.0).then_translate(offset.cast())
             } else {
-                content_offset.into()
+                offset.into()
             };
             update.with_transform(binding_key.update(transform, true), false, |update| {
                 child.render_update(update);
@@ -282,6 +318,13 @@ pub fn viewport(child: impl UiNode, mode: impl IntoVar<ScrollMode>, child_align:
     })
 }
 
+/// Content displacement for the `overscroll` factor, the resistance increases up to about a third of the `viewport`.
+fn rubber_band(overscroll: Factor, viewport: Px) -> Px {
+    let f = overscroll.0.clamp(-1.0, 1.0);
+    let d = (1.0 - 1.0 / (f.abs() * 0.55 + 1.0)) * viewport.0 as f32;
+    Px((-d * f.signum()) as i32)
+}
+
 /// Create a node that generates and presents the [vertical scrollbar].
 ///
 /// [vertical scrollbar]: VERTICAL_SCROLLBAR_FN_VAR
@@ -742,7 +785,7 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                                             scroll = !is_large_visible_v && !is_large_visible_h;
                                         }
                                         if scroll {
-                                            scroll_to = Some((Rect::from(target_bounds), mode, None, false));
+                                            scroll_to = Some(PendingScrollTo::new(Rect::from(target_bounds), mode, None, false));
                                             WIDGET.layout();
                                         }
                                     }
@@ -753,7 +796,28 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                 }
             } else if let Some(args) = SCROLL_TO_CMD.scoped(self_id).on(update) {
                 // event send to us and enabled
-                if let Some(request) = ScrollToRequest::from_args(args) {
+                let mut chain = false;
+                let mut request = ScrollToRequest::from_args(args);
+                if let Some(c) = args.enabled_param::<ChainedScrollTo>() {
+                    if !args.propagation().is_stopped() {
+                        chain = true;
+                        if let Some(rect) = c.window_rect {
+                            // forwarded by an inner scroll
+                            let r = &c.request;
+                            let mut p = PendingScrollTo::new(Rect::from(rect), r.mode.clone(), r.zoom, false);
+                            p.smooth = r.smooth.clone();
+                            p.chain = true;
+                            scroll_to = Some(p);
+                            scroll_to_from_cmd = true;
+                            WIDGET.layout();
+                            args.propagation().stop();
+                            request = None;
+                        } else {
+                            request = Some(c.request.clone());
+                        }
+                    }
+                }
+                if let Some(request) = request {
                     // has unhandled request
                     let tree = WINDOW.info();
                     match request.target {
@@ -763,7 +827,11 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                                 if let Some(us) = target.ancestors().find(|w| w.id() == self_id) {
                                     // target is descendant
                                     if us.is_scroll() {
-                                        scroll_to = Some((Rect::from(target.inner_bounds()), request.mode, request.zoom, false));
+                                        let mut p =
+                                            PendingScrollTo::new(Rect::from(target.inner_bounds()), request.mode, request.zoom, false);
+                                        p.smooth = request.smooth;
+                                        p.chain = chain;
+                                        scroll_to = Some(p);
                                         scroll_to_from_cmd = true;
                                         WIDGET.layout();
 
@@ -773,7 +841,10 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                             }
                         }
                         ScrollToTarget::Rect(rect) => {
-                            scroll_to = Some((rect, request.mode, request.zoom, true));
+                            let mut p = PendingScrollTo::new(rect, request.mode, request.zoom, true);
+                            p.smooth = request.smooth;
+                            p.chain = chain;
+                            scroll_to = Some(p);
                             scroll_to_from_cmd = true;
                             WIDGET.layout();
 
@@ -786,7 +857,15 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
         UiNodeOp::Layout { wl, final_size } => {
             *final_size = child.layout(wl);
 
-            if let Some((bounds, mode, mut zoom, in_content)) = scroll_to.take() {
+            if let Some(PendingScrollTo {
+                bounds,
+                mode,
+                mut zoom,
+                in_content,
+                smooth,
+                chain,
+            }) = scroll_to.take()
+            {
                 scroll_to_from_cmd = false;
                 let tree = WINDOW.info();
                 let us = tree.get(WIDGET.id()).unwrap();
@@ -830,11 +909,23 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                     }
                     // target bounds is now in the content space at future scale
 
-                    let viewport_size = scroll_info.viewport_size();
+                    let full_viewport_size = scroll_info.viewport_size();
+
+                    // exclude padding from the viewport
+                    let padding = LAYOUT.with_constraints(PxConstraints2d::new_fill_size(full_viewport_size), || {
+                        SCROLL_TO_PADDING_VAR.layout()
+                    });
+                    let padding_offset = PxVector::new(padding.left, padding.top);
+                    let viewport_size = PxSize::new(
+                        (full_viewport_size.width - padding.horizontal()).max(Px(1)),
+                        (full_viewport_size.height - padding.vertical()).max(Px(1)),
+                    );
+                    bounds.origin -= padding_offset;
+                    let current_bounds = current_bounds.translate(-padding_offset);
 
                     let mut offset = PxVector::splat(Px::MAX);
 
-                    match mode {
+                    match &mode {
                         ScrollToMode::Minimal { margin } => {
                             // add minimal margin at new scale to target bounds
                             let scaled_margin = LAYOUT.with_constraints(PxConstraints2d::new_fill_size(bounds.size), || margin.layout());
@@ -907,6 +998,7 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                         ScrollToMode::Center {
                             widget_point,
                             scroll_point,
+                            margin,
                         } => {
                             // find the two points
                             let default = (bounds.size / Px(2)).to_vector().to_point();
@@ -917,6 +1009,16 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                                 LAYOUT.with_constraints(PxConstraints2d::new_fill_size(viewport_size), || scroll_point.layout_dft(default));
 
                             offset = (widget_point + bounds.origin.to_vector()) - scroll_point;
+
+                            // offset minimal so that the widget plus margin is visible, if it fits
+                            let margin = LAYOUT.with_constraints(PxConstraints2d::new_fill_size(bounds.size), || margin.layout());
+                            let bounds = inflate_margin(bounds, margin);
+                            if bounds.size.height <= viewport_size.height {
+                                offset.y = offset.y.max(bounds.max_y() - viewport_size.height).min(bounds.origin.y);
+                            }
+                            if bounds.size.width <= viewport_size.width {
+                                offset.x = offset.x.max(bounds.max_x() - viewport_size.width).min(bounds.origin.x);
+                            }
                         }
                     }
 
@@ -925,19 +1027,47 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
                     if let Some(scale) = zoom {
                         content_size *= scale / rendered_scale;
                     }
-                    let max_scroll = content_size - viewport_size;
+                    let max_scroll = content_size - full_viewport_size;
 
                     // apply
+                    let smooth = smooth.unwrap_or_else(|| SMOOTH_SCROLLING_VAR.get());
                     if let Some(scale) = zoom {
-                        SCROLL.chase_zoom(|_| scale);
+                        SCROLL.chase_zoom_impl(smooth.clone(), |_| scale);
                     }
+                    // offset that will be rendered after the scroll
+                    let mut final_offset = -rendered_offset;
                     if offset.y != Px::MAX && max_scroll.height > Px(0) {
+                        final_offset.y = offset.y.max(Px(0)).min(max_scroll.height);
                         let offset_y = offset.y.0 as f32 / max_scroll.height.0 as f32;
-                        SCROLL.chase_vertical(|_| offset_y.fct());
+                        SCROLL.chase_with(true, smooth.clone(), |_| offset_y.fct());
                     }
                     if offset.x != Px::MAX && max_scroll.width > Px(0) {
+                        final_offset.x = offset.x.max(Px(0)).min(max_scroll.width);
                         let offset_x = offset.x.0 as f32 / max_scroll.width.0 as f32;
-                        SCROLL.chase_horizontal(|_| offset_x.fct());
+                        SCROLL.chase_with(false, smooth.clone(), |_| offset_x.fct());
+                    }
+
+                    if chain {
+                        if let Some(parent) = us.ancestors().find(|a| a.is_scroll()) {
+                            // future target bounds in the viewport, clipped
+                            let target = bounds.translate(padding_offset - final_offset);
+                            let target = target.intersection(&PxRect::from_size(full_viewport_size)).unwrap_or(target);
+                            let target = scroll_info
+                                .viewport_transform()
+                                .outer_transformed(target.to_box2d())
+                                .map(|b| b.to_rect())
+                                .unwrap_or(target);
+
+                            SCROLL_TO_CMD.scoped(parent.id()).notify_param(ChainedScrollTo {
+                                request: ScrollToRequest {
+                                    target: ScrollToTarget::Descendant(us.id()),
+                                    mode,
+                                    zoom,
+                                    smooth: Some(smooth),
+                                },
+                                window_rect: Some(target),
+                            });
+                        }
                     }
                 }
             }
@@ -945,6 +1075,27 @@ pub fn scroll_to_node(child: impl UiNode) -> impl UiNode {
         _ => {}
     })
 }
+struct PendingScrollTo {
+    bounds: Rect,
+    mode: ScrollToMode,
+    zoom: Option<Factor>,
+    in_content: bool,
+    smooth: Option<SmoothScrolling>,
+    // forward to parent scroll after applying.
+    chain: bool,
+}
+impl PendingScrollTo {
+    fn new(bounds: Rect, mode: ScrollToMode, zoom: Option<Factor>, in_content: bool) -> Self {
+        Self {
+            bounds,
+            mode,
+            zoom,
+            in_content,
+            smooth: None,
+            chain: false,
+        }
+    }
+}
 fn inflate_margin(mut r: PxRect, margin: PxSideOffsets) -> PxRect {
     r.origin.x -= margin.left;
     r.origin.y -= margin.top;
@@ -1002,18 +1153,28 @@ pub fn scroll_touch_node(child: impl UiNode) -> impl UiNode {
                     TouchPhase::End => {
                         applied_offset = PxVector::zero();
 
-                        let friction = Dip::new(1000);
+                        let friction = SCROLL_PHYSICS_VAR.with(|p| p.fling_deceleration);
+                        let fling = friction > Dip::new(0);
                         let mode = SCROLL.mode().get();
                         if mode.contains(ScrollMode::VERTICAL) {
-                            let (delta, duration) = args.translation_inertia_y(friction);
-
+                            // fling disabled, also avoids division by zero
+                            let (delta, duration) = if fling {
+                                args.translation_inertia_y(friction)
+                            } else {
+                                (Px(0), Default::default())
+                            };
                             if delta != Px(0) {
                                 SCROLL.scroll_vertical_touch_inertia(-delta, duration);
                             }
                             SCROLL.clear_vertical_overscroll();
                         }
                         if mode.contains(ScrollMode::HORIZONTAL) {
-                            let (delta, duration) = args.translation_inertia_x(friction);
+                            // fling disabled, also avoids division by zero
+                            let (delta, duration) = if fling {
+                                args.translation_inertia_x(friction)
+                            } else {
+                                (Px(0), Default::default())
+                            };
                             if delta != Px(0) {
                                 SCROLL.scroll_horizontal_touch_inertia(-delta, duration);
                             }
@@ -1200,13 +1361,16 @@ pub fn overscroll_node(child: impl UiNode) -> impl UiNode {
         UiNodeOp::Init => {
             WIDGET
                 .sub_var_layout(&OVERSCROLL_VERTICAL_OFFSET_VAR)
-                .sub_var_layout(&OVERSCROLL_HORIZONTAL_OFFSET_VAR);
+                .sub_var_layout(&OVERSCROLL_HORIZONTAL_OFFSET_VAR)
+                .sub_var_layout(&SCROLL_PHYSICS_VAR);
         }
         UiNodeOp::Layout { final_size, wl } => {
             *final_size = c.layout(wl);
 
+            let indicator = SCROLL_PHYSICS_VAR.with(|p| p.overscroll == OverscrollMode::Indicator);
+
             let mut new_v_rect = PxRect::zero();
-            let v = OVERSCROLL_VERTICAL_OFFSET_VAR.get();
+            let v = if indicator { OVERSCROLL_VERTICAL_OFFSET_VAR.get() } else { 0.fct() };
             if v < 0.fct() {
                 // overscroll top
                 new_v_rect.size = *final_size;
@@ -1221,7 +1385,11 @@ pub fn overscroll_node(child: impl UiNode) -> impl UiNode {
             }
 
             let mut new_h_rect = PxRect::zero();
-            let h = OVERSCROLL_HORIZONTAL_OFFSET_VAR.get();
+            let h = if indicator {
+                OVERSCROLL_HORIZONTAL_OFFSET_VAR.get()
+            } else {
+                0.fct()
+            };
             if h < 0.fct() {
                 // overscroll left
                 new_h_rect.size = *final_size;
@@ -1357,6 +1525,72 @@ pub fn access_scroll_node(child: impl UiNode) -> impl UiNode {
     })
 }
 
+/// Distance from the scroll edges that starts scrolling while dragging.
+const DRAG_SCROLL_EDGE: Dip = Dip::new(24);
+/// Offset scrolled every [`DRAG_SCROLL_INTERVAL`] while dragging near the edges.
+const DRAG_SCROLL_STEP: Dip = Dip::new(8);
+const DRAG_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Create a node that scrolls while in-app drag&drop data is dragged near the scroll edges.
+///
+/// See [`DRAG_DROP`] for more details about in-app drag&drop.
+pub fn drag_scroll_node(child: impl UiNode) -> impl UiNode {
+    let mut timer = None::<(TimerVar, VarHandle)>;
+    let mut step = PxVector::zero();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&DRAG_DROP.dragging());
+        }
+        UiNodeOp::Deinit => {
+            timer = None;
+        }
+        UiNodeOp::Update { .. } => {
+            if let Some(d) = DRAG_DROP.dragging().get_new() {
+                step = PxVector::zero();
+                if let Some(d) = d.filter(|d| d.window_id == WINDOW.id()) {
+                    let scale = WINDOW.vars().scale_factor().get();
+                    let p = d.position.to_px(scale);
+                    let bounds = WIDGET.info().inner_bounds();
+                    if bounds.contains(p) {
+                        let edge = DRAG_SCROLL_EDGE.to_px(scale);
+                        let speed = DRAG_SCROLL_STEP.to_px(scale);
+                        if p.y - bounds.min_y() < edge {
+                            step.y = -speed;
+                        } else if bounds.max_y() - p.y < edge {
+                            step.y = speed;
+                        }
+                        if p.x - bounds.min_x() < edge {
+                            step.x = -speed;
+                        } else if bounds.max_x() - p.x < edge {
+                            step.x = speed;
+                        }
+                    }
+                }
+
+                if step == PxVector::zero() {
+                    timer = None;
+                } else if timer.is_none() {
+                    let t = TIMERS.interval(DRAG_SCROLL_INTERVAL, false);
+                    let h = t.subscribe(UpdateOp::Update, WIDGET.id());
+                    timer = Some((t, h));
+                }
+            }
+
+            if let Some((t, _)) = &timer {
+                if t.is_new() {
+                    if step.y != Px(0) {
+                        SCROLL.scroll_vertical(ScrollFrom::VarTarget(step.y));
+                    }
+                    if step.x != Px(0) {
+                        SCROLL.scroll_horizontal(ScrollFrom::VarTarget(step.x));
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
 /// Create a note that spawns the auto scroller on middle click and fulfill `AUTO_SCROLL_CMD` requests.
 pub fn auto_scroll_node(child: impl UiNode) -> impl UiNode {
     let mut middle_handle = EventHandle::dummy();
@@ -1416,6 +1650,8 @@ pub fn auto_scroll_node(child: impl UiNode) -> impl UiNode {
                                 min_size: zng_wgt_layer::AnchorSize::Unbounded,
                                 max_size: zng_wgt_layer::AnchorSize::Window,
                                 viewport_bound: true,
+                                flip: false,
+                                work_area_bound: false,
                                 corner_radius: false,
                                 visibility: true,
                                 interactivity: false,
@@ -70,6 +70,9 @@ context_var! {
     /// Smooth scrolling config for an scroll widget.
     pub static SMOOTH_SCROLLING_VAR: SmoothScrolling = SmoothScrolling::default();
 
+    /// Touch scroll physics config for an scroll widget.
+    pub static SCROLL_PHYSICS_VAR: ScrollPhysics = ScrollPhysics::default();
+
     /// If a scroll widget defines its viewport size as the [`LayoutMetrics::viewport`] for the scroll content.
     ///
     /// This is `true` by default.
@@ -84,6 +87,11 @@ context_var! {
         margin: SideOffsets::new_all(0.dip())
     };
 
+    /// Insets of the viewport that are excluded when scrolling to a target.
+    ///
+    /// Is zero by default.
+    pub static SCROLL_TO_PADDING_VAR: SideOffsets = SideOffsets::zero();
+
     /// Extra space added to the viewport auto-hide rectangle.
     ///
     /// The scroll sets the viewport plus these offsets as the [`FrameBuilder::auto_hide_rect`], this value is used
@@ -356,6 +364,20 @@ pub fn scroll_to_focused_mode(child: impl UiNode, mode: impl IntoVar<Option<Scro
     with_context_var(child, SCROLL_TO_FOCUSED_MODE_VAR, mode)
 }
 
+/// Insets of the viewport that are excluded when scrolling to a target.
+///
+/// Scroll-to requests and scroll to focused treat the viewport as if it was deflated by these offsets, use this to
+/// avoid scrolling targets under sticky headers or other content that overlays the viewport. Relative
+/// lengths are relative to the viewport size.
+///
+/// Is zero by default.
+///
+/// This property sets the [`SCROLL_TO_PADDING_VAR`].
+#[property(CONTEXT, default(SCROLL_TO_PADDING_VAR), widget_impl(Scroll))]
+pub fn scroll_to_padding(child: impl UiNode, padding: impl IntoVar<SideOffsets>) -> impl UiNode {
+    with_context_var(child, SCROLL_TO_PADDING_VAR, padding)
+}
+
 /// Extra space added to the viewport auto-hide rectangle.
 ///
 /// The scroll sets the viewport plus these offsets as the [`FrameBuilder::auto_hide_rect`], this value is used
@@ -375,6 +397,20 @@ pub fn auto_hide_extra(child: impl UiNode, extra: impl IntoVar<SideOffsets>) ->
     with_context_var(child, AUTO_HIDE_EXTRA_VAR, extra)
 }
 
+/// Touch scroll physics.
+///
+/// Configures the kinetic fling animation that continues a touch scroll after release and the
+/// overscroll feedback, see [`ScrollPhysics`] for more details. Note that keyboard and mouse wheel scrolling
+/// is configured by [`smooth_scrolling`], the fling is interrupted by any of these scroll requests.
+///
+/// This property sets the [`SCROLL_PHYSICS_VAR`].
+///
+/// [`smooth_scrolling`]: fn@smooth_scrolling
+#[property(CONTEXT, default(SCROLL_PHYSICS_VAR), widget_impl(Scroll))]
+pub fn scroll_physics(child: impl UiNode, physics: impl IntoVar<ScrollPhysics>) -> impl UiNode {
+    with_context_var(child, SCROLL_PHYSICS_VAR, physics)
+}
+
 /// Color of the overscroll indicator.
 ///
 /// The overscroll indicator appears when touch scroll tries to scroll past an edge in a dimension
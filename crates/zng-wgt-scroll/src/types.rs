@@ -13,7 +13,7 @@ use zng_var::{
 };
 use zng_wgt::prelude::*;
 
-use super::{cmd, SMOOTH_SCROLLING_VAR};
+use super::{cmd, SCROLL_PHYSICS_VAR, SMOOTH_SCROLLING_VAR};
 
 bitflags! {
     /// What dimensions are scrollable in a widget.
@@ -136,6 +136,9 @@ struct ScrollConfig {
     overscroll: [Mutex<AnimationHandle>; 2],
     inertia: [Mutex<AnimationHandle>; 2],
     auto: [Mutex<AnimationHandle>; 2],
+
+    // offset shift requested by the content in the current layout pass.
+    anchor: Mutex<PxVector>,
 }
 impl Default for ScrollConfig {
     fn default() -> Self {
@@ -151,6 +154,7 @@ impl Default for ScrollConfig {
             overscroll: Default::default(),
             inertia: Default::default(),
             auto: Default::default(),
+            anchor: Default::default(),
         }
     }
 }
@@ -362,16 +366,22 @@ impl SCROLL {
             ScrollFrom::Var(a) => {
                 let amount = a.0 as f32 / max_scroll.0 as f32;
                 let f = scroll_offset_var.get();
-                SCROLL.chase(vertical, scroll_offset_var, |_| (f.0 + amount).clamp(min, max).fct());
+                SCROLL.chase(vertical, scroll_offset_var, SMOOTH_SCROLLING_VAR.get(), |_| {
+                    (f.0 + amount).clamp(min, max).fct()
+                });
             }
             ScrollFrom::VarTarget(a) => {
                 let amount = a.0 as f32 / max_scroll.0 as f32;
-                SCROLL.chase(vertical, scroll_offset_var, |f| (f.0 + amount).clamp(min, max).fct());
+                SCROLL.chase(vertical, scroll_offset_var, SMOOTH_SCROLLING_VAR.get(), |f| {
+                    (f.0 + amount).clamp(min, max).fct()
+                });
             }
             ScrollFrom::Rendered(a) => {
                 let amount = a.0 as f32 / max_scroll.0 as f32;
                 let f = SCROLL_CONFIG.get().rendered.load(Ordering::Relaxed).h;
-                SCROLL.chase(vertical, scroll_offset_var, |_| (f.0 + amount).clamp(min, max).fct());
+                SCROLL.chase(vertical, scroll_offset_var, SMOOTH_SCROLLING_VAR.get(), |_| {
+                    (f.0 + amount).clamp(min, max).fct()
+                });
             }
         }
     }
@@ -452,15 +462,18 @@ impl SCROLL {
             overscroll = -(overscroll_px.min(overscroll_max) / overscroll_max);
         }
 
+        // touch catches the fling
+        mem::take(&mut *SCROLL_CONFIG.get().inertia[vertical as usize].lock()).stop();
+
         let _ = scroll_offset_var.set(next);
-        if overscroll != 0.fct() {
+        if overscroll != 0.fct() && SCROLL_PHYSICS_VAR.with(|p| p.overscroll != OverscrollMode::None) {
             let new_handle = self.increment_overscroll(overscroll_offset_var, overscroll);
 
             let config = SCROLL_CONFIG.get();
             let mut handle = config.overscroll[vertical as usize].lock();
             mem::replace(&mut *handle, new_handle).stop();
         } else {
-            self.clear_horizontal_overscroll();
+            self.clear_overscroll(vertical, overscroll_offset_var);
         }
     }
 
@@ -471,13 +484,14 @@ impl SCROLL {
             Clear(Transition<Factor>),
         }
         let mut state = State::Increment;
+        let duration = SCROLL_PHYSICS_VAR.with(|p| p.overscroll_return);
         overscroll.animate(move |a, o| match &mut state {
             State::Increment => {
                 // set the increment and start delay to animation.
                 *o.to_mut() += delta;
                 *o.to_mut() = (*o).clamp((-1).fct(), 1.fct());
 
-                a.sleep(300.ms());
+                a.sleep(duration);
                 state = State::ClearDelay;
             }
             State::ClearDelay => {
@@ -486,12 +500,26 @@ impl SCROLL {
                 state = State::Clear(t);
             }
             State::Clear(t) => {
-                let step = easing::linear(a.elapsed_stop(300.ms()));
+                let step = easing::linear(a.elapsed_stop(duration));
                 o.set(t.sample(step));
             }
         })
     }
 
+    /// Shift the scroll offset by `delta` pixels in the current layout pass, without smooth scrolling.
+    ///
+    /// This is used by content that changes size above or before the viewport during layout to keep the visible content
+    /// stable, for example when items above the viewport are measured for the first time. This method must be called during
+    /// the scroll content layout, the scroll applies the shift before positioning the content, so the visible content
+    /// does not jump.
+    pub fn anchor_offset(&self, delta: PxVector) {
+        *SCROLL_CONFIG.get().anchor.lock() += delta;
+    }
+
+    pub(crate) fn take_anchor_offset(&self) -> PxVector {
+        mem::take(&mut *SCROLL_CONFIG.get().anchor.lock())
+    }
+
     /// Quick ease vertical overscroll to zero.
     pub fn clear_vertical_overscroll(&self) {
         self.clear_overscroll(true, OVERSCROLL_VERTICAL_OFFSET_VAR)
@@ -565,8 +593,15 @@ impl SCROLL {
             overscroll = -(overscroll_px.min(overscroll_max) / overscroll_max);
         }
 
+        let physics = SCROLL_PHYSICS_VAR.get();
+        if physics.overscroll == OverscrollMode::None {
+            overscroll = 0.fct();
+        }
+        let return_duration = physics.overscroll_return;
+        let fling_easing = physics.fling_easing;
+        let easing = move |t| fling_easing(t);
+
         let cfg = SCROLL_CONFIG.get();
-        let easing = |t| easing::ease_out(easing::quad, t);
         *cfg.inertia[vertical as usize].lock() = if overscroll != 0.fct() {
             let transition = Transition::new(current, next + overscroll);
 
@@ -591,7 +626,7 @@ impl SCROLL {
                     }
                 } else {
                     // overscroll clear ease animation
-                    let step = easing::linear(animation.elapsed_stop(300.ms()));
+                    let step = easing::linear(animation.elapsed_stop(return_duration));
                     let v = overscroll_tr.sample(step);
                     let _ = overscroll_var.set(v);
                 }
@@ -606,7 +641,7 @@ impl SCROLL {
     pub fn chase_vertical(&self, modify_offset: impl FnOnce(Factor) -> Factor) {
         #[cfg(feature = "dyn_closure")]
         let modify_offset: Box<dyn FnOnce(Factor) -> Factor> = Box::new(modify_offset);
-        self.chase(true, SCROLL_VERTICAL_OFFSET_VAR, modify_offset);
+        self.chase(true, SCROLL_VERTICAL_OFFSET_VAR, SMOOTH_SCROLLING_VAR.get(), modify_offset);
     }
 
     /// Set the horizontal offset to a new offset derived from the last set offset, blending into the active smooth
@@ -614,12 +649,32 @@ impl SCROLL {
     pub fn chase_horizontal(&self, modify_offset: impl FnOnce(Factor) -> Factor) {
         #[cfg(feature = "dyn_closure")]
         let modify_offset: Box<dyn FnOnce(Factor) -> Factor> = Box::new(modify_offset);
-        self.chase(false, SCROLL_HORIZONTAL_OFFSET_VAR, modify_offset);
+        self.chase(false, SCROLL_HORIZONTAL_OFFSET_VAR, SMOOTH_SCROLLING_VAR.get(), modify_offset);
+    }
+
+    /// Like [`chase_vertical`] or [`chase_horizontal`], but with a custom smooth scrolling config.
+    ///
+    /// [`chase_vertical`]: Self::chase_vertical
+    /// [`chase_horizontal`]: Self::chase_horizontal
+    pub(crate) fn chase_with(&self, vertical: bool, smooth: SmoothScrolling, modify_offset: impl FnOnce(Factor) -> Factor) {
+        let var = if vertical {
+            SCROLL_VERTICAL_OFFSET_VAR
+        } else {
+            SCROLL_HORIZONTAL_OFFSET_VAR
+        };
+        self.chase(vertical, var, smooth, modify_offset);
     }
 
-    fn chase(&self, vertical: bool, scroll_offset_var: ContextVar<Factor>, modify_offset: impl FnOnce(Factor) -> Factor) {
-        let smooth = SMOOTH_SCROLLING_VAR.get();
+    fn chase(
+        &self,
+        vertical: bool,
+        scroll_offset_var: ContextVar<Factor>,
+        smooth: SmoothScrolling,
+        modify_offset: impl FnOnce(Factor) -> Factor,
+    ) {
         let config = SCROLL_CONFIG.get();
+        // wheel or keyboard scroll interrupts the fling
+        mem::take(&mut *config.inertia[vertical as usize].lock()).stop();
         let mut chase = config.chase[vertical as usize].lock();
         match &mut *chase {
             Some(t) => {
@@ -650,14 +705,13 @@ impl SCROLL {
     pub fn chase_zoom(&self, modify_scale: impl FnOnce(Factor) -> Factor) {
         #[cfg(feature = "dyn_closure")]
         let modify_scale: Box<dyn FnOnce(Factor) -> Factor> = Box::new(modify_scale);
-        self.chase_zoom_impl(modify_scale);
+        self.chase_zoom_impl(SMOOTH_SCROLLING_VAR.get(), modify_scale);
     }
-    fn chase_zoom_impl(&self, modify_scale: impl FnOnce(Factor) -> Factor) {
+    pub(crate) fn chase_zoom_impl(&self, smooth: SmoothScrolling, modify_scale: impl FnOnce(Factor) -> Factor) {
         if !SCROLL_MODE_VAR.get().contains(ScrollMode::ZOOM) {
             return;
         }
 
-        let smooth = SMOOTH_SCROLLING_VAR.get();
         let config = SCROLL_CONFIG.get();
         let mut zoom = config.zoom.lock();
 
@@ -872,15 +926,17 @@ impl SCROLL {
     /// Scroll the [`WIDGET`] into view.
     ///
     /// [`WIDGET`]: zng_wgt::prelude::WIDGET
-    pub fn scroll_to(&self, mode: impl Into<super::cmd::ScrollToMode>) {
-        cmd::scroll_to(WIDGET.info(), mode.into())
+    ///
+    /// See [`cmd::scroll_to`] for more details.
+    pub fn scroll_to(&self, options: impl Into<super::cmd::ScrollToOptions>) {
+        cmd::scroll_to(WIDGET.info(), options.into())
     }
 
     /// Scroll the [`WIDGET`] into view and adjusts the zoom scale.
     ///
     /// [`WIDGET`]: zng_wgt::prelude::WIDGET
-    pub fn scroll_to_zoom(&self, mode: impl Into<super::cmd::ScrollToMode>, zoom: impl Into<Factor>) {
-        cmd::scroll_to_zoom(WIDGET.info(), mode.into(), zoom.into())
+    pub fn scroll_to_zoom(&self, options: impl Into<super::cmd::ScrollToOptions>, zoom: impl Into<Factor>) {
+        cmd::scroll_to_zoom(WIDGET.info(), options.into(), zoom.into())
     }
 
     /// Returns `true` if the content can be scaled and the current scale is less than the max.
@@ -1019,7 +1075,9 @@ static_id! {
 
 /// Smooth scrolling config.
 ///
-/// This config can be set by the [`smooth_scrolling`] property.
+/// This config can be set by the [`smooth_scrolling`] property. The config is used by all scroll animations
+/// that chase a target offset or scale, that is the keyboard, mouse wheel and scroll commands, touch fling
+/// animations are configured by [`ScrollPhysics`].
 ///
 /// [`smooth_scrolling`]: fn@crate::smooth_scrolling
 #[derive(Clone)]
@@ -1100,6 +1158,134 @@ impl_from_and_into_var! {
     }
 }
 
+/// Touch scroll physics config.
+///
+/// This config can be set by the [`scroll_physics`] property.
+///
+/// [`scroll_physics`]: fn@crate::scroll_physics
+#[derive(Clone)]
+pub struct ScrollPhysics {
+    /// Deceleration of the kinetic fling animation that continues a touch scroll after the touch is released,
+    /// in DIPs per second.
+    ///
+    /// Higher values stop the fling faster, zero disables the fling.
+    ///
+    /// Default is `1000.dip()`.
+    pub fling_deceleration: Dip,
+    /// Fling animation easing function.
+    ///
+    /// Default is `ease_out(quad)`, that simulates a constant friction.
+    pub fling_easing: Arc<dyn Fn(EasingTime) -> EasingStep + Send + Sync>,
+    /// Feedback shown when the touch scroll or fling reaches an edge.
+    ///
+    /// Default is [`OverscrollMode::Indicator`].
+    pub overscroll: OverscrollMode,
+    /// Duration of the animation that returns the overscroll to zero.
+    ///
+    /// Default is `300.ms()`.
+    pub overscroll_return: Duration,
+}
+impl fmt::Debug for ScrollPhysics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollPhysics")
+            .field("fling_deceleration", &self.fling_deceleration)
+            .field("overscroll", &self.overscroll)
+            .field("overscroll_return", &self.overscroll_return)
+            .finish_non_exhaustive()
+    }
+}
+impl PartialEq for ScrollPhysics {
+    fn eq(&self, other: &Self) -> bool {
+        self.fling_deceleration == other.fling_deceleration
+            && Arc::ptr_eq(&self.fling_easing, &other.fling_easing)
+            && self.overscroll == other.overscroll
+            && self.overscroll_return == other.overscroll_return
+    }
+}
+impl Default for ScrollPhysics {
+    fn default() -> Self {
+        Self {
+            fling_deceleration: Dip::new(1000),
+            fling_easing: Arc::new(|t| easing::ease_out(easing::quad, t)),
+            overscroll: OverscrollMode::Indicator,
+            overscroll_return: 300.ms(),
+        }
+    }
+}
+impl ScrollPhysics {
+    /// New with custom fling deceleration and other values default.
+    pub fn new(fling_deceleration: Dip) -> Self {
+        Self {
+            fling_deceleration,
+            ..Default::default()
+        }
+    }
+
+    /// No fling and no overscroll feedback, touch scroll stops immediately on release.
+    pub fn disabled() -> Self {
+        Self {
+            fling_deceleration: Dip::new(0),
+            overscroll: OverscrollMode::None,
+            ..Default::default()
+        }
+    }
+
+    /// Set the fling easing function.
+    pub fn with_fling_easing(mut self, easing: impl Fn(EasingTime) -> EasingStep + Send + Sync + 'static) -> Self {
+        self.fling_easing = Arc::new(easing);
+        self
+    }
+
+    /// Set the overscroll mode.
+    pub fn with_overscroll(mut self, overscroll: OverscrollMode) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    /// If the fling animation is enabled.
+    pub fn is_fling_enabled(&self) -> bool {
+        self.fling_deceleration > Dip::new(0)
+    }
+}
+impl_from_and_into_var! {
+    /// Fling deceleration, other values default.
+    fn from(fling_deceleration: Dip) -> ScrollPhysics {
+        ScrollPhysics::new(fling_deceleration)
+    }
+
+    /// Overscroll mode, other values default.
+    fn from(overscroll: OverscrollMode) -> ScrollPhysics {
+        ScrollPhysics::default().with_overscroll(overscroll)
+    }
+
+    /// Returns default config for `true`, [`disabled`] for `false`.
+    ///
+    /// [`disabled`]: ScrollPhysics::disabled
+    fn from(enabled: bool) -> ScrollPhysics {
+        if enabled {
+            ScrollPhysics::default()
+        } else {
+            ScrollPhysics::disabled()
+        }
+    }
+}
+
+/// Overscroll feedback mode.
+///
+/// See [`ScrollPhysics::overscroll`] for more details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum OverscrollMode {
+    /// No feedback, scrolling stops at the edge.
+    None,
+    /// A glow indicator is drawn in the edge, the color is set by [`overscroll_color`].
+    ///
+    /// [`overscroll_color`]: fn@crate::overscroll_color
+    #[default]
+    Indicator,
+    /// The content is dragged past the edge with increasing resistance and springs back when released.
+    RubberBand,
+}
+
 /// Arguments for the [`auto_scroll_indicator`] closure.
 ///
 /// Empty struct, there are no args in the current release, this struct is declared so that if
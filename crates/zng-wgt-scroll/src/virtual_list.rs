@@ -0,0 +1,561 @@
+//! Virtualizing list widget, properties and nodes.
+
+use std::{collections::HashSet, ops};
+
+use zng_ext_window::WINDOW_Ext as _;
+use zng_var::types::VecChange;
+use zng_wgt::prelude::*;
+
+use crate::{WidgetInfoExt as _, SCROLL};
+
+/// Vertical list that only instantiates the items that are near the parent scroll viewport.
+///
+/// The list presents an [`ObservableVec`], the [`VecChange`] diff of each update is applied to the list,
+/// in place, so the list can present millions of items that change partially. Only the items that intersect the
+/// render culling rect of the parent [`Scroll!`] are realized, the other items are represented by their last
+/// measured height, or the estimated [`item_height`].
+///
+/// Item widgets are recycled, when an item scrolls out of view its widget is kept in a pool and is reused for the next
+/// item that scrolls into view, the item function must bind to the [`VirtualItemArgs`] variables to support this.
+///
+/// When items above the viewport change size, for example when they are measured for the first time, the parent scroll
+/// offset is corrected so that the visible items don't move, see [`SCROLL.anchor_offset`] for more details.
+///
+/// # Shorthand
+///
+/// The `VirtualList!` macro provides a shorthand init that sets the `items` property directly.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt_scroll::virtual_list::*;
+/// # use zng_wgt::prelude::*;
+/// # fn main() { }
+/// # fn shorthand_demo<T: VarValue>(items: impl IntoVar<ObservableVec<T>>, item_fn: impl IntoVar<WidgetFn<VirtualItemArgs<T>>>) -> impl UiNode {
+/// VirtualList!(::<T>, items, item_fn)
+/// # }
+/// ```
+///
+/// Note that the first argument is a *turbo-fish* that defines the item type and is required.
+///
+/// [`Scroll!`]: struct@crate::Scroll
+/// [`item_height`]: fn@item_height
+/// [`SCROLL.anchor_offset`]: crate::SCROLL::anchor_offset
+/// [`ObservableVec`]: zng_var::ObservableVec
+#[widget($crate::virtual_list::VirtualList {
+    (::<$T:ty>, $items:expr, $item_fn:expr $(,)?) => {
+        items::<$T> = {
+            items: $items,
+            item_fn: $item_fn,
+        };
+    }
+})]
+pub struct VirtualList(WidgetBase);
+
+/// Arguments for the [`VirtualList!`] item function.
+///
+/// The item widget is recycled, the variables update when the widget is reused to present another item.
+///
+/// [`VirtualList!`]: struct@VirtualList
+#[derive(Clone)]
+pub struct VirtualItemArgs<D: VarValue> {
+    /// Index of the item in the list.
+    pub index: ReadOnlyArcVar<usize>,
+    /// The item.
+    pub item: ReadOnlyArcVar<D>,
+}
+
+context_var! {
+    /// Estimated height of items that have not been measured yet.
+    pub static ITEM_HEIGHT_VAR: Length = 32;
+
+    /// Space in between items.
+    pub static SPACING_VAR: Length = 0;
+
+    /// Maximum number of item widgets retained for reuse.
+    pub static POOL_LEN_VAR: usize = 32;
+}
+
+/// The items and item widget function.
+///
+/// The `item_fn` is called to create a new widget when the pool of recycled widgets is empty, the widget
+/// must bind to the [`VirtualItemArgs`] variables.
+///
+/// This property replaces the widget child.
+#[property(CHILD, widget_impl(VirtualList))]
+pub fn items<D: VarValue>(
+    child: impl UiNode,
+    items: impl IntoVar<ObservableVec<D>>,
+    item_fn: impl IntoVar<WidgetFn<VirtualItemArgs<D>>>,
+) -> impl UiNode {
+    let _ = child;
+    node(items, item_fn)
+}
+
+/// Estimated height of items that have not been measured yet.
+///
+/// The estimate is used to compute the list height and scroll position, a good estimate avoids scroll offset
+/// corrections as the items are measured. Relative values are relative to the list width.
+///
+/// Is `32` by default.
+///
+/// Sets the [`ITEM_HEIGHT_VAR`].
+#[property(CONTEXT, default(ITEM_HEIGHT_VAR), widget_impl(VirtualList))]
+pub fn item_height(child: impl UiNode, height: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, ITEM_HEIGHT_VAR, height)
+}
+
+/// Space in between items.
+///
+/// Is `0` by default.
+///
+/// Sets the [`SPACING_VAR`].
+#[property(CONTEXT, default(SPACING_VAR), widget_impl(VirtualList))]
+pub fn spacing(child: impl UiNode, spacing: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, SPACING_VAR, spacing)
+}
+
+/// Maximum number of item widgets retained for reuse.
+///
+/// Items that scroll out of view are kept inited in a pool and are reused for items that scroll into view, widgets
+/// that don't fit in the pool are dropped.
+///
+/// Is `32` by default.
+///
+/// Sets the [`POOL_LEN_VAR`].
+#[property(CONTEXT, default(POOL_LEN_VAR), widget_impl(VirtualList))]
+pub fn pool_len(child: impl UiNode, len: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, POOL_LEN_VAR, len)
+}
+
+/// Virtualizing list node.
+///
+/// Can be used directly to present a virtual list without declaring a widget. This node is the child of the
+/// `VirtualList!` widget, see it for more details.
+pub fn node<D: VarValue>(items: impl IntoVar<ObservableVec<D>>, item_fn: impl IntoVar<WidgetFn<VirtualItemArgs<D>>>) -> impl UiNode {
+    let items = items.into_var();
+    let item_fn = item_fn.into_var();
+
+    let list = EditableUiNodeList::new();
+    let list_ref = list.reference();
+    let children = PanelList::new(list);
+
+    let mut heights = HeightIndex::default();
+    let mut slots = IdMap::<WidgetId, Slot<D>>::default();
+    let mut realized = 0..0;
+    let mut need_realize = false;
+    // render culling rect and viewport, in the list space.
+    let mut culling = None::<ops::Range<Px>>;
+    let mut viewport = None::<ops::Range<Px>>;
+    // item index and offset, used to keep the item visually stable when items before it change size.
+    let mut anchor = None::<(usize, Px)>;
+    let mut width = Px(0);
+
+    match_node_list(children, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET
+                .sub_var(&items)
+                .sub_var(&item_fn)
+                .sub_var(&POOL_LEN_VAR)
+                .sub_var_layout(&ITEM_HEIGHT_VAR)
+                .sub_var_layout(&SPACING_VAR);
+
+            // recycled widgets are retained on deinit
+            for s in slots.values_mut() {
+                s.index = None;
+            }
+            heights.reset(items.with(|l| l.len()));
+            anchor = None;
+            need_realize = true;
+            WIDGET.update();
+        }
+        UiNodeOp::Update { updates } => {
+            if item_fn.is_new() {
+                list_ref.clear();
+                slots.clear();
+                need_realize = true;
+            }
+
+            let mut reset = false;
+            items.with_new(|l| {
+                let changes = l.changes();
+                if changes.is_empty() || changes == [VecChange::Clear] {
+                    reset = true;
+                    return;
+                }
+                for change in changes {
+                    let remap: Box<dyn Fn(usize) -> Option<usize>> = match *change {
+                        VecChange::Insert { index, count } => {
+                            heights.insert(index, count);
+                            Box::new(move |i| Some(if i >= index { i + count } else { i }))
+                        }
+                        VecChange::Remove { index, count } => {
+                            heights.remove(index..index + count);
+                            Box::new(move |i| {
+                                if i < index {
+                                    Some(i)
+                                } else if i >= index + count {
+                                    Some(i - count)
+                                } else {
+                                    None
+                                }
+                            })
+                        }
+                        VecChange::Move { from_index, to_index } => {
+                            heights.move_item(from_index, to_index);
+                            Box::new(move |i| {
+                                Some(if i == from_index {
+                                    to_index
+                                } else if from_index < to_index && i > from_index && i <= to_index {
+                                    i - 1
+                                } else if to_index < from_index && i >= to_index && i < from_index {
+                                    i + 1
+                                } else {
+                                    i
+                                })
+                            })
+                        }
+                        VecChange::Clear => unreachable!(),
+                    };
+                    for s in slots.values_mut() {
+                        if let Some(i) = s.index {
+                            s.index = remap(i);
+                            if let Some(n) = s.index {
+                                if n != i {
+                                    s.index_var.set(n);
+                                }
+                            }
+                        }
+                    }
+                    if let Some((i, y)) = anchor {
+                        // anchor removed, keep the offset on the next item
+                        anchor = Some((remap(i).unwrap_or(i.min(heights.len().saturating_sub(1))), y));
+                    }
+                }
+                need_realize = true;
+            });
+
+            if !reset && heights.len() != items.with(|l| l.len()) {
+                tracing::error!("virtual list out of sync with data, will reset");
+                reset = true;
+            }
+            if reset {
+                heights.reset(items.with(|l| l.len()));
+                for s in slots.values_mut() {
+                    s.index = None;
+                }
+                anchor = None;
+                need_realize = true;
+            }
+
+            if POOL_LEN_VAR.is_new() {
+                need_realize = true;
+            }
+
+            if std::mem::take(&mut need_realize) {
+                let window = viewport.clone().unwrap_or_else(|| {
+                    let h = WINDOW.vars().actual_size().get().height.to_px(WINDOW.vars().scale_factor().get());
+                    Px(0)..h
+                });
+                let range = heights.range(culling.as_ref().unwrap_or(&window));
+                realize(range, &items, &item_fn, &list_ref, &mut slots);
+                realized = heights.range(culling.as_ref().unwrap_or(&window));
+                WIDGET.layout();
+            }
+
+            // apply realize inserts and removes
+            let mut changed = false;
+            c.update_all(updates, &mut changed);
+            if changed {
+                WIDGET.layout();
+            }
+        }
+        UiNodeOp::Measure { desired_size, .. } => {
+            c.delegated();
+
+            let constraints = LAYOUT.constraints();
+            *desired_size = PxSize::new(constraints.x.fill_or(width), constraints.y.clamp(heights.total()));
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            c.delegated();
+
+            let constraints = LAYOUT.constraints();
+            heights.set_metrics(ITEM_HEIGHT_VAR.layout_y().max(Px(1)), SPACING_VAR.layout_y());
+
+            let children = c.children();
+            let mut bound = Vec::with_capacity(children.len());
+            let mut max_width = Px(0);
+            LAYOUT.with_constraints(constraints.with_new_min(Px(0), Px(0)).with_unbounded_y().with_fill_y(false), || {
+                children.for_each(|i, child, o| {
+                    let index = child
+                        .with_context(WidgetUpdateMode::Ignore, || WIDGET.id())
+                        .and_then(|id| slots.get(&id))
+                        .and_then(|s| s.index)
+                        .filter(|i| *i < heights.len());
+                    match index {
+                        Some(index) => {
+                            let (size, define_ref_frame) = wl.with_child(|wl| child.layout(wl));
+                            o.define_reference_frame = define_ref_frame;
+                            heights.set(index, size.height);
+                            max_width = max_width.max(size.width);
+                        }
+                        None => {
+                            // pooled
+                            wl.collapse_child(i);
+                            o.child_offset = PxVector::zero();
+                            o.define_reference_frame = false;
+                        }
+                    }
+                    bound.push(index);
+                });
+            });
+
+            if let Some((i, y)) = anchor.take() {
+                if i < heights.len() && SCROLL.try_id().is_some() {
+                    let shift = heights.offset(i) - y;
+                    if shift != Px(0) {
+                        SCROLL.anchor_offset(PxVector::new(Px(0), shift));
+                        for r in [&mut culling, &mut viewport].into_iter().flatten() {
+                            r.start += shift;
+                            r.end += shift;
+                        }
+                    }
+                }
+            }
+
+            children.for_each(|i, _, o| {
+                if let Some(index) = bound[i] {
+                    o.child_offset = PxVector::new(Px(0), heights.offset(index));
+                }
+            });
+            children.commit_data().request_render();
+
+            // anchor at the first visible item, if not scrolled to the top.
+            if let Some(v) = &viewport {
+                if v.start > Px(0) && !heights.is_empty() {
+                    let i = heights.find(v.start);
+                    anchor = Some((i, heights.offset(i)));
+                }
+            }
+
+            width = max_width;
+            *final_size = PxSize::new(constraints.x.fill_or(width), constraints.y.clamp(heights.total()));
+        }
+        UiNodeOp::Render { frame } => {
+            if let Some(t) = frame.transform().inverse() {
+                let to_local = |r: PxRect| t.outer_transformed(r.to_box2d()).map(|b| b.min.y..b.max.y);
+
+                viewport = SCROLL
+                    .try_id()
+                    .and_then(|id| WINDOW.info().get(id))
+                    .and_then(|w| w.viewport())
+                    .and_then(to_local);
+
+                culling = to_local(frame.auto_hide_rect());
+                if let Some(r) = &culling {
+                    if heights.range(r) != realized {
+                        need_realize = true;
+                        WIDGET.update();
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Item widget state.
+struct Slot<D: VarValue> {
+    index_var: ArcVar<usize>,
+    item_var: ArcVar<D>,
+    // bound item index, `None` if is in the pool.
+    index: Option<usize>,
+}
+
+/// Bind item widgets to all items in the `range`, recycling widgets out of range.
+fn realize<D: VarValue>(
+    range: ops::Range<usize>,
+    items: &impl Var<ObservableVec<D>>,
+    item_fn: &impl Var<WidgetFn<VirtualItemArgs<D>>>,
+    list: &EditableUiNodeListRef,
+    slots: &mut IdMap<WidgetId, Slot<D>>,
+) {
+    let mut bound = HashSet::new();
+    let mut pool = vec![];
+    for (id, s) in slots.iter_mut() {
+        match s.index {
+            Some(i) if range.contains(&i) => {
+                bound.insert(i);
+            }
+            _ => {
+                s.index = None;
+                pool.push(*id);
+            }
+        }
+    }
+
+    items.with(|l| {
+        let mut new_fn = None;
+        for i in range {
+            if bound.contains(&i) {
+                continue;
+            }
+            let item = l[i].clone();
+            if let Some(id) = pool.pop() {
+                let s = slots.get_mut(&id).unwrap();
+                s.index = Some(i);
+                s.index_var.set(i);
+                s.item_var.set(item);
+            } else {
+                let index_var = var(i);
+                let item_var = var(item);
+                let item_fn = new_fn.get_or_insert_with(|| item_fn.get());
+                let mut wgt = item_fn(VirtualItemArgs {
+                    index: index_var.read_only(),
+                    item: item_var.read_only(),
+                })
+                .into_widget();
+                let id = wgt.with_context(WidgetUpdateMode::Ignore, || WIDGET.id()).unwrap();
+                slots.insert(
+                    id,
+                    Slot {
+                        index_var,
+                        item_var,
+                        index: Some(i),
+                    },
+                );
+                list.push(wgt);
+            }
+        }
+    });
+
+    let pool_len = POOL_LEN_VAR.get();
+    while pool.len() > pool_len {
+        let id = pool.pop().unwrap();
+        slots.remove(&id);
+        list.remove(id);
+    }
+}
+
+/// Item heights, with prefix sums.
+#[derive(Default)]
+struct HeightIndex {
+    // measured height of each item, or `None` to use the estimate.
+    heights: Vec<Option<Px>>,
+    estimate: Px,
+    spacing: Px,
+    // Fenwick tree of the item heights plus spacing.
+    tree: Vec<i64>,
+}
+impl HeightIndex {
+    fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    fn item_len(&self, i: usize) -> i64 {
+        (self.heights[i].unwrap_or(self.estimate) + self.spacing).0 as i64
+    }
+
+    fn rebuild(&mut self) {
+        let n = self.len();
+        self.tree.clear();
+        self.tree.resize(n + 1, 0);
+        for i in 0..n {
+            let j = i + 1;
+            self.tree[j] += self.item_len(i);
+            let p = j + (j & j.wrapping_neg());
+            if p <= n {
+                self.tree[p] += self.tree[j];
+            }
+        }
+    }
+
+    fn reset(&mut self, len: usize) {
+        self.heights.clear();
+        self.heights.resize(len, None);
+        self.rebuild();
+    }
+
+    fn set_metrics(&mut self, estimate: Px, spacing: Px) {
+        if self.estimate != estimate || self.spacing != spacing {
+            self.estimate = estimate;
+            self.spacing = spacing;
+            self.rebuild();
+        }
+    }
+
+    fn set(&mut self, i: usize, height: Px) {
+        let prev = self.item_len(i);
+        self.heights[i] = Some(height);
+        let delta = self.item_len(i) - prev;
+        if delta != 0 {
+            let mut j = i + 1;
+            while j < self.tree.len() {
+                self.tree[j] += delta;
+                j += j & j.wrapping_neg();
+            }
+        }
+    }
+
+    fn insert(&mut self, index: usize, count: usize) {
+        self.heights.splice(index..index, std::iter::repeat_n(None, count));
+        self.rebuild();
+    }
+
+    fn remove(&mut self, range: ops::Range<usize>) {
+        self.heights.drain(range);
+        self.rebuild();
+    }
+
+    fn move_item(&mut self, from: usize, to: usize) {
+        let h = self.heights.remove(from);
+        self.heights.insert(to, h);
+        self.rebuild();
+    }
+
+    /// Offset of the item `i`.
+    fn offset(&self, i: usize) -> Px {
+        let mut s = 0;
+        let mut j = i;
+        while j > 0 {
+            s += self.tree[j];
+            j -= j & j.wrapping_neg();
+        }
+        Px(s.min(i32::MAX as i64) as i32)
+    }
+
+    fn total(&self) -> Px {
+        if self.is_empty() {
+            Px(0)
+        } else {
+            self.offset(self.len()) - self.spacing
+        }
+    }
+
+    /// Index of the item at the `y` offset, clamped to the items range.
+    fn find(&self, y: Px) -> usize {
+        let n = self.len();
+        let mut rem = y.0.max(0) as i64;
+        let mut pos = 0;
+        let mut step = if n == 0 { 0 } else { 1 << n.ilog2() };
+        while step > 0 {
+            if pos + step <= n && self.tree[pos + step] <= rem {
+                pos += step;
+                rem -= self.tree[pos];
+            }
+            step >>= 1;
+        }
+        pos.min(n.saturating_sub(1))
+    }
+
+    /// Range of items that intersect the `y` range.
+    fn range(&self, y: &ops::Range<Px>) -> ops::Range<usize> {
+        if self.is_empty() || y.end <= Px(0) {
+            return 0..0;
+        }
+        self.find(y.start)..self.find(y.end) + 1
+    }
+}
@@ -0,0 +1,168 @@
+//! Keymap editor widget.
+
+use zng_app::{
+    event::{Command, CommandNameExt as _},
+    shortcut::{GestureKey, KeyGesture, Shortcut, Shortcuts},
+};
+use zng_ext_font::FontWeight;
+use zng_ext_input::{
+    keyboard::Key,
+    keymap::{KeymapEntry, KEYMAP},
+};
+use zng_wgt::prelude::*;
+use zng_wgt_button::Button;
+use zng_wgt_container::Container;
+use zng_wgt_filter::opacity;
+use zng_wgt_rule_line::hr::Hr;
+use zng_wgt_scroll::{Scroll, ScrollMode};
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_text::Text;
+use zng_wgt_text_input::TextInput;
+
+/// Command shortcuts editor.
+///
+/// Lists all commands in the [`KEYMAP`] with their current shortcuts, the user can search by command name or shortcut,
+/// rebind by pressing the edit button and then the new shortcut and reset to the default shortcuts. Conflicting shortcuts
+/// are not applied, the conflict is shown in the command entry.
+///
+/// Note that this widget only edits the keymap, bind the [`KEYMAP.keymap`] variable to a config key to persist the changes.
+///
+/// [`KEYMAP.keymap`]: zng_ext_input::keymap::KEYMAP::keymap
+#[widget($crate::keymap::KeymapEditor)]
+pub struct KeymapEditor(WidgetBase);
+impl KeymapEditor {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            zng_wgt_container::padding = 10;
+        }
+        self.widget_builder().push_build_action(|wgt| {
+            wgt.set_child(keymap_editor_node());
+        });
+    }
+}
+
+/// Implements the [`KeymapEditor!`] inner widgets.
+///
+/// [`KeymapEditor!`]: struct@KeymapEditor
+pub fn keymap_editor_node() -> impl UiNode {
+    let search = var(Txt::from(""));
+    let entries = merge_var!(KEYMAP.keymap(), search.clone(), |_, s| {
+        let s = s.trim().to_lowercase();
+        let mut entries = KEYMAP.entries();
+        entries.retain(|e| {
+            s.is_empty() || e.command.name().with(|n| n.to_lowercase().contains(&s)) || e.shortcut.to_string().to_lowercase().contains(&s)
+        });
+        entries.sort_by(|a, b| a.command.name().with(|a| b.command.name().with(|b| a.cmp(b))));
+        entries
+    });
+
+    Container! {
+        child_top = Container! {
+            child = TextInput! {
+                txt = search;
+                style_fn = zng_wgt_text_input::SearchStyle!();
+                placeholder_txt = "search shortcuts";
+            };
+            child_bottom = Hr!(zng_wgt::margin = (10, 0, 0, 0)), 0;
+        }, 0;
+        child = Scroll! {
+            mode = ScrollMode::VERTICAL;
+            child_align = Align::FILL_TOP;
+            padding = (10, 0);
+            child = presenter(
+                entries,
+                wgt_fn!(|entries: Vec<KeymapEntry>| {
+                    Stack! {
+                        direction = StackDirection::top_to_bottom();
+                        spacing = 4;
+                        children = entries.into_iter().map(|e| keymap_entry(e).boxed()).collect::<UiNodeVec>();
+                    }
+                }),
+            );
+        };
+    }
+}
+
+fn keymap_entry(entry: KeymapEntry) -> impl UiNode {
+    let cmd = entry.command;
+    let is_capturing = var(false);
+    let conflict = var(Txt::from(""));
+    let shortcut = entry.shortcut.to_txt();
+    Container! {
+        child_start = Text! {
+            txt = cmd.name();
+            font_weight = FontWeight::BOLD;
+            zng_wgt_size_offset::width = 250;
+        }, 10;
+        child = Stack! {
+            direction = StackDirection::left_to_right();
+            spacing = 4;
+            children = ui_vec![
+                Button! {
+                    child = Text!(is_capturing.map(move |&c| if c { Txt::from("press shortcut…") } else { shortcut.clone() }));
+                    zng_wgt_size_offset::min_width = 150;
+                    on_click = hn!(is_capturing, conflict, |_| {
+                        is_capturing.set(true);
+                        conflict.set("");
+                    });
+                    zng_wgt_input::focus::on_blur = hn!(is_capturing, |_| {
+                        is_capturing.set(false);
+                    });
+                    zng_wgt_input::keyboard::on_pre_key_down = hn!(is_capturing, conflict, |args: &zng_ext_input::keyboard::KeyInputArgs| {
+                        if !is_capturing.get() {
+                            return;
+                        }
+                        args.propagation().stop();
+                        if matches!(args.key, Key::Escape) && args.modifiers.is_empty() {
+                            is_capturing.set(false);
+                        } else if let Some(s) = capture_shortcut(args) {
+                            is_capturing.set(false);
+                            if let Err(e) = KEYMAP.rebind(cmd, s) {
+                                conflict.set(conflict_msg(&e.shortcut, &e.with));
+                            }
+                        }
+                    });
+                },
+                Button! {
+                    child = Text!("reset");
+                    zng_wgt::enabled = entry.is_overridden();
+                    on_click = hn!(conflict, |_| {
+                        KEYMAP.reset(cmd);
+                        conflict.set("");
+                    });
+                },
+                Button! {
+                    child = Text!("clear");
+                    zng_wgt::enabled = !entry.shortcut.is_empty();
+                    on_click = hn!(conflict, |_| {
+                        KEYMAP.force_rebind(cmd, Shortcuts::new());
+                        conflict.set("");
+                    });
+                },
+            ];
+        };
+        child_bottom = Text! {
+            txt = conflict.clone();
+            opacity = 70.pct();
+            zng_wgt::visibility = conflict.map(|c| if c.is_empty() { Visibility::Collapsed } else { Visibility::Visible });
+        }, 2;
+    }
+}
+
+/// Converts the key press to a shortcut, returns `None` for modifier keys.
+fn capture_shortcut(args: &zng_ext_input::keyboard::KeyInputArgs) -> Option<Shortcuts> {
+    let key = GestureKey::try_from(args.shortcut_key().clone()).ok()?;
+    Some([Shortcut::Gesture(KeyGesture::new(args.modifiers, key))].into())
+}
+
+fn conflict_msg(shortcut: &Shortcut, with: &[Command]) -> Txt {
+    let mut names = String::new();
+    for c in with {
+        if !names.is_empty() {
+            names.push_str(", ");
+        }
+        c.name().with(|n| names.push_str(n));
+    }
+    formatx!("{shortcut} is already used by {names}")
+}
@@ -4,6 +4,7 @@
 
 zng_wgt::enable_widget_macros!();
 
+pub mod keymap;
 mod view_fn;
 pub use view_fn::*;
 
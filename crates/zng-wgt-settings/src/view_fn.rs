@@ -7,11 +7,12 @@ use zng_app::{
 };
 use zng_ext_config::{
     settings::{Category, CategoryId, Setting, SettingBuilder, SETTINGS},
-    ConfigKey,
+    ConfigKey, ConfigLayer,
 };
-use zng_ext_font::FontWeight;
+use zng_ext_font::{FontStyle, FontWeight};
 use zng_var::{ContextInitHandle, ReadOnlyContextVar};
 use zng_wgt::{node::with_context_var, prelude::*, Wgt, WidgetFn, EDITORS, ICONS};
+use zng_wgt_button::{Button, LinkStyle};
 use zng_wgt_container::Container;
 use zng_wgt_filter::opacity;
 use zng_wgt_markdown::Markdown;
@@ -107,10 +108,22 @@ pub fn default_category_item_fn(args: CategoryItemArgs) -> impl UiNode {
 ///
 /// See [`CATEGORY_HEADER_FN_VAR`] for more details.
 pub fn default_category_header_fn(args: CategoryHeaderArgs) -> impl UiNode {
-    Text! {
-        txt = args.category.name().clone();
-        font_size = 1.5.em();
+    let id = args.category.id().clone();
+    Container! {
         zng_wgt::margin = (10, 10, 10, 28);
+        child = Text! {
+            txt = args.category.name().clone();
+            font_size = 1.5.em();
+        };
+        child_end = Button! {
+            style_fn = LinkStyle!();
+            zng_wgt::align = Align::BOTTOM;
+            child = Text!("reset all");
+            tooltip = Tip!(Text!("reset all settings in this category"));
+            on_click = hn!(|_| {
+                SETTINGS.reset(|_, c| c == &id);
+            });
+        }, 10;
     }
 }
 
@@ -154,6 +167,21 @@ pub fn default_setting_fn(args: SettingArgs) -> impl UiNode {
     let name = args.setting.name().clone();
     let description = args.setting.description().clone();
     let can_reset = args.setting.can_reset();
+    let is_changed = args.setting.is_changed();
+    let notes = {
+        let mut notes = vec![];
+        if let Some(r) = args.setting.range() {
+            notes.push(formatx!("range: {}", r.display()));
+        }
+        if args.setting.requires_restart() {
+            notes.push(Txt::from_static("requires restart"));
+        }
+        Txt::from_str(&notes.join(", "))
+    };
+    let is_system = match args.setting.origin() {
+        Some(o) => o.map(|o| *o == Some(ConfigLayer::System)).boxed(),
+        None => LocalVar(false).boxed(),
+    };
     Container! {
         setting = args.setting.clone();
 
@@ -190,9 +218,32 @@ pub fn default_setting_fn(args: SettingArgs) -> impl UiNode {
             }
         }, 4;
         child_top = Container! {
-            child_top = Text! {
-                txt = name;
-                font_weight = FontWeight::BOLD;
+            child_top = Stack! {
+                direction = StackDirection::left_to_right();
+                spacing = 4;
+                children = ui_vec![
+                    Text! {
+                        txt = name;
+                        font_weight = FontWeight::BOLD;
+                    },
+                    Text! {
+                        txt = "•";
+                        tooltip = Tip!(Text!("changed"));
+                        zng_wgt::visibility = is_changed.map_into();
+                    },
+                    Text! {
+                        txt = notes.clone();
+                        opacity = 70.pct();
+                        font_style = FontStyle::Italic;
+                        zng_wgt::visibility = !notes.is_empty();
+                    },
+                    Text! {
+                        txt = "managed by system";
+                        opacity = 70.pct();
+                        font_style = FontStyle::Italic;
+                        zng_wgt::visibility = is_system.map_into();
+                    },
+                ];
             }, 4;
             child = Markdown! {
                 txt = description;
@@ -207,6 +258,12 @@ pub fn default_setting_fn(args: SettingArgs) -> impl UiNode {
 pub fn default_settings_fn(args: SettingsArgs) -> impl UiNode {
     Container! {
         child_top = args.header, 5;
+        child_bottom = Text! {
+            txt = "some changes only apply after restart";
+            zng_wgt::margin = (10, 10, 0, 28);
+            font_style = FontStyle::Italic;
+            zng_wgt::visibility = SETTINGS.restart_required().map_into();
+        }, 0;
         child = Scroll! {
             mode = ScrollMode::VERTICAL;
             padding = (0, 20, 20, 10);
@@ -18,7 +18,7 @@ use zng_wgt::prelude::*;
 /// # `x` and `y`
 ///
 /// You can use the [`x`](fn@x) and [`y`](fn@y) properties to only set the position in one dimension.
-#[property(LAYOUT, default((0, 0)))]
+#[property(LAYOUT, animatable, default((0, 0)))]
 pub fn offset(child: impl UiNode, offset: impl IntoVar<Vector>) -> impl UiNode {
     let offset = offset.into_var();
     match_node(child, move |child, op| match op {
@@ -344,7 +344,7 @@ pub fn max_height(child: impl UiNode, max_height: impl IntoVar<Length>) -> impl
 /// [`height`]: fn@height
 /// [`force_size`]: fn@force_size
 /// [`align`]: fn@zng_wgt::align
-#[property(SIZE)]
+#[property(SIZE, animatable)]
 pub fn size(child: impl UiNode, size: impl IntoVar<Size>) -> impl UiNode {
     let size = size.into_var();
     match_node(child, move |child, op| match op {
@@ -400,7 +400,7 @@ pub fn size(child: impl UiNode, size: impl IntoVar<Size>) -> impl UiNode {
 /// [`min_width`]: fn@min_width
 /// [`max_width`]: fn@max_width
 /// [`force_width`]: fn@force_width
-#[property(SIZE)]
+#[property(SIZE, animatable)]
 pub fn width(child: impl UiNode, width: impl IntoVar<Length>) -> impl UiNode {
     let width = width.into_var();
     match_node(child, move |child, op| match op {
@@ -455,7 +455,7 @@ pub fn width(child: impl UiNode, width: impl IntoVar<Length>) -> impl UiNode {
 /// [`min_height`]: fn@min_height
 /// [`max_height`]: fn@max_height
 /// [`force_height`]: fn@force_height
-#[property(SIZE)]
+#[property(SIZE, animatable)]
 pub fn height(child: impl UiNode, height: impl IntoVar<Length>) -> impl UiNode {
     let height = height.into_var();
     match_node(child, move |child, op| match op {
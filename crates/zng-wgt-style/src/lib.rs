@@ -291,11 +291,23 @@ impl StyleBuilder {
         }
     }
 
+    /// Override `self` with `other`, even if `other` is a replace style.
+    ///
+    /// The properties of `self` that are not reset in `other` are retained and `self` keeps its replace flag.
+    pub fn layer(&mut self, other: StyleBuilder) {
+        self.builder.extend(other.builder);
+    }
+
     /// if the style removes all contextual properties.
     pub fn is_replace(&self) -> bool {
         self.replace
     }
 
+    /// Sets if the style removes all contextual properties.
+    pub fn set_replace(&mut self, replace: bool) {
+        self.replace = replace;
+    }
+
     /// If the style does nothing.
     pub fn is_empty(&self) -> bool {
         !self.builder.has_properties() && !self.builder.has_whens() && !self.builder.has_unsets()
@@ -417,6 +429,51 @@ impl StyleFn {
             })
         }
     }
+
+    /// New style function that instantiates `self` and `other` and then [`layer`] `other` over `self`.
+    ///
+    /// Unlike [`with_extend`] the `other` style never replaces `self`, even if it sets `replace = true`, so the
+    /// properties of `self` that are not reset by `other` are retained.
+    ///
+    /// [`layer`]: StyleBuilder::layer
+    /// [`with_extend`]: Self::with_extend
+    pub fn with_layer(self, other: StyleFn) -> StyleFn {
+        if self.is_nil() {
+            other
+        } else if other.is_nil() {
+            self
+        } else {
+            StyleFn::new(move |args| match (self(args), other(args)) {
+                (Some(mut a), Some(b)) => {
+                    a.layer(b);
+                    a
+                }
+                (Some(r), None) => r,
+                (None, Some(mut r)) => {
+                    r.set_replace(false);
+                    r
+                }
+                _ => StyleBuilder::default(),
+            })
+        }
+    }
+
+    /// New style function that composes the `layers` in priority order.
+    ///
+    /// The layers are sorted by priority, lowest first, layers with the same priority retain the iteration order. The
+    /// first layer defines the base style and the other layers are applied over it using [`with_layer`], so a layer
+    /// never discards the properties of lower priority layers.
+    ///
+    /// [`with_layer`]: Self::with_layer
+    pub fn layered(layers: impl IntoIterator<Item = (i32, StyleFn)>) -> StyleFn {
+        let mut layers: Vec<_> = layers.into_iter().filter(|(_, l)| !l.is_nil()).collect();
+        layers.sort_by_key(|(p, _)| *p);
+        let mut layers = layers.into_iter().map(|(_, l)| l);
+        match layers.next() {
+            Some(base) => layers.fold(base, StyleFn::with_layer),
+            None => StyleFn::nil(),
+        }
+    }
 }
 impl fmt::Debug for StyleFn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -470,9 +527,31 @@ fn nil_func(_: &StyleArgs) -> Option<StyleBuilder> {
 /// # }
 /// ```
 ///
+/// # Layers
+///
+/// The macro can also compose multiple style functions or style instances, the `layer:` prefix followed by a list of
+/// style expressions is equivalent to [`StyleFn::layered`], the styles are applied in the declared order and each
+/// style extends the previous ones, even if it sets `replace = true`. A priority can be set for each item using
+/// `style => priority`, items without priority have priority `0`.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_style::*;
+/// # fn main() {
+/// # let base_style = StyleFn::nil();
+/// let style_fn = style_fn!(layer: base_style, Style! { /* feature */ } => 10);
+/// # }
+/// ```
+///
 /// [`clmv!`]: zng_wgt::prelude::clmv
 #[macro_export]
 macro_rules! style_fn {
+    (layer: $($style:expr $(=> $priority:expr)?),+ $(,)?) => {
+        $crate::StyleFn::layered([$(
+            ($crate::__style_layer_priority!($($priority)?), $crate::StyleFn::from($style)),
+        )+])
+    };
     ($fn:path) => {
         $crate::StyleFn::new($fn)
     };
@@ -486,6 +565,17 @@ macro_rules! style_fn {
     };
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __style_layer_priority {
+    () => {
+        0
+    };
+    ($priority:expr) => {
+        $priority
+    };
+}
+
 context_var! {
     static MISSING_STYLE_VAR: StyleFn = StyleFn::nil();
 }
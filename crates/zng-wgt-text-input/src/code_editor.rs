@@ -0,0 +1,145 @@
+//! Code editor widget, styles and properties.
+
+use std::fmt::Write as _;
+
+use zng_wgt::prelude::*;
+use zng_wgt_style::style_fn;
+use zng_wgt_text::{node::TEXT, *};
+
+use crate::TextInput;
+
+/// Multi-line text editor for source code.
+///
+/// The editor is a [`TextInput!`] that uses a monospace font, does not wrap, accepts tab and enter and shows a
+/// line-number [`Gutter!`] at the start. Set the [`syntax_highlight`] property to style the code.
+///
+/// # Performance
+///
+/// Monospace fonts without glyph substitutions are shaped by a fast path that does not call the font shaper for
+/// ASCII words. After an edit only the changed lines are highlighted again and the words of unchanged lines are
+/// reused from the font word cache, so the text reshape cost is mostly proportional to the changed lines.
+///
+/// # Shorthand
+///
+/// The `CodeEditor!` macro provides shorthand syntax that sets the `txt` property.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_text_input::code_editor::*;
+/// #
+/// # fn main() {
+/// let code = CodeEditor!(var_from("fn main() {\n}\n"));
+/// # }
+/// ```
+///
+/// [`TextInput!`]: struct@TextInput
+/// [`Gutter!`]: struct@Gutter
+/// [`syntax_highlight`]: fn@zng_wgt_text::syntax_highlight
+#[widget($crate::code_editor::CodeEditor {
+    ($txt:expr) => {
+        txt = $txt;
+    };
+})]
+pub struct CodeEditor(TextInput);
+impl CodeEditor {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+
+            font_family = ["JetBrains Mono", "Consolas", "monospace"];
+            txt_wrap = false;
+            accepts_tab = true;
+            accepts_enter = true;
+            style_base_fn = style_fn!(|_| DefaultStyle!());
+        }
+    }
+}
+
+/// Code editor default style.
+///
+/// Extends the text input default style with a [`Gutter!`] set on the `child_out_start`. The gutter is aligned with the text lines
+/// only if the top padding of the editor and the gutter are equal.
+///
+/// [`Gutter!`]: struct@Gutter
+#[widget($crate::code_editor::DefaultStyle)]
+pub struct DefaultStyle(crate::DefaultStyle);
+impl DefaultStyle {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            zng_wgt_container::padding = (7, 10, 7, 6);
+            zng_wgt_container::child_out_start = Gutter! {
+                zng_wgt_container::padding = (7, 6, 7, 10);
+            }, 0;
+        }
+    }
+}
+
+/// Line numbers of the parent [`CodeEditor!`].
+///
+/// The gutter is a read-only text that shows one number for each line of the parent text, it uses the same font
+/// and line height, so the numbers align with the lines if the parent does not wrap.
+///
+/// The gutter must be set in a property of the parent text, like `child_out_start`, it reads the text from the
+/// parent [`TEXT`] context.
+///
+/// [`CodeEditor!`]: struct@CodeEditor
+/// [`TEXT`]: zng_wgt_text::node::TEXT
+#[widget($crate::code_editor::Gutter)]
+pub struct Gutter(Text);
+impl Gutter {
+    fn widget_intrinsic(&mut self) {
+        let numbers = var(Txt::from_static("1"));
+        widget_set! {
+            self;
+
+            txt = numbers.clone();
+            txt_align = Align::TOP_END;
+            txt_editable = false;
+            txt_selectable = false;
+            syntax_highlight = SyntaxHighlightFn::nil();
+            font_color = FONT_COLOR_VAR.map(|c| c.with_alpha(50.pct()));
+            zng_wgt::hit_test_mode = false;
+            zng_wgt_access::accessible = false;
+        }
+
+        self.widget_builder().push_build_action(move |wgt| {
+            let numbers = numbers.clone();
+            wgt.push_intrinsic(NestGroup::CONTEXT, "line_numbers", move |child| line_numbers(child, numbers));
+        });
+    }
+}
+
+/// Updates `numbers` to the line numbers of the contextual text.
+fn line_numbers(child: impl UiNode, numbers: ArcVar<Txt>) -> impl UiNode {
+    let mut count = 0;
+    match_node(child, move |_, op| {
+        let update = match op {
+            UiNodeOp::Init => {
+                WIDGET.sub_var(&TEXT.resolved().txt);
+                true
+            }
+            UiNodeOp::Deinit => {
+                count = 0;
+                false
+            }
+            UiNodeOp::Update { .. } => TEXT.resolved().txt.is_new(),
+            _ => false,
+        };
+        if update {
+            let c = TEXT.resolved().txt.with(|t| t.bytes().filter(|&b| b == b'\n').count() + 1);
+            if c != count {
+                count = c;
+                let mut s = String::with_capacity(c * 4);
+                for i in 1..=c {
+                    if i > 1 {
+                        s.push('\n');
+                    }
+                    let _ = write!(&mut s, "{i}");
+                }
+                numbers.set(s);
+            }
+        }
+    })
+}
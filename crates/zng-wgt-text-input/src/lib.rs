@@ -11,6 +11,7 @@
 
 zng_wgt::enable_widget_macros!();
 
+pub mod code_editor;
 pub mod label;
 pub mod selectable;
 
@@ -1,4 +1,5 @@
-use colors::{ACCENT_COLOR_VAR, BASE_COLOR_VAR};
+use colors::BASE_COLOR_VAR;
+use theme::PRIMARY_COLOR_VAR;
 use zng_ext_clipboard::{COPY_CMD, CUT_CMD, PASTE_CMD};
 use zng_wgt::base_color;
 use zng_wgt::{align, is_disabled, margin, prelude::*};
@@ -160,7 +161,7 @@ impl DefaultStyle {
             popup::context_capture = default_popup_context_capture();
             context_menu_fn = WidgetFn::new(default_context_menu);
             selection_toolbar_fn = WidgetFn::new(default_selection_toolbar);
-            selection_color = ACCENT_COLOR_VAR.rgba_map(|c| c.with_alpha(30.pct()));
+            selection_color = PRIMARY_COLOR_VAR.rgba_map(|c| c.with_alpha(30.pct()));
 
             when *#is_cap_hovered || *#is_return_focus {
                 border = {
@@ -172,7 +173,7 @@ impl DefaultStyle {
             when *#is_focused {
                 border = {
                     widths: 1,
-                    sides: ACCENT_COLOR_VAR.rgba_into(),
+                    sides: PRIMARY_COLOR_VAR.rgba_into(),
                 };
             }
 
@@ -304,12 +305,14 @@ impl FieldStyle {
         let adorn = merge_var!(top_txt.clone(), FIELD_HELP_VAR, |t, h| (t.is_empty(), h.is_empty()));
 
         let chars_count = var(0usize);
-        let has_max_count = MAX_CHARS_COUNT_VAR.map(|&c| c > 0);
+        let graphemes_count = var(0usize);
+        let has_max_count = merge_var!(MAX_CHARS_COUNT_VAR, MAX_GRAPHEMES_COUNT_VAR, |&c, &g| c > 0 || g > 0);
 
         widget_set! {
             self;
             zng_wgt_data::get_data_notes_top = top_notes.clone();
             get_chars_count = chars_count.clone();
+            get_graphemes_count = graphemes_count.clone();
             auto_selection = true;
 
             foreground_highlight = {
@@ -350,11 +353,17 @@ impl FieldStyle {
             });
 
             max_chars_count_adorner_fn = has_max_count.map(move |&has| if has {
-                wgt_fn!(chars_count, |_| Text! {
+                wgt_fn!(chars_count, graphemes_count, |_| Text! {
                     focusable = false;
                     txt_editable = false;
                     txt_selectable = false;
-                    txt = merge_var!(chars_count.clone(), text::MAX_CHARS_COUNT_VAR, |c, m| formatx!("{c}/{m}"));
+                    txt = merge_var!(
+                        chars_count.clone(),
+                        text::MAX_CHARS_COUNT_VAR,
+                        graphemes_count.clone(),
+                        text::MAX_GRAPHEMES_COUNT_VAR,
+                        |c, m, g, gm| if *gm > 0 { formatx!("{g}/{gm}") } else { formatx!("{c}/{m}") }
+                    );
                     font_color = text::FONT_COLOR_VAR.map(|c| colors::GRAY.with_alpha(10.pct()).mix_normal(*c));
                     font_size = 0.8.em();
                     align = Align::BOTTOM_END;
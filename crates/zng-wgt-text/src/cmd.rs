@@ -8,6 +8,7 @@
 use std::{any::Any, borrow::Cow, fmt, ops, sync::Arc};
 
 use parking_lot::Mutex;
+use unicode_segmentation::UnicodeSegmentation as _;
 use zng_ext_font::*;
 use zng_ext_undo::*;
 use zng_wgt::prelude::*;
@@ -38,6 +39,32 @@ command! {
     ///
     /// [`txt_parse`]: fn@super::txt_parse
     pub static PARSE_CMD;
+
+    /// Move to the next or previous match of the [`txt_search`].
+    ///
+    /// The parameter can be a [`TextSearchOp`], if not set moves to the next match. If the text is selectable the
+    /// caret selects the current match.
+    ///
+    /// [`txt_search`]: fn@super::txt_search
+    pub static TEXT_SEARCH_CMD = {
+        name: "Find Next",
+        shortcut: shortcut!(F3),
+        shortcut_filter: ShortcutFilter::FOCUSED | ShortcutFilter::CMD_ENABLED,
+    };
+}
+
+/// Represents a search navigation operation that can be send to a text using [`TEXT_SEARCH_CMD`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextSearchOp {
+    /// Move to the next match after the current match or after the caret. Wraps around to the first match.
+    #[default]
+    Next,
+    /// Move to the previous match before the current match or before the caret. Wraps around to the last match.
+    Prev,
+    /// Move to the first match.
+    First,
+    /// Move to the last match.
+    Last,
 }
 
 struct SharedTextEditOp {
@@ -92,11 +119,13 @@ impl TextEditOp {
             insert: Txt,
             selection_state: SelectionState,
             removed: Txt,
+            masked: Option<MaskedEdit>,
         }
         let data = InsertData {
             insert: insert.into(),
             selection_state: SelectionState::PreInit,
             removed: Txt::from_static(""),
+            masked: None,
         };
 
         Self::new(data, move |data, op| match op {
@@ -125,9 +154,19 @@ impl TextEditOp {
                     data.selection_state = SelectionState::Caret(caret.index.unwrap_or(CaretIndex::ZERO));
                 }
 
-                Self::apply_max_count(redo, &ctx.txt, rmv_range, &mut data.insert)
+                Self::apply_max_count(redo, &ctx.txt, rmv_range.clone(), &mut data.insert);
+                data.masked = Self::apply_mask(redo, &ctx.txt, rmv_range, &data.insert);
             }
             UndoFullOp::Op(UndoOp::Redo) => {
+                if let Some(m) = &data.masked {
+                    let _ = TEXT.resolved().txt.set(m.txt.clone());
+
+                    let mut caret = TEXT.resolve_caret();
+                    caret.set_char_index(m.caret);
+                    caret.clear_selection();
+                    return;
+                }
+
                 let insert = &data.insert;
 
                 match data.selection_state {
@@ -174,12 +213,16 @@ impl TextEditOp {
                 let i = insert_idx.index;
                 let removed = &data.removed;
 
-                TEXT.resolved()
-                    .txt
-                    .modify(clmv!(removed, |args| {
-                        args.to_mut().to_mut().replace_range(i..i + len, removed.as_str());
-                    }))
-                    .unwrap();
+                if let Some(m) = &data.masked {
+                    let _ = TEXT.resolved().txt.set(m.prev.clone());
+                } else {
+                    TEXT.resolved()
+                        .txt
+                        .modify(clmv!(removed, |args| {
+                            args.to_mut().to_mut().replace_range(i..i + len, removed.as_str());
+                        }))
+                        .unwrap();
+                }
 
                 let mut caret = TEXT.resolve_caret();
                 caret.set_index(caret_idx);
@@ -210,7 +253,16 @@ impl TextEditOp {
             } => {
                 if within_undo_interval {
                     if let Some(next_data) = next_data.downcast_mut::<InsertData>() {
-                        if let (SelectionState::Caret(mut after_idx), SelectionState::Caret(caret)) =
+                        if data.masked.is_some() || next_data.masked.is_some() {
+                            if let (Some(m), Some(next_m)) = (&mut data.masked, &next_data.masked) {
+                                if m.txt == next_m.prev {
+                                    m.txt = next_m.txt.clone();
+                                    m.caret = next_m.caret;
+                                    data.insert.push_str(&next_data.insert);
+                                    *merged = true;
+                                }
+                            }
+                        } else if let (SelectionState::Caret(mut after_idx), SelectionState::Caret(caret)) =
                             (data.selection_state, next_data.selection_state)
                         {
                             after_idx.index += data.insert.len();
@@ -487,30 +539,170 @@ impl TextEditOp {
         })
     }
 
+    /// Remove all selection ranges, including the [`extra_selections`].
+    ///
+    /// This is the cut operation when there are multiple selection ranges, the undo restores all selections.
+    ///
+    /// [`extra_selections`]: crate::node::CaretInfo::extra_selections
+    pub fn delete_selections() -> Self {
+        #[derive(Default)]
+        struct DeleteSelectionsData {
+            caret: Option<CaretIndex>,
+            selection_index: Option<CaretIndex>,
+            extra_selections: Vec<ops::Range<CaretIndex>>,
+            ranges: Vec<ops::Range<usize>>,
+            removed: Vec<Txt>,
+        }
+
+        Self::new(DeleteSelectionsData::default(), move |data, op| match op {
+            UndoFullOp::Init { .. } => {
+                let ctx = TEXT.resolved();
+                let caret = &ctx.caret;
+                data.caret = caret.index;
+                data.selection_index = caret.selection_index;
+                data.extra_selections.clone_from(&caret.extra_selections);
+                data.ranges = caret
+                    .selection_ranges()
+                    .into_iter()
+                    .map(|r| r.start.index..r.end.index)
+                    .filter(|r| !r.is_empty())
+                    .collect();
+            }
+            UndoFullOp::Op(UndoOp::Redo) => {
+                let first = match data.ranges.first() {
+                    Some(r) => r.start,
+                    None => return,
+                };
+
+                {
+                    let mut caret = TEXT.resolve_caret();
+                    caret.set_char_index(first);
+                    caret.clear_selection();
+                }
+
+                let ctx = TEXT.resolved();
+                data.removed = ctx.txt.with(|t| data.ranges.iter().map(|r| Txt::from_str(&t[r.clone()])).collect());
+                let ranges = data.ranges.clone();
+                ctx.txt
+                    .modify(move |args| {
+                        let t = args.to_mut().to_mut();
+                        for r in ranges.into_iter().rev() {
+                            t.replace_range(r, "");
+                        }
+                    })
+                    .unwrap();
+            }
+            UndoFullOp::Op(UndoOp::Undo) => {
+                if data.removed.is_empty() {
+                    return;
+                }
+
+                let ranges = data.ranges.clone();
+                let removed = data.removed.clone();
+                TEXT.resolved()
+                    .txt
+                    .modify(move |args| {
+                        let t = args.to_mut().to_mut();
+                        for (r, txt) in ranges.into_iter().zip(removed) {
+                            t.insert_str(r.start, txt.as_str());
+                        }
+                    })
+                    .unwrap();
+
+                let mut caret = TEXT.resolve_caret();
+                caret.set_index(data.caret.unwrap_or(CaretIndex::ZERO));
+                caret.selection_index = data.selection_index;
+                caret.extra_selections.clone_from(&data.extra_selections);
+            }
+            UndoFullOp::Info { info } => *info = Some(Arc::new(formatx!("⌦ (x{})", data.ranges.len()))),
+            UndoFullOp::Merge { .. } => {}
+        })
+    }
+
     fn apply_max_count(redo: &mut bool, txt: &BoxedVar<Txt>, rmv_range: ops::Range<usize>, insert: &mut Txt) {
         let max_count = MAX_CHARS_COUNT_VAR.get();
         if max_count > 0 {
             // max count enabled
-            let (txt_count, rmv_count) = txt.with(|t| (t.chars().count(), t[rmv_range].chars().count()));
-            let ins_count = insert.chars().count();
-
-            let final_count = txt_count - rmv_count + ins_count;
-            if final_count > max_count {
-                // need to truncate insert
-                let ins_rmv = final_count - max_count;
-                if ins_rmv < ins_count {
-                    // can truncate insert
-                    let i = insert.char_indices().nth(ins_count - ins_rmv).unwrap().0;
-                    insert.truncate(i);
-                } else {
-                    // cannot insert
-                    debug_assert!(txt_count >= max_count);
-                    *redo = false;
-                }
+            Self::truncate_insert(
+                redo,
+                txt,
+                rmv_range.clone(),
+                insert,
+                max_count,
+                |t| t.chars().count(),
+                |t, n| t.char_indices().nth(n).map(|(i, _)| i),
+            );
+        }
+
+        let max_count = MAX_GRAPHEMES_COUNT_VAR.get();
+        if *redo && max_count > 0 {
+            // max graphemes enabled
+            Self::truncate_insert(
+                redo,
+                txt,
+                rmv_range,
+                insert,
+                max_count,
+                |t| t.graphemes(true).count(),
+                |t, n| t.grapheme_indices(true).nth(n).map(|(i, _)| i),
+            );
+        }
+    }
+    fn truncate_insert(
+        redo: &mut bool,
+        txt: &BoxedVar<Txt>,
+        rmv_range: ops::Range<usize>,
+        insert: &mut Txt,
+        max_count: usize,
+        count: fn(&str) -> usize,
+        nth_index: fn(&str, usize) -> Option<usize>,
+    ) {
+        let (txt_count, rmv_count) = txt.with(|t| (count(t), count(&t[rmv_range])));
+        let ins_count = count(insert);
+
+        let final_count = txt_count - rmv_count + ins_count;
+        if final_count > max_count {
+            // need to truncate insert
+            let ins_rmv = final_count - max_count;
+            if ins_rmv < ins_count {
+                // can truncate insert
+                let i = nth_index(insert, ins_count - ins_rmv).unwrap();
+                insert.truncate(i);
+            } else {
+                // cannot insert
+                debug_assert!(txt_count >= max_count);
+                *redo = false;
             }
         }
     }
 
+    /// Applies the [`INPUT_MASK_VAR`] to the text with `rmv_range` replaced by `insert`.
+    ///
+    /// Returns `None` if there is no mask or the edit cannot be inserted.
+    fn apply_mask(redo: &mut bool, txt: &BoxedVar<Txt>, rmv_range: ops::Range<usize>, insert: &str) -> Option<MaskedEdit> {
+        if !*redo {
+            return None;
+        }
+        let mask = INPUT_MASK_VAR.get();
+        if mask.is_none() {
+            return None;
+        }
+
+        let prev = txt.get();
+        let caret = rmv_range.start + insert.len();
+        let mut raw = prev.to_string();
+        raw.replace_range(rmv_range, insert);
+        let (txt, caret) = mask.apply(&raw, caret);
+
+        if txt == prev {
+            // input does not match the mask
+            *redo = false;
+            return None;
+        }
+
+        Some(MaskedEdit { prev, txt, caret })
+    }
+
     /// Remove all the text.
     pub fn clear() -> Self {
         #[derive(Default, Clone)]
@@ -567,6 +759,7 @@ impl TextEditOp {
     pub fn replace(mut select_before: ops::Range<usize>, insert: impl Into<Txt>, mut select_after: ops::Range<usize>) -> Self {
         let mut insert = insert.into();
         let mut removed = Txt::from_static("");
+        let mut masked = None::<MaskedEdit>;
 
         Self::new((), move |_, op| match op {
             UndoFullOp::Init { redo } => {
@@ -580,8 +773,15 @@ impl TextEditOp {
                 });
 
                 Self::apply_max_count(redo, &ctx.txt, select_before.clone(), &mut insert);
+                masked = Self::apply_mask(redo, &ctx.txt, select_before.clone(), &insert);
             }
             UndoFullOp::Op(UndoOp::Redo) => {
+                if let Some(m) = &masked {
+                    let _ = TEXT.resolved().txt.set(m.txt.clone());
+                    TEXT.resolve_caret().set_char_selection(m.caret, m.caret);
+                    return;
+                }
+
                 TEXT.resolved()
                     .txt
                     .modify(clmv!(select_before, insert, |args| {
@@ -594,6 +794,13 @@ impl TextEditOp {
             UndoFullOp::Op(UndoOp::Undo) => {
                 let ctx = TEXT.resolved();
 
+                if let Some(m) = &masked {
+                    let _ = ctx.txt.set(m.prev.clone());
+                    drop(ctx);
+                    TEXT.resolve_caret().set_char_selection(select_before.start, select_before.end);
+                    return;
+                }
+
                 select_after.start = ctx.segmented_text.snap_grapheme_boundary(select_after.start);
                 select_after.end = ctx.segmented_text.snap_grapheme_boundary(select_after.end);
 
@@ -681,6 +888,13 @@ impl TextEditOp {
         }
     }
 }
+/// Used by `TextEditOp::insert` and `replace` when the [`INPUT_MASK_VAR`] is set.
+struct MaskedEdit {
+    prev: Txt,
+    txt: Txt,
+    caret: usize,
+}
+
 /// Used by `TextEditOp::insert`, `backspace` and `delete`.
 #[derive(Clone, Copy, Default)]
 enum SelectionState {
@@ -1019,11 +1233,25 @@ impl TextSelectOp {
         Self::new(move || select_line_word_nearest_to(replace_selection, false, window_point))
     }
 
+    /// Replace the selection with a rectangular selection from `start` to `end`.
+    ///
+    /// Each line in between the points is selected from the insert point nearest to the `start` x to the insert point
+    /// nearest to the `end` x, the line nearest to `end` is the primary selection, the other lines are set in the
+    /// [`extra_selections`].
+    ///
+    /// This is the mouse primary button down and drag when holding ALT operation.
+    ///
+    /// [`extra_selections`]: crate::node::CaretInfo::extra_selections
+    pub fn select_rectangle(start: DipPoint, end: DipPoint) -> Self {
+        Self::new(move || select_rectangle(start, end))
+    }
+
     /// Select the full text.
     pub fn select_all() -> Self {
         Self::new(|| {
             let len = TEXT.resolved().segmented_text.text().len();
             let mut caret = TEXT.resolve_caret();
+            caret.extra_selections.clear();
             caret.set_char_selection(0, len);
             caret.skip_next_scroll = true;
         })
@@ -1083,17 +1311,9 @@ fn line_up_down(clear_selection: bool, diff: i8) {
         if li != next_li {
             drop(caret);
             let resolved = TEXT.resolved();
-            match laidout.shaped_text.line(next_li) {
-                Some(l) => {
-                    i.line = next_li;
-                    i.index = match l.nearest_seg(laidout.caret_retained_x) {
-                        Some(s) => s.nearest_char_index(laidout.caret_retained_x, resolved.segmented_text.text()),
-                        None => l.text_range().end,
-                    }
-                }
-                None => i = CaretIndex::ZERO,
-            };
-            i.index = resolved.segmented_text.snap_grapheme_boundary(i.index);
+            i = laidout
+                .shaped_text
+                .nearest_line_caret(next_li, laidout.caret_retained_x, &resolved.segmented_text);
             drop(resolved);
             caret = TEXT.resolve_caret();
             caret.set_index(i);
@@ -1142,17 +1362,9 @@ fn page_up_down(clear_selection: bool, diff: i8) {
             let resolved = TEXT.resolved();
 
             let target_line_y = li.rect().origin.y + page_y;
-            match laidout.shaped_text.nearest_line(target_line_y) {
-                Some(l) => {
-                    i.line = l.index();
-                    i.index = match l.nearest_seg(laidout.caret_retained_x) {
-                        Some(s) => s.nearest_char_index(laidout.caret_retained_x, resolved.segmented_text.text()),
-                        None => l.text_range().end,
-                    }
-                }
-                None => i = CaretIndex::ZERO,
-            };
-            i.index = resolved.segmented_text.snap_grapheme_boundary(i.index);
+            i = laidout
+                .shaped_text
+                .nearest_caret(PxPoint::new(laidout.caret_retained_x, target_line_y), &resolved.segmented_text);
 
             drop(resolved);
             caret = TEXT.resolve_caret();
@@ -1227,17 +1439,7 @@ fn nearest_to(clear_selection: bool, window_point: DipPoint) {
         let resolved = TEXT.resolved();
 
         //if has rendered
-        i = match laidout.shaped_text.nearest_line(pos.y) {
-            Some(l) => CaretIndex {
-                line: l.index(),
-                index: match l.nearest_seg(pos.x) {
-                    Some(s) => s.nearest_char_index(pos.x, resolved.segmented_text.text()),
-                    None => l.text_range().end,
-                },
-            },
-            None => CaretIndex::ZERO,
-        };
-        i.index = resolved.segmented_text.snap_grapheme_boundary(i.index);
+        i = laidout.shaped_text.nearest_caret(pos, &resolved.segmented_text);
 
         drop(resolved);
         caret = TEXT.resolve_caret();
@@ -1251,6 +1453,46 @@ fn nearest_to(clear_selection: bool, window_point: DipPoint) {
     }
 }
 
+fn select_rectangle(start: DipPoint, end: DipPoint) {
+    let laidout = TEXT.laidout();
+    let transform = match laidout.render_info.transform.inverse() {
+        Some(t) => t,
+        None => return,
+    };
+    let scale_factor = laidout.render_info.scale_factor;
+    let (start, end) = match (
+        transform.project_point(start.to_px(scale_factor)),
+        transform.project_point(end.to_px(scale_factor)),
+    ) {
+        (Some(s), Some(e)) => (s, e),
+        _ => return,
+    };
+    let (start_line, end_line) = match (laidout.shaped_text.nearest_line(start.y), laidout.shaped_text.nearest_line(end.y)) {
+        (Some(s), Some(e)) => (s.index(), e.index()),
+        _ => return,
+    };
+
+    let resolved = TEXT.resolved();
+    let mut primary = (CaretIndex::ZERO, CaretIndex::ZERO);
+    let mut extra = vec![];
+    for line in start_line.min(end_line)..=start_line.max(end_line) {
+        let a = laidout.shaped_text.nearest_line_caret(line, start.x, &resolved.segmented_text);
+        let b = laidout.shaped_text.nearest_line_caret(line, end.x, &resolved.segmented_text);
+        if line == end_line {
+            primary = (a, b);
+        } else {
+            extra.push(a.min(b)..a.max(b));
+        }
+    }
+    drop(resolved);
+
+    let mut caret = TEXT.resolve_caret();
+    caret.clear_selection();
+    caret.extra_selections = extra;
+    caret.set_selection(primary.0, primary.1);
+    caret.used_retained_x = false;
+}
+
 fn index_nearest_to(window_point: DipPoint, move_selection_index: bool) {
     let mut caret = TEXT.resolve_caret();
 
@@ -1274,17 +1516,7 @@ fn index_nearest_to(window_point: DipPoint, move_selection_index: bool) {
         drop(caret);
         let resolved = TEXT.resolved();
 
-        let mut i = match laidout.shaped_text.nearest_line(pos.y) {
-            Some(l) => CaretIndex {
-                line: l.index(),
-                index: match l.nearest_seg(pos.x) {
-                    Some(s) => s.nearest_char_index(pos.x, resolved.segmented_text.text()),
-                    None => l.text_range().end,
-                },
-            },
-            None => CaretIndex::ZERO,
-        };
-        i.index = resolved.segmented_text.snap_grapheme_boundary(i.index);
+        let i = laidout.shaped_text.nearest_caret(pos, &resolved.segmented_text);
 
         drop(resolved);
         caret = TEXT.resolve_caret();
@@ -23,6 +23,12 @@ pub mod node;
 mod text_properties;
 pub use text_properties::*;
 
+mod rich;
+pub use rich::*;
+
+mod validate;
+pub use validate::*;
+
 #[doc(hidden)]
 pub use zng_wgt::prelude::formatx as __formatx;
 
@@ -145,6 +151,15 @@ impl Text {
 #[property(CHILD, capture, default(""), widget_impl(Text))]
 pub fn txt(txt: impl IntoVar<Txt>) {}
 
+/// The text string with styled spans.
+///
+/// If set replaces the [`txt`] with the rich text, the text is read-only, text transform and white space correction
+/// are not applied. See [`RichTxt`] for more details.
+///
+/// [`txt`]: fn@txt
+#[property(CHILD, capture, widget_impl(Text))]
+pub fn rich_txt(txt: impl IntoVar<RichTxt>) {}
+
 /// Value that is parsed from the text and displayed as the text.
 ///
 /// This is an alternative to [`txt`] that converts to and from `T` if it can be formatted to display text and can parse, with
@@ -217,6 +232,11 @@ impl Text {
                 node::layout_text(child)
             });
 
+            if let Some(rich) = wgt.capture_var::<RichTxt>(property_id!(Self::rich_txt)) {
+                wgt.push_intrinsic(NestGroup::EVENT, "resolve_text", |child| node::resolve_rich_text(child, rich));
+                return;
+            }
+
             let text = if wgt.property(property_id!(Self::txt_parse)).is_some() {
                 wgt.capture_var(property_id!(Self::txt))
                     .unwrap_or_else(|| var(Txt::from_str("")).boxed())
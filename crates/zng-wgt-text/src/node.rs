@@ -3,6 +3,7 @@
 use std::{fmt, num::Wrapping, ops, sync::Arc};
 
 use super::text_properties::*;
+use crate::{RichSpan, RichTxt};
 use atomic::{Atomic, Ordering};
 use parking_lot::RwLock;
 use zng_app::render::FontSynthesis;
@@ -54,6 +55,17 @@ pub struct CaretInfo {
     /// Second index that defines the start or end of a selection range.
     pub selection_index: Option<CaretIndex>,
 
+    /// Other selection ranges, in addition to the primary selection defined by [`index`] and [`selection_index`].
+    ///
+    /// Rectangular selection sets one range for each line, the ranges can be empty. The extra selections are
+    /// cleared by [`clear_selection`], most edit and caret move operations only apply to the primary selection and
+    /// clear the extra selections.
+    ///
+    /// [`index`]: Self::index
+    /// [`selection_index`]: Self::selection_index
+    /// [`clear_selection`]: Self::clear_selection
+    pub extra_selections: Vec<ops::Range<CaretIndex>>,
+
     /// Selection by word or line sets this value, selection extend by word or line
     /// grows from this central selection. The value is `(selection, is_word)`.
     pub initial_selection: Option<(ops::Range<CaretIndex>, bool)>,
@@ -81,6 +93,8 @@ impl fmt::Debug for CaretInfo {
         f.debug_struct("CaretInfo")
             .field("opacity", &self.opacity.debug())
             .field("index", &self.index)
+            .field("selection_index", &self.selection_index)
+            .field("extra_selections", &self.extra_selections)
             .field("index_version", &self.index_version)
             .field("used_retained_x", &self.used_retained_x)
             .finish()
@@ -101,10 +115,11 @@ impl CaretInfo {
         self.set_index(end);
     }
 
-    /// Clears selection.
+    /// Clears selection, including the extra selections.
     pub fn clear_selection(&mut self) {
         self.selection_index = None;
         self.initial_selection = None;
+        self.extra_selections.clear();
         self.index_version += 1;
     }
 
@@ -157,6 +172,58 @@ impl CaretInfo {
     pub fn selection_char_range(&self) -> Option<ops::Range<usize>> {
         self.selection_range().map(|r| r.start.index..r.end.index)
     }
+
+    /// Gets the primary selection range and the [`extra_selections`], sorted and merged.
+    ///
+    /// The ranges can be empty if there are extra selections, as is the case for rectangular selection over short lines.
+    ///
+    /// [`extra_selections`]: Self::extra_selections
+    pub fn selection_ranges(&self) -> Vec<ops::Range<CaretIndex>> {
+        let primary = match (self.index, self.selection_index) {
+            (Some(a), Some(b)) => Some(a.min(b)..a.max(b)),
+            _ => None,
+        };
+        if self.extra_selections.is_empty() {
+            return primary.filter(|r| r.start.index != r.end.index).into_iter().collect();
+        }
+
+        let mut ranges: Vec<_> = self.extra_selections.iter().cloned().chain(primary).collect();
+        ranges.sort_by_key(|r| r.start.index);
+        let mut merged: Vec<ops::Range<CaretIndex>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            if let Some(last) = merged.last_mut() {
+                if r.start.index < last.end.index {
+                    last.end = last.end.max(r.end);
+                    continue;
+                }
+            }
+            merged.push(r);
+        }
+        merged
+    }
+
+    /// If any selection range is not empty.
+    pub fn has_selection(&self) -> bool {
+        self.selection_range().is_some() || self.extra_selections.iter().any(|r| r.start.index != r.end.index)
+    }
+
+    /// Gets the selected text from the full `text`.
+    ///
+    /// If there are extra selections the text of each range is joined by a line-break, in the text order.
+    /// Returns `None` if there is no selection.
+    pub fn selection_txt(&self, text: &str) -> Option<Txt> {
+        if !self.has_selection() {
+            return None;
+        }
+        let mut r = String::new();
+        for (i, range) in self.selection_ranges().into_iter().enumerate() {
+            if i > 0 {
+                r.push('\n');
+            }
+            r.push_str(&text[range.start.index.min(text.len())..range.end.index.min(text.len())]);
+        }
+        Some(r.into())
+    }
 }
 
 /// IME text edit that is not committed yet.
@@ -171,6 +238,11 @@ pub struct ImePreview {
     ///
     /// If set defines a selection of the text variable that is replaced with the `txt`.
     pub prev_selection: Option<CaretIndex>,
+
+    /// Byte range in `txt` of the clause that is being converted by the IME.
+    ///
+    /// Is empty if the IME does not define a target clause, the caret is positioned at the end of the target.
+    pub target: ops::Range<usize>,
 }
 
 /// Text internals used by text implementer nodes and properties.
@@ -229,6 +301,36 @@ impl TEXT {
         RwLockWriteGuardOwned::map(self.resolve(), |ctx| &mut ctx.caret)
     }
 
+    /// Gets the rich text span at the window point and the span index.
+    ///
+    /// Returns `None` if the text has no [spans], the text has not rendered or there is no span at the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called inside [`layout_text`].
+    ///
+    /// [rich]: ResolvedText::spans
+    pub fn rich_span_at(&self, window_point: DipPoint) -> Option<(usize, RichSpan)> {
+        let resolved = self.resolved();
+        if resolved.spans.is_empty() {
+            return None;
+        }
+        let laidout = self.laidout();
+        let pos = laidout
+            .render_info
+            .transform
+            .inverse()
+            .and_then(|t| t.project_point(window_point.to_px(laidout.render_info.scale_factor)))?;
+
+        let line = laidout.shaped_text.nearest_line(pos.y)?;
+        let seg = line.nearest_seg(pos.x)?;
+        if !seg.rect().contains(pos) {
+            return None;
+        }
+        // segmented text is split at span boundaries.
+        crate::rich::span_index_at(&resolved.spans, seg.text_start()).map(|i| (i, resolved.spans[i].clone()))
+    }
+
     pub(crate) fn resolve(&self) -> RwLockWriteGuardOwned<ResolvedText> {
         RESOLVED_TEXT.write()
     }
@@ -274,12 +376,36 @@ pub struct ResolvedText {
     /// the text variable is not updated yet and undo is not tracking these changes.
     pub ime_preview: Option<ImePreview>,
 
+    /// Rich text source variable, if the text was set by [`rich_txt`].
+    ///
+    /// The [`txt`] variable is a read-only mapping of the rich text, text transform and white space correction are not
+    /// applied in rich text, the segments are split at the span boundaries.
+    ///
+    /// [`rich_txt`]: fn@crate::rich_txt
+    /// [`txt`]: Self::txt
+    pub rich_txt: Option<BoxedVar<RichTxt>>,
+    /// Style spans of the [`segmented_text`], from the [`rich_txt`] or the [`syntax_highlight`] function.
+    ///
+    /// The spans are sorted and not overlapping, the segments are split at the span boundaries.
+    ///
+    /// [`segmented_text`]: Self::segmented_text
+    /// [`rich_txt`]: Self::rich_txt
+    /// [`syntax_highlight`]: fn@crate::syntax_highlight
+    pub spans: Vec<RichSpan>,
+
     /// Text transformed, white space corrected and segmented.
     pub segmented_text: SegmentedText,
     /// Queried font faces.
     pub faces: FontFaceList,
     /// Font synthesis allowed by the text context and required to render the best font match.
     pub synthesis: FontSynthesis,
+    /// Queried font faces and synthesis for each of the [`spans`], `None` for spans that use the [`faces`].
+    ///
+    /// Is empty while the span faces are loading.
+    ///
+    /// [`spans`]: Self::spans
+    /// [`faces`]: Self::faces
+    pub rich_faces: Vec<Option<(FontFaceList, FontSynthesis)>>,
 
     /// Layout that needs to be recomputed as identified by the text resolver node.
     ///
@@ -305,6 +431,8 @@ impl fmt::Debug for ResolvedText {
             .field("segmented_text", &self.segmented_text)
             .field("faces", &self.faces)
             .field("synthesis", &self.synthesis)
+            .field("spans", &self.spans)
+            .field("rich_faces", &self.rich_faces)
             .field("pending_layout", &self.pending_layout)
             .field("pending_edit", &self.pending_edit)
             .field("caret", &self.caret)
@@ -388,15 +516,42 @@ pub struct LaidoutText {
     /// Computed [`UNDERLINE_THICKNESS_VAR`].
     pub underline_thickness: Px,
 
+    /// List of underline segments of rich text spans with [`underline`], defining origin and width of each line.
+    ///
+    /// Default underlines are rendered by [`render_underlines`] using the [`rich_underline_thickness`].
+    ///
+    /// [`underline`]: crate::SpanStyle::underline
+    /// [`rich_underline_thickness`]: Self::rich_underline_thickness
+    pub rich_underlines: Vec<(PxPoint, Px)>,
+    /// Computed [`UNDERLINE_THICKNESS_VAR`] or the font underline thickness if it is `0`.
+    pub rich_underline_thickness: Px,
+
+    /// Glyph ranges of rich text spans that override the text color or font synthesis.
+    ///
+    /// The ranges are sorted and are glyph indexes in the `shaped_text`, see [`ShapedText::glyphs_slice`].
+    pub rich_glyphs: Vec<(ops::Range<usize>, Option<Rgba>, FontSynthesis)>,
+
     /// List of underline segments for IME preview text, defining origin and width of each line.
     ///
     /// Note that underlines are only computed if the `ime_underline_thickness` is more than `0`.
     ///
-    /// Default underlines are rendered by [`render_underlines`].
+    /// Default underlines are rendered by [`render_ime_preview_underlines`].
     pub ime_underlines: Vec<(PxPoint, Px)>,
     /// Computed [`IME_UNDERLINE_THICKNESS_VAR`].
     pub ime_underline_thickness: Px,
 
+    /// List of underline segments for the IME preview target clause, defining origin and width of each line.
+    ///
+    /// Note that underlines are only computed if the `ime_target_underline_thickness` is more than `0`, the
+    /// [`ime_underlines`] are clipped out of the target.
+    ///
+    /// Default underlines are rendered by [`render_ime_preview_underlines`].
+    ///
+    /// [`ime_underlines`]: Self::ime_underlines
+    pub ime_target_underlines: Vec<(PxPoint, Px)>,
+    /// Computed [`IME_TARGET_UNDERLINE_THICKNESS_VAR`].
+    pub ime_target_underline_thickness: Px,
+
     /// Top-middle offset of the caret index in the shaped text.
     pub caret_origin: Option<PxPoint>,
 
@@ -788,6 +943,203 @@ where
     })
 }
 
+/// Implements the [`txt_validate`] property.
+///
+/// [`txt_validate`]: fn@super::txt_validate
+pub(super) fn validate_text(child: impl UiNode, validate: impl IntoVar<crate::TxtValidateFn>) -> impl UiNode {
+    let validate = validate.into_var();
+    let mut error = None::<Txt>;
+    let mut _error_note = DataNoteHandle::dummy();
+
+    match_node(child, move |_, op| {
+        let run = match op {
+            UiNodeOp::Init => {
+                WIDGET.sub_var(&validate).sub_var(&TEXT.resolved().txt);
+                true
+            }
+            UiNodeOp::Deinit => {
+                error = None;
+                _error_note = DataNoteHandle::dummy();
+                let _ = TXT_INVALID_VAR.set(false);
+                false
+            }
+            UiNodeOp::Update { .. } => validate.is_new() || TEXT.resolved().txt.is_new(),
+            _ => false,
+        };
+        if run {
+            let new_error = TEXT.resolved().txt.with(|t| validate.with(|v| v.call(t))).err();
+            if new_error != error {
+                // remove or replace the error
+
+                _error_note = match &new_error {
+                    Some(e) => DATA.invalidate(e.clone()),
+                    None => DataNoteHandle::dummy(),
+                };
+                let _ = TXT_INVALID_VAR.set(new_error.is_some());
+                error = new_error;
+            }
+        }
+    })
+}
+
+pub(super) fn search_text(child: impl UiNode, query: impl IntoVar<Txt>) -> impl UiNode {
+    let query = query.into_var();
+    let mut matches = Vec::<ops::Range<usize>>::new();
+    let mut current = None::<usize>;
+    let mut _cmd_handle = CommandHandle::dummy();
+
+    match_node(child, move |_, op| {
+        let mut search = false;
+        match op {
+            UiNodeOp::Init => {
+                WIDGET
+                    .sub_var(&query)
+                    .sub_var(&TEXT.resolved().txt)
+                    .sub_var(&TXT_SEARCH_MATCH_CASE_VAR)
+                    .sub_var_render(&TXT_SEARCH_HIGHLIGHT_VAR)
+                    .sub_var_render(&TXT_SEARCH_CURRENT_HIGHLIGHT_VAR);
+                search = true;
+            }
+            UiNodeOp::Deinit => {
+                matches = vec![];
+                current = None;
+                _cmd_handle = CommandHandle::dummy();
+                let _ = TXT_SEARCH_COUNT_VAR.set(0usize);
+                let _ = TXT_SEARCH_CURRENT_VAR.set(None);
+            }
+            UiNodeOp::Event { update } => {
+                if let Some(args) = super::cmd::TEXT_SEARCH_CMD.scoped(WIDGET.id()).on_unhandled(update) {
+                    if matches.is_empty() {
+                        return;
+                    }
+                    args.propagation().stop();
+
+                    let search_op = args.param::<super::cmd::TextSearchOp>().copied().unwrap_or_default();
+                    let caret = TEXT.resolved().caret.index.map(|c| c.index).unwrap_or(0);
+                    let last = matches.len() - 1;
+                    let next = match search_op {
+                        super::cmd::TextSearchOp::Next => match current {
+                            Some(i) => {
+                                if i == last {
+                                    0
+                                } else {
+                                    i + 1
+                                }
+                            }
+                            None => matches.iter().position(|m| m.start >= caret).unwrap_or(0),
+                        },
+                        super::cmd::TextSearchOp::Prev => match current {
+                            Some(i) => i.checked_sub(1).unwrap_or(last),
+                            None => matches.iter().rposition(|m| m.end <= caret).unwrap_or(last),
+                        },
+                        super::cmd::TextSearchOp::First => 0,
+                        super::cmd::TextSearchOp::Last => last,
+                    };
+                    current = Some(next);
+                    let _ = TXT_SEARCH_CURRENT_VAR.set(current);
+                    WIDGET.render();
+
+                    if TEXT_SELECTABLE_VAR.get() || TEXT_EDITABLE_VAR.get() {
+                        let m = matches[next].clone();
+                        super::cmd::SELECT_CMD
+                            .scoped(WIDGET.id())
+                            .notify_param(super::cmd::TextSelectOp::new(move || {
+                                TEXT.resolve_caret().set_char_selection(m.start, m.end);
+                            }));
+                    }
+                }
+            }
+            UiNodeOp::Update { .. } => {
+                search = query.is_new() || TEXT.resolved().txt.is_new() || TXT_SEARCH_MATCH_CASE_VAR.is_new();
+            }
+            UiNodeOp::Render { frame } => {
+                if matches.is_empty() {
+                    return;
+                }
+                let l_txt = TEXT.laidout();
+                let r_txt = TEXT.resolved();
+                let txt = r_txt.segmented_text.text();
+                if txt.len() < matches.last().unwrap().end {
+                    // text changed, matches update on the next update.
+                    return;
+                }
+
+                let color = TXT_SEARCH_HIGHLIGHT_VAR.get();
+                let current_color = TXT_SEARCH_CURRENT_HIGHLIGHT_VAR.get();
+                for (i, m) in matches.iter().enumerate() {
+                    let range = l_txt.shaped_text.snap_caret_line(CaretIndex { index: m.start, line: 0 })
+                        ..l_txt.shaped_text.snap_caret_line(CaretIndex { index: m.end, line: 0 });
+                    let color = if current == Some(i) { current_color } else { color };
+                    for line_rect in l_txt.shaped_text.highlight_rects(range, txt) {
+                        if !line_rect.size.is_empty() {
+                            frame.push_color(line_rect, FrameValue::Value(color));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if search {
+            let new_matches = query.with(|q| TEXT.resolved().txt.with(|t| search_matches(t, q, TXT_SEARCH_MATCH_CASE_VAR.get())));
+            if new_matches != matches {
+                matches = new_matches;
+                current = None;
+                let _ = TXT_SEARCH_COUNT_VAR.set(matches.len());
+                let _ = TXT_SEARCH_CURRENT_VAR.set(None);
+                WIDGET.render();
+            }
+            if matches.is_empty() {
+                _cmd_handle = CommandHandle::dummy();
+            } else if _cmd_handle.is_dummy() {
+                _cmd_handle = super::cmd::TEXT_SEARCH_CMD.scoped(WIDGET.id()).subscribe(true);
+            }
+        }
+    })
+}
+
+/// Non-overlapping matches of `query` in `txt`, matches only start and end at grapheme boundaries.
+fn search_matches(txt: &str, query: &str, match_case: bool) -> Vec<ops::Range<usize>> {
+    use unicode_segmentation::UnicodeSegmentation as _;
+
+    let mut r = vec![];
+    if query.is_empty() {
+        return r;
+    }
+
+    let bounds: Vec<_> = txt.grapheme_indices(true).map(|(i, _)| i).chain([txt.len()]).collect();
+    let mut b = 0;
+    while b < bounds.len() - 1 {
+        let start = bounds[b];
+        let t = &txt[start..];
+        let len = if match_case {
+            t.starts_with(query).then_some(query.len())
+        } else {
+            search_prefix_ignore_case(t, query)
+        };
+        // "e" does not match the start of "é" written as "e\u{301}".
+        match len.and_then(|l| bounds[b + 1..].binary_search(&(start + l)).ok()) {
+            Some(i) => {
+                b += 1 + i;
+                r.push(start..bounds[b]);
+            }
+            None => b += 1,
+        }
+    }
+    r
+}
+/// Byte length of the prefix of `txt` that matches `query` ignoring case.
+fn search_prefix_ignore_case(txt: &str, query: &str) -> Option<usize> {
+    let mut t = txt.char_indices();
+    for qc in query.chars() {
+        match t.next() {
+            Some((_, tc)) if tc == qc || tc.to_lowercase().eq(qc.to_lowercase()) => {}
+            _ => return None,
+        }
+    }
+    Some(t.next().map(|(i, _)| i).unwrap_or(txt.len()))
+}
+
 pub(super) fn on_change_stop(child: impl UiNode, mut handler: impl WidgetHandler<ChangeStopArgs>) -> impl UiNode {
     let mut pending = None;
     match_node(child, move |c, op| match op {
@@ -1061,3 +1413,41 @@ fn adjust_viewport_bound(transform: PxTransform, widget: &mut impl UiNode) -> Px
 
     transform.then_translate(correction.cast())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_multi_byte() {
+        assert_eq!(search_matches("aé aÉ", "é", false), vec![1..3, 5..7]);
+        assert_eq!(search_matches("aé aÉ", "é", true), vec![1..3]);
+        assert_eq!(search_matches("日本語の日本", "日本", true), vec![0..6, 12..18]);
+        // KELVIN SIGN is 3 bytes, lowercase is 'k'.
+        assert_eq!(search_matches("\u{212A}m km", "km", false), vec![0..4, 5..7]);
+        assert_eq!(search_matches("\u{212A}m km", "km", true), vec![5..7]);
+    }
+
+    #[test]
+    fn search_graphemes() {
+        // combining acute accent.
+        assert_eq!(search_matches("e\u{301}e", "e", true), vec![3..4]);
+        assert_eq!(search_matches("e\u{301}e", "e\u{301}", true), vec![0..3]);
+        assert!(search_matches("e\u{301}\u{302}", "e\u{301}", false).is_empty());
+        // family emoji is a ZWJ sequence that starts with the man emoji.
+        assert_eq!(
+            search_matches("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} \u{1F468}", "\u{1F468}", true),
+            vec![19..23]
+        );
+        // CRLF is a single grapheme.
+        assert_eq!(search_matches("a\r\nb\r", "\r", true), vec![4..5]);
+    }
+
+    #[test]
+    fn search_non_overlapping() {
+        assert_eq!(search_matches("aaaa", "aa", true), vec![0..2, 2..4]);
+        assert_eq!(search_matches("AaA", "aa", false), vec![0..2]);
+        assert!(search_matches("abc", "", false).is_empty());
+        assert!(search_matches("", "a", false).is_empty());
+    }
+}
@@ -1,9 +1,10 @@
-use std::{mem, sync::Arc};
+use std::{mem, ops, sync::Arc};
 
 use atomic::Atomic;
 use parking_lot::RwLock;
 use zng_app::{
     event::{AnyEventArgs as _, CommandHandle, EventHandle, EventHandles},
+    render::FontSynthesis,
     widget::{
         node::{match_node, UiNode, UiNodeOp},
         WIDGET,
@@ -32,12 +33,12 @@ use zng_wgt_scroll::{cmd::ScrollToMode, SCROLL};
 use crate::{
     cmd::{TextSelectOp, SELECT_ALL_CMD, SELECT_CMD},
     node::SelectionBy,
-    AutoSelection, TextOverflow, UnderlinePosition, UnderlineSkip, ACCEPTS_ENTER_VAR, ACCEPTS_TAB_VAR, AUTO_SELECTION_VAR, FONT_FAMILY_VAR,
-    FONT_FEATURES_VAR, FONT_SIZE_VAR, FONT_STRETCH_VAR, FONT_STYLE_VAR, FONT_VARIATIONS_VAR, FONT_WEIGHT_VAR, HYPHENS_VAR, HYPHEN_CHAR_VAR,
-    IME_UNDERLINE_THICKNESS_VAR, LETTER_SPACING_VAR, LINE_BREAK_VAR, LINE_HEIGHT_VAR, LINE_SPACING_VAR, OBSCURE_TXT_VAR,
-    OBSCURING_CHAR_VAR, OVERLINE_THICKNESS_VAR, STRIKETHROUGH_THICKNESS_VAR, TAB_LENGTH_VAR, TEXT_ALIGN_VAR, TEXT_EDITABLE_VAR,
-    TEXT_OVERFLOW_ALIGN_VAR, TEXT_OVERFLOW_VAR, TEXT_SELECTABLE_VAR, TEXT_WRAP_VAR, UNDERLINE_POSITION_VAR, UNDERLINE_SKIP_VAR,
-    UNDERLINE_THICKNESS_VAR, WORD_BREAK_VAR, WORD_SPACING_VAR,
+    AutoSelection, RichSpan, TextOverflow, UnderlinePosition, UnderlineSkip, ACCEPTS_ENTER_VAR, ACCEPTS_TAB_VAR, AUTO_SELECTION_VAR,
+    FONT_FAMILY_VAR, FONT_FEATURES_VAR, FONT_SIZE_VAR, FONT_STRETCH_VAR, FONT_STYLE_VAR, FONT_VARIATIONS_VAR, FONT_WEIGHT_VAR, HYPHENS_VAR,
+    HYPHEN_CHAR_VAR, IME_TARGET_UNDERLINE_THICKNESS_VAR, IME_UNDERLINE_THICKNESS_VAR, LETTER_SPACING_VAR, LINE_BREAK_VAR, LINE_HEIGHT_VAR,
+    LINE_SPACING_VAR, OBSCURE_TXT_VAR, OBSCURING_CHAR_VAR, OVERLINE_THICKNESS_VAR, STRIKETHROUGH_THICKNESS_VAR, TAB_LENGTH_VAR,
+    TEXT_ALIGN_VAR, TEXT_EDITABLE_VAR, TEXT_OVERFLOW_ALIGN_VAR, TEXT_OVERFLOW_VAR, TEXT_SELECTABLE_VAR, TEXT_WRAP_VAR,
+    UNDERLINE_POSITION_VAR, UNDERLINE_SKIP_VAR, UNDERLINE_THICKNESS_VAR, WORD_BREAK_VAR, WORD_SPACING_VAR,
 };
 
 use super::{LaidoutText, PendingLayout, RenderInfo, LAIDOUT_TEXT, TEXT};
@@ -74,8 +75,13 @@ fn layout_text_context(child: impl UiNode) -> impl UiNode {
                 strikethrough_thickness: Px(0),
                 underlines: vec![],
                 underline_thickness: Px(0),
+                rich_underlines: vec![],
+                rich_underline_thickness: Px(0),
+                rich_glyphs: vec![],
                 ime_underlines: vec![],
                 ime_underline_thickness: Px(0),
+                ime_target_underlines: vec![],
+                ime_target_underline_thickness: Px(0),
                 caret_origin: None,
                 caret_selection_origin: None,
                 caret_retained_x: Px(0),
@@ -368,6 +374,42 @@ fn layout_text_layout(child: impl UiNode) -> impl UiNode {
         _ => {}
     })
 }
+/// Glyph ranges of spans that override the color or synthesis, merged.
+fn rich_glyphs(
+    shaped_text: &ShapedText,
+    spans: &[RichSpan],
+    faces: &[Option<(FontFaceList, FontSynthesis)>],
+    synthesis: FontSynthesis,
+) -> Vec<(ops::Range<usize>, Option<Rgba>, FontSynthesis)> {
+    let mut r: Vec<(ops::Range<usize>, Option<Rgba>, FontSynthesis)> = vec![];
+    for line in shaped_text.lines() {
+        for seg in line.segs() {
+            let i = match crate::rich::span_index_at(spans, seg.text_start()) {
+                Some(i) => i,
+                None => continue,
+            };
+            let color = spans[i].style.color;
+            let synthesis = faces.get(i).and_then(|f| f.as_ref().map(|(_, s)| *s)).unwrap_or(synthesis);
+            if color.is_none() && faces.get(i).map(|f| f.is_none()).unwrap_or(true) {
+                continue;
+            }
+            let glyphs = seg.glyphs_index_range();
+            if glyphs.is_empty() {
+                continue;
+            }
+            if let Some(last) = r.last_mut() {
+                if last.0.end == glyphs.start && last.1 == color && last.2 == synthesis {
+                    last.0.end = glyphs.end;
+                    continue;
+                }
+            }
+            r.push((glyphs, color, synthesis));
+        }
+    }
+    r.sort_by_key(|(g, _, _)| g.start);
+    r
+}
+
 struct LayoutTextFinal {
     shaping_args: TextShapingArgs,
     pending: PendingLayout,
@@ -508,13 +550,14 @@ impl LayoutTextFinal {
         self.shaping_args.line_spacing = line_spacing;
 
         let dft_thickness = font.metrics().underline_thickness;
-        let (overline, strikethrough, underline, ime_underline) = {
+        let (overline, strikethrough, underline, ime_underline, ime_target_underline) = {
             LAYOUT.with_constraints(PxConstraints2d::new_exact(line_height, line_height), || {
                 (
                     OVERLINE_THICKNESS_VAR.layout_dft_y(dft_thickness),
                     STRIKETHROUGH_THICKNESS_VAR.layout_dft_y(dft_thickness),
                     UNDERLINE_THICKNESS_VAR.layout_dft_y(dft_thickness),
                     IME_UNDERLINE_THICKNESS_VAR.layout_dft_y(dft_thickness),
+                    IME_TARGET_UNDERLINE_THICKNESS_VAR.layout_dft_y(dft_thickness),
                 )
             })
         };
@@ -527,7 +570,8 @@ impl LayoutTextFinal {
         }
         if !self.pending.contains(PendingLayout::UNDERLINE)
             && ((ctx.underline_thickness == Px(0)) != (underline == Px(0))
-                || (ctx.ime_underline_thickness != Px(0)) != (ime_underline != Px(0)))
+                || (ctx.ime_underline_thickness != Px(0)) != (ime_underline != Px(0))
+                || (ctx.ime_target_underline_thickness != Px(0)) != (ime_target_underline != Px(0)))
         {
             self.pending.insert(PendingLayout::UNDERLINE);
         }
@@ -535,6 +579,7 @@ impl LayoutTextFinal {
         ctx.strikethrough_thickness = strikethrough;
         ctx.underline_thickness = underline;
         ctx.ime_underline_thickness = ime_underline;
+        ctx.ime_target_underline_thickness = ime_target_underline;
 
         let align = TEXT_ALIGN_VAR.get();
         let overflow_align = TEXT_OVERFLOW_ALIGN_VAR.get();
@@ -549,7 +594,20 @@ impl LayoutTextFinal {
         */
 
         if self.pending.contains(PendingLayout::RESHAPE) {
-            ctx.shaped_text = ctx.fonts.shape_text(&resolved.segmented_text, &self.shaping_args);
+            if !resolved.spans.is_empty() {
+                let variations = FONT_VARIATIONS_VAR.with(FontVariations::finalize);
+                let runs: Vec<_> = resolved
+                    .spans
+                    .iter()
+                    .zip(&resolved.rich_faces)
+                    .filter_map(|(s, f)| f.as_ref().map(|(f, _)| (s.range.clone(), f.sized(font_size, variations.clone()))))
+                    .collect();
+                ctx.shaped_text = ctx.fonts.shape_text_runs(&resolved.segmented_text, &runs, &self.shaping_args);
+                ctx.rich_glyphs = rich_glyphs(&ctx.shaped_text, &resolved.spans, &resolved.rich_faces, resolved.synthesis);
+            } else {
+                ctx.shaped_text = ctx.fonts.shape_text(&resolved.segmented_text, &self.shaping_args);
+                ctx.rich_glyphs = vec![];
+            }
             self.pending = self.pending.intersection(PendingLayout::RESHAPE_LINES);
         }
 
@@ -618,23 +676,27 @@ impl LayoutTextFinal {
             }
 
             if self.pending.contains(PendingLayout::UNDERLINE) {
-                let ime_range = if let Some(ime) = &resolved.ime_preview {
+                let (ime_range, ime_target_range) = if let Some(ime) = &resolved.ime_preview {
                     let start = ime.prev_selection.unwrap_or(ime.prev_caret).index.min(ime.prev_caret.index);
-                    start..start + ime.txt.len()
+                    (start..start + ime.txt.len(), start + ime.target.start..start + ime.target.end)
                 } else {
-                    0..0
+                    (0..0, 0..0)
                 };
-                let caret_ime_range = if !ime_range.is_empty() && (ctx.underline_thickness > Px(0) || ctx.ime_underline_thickness > Px(0)) {
+                let caret_range = |range: &ops::Range<usize>| {
                     let start = ctx.shaped_text.snap_caret_line(CaretIndex {
-                        index: ime_range.start,
-                        line: 0,
-                    });
-                    let end = ctx.shaped_text.snap_caret_line(CaretIndex {
-                        index: ime_range.end,
+                        index: range.start,
                         line: 0,
                     });
-
+                    let end = ctx.shaped_text.snap_caret_line(CaretIndex { index: range.end, line: 0 });
                     start..end
+                };
+                let caret_ime_range = if !ime_range.is_empty() && (ctx.underline_thickness > Px(0) || ctx.ime_underline_thickness > Px(0)) {
+                    caret_range(&ime_range)
+                } else {
+                    CaretIndex::ZERO..CaretIndex::ZERO
+                };
+                let caret_ime_target_range = if !ime_target_range.is_empty() {
+                    caret_range(&ime_target_range)
                 } else {
                     CaretIndex::ZERO..CaretIndex::ZERO
                 };
@@ -701,30 +763,82 @@ impl LayoutTextFinal {
                     ctx.underlines = vec![];
                 }
 
-                if ctx.ime_underline_thickness > Px(0) && !ime_range.is_empty() {
-                    let mut ime_underlines = vec![];
+                ctx.rich_underlines = vec![];
+                if !resolved.spans.is_empty() {
+                    ctx.rich_underline_thickness = if ctx.underline_thickness > Px(0) {
+                        ctx.underline_thickness
+                    } else {
+                        dft_thickness.max(Px(1))
+                    };
+                    let mut underlines = vec![];
+                    for line in ctx.shaped_text.lines() {
+                        for seg in line.segs() {
+                            if crate::rich::span_index_at(&resolved.spans, seg.text_start())
+                                .map(|i| resolved.spans[i].style.underline)
+                                .unwrap_or(false)
+                            {
+                                underlines.push(seg.underline());
+                            }
+                        }
+                    }
+                    ctx.rich_underlines = underlines;
+                }
 
-                    // collects underlines for all segments that intersect with the IME text.
+                // collects underlines for all segments that intersect with the IME text range.
+                let ime_segs_underlines = |range: &ops::Range<usize>, thickness: Px| {
+                    let mut underlines = vec![];
                     for line in ctx.shaped_text.lines() {
                         let line_range = line.text_range();
-                        if line_range.start < ime_range.end && line_range.end > ime_range.start {
+                        if line_range.start < range.end && line_range.end > range.start {
                             for seg in line.segs() {
                                 let seg_range = seg.text_range();
-                                if seg_range.start < ime_range.end && seg_range.end > ime_range.start {
-                                    for und in seg.underline_skip_glyphs(ctx.ime_underline_thickness) {
-                                        ime_underlines.push(und);
+                                if seg_range.start < range.end && seg_range.end > range.start {
+                                    for und in seg.underline_skip_glyphs(thickness) {
+                                        underlines.push(und);
                                     }
                                 }
                             }
                         }
                     }
+                    underlines
+                };
+
+                let has_target = ctx.ime_target_underline_thickness > Px(0) && !ime_target_range.is_empty();
+                let target_underlines = if has_target {
+                    ime_segs_underlines(&ime_target_range, ctx.ime_target_underline_thickness)
+                } else {
+                    vec![]
+                };
+
+                if ctx.ime_underline_thickness > Px(0) && !ime_range.is_empty() {
+                    let ime_underlines = ime_segs_underlines(&ime_range, ctx.ime_underline_thickness);
 
-                    ctx.ime_underlines =
+                    let mut ime_underlines =
                         ctx.shaped_text
                             .clip_lines(caret_ime_range, false, resolved.segmented_text.text(), ime_underlines.into_iter());
+                    if has_target {
+                        ime_underlines = ctx.shaped_text.clip_lines(
+                            caret_ime_target_range.clone(),
+                            true,
+                            resolved.segmented_text.text(),
+                            ime_underlines.into_iter(),
+                        );
+                    }
+                    ctx.ime_underlines = ime_underlines;
                 } else {
                     ctx.ime_underlines = vec![];
                 }
+
+                if has_target {
+                    ctx.ime_target_underlines = ctx.shaped_text.clip_lines(
+                        caret_ime_target_range,
+                        false,
+                        resolved.segmented_text.text(),
+                        target_underlines.into_iter(),
+                    );
+                } else {
+                    ctx.ime_target_underlines = vec![];
+                }
             }
 
             if self.pending.contains(PendingLayout::CARET) {
@@ -745,6 +859,10 @@ impl LayoutTextFinal {
                         *sel = ctx.shaped_text.snap_caret_line(*sel);
                         ctx.caret_selection_origin = Some(ctx.shaped_text.caret_origin(*sel, resolved_mut.segmented_text.text()));
                     }
+                    for r in &mut caret.extra_selections {
+                        r.start = ctx.shaped_text.snap_caret_line(r.start);
+                        r.end = ctx.shaped_text.snap_caret_line(r.end);
+                    }
 
                     if !mem::take(&mut caret.skip_next_scroll) && SCROLL.try_id().is_some() {
                         let line_height = ctx
@@ -882,6 +1000,7 @@ struct LayoutTextEdit {
     ime_area: Arc<Atomic<PxRect>>,
     click_count: u8,
     selection_mouse_down: Option<SelectionMouseDown>,
+    rectangle_selection_start: Option<DipPoint>,
     auto_select: bool,
     selection_move_handles: EventHandles,
 }
@@ -913,7 +1032,7 @@ impl LayoutTextEdit {
                 let b_line = PxRect::new(b, PxSize::new(Px(1), txt.shaped_text.line(bc.line).unwrap().height())).to_box2d();
 
                 a_line.min = a_line.min.min(b_line.min);
-                a_line.max = a_line.max.min(b_line.max);
+                a_line.max = a_line.max.max(b_line.max);
             }
             area = a_line;
         } else {
@@ -1130,12 +1249,28 @@ fn layout_text_edit_events(update: &EventUpdate, edit: &mut LayoutTextEdit) {
         if args.is_primary() && args.is_mouse_down() && args.target.widget_id() == widget.id() {
             let mut modifiers = args.modifiers;
             let select = selectable && modifiers.take_shift();
+            let rectangle = selectable && !select && modifiers.take_alt();
 
-            if modifiers.is_empty() {
+            if rectangle && modifiers.is_empty() {
                 args.propagation().stop();
 
                 TEXT.resolve().selection_by = SelectionBy::Mouse;
 
+                edit.click_count = 1;
+                edit.selection_mouse_down = None;
+                edit.rectangle_selection_start = Some(args.position);
+                TextSelectOp::select_rectangle(args.position, args.position).call();
+
+                let id = widget.id();
+                edit.selection_move_handles.push(MOUSE_MOVE_EVENT.subscribe(id));
+                edit.selection_move_handles.push(POINTER_CAPTURE_EVENT.subscribe(id));
+                POINTER_CAPTURE.capture_widget(id);
+            } else if modifiers.is_empty() {
+                args.propagation().stop();
+
+                TEXT.resolve().selection_by = SelectionBy::Mouse;
+                edit.rectangle_selection_start = None;
+
                 edit.click_count = if let Some(info) = &mut edit.selection_mouse_down {
                     let cfg = MOUSE.multi_click_config().get();
 
@@ -1256,17 +1391,22 @@ fn layout_text_edit_events(update: &EventUpdate, edit: &mut LayoutTextEdit) {
         if !edit.selection_move_handles.is_dummy() && selectable && args.target.widget_id() == widget.id() {
             args.propagation().stop();
 
-            match edit.click_count {
-                1 => TextSelectOp::select_nearest_to(args.position).call(),
-                2 => TextSelectOp::select_word_nearest_to(false, args.position).call(),
-                3 => TextSelectOp::select_line_nearest_to(false, args.position).call(),
-                4 => {}
-                _ => unreachable!(),
+            if let Some(start) = edit.rectangle_selection_start {
+                TextSelectOp::select_rectangle(start, args.position).call();
+            } else {
+                match edit.click_count {
+                    1 => TextSelectOp::select_nearest_to(args.position).call(),
+                    2 => TextSelectOp::select_word_nearest_to(false, args.position).call(),
+                    3 => TextSelectOp::select_line_nearest_to(false, args.position).call(),
+                    4 => {}
+                    _ => unreachable!(),
+                }
             }
         }
     } else if let Some(args) = POINTER_CAPTURE_EVENT.on(update) {
         if args.is_lost(widget.id()) {
             edit.selection_move_handles.clear();
+            edit.rectangle_selection_start = None;
             edit.auto_select = false;
         }
     } else if selectable {
@@ -9,7 +9,7 @@ use zng_app::{
     },
 };
 use zng_color::Rgba;
-use zng_ext_font::{Font, ShapedColoredGlyphs};
+use zng_ext_font::{CaretIndex, Font, ShapedColoredGlyphs};
 use zng_ext_input::focus::FOCUS_CHANGED_EVENT;
 use zng_layout::{
     context::LAYOUT,
@@ -19,9 +19,10 @@ use zng_view_api::{config::FontAntiAliasing, display_list::FrameValue, font::Gly
 use zng_wgt::prelude::*;
 
 use crate::{
-    TextOverflow, FONT_AA_VAR, FONT_COLOR_VAR, FONT_PALETTE_COLORS_VAR, FONT_PALETTE_VAR, IME_UNDERLINE_STYLE_VAR, OVERLINE_COLOR_VAR,
-    OVERLINE_STYLE_VAR, SELECTION_COLOR_VAR, STRIKETHROUGH_COLOR_VAR, STRIKETHROUGH_STYLE_VAR, TEXT_EDITABLE_VAR, TEXT_OVERFLOW_VAR,
-    UNDERLINE_COLOR_VAR, UNDERLINE_STYLE_VAR,
+    TextOverflow, FONT_AA_VAR, FONT_COLOR_VAR, FONT_PALETTE_COLORS_VAR, FONT_PALETTE_VAR, IME_PREVIEW_BACKGROUND_VAR,
+    IME_TARGET_UNDERLINE_STYLE_VAR, IME_UNDERLINE_COLOR_VAR, IME_UNDERLINE_STYLE_VAR, OVERLINE_COLOR_VAR, OVERLINE_STYLE_VAR,
+    SELECTION_COLOR_VAR, STRIKETHROUGH_COLOR_VAR, STRIKETHROUGH_STYLE_VAR, TEXT_EDITABLE_VAR, TEXT_OVERFLOW_VAR, UNDERLINE_COLOR_VAR,
+    UNDERLINE_STYLE_VAR,
 };
 
 use super::TEXT;
@@ -55,15 +56,30 @@ pub fn render_underlines(child: impl UiNode) -> impl UiNode {
                     }
                 }
             }
+
+            if !t.rich_underlines.is_empty() {
+                let style = match UNDERLINE_STYLE_VAR.get() {
+                    LineStyle::Hidden => LineStyle::Solid,
+                    s => s,
+                };
+                let color = UNDERLINE_COLOR_VAR.get();
+                for &(origin, width) in &t.rich_underlines {
+                    frame.push_line(
+                        PxRect::new(origin, PxSize::new(width, t.rich_underline_thickness)),
+                        LineOrientation::Horizontal,
+                        color,
+                        style,
+                    );
+                }
+            }
         }
         _ => {}
     })
 }
 
-/// An Ui node that renders the default IME preview underline visual using the parent [`LaidoutText`].
+/// An Ui node that renders the default IME preview background and underline visual using the parent [`LaidoutText`].
 ///
-///
-/// The lines are rendered before `child`, under it.
+/// The background and lines are rendered before `child`, under it.
 ///
 /// The `Text!` widgets introduces this node in `new_child`, around the [`render_underlines`] node.
 ///
@@ -71,15 +87,37 @@ pub fn render_underlines(child: impl UiNode) -> impl UiNode {
 pub fn render_ime_preview_underlines(child: impl UiNode) -> impl UiNode {
     match_node(child, move |_, op| match op {
         UiNodeOp::Init => {
-            WIDGET.sub_var_render(&IME_UNDERLINE_STYLE_VAR).sub_var_render(&FONT_COLOR_VAR);
+            WIDGET
+                .sub_var_render(&IME_UNDERLINE_STYLE_VAR)
+                .sub_var_render(&IME_TARGET_UNDERLINE_STYLE_VAR)
+                .sub_var_render(&IME_UNDERLINE_COLOR_VAR)
+                .sub_var_render(&IME_PREVIEW_BACKGROUND_VAR);
         }
         UiNodeOp::Render { frame } => {
+            let r = TEXT.resolved();
             let t = TEXT.laidout();
 
+            if let Some(ime) = &r.ime_preview {
+                let background = IME_PREVIEW_BACKGROUND_VAR.get();
+                if background.alpha > 0.0 && !ime.txt.is_empty() {
+                    let start = ime.prev_selection.unwrap_or(ime.prev_caret).index.min(ime.prev_caret.index);
+                    let range = t.shaped_text.snap_caret_line(CaretIndex { index: start, line: 0 })
+                        ..t.shaped_text.snap_caret_line(CaretIndex {
+                            index: start + ime.txt.len(),
+                            line: 0,
+                        });
+                    for line_rect in t.shaped_text.highlight_rects(range, r.segmented_text.text()) {
+                        if !line_rect.size.is_empty() {
+                            frame.push_color(line_rect, FrameValue::Value(background));
+                        }
+                    }
+                }
+            }
+
+            let color = IME_UNDERLINE_COLOR_VAR.get();
             if !t.ime_underlines.is_empty() {
                 let style = IME_UNDERLINE_STYLE_VAR.get();
                 if style != LineStyle::Hidden {
-                    let color = FONT_COLOR_VAR.get();
                     for &(origin, width) in &t.ime_underlines {
                         frame.push_line(
                             PxRect::new(origin, PxSize::new(width, t.ime_underline_thickness)),
@@ -90,6 +128,19 @@ pub fn render_ime_preview_underlines(child: impl UiNode) -> impl UiNode {
                     }
                 }
             }
+            if !t.ime_target_underlines.is_empty() {
+                let style = IME_TARGET_UNDERLINE_STYLE_VAR.get();
+                if style != LineStyle::Hidden {
+                    for &(origin, width) in &t.ime_target_underlines {
+                        frame.push_line(
+                            PxRect::new(origin, PxSize::new(width, t.ime_target_underline_thickness)),
+                            LineOrientation::Horizontal,
+                            color,
+                            style,
+                        );
+                    }
+                }
+            }
         }
         _ => {}
     })
@@ -187,7 +238,8 @@ pub fn render_selection(child: impl UiNode) -> impl UiNode {
         UiNodeOp::Render { frame } => {
             let r_txt = TEXT.resolved();
 
-            if let Some(range) = r_txt.caret.selection_range() {
+            let ranges = r_txt.caret.selection_ranges();
+            if !ranges.is_empty() {
                 let l_txt = TEXT.laidout();
                 let txt = r_txt.segmented_text.text();
 
@@ -196,12 +248,14 @@ pub fn render_selection(child: impl UiNode) -> impl UiNode {
                     selection_color = selection_color.desaturate(100.pct());
                 }
 
-                for line_rect in l_txt.shaped_text.highlight_rects(range, txt) {
-                    if !line_rect.size.is_empty() {
-                        frame.push_color(line_rect, FrameValue::Value(selection_color));
+                for range in ranges {
+                    for line_rect in l_txt.shaped_text.highlight_rects(range, txt) {
+                        if !line_rect.size.is_empty() {
+                            frame.push_color(line_rect, FrameValue::Value(selection_color));
+                        }
                     }
                 }
-            };
+            }
         }
         _ => {}
     })
@@ -293,76 +347,122 @@ pub fn render_text() -> impl UiNode {
             t.render_info.scale_factor = frame.scale_factor();
 
             frame.push_reuse(&mut reuse, |frame| {
-                if t.shaped_text.has_colored_glyphs() || t.overflow_suffix.as_ref().map(|o| o.has_colored_glyphs()).unwrap_or(false) {
+                if t.shaped_text.has_colored_glyphs()
+                    || t.overflow_suffix.as_ref().map(|o| o.has_colored_glyphs()).unwrap_or(false)
+                    || !t.rich_glyphs.is_empty()
+                {
                     let palette_query = FONT_PALETTE_VAR.get();
                     FONT_PALETTE_COLORS_VAR.with(|palette_colors| {
-                        let mut push_font_glyphs = |font: &Font, glyphs, offset: Option<euclid::Vector2D<f32, Px>>| {
-                            let mut palette = None;
-
-                            match glyphs {
-                                ShapedColoredGlyphs::Normal(glyphs) => {
-                                    if let Some(offset) = offset {
-                                        let mut glyphs = glyphs.to_vec();
-                                        for g in &mut glyphs {
-                                            g.point.x += offset.x;
-                                            g.point.y += offset.y;
+                        let mut push_font_glyphs =
+                            |font: &Font,
+                             glyphs,
+                             offset: Option<euclid::Vector2D<f32, Px>>,
+                             (color, color_value, synthesis): (Rgba, FrameValue<Rgba>, FontSynthesis)| {
+                                let mut palette = None;
+
+                                match glyphs {
+                                    ShapedColoredGlyphs::Normal(glyphs) => {
+                                        if let Some(offset) = offset {
+                                            let mut glyphs = glyphs.to_vec();
+                                            for g in &mut glyphs {
+                                                g.point.x += offset.x;
+                                                g.point.y += offset.y;
+                                            }
+                                            frame.push_text(clip, &glyphs, font, color_value, synthesis, aa);
+                                        } else {
+                                            frame.push_text(clip, glyphs, font, color_value, synthesis, aa);
                                         }
-                                        frame.push_text(clip, &glyphs, font, color_value, r.synthesis, aa);
-                                    } else {
-                                        frame.push_text(clip, glyphs, font, color_value, r.synthesis, aa);
                                     }
-                                }
-                                ShapedColoredGlyphs::Colored { point, glyphs, .. } => {
-                                    for (index, color_i) in glyphs.iter() {
-                                        let color = if let Some(color_i) = color_i {
-                                            if let Some(i) = palette_colors.iter().position(|(ci, _)| *ci == color_i as u16) {
-                                                palette_colors[i].1
+                                    ShapedColoredGlyphs::Colored { point, glyphs, .. } => {
+                                        for (index, color_i) in glyphs.iter() {
+                                            let color = if let Some(color_i) = color_i {
+                                                if let Some(i) = palette_colors.iter().position(|(ci, _)| *ci == color_i as u16) {
+                                                    palette_colors[i].1
+                                                } else {
+                                                    // FontFace only parses colored glyphs if the font has at least one
+                                                    // palette, so it is safe to unwrap here
+                                                    let palette = palette.get_or_insert_with(|| {
+                                                        font.face().color_palettes().palette(palette_query).unwrap()
+                                                    });
+
+                                                    // the font could have a bug and return an invalid palette index
+                                                    palette.colors.get(color_i).copied().unwrap_or(color)
+                                                }
                                             } else {
-                                                // FontFace only parses colored glyphs if the font has at least one
-                                                // palette, so it is safe to unwrap here
-                                                let palette = palette
-                                                    .get_or_insert_with(|| font.face().color_palettes().palette(palette_query).unwrap());
-
-                                                // the font could have a bug and return an invalid palette index
-                                                palette.colors.get(color_i).copied().unwrap_or(color)
+                                                // color_i is None, meaning the base color.
+                                                color
+                                            };
+
+                                            let mut g = GlyphInstance { point, index };
+                                            if let Some(offset) = offset {
+                                                g.point.x += offset.x;
+                                                g.point.y += offset.y;
                                             }
-                                        } else {
-                                            // color_i is None, meaning the base color.
-                                            color
-                                        };
-
-                                        let mut g = GlyphInstance { point, index };
-                                        if let Some(offset) = offset {
-                                            g.point.x += offset.x;
-                                            g.point.y += offset.y;
+                                            frame.push_text(clip, &[g], font, FrameValue::Value(color), synthesis, aa);
                                         }
-                                        frame.push_text(clip, &[g], font, FrameValue::Value(color), r.synthesis, aa);
                                     }
                                 }
-                            }
+                            };
+
+                        let base = (color, color_value, r.synthesis);
+                        let truncated = match (&t.overflow, TEXT_OVERFLOW_VAR.get(), TEXT_EDITABLE_VAR.get()) {
+                            (Some(o), TextOverflow::Truncate(_), false) => Some(o),
+                            _ => None,
                         };
 
-                        match (&t.overflow, TEXT_OVERFLOW_VAR.get(), TEXT_EDITABLE_VAR.get()) {
-                            (Some(o), TextOverflow::Truncate(_), false) => {
-                                for glyphs in &o.included_glyphs {
-                                    for (font, glyphs) in t.shaped_text.colored_glyphs_slice(glyphs.clone()) {
-                                        push_font_glyphs(font, glyphs, None)
+                        if !t.rich_glyphs.is_empty() {
+                            let all = 0..t.shaped_text.glyphs().map(|(_, g)| g.len()).sum();
+                            let included = match truncated {
+                                Some(o) => &o.included_glyphs[..],
+                                None => std::slice::from_ref(&all),
+                            };
+                            for range in included.iter().cloned() {
+                                // split the range in the span runs
+                                let mut start = range.start;
+                                for (run, run_color, run_synthesis) in &t.rich_glyphs {
+                                    if run.end <= start || run.start >= range.end {
+                                        continue;
+                                    }
+                                    if run.start > start {
+                                        for (font, glyphs) in t.shaped_text.colored_glyphs_slice(start..run.start) {
+                                            push_font_glyphs(font, glyphs, None, base)
+                                        }
+                                        start = run.start;
                                     }
+                                    let end = run.end.min(range.end);
+                                    let style = match run_color {
+                                        Some(c) => (*c, FrameValue::Value(*c), *run_synthesis),
+                                        None => (color, color_value, *run_synthesis),
+                                    };
+                                    for (font, glyphs) in t.shaped_text.colored_glyphs_slice(start..end) {
+                                        push_font_glyphs(font, glyphs, None, style)
+                                    }
+                                    start = end;
                                 }
-
-                                if let Some(suf) = &t.overflow_suffix {
-                                    let suf_offset = o.suffix_origin.to_vector().cast_unit();
-                                    for (font, glyphs) in suf.colored_glyphs() {
-                                        push_font_glyphs(font, glyphs, Some(suf_offset))
+                                if start < range.end {
+                                    for (font, glyphs) in t.shaped_text.colored_glyphs_slice(start..range.end) {
+                                        push_font_glyphs(font, glyphs, None, base)
                                     }
                                 }
                             }
-                            _ => {
-                                // no overflow truncating
-                                for (font, glyphs) in t.shaped_text.colored_glyphs() {
-                                    push_font_glyphs(font, glyphs, None)
+                        } else if let Some(o) = truncated {
+                            for glyphs in &o.included_glyphs {
+                                for (font, glyphs) in t.shaped_text.colored_glyphs_slice(glyphs.clone()) {
+                                    push_font_glyphs(font, glyphs, None, base)
                                 }
                             }
+                        } else {
+                            // no overflow truncating
+                            for (font, glyphs) in t.shaped_text.colored_glyphs() {
+                                push_font_glyphs(font, glyphs, None, base)
+                            }
+                        }
+
+                        if let (Some(o), Some(suf)) = (truncated, &t.overflow_suffix) {
+                            let suf_offset = o.suffix_origin.to_vector().cast_unit();
+                            for (font, glyphs) in suf.colored_glyphs() {
+                                push_font_glyphs(font, glyphs, Some(suf_offset), base)
+                            }
                         }
                     });
                 } else {
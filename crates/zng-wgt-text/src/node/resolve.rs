@@ -1,6 +1,7 @@
-use std::{borrow::Cow, num::Wrapping, sync::Arc};
+use std::{borrow::Cow, num::Wrapping, ops, sync::Arc};
 
 use parking_lot::RwLock;
+use unicode_segmentation::UnicodeSegmentation as _;
 use zng_app::{
     access::{ACCESS_SELECTION_EVENT, ACCESS_TEXT_EVENT},
     event::{CommandHandle, EventHandle},
@@ -13,8 +14,8 @@ use zng_app::{
     },
     window::WINDOW,
 };
-use zng_ext_clipboard::{CLIPBOARD, COPY_CMD, CUT_CMD, PASTE_CMD};
-use zng_ext_font::{CaretIndex, FontFaceList, SegmentedText, FONTS, FONT_CHANGED_EVENT};
+use zng_ext_clipboard::{ClipboardEntry, CLIPBOARD, COPY_CMD, CUT_CMD, PASTE_CMD, PASTE_FROM_HISTORY_CMD};
+use zng_ext_font::{CaretIndex, FontFaceList, FontNames, FontStyle, FontWeight, SegmentedText, FONTS, FONT_CHANGED_EVENT};
 use zng_ext_input::{
     focus::{FocusInfoBuilder, WidgetInfoFocusExt as _, FOCUS, FOCUS_CHANGED_EVENT},
     keyboard::{KEYBOARD, KEY_INPUT_EVENT},
@@ -29,10 +30,12 @@ use zng_wgt::prelude::*;
 use crate::{
     cmd::{TextEditOp, TextSelectOp, UndoTextEditOp, EDIT_CMD, SELECT_ALL_CMD, SELECT_CMD},
     AutoSelection, ACCEPTS_ENTER_VAR, ACCEPTS_TAB_VAR, AUTO_SELECTION_VAR, FONT_FAMILY_VAR, FONT_STRETCH_VAR, FONT_STYLE_VAR,
-    FONT_SYNTHESIS_VAR, FONT_WEIGHT_VAR, MAX_CHARS_COUNT_VAR, OBSCURE_TXT_VAR, TEXT_EDITABLE_VAR, TEXT_SELECTABLE_VAR, TEXT_TRANSFORM_VAR,
-    WHITE_SPACE_VAR,
+    FONT_SYNTHESIS_VAR, FONT_WEIGHT_VAR, MAX_CHARS_COUNT_VAR, MAX_GRAPHEMES_COUNT_VAR, OBSCURE_TXT_VAR, TEXT_EDITABLE_VAR,
+    TEXT_SELECTABLE_VAR, TEXT_TRANSFORM_VAR, WHITE_SPACE_VAR,
 };
 
+use crate::{RichSpan, RichTxt, SpanStyle, SyntaxHighlightFn, SYNTAX_HIGHLIGHT_VAR};
+
 use super::{CaretInfo, ImePreview, PendingLayout, ResolvedText, SelectionBy, RESOLVED_TEXT, TEXT};
 
 /// An UI node that resolves the text context vars, applies the text transform and white space correction and segments the `text`.
@@ -45,21 +48,41 @@ use super::{CaretInfo, ImePreview, PendingLayout, ResolvedText, SelectionBy, RES
 /// [`NestGroup::EVENT`]: zng_wgt::prelude::NestGroup::EVENT
 /// [`NestGroup::CONTEXT`]: zng_wgt::prelude::NestGroup::CONTEXT
 pub fn resolve_text(child: impl UiNode, text: impl IntoVar<Txt>) -> impl UiNode {
+    let child = resolve_span_font(child);
     let child = resolve_text_font(child);
     let child = resolve_text_access(child);
     let child = resolve_text_edit(child);
     let child = resolve_text_segments(child);
-    resolve_text_context(child, text.into_var().boxed())
+    resolve_text_context(child, text.into_var().boxed(), None)
 }
-fn resolve_text_context(child: impl UiNode, text: BoxedVar<Txt>) -> impl UiNode {
+
+/// An UI node that resolves the text context vars and segments the rich `text`.
+///
+/// This node is like [`resolve_text`], but also setups the [`ResolvedText::rich_txt`] and the [`ResolvedText::spans`] of the rich text.
+/// The `Text!` widget uses this node instead of [`resolve_text`] if the [`rich_txt`] property is set.
+///
+/// [`rich_txt`]: fn@crate::rich_txt
+pub fn resolve_rich_text(child: impl UiNode, text: impl IntoVar<RichTxt>) -> impl UiNode {
+    let text = text.into_var().boxed();
+    let child = resolve_span_font(child);
+    let child = resolve_text_font(child);
+    let child = resolve_text_access(child);
+    let child = resolve_text_edit(child);
+    let child = resolve_text_segments(child);
+    resolve_text_context(child, text.map(|t| t.text().clone()).boxed(), Some(text))
+}
+fn resolve_text_context(child: impl UiNode, text: BoxedVar<Txt>, rich_txt: Option<BoxedVar<RichTxt>>) -> impl UiNode {
     let mut resolved = None;
     match_node(child, move |child, op| match op {
         UiNodeOp::Init => {
             resolved = Some(Arc::new(RwLock::new(ResolvedText {
                 txt: text.clone(),
+                rich_txt: rich_txt.clone(),
+                spans: vec![],
                 ime_preview: None,
                 synthesis: FontSynthesis::empty(),
                 faces: FontFaceList::empty(),
+                rich_faces: vec![],
                 segmented_text: SegmentedText::new(Txt::from_static(""), LayoutDirection::LTR),
                 pending_layout: PendingLayout::empty(),
                 pending_edit: false,
@@ -67,6 +90,7 @@ fn resolve_text_context(child: impl UiNode, text: BoxedVar<Txt>) -> impl UiNode
                     opacity: var(0.fct()).read_only(),
                     index: None,
                     selection_index: None,
+                    extra_selections: vec![],
                     initial_selection: None,
                     index_version: Wrapping(0),
                     used_retained_x: false,
@@ -177,8 +201,149 @@ fn resolve_text_font(child: impl UiNode) -> impl UiNode {
         }
     })
 }
+fn resolve_span_font(child: impl UiNode) -> impl UiNode {
+    let mut reload = true;
+    // font overrides of the spans in the last reload.
+    let mut keys = vec![];
+    let mut loading = vec![];
+    let mut _loading_handles = vec![];
+    let mut _window_load_handle = None;
+
+    match_node(child, move |_, op| {
+        match op {
+            UiNodeOp::Init => {
+                WIDGET
+                    .sub_var(&FONT_FAMILY_VAR)
+                    .sub_var(&FONT_STYLE_VAR)
+                    .sub_var(&FONT_WEIGHT_VAR)
+                    .sub_var(&FONT_STRETCH_VAR)
+                    .sub_event(&FONT_CHANGED_EVENT)
+                    .sub_var(&FONT_SYNTHESIS_VAR);
+            }
+            UiNodeOp::Deinit => {
+                reload = true;
+                keys.clear();
+                loading.clear();
+                _loading_handles.clear();
+                _window_load_handle = None;
+                return;
+            }
+            UiNodeOp::Event { update } => {
+                if FONT_CHANGED_EVENT.has(update) {
+                    reload = true;
+                }
+            }
+            UiNodeOp::Update { .. } => {
+                if FONT_FAMILY_VAR.is_new()
+                    || FONT_STYLE_VAR.is_new()
+                    || FONT_WEIGHT_VAR.is_new()
+                    || FONT_STRETCH_VAR.is_new()
+                    || FONT_SYNTHESIS_VAR.is_new()
+                {
+                    reload = true;
+                } else {
+                    let txt = TEXT.resolved();
+                    // spans are only replaced by the segments node, that also requests reshape
+                    if txt.pending_layout.contains(PendingLayout::RESHAPE) && !span_font_keys_eq(&keys, &txt.spans) {
+                        reload = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if reload {
+            reload = false;
+
+            let txt = TEXT.resolved();
+            keys = txt
+                .spans
+                .iter()
+                .map(|s| {
+                    if s.style.has_font() {
+                        Some((s.style.font_family.clone(), s.style.font_weight, s.style.font_style))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            drop(txt);
+
+            loading = keys
+                .iter()
+                .map(|k| {
+                    let (family, weight, style) = k.as_ref()?;
+                    let style = style.unwrap_or_else(|| FONT_STYLE_VAR.get());
+                    let weight = weight.unwrap_or_else(|| FONT_WEIGHT_VAR.get());
+                    let list = LANG_VAR.with(|lang| match family {
+                        Some(f) => FONTS.list(f, style, weight, FONT_STRETCH_VAR.get(), lang.best()),
+                        None => FONT_FAMILY_VAR.with(|f| FONTS.list(f, style, weight, FONT_STRETCH_VAR.get(), lang.best())),
+                    });
+                    Some((list, style, weight))
+                })
+                .collect();
+            if loading.iter().all(|l| l.is_none()) {
+                loading.clear();
+            }
+
+            let id = WIDGET.id();
+            _loading_handles = loading
+                .iter()
+                .flatten()
+                .filter(|(l, _, _)| !l.is_done())
+                .map(|(l, _, _)| l.subscribe(UpdateOp::Update, id))
+                .collect();
+            _window_load_handle = if _loading_handles.is_empty() {
+                None
+            } else {
+                WINDOW.loading_handle(1.secs())
+            };
+
+            // clear faces of previous spans
+            let mut txt = TEXT.resolve();
+            if !txt.rich_faces.is_empty() {
+                txt.rich_faces = vec![];
+                txt.pending_layout |= PendingLayout::RESHAPE;
+                WIDGET.layout();
+            }
+        }
+
+        if !loading.is_empty() && loading.iter().flatten().all(|(l, _, _)| l.is_done()) {
+            let synthesis = FONT_SYNTHESIS_VAR.get();
+            let faces = loading
+                .drain(..)
+                .map(|l| {
+                    l.map(|(l, style, weight)| {
+                        let f = l.into_rsp().unwrap();
+                        let s = synthesis & f.best().synthesis_for(style, weight);
+                        (f, s)
+                    })
+                })
+                .collect();
+            _loading_handles.clear();
+            _window_load_handle = None;
+
+            let mut txt = TEXT.resolve();
+            txt.rich_faces = faces;
+            txt.pending_layout |= PendingLayout::RESHAPE;
+            WIDGET.layout();
+        }
+    })
+}
+type SpanFontKey = Option<(Option<FontNames>, Option<FontWeight>, Option<FontStyle>)>;
+fn span_font_keys_eq(keys: &[SpanFontKey], spans: &[RichSpan]) -> bool {
+    keys.len() == spans.len()
+        && keys.iter().zip(spans).all(|(k, s)| match k {
+            Some((family, weight, style)) => {
+                s.style.has_font() && &s.style.font_family == family && &s.style.font_weight == weight && &s.style.font_style == style
+            }
+            None => !s.style.has_font(),
+        })
+}
 fn resolve_text_access(child: impl UiNode) -> impl UiNode {
-    match_node(child, |child, op| match op {
+    // caret and selection in the last info build.
+    let mut info_selection = None::<(usize, usize)>;
+    match_node(child, move |child, op| match op {
         UiNodeOp::Init => {
             WIDGET
                 .sub_var_info(&TEXT.resolved().txt)
@@ -194,35 +359,74 @@ fn resolve_text_access(child: impl UiNode) -> impl UiNode {
 
             child.info(info);
 
-            if !editable && !OBSCURE_TXT_VAR.get() {
+            info_selection = None;
+            if !OBSCURE_TXT_VAR.get() {
                 if let Some(mut a) = info.access() {
-                    a.set_label(TEXT.resolved().segmented_text.text().clone());
+                    let ctx = TEXT.resolved();
+                    let txt = ctx.segmented_text.text().clone();
+                    if editable {
+                        a.set_value_text(txt);
+                    } else {
+                        a.set_label(txt);
+                    }
+
+                    info_selection = text_selection(&ctx.caret);
+                    if let Some((start, caret)) = info_selection {
+                        a.set_text_selection(start, caret);
+                    }
+                }
+            }
+        }
+        UiNodeOp::Layout { wl, final_size } => {
+            *final_size = child.layout(wl);
+
+            // caret and selection changes flag layout, update the access info if needed.
+            if WINDOW.info().access_enabled().is_enabled() && !OBSCURE_TXT_VAR.get() {
+                let ctx = TEXT.resolved();
+                if text_selection(&ctx.caret) != info_selection {
+                    WIDGET.update_info();
                 }
             }
         }
         _ => {}
     })
 }
+fn text_selection(caret: &CaretInfo) -> Option<(usize, usize)> {
+    let c = caret.index?.index;
+    Some((caret.selection_index.map(|s| s.index).unwrap_or(c), c))
+}
 fn resolve_text_segments(child: impl UiNode) -> impl UiNode {
-    match_node(child, |_, op| {
+    let mut highlight = HighlightCache::default();
+    match_node(child, move |_, op| {
         let mut segment = false;
         match op {
             UiNodeOp::Init => {
+                let ctx = TEXT.resolved();
+                WIDGET.sub_var(&ctx.txt);
+                if let Some(r) = &ctx.rich_txt {
+                    WIDGET.sub_var(r);
+                }
                 WIDGET
-                    .sub_var(&TEXT.resolved().txt)
                     .sub_var(&TEXT_TRANSFORM_VAR)
                     .sub_var(&WHITE_SPACE_VAR)
                     .sub_var(&DIRECTION_VAR)
-                    .sub_var(&TEXT_EDITABLE_VAR);
+                    .sub_var(&TEXT_EDITABLE_VAR)
+                    .sub_var(&SYNTAX_HIGHLIGHT_VAR);
 
                 segment = true;
             }
+            UiNodeOp::Deinit => {
+                highlight = HighlightCache::default();
+            }
             UiNodeOp::Update { .. } => {
-                segment = TEXT.resolved().txt.is_new()
+                let ctx = TEXT.resolved();
+                segment = ctx.txt.is_new()
+                    || ctx.rich_txt.as_ref().map(|r| r.is_new()).unwrap_or(false)
                     || TEXT_TRANSFORM_VAR.is_new()
                     || WHITE_SPACE_VAR.is_new()
                     || DIRECTION_VAR.is_new()
-                    || TEXT_EDITABLE_VAR.is_new();
+                    || TEXT_EDITABLE_VAR.is_new()
+                    || SYNTAX_HIGHLIGHT_VAR.is_new();
             }
             _ => {}
         }
@@ -231,6 +435,16 @@ fn resolve_text_segments(child: impl UiNode) -> impl UiNode {
 
             let mut txt = ctx.txt.get();
 
+            if let Some(r) = &ctx.rich_txt {
+                let spans = r.with(|r| r.spans().to_vec());
+                ctx.segmented_text = segmented_spans(txt, DIRECTION_VAR.get(), &spans);
+                ctx.spans = spans;
+                // spans can change without changing the text
+                ctx.pending_layout = PendingLayout::RESHAPE;
+                WIDGET.layout();
+                return;
+            }
+
             if !TEXT_EDITABLE_VAR.get() {
                 TEXT_TRANSFORM_VAR.with(|t| {
                     if let Cow::Owned(t) = t.transform(&txt) {
@@ -245,15 +459,107 @@ fn resolve_text_segments(child: impl UiNode) -> impl UiNode {
             }
 
             let direction = DIRECTION_VAR.get();
-            if ctx.segmented_text.text() != &txt || ctx.segmented_text.base_direction() != direction {
-                ctx.segmented_text = SegmentedText::new(txt, direction);
-
-                ctx.pending_layout = PendingLayout::RESHAPE;
-                WIDGET.layout();
+            let highlight_fn = SYNTAX_HIGHLIGHT_VAR.get();
+            if highlight_fn.is_nil() {
+                highlight = HighlightCache::default();
+                if ctx.segmented_text.text() != &txt || ctx.segmented_text.base_direction() != direction || !ctx.spans.is_empty() {
+                    ctx.segmented_text = SegmentedText::new(txt, direction);
+                    ctx.spans = vec![];
+
+                    ctx.pending_layout = PendingLayout::RESHAPE;
+                    WIDGET.layout();
+                }
+            } else {
+                // only highlights the lines that changed
+                let spans = highlight.highlight(&highlight_fn, &txt);
+                if ctx.segmented_text.text() != &txt || ctx.segmented_text.base_direction() != direction || ctx.spans != spans {
+                    ctx.segmented_text = segmented_spans(txt, direction, &spans);
+                    ctx.spans = spans;
+
+                    ctx.pending_layout = PendingLayout::RESHAPE;
+                    WIDGET.layout();
+                }
             }
         }
     })
 }
+/// Segment the text and split the segments at the span boundaries.
+fn segmented_spans(txt: Txt, direction: LayoutDirection, spans: &[RichSpan]) -> SegmentedText {
+    let mut segmented_text = SegmentedText::new(txt, direction);
+    for s in spans {
+        segmented_text.split_seg_at(s.range.start);
+        segmented_text.split_seg_at(s.range.end);
+    }
+    segmented_text
+}
+
+/// Highlight spans of each line of the previous text, only lines that changed are highlighted again.
+#[derive(Default)]
+struct HighlightCache {
+    func: SyntaxHighlightFn,
+    // line text without the line break and valid spans relative to the line start.
+    lines: Vec<(Txt, Vec<(ops::Range<usize>, SpanStyle)>)>,
+}
+impl HighlightCache {
+    fn highlight(&mut self, func: &SyntaxHighlightFn, txt: &str) -> Vec<RichSpan> {
+        if &self.func != func {
+            self.func = func.clone();
+            self.lines.clear();
+        }
+
+        // line start and text, without the line break
+        let lines: Vec<(usize, &str)> = txt
+            .split('\n')
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len() + 1;
+                Some((line_start, line.strip_suffix('\r').unwrap_or(line)))
+            })
+            .collect();
+
+        let prefix = self.lines.iter().zip(&lines).take_while(|((old, _), (_, new))| old == new).count();
+        let suffix = self
+            .lines
+            .iter()
+            .rev()
+            .zip(lines.iter().rev())
+            .take(self.lines.len().min(lines.len()) - prefix)
+            .take_while(|((old, _), (_, new))| old == new)
+            .count();
+
+        let changed = lines[prefix..lines.len() - suffix].iter().map(|(_, line)| {
+            let mut spans = func.call(line);
+            let mut prev_end = 0;
+            spans.retain(|(range, style)| {
+                let valid = range.start >= prev_end
+                    && range.start < range.end
+                    && range.end <= line.len()
+                    && line.is_char_boundary(range.start)
+                    && line.is_char_boundary(range.end)
+                    && style != &SpanStyle::default();
+                if valid {
+                    prev_end = range.end;
+                }
+                valid
+            });
+            (Txt::from_str(line), spans)
+        });
+        let old_suffix = self.lines.len() - suffix;
+        let new_lines: Vec<_> = changed.collect();
+        self.lines.splice(prefix..old_suffix, new_lines);
+
+        let mut r = vec![];
+        for ((start, _), (_, spans)) in lines.iter().zip(&self.lines) {
+            for (range, style) in spans {
+                r.push(RichSpan {
+                    range: range.start + start..range.end + start,
+                    style: style.clone(),
+                });
+            }
+        }
+        r
+    }
+}
 fn resolve_text_edit(child: impl UiNode) -> impl UiNode {
     // Use `ResolveTextEdit::get` to access.
     let mut edit = None::<Box<ResolveTextEdit>>;
@@ -265,7 +571,8 @@ fn resolve_text_edit(child: impl UiNode) -> impl UiNode {
                 WIDGET
                     .sub_var(&TEXT_EDITABLE_VAR)
                     .sub_var(&TEXT_SELECTABLE_VAR)
-                    .sub_var(&MAX_CHARS_COUNT_VAR);
+                    .sub_var(&MAX_CHARS_COUNT_VAR)
+                    .sub_var(&MAX_GRAPHEMES_COUNT_VAR);
                 enable = TEXT_EDITABLE_VAR.get() || TEXT_SELECTABLE_VAR.get();
             }
             UiNodeOp::Deinit => {
@@ -295,7 +602,7 @@ fn resolve_text_edit(child: impl UiNode) -> impl UiNode {
                         resolve_text_edit_or_select_events(update, edit);
                     }
 
-                    let enable = !OBSCURE_TXT_VAR.get() && TEXT.resolved().caret.selection_range().is_some();
+                    let enable = !OBSCURE_TXT_VAR.get() && TEXT.resolved().caret.has_selection();
                     edit.cut.set_enabled(enable);
                     edit.copy.set_enabled(enable);
                 }
@@ -316,9 +623,11 @@ fn resolve_text_edit(child: impl UiNode) -> impl UiNode {
                 edit.events[5] = IME_EVENT.subscribe(id);
 
                 edit.paste = PASTE_CMD.scoped(id).subscribe(true);
+                edit.paste_history = PASTE_FROM_HISTORY_CMD.scoped(id).subscribe(true);
                 edit.edit = EDIT_CMD.scoped(id).subscribe(true);
 
                 edit.max_count = MAX_CHARS_COUNT_VAR.subscribe(UpdateOp::Update, id);
+                edit.max_graphemes = MAX_GRAPHEMES_COUNT_VAR.subscribe(UpdateOp::Update, id);
 
                 let mut ctx = TEXT.resolve();
 
@@ -335,7 +644,7 @@ fn resolve_text_edit(child: impl UiNode) -> impl UiNode {
 
                 edit.events[4] = ACCESS_SELECTION_EVENT.subscribe(id);
 
-                let enabled = !OBSCURE_TXT_VAR.get() && TEXT.resolved().caret.selection_range().is_some();
+                let enabled = !OBSCURE_TXT_VAR.get() && TEXT.resolved().caret.has_selection();
                 edit.copy = COPY_CMD.scoped(id).subscribe(enabled);
                 if editable {
                     edit.cut = CUT_CMD.scoped(id).subscribe(enabled);
@@ -355,9 +664,11 @@ struct ResolveTextEdit {
     events: [EventHandle; 6],
     caret_animation: VarHandle,
     max_count: VarHandle,
+    max_graphemes: VarHandle,
     cut: CommandHandle,
     copy: CommandHandle,
     paste: CommandHandle,
+    paste_history: CommandHandle,
     edit: CommandHandle,
 }
 impl ResolveTextEdit {
@@ -378,6 +689,19 @@ fn enforce_max_count(text: &BoxedVar<Txt>) {
             });
         }
     }
+
+    let max_count = MAX_GRAPHEMES_COUNT_VAR.get();
+    if max_count > 0 {
+        let count = text.with(|t| t.graphemes(true).count());
+        if count > max_count {
+            tracing::debug!("txt var set to text longer than can be typed");
+            let _ = text.modify(move |t| {
+                if let Some((i, _)) = t.as_str().grapheme_indices(true).nth(max_count) {
+                    t.to_mut().truncate(i);
+                }
+            });
+        }
+    }
 }
 fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
     if let Some(args) = INTERACTIVITY_CHANGED_EVENT.on(update) {
@@ -497,12 +821,17 @@ fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
         }
     } else if let Some(args) = CUT_CMD.scoped(widget.id()).on_unhandled(update) {
         let mut ctx = TEXT.resolve();
-        if let Some(range) = ctx.caret.selection_char_range() {
+        if let Some(txt) = ctx.caret.selection_txt(ctx.segmented_text.text()) {
             args.propagation().stop();
             ctx.selection_by = SelectionBy::Command;
-            CLIPBOARD.set_text(Txt::from_str(&ctx.segmented_text.text()[range]));
+            let multi = !ctx.caret.extra_selections.is_empty();
+            CLIPBOARD.set_text(txt);
             drop(ctx);
-            TextEditOp::delete().call_edit_op();
+            if multi {
+                TextEditOp::delete_selections().call_edit_op();
+            } else {
+                TextEditOp::delete().call_edit_op();
+            }
         }
     } else if let Some(args) = PASTE_CMD.scoped(widget.id()).on_unhandled(update) {
         if let Some(paste) = CLIPBOARD.text().ok().flatten() {
@@ -512,6 +841,16 @@ fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
                 TextEditOp::insert(paste).call_edit_op();
             }
         }
+    } else if let Some(args) = PASTE_FROM_HISTORY_CMD.scoped(widget.id()).on_unhandled(update) {
+        if let Some(&i) = args.param::<usize>() {
+            if let Some(ClipboardEntry::Text(paste)) = CLIPBOARD.history().with(|h| h.get(i).cloned()) {
+                if !paste.is_empty() {
+                    args.propagation().stop();
+                    TEXT.resolve().selection_by = SelectionBy::Command;
+                    TextEditOp::insert(paste).call_edit_op();
+                }
+            }
+        }
     } else if let Some(args) = EDIT_CMD.scoped(widget.id()).on_unhandled(update) {
         if let Some(op) = args.param::<UndoTextEditOp>() {
             args.propagation().stop();
@@ -567,28 +906,39 @@ fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
                     txt: args.txt.clone(),
                     prev_caret: caret.index.unwrap_or(CaretIndex::ZERO),
                     prev_selection: caret.selection_index,
+                    target: 0..0,
                 });
             }
 
-            // update preview caret/selection indexes.
-            if let Some(preview) = &ctx.ime_preview {
+            // update preview caret and target clause.
+            if let Some(preview) = &mut ctx.ime_preview {
                 let caret = &mut ctx.caret;
                 let ime_start = if let Some(s) = preview.prev_selection {
                     preview.prev_caret.index.min(s.index)
                 } else {
                     preview.prev_caret.index
                 };
-                if start != end {
-                    let start = ime_start + start;
-                    let end = ime_start + end;
-                    resegment |= caret.selection_char_range() != Some(start..end);
-                    caret.set_char_selection(start, end);
-                } else {
-                    let start = ime_start + start;
-                    resegment |= caret.selection_index.is_some() || caret.index.map(|c| c.index) != Some(start);
-                    caret.set_char_index(start);
-                    caret.selection_index = None;
+
+                // the IME selection is the target clause, it is not a text selection, only underlined.
+                // the indexes are char byte offsets in the preview txt, snap in case the IME is not exact.
+                let snap = |i: usize| {
+                    let mut i = i.min(preview.txt.len());
+                    while !preview.txt.is_char_boundary(i) {
+                        i -= 1;
+                    }
+                    i
+                };
+                let target = snap(start.min(end))..snap(start.max(end));
+                if preview.target != target {
+                    preview.target = target;
+                    ctx.pending_layout |= PendingLayout::UNDERLINE;
+                    WIDGET.layout();
                 }
+
+                let end = ime_start + preview.target.end;
+                resegment |= caret.selection_index.is_some() || caret.index.map(|c| c.index) != Some(end);
+                caret.set_char_index(end);
+                caret.selection_index = None;
             }
         } else {
             // commit IME insert
@@ -643,7 +993,11 @@ fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
                 }
                 text.end_mut();
             }
-            ctx.segmented_text = SegmentedText::new(text, DIRECTION_VAR.get());
+            if ctx.ime_preview.is_some() {
+                // highlight spans are restored on the next text update
+                ctx.spans = vec![];
+            }
+            ctx.segmented_text = segmented_spans(text, DIRECTION_VAR.get(), &ctx.spans);
 
             ctx.pending_layout |= PendingLayout::RESHAPE;
             WIDGET.layout();
@@ -664,6 +1018,7 @@ fn resolve_text_edit_events(update: &EventUpdate, edit: &mut ResolveTextEdit) {
         }
         ctx.pending_layout |= PendingLayout::CARET;
         WIDGET.layout(); // update caret_origin
+        WIDGET.update(); // update caret getters, they are inner and already handled the event
     }
 }
 fn resolve_text_edit_or_select_events(update: &EventUpdate, _: &mut ResolveTextEdit) {
@@ -671,9 +1026,9 @@ fn resolve_text_edit_or_select_events(update: &EventUpdate, _: &mut ResolveTextE
 
     if let Some(args) = COPY_CMD.scoped(widget_id).on_unhandled(update) {
         let ctx = TEXT.resolved();
-        if let Some(range) = ctx.caret.selection_char_range() {
+        if let Some(txt) = ctx.caret.selection_txt(ctx.segmented_text.text()) {
             args.propagation().stop();
-            let _ = CLIPBOARD.set_text(Txt::from_str(&ctx.segmented_text.text()[range]));
+            let _ = CLIPBOARD.set_text(txt);
         }
     } else if let Some(args) = ACCESS_SELECTION_EVENT.on_unhandled(update) {
         if args.start.0 == widget_id && args.caret.0 == widget_id {
@@ -717,9 +1072,13 @@ fn resolve_text_edit_update(_: &mut ResolveTextEdit) {
             cr.start.index = ctx.segmented_text.snap_grapheme_boundary(cr.start.index);
             cr.end.index = ctx.segmented_text.snap_grapheme_boundary(cr.end.index);
         }
+        for r in &mut caret.extra_selections {
+            r.start.index = ctx.segmented_text.snap_grapheme_boundary(r.start.index);
+            r.end.index = ctx.segmented_text.snap_grapheme_boundary(r.end.index);
+        }
     }
 
-    if TEXT_EDITABLE_VAR.get() && MAX_CHARS_COUNT_VAR.is_new() {
+    if TEXT_EDITABLE_VAR.get() && (MAX_CHARS_COUNT_VAR.is_new() || MAX_GRAPHEMES_COUNT_VAR.is_new()) {
         enforce_max_count(&TEXT.resolved().txt);
     }
 
@@ -0,0 +1,441 @@
+use std::{fmt, ops, sync::Arc};
+
+use zng_ext_font::{FontNames, FontStyle, FontWeight};
+use zng_ext_input::{
+    gesture::CLICK_EVENT,
+    mouse::{MOUSE_HOVERED_EVENT, MOUSE_MOVE_EVENT},
+};
+use zng_wgt::prelude::*;
+
+use crate::node::TEXT;
+
+/// Text with styled spans.
+///
+/// Rich text is set on a [`Text!`] using the [`rich_txt`] property, the text is shaped as a single text, so the spans
+/// wrap and align together, each span can override the font family, weight, style, color and underline of
+/// the contextual text properties and can be a link handled by [`on_link_click`].
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt_text::*;
+/// # use zng_wgt::prelude::*;
+/// let txt = RichTxt::new()
+///     .plain("Read the ")
+///     .span("docs", SpanStyle::new().bold().underline().link("https://zng-ui.github.io/doc"))
+///     .plain(" for more details.");
+///
+/// assert_eq!("Read the docs for more details.", txt.text());
+/// ```
+///
+/// [`Text!`]: struct@crate::Text
+/// [`rich_txt`]: fn@crate::rich_txt
+/// [`on_link_click`]: fn@on_link_click
+#[derive(Clone, Default, PartialEq)]
+pub struct RichTxt {
+    text: Txt,
+    spans: Vec<RichSpan>,
+}
+impl fmt::Debug for RichTxt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("RichTxt")
+                .field("text", &self.text)
+                .field("spans", &self.spans)
+                .finish()
+        } else {
+            write!(f, "{:?}", self.text)
+        }
+    }
+}
+impl fmt::Display for RichTxt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+impl RichTxt {
+    /// New empty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New from text and spans.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spans are not sorted, overlap, are empty, or are not in char boundaries of the text.
+    pub fn from_parts(text: impl Into<Txt>, spans: Vec<RichSpan>) -> Self {
+        let text = text.into();
+        let mut prev_end = 0;
+        for s in &spans {
+            assert!(
+                s.range.start >= prev_end && s.range.start < s.range.end && s.range.end <= text.len(),
+                "invalid span range {:?}",
+                s.range
+            );
+            assert!(text.is_char_boundary(s.range.start) && text.is_char_boundary(s.range.end));
+            prev_end = s.range.end;
+        }
+        Self { text, spans }
+    }
+
+    /// Destructs into the text and spans.
+    pub fn into_parts(self) -> (Txt, Vec<RichSpan>) {
+        (self.text, self.spans)
+    }
+
+    /// Append a styled span.
+    pub fn push(&mut self, text: &str, style: SpanStyle) {
+        if text.is_empty() {
+            return;
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        if style != SpanStyle::default() {
+            self.spans.push(RichSpan {
+                range: start..self.text.len(),
+                style,
+            });
+        }
+    }
+
+    /// Append text that uses the contextual text style.
+    pub fn push_plain(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    /// Builder style [`push`].
+    ///
+    /// [`push`]: Self::push
+    pub fn span(mut self, text: &str, style: SpanStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Builder style [`push_plain`].
+    ///
+    /// [`push_plain`]: Self::push_plain
+    pub fn plain(mut self, text: &str) -> Self {
+        self.push_plain(text);
+        self
+    }
+
+    /// The full text.
+    pub fn text(&self) -> &Txt {
+        &self.text
+    }
+
+    /// The styled spans, sorted by range and not overlapping.
+    ///
+    /// Text outside of spans uses the contextual text style.
+    pub fn spans(&self) -> &[RichSpan] {
+        &self.spans
+    }
+
+    /// If the text has no spans.
+    pub fn is_plain(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Gets the index of the span that contains the text byte `index`.
+    pub fn span_index_at(&self, index: usize) -> Option<usize> {
+        span_index_at(&self.spans, index)
+    }
+
+    /// Gets the span that contains the text byte `index`.
+    pub fn span_at(&self, index: usize) -> Option<&RichSpan> {
+        self.span_index_at(index).map(|i| &self.spans[i])
+    }
+}
+/// Binary search the sorted `spans` for the span that contains the text byte `index`.
+pub(crate) fn span_index_at(spans: &[RichSpan], index: usize) -> Option<usize> {
+    spans
+        .binary_search_by(|s| {
+            if s.range.end <= index {
+                std::cmp::Ordering::Less
+            } else if s.range.start > index {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .ok()
+}
+impl_from_and_into_var! {
+    fn from(text: Txt) -> RichTxt {
+        RichTxt { text, spans: vec![] }
+    }
+    fn from(text: &'static str) -> RichTxt {
+        Txt::from_static(text).into()
+    }
+    fn from(text: String) -> RichTxt {
+        Txt::from(text).into()
+    }
+    fn from(text: RichTxt) -> Txt {
+        text.text
+    }
+}
+
+/// Represents a styled range of a [`RichTxt`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichSpan {
+    /// Byte range in the text.
+    pub range: ops::Range<usize>,
+    /// Style overrides.
+    pub style: SpanStyle,
+}
+
+/// Style overrides of a [`RichSpan`].
+///
+/// Each `None` or `false` value uses the contextual text style.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpanStyle {
+    /// Font family.
+    pub font_family: Option<FontNames>,
+    /// Font weight.
+    pub font_weight: Option<FontWeight>,
+    /// Font style.
+    pub font_style: Option<FontStyle>,
+    /// Text color.
+    pub color: Option<Rgba>,
+    /// If the span is underlined.
+    ///
+    /// The underline is rendered using the contextual [`underline`] style and color, or a solid line of the font thickness
+    /// if the contextual underline is not set.
+    ///
+    /// [`underline`]: fn@crate::underline
+    pub underline: bool,
+    /// Link metadata, usually an URL.
+    ///
+    /// Link spans are not styled by default, a click on a link span calls [`on_link_click`].
+    ///
+    /// [`on_link_click`]: fn@on_link_click
+    pub link: Option<Txt>,
+}
+impl SpanStyle {
+    /// New default, no style overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the font family.
+    pub fn font_family(mut self, family: impl Into<FontNames>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Set the font weight.
+    pub fn font_weight(mut self, weight: impl Into<FontWeight>) -> Self {
+        self.font_weight = Some(weight.into());
+        self
+    }
+
+    /// Set the font weight to bold.
+    pub fn bold(self) -> Self {
+        self.font_weight(FontWeight::BOLD)
+    }
+
+    /// Set the font style.
+    pub fn font_style(mut self, style: FontStyle) -> Self {
+        self.font_style = Some(style);
+        self
+    }
+
+    /// Set the font style to italic.
+    pub fn italic(self) -> Self {
+        self.font_style(FontStyle::Italic)
+    }
+
+    /// Set the text color.
+    pub fn color(mut self, color: impl Into<Rgba>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set underline.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Set the link metadata.
+    pub fn link(mut self, link: impl Into<Txt>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// If overrides any font property, the span needs a different font query.
+    pub fn has_font(&self) -> bool {
+        self.font_family.is_some() || self.font_weight.is_some() || self.font_style.is_some()
+    }
+}
+
+type BoxedSyntaxHighlightFn = Box<dyn Fn(&str) -> Vec<(ops::Range<usize>, SpanStyle)> + Send + Sync>;
+
+/// Syntax highlight function.
+///
+/// The function is called for each line of the text, it must return the styled byte ranges of the line, the ranges are relative to
+/// the line start and must be sorted, not overlapping and in char boundaries, invalid ranges are ignored. The line text does not
+/// include the line break.
+///
+/// The function is set on a [`Text!`] using the [`syntax_highlight`] property, the highlight is applied incrementally, after an
+/// edit only the lines that changed are highlighted again. Note that because each line is highlighted independently the function
+/// cannot track state across lines, like multi-line comments.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt_text::*;
+/// # use zng_wgt::prelude::*;
+/// let highlight = SyntaxHighlightFn::new(|line: &str| {
+///     match line.find("//") {
+///         Some(i) => vec![(i..line.len(), SpanStyle::new().color(colors::GREEN))],
+///         None => vec![],
+///     }
+/// });
+///
+/// assert_eq!(1, highlight.call("let a = 0; // comment").len());
+/// ```
+///
+/// [`Text!`]: struct@crate::Text
+/// [`syntax_highlight`]: fn@crate::syntax_highlight
+#[derive(Clone, Default)]
+pub struct SyntaxHighlightFn(Option<Arc<BoxedSyntaxHighlightFn>>);
+impl SyntaxHighlightFn {
+    /// New from a closure that highlights a line.
+    pub fn new(highlight: impl Fn(&str) -> Vec<(ops::Range<usize>, SpanStyle)> + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(Box::new(highlight))))
+    }
+
+    /// Function that does not highlight.
+    ///
+    /// No heap allocation happens to create this value.
+    pub const fn nil() -> Self {
+        Self(None)
+    }
+
+    /// If this is the [`nil`] function.
+    ///
+    /// [`nil`]: Self::nil
+    pub fn is_nil(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Calls the function for the `line`.
+    pub fn call(&self, line: &str) -> Vec<(ops::Range<usize>, SpanStyle)> {
+        match &self.0 {
+            Some(f) => f(line),
+            None => vec![],
+        }
+    }
+}
+impl fmt::Debug for SyntaxHighlightFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nil() {
+            write!(f, "SyntaxHighlightFn::nil()")
+        } else {
+            write!(f, "SyntaxHighlightFn(_)")
+        }
+    }
+}
+impl PartialEq for SyntaxHighlightFn {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Arguments for [`on_link_click`].
+///
+/// [`on_link_click`]: fn@on_link_click
+#[derive(Debug, Clone)]
+pub struct LinkClickArgs {
+    /// The span link.
+    pub link: Txt,
+    /// Index of the span in the [`RichTxt::spans`].
+    pub span_index: usize,
+    /// The span text.
+    pub text: Txt,
+}
+
+/// Called when a [`RichTxt`] span with [`link`] is clicked.
+///
+/// This property is in the [`NestGroup::CHILD`] group so that it has access to the laidout text.
+///
+/// [`link`]: SpanStyle::link
+/// [`NestGroup::CHILD`]: zng_wgt::prelude::NestGroup::CHILD
+#[property(CHILD, widget_impl(crate::Text))]
+pub fn on_link_click(child: impl UiNode, handler: impl WidgetHandler<LinkClickArgs>) -> impl UiNode {
+    let mut handler = handler;
+    match_node(child, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_event(&CLICK_EVENT);
+        }
+        UiNodeOp::Event { update } => {
+            c.event(update);
+
+            if let Some(args) = CLICK_EVENT.on_unhandled(update) {
+                if !args.is_primary() {
+                    return;
+                }
+                let point = match args.position() {
+                    Some(p) => p,
+                    None => return,
+                };
+                if let Some((span_index, span)) = TEXT.rich_span_at(point) {
+                    if let Some(link) = span.style.link {
+                        args.propagation().stop();
+                        let text = Txt::from_str(&TEXT.resolved().segmented_text.text()[span.range]);
+                        handler.event(&LinkClickArgs { link, span_index, text });
+                    }
+                }
+            }
+        }
+        UiNodeOp::Update { updates } => {
+            c.update(updates);
+            handler.update();
+        }
+        _ => {}
+    })
+}
+
+/// Gets the link of the [`RichTxt`] span under the mouse cursor.
+///
+/// This property is in the [`NestGroup::CHILD`] group so that it has access to the laidout text.
+///
+/// [`NestGroup::CHILD`]: zng_wgt::prelude::NestGroup::CHILD
+#[property(CHILD, widget_impl(crate::Text))]
+pub fn get_hovered_link(child: impl UiNode, state: impl IntoVar<Option<Txt>>) -> impl UiNode {
+    let state = state.into_var();
+    let mut hovered = None::<Txt>;
+    match_node(child, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_event(&MOUSE_MOVE_EVENT).sub_event(&MOUSE_HOVERED_EVENT);
+        }
+        UiNodeOp::Deinit => {
+            if hovered.take().is_some() {
+                let _ = state.set(None);
+            }
+        }
+        UiNodeOp::Event { update } => {
+            c.event(update);
+
+            let mut new = hovered.clone();
+            if let Some(args) = MOUSE_MOVE_EVENT.on(update) {
+                new = TEXT.rich_span_at(args.position).and_then(|(_, s)| s.style.link);
+            } else if let Some(args) = MOUSE_HOVERED_EVENT.on(update) {
+                if args.is_mouse_leave() {
+                    new = None;
+                }
+            }
+            if new != hovered {
+                hovered = new;
+                let _ = state.set(hovered.clone());
+            }
+        }
+        _ => {}
+    })
+}
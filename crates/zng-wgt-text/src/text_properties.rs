@@ -8,6 +8,8 @@ use zng_view_api::config::FontAntiAliasing;
 use zng_wgt::prelude::*;
 use zng_wgt_layer::AnchorOffset;
 
+use crate::{InputMask, SyntaxHighlightFn, TxtValidateFn};
+
 /// Basic text font properties.
 ///
 /// All properties in this mixin affects [`Text!`] nodes inside the widget where they are set.
@@ -220,7 +222,7 @@ impl TextFillMix<()> {
 /// Sets the [`FONT_COLOR_VAR`].
 ///
 /// [`font_palette`]: fn@font_palette
-#[property(CONTEXT, default(FONT_COLOR_VAR), widget_impl(TextFillMix<P>))]
+#[property(CONTEXT, animatable, default(FONT_COLOR_VAR), widget_impl(TextFillMix<P>))]
 pub fn font_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
     with_context_var(child, FONT_COLOR_VAR, color)
 }
@@ -675,6 +677,16 @@ context_var! {
     pub static IME_UNDERLINE_THICKNESS_VAR: UnderlineThickness = 1;
     /// Underline style for the IME preview underline.
     pub static IME_UNDERLINE_STYLE_VAR: LineStyle = LineStyle::Dotted;
+    /// Underline color for the IME preview underlines, inherits from [`FONT_COLOR_VAR`].
+    pub static IME_UNDERLINE_COLOR_VAR: Rgba = FONT_COLOR_VAR;
+
+    /// Underline thickness for the IME preview target clause.
+    pub static IME_TARGET_UNDERLINE_THICKNESS_VAR: UnderlineThickness = 2;
+    /// Underline style for the IME preview target clause.
+    pub static IME_TARGET_UNDERLINE_STYLE_VAR: LineStyle = LineStyle::Solid;
+
+    /// Background color of the IME preview text.
+    pub static IME_PREVIEW_BACKGROUND_VAR: Rgba = rgba(0, 0, 0, 0);
 }
 
 impl TextDecorationMix<()> {
@@ -693,6 +705,10 @@ impl TextDecorationMix<()> {
         set.insert(&STRIKETHROUGH_COLOR_VAR);
         set.insert(&IME_UNDERLINE_THICKNESS_VAR);
         set.insert(&IME_UNDERLINE_STYLE_VAR);
+        set.insert(&IME_UNDERLINE_COLOR_VAR);
+        set.insert(&IME_TARGET_UNDERLINE_THICKNESS_VAR);
+        set.insert(&IME_TARGET_UNDERLINE_STYLE_VAR);
+        set.insert(&IME_PREVIEW_BACKGROUND_VAR);
     }
 }
 
@@ -774,6 +790,42 @@ pub fn ime_underline(child: impl UiNode, thickness: impl IntoVar<UnderlineThickn
     let child = with_context_var(child, IME_UNDERLINE_THICKNESS_VAR, thickness);
     with_context_var(child, IME_UNDERLINE_STYLE_VAR, style)
 }
+/// Custom [`ime_underline`](fn@ime_underline) and [`ime_target_underline`](fn@ime_target_underline) color, if not set
+/// the [`font_color`](fn@font_color) is used.
+///
+/// Sets the [`IME_UNDERLINE_COLOR_VAR`].
+#[property(CONTEXT, default(IME_UNDERLINE_COLOR_VAR), widget_impl(TextDecorationMix<P>))]
+pub fn ime_underline_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+    with_context_var(child, IME_UNDERLINE_COLOR_VAR, color)
+}
+/// Style and thickness of the line drawn *under* the IME preview target clause.
+///
+/// Some IMEs convert the preview text in clauses, the target clause is the one being converted, it is underlined with this
+/// line instead of the [`ime_underline`](fn@ime_underline). Is a solid line of `2` thickness by default.
+///
+/// Sets the [`IME_TARGET_UNDERLINE_THICKNESS_VAR`] and [`IME_TARGET_UNDERLINE_STYLE_VAR`].
+#[property(
+    CONTEXT,
+    default(IME_TARGET_UNDERLINE_THICKNESS_VAR, IME_TARGET_UNDERLINE_STYLE_VAR),
+    widget_impl(TextDecorationMix<P>)
+)]
+pub fn ime_target_underline(
+    child: impl UiNode,
+    thickness: impl IntoVar<UnderlineThickness>,
+    style: impl IntoVar<LineStyle>,
+) -> impl UiNode {
+    let child = with_context_var(child, IME_TARGET_UNDERLINE_THICKNESS_VAR, thickness);
+    with_context_var(child, IME_TARGET_UNDERLINE_STYLE_VAR, style)
+}
+/// Background color of the IME preview text.
+///
+/// Is transparent by default.
+///
+/// Sets the [`IME_PREVIEW_BACKGROUND_VAR`].
+#[property(CONTEXT, default(IME_PREVIEW_BACKGROUND_VAR), widget_impl(TextDecorationMix<P>))]
+pub fn ime_preview_background(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+    with_context_var(child, IME_PREVIEW_BACKGROUND_VAR, color)
+}
 
 /// Text spacing properties.
 ///
@@ -924,6 +976,11 @@ context_var! {
     ///
     /// [`Text!`]: struct@crate::Text
     pub static TEXT_TRANSFORM_VAR: TextTransformFn = TextTransformFn::None;
+
+    /// Syntax highlight function applied to [`Text!`] lines.
+    ///
+    /// [`Text!`]: struct@crate::Text
+    pub static SYNTAX_HIGHLIGHT_VAR: SyntaxHighlightFn = SyntaxHighlightFn::nil();
 }
 
 impl TextTransformMix<()> {
@@ -931,6 +988,7 @@ impl TextTransformMix<()> {
     pub fn context_vars_set(set: &mut ContextValueSet) {
         set.insert(&WHITE_SPACE_VAR);
         set.insert(&TEXT_TRANSFORM_VAR);
+        set.insert(&SYNTAX_HIGHLIGHT_VAR);
     }
 }
 
@@ -962,6 +1020,20 @@ pub fn txt_transform(child: impl UiNode, transform: impl IntoVar<TextTransformFn
     with_context_var(child, TEXT_TRANSFORM_VAR, transform)
 }
 
+/// Syntax highlight, function that styles byte ranges of each text line.
+///
+/// The spans are applied like the spans of a [`RichTxt`], but the text can be editable. After an edit only the changed
+/// lines are highlighted again. This property is not applied when the text is set by [`rich_txt`].
+///
+/// Sets the [`SYNTAX_HIGHLIGHT_VAR`].
+///
+/// [`RichTxt`]: crate::RichTxt
+/// [`rich_txt`]: fn@crate::rich_txt
+#[property(CONTEXT, default(SYNTAX_HIGHLIGHT_VAR), widget_impl(TextTransformMix<P>))]
+pub fn syntax_highlight(child: impl UiNode, highlight: impl IntoVar<SyntaxHighlightFn>) -> impl UiNode {
+    with_context_var(child, SYNTAX_HIGHLIGHT_VAR, highlight)
+}
+
 /// Language and text direction properties.
 ///
 /// All properties in this mixin affects [`Text!`] nodes inside the widget where they are set.
@@ -1274,6 +1346,16 @@ context_var! {
     /// Zero means no limit. Is zero by default.
     pub static MAX_CHARS_COUNT_VAR: usize = 0;
 
+    /// Maximum number of graphemes that can be input.
+    ///
+    /// Zero means no limit. Is zero by default.
+    pub static MAX_GRAPHEMES_COUNT_VAR: usize = 0;
+
+    /// Input mask applied to typed and pasted text.
+    ///
+    /// Is [`InputMask::none`] by default.
+    pub static INPUT_MASK_VAR: InputMask = InputMask::none();
+
     /// Replacement character used when obscuring text.
     pub static OBSCURING_CHAR_VAR: char = '•';
 
@@ -1281,6 +1363,33 @@ context_var! {
     pub static OBSCURE_TXT_VAR: bool = false;
 
     pub(super) static TXT_PARSE_PENDING_VAR: bool = false;
+
+    pub(super) static TXT_INVALID_VAR: bool = false;
+
+    /// If [`txt_search`] matches are case sensitive.
+    ///
+    /// Is `false` by default.
+    ///
+    /// [`txt_search`]: fn@txt_search
+    pub static TXT_SEARCH_MATCH_CASE_VAR: bool = false;
+
+    /// Highlight color of [`txt_search`] matches.
+    ///
+    /// Is yellow with 40% opacity by default.
+    ///
+    /// [`txt_search`]: fn@txt_search
+    pub static TXT_SEARCH_HIGHLIGHT_VAR: Rgba = rgba(255, 230, 0, 40.pct());
+
+    /// Highlight color of the current [`txt_search`] match.
+    ///
+    /// Is orange with 70% opacity by default.
+    ///
+    /// [`txt_search`]: fn@txt_search
+    pub static TXT_SEARCH_CURRENT_HIGHLIGHT_VAR: Rgba = rgba(255, 150, 0, 70.pct());
+
+    pub(super) static TXT_SEARCH_COUNT_VAR: usize = 0;
+
+    pub(super) static TXT_SEARCH_CURRENT_VAR: Option<usize> = None;
 }
 
 impl TextEditMix<()> {
@@ -1288,6 +1397,9 @@ impl TextEditMix<()> {
     pub fn context_vars_set(set: &mut ContextValueSet) {
         set.insert(&TEXT_EDITABLE_VAR);
         set.insert(&TEXT_SELECTABLE_VAR);
+        set.insert(&TXT_SEARCH_MATCH_CASE_VAR);
+        set.insert(&TXT_SEARCH_HIGHLIGHT_VAR);
+        set.insert(&TXT_SEARCH_CURRENT_HIGHLIGHT_VAR);
         set.insert(&ACCEPTS_ENTER_VAR);
         set.insert(&CARET_COLOR_VAR);
         set.insert(&INTERACTIVE_CARET_VISUAL_VAR);
@@ -1297,6 +1409,8 @@ impl TextEditMix<()> {
         set.insert(&CHANGE_STOP_DELAY_VAR);
         set.insert(&AUTO_SELECTION_VAR);
         set.insert(&MAX_CHARS_COUNT_VAR);
+        set.insert(&MAX_GRAPHEMES_COUNT_VAR);
+        set.insert(&INPUT_MASK_VAR);
         set.insert(&OBSCURING_CHAR_VAR);
         set.insert(&OBSCURE_TXT_VAR);
     }
@@ -1479,6 +1593,132 @@ pub fn max_chars_count(child: impl UiNode, max: impl IntoVar<usize>) -> impl UiN
     with_context_var(child, MAX_CHARS_COUNT_VAR, max)
 }
 
+/// Maximum number of graphemes that can be input.
+///
+/// A grapheme is what the user perceives as a single character, for example, an emoji with skin tone modifier is
+/// a single grapheme of multiple chars. This limit is enforced in addition to the [`max_chars_count`].
+///
+/// Zero means no limit. Is zero by default.
+///
+/// This property sets the [`MAX_GRAPHEMES_COUNT_VAR`].
+///
+/// [`max_chars_count`]: fn@max_chars_count
+#[property(CONTEXT, default(MAX_GRAPHEMES_COUNT_VAR), widget_impl(TextEditMix<P>))]
+pub fn max_graphemes_count(child: impl UiNode, max: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, MAX_GRAPHEMES_COUNT_VAR, max)
+}
+
+/// Input mask applied to typed and pasted text.
+///
+/// Literals of the mask are inserted automatically and input that does not match the mask is skipped, as an
+/// example, the mask `"(999) 999-9999"` formats a phone number. See [`InputMask`] for details about the mask syntax.
+///
+/// Note that the mask is only applied to text input, you can use [`txt_validate`] with [`InputMask::is_complete`] to
+/// validate the full text.
+///
+/// This property sets the [`INPUT_MASK_VAR`].
+///
+/// [`txt_validate`]: fn@txt_validate
+#[property(CONTEXT, default(INPUT_MASK_VAR), widget_impl(TextEditMix<P>))]
+pub fn input_mask(child: impl UiNode, mask: impl IntoVar<InputMask>) -> impl UiNode {
+    with_context_var(child, INPUT_MASK_VAR, mask)
+}
+
+/// Validate the text after every change.
+///
+/// The `validate` function is called with the text on init and after every change, if it returns an error the message
+/// is set in [`DATA.invalidate`] and [`is_txt_invalid`] is `true`. Styles can use [`has_data_error`] and [`get_data_error_txt`]
+/// to display the error, the text input `FieldStyle!` shows data errors by default.
+///
+/// Multiple validations can be chained using [`TxtValidateFn::and`], the first error is shown.
+///
+/// [`DATA.invalidate`]: zng_wgt_data::DATA::invalidate
+/// [`is_txt_invalid`]: fn@is_txt_invalid
+/// [`has_data_error`]: fn@zng_wgt_data::has_data_error
+/// [`get_data_error_txt`]: fn@zng_wgt_data::get_data_error_txt
+#[property(EVENT, default(TxtValidateFn::nil()), widget_impl(TextEditMix<P>))]
+pub fn txt_validate(child: impl UiNode, validate: impl IntoVar<TxtValidateFn>) -> impl UiNode {
+    super::node::validate_text(child, validate)
+}
+
+/// If the [`txt_validate`] function returned an error for the current text.
+///
+/// [`txt_validate`]: fn@txt_validate
+#[property(CONTEXT, default(false), widget_impl(TextEditMix<P>))]
+pub fn is_txt_invalid(child: impl UiNode, state: impl IntoVar<bool>) -> impl UiNode {
+    // reverse context, `txt_validate` sets `TXT_INVALID_VAR`
+    with_context_var(child, TXT_INVALID_VAR, state)
+}
+
+/// Search and highlight all matches of the `query` in the text.
+///
+/// All matches are highlighted with the [`txt_search_highlight`] colors, the [`TEXT_SEARCH_CMD`] moves to the next
+/// or previous match, if the text is selectable the caret selects the current match. The number of matches and the
+/// current match can be bound to a search bar using [`get_txt_search_count`] and [`get_txt_search_current`].
+///
+/// An empty query disables the search. Matches don't overlap, only start and end at grapheme boundaries and are not
+/// case sensitive by default, see [`txt_search_match_case`].
+///
+/// This property must be set in the text widget.
+///
+/// [`txt_search_highlight`]: fn@txt_search_highlight
+/// [`txt_search_match_case`]: fn@txt_search_match_case
+/// [`get_txt_search_count`]: fn@get_txt_search_count
+/// [`get_txt_search_current`]: fn@get_txt_search_current
+/// [`TEXT_SEARCH_CMD`]: crate::cmd::TEXT_SEARCH_CMD
+#[property(CHILD_LAYOUT+100, default(""), widget_impl(TextEditMix<P>))]
+pub fn txt_search(child: impl UiNode, query: impl IntoVar<Txt>) -> impl UiNode {
+    super::node::search_text(child, query)
+}
+
+/// If [`txt_search`] matches are case sensitive.
+///
+/// Is `false` by default.
+///
+/// Sets the [`TXT_SEARCH_MATCH_CASE_VAR`].
+///
+/// [`txt_search`]: fn@txt_search
+#[property(CONTEXT, default(TXT_SEARCH_MATCH_CASE_VAR), widget_impl(TextEditMix<P>))]
+pub fn txt_search_match_case(child: impl UiNode, enabled: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, TXT_SEARCH_MATCH_CASE_VAR, enabled)
+}
+
+/// Highlight colors of [`txt_search`] matches and the current match.
+///
+/// Sets the [`TXT_SEARCH_HIGHLIGHT_VAR`] and [`TXT_SEARCH_CURRENT_HIGHLIGHT_VAR`].
+///
+/// [`txt_search`]: fn@txt_search
+#[property(
+    CONTEXT,
+    default(TXT_SEARCH_HIGHLIGHT_VAR, TXT_SEARCH_CURRENT_HIGHLIGHT_VAR),
+    widget_impl(TextEditMix<P>)
+)]
+pub fn txt_search_highlight(child: impl UiNode, color: impl IntoVar<Rgba>, current: impl IntoVar<Rgba>) -> impl UiNode {
+    let child = with_context_var(child, TXT_SEARCH_HIGHLIGHT_VAR, color);
+    with_context_var(child, TXT_SEARCH_CURRENT_HIGHLIGHT_VAR, current)
+}
+
+/// Gets the number of [`txt_search`] matches.
+///
+/// [`txt_search`]: fn@txt_search
+#[property(CONTEXT, default(0), widget_impl(TextEditMix<P>))]
+pub fn get_txt_search_count(child: impl UiNode, count: impl IntoVar<usize>) -> impl UiNode {
+    // reverse context, `txt_search` sets `TXT_SEARCH_COUNT_VAR`
+    with_context_var(child, TXT_SEARCH_COUNT_VAR, count)
+}
+
+/// Gets the index of the current [`txt_search`] match.
+///
+/// Is `None` until the [`TEXT_SEARCH_CMD`] moves to a match, and after the matches change.
+///
+/// [`txt_search`]: fn@txt_search
+/// [`TEXT_SEARCH_CMD`]: crate::cmd::TEXT_SEARCH_CMD
+#[property(CONTEXT, default(None), widget_impl(TextEditMix<P>))]
+pub fn get_txt_search_current(child: impl UiNode, index: impl IntoVar<Option<usize>>) -> impl UiNode {
+    // reverse context, `txt_search` sets `TXT_SEARCH_CURRENT_VAR`
+    with_context_var(child, TXT_SEARCH_CURRENT_VAR, index)
+}
+
 /// If text has changed but [`txt_parse`] has not tried to parse the new text yet.
 ///
 /// This can only be `true` if [`txt_parse_live`] is `false`.
@@ -1860,6 +2100,22 @@ pub fn get_chars_count(child: impl UiNode, chars: impl IntoVar<usize>) -> impl U
     })
 }
 
+/// Gets the number of graphemes in the text.
+#[property(EVENT, default(0), widget_impl(TextInspectMix<P>))]
+pub fn get_graphemes_count(child: impl UiNode, graphemes: impl IntoVar<usize>) -> impl UiNode {
+    use unicode_segmentation::UnicodeSegmentation as _;
+
+    let graphemes = graphemes.into_var();
+    match_node(child, move |_, op| {
+        if let UiNodeOp::Init = op {
+            let ctx = super::node::TEXT.resolved();
+            let _ = graphemes.set_from_map(&ctx.txt, |t| t.graphemes(true).count());
+            let handle = ctx.txt.bind_map(&graphemes, |t| t.graphemes(true).count());
+            WIDGET.push_var_handle(handle);
+        }
+    })
+}
+
 /// Highlight a text range.
 ///
 /// This property must be set in the text widget.
@@ -0,0 +1,337 @@
+use std::{fmt, sync::Arc};
+
+use zng_wgt::prelude::*;
+
+/// Input mask of an editable text.
+///
+/// The mask is a pattern where each char is a placeholder or a literal:
+///
+/// * `9` - An ASCII digit.
+/// * `a` - A letter.
+/// * `*` - A letter or digit.
+/// * `\` - Escapes the next char, it is a literal.
+///
+/// Any other char is a literal. Literals are inserted automatically as the user types, for example, with the mask
+/// `"(999) 999-9999"` typing `"5551234567"` results in the text `"(555) 123-4567"`.
+///
+/// The mask is applied on insert and paste, the text after the caret is reflowed, input chars that do not match
+/// the placeholder are skipped and chars after the end of the mask are dropped. Deletes are not reflowed and external
+/// changes to the text variable are not masked. Use [`is_complete`] in a [`txt_validate`] to check if the text matches
+/// all the mask.
+///
+/// See [`input_mask`] for more details.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt_text::*;
+/// let mask = InputMask::new("(999) 999-9999");
+///
+/// assert_eq!(mask.apply("5551234567", 10).0, "(555) 123-4567");
+/// assert!(mask.is_complete("(555) 123-4567"));
+/// assert!(!mask.is_complete("(555) 123"));
+/// ```
+///
+/// [`is_complete`]: Self::is_complete
+/// [`txt_validate`]: fn@crate::txt_validate
+/// [`input_mask`]: fn@crate::input_mask
+#[derive(Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct InputMask(Txt);
+impl InputMask {
+    /// New from the mask pattern.
+    pub fn new(mask: impl Into<Txt>) -> Self {
+        Self(mask.into())
+    }
+
+    /// No mask.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// If the mask pattern is empty, allows any input.
+    pub fn is_none(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The mask pattern.
+    pub fn pattern(&self) -> &Txt {
+        &self.0
+    }
+
+    fn tokens(&self) -> impl Iterator<Item = MaskToken> + '_ {
+        let mut chars = self.0.chars();
+        std::iter::from_fn(move || {
+            let c = chars.next()?;
+            Some(match c {
+                '9' => MaskToken::Digit,
+                'a' => MaskToken::Letter,
+                '*' => MaskToken::Alphanumeric,
+                '\\' => MaskToken::Literal(chars.next().unwrap_or('\\')),
+                c => MaskToken::Literal(c),
+            })
+        })
+    }
+
+    /// Reflow `txt` on the mask.
+    ///
+    /// The `caret` is a byte index in `txt`, returns the masked text and the caret index in the masked text.
+    pub fn apply(&self, txt: &str, caret: usize) -> (Txt, usize) {
+        if self.is_none() {
+            return (Txt::from_str(txt), caret);
+        }
+
+        let mut out = String::with_capacity(self.0.len());
+        let mut out_caret = 0;
+        let mut input = txt.char_indices().peekable();
+
+        'mask: for token in self.tokens() {
+            let Some(&(_, c)) = input.peek() else {
+                break;
+            };
+            if let MaskToken::Literal(l) = token {
+                out.push(l);
+                if c == l {
+                    let (i, _) = input.next().unwrap();
+                    if i < caret {
+                        out_caret = out.len();
+                    }
+                }
+            } else {
+                loop {
+                    let Some((i, c)) = input.next() else {
+                        break 'mask;
+                    };
+                    let matches = token.matches(c);
+                    if matches {
+                        out.push(c);
+                    }
+                    if i < caret {
+                        out_caret = out.len();
+                    }
+                    if matches {
+                        break;
+                    }
+                }
+            }
+        }
+
+        (out.into(), out_caret)
+    }
+
+    /// If `txt` matches all the mask.
+    pub fn is_complete(&self, txt: &str) -> bool {
+        let mut chars = txt.chars();
+        for token in self.tokens() {
+            match chars.next() {
+                Some(c) if token.matches(c) => {}
+                _ => return false,
+            }
+        }
+        chars.next().is_none()
+    }
+}
+impl fmt::Debug for InputMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "InputMask(")?;
+        }
+        write!(f, "{:?}", self.0)?;
+        if f.alternate() {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+impl fmt::Display for InputMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl_from_and_into_var! {
+    fn from(mask: Txt) -> InputMask {
+        InputMask(mask)
+    }
+    fn from(mask: &'static str) -> InputMask {
+        InputMask(Txt::from_static(mask))
+    }
+    fn from(mask: String) -> InputMask {
+        InputMask(mask.into())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MaskToken {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+impl MaskToken {
+    fn matches(self, c: char) -> bool {
+        match self {
+            MaskToken::Digit => c.is_ascii_digit(),
+            MaskToken::Letter => c.is_alphabetic(),
+            MaskToken::Alphanumeric => c.is_alphanumeric(),
+            MaskToken::Literal(l) => c == l,
+        }
+    }
+}
+
+type BoxedTxtValidateFn = Box<dyn Fn(&Txt) -> Result<(), Txt> + Send + Sync>;
+
+/// Text validation function.
+///
+/// The function is called for each text change, it returns the error message if the text is not valid. Functions
+/// can be chained using [`and`], the first error is used.
+///
+/// See [`txt_validate`] for more details.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_text::*;
+/// let mask = InputMask::new("(999) 999-9999");
+/// let validate = TxtValidateFn::new(|txt| if txt.is_empty() { Err("required".into()) } else { Ok(()) })
+///     .and(TxtValidateFn::new(move |txt| if mask.is_complete(txt) { Ok(()) } else { Err("incomplete phone number".into()) }));
+///
+/// assert_eq!(validate.call(&Txt::from("")), Err(Txt::from("required")));
+/// assert_eq!(validate.call(&Txt::from("(555) 123")), Err(Txt::from("incomplete phone number")));
+/// ```
+///
+/// [`and`]: Self::and
+/// [`txt_validate`]: fn@crate::txt_validate
+#[derive(Clone, Default)]
+pub struct TxtValidateFn(Option<Arc<BoxedTxtValidateFn>>);
+impl TxtValidateFn {
+    /// New from a closure that validates the text.
+    pub fn new(validate: impl Fn(&Txt) -> Result<(), Txt> + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(Box::new(validate))))
+    }
+
+    /// Function that accepts any text.
+    pub const fn nil() -> Self {
+        Self(None)
+    }
+
+    /// If this is the [`nil`] function.
+    ///
+    /// [`nil`]: Self::nil
+    pub fn is_nil(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Validate the text.
+    pub fn call(&self, txt: &Txt) -> Result<(), Txt> {
+        match &self.0 {
+            Some(f) => f(txt),
+            None => Ok(()),
+        }
+    }
+
+    /// New function that validates using `self` first, then `other` if `self` accepts the text.
+    pub fn and(self, other: TxtValidateFn) -> Self {
+        if self.is_nil() {
+            other
+        } else if other.is_nil() {
+            self
+        } else {
+            Self::new(move |txt| {
+                self.call(txt)?;
+                other.call(txt)
+            })
+        }
+    }
+}
+impl fmt::Debug for TxtValidateFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nil() {
+            write!(f, "TxtValidateFn::nil()")
+        } else {
+            write!(f, "TxtValidateFn(_)")
+        }
+    }
+}
+impl PartialEq for TxtValidateFn {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(mask: &str, txt: &str, caret: usize) -> (Txt, usize) {
+        InputMask::new(Txt::from_str(mask)).apply(txt, caret)
+    }
+
+    #[test]
+    fn mask_accept() {
+        assert_eq!(apply("(999) 999-9999", "5551234567", 10), (Txt::from("(555) 123-4567"), 14));
+        assert_eq!(apply("(999) 999-9999", "555", 3), (Txt::from("(555"), 4));
+        assert_eq!(apply("(999)", "(555)", 5), (Txt::from("(555)"), 5));
+        assert_eq!(apply("aa-**", "abx1", 4), (Txt::from("ab-x1"), 5));
+    }
+
+    #[test]
+    fn mask_reject() {
+        assert_eq!(apply("999", "5a5b5", 5), (Txt::from("555"), 3));
+        assert_eq!(apply("aa", "1é2b", 5), (Txt::from("éb"), 3));
+        assert_eq!(apply("99", "1234", 4), (Txt::from("12"), 2));
+        assert_eq!(apply("99", "ab", 2), (Txt::from(""), 0));
+    }
+
+    #[test]
+    fn mask_escape() {
+        assert_eq!(apply("\\9-99", "12", 2), (Txt::from("9-12"), 4));
+        assert!(InputMask::new("\\a9").is_complete("a1"));
+        assert!(!InputMask::new("\\a9").is_complete("b1"));
+    }
+
+    #[test]
+    fn mask_none() {
+        let mask = InputMask::none();
+        assert!(mask.is_none());
+        assert_eq!(mask.apply("any text", 3), (Txt::from("any text"), 3));
+    }
+
+    #[test]
+    fn mask_is_complete() {
+        let mask = InputMask::new("(999) 999-9999");
+        assert!(mask.is_complete("(555) 123-4567"));
+        assert!(!mask.is_complete("(555) 123"));
+        assert!(!mask.is_complete("(555) 123-45678"));
+        assert!(!mask.is_complete("(555)x123-4567"));
+        assert!(!mask.is_complete("(555) 123-456a"));
+    }
+
+    #[test]
+    fn validate_messages() {
+        let required = TxtValidateFn::new(|txt| if txt.is_empty() { Err("required".into()) } else { Ok(()) });
+        let mask = InputMask::new("999");
+        let complete = TxtValidateFn::new(move |txt| if mask.is_complete(txt) { Ok(()) } else { Err("incomplete".into()) });
+        let validate = required.and(complete);
+
+        assert_eq!(validate.call(&Txt::from("")), Err(Txt::from("required")));
+        assert_eq!(validate.call(&Txt::from("12")), Err(Txt::from("incomplete")));
+        assert_eq!(validate.call(&Txt::from("123")), Ok(()));
+    }
+
+    #[test]
+    fn validate_nil() {
+        let nil = TxtValidateFn::nil();
+        assert!(nil.is_nil());
+        assert_eq!(nil.call(&Txt::from("")), Ok(()));
+
+        let f = TxtValidateFn::new(|_| Err("error".into()));
+        assert_eq!(TxtValidateFn::nil().and(f.clone()), f);
+        assert_eq!(f.clone().and(TxtValidateFn::nil()), f);
+        assert_ne!(f.clone().and(f.clone()), f);
+    }
+}
@@ -0,0 +1,545 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Toast notifications service and widget.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use std::{mem, time::Duration};
+
+use zng_app::{
+    view_process::{
+        raw_events::{RawNotificationRespondedArgs, RAW_NOTIFICATION_RESPONDED_EVENT},
+        VIEW_PROCESS,
+    },
+    window::WindowMode,
+};
+use zng_ext_font::FontWeight;
+use zng_ext_input::gesture::ClickArgs;
+use zng_ext_window::{WindowState, WINDOWS};
+use zng_view_api::notification as api;
+use zng_wgt::{align, base_color, border, corner_radius, margin, prelude::*};
+use zng_wgt_access::{access_role, AccessRole};
+use zng_wgt_button::Button;
+use zng_wgt_container::{padding, Container};
+use zng_wgt_fill::background_color;
+use zng_wgt_input::gesture::on_click;
+use zng_wgt_layer::{LayerIndex, LAYERS};
+use zng_wgt_size_offset::width;
+use zng_wgt_stack::{Stack, StackDirection};
+use zng_wgt_style::{impl_style_fn, style_fn, Style, StyleMix};
+use zng_wgt_text::Text;
+
+pub use zng_view_api::notification::{NotificationAction, NotificationId, NotificationResponse};
+
+/// Notifications service.
+///
+/// Shows notifications as in-app toasts or as native operating system notifications. In-app toasts are shown
+/// in a layer of the focused window, or of any open window if none is focused, windows must have the [`toast_host`]
+/// node to show toasts, the default `APP.defaults()` sets this node in all windows.
+///
+/// Toasts that exceed the [`max_visible`] count are queued and shown in the order they were requested as the previous
+/// toasts close. Toasts close when they are clicked, when an action is clicked or after the notification timeout elapses.
+///
+/// Native notifications are shown by the view-process, the default view-process only implements native notifications
+/// on Linux and BSD, other systems fallback to in-app toasts. When a native notification is clicked the app main window
+/// is focused.
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_toast::*;
+/// # fn demo() {
+/// let response = NOTIFICATIONS.notify(Notification::new("Download", "File downloaded.").action("open", "Open"));
+/// task::spawn(async move {
+///     if let NotificationResponse::Action(a) = response.wait_into_rsp().await {
+///         tracing::info!("clicked {a}");
+///     }
+/// });
+/// # }
+/// ```
+///
+/// [`max_visible`]: NOTIFICATIONS::max_visible
+pub struct NOTIFICATIONS;
+impl NOTIFICATIONS {
+    /// Show the `notification`.
+    ///
+    /// Returns a response var that updates once when the notification is clicked, an action is clicked or
+    /// the notification is dismissed.
+    pub fn notify(&self, notification: Notification) -> ResponseVar<NotificationResponse> {
+        let (responder, response) = response_var();
+
+        let mut s = NOTIFICATIONS_SV.write();
+        if !mem::replace(&mut s.hooked, true) {
+            RAW_NOTIFICATION_RESPONDED_EVENT
+                .on_pre_event(app_hn!(|args: &RawNotificationRespondedArgs, _| {
+                    if matches!(args.response, NotificationResponse::Activated | NotificationResponse::Action(_))
+                        && NOTIFICATIONS_SV.read().shown.iter().any(|e| e.id == args.notification_id)
+                    {
+                        focus_main_window();
+                    }
+                    NOTIFICATIONS.respond(args.notification_id, args.response.clone());
+                }))
+                .perm();
+        }
+
+        let mut entry = NotificationEntry {
+            id: s.id_gen.incr(),
+            notification,
+            responder,
+            state: EntryState::Queued,
+        };
+
+        let native = match entry.notification.route.unwrap_or_else(|| s.route.get()) {
+            NotificationRoute::InApp => false,
+            NotificationRoute::Auto => WINDOWS.focused_window_id().is_none(),
+            NotificationRoute::Native => true,
+        };
+        if native {
+            let request = api::NotificationRequest {
+                id: entry.id,
+                title: entry.notification.title.clone(),
+                body: entry.notification.body.clone(),
+                actions: entry.notification.actions.clone(),
+            };
+            match VIEW_PROCESS.show_notification(request) {
+                Ok(Ok(())) => {
+                    entry.state = EntryState::Native;
+                    s.shown.push(entry);
+                    return response;
+                }
+                Ok(Err(e)) => tracing::debug!("cannot show native notification, {e}, will show toast"),
+                Err(_) => tracing::debug!("cannot show native notification, view-process offline, will show toast"),
+            }
+        }
+
+        s.queue.push(entry);
+        s.show_queued();
+
+        response
+    }
+
+    /// Close the notification with the `response`.
+    ///
+    /// This is used by toast widgets to respond, custom toast widgets created by [`toast_fn`] must call this method to close.
+    /// Does nothing if the notification is not shown.
+    ///
+    /// [`toast_fn`]: Self::toast_fn
+    pub fn respond(&self, id: NotificationId, response: NotificationResponse) {
+        let mut s = NOTIFICATIONS_SV.write();
+        if let Some(i) = s.shown.iter().position(|e| e.id == id) {
+            let e = s.shown.remove(i);
+            match e.state {
+                EntryState::InApp { host, widget_id, .. } => host.remove(widget_id),
+                EntryState::Native => {
+                    let _ = VIEW_PROCESS.close_notification(id);
+                }
+                EntryState::Queued => unreachable!(),
+            }
+            e.responder.respond(response);
+            s.show_queued();
+        } else if let Some(i) = s.queue.iter().position(|e| e.id == id) {
+            s.queue.remove(i).responder.respond(response);
+        }
+    }
+
+    /// Maximum number of toasts visible at the same time, other toasts are queued.
+    ///
+    /// Is `3` by default.
+    pub fn max_visible(&self) -> ArcVar<usize> {
+        NOTIFICATIONS_SV.read().max_visible.clone()
+    }
+
+    /// Default toast timeout.
+    ///
+    /// Is 5 seconds by default. This is used for notifications that do not set [`Notification::timeout`].
+    pub fn default_timeout(&self) -> ArcVar<Duration> {
+        NOTIFICATIONS_SV.read().default_timeout.clone()
+    }
+
+    /// Default route for notifications that do not set [`Notification::route`].
+    ///
+    /// Is [`NotificationRoute::Auto`] by default.
+    pub fn route(&self) -> ArcVar<NotificationRoute> {
+        NOTIFICATIONS_SV.read().route.clone()
+    }
+
+    /// Widget function that creates the in-app toasts.
+    ///
+    /// Is [`default_toast_fn`] by default.
+    pub fn toast_fn(&self) -> ArcVar<WidgetFn<ToastArgs>> {
+        NOTIFICATIONS_SV.read().toast_fn.clone()
+    }
+}
+
+/// Defines how a notification is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum NotificationRoute {
+    /// Always show as a toast in an app window.
+    ///
+    /// If no window is open the toast is queued until a window opens.
+    InApp,
+    /// Show as a toast if an app window is focused, or as a native notification if no app window is
+    /// focused or open.
+    #[default]
+    Auto,
+    /// Always show as a native notification.
+    ///
+    /// If native notifications are not supported shows as a toast.
+    Native,
+}
+
+/// Notification definition.
+///
+/// See [`NOTIFICATIONS.notify`] for more details.
+///
+/// [`NOTIFICATIONS.notify`]: NOTIFICATIONS::notify
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// Notification title.
+    pub title: Txt,
+    /// Notification message.
+    pub body: Txt,
+    /// Action buttons.
+    pub actions: Vec<NotificationAction>,
+    /// Toast timeout, overrides [`NOTIFICATIONS.default_timeout`].
+    ///
+    /// If set to [`Duration::MAX`] the toast only closes on user interaction. Native notifications
+    /// timeout is defined by the operating system.
+    ///
+    /// [`NOTIFICATIONS.default_timeout`]: NOTIFICATIONS::default_timeout
+    pub timeout: Option<Duration>,
+    /// Route, overrides [`NOTIFICATIONS.route`].
+    ///
+    /// [`NOTIFICATIONS.route`]: NOTIFICATIONS::route
+    pub route: Option<NotificationRoute>,
+}
+impl Notification {
+    /// New notification.
+    pub fn new(title: impl Into<Txt>, body: impl Into<Txt>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            actions: vec![],
+            timeout: None,
+            route: None,
+        }
+    }
+
+    /// Push an action button.
+    pub fn action(mut self, id: impl Into<Txt>, label: impl Into<Txt>) -> Self {
+        self.actions.push(NotificationAction::new(id, label));
+        self
+    }
+
+    /// Set the toast timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the route.
+    pub fn route(mut self, route: NotificationRoute) -> Self {
+        self.route = Some(route);
+        self
+    }
+}
+
+/// Arguments for [`NOTIFICATIONS.toast_fn`].
+///
+/// [`NOTIFICATIONS.toast_fn`]: NOTIFICATIONS::toast_fn
+#[derive(Clone, Debug)]
+pub struct ToastArgs {
+    /// Notification ID, must be used to [`NOTIFICATIONS.respond`].
+    ///
+    /// [`NOTIFICATIONS.respond`]: NOTIFICATIONS::respond
+    pub id: NotificationId,
+    /// The notification.
+    pub notification: Notification,
+}
+
+app_local! {
+    static NOTIFICATIONS_SV: NotificationsService = NotificationsService {
+        max_visible: var(3),
+        default_timeout: var(5.secs()),
+        route: var(NotificationRoute::Auto),
+        toast_fn: var(WidgetFn::new(default_toast_fn)),
+        id_gen: NotificationId::first(),
+        hooked: false,
+        hosts: vec![],
+        queue: vec![],
+        shown: vec![],
+    };
+}
+
+struct NotificationsService {
+    max_visible: ArcVar<usize>,
+    default_timeout: ArcVar<Duration>,
+    route: ArcVar<NotificationRoute>,
+    toast_fn: ArcVar<WidgetFn<ToastArgs>>,
+
+    id_gen: NotificationId,
+    hooked: bool,
+    hosts: Vec<(WindowId, EditableUiNodeListRef)>,
+    queue: Vec<NotificationEntry>,
+    shown: Vec<NotificationEntry>,
+}
+impl NotificationsService {
+    fn host(&self) -> Option<(WindowId, EditableUiNodeListRef)> {
+        let focused = WINDOWS.focused_window_id();
+        self.hosts
+            .iter()
+            .filter(|(_, h)| h.alive())
+            .find(|(w, _)| Some(*w) == focused)
+            .or_else(|| self.hosts.iter().find(|(_, h)| h.alive()))
+            .cloned()
+    }
+
+    fn show_queued(&mut self) {
+        let visible = self.shown.iter().filter(|e| matches!(e.state, EntryState::InApp { .. })).count();
+        let mut slots = self.max_visible.get().saturating_sub(visible);
+
+        while slots > 0 && !self.queue.is_empty() {
+            let (window_id, host) = match self.host() {
+                Some(h) => h,
+                None => break,
+            };
+
+            let mut e = self.queue.remove(0);
+            let widget_id = WidgetId::new_unique();
+            let toast = self.toast_fn.get().call(ToastArgs {
+                id: e.id,
+                notification: e.notification.clone(),
+            });
+            host.push(Container! {
+                id = widget_id;
+                child = toast;
+            });
+
+            let timeout = e.notification.timeout.unwrap_or_else(|| self.default_timeout.get());
+            let timer = if timeout == Duration::MAX {
+                DeadlineHandle::dummy()
+            } else {
+                let id = e.id;
+                TIMERS.on_deadline(
+                    timeout,
+                    app_hn_once!(|_| {
+                        NOTIFICATIONS.respond(id, NotificationResponse::Dismissed);
+                    }),
+                )
+            };
+
+            e.state = EntryState::InApp {
+                window_id,
+                host,
+                widget_id,
+                _timer: timer,
+            };
+            self.shown.push(e);
+            slots -= 1;
+        }
+    }
+
+    fn register_host(&mut self, window_id: WindowId, host: EditableUiNodeListRef) {
+        self.hosts.push((window_id, host));
+        self.show_queued();
+    }
+
+    fn unregister_host(&mut self, window_id: WindowId) {
+        self.hosts.retain(|(w, _)| *w != window_id);
+
+        // re-queue toasts shown in the window
+        let mut requeue = vec![];
+        let mut i = 0;
+        while i < self.shown.len() {
+            if matches!(&self.shown[i].state, EntryState::InApp { window_id: w, .. } if *w == window_id) {
+                let mut e = self.shown.remove(i);
+                e.state = EntryState::Queued;
+                requeue.push(e);
+            } else {
+                i += 1;
+            }
+        }
+        if !requeue.is_empty() {
+            requeue.append(&mut self.queue);
+            self.queue = requeue;
+            self.show_queued();
+        }
+    }
+}
+
+struct NotificationEntry {
+    id: NotificationId,
+    notification: Notification,
+    responder: ResponderVar<NotificationResponse>,
+    state: EntryState,
+}
+
+enum EntryState {
+    Queued,
+    InApp {
+        window_id: WindowId,
+        host: EditableUiNodeListRef,
+        widget_id: WidgetId,
+        _timer: DeadlineHandle,
+    },
+    Native,
+}
+
+/// Raise and focus the first headed window if no app window is focused.
+fn focus_main_window() {
+    if WINDOWS.focused_window_id().is_some() {
+        return;
+    }
+    for w in WINDOWS.widget_trees() {
+        if w.is_rendered() && WINDOWS.mode(w.window_id()) == Ok(WindowMode::Headed) {
+            if let Ok(vars) = WINDOWS.vars(w.window_id()) {
+                if vars.state().get() == WindowState::Minimized {
+                    vars.state().set(vars.restore_state().get());
+                }
+            }
+            if WINDOWS.focus(w.window_id()).is_ok() {
+                break;
+            }
+        }
+    }
+}
+
+/// Toasts host.
+///
+/// Inserts a layer in the window that shows the in-app toasts of [`NOTIFICATIONS`]. This node must be
+/// set in the window root, the default `APP.defaults()` sets it for all windows using a root extender.
+pub fn toast_host(child: impl UiNode) -> impl UiNode {
+    let stack_id = WidgetId::new_unique();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            let toasts = EditableUiNodeList::new();
+            let host = toasts.reference();
+            LAYERS.insert(
+                LayerIndex::TOP_MOST,
+                Stack! {
+                    id = stack_id;
+                    align = Align::BOTTOM_RIGHT;
+                    margin = 10;
+                    direction = StackDirection::top_to_bottom();
+                    spacing = 5;
+                    children = toasts;
+                },
+            );
+            NOTIFICATIONS_SV.write().register_host(WINDOW.id(), host);
+        }
+        UiNodeOp::Deinit => {
+            NOTIFICATIONS_SV.write().unregister_host(WINDOW.id());
+            LAYERS.remove(stack_id);
+        }
+        _ => {}
+    })
+}
+
+/// Default [`NOTIFICATIONS.toast_fn`].
+///
+/// Returns a [`Toast!`] with the title, message, action buttons and a close button.
+///
+/// [`NOTIFICATIONS.toast_fn`]: NOTIFICATIONS::toast_fn
+/// [`Toast!`]: struct@Toast
+pub fn default_toast_fn(args: ToastArgs) -> impl UiNode {
+    let id = args.id;
+    let n = args.notification;
+
+    let mut content = ui_vec![];
+    if !n.title.is_empty() {
+        content.push(Text! {
+            txt = n.title;
+            font_weight = FontWeight::BOLD;
+        });
+    }
+    if !n.body.is_empty() {
+        content.push(Text!(n.body));
+    }
+    if !n.actions.is_empty() {
+        content.push(Stack! {
+            direction = StackDirection::left_to_right();
+            spacing = 4;
+            children = n
+                .actions
+                .into_iter()
+                .map(|a| {
+                    Button! {
+                        child = Text!(a.label);
+                        on_click = hn!(|args: &ClickArgs| {
+                            args.propagation().stop();
+                            NOTIFICATIONS.respond(id, NotificationResponse::Action(a.id.clone()));
+                        });
+                    }
+                    .boxed()
+                })
+                .collect::<UiNodeVec>();
+        });
+    }
+
+    Toast! {
+        child = Stack! {
+            direction = StackDirection::top_to_bottom();
+            spacing = 4;
+            children = content;
+        };
+        child_end = Button! {
+            child = Text!("✕");
+            style_fn = style_fn!(|_| zng_wgt_button::LightStyle!());
+            on_click = hn!(|args: &ClickArgs| {
+                args.propagation().stop();
+                NOTIFICATIONS.respond(id, NotificationResponse::Dismissed);
+            });
+        }, 4;
+        on_click = hn!(|_| {
+            NOTIFICATIONS.respond(id, NotificationResponse::Activated);
+        });
+    }
+}
+
+/// In-app notification widget.
+///
+/// This widget is used by the [`default_toast_fn`], it is a container that provides the toast style.
+#[widget($crate::Toast {
+    ($child:expr) => {
+        child = $child;
+    };
+})]
+pub struct Toast(StyleMix<Container>);
+impl Toast {
+    fn widget_intrinsic(&mut self) {
+        self.style_intrinsic(STYLE_FN_VAR, property_id!(self::style_fn));
+        widget_set! {
+            self;
+            access_role = AccessRole::Alert;
+            style_base_fn = style_fn!(|_| DefaultStyle!());
+        }
+    }
+}
+impl_style_fn!(Toast);
+
+/// Toast default style.
+#[widget($crate::DefaultStyle)]
+pub struct DefaultStyle(Style);
+impl DefaultStyle {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            replace = true;
+            padding = (8, 10);
+            width = 300;
+            corner_radius = 4;
+            base_color = light_dark(rgb(235, 235, 235), rgb(20, 20, 20));
+            background_color = colors::BASE_COLOR_VAR.rgba();
+            border = {
+                widths: 1.px(),
+                sides: colors::BASE_COLOR_VAR.shade_into(1)
+            };
+        }
+    }
+}
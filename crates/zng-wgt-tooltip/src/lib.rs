@@ -11,14 +11,15 @@
 
 zng_wgt::enable_widget_macros!();
 
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
+use zng_app::var::ContextInitHandle;
 use zng_app::{access::ACCESS_TOOLTIP_EVENT, widget::OnVarArgs};
 use zng_ext_input::{
-    focus::FOCUS_CHANGED_EVENT,
+    focus::{FOCUS, FOCUS_CHANGED_EVENT},
     gesture::CLICK_EVENT,
     keyboard::KEY_INPUT_EVENT,
-    mouse::{MOUSE, MOUSE_HOVERED_EVENT, MOUSE_INPUT_EVENT, MOUSE_WHEEL_EVENT},
+    mouse::{MOUSE, MOUSE_HOVERED_EVENT, MOUSE_INPUT_EVENT, MOUSE_MOVE_EVENT, MOUSE_WHEEL_EVENT},
 };
 use zng_wgt::{base_color, border, corner_radius, hit_test_mode, prelude::*, HitTestMode};
 use zng_wgt_access::{access_role, AccessRole};
@@ -35,15 +36,18 @@ use zng_wgt_style::{impl_style_fn, style_fn, Style};
 /// Any other widget can be used as tooltip, the recommended widget is the [`Tip!`] container, it provides the tooltip style. Note
 /// that if the `tip` node is not a widget even after initializing it will not be shown.
 ///
-/// This property can be configured by [`tooltip_anchor`], [`tooltip_delay`], [`tooltip_interval`] and [`tooltip_duration`].
+/// This property can be configured by [`tooltip_anchor`], [`tooltip_delay`], [`tooltip_hide_delay`], [`tooltip_interval`],
+/// [`tooltip_duration`] and [`tooltip_interactive`].
 ///
 /// This tooltip only opens if the widget is enabled, see [`disabled_tooltip`] for a tooltip that only shows when the widget is disabled.
 ///
 /// [`Tip!`]: struct@crate::Tip
 /// [`tooltip_anchor`]: fn@tooltip_anchor
 /// [`tooltip_delay`]: fn@tooltip_delay
+/// [`tooltip_hide_delay`]: fn@tooltip_hide_delay
 /// [`tooltip_interval`]: fn@tooltip_interval
 /// [`tooltip_duration`]: fn@tooltip_duration
+/// [`tooltip_interactive`]: fn@tooltip_interactive
 /// [`disabled_tooltip`]: fn@disabled_tooltip
 #[property(EVENT)]
 pub fn tooltip(child: impl UiNode, tip: impl UiNode) -> impl UiNode {
@@ -173,7 +177,10 @@ fn tooltip_node(child: impl UiNode, tip: impl IntoVar<WidgetFn<TooltipArgs>>, di
                         PopupState::Open(id) => {
                             if hide && !hover_target.map(|t| t.contains(id)).unwrap_or(false) {
                                 // mouse not over self and tooltip
-                                POPUP.close_id(id);
+                                // track the cursor to the interactive tooltip, if it did not leave the window
+                                let leave = MOUSE_HOVERED_EVENT.on(update).filter(|a| a.target.is_some()).map(|a| a.position);
+                                let safe_target = TOOLTIP_INTERACTIVE_VAR.get().then_some(id);
+                                hide_tooltip(WIDGET.id(), id, safe_target, leave, TOOLTIP_HIDE_DELAY_VAR.get());
                             }
                         }
                         PopupState::Closed => {
@@ -240,54 +247,61 @@ fn tooltip_node(child: impl UiNode, tip: impl IntoVar<WidgetFn<TooltipArgs>>, di
                 disabled: disabled_only,
             });
             let anchor_id = WIDGET.id();
-            let popup = match_widget(popup, move |c, op| match op {
-                UiNodeOp::Init => {
-                    c.init();
-
-                    c.with_context(WidgetUpdateMode::Bubble, || {
-                        // if the tooltip is hit-testable and the mouse hovers it, the anchor widget
-                        // will not receive mouse-leave, because it is not the logical parent of the tooltip,
-                        // so we need to duplicate cleanup logic here.
-                        WIDGET.sub_event(&MOUSE_HOVERED_EVENT);
-
-                        let mut global = OPEN_TOOLTIP.write();
-                        if let Some(id) = global.take() {
-                            POPUP.force_close_id(id);
-                        }
-                        *global = Some(WIDGET.id());
-                    });
-                }
-                UiNodeOp::Deinit => {
-                    c.with_context(WidgetUpdateMode::Bubble, || {
-                        let mut global = OPEN_TOOLTIP.write();
-                        if *global == Some(WIDGET.id()) {
-                            *global = None;
-                            TOOLTIP_LAST_CLOSED.set(Some(INSTANT.now()));
-                        }
-                    });
-                    c.deinit();
-                }
-                UiNodeOp::Event { update } => {
-                    c.event(update);
-
-                    if let Some(args) = MOUSE_HOVERED_EVENT.on(update) {
-                        let tooltip_id = match c.with_context(WidgetUpdateMode::Ignore, || WIDGET.id()) {
-                            Some(id) => id,
-                            None => {
-                                // was widget on init, now is not,
-                                // this can happen if child is an `ArcNode` that was moved
-                                return;
+            let interactive = TOOLTIP_INTERACTIVE_VAR.get();
+            let mut interactive_ctx = Some(Arc::new(LocalVar(interactive).boxed()));
+            let interactive_id = ContextInitHandle::new();
+            let hide_delay = TOOLTIP_HIDE_DELAY_VAR.get();
+            let popup = match_widget(popup, move |c, op| {
+                TOOLTIP_INTERACTIVE_VAR.with_context(interactive_id.clone(), &mut interactive_ctx, || match op {
+                    UiNodeOp::Init => {
+                        c.init();
+
+                        c.with_context(WidgetUpdateMode::Bubble, || {
+                            // if the tooltip is hit-testable and the mouse hovers it, the anchor widget
+                            // will not receive mouse-leave, because it is not the logical parent of the tooltip,
+                            // so we need to duplicate cleanup logic here.
+                            WIDGET.sub_event(&MOUSE_HOVERED_EVENT);
+
+                            let mut global = OPEN_TOOLTIP.write();
+                            if let Some(id) = global.take() {
+                                POPUP.force_close_id(id);
                             }
-                        };
+                            *global = Some(WIDGET.id());
+                        });
+                    }
+                    UiNodeOp::Deinit => {
+                        c.with_context(WidgetUpdateMode::Bubble, || {
+                            let mut global = OPEN_TOOLTIP.write();
+                            if *global == Some(WIDGET.id()) {
+                                *global = None;
+                                TOOLTIP_LAST_CLOSED.set(Some(INSTANT.now()));
+                            }
+                        });
+                        c.deinit();
+                    }
+                    UiNodeOp::Event { update } => {
+                        c.event(update);
+
+                        if let Some(args) = MOUSE_HOVERED_EVENT.on(update) {
+                            let tooltip_id = match c.with_context(WidgetUpdateMode::Ignore, || WIDGET.id()) {
+                                Some(id) => id,
+                                None => {
+                                    // was widget on init, now is not,
+                                    // this can happen if child is an `ArcNode` that was moved
+                                    return;
+                                }
+                            };
 
-                        if let Some(t) = &args.target {
-                            if !t.contains(anchor_id) && !t.contains(tooltip_id) {
-                                POPUP.close_id(tooltip_id);
+                            if let Some(t) = &args.target {
+                                if !t.contains(anchor_id) && !t.contains(tooltip_id) {
+                                    let safe_target = interactive.then_some(anchor_id);
+                                    hide_tooltip(anchor_id, tooltip_id, safe_target, Some(args.position), hide_delay);
+                                }
                             }
                         }
                     }
-                }
-                _ => {}
+                    _ => {}
+                })
             });
 
             let (anchor_var, duration_var) = if check_cursor && !MOUSE.hovered().with(|p| matches!(p, Some(p) if p.contains(anchor_id))) {
@@ -318,18 +332,115 @@ fn tooltip_node(child: impl UiNode, tip: impl IntoVar<WidgetFn<TooltipArgs>>, di
                 KEY_INPUT_EVENT.as_any(),
                 MOUSE_WHEEL_EVENT.as_any(),
             ] {
-                close_event_handles.push(event.hook(clmv!(pop_state, |_| {
-                    let retain = monitor_start.elapsed() <= 200.ms();
-                    if !retain {
-                        POPUP.close(&pop_state);
+                close_event_handles.push(event.hook(clmv!(pop_state, |update| {
+                    if monitor_start.elapsed() <= 200.ms() {
+                        return true;
+                    }
+                    if interactive && pop_state.with(|s| matches!(s, PopupState::Open(id) if update.delivery_list().enter_widget(*id))) {
+                        // interaction inside the interactive tooltip
+                        return true;
                     }
-                    retain
+                    POPUP.close(&pop_state);
+                    false
                 })));
             }
         }
     })
 }
 
+/// Closes the tooltip after the hide delay, or after the cursor leaves the safe polygon if `safe_target` is set.
+///
+/// The safe polygon is the convex hull of the `leave` point and the `safe_target` bounds, it allows moving the cursor
+/// diagonally from the anchor widget to an interactive tooltip, or back, over other widgets without closing the tooltip.
+fn hide_tooltip(anchor_id: WidgetId, tooltip_id: WidgetId, safe_target: Option<WidgetId>, leave: Option<DipPoint>, hide_delay: Duration) {
+    if let (Some(target), Some(leave)) = (safe_target, leave) {
+        if let Some(target) = WINDOW.info().get(target) {
+            let window_id = WINDOW.id();
+            let fct = target.tree().scale_factor();
+            let leave = leave.to_px(fct);
+            let target = target.outer_bounds();
+
+            MOUSE_MOVE_EVENT
+                .as_any()
+                .hook(move |update| {
+                    let args = match MOUSE_MOVE_EVENT.on(update) {
+                        Some(a) => a,
+                        None => return true,
+                    };
+                    if OPEN_TOOLTIP.get() != Some(tooltip_id) {
+                        // already closed
+                        return false;
+                    }
+                    if args.window_id == window_id {
+                        if args.target.contains(anchor_id) || args.target.contains(tooltip_id) {
+                            // hover tracking resumes
+                            return false;
+                        }
+                        if safe_polygon_contains(leave, target, args.position.to_px(fct)) {
+                            return true;
+                        }
+                    }
+                    close_tooltip_after(anchor_id, tooltip_id, hide_delay);
+                    false
+                })
+                .perm();
+            return;
+        }
+    }
+    close_tooltip_after(anchor_id, tooltip_id, hide_delay);
+}
+
+fn close_tooltip_after(anchor_id: WidgetId, tooltip_id: WidgetId, delay: Duration) {
+    if delay == Duration::ZERO {
+        if !FOCUS.focused().with(|p| matches!(p, Some(p) if p.contains(tooltip_id))) {
+            POPUP.close_id(tooltip_id);
+        }
+    } else {
+        TIMERS
+            .on_deadline(
+                delay,
+                app_hn_once!(|_| {
+                    let hovered = MOUSE
+                        .hovered()
+                        .with(|p| matches!(p, Some(p) if p.contains(anchor_id) || p.contains(tooltip_id)));
+                    let focused = FOCUS.focused().with(|p| matches!(p, Some(p) if p.contains(tooltip_id)));
+                    if !hovered && !focused {
+                        POPUP.close_id(tooltip_id);
+                    }
+                }),
+            )
+            .perm();
+    }
+}
+
+/// If `point` is inside the convex hull of `leave` and `target`.
+fn safe_polygon_contains(leave: PxPoint, target: PxRect, point: PxPoint) -> bool {
+    if target.contains(point) {
+        return true;
+    }
+
+    fn cross(o: PxPoint, a: PxPoint, b: PxPoint) -> i64 {
+        (a.x.0 as i64 - o.x.0 as i64) * (b.y.0 as i64 - o.y.0 as i64) - (a.y.0 as i64 - o.y.0 as i64) * (b.x.0 as i64 - o.x.0 as i64)
+    }
+    fn triangle_contains(a: PxPoint, b: PxPoint, c: PxPoint, p: PxPoint) -> bool {
+        let d1 = cross(a, b, p);
+        let d2 = cross(b, c, p);
+        let d3 = cross(c, a, p);
+        let has_neg = d1 < 0 || d2 < 0 || d3 < 0;
+        let has_pos = d1 > 0 || d2 > 0 || d3 > 0;
+        !(has_neg && has_pos)
+    }
+
+    // the hull is covered by the triangles from `leave` to each edge of `target`.
+    let corners = [
+        target.min(),
+        PxPoint::new(target.max_x(), target.min_y()),
+        target.max(),
+        PxPoint::new(target.min_x(), target.max_y()),
+    ];
+    (0..4).any(|i| triangle_contains(leave, corners[i], corners[(i + 1) % 4], point))
+}
+
 /// Set the position of the tip widgets opened for the widget or its descendants.
 ///
 /// Tips are inserted as [`POPUP`] when shown, this property defines how the tip layer
@@ -392,6 +503,28 @@ pub fn tooltip_delay(child: impl UiNode, delay: impl IntoVar<Duration>) -> impl
     with_context_var(child, TOOLTIP_DELAY_VAR, delay)
 }
 
+/// Set the duration the tip widget stays open after the cursor leaves the widget or its descendants.
+///
+/// If the cursor returns to the widget or hovers the tooltip within this delay the tooltip stays open.
+///
+/// This property sets the [`TOOLTIP_HIDE_DELAY_VAR`].
+#[property(CONTEXT, default(TOOLTIP_HIDE_DELAY_VAR))]
+pub fn tooltip_hide_delay(child: impl UiNode, delay: impl IntoVar<Duration>) -> impl UiNode {
+    with_context_var(child, TOOLTIP_HIDE_DELAY_VAR, delay)
+}
+
+/// Defines if the tip widgets opened for the widget or its descendants can be interacted with.
+///
+/// Interactive tooltips are hit-testable and focusable, the cursor can move from the widget to the tooltip, even diagonally
+/// over other widgets as long as it moves inside the *safe polygon* between the leave point and the tooltip. Interactive
+/// tooltips also stay open while focus is inside them and when the user clicks or types inside them.
+///
+/// This property sets the [`TOOLTIP_INTERACTIVE_VAR`].
+#[property(CONTEXT, default(TOOLTIP_INTERACTIVE_VAR))]
+pub fn tooltip_interactive(child: impl UiNode, interactive: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, TOOLTIP_INTERACTIVE_VAR, interactive)
+}
+
 /// Sets the maximum interval a second tooltip is opened instantly if a previous tip was just closed.
 ///
 /// The config applies for tooltips opening on the widget or descendants, but considers previous tooltips opened on any widget.
@@ -445,7 +578,40 @@ pub struct TooltipArgs {
     pub disabled: bool,
 }
 
+/// Tooltip app config.
+///
+/// The config vars are the default values of the tooltip context vars, set them to change the delays for all tooltips in the app.
+pub struct TOOLTIP;
+impl TOOLTIP {
+    /// Default [`TOOLTIP_DELAY_VAR`], is `500.ms()` by default.
+    pub fn delay(&self) -> ArcVar<Duration> {
+        TOOLTIP_SV.read().delay.clone()
+    }
+
+    /// Default [`TOOLTIP_HIDE_DELAY_VAR`], is `0.ms()` by default.
+    pub fn hide_delay(&self) -> ArcVar<Duration> {
+        TOOLTIP_SV.read().hide_delay.clone()
+    }
+
+    /// Default [`TOOLTIP_INTERVAL_VAR`], the reshow interval, is `200.ms()` by default.
+    pub fn interval(&self) -> ArcVar<Duration> {
+        TOOLTIP_SV.read().interval.clone()
+    }
+}
+
+struct TooltipService {
+    delay: ArcVar<Duration>,
+    hide_delay: ArcVar<Duration>,
+    interval: ArcVar<Duration>,
+}
+
 app_local! {
+    static TOOLTIP_SV: TooltipService = TooltipService {
+        delay: var(500.ms()),
+        hide_delay: var(0.ms()),
+        interval: var(200.ms()),
+    };
+
     /// Tracks the instant the last tooltip was closed on the widget.
     ///
     /// This value is used to implement the [`TOOLTIP_INTERVAL_VAR`], custom tooltip implementers must set it
@@ -475,10 +641,34 @@ context_var! {
     pub static ACCESS_TOOLTIP_ANCHOR_VAR: AnchorMode = AnchorMode::tooltip_shortcut();
 
     /// Duration the cursor must be over the anchor widget before the tip widget is opened.
-    pub static TOOLTIP_DELAY_VAR: Duration = 500.ms();
+    ///
+    /// Is [`TOOLTIP.delay`] by default.
+    ///
+    /// [`TOOLTIP.delay`]: TOOLTIP::delay
+    pub static TOOLTIP_DELAY_VAR: Duration = TOOLTIP.delay();
+
+    /// Duration the tip widget stays open after the cursor leaves the anchor widget.
+    ///
+    /// Is [`TOOLTIP.hide_delay`] by default.
+    ///
+    /// [`TOOLTIP.hide_delay`]: TOOLTIP::hide_delay
+    pub static TOOLTIP_HIDE_DELAY_VAR: Duration = TOOLTIP.hide_delay();
 
     /// Maximum duration from the last time a tooltip was shown that a new tooltip opens instantly.
-    pub static TOOLTIP_INTERVAL_VAR: Duration = 200.ms();
+    ///
+    /// Is [`TOOLTIP.interval`] by default.
+    ///
+    /// [`TOOLTIP.interval`]: TOOLTIP::interval
+    pub static TOOLTIP_INTERVAL_VAR: Duration = TOOLTIP.interval();
+
+    /// If the tip widget can be interacted with.
+    ///
+    /// This is also set in the tip widget context, the [`Tip!`] widget uses it to enable hit-test and focus.
+    ///
+    /// Is `false` by default.
+    ///
+    /// [`Tip!`]: struct@Tip
+    pub static TOOLTIP_INTERACTIVE_VAR: bool = false;
 
     /// Maximum time a tooltip stays open, when opened with cursor interaction.
     ///
@@ -514,11 +704,11 @@ impl Tip {
         self.style_intrinsic(STYLE_FN_VAR, property_id!(self::style_fn));
         widget_set! {
             self;
-            hit_test_mode = false;
+            hit_test_mode = TOOLTIP_INTERACTIVE_VAR.map_into();
 
             access_role = AccessRole::ToolTip;
 
-            focusable = false;
+            focusable = TOOLTIP_INTERACTIVE_VAR;
             focus_on_init = unset!;
 
             style_base_fn = style_fn!(|_| DefaultStyle!());
@@ -528,7 +718,9 @@ impl Tip {
     widget_impl! {
         /// If the tooltip can be interacted with the mouse.
         ///
-        /// This is disabled by default.
+        /// Is enabled if the tooltip was opened by a widget with [`tooltip_interactive`], disabled by default.
+        ///
+        /// [`tooltip_interactive`]: fn@tooltip_interactive
         pub hit_test_mode(mode: impl IntoVar<HitTestMode>);
     }
 }
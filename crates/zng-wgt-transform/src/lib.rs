@@ -18,7 +18,7 @@ use zng_wgt::prelude::*;
 ///
 /// [`transform_origin`]: fn@transform_origin
 /// [`Transform`]: zng_wgt::prelude::Transform
-#[property(LAYOUT, default(Transform::identity()))]
+#[property(LAYOUT, animatable, default(Transform::identity()))]
 pub fn transform(child: impl UiNode, transform: impl IntoVar<Transform>) -> impl UiNode {
     let binding_key = FrameValueKey::new_unique();
     let transform = transform.into_var();
@@ -83,7 +83,7 @@ pub fn transform(child: impl UiNode, transform: impl IntoVar<Transform>) -> impl
 ///
 /// [`transform`]: fn@transform
 /// [`transform_origin`]: fn@transform_origin
-#[property(LAYOUT, default(0.rad()))]
+#[property(LAYOUT, animatable, default(0.rad()))]
 pub fn rotate(child: impl UiNode, angle: impl IntoVar<AngleRadian>) -> impl UiNode {
     transform(child, angle.into_var().map(|&a| Transform::new_rotate(a)))
 }
@@ -127,7 +127,7 @@ pub fn rotate_z(child: impl UiNode, angle: impl IntoVar<AngleRadian>) -> impl Ui
 /// This property is a shorthand way of setting [`transform`] to [`new_scale(s)`](Transform::new_scale) using variable mapping.
 ///
 /// [`transform`]: fn@transform
-#[property(LAYOUT, default(1.0))]
+#[property(LAYOUT, animatable, default(1.0))]
 pub fn scale(child: impl UiNode, s: impl IntoVar<Factor>) -> impl UiNode {
     transform(child, s.into_var().map(|&x| Transform::new_scale(x)))
 }
@@ -165,6 +165,34 @@ pub fn scale_y(child: impl UiNode, y: impl IntoVar<Factor>) -> impl UiNode {
     transform(child, y.into_var().map(|&y| Transform::new_scale_y(y)))
 }
 
+/// Scale Z transform.
+///
+/// This property is a shorthand way of setting [`transform`] to [`new_scale_z(z)`](Transform::new_scale_z) using variable mapping.
+///
+/// Note that the Z scale only affects descendants translated on the Z axis in a [`transform_style`] 3D context.
+///
+/// [`transform`]: fn@transform
+/// [`transform_style`]: fn@transform_style
+#[property(LAYOUT, default(1.0))]
+pub fn scale_z(child: impl UiNode, z: impl IntoVar<Factor>) -> impl UiNode {
+    transform(child, z.into_var().map(|&z| Transform::new_scale_z(z)))
+}
+
+/// Scale X, Y and Z transform.
+///
+/// This property is a shorthand way of setting [`transform`] to [`new_scale_3d(x, y, z)`](Transform::new_scale_3d) using variable merging.
+///
+/// [`transform`]: fn@transform
+#[property(LAYOUT, default(1.0, 1.0, 1.0))]
+pub fn scale_3d(child: impl UiNode, x: impl IntoVar<Factor>, y: impl IntoVar<Factor>, z: impl IntoVar<Factor>) -> impl UiNode {
+    transform(
+        child,
+        merge_var!(x.into_var(), y.into_var(), z.into_var(), |&x, &y, &z| Transform::new_scale_3d(
+            x, y, z
+        )),
+    )
+}
+
 /// Skew transform.
 ///
 /// This property is a shorthand way of setting [`transform`] to [`new_skew(x, y)`](Transform::new_skew) using variable merging.
@@ -200,7 +228,7 @@ pub fn skew_y(child: impl UiNode, y: impl IntoVar<AngleRadian>) -> impl UiNode {
 /// This property is a shorthand way of setting [`transform`] to [`new_translate(x, y)`](Transform::new_translate) using variable merging.
 ///
 /// [`transform`]: fn@transform
-#[property(LAYOUT, default(0, 0))]
+#[property(LAYOUT, animatable, default(0, 0))]
 pub fn translate(child: impl UiNode, x: impl IntoVar<Length>, y: impl IntoVar<Length>) -> impl UiNode {
     transform(
         child,
@@ -0,0 +1,445 @@
+use parking_lot::Mutex;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+use zng_wgt::prelude::*;
+
+/// Future returned by [`TreeSource::load_children`].
+pub type TreeSourceFuture<T> = Pin<Box<dyn Future<Output = Result<Vec<TreeItem<T>>, Txt>> + Send>>;
+
+/// Asynchronous tree data source.
+///
+/// Tree sources are used by [`TreeData`] to load the children of a node when it is expanded for the first time.
+///
+/// # Examples
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_tree_view::*;
+/// struct Numbers;
+/// impl TreeSource for Numbers {
+///     type Item = u32;
+///
+///     fn load_children(&self, parent: Option<u32>) -> TreeSourceFuture<u32> {
+///         Box::pin(async move {
+///             let start = parent.map(|p| p * 10).unwrap_or(1);
+///             let items = (start..start + 9).map(|n| TreeItem::new(n, n < 1000)).collect();
+///             Ok(items)
+///         })
+///     }
+/// }
+///
+/// let numbers: TreeData<u32> = TreeData::new(Numbers);
+/// ```
+pub trait TreeSource: Send + Sync + 'static {
+    /// Item type.
+    type Item: VarValue;
+
+    /// Load the children of the `parent` item, or the root items if `parent` is `None`.
+    ///
+    /// Returns the items or an error message, the node status is set to [`TreeNodeStatus::Error`] on error.
+    fn load_children(&self, parent: Option<Self::Item>) -> TreeSourceFuture<Self::Item>;
+}
+
+/// Represents an item loaded by [`TreeSource::load_children`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeItem<T> {
+    /// Item data.
+    pub data: T,
+    /// If the item can have children.
+    ///
+    /// Only items that can have children can expand, the children are only loaded on the first expand.
+    pub has_children: bool,
+}
+impl<T> TreeItem<T> {
+    /// New item.
+    pub fn new(data: T, has_children: bool) -> Self {
+        Self { data, has_children }
+    }
+
+    /// New item that can have children.
+    pub fn branch(data: T) -> Self {
+        Self::new(data, true)
+    }
+
+    /// New item without children.
+    pub fn leaf(data: T) -> Self {
+        Self::new(data, false)
+    }
+}
+
+zng_unique_id::unique_id_32! {
+    /// Unique identifier of a node in a [`TreeData`].
+    ///
+    /// Node IDs are not retained when the parent node children are reloaded.
+    #[derive(Debug)]
+    pub struct TreeNodeId;
+}
+
+/// Children loading status of a tree node.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum TreeNodeStatus {
+    /// Children not requested yet.
+    #[default]
+    NotLoaded,
+    /// Children are loading.
+    Loading,
+    /// Children are loaded, or the node cannot have children.
+    Loaded,
+    /// Children request failed.
+    ///
+    /// The request is retried on the next expand or can be retried using [`TreeData::reload`].
+    Error(Txt),
+}
+impl TreeNodeStatus {
+    /// If is [`Loading`].
+    ///
+    /// [`Loading`]: Self::Loading
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    /// If is [`Loaded`].
+    ///
+    /// [`Loaded`]: Self::Loaded
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, Self::Loaded)
+    }
+
+    /// Gets the error message if is [`Error`].
+    ///
+    /// [`Error`]: Self::Error
+    pub fn error(&self) -> Option<&Txt> {
+        match self {
+            Self::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments for the [`tree`] item function.
+///
+/// [`tree`]: fn@crate::tree
+#[derive(Clone)]
+pub struct TreeItemArgs<T: VarValue> {
+    /// Node ID.
+    pub id: TreeNodeId,
+    /// Item data.
+    pub data: T,
+    /// Depth of the node, root items are level `0`.
+    pub level: u32,
+    /// If the item can have children.
+    pub has_children: bool,
+    /// Expanded state of the node.
+    ///
+    /// Can be set to expand or collapse the node, children are loaded on the first expand.
+    pub expanded: ArcVar<bool>,
+    /// Children loading status.
+    pub status: ReadOnlyArcVar<TreeNodeStatus>,
+    /// ID of the item container widget.
+    ///
+    /// The tree view generates one widget for each visible node, this ID can be used to focus or scroll to the node.
+    pub widget_id: WidgetId,
+}
+
+/// Tree nodes loaded from a [`TreeSource`].
+///
+/// The tree data tracks the loaded nodes and expanded state, the visible nodes, that are all root nodes and
+/// descendants of expanded nodes, are available in the [`rows`] variable in display order. The children of a node
+/// are loaded asynchronously on the first expand.
+///
+/// Use the [`tree`] property to present the data.
+///
+/// [`rows`]: Self::rows
+/// [`tree`]: fn@crate::tree
+pub struct TreeData<T: VarValue>(Arc<TreeDataInner<T>>);
+struct TreeDataInner<T: VarValue> {
+    source: Box<dyn TreeSource<Item = T>>,
+    rows: ArcVar<Vec<TreeNodeId>>,
+    status: ArcVar<TreeNodeStatus>,
+    state: Mutex<TreeState<T>>,
+}
+struct TreeState<T> {
+    roots: Vec<TreeNodeId>,
+    roots_loading: bool,
+    nodes: IdMap<TreeNodeId, TreeNode<T>>,
+}
+struct TreeNode<T> {
+    data: T,
+    parent: Option<TreeNodeId>,
+    level: u32,
+    has_children: bool,
+    children: Vec<TreeNodeId>,
+    loading: bool,
+    expanded: ArcVar<bool>,
+    status: ArcVar<TreeNodeStatus>,
+    widget_id: WidgetId,
+    _expanded_hook: VarHandle,
+}
+impl<T: VarValue> Clone for TreeData<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+impl<T: VarValue> fmt::Debug for TreeData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreeData")
+            .field("status", &self.0.status.get())
+            .field("rows", &self.0.rows.with(Vec::len))
+            .finish_non_exhaustive()
+    }
+}
+impl<T: VarValue> PartialEq for TreeData<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl<T: VarValue> TreeData<T> {
+    /// New tree data that loads nodes from the `source`.
+    ///
+    /// The root items are only requested when the data is presented or [`reload`] is called.
+    ///
+    /// [`reload`]: Self::reload
+    pub fn new(source: impl TreeSource<Item = T>) -> Self {
+        Self(Arc::new(TreeDataInner {
+            source: Box::new(source),
+            rows: var(vec![]),
+            status: var(TreeNodeStatus::NotLoaded),
+            state: Mutex::new(TreeState {
+                roots: vec![],
+                roots_loading: false,
+                nodes: IdMap::default(),
+            }),
+        }))
+    }
+
+    /// The visible nodes in display order.
+    ///
+    /// Root nodes are always visible, other nodes are visible if all ancestors are expanded.
+    pub fn rows(&self) -> ReadOnlyArcVar<Vec<TreeNodeId>> {
+        self.0.rows.read_only()
+    }
+
+    /// The root items loading status.
+    pub fn status(&self) -> ReadOnlyArcVar<TreeNodeStatus> {
+        self.0.status.read_only()
+    }
+
+    /// Gets the node item args, if the node is loaded.
+    pub fn item(&self, id: TreeNodeId) -> Option<TreeItemArgs<T>> {
+        self.0.state.lock().nodes.get(&id).map(|n| TreeItemArgs {
+            id,
+            data: n.data.clone(),
+            level: n.level,
+            has_children: n.has_children,
+            expanded: n.expanded.clone(),
+            status: n.status.read_only(),
+            widget_id: n.widget_id,
+        })
+    }
+
+    /// Gets the parent node, or `None` if the node is a root or is not loaded.
+    pub fn parent(&self, id: TreeNodeId) -> Option<TreeNodeId> {
+        self.0.state.lock().nodes.get(&id).and_then(|n| n.parent)
+    }
+
+    /// Gets the loaded children of the `parent` node, or the root nodes if `parent` is `None`.
+    pub fn children(&self, parent: Option<TreeNodeId>) -> Vec<TreeNodeId> {
+        let state = self.0.state.lock();
+        match parent {
+            Some(id) => state.nodes.get(&id).map(|n| n.children.clone()).unwrap_or_default(),
+            None => state.roots.clone(),
+        }
+    }
+
+    /// Expand the node, if it can have children.
+    pub fn expand(&self, id: TreeNodeId) {
+        self.set_expanded(id, true);
+    }
+
+    /// Collapse the node.
+    pub fn collapse(&self, id: TreeNodeId) {
+        self.set_expanded(id, false);
+    }
+
+    fn set_expanded(&self, id: TreeNodeId, expanded: bool) {
+        let var = self
+            .0
+            .state
+            .lock()
+            .nodes
+            .get(&id)
+            .filter(|n| n.has_children)
+            .map(|n| n.expanded.clone());
+        if let Some(var) = var {
+            var.set(expanded);
+        }
+    }
+
+    /// Reload the children of the `parent` node, or the root nodes if `parent` is `None`.
+    ///
+    /// The current children and descendants are retained until the request finishes, then they are replaced
+    /// with new nodes. Does nothing if the children are already loading.
+    pub fn reload(&self, parent: Option<TreeNodeId>) {
+        self.load(parent);
+    }
+
+    /// Load the root nodes if they are not loaded yet.
+    pub(crate) fn init(&self) {
+        if self.0.status.get() == TreeNodeStatus::NotLoaded {
+            self.load(None);
+        }
+    }
+
+    fn load(&self, parent: Option<TreeNodeId>) {
+        let (request, status) = {
+            let mut state = self.0.state.lock();
+            match parent {
+                Some(id) => match state.nodes.get_mut(&id) {
+                    Some(n) if n.has_children && !n.loading => {
+                        n.loading = true;
+                        (Some(n.data.clone()), n.status.clone())
+                    }
+                    _ => return,
+                },
+                None => {
+                    if state.roots_loading {
+                        return;
+                    }
+                    state.roots_loading = true;
+                    (None, self.0.status.clone())
+                }
+            }
+        };
+        status.set(TreeNodeStatus::Loading);
+
+        let inner = self.0.clone();
+        task::spawn(async move {
+            let r = inner.source.load_children(request).await;
+            TreeData(inner).loaded(parent, r, status);
+        });
+    }
+
+    fn loaded(&self, parent: Option<TreeNodeId>, r: Result<Vec<TreeItem<T>>, Txt>, status: ArcVar<TreeNodeStatus>) {
+        let mut state = self.0.state.lock();
+        let state = &mut *state;
+
+        let level = match parent {
+            Some(id) => match state.nodes.get_mut(&id) {
+                Some(n) => {
+                    n.loading = false;
+                    n.level + 1
+                }
+                // removed by parent reload
+                None => return,
+            },
+            None => {
+                state.roots_loading = false;
+                0
+            }
+        };
+
+        match r {
+            Ok(items) => {
+                let old = match parent {
+                    Some(id) => std::mem::take(&mut state.nodes.get_mut(&id).unwrap().children),
+                    None => std::mem::take(&mut state.roots),
+                };
+                for id in old {
+                    state.remove(id);
+                }
+
+                let children: Vec<_> = items.into_iter().map(|item| state.insert(&self.0, parent, level, item)).collect();
+                match parent {
+                    Some(id) => state.nodes.get_mut(&id).unwrap().children = children,
+                    None => state.roots = children,
+                }
+                status.set(TreeNodeStatus::Loaded);
+            }
+            Err(e) => {
+                tracing::debug!("tree children request failed, {e}");
+                status.set(TreeNodeStatus::Error(e));
+            }
+        }
+
+        self.0.rows.set(state.rows());
+    }
+
+    fn on_expanded(&self, id: TreeNodeId, expanded: bool) {
+        let load = {
+            let state = self.0.state.lock();
+            let load = match state.nodes.get(&id) {
+                Some(n) => expanded && !n.loading && matches!(n.status.get(), TreeNodeStatus::NotLoaded | TreeNodeStatus::Error(_)),
+                None => return,
+            };
+            self.0.rows.set(state.rows());
+            load
+        };
+        if load {
+            self.load(Some(id));
+        }
+    }
+}
+impl<T: VarValue> TreeState<T> {
+    fn insert(&mut self, inner: &Arc<TreeDataInner<T>>, parent: Option<TreeNodeId>, level: u32, item: TreeItem<T>) -> TreeNodeId {
+        let id = TreeNodeId::new_unique();
+
+        let expanded = var(false);
+        let wk: Weak<TreeDataInner<T>> = Arc::downgrade(inner);
+        let hook = expanded.hook(move |a| match wk.upgrade() {
+            Some(inner) => {
+                TreeData(inner).on_expanded(id, *a.value());
+                true
+            }
+            None => false,
+        });
+
+        self.nodes.insert(
+            id,
+            TreeNode {
+                data: item.data,
+                parent,
+                level,
+                has_children: item.has_children,
+                children: vec![],
+                loading: false,
+                expanded,
+                status: var(if item.has_children {
+                    TreeNodeStatus::NotLoaded
+                } else {
+                    TreeNodeStatus::Loaded
+                }),
+                widget_id: WidgetId::new_unique(),
+                _expanded_hook: hook,
+            },
+        );
+        id
+    }
+
+    fn remove(&mut self, id: TreeNodeId) {
+        if let Some(n) = self.nodes.remove(&id) {
+            for c in n.children {
+                self.remove(c);
+            }
+        }
+    }
+
+    fn rows(&self) -> Vec<TreeNodeId> {
+        let mut rows = Vec::with_capacity(self.roots.len());
+        self.visit_rows(&self.roots, &mut rows);
+        rows
+    }
+    fn visit_rows(&self, ids: &[TreeNodeId], rows: &mut Vec<TreeNodeId>) {
+        for id in ids {
+            rows.push(*id);
+            let n = &self.nodes[id];
+            if n.expanded.get() {
+                self.visit_rows(&n.children, rows);
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Tree view widget, properties and nodes.
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+zng_wgt::enable_widget_macros!();
+
+use std::num::NonZeroU32;
+
+use zng_ext_input::{
+    focus::{DirectionalNav, FocusScopeOnFocus, TabNav, FOCUS},
+    keyboard::{Key, KeyState, KEY_INPUT_EVENT},
+};
+use zng_wgt::prelude::*;
+use zng_wgt_access::{access_role, expanded, level, AccessRole};
+use zng_wgt_container::Container;
+use zng_wgt_input::focus::{directional_nav, focus_scope, focus_scope_behavior, focusable, tab_nav};
+use zng_wgt_scroll::{lazy, LazyMode};
+use zng_wgt_size_offset::height;
+use zng_wgt_stack::StackDirection;
+
+mod data;
+pub use data::*;
+
+/// Hierarchical list of items that can expand and collapse.
+///
+/// The tree view presents the visible nodes of a [`TreeData`] as a vertical list of items indented by level,
+/// the children of a node are loaded asynchronously on the first expand.
+///
+/// # Keyboard
+///
+/// The tree is a single tab stop, inside it the arrow keys navigate the items:
+///
+/// * `Up` and `Down` move focus to the previous and next visible item.
+/// * `Home` and `End` move focus to the first and last visible item.
+/// * `Right` expands a collapsed item, or moves focus to the first child of an expanded item.
+/// * `Left` collapses an expanded item, or moves focus to the parent item.
+/// * `*` expands all sibling items.
+///
+/// `Left` and `Right` are inverted in right-to-left contexts.
+///
+/// # Virtualization
+///
+/// Items are lazy inited, only the items visible in the parent scroll viewport are inited and rendered, the others
+/// are replaced by placeholders of the [`item_height`]. Item widgets are always sized to the item height.
+///
+/// # Shorthand
+///
+/// The `TreeView!` macro provides a shorthand init that sets `tree` property directly.
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt_tree_view::*;
+/// # use zng_wgt::prelude::*;
+/// # fn main() { }
+/// # fn shorthand_demo<T: VarValue>(data: TreeData<T>, item_fn: WidgetFn<TreeItemArgs<T>>) -> impl UiNode {
+/// TreeView!(::<T>, data, item_fn)
+/// # }
+/// ```
+///
+/// Note that the first argument is a *turbo-fish* that defines the data type and is required.
+///
+/// [`item_height`]: fn@item_height
+#[widget($crate::TreeView {
+    (::<$T:ty>, $data:expr, $item_fn:expr $(,)?) => {
+        tree::<$T> = {
+            data: $data,
+            item_fn: $item_fn,
+        };
+    }
+})]
+pub struct TreeView(WidgetBase);
+impl TreeView {
+    fn widget_intrinsic(&mut self) {
+        widget_set! {
+            self;
+            access_role = AccessRole::Tree;
+            focus_scope = true;
+            tab_nav = TabNav::Once;
+            directional_nav = DirectionalNav::None;
+            focus_scope_behavior = FocusScopeOnFocus::LastFocused;
+        }
+    }
+}
+
+context_var! {
+    /// Indentation of each level of the tree.
+    ///
+    /// Is `16.dip()` by default.
+    pub static INDENT_VAR: Length = 16.dip();
+
+    /// Height of each tree item.
+    ///
+    /// Is `24.dip()` by default.
+    pub static ITEM_HEIGHT_VAR: Length = 24.dip();
+}
+
+/// Indentation of each level of the tree.
+///
+/// Items are padded by the indent multiplied by the item level.
+///
+/// Sets the [`INDENT_VAR`].
+#[property(CONTEXT, default(INDENT_VAR), widget_impl(TreeView))]
+pub fn indent(child: impl UiNode, indent: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, INDENT_VAR, indent)
+}
+
+/// Height of each tree item.
+///
+/// All items have the same height, this is required for virtualization, the items outside the viewport
+/// are replaced by placeholders of this height.
+///
+/// Sets the [`ITEM_HEIGHT_VAR`].
+#[property(CONTEXT, default(ITEM_HEIGHT_VAR), widget_impl(TreeView))]
+pub fn item_height(child: impl UiNode, height: impl IntoVar<Length>) -> impl UiNode {
+    with_context_var(child, ITEM_HEIGHT_VAR, height)
+}
+
+/// The tree data and item generator.
+///
+/// The `item_fn` is called for each visible node of the `data` to generate the item content, the item content is
+/// placed in an item widget that implements indentation, focus and accessibility. The item content can bind to
+/// [`TreeItemArgs::expanded`] to implement an expander toggle and to [`TreeItemArgs::status`] to show the
+/// children loading status.
+///
+/// The root items are loaded on init if they are not loaded yet. Note that this property replaces the widget child.
+///
+/// # Examples
+///
+/// ```
+/// # zng_wgt::enable_widget_macros!();
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_tree_view::*;
+/// # fn main() { }
+/// # fn item(a: TreeItemArgs<Txt>) -> impl UiNode { NilUiNode }
+/// # fn demo(data: TreeData<Txt>) -> impl UiNode {
+/// TreeView! {
+///     tree::<Txt> = {
+///         data: data,
+///         item_fn: wgt_fn!(|a: TreeItemArgs<Txt>| item(a)),
+///     };
+///     indent = 20;
+/// }
+/// # }
+/// ```
+#[property(CHILD, widget_impl(TreeView))]
+pub fn tree<T: VarValue>(
+    _child: impl UiNode,
+    data: impl IntoValue<TreeData<T>>,
+    item_fn: impl IntoVar<WidgetFn<TreeItemArgs<T>>>,
+) -> impl UiNode {
+    let data = data.into();
+    let rows = data.rows();
+    let item_fn = item_fn.into_var();
+
+    let list = EditableUiNodeList::new();
+    let list_ref = list.reference();
+    // nodes and item widgets in the list, in display order.
+    let mut shown = vec![];
+
+    let child = zng_wgt_stack::node(list, StackDirection::top_to_bottom(), 0, Align::FILL);
+    match_node(child, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&rows).sub_var(&item_fn).sub_event(&KEY_INPUT_EVENT);
+            data.init();
+
+            // init first so that the list requests an update for the edits.
+            c.init();
+            rows.with(|r| sync_items(&data, &list_ref, &mut shown, r, &item_fn.get()));
+        }
+        UiNodeOp::Update { .. } => {
+            if let Some(f) = item_fn.get_new() {
+                list_ref.clear();
+                shown.clear();
+                rows.with(|r| sync_items(&data, &list_ref, &mut shown, r, &f));
+            } else if rows.is_new() {
+                rows.with(|r| sync_items(&data, &list_ref, &mut shown, r, &item_fn.get()));
+            }
+        }
+        UiNodeOp::Event { update } => {
+            c.event(update);
+
+            if let Some(args) = KEY_INPUT_EVENT.on_unhandled(update) {
+                if let KeyState::Pressed = args.state {
+                    let focused = args
+                        .target
+                        .widgets_path()
+                        .iter()
+                        .rev()
+                        .find_map(|w| shown.iter().position(|(_, wgt_id)| wgt_id == w));
+                    if let Some(i) = focused {
+                        if key_nav(&data, &shown, i, &args.key) {
+                            args.propagation().stop();
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
+
+/// Insert and remove item widgets to match the `rows`.
+fn sync_items<T: VarValue>(
+    data: &TreeData<T>,
+    list: &EditableUiNodeListRef,
+    shown: &mut Vec<(TreeNodeId, WidgetId)>,
+    rows: &[TreeNodeId],
+    item_fn: &WidgetFn<TreeItemArgs<T>>,
+) {
+    let items: Vec<_> = rows.iter().filter_map(|id| data.item(*id)).collect();
+
+    let new: IdSet<_> = items.iter().map(|a| a.id).collect();
+    shown.retain(|(id, wgt_id)| {
+        let retain = new.contains(id);
+        if !retain {
+            list.remove(*wgt_id);
+        }
+        retain
+    });
+
+    let old: IdSet<_> = shown.iter().map(|(id, _)| *id).collect();
+    for (i, args) in items.into_iter().enumerate() {
+        if !old.contains(&args.id) {
+            shown.insert(i, (args.id, args.widget_id));
+            list.insert(i, tree_item(args, item_fn));
+        }
+    }
+}
+
+/// Item container widget.
+fn tree_item<T: VarValue>(args: TreeItemArgs<T>, item_fn: &WidgetFn<TreeItemArgs<T>>) -> impl UiNode {
+    let lvl = args.level;
+    let id = args.widget_id;
+
+    let mut item = item_fn.call(args.clone());
+    if args.has_children {
+        item = expanded(item, args.expanded).boxed();
+    }
+    let item = level(item, NonZeroU32::new(lvl + 1).unwrap());
+
+    Container! {
+        id = id;
+        child = item;
+        access_role = AccessRole::TreeItem;
+        focusable = true;
+        padding = INDENT_VAR.map(move |i| SideOffsets::new(0, 0, 0, i.clone() * lvl as f32));
+        height = ITEM_HEIGHT_VAR;
+        lazy = ITEM_HEIGHT_VAR.map(|h| LazyMode::lazy_vertical(wgt_fn!(h, |_| height(NilUiNode, h.clone()))));
+    }
+}
+
+/// Handle navigation keys pressed in the `i` item, returns `true` if handled.
+fn key_nav<T: VarValue>(data: &TreeData<T>, shown: &[(TreeNodeId, WidgetId)], i: usize, key: &Key) -> bool {
+    let (id, _) = shown[i];
+    let focus = |wgt_id: WidgetId| FOCUS.focus_widget(wgt_id, true);
+
+    let (expand_key, collapse_key) = match DIRECTION_VAR.get() {
+        LayoutDirection::LTR => (Key::ArrowRight, Key::ArrowLeft),
+        LayoutDirection::RTL => (Key::ArrowLeft, Key::ArrowRight),
+    };
+
+    match key {
+        Key::ArrowDown => {
+            if let Some((_, wgt_id)) = shown.get(i + 1) {
+                focus(*wgt_id);
+            }
+        }
+        Key::ArrowUp => {
+            if i > 0 {
+                focus(shown[i - 1].1);
+            }
+        }
+        Key::Home => focus(shown[0].1),
+        Key::End => focus(shown[shown.len() - 1].1),
+        Key::Char('*') => {
+            for sibling in data.children(data.parent(id)) {
+                data.expand(sibling);
+            }
+        }
+        k if *k == expand_key => {
+            let item = match data.item(id) {
+                Some(a) => a,
+                None => return false,
+            };
+            if item.has_children {
+                if !item.expanded.get() {
+                    item.expanded.set(true);
+                } else if let Some(child) = data.children(Some(id)).first().and_then(|c| data.item(*c)) {
+                    focus(child.widget_id);
+                }
+            }
+        }
+        k if *k == collapse_key => {
+            let item = match data.item(id) {
+                Some(a) => a,
+                None => return false,
+            };
+            if item.has_children && item.expanded.get() {
+                item.expanded.set(false);
+            } else if let Some(parent) = data.parent(id).and_then(|p| data.item(p)) {
+                focus(parent.widget_id);
+            }
+        }
+        _ => return false,
+    }
+    true
+}
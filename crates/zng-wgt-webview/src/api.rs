@@ -0,0 +1,91 @@
+//! Web view host protocol.
+//!
+//! These types are shared by the app-process widget and the view-process host, custom hosts (like a CEF integration)
+//! can be implemented by registering a [`zng_view::native_child`] host that handles this protocol, the widget
+//! uses the host name set by the [`webview_host`] property.
+//!
+//! [`zng_view::native_child`]: https://zng-ui.github.io/doc/zng_view/native_child/index.html
+//! [`webview_host`]: fn@crate::webview_host
+
+use zng_wgt::prelude::*;
+
+/// Name of the default web view host, implemented using `wry` when the `"view"` feature is enabled.
+pub const WEBVIEW_HOST: &str = "zng-wgt-webview.wry";
+
+/// Open payload sent to the host.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebViewOpen {
+    /// Initial URL.
+    ///
+    /// Is empty if the [`html`] is set.
+    ///
+    /// [`html`]: Self::html
+    pub url: Txt,
+    /// Initial HTML content.
+    pub html: Txt,
+    /// Cookie and storage session.
+    pub session: WebViewSession,
+    /// Enable the browser developer tools.
+    pub devtools: bool,
+}
+
+/// Custom command sent to the host.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum WebViewCommand {
+    /// Navigate to the URL.
+    Navigate(Txt),
+    /// Load the HTML content.
+    LoadHtml(Txt),
+    /// Reload the current page.
+    Reload,
+    /// Evaluate the JavaScript code, the result is send back using [`WebViewEvent::Eval`].
+    Eval {
+        /// Request ID.
+        id: u32,
+        /// JavaScript code.
+        script: Txt,
+    },
+}
+
+/// Custom event sent by the host.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum WebViewEvent {
+    /// Navigation to the URL started.
+    Navigate(Txt),
+    /// Page finished loading the URL.
+    Load(Txt),
+    /// Document title changed.
+    TitleChanged(Txt),
+    /// Result of a [`WebViewCommand::Eval`].
+    Eval {
+        /// Request ID.
+        id: u32,
+        /// JSON result or error message.
+        result: Result<Txt, Txt>,
+    },
+}
+
+/// Web view cookie and storage session.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+pub enum WebViewSession {
+    /// Persistent session shared by all web views of the app that also use this value.
+    ///
+    /// The session data is stored in the app cache directory.
+    #[default]
+    Shared,
+    /// Persistent session shared only by web views with the same name.
+    ///
+    /// The session data is stored in a sub-directory of the app cache directory. The name must only contain
+    /// ASCII letters, digits, `-`, `_` and `.` and must not start with `.`, the web view fails to open otherwise.
+    Named(Txt),
+    /// In-memory session, cookies and storage are discarded when the web view closes.
+    Incognito,
+}
+impl_from_and_into_var! {
+    fn from(name: Txt) -> WebViewSession {
+        WebViewSession::Named(name)
+    }
+    fn from(name: &'static str) -> WebViewSession {
+        WebViewSession::Named(Txt::from_static(name))
+    }
+}
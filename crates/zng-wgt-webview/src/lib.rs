@@ -0,0 +1,357 @@
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo-icon.png")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/zng-ui/zng/main/examples/image/res/zng-logo.png")]
+//!
+//! Web view widget.
+//!
+//! The web view is a native child window hosted by the view-process, see `zng-wgt-native-child` for details about the
+//! native child integration. The default host is implemented using [`wry`], it is available when the `"view"` feature is enabled
+//! in the crate that builds the view-process, custom hosts can be implemented using the [`api`] protocol.
+//!
+//! # Platform Support
+//!
+//! The `wry` host uses WebView2 on Windows and WebKit on macOS. Linux and BSD are not supported by the default host,
+//! the web view fails to open with a not supported error, a custom host can be implemented for these platforms.
+//! The `wry` WebKitGTK backend needs a GTK main context pumped by the view-process event loop, this integration is not
+//! implemented.
+//! The native child is composited on top of the window content and is not clipped by scroll viewports, it is only hidden
+//! when the widget is fully clipped.
+//!
+//! [`wry`]: https://docs.rs/wry
+//!
+//! # Crate
+//!
+#![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
+#![warn(unused_extern_crates)]
+#![warn(missing_docs)]
+
+use std::{mem, sync::Arc};
+
+use parking_lot::Mutex;
+use zng_view_api::api_extension::ApiExtensionPayload;
+use zng_wgt::prelude::*;
+use zng_wgt_input::focus::FocusableMix;
+
+pub mod api;
+pub use api::WebViewSession;
+use api::*;
+
+#[cfg(feature = "view")]
+pub mod view;
+
+/// Web browser widget.
+///
+/// The page is loaded from the [`url`] or [`html`] and can be inspected and controlled using the [`WEBVIEW`] service.
+///
+/// ```
+/// # use zng_wgt::prelude::*;
+/// # use zng_wgt_webview::*;
+/// # fn demo() -> impl UiNode {
+/// WebView! {
+///     url = "https://zng-ui.github.io";
+///     session = WebViewSession::Incognito;
+///     on_webview_load = hn!(|args: &WebViewLoadArgs| {
+///         tracing::info!("loaded {}", args.url);
+///     });
+/// }
+/// # }
+/// ```
+///
+/// Note that the web view is hosted by the view-process, see the [crate] level documentation for more details.
+/// The default host only supports Windows and macOS, on Linux and BSD the widget stays empty and the open error is logged.
+///
+/// [`url`]: fn@url
+/// [`html`]: fn@html
+#[widget($crate::WebView)]
+pub struct WebView(FocusableMix<WidgetBase>);
+impl WebView {
+    fn widget_intrinsic(&mut self) {
+        self.widget_builder().push_build_action(|wgt| {
+            let url = wgt.capture_var_or_default(property_id!(url));
+            let html = wgt.capture_var_or_default(property_id!(html));
+            let session = wgt.capture_var_or_default(property_id!(session));
+            let devtools = wgt.capture_var_or_default(property_id!(devtools));
+            wgt.set_child(node(url, html, session, devtools));
+        });
+    }
+}
+
+/// Web page URL.
+///
+/// The web view navigates to the URL when the value changes. If the variable can be modified it is updated
+/// when the page navigates to another URL.
+#[property(CONTEXT, capture, widget_impl(WebView))]
+pub fn url(url: impl IntoVar<Txt>) {}
+
+/// Web page HTML content.
+///
+/// If not empty the content is loaded instead of the [`url`], the content is loaded again when the value changes.
+///
+/// [`url`]: fn@url
+#[property(CONTEXT, capture, widget_impl(WebView))]
+pub fn html(html: impl IntoVar<Txt>) {}
+
+/// Cookie and storage session.
+///
+/// Is [`WebViewSession::Shared`] by default. The value is only used when the web view opens.
+#[property(CONTEXT, capture, widget_impl(WebView))]
+pub fn session(session: impl IntoVar<WebViewSession>) {}
+
+/// Enable the browser developer tools.
+///
+/// Is `false` by default. The value is only used when the web view opens.
+#[property(CONTEXT, capture, widget_impl(WebView))]
+pub fn devtools(enabled: impl IntoVar<bool>) {}
+
+context_var! {
+    /// Name of the view-process web view host.
+    ///
+    /// Is [`WEBVIEW_HOST`] by default.
+    pub static WEBVIEW_HOST_VAR: Txt = Txt::from_static(WEBVIEW_HOST);
+}
+
+/// Defines the view-process web view host used by web view descendants.
+///
+/// This property sets the [`WEBVIEW_HOST_VAR`].
+#[property(CONTEXT, default(WEBVIEW_HOST_VAR))]
+pub fn webview_host(child: impl UiNode, host: impl IntoVar<Txt>) -> impl UiNode {
+    with_context_var(child, WEBVIEW_HOST_VAR, host)
+}
+
+event! {
+    /// Web view started navigating to an URL.
+    pub static WEBVIEW_NAVIGATE_EVENT: WebViewNavigateArgs;
+
+    /// Web view finished loading a page.
+    pub static WEBVIEW_LOAD_EVENT: WebViewLoadArgs;
+
+    /// Web view document title changed.
+    pub static WEBVIEW_TITLE_CHANGED_EVENT: WebViewTitleChangedArgs;
+}
+
+event_args! {
+    /// Arguments for the [`WEBVIEW_NAVIGATE_EVENT`].
+    pub struct WebViewNavigateArgs {
+        /// Web view widget.
+        pub target: WidgetPath,
+        /// Navigation URL.
+        pub url: Txt,
+
+        ..
+
+        fn delivery_list(&self, delivery_list: &mut UpdateDeliveryList) {
+            delivery_list.insert_wgt(&self.target)
+        }
+    }
+
+    /// Arguments for the [`WEBVIEW_LOAD_EVENT`].
+    pub struct WebViewLoadArgs {
+        /// Web view widget.
+        pub target: WidgetPath,
+        /// Loaded URL.
+        pub url: Txt,
+
+        ..
+
+        fn delivery_list(&self, delivery_list: &mut UpdateDeliveryList) {
+            delivery_list.insert_wgt(&self.target)
+        }
+    }
+
+    /// Arguments for the [`WEBVIEW_TITLE_CHANGED_EVENT`].
+    pub struct WebViewTitleChangedArgs {
+        /// Web view widget.
+        pub target: WidgetPath,
+        /// New document title.
+        pub title: Txt,
+
+        ..
+
+        fn delivery_list(&self, delivery_list: &mut UpdateDeliveryList) {
+            delivery_list.insert_wgt(&self.target)
+        }
+    }
+}
+
+event_property! {
+    /// Web view started navigating to an URL.
+    pub fn webview_navigate {
+        event: WEBVIEW_NAVIGATE_EVENT,
+        args: WebViewNavigateArgs,
+    }
+
+    /// Web view finished loading a page.
+    pub fn webview_load {
+        event: WEBVIEW_LOAD_EVENT,
+        args: WebViewLoadArgs,
+    }
+
+    /// Web view document title changed.
+    pub fn webview_title_changed {
+        event: WEBVIEW_TITLE_CHANGED_EVENT,
+        args: WebViewTitleChangedArgs,
+    }
+}
+
+/// Web view service.
+///
+/// Controls web view widgets by ID.
+pub struct WEBVIEW;
+impl WEBVIEW {
+    /// Evaluate the JavaScript `script` in the current page of the web view.
+    ///
+    /// Returns a response var that updates with the result serialized to JSON, or an error message. The response
+    /// never updates if no web view widget with the ID is inited.
+    pub fn eval(&self, webview_id: impl Into<WidgetId>, script: impl Into<Txt>) -> ResponseVar<Result<Txt, Txt>> {
+        let (responder, response) = response_var();
+        self.request(webview_id.into(), WebViewRequest::Eval(script.into(), responder));
+        response
+    }
+
+    /// Reload the current page of the web view.
+    pub fn reload(&self, webview_id: impl Into<WidgetId>) {
+        self.request(webview_id.into(), WebViewRequest::Reload);
+    }
+
+    fn request(&self, id: WidgetId, request: WebViewRequest) {
+        WEBVIEW_SV.write().requests.push((id, request));
+        UPDATES.update(id);
+    }
+}
+
+enum WebViewRequest {
+    Eval(Txt, ResponderVar<Result<Txt, Txt>>),
+    Reload,
+}
+
+struct WebViewService {
+    requests: Vec<(WidgetId, WebViewRequest)>,
+}
+
+app_local! {
+    static WEBVIEW_SV: WebViewService = WebViewService { requests: vec![] };
+}
+
+fn command(command: WebViewCommand) -> bool {
+    zng_wgt_native_child::command(ApiExtensionPayload::serialize(&command).unwrap()).is_some()
+}
+
+/// Web view node.
+///
+/// See [`WebView!`] for more details.
+///
+/// [`WebView!`]: struct@WebView
+pub fn node(
+    url: impl IntoVar<Txt>,
+    html: impl IntoVar<Txt>,
+    session: impl IntoVar<WebViewSession>,
+    devtools: impl IntoVar<bool>,
+) -> impl UiNode {
+    let url = url.into_var();
+    let html = html.into_var();
+    let session = session.into_var();
+    let devtools = devtools.into_var();
+
+    // last URL requested or navigated, used to avoid navigating again when `url` is updated from a navigation event.
+    let last_url = Arc::new(Mutex::new(Txt::from_static("")));
+    // pending eval requests.
+    let evals = Arc::new(Mutex::new(Vec::<(u32, ResponderVar<Result<Txt, Txt>>)>::new()));
+    let mut next_eval = 0u32;
+
+    let child = zng_wgt_native_child::node(
+        WEBVIEW_HOST_VAR,
+        clmv!(url, html, last_url, || {
+            let html = html.get();
+            let url = if html.is_empty() { url.get() } else { Txt::from_static("") };
+            *last_url.lock() = url.clone();
+            ApiExtensionPayload::serialize(&WebViewOpen {
+                url,
+                html,
+                session: session.get(),
+                devtools: devtools.get(),
+            })
+            .unwrap()
+        }),
+        clmv!(url, last_url, evals, |payload| {
+            let event = match payload.deserialize::<WebViewEvent>() {
+                Ok(e) => e,
+                Err(e) => {
+                    tracing::error!("invalid web view event, {e}");
+                    return;
+                }
+            };
+            match event {
+                WebViewEvent::Navigate(u) => {
+                    *last_url.lock() = u.clone();
+                    let _ = url.set(u.clone());
+                    WEBVIEW_NAVIGATE_EVENT.notify(WebViewNavigateArgs::now(WIDGET.info().path(), u));
+                }
+                WebViewEvent::Load(u) => {
+                    WEBVIEW_LOAD_EVENT.notify(WebViewLoadArgs::now(WIDGET.info().path(), u));
+                }
+                WebViewEvent::TitleChanged(t) => {
+                    WEBVIEW_TITLE_CHANGED_EVENT.notify(WebViewTitleChangedArgs::now(WIDGET.info().path(), t));
+                }
+                WebViewEvent::Eval { id, result } => {
+                    let mut evals = evals.lock();
+                    if let Some(i) = evals.iter().position(|(i, _)| *i == id) {
+                        let (_, r) = evals.swap_remove(i);
+                        r.respond(result);
+                    }
+                }
+            }
+        }),
+    );
+
+    match_node(child, move |c, op| match op {
+        UiNodeOp::Init => {
+            WIDGET.sub_var(&url).sub_var(&html);
+        }
+        UiNodeOp::Deinit => {
+            c.deinit();
+            for (_, r) in evals.lock().drain(..) {
+                r.respond(Err(Txt::from_static("web view closed")));
+            }
+        }
+        UiNodeOp::Update { updates } => {
+            c.update(updates);
+
+            if let Some(u) = url.get_new() {
+                let mut last_url = last_url.lock();
+                if *last_url != u {
+                    *last_url = u.clone();
+                    command(WebViewCommand::Navigate(u));
+                }
+            }
+            if let Some(h) = html.get_new() {
+                if !h.is_empty() {
+                    command(WebViewCommand::LoadHtml(h));
+                }
+            }
+
+            let id = WIDGET.id();
+            let requests = {
+                let mut sv = WEBVIEW_SV.write();
+                let (requests, others): (Vec<_>, Vec<_>) = mem::take(&mut sv.requests).into_iter().partition(|(i, _)| *i == id);
+                sv.requests = others;
+                requests
+            };
+            for (_, request) in requests {
+                match request {
+                    WebViewRequest::Eval(script, responder) => {
+                        let id = next_eval;
+                        next_eval = next_eval.wrapping_add(1);
+                        evals.lock().push((id, responder.clone()));
+                        if !command(WebViewCommand::Eval { id, script }) {
+                            evals.lock().retain(|(i, _)| *i != id);
+                            responder.respond(Err(Txt::from_static("web view not open")));
+                        }
+                    }
+                    WebViewRequest::Reload => {
+                        command(WebViewCommand::Reload);
+                    }
+                }
+            }
+        }
+        _ => {}
+    })
+}
@@ -0,0 +1,258 @@
+//! View-process web view host.
+//!
+//! The host is registered automatically in the view-process when this module is compiled, it implements
+//! the [`WEBVIEW_HOST`] using [`wry`].
+//!
+//! The host is only implemented on Windows and macOS, on Linux and BSD the host is registered but fails to open with
+//! a not supported error, the `wry` WebKitGTK backend requires GTK to be initialized and pumped by the event loop and
+//! the view-process does not run a GTK main context.
+//!
+//! [`wry`]: https://docs.rs/wry
+
+use std::path::PathBuf;
+
+#[cfg(any(windows, target_os = "macos"))]
+use zng_view::native_child::NativeChildEventSender;
+use zng_view::native_child::{NativeChildBoundsArgs, NativeChildHost, NativeChildOpenArgs};
+#[cfg(any(windows, target_os = "macos"))]
+use zng_view_api::api_extension::ApiExtensionPayload;
+#[cfg(any(windows, target_os = "macos"))]
+use zng_wgt::prelude::Txt;
+
+use crate::api::*;
+
+zng_view::view_process_extension!(|exts| {
+    exts.native_child(WEBVIEW_HOST, WryHost::default);
+});
+
+/// Gets the session data directory.
+///
+/// Returns an error if the named session is not a valid file name.
+fn session_dir(session: &WebViewSession) -> Result<Option<PathBuf>, String> {
+    match session {
+        WebViewSession::Shared => Ok(Some(zng_env::cache("zng-wgt-webview/shared"))),
+        WebViewSession::Named(n) => {
+            if !is_valid_session_name(n) {
+                return Err(format!("invalid webview session name {n:?}"));
+            }
+            Ok(Some(zng_env::cache("zng-wgt-webview/named").join(n.as_str())))
+        }
+        WebViewSession::Incognito => Ok(None),
+    }
+}
+
+fn is_valid_session_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+#[derive(Default)]
+struct WryHost {}
+#[cfg(not(any(windows, target_os = "macos")))]
+impl NativeChildHost for WryHost {
+    fn open(&mut self, args: &mut NativeChildOpenArgs) -> Result<(), String> {
+        let open = args.payload.deserialize::<WebViewOpen>().map_err(|e| e.to_string())?;
+        session_dir(&open.session)?;
+        Err("webview not supported on this platform, the default host only implements Windows and macOS".into())
+    }
+
+    fn set_bounds(&mut self, args: &mut NativeChildBoundsArgs) {
+        let _ = args;
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        let _ = visible;
+    }
+}
+
+/// Script injected in every page to notify focus.
+#[cfg(any(windows, target_os = "macos"))]
+const FOCUS_SCRIPT: &str = "window.addEventListener('focus', () => window.ipc.postMessage('zng-wgt-webview.focus'));";
+
+#[cfg(any(windows, target_os = "macos"))]
+#[derive(Default)]
+struct WryHost {
+    // webview must drop before the context.
+    webview: Option<wry::WebView>,
+    context: Option<wry::WebContext>,
+    events: Option<NativeChildEventSender>,
+    visible: bool,
+    clipped: bool,
+}
+#[cfg(any(windows, target_os = "macos"))]
+impl WryHost {
+    fn rect(args: &NativeChildBoundsArgs) -> wry::Rect {
+        wry::Rect {
+            position: wry::dpi::PhysicalPosition::new(args.bounds.origin.x.0, args.bounds.origin.y.0).into(),
+            size: wry::dpi::PhysicalSize::new(args.bounds.size.width.0.max(1) as u32, args.bounds.size.height.0.max(1) as u32).into(),
+        }
+    }
+
+    fn send(events: &NativeChildEventSender, event: WebViewEvent) -> Result<(), String> {
+        let payload = ApiExtensionPayload::serialize(&event).map_err(|e| format!("cannot serialize webview event, {e}"))?;
+        events.custom(payload);
+        Ok(())
+    }
+
+    fn send_or_log(events: &NativeChildEventSender, event: WebViewEvent) {
+        if let Err(e) = Self::send(events, event) {
+            tracing::error!("{e}");
+        }
+    }
+
+    fn update_visible(&self) {
+        if let Some(w) = &self.webview {
+            if let Err(e) = w.set_visible(self.visible && !self.clipped) {
+                tracing::error!("webview set_visible error, {e}");
+            }
+        }
+    }
+}
+#[cfg(any(windows, target_os = "macos"))]
+impl NativeChildHost for WryHost {
+    fn open(&mut self, args: &mut NativeChildOpenArgs) -> Result<(), String> {
+        let open = args.payload.deserialize::<WebViewOpen>().map_err(|e| e.to_string())?;
+
+        let mut context = wry::WebContext::new(session_dir(&open.session)?);
+
+        let mut builder = wry::WebViewBuilder::with_web_context(&mut context)
+            .with_incognito(matches!(open.session, WebViewSession::Incognito))
+            .with_devtools(open.devtools)
+            .with_bounds(Self::rect(&args.bounds))
+            .with_visible(args.visible)
+            .with_initialization_script(FOCUS_SCRIPT);
+
+        if open.html.is_empty() {
+            builder = builder.with_url(open.url.as_str());
+        } else {
+            builder = builder.with_html(open.html.as_str());
+        }
+
+        let events = args.events.clone();
+        builder = builder.with_ipc_handler(move |r| {
+            if r.body() == "zng-wgt-webview.focus" {
+                events.focused();
+            }
+        });
+        let events = args.events.clone();
+        builder = builder.with_navigation_handler(move |url| {
+            Self::send_or_log(&events, WebViewEvent::Navigate(Txt::from_str(&url)));
+            true
+        });
+        let events = args.events.clone();
+        builder = builder.with_on_page_load_handler(move |event, url| {
+            if let wry::PageLoadEvent::Finished = event {
+                Self::send_or_log(&events, WebViewEvent::Load(Txt::from_str(&url)));
+            }
+        });
+        let events = args.events.clone();
+        builder = builder.with_document_title_changed_handler(move |title| {
+            Self::send_or_log(&events, WebViewEvent::TitleChanged(Txt::from_str(&title)));
+        });
+
+        let webview = builder.build_as_child(args.window).map_err(|e| e.to_string())?;
+
+        self.webview = Some(webview);
+        self.context = Some(context);
+        self.events = Some(args.events.clone());
+        self.visible = args.visible;
+        self.clipped = args.bounds.clip.is_empty();
+        self.update_visible();
+        Ok(())
+    }
+
+    fn set_bounds(&mut self, args: &mut NativeChildBoundsArgs) {
+        if let Some(w) = &self.webview {
+            if let Err(e) = w.set_bounds(Self::rect(args)) {
+                tracing::error!("webview set_bounds error, {e}");
+            }
+        }
+        // wry does not support clipping, hide when fully clipped.
+        let clipped = args.clip.is_empty();
+        if self.clipped != clipped {
+            self.clipped = clipped;
+            self.update_visible();
+        }
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.update_visible();
+        }
+    }
+
+    fn focus(&mut self) {
+        if let Some(w) = &self.webview {
+            if let Err(e) = w.focus() {
+                tracing::error!("webview focus error, {e}");
+            }
+        }
+    }
+
+    fn command(&mut self, request: ApiExtensionPayload) -> ApiExtensionPayload {
+        let (webview, events) = match (&self.webview, &self.events) {
+            (Some(w), Some(e)) => (w, e),
+            _ => return ApiExtensionPayload::empty(),
+        };
+        let command = match request.deserialize::<WebViewCommand>() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!("invalid webview command, {e}");
+                return ApiExtensionPayload::empty();
+            }
+        };
+        let r = match command {
+            WebViewCommand::Navigate(url) => webview.load_url(url.as_str()),
+            WebViewCommand::LoadHtml(html) => webview.load_html(html.as_str()),
+            WebViewCommand::Reload => webview.reload(),
+            WebViewCommand::Eval { id, script } => {
+                let events = events.clone();
+                let r = webview.evaluate_script_with_callback(script.as_str(), move |json| {
+                    Self::send_or_log(
+                        &events,
+                        WebViewEvent::Eval {
+                            id,
+                            result: Ok(Txt::from_str(&json)),
+                        },
+                    );
+                });
+                if let Err(e) = &r {
+                    Self::send_or_log(
+                        events,
+                        WebViewEvent::Eval {
+                            id,
+                            result: Err(Txt::from_str(&e.to_string())),
+                        },
+                    );
+                }
+                r
+            }
+        };
+        if let Err(e) = r {
+            tracing::error!("webview command error, {e}");
+        }
+        ApiExtensionPayload::empty()
+    }
+
+    fn close(&mut self) {
+        self.webview = None;
+        self.context = None;
+        self.events = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_name_validation() {
+        for valid in ["user-1", "main_session", "v1.2"] {
+            assert!(is_valid_session_name(valid), "{valid}");
+        }
+        for invalid in ["", ".", "..", ".hidden", "../escape", "a/b", "a\\b", "C:", "a b"] {
+            assert!(!is_valid_session_name(invalid), "{invalid}");
+        }
+    }
+}
@@ -159,6 +159,15 @@ event_property! {
         filter: |args| args.window_id == WINDOW.id(),
     }
 
+    /// On window frame presented.
+    ///
+    /// Note that this event only notifies if [`WindowVars::present_notify`] is enabled.
+    pub fn frame_presented {
+        event: FRAME_PRESENTED_EVENT,
+        args: FramePresentedArgs,
+        filter: |args| args.window_id == WINDOW.id(),
+    }
+
     /// On Input Method Editor event.
     pub fn ime {
         event: IME_EVENT,
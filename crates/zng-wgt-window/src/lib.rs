@@ -11,8 +11,8 @@
 
 use zng_ext_input::focus::{DirectionalNav, FocusScopeOnFocus, TabNav};
 use zng_ext_window::{
-    FrameImageReadyArgs, HeadlessMonitor, RenderMode, StartPosition, WindowChangedArgs, WindowCloseArgs, WindowCloseRequestedArgs,
-    WindowOpenArgs, WindowRoot,
+    FrameImageReadyArgs, FramePresentedArgs, HeadlessMonitor, RenderMode, StartPosition, WINDOW_Ext as _, WindowChangedArgs,
+    WindowCloseArgs, WindowCloseRequestedArgs, WindowOpenArgs, WindowRoot,
 };
 use zng_wgt::prelude::*;
 use zng_wgt_fill::background_color;
@@ -22,12 +22,15 @@ use zng_wgt_input::focus::{
 use zng_wgt_text::{font_color, lang, FONT_SIZE_VAR};
 
 pub mod events;
+mod splash;
 mod window_properties;
 
 #[allow(clippy::useless_attribute)] // not useless
 #[allow(ambiguous_glob_reexports)] // we override `font_size`.
 pub use self::window_properties::*;
 
+pub use self::splash::*;
+
 /// A window container.
 ///
 /// The instance type is [`WindowRoot`], it can be given to the [`WINDOWS`](zng_ext_window::WINDOWS) service
@@ -53,14 +56,14 @@ impl Window {
             // set layout direction.
             lang = zng_ext_l10n::LANG_VAR;
 
-            font_color = light_dark(rgb(0.08, 0.08, 0.08), rgb(0.92, 0.92, 0.92));
-            background_color = light_dark(rgb(0.9, 0.9, 0.9), rgb(0.1, 0.1, 0.1));
+            font_color = theme::ON_BACKGROUND_COLOR_VAR.rgba();
+            background_color = theme::BACKGROUND_COLOR_VAR.rgba();
             focus_highlight = {
                 offsets: FOCUS_HIGHLIGHT_OFFSETS_VAR,
                 widths: FOCUS_HIGHLIGHT_WIDTHS_VAR,
                 sides: light_dark(colors::BLACK, rgb(200, 200, 200)).rgba_map(BorderSides::dashed),
             };
-            clear_color = light_dark(rgb(0.9, 0.9, 0.9), rgb(0.1, 0.1, 0.1));
+            clear_color = theme::BACKGROUND_COLOR_VAR.rgba();
             focus_scope = true;
             tab_nav = TabNav::Cycle;
             directional_nav = DirectionalNav::Cycle;
@@ -325,6 +328,22 @@ pub fn on_frame_image_ready(child: impl UiNode, handler: impl WidgetHandler<Fram
     events::on_pre_frame_image_ready(child, handler)
 }
 
+/// On window frame presented.
+///
+/// The event args include the presentation timestamp and an estimate of the display refresh interval. This
+/// property enables [`present_notify`] on init.
+///
+/// [`present_notify`]: zng_ext_window::WindowVars::present_notify
+#[property(EVENT, widget_impl(Window))]
+pub fn on_frame_presented(child: impl UiNode, handler: impl WidgetHandler<FramePresentedArgs>) -> impl UiNode {
+    let child = match_node(child, |_, op| {
+        if let UiNodeOp::Init = op {
+            WINDOW.vars().present_notify().set(true);
+        }
+    });
+    events::on_pre_frame_presented(child, handler)
+}
+
 /// Imaginary monitor used by the window when it runs in [headless mode](zng_app::window::WindowMode::is_headless).
 #[property(LAYOUT, capture, widget_impl(Window))]
 pub fn headless_monitor(monitor: impl IntoValue<HeadlessMonitor>) {}
@@ -0,0 +1,286 @@
+//! Splash screen properties and nodes.
+
+use std::time::Duration;
+
+use zng_var::animation::{easing, AnimationHandle};
+use zng_wgt::prelude::*;
+
+use crate::Window;
+
+context_var! {
+    /// Loading progress presented by the splash screen.
+    pub static SPLASH_PROGRESS_VAR: Factor = 0.fct();
+
+    /// Loading status message presented by the splash screen.
+    pub static SPLASH_MESSAGE_VAR: Txt = "";
+
+    /// If the window content is ready to be revealed.
+    pub static SPLASH_READY_VAR: bool = true;
+
+    /// Duration of the cross-fade from the splash screen to the window content.
+    pub static SPLASH_FADE_VAR: Duration = 300.ms();
+}
+
+/// Arguments for the [`splash_fn`] widget function.
+///
+/// [`splash_fn`]: fn@splash_fn
+#[derive(Clone)]
+pub struct SplashArgs {
+    /// Loading progress, see [`splash_progress`] for more details.
+    ///
+    /// [`splash_progress`]: fn@splash_progress
+    pub progress: BoxedVar<Factor>,
+    /// Loading status message, see [`splash_message`] for more details.
+    ///
+    /// [`splash_message`]: fn@splash_message
+    pub message: BoxedVar<Txt>,
+}
+
+/// Splash screen presented while the window content loads.
+///
+/// When set the window first inits and renders only the splash screen, the window content is only inited in the
+/// update after this first frame is rendered, so the window opens showing the splash screen instead of a blank
+/// background even if the content init is heavy. After the content is inited it is laid out, but not rendered, until
+/// [`splash_ready`] is `true`, then the content is revealed by a cross-fade with duration defined by [`splash_fade`]
+/// and the splash screen is deinited.
+///
+/// The splash screen should be lightweight, a logo and a progress indicator that reads [`SplashArgs::progress`].
+/// The content can do the heavy loading asynchronously and report progress using [`splash_progress`] and [`splash_message`].
+///
+/// The widget function is called only once when the window content inits, by default no splash screen is shown.
+///
+/// [`splash_ready`]: fn@splash_ready
+/// [`splash_fade`]: fn@splash_fade
+/// [`splash_progress`]: fn@splash_progress
+/// [`splash_message`]: fn@splash_message
+#[property(CHILD_CONTEXT, default(WidgetFn::nil()), widget_impl(Window))]
+pub fn splash_fn(child: impl UiNode, splash_fn: impl IntoVar<WidgetFn<SplashArgs>>) -> impl UiNode {
+    #[derive(Clone, Copy)]
+    enum State {
+        /// Only the content is inited, splash disabled or finished.
+        Content,
+        /// Only the splash is inited, awaiting first render.
+        Splash { rendered: bool },
+        /// Content is inited but not rendered, awaiting ready.
+        Loading,
+        /// Content is inited and fading in.
+        Reveal,
+    }
+
+    let splash_fn = splash_fn.into_var();
+    let mut splash: Option<BoxedUiNode> = None;
+    let mut state = State::Content;
+    let mut fade = var(0.fct());
+    let mut _fade_anim = AnimationHandle::dummy();
+    let content_key = FrameValueKey::new_unique();
+    let splash_key = FrameValueKey::new_unique();
+
+    match_node(child, move |c, op| {
+        let mut finish = false;
+        match op {
+            UiNodeOp::Init => {
+                WIDGET.sub_var(&SPLASH_READY_VAR);
+
+                if let Some(mut s) = splash_fn.get().call_checked(SplashArgs {
+                    progress: SPLASH_PROGRESS_VAR.boxed(),
+                    message: SPLASH_MESSAGE_VAR.boxed(),
+                }) {
+                    fade = var(0.fct());
+                    WIDGET.sub_var(&fade);
+
+                    s.init();
+                    splash = Some(s);
+                    state = State::Splash { rendered: false };
+                    c.delegated();
+                }
+            }
+            UiNodeOp::Deinit => {
+                if let Some(mut s) = splash.take() {
+                    s.deinit();
+                    if let State::Splash { .. } = state {
+                        c.delegated();
+                    }
+                }
+                _fade_anim = AnimationHandle::dummy();
+                state = State::Content;
+            }
+            UiNodeOp::Info { info } => {
+                if let Some(s) = &mut splash {
+                    s.info(info);
+                    if let State::Splash { .. } = state {
+                        c.delegated();
+                    }
+                }
+            }
+            UiNodeOp::Event { update } => {
+                if let Some(s) = &mut splash {
+                    s.event(update);
+                    if let State::Splash { .. } = state {
+                        c.delegated();
+                    }
+                }
+            }
+            UiNodeOp::Update { updates } => {
+                if let Some(s) = &mut splash {
+                    s.update(updates);
+                    match state {
+                        State::Splash { rendered } => {
+                            c.delegated();
+                            if rendered {
+                                // first frame with only the splash screen was rendered, now init the content.
+                                c.child().init();
+                                state = State::Loading;
+                                WIDGET.update_info().layout().render().update();
+                            }
+                        }
+                        State::Loading => {
+                            c.update(updates);
+                            if SPLASH_READY_VAR.get() {
+                                let duration = SPLASH_FADE_VAR.get();
+                                if duration == Duration::ZERO {
+                                    finish = true;
+                                } else {
+                                    state = State::Reveal;
+                                    _fade_anim = fade.ease(1.fct(), duration, easing::linear);
+                                    WIDGET.render();
+                                }
+                            }
+                        }
+                        State::Reveal => {
+                            if fade.is_new() {
+                                if fade.get() >= 1.fct() {
+                                    finish = true;
+                                } else {
+                                    WIDGET.render_update();
+                                }
+                            }
+                        }
+                        State::Content => {}
+                    }
+                }
+            }
+            UiNodeOp::Measure { wm, desired_size } => {
+                if let Some(s) = &mut splash {
+                    let s_size = s.measure(wm);
+                    if let State::Splash { .. } = state {
+                        c.delegated();
+                        *desired_size = s_size;
+                    } else {
+                        *desired_size = c.measure(wm).max(s_size);
+                    }
+                }
+            }
+            UiNodeOp::Layout { wl, final_size } => {
+                if let Some(s) = &mut splash {
+                    let s_size = s.layout(wl);
+                    if let State::Splash { .. } = state {
+                        c.delegated();
+                        *final_size = s_size;
+                    } else {
+                        *final_size = c.layout(wl).max(s_size);
+                    }
+                }
+            }
+            UiNodeOp::Render { frame } => {
+                if let Some(s) = &mut splash {
+                    match state {
+                        State::Splash { rendered } => {
+                            c.delegated();
+                            s.render(frame);
+                            if !rendered {
+                                state = State::Splash { rendered: true };
+                                WIDGET.update();
+                            }
+                        }
+                        State::Loading => {
+                            frame.hide(|frame| c.render(frame));
+                            s.render(frame);
+                        }
+                        State::Reveal => {
+                            frame.push_opacity(content_key.bind_var(&fade, |f| f.0), |frame| c.render(frame));
+                            frame.push_opacity(splash_key.bind_var(&fade, |f| 1.0 - f.0), |frame| s.render(frame));
+                        }
+                        State::Content => {}
+                    }
+                }
+            }
+            UiNodeOp::RenderUpdate { update } => {
+                if let Some(s) = &mut splash {
+                    match state {
+                        State::Splash { .. } => {
+                            c.delegated();
+                            s.render_update(update);
+                        }
+                        State::Loading => {
+                            c.render_update(update);
+                            s.render_update(update);
+                        }
+                        State::Reveal => {
+                            update.update_f32_opt(content_key.update_var(&fade, |f| f.0));
+                            update.update_f32_opt(splash_key.update_var(&fade, |f| 1.0 - f.0));
+                            c.render_update(update);
+                            s.render_update(update);
+                        }
+                        State::Content => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if finish {
+            if let Some(mut s) = splash.take() {
+                s.deinit();
+            }
+            _fade_anim = AnimationHandle::dummy();
+            state = State::Content;
+            WIDGET.update_info().layout().render();
+        }
+    })
+}
+
+/// Loading progress presented by the [`splash_fn`].
+///
+/// This property sets the [`SPLASH_PROGRESS_VAR`].
+///
+/// [`splash_fn`]: fn@splash_fn
+#[property(CONTEXT, default(SPLASH_PROGRESS_VAR), widget_impl(Window))]
+pub fn splash_progress(child: impl UiNode, progress: impl IntoVar<Factor>) -> impl UiNode {
+    with_context_var(child, SPLASH_PROGRESS_VAR, progress)
+}
+
+/// Loading status message presented by the [`splash_fn`].
+///
+/// This property sets the [`SPLASH_MESSAGE_VAR`].
+///
+/// [`splash_fn`]: fn@splash_fn
+#[property(CONTEXT, default(SPLASH_MESSAGE_VAR), widget_impl(Window))]
+pub fn splash_message(child: impl UiNode, message: impl IntoVar<Txt>) -> impl UiNode {
+    with_context_var(child, SPLASH_MESSAGE_VAR, message)
+}
+
+/// If the window content is ready to be revealed.
+///
+/// The [`splash_fn`] is presented until this is `true` after the content inits. Set this to a variable that is
+/// only `true` after the content finishes loading asynchronously. Is `true` by default, the content is revealed right
+/// after it inits.
+///
+/// This property sets the [`SPLASH_READY_VAR`].
+///
+/// [`splash_fn`]: fn@splash_fn
+#[property(CONTEXT, default(SPLASH_READY_VAR), widget_impl(Window))]
+pub fn splash_ready(child: impl UiNode, ready: impl IntoVar<bool>) -> impl UiNode {
+    with_context_var(child, SPLASH_READY_VAR, ready)
+}
+
+/// Duration of the cross-fade from the [`splash_fn`] to the window content.
+///
+/// Is `300.ms()` by default, if set to zero the content is revealed immediately.
+///
+/// This property sets the [`SPLASH_FADE_VAR`].
+///
+/// [`splash_fn`]: fn@splash_fn
+#[property(CONTEXT, default(SPLASH_FADE_VAR), widget_impl(Window))]
+pub fn splash_fade(child: impl UiNode, duration: impl IntoVar<Duration>) -> impl UiNode {
+    with_context_var(child, SPLASH_FADE_VAR, duration)
+}
@@ -1,9 +1,10 @@
 use std::time::Duration;
 
+use zng_color::theme::THEME;
 use zng_ext_config::{AnyConfig as _, ConfigKey, ConfigStatus, ConfigValue, CONFIG};
 use zng_ext_window::{
-    AutoSize, FrameCaptureMode, MonitorQuery, WINDOW_Ext as _, WindowButton, WindowIcon, WindowLoadingHandle, WindowState, WindowVars,
-    MONITORS, WINDOW_LOAD_EVENT,
+    AppMenu, AutoSize, FrameCaptureMode, MonitorQuery, SystemMenu, WINDOW_Ext as _, WindowButton, WindowIcon, WindowLayer,
+    WindowLoadingHandle, WindowState, WindowVars, MONITORS, WINDOW_LOAD_EVENT,
 };
 use zng_wgt::prelude::*;
 
@@ -81,7 +82,7 @@ set_properties! {
     resizable: bool,
     movable: bool,
 
-    always_on_top: bool,
+    layer: WindowLayer,
 
     visible: bool,
     taskbar_visible: bool,
@@ -95,6 +96,10 @@ set_properties! {
     frame_capture_mode: FrameCaptureMode,
 
     enabled_buttons: WindowButton,
+
+    system_menu: SystemMenu,
+
+    app_menu: AppMenu,
 }
 
 macro_rules! map_properties {
@@ -311,16 +316,24 @@ pub fn save_state_node<S: ConfigValue>(
 /// If enabled a config entry is created for the window state in [`CONFIG`], and if a config backend is set
 /// the window state is persisted on change and restored when the app reopens.
 ///
+/// The state includes the window state, the normal position and size and the monitor. On restore the window
+/// is moved to the same monitor if it is still available and the normal position and size are fitted inside
+/// the monitor, so the window is visible even if the monitor geometry changed. A minimized window restores in its
+/// previous [`restore_state`].
+///
 /// This property is enabled by default in the `Window!` widget, without a key. Note that without a config key
 /// the state only actually enables if the window root widget ID or the window ID have a name.
 ///
 /// [`CONFIG`]: zng_ext_config::CONFIG
+/// [`restore_state`]: zng_ext_window::WindowVars::restore_state
 #[property(CONTEXT, default(SaveState::Disabled), widget_impl(Window))]
 pub fn save_state(child: impl UiNode, enabled: impl IntoValue<SaveState>) -> impl UiNode {
     #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
     struct WindowStateCfg {
         state: WindowState,
         restore_rect: euclid::Rect<f32, Dip>,
+        #[serde(default)]
+        monitor: Option<Txt>,
     }
     save_state_node::<WindowStateCfg>(
         child,
@@ -328,18 +341,28 @@ pub fn save_state(child: impl UiNode, enabled: impl IntoValue<SaveState>) -> imp
         |cfg| {
             let vars = WINDOW.vars();
             let state = vars.state();
-            WIDGET.sub_var(&state).sub_var(&vars.restore_rect());
+            WIDGET.sub_var(&state).sub_var(&vars.restore_rect()).sub_var(&vars.actual_monitor());
 
             if let Some(cfg) = cfg {
-                // restore state
-                state.set(cfg.state);
+                // restore monitor if it is still available
+                let monitor = cfg.monitor.and_then(|name| {
+                    let m = MONITORS.available_monitors().into_iter().find(|m| m.name().get() == name)?;
+                    vars.monitor().set(MonitorQuery::new(move || {
+                        MONITORS.available_monitors().into_iter().find(|m| m.name().get() == name)
+                    }));
+                    Some(m)
+                });
+                let monitor = monitor.unwrap_or_else(|| vars.monitor().get().select_fallback());
 
-                // restore normal position if it is valid (visible in a monitor)
-                let restore_rect: DipRect = cfg.restore_rect.cast();
-                let visible = MONITORS.available_monitors().iter().any(|m| m.dip_rect().intersects(&restore_rect));
-                if visible {
-                    vars.position().set(restore_rect.origin);
-                }
+                // restore state
+                state.set(match cfg.state {
+                    WindowState::Minimized => WindowState::Normal,
+                    s => s,
+                });
+
+                // restore normal position and size, fitted in the monitor
+                let restore_rect = fit_rect(cfg.restore_rect.cast(), monitor.dip_rect().size);
+                vars.position().set(restore_rect.origin);
                 vars.size().set(restore_rect.size);
             }
         },
@@ -347,10 +370,15 @@ pub fn save_state(child: impl UiNode, enabled: impl IntoValue<SaveState>) -> imp
             let vars = WINDOW.vars();
             let state = vars.state();
             let rect = vars.restore_rect();
-            if required || state.is_new() || rect.is_new() {
+            let monitor = vars.actual_monitor();
+            if required || state.is_new() || rect.is_new() || monitor.is_new() {
                 Some(WindowStateCfg {
-                    state: state.get(),
+                    state: match state.get() {
+                        WindowState::Minimized => vars.restore_state().get(),
+                        s => s,
+                    },
                     restore_rect: rect.get().cast(),
+                    monitor: monitor.get().and_then(|id| MONITORS.monitor(id)).map(|m| m.name().get()),
                 })
             } else {
                 None
@@ -359,6 +387,46 @@ pub fn save_state(child: impl UiNode, enabled: impl IntoValue<SaveState>) -> imp
     )
 }
 
+/// Save and restore the user theme overrides.
+///
+/// If enabled a config entry is created for the [`THEME.overrides`] in [`CONFIG`], and if a config backend is set
+/// the overrides are persisted on change and restored when the app reopens. If enabled without a key the `"theme"`
+/// key is used.
+///
+/// Note that the overrides are app wide, usually this property is only set in the main window.
+///
+/// [`CONFIG`]: zng_ext_config::CONFIG
+/// [`THEME.overrides`]: zng_color::theme::THEME::overrides
+#[property(CONTEXT, default(SaveState::Disabled), widget_impl(Window))]
+pub fn save_theme(child: impl UiNode, enabled: impl IntoValue<SaveState>) -> impl UiNode {
+    let enabled = enabled.into();
+    let mut binding = VarHandles::dummy();
+    match_node(child, move |_, op| match op {
+        UiNodeOp::Init => {
+            if let SaveState::Enabled { key } = &enabled {
+                let key = key.clone().unwrap_or_else(|| Txt::from_static("theme"));
+                let overrides = THEME.overrides();
+                let cfg = CONFIG.get(key, overrides.get());
+                overrides.set_from(&cfg);
+                binding = cfg.bind_bidi(&overrides);
+            }
+        }
+        UiNodeOp::Deinit => {
+            binding.clear();
+        }
+        _ => {}
+    })
+}
+
+/// Fit the window `rect` in a monitor of `size`, the position is relative to the monitor.
+fn fit_rect(mut rect: DipRect, size: DipSize) -> DipRect {
+    rect.size.width = rect.size.width.min(size.width);
+    rect.size.height = rect.size.height.min(size.height);
+    rect.origin.x = rect.origin.x.min(size.width - rect.size.width).max(Dip::new(0));
+    rect.origin.y = rect.origin.y.min(size.height - rect.size.height).max(Dip::new(0));
+    rect
+}
+
 /// Defines if a widget load affects the parent window load.
 ///
 /// Widgets that support this behavior have a `block_window_load` property.
@@ -1,5 +1,6 @@
 use zng_color::{
     colors::{ACCENT_COLOR_VAR, BASE_COLOR_VAR},
+    theme::{Theme, THEME_VAR},
     COLOR_SCHEME_VAR,
 };
 
@@ -30,3 +31,16 @@ pub fn accent_color(child: impl UiNode, color: impl IntoVar<LightDark>) -> impl
 pub fn base_color(child: impl UiNode, color: impl IntoVar<LightDark>) -> impl UiNode {
     with_context_var(child, BASE_COLOR_VAR, color)
 }
+
+/// Defines the theme colors in the widget and descendants.
+///
+/// Widget styles select colors from the theme role variables, like [`SURFACE_COLOR_VAR`], so this property
+/// re-skins all descendant widgets.
+///
+/// Sets the [`THEME_VAR`].
+///
+/// [`SURFACE_COLOR_VAR`]: zng_color::theme::SURFACE_COLOR_VAR
+#[property(CONTEXT, default(THEME_VAR))]
+pub fn color_theme(child: impl UiNode, theme: impl IntoVar<Theme>) -> impl UiNode {
+    with_context_var(child, THEME_VAR, theme)
+}
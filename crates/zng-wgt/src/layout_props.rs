@@ -10,7 +10,7 @@ use crate::prelude::*;
 /// layout properties of the widget to define the inner visual position and widget size.
 ///
 /// This property disables inline layout for the widget.
-#[property(LAYOUT, default(0))]
+#[property(LAYOUT, animatable, default(0))]
 pub fn margin(child: impl UiNode, margin: impl IntoVar<SideOffsets>) -> impl UiNode {
     let margin = margin.into_var();
     match_node(child, move |child, op| match op {
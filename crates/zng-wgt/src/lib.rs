@@ -89,7 +89,7 @@ mod __prelude {
     pub use zng_unique_id::{IdEntry, IdMap, IdSet};
 
     pub use zng_color::{
-        colors, gradient, hex, hsl, hsla, hsv, hsva, light_dark, rgb, rgba, web_colors, ColorScheme, Hsla, Hsva, LightDark,
+        colors, gradient, hex, hsl, hsla, hsv, hsva, light_dark, rgb, rgba, theme, web_colors, ColorScheme, Hsla, Hsva, LightDark,
         LightDarkVarExt as _, MixAdjust as _, MixBlendMode, Rgba,
     };
 
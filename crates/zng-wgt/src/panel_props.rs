@@ -1,4 +1,4 @@
-use zng_app::widget::node::Z_INDEX;
+use zng_app::widget::node::{PanelTransition, PANEL_TRANSITION_VAR, Z_INDEX};
 
 use crate::prelude::*;
 
@@ -38,3 +38,18 @@ pub fn z_index(child: impl UiNode, index: impl IntoVar<ZIndex>) -> impl UiNode {
         _ => {}
     })
 }
+
+/// Defines the layout transition of panel items in the widget and descendants.
+///
+/// When enabled the children of panels like `Stack!` and `Wrap!` animate from the previous layout to the new layout
+/// when children are inserted, removed or moved, inserted children fade-in. Set this property in the panel widget, note
+/// that nested panels also inherit the transition, set it to `false` in the nested panels to disable.
+///
+/// This property sets the [`PANEL_TRANSITION_VAR`], see [`PanelTransition`] for more details.
+///
+/// [`PANEL_TRANSITION_VAR`]: zng_app::widget::node::PANEL_TRANSITION_VAR
+/// [`PanelTransition`]: zng_app::widget::node::PanelTransition
+#[property(CONTEXT, default(PANEL_TRANSITION_VAR))]
+pub fn panel_transition(child: impl UiNode, transition: impl IntoVar<PanelTransition>) -> impl UiNode {
+    with_context_var(child, PANEL_TRANSITION_VAR, transition)
+}
@@ -1,4 +1,4 @@
-use zng_app::widget::base::{Parallel, PARALLEL_VAR};
+use zng_app::widget::base::{Parallel, PARALLEL_MIN_CHUNK_VAR, PARALLEL_VAR};
 
 use crate::prelude::*;
 
@@ -15,3 +15,15 @@ use crate::prelude::*;
 pub fn parallel(child: impl UiNode, enabled: impl IntoVar<Parallel>) -> impl UiNode {
     with_context_var(child, PARALLEL_VAR, enabled)
 }
+
+/// Defines the minimum number of nodes processed by each parallel task in node lists of the widget and descendants.
+///
+/// This property sets the [`PARALLEL_MIN_CHUNK_VAR`], increase it for very wide lists of cheap widgets, where
+/// the overhead of splitting the work between threads is more than the work. Lists with less than twice this number
+/// of nodes are processed in a single task.
+///
+/// [`PARALLEL_MIN_CHUNK_VAR`]: zng_app::widget::base::PARALLEL_MIN_CHUNK_VAR
+#[property(CONTEXT, default(PARALLEL_MIN_CHUNK_VAR))]
+pub fn parallel_min_chunk(child: impl UiNode, min_len: impl IntoVar<usize>) -> impl UiNode {
+    with_context_var(child, PARALLEL_MIN_CHUNK_VAR, min_len)
+}
@@ -14,10 +14,31 @@
 //!
 //! The example above renders <code style="color:green;font-weight:bold;">GREEN&BOLD</code>.
 //!
+//! # Streams
+//!
+//! Live output like logs can be shown using [`txt_stream`], new lines are appended incrementally and the
+//! scrollback can be capped. The [`ansi_txt_stream`] function creates a stream fed by a channel.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! let (sender, receiver) = task::channel::unbounded();
+//! # let _ =
+//! zng::ansi_text::AnsiText! {
+//!     txt_stream = zng::ansi_text::ansi_txt_stream(receiver, 10_000);
+//! }
+//! # ;
+//! sender.send(Txt::from("\x1b[32mINFO\x1b[0m started\n")).unwrap();
+//! ```
+//!
+//! [`txt_stream`]: fn@txt_stream
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_ansi_text`] for the full widget API.
 
 pub use zng_wgt_ansi_text::{
-    AnsiColor, AnsiStyle, AnsiText, AnsiTextParser, AnsiTxt, AnsiWeight, LineFnArgs, PageFnArgs, PanelFnArgs, TextFnArgs,
+    ansi_txt_stream, txt_stream, AnsiColor, AnsiStyle, AnsiText, AnsiTextParser, AnsiTxt, AnsiWeight, LineFnArgs, PageFnArgs, PanelFnArgs,
+    TextFnArgs,
 };
@@ -333,7 +333,7 @@
 //!     6. Calls [`AppExtension::update`] if any update was requested.
 //!     7. Calls [`UPDATES.on_update`] handlers if needed.
 //! 3. The loop repeats immediately if any info rebuild or update was requested by update callbacks.
-//!     * The loops breaks if it repeats over 1000 times.
+//!     * The loops breaks if it repeats over 1000 times, see [`APP_PERF.update_loop_budget`].
 //!     * An error is logged with a trace the most frequent sources of update requests.
 //!
 //! #### Var Updates Loop
@@ -372,7 +372,7 @@
 //!         * Windows and widgets that requested layout update in parallel by default.
 //!     2. Does an [updates loop](#updates-loop).
 //!     3. Take layout and render requests, the loop repeats immediately if layout was requested again.
-//!         * The loop breaks if it repeats over 1000 times.
+//!         * The loop breaks if it repeats over 1000 times, see [`APP_PERF.update_loop_budget`].
 //!         * An error is logged with a trace the most frequent sources of update requests.
 //! 3. If render was requested, calls [`AppExtension::render`].
 //!     * Windows and widgets that requested render (or render_update) do know in parallel by default.
@@ -393,6 +393,7 @@
 //! [`Event::on_event`]: crate::event::Event::on_event
 //! [`WINDOWS.exit_on_last_close`]: crate::window::WINDOWS::exit_on_last_close
 //! [`APP.exit`]: crate::APP#method.exit
+//! [`APP_PERF.update_loop_budget`]: APP_PERF::update_loop_budget
 //!
 //! # Full API
 //!
@@ -400,9 +401,9 @@
 //! may be available at the [`zng_app`], [`zng_app_context`] and [`zng_ext_single_instance`] base crates.
 
 pub use zng_app::{
-    on_app_start, print_tracing, print_tracing_filter, AppControlFlow, AppEventObserver, AppExtended, AppExtension, AppExtensionBoxed,
-    AppExtensionInfo, AppStartArgs, DInstant, Deadline, ExitRequestedArgs, HeadlessApp, InstantMode, EXIT_CMD, EXIT_REQUESTED_EVENT,
-    INSTANT,
+    on_app_start, print_tracing, print_tracing_filter, AppControlFlow, AppCycleTimings, AppEventObserver, AppExtended, AppExtension,
+    AppExtensionBoxed, AppExtensionInfo, AppPerfHistory, AppStartArgs, DInstant, Deadline, ExitRequestedArgs, HeadlessApp, InstantMode,
+    APP_PERF, EXIT_CMD, EXIT_REQUESTED_EVENT, INSTANT,
 };
 
 #[cfg(feature = "test_util")]
@@ -428,7 +429,9 @@ pub mod raw_device_events {
 }
 
 #[cfg(feature = "single_instance")]
-pub use zng_ext_single_instance::{AppInstanceArgs, APP_INSTANCE_EVENT};
+pub use zng_ext_single_instance::{
+    register_uri_scheme, AppDeepLinkArgs, AppInstanceArgs, AppSingleInstanceExt, APP_DEEP_LINK_EVENT, APP_INSTANCE_EVENT,
+};
 
 /// App-process crash handler.
 ///
@@ -528,6 +531,37 @@ pub use zng_ext_single_instance::{AppInstanceArgs, APP_INSTANCE_EVENT};
 ///
 /// ```
 ///
+/// # Metadata and Reports
+///
+/// Crash reports can include custom metadata, static entries like the app version can be set using [`CrashConfig::metadata`], the
+/// app-process can set dynamic entries using [`set_metadata`] and record the user's last actions using [`breadcrumb`]. Crash dialogs
+/// can offer an option to send the report, after the user consents, using [`CrashArgs::send_report`], the upload is implemented
+/// by the handler set in [`CrashConfig::report`].
+///
+/// ```no_run
+/// use zng::app::crash_handler;
+///
+/// crash_handler::crash_handler_config!(|cfg| {
+///     cfg.metadata("version", env!("CARGO_PKG_VERSION"));
+///     cfg.report(|args| {
+///         // upload `args` to a server
+///         let _report = args.to_string();
+///         Ok(())
+///     });
+/// });
+///
+/// fn on_save_click() {
+///     crash_handler::breadcrumb("clicked save");
+///     crash_handler::set_metadata("document", "example.txt");
+/// }
+/// ```
+///
+/// [`CrashConfig::metadata`]: crate::app::crash_handler::CrashConfig::metadata
+/// [`CrashConfig::report`]: crate::app::crash_handler::CrashConfig::report
+/// [`CrashArgs::send_report`]: crate::app::crash_handler::CrashArgs::send_report
+/// [`set_metadata`]: crate::app::crash_handler::set_metadata
+/// [`breadcrumb`]: crate::app::crash_handler::breadcrumb
+///
 /// # Debugger
 ///
 /// Note that because the crash handler spawns a different process for the app debuggers will not
@@ -547,7 +581,9 @@ pub use zng_ext_single_instance::{AppInstanceArgs, APP_INSTANCE_EVENT};
 /// See [`zng_app::crash_handler`] and [`zng_wgt_inspector::crash_handler`] for the full API.
 #[cfg(feature = "crash_handler")]
 pub mod crash_handler {
-    pub use zng_app::crash_handler::{crash_handler_config, BacktraceFrame, CrashArgs, CrashConfig, CrashError, CrashPanic};
+    pub use zng_app::crash_handler::{
+        breadcrumb, crash_handler_config, set_metadata, BacktraceFrame, CrashArgs, CrashConfig, CrashError, CrashPanic,
+    };
 
     pub use zng_wgt_inspector::crash_handler::debug_dialog;
 
@@ -0,0 +1,31 @@
+//! Chart widget, properties and nodes.
+//!
+//! The [`Chart!`](struct@Chart) widget presents line, area and bar [`ChartSeries`] over a grid with labeled ticks. The
+//! series values are [`ObservableVec<f64>`](crate::var::ObservableVec) variables, the chart tracks the changes to only
+//! regenerate the display items of the changed ranges, so live time series can be presented efficiently.
+//!
+//! ```
+//! use zng::{chart::*, prelude::*, var::ObservableVec};
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! Chart! {
+//!     series = vec![
+//!         ChartSeries::bar(var(ObservableVec::from(vec![3.0, 7.0, 4.0]))).with_label("Sales"),
+//!         ChartSeries::line(var(ObservableVec::from(vec![5.0, 5.5, 6.0])))
+//!             .with_label("Target")
+//!             .with_color(colors::ORANGE),
+//!     ];
+//!     y_range = AxisRange::fixed(0.0, 10.0);
+//!     tick_count = 5;
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_chart`] for the full view API.
+
+pub use zng_wgt_chart::{
+    default_tick_label_fn, grid_color, nice_step, nice_ticks, series, tick_count, tick_label_fn, x_range, y_range, AxisRange, Chart,
+    ChartAxis, ChartSeries, SeriesKind, TickLabelArgs, GRID_COLOR_VAR, TICK_COUNT_VAR, TICK_LABEL_FN_VAR, X_RANGE_VAR, Y_RANGE_VAR,
+};
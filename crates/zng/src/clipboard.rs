@@ -101,9 +101,43 @@
 //! # });
 //! ```
 //!
+//! # History
+//!
+//! The service can record an in-app clipboard history, it is disabled by default, set [`CLIPBOARD.history_capacity`]
+//! to enable. The [`CLIPBOARD_CHANGED_EVENT`] notifies when the system clipboard changes, if the view-process supports it,
+//! content copied from other apps is also recorded in the history.
+//!
+//! The `TextInput` widget implements [`PASTE_FROM_HISTORY_CMD`] for text entries, the command parameter is the history index.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::clipboard;
+//!
+//! # let _scope = APP.defaults();
+//! clipboard::CLIPBOARD.history_capacity().set(10usize);
+//! # let _ =
+//! Container! {
+//!     child = TextInput! { id = "input-1"; txt = var(Txt::from("")); };
+//!     child_end = Button! {
+//!         child = Text!("Paste Previous");
+//!         on_click = hn!(|_| {
+//!             clipboard::PASTE_FROM_HISTORY_CMD.scoped(WidgetId::named("input-1")).notify_param(1usize);
+//!         });
+//!     }, 4;
+//! }
+//! # ;
+//! ```
+//!
+//! [`CLIPBOARD.history_capacity`]: CLIPBOARD::history_capacity
+//!
 //! # Full API
 //!
 //! See [`zng_ext_clipboard`] for the full clipboard API.
 
-pub use zng_ext_clipboard::{ClipboardError, CLIPBOARD, COPY_CMD, CUT_CMD, PASTE_CMD};
-pub use zng_wgt_input::cmd::{on_copy, on_cut, on_paste, on_pre_copy, on_pre_cut, on_pre_paste};
+pub use zng_ext_clipboard::{
+    ClipboardChangedArgs, ClipboardEntry, ClipboardError, CLIPBOARD, CLIPBOARD_CHANGED_EVENT, COPY_CMD, CUT_CMD, PASTE_CMD,
+    PASTE_FROM_HISTORY_CMD,
+};
+pub use zng_wgt_input::cmd::{
+    on_copy, on_cut, on_paste, on_paste_from_history, on_pre_copy, on_pre_cut, on_pre_paste, on_pre_paste_from_history,
+};
@@ -0,0 +1,37 @@
+//! Code editor widget and properties.
+//!
+//! The [`CodeEditor!`](struct@CodeEditor) widget is a [`TextInput!`] for source code, it uses a monospace font, does not wrap
+//! and shows line numbers in a [`Gutter!`](struct@Gutter). Code is styled using the [`text::syntax_highlight`] function, it is called
+//! for each line and only the lines that changed are highlighted again after an edit.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! let highlight = zng::text::SyntaxHighlightFn::new(|line: &str| {
+//!     let mut r = vec![];
+//!     for kw in ["fn", "let"] {
+//!         for (i, _) in line.match_indices(kw) {
+//!             r.push((i..i + kw.len(), zng::text::SpanStyle::new().bold().color(colors::BLUE)));
+//!         }
+//!     }
+//!     r.sort_by_key(|(r, _)| r.start);
+//!     r
+//! });
+//!
+//! # let _ =
+//! zng::code_editor::CodeEditor! {
+//!     txt = var_from("fn main() {\n    let a = 0;\n}\n");
+//!     zng::text::syntax_highlight = highlight;
+//! }
+//! # ;
+//! ```
+//!
+//! [`TextInput!`]: struct@crate::text_input::TextInput
+//! [`text::syntax_highlight`]: fn@crate::text::syntax_highlight
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_text_input::code_editor`] for the full widget API.
+
+pub use zng_wgt_text_input::code_editor::{CodeEditor, DefaultStyle, Gutter};
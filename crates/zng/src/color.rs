@@ -106,14 +106,27 @@
 
 pub use zng_color::{
     colors, hex, hsl, hsla, hsla_linear_sampler, hsla_sampler, hsv, hsva, lerp_space, light_dark, rgb, rgba, rgba_sampler, web_colors,
-    with_lerp_space, ColorScheme, Hsla, Hsva, LerpSpace, LightDark, LightDarkVarExt, MixAdjust, MixBlendMode, PreMulRgba,
-    RenderMixBlendMode, Rgba, COLOR_SCHEME_VAR,
+    with_lerp_space, ColorScheme, HighContrastColors, Hsla, Hsva, LerpSpace, LightDark, LightDarkVarExt, MixAdjust, MixBlendMode,
+    PreMulRgba, RenderMixBlendMode, Rgba, COLOR_SCHEME_VAR, HIGH_CONTRAST_VAR,
 };
 
-pub use zng_wgt::{accent_color, base_color, color_scheme};
+pub use zng_wgt::{accent_color, base_color, color_scheme, color_theme};
 
 pub use zng_wgt_fill::node::flood;
 
+/// Theme color tokens and service.
+///
+/// See [`zng_color::theme`] for the full API.
+pub mod theme {
+    pub use zng_color::theme::{
+        ColorRole, Theme, ThemeOverrides, BACKGROUND_COLOR_VAR, ERROR_COLOR_VAR, ON_BACKGROUND_COLOR_VAR, ON_ERROR_COLOR_VAR,
+        ON_PRIMARY_COLOR_VAR, ON_SECONDARY_COLOR_VAR, ON_SURFACE_COLOR_VAR, OUTLINE_COLOR_VAR, PRIMARY_COLOR_VAR, SECONDARY_COLOR_VAR,
+        SURFACE_COLOR_VAR, SURFACE_VARIANT_COLOR_VAR, THEME, THEME_VAR,
+    };
+
+    pub use zng_wgt_window::save_theme;
+}
+
 /// Color filter types and properties.
 pub mod filter {
     pub use zng_color::filter::{ColorMatrix, Filter, RenderFilter};
@@ -129,7 +142,7 @@ pub mod filter {
 /// Color gradient types and nodes.
 pub mod gradient {
     pub use zng_color::gradient::{
-        stops, ColorStop, ExtendMode, GradientRadius, GradientRadiusBase, GradientStop, GradientStops, LinearGradientAxis,
+        stops, ColorStop, ExtendMode, GradientRadius, GradientRadiusBase, GradientSpace, GradientStop, GradientStops, LinearGradientAxis,
         RenderExtendMode, RenderGradientStop,
     };
 
@@ -0,0 +1,26 @@
+//! Command palette widget and properties.
+//!
+//! The [`CommandPalette!`](struct@CommandPalette) widget lists the commands enabled in the focused context, the user
+//! can fuzzy search the commands by name and invoke them using the keyboard. The [`command_palette`](fn@command_palette)
+//! property opens the palette as a popup on [`COMMAND_PALETTE_CMD`], the command shortcut is `Ctrl+Shift+P` by default.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! Window! {
+//!     zng::command_palette::command_palette = true;
+//!     child = Button!(zng::clipboard::COPY_CMD);
+//! }
+//! # ;
+//! ```
+//!
+//! See [`COMMANDS`](crate::event::COMMANDS) for more details about the command enumeration and search.
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_command_palette`] for the full view API.
+
+pub use zng_wgt_command_palette::{
+    command_palette, command_palette_anchor, command_palette_node, CommandPalette, COMMAND_PALETTE_CMD, PALETTE_ANCHOR_VAR,
+};
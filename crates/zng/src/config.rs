@@ -54,6 +54,9 @@
 //! The JSON format is available by default, TOML, YAML and RON are also available behind a feature flags, you can
 //! also implement your own source.
 //!
+//! The `SecretConfig` source is available behind the `"secret"` feature, it stores values in the operating system
+//! credential store and is intended for tokens and passwords, usually set on a [`SwitchConfig`] with a `"secret::"` prefix.
+//!
 //! Apart from config sources that represents a format some *meta* sources are provided, they enables composite sources,
 //! such as having two sources app default and user where the user config file only records the non-default values.
 //!
@@ -83,13 +86,38 @@
 //! }
 //! ```
 //!
+//! ## Layered
+//!
+//! The [`LayeredConfig`] source merges sources by [`ConfigLayer`] precedence, default < system < user < session. Config
+//! variables read the value of the greatest layer that contains the key and write to the user layer or another selected
+//! layer. The [`LayeredConfig::origin`] of a key can be used by settings UIs to show values managed by the system and to
+//! implement reset to default.
+//!
+//! ```
+//! use zng::config::*;
+//!
+//! fn load_config() -> LayeredConfig {
+//!     let cfg = LayeredConfig::new()
+//!         .with_layer(ConfigLayer::Default, ReadOnlyConfig::new(JsonConfig::sync("examples/res/config/defaults.json")))
+//!         .with_layer(ConfigLayer::System, ReadOnlyConfig::new(JsonConfig::sync("target/tmp/example.config.system.json")))
+//!         .with_layer(ConfigLayer::User, JsonConfig::sync("target/tmp/example.config.json"))
+//!         .with_layer(ConfigLayer::Session, MemoryConfig::default());
+//!
+//!     // window state only lasts for the session.
+//!     cfg.set_key_write_layer("window.state", Some(ConfigLayer::Session));
+//!
+//!     CONFIG.load(cfg.clone());
+//!     cfg
+//! }
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_ext_config`] for the full config API.
 
 pub use zng_ext_config::{
-    AnyConfig, Config, ConfigKey, ConfigStatus, ConfigValue, FallbackConfig, FallbackConfigReset, JsonConfig, MemoryConfig, RawConfigValue,
-    ReadOnlyConfig, SwapConfig, SwitchConfig, CONFIG,
+    AnyConfig, Config, ConfigKey, ConfigLayer, ConfigStatus, ConfigValue, FallbackConfig, FallbackConfigReset, JsonConfig, LayeredConfig,
+    MemoryConfig, RawConfigValue, ReadOnlyConfig, SwapConfig, SwitchConfig, CONFIG,
 };
 
 pub use zng_wgt_window::{save_state_node, SaveState};
@@ -98,19 +126,53 @@ pub use zng_wgt_window::{save_state_node, SaveState};
 pub use zng_ext_config::RonConfig;
 
 #[cfg(feature = "toml")]
-pub use zng_ext_config::TomlConfig;
+pub use zng_ext_config::{TomlConfig, TomlDocument};
+
+#[cfg(feature = "secret")]
+pub use zng_ext_config::SecretConfig;
 
 #[cfg(feature = "yaml")]
 pub use zng_ext_config::YamlConfig;
 
 /// Settings are the config the user can directly edit, this module implements a basic settings data model.
 ///
+/// Settings are declared in code, each entry defines the config key, category, value type and default, and optionally
+/// the value range and if the setting only applies after restart. The settings editor widget is generated from the
+/// declared settings, it implements search, reset and change tracking.
+///
+/// ```
+/// use zng::prelude::*;
+/// use zng::config::settings::*;
+///
+/// # fn demo() {
+/// SETTINGS.register_categories(|c| {
+///     c.entry("editor", |c| c.name("Editor"));
+/// });
+/// SETTINGS.register(|s| {
+///     s.entry("editor.font-size", "editor", |s| {
+///         s.name("Font Size")
+///             .description("Editor font size, in DIPs.")
+///             .value(14u32)
+///             .default(14u32)
+///             .range(6u32, 72u32)
+///     });
+///     s.entry("editor.gpu", "editor", |s| {
+///         s.name("GPU Rendering")
+///             .description("Use hardware acceleration.")
+///             .value(true)
+///             .default(true)
+///             .requires_restart()
+///     });
+/// });
+/// # }
+/// ```
+///
 /// # Full API
 ///
 /// See [`zng_ext_config::settings`] for the full settings API.
 pub mod settings {
     pub use zng_ext_config::settings::{
-        CategoriesBuilder, Category, CategoryBuilder, CategoryId, Setting, SettingBuilder, SettingsBuilder, SETTINGS,
+        CategoriesBuilder, Category, CategoryBuilder, CategoryId, Setting, SettingBuilder, SettingRange, SettingsBuilder, SETTINGS,
     };
     pub use zng_wgt_input::cmd::{on_pre_settings, on_settings, SETTINGS_CMD};
 
@@ -124,5 +186,7 @@ pub mod settings {
             categories_list_fn, category_header_fn, category_item_fn, setting_fn, settings_fn, CategoriesListArgs, CategoryHeaderArgs,
             CategoryItemArgs, SettingArgs, SettingBuilderEditorExt, SettingsArgs, SettingsCtxExt, SettingsEditor,
         };
+
+        pub use zng_wgt_settings::keymap::KeymapEditor;
     }
 }
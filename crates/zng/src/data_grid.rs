@@ -0,0 +1,53 @@
+//! Data grid widget, properties and nodes.
+//!
+//! The [`DataGrid!`](struct@DataGrid) widget presents rows of data in columns defined by [`DataColumn`], the user
+//! can sort by clicking the column headers, resize and reorder the columns by dragging the headers and select rows
+//! or cell ranges according to the [`selection_mode`](fn@selection_mode).
+//!
+//! The header row stays visible at the top of the parent scroll viewport and the [`frozen_columns`](fn@frozen_columns)
+//! stay visible at the left. Rows are virtualized, only the rows near the viewport are instantiated, so the grid can
+//! present a large number of rows.
+//!
+//! ```
+//! use zng::{data_grid::*, prelude::*};
+//!
+//! #[derive(Clone, Debug, PartialEq)]
+//! struct File {
+//!     name: Txt,
+//!     size: u64,
+//! }
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! Scroll! {
+//!     child = DataGrid! {
+//!         grid::<File> = {
+//!             columns: var(vec![
+//!                 DataColumn::new("Name", wgt_fn!(|a: DataCellArgs<File>| Text!(a.data.map(|f| f.name.clone()))))
+//!                     .with_width(200)
+//!                     .with_sort_key(|f: &File| f.name.clone()),
+//!                 DataColumn::new("Size", wgt_fn!(|a: DataCellArgs<File>| Text!(a.data.map(|f| f.size.to_txt()))))
+//!                     .with_sort_key(|f: &File| f.size),
+//!             ]),
+//!             rows: (0..1000)
+//!                 .map(|i| File {
+//!                     name: formatx!("file-{i}.txt"),
+//!                     size: i * 100,
+//!                 })
+//!                 .collect::<Vec<_>>(),
+//!         };
+//!         frozen_columns = 1;
+//!         selection_mode = SelectionMode::Multi;
+//!     };
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_data_grid`] for the full view API.
+
+pub use zng_wgt_data_grid::{
+    default_header_fn, frozen_columns, grid, header_fn, row_height, selection, selection_color, selection_mode, sort, CellPos, CellRange,
+    ColumnSort, DataCellArgs, DataColumn, DataGrid, DataHeaderArgs, GridSelection, SelectionMode, SortFn, FROZEN_COLUMNS_VAR,
+    HEADER_FN_VAR, ROW_HEIGHT_VAR, SELECTION_COLOR_VAR, SELECTION_MODE_VAR, SELECTION_VAR, SORT_VAR,
+};
@@ -47,8 +47,47 @@
 //! # ;
 //! ```
 //!
+//! # Paged Data
+//!
+//! Infinite-scroll feeds and other paged listings can implement [`DataSource`] and use [`PagedData`] to load
+//! pages with automatic retry. The [`paged_data`] property exposes the [`DATA_STATUS_VAR`] for loading and error
+//! indicators and implements the [`LOAD_MORE_CMD`], [`REFRESH_CMD`] and [`RETRY_CMD`] scoped on the widget.
+//!
+//! ```
+//! use zng::{data_view::*, prelude::*};
+//!
+//! struct Feed;
+//! impl DataSource for Feed {
+//!     type Item = Txt;
+//!
+//!     fn load_page(&self, request: PageRequest) -> DataSourceFuture<Txt> {
+//!         Box::pin(async move {
+//!             let items = (0..request.size).map(|i| formatx!("item {}", request.index * request.size + i)).collect();
+//!             Ok(DataPage::new(items, request.index == 4))
+//!         })
+//!     }
+//! }
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! DataView! {
+//!     paged_data = PagedData::new(Feed, 50);
+//!     view::<DataStatus> = {
+//!         data: DATA_STATUS_VAR,
+//!         update: hn!(|a: &DataViewArgs<DataStatus>| {
+//!             // show loading indicator or error with retry button.
+//!         }),
+//!     };
+//! }
+//! # ;
+//! ```
+//!
+//! [`paged_data`]: fn@paged_data
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_data_view`] for the full view API.
 
-pub use zng_wgt_data_view::{DataView, DataViewArgs};
+pub use zng_wgt_data_view::{
+    paged_data, DataPage, DataSource, DataSourceFuture, DataStatus, DataView, DataViewArgs, PageRequest, PagedData, RetryBackoff,
+    DATA_STATUS_VAR, LOAD_MORE_CMD, REFRESH_CMD, RETRY_CMD,
+};
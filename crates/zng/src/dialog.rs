@@ -0,0 +1,63 @@
+//! Dialog service and widgets.
+//!
+//! The [`DIALOG`] service shows file dialogs, using the native dialogs provided by the view-process or
+//! an in-app dialog if the native dialog is not available. The dialog builders can be awaited directly
+//! or shown using `show` that returns a response var.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::dialog::{FileDialogResponse, DIALOG};
+//!
+//! # let _scope = APP.defaults();
+//! # let _ =
+//! Button! {
+//!     child = Text!("Open");
+//!     on_click = async_hn!(|_| {
+//!         if let FileDialogResponse::Selected(paths) = DIALOG.open_file().filter("Images", ["png", "jpg"]).multiple().await {
+//!             tracing::info!("selected {paths:?}");
+//!         }
+//!     });
+//! }
+//! # ;
+//! ```
+//!
+//! The service also shows custom modal dialogs, the [`Dialog!`] widget handles the `Enter` and `Escape` keys
+//! and the dialog responds using [`DIALOG.respond`].
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::dialog::{self, Dialog, DIALOG};
+//!
+//! # let _scope = APP.defaults();
+//! # let _ =
+//! Button! {
+//!     child = Text!("Delete");
+//!     on_click = async_hn!(|_| {
+//!         let delete = DIALOG.show(Dialog! {
+//!             child = Text!("Delete the file?");
+//!             child_bottom = Button! {
+//!                 child = Text!("Delete");
+//!                 on_click = hn!(|_| DIALOG.respond(true));
+//!             }, 10;
+//!             dialog::on_dialog_accept = hn!(|_| DIALOG.respond(true));
+//!             dialog::on_dialog_cancel = hn!(|_| DIALOG.respond(false));
+//!         });
+//!         if delete.wait_into_rsp().await {
+//!             tracing::info!("delete");
+//!         }
+//!     });
+//! }
+//! # ;
+//! ```
+//!
+//! [`Dialog!`]: struct@Dialog
+//! [`DIALOG.respond`]: DIALOG::respond
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_dialog`] for the full dialog API.
+
+pub use zng_wgt_dialog::{
+    dialog_host, on_dialog_accept, on_dialog_cancel, on_pre_dialog_accept, on_pre_dialog_cancel, style_fn, DefaultStyle, Dialog,
+    DialogQueue, FileDialogBuilder, FileDialogResponse, DIALOG, DIALOG_ACCEPT_CMD, DIALOG_CANCEL_CMD,
+};
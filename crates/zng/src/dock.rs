@@ -0,0 +1,36 @@
+//! Docking layout widget, properties and nodes.
+//!
+//! The [`Dock!`](struct@Dock) widget presents panels in tab groups arranged in nested splits, the user can drag a tab
+//! to dock it in another group or beside a group, a preview overlay shows the drop target. Dragging a tab outside the
+//! dock moves the panel to a floating tool window, closing the window docks the panel back.
+//!
+//! The arrangement is a [`DockLayout`] variable that is updated by the user edits, the layout is serializable so it
+//! can be bound to a [`CONFIG`](crate::config::CONFIG) key to persist the layout between runs.
+//!
+//! ```no_run
+//! use zng::{config::CONFIG, dock::*, prelude::*};
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! Window! {
+//!     child = Dock! {
+//!         dock_layout = CONFIG.get(
+//!             "dock.layout",
+//!             DockLayout::new(DockNode::split(
+//!                 DockAxis::Horizontal,
+//!                 [DockNode::tabs(["files", "search"]), DockNode::tabs(["editor"])],
+//!             )),
+//!         );
+//!         panel_fn = wgt_fn!(|a: DockPanelArgs| Text!("{} panel", a.id));
+//!     };
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_dock`] for the full view API.
+
+pub use zng_wgt_dock::{
+    default_tab_fn, dock_layout, panel_fn, preview_color, splitter_width, tab_fn, Dock, DockAxis, DockLayout, DockNode, DockPanelArgs,
+    DockSide, DockTabArgs, DockTarget, FloatingPanel, PANEL_FN_VAR, PREVIEW_COLOR_VAR, SPLITTER_WIDTH_VAR, TAB_FN_VAR,
+};
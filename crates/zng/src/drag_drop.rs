@@ -0,0 +1,73 @@
+//! Drag&drop service, properties and other types.
+//!
+//! The [`drag_source`](fn@drag_source) property can be used to make a widget draggable, the data can be dropped
+//! in other apps. The [`WINDOWS.native_drag_drop`] method can be used to start a native drag&drop operation directly.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Text! {
+//!     txt = "Drag Me!";
+//!     zng::drag_drop::drag_source = vec![zng::drag_drop::DragDropData::Text("Hello!".into())];
+//!     zng::drag_drop::drag_allowed_effects = zng::drag_drop::DragDropEffect::COPY;
+//! }
+//! # ;
+//! ```
+//!
+//! The [`drag_image`](fn@drag_image) property defines the image shown under the cursor while dragging.
+//!
+//! The default view-process implements the drag&drop source on Windows, macOS and Linux X11, on Linux Wayland
+//! the operation responds with [`DragDropError::NotSupported`]. On macOS a cancel request does not end the operation,
+//! it continues until the user releases the pointer, but no drop target accepts the data. On X11 the drag image
+//! is not shown, the protocol does not support it.
+//!
+//! The [`draggable`](fn@draggable) property can be used to drag data to [`drop_target`](fn@drop_target) widgets in the app.
+//! While dragging a preview is shown under the cursor and scroll widgets scroll when the cursor is near their edges, if the
+//! cursor leaves the window the operation continues as a native drag&drop.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::drag_drop::{draggable, drag_preview_fn, drop_target, is_drag_hovered, on_drop, DragDropData, DragDropEffect};
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Stack! {
+//!     direction = StackDirection::top_to_bottom();
+//!     spacing = 10;
+//!     children = ui_vec![
+//!         Text! {
+//!             txt = "Drag Me!";
+//!             draggable = vec![DragDropData::Text("Hello!".into())];
+//!             drag_preview_fn = wgt_fn!(|_| Text!("Hello!"));
+//!         },
+//!         Text! {
+//!             txt = "Drop Here";
+//!             drop_target = DragDropEffect::COPY;
+//!             on_drop = hn!(|args: &zng::drag_drop::DropArgs| {
+//!                 tracing::info!("dropped {:?}", args.data);
+//!             });
+//!             when *#is_drag_hovered {
+//!                 font_weight = FontWeight::BOLD;
+//!             }
+//!         },
+//!     ];
+//! }
+//! # ;
+//! ```
+//!
+//! [`WINDOWS.native_drag_drop`]: crate::window::WINDOWS::native_drag_drop
+//!
+//! # Full API
+//!
+//! See [`zng_view_api::drag_drop`] and [`zng_wgt_input::drag_drop`] for the full drag&drop API.
+
+pub use zng_view_api::drag_drop::{DragDropData, DragDropEffect, DragDropError, DragDropId};
+
+pub use zng_wgt_input::drag_drop::{
+    drag_allowed_effects, drag_image, drag_preview_fn, drag_source, draggable, drop_target, is_drag_hovered, on_drag_end, on_drag_enter,
+    on_drag_leave, on_drop, on_pre_drag_end, on_pre_drag_enter, on_pre_drag_leave, on_pre_drop, DragEndArgs, DragHoveredArgs, DragInfo,
+    DragPreviewArgs, DropArgs, DropFilter, DRAG_ALLOWED_EFFECTS_VAR, DRAG_DROP, DRAG_END_EVENT, DRAG_HOVERED_EVENT, DRAG_IMAGE_VAR,
+    DRAG_PREVIEW_FN_VAR, DROP_EVENT,
+};
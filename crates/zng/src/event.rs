@@ -309,13 +309,22 @@
 //! Widgets should prefer subscribing only to the command scoped to the widget. App scoped commands target all subscribers,
 //! widget scoped commands target the widget only.
 //!
+//! ## Discoverability
+//!
+//! The [`COMMANDS`] service enumerates the commands that are enabled in a context with their display metadata, and
+//! [`COMMANDS.search`] filters and sorts the commands using [`fuzzy_match`]. The [`CommandPalette!`] widget
+//! is a search UI for invoking commands implemented using this service.
+//!
+//! [`COMMANDS.search`]: COMMANDS::search
+//! [`CommandPalette!`]: struct@crate::command_palette::CommandPalette
+//!
 //! # Full API
 //!
 //! See [`zng_app::event`] for the full event API.
 
 pub use zng_app::event::{
-    command, event, event_args, AnyEvent, AnyEventArgs, Command, CommandArgs, CommandHandle, CommandInfoExt, CommandMeta, CommandMetaVar,
-    CommandMetaVarId, CommandNameExt, CommandParam, CommandScope, Event, EventArgs, EventHandle, EventHandles, EventPropagationHandle,
-    EventReceiver, EVENTS,
+    command, event, event_args, fuzzy_match, AnyEvent, AnyEventArgs, Command, CommandArgs, CommandEntry, CommandHandle, CommandInfoExt,
+    CommandMeta, CommandMetaVar, CommandMetaVarId, CommandNameExt, CommandParam, CommandScope, Event, EventArgs, EventHandle, EventHandles,
+    EventPropagationHandle, EventReceiver, FuzzyMatch, COMMANDS, EVENTS,
 };
 pub use zng_wgt::node::{command_property, event_property, on_command, on_event, on_pre_command, on_pre_event};
@@ -80,6 +80,11 @@
 //!
 //! Focus is moved by pressing the **arrow keys** or calling the focus direction methods in the [`FOCUS`](FOCUS::focus_up) service.
 //!
+//! By default the focus moves to the nearest widget in the direction inside the current focus scope and then continues
+//! out from the scope edges. Focus scopes set to [`DirectionalNav::Spatial`] instead move to the geometrically nearest
+//! widget in the direction anywhere inside the scope, across containers and nested scopes, this is the navigation style
+//! expected by TV remote and gamepad UIs.
+//!
 //! ## Focus Scopes
 //!
 //! Focus scopes are widgets that configure how focus navigation happens inside then. They control what happens
@@ -112,6 +117,14 @@
 //!
 //! You can use the [`focus_scope_behavior`](fn@focus_scope_behavior) property to configure a custom focus scope
 //! to remember the return focus.
+//! The [`FOCUS.clear_return_focus`](FOCUS::clear_return_focus) method can be used to forget the return focus, the next time
+//! the scope is focused it focuses the first descendant, this is useful for wizard flows that reset the step.
+//!
+//! ### History
+//!
+//! The focus service also tracks the widgets that where focused before, the [`FOCUS.back`](FOCUS::back) method returns
+//! the focus to the previous widget that still exists, like a browser back button for focus. The [`FocusChangedCause::request_reason`]
+//! method can be used to find out what originated a focus move, keyboard navigation, click, ALT mode, history or direct API call.
 //!
 //! # Configuring Widgets
 //!
@@ -132,8 +145,8 @@
 
 pub use zng_ext_input::focus::{
     cmd, iter, DirectionalNav, FocusChangedArgs, FocusChangedCause, FocusInfo, FocusInfoBuilder, FocusInfoTree, FocusNavAction,
-    FocusRequest, FocusScopeOnFocus, FocusTarget, ReturnFocusChangedArgs, TabIndex, TabNav, WidgetFocusInfo, WidgetInfoFocusExt, FOCUS,
-    FOCUS_CHANGED_EVENT, RETURN_FOCUS_CHANGED_EVENT,
+    FocusReason, FocusRequest, FocusScopeOnFocus, FocusTarget, ReturnFocusChangedArgs, TabIndex, TabNav, WidgetFocusInfo,
+    WidgetInfoFocusExt, FOCUS, FOCUS_CHANGED_EVENT, RETURN_FOCUS_CHANGED_EVENT,
 };
 pub use zng_wgt_input::focus::{
     alt_focus_scope, directional_nav, focus_click_behavior, focus_highlight, focus_on_init, focus_scope, focus_scope_behavior,
@@ -59,6 +59,6 @@
 //! See [`zng_ext_fs_watcher`] for the full watcher API.
 
 pub use zng_ext_fs_watcher::{
-    fs_event, FsChange, FsChangeNote, FsChangeNoteHandle, FsChangesArgs, WatchFile, WatcherHandle, WatcherReadStatus, WatcherSyncStatus,
-    WriteFile, FS_CHANGES_EVENT, WATCHER,
+    fs_event, FsChange, FsChangeNote, FsChangeNoteHandle, FsChangesArgs, WatchFile, WatchOptions, WatcherHandle, WatcherReadStatus,
+    WatcherSyncStatus, WriteFile, FS_CHANGES_EVENT, WATCHER,
 };
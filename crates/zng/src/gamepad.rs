@@ -0,0 +1,33 @@
+//! Gamepad service, events and other types.
+//!
+//! The [`GAMEPAD`] service tracks connected gamepads and the state of each button and axis. By default the primary gamepad
+//! also controls the keyboard focus, the directional pad and left stick move the focus, the `South` button clicks the focused
+//! widget and the `East` button exits the focus scope.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::gamepad;
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Window! {
+//!     child = Text!(gamepad::GAMEPAD.gamepads().map(|g| formatx!("{} gamepads connected", g.len())));
+//!     gesture::on_click = hn!(|args: &gesture::ClickArgs| {
+//!         if args.is_from_gamepad() {
+//!             println!("gamepad click");
+//!         }
+//!     });
+//! }
+//! # ;
+//! ```
+//!
+//! Note that the default view-process only reports gamepad input if built with the `"view_gamepad"` feature.
+//!
+//! # Full API
+//!
+//! See [`zng_ext_input::gamepad`] for the full gamepad API.
+//! See [`zng_app::view_process::raw_events`] for raw gamepad events that are processed to generate the state and events.
+
+pub use zng_ext_input::gamepad::{
+    ButtonState, GamepadAxis, GamepadButton, GamepadButtonArgs, GamepadInfo, GamepadManager, GAMEPAD, GAMEPAD_BUTTON_EVENT,
+};
@@ -2,7 +2,7 @@
 //!
 //! A gesture is an event that is generated from multiple lower-level events. A shortcut is a gesture generated
 //! from one or more keyboard inputs, a click is also a gesture generated from mouse clicks, accessibility clicks,
-//! touch taps and some shortcuts. In essence, events, types and states that aggregate multiple difference sources
+//! touch taps, gamepad presses and some shortcuts. In essence, events, types and states that aggregate multiple difference sources
 //! are found here, gestures generated from a single event source are defined in other modules, for example touch gestures
 //! are defined in [`touch`](crate::touch).
 //!
@@ -19,6 +19,7 @@
 //!             Touch { .. } => println!("touch tap"),
 //!             Shortcut { .. } => println!("shortcut press"),
 //!             Access { .. } => println!("access click"),
+//!             Gamepad { .. } => println!("gamepad press"),
 //!         }
 //!     });
 //! }
@@ -27,27 +28,72 @@
 //!
 //! The example above handles the click gesture on a window and prints what underlying event was interpreted as a click.
 //!
+//! # Global Shortcuts
+//!
+//! The [`GLOBAL_SHORTCUTS`] service registers shortcuts that are detected even when the app is not focused.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! # let _scope = APP.defaults();
+//! match gesture::GLOBAL_SHORTCUTS.register(shortcut![CTRL | ALT + 'K']) {
+//!     Ok(handle) => {
+//!         handle.perm();
+//!         gesture::GLOBAL_SHORTCUT_EVENT
+//!             .on_event(app_hn!(|args: &gesture::GlobalShortcutArgs, _| {
+//!                 println!("{} pressed", args.shortcut);
+//!             }))
+//!             .perm();
+//!     }
+//!     Err(e) => eprintln!("cannot register global shortcut, {e}"),
+//! }
+//! ```
+//!
+//! # Keymap
+//!
+//! The [`KEYMAP`] service lists the shortcuts of all commands in use and allows runtime rebinding with conflict detection.
+//! The user overrides are a serializable [`Keymap`] that can be bound to a config key, the [`KeymapEditor!`] widget
+//! is a ready-made view for editing the keymap.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! # let _scope = APP.defaults();
+//! gesture::KEYMAP.keymap().set_bind(&CONFIG.get("keymap", gesture::Keymap::default())).perm();
+//! ```
+//!
+//! [`KeymapEditor!`]: struct@crate::config::settings::editor::KeymapEditor
+//!
 //! # Full API
 //!
-//! See [`zng_ext_input::gesture`] and [`zng_wgt_input::gesture`] for the full gesture API
+//! See [`zng_ext_input::gesture`] and [`zng_wgt_input::gesture`] for the full gesture API,
+//! [`zng_ext_input::global_shortcut`] for the global shortcuts API, [`zng_ext_input::keymap`] for the keymap API
 //! and [`zng_app::shortcut`] for the shortcut API.
 //!
 //! [`zng_app::shortcut`]: mod@zng_app::shortcut
 
 pub use zng_ext_input::gesture::{
-    ClickArgs, ClickArgsSource, CommandShortcutMatchesExt, HeadlessAppGestureExt, ShortcutActions, ShortcutArgs, ShortcutClick,
-    ShortcutsHandle, WeakShortcutsHandle, CLICK_EVENT, GESTURES, SHORTCUT_EVENT,
+    ClickArgs, ClickArgsSource, CommandShortcutMatchesExt, HeadlessAppGestureExt, PanGestureArgs, PinchGestureArgs, RotationGestureArgs,
+    ShortcutActions, ShortcutArgs, ShortcutClick, ShortcutsHandle, WeakShortcutsHandle, CLICK_EVENT, GESTURES, PAN_GESTURE_EVENT,
+    PINCH_GESTURE_EVENT, ROTATION_GESTURE_EVENT, SHORTCUT_EVENT,
 };
 
+pub use zng_ext_input::global_shortcut::{
+    GlobalShortcutArgs, GlobalShortcutError, GlobalShortcutHandle, WeakGlobalShortcutHandle, GLOBAL_SHORTCUTS, GLOBAL_SHORTCUT_EVENT,
+};
+
+pub use zng_ext_input::keymap::{Keymap, KeymapConflict, KeymapEntry, KEYMAP};
+
 pub use zng_app::shortcut::{
-    shortcut, CommandShortcutExt, GestureKey, KeyChord, KeyGesture, ModifierGesture, Shortcut, ShortcutFilter, Shortcuts,
+    shortcut, CommandShortcutExt, GestureKey, KeyChord, KeyGesture, KeyLabels, ModifierGesture, Shortcut, ShortcutFilter, Shortcuts,
 };
 
 pub use zng_wgt_input::gesture::{
     click_shortcut, context_click_shortcut, on_any_click, on_any_double_click, on_any_single_click, on_any_triple_click, on_click,
-    on_context_click, on_disabled_click, on_double_click, on_pre_any_click, on_pre_any_double_click, on_pre_any_single_click,
-    on_pre_any_triple_click, on_pre_click, on_pre_context_click, on_pre_disabled_click, on_pre_double_click, on_pre_single_click,
-    on_pre_triple_click, on_single_click, on_triple_click,
+    on_context_click, on_disabled_click, on_double_click, on_pan_gesture, on_pinch_gesture, on_pre_any_click, on_pre_any_double_click,
+    on_pre_any_single_click, on_pre_any_triple_click, on_pre_click, on_pre_context_click, on_pre_disabled_click, on_pre_double_click,
+    on_pre_pan_gesture, on_pre_pinch_gesture, on_pre_rotation_gesture, on_pre_single_click, on_pre_triple_click, on_rotation_gesture,
+    on_single_click, on_triple_click,
 };
 
 pub use zng_wgt_input::{is_cap_hovered, is_cap_pointer_pressed, is_cap_pressed, is_hovered, is_hovered_disabled, is_pressed};
@@ -82,7 +82,7 @@
 //!
 //! See [`zng_wgt_grid`] for the full widget API.
 
-pub use zng_wgt_grid::{node, AutoGrowFnArgs, AutoGrowMode, Cell, Column, Grid, Row};
+pub use zng_wgt_grid::{node, AutoGrowFnArgs, AutoGrowMode, Cell, Column, Grid, GridFlow, Row};
 
 /// Cell widget and properties.
 pub mod cell {
@@ -114,7 +114,27 @@
 //! under iterative development.
 //!
 //! Hot node reinit reloads the entire tree branch, so descendants of hot nodes are reinited too. This may cause some state to be lost,
-//! in particular all state inited inside the hot node will be reinited.
+//! in particular all state inited inside the hot node will be reinited. You can use [`HOT_RELOAD.persistent_var`] to preserve
+//! selected state across reloads.
+//!
+//! ```
+//! use zng::{prelude::*, prelude_wgt::*};
+//! # zng::hot_reload::zng_hot_entry!();
+//!
+//! #[hot_node]
+//! pub fn counter() -> impl UiNode {
+//!     let count = zng::hot_reload::HOT_RELOAD.persistent_var("counter.count", || 0u32);
+//!     Button! {
+//!         child = Text!(count.map_debug());
+//!         on_click = hn!(count, |_| {
+//!             count.set(count.get() + 1);
+//!         });
+//!     }
+//! }
+//! # fn main() { }
+//! ```
+//!
+//! [`HOT_RELOAD.persistent_var`]: HOT_RELOAD::persistent_var
 //!
 //! ##### Hot Libraries Don't Unload
 //!
@@ -65,23 +65,65 @@
 //! The example above changes the global limits to allow image downloads only from an specific host and
 //! only allow images with sizes less or equal to 1 megabyte and that only expands to up to 10 megabytes
 //! after decoding.
+//!
+//! # Progressive Loading
+//!
+//! Downloaded images are decoded as the bytes are received, PNG and JPEG images are shown partially decoded,
+//! see [`Img::is_partially_loaded`] for more details. The [`img_placeholder`](fn@img_placeholder) property
+//! sets a compact preview computed by the server that is shown until the image is loaded.
+//!
+//! ```
+//! use zng::{prelude::*, image};
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Image! {
+//!     source = "https://httpbin.org/image/jpeg";
+//!     image::img_placeholder = "LEHV6nWB2yk8pyo0adR*.7kCMdnj";
+//!     layout::size = (200, 150);
+//! }
+//! # ;
+//! ```
 //!  
+//! # Memory Budget
+//!
+//! The [`IMAGES.memory_budget`](fn@IMAGES::memory_budget) variable limits the memory used by decoded cached images, the
+//! least recently used images that are not in use are evicted first. Combined with the [`img_auto_downscale`](fn@img_auto_downscale)
+//! property images are decoded at the size they are displayed.
+//!
+//! ```
+//! use zng::{prelude::*, image};
+//! # let _scope = APP.defaults();
+//!
+//! image::IMAGES.memory_budget().set(256.megabytes());
+//!
+//! # let _ =
+//! Image! {
+//!     source = "https://httpbin.org/image/jpeg";
+//!     image::img_auto_downscale = true;
+//!     layout::size = (200, 150);
+//! }
+//! # ;
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_ext_image`] for the full image API and [`zng_wgt_image`] for the full widget API.
 
 pub use zng_ext_image::{
-    render_retain, ImageCacheMode, ImageDataFormat, ImageDownscale, ImageHash, ImageHasher, ImageLimits, ImagePpi, ImageRenderArgs,
-    ImageSource, ImageSourceFilter, ImageVar, Img, PathFilter, IMAGES, IMAGE_RENDER,
+    render_retain, ColorSpace, ImageCacheChange, ImageCacheChangedArgs, ImageCacheMode, ImageDataFormat, ImageDownscale,
+    ImageEncodeOptions, ImageHash, ImageHasher, ImageLimits, ImagePpi, ImageRenderArgs, ImageSource, ImageSourceFilter, ImageVar, Img,
+    PathFilter, IMAGES, IMAGE_CACHE_CHANGED_EVENT, IMAGE_RENDER,
 };
 
 #[cfg(feature = "http")]
 pub use zng_ext_image::UriFilter;
 
 pub use zng_wgt_image::{
-    img_align, img_cache, img_crop, img_downscale, img_error_fn, img_fit, img_limits, img_loading_fn, img_offset, img_rendering,
-    img_repeat, img_repeat_spacing, img_scale, img_scale_factor, img_scale_ppi, is_error, is_loaded, on_error, on_load, Image, ImageFit,
-    ImageRepeat, ImgErrorArgs, ImgLoadArgs, ImgLoadingArgs,
+    img_align, img_auto_downscale, img_cache, img_crop, img_downscale, img_error_fn, img_fit, img_frame, img_limits, img_loading_fn,
+    img_loop, img_offset, img_placeholder, img_playing, img_rendering, img_repeat, img_repeat_spacing, img_scale, img_scale_factor,
+    img_scale_ppi, is_error, is_loaded, on_error, on_load, Image, ImageFit, ImagePlaceholder, ImageRepeat, ImgErrorArgs, ImgLoadArgs,
+    ImgLoadingArgs,
 };
 
 /// Mask image properties.
@@ -19,16 +19,42 @@
 //! Keyboard events are send to the focused widget, if there is no focused widget no event is send. You can
 //! subscribe directly to the [`KEY_INPUT_EVENT`] to monitor all keyboard events for any focused widget.
 //!
+//! # Keyboard Layout
+//!
+//! Physical keys ([`KeyCode`]) are named by their position on a US keyboard, the [`KEYBOARD.key_labels`] variable maps
+//! key codes to the glyph generated by the same key in the current system keyboard layout. Shortcuts displayed using
+//! [`CommandShortcutExt::shortcut_txt`] label physical keys using this variable and update when the layout changes,
+//! the [`KEYBOARD_LAYOUT_CHANGED_EVENT`] also notifies on change.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Text!(keyboard::KEYBOARD.key_labels().map(|l| match l.get(keyboard::KeyCode::KeyQ) {
+//!     Some(k) => formatx!("KeyQ is {k:?}"),
+//!     None => Txt::from("KeyQ label unknown"),
+//! }))
+//! # ;
+//! ```
+//!
+//! Note that the default view-process only implements key labels on Windows and Linux. On Linux Wayland the labels
+//! are from the configured default layout and the layout changed event only notifies on GNOME. On macOS and other
+//! systems the variable is empty, physical keys display the key code name and the layout changed event does not notify.
+//!
+//! [`KEYBOARD.key_labels`]: KEYBOARD::key_labels
+//! [`CommandShortcutExt::shortcut_txt`]: crate::gesture::CommandShortcutExt::shortcut_txt
+//!
 //! # Full API
 //!
 //! See [`zng_ext_input::keyboard`] and [`zng_wgt_input::keyboard`] for the full keyboard API.
 //! See [`zng_app::view_process::raw_events`] for raw keyboard events that are processed to generate the key input event.
 
-pub use zng_app::shortcut::ModifiersState;
+pub use zng_app::shortcut::{KeyLabels, ModifiersState};
 
 pub use zng_ext_input::keyboard::{
-    HeadlessAppKeyboardExt, Key, KeyCode, KeyInputArgs, KeyLocation, KeyRepeatConfig, KeyState, ModifiersChangedArgs, NativeKeyCode,
-    KEYBOARD, KEY_INPUT_EVENT, MODIFIERS_CHANGED_EVENT,
+    HeadlessAppKeyboardExt, Key, KeyCode, KeyInputArgs, KeyLocation, KeyRepeatConfig, KeyState, KeyboardLayoutChangedArgs,
+    ModifiersChangedArgs, NativeKeyCode, KEYBOARD, KEYBOARD_LAYOUT_CHANGED_EVENT, KEY_INPUT_EVENT, MODIFIERS_CHANGED_EVENT,
 };
 
 pub use zng_wgt_input::keyboard::{
@@ -109,11 +109,31 @@
 //! l10n!("COPY_CMD.name", "Copy").set_bind(&zng::clipboard::COPY_CMD.name()).perm();
 //! ```
 //!
+//! # Testing
+//!
+//! Enable [`L10N.set_pseudo`] to transform all localized text into accented and expanded pseudo text, this helps identify
+//! text that is not localized and layouts that do not accommodate longer translations. Enable [`L10N.set_collect_missing`]
+//! to record all message keys requested but not found in the loaded lang resources, the list can be inspected
+//! using [`L10N.missing_keys`] or saved to a report file using [`L10N.save_missing_keys`].
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! L10N.set_pseudo(true);
+//! L10N.set_collect_missing(true);
+//! ```
+//!
+//! [`L10N.set_pseudo`]: L10N::set_pseudo
+//! [`L10N.set_collect_missing`]: L10N::set_collect_missing
+//! [`L10N.missing_keys`]: L10N::missing_keys
+//! [`L10N.save_missing_keys`]: L10N::save_missing_keys
+//!
 //! # Full API
 //!
 //! See [`zng_ext_l10n`] for the full localization API.
 
 pub use zng_ext_l10n::{
-    l10n, lang, IntoL10nVar, L10nArgument, L10nDir, L10nMessageBuilder, L10nSource, Lang, LangMap, LangResource, LangResourceStatus,
-    LangResources, Langs, NilL10nSource, SwapL10nSource, L10N, LANG_VAR,
+    l10n, l10n_args, lang, IntoL10nVar, L10nArgument, L10nDir, L10nMessageBuilder, L10nMissingKey, L10nSource, L10nTar, Lang, LangMap,
+    LangResource, LangResourceStatus, LangResources, Langs, NilL10nSource, SwapL10nSource, L10N, LANG_VAR,
 };
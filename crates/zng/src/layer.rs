@@ -42,4 +42,7 @@
 //!
 //! See [`zng_wgt_layer`] for the full layers API.
 
-pub use zng_wgt_layer::{adorner, adorner_fn, AnchorMode, AnchorOffset, AnchorSize, AnchorTransform, LayerIndex, LAYERS};
+pub use zng_wgt_layer::{
+    adorner, adorner_fn, anchor_arrow, AnchorMode, AnchorOffset, AnchorPlacement, AnchorSide, AnchorSize, AnchorTransform, LayerIndex,
+    LAYERS,
+};
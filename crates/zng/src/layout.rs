@@ -365,8 +365,8 @@ pub use zng_layout::context::{
 pub use zng_app::widget::info::{WidgetLayout, WidgetMeasure};
 
 pub use zng_wgt_transform::{
-    backface_visibility, perspective, perspective_origin, rotate, rotate_x, rotate_y, rotate_z, scale, scale_x, scale_xy, scale_y, skew,
-    skew_x, skew_y, transform, transform_origin, transform_style, translate, translate_x, translate_y, translate_z,
+    backface_visibility, perspective, perspective_origin, rotate, rotate_x, rotate_y, rotate_z, scale, scale_3d, scale_x, scale_xy,
+    scale_y, scale_z, skew, skew_x, skew_y, transform, transform_origin, transform_style, translate, translate_x, translate_y, translate_z,
 };
 
 pub use zng_wgt_size_offset::{
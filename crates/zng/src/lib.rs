@@ -484,18 +484,26 @@ pub mod access;
 pub mod ansi_text;
 pub mod app;
 pub mod button;
+pub mod chart;
 pub mod checkerboard;
 pub mod clipboard;
+pub mod code_editor;
 pub mod color;
+pub mod command_palette;
 pub mod config;
 pub mod container;
 pub mod data_context;
+pub mod data_grid;
 pub mod data_view;
+pub mod dialog;
+pub mod dock;
+pub mod drag_drop;
 pub mod env;
 pub mod event;
 pub mod focus;
 pub mod font;
 pub mod fs_watcher;
+pub mod gamepad;
 pub mod gesture;
 pub mod grid;
 pub mod handler;
@@ -508,6 +516,7 @@ pub mod label;
 pub mod layer;
 pub mod layout;
 pub mod markdown;
+pub mod masonry;
 pub mod menu;
 pub mod mouse;
 pub mod panel;
@@ -517,6 +526,7 @@ pub mod render;
 pub mod rule_line;
 pub mod scroll;
 pub mod selectable;
+pub mod sound;
 pub mod stack;
 pub mod state_map;
 pub mod style;
@@ -526,8 +536,11 @@ pub mod text_input;
 pub mod third_party;
 pub mod timer;
 pub mod tip;
+pub mod toast;
 pub mod toggle;
 pub mod touch;
+pub mod tray;
+pub mod tree_view;
 pub mod undo;
 pub mod update;
 pub mod var;
@@ -582,6 +595,9 @@ mod __prelude {
 
     pub use zng_app::widget::inspector::WidgetInfoInspectorExt as _;
 
+    #[cfg(feature = "single_instance")]
+    pub use zng_ext_single_instance::AppSingleInstanceExt as _;
+
     pub use zng_var::{
         context_var, expr_var, getter_var, merge_var, state_var, var, var_from, when_var, AnyVar as _, AnyWeakVar as _, IntoValue, IntoVar,
         Var, VarValue, WeakVar as _,
@@ -678,8 +694,18 @@ mod __prelude {
 
     pub use zng_wgt_wrap::Wrap;
 
+    pub use zng_wgt_masonry::Masonry;
+
     pub use zng_wgt_data_view::{DataView, DataViewArgs};
 
+    pub use zng_wgt_tree_view::TreeView;
+
+    pub use zng_wgt_data_grid::DataGrid;
+
+    pub use zng_wgt_dock::Dock;
+
+    pub use zng_wgt_chart::Chart;
+
     pub use zng_wgt_settings::SettingBuilderEditorExt as _;
 }
 
@@ -805,10 +831,11 @@ mod defaults {
     use zng_ext_fs_watcher::FsWatcherManager;
     use zng_ext_image::ImageManager;
     use zng_ext_input::{
-        focus::FocusManager, gesture::GestureManager, keyboard::KeyboardManager, mouse::MouseManager,
-        pointer_capture::PointerCaptureManager, touch::TouchManager,
+        focus::FocusManager, gamepad::GamepadManager, gesture::GestureManager, global_shortcut::GlobalShortcutManager,
+        keyboard::KeyboardManager, mouse::MouseManager, pointer_capture::PointerCaptureManager, touch::TouchManager,
     };
     use zng_ext_l10n::L10nManager;
+    use zng_ext_tray::TrayIconManager;
     use zng_ext_undo::UndoManager;
     use zng_ext_window::WindowManager;
 
@@ -841,12 +868,15 @@ mod defaults {
         /// * [`MouseManager`]
         /// * [`TouchManager`]
         /// * [`KeyboardManager`]
+        /// * [`GamepadManager`]
         /// * [`GestureManager`]
+        /// * [`GlobalShortcutManager`]
         /// * [`WindowManager`]
         /// * [`FontManager`]
         /// * [`FocusManager`]
         /// * [`ImageManager`]
         /// * [`ClipboardManager`]
+        /// * [`TrayIconManager`]
         /// * [`UndoManager`]
         /// * [`SingleInstanceManager`] if the `"single_instance"` feature is enabled.
         /// * [`HotReloadManager`] if the `"hot_reload"` feature is enabled.
@@ -868,12 +898,15 @@ mod defaults {
                 .extend(MouseManager::default())
                 .extend(TouchManager::default())
                 .extend(KeyboardManager::default())
+                .extend(GamepadManager::default())
                 .extend(GestureManager::default())
+                .extend(GlobalShortcutManager::default())
                 .extend(WindowManager::default())
                 .extend(FontManager::default())
                 .extend(FocusManager::default())
                 .extend(ImageManager::default())
                 .extend(ClipboardManager::default())
+                .extend(TrayIconManager::default())
                 .extend(UndoManager::default());
 
             #[cfg(all(feature = "view", feature = "view_prebuilt"))]
@@ -908,6 +941,9 @@ mod defaults {
             zng_ext_window::WINDOWS.register_root_extender(|a| {
                 let child = a.root;
 
+                let child = zng_wgt_toast::toast_host(child);
+                let child = zng_wgt_dialog::dialog_host(child);
+
                 #[cfg(feature = "inspector")]
                 let child = zng_wgt_inspector::inspector(child, zng_wgt_inspector::live_inspector(true));
 
@@ -927,16 +963,20 @@ mod defaults {
             {
                 crate::app::APP_INSTANCE_EVENT
                     .on_pre_event(crate::handler::app_hn!(|args: &crate::app::AppInstanceArgs, _| {
-                        use crate::window::*;
+                        use crate::{var::Var as _, window::*};
 
-                        // focus a window if none are focused.
+                        // raise and focus the main window if none are focused.
                         if !args.is_current() && WINDOWS.focused_window_id().is_none() {
                             for w in WINDOWS.widget_trees() {
-                                if w.is_rendered()
-                                    && WINDOWS.mode(w.window_id()) == Ok(WindowMode::Headed)
-                                    && WINDOWS.focus(w.window_id()).is_ok()
-                                {
-                                    break;
+                                if w.is_rendered() && WINDOWS.mode(w.window_id()) == Ok(WindowMode::Headed) {
+                                    if let Ok(vars) = WINDOWS.vars(w.window_id()) {
+                                        if vars.state().get() == WindowState::Minimized {
+                                            vars.state().set(vars.restore_state().get());
+                                        }
+                                    }
+                                    if WINDOWS.focus(w.window_id()).is_ok() {
+                                        break;
+                                    }
                                 }
                             }
                         }
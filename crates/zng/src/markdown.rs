@@ -12,15 +12,40 @@
 //! # ;
 //! ```
 //!
+//! # Extensions
+//!
+//! Tables, strikethrough, footnotes and task lists are also supported. Task list check marks are interactive
+//! if the `txt` is a read-write variable, toggling a check updates the `[ ]` marker in the text.
+//!
+//! Custom widgets can replace fenced code blocks of a language or links of an URL scheme using the [`node_resolver`] property.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! let tasks = var(Txt::from("- [x] Done.\n- [ ] Todo."));
+//! # let _ =
+//! zng::markdown::Markdown! {
+//!     txt = tasks;
+//!     node_resolver = zng::markdown::NodeResolver::default().with_code_block(
+//!         "mermaid",
+//!         wgt_fn!(|args: zng::markdown::CodeBlockFnArgs| Text!(args.txt)),
+//!     );
+//! }
+//! # ;
+//! ```
+//!
+//! [`node_resolver`]: fn@node_resolver
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_markdown`] for the full widget API.
 
 pub use zng_wgt_markdown::{
     anchor, block_quote_fn, code_block_fn, code_inline_fn, footnote_def_fn, footnote_ref_fn, heading_anchor, heading_fn, image_fn,
-    image_resolver, link_fn, link_resolver, link_scroll_mode, list_fn, list_item_bullet_fn, list_item_fn, on_link, on_pre_link, panel_fn,
-    paragraph_fn, rule_fn, table_fn, text_fn, BlockQuoteFnArgs, CodeBlockFnArgs, CodeInlineFnArgs, FootnoteDefFnArgs, FootnoteRefFnArgs,
-    HeadingFnArgs, HeadingLevel, ImageFnArgs, ImageResolver, LinkArgs, LinkFnArgs, LinkResolver, ListFnArgs, ListItemBulletFnArgs,
-    ListItemFnArgs, Markdown, MarkdownStyle, PanelFnArgs, ParagraphFnArgs, RuleFnArgs, TableCellFnArgs, TableFnArgs, TextFnArgs,
-    WidgetInfoExt, LINK_EVENT,
+    image_resolver, link_fn, link_resolver, link_scroll_mode, list_fn, list_item_bullet_fn, list_item_fn, node_resolver, on_link,
+    on_pre_link, panel_fn, paragraph_fn, rule_fn, table_cell_fn, table_fn, text_fn, BlockQuoteFnArgs, CodeBlockFnArgs, CodeInlineFnArgs,
+    FootnoteDefFnArgs, FootnoteRefFnArgs, HeadingFnArgs, HeadingLevel, ImageFnArgs, ImageResolver, LinkArgs, LinkFnArgs, LinkResolver,
+    ListFnArgs, ListItemBulletFnArgs, ListItemFnArgs, Markdown, MarkdownStyle, NodeResolver, PanelFnArgs, ParagraphFnArgs, RuleFnArgs,
+    TableCellFnArgs, TableFnArgs, TextFnArgs, WidgetInfoExt, LINK_EVENT, NODE_RESOLVER_VAR,
 };
@@ -0,0 +1,36 @@
+//! Masonry layout widget and properties.
+//!
+//! The [`Masonry!`](struct@Masonry) widget lays out children in columns of equal width, placing each child in the
+//! shortest column, this is the *waterfall* layout used in galleries and pin boards. The example below demonstrates
+//! a scrollable gallery of items of varied height.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! # let _ =
+//! Scroll! {
+//!     mode = zng::scroll::ScrollMode::VERTICAL;
+//!     child = Masonry! {
+//!         column_width = 150;
+//!         spacing = 5;
+//!         children = (0..100)
+//!             .map(|i| {
+//!                 Text! {
+//!                     txt = formatx!("Item {i}");
+//!                     widget::background_color = colors::BLUE;
+//!                     layout::height = (50 + (i * 37) % 100).dip();
+//!                 }
+//!                 .boxed()
+//!             })
+//!             .collect::<UiNodeVec>();
+//!     };
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_masonry`] for the full view API.
+
+pub use zng_wgt_masonry::{get_index, get_index_len, get_rev_index, node, Masonry, WidgetInfoMasonryExt};
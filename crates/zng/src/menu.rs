@@ -80,12 +80,40 @@
 //! # ; }
 //! ```
 //!
+//! # Global Menu
+//!
+//! The [`global_menu`](fn@global_menu) property exports a menu of commands to the operating system global menu bar,
+//! the macOS screen menu bar or the Unity/KDE appmenu on Linux X11. The in-window menu is collapsed while the menu is exported.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::window::{AppMenu, AppMenuItem};
+//! # fn demo() -> impl UiNode {
+//!
+//! Menu! {
+//!     children = ui_vec![SubMenu!(
+//!         "File",
+//!         ui_vec![Button!(zng::app::NEW_CMD.scoped(WINDOW.id())), Hr!(), Button!(zng::app::EXIT_CMD)]
+//!     )];
+//!     global_menu = AppMenu::new(vec![AppMenuItem::Submenu {
+//!         label: "File".into(),
+//!         items: vec![
+//!             zng::app::NEW_CMD.scoped(WINDOW.id()).into(),
+//!             AppMenuItem::Separator,
+//!             zng::app::EXIT_CMD.into(),
+//!         ],
+//!     }]);
+//! }
+//! # }
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_menu`] for the full widget API.
 
 pub use zng_wgt_menu::{
-    icon, icon_fn, panel_fn, shortcut_spacing, shortcut_txt, style_fn, ButtonStyle, DefaultStyle, Menu, ToggleStyle, TouchButtonStyle,
+    global_menu, icon, icon_fn, panel_fn, shortcut_spacing, shortcut_txt, style_fn, ButtonStyle, DefaultStyle, Menu, ToggleStyle,
+    TouchButtonStyle,
 };
 
 /// Submenu widget and properties.
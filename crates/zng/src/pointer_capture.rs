@@ -36,13 +36,24 @@
 //! [`gesture::is_cap_hovered`]: fn@crate::gesture::is_cap_hovered
 //! [`gesture::is_hovered`]: fn@crate::gesture::is_hovered
 //!
+//! Drag interactions that can move the pointer across windows can use [`POINTER_CAPTURE.capture_exclusive`] to keep the capture
+//! when the window loses focus, or [`POINTER_CAPTURE.capture`] with [`CaptureOptions`] to also capture only a specific device. The
+//! [`on_pointer_capture_lost`](fn@on_pointer_capture_lost) event property notifies when and why the capture ended.
+//!
+//! [`POINTER_CAPTURE.capture_exclusive`]: POINTER_CAPTURE::capture_exclusive
+//! [`POINTER_CAPTURE.capture`]: POINTER_CAPTURE::capture
+//!
 //! # Full API
 //!
 //! See [`zng_ext_input::pointer_capture`] and [`zng_wgt_input::pointer_capture`] for the full pointer capture API.
 
-pub use zng_ext_input::pointer_capture::{CaptureInfo, CaptureMode, PointerCaptureArgs, POINTER_CAPTURE, POINTER_CAPTURE_EVENT};
+pub use zng_ext_input::pointer_capture::{
+    CaptureInfo, CaptureLostCause, CaptureMode, CaptureOptions, PointerCaptureArgs, PointerCaptureLostArgs, POINTER_CAPTURE,
+    POINTER_CAPTURE_EVENT, POINTER_CAPTURE_LOST_EVENT,
+};
 
 pub use zng_wgt_input::pointer_capture::{
     capture_pointer, capture_pointer_on_init, on_got_pointer_capture, on_lost_pointer_capture, on_pointer_capture_changed,
-    on_pre_got_pointer_capture, on_pre_lost_pointer_capture, on_pre_pointer_capture_changed,
+    on_pointer_capture_lost, on_pre_got_pointer_capture, on_pre_lost_pointer_capture, on_pre_pointer_capture_changed,
+    on_pre_pointer_capture_lost,
 };
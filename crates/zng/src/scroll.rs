@@ -60,10 +60,11 @@
 
 pub use zng_wgt_scroll::{
     alt_factor, auto_hide_extra, clip_to_viewport, define_viewport_unit, h_line_unit, h_page_unit, h_scrollbar_fn, h_wheel_unit, lazy,
-    line_units, max_zoom, min_zoom, mode, mouse_pan, overscroll_color, page_units, scroll_to_focused_mode, scrollbar_fn,
-    scrollbar_joiner_fn, smooth_scrolling, v_line_unit, v_page_unit, v_scrollbar_fn, v_wheel_unit, wheel_units, zoom_origin,
-    zoom_touch_origin, zoom_wheel_origin, zoom_wheel_unit, LazyMode, Scroll, ScrollBarArgs, ScrollFrom, ScrollInfo, ScrollMode,
-    ScrollUnitsMix, Scrollbar, ScrollbarFnMix, SmoothScrolling, Thumb, WidgetInfoExt, SCROLL,
+    line_units, max_zoom, min_zoom, mode, mouse_pan, overscroll_color, page_units, scroll_physics, scroll_to_focused_mode,
+    scroll_to_padding, scrollbar_fn, scrollbar_joiner_fn, smooth_scrolling, v_line_unit, v_page_unit, v_scrollbar_fn, v_wheel_unit,
+    wheel_units, zoom_origin, zoom_touch_origin, zoom_wheel_origin, zoom_wheel_unit, LazyMode, OverscrollMode, Scroll, ScrollBarArgs,
+    ScrollFrom, ScrollInfo, ScrollMode, ScrollPhysics, ScrollUnitsMix, Scrollbar, ScrollbarFnMix, SmoothScrolling, Thumb, VirtualList,
+    WidgetInfoExt, SCROLL,
 };
 
 /// Scrollbar thumb widget.
@@ -76,12 +77,19 @@ pub mod scrollbar {
     pub use zng_wgt_scroll::scrollbar::{orientation, Orientation, Scrollbar, SCROLLBAR};
 }
 
+/// Virtualizing list widget.
+pub mod virtual_list {
+    pub use zng_wgt_scroll::virtual_list::{
+        item_height, items, node, pool_len, spacing, VirtualItemArgs, VirtualList, ITEM_HEIGHT_VAR, POOL_LEN_VAR, SPACING_VAR,
+    };
+}
+
 /// Scroll commands.
 pub mod cmd {
     pub use zng_wgt_scroll::cmd::{
-        scroll_to, scroll_to_zoom, ScrollRequest, ScrollToMode, ScrollToRequest, ScrollToTarget, PAGE_DOWN_CMD, PAGE_LEFT_CMD,
-        PAGE_RIGHT_CMD, PAGE_UP_CMD, SCROLL_DOWN_CMD, SCROLL_LEFT_CMD, SCROLL_RIGHT_CMD, SCROLL_TO_BOTTOM_CMD, SCROLL_TO_CMD,
-        SCROLL_TO_LEFTMOST_CMD, SCROLL_TO_RIGHTMOST_CMD, SCROLL_TO_TOP_CMD, SCROLL_UP_CMD, ZOOM_IN_CMD, ZOOM_OUT_CMD, ZOOM_RESET_CMD,
-        ZOOM_TO_FIT_CMD,
+        scroll_to, scroll_to_zoom, ScrollRequest, ScrollToMode, ScrollToOptions, ScrollToRequest, ScrollToTarget, PAGE_DOWN_CMD,
+        PAGE_LEFT_CMD, PAGE_RIGHT_CMD, PAGE_UP_CMD, SCROLL_DOWN_CMD, SCROLL_LEFT_CMD, SCROLL_RIGHT_CMD, SCROLL_TO_BOTTOM_CMD,
+        SCROLL_TO_CMD, SCROLL_TO_LEFTMOST_CMD, SCROLL_TO_RIGHTMOST_CMD, SCROLL_TO_TOP_CMD, SCROLL_UP_CMD, ZOOM_IN_CMD, ZOOM_OUT_CMD,
+        ZOOM_RESET_CMD, ZOOM_TO_FIT_CMD,
     };
 }
@@ -0,0 +1,28 @@
+//! Sound service and types.
+//!
+//! This module provides the [`SOUND`] service, it plays system notification sounds and simple WAV sounds
+//! for auditory feedback, it is not a full audio stack.
+//!
+//! Note that the [`SOUND`] service uses the view-process to play sounds, so it will only
+//! work if a headed app or headless app with renderer is running.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::sound::{SystemSound, SOUND};
+//!
+//! # let _scope = APP.defaults();
+//! # let _ =
+//! Button! {
+//!     child = Text!("Error");
+//!     on_click = hn!(|_| {
+//!         let _ = SOUND.play(SystemSound::Error);
+//!     });
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_ext_sound`] for the full sound API.
+
+pub use zng_ext_sound::{Sound, SoundError, SystemSound, SOUND};
@@ -41,6 +41,27 @@
 //! child and the `origin` point in the next child and then positions the next child so that both points overlap. This enables
 //! custom layouts like partially overlapping children and the traditional horizontal and vertical stack.
 //!
+//! # Transitions
+//!
+//! The [`widget::panel_transition`](fn@crate::widget::panel_transition) property animates the children to the new layout
+//! when children are inserted, removed or moved, instead of jumping to the new position.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! # let _scope = APP.defaults();
+//! let items = zng::widget::node::EditableUiNodeList::new();
+//! let items_ref = items.reference();
+//! # let _ =
+//! Stack! {
+//!     direction = StackDirection::top_to_bottom();
+//!     widget::panel_transition = 300.ms();
+//!     children = items;
+//! }
+//! # ;
+//! items_ref.push(Text!("new item"));
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_stack`] for the full widget API.
@@ -106,6 +106,8 @@
 //! # ;
 //! ```
 //!
+//! For large files use the [`http::DOWNLOADS`] service, it downloads to a target file with progress variables, pause, resume and retries.
+//!
 //! For other protocols or alternative HTTP clients you can use [external crates](#async-crates-integration).
 //!
 //! # Async Crates Integration
@@ -126,6 +128,29 @@
 //! External tasks also don't propagate the thread context, if you want access to app services or want to set vars inside external
 //! parallel closures you must capture and load the [`LocalContext`] manually.
 //!
+//! # Streams
+//!
+//! Async streams, like websocket messages or channels, can be connected to UI state using [`VarStreamExt::set_from_stream`],
+//! the stream is polled in parallel and the items are applied to the variable in app updates, only the latest item per update by default.
+//! Use [`VecVarStreamExt::extend_from_stream`] to push all items to an [`ObservableVec`] variable.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::task::VarStreamExt as _;
+//!
+//! # fn demo() {
+//! let (sender, receiver) = task::channel::unbounded();
+//! let last_message = var(Txt::from_static(""));
+//! let handle = last_message.set_from_stream(receiver.into_stream(), task::StreamVarMode::Coalesce);
+//!
+//! task::spawn(async move {
+//!     let _ = sender.send(Txt::from_static("hello"));
+//! });
+//! // stream stops driving the var when `handle` is dropped.
+//! # handle.perm(); }
+//! ```
+//!
+//! [`ObservableVec`]: crate::var::ObservableVec
 //! [`LocalContext`]: crate::app::LocalContext
 //! [`isahc`]: https://docs.rs/isahc
 //! [`AppExtension`]: crate::app::AppExtension
@@ -146,7 +171,8 @@
 pub use zng_task::{
     all, all_ok, all_some, any, any_ok, any_some, block_on, channel, deadline, fs, future_fn, io, join, join_context, poll_respond,
     poll_spawn, respond, run, run_catch, scope, spawn, spawn_wait, wait, wait_catch, wait_respond, with_deadline, yield_now, DeadlineError,
-    McWaker, ParallelIteratorExt, ParallelIteratorWithCtx, ScopeCtx, SignalOnce, UiTask,
+    McWaker, ParallelIteratorExt, ParallelIteratorWithCtx, ScopeCtx, SignalOnce, StreamVarHandle, StreamVarMode, UiTask, VarStreamExt,
+    VecVarStreamExt,
 };
 
 #[cfg(any(doc, feature = "test_util"))]
@@ -59,6 +59,27 @@
 //! [`AnsiText!`]: struct@crate::ansi_text::AnsiText
 //! [`wrap`]: crate::wrap
 //!
+//! # Search
+//!
+//! The [`txt_search`](fn@txt_search) property highlights all matches of a query in the text, the [`cmd::TEXT_SEARCH_CMD`] moves
+//! to the next match and selects it. The match count and current match can be bound to a search bar.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! let query = var(Txt::from("lorem"));
+//! let count = var(0usize);
+//! # let _ =
+//! Text! {
+//!     txt = "Lorem ipsum dolor sit amet, lorem ipsum.";
+//!     txt_selectable = true;
+//!     zng::text::txt_search = query;
+//!     zng::text::get_txt_search_count = count;
+//! }
+//! # ;
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_text`] for the full widget API.
@@ -71,13 +92,16 @@ pub use zng_wgt_text::{
     font_family, font_features, font_historical_forms, font_historical_lig, font_jp_variant, font_kerning, font_num_fraction,
     font_num_spacing, font_numeric, font_ornaments, font_palette, font_palette_colors, font_position, font_size, font_stretch, font_style,
     font_style_set, font_stylistic, font_swash, font_synthesis, font_variations, font_weight, get_caret_index, get_caret_status,
-    get_chars_count, get_lines_len, get_lines_wrap_count, get_overflow, hyphen_char, hyphens, ime_underline, interactive_caret,
-    interactive_caret_visual, is_line_overflown, is_overflown, is_parse_pending, justify, lang, letter_spacing, line_break, line_height,
-    line_spacing, max_chars_count,
+    get_chars_count, get_graphemes_count, get_lines_len, get_lines_wrap_count, get_overflow, get_txt_search_count, get_txt_search_current,
+    hyphen_char, hyphens, ime_preview_background, ime_target_underline, ime_underline, ime_underline_color, input_mask, interactive_caret,
+    interactive_caret_visual, is_line_overflown, is_overflown, is_parse_pending, is_txt_invalid, justify, lang, letter_spacing, line_break,
+    line_height, line_spacing, max_chars_count, max_graphemes_count,
     node::{set_interactive_caret_spot, TEXT},
     obscure_txt, obscuring_char, on_change_stop, overline, overline_color, paragraph_spacing, selection_color, selection_toolbar,
-    selection_toolbar_anchor, selection_toolbar_fn, strikethrough, strikethrough_color, tab_length, txt_align, txt_editable, txt_overflow,
-    txt_overflow_align, underline, underline_color, underline_skip, white_space, word_break, word_spacing, AutoSelection, CaretShape,
-    CaretStatus, ChangeStopArgs, ChangeStopCause, Em, InteractiveCaretMode, LangMix, LinesWrapCount, ParagraphMix, SelectionToolbarArgs,
-    Strong, Text, TextOverflow, TxtParseValue, UnderlinePosition, UnderlineSkip, FONT_COLOR_VAR,
+    selection_toolbar_anchor, selection_toolbar_fn, strikethrough, strikethrough_color, syntax_highlight, tab_length, txt_align,
+    txt_editable, txt_overflow, txt_overflow_align, txt_search, txt_search_highlight, txt_search_match_case, txt_validate, underline,
+    underline_color, underline_skip, white_space, word_break, word_spacing, AutoSelection, CaretShape, CaretStatus, ChangeStopArgs,
+    ChangeStopCause, Em, InputMask, InteractiveCaretMode, LangMix, LinesWrapCount, LinkClickArgs, ParagraphMix, RichSpan, RichTxt,
+    SelectionToolbarArgs, SpanStyle, Strong, SyntaxHighlightFn, Text, TextOverflow, TxtParseValue, TxtValidateFn, UnderlinePosition,
+    UnderlineSkip, FONT_COLOR_VAR,
 };
@@ -37,6 +37,36 @@
 //! adorners to the `TextInput!`, in the first field a char count is shown, in the second field the [`field_help`](fn@field_help)
 //! or parse errors are shown.
 //!
+//! # Masks and Validation
+//!
+//! The [`input_mask`](struct@TextInput#method.input_mask) property formats the text as the user types, literals of the mask are
+//! inserted automatically and input that does not match the mask is skipped. The [`txt_validate`](struct@TextInput#method.txt_validate)
+//! property validates the text after every change, errors are shown by the `FieldStyle!` like parse errors.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::text::{InputMask, TxtValidateFn};
+//! # let _scope = APP.defaults();
+//!
+//! let mask = InputMask::new("(999) 999-9999");
+//! # let _ =
+//! TextInput! {
+//!     style_fn = style_fn!(|_| zng::text_input::FieldStyle!());
+//!     txt = var(Txt::from(""));
+//!     input_mask = mask.clone();
+//!     txt_validate = TxtValidateFn::new(move |txt| if mask.is_complete(txt) { Ok(()) } else { Err("incomplete phone number".into()) });
+//! }
+//! # ;
+//! ```
+//!
+//! # Selection
+//!
+//! Editable and selectable text supports multiple selection ranges, the primary selection is defined by the caret and the
+//! [`CaretInfo::extra_selections`] defines the other ranges. Mouse drag holding ALT selects a rectangle, one range for each line,
+//! copy and cut join the selected text of each range with a line-break.
+//!
+//! [`CaretInfo::extra_selections`]: zng_wgt_text::node::CaretInfo::extra_selections
+//!
 //! # Full API
 //!
 //! See [`zng_wgt_text_input`] for the full widget API.
@@ -25,11 +25,36 @@
 //! [`task::deadline`]: crate::task::deadline
 //! [`TIMERS.interval`]: TIMERS::interval
 //!
+//! # Schedules
+//!
+//! Deadlines and intervals are relative to the app monotonic clock, for reminder-style features that must elapse at a
+//! date and time use [`TIMERS.on_schedule`] with a wall-clock [`Schedule`], schedules can be daily, weekly or parsed
+//! from cron expressions and observe system time and time zone changes. The [`SYSTEM_TIME_CHANGED_EVENT`] notifies
+//! when the system time or time zone changes.
+//!
+//! ```
+//! use zng::prelude::*;
+//! # let _scope = APP.defaults();
+//!
+//! let schedule: timer::Schedule = "0 9 * * MON-FRI".parse().unwrap();
+//! timer::TIMERS
+//!     .on_schedule(
+//!         schedule,
+//!         app_hn!(|_, _| {
+//!             tracing::info!("weekday morning reminder");
+//!         }),
+//!     )
+//!     .perm();
+//! ```
+//!
+//! [`TIMERS.on_schedule`]: TIMERS::on_schedule
+//!
 //! # Full API
 //!
 //! See [`zng_app::timer`] for the full time API.
 //!
 
 pub use zng_app::timer::{
-    DeadlineArgs, DeadlineHandle, DeadlineVar, Timer, TimerArgs, TimerHandle, TimerVar, WeakDeadlineHandle, WeakTimerHandle, TIMERS,
+    CronParseError, DeadlineArgs, DeadlineHandle, DeadlineVar, Schedule, ScheduleArgs, ScheduleHandle, SystemTimeChangedArgs, Timer,
+    TimerArgs, TimerHandle, TimerVar, WeakDeadlineHandle, WeakScheduleHandle, WeakTimerHandle, SYSTEM_TIME_CHANGED_EVENT, TIMERS,
 };
@@ -30,5 +30,6 @@
 
 pub use zng_wgt_tooltip::{
     access_tooltip_anchor, access_tooltip_duration, disabled_tooltip, disabled_tooltip_fn, style_fn, tooltip, tooltip_anchor,
-    tooltip_context_capture, tooltip_delay, tooltip_duration, tooltip_fn, tooltip_interval, DefaultStyle, Tip, TooltipArgs,
+    tooltip_context_capture, tooltip_delay, tooltip_duration, tooltip_fn, tooltip_hide_delay, tooltip_interactive, tooltip_interval,
+    DefaultStyle, Tip, TooltipArgs, TOOLTIP,
 };
@@ -0,0 +1,37 @@
+//! Toast notifications service and widget.
+//!
+//! The [`NOTIFICATIONS`] service shows notifications as in-app toasts in a layer of the focused window, or as native
+//! operating system notifications when no app window is focused. Toasts are queued when more than [`max_visible`]
+//! are open and close after a timeout, when clicked or when an action button is clicked.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::toast::{Notification, NotificationResponse, NOTIFICATIONS};
+//!
+//! # let _scope = APP.defaults();
+//! # let _ =
+//! Button! {
+//!     child = Text!("Save");
+//!     on_click = async_hn!(|_| {
+//!         let r = NOTIFICATIONS.notify(Notification::new("Saved", "File saved.").action("open", "Open Folder"));
+//!         if let NotificationResponse::Action(a) = r.wait_into_rsp().await {
+//!             tracing::info!("{a}");
+//!         }
+//!     });
+//! }
+//! # ;
+//! ```
+//!
+//! Note that native notifications are implemented by the view-process, the default view-process implementation
+//! only supports native notifications on Linux and BSD, in other systems the notifications are always shown as toasts.
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_toast`] for the full toast API.
+//!
+//! [`max_visible`]: NOTIFICATIONS::max_visible
+
+pub use zng_wgt_toast::{
+    default_toast_fn, style_fn, toast_host, DefaultStyle, Notification, NotificationAction, NotificationId, NotificationResponse,
+    NotificationRoute, Toast, ToastArgs, NOTIFICATIONS,
+};
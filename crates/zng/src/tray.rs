@@ -0,0 +1,44 @@
+//! System tray icon service, events and types.
+//!
+//! This module provides the [`TRAY_ICONS`] service, it shows icons in the system tray or notification area, the
+//! icons can have a tooltip and a popup menu. Tray icons are usually used by apps that run in the background,
+//! see also [`GLOBAL_SHORTCUTS`] for shortcuts that work when the app is not focused.
+//!
+//! Note that the [`TRAY_ICONS`] service uses the view-process, the default view-process implementation
+//! supports tray icons on Windows and Linux (StatusNotifierItem), macOS is not supported.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::tray::{TrayIcon, TrayMenuItem, TRAY_ICONS, TRAY_MENU_ITEM_CLICK_EVENT};
+//!
+//! # let _scope = APP.defaults();
+//! let tray = TrayIcon::new("My App", "res/icon.png").menu(vec![
+//!     TrayMenuItem::item("show", "Show"),
+//!     TrayMenuItem::Separator,
+//!     TrayMenuItem::item("exit", "Exit"),
+//! ]);
+//! match TRAY_ICONS.open(tray) {
+//!     Ok(handle) => {
+//!         handle.perm();
+//!         TRAY_MENU_ITEM_CLICK_EVENT
+//!             .on_event(app_hn!(|args: &zng::tray::TrayMenuItemClickArgs, _| {
+//!                 if args.item == "exit" {
+//!                     APP.exit();
+//!                 }
+//!             }))
+//!             .perm();
+//!     }
+//!     Err(e) => eprintln!("cannot open tray icon, {e}"),
+//! }
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_ext_tray`] for the full tray icon API.
+//!
+//! [`GLOBAL_SHORTCUTS`]: crate::gesture::GLOBAL_SHORTCUTS
+
+pub use zng_ext_tray::{
+    TrayIcon, TrayIconClickArgs, TrayIconError, TrayIconHandle, TrayIconId, TrayMenuItem, TrayMenuItemClickArgs, WeakTrayIconHandle,
+    TRAY_ICONS, TRAY_ICON_CLICK_EVENT, TRAY_MENU_ITEM_CLICK_EVENT,
+};
@@ -0,0 +1,56 @@
+//! Tree view widget, properties and nodes.
+//!
+//! The [`TreeView!`](struct@TreeView) widget presents hierarchical data that loads asynchronously, like a file
+//! explorer or a settings tree. The data is provided by a [`TreeSource`] that loads the children of a node on the first
+//! expand, the [`TreeData`] tracks the loaded nodes, the expanded state and the visible rows.
+//!
+//! Items are lazy inited, only the items in the parent scroll viewport are inited, so trees with many expanded nodes
+//! are cheap to present. The tree is a single tab stop, arrow keys navigate, expand and collapse the items.
+//!
+//! ```
+//! use zng::{prelude::*, tree_view::*};
+//!
+//! struct Files;
+//! impl TreeSource for Files {
+//!     type Item = Txt;
+//!
+//!     fn load_children(&self, parent: Option<Txt>) -> TreeSourceFuture<Txt> {
+//!         Box::pin(async move {
+//!             let parent = parent.unwrap_or_default();
+//!             let dirs = (0..3).map(|i| TreeItem::branch(formatx!("{parent}/dir-{i}")));
+//!             let files = (0..5).map(|i| TreeItem::leaf(formatx!("{parent}/file-{i}.txt")));
+//!             Ok(dirs.chain(files).collect())
+//!         })
+//!     }
+//! }
+//!
+//! # let _scope = APP.defaults(); let _ =
+//! Scroll! {
+//!     mode = zng::scroll::ScrollMode::VERTICAL;
+//!     child = TreeView! {
+//!         tree::<Txt> = {
+//!             data: TreeData::new(Files),
+//!             item_fn: wgt_fn!(|a: TreeItemArgs<Txt>| {
+//!                 let expanded = a.expanded.clone();
+//!                 Text! {
+//!                     txt = a.data.clone();
+//!                     gesture::on_double_click = hn!(|_| {
+//!                         expanded.set(!expanded.get());
+//!                     });
+//!                 }
+//!             }),
+//!         };
+//!         indent = 20;
+//!     };
+//! }
+//! # ;
+//! ```
+//!
+//! # Full API
+//!
+//! See [`zng_wgt_tree_view`] for the full view API.
+
+pub use zng_wgt_tree_view::{
+    indent, item_height, tree, TreeData, TreeItem, TreeItemArgs, TreeNodeId, TreeNodeStatus, TreeSource, TreeSourceFuture, TreeView,
+    INDENT_VAR, ITEM_HEIGHT_VAR,
+};
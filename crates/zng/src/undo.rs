@@ -50,14 +50,51 @@
 //! # ;
 //! ```
 //!
+//! # Transactions and Coalescing
+//!
+//! Multiple actions can be grouped into a single undo entry using [`UNDO.transaction_named`] or [`UNDO.group`]. Consecutive
+//! actions of the same type can also be coalesced automatically, the [`UNDO.set_coalesce`] method defines the rule
+//! for each action type.
+//!
+//! ```
+//! use zng::prelude::*;
+//! use zng::undo::*;
+//!
+//! # let _scope = APP.defaults();
+//! let name = var(Txt::from("old"));
+//! let t = UNDO.transaction_named("Rename", || {
+//!     UNDO.run_op("set name", clmv!(name, |op| {
+//!         let _ = match op {
+//!             UndoOp::Undo => name.set("old"),
+//!             UndoOp::Redo => name.set("new"),
+//!         };
+//!     }));
+//! });
+//! // commits a single "Rename" entry.
+//! t.commit();
+//! ```
+//!
+//! # Persistence
+//!
+//! Actions registered using [`UNDO.register_persistent_op`] can be saved using [`UNDO.save_stack`] and restored
+//! using [`UNDO.restore_stack`], so that undo survives app restart. The saved [`UndoStackData`] is serializable, it
+//! can be stored with the document the undo scope edits.
+//!
+//! [`UNDO.transaction_named`]: UNDO::transaction_named
+//! [`UNDO.group`]: UNDO::group
+//! [`UNDO.set_coalesce`]: UNDO::set_coalesce
+//! [`UNDO.register_persistent_op`]: UNDO::register_persistent_op
+//! [`UNDO.save_stack`]: UNDO::save_stack
+//! [`UNDO.restore_stack`]: UNDO::restore_stack
+//!
 //! # Full API
 //!
 //! See [`zng_ext_undo`] for the full undo API.
 
 pub use zng_ext_undo::{
-    CommandUndoExt, RedoAction, UndoAction, UndoActionMergeArgs, UndoFullOp, UndoInfo, UndoOp, UndoSelect, UndoSelectInterval,
-    UndoSelectLtEq, UndoSelector, UndoStackInfo, UndoTransaction, UndoVarModifyTag, WidgetInfoUndoExt, WidgetUndoScope, CLEAR_HISTORY_CMD,
-    REDO_CMD, UNDO, UNDO_CMD,
+    CommandUndoExt, RedoAction, UndoAction, UndoActionData, UndoActionMergeArgs, UndoCoalesce, UndoFullOp, UndoInfo, UndoOp, UndoSelect,
+    UndoSelectInterval, UndoSelectLtEq, UndoSelector, UndoStackData, UndoStackInfo, UndoTransaction, UndoVarModifyTag, WidgetInfoUndoExt,
+    WidgetUndoScope, CLEAR_HISTORY_CMD, REDO_CMD, UNDO, UNDO_CMD,
 };
 
 pub use zng_wgt_undo::{undo_enabled, undo_interval, undo_limit, undo_scope, UndoMix};
@@ -37,15 +37,19 @@
 //!
 //! See [`zng_app::widget`] for the full API.
 
-pub use zng_app::widget::base::{HitTestMode, NonWidgetBase, Parallel, WidgetBase, WidgetExt, WidgetImpl, PARALLEL_VAR};
+pub use zng_app::widget::base::{
+    HitTestMode, NonWidgetBase, Parallel, WidgetBase, WidgetExt, WidgetImpl, PARALLEL_MIN_CHUNK_VAR, PARALLEL_VAR,
+};
 
-pub use zng_app::widget::{widget_impl, widget_set, WidgetId, WidgetUpdateMode, WIDGET};
+pub use zng_app::widget::{
+    easing_all, widget_impl, widget_set, EasingAll, WidgetId, WidgetTaskPanicArgs, WidgetUpdateMode, WIDGET, WIDGET_TASK_PANIC_EVENT,
+};
 
 pub use zng_app::widget::border::{
     BorderSide, BorderSides, BorderStyle, CornerRadius, CornerRadiusFit, LineOrientation, LineStyle, BORDER,
 };
 pub use zng_app::widget::info::Visibility;
-pub use zng_app::widget::node::ZIndex;
+pub use zng_app::widget::node::{PanelTransition, ZIndex, PANEL_TRANSITION_VAR};
 
 pub use zng_wgt::{
     auto_hide, border, border_align, border_over, clip_to_bounds, corner_radius, corner_radius_fit, enabled, hit_test_mode, inline,
@@ -54,8 +58,8 @@ pub use zng_wgt::{
     on_interactivity_changed, on_move, on_node_op, on_pre_block, on_pre_blocked_changed, on_pre_deinit, on_pre_disable, on_pre_enable,
     on_pre_enabled_changed, on_pre_init, on_pre_interactivity_changed, on_pre_move, on_pre_node_op, on_pre_transform_changed,
     on_pre_unblock, on_pre_update, on_pre_vis_disable, on_pre_vis_enable, on_pre_vis_enabled_changed, on_transform_changed, on_unblock,
-    on_update, on_vis_disable, on_vis_enable, on_vis_enabled_changed, parallel, visibility, wgt_fn, z_index, EditorRequestArgs,
-    OnNodeOpArgs, WeakWidgetFn, Wgt, WidgetFn, EDITORS,
+    on_update, on_vis_disable, on_vis_enable, on_vis_enabled_changed, panel_transition, parallel, parallel_min_chunk, visibility, wgt_fn,
+    z_index, EditorRequestArgs, OnNodeOpArgs, WeakWidgetFn, Wgt, WidgetFn, EDITORS,
 };
 
 pub use zng_wgt_fill::{
@@ -125,8 +129,8 @@ pub mod builder {
     pub use zng_app::widget::builder::{
         property_args, property_id, property_info, property_input_types, source_location, widget_type, AnyWhenArcWidgetHandlerBuilder,
         ArcWidgetHandler, BuilderProperty, BuilderPropertyMut, BuilderPropertyRef, Importance, InputKind, NestGroup, NestPosition,
-        PropertyArgs, PropertyBuildAction, PropertyBuildActionArgs, PropertyBuildActions, PropertyBuildActionsWhenData, PropertyId,
-        PropertyInfo, PropertyInput, PropertyInputTypes, PropertyNewArgs, SourceLocation, WhenBuildAction, WhenInfo, WhenInput,
+        PropertyArgs, PropertyBuildAction, PropertyBuildActionArgs, PropertyBuildActions, PropertyBuildActionsWhenData, PropertyEasingFn,
+        PropertyId, PropertyInfo, PropertyInput, PropertyInputTypes, PropertyNewArgs, SourceLocation, WhenBuildAction, WhenInfo, WhenInput,
         WhenInputMember, WhenInputVar, WidgetBuilder, WidgetBuilderProperties, WidgetBuilding, WidgetType,
     };
 }
@@ -703,6 +707,27 @@ pub use zng_app::widget::easing;
 /// pub fn children(children: impl UiNodeList) { }
 /// ```
 ///
+/// #### Animatable
+///
+/// After the nest group and capture flag and before default the `, animatable, ` value indicates that all property inputs
+/// are [`Transitionable`] variables. The flag validates the input types at compile time and sets the [`PropertyInfo::animatable`]
+/// metadata, animatable properties are eased by the [`easing_all`] property and are highlighted in the inspector.
+///
+/// ```
+/// # fn main() { }
+/// use zng::prelude_wgt::*;
+///
+/// #[property(FILL, animatable, default(rgba(0, 0, 0, 0)))]
+/// pub fn background_color(child: impl UiNode, color: impl IntoVar<Rgba>) -> impl UiNode {
+///     // ..
+/// #   child
+/// }
+/// ```
+///
+/// [`Transitionable`]: zng::var::animation::Transitionable
+/// [`PropertyInfo::animatable`]: zng::widget::builder::PropertyInfo::animatable
+/// [`easing_all`]: fn@zng::widget::easing_all
+///
 /// # Args
 ///
 /// The property function requires at least two args, the first is the child node and the other(s) the input values. The
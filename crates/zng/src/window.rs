@@ -93,6 +93,84 @@
 //! }
 //! ```
 //!
+//! # Frame Capture
+//!
+//! The [`WINDOWS.capture_frame`] method reads back the last rendered frame of a headed or headless window, this can be used
+//! to implement features like "copy as image", screenshots for bug reports and live thumbnails. The [`WINDOWS.frame_image`]
+//! and [`WINDOWS.frame_image_rect`] methods return the image var before it is loaded.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! async fn copy_window_image(window_id: WindowId) {
+//!     let img = WINDOWS.capture_frame(window_id, None).wait_rsp().await;
+//!     if !img.is_error() {
+//!         let _ = zng::clipboard::CLIPBOARD.set_image(img).wait_rsp().await;
+//!     }
+//! }
+//! ```
+//!
+//! [`WINDOWS.capture_frame`]: WINDOWS::capture_frame
+//! [`WINDOWS.frame_image`]: WINDOWS::frame_image
+//! [`WINDOWS.frame_image_rect`]: WINDOWS::frame_image_rect
+//!
+//! # Splash Screen
+//!
+//! The [`splash_fn`] property of the window widget sets a lightweight splash screen that is the first frame presented by the window,
+//! the window content is only inited after this frame renders, so the window does not open blank during heavy content init. The content
+//! is revealed by a cross-fade after [`splash_ready`] is `true`, content that loads asynchronously can report progress using
+//! [`splash_progress`] and [`splash_message`].
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! fn window() -> window::WindowRoot {
+//!     let ready = var(false);
+//!     let progress = var(0.fct());
+//!     Window! {
+//!         splash_fn = wgt_fn!(|args: window::SplashArgs| Text! {
+//!             txt = args.progress.map(|p| formatx!("loading {}", layout::FactorPercent::from(*p)));
+//!             layout::align = layout::Align::CENTER;
+//!         });
+//!         splash_ready = ready.read_only();
+//!         splash_progress = progress.read_only();
+//!
+//!         on_load = async_hn!(ready, progress, |_| {
+//!             for i in 1..=10 {
+//!                 task::deadline(100.ms()).await;
+//!                 progress.set((i as f32 / 10.0).fct());
+//!             }
+//!             ready.set(true);
+//!         });
+//!         child = Text!("Loaded!");
+//!     }
+//! }
+//! ```
+//!
+//! [`splash_fn`]: fn@zng_wgt_window::splash_fn
+//! [`splash_ready`]: fn@zng_wgt_window::splash_ready
+//! [`splash_progress`]: fn@zng_wgt_window::splash_progress
+//! [`splash_message`]: fn@zng_wgt_window::splash_message
+//!
+//! # Frame Presented
+//!
+//! The [`FRAME_PRESENTED_EVENT`] notifies after a window frame is presented on screen, with the presentation timestamp and
+//! an estimate of the display refresh interval. Media widgets can use it to sync audio and video and animations can use it to
+//! step adaptively to the actual frame rate. The event only notifies for windows with [`WindowVars::present_notify`] enabled, the
+//! `on_frame_presented` property of the window widget enables it automatically.
+//!
+//! ```
+//! use zng::prelude::*;
+//!
+//! # fn example() -> window::WindowRoot {
+//! Window! {
+//!     on_frame_presented = hn!(|args: &window::FramePresentedArgs| {
+//!         tracing::info!("frame {:?} presented, refresh interval {:?}", args.frame_id, args.refresh_interval);
+//!     });
+//! }
+//! # }
+//! ```
+//!
 //! # Full API
 //!
 //! See [`zng_ext_window`], [`zng_app::window`] and [`zng_wgt_window`] for the full window API.
@@ -100,12 +178,13 @@
 pub use zng_app::window::{MonitorId, WindowId, WindowMode, WINDOW};
 
 pub use zng_ext_window::{
-    AppRunWindowExt, AutoSize, CloseWindowResult, FocusIndicator, FrameCaptureMode, FrameImageReadyArgs, HeadlessAppWindowExt,
-    HeadlessMonitor, ImeArgs, MonitorInfo, MonitorQuery, MonitorsChangedArgs, ParallelWin, RenderMode, StartPosition, VideoMode,
-    WINDOW_Ext, WidgetInfoBuilderImeArea, WidgetInfoImeArea, WindowButton, WindowChangedArgs, WindowCloseArgs, WindowCloseRequestedArgs,
-    WindowIcon, WindowLoadingHandle, WindowOpenArgs, WindowRoot, WindowRootExtenderArgs, WindowState, WindowStateAllowed, WindowVars,
-    FRAME_IMAGE_READY_EVENT, IME_EVENT, MONITORS, MONITORS_CHANGED_EVENT, WINDOWS, WINDOW_CHANGED_EVENT, WINDOW_CLOSE_EVENT,
-    WINDOW_CLOSE_REQUESTED_EVENT, WINDOW_LOAD_EVENT, WINDOW_OPEN_EVENT,
+    AppMenu, AppMenuItem, AppRunWindowExt, AutoSize, CloseWindowResult, FocusIndicator, FrameCaptureMode, FrameImageReadyArgs,
+    FramePresentedArgs, HeadlessAppWindowExt, HeadlessMonitor, HitTestKind, ImeArgs, MonitorInfo, MonitorQuery, MonitorsChangedArgs,
+    ParallelWin, RenderMode, RendererStats, RendererTrim, StartPosition, SystemMenu, SystemMenuItem, VideoMode, WINDOW_Ext,
+    WidgetInfoBuilderImeArea, WidgetInfoImeArea, WindowButton, WindowChangedArgs, WindowCloseArgs, WindowCloseRequestedArgs, WindowGroup,
+    WindowIcon, WindowLayer, WindowLoadingHandle, WindowOpenArgs, WindowRoot, WindowRootExtenderArgs, WindowState, WindowStateAllowed,
+    WindowVars, FRAME_IMAGE_READY_EVENT, FRAME_PRESENTED_EVENT, IME_EVENT, MONITORS, MONITORS_CHANGED_EVENT, WINDOWS, WINDOW_CHANGED_EVENT,
+    WINDOW_CLOSE_EVENT, WINDOW_CLOSE_REQUESTED_EVENT, WINDOW_LOAD_EVENT, WINDOW_OPEN_EVENT,
 };
 
 /// Window commands.
@@ -116,15 +195,15 @@ pub mod cmd {
     pub use zng_wgt_inspector::INSPECT_CMD;
 }
 
-pub use zng_wgt_window::{BlockWindowLoad, Window};
+pub use zng_wgt_window::{BlockWindowLoad, SplashArgs, Window, SPLASH_FADE_VAR, SPLASH_MESSAGE_VAR, SPLASH_PROGRESS_VAR, SPLASH_READY_VAR};
 
 pub use zng_wgt_window::events::{
-    on_frame_image_ready, on_ime, on_pre_frame_image_ready, on_pre_ime, on_pre_window_changed, on_pre_window_close_requested,
-    on_pre_window_exited_fullscreen, on_pre_window_fullscreen, on_pre_window_load, on_pre_window_maximized, on_pre_window_minimized,
-    on_pre_window_moved, on_pre_window_open, on_pre_window_resized, on_pre_window_restored, on_pre_window_state_changed,
-    on_pre_window_unmaximized, on_pre_window_unminimized, on_window_changed, on_window_close_requested, on_window_exited_fullscreen,
-    on_window_fullscreen, on_window_load, on_window_maximized, on_window_minimized, on_window_moved, on_window_open, on_window_resized,
-    on_window_restored, on_window_state_changed, on_window_unmaximized, on_window_unminimized,
+    on_frame_image_ready, on_frame_presented, on_ime, on_pre_frame_image_ready, on_pre_frame_presented, on_pre_ime, on_pre_window_changed,
+    on_pre_window_close_requested, on_pre_window_exited_fullscreen, on_pre_window_fullscreen, on_pre_window_load, on_pre_window_maximized,
+    on_pre_window_minimized, on_pre_window_moved, on_pre_window_open, on_pre_window_resized, on_pre_window_restored,
+    on_pre_window_state_changed, on_pre_window_unmaximized, on_pre_window_unminimized, on_window_changed, on_window_close_requested,
+    on_window_exited_fullscreen, on_window_fullscreen, on_window_load, on_window_maximized, on_window_minimized, on_window_moved,
+    on_window_open, on_window_resized, on_window_restored, on_window_state_changed, on_window_unmaximized, on_window_unminimized,
 };
 
 /// Native dialog types.
@@ -152,5 +231,8 @@ pub mod native_dialog {
 ///
 /// [`cmd::INSPECT_CMD`]: crate::window::cmd::INSPECT_CMD
 pub mod inspector {
-    pub use zng_wgt_inspector::debug::{show_bounds, show_center_points, show_directional_query, show_hit_test, show_rows, InspectMode};
+    pub use zng_wgt_inspector::debug::{
+        show_bounds, show_center_points, show_directional_query, show_hit_test, show_layout_thrash, show_overdraw, show_repaints,
+        show_rows, InspectMode,
+    };
 }
@@ -5,7 +5,7 @@ use zng::{
     color::filter::drop_shadow,
     focus::{
         alt_focus_scope, directional_nav, focus_click_behavior, focus_scope, focus_shortcut, focusable, is_focused, is_return_focus,
-        tab_index, tab_nav, DirectionalNav, FocusChangedArgs, FocusClickBehavior, FocusRequest, FocusTarget, TabIndex, TabNav,
+        tab_index, tab_nav, DirectionalNav, FocusChangedArgs, FocusClickBehavior, FocusReason, FocusRequest, FocusTarget, TabIndex, TabNav,
         FOCUS_CHANGED_EVENT,
     },
     font::FontName,
@@ -218,6 +218,7 @@ fn delayed_focus() -> impl UiNode {
                     highlight: true,
                     force_window_focus: true,
                     window_indicator: None,
+                    reason: FocusReason::Api,
                 });
             }),
             delayed_btn("Info Indicator", || {
@@ -226,6 +227,7 @@ fn delayed_focus() -> impl UiNode {
                     highlight: true,
                     force_window_focus: false,
                     window_indicator: Some(FocusIndicator::Info),
+                    reason: FocusReason::Api,
                 });
             }),
             delayed_btn("Critical Indicator", || {
@@ -234,6 +236,7 @@ fn delayed_focus() -> impl UiNode {
                     highlight: true,
                     force_window_focus: false,
                     window_indicator: Some(FocusIndicator::Critical),
+                    reason: FocusReason::Api,
                 });
             }),
 
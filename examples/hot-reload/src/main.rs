@@ -29,7 +29,7 @@ fn main() {
 
         Window! {
             title = "Hot Reload Example";
-            always_on_top = true;
+            layer = zng::window::WindowLayer::AlwaysOnTop;
 
             child = example;
 
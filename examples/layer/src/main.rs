@@ -172,6 +172,8 @@ fn anchor_example() -> impl UiNode {
         interactivity: false,
         corner_radius: false,
         viewport_bound: false,
+        flip: false,
+        work_area_bound: false,
     });
 
     let next_offset = hn!(|_| {
@@ -17,7 +17,7 @@ use zng::{
     scroll::ScrollMode,
     var::ArcVar,
     widget::{background_color, corner_radius, enabled, visibility, LineStyle},
-    window::{native_dialog, FocusIndicator, FrameCaptureMode, FrameImageReadyArgs, WindowChangedArgs, WindowState},
+    window::{native_dialog, FocusIndicator, FrameCaptureMode, FrameImageReadyArgs, WindowChangedArgs, WindowLayer, WindowState},
 };
 
 fn main() {
@@ -525,7 +525,17 @@ fn misc() -> impl UiNode {
             },
             Toggle! {
                 child = Text!("Always on Top");
-                checked = window_vars.always_on_top();
+                checked = window_vars.layer().map_bidi(
+                    |l| *l == WindowLayer::AlwaysOnTop,
+                    |&t| if t { WindowLayer::AlwaysOnTop } else { WindowLayer::Normal },
+                );
+            },
+            Toggle! {
+                child = Text!("Always on Bottom");
+                checked = window_vars.layer().map_bidi(
+                    |l| *l == WindowLayer::AlwaysOnBottom,
+                    |&b| if b { WindowLayer::AlwaysOnBottom } else { WindowLayer::Normal },
+                );
             },
             separator(),
             cmd_btn(zng::window::cmd::INSPECT_CMD.scoped(window_id)),
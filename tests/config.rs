@@ -33,6 +33,126 @@ fn yaml() {
     test_config("test.config.yml", |p| YamlConfig::sync(p));
 }
 
+#[test]
+fn toml_preserve_format() {
+    let file = PathBuf::from("../target/tmp/test.preserve_format.toml");
+    rmv_file_assert(&file);
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, "# header comment\n\n# b comment\nb = 1_000 # b inline\na = \"a\"\n").unwrap();
+
+    let mut app = APP.defaults().run_headless(false);
+    zng::app::test_log();
+
+    CONFIG.load(TomlConfig::sync(&file));
+    app.run_task(async {
+        task::with_deadline(CONFIG.wait_idle(), 60.secs()).await.unwrap();
+    });
+
+    assert_eq!(1000, CONFIG.get("b", 0).get());
+    CONFIG.get("a", Txt::from_static("")).set("edited").unwrap();
+    CONFIG.get("c", false).set(true).unwrap();
+    app.update(false).assert_wait();
+    app.run_task(async {
+        task::with_deadline(CONFIG.wait_idle(), 60.secs()).await.unwrap();
+    });
+    app.exit();
+
+    let raw_config = std::fs::read_to_string(&file).unwrap();
+    pretty_assertions::assert_eq!(
+        "# header comment\n\n# b comment\nb = 1_000 # b inline\na = \"edited\"\nc = true\n",
+        raw_config
+    );
+}
+
+#[test]
+fn toml_preserve_order_and_comments() {
+    let file = PathBuf::from("../target/tmp/test.preserve_order.toml");
+    rmv_file_assert(&file);
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(
+        &file,
+        "\
+# header comment
+
+z = 1 # z comment
+removed = 2
+a = 3
+
+# table comment
+[table]
+# y comment
+y = \"y\"
+x = 1979-05-27T07:32:00Z # x comment
+w = \"w\"
+
+[inline]
+k = { b = \"b\", a = 1979-05-27 } # k comment
+",
+    )
+    .unwrap();
+
+    let mut app = APP.defaults().run_headless(false);
+    zng::app::test_log();
+
+    CONFIG.load(TomlConfig::sync(&file));
+    app.run_task(async {
+        task::with_deadline(CONFIG.wait_idle(), 60.secs()).await.unwrap();
+    });
+
+    type Table = std::collections::BTreeMap<Txt, Txt>;
+
+    // datetimes are read as strings.
+    let table = CONFIG.get("table", Table::new());
+    assert_eq!("1979-05-27T07:32:00Z", table.get()["x"]);
+
+    CONFIG.get("z", 0).set(10).unwrap();
+    CONFIG.get("new", 0).set(4).unwrap();
+    table
+        .modify(|t| {
+            let t = t.to_mut();
+            t.insert(Txt::from_static("y"), Txt::from_static("edited"));
+            t.insert(Txt::from_static("x"), Txt::from_static("2000-01-01T00:00:00Z"));
+            t.remove("w");
+        })
+        .unwrap();
+    CONFIG
+        .get("inline", std::collections::BTreeMap::<Txt, Table>::new())
+        .modify(|t| {
+            let k = t.to_mut().get_mut("k").unwrap();
+            k.insert(Txt::from_static("b"), Txt::from_static("edited"));
+            k.insert(Txt::from_static("c"), Txt::from_static("c"));
+        })
+        .unwrap();
+    app.update(false).assert_wait();
+    assert!(CONFIG.remove(&ConfigKey::from_static("removed")));
+    app.update(false).assert_wait();
+    app.run_task(async {
+        task::with_deadline(CONFIG.wait_idle(), 60.secs()).await.unwrap();
+    });
+    app.exit();
+
+    let raw_config = std::fs::read_to_string(&file).unwrap();
+    pretty_assertions::assert_eq!(
+        "\
+# header comment
+
+z = 10 # z comment
+a = 3
+new = 4
+
+# table comment
+[table]
+# y comment
+y = \"edited\"
+x = 2000-01-01T00:00:00Z # x comment
+
+[inline]
+k = { b = \"edited\", a = 1979-05-27, c = \"c\" } # k comment
+",
+        raw_config
+    );
+}
+
 fn test_config<C: AnyConfig>(file: &str, source: impl Fn(&Path) -> C) {
     let file = PathBuf::from("../target/tmp").join(file);
 
@@ -492,6 +612,59 @@ fn fallback_reset_entry() {
     app.exit();
 }
 
+#[test]
+fn layered() {
+    let mut app = APP.defaults().run_headless(false);
+    zng::app::test_log();
+
+    let mut system = MemoryConfig::default();
+    system.get("managed", Txt::from_static("default/system")).set("system").unwrap();
+    app.update(false).assert_wait();
+
+    let cfg = LayeredConfig::new()
+        .with_layer(ConfigLayer::Default, MemoryConfig::default())
+        .with_layer(ConfigLayer::System, system)
+        .with_layer(ConfigLayer::User, MemoryConfig::default())
+        .with_layer(ConfigLayer::Session, MemoryConfig::default());
+    cfg.set_key_write_layer("session", Some(ConfigLayer::Session));
+    CONFIG.load(cfg.clone());
+
+    let managed = CONFIG.get("managed", Txt::from_static("default"));
+    let user = CONFIG.get("user", Txt::from_static("default"));
+    let session = CONFIG.get("session", Txt::from_static("default"));
+    let managed_origin = cfg.origin("managed");
+    let user_origin = cfg.origin("user");
+    let session_origin = cfg.origin("session");
+    app.update(false).assert_wait();
+
+    assert_eq!("system", managed.get());
+    assert_eq!(Some(ConfigLayer::System), managed_origin.get());
+    assert_eq!("default", user.get());
+    assert_eq!(None, user_origin.get());
+
+    user.set("user").unwrap();
+    session.set("session").unwrap();
+    app.update(false).assert_wait();
+    assert_eq!("user", user.get());
+    assert_eq!(Some(ConfigLayer::User), user_origin.get());
+    assert_eq!("session", session.get());
+    assert_eq!(Some(ConfigLayer::Session), session_origin.get());
+
+    // written to the default layer, but the system layer has precedence.
+    cfg.set_key_write_layer("managed", Some(ConfigLayer::Default));
+    managed.set("written").unwrap();
+    app.update(false).assert_wait();
+    assert_eq!("system", managed.get());
+    assert_eq!(Some(ConfigLayer::System), managed_origin.get());
+
+    assert!(cfg.reset_to_default(&ConfigKey::from_static("user")));
+    app.update(false).assert_wait();
+    assert_eq!("default", user.get());
+    assert_eq!(None, user_origin.get());
+
+    app.exit();
+}
+
 fn rmv_file_assert(path: &Path) {
     if let Err(e) = std::fs::remove_file(path) {
         if !matches!(e.kind(), std::io::ErrorKind::NotFound) {
@@ -0,0 +1,72 @@
+use zng::{
+    app::{raw_device_events::DeviceId, HeadlessApp},
+    drag_drop::{draggable, DragDropData, DragDropEffect, DragEndArgs, DRAG_DROP, DRAG_END_EVENT},
+    layout::{Dip, DipPoint},
+    mouse::{ButtonState, MouseButton},
+    prelude::*,
+};
+use zng_app::view_process::raw_events::{RawMouseInputArgs, RawMouseMovedArgs, RAW_MOUSE_INPUT_EVENT, RAW_MOUSE_MOVED_EVENT};
+
+#[test]
+pub fn draggable_continues_native_outside_window() {
+    let mut app = APP.defaults().run_headless(false);
+    let drag_end = DRAG_END_EVENT.receiver();
+
+    let window_id = app.open_window(async {
+        Window! {
+            size = (200, 200);
+            draggable = vec![DragDropData::Text("drag".into())];
+        }
+    });
+    let _ = app.update(false);
+
+    move_mouse(&mut app, window_id, DipPoint::new(Dip::new(50), Dip::new(50)));
+    mouse_input(&mut app, window_id, ButtonState::Pressed);
+    assert!(DRAG_DROP.dragging().get().is_none());
+
+    // exceeds the drag threshold, starts the in-app operation.
+    move_mouse(&mut app, window_id, DipPoint::new(Dip::new(80), Dip::new(80)));
+    let info = DRAG_DROP.dragging().get().expect("expected in-app drag");
+    assert_eq!(window_id, info.window_id);
+    assert_eq!(vec![DragDropData::Text("drag".into())], info.data);
+
+    // leaves the window, the in-app operation ends and continues as native.
+    move_mouse(&mut app, window_id, DipPoint::new(Dip::new(-20), Dip::new(80)));
+    assert!(DRAG_DROP.dragging().get().is_none());
+
+    // headless without view-process, the native operation cannot start.
+    let args = wait_drag_end(&mut app, &drag_end);
+    assert_eq!(DragDropEffect::empty(), args.applied);
+    assert_eq!(window_id, args.source.window_id());
+
+    // in-app operation does not resume when the cursor returns.
+    move_mouse(&mut app, window_id, DipPoint::new(Dip::new(50), Dip::new(50)));
+    assert!(DRAG_DROP.dragging().get().is_none());
+    assert!(drag_end.try_recv().is_err());
+}
+
+fn move_mouse(app: &mut HeadlessApp, window_id: WindowId, position: DipPoint) {
+    RAW_MOUSE_MOVED_EVENT.notify(RawMouseMovedArgs::now(window_id, DeviceId::virtual_mouse(), vec![], position));
+    let _ = app.update(false);
+}
+
+fn mouse_input(app: &mut HeadlessApp, window_id: WindowId, state: ButtonState) {
+    RAW_MOUSE_INPUT_EVENT.notify(RawMouseInputArgs::now(
+        window_id,
+        DeviceId::virtual_mouse(),
+        state,
+        MouseButton::Left,
+    ));
+    let _ = app.update(false);
+}
+
+fn wait_drag_end(app: &mut HeadlessApp, drag_end: &zng::event::EventReceiver<DragEndArgs>) -> DragEndArgs {
+    for _ in 0..100 {
+        if let Ok(args) = drag_end.try_recv() {
+            return args;
+        }
+        let _ = app.update(false);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    panic!("expected drag end");
+}
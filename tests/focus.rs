@@ -1,14 +1,15 @@
 use keyboard::KeyLocation;
 use zng::{
-    app::{AppExtended, AppExtension, HeadlessApp},
+    app::{raw_device_events::DeviceId, AppExtended, AppExtension, HeadlessApp},
     data_view::{DataView, DataViewArgs},
     event::EventReceiver,
     focus::{
         alt_focus_scope,
         cmd::{FOCUS_NEXT_CMD, FOCUS_PREV_CMD},
-        directional_nav, focus_scope, focusable, tab_index, tab_nav, DirectionalNav, FocusChangedArgs, FocusChangedCause,
+        directional_nav, focus_scope, focusable, tab_index, tab_nav, DirectionalNav, FocusChangedArgs, FocusChangedCause, FocusReason,
         ReturnFocusChangedArgs, TabIndex, TabNav, FOCUS_CHANGED_EVENT, RETURN_FOCUS_CHANGED_EVENT,
     },
+    gamepad::{ButtonState, GamepadButton, GAMEPAD},
     keyboard::{Key, KeyCode, KeyState},
     prelude::*,
     widget::{info::InteractionPath, interactive, node::ArcNode, visibility, Visibility, WidgetUpdateMode},
@@ -1655,6 +1656,127 @@ pub fn directional_continue_right() {
     assert_eq!(Some(ids[2]), app.focused());
 }
 
+#[test]
+pub fn directional_spatial() {
+    let app = TestApp::start();
+
+    let mut row0 = ui_vec![Button! { child = Text!("Button 0") }, Button! { child = Text!("Button 1") },];
+    let mut row1 = ui_vec![Button! { child = Text!("Button 2") }, Button! { child = Text!("Button 3") },];
+    let ids = [row0.item_id(0), row0.item_id(1), row1.item_id(0), row1.item_id(1)];
+
+    let mut app = app.run_window(Window! {
+        directional_nav = DirectionalNav::Spatial;
+        child = Stack! {
+            direction = StackDirection::top_to_bottom();
+            children = ui_vec![
+                Stack! {
+                    direction = StackDirection::left_to_right();
+                    focus_scope = true;
+                    children = row0;
+                },
+                Stack! {
+                    direction = StackDirection::left_to_right();
+                    focus_scope = true;
+                    children = row1;
+                },
+            ];
+        };
+    });
+
+    app.focus(ids[1]);
+    assert_eq!(Some(ids[1]), app.focused());
+
+    app.press_down();
+    assert_eq!(Some(ids[3]), app.focused());
+
+    app.press_down();
+    assert_eq!(Some(ids[3]), app.focused());
+
+    app.press_left();
+    assert_eq!(Some(ids[2]), app.focused());
+
+    app.press_up();
+    assert_eq!(Some(ids[0]), app.focused());
+
+    app.press_right();
+    assert_eq!(Some(ids[1]), app.focused());
+}
+
+#[test]
+pub fn gamepad_nav() {
+    let app = TestApp::start();
+
+    let clicks = var(0);
+    let mut buttons = ui_vec![
+        Button! { child = Text!("Button 0") },
+        Button! {
+            child = Text!("Button 1");
+            on_click = hn!(clicks, |_| clicks.set(clicks.get() + 1));
+        },
+    ];
+    let ids: Vec<_> = (0..2).map(|i| buttons.item_id(i)).collect();
+
+    let mut app = app.run(Stack!(left_to_right, buttons));
+    assert_eq!(Some(ids[0]), app.focused());
+
+    app.press_gamepad(GamepadButton::DPadRight);
+    assert_eq!(Some(ids[1]), app.focused());
+
+    app.press_gamepad(GamepadButton::South);
+    assert_eq!(1, clicks.get());
+
+    app.set_vars(|| GAMEPAD.nav_enabled().set(false));
+    app.press_gamepad(GamepadButton::DPadLeft);
+    assert_eq!(Some(ids[1]), app.focused());
+}
+
+#[test]
+pub fn focus_changed_reason() {
+    let app = TestApp::start();
+
+    let buttons = ui_vec![Button! { child = Text!("Button 0") }, Button! { child = Text!("Button 1") },];
+    let mut app = app.run(Stack!(top_to_bottom, buttons));
+    let _ = app.take_focus_changed();
+
+    app.press_tab();
+    let events = app.take_focus_changed();
+    assert_eq!(Some(FocusReason::Navigation), events[0].cause.request_reason());
+
+    let id = app.focused().unwrap();
+    app.press_tab();
+    app.focus(id);
+    let events = app.take_focus_changed();
+    assert_eq!(Some(FocusReason::Api), events.last().unwrap().cause.request_reason());
+}
+
+#[test]
+pub fn focus_history_back() {
+    let app = TestApp::start();
+
+    let mut buttons = ui_vec![
+        Button! { child = Text!("Button 0") },
+        Button! { child = Text!("Button 1") },
+        Button! { child = Text!("Button 2") },
+    ];
+    let ids: Vec<_> = (0..3).map(|i| buttons.item_id(i)).collect();
+    let mut app = app.run(Stack!(top_to_bottom, buttons));
+    FOCUS.clear_history();
+
+    assert_eq!(Some(ids[0]), app.focused());
+    app.press_tab();
+    app.press_tab();
+    assert_eq!(Some(ids[2]), app.focused());
+    assert_eq!(vec![ids[0], ids[1]], FOCUS.history().get());
+
+    assert!(app.back());
+    assert_eq!(Some(ids[1]), app.focused());
+    assert_eq!(vec![ids[0]], FOCUS.history().get());
+
+    assert!(app.back());
+    assert_eq!(Some(ids[0]), app.focused());
+    assert!(!app.back());
+}
+
 struct TestAppBuilder<E: AppExtension> {
     app: AppExtended<E>,
 }
@@ -1784,11 +1906,26 @@ impl TestApp {
         let _ = self.app.update(false);
     }
 
+    pub fn press_gamepad(&mut self, button: GamepadButton) {
+        use zng_app::view_process::raw_events::{RawGamepadButtonArgs, RAW_GAMEPAD_BUTTON_EVENT};
+        let device_id = DeviceId::virtual_gamepad();
+        for (value, state) in [(1.0, ButtonState::Pressed), (0.0, ButtonState::Released)] {
+            RAW_GAMEPAD_BUTTON_EVENT.notify(RawGamepadButtonArgs::now(device_id, button, value, state));
+            let _ = self.app.update(false);
+        }
+    }
+
     pub fn focus(&mut self, widget_id: WidgetId) {
         FOCUS.focus_widget(widget_id, true);
         let _ = self.app.update(false);
     }
 
+    pub fn back(&mut self) -> bool {
+        let r = FOCUS.back();
+        let _ = self.app.update(false);
+        r
+    }
+
     pub fn focus_or_parent(&mut self, widget_id: WidgetId) {
         FOCUS.focus_widget_or_exit(widget_id, false, true);
         let _ = self.app.update(false);
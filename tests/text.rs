@@ -1,9 +1,13 @@
 use std::fmt::Write as _;
 
 use zng::{
-    font::SegmentedText,
+    app::HeadlessApp,
+    focus::FOCUS,
+    font::{CaretIndex, SegmentedText},
     layout::TextSegmentKind,
-    prelude_wgt::{LayoutDirection, Txt},
+    prelude::*,
+    prelude_wgt::LayoutDirection,
+    window::{HeadlessAppWindowExt, ImeArgs, IME_EVENT},
 };
 
 #[test]
@@ -47,3 +51,54 @@ fn emoji_segs() {
         panic!("\n\n{errors}");
     }
 }
+
+#[test]
+fn ime_multi_byte() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let txt = var(Txt::from_static("aé"));
+    let caret = var(None::<CaretIndex>);
+    let id = WidgetId::new_unique();
+    let window_id = app.open_window(async_clmv!(txt, caret, {
+        Window! {
+            child = TextInput! {
+                id;
+                txt;
+                get_caret_index = caret;
+            };
+        }
+    }));
+    app.focus_window(window_id);
+    FOCUS.focus_widget(id, true);
+    app.update(false).assert_wait();
+
+    let ime = |app: &mut HeadlessApp, preview: &'static str, caret: Option<(usize, usize)>| {
+        let target = WINDOWS.widget_tree(window_id).unwrap().get(id).unwrap().path();
+        IME_EVENT.notify(ImeArgs::now(target, preview, caret));
+        app.update(false).assert_wait();
+    };
+    let caret_index = || caret.get().map(|c| c.index);
+
+    // caret at the end, after the 2 bytes 'é'.
+    zng::text::cmd::SELECT_CMD
+        .scoped(id)
+        .notify_param(zng::text::cmd::TextSelectOp::text_end());
+    app.update(false).assert_wait();
+    assert_eq!(Some(3), caret_index());
+
+    // preview caret is a byte offset in the preview text, relative to the insert caret.
+    ime(&mut app, "日本語", Some((3, 6)));
+    assert_eq!(Some(3 + 6), caret_index());
+    assert_eq!("aé", txt.get());
+
+    // offsets inside a char snap to the char start.
+    ime(&mut app, "日本語", Some((4, 8)));
+    assert_eq!(Some(3 + 6), caret_index());
+
+    // commit inserts at the original caret, caret moves after the insert.
+    ime(&mut app, "日本語", None);
+    assert_eq!("aé日本語", txt.get());
+    assert_eq!(Some(3 + 9), caret_index());
+
+    app.exit();
+}
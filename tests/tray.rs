@@ -0,0 +1,41 @@
+use zng::{
+    image::ImageSource,
+    layout::{Px, PxSize},
+    mouse::MouseButton,
+    prelude::*,
+    tray::{TrayIcon, TrayIconError, TrayIconId, TrayMenuItem, TRAY_ICONS, TRAY_ICON_CLICK_EVENT},
+};
+use zng_app::view_process::{
+    raw_events::{RawTrayIconClickedArgs, RAW_TRAY_ICON_CLICKED_EVENT},
+    VIEW_PROCESS,
+};
+
+#[test]
+pub fn open_without_view_process() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let tray =
+        TrayIcon::new("Test", ImageSource::flood(PxSize::splat(Px(16)), colors::RED, None)).menu(vec![TrayMenuItem::item("exit", "Exit")]);
+    assert_eq!(Err(TrayIconError::ViewProcessOffline), TRAY_ICONS.open(tray));
+
+    assert!(VIEW_PROCESS.close_tray_icon(TrayIconId::first()).is_err());
+
+    app.update(false).assert_wait();
+}
+
+#[test]
+pub fn click_unknown_icon() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let clicks = var(0);
+    TRAY_ICON_CLICK_EVENT
+        .on_event(app_hn!(clicks, |_, _| {
+            clicks.modify(|c| *c.to_mut() += 1);
+        }))
+        .perm();
+
+    RAW_TRAY_ICON_CLICKED_EVENT.notify(RawTrayIconClickedArgs::now(TrayIconId::first(), MouseButton::Left, false));
+    app.update(false).assert_wait();
+
+    assert_eq!(0, clicks.get());
+}
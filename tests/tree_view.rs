@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use zng::{
+    app::HeadlessApp,
+    focus::FOCUS,
+    keyboard::{Key, KeyCode, KeyLocation},
+    prelude::*,
+    tree_view::{TreeData, TreeItem, TreeItemArgs, TreeNodeId, TreeNodeStatus, TreeSource, TreeSourceFuture},
+    window::HeadlessAppWindowExt,
+};
+
+/// Roots `1..=3`, children of `n` are `n*10+1..=n*10+3`, items `>= 100` are leaves, children of `3` fail to load.
+struct Numbers;
+impl TreeSource for Numbers {
+    type Item = u32;
+
+    fn load_children(&self, parent: Option<u32>) -> TreeSourceFuture<u32> {
+        Box::pin(async move {
+            match parent {
+                Some(3) => Err(Txt::from_static("error")),
+                p => {
+                    let start = p.map(|p| p * 10).unwrap_or(0);
+                    Ok((start + 1..=start + 3).map(|n| TreeItem::new(n, n < 100)).collect())
+                }
+            }
+        })
+    }
+}
+
+fn wait(app: &mut HeadlessApp, cond: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !cond() {
+        assert!(Instant::now() < deadline, "timeout");
+        app.update(false).assert_wait();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn rows(data: &TreeData<u32>) -> Vec<u32> {
+    data.rows().get().into_iter().map(|id| data.item(id).unwrap().data).collect()
+}
+
+fn find(data: &TreeData<u32>, n: u32) -> TreeNodeId {
+    data.rows().get().into_iter().find(|id| data.item(*id).unwrap().data == n).unwrap()
+}
+
+#[test]
+pub fn expand_collapse() {
+    let mut app = APP.minimal().run_headless(false);
+
+    let data = TreeData::new(Numbers);
+    data.reload(None);
+    wait(&mut app, || data.status().get().is_loaded());
+    assert_eq!(vec![1, 2, 3], rows(&data));
+
+    // first expand loads the children.
+    let one = find(&data, 1);
+    assert_eq!(TreeNodeStatus::NotLoaded, data.item(one).unwrap().status.get());
+    data.expand(one);
+    wait(&mut app, || data.item(one).unwrap().status.get().is_loaded());
+    assert_eq!(vec![1, 11, 12, 13, 2, 3], rows(&data));
+
+    let twelve = find(&data, 12);
+    let item = data.item(twelve).unwrap();
+    assert_eq!(1, item.level);
+    assert_eq!(Some(one), data.parent(twelve));
+    assert_eq!(3, data.children(Some(one)).len());
+
+    // nested expand.
+    data.expand(twelve);
+    wait(&mut app, || data.item(twelve).unwrap().status.get().is_loaded());
+    assert_eq!(vec![1, 11, 12, 121, 122, 123, 13, 2, 3], rows(&data));
+    assert_eq!(2, data.item(find(&data, 122)).unwrap().level);
+
+    // collapse hides descendants, but retains them.
+    data.collapse(one);
+    app.update(false).assert_wait();
+    assert_eq!(vec![1, 2, 3], rows(&data));
+    assert_eq!(3, data.children(Some(one)).len());
+
+    // expand again does not reload, the expanded state of descendants is retained.
+    data.expand(one);
+    app.update(false).assert_wait();
+    assert!(data.item(one).unwrap().status.get().is_loaded());
+    assert_eq!(vec![1, 11, 12, 121, 122, 123, 13, 2, 3], rows(&data));
+
+    // leaves cannot expand.
+    let leaf = find(&data, 121);
+    assert!(!data.item(leaf).unwrap().has_children);
+    data.expand(leaf);
+    app.update(false).assert_wait();
+    assert!(!data.item(leaf).unwrap().expanded.get());
+
+    // load error.
+    let three = find(&data, 3);
+    data.expand(three);
+    wait(&mut app, || data.item(three).unwrap().status.get().error().is_some());
+    assert!(data.children(Some(three)).is_empty());
+    assert_eq!(vec![1, 11, 12, 121, 122, 123, 13, 2, 3], rows(&data));
+
+    app.exit();
+}
+
+#[test]
+pub fn keyboard_nav() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let data = TreeData::new(Numbers);
+    let window_id = app.open_window(async_clmv!(data, {
+        Window! {
+            child = TreeView!(::<u32>, data, wgt_fn!(|a: TreeItemArgs<u32>| Text!("{}", a.data)));
+        }
+    }));
+    app.focus_window(window_id);
+    wait(&mut app, || data.status().get().is_loaded());
+    app.update(false).assert_wait();
+
+    let widget = |n: u32| data.item(find(&data, n)).unwrap().widget_id;
+    let focused = || FOCUS.focused().get().map(|p| p.widget_id());
+    let press = |app: &mut HeadlessApp, code: KeyCode, location: KeyLocation, key: Key| {
+        app.press_key(window_id, code, location, key);
+        let _ = app.update(false);
+    };
+    let arrow = |app: &mut HeadlessApp, key: Key| {
+        let code = match key {
+            Key::ArrowUp => KeyCode::ArrowUp,
+            Key::ArrowDown => KeyCode::ArrowDown,
+            Key::ArrowLeft => KeyCode::ArrowLeft,
+            Key::ArrowRight => KeyCode::ArrowRight,
+            Key::Home => KeyCode::Home,
+            Key::End => KeyCode::End,
+            _ => unreachable!(),
+        };
+        press(app, code, KeyLocation::Standard, key);
+    };
+
+    FOCUS.focus_widget(widget(1), true);
+    app.update(false).assert_wait();
+    assert_eq!(Some(widget(1)), focused());
+
+    // up and down.
+    arrow(&mut app, Key::ArrowDown);
+    assert_eq!(Some(widget(2)), focused());
+    arrow(&mut app, Key::ArrowUp);
+    assert_eq!(Some(widget(1)), focused());
+    arrow(&mut app, Key::ArrowUp);
+    assert_eq!(Some(widget(1)), focused());
+
+    // home and end.
+    arrow(&mut app, Key::End);
+    assert_eq!(Some(widget(3)), focused());
+    arrow(&mut app, Key::Home);
+    assert_eq!(Some(widget(1)), focused());
+
+    // right expands, then moves to the first child.
+    let one = find(&data, 1);
+    arrow(&mut app, Key::ArrowRight);
+    assert!(data.item(one).unwrap().expanded.get());
+    wait(&mut app, || data.item(one).unwrap().status.get().is_loaded());
+    app.update(false).assert_wait();
+    assert_eq!(Some(widget(1)), focused());
+    arrow(&mut app, Key::ArrowRight);
+    assert_eq!(Some(widget(11)), focused());
+
+    // down visits the children.
+    arrow(&mut app, Key::ArrowDown);
+    assert_eq!(Some(widget(12)), focused());
+
+    // left moves to the parent, then collapses.
+    arrow(&mut app, Key::ArrowLeft);
+    assert_eq!(Some(widget(1)), focused());
+    arrow(&mut app, Key::ArrowLeft);
+    assert!(!data.item(one).unwrap().expanded.get());
+    assert_eq!(vec![1, 2, 3], rows(&data));
+    assert_eq!(Some(widget(1)), focused());
+
+    // * expands all siblings.
+    press(&mut app, KeyCode::NumpadMultiply, KeyLocation::Numpad, Key::Char('*'));
+    wait(&mut app, || {
+        [1, 2].iter().all(|n| data.item(find(&data, *n)).unwrap().status.get().is_loaded())
+            && data.item(find(&data, 3)).unwrap().status.get().error().is_some()
+    });
+    for n in [1, 2, 3] {
+        assert!(data.item(find(&data, n)).unwrap().expanded.get());
+    }
+    assert_eq!(vec![1, 11, 12, 13, 2, 21, 22, 23, 3], rows(&data));
+
+    app.exit();
+}
@@ -0,0 +1,74 @@
+use zng::{
+    layout::{Px, PxRect, PxSize},
+    prelude::*,
+    window::{HeadlessAppWindowExt, WindowVars},
+};
+
+#[test]
+pub fn group_fallback() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let a = app.open_window(async { Window!(child = Text!("A")) });
+    let b = app.open_window(async { Window!(child = Text!("B")) });
+    let c = app.open_window(async { Window!(child = Text!("C")) });
+    let vars = |id: WindowId| -> WindowVars { WINDOWS.vars(id).unwrap() };
+
+    WINDOWS.group([a, b, c]).unwrap();
+    app.update(false).assert_wait();
+
+    // headless windows use the fallback group, only the selected window is visible.
+    let group = vars(a).group().get().unwrap();
+    assert_eq!(vec![a, b, c], group.windows);
+    assert_eq!(a, group.selected);
+    assert!(!group.is_native);
+    assert_eq!(Some(group), vars(c).group().get());
+    assert!(vars(a).visible().get());
+    assert!(!vars(b).visible().get());
+    assert!(!vars(c).visible().get());
+
+    // focus selects the tab.
+    WINDOWS.focus(b).unwrap();
+    app.update(false).assert_wait();
+    assert_eq!(b, vars(a).group().get().unwrap().selected);
+    assert!(!vars(a).visible().get());
+    assert!(vars(b).visible().get());
+    assert!(!vars(c).visible().get());
+
+    // removing the selected window selects the first tab.
+    WINDOWS.ungroup(b).unwrap();
+    app.update(false).assert_wait();
+    assert_eq!(None, vars(b).group().get());
+    assert!(vars(b).visible().get());
+    let group = vars(a).group().get().unwrap();
+    assert_eq!(vec![a, c], group.windows);
+    assert_eq!(a, group.selected);
+    assert!(vars(a).visible().get());
+    assert!(!vars(c).visible().get());
+
+    // closed windows are removed from the group, a group of one window is removed.
+    assert!(app.close_window(c));
+    assert_eq!(None, vars(a).group().get());
+    assert!(vars(a).visible().get());
+
+    app.exit();
+}
+
+#[test]
+pub fn capture_frame_error() {
+    let mut app = APP.defaults().run_headless(false);
+
+    let id = app.open_window(async { Window!(child = Text!("A")) });
+
+    // headless without renderer.
+    let rsp = WINDOWS.capture_frame(id, None);
+    let img = app.run_task(async move { rsp.wait_rsp().await }).unwrap();
+    assert!(img.is_error());
+    assert!(img.error().unwrap().contains("without renderer"));
+
+    // not found.
+    let rsp = WINDOWS.capture_frame(WindowId::new_unique(), Some(PxRect::from_size(PxSize::splat(Px(10)))));
+    let img = app.run_task(async move { rsp.wait_rsp().await }).unwrap();
+    assert!(img.is_error());
+
+    app.exit();
+}